@@ -70,8 +70,7 @@ fn bench_content_filter_creation(c: &mut Criterion) {
 
     c.bench_function("api/SCContentFilter::create", |b| {
         b.iter(|| {
-            let filter = SCContentFilter::create()
-                .with_display(&display)
+            let filter = SCContentFilter::for_display(&display)
                 .with_excluding_windows(&[])
                 .build();
             black_box(filter)
@@ -96,8 +95,7 @@ fn bench_stream_creation(c: &mut Criterion) {
 
     let content = SCShareableContent::get().expect("Failed to get shareable content");
     let display = content.displays().into_iter().next().expect("No display");
-    let filter = SCContentFilter::create()
-        .with_display(&display)
+    let filter = SCContentFilter::for_display(&display)
         .with_excluding_windows(&[])
         .build();
     let config = SCStreamConfiguration::new()
@@ -166,8 +164,7 @@ fn bench_frame_throughput(c: &mut Criterion) {
             BenchmarkId::new("frames", label),
             &(width, height),
             |b, &(w, h)| {
-                let filter = SCContentFilter::create()
-                    .with_display(&display)
+                let filter = SCContentFilter::for_display(&display)
                     .with_excluding_windows(&[])
                     .build();
 
@@ -243,8 +240,7 @@ fn bench_stream_startup(c: &mut Criterion) {
     group.sample_size(10);
 
     group.bench_function("create_start_first_frame", |b| {
-        let filter = SCContentFilter::create()
-            .with_display(&display)
+        let filter = SCContentFilter::for_display(&display)
             .with_excluding_windows(&[])
             .build();
 
@@ -311,8 +307,7 @@ fn bench_frame_latency(c: &mut Criterion) {
     group.sample_size(20);
 
     group.bench_function("first_frame", |b| {
-        let filter = SCContentFilter::create()
-            .with_display(&display)
+        let filter = SCContentFilter::for_display(&display)
             .with_excluding_windows(&[])
             .build();
 
@@ -384,8 +379,7 @@ fn bench_pixel_buffer_access(c: &mut Criterion) {
     let content = SCShareableContent::get().expect("Failed to get shareable content");
     let display = content.displays().into_iter().next().expect("No display");
 
-    let filter = SCContentFilter::create()
-        .with_display(&display)
+    let filter = SCContentFilter::for_display(&display)
         .with_excluding_windows(&[])
         .build();
 
@@ -508,8 +502,7 @@ fn bench_screenshot_capture(c: &mut Criterion) {
 
     let content = SCShareableContent::get().expect("Failed to get shareable content");
     let display = content.displays().into_iter().next().expect("No display");
-    let filter = SCContentFilter::create()
-        .with_display(&display)
+    let filter = SCContentFilter::for_display(&display)
         .with_excluding_windows(&[])
         .build();
 
@@ -569,8 +562,7 @@ fn bench_stream_lifecycle(c: &mut Criterion) {
     let content = SCShareableContent::get().expect("Failed to get shareable content");
     let display = content.displays().into_iter().next().expect("No display");
 
-    let filter = SCContentFilter::create()
-        .with_display(&display)
+    let filter = SCContentFilter::for_display(&display)
         .with_excluding_windows(&[])
         .build();
 
@@ -632,8 +624,7 @@ fn bench_configuration_updates(c: &mut Criterion) {
     let content = SCShareableContent::get().expect("Failed to get shareable content");
     let display = content.displays().into_iter().next().expect("No display");
 
-    let filter = SCContentFilter::create()
-        .with_display(&display)
+    let filter = SCContentFilter::for_display(&display)
         .with_excluding_windows(&[])
         .build();
 
@@ -124,8 +124,7 @@ fn bench_content_filter_with_excludes(c: &mut Criterion) {
             slice,
             |b, slice| {
                 b.iter(|| {
-                    let f = SCContentFilter::create()
-                        .with_display(display)
+                    let f = SCContentFilter::for_display(display)
                         .with_excluding_windows(slice)
                         .build();
                     black_box(f);
@@ -146,8 +145,7 @@ fn capture_single_frame() -> Option<CMSampleBuffer> {
     let content = SCShareableContent::get().ok()?;
     let display = content.displays().into_iter().next()?;
 
-    let filter = SCContentFilter::create()
-        .with_display(&display)
+    let filter = SCContentFilter::for_display(&display)
         .with_excluding_windows(&[])
         .build();
 
@@ -255,8 +253,7 @@ fn bench_screenshot_rgba(c: &mut Criterion) {
     cg_init();
     let content = SCShareableContent::get().expect("perms?");
     let display = content.displays().into_iter().next().expect("no display");
-    let filter = SCContentFilter::create()
-        .with_display(&display)
+    let filter = SCContentFilter::for_display(&display)
         .with_excluding_windows(&[])
         .build();
 
@@ -436,8 +433,7 @@ fn run_stream(duration: Duration, with_audio: bool) -> Arc<AvStats> {
     cg_init();
     let content = SCShareableContent::get().expect("perms?");
     let display = content.displays().into_iter().next().expect("no display");
-    let filter = SCContentFilter::create()
-        .with_display(&display)
+    let filter = SCContentFilter::for_display(&display)
         .with_excluding_windows(&[])
         .build();
 
@@ -568,8 +564,7 @@ fn capture_single_audio_sample() -> Option<CMSampleBuffer> {
     cg_init();
     let content = SCShareableContent::get().ok()?;
     let display = content.displays().into_iter().next()?;
-    let filter = SCContentFilter::create()
-        .with_display(&display)
+    let filter = SCContentFilter::for_display(&display)
         .with_excluding_windows(&[])
         .build();
     let config = SCStreamConfiguration::new()
@@ -258,6 +258,9 @@ fn link_swift_bridge(swift_build_dir: &str) {
     println!("cargo:rustc-link-lib=framework=CoreGraphics");
     println!("cargo:rustc-link-lib=framework=CoreMedia");
     println!("cargo:rustc-link-lib=framework=IOSurface");
+    println!("cargo:rustc-link-lib=framework=AudioToolbox");
+    println!("cargo:rustc-link-lib=framework=CoreAudio");
+    println!("cargo:rustc-link-lib=framework=VideoToolbox");
 
     // Add rpath for Swift runtime libraries
     println!("cargo:rustc-link-arg=-Wl,-rpath,/usr/lib/swift");
@@ -76,8 +76,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .next()
         .ok_or("No displays found")?;
 
-    let filter = SCContentFilter::create()
-        .with_display(&display)
+    let filter = SCContentFilter::for_display(&display)
         .with_excluding_windows(&[])
         .build();
 
@@ -58,8 +58,7 @@ fn print_display_scale_info(content: &SCShareableContent, display: &DisplaySnaps
         .into_iter()
         .find(|live| live.display_id() == display.display_id)
     {
-        let filter = SCContentFilter::create()
-            .with_display(&live)
+        let filter = SCContentFilter::for_display(&live)
             .with_excluding_windows(&[])
             .build();
 
@@ -159,14 +158,13 @@ fn print_filter_styles(content: &SCShareableContent) {
     let live_displays = content.displays();
     let live_windows = content.windows();
     if let Some(display) = live_displays.first() {
-        let display_filter = SCContentFilter::create()
-            .with_display(display)
+        let display_filter = SCContentFilter::for_display(display)
             .with_excluding_windows(&[])
             .build();
         println!("  Display filter style: {:?}", display_filter.style());
     }
     if let Some(window) = live_windows.first() {
-        let window_filter = SCContentFilter::create().with_window(window).build();
+        let window_filter = SCContentFilter::for_window(window).build();
         println!("  Window filter style: {:?}", window_filter.style());
     }
     println!("\n  Style values:");
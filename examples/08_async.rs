@@ -153,8 +153,7 @@ async fn async_stream_iteration() -> Result<(), Box<dyn std::error::Error>> {
     let displays = content.displays();
 
     if let Some(display) = displays.first() {
-        let filter = SCContentFilter::create()
-            .with_display(display)
+        let filter = SCContentFilter::for_display(display)
             .with_excluding_windows(&[])
             .build();
 
@@ -249,8 +248,7 @@ async fn av_capture() -> Result<(), Box<dyn std::error::Error>> {
         return Ok(());
     };
 
-    let filter = SCContentFilter::create()
-        .with_display(display)
+    let filter = SCContentFilter::for_display(display)
         .with_excluding_windows(&[])
         .build();
 
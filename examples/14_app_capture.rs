@@ -127,8 +127,7 @@ fn demo_single_app_filters(
     app_snap: &ApplicationSnapshot,
 ) -> SCContentFilter {
     println!("\n📦 Option A: Include specific application");
-    let include_filter = SCContentFilter::create()
-        .with_display(display)
+    let include_filter = SCContentFilter::for_display(display)
         .with_including_applications(&[live_app], &[])
         .build();
     println!(
@@ -137,8 +136,7 @@ fn demo_single_app_filters(
     );
 
     println!("\n📦 Option B: Exclude specific application");
-    let _exclude_filter = SCContentFilter::create()
-        .with_display(display)
+    let _exclude_filter = SCContentFilter::for_display(display)
         .with_excluding_applications(&[live_app], &[])
         .build();
     println!("   Filter created: exclude {}", app_snap.application_name);
@@ -172,8 +170,7 @@ fn demo_multi_app_filter(
         .collect();
 
     if !multi_apps.is_empty() {
-        let _multi_filter = SCContentFilter::create()
-            .with_display(display)
+        let _multi_filter = SCContentFilter::for_display(display)
             .with_including_applications(&multi_apps, &[])
             .build();
 
@@ -101,14 +101,15 @@ pub fn start_capture(
     let filter_to_use = if let Some(filter) = current_filter {
         filter.clone()
     } else if mic_only {
-        // For mic-only capture, we still need a valid display filter
+        // For mic-only capture, we still need a valid display filter.
+        // Apps that only want microphone PCM can use
+        // `screencapturekit::stream::mic_capture::MicCapture` instead.
         println!("🎤 Starting mic-only capture (using main display)");
         match screencapturekit::shareable_content::SCShareableContent::get() {
             Ok(content) => {
                 let displays = content.displays();
                 if let Some(display) = displays.first() {
-                    SCContentFilter::create()
-                        .with_display(display)
+                    SCContentFilter::for_display(display)
                         .with_excluding_windows(&[])
                         .build()
                 } else {
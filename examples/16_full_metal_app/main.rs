@@ -1016,6 +1016,11 @@ fn render_frame(
     encoder.draw_primitives(MTLPrimitiveType::Triangle, 0, vertex_count);
     encoder.end_encoding();
 
+    // The capture surface may be recycled once the sample buffer is gone;
+    // keep it in use until the GPU has sampled it.
+    if let Some(textures) = capture_textures {
+        cmd_buffer.hold_textures_until_completed(textures);
+    }
     cmd_buffer.present_drawable(&drawable);
     cmd_buffer.commit();
 }
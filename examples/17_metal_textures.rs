@@ -159,7 +159,8 @@ impl SCStreamOutputTrait for Handler {
         // 3. Create render pass with drawable texture
         // 4. Create encoder and set pipeline, uniforms, textures
         // 5. Draw triangle strip (4 vertices for fullscreen quad)
-        // 6. End encoding, present drawable, commit
+        // 6. End encoding, hold the capture surface, present drawable, commit:
+        //    cmd_buffer.hold_textures_until_completed(&textures)
 
         println!("   ✅ Ready to render (pipeline={:p})", pipeline.as_ptr());
     }
@@ -183,8 +184,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .next()
         .ok_or("No displays found")?;
 
-    let filter = SCContentFilter::create()
-        .with_display(&display)
+    let filter = SCContentFilter::for_display(&display)
         .with_excluding_windows(&[])
         .build();
 
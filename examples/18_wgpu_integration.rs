@@ -410,8 +410,7 @@ impl ApplicationHandler for App<'_> {
         // Start capture
         if let Ok(content) = SCShareableContent::get() {
             if let Some(display) = content.displays().into_iter().next() {
-                let filter = SCContentFilter::create()
-                    .with_display(&display)
+                let filter = SCContentFilter::for_display(&display)
                     .with_excluding_windows(&[])
                     .build();
 
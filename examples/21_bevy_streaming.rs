@@ -133,8 +133,7 @@ fn setup(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
 
     println!("Display: {}x{}", display.width(), display.height());
 
-    let filter = SCContentFilter::create()
-        .with_display(&display)
+    let filter = SCContentFilter::for_display(&display)
         .with_excluding_windows(&[])
         .build();
 
@@ -3,9 +3,8 @@
 //! This example demonstrates integrating screencapturekit-rs with Tauri 2.0
 //! to build a cross-platform (macOS) screen capture application.
 
-use base64::{engine::general_purpose::STANDARD, Engine};
 use screencapturekit::prelude::*;
-use screencapturekit::screenshot_manager::{CGImageExt, SCScreenshotManager};
+use screencapturekit::screenshot_manager::{CGImageExt, ImageFormat, SCScreenshotManager};
 use serde::{Deserialize, Serialize};
 
 /// Display information returned to the frontend
@@ -32,10 +31,10 @@ pub struct WindowInfo {
     pub height: f64,
 }
 
-/// Screenshot result with base64-encoded RGBA data for WebGL rendering
+/// Screenshot result, ready to drop into an `<img src>`
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScreenshotResult {
-    pub data: String, // Base64-encoded RGBA pixels
+    pub data_url: String,
     pub width: usize,
     pub height: usize,
 }
@@ -88,7 +87,7 @@ fn list_windows() -> Result<Vec<WindowInfo>, String> {
     Ok(windows)
 }
 
-/// Take a screenshot of the primary display - returns RGBA data for WebGL
+/// Take a screenshot of the primary display - returns a JPEG data URL
 #[tauri::command]
 fn take_screenshot_display(display_id: Option<u32>) -> Result<ScreenshotResult, String> {
     let content = SCShareableContent::get().map_err(|e| format!("Failed to get content: {}", e))?;
@@ -109,8 +108,7 @@ fn take_screenshot_display(display_id: Option<u32>) -> Result<ScreenshotResult,
             .ok_or("No displays available")?
     };
 
-    let filter = SCContentFilter::create()
-        .with_display(&display)
+    let filter = SCContentFilter::for_display(&display)
         .with_excluding_windows(&[])
         .build();
 
@@ -122,19 +120,19 @@ fn take_screenshot_display(display_id: Option<u32>) -> Result<ScreenshotResult,
     let image = SCScreenshotManager::capture_image(&filter, &config)
         .map_err(|e| format!("Screenshot failed: {}", e))?;
 
-    // Get RGBA data for WebGL rendering
-    let rgba_data = image
-        .rgba_data()
-        .map_err(|e| format!("Failed to get pixel data: {}", e))?;
+    // JPEG keeps full-display payloads small enough for the IPC bridge
+    let data_url = image
+        .to_data_url(ImageFormat::Jpeg(0.85))
+        .map_err(|e| format!("Failed to encode screenshot: {}", e))?;
 
     Ok(ScreenshotResult {
-        data: STANDARD.encode(&rgba_data),
+        data_url,
         width: image.width(),
         height: image.height(),
     })
 }
 
-/// Take a screenshot of a specific window - returns RGBA data for WebGL
+/// Take a screenshot of a specific window - returns a PNG data URL
 #[tauri::command]
 fn take_screenshot_window(window_id: u32) -> Result<ScreenshotResult, String> {
     let content = SCShareableContent::get().map_err(|e| format!("Failed to get content: {}", e))?;
@@ -146,7 +144,7 @@ fn take_screenshot_window(window_id: u32) -> Result<ScreenshotResult, String> {
         .cloned()
         .ok_or_else(|| format!("Window {} not found", window_id))?;
 
-    let filter = SCContentFilter::create().with_window(&window).build();
+    let filter = SCContentFilter::for_window(&window).build();
 
     let frame = window.frame();
     let config = SCStreamConfiguration::new()
@@ -157,13 +155,12 @@ fn take_screenshot_window(window_id: u32) -> Result<ScreenshotResult, String> {
     let image = SCScreenshotManager::capture_image(&filter, &config)
         .map_err(|e| format!("Screenshot failed: {}", e))?;
 
-    // Get RGBA data for WebGL rendering
-    let rgba_data = image
-        .rgba_data()
-        .map_err(|e| format!("Failed to get pixel data: {}", e))?;
+    let data_url = image
+        .to_data_url(ImageFormat::Png)
+        .map_err(|e| format!("Failed to encode screenshot: {}", e))?;
 
     Ok(ScreenshotResult {
-        data: STANDARD.encode(&rgba_data),
+        data_url,
         width: image.width(),
         height: image.height(),
     })
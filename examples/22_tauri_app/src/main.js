@@ -1,4 +1,4 @@
-// Tauri Screen Capture Example with WebGL Rendering
+// Tauri Screen Capture Example
 // Uses screencapturekit-rs via Tauri commands
 
 const { invoke } = window.__TAURI__.core;
@@ -6,9 +6,6 @@ const { invoke } = window.__TAURI__.core;
 // State
 let displays = [];
 let windows = [];
-let gl = null;
-let glProgram = null;
-let glTexture = null;
 
 // DOM Elements
 const statusEl = document.getElementById('status');
@@ -17,31 +14,9 @@ const windowsListEl = document.getElementById('windows-list');
 const previewContainerEl = document.getElementById('preview-container');
 const previewInfoEl = document.getElementById('preview-info');
 
-// WebGL shaders
-const vertexShaderSource = `
-  attribute vec2 a_position;
-  attribute vec2 a_texCoord;
-  varying vec2 v_texCoord;
-  void main() {
-    gl_Position = vec4(a_position, 0.0, 1.0);
-    v_texCoord = a_texCoord;
-  }
-`;
-
-const fragmentShaderSource = `
-  precision mediump float;
-  varying vec2 v_texCoord;
-  uniform sampler2D u_texture;
-  void main() {
-    // Data is RGBA with premultiplied alpha from CGImage rgba_data()
-    gl_FragColor = texture2D(u_texture, v_texCoord);
-  }
-`;
-
 // Initialize
 document.addEventListener('DOMContentLoaded', async () => {
   setupEventListeners();
-  initWebGL();
   await refreshStatus();
   await refreshContent();
 });
@@ -51,99 +26,6 @@ function setupEventListeners() {
   document.getElementById('btn-refresh').addEventListener('click', refreshContent);
 }
 
-// Initialize WebGL
-function initWebGL() {
-  const canvas = document.getElementById('preview-canvas');
-  gl = canvas.getContext('webgl', { preserveDrawingBuffer: true });
-  
-  if (!gl) {
-    console.error('WebGL not supported');
-    return;
-  }
-
-  // Compile shaders
-  const vertexShader = gl.createShader(gl.VERTEX_SHADER);
-  gl.shaderSource(vertexShader, vertexShaderSource);
-  gl.compileShader(vertexShader);
-
-  const fragmentShader = gl.createShader(gl.FRAGMENT_SHADER);
-  gl.shaderSource(fragmentShader, fragmentShaderSource);
-  gl.compileShader(fragmentShader);
-
-  // Link program
-  glProgram = gl.createProgram();
-  gl.attachShader(glProgram, vertexShader);
-  gl.attachShader(glProgram, fragmentShader);
-  gl.linkProgram(glProgram);
-  gl.useProgram(glProgram);
-
-  // Set up geometry (fullscreen quad)
-  const positions = new Float32Array([
-    -1, -1,  0, 1,
-     1, -1,  1, 1,
-    -1,  1,  0, 0,
-     1,  1,  1, 0,
-  ]);
-
-  const buffer = gl.createBuffer();
-  gl.bindBuffer(gl.ARRAY_BUFFER, buffer);
-  gl.bufferData(gl.ARRAY_BUFFER, positions, gl.STATIC_DRAW);
-
-  const positionLoc = gl.getAttribLocation(glProgram, 'a_position');
-  const texCoordLoc = gl.getAttribLocation(glProgram, 'a_texCoord');
-
-  gl.enableVertexAttribArray(positionLoc);
-  gl.vertexAttribPointer(positionLoc, 2, gl.FLOAT, false, 16, 0);
-  gl.enableVertexAttribArray(texCoordLoc);
-  gl.vertexAttribPointer(texCoordLoc, 2, gl.FLOAT, false, 16, 8);
-
-  // Create texture
-  glTexture = gl.createTexture();
-  gl.bindTexture(gl.TEXTURE_2D, glTexture);
-  gl.texParameteri(gl.TEXTURE_2D, gl.TEXTURE_WRAP_S, gl.CLAMP_TO_EDGE);
-  gl.texParameteri(gl.TEXTURE_2D, gl.TEXTURE_WRAP_T, gl.CLAMP_TO_EDGE);
-  gl.texParameteri(gl.TEXTURE_2D, gl.TEXTURE_MIN_FILTER, gl.LINEAR);
-  gl.texParameteri(gl.TEXTURE_2D, gl.TEXTURE_MAG_FILTER, gl.LINEAR);
-}
-
-// Render RGBA data with WebGL
-function renderWithWebGL(rgbaData, width, height) {
-  if (!gl) return;
-
-  const canvas = document.getElementById('preview-canvas');
-  
-  // Calculate aspect ratio preserving dimensions
-  const containerWidth = previewContainerEl.clientWidth - 20;
-  const containerHeight = 400;
-  const aspectRatio = width / height;
-  
-  let canvasWidth, canvasHeight;
-  if (containerWidth / containerHeight > aspectRatio) {
-    canvasHeight = containerHeight;
-    canvasWidth = canvasHeight * aspectRatio;
-  } else {
-    canvasWidth = containerWidth;
-    canvasHeight = canvasWidth / aspectRatio;
-  }
-  
-  canvas.width = width;
-  canvas.height = height;
-  canvas.style.width = `${canvasWidth}px`;
-  canvas.style.height = `${canvasHeight}px`;
-  canvas.style.display = 'block';
-
-  gl.viewport(0, 0, width, height);
-
-  // Upload texture data
-  gl.bindTexture(gl.TEXTURE_2D, glTexture);
-  gl.texImage2D(gl.TEXTURE_2D, 0, gl.RGBA, width, height, 0, gl.RGBA, gl.UNSIGNED_BYTE, rgbaData);
-
-  // Draw
-  gl.clearColor(0, 0, 0, 1);
-  gl.clear(gl.COLOR_BUFFER_BIT);
-  gl.drawArrays(gl.TRIANGLE_STRIP, 0, 4);
-}
-
 // Status
 async function refreshStatus() {
   try {
@@ -263,7 +145,7 @@ window.captureWindowById = async function(windowId) {
   }
 };
 
-// Display screenshot result using WebGL
+// Display screenshot result
 function showScreenshot(result) {
   // Hide placeholder
   const placeholder = previewContainerEl.querySelector('.placeholder');
@@ -271,17 +153,12 @@ function showScreenshot(result) {
     placeholder.style.display = 'none';
   }
 
-  // Decode base64 RGBA data
-  const binaryString = atob(result.data);
-  const rgbaData = new Uint8Array(binaryString.length);
-  for (let i = 0; i < binaryString.length; i++) {
-    rgbaData[i] = binaryString.charCodeAt(i);
-  }
+  // The backend already encoded the image, so the browser decodes it natively
+  const img = document.getElementById('preview-image');
+  img.src = result.data_url;
+  img.style.display = 'block';
 
-  // Render with WebGL
-  renderWithWebGL(rgbaData, result.width, result.height);
-  
-  previewInfoEl.textContent = `${result.width} × ${result.height} pixels (WebGL rendered)`;
+  previewInfoEl.textContent = `${result.width} × ${result.height} pixels`;
 }
 
 // Utility: escape HTML
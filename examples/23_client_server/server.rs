@@ -102,8 +102,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         display.height()
     );
 
-    let filter = SCContentFilter::create()
-        .with_display(&display)
+    let filter = SCContentFilter::for_display(&display)
         .with_excluding_windows(&[])
         .build();
 
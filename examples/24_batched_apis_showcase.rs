@@ -135,8 +135,7 @@ fn showcase_bgra_data() -> Result<(), Box<dyn std::error::Error>> {
     println!("== bgra_data vs rgba_data ==");
     let content = SCShareableContent::get()?;
     let display = content.displays().into_iter().next().ok_or("no display")?;
-    let filter = SCContentFilter::create()
-        .with_display(&display)
+    let filter = SCContentFilter::for_display(&display)
         .with_excluding_windows(&[])
         .build();
 
@@ -188,8 +187,7 @@ fn bench<F: FnMut()>(iters: u32, mut f: F) -> Duration {
 fn capture_one_video_frame() -> Option<CMSampleBuffer> {
     let content = SCShareableContent::get().ok()?;
     let display = content.displays().into_iter().next()?;
-    let filter = SCContentFilter::create()
-        .with_display(&display)
+    let filter = SCContentFilter::for_display(&display)
         .with_excluding_windows(&[])
         .build();
     let config = SCStreamConfiguration::new()
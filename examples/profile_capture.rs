@@ -58,8 +58,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let content = SCShareableContent::get()?;
     let display = content.displays().into_iter().next().ok_or("no display")?;
-    let filter = SCContentFilter::create()
-        .with_display(&display)
+    let filter = SCContentFilter::for_display(&display)
         .with_excluding_windows(&[])
         .build();
     let config = SCStreamConfiguration::new()
@@ -12,6 +12,7 @@
 //! | [`AsyncSCScreenshotManager`] | Async screenshot capture (macOS 14.0+) |
 //! | [`AsyncSCContentSharingPicker`] | Async content picker UI (macOS 14.0+) |
 //! | [`AsyncSCRecordingOutput`] | Async recording with events (macOS 15.0+) |
+//! | [`CancellationToken`] | Abandon any of the above when the user navigates away |
 //!
 //! ## Runtime Agnostic Design
 //!
@@ -48,7 +49,7 @@
 //!
 //! let content = AsyncSCShareableContent::get().await?;
 //! let display = &content.displays()[0];
-//! let filter = SCContentFilter::create().with_display(display).with_excluding_windows(&[]).build();
+//! let filter = SCContentFilter::for_display(display).with_excluding_windows(&[]).build();
 //! let config = SCStreamConfiguration::new().with_width(1920).with_height(1080);
 //!
 //! let stream = AsyncSCStream::new(&filter, &config, 30, SCStreamOutputType::Screen);
@@ -65,6 +66,29 @@
 //! # Ok(())
 //! # }
 //! ```
+//!
+//! ### Cancellation
+//!
+//! Content queries can sit behind a permission prompt for as long as the
+//! user ignores it. Pass a [`CancellationToken`] and call
+//! [`cancel`](CancellationToken::cancel) from elsewhere, e.g. when a
+//! window closes, to resolve the future with [`SCError::Cancelled`]:
+//!
+//! ```rust,no_run
+//! # async fn example() {
+//! use screencapturekit::async_api::{AsyncSCShareableContent, CancellationToken};
+//!
+//! let token = CancellationToken::new();
+//! let on_close = token.clone();
+//! // ... later, from the UI: on_close.cancel();
+//!
+//! match AsyncSCShareableContent::get_with_cancel(&token).await {
+//!     Ok(content) => println!("{} displays", content.displays().len()),
+//!     Err(e) if e.is_cancelled() => println!("No longer needed"),
+//!     Err(e) => eprintln!("{e}"),
+//! }
+//! # }
+//! ```
 
 use crate::error::SCError;
 use crate::shareable_content::SCShareableContent;
@@ -75,9 +99,157 @@ use crate::utils::completion::{error_from_cstr, AsyncCompletion, AsyncCompletion
 use std::ffi::c_void;
 use std::future::Future;
 use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 use std::task::{Context, Poll, Waker};
 
+// ============================================================================
+// CancellationToken - Abandon pending async operations
+// ============================================================================
+
+/// Shared flag that resolves pending [`Cancellable`] futures with
+/// [`SCError::Cancelled`]
+///
+/// Clones share the flag: hand one to the code that decides to give up and
+/// keep another next to the `.await`. Cancelling is permanent.
+///
+/// Cancelling abandons the *wait*, not the macOS call behind it. A content
+/// query or picker that was already sent to `ScreenCaptureKit` still runs
+/// to completion and its result is discarded; a `start_capture` that was
+/// already sent may still start the stream.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    inner: Arc<Mutex<CancellationState>>,
+}
+
+#[derive(Debug, Default)]
+struct CancellationState {
+    cancelled: bool,
+    /// Wakers of pending futures, keyed by [`Cancellable::id`]
+    wakers: Vec<(u64, Waker)>,
+}
+
+static NEXT_CANCELLABLE_ID: AtomicU64 = AtomicU64::new(0);
+
+impl CancellationToken {
+    /// A token that hasn't been cancelled
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Resolve every future watching this token with [`SCError::Cancelled`]
+    pub fn cancel(&self) {
+        let wakers = {
+            let Ok(mut state) = self.inner.lock() else {
+                return;
+            };
+            state.cancelled = true;
+            std::mem::take(&mut state.wakers)
+        };
+        for (_, waker) in wakers {
+            waker.wake();
+        }
+    }
+
+    /// Whether [`cancel`](Self::cancel) has been called on this token or a
+    /// clone
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.lock().map_or(true, |state| state.cancelled)
+    }
+
+    /// Make `future` resolve with [`SCError::Cancelled`] as soon as this
+    /// token is cancelled
+    ///
+    /// Works with any of this module's fallible futures, e.g.
+    /// `token.guard(stream.start_capture())`.
+    pub fn guard<F, T>(&self, future: F) -> Cancellable<F>
+    where
+        F: Future<Output = Result<T, SCError>> + Unpin,
+    {
+        Cancellable {
+            future,
+            token: self.clone(),
+            id: NEXT_CANCELLABLE_ID.fetch_add(1, Ordering::Relaxed),
+        }
+    }
+
+    /// Remember `waker` for a pending future, returning whether the token
+    /// is already cancelled
+    fn register(&self, id: u64, waker: &Waker) -> bool {
+        let Ok(mut state) = self.inner.lock() else {
+            return true;
+        };
+        if state.cancelled {
+            return true;
+        }
+        match state
+            .wakers
+            .iter_mut()
+            .find(|(existing, _)| *existing == id)
+        {
+            Some((_, existing)) if existing.will_wake(waker) => {}
+            Some((_, existing)) => existing.clone_from(waker),
+            None => state.wakers.push((id, waker.clone())),
+        }
+        false
+    }
+
+    fn unregister(&self, id: u64) {
+        if let Ok(mut state) = self.inner.lock() {
+            state.wakers.retain(|(existing, _)| *existing != id);
+        }
+    }
+}
+
+/// A future that gives up with [`SCError::Cancelled`] when its
+/// [`CancellationToken`] is cancelled
+///
+/// Created by [`CancellationToken::guard`] and the `*_with_cancel` methods.
+pub struct Cancellable<F> {
+    future: F,
+    token: CancellationToken,
+    id: u64,
+}
+
+impl<F> std::fmt::Debug for Cancellable<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Cancellable")
+            .field("cancelled", &self.token.is_cancelled())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<F, T> Future for Cancellable<F>
+where
+    F: Future<Output = Result<T, SCError>> + Unpin,
+{
+    type Output = Result<T, SCError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let cancelled = || Poll::Ready(Err(SCError::Cancelled("async operation".to_string())));
+        if self.token.is_cancelled() {
+            return cancelled();
+        }
+        if let Poll::Ready(output) = Pin::new(&mut self.future).poll(cx) {
+            return Poll::Ready(output);
+        }
+        // Registering re-checks the flag under the lock, so a cancel that
+        // raced the poll above is not missed.
+        if self.token.register(self.id, cx.waker()) {
+            return cancelled();
+        }
+        Poll::Pending
+    }
+}
+
+impl<F> Drop for Cancellable<F> {
+    fn drop(&mut self) {
+        self.token.unregister(self.id);
+    }
+}
+
 // ============================================================================
 // AsyncSCShareableContent - True async with callback-based FFI
 // ============================================================================
@@ -154,6 +326,15 @@ impl AsyncSCShareableContent {
         Self::create().get()
     }
 
+    /// Like [`get`](Self::get), but resolves with [`SCError::Cancelled`]
+    /// once `token` is cancelled
+    ///
+    /// Useful while the permission prompt is up: the user may never answer
+    /// it.
+    pub fn get_with_cancel(token: &CancellationToken) -> Cancellable<AsyncShareableContentFuture> {
+        token.guard(Self::get())
+    }
+
     /// Create options builder for customizing shareable content retrieval
     #[must_use]
     pub fn create() -> AsyncSCShareableContentOptions {
@@ -200,6 +381,15 @@ impl AsyncSCShareableContentOptions {
         AsyncShareableContentFuture { inner: future }
     }
 
+    /// Get the shareable content with these options, giving up once
+    /// `token` is cancelled
+    pub fn get_with_cancel(
+        self,
+        token: &CancellationToken,
+    ) -> Cancellable<AsyncShareableContentFuture> {
+        token.guard(self.get())
+    }
+
     /// Asynchronously get shareable content with only windows below a reference window
     ///
     /// This returns windows that are stacked below the specified reference window
@@ -425,6 +615,50 @@ impl Future for NextSampleTyped<'_> {
     }
 }
 
+/// Future for the next sample buffer that gives up when a
+/// [`CancellationToken`] is cancelled
+///
+/// Resolves to `Ok(None)` when the stream closes and to
+/// [`SCError::Cancelled`] on cancellation. Returned by
+/// [`AsyncSCStream::next_with_cancel`].
+pub struct NextSampleWithCancel<'a> {
+    state: &'a Arc<Mutex<AsyncSampleIteratorState>>,
+    token: CancellationToken,
+    id: u64,
+}
+
+impl std::fmt::Debug for NextSampleWithCancel<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NextSampleWithCancel")
+            .field("cancelled", &self.token.is_cancelled())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Future for NextSampleWithCancel<'_> {
+    type Output = Result<Option<crate::cm::CMSampleBuffer>, SCError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let cancelled = || Poll::Ready(Err(SCError::Cancelled("next sample".to_string())));
+        if self.token.is_cancelled() {
+            return cancelled();
+        }
+        if let Poll::Ready(sample) = poll_next_sample(self.state, cx) {
+            return Poll::Ready(Ok(sample.map(|(buffer, _of_type)| buffer)));
+        }
+        if self.token.register(self.id, cx.waker()) {
+            return cancelled();
+        }
+        Poll::Pending
+    }
+}
+
+impl Drop for NextSampleWithCancel<'_> {
+    fn drop(&mut self) {
+        self.token.unregister(self.id);
+    }
+}
+
 /// A [`Stream`](futures_core::Stream) of captured sample buffers.
 ///
 /// Yields `CMSampleBuffer`s and ends (`None`) when the stream closes. Returned
@@ -530,18 +764,20 @@ unsafe impl Sync for AsyncStreamDelegate {}
 /// waker-based [`AsyncCompletion`] machinery, so awaiting a control future
 /// resumes the task via its [`Waker`] instead of parking a thread. This is the
 /// same primitive used by the content / screenshot / picker futures.
-extern "C" fn stream_control_callback(context: *mut c_void, success: bool, msg: *const i8) {
+extern "C" fn stream_control_callback(context: *mut c_void, success: bool, error: *const c_void) {
     crate::utils::panic_safe::catch_user_panic("stream_control_callback", move || {
-        if success {
-            // SAFETY: `context` is the one-shot completion pointer from
-            // `AsyncCompletion::<()>::create()`; Swift invokes this callback
-            // exactly once, after which the pointer is consumed.
-            unsafe { AsyncCompletion::<()>::complete_ok(context, ()) };
+        let result = if success || error.is_null() {
+            Ok(())
         } else {
-            let error = unsafe { error_from_cstr(msg) };
-            // SAFETY: see above — one-shot completion pointer, fired once.
-            unsafe { AsyncCompletion::<()>::complete_err(context, error) };
-        }
+            // SAFETY: Swift lends a live NSError for the duration of the callback.
+            Err(SCError::from_ns_error(unsafe {
+                crate::error::NSErrorInfo::from_borrowed(error)
+            }))
+        };
+        // SAFETY: `context` is the one-shot completion pointer from
+        // `AsyncCompletion::create()`; Swift invokes this callback exactly
+        // once, after which the pointer is consumed.
+        unsafe { AsyncCompletion::<Result<(), SCError>>::complete_ok(context, result) };
     });
 }
 
@@ -560,8 +796,27 @@ extern "C" fn stream_control_callback(context: *mut c_void, success: bool, msg:
 /// safe; it simply means success/failure is not observed.
 #[must_use = "the operation starts eagerly, but you must .await the future to observe success or failure"]
 pub struct StreamControlFuture {
-    inner: AsyncCompletionFuture<()>,
+    inner: AsyncCompletionFuture<Result<(), SCError>>,
+    /// Wraps a failure of the completion itself (no framework error).
     map_err: fn(String) -> SCError,
+    /// Run once when the operation completes, with whether it succeeded
+    /// (e.g. to record the applied configuration and the new state on the
+    /// stream).
+    on_complete: Option<Box<dyn FnOnce(bool) + Send + Sync>>,
+}
+
+impl StreamControlFuture {
+    /// A future that resolves to `error` without contacting `ScreenCaptureKit`.
+    fn rejected(error: SCError) -> Self {
+        let (future, context) = AsyncCompletion::<Result<(), SCError>>::create();
+        // SAFETY: `context` was just created and is completed exactly once.
+        unsafe { AsyncCompletion::<Result<(), SCError>>::complete_ok(context, Err(error)) };
+        Self {
+            inner: future,
+            map_err: SCError::StreamError,
+            on_complete: None,
+        }
+    }
 }
 
 impl std::fmt::Debug for StreamControlFuture {
@@ -576,9 +831,15 @@ impl Future for StreamControlFuture {
 
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         let map_err = self.map_err;
-        Pin::new(&mut self.inner)
+        let poll = Pin::new(&mut self.inner)
             .poll(cx)
-            .map(|r| r.map_err(map_err))
+            .map(|r| r.map_err(map_err).and_then(|result| result));
+        if let Poll::Ready(result) = &poll {
+            if let Some(on_complete) = self.on_complete.take() {
+                on_complete(result.is_ok());
+            }
+        }
+        poll
     }
 }
 
@@ -598,7 +859,7 @@ impl Future for StreamControlFuture {
 ///
 /// let content = AsyncSCShareableContent::get().await?;
 /// let display = &content.displays()[0];
-/// let filter = SCContentFilter::create().with_display(display).with_excluding_windows(&[]).build();
+/// let filter = SCContentFilter::for_display(display).with_excluding_windows(&[]).build();
 /// let config = SCStreamConfiguration::new()
 ///     .with_width(1920)
 ///     .with_height(1080);
@@ -702,6 +963,19 @@ impl AsyncSCStream {
         }
     }
 
+    /// Get the next sample buffer, giving up once `token` is cancelled
+    ///
+    /// Resolves to `Ok(None)` when the stream closes and to
+    /// [`SCError::Cancelled`] on cancellation. A sample that arrives after
+    /// cancellation stays buffered for the next call.
+    pub fn next_with_cancel(&self, token: &CancellationToken) -> NextSampleWithCancel<'_> {
+        NextSampleWithCancel {
+            state: &self.iterator_state,
+            token: token.clone(),
+            id: NEXT_CANCELLABLE_ID.fetch_add(1, Ordering::Relaxed),
+        }
+    }
+
     /// Get the next sample buffer together with its output type.
     ///
     /// Use this when the stream carries more than one output type (e.g. screen
@@ -866,10 +1140,19 @@ impl AsyncSCStream {
     ///
     /// # Errors
     ///
-    /// The awaited result is `Err(SCError::CaptureStartFailed)` if the stream
-    /// fails to start.
+    /// The awaited result carries the framework's error
+    /// ([`SCError::SCStreamError`] or [`SCError::NSError`]) if the stream fails
+    /// to start, or [`SCError::InvalidState`] if it is already starting,
+    /// running or stopping.
+    ///
+    /// The stream's [`state`](crate::stream::SCStream::state) leaves
+    /// `Starting` when the future is polled to completion.
     pub fn start_capture(&self) -> StreamControlFuture {
-        let (future, context) = AsyncCompletion::<()>::create();
+        let starting = match self.stream.begin_start() {
+            Ok(starting) => starting,
+            Err(error) => return StreamControlFuture::rejected(error),
+        };
+        let (future, context) = AsyncCompletion::<Result<(), SCError>>::create();
         // SAFETY: `self.stream.as_ptr()` is a valid, live `SCStream` pointer for
         // the duration of this call; `context` is the one-shot completion
         // pointer from `AsyncCompletion::create()`, invoked exactly once.
@@ -880,9 +1163,13 @@ impl AsyncSCStream {
                 stream_control_callback,
             );
         }
+        let stream = self.stream.clone();
         StreamControlFuture {
             inner: future,
             map_err: SCError::CaptureStartFailed,
+            on_complete: Some(Box::new(move |succeeded| {
+                stream.finish_start(starting, succeeded);
+            })),
         }
     }
 
@@ -893,10 +1180,15 @@ impl AsyncSCStream {
     ///
     /// # Errors
     ///
-    /// The awaited result is `Err(SCError::CaptureStopFailed)` if the stream
-    /// fails to stop.
+    /// The awaited result carries the framework's error
+    /// ([`SCError::SCStreamError`] or [`SCError::NSError`]) if the stream fails
+    /// to stop, or [`SCError::InvalidState`] if it is not running.
     pub fn stop_capture(&self) -> StreamControlFuture {
-        let (future, context) = AsyncCompletion::<()>::create();
+        let stopping = match self.stream.begin_stop() {
+            Ok(stopping) => stopping,
+            Err(error) => return StreamControlFuture::rejected(error),
+        };
+        let (future, context) = AsyncCompletion::<Result<(), SCError>>::create();
         // SAFETY: see `start_capture` — live stream pointer, one-shot context.
         unsafe {
             crate::ffi::sc_stream_stop_capture(
@@ -905,9 +1197,13 @@ impl AsyncSCStream {
                 stream_control_callback,
             );
         }
+        let stream = self.stream.clone();
         StreamControlFuture {
             inner: future,
             map_err: SCError::CaptureStopFailed,
+            on_complete: Some(Box::new(move |succeeded| {
+                stream.finish_stop(stopping, succeeded);
+            })),
         }
     }
 
@@ -918,22 +1214,40 @@ impl AsyncSCStream {
     ///
     /// # Errors
     ///
-    /// The awaited result is `Err(SCError::StreamError)` if the update fails.
+    /// The awaited result carries the framework's error
+    /// ([`SCError::SCStreamError`] or [`SCError::NSError`]) if the update fails,
+    /// or [`SCError::InvalidState`] while another start, stop or update is in
+    /// flight.
     pub fn update_configuration(&self, config: &SCStreamConfiguration) -> StreamControlFuture {
-        let (future, context) = AsyncCompletion::<()>::create();
-        // SAFETY: `self.stream.as_ptr()` and `config.as_ptr()` are valid for the
-        // duration of this call; `context` is the one-shot completion pointer.
+        let updating = match self.stream.begin_update("update the configuration") {
+            Ok(updating) => updating,
+            Err(error) => return StreamControlFuture::rejected(error),
+        };
+        // The caller may mutate `config` (or a clone of it) while the update is
+        // in flight, so Swift gets a private copy.
+        let applied = config.deep_copy();
+        let (future, context) = AsyncCompletion::<Result<(), SCError>>::create();
+        // SAFETY: `self.stream.as_ptr()` and `applied.as_ptr()` are valid for the
+        // duration of this call (the Swift task retains the configuration);
+        // `context` is the one-shot completion pointer.
         unsafe {
             crate::ffi::sc_stream_update_configuration(
                 self.stream.as_ptr(),
-                config.as_ptr(),
+                applied.as_ptr(),
                 context,
                 stream_control_callback,
             );
         }
+        let stream = self.stream.clone();
         StreamControlFuture {
             inner: future,
             map_err: SCError::StreamError,
+            on_complete: Some(Box::new(move |succeeded| {
+                if succeeded {
+                    stream.store_configuration(&applied);
+                }
+                stream.finish_update(updating);
+            })),
         }
     }
 
@@ -944,9 +1258,16 @@ impl AsyncSCStream {
     ///
     /// # Errors
     ///
-    /// The awaited result is `Err(SCError::StreamError)` if the update fails.
+    /// The awaited result carries the framework's error
+    /// ([`SCError::SCStreamError`] or [`SCError::NSError`]) if the update fails,
+    /// or [`SCError::InvalidState`] while another start, stop or update is in
+    /// flight.
     pub fn update_content_filter(&self, filter: &SCContentFilter) -> StreamControlFuture {
-        let (future, context) = AsyncCompletion::<()>::create();
+        let updating = match self.stream.begin_update("update the content filter") {
+            Ok(updating) => updating,
+            Err(error) => return StreamControlFuture::rejected(error),
+        };
+        let (future, context) = AsyncCompletion::<Result<(), SCError>>::create();
         // SAFETY: `self.stream.as_ptr()` and `filter.as_ptr()` are valid for the
         // duration of this call; `context` is the one-shot completion pointer.
         unsafe {
@@ -957,9 +1278,11 @@ impl AsyncSCStream {
                 stream_control_callback,
             );
         }
+        let stream = self.stream.clone();
         StreamControlFuture {
             inner: future,
             map_err: SCError::StreamError,
+            on_complete: Some(Box::new(move |_| stream.finish_update(updating))),
         }
     }
 
@@ -1001,7 +1324,7 @@ impl std::fmt::Debug for AsyncSCStream {
 ///
 /// let content = AsyncSCShareableContent::get().await?;
 /// let display = &content.displays()[0];
-/// let filter = SCContentFilter::create().with_display(display).with_excluding_windows(&[]).build();
+/// let filter = SCContentFilter::for_display(display).with_excluding_windows(&[]).build();
 /// let config = SCStreamConfiguration::new()
 ///     .with_width(1920)
 ///     .with_height(1080);
@@ -1079,6 +1402,35 @@ extern "C" fn screenshot_buffer_callback(
     });
 }
 
+/// Callback for async capture of an image together with its sample buffer
+#[cfg(feature = "macos_14_0")]
+extern "C" fn screenshot_both_callback(
+    buffer_ptr: *const c_void,
+    error_ptr: *const i8,
+    user_data: *mut c_void,
+) {
+    use crate::screenshot_manager::Screenshot;
+    crate::utils::panic_safe::catch_user_panic("screenshot_both_callback", move || {
+        let result = if !error_ptr.is_null() {
+            // SAFETY: `error` is non-null (checked above) and points to a valid null-terminated C string provided by the Swift completion handler.
+            Err(unsafe { error_from_cstr(error_ptr) })
+        } else if buffer_ptr.is_null() {
+            Err("Unknown error".to_string())
+        } else {
+            // SAFETY: `buffer_ptr` is non-null (checked above) and is a +1 `CMSampleBuffer` we now own.
+            let buffer = unsafe { crate::cm::CMSampleBuffer::from_ptr(buffer_ptr.cast_mut()) };
+            Screenshot::from_sample_buffer(buffer).map_err(|error| error.to_string())
+        };
+        // SAFETY: `user_data` is the one-shot completion context from `AsyncCompletion::create()`; Swift invokes this callback exactly once, so the pointer is still valid.
+        unsafe {
+            match result {
+                Ok(screenshot) => AsyncCompletion::complete_ok(user_data, screenshot),
+                Err(error) => AsyncCompletion::<Screenshot>::complete_err(user_data, error),
+            }
+        };
+    });
+}
+
 /// Future for async screenshot capture
 #[cfg(feature = "macos_14_0")]
 pub struct AsyncScreenshotFuture<T> {
@@ -1156,6 +1508,35 @@ impl AsyncSCScreenshotManager {
         AsyncScreenshotFuture { inner: future }
     }
 
+    /// Capture a single screenshot as both a `CGImage` and a `CMSampleBuffer`
+    /// asynchronously
+    ///
+    /// See [`SCScreenshotManager::capture_both`](crate::screenshot_manager::SCScreenshotManager::capture_both).
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - Screen recording permission is not granted
+    /// - The capture fails for any reason
+    /// - The sample buffer holds no image
+    pub fn capture_both(
+        content_filter: &crate::stream::content_filter::SCContentFilter,
+        configuration: &SCStreamConfiguration,
+    ) -> AsyncScreenshotFuture<crate::screenshot_manager::Screenshot> {
+        let (future, context) = AsyncCompletion::create();
+
+        // SAFETY: `content_filter.as_ptr()` and `configuration.as_ptr()` return valid non-null pointers for the duration of this call (borrowed via `&`). `context` is a one-shot completion pointer from `AsyncCompletion::create()`.
+        unsafe {
+            crate::ffi::sc_screenshot_manager_capture_sample_buffer(
+                content_filter.as_ptr(),
+                configuration.as_ptr(),
+                screenshot_both_callback,
+                context,
+            );
+        }
+
+        AsyncScreenshotFuture { inner: future }
+    }
+
     /// Capture a screenshot of a specific screen region asynchronously (macOS 15.2+)
     ///
     /// This method captures the content within the specified rectangle,
@@ -1509,7 +1890,7 @@ impl AsyncSCContentSharingPicker {
     ///     let content = SCShareableContent::get().ok()?;
     ///     let displays = content.displays();
     ///     let display = displays.first()?;
-    ///     let filter = SCContentFilter::create().with_display(display).with_excluding_windows(&[]).build();
+    ///     let filter = SCContentFilter::for_display(display).with_excluding_windows(&[]).build();
     ///     let stream_config = SCStreamConfiguration::new();
     ///     let stream = SCStream::new(&filter, &stream_config);
     ///
@@ -1695,7 +2076,7 @@ impl futures_core::Stream for RecordingEventStream<'_> {
 ///     let content = AsyncSCShareableContent::get().await.ok()?;
 ///     let displays = content.displays();
 ///     let display = displays.first()?;
-///     let filter = SCContentFilter::create().with_display(display).with_excluding_windows(&[]).build();
+///     let filter = SCContentFilter::for_display(display).with_excluding_windows(&[]).build();
 ///     let config = SCStreamConfiguration::new().with_width(1920).with_height(1080);
 ///
 ///     let rec_config = SCRecordingOutputConfiguration::new()
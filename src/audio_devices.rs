@@ -1,13 +1,26 @@
 //! Audio input device enumeration using `AVFoundation`.
 //!
-//! This module provides access to available microphone devices on macOS.
+//! This module provides access to available microphone devices on macOS,
+//! and lists output devices with [`AudioOutputDevice`].
+//! Beyond the device list, each [`AudioInputDevice`] can report its Core
+//! Audio capabilities (sample rates, input channels), and an
+//! [`AudioDeviceMonitor`] delivers [`AudioDeviceEvent`]s as devices are
+//! plugged in, unplugged, or the default input changes.
 
+use std::ffi::{c_void, CString};
+use std::fmt;
+use std::sync::mpsc::{self, Receiver};
+use std::sync::{Mutex, PoisonError};
+
+use crate::error::SCError;
 use crate::utils::ffi_string::{ffi_string_from_buffer, SMALL_BUFFER_SIZE};
 
 /// Represents an audio input device (microphone).
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct AudioInputDevice {
     /// The unique device ID used with `SCStreamConfiguration::with_microphone_capture_device_id`
+    ///
+    /// This is the Core Audio device UID, stable across reboots and replugs.
     pub id: String,
     /// Human-readable device name
     pub name: String,
@@ -103,3 +116,338 @@ impl AudioInputDevice {
         }
     }
 }
+
+/// Represents an audio output device (speakers, headphones, an HDMI
+/// display, a virtual device).
+///
+/// `ScreenCaptureKit`'s system audio is the mix of everything the system
+/// plays, whichever device it goes to. To capture only what one device
+/// plays, pass it to
+/// [`AudioOutputTap`](crate::audio_output_tap::AudioOutputTap) (macOS 14.2+).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AudioOutputDevice {
+    /// The Core Audio device UID, stable across reboots and replugs
+    pub id: String,
+    /// Human-readable device name
+    pub name: String,
+    /// Whether this is the system default output device
+    pub is_default: bool,
+}
+
+impl AudioOutputDevice {
+    /// List all audio devices with at least one output stream.
+    ///
+    /// **Not cached**, like [`AudioInputDevice::list`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use screencapturekit::audio_devices::AudioOutputDevice;
+    ///
+    /// for device in AudioOutputDevice::list() {
+    ///     println!("{}: {}", device.id, device.name);
+    /// }
+    /// ```
+    #[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
+    pub fn list() -> Vec<Self> {
+        let count = unsafe { crate::ffi::sc_audio_get_output_device_count() };
+        let default_id = unsafe {
+            ffi_string_from_buffer(SMALL_BUFFER_SIZE, |buf, len| {
+                crate::ffi::sc_audio_get_default_output_device_id(buf, len)
+            })
+        };
+        let mut devices = Vec::with_capacity(count as usize);
+
+        for i in 0..count {
+            let id = unsafe {
+                ffi_string_from_buffer(SMALL_BUFFER_SIZE, |buf, len| {
+                    crate::ffi::sc_audio_get_output_device_id(i, buf, len)
+                })
+            };
+            let name = unsafe {
+                ffi_string_from_buffer(SMALL_BUFFER_SIZE, |buf, len| {
+                    crate::ffi::sc_audio_get_output_device_name(i, buf, len)
+                })
+            };
+
+            if let (Some(id), Some(name)) = (id, name) {
+                let is_default = default_id.as_ref() == Some(&id);
+                devices.push(Self {
+                    id,
+                    name,
+                    is_default,
+                });
+            }
+        }
+
+        devices
+    }
+
+    /// Get the default audio output device, if any.
+    pub fn default_device() -> Option<Self> {
+        Self::list().into_iter().find(|device| device.is_default)
+    }
+}
+
+/// A range of supported nominal sample rates, in Hz. Devices with fixed
+/// rates report ranges where `min == max`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SampleRateRange {
+    /// Lowest rate in the range
+    pub min: f64,
+    /// Highest rate in the range
+    pub max: f64,
+}
+
+impl SampleRateRange {
+    /// Whether `rate` falls inside the range
+    #[must_use]
+    pub fn contains(&self, rate: f64) -> bool {
+        (self.min..=self.max).contains(&rate)
+    }
+}
+
+impl AudioInputDevice {
+    /// The Core Audio device UID; the same value as [`id`](Self::id)
+    #[must_use]
+    pub fn uid(&self) -> &str {
+        &self.id
+    }
+
+    /// The sample rate the device is currently running at, or `None` if the
+    /// device is gone or Core Audio doesn't report one
+    #[must_use]
+    pub fn nominal_sample_rate(&self) -> Option<f64> {
+        let uid = CString::new(self.id.as_str()).ok()?;
+        let rate = unsafe { crate::ffi::sc_audio_get_device_nominal_sample_rate(uid.as_ptr()) };
+        (rate > 0.0).then_some(rate)
+    }
+
+    /// Sample rates the device can be switched to
+    ///
+    /// Empty if the device is gone.
+    #[must_use]
+    #[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
+    pub fn available_sample_rates(&self) -> Vec<SampleRateRange> {
+        let Ok(uid) = CString::new(self.id.as_str()) else {
+            return Vec::new();
+        };
+        let count = unsafe {
+            crate::ffi::sc_audio_get_device_sample_rate_ranges(
+                uid.as_ptr(),
+                std::ptr::null_mut(),
+                0,
+            )
+        };
+        if count <= 0 {
+            return Vec::new();
+        }
+        let mut pairs = vec![0.0f64; count as usize * 2];
+        let written = unsafe {
+            crate::ffi::sc_audio_get_device_sample_rate_ranges(
+                uid.as_ptr(),
+                pairs.as_mut_ptr(),
+                count,
+            )
+        };
+        // The device list may have changed between the two calls.
+        let written = written.clamp(0, count) as usize;
+        pairs[..written * 2]
+            .chunks_exact(2)
+            .map(|pair| SampleRateRange {
+                min: pair[0],
+                max: pair[1],
+            })
+            .collect()
+    }
+
+    /// Whether the device can run at `rate` Hz
+    #[must_use]
+    pub fn supports_sample_rate(&self, rate: f64) -> bool {
+        self.available_sample_rates()
+            .iter()
+            .any(|range| range.contains(rate))
+    }
+
+    /// Number of input channels, or `None` if the device is gone
+    #[must_use]
+    #[allow(clippy::cast_sign_loss)]
+    pub fn input_channel_count(&self) -> Option<u32> {
+        let uid = CString::new(self.id.as_str()).ok()?;
+        let channels = unsafe { crate::ffi::sc_audio_get_device_input_channel_count(uid.as_ptr()) };
+        (channels >= 0).then_some(channels as u32)
+    }
+}
+
+/// A change to the set of audio input devices.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AudioDeviceEvent {
+    /// A device was connected
+    Added(AudioInputDevice),
+    /// A device was disconnected
+    Removed(AudioInputDevice),
+    /// The system default input changed; `None` if there no longer is one
+    DefaultChanged(Option<AudioInputDevice>),
+}
+
+type EventCallback = Box<dyn Fn(AudioDeviceEvent) + Send + Sync + 'static>;
+
+struct MonitorState {
+    callback: EventCallback,
+    devices: Mutex<Vec<AudioInputDevice>>,
+}
+
+impl MonitorState {
+    /// Re-list devices and report what changed since the last call.
+    fn refresh(&self) {
+        let current = AudioInputDevice::list();
+        let events = {
+            let mut known = self.devices.lock().unwrap_or_else(PoisonError::into_inner);
+            let events = diff_devices(&known, &current);
+            *known = current;
+            events
+        };
+        for event in events {
+            (self.callback)(event);
+        }
+    }
+}
+
+/// Events that turn `old` into `new`: removals, then additions, then a
+/// default change.
+fn diff_devices(old: &[AudioInputDevice], new: &[AudioInputDevice]) -> Vec<AudioDeviceEvent> {
+    let mut events: Vec<AudioDeviceEvent> = old
+        .iter()
+        .filter(|device| !new.iter().any(|d| d.id == device.id))
+        .cloned()
+        .map(AudioDeviceEvent::Removed)
+        .collect();
+    events.extend(
+        new.iter()
+            .filter(|device| !old.iter().any(|d| d.id == device.id))
+            .cloned()
+            .map(AudioDeviceEvent::Added),
+    );
+    let old_default = old.iter().find(|d| d.is_default);
+    let new_default = new.iter().find(|d| d.is_default);
+    if old_default.map(|d| &d.id) != new_default.map(|d| &d.id) {
+        events.push(AudioDeviceEvent::DefaultChanged(new_default.cloned()));
+    }
+    events
+}
+
+extern "C" fn monitor_callback(user_data: *mut c_void) {
+    crate::utils::panic_safe::catch_user_panic("audio device monitor", || {
+        // SAFETY: `user_data` is the `MonitorState` owned by the monitor,
+        // which unregisters this callback before freeing it.
+        let state = unsafe { &*user_data.cast::<MonitorState>() };
+        state.refresh();
+    });
+}
+
+/// Reports audio input devices being added, removed, or the default input
+/// changing.
+///
+/// Notifications come from Core Audio on a private queue, so the callback
+/// runs on a background thread. Dropping the monitor unregisters it; no
+/// callback runs after `drop` returns.
+///
+/// # Example
+///
+/// ```no_run
+/// use screencapturekit::audio_devices::{AudioDeviceEvent, AudioDeviceMonitor};
+///
+/// let (monitor, events) = AudioDeviceMonitor::channel()?;
+/// for event in events {
+///     match event {
+///         AudioDeviceEvent::Added(device) => println!("+ {}", device.name),
+///         AudioDeviceEvent::Removed(device) => println!("- {}", device.name),
+///         AudioDeviceEvent::DefaultChanged(device) => println!("default: {device:?}"),
+///     }
+/// }
+/// # drop(monitor);
+/// # Ok::<(), screencapturekit::error::SCError>(())
+/// ```
+pub struct AudioDeviceMonitor {
+    listener: *mut c_void,
+    state: *mut MonitorState,
+}
+
+// SAFETY: the listener handle is only passed back to Swift on drop, and
+// `MonitorState` is `Send + Sync` (its callback is required to be).
+unsafe impl Send for AudioDeviceMonitor {}
+unsafe impl Sync for AudioDeviceMonitor {}
+
+impl AudioDeviceMonitor {
+    /// Start monitoring, calling `callback` for every change.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if Core Audio refuses the property listener.
+    pub fn new<F>(callback: F) -> Result<Self, SCError>
+    where
+        F: Fn(AudioDeviceEvent) + Send + Sync + 'static,
+    {
+        let state = Box::into_raw(Box::new(MonitorState {
+            callback: Box::new(callback),
+            devices: Mutex::new(AudioInputDevice::list()),
+        }));
+        let listener =
+            unsafe { crate::ffi::sc_audio_device_listener_create(monitor_callback, state.cast()) };
+        if listener.is_null() {
+            drop(unsafe { Box::from_raw(state) });
+            return Err(SCError::internal_error(
+                "failed to register audio device listener",
+            ));
+        }
+        Ok(Self { listener, state })
+    }
+
+    /// Start monitoring and receive events on a channel.
+    ///
+    /// The channel disconnects when the monitor is dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if Core Audio refuses the property listener.
+    pub fn channel() -> Result<(Self, Receiver<AudioDeviceEvent>), SCError> {
+        let (sender, receiver) = mpsc::channel();
+        let sender = Mutex::new(sender);
+        let monitor = Self::new(move |event| {
+            let _ = sender
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .send(event);
+        })?;
+        Ok((monitor, receiver))
+    }
+
+    /// The device list as of the last notification.
+    #[must_use]
+    pub fn devices(&self) -> Vec<AudioInputDevice> {
+        // SAFETY: `state` stays valid until drop.
+        let state = unsafe { &*self.state };
+        state
+            .devices
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+}
+
+impl Drop for AudioDeviceMonitor {
+    fn drop(&mut self) {
+        unsafe {
+            crate::ffi::sc_audio_device_listener_release(self.listener);
+            drop(Box::from_raw(self.state));
+        }
+    }
+}
+
+impl fmt::Debug for AudioDeviceMonitor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AudioDeviceMonitor")
+            .field("devices", &self.devices().len())
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,355 @@
+//! AAC and Opus encoding of captured audio
+//!
+//! Requires the `audio_encoder` feature.
+//!
+//! [`SCAudioEncoder`] turns the Float32 PCM that `ScreenCaptureKit` delivers
+//! into compressed packets using `AudioToolbox`'s `AudioConverter`, so no
+//! extra native libraries are needed. Input is re-chunked internally with an
+//! [`AudioChunker`] to the codec's packet size (1024 frames for AAC, 960 for
+//! Opus), and every packet carries the presentation time of its first frame.
+//!
+//! ## Timestamps and priming
+//!
+//! AAC encoders emit [`priming_frames`](SCAudioEncoder::priming_frames) of
+//! encoder delay (usually 2112) before real audio, so the first packets come
+//! out a few chunks after the first input. Packets are stamped with the input
+//! timeline in order, which is what RTMP/FLV and WebRTC senders expect;
+//! muxers that write an edit list should subtract the priming duration.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use screencapturekit::audio_encoder::{AudioCodec, SCAudioEncoder};
+//! use screencapturekit::cm::CMSampleBuffer;
+//!
+//! let mut encoder = SCAudioEncoder::new(AudioCodec::Opus, 48_000, 2, 96_000).unwrap();
+//!
+//! let mut on_audio = |sample: &CMSampleBuffer| {
+//!     for packet in encoder.encode(sample).unwrap() {
+//!         // send(packet.pts(), packet.data());
+//!         println!("{} bytes at {:?}", packet.data().len(), packet.pts());
+//!     }
+//! };
+//! # let _ = &mut on_audio;
+//! ```
+
+use std::collections::VecDeque;
+use std::ffi::c_void;
+use std::fmt;
+
+use crate::cm::{AudioChunk, AudioChunkLayout, AudioChunker, CMSampleBuffer, CMTime};
+use crate::error::SCError;
+
+/// Sample rates the Opus encoder accepts.
+const OPUS_SAMPLE_RATES: [u32; 5] = [8_000, 12_000, 16_000, 24_000, 48_000];
+
+/// Compressed audio codec.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AudioCodec {
+    /// MPEG-4 AAC Low Complexity, 1024 frames per packet.
+    Aac,
+    /// Opus, 960 frames (20 ms) per packet. Requires a 48 kHz (or 8, 12, 16,
+    /// 24 kHz) sample rate.
+    Opus,
+}
+
+impl AudioCodec {
+    const fn as_ffi(self) -> i32 {
+        match self {
+            Self::Aac => 0,
+            Self::Opus => 1,
+        }
+    }
+}
+
+impl fmt::Display for AudioCodec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Aac => f.write_str("AAC"),
+            Self::Opus => f.write_str("Opus"),
+        }
+    }
+}
+
+/// One compressed audio packet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncodedAudioPacket {
+    data: Vec<u8>,
+    pts: CMTime,
+    duration: CMTime,
+}
+
+impl EncodedAudioPacket {
+    /// The compressed bytes (raw AAC access unit or Opus packet, no framing).
+    #[must_use]
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Take ownership of the compressed bytes.
+    #[must_use]
+    pub fn into_data(self) -> Vec<u8> {
+        self.data
+    }
+
+    /// Presentation time of the packet.
+    #[must_use]
+    pub const fn pts(&self) -> CMTime {
+        self.pts
+    }
+
+    /// Duration of the packet.
+    #[must_use]
+    pub const fn duration(&self) -> CMTime {
+        self.duration
+    }
+}
+
+/// Encodes captured PCM to AAC or Opus.
+///
+/// Requires the `audio_encoder` feature.
+pub struct SCAudioEncoder {
+    ptr: *const c_void,
+    codec: AudioCodec,
+    sample_rate: u32,
+    channel_count: u32,
+    frames_per_packet: usize,
+    max_packet_size: usize,
+    chunker: AudioChunker,
+    /// Timestamps of chunks fed to the converter but not yet returned as packets.
+    pending: VecDeque<CMTime>,
+    last_pts: Option<CMTime>,
+}
+
+// SAFETY: the converter is only touched through `&mut self`, and
+// `AudioConverter` has no thread affinity.
+unsafe impl Send for SCAudioEncoder {}
+
+impl fmt::Debug for SCAudioEncoder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SCAudioEncoder")
+            .field("codec", &self.codec)
+            .field("sample_rate", &self.sample_rate)
+            .field("channel_count", &self.channel_count)
+            .field("frames_per_packet", &self.frames_per_packet)
+            .finish_non_exhaustive()
+    }
+}
+
+impl SCAudioEncoder {
+    /// Create an encoder for `channel_count` channels at `sample_rate` Hz.
+    ///
+    /// `bitrate` is in bits per second; pass `0` for the codec default.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidConfiguration`] for a sample rate Opus does
+    /// not support, [`SCError::InvalidDimension`] for zero channels or sample
+    /// rate, or [`SCError::OSError`] if `AudioToolbox` rejects the
+    /// configuration (e.g. an out-of-range bitrate).
+    pub fn new(
+        codec: AudioCodec,
+        sample_rate: u32,
+        channel_count: u32,
+        bitrate: u32,
+    ) -> Result<Self, SCError> {
+        if channel_count == 0 {
+            return Err(SCError::invalid_dimension("channel_count", 0));
+        }
+        if sample_rate == 0 {
+            return Err(SCError::invalid_dimension("sample_rate", 0));
+        }
+        if codec == AudioCodec::Opus && !OPUS_SAMPLE_RATES.contains(&sample_rate) {
+            return Err(SCError::invalid_config(format!(
+                "Opus does not support a {sample_rate} Hz sample rate"
+            )));
+        }
+
+        let mut status = 0;
+        let ptr = unsafe {
+            crate::ffi::sc_audio_encoder_create(
+                codec.as_ffi(),
+                f64::from(sample_rate),
+                channel_count,
+                bitrate,
+                &mut status,
+            )
+        };
+        if ptr.is_null() {
+            return Err(SCError::os_error(
+                status,
+                format!("failed to create {codec} encoder"),
+            ));
+        }
+
+        let frames_per_packet =
+            unsafe { crate::ffi::sc_audio_encoder_frames_per_packet(ptr) } as usize;
+        let max_packet_size = unsafe { crate::ffi::sc_audio_encoder_max_packet_size(ptr) } as usize;
+        let chunker = AudioChunker::new(frames_per_packet, channel_count as usize, sample_rate)?
+            .with_layout(AudioChunkLayout::Interleaved);
+
+        Ok(Self {
+            ptr,
+            codec,
+            sample_rate,
+            channel_count,
+            frames_per_packet,
+            max_packet_size,
+            chunker,
+            pending: VecDeque::new(),
+            last_pts: None,
+        })
+    }
+
+    /// The codec this encoder produces.
+    #[must_use]
+    pub const fn codec(&self) -> AudioCodec {
+        self.codec
+    }
+
+    /// Frames per encoded packet.
+    #[must_use]
+    pub const fn frames_per_packet(&self) -> usize {
+        self.frames_per_packet
+    }
+
+    /// Encoder delay in frames that precedes real audio in the output.
+    #[must_use]
+    pub fn priming_frames(&self) -> u32 {
+        unsafe { crate::ffi::sc_audio_encoder_priming_frames(self.ptr) }
+    }
+
+    /// The codec's magic cookie as reported by `AudioToolbox`.
+    ///
+    /// For AAC this is an MPEG-4 `ES_Descriptor` (the payload of an `esds`
+    /// box) that wraps the `AudioSpecificConfig`; for Opus it is the
+    /// identification header. Useful for MP4 muxers and SDP.
+    #[must_use]
+    #[allow(clippy::cast_sign_loss, clippy::cast_possible_wrap)]
+    pub fn magic_cookie(&self) -> Vec<u8> {
+        let size =
+            unsafe { crate::ffi::sc_audio_encoder_magic_cookie(self.ptr, std::ptr::null_mut(), 0) };
+        if size <= 0 {
+            return Vec::new();
+        }
+        let mut cookie = vec![0u8; size as usize];
+        let written = unsafe {
+            crate::ffi::sc_audio_encoder_magic_cookie(
+                self.ptr,
+                cookie.as_mut_ptr().cast(),
+                cookie.len() as isize,
+            )
+        };
+        cookie.truncate(written.max(0) as usize);
+        cookie
+    }
+
+    /// Encode a captured audio sample buffer, returning every packet that is
+    /// ready.
+    ///
+    /// # Errors
+    ///
+    /// Returns the chunking error for malformed buffers, or
+    /// [`SCError::OSError`] if the encoder fails.
+    pub fn encode(&mut self, sample: &CMSampleBuffer) -> Result<Vec<EncodedAudioPacket>, SCError> {
+        let chunks = self.chunker.push(sample)?;
+        self.encode_chunks(&chunks)
+    }
+
+    /// Encode interleaved `f32` samples starting at `pts`.
+    ///
+    /// # Errors
+    ///
+    /// See [`encode`](Self::encode).
+    pub fn encode_interleaved(
+        &mut self,
+        pts: CMTime,
+        samples: &[f32],
+    ) -> Result<Vec<EncodedAudioPacket>, SCError> {
+        let chunks = self.chunker.push_interleaved(pts, samples)?;
+        self.encode_chunks(&chunks)
+    }
+
+    /// Encode any buffered audio (padded with silence) and drain the
+    /// encoder's delay line. Call once at the end of the stream.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::OSError`] if the encoder fails.
+    pub fn finish(&mut self) -> Result<Vec<EncodedAudioPacket>, SCError> {
+        let mut packets = match self.chunker.flush() {
+            Some(chunk) => self.encode_chunks(std::slice::from_ref(&chunk))?,
+            None => Vec::new(),
+        };
+        while let Some(packet) = self.encode_one(None)? {
+            packets.push(packet);
+        }
+        Ok(packets)
+    }
+
+    fn encode_chunks(&mut self, chunks: &[AudioChunk]) -> Result<Vec<EncodedAudioPacket>, SCError> {
+        let mut packets = Vec::new();
+        for chunk in chunks {
+            self.pending.push_back(chunk.pts());
+            if let Some(packet) = self.encode_one(Some(chunk))? {
+                packets.push(packet);
+            }
+        }
+        Ok(packets)
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    fn encode_one(
+        &mut self,
+        chunk: Option<&AudioChunk>,
+    ) -> Result<Option<EncodedAudioPacket>, SCError> {
+        let mut data = vec![0u8; self.max_packet_size];
+        let mut size = 0u32;
+        let (samples, frames) = chunk.map_or((std::ptr::null(), 0), |c| {
+            (c.samples().as_ptr(), c.frame_count() as u32)
+        });
+
+        let status = unsafe {
+            crate::ffi::sc_audio_encoder_encode(
+                self.ptr,
+                samples,
+                frames,
+                data.as_mut_ptr().cast(),
+                data.len() as u32,
+                &mut size,
+            )
+        };
+        if status != 0 {
+            return Err(SCError::os_error(
+                status,
+                format!("{} encode failed", self.codec),
+            ));
+        }
+        if size == 0 {
+            return Ok(None);
+        }
+        data.truncate(size as usize);
+
+        let duration = CMTime::new(self.frames_per_packet as i64, self.sample_rate as i32);
+        let pts = match self.pending.pop_front() {
+            Some(pts) => pts,
+            // Flushed delay-line packets continue the last timestamp.
+            None => self.last_pts.map_or(CMTime::ZERO, |last| {
+                CMTime::new(last.value + duration.value, duration.timescale)
+            }),
+        };
+        self.last_pts = Some(pts);
+
+        Ok(Some(EncodedAudioPacket {
+            data,
+            pts,
+            duration,
+        }))
+    }
+}
+
+impl Drop for SCAudioEncoder {
+    fn drop(&mut self) {
+        unsafe { crate::ffi::sc_audio_encoder_release(self.ptr) }
+    }
+}
@@ -0,0 +1,282 @@
+//! Capture what one audio output device plays (macOS 14.2+)
+//!
+//! `SCStreamConfiguration::with_captures_audio` captures the *system* mix:
+//! everything any app plays, whether it goes to the speakers, headphones or
+//! an HDMI display. On a Mac with several outputs in use there is no way to
+//! ask `ScreenCaptureKit` for only one of them, and a filter can only pick
+//! audio by application.
+//!
+//! [`AudioOutputTap`] uses a Core Audio process tap instead. The tap is
+//! restricted to one output device and added to a private aggregate device,
+//! so its input carries a stereo mixdown of exactly what that device plays.
+//! The aggregate device is created and destroyed with the tap and never
+//! shows up in Audio MIDI Setup or other apps' device lists.
+//!
+//! Taps need `NSAudioCaptureUsageDescription` in the app's `Info.plist`;
+//! the first tap prompts for permission. They don't need screen recording
+//! permission and don't involve an `SCStream`, so combine a tap with a
+//! video-only stream and align the two by host time.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use screencapturekit::audio_devices::AudioOutputDevice;
+//! use screencapturekit::audio_output_tap::AudioOutputTap;
+//!
+//! # fn example() -> Result<(), screencapturekit::error::SCError> {
+//! let headphones = AudioOutputDevice::list()
+//!     .into_iter()
+//!     .find(|device| device.name.contains("Headphones"));
+//! let mut builder = AudioOutputTap::builder().with_excludes_current_process(true);
+//! if let Some(device) = &headphones {
+//!     builder = builder.with_device(device);
+//! }
+//! let tap = builder.start(|buffer| {
+//!     println!("{} frames at {:?}", buffer.frame_count(), buffer.host_time());
+//! })?;
+//! std::thread::sleep(std::time::Duration::from_secs(5));
+//! tap.stop();
+//! # Ok(())
+//! # }
+//! ```
+
+use std::ffi::{c_void, CString};
+use std::fmt;
+
+use crate::audio_devices::AudioOutputDevice;
+use crate::cm::{CMTime, MachTime};
+use crate::error::SCError;
+
+/// `kAudioHardwareUnsupportedOperationError` (`'unop'`)
+pub(crate) const UNSUPPORTED_OPERATION: i32 = 0x756e_6f70;
+
+/// Interleaved `f32` samples delivered by an [`AudioOutputTap`]
+#[derive(Debug, Clone, Copy)]
+pub struct AudioOutputBuffer<'a> {
+    samples: &'a [f32],
+    channel_count: u32,
+    sample_rate: f64,
+    host_time: MachTime,
+}
+
+impl<'a> AudioOutputBuffer<'a> {
+    /// Samples, interleaved by channel
+    #[must_use]
+    pub const fn samples(&self) -> &'a [f32] {
+        self.samples
+    }
+
+    /// Number of frames (samples per channel)
+    #[must_use]
+    pub fn frame_count(&self) -> usize {
+        self.samples.len() / self.channel_count.max(1) as usize
+    }
+
+    /// Number of interleaved channels
+    #[must_use]
+    pub const fn channel_count(&self) -> u32 {
+        self.channel_count
+    }
+
+    /// Sample rate in Hz
+    #[must_use]
+    pub const fn sample_rate(&self) -> f64 {
+        self.sample_rate
+    }
+
+    /// Host time at which the first frame was played
+    #[must_use]
+    pub const fn host_time(&self) -> MachTime {
+        self.host_time
+    }
+
+    /// [`host_time`](Self::host_time) as a presentation timestamp, on the
+    /// same timeline as an `SCStream`'s video samples
+    #[must_use]
+    pub fn presentation_time(&self) -> CMTime {
+        self.host_time.to_cmtime()
+    }
+}
+
+/// Builder for [`AudioOutputTap`]
+///
+/// Defaults to the system default output, including this process's own
+/// audio.
+#[derive(Debug, Clone, Default)]
+pub struct AudioOutputTapBuilder {
+    device_id: Option<String>,
+    excludes_current_process: bool,
+}
+
+impl AudioOutputTapBuilder {
+    /// Tap `device` instead of the default output
+    #[must_use]
+    pub fn with_device(mut self, device: &AudioOutputDevice) -> Self {
+        self.device_id = Some(device.id.clone());
+        self
+    }
+
+    /// Tap the output device with this Core Audio UID
+    #[must_use]
+    pub fn with_device_id(mut self, device_id: impl Into<String>) -> Self {
+        self.device_id = Some(device_id.into());
+        self
+    }
+
+    /// Leave out audio played by this process, e.g. a monitoring preview
+    #[must_use]
+    pub const fn with_excludes_current_process(mut self, excludes: bool) -> Self {
+        self.excludes_current_process = excludes;
+        self
+    }
+
+    /// Start tapping, calling `on_buffer` from a Core Audio thread for
+    /// every I/O cycle
+    ///
+    /// Keep `on_buffer` short; it runs on the device's real-time I/O path.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::FeatureNotAvailable`] before macOS 14.2,
+    /// [`SCError::InvalidConfiguration`] if the device UID contains a nul
+    /// byte, or [`SCError::OSError`] with Core Audio's status if the device
+    /// doesn't exist or the tap can't be created, e.g. because audio
+    /// capture permission was denied.
+    pub fn start<F>(self, on_buffer: F) -> Result<AudioOutputTap, SCError>
+    where
+        F: FnMut(&AudioOutputBuffer<'_>) + Send + 'static,
+    {
+        let uid = self
+            .device_id
+            .as_deref()
+            .map(CString::new)
+            .transpose()
+            .map_err(|_| SCError::invalid_config("device UID contains a nul byte"))?;
+        let context: *mut TapCallback = Box::into_raw(Box::new(Box::new(on_buffer)));
+        let mut status = 0;
+        let tap = unsafe {
+            crate::ffi::sc_audio_output_tap_create(
+                uid.as_ref().map_or(std::ptr::null(), |uid| uid.as_ptr()),
+                self.excludes_current_process,
+                tap_callback,
+                context.cast(),
+                &mut status,
+            )
+        };
+        if tap.is_null() {
+            // SAFETY: the tap was never started, so Swift holds no reference.
+            drop(unsafe { Box::from_raw(context) });
+            return Err(match status {
+                UNSUPPORTED_OPERATION => {
+                    SCError::feature_not_available("Audio output taps", "14.2")
+                }
+                status => SCError::os_error(status, "failed to create audio output tap"),
+            });
+        }
+        Ok(AudioOutputTap {
+            tap,
+            context,
+            device_id: self.device_id,
+        })
+    }
+}
+
+pub(crate) type TapCallback = Box<dyn FnMut(&AudioOutputBuffer<'_>) + Send>;
+
+pub(crate) extern "C" fn tap_callback(
+    user_data: *mut c_void,
+    samples: *const f32,
+    frame_count: isize,
+    channel_count: u32,
+    sample_rate: f64,
+    host_time: u64,
+) {
+    crate::utils::panic_safe::catch_user_panic("audio output tap", || {
+        let Ok(frames) = usize::try_from(frame_count) else {
+            return;
+        };
+        if samples.is_null() || frames == 0 {
+            return;
+        }
+        // SAFETY: Swift passes `frames * channel_count` interleaved samples
+        // that stay valid for the duration of this call.
+        let samples =
+            unsafe { std::slice::from_raw_parts(samples, frames * channel_count as usize) };
+        // SAFETY: `user_data` is the callback owned by the tap, which is
+        // only freed after Swift stops calling back; Core Audio calls one
+        // I/O proc at a time, so the `&mut` is unique.
+        let on_buffer = unsafe { &mut *user_data.cast::<TapCallback>() };
+        on_buffer(&AudioOutputBuffer {
+            samples,
+            channel_count,
+            sample_rate,
+            host_time: MachTime::from_ticks(host_time),
+        });
+    });
+}
+
+/// A running tap on one audio output device
+///
+/// Stops when dropped; no callback runs after [`stop`](Self::stop) or `drop`
+/// returns.
+pub struct AudioOutputTap {
+    tap: *mut c_void,
+    context: *mut TapCallback,
+    device_id: Option<String>,
+}
+
+// SAFETY: the tap handle is only read from and released through Swift,
+// which serialises access to it, and the callback is `Send`.
+unsafe impl Send for AudioOutputTap {}
+unsafe impl Sync for AudioOutputTap {}
+
+impl AudioOutputTap {
+    /// Configure a new tap
+    #[must_use]
+    pub fn builder() -> AudioOutputTapBuilder {
+        AudioOutputTapBuilder::default()
+    }
+
+    /// UID of the tapped device, or `None` for the default output at the
+    /// time the tap started
+    #[must_use]
+    pub fn device_id(&self) -> Option<&str> {
+        self.device_id.as_deref()
+    }
+
+    /// Sample rate of the delivered audio, in Hz
+    #[must_use]
+    pub fn sample_rate(&self) -> f64 {
+        unsafe { crate::ffi::sc_audio_output_tap_sample_rate(self.tap) }
+    }
+
+    /// Channel count of the delivered audio (2 for the stereo mixdown)
+    #[must_use]
+    pub fn channel_count(&self) -> u32 {
+        unsafe { crate::ffi::sc_audio_output_tap_channel_count(self.tap) }
+    }
+
+    /// Stop tapping and destroy the tap's aggregate device
+    pub fn stop(self) {
+        drop(self);
+    }
+}
+
+impl Drop for AudioOutputTap {
+    fn drop(&mut self) {
+        unsafe {
+            crate::ffi::sc_audio_output_tap_release(self.tap);
+            drop(Box::from_raw(self.context));
+        }
+    }
+}
+
+impl fmt::Debug for AudioOutputTap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AudioOutputTap")
+            .field("device_id", &self.device_id)
+            .field("sample_rate", &self.sample_rate())
+            .field("channel_count", &self.channel_count())
+            .finish_non_exhaustive()
+    }
+}
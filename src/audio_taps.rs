@@ -0,0 +1,213 @@
+//! Capture audio from specific applications (macOS 14.4+)
+//!
+//! A content filter decides which apps' audio an `SCStream` captures, but
+//! that audio arrives mixed into one track, and only while the stream runs.
+//! An [`AppAudioTap`] records a set of processes through a Core Audio
+//! process tap instead, independent of any stream: one tap per app gives
+//! each app its own track, and the tapped apps can be muted on the speakers
+//! while they are recorded.
+//!
+//! Like [`audio_output_tap`](crate::audio_output_tap), a tap needs
+//! `NSAudioCaptureUsageDescription` in the app's `Info.plist` but no screen
+//! recording permission. Every buffer carries the host time its first frame
+//! was played at; [`AudioOutputBuffer::presentation_time`] is on the same
+//! timeline as the video samples and the
+//! [synchronization clock](crate::stream::sc_stream::SCStream::synchronization_clock)
+//! of a stream, so the two mux without further alignment.
+//!
+//! Core Audio only knows about a process once it has played or recorded
+//! something, so tap apps that are already producing audio.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use screencapturekit::audio_taps::AppAudioTap;
+//! use screencapturekit::shareable_content::SCShareableContent;
+//!
+//! # fn example() -> Result<(), screencapturekit::error::SCError> {
+//! let content = SCShareableContent::get()?;
+//! let music = content
+//!     .applications()
+//!     .into_iter()
+//!     .find(|app| app.bundle_identifier() == "com.apple.Music");
+//! let Some(music) = music else { return Ok(()) };
+//!
+//! let tap = AppAudioTap::builder()
+//!     .with_application(&music)
+//!     .start(|buffer| {
+//!         println!("{} frames at {:?}", buffer.frame_count(), buffer.presentation_time());
+//!     })?;
+//! std::thread::sleep(std::time::Duration::from_secs(5));
+//! tap.stop();
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+
+use crate::audio_output_tap::{
+    tap_callback, AudioOutputBuffer, TapCallback, UNSUPPORTED_OPERATION,
+};
+use crate::error::SCError;
+use crate::shareable_content::SCRunningApplication;
+
+/// `kAudioHardwareBadObjectError` (`'!obj'`)
+const BAD_OBJECT: i32 = 0x216f_626a;
+
+/// Builder for [`AppAudioTap`]
+///
+/// Add at least one process. Tapped apps keep playing on the speakers
+/// unless [`with_mutes_processes`](Self::with_mutes_processes) is set.
+#[derive(Debug, Clone, Default)]
+pub struct AppAudioTapBuilder {
+    process_ids: Vec<i32>,
+    mutes_processes: bool,
+}
+
+impl AppAudioTapBuilder {
+    /// Tap `application`'s process
+    #[must_use]
+    pub fn with_application(self, application: &SCRunningApplication) -> Self {
+        self.with_process_id(application.process_id())
+    }
+
+    /// Tap the process with this pid
+    #[must_use]
+    pub fn with_process_id(mut self, process_id: i32) -> Self {
+        if !self.process_ids.contains(&process_id) {
+            self.process_ids.push(process_id);
+        }
+        self
+    }
+
+    /// Silence the tapped processes on their output devices while the tap
+    /// runs, e.g. to record a call without it playing in the room
+    #[must_use]
+    pub const fn with_mutes_processes(mut self, mutes: bool) -> Self {
+        self.mutes_processes = mutes;
+        self
+    }
+
+    /// Pids of the processes to tap
+    #[must_use]
+    pub fn process_ids(&self) -> &[i32] {
+        &self.process_ids
+    }
+
+    /// Start tapping, calling `on_buffer` from a Core Audio thread with a
+    /// stereo mixdown of the processes
+    ///
+    /// Keep `on_buffer` short; it runs on the audio I/O path.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidConfiguration`] if no process was added or
+    /// one of them has not done any audio I/O yet,
+    /// [`SCError::FeatureNotAvailable`] before macOS 14.4, or
+    /// [`SCError::OSError`] with Core Audio's status if the tap can't be
+    /// created, e.g. because audio capture permission was denied.
+    pub fn start<F>(self, on_buffer: F) -> Result<AppAudioTap, SCError>
+    where
+        F: FnMut(&AudioOutputBuffer<'_>) + Send + 'static,
+    {
+        if self.process_ids.is_empty() {
+            return Err(SCError::invalid_config("an app audio tap needs a process"));
+        }
+        let count = isize::try_from(self.process_ids.len())
+            .map_err(|_| SCError::invalid_config("too many processes to tap"))?;
+        let context: *mut TapCallback = Box::into_raw(Box::new(Box::new(on_buffer)));
+        let mut status = 0;
+        let tap = unsafe {
+            crate::ffi::sc_audio_app_tap_create(
+                self.process_ids.as_ptr(),
+                count,
+                self.mutes_processes,
+                tap_callback,
+                context.cast(),
+                &mut status,
+            )
+        };
+        if tap.is_null() {
+            // SAFETY: the tap was never started, so Swift holds no reference.
+            drop(unsafe { Box::from_raw(context) });
+            return Err(match status {
+                UNSUPPORTED_OPERATION => SCError::feature_not_available("App audio taps", "14.4"),
+                BAD_OBJECT => SCError::invalid_config(format!(
+                    "no audio from one of processes {:?} yet",
+                    self.process_ids
+                )),
+                status => SCError::os_error(status, "failed to create app audio tap"),
+            });
+        }
+        Ok(AppAudioTap {
+            tap,
+            context,
+            process_ids: self.process_ids,
+        })
+    }
+}
+
+/// A running tap on the audio of one or more applications
+///
+/// Stops when dropped; no callback runs after [`stop`](Self::stop) or `drop`
+/// returns.
+pub struct AppAudioTap {
+    tap: *mut std::ffi::c_void,
+    context: *mut TapCallback,
+    process_ids: Vec<i32>,
+}
+
+// SAFETY: as for `AudioOutputTap`, which shares the Swift implementation:
+// the handle is only used through Swift, and the callback is `Send`.
+unsafe impl Send for AppAudioTap {}
+unsafe impl Sync for AppAudioTap {}
+
+impl AppAudioTap {
+    /// Configure a new tap
+    #[must_use]
+    pub fn builder() -> AppAudioTapBuilder {
+        AppAudioTapBuilder::default()
+    }
+
+    /// Pids of the tapped processes
+    #[must_use]
+    pub fn process_ids(&self) -> &[i32] {
+        &self.process_ids
+    }
+
+    /// Sample rate of the delivered audio, in Hz
+    #[must_use]
+    pub fn sample_rate(&self) -> f64 {
+        unsafe { crate::ffi::sc_audio_output_tap_sample_rate(self.tap) }
+    }
+
+    /// Channel count of the delivered audio (2 for the stereo mixdown)
+    #[must_use]
+    pub fn channel_count(&self) -> u32 {
+        unsafe { crate::ffi::sc_audio_output_tap_channel_count(self.tap) }
+    }
+
+    /// Stop tapping and destroy the tap's aggregate device
+    pub fn stop(self) {
+        drop(self);
+    }
+}
+
+impl Drop for AppAudioTap {
+    fn drop(&mut self) {
+        unsafe {
+            crate::ffi::sc_audio_output_tap_release(self.tap);
+            drop(Box::from_raw(self.context));
+        }
+    }
+}
+
+impl fmt::Debug for AppAudioTap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AppAudioTap")
+            .field("process_ids", &self.process_ids)
+            .field("sample_rate", &self.sample_rate())
+            .field("channel_count", &self.channel_count())
+            .finish_non_exhaustive()
+    }
+}
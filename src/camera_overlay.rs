@@ -0,0 +1,389 @@
+//! Webcam picture-in-picture over captured frames
+//!
+//! [`SCCameraOverlay`] runs an `AVCaptureSession` on a camera and draws its
+//! latest frame into a corner of each screen frame. Wrap any output handler
+//! in a [`CameraOverlayOutput`] and it receives composited frames instead of
+//! the raw capture, so a [`SCMuxer`](crate::muxer::SCMuxer), a replay buffer
+//! or your own encoder records the facecam with no further changes. Audio
+//! passes through untouched.
+//!
+//! Composited frames keep the capture's timing and `SCStreamFrameInfo`
+//! attachments, pixel format and size. Core Image renders them, so BGRA and
+//! the 8-bit 4:2:0 formats work; 10-bit packed formats such as `l10r` may
+//! not.
+//!
+//! Apps need an `NSCameraUsageDescription` entry in their `Info.plist`.
+//! [`SCCameraOverlay::new`] blocks while the user answers the camera
+//! permission prompt the first time.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use screencapturekit::camera_overlay::{
+//!     CameraOverlayLayout, CameraOverlayOutput, OverlayCorner, SCCameraOverlay,
+//! };
+//! use screencapturekit::prelude::*;
+//! use std::sync::Arc;
+//!
+//! # fn main() -> Result<(), SCError> {
+//! # let (filter, config): (SCContentFilter, SCStreamConfiguration) = todo!();
+//! let overlay = Arc::new(SCCameraOverlay::new(
+//!     CameraOverlayLayout::default()
+//!         .with_corner(OverlayCorner::BottomLeft)
+//!         .with_scale(0.2),
+//! )?);
+//!
+//! let mut stream = SCStream::new(&filter, &config);
+//! stream.add_output_handler(
+//!     CameraOverlayOutput::new(Arc::clone(&overlay), |sample: CMSampleBuffer, _| {
+//!         // `sample` already shows the camera in the bottom-left corner.
+//!     }),
+//!     SCStreamOutputType::Screen,
+//! );
+//! stream.start_capture()?;
+//!
+//! // Move the camera out of the way while capturing.
+//! overlay.set_layout(&overlay.layout().with_corner(OverlayCorner::TopRight))?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::ffi::{c_void, CString};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use crate::cm::CMSampleBuffer;
+use crate::error::SCError;
+use crate::stream::output_trait::SCStreamOutputTrait;
+use crate::stream::output_type::SCStreamOutputType;
+use crate::utils::ffi_string::{ffi_string_from_buffer, SMALL_BUFFER_SIZE};
+
+/// A camera that can feed an [`SCCameraOverlay`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CameraDevice {
+    /// `AVCaptureDevice` unique ID, for [`SCCameraOverlay::for_device`]
+    pub id: String,
+    /// Human-readable device name
+    pub name: String,
+}
+
+impl CameraDevice {
+    /// List the built-in, external and Continuity cameras.
+    ///
+    /// **Not cached**; each call runs an `AVCaptureDevice` discovery session.
+    pub fn list() -> Vec<Self> {
+        let count = unsafe { crate::ffi::sc_camera_get_device_count() };
+        (0..count)
+            .filter_map(|index| {
+                let id = unsafe {
+                    ffi_string_from_buffer(SMALL_BUFFER_SIZE, |buf, len| {
+                        crate::ffi::sc_camera_get_device_id(index, buf, len)
+                    })
+                }?;
+                let name = unsafe {
+                    ffi_string_from_buffer(SMALL_BUFFER_SIZE, |buf, len| {
+                        crate::ffi::sc_camera_get_device_name(index, buf, len)
+                    })
+                }?;
+                Some(Self { id, name })
+            })
+            .collect()
+    }
+}
+
+/// Corner of the frame the camera is drawn in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum OverlayCorner {
+    /// Top-left corner
+    TopLeft,
+    /// Top-right corner
+    TopRight,
+    /// Bottom-left corner
+    BottomLeft,
+    /// Bottom-right corner
+    #[default]
+    BottomRight,
+}
+
+impl OverlayCorner {
+    const fn to_raw(self) -> i32 {
+        match self {
+            Self::TopLeft => 0,
+            Self::TopRight => 1,
+            Self::BottomLeft => 2,
+            Self::BottomRight => 3,
+        }
+    }
+}
+
+/// Where and how large the camera appears in composited frames.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CameraOverlayLayout {
+    corner: OverlayCorner,
+    scale: f64,
+    margin: f64,
+    mirrored: bool,
+}
+
+impl Default for CameraOverlayLayout {
+    /// Bottom-right, a quarter of the frame width, 24 px from the edges,
+    /// mirrored like a video-call preview.
+    fn default() -> Self {
+        Self {
+            corner: OverlayCorner::BottomRight,
+            scale: 0.25,
+            margin: 24.0,
+            mirrored: true,
+        }
+    }
+}
+
+impl CameraOverlayLayout {
+    /// Corner the camera is drawn in.
+    #[must_use]
+    pub const fn with_corner(mut self, corner: OverlayCorner) -> Self {
+        self.corner = corner;
+        self
+    }
+
+    /// Camera width as a fraction of the frame width, in `(0, 1]`. The
+    /// height follows the camera's aspect ratio.
+    #[must_use]
+    pub const fn with_scale(mut self, scale: f64) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Distance from the frame edges in pixels of the captured frame.
+    #[must_use]
+    pub const fn with_margin(mut self, margin: f64) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    /// Flip the camera horizontally.
+    #[must_use]
+    pub const fn with_mirrored(mut self, mirrored: bool) -> Self {
+        self.mirrored = mirrored;
+        self
+    }
+
+    /// Corner the camera is drawn in.
+    #[must_use]
+    pub const fn corner(&self) -> OverlayCorner {
+        self.corner
+    }
+
+    /// Camera width as a fraction of the frame width.
+    #[must_use]
+    pub const fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// Distance from the frame edges in pixels.
+    #[must_use]
+    pub const fn margin(&self) -> f64 {
+        self.margin
+    }
+
+    /// Whether the camera is flipped horizontally.
+    #[must_use]
+    pub const fn mirrored(&self) -> bool {
+        self.mirrored
+    }
+
+    /// Check the scale and margin.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidConfiguration`] if the scale is outside
+    /// `(0, 1]` or the margin is negative or not finite.
+    pub fn validate(&self) -> Result<(), SCError> {
+        if !(self.scale > 0.0 && self.scale <= 1.0) {
+            return Err(SCError::invalid_config(format!(
+                "camera overlay scale must be in (0, 1], got {}",
+                self.scale
+            )));
+        }
+        if !(self.margin.is_finite() && self.margin >= 0.0) {
+            return Err(SCError::invalid_config(format!(
+                "camera overlay margin must be a non-negative number, got {}",
+                self.margin
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// A running camera whose frames are composited over screen captures.
+///
+/// Thread-safe; share it behind an [`Arc`] between the stream handler and
+/// whatever adjusts the layout. The camera stops when it is dropped. See the
+/// [module documentation](self).
+pub struct SCCameraOverlay {
+    ptr: *const c_void,
+    layout: Mutex<CameraOverlayLayout>,
+}
+
+// SAFETY: the Swift side guards the latest camera frame, the layout and the
+// render state with one lock.
+unsafe impl Send for SCCameraOverlay {}
+unsafe impl Sync for SCCameraOverlay {}
+
+impl std::fmt::Debug for SCCameraOverlay {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SCCameraOverlay")
+            .field("layout", &self.layout())
+            .finish_non_exhaustive()
+    }
+}
+
+impl SCCameraOverlay {
+    /// Start the default camera.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidConfiguration`] for an invalid layout or
+    /// when there is no camera, [`SCError::PermissionDenied`] if camera
+    /// access is denied, or [`SCError::OSError`] if the camera can't be
+    /// opened.
+    pub fn new(layout: CameraOverlayLayout) -> Result<Self, SCError> {
+        Self::start(None, layout)
+    }
+
+    /// Start the camera with [`CameraDevice::id`] `device_id`.
+    ///
+    /// # Errors
+    ///
+    /// See [`new`](Self::new); an unknown ID is reported as no camera.
+    pub fn for_device(device_id: &str, layout: CameraOverlayLayout) -> Result<Self, SCError> {
+        let id = CString::new(device_id)
+            .map_err(|_| SCError::invalid_config("camera device ID contains a NUL byte"))?;
+        Self::start(Some(&id), layout)
+    }
+
+    fn start(device_id: Option<&CString>, layout: CameraOverlayLayout) -> Result<Self, SCError> {
+        layout.validate()?;
+        let mut status = 0;
+        let ptr = unsafe {
+            crate::ffi::sc_camera_overlay_create(
+                device_id.map_or(std::ptr::null(), |id| id.as_ptr()),
+                layout.corner.to_raw(),
+                layout.scale,
+                layout.margin,
+                layout.mirrored,
+                &mut status,
+            )
+        };
+        if ptr.is_null() {
+            return Err(match status {
+                -1 => SCError::invalid_config("no camera available"),
+                -2 => SCError::permission_denied("camera access was denied"),
+                -3 => SCError::invalid_config("the capture session rejected the camera"),
+                code => SCError::os_error(code, "failed to open camera"),
+            });
+        }
+        Ok(Self {
+            ptr,
+            layout: Mutex::new(layout),
+        })
+    }
+
+    /// The current layout.
+    pub fn layout(&self) -> CameraOverlayLayout {
+        *self.layout.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Change the layout. Applies from the next composited frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidConfiguration`] for an invalid layout.
+    pub fn set_layout(&self, layout: &CameraOverlayLayout) -> Result<(), SCError> {
+        layout.validate()?;
+        // Hold the lock across the call so concurrent changes land in order.
+        let mut current = self.layout.lock().unwrap_or_else(PoisonError::into_inner);
+        unsafe {
+            crate::ffi::sc_camera_overlay_set_layout(
+                self.ptr,
+                layout.corner.to_raw(),
+                layout.scale,
+                layout.margin,
+                layout.mirrored,
+            );
+        }
+        *current = *layout;
+        drop(current);
+        Ok(())
+    }
+
+    /// Whether the camera has delivered a frame yet. Until it has, frames
+    /// pass through [`composite`](Self::composite) unchanged.
+    pub fn has_camera_frame(&self) -> bool {
+        unsafe { crate::ffi::sc_camera_overlay_has_frame(self.ptr) }
+    }
+
+    /// Draw the latest camera frame over `frame`.
+    ///
+    /// Returns `None` when there is nothing to draw on (audio, idle frames
+    /// without an image) or the camera has not produced a frame yet; use
+    /// the original sample in that case.
+    pub fn composite(&self, frame: &CMSampleBuffer) -> Option<CMSampleBuffer> {
+        let ptr = unsafe { crate::ffi::sc_camera_overlay_composite(self.ptr, frame.as_ptr()) };
+        // SAFETY: a non-NULL result is a +1 CMSampleBuffer we now own.
+        (!ptr.is_null()).then(|| unsafe { CMSampleBuffer::from_ptr(ptr.cast_mut()) })
+    }
+}
+
+impl Drop for SCCameraOverlay {
+    fn drop(&mut self) {
+        unsafe { crate::ffi::sc_camera_overlay_release(self.ptr) }
+    }
+}
+
+/// Output handler that composites the camera into screen frames before
+/// passing them to `inner`.
+///
+/// Samples of other types, and frames that can't be composited, reach
+/// `inner` unchanged.
+pub struct CameraOverlayOutput<H> {
+    overlay: Arc<SCCameraOverlay>,
+    inner: H,
+}
+
+impl<H: SCStreamOutputTrait> CameraOverlayOutput<H> {
+    /// Wrap `inner` so it receives frames with the camera drawn in.
+    pub const fn new(overlay: Arc<SCCameraOverlay>, inner: H) -> Self {
+        Self { overlay, inner }
+    }
+
+    /// The overlay frames are composited with.
+    pub const fn overlay(&self) -> &Arc<SCCameraOverlay> {
+        &self.overlay
+    }
+
+    /// The wrapped handler.
+    pub const fn inner(&self) -> &H {
+        &self.inner
+    }
+}
+
+impl<H> std::fmt::Debug for CameraOverlayOutput<H> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CameraOverlayOutput")
+            .field("overlay", &self.overlay)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<H: SCStreamOutputTrait> SCStreamOutputTrait for CameraOverlayOutput<H> {
+    fn did_output_sample_buffer(&self, sample_buffer: CMSampleBuffer, of_type: SCStreamOutputType) {
+        let sample_buffer = if of_type == SCStreamOutputType::Screen {
+            self.overlay
+                .composite(&sample_buffer)
+                .unwrap_or(sample_buffer)
+        } else {
+            sample_buffer
+        };
+        self.inner.did_output_sample_buffer(sample_buffer, of_type);
+    }
+}
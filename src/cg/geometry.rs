@@ -0,0 +1,366 @@
+//! Rectangle math and conversions between desktop coordinate spaces
+//!
+//! `ScreenCaptureKit` and Quartz describe the desktop in one global space
+//! measured in points: the main display's top-left corner is the origin
+//! and y grows downwards. Displays left of or above the main display have
+//! negative origins. `AppKit` uses the same units with the origin at the
+//! main display's *bottom*-left and y growing upwards, and captured frames
+//! are measured in pixels relative to the captured display.
+//!
+//! [`DisplaySpace`] converts between those spaces for one display, and
+//! [`CGRectExt`] / [`CGPointExt`] add the rectangle math the conversions
+//! need.
+//!
+//! ```no_run
+//! use screencapturekit::cg::DisplaySpace;
+//! use screencapturekit::prelude::*;
+//!
+//! # fn example() -> Result<(), SCError> {
+//! let content = SCShareableContent::get()?;
+//! let window = &content.windows()[0];
+//! let Some(display) = DisplaySpace::containing(&content.displays(), window.frame()) else {
+//!     return Ok(());
+//! };
+//! // Where the window lands in a frame captured from that display.
+//! let in_frame = display.global_to_pixels(window.frame());
+//! println!("{in_frame:?}");
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::shareable_content::SCDisplay;
+use crate::stream::display_follower::DisplayMode;
+
+use super::{CGPoint, CGRect, CGSize};
+
+/// Rectangle math for [`CGRect`]
+///
+/// Rectangles with negative sizes are standardized first, as Core
+/// Graphics does.
+pub trait CGRectExt {
+    /// The same rectangle with a non-negative width and height
+    #[must_use]
+    fn standardized(&self) -> CGRect;
+
+    /// Whether the rectangle covers no area
+    #[must_use]
+    fn has_no_area(&self) -> bool;
+
+    /// The overlap of two rectangles, or `None` if they only touch or
+    /// don't meet
+    #[must_use]
+    fn intersection(&self, other: &CGRect) -> Option<CGRect>;
+
+    /// Whether the rectangles overlap by more than an edge
+    #[must_use]
+    fn intersects(&self, other: &CGRect) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// The smallest rectangle containing both, ignoring either one if it
+    /// covers no area
+    #[must_use]
+    fn union(&self, other: &CGRect) -> CGRect;
+
+    /// Whether `point` lies inside, including the top and left edges but
+    /// not the bottom and right ones
+    #[must_use]
+    fn contains_point(&self, point: CGPoint) -> bool;
+
+    /// Whether `other` lies entirely inside
+    #[must_use]
+    fn contains_rect(&self, other: &CGRect) -> bool;
+
+    /// Move by `dx`, `dy`
+    #[must_use]
+    fn offset_by(&self, dx: f64, dy: f64) -> CGRect;
+
+    /// Multiply origin and size by `factor`, e.g. to go from points to
+    /// pixels
+    #[must_use]
+    fn scaled(&self, factor: f64) -> CGRect;
+
+    /// The smallest rectangle with whole-number edges that contains this
+    /// one
+    #[must_use]
+    fn integral(&self) -> CGRect;
+
+    /// Mirror vertically inside a container `height` tall, switching
+    /// between top-left and bottom-left origins
+    #[must_use]
+    fn flipped(&self, height: f64) -> CGRect;
+}
+
+impl CGRectExt for CGRect {
+    fn standardized(&self) -> Self {
+        let (x, width) = if self.size.width < 0.0 {
+            (self.origin.x + self.size.width, -self.size.width)
+        } else {
+            (self.origin.x, self.size.width)
+        };
+        let (y, height) = if self.size.height < 0.0 {
+            (self.origin.y + self.size.height, -self.size.height)
+        } else {
+            (self.origin.y, self.size.height)
+        };
+        Self::new(x, y, width, height)
+    }
+
+    fn has_no_area(&self) -> bool {
+        // Also true for NaN sizes.
+        !(self.size.width.abs() > 0.0 && self.size.height.abs() > 0.0)
+    }
+
+    fn intersection(&self, other: &Self) -> Option<Self> {
+        let (a, b) = (self.standardized(), other.standardized());
+        let left = a.origin.x.max(b.origin.x);
+        let top = a.origin.y.max(b.origin.y);
+        let right = (a.origin.x + a.size.width).min(b.origin.x + b.size.width);
+        let bottom = (a.origin.y + a.size.height).min(b.origin.y + b.size.height);
+        (right > left && bottom > top).then(|| Self::new(left, top, right - left, bottom - top))
+    }
+
+    fn union(&self, other: &Self) -> Self {
+        let (a, b) = (self.standardized(), other.standardized());
+        if b.has_no_area() {
+            return a;
+        }
+        if a.has_no_area() {
+            return b;
+        }
+        let left = a.origin.x.min(b.origin.x);
+        let top = a.origin.y.min(b.origin.y);
+        let right = (a.origin.x + a.size.width).max(b.origin.x + b.size.width);
+        let bottom = (a.origin.y + a.size.height).max(b.origin.y + b.size.height);
+        Self::new(left, top, right - left, bottom - top)
+    }
+
+    fn contains_point(&self, point: CGPoint) -> bool {
+        let rect = self.standardized();
+        point.x >= rect.origin.x
+            && point.y >= rect.origin.y
+            && point.x < rect.origin.x + rect.size.width
+            && point.y < rect.origin.y + rect.size.height
+    }
+
+    fn contains_rect(&self, other: &Self) -> bool {
+        let (a, b) = (self.standardized(), other.standardized());
+        b.origin.x >= a.origin.x
+            && b.origin.y >= a.origin.y
+            && b.origin.x + b.size.width <= a.origin.x + a.size.width
+            && b.origin.y + b.size.height <= a.origin.y + a.size.height
+    }
+
+    fn offset_by(&self, dx: f64, dy: f64) -> Self {
+        Self::new(
+            self.origin.x + dx,
+            self.origin.y + dy,
+            self.size.width,
+            self.size.height,
+        )
+    }
+
+    fn scaled(&self, factor: f64) -> Self {
+        Self::new(
+            self.origin.x * factor,
+            self.origin.y * factor,
+            self.size.width * factor,
+            self.size.height * factor,
+        )
+    }
+
+    fn integral(&self) -> Self {
+        let rect = self.standardized();
+        let left = rect.origin.x.floor();
+        let top = rect.origin.y.floor();
+        let right = (rect.origin.x + rect.size.width).ceil();
+        let bottom = (rect.origin.y + rect.size.height).ceil();
+        Self::new(left, top, right - left, bottom - top)
+    }
+
+    fn flipped(&self, height: f64) -> Self {
+        let rect = self.standardized();
+        Self::new(
+            rect.origin.x,
+            height - rect.origin.y - rect.size.height,
+            rect.size.width,
+            rect.size.height,
+        )
+    }
+}
+
+/// Point math for [`CGPoint`]
+pub trait CGPointExt {
+    /// Move by `dx`, `dy`
+    #[must_use]
+    fn offset_by(&self, dx: f64, dy: f64) -> CGPoint;
+
+    /// Multiply both coordinates by `factor`
+    #[must_use]
+    fn scaled(&self, factor: f64) -> CGPoint;
+
+    /// Mirror vertically inside a container `height` tall
+    #[must_use]
+    fn flipped(&self, height: f64) -> CGPoint;
+}
+
+impl CGPointExt for CGPoint {
+    fn offset_by(&self, dx: f64, dy: f64) -> Self {
+        Self::new(self.x + dx, self.y + dy)
+    }
+
+    fn scaled(&self, factor: f64) -> Self {
+        Self::new(self.x * factor, self.y * factor)
+    }
+
+    fn flipped(&self, height: f64) -> Self {
+        Self::new(self.x, height - self.y)
+    }
+}
+
+/// Convert a rectangle from `AppKit`'s global space (bottom-left origin)
+/// to Quartz's (top-left origin)
+///
+/// `main_display_height` is the height in points of the main display,
+/// the one whose Quartz frame starts at (0, 0). The conversion is its own
+/// inverse.
+#[must_use]
+pub fn cocoa_to_quartz(rect: CGRect, main_display_height: f64) -> CGRect {
+    rect.flipped(main_display_height)
+}
+
+/// Convert a rectangle from Quartz's global space (top-left origin) to
+/// `AppKit`'s (bottom-left origin)
+#[must_use]
+pub fn quartz_to_cocoa(rect: CGRect, main_display_height: f64) -> CGRect {
+    rect.flipped(main_display_height)
+}
+
+/// One display's place in the global desktop space
+///
+/// Converts between global coordinates (points, Quartz orientation),
+/// coordinates local to the display (points, origin at its top-left
+/// corner) and pixels in frames captured from the display at its native
+/// resolution.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DisplaySpace {
+    frame: CGRect,
+    scale: f64,
+}
+
+impl DisplaySpace {
+    /// A display occupying `frame` in global points, with `scale` pixels
+    /// per point
+    ///
+    /// Scales that aren't positive are treated as 1.
+    #[must_use]
+    pub fn new(frame: CGRect, scale: f64) -> Self {
+        let scale = if scale > 0.0 { scale } else { 1.0 };
+        Self {
+            frame: frame.standardized(),
+            scale,
+        }
+    }
+
+    /// `display`'s frame and current backing scale
+    ///
+    /// Falls back to a scale of 1 if the display mode can't be read.
+    #[must_use]
+    pub fn for_display(display: &SCDisplay) -> Self {
+        let scale =
+            DisplayMode::current(display.display_id()).map_or(1.0, |mode| mode.scale_factor());
+        Self::new(display.frame(), scale)
+    }
+
+    /// The display among `displays` that overlaps most of `rect`
+    #[must_use]
+    pub fn containing(displays: &[SCDisplay], rect: CGRect) -> Option<Self> {
+        displays
+            .iter()
+            .filter_map(|display| {
+                let area = display
+                    .frame()
+                    .intersection(&rect)
+                    .map(|overlap| overlap.size.width * overlap.size.height)?;
+                Some((area, display))
+            })
+            .max_by(|a, b| a.0.total_cmp(&b.0))
+            .map(|(_, display)| Self::for_display(display))
+    }
+
+    /// The display's frame in global points
+    #[must_use]
+    pub const fn frame(&self) -> CGRect {
+        self.frame
+    }
+
+    /// Pixels per point
+    #[must_use]
+    pub const fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// The display's size in pixels
+    #[must_use]
+    pub fn pixel_size(&self) -> CGSize {
+        CGSize::new(
+            self.frame.size.width * self.scale,
+            self.frame.size.height * self.scale,
+        )
+    }
+
+    /// Whether `point`, in global points, is on this display
+    #[must_use]
+    pub fn contains(&self, point: CGPoint) -> bool {
+        self.frame.contains_point(point)
+    }
+
+    /// Global points to points local to the display
+    #[must_use]
+    pub fn global_to_local(&self, rect: CGRect) -> CGRect {
+        rect.offset_by(-self.frame.origin.x, -self.frame.origin.y)
+    }
+
+    /// Points local to the display to global points
+    #[must_use]
+    pub fn local_to_global(&self, rect: CGRect) -> CGRect {
+        rect.offset_by(self.frame.origin.x, self.frame.origin.y)
+    }
+
+    /// Global points to pixels in a frame captured from the display
+    #[must_use]
+    pub fn global_to_pixels(&self, rect: CGRect) -> CGRect {
+        self.global_to_local(rect).scaled(self.scale)
+    }
+
+    /// Pixels in a frame captured from the display to global points
+    #[must_use]
+    pub fn pixels_to_global(&self, rect: CGRect) -> CGRect {
+        self.local_to_global(rect.scaled(self.scale.recip()))
+    }
+
+    /// A global point to a pixel position in a frame captured from the
+    /// display
+    #[must_use]
+    pub fn global_point_to_pixels(&self, point: CGPoint) -> CGPoint {
+        point
+            .offset_by(-self.frame.origin.x, -self.frame.origin.y)
+            .scaled(self.scale)
+    }
+
+    /// A pixel position in a frame captured from the display to a global
+    /// point
+    #[must_use]
+    pub fn pixel_point_to_global(&self, point: CGPoint) -> CGPoint {
+        point
+            .scaled(self.scale.recip())
+            .offset_by(self.frame.origin.x, self.frame.origin.y)
+    }
+
+    /// Pixels with a bottom-left origin, as OpenGL and Core Image use, to
+    /// the top-left origin captured frames use (and back)
+    #[must_use]
+    pub fn flip_pixels(&self, rect: CGRect) -> CGRect {
+        rect.flipped(self.frame.size.height * self.scale)
+    }
+}
@@ -5,5 +5,11 @@
 //! This module used to vendor its own copies; the canonical implementations
 //! now live in `apple_cf::cg` and this re-export preserves the
 //! `screencapturekit::cg::CGRect` (etc.) public path for backward compatibility.
+//!
+//! [`geometry`] adds rectangle math and conversions between the global,
+//! display-local and pixel coordinate spaces.
+
+pub mod geometry;
 
 pub use apple_cf::cg::{CGPoint, CGRect, CGSize};
+pub use geometry::{CGPointExt, CGRectExt, DisplaySpace};
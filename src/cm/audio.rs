@@ -7,6 +7,30 @@
 //! - [`AudioBuffer`] - Single audio buffer containing sample data
 //! - [`AudioBufferList`] - Collection of audio buffers (typically one per channel)
 //! - [`AudioBufferRef`] - Reference to an audio buffer with convenience methods
+//! - [`AudioBuffersView`] - Borrowed buffers of one sample, read without heap
+//!   allocation
+//!
+//! ## Real-time audio
+//!
+//! [`CMSampleBufferExt::audio_buffer_list`](super::CMSampleBufferExt::audio_buffer_list)
+//! allocates the buffer descriptors on every call. Handlers that must not
+//! allocate, such as ones feeding an audio render thread, use
+//! [`with_audio_buffers`](super::CMSampleBufferExt::with_audio_buffers)
+//! instead: the descriptors are read into a fixed array on the stack and the
+//! sample data is borrowed in place.
+//!
+//! ```rust,no_run
+//! use screencapturekit::cm::{CMSampleBuffer, CMSampleBufferExt};
+//!
+//! fn peak(sample: &CMSampleBuffer) -> Option<f32> {
+//!     sample.with_audio_buffers(|buffers| {
+//!         (0..buffers.len())
+//!             .filter_map(|index| buffers.samples_f32(index))
+//!             .flatten()
+//!             .fold(0.0_f32, |peak, sample| peak.max(sample.abs()))
+//!     })
+//! }
+//! ```
 
 use super::ffi;
 use std::fmt;
@@ -53,6 +77,13 @@ impl fmt::Display for AudioBuffer {
 }
 
 impl AudioBuffer {
+    /// Placeholder for fixed-size buffer arrays
+    pub(crate) const EMPTY: Self = Self {
+        number_channels: 0,
+        data_bytes_size: 0,
+        data_ptr: std::ptr::null_mut(),
+    };
+
     /// Get the raw audio data as a byte slice
     pub fn data(&self) -> &[u8] {
         if self.data_ptr.is_null() || self.data_bytes_size == 0 {
@@ -272,3 +303,80 @@ impl<'a> Iterator for AudioBufferListIter<'a> {
         }
     }
 }
+
+/// Most buffers one sample can have for
+/// [`with_audio_buffers`](super::CMSampleBufferExt::with_audio_buffers)
+///
+/// `ScreenCaptureKit` delivers one buffer per channel, so this covers
+/// anything up to 16-channel planar audio.
+pub const MAX_AUDIO_BUFFERS: usize = 16;
+
+/// Audio buffers of one sample, borrowed without heap allocation
+///
+/// Passed to the closure of
+/// [`with_audio_buffers`](super::CMSampleBufferExt::with_audio_buffers).
+/// The buffer descriptors live on that method's stack and the data stays in
+/// the sample's block buffer, which is kept alive until the closure returns.
+pub struct AudioBuffersView<'a> {
+    pub(crate) buffers: &'a [AudioBuffer],
+}
+
+impl<'a> AudioBuffersView<'a> {
+    /// Number of buffers
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.buffers.len()
+    }
+
+    /// Whether there are no buffers
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.buffers.is_empty()
+    }
+
+    /// Get a buffer by index
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&'a AudioBuffer> {
+        self.buffers.get(index)
+    }
+
+    /// All buffers as a slice
+    #[must_use]
+    pub const fn as_slice(&self) -> &'a [AudioBuffer] {
+        self.buffers
+    }
+
+    /// Iterate over the buffers
+    pub fn iter(&self) -> std::slice::Iter<'a, AudioBuffer> {
+        self.buffers.iter()
+    }
+
+    /// Data of buffer `index` as 32-bit float samples
+    ///
+    /// `ScreenCaptureKit` delivers non-interleaved `Float32` PCM, so each
+    /// buffer is one channel. Returns `None` if `index` is out of range or
+    /// the data isn't a whole number of aligned `f32`s.
+    #[must_use]
+    pub fn samples_f32(&self, index: usize) -> Option<&'a [f32]> {
+        let data = self.get(index)?.data();
+        // SAFETY: every bit pattern is a valid f32, and the slice is only
+        // returned when align_to used all of `data`.
+        let (prefix, samples, suffix) = unsafe { data.align_to::<f32>() };
+        (prefix.is_empty() && suffix.is_empty()).then_some(samples)
+    }
+}
+
+impl<'a> IntoIterator for &AudioBuffersView<'a> {
+    type Item = &'a AudioBuffer;
+    type IntoIter = std::slice::Iter<'a, AudioBuffer>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl fmt::Debug for AudioBuffersView<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.buffers).finish()
+    }
+}
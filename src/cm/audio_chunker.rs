@@ -0,0 +1,617 @@
+//! Fixed-size audio chunking
+//!
+//! `ScreenCaptureKit` delivers audio in buffers of whatever size the system
+//! mixer happens to produce, while encoders want exact frame counts — 1024
+//! for AAC, 960 for Opus at 48 kHz. [`AudioChunker`] accumulates captured
+//! samples and hands back [`AudioChunk`]s of exactly the requested size, each
+//! stamped with the presentation time of its first frame.
+//!
+//! Audio is expected as 32-bit float, which is what `ScreenCaptureKit`
+//! produces. Both planar (one buffer per channel, the `ScreenCaptureKit`
+//! default) and interleaved input are accepted.
+//!
+//! ## Timing
+//!
+//! Chunk timestamps are derived from the sample count rather than copied from
+//! the input buffers, so they never drift. When an input timestamp shows a
+//! gap (for example after dropped audio), the gap is filled with silence so
+//! later chunks stay aligned; gaps longer than a second, and timestamps that
+//! jump backwards, re-anchor the timeline instead.
+//!
+//! ## Gaps
+//!
+//! Code that writes audio as it arrives, rather than through a chunker, can
+//! wrap each buffer with [`AudioChunk::from_sample_buffer`] to keep its own
+//! timestamp and duration, and feed it to an [`AudioGapDetector`]. The
+//! detector reports every [`AudioGap`] between consecutive buffers so the
+//! missing span can be written as [silence](AudioGap::silence) instead of
+//! letting audio drift ahead of video.
+//!
+//! ```no_run
+//! use screencapturekit::cm::{AudioChunk, AudioGapDetector, CMSampleBuffer};
+//!
+//! let mut gaps = AudioGapDetector::new(48_000).unwrap();
+//! let mut on_audio = |sample: &CMSampleBuffer| {
+//!     let chunk = AudioChunk::from_sample_buffer(sample, 48_000).unwrap();
+//!     if let Some(gap) = gaps.observe(&chunk) {
+//!         let silence = gap.silence(chunk.channel_count(), chunk.layout());
+//!         // write(silence);
+//! #       let _ = silence;
+//!     }
+//!     // write(chunk);
+//! };
+//! # let _ = &mut on_audio;
+//! ```
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use screencapturekit::cm::{AudioChunker, CMSampleBuffer};
+//!
+//! // 1024-frame stereo chunks for AAC at 48 kHz
+//! let mut chunker = AudioChunker::new(1024, 2, 48_000).unwrap();
+//!
+//! let mut on_audio = |sample: &CMSampleBuffer| {
+//!     for chunk in chunker.push(sample).unwrap() {
+//!         // encode(chunk.pts(), chunk.samples());
+//!         println!("{} frames at {:?}", chunk.frame_count(), chunk.pts());
+//!     }
+//! };
+//! # let _ = &mut on_audio;
+//! ```
+
+use super::{CMSampleBuffer, CMSampleBufferExt, CMTime};
+use crate::error::SCError;
+
+/// Sample layout of an [`AudioChunk`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum AudioChunkLayout {
+    /// All frames of channel 0, then all of channel 1, and so on.
+    #[default]
+    Planar,
+    /// `L R L R …` — one frame after another.
+    Interleaved,
+}
+
+/// A fixed-size block of audio produced by [`AudioChunker`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct AudioChunk {
+    pts: CMTime,
+    sample_rate: u32,
+    frame_count: usize,
+    channel_count: usize,
+    layout: AudioChunkLayout,
+    samples: Vec<f32>,
+}
+
+impl AudioChunk {
+    /// Presentation time of the first frame, in a `1 / sample_rate` timescale.
+    #[must_use]
+    pub const fn pts(&self) -> CMTime {
+        self.pts
+    }
+
+    /// Duration of the chunk.
+    #[must_use]
+    #[allow(clippy::cast_possible_wrap, clippy::cast_possible_truncation)]
+    pub const fn duration(&self) -> CMTime {
+        CMTime::new(self.frame_count as i64, self.sample_rate as i32)
+    }
+
+    /// Number of frames (samples per channel).
+    #[must_use]
+    pub const fn frame_count(&self) -> usize {
+        self.frame_count
+    }
+
+    /// Number of channels.
+    #[must_use]
+    pub const fn channel_count(&self) -> usize {
+        self.channel_count
+    }
+
+    /// Layout of [`samples`](Self::samples).
+    #[must_use]
+    pub const fn layout(&self) -> AudioChunkLayout {
+        self.layout
+    }
+
+    /// All samples, `frame_count * channel_count` values in [`layout`](Self::layout) order.
+    #[must_use]
+    pub fn samples(&self) -> &[f32] {
+        &self.samples
+    }
+
+    /// The samples of one channel. Only available for planar chunks.
+    #[must_use]
+    pub fn channel(&self, index: usize) -> Option<&[f32]> {
+        if self.layout != AudioChunkLayout::Planar || index >= self.channel_count {
+            return None;
+        }
+        let start = index * self.frame_count;
+        Some(&self.samples[start..start + self.frame_count])
+    }
+
+    /// Take ownership of the samples.
+    #[must_use]
+    pub fn into_samples(self) -> Vec<f32> {
+        self.samples
+    }
+
+    /// Copy one captured audio buffer into a chunk of the same size, keeping
+    /// its presentation time (converted to a `1 / sample_rate` timescale)
+    /// and its planar or interleaved layout.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidDimension`] for a zero `sample_rate`, or
+    /// [`SCError::InvalidBuffer`] if the buffer has no audio, is not 32-bit
+    /// float at `sample_rate`, or has no valid timestamp.
+    #[allow(clippy::cast_possible_wrap, clippy::cast_possible_truncation)]
+    pub fn from_sample_buffer(sample: &CMSampleBuffer, sample_rate: u32) -> Result<Self, SCError> {
+        if sample_rate == 0 {
+            return Err(SCError::invalid_dimension("sample_rate", 0));
+        }
+        let list = sample
+            .audio_buffer_list()
+            .ok_or_else(|| SCError::InvalidBuffer("sample buffer has no audio".to_string()))?;
+        check_format(sample, sample_rate)?;
+        let start = frames_at(sample.presentation_timestamp(), sample_rate).ok_or_else(|| {
+            SCError::InvalidBuffer("sample buffer has no valid timestamp".to_string())
+        })?;
+
+        let (layout, channel_count, samples) = if list.iter().all(|b| b.number_channels == 1) {
+            let samples: Vec<f32> = list.iter().flat_map(|b| floats(b.data())).collect();
+            (AudioChunkLayout::Planar, list.num_buffers(), samples)
+        } else if let (1, Some(buffer)) = (list.num_buffers(), list.get(0)) {
+            (
+                AudioChunkLayout::Interleaved,
+                buffer.number_channels as usize,
+                floats(buffer.data()),
+            )
+        } else {
+            return Err(SCError::InvalidBuffer(format!(
+                "unsupported layout of {} audio buffer(s)",
+                list.num_buffers()
+            )));
+        };
+        let frame_count = samples.len() / channel_count.max(1);
+
+        Ok(Self {
+            pts: CMTime::new(start, sample_rate as i32),
+            sample_rate,
+            frame_count,
+            channel_count,
+            layout,
+            samples,
+        })
+    }
+}
+
+/// Audio missing between two buffers, reported by [`AudioGapDetector`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AudioGap {
+    start: i64,
+    missing_frames: u64,
+    sample_rate: u32,
+}
+
+impl AudioGap {
+    /// Where the missing audio should have started, in a `1 / sample_rate`
+    /// timescale.
+    #[must_use]
+    #[allow(clippy::cast_possible_wrap)]
+    pub const fn start(&self) -> CMTime {
+        CMTime::new(self.start, self.sample_rate as i32)
+    }
+
+    /// Length of the gap.
+    #[must_use]
+    #[allow(clippy::cast_possible_wrap)]
+    pub const fn duration(&self) -> CMTime {
+        CMTime::new(self.missing_frames as i64, self.sample_rate as i32)
+    }
+
+    /// Number of missing frames (samples per channel).
+    #[must_use]
+    pub const fn missing_frames(&self) -> u64 {
+        self.missing_frames
+    }
+
+    /// A silent chunk covering the gap, to write in place of the missing
+    /// audio.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn silence(&self, channel_count: usize, layout: AudioChunkLayout) -> AudioChunk {
+        let frame_count = self.missing_frames as usize;
+        AudioChunk {
+            pts: self.start(),
+            sample_rate: self.sample_rate,
+            frame_count,
+            channel_count,
+            layout,
+            samples: vec![0.0; frame_count * channel_count],
+        }
+    }
+}
+
+/// Detects missing audio from the timestamps of consecutive buffers.
+///
+/// Each observed buffer is expected to start where the previous one ended.
+/// A later start is reported as an [`AudioGap`] once it exceeds the
+/// tolerance (one frame by default, to absorb rounding). A start at or
+/// before the expected position, such as overlap or a clock reset, is not a
+/// gap; the detector continues from the new buffer.
+#[derive(Debug, Clone)]
+pub struct AudioGapDetector {
+    sample_rate: u32,
+    tolerance_frames: u64,
+    expected: Option<i64>,
+    gap_count: u64,
+    missing_frames: u64,
+}
+
+impl AudioGapDetector {
+    /// Create a detector for audio at `sample_rate` Hz.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidDimension`] if `sample_rate` is zero.
+    pub fn new(sample_rate: u32) -> Result<Self, SCError> {
+        if sample_rate == 0 {
+            return Err(SCError::invalid_dimension("sample_rate", 0));
+        }
+        Ok(Self {
+            sample_rate,
+            tolerance_frames: 1,
+            expected: None,
+            gap_count: 0,
+            missing_frames: 0,
+        })
+    }
+
+    /// Ignore gaps of up to `frames` frames.
+    #[must_use]
+    pub const fn with_tolerance(mut self, frames: u64) -> Self {
+        self.tolerance_frames = frames;
+        self
+    }
+
+    /// Check a chunk from [`AudioChunk::from_sample_buffer`] or an
+    /// [`AudioChunker`] against the previous one.
+    pub fn observe(&mut self, chunk: &AudioChunk) -> Option<AudioGap> {
+        self.observe_timing(chunk.pts(), chunk.frame_count())
+    }
+
+    /// Check a buffer of `frame_count` frames starting at `pts` against the
+    /// previous one. An invalid `pts` continues from the previous buffer.
+    #[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
+    pub fn observe_timing(&mut self, pts: CMTime, frame_count: usize) -> Option<AudioGap> {
+        let expected = self.expected;
+        let start = frames_at(pts, self.sample_rate)
+            .or(expected)
+            .unwrap_or_default();
+        self.expected = Some(start + frame_count as i64);
+
+        let previous_end = expected?;
+        let missing = start - previous_end;
+        if missing <= 0 || missing as u64 <= self.tolerance_frames {
+            return None;
+        }
+        self.gap_count += 1;
+        self.missing_frames += missing as u64;
+        Some(AudioGap {
+            start: previous_end,
+            missing_frames: missing as u64,
+            sample_rate: self.sample_rate,
+        })
+    }
+
+    /// Number of gaps reported so far.
+    #[must_use]
+    pub const fn gap_count(&self) -> u64 {
+        self.gap_count
+    }
+
+    /// Total frames missing across all reported gaps.
+    #[must_use]
+    pub const fn missing_frames(&self) -> u64 {
+        self.missing_frames
+    }
+
+    /// Forget the previous buffer and the totals.
+    pub fn reset(&mut self) {
+        self.expected = None;
+        self.gap_count = 0;
+        self.missing_frames = 0;
+    }
+}
+
+/// Re-chunks variable-size audio into fixed frame counts.
+///
+/// See the [module docs](self) for timing behaviour.
+#[derive(Debug)]
+pub struct AudioChunker {
+    frames_per_chunk: usize,
+    sample_rate: u32,
+    layout: AudioChunkLayout,
+    /// One queue per channel.
+    channels: Vec<Vec<f32>>,
+    /// Timeline position (in frames) of the first buffered frame.
+    start_frame: i64,
+    anchored: bool,
+}
+
+impl AudioChunker {
+    /// Create a chunker emitting `frames_per_chunk` frames of `channel_count`
+    /// channels at `sample_rate` Hz.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidDimension`] if any argument is zero.
+    pub fn new(
+        frames_per_chunk: usize,
+        channel_count: usize,
+        sample_rate: u32,
+    ) -> Result<Self, SCError> {
+        if frames_per_chunk == 0 {
+            return Err(SCError::invalid_dimension("frames_per_chunk", 0));
+        }
+        if channel_count == 0 {
+            return Err(SCError::invalid_dimension("channel_count", 0));
+        }
+        if sample_rate == 0 {
+            return Err(SCError::invalid_dimension("sample_rate", 0));
+        }
+        Ok(Self {
+            frames_per_chunk,
+            sample_rate,
+            layout: AudioChunkLayout::default(),
+            channels: vec![Vec::with_capacity(frames_per_chunk * 2); channel_count],
+            start_frame: 0,
+            anchored: false,
+        })
+    }
+
+    /// Emit chunks in `layout` (planar by default).
+    #[must_use]
+    pub const fn with_layout(mut self, layout: AudioChunkLayout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Frames per emitted chunk.
+    #[must_use]
+    pub const fn frames_per_chunk(&self) -> usize {
+        self.frames_per_chunk
+    }
+
+    /// Frames buffered but not yet emitted.
+    #[must_use]
+    pub fn buffered_frames(&self) -> usize {
+        self.channels[0].len()
+    }
+
+    /// Add a captured audio sample buffer and return every chunk that is now
+    /// complete.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidBuffer`] if the buffer has no audio, is not
+    /// 32-bit float at the chunker's sample rate, or its channel layout does
+    /// not match the chunker.
+    pub fn push(&mut self, sample: &CMSampleBuffer) -> Result<Vec<AudioChunk>, SCError> {
+        let list = sample
+            .audio_buffer_list()
+            .ok_or_else(|| SCError::InvalidBuffer("sample buffer has no audio".to_string()))?;
+        check_format(sample, self.sample_rate)?;
+        let pts = sample.presentation_timestamp();
+        let channel_count = self.channels.len();
+        crate::instrument::buffer_copied(
+            "audio_chunker",
+            list.iter().map(|b| b.data().len()).sum(),
+        );
+
+        if list.num_buffers() == channel_count && list.iter().all(|b| b.number_channels == 1) {
+            let planes: Vec<Vec<f32>> = list.iter().map(|b| floats(b.data())).collect();
+            let refs: Vec<&[f32]> = planes.iter().map(Vec::as_slice).collect();
+            self.push_planar(pts, &refs)
+        } else if list.num_buffers() == 1
+            && list.get(0).map(|b| b.number_channels as usize) == Some(channel_count)
+        {
+            let data = list.get(0).map(|b| floats(b.data())).unwrap_or_default();
+            self.push_interleaved(pts, &data)
+        } else {
+            Err(SCError::InvalidBuffer(format!(
+                "expected {channel_count} channel(s), got {} buffer(s)",
+                list.num_buffers()
+            )))
+        }
+    }
+
+    /// Add planar samples (one slice per channel) starting at `pts`.
+    ///
+    /// Pass [`CMTime::INVALID`] to continue from the previous push.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidBuffer`] if the number of slices does not
+    /// match the channel count or the slices differ in length.
+    pub fn push_planar(
+        &mut self,
+        pts: CMTime,
+        channels: &[&[f32]],
+    ) -> Result<Vec<AudioChunk>, SCError> {
+        if channels.len() != self.channels.len() {
+            return Err(SCError::InvalidBuffer(format!(
+                "expected {} channel(s), got {}",
+                self.channels.len(),
+                channels.len()
+            )));
+        }
+        let frames = channels.first().copied().unwrap_or_default().len();
+        if channels.iter().any(|c| c.len() != frames) {
+            return Err(SCError::InvalidBuffer(
+                "planar channels differ in length".to_string(),
+            ));
+        }
+
+        self.anchor(pts);
+        for (queue, input) in self.channels.iter_mut().zip(channels) {
+            queue.extend_from_slice(input);
+        }
+        Ok(self.drain_chunks())
+    }
+
+    /// Add interleaved samples starting at `pts`.
+    ///
+    /// Pass [`CMTime::INVALID`] to continue from the previous push.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidBuffer`] if `samples` is not a whole number
+    /// of frames.
+    pub fn push_interleaved(
+        &mut self,
+        pts: CMTime,
+        samples: &[f32],
+    ) -> Result<Vec<AudioChunk>, SCError> {
+        let channel_count = self.channels.len();
+        if samples.len() % channel_count != 0 {
+            return Err(SCError::InvalidBuffer(format!(
+                "{} samples is not a whole number of {channel_count}-channel frames",
+                samples.len()
+            )));
+        }
+
+        self.anchor(pts);
+        for frame in samples.chunks_exact(channel_count) {
+            for (queue, &sample) in self.channels.iter_mut().zip(frame) {
+                queue.push(sample);
+            }
+        }
+        Ok(self.drain_chunks())
+    }
+
+    /// Emit whatever is buffered as a final chunk, padded with silence to a
+    /// full `frames_per_chunk`. Returns `None` if nothing is buffered.
+    pub fn flush(&mut self) -> Option<AudioChunk> {
+        let buffered = self.buffered_frames();
+        if buffered == 0 {
+            return None;
+        }
+        for queue in &mut self.channels {
+            queue.resize(self.frames_per_chunk, 0.0);
+        }
+        self.drain_chunks().pop()
+    }
+
+    /// Drop buffered audio and forget the timeline.
+    pub fn reset(&mut self) {
+        for queue in &mut self.channels {
+            queue.clear();
+        }
+        self.start_frame = 0;
+        self.anchored = false;
+    }
+
+    /// Align the buffered timeline with an incoming timestamp.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    fn anchor(&mut self, pts: CMTime) {
+        let Some(incoming) = frames_at(pts, self.sample_rate) else {
+            return;
+        };
+        let buffered = self.buffered_frames() as i64;
+
+        if !self.anchored {
+            self.start_frame = incoming - buffered;
+            self.anchored = true;
+            return;
+        }
+
+        let gap = incoming - (self.start_frame + buffered);
+        match usize::try_from(gap) {
+            Ok(missing) if missing > 1 && gap <= i64::from(self.sample_rate) => {
+                for queue in &mut self.channels {
+                    queue.resize(queue.len() + missing, 0.0);
+                }
+            }
+            _ if gap.abs() > 1 => self.start_frame = incoming - buffered,
+            _ => {}
+        }
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    fn drain_chunks(&mut self) -> Vec<AudioChunk> {
+        let n = self.frames_per_chunk;
+        let channel_count = self.channels.len();
+        let mut chunks = Vec::new();
+
+        while self.buffered_frames() >= n {
+            let mut samples = Vec::with_capacity(n * channel_count);
+            match self.layout {
+                AudioChunkLayout::Planar => {
+                    for queue in &self.channels {
+                        samples.extend_from_slice(&queue[..n]);
+                    }
+                }
+                AudioChunkLayout::Interleaved => {
+                    for i in 0..n {
+                        samples.extend(self.channels.iter().map(|queue| queue[i]));
+                    }
+                }
+            }
+            for queue in &mut self.channels {
+                queue.drain(..n);
+            }
+
+            chunks.push(AudioChunk {
+                pts: CMTime::new(self.start_frame, self.sample_rate as i32),
+                sample_rate: self.sample_rate,
+                frame_count: n,
+                channel_count,
+                layout: self.layout,
+                samples,
+            });
+            self.start_frame += n as i64;
+        }
+        chunks
+    }
+}
+
+/// Position of `pts` on a `1 / sample_rate` timeline, or `None` if invalid.
+#[allow(clippy::cast_possible_truncation)]
+fn frames_at(pts: CMTime, sample_rate: u32) -> Option<i64> {
+    if !pts.is_valid() || pts.timescale <= 0 {
+        return None;
+    }
+    Some((i128::from(pts.value) * i128::from(sample_rate) / i128::from(pts.timescale)) as i64)
+}
+
+/// Reject audio that isn't 32-bit float at `sample_rate` Hz. Buffers without
+/// a format description are taken as they are.
+#[allow(clippy::float_cmp)]
+fn check_format(sample: &CMSampleBuffer, sample_rate: u32) -> Result<(), SCError> {
+    let Some(format) = sample.format_description() else {
+        return Ok(());
+    };
+    if !format.audio_is_float() || format.audio_bits_per_channel() != Some(32) {
+        return Err(SCError::InvalidBuffer(
+            "expected 32-bit float audio".to_string(),
+        ));
+    }
+    match format.audio_sample_rate() {
+        Some(rate) if rate != f64::from(sample_rate) => Err(SCError::InvalidBuffer(format!(
+            "expected {sample_rate} Hz audio, got {rate} Hz"
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Reinterpret native-endian `f32` bytes.
+fn floats(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks_exact(4)
+        .map(|b| f32::from_ne_bytes([b[0], b[1], b[2], b[3]]))
+        .collect()
+}
@@ -16,6 +16,12 @@ extern "C" {
         sample_buffer: *mut std::ffi::c_void,
         out_status: *mut i32,
     ) -> *const std::ffi::c_void;
+    /// Deep-copy a sample buffer out of the capture pool. Returns a retained
+    /// sample buffer, or null with `out_status` set to the `OSStatus`.
+    pub fn cm_sample_buffer_create_owned_copy(
+        sample_buffer: *mut std::ffi::c_void,
+        out_status: *mut i32,
+    ) -> *mut std::ffi::c_void;
 
     // Frame info accessors
     pub fn cm_sample_buffer_get_display_time(
@@ -84,6 +90,11 @@ extern "C" {
         out_presenter_overlay_rect: *mut f64, // [4]
     ) -> bool;
 
+    /// Every first-sample attachment as sorted `key\tvalue` lines, or NULL
+    /// when there are none. Free with `sc_free_string`.
+    #[cfg(feature = "debug_attachments")]
+    pub fn cm_sample_buffer_copy_attachment_dump(sample_buffer: *mut std::ffi::c_void) -> *mut i8;
+
     pub fn cm_sample_buffer_get_presentation_timestamp(
         sample_buffer: *mut std::ffi::c_void,
         out_value: *mut i64,
@@ -99,9 +110,15 @@ extern "C" {
         out_epoch: *mut i64,
     );
     pub fn cm_sample_buffer_release(sample_buffer: *mut std::ffi::c_void);
-    pub fn cm_sample_buffer_retain(sample_buffer: *mut std::ffi::c_void);
     pub fn cm_sample_buffer_is_valid(sample_buffer: *mut std::ffi::c_void) -> bool;
     pub fn cm_sample_buffer_get_num_samples(sample_buffer: *mut std::ffi::c_void) -> usize;
+    /// Sample rate and channels per frame of an audio buffer's format;
+    /// false for buffers without an audio format.
+    pub fn cm_sample_buffer_get_audio_format(
+        sample_buffer: *mut std::ffi::c_void,
+        out_sample_rate: *mut f64,
+        out_channel_count: *mut u32,
+    ) -> bool;
     pub fn cm_sample_buffer_get_audio_buffer_list(
         sample_buffer: *mut std::ffi::c_void,
         out_num_buffers: *mut u32,
@@ -109,6 +126,15 @@ extern "C" {
         out_buffers_len: *mut usize,
         out_block_buffer: *mut *mut std::ffi::c_void,
     );
+    /// Copy up to `capacity` audio buffers into `out_buffers` without heap
+    /// allocation; returns the count (0 if none or too many) and a +1 block
+    /// buffer in `out_block_buffer`.
+    pub fn cm_sample_buffer_fill_audio_buffers(
+        sample_buffer: *mut std::ffi::c_void,
+        out_buffers: *mut std::ffi::c_void,
+        capacity: u32,
+        out_block_buffer: *mut *mut std::ffi::c_void,
+    ) -> u32;
     pub fn cm_block_buffer_release(block_buffer: *mut std::ffi::c_void);
     pub fn cm_block_buffer_retain(block_buffer: *mut std::ffi::c_void) -> *mut std::ffi::c_void;
     pub fn cm_block_buffer_get_data_length(block_buffer: *mut std::ffi::c_void) -> usize;
@@ -395,4 +421,18 @@ extern "C" {
         block_buffer_out: *mut *mut std::ffi::c_void,
     ) -> i32;
     pub fn cm_block_buffer_create_empty(block_buffer_out: *mut *mut std::ffi::c_void) -> i32;
+
+    // CVPixelBuffer color attachments (0 = primaries, 1 = transfer, 2 = matrix)
+    pub fn cv_pixel_buffer_get_color_attachment(
+        pixel_buffer: *mut std::ffi::c_void,
+        key: i32,
+        buffer: *mut i8,
+        buffer_size: isize,
+    ) -> bool;
+
+    // IOSurface plane base address (surface must be locked)
+    pub fn iosurface_get_base_address_of_plane(
+        surface: *mut std::ffi::c_void,
+        plane: isize,
+    ) -> *mut std::ffi::c_void;
 }
@@ -7,10 +7,13 @@
 //!
 //! - [`CMSampleBuffer`] - Container for media samples (audio/video frames)
 //! - [`CMTime`] - Time value with rational timescale for precise timing
+//! - [`MachTime`] - Host-clock timestamp (frame display time) with `Instant`/`Duration`/`CMTime` conversions
+//! - [`CMClockExt`] / [`ClockDrift`] - The host time clock, and how far another clock drifts from it
 //! - [`IOSurface`] - Hardware-accelerated surface for zero-copy GPU access
 //! - [`CMBlockBuffer`] - Block of contiguous data (audio/compressed video)
 //! - [`AudioBuffer`] - Audio data buffer with sample data
 //! - [`AudioBufferList`] - Collection of audio buffers for multi-channel audio
+//! - [`AudioChunker`] - Re-chunks captured audio into fixed frame counts for encoders
 //! - [`SCFrameStatus`] - Status of a captured frame (complete, idle, dropped, etc.)
 //!
 //! ## Example
@@ -37,6 +40,7 @@
 //! ```
 
 mod audio;
+mod audio_chunker;
 mod block_buffer;
 pub mod ffi;
 mod format_description;
@@ -48,15 +52,18 @@ mod time;
 // Re-export all public types
 pub use audio::{
     AudioBuffer, AudioBufferList, AudioBufferListIter, AudioBufferListRaw, AudioBufferRef,
+    AudioBuffersView, MAX_AUDIO_BUFFERS,
 };
+pub use audio_chunker::{AudioChunk, AudioChunkLayout, AudioChunker, AudioGap, AudioGapDetector};
 pub use block_buffer::CMBlockBuffer;
 pub use format_description::CMFormatDescription;
 pub use frame_status::SCFrameStatus;
 pub use iosurface::{IOSurface, IOSurfaceLockGuard, IOSurfaceLockOptions, PlaneProperties};
 pub use sample_buffer::{
-    CMSampleBuffer, CMSampleBufferDataBufferExt, CMSampleBufferExt, CMSampleBufferSCExt, FrameInfo,
+    CMSampleBuffer, CMSampleBufferDataBufferExt, CMSampleBufferExt, CMSampleBufferRetainExt,
+    CMSampleBufferSCExt, FrameInfo,
 };
-pub use time::{CMClock, CMSampleTimingInfo, CMTime};
+pub use time::{CMClock, CMClockExt, CMSampleTimingInfo, CMTime, ClockDrift, MachTime};
 
 // Re-export codec and media type modules from format_description
 pub use format_description::codec_types;
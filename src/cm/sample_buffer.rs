@@ -7,14 +7,14 @@
 //! `display_time()`, `frame_info()`, etc. on any `CMSampleBuffer` carrying
 //! `ScreenCaptureKit` attachments.
 //!
-//! Bring [`CMSampleBufferExt`] into scope for the
-//! `image_buffer()`/`audio_buffer_list()`/`make_data_ready()` accessors
-//! that are pending an apple-cf v0.2 API addition.
+//! Bring [`CMSampleBufferExt`] into scope for the `image_buffer()`,
+//! `audio_buffer_list()`/`with_audio_buffers()` and `make_data_ready()`
+//! accessors that are pending an apple-cf v0.2 API addition.
 
 use super::ffi;
 use super::{
-    AudioBuffer, AudioBufferList, AudioBufferListRaw, CMBlockBuffer, CMSampleTimingInfo, CMTime,
-    SCFrameStatus,
+    AudioBuffer, AudioBufferList, AudioBufferListRaw, AudioBuffersView, CMBlockBuffer,
+    CMSampleTimingInfo, CMTime, MachTime, SCFrameStatus, MAX_AUDIO_BUFFERS,
 };
 use crate::cv::CVPixelBuffer;
 
@@ -73,6 +73,13 @@ pub struct FrameInfo {
     pub presenter_overlay_content_rect: Option<crate::cg::CGRect>,
 }
 
+impl FrameInfo {
+    /// [`display_time`](Self::display_time) as a [`MachTime`].
+    pub fn display_mach_time(&self) -> Option<MachTime> {
+        self.display_time.map(MachTime::from_ticks)
+    }
+}
+
 // ------------------------------------------------------------------
 // CMSampleBufferSCExt — ScreenCaptureKit-specific attachment readers.
 // ------------------------------------------------------------------
@@ -88,6 +95,12 @@ pub trait CMSampleBufferSCExt {
     fn frame_status(&self) -> Option<SCFrameStatus>;
     /// `SCStreamFrameInfo.displayTime` attachment.
     fn display_time(&self) -> Option<u64>;
+    /// `SCStreamFrameInfo.displayTime` as a [`MachTime`], for converting to
+    /// [`Instant`](std::time::Instant), [`Duration`](std::time::Duration) or
+    /// a host-clock [`CMTime`].
+    fn display_mach_time(&self) -> Option<MachTime> {
+        self.display_time().map(MachTime::from_ticks)
+    }
     /// `SCStreamFrameInfo.scaleFactor` attachment.
     fn scale_factor(&self) -> Option<f64>;
     /// `SCStreamFrameInfo.contentScale` attachment.
@@ -105,6 +118,27 @@ pub trait CMSampleBufferSCExt {
     /// Read every populated `SCStreamFrameInfo` attachment in a single
     /// FFI round-trip.
     fn frame_info(&self) -> Option<FrameInfo>;
+    /// Every attachment on the sample, keyed by its raw name
+    /// (`SCStreamUpdateFrameStatus`, `SCStreamUpdateFrameContentRect`, …)
+    /// with values stringified for logging.
+    ///
+    /// Unlike [`frame_info`](Self::frame_info) this includes keys the crate
+    /// doesn't model, which helps when triaging odd frames — why the status
+    /// is `idle`, what the content rect really was — without Xcode. Rects
+    /// read `(x, y, width, height)` and the status carries its name. Empty
+    /// when the sample has no attachments.
+    ///
+    /// ```no_run
+    /// # use screencapturekit::prelude::*;
+    /// # fn handle(sample: &CMSampleBuffer) {
+    /// for (key, value) in sample.debug_attachments() {
+    ///     eprintln!("{key} = {value}");
+    /// }
+    /// # }
+    /// ```
+    #[cfg(feature = "debug_attachments")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "debug_attachments")))]
+    fn debug_attachments(&self) -> std::collections::BTreeMap<String, String>;
 }
 
 impl CMSampleBufferSCExt for CMSampleBuffer {
@@ -300,6 +334,22 @@ impl CMSampleBufferSCExt for CMSampleBuffer {
             })
         }
     }
+
+    #[cfg(feature = "debug_attachments")]
+    fn debug_attachments(&self) -> std::collections::BTreeMap<String, String> {
+        let raw = unsafe { ffi::cm_sample_buffer_copy_attachment_dump(self.as_ptr()) };
+        if raw.is_null() {
+            return std::collections::BTreeMap::new();
+        }
+        let dump = unsafe { std::ffi::CStr::from_ptr(raw) }
+            .to_string_lossy()
+            .into_owned();
+        unsafe { crate::ffi::sc_free_string(raw) };
+        dump.lines()
+            .filter_map(|line| line.split_once('\t'))
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
 }
 
 // ------------------------------------------------------------------
@@ -330,6 +380,18 @@ pub trait CMSampleBufferExt {
     /// Read the audio sample buffer's underlying `AudioBufferList`, if any.
     fn audio_buffer_list(&self) -> Option<AudioBufferList>;
 
+    /// Run `f` on the sample's audio buffers without allocating.
+    ///
+    /// Unlike [`audio_buffer_list`](Self::audio_buffer_list), neither this
+    /// crate nor the Swift bridge touches the heap: the buffer descriptors
+    /// are read into a fixed array on the stack and the data is borrowed
+    /// from the sample's block buffer. This makes it safe to call from
+    /// real-time audio code.
+    ///
+    /// Returns `None` for samples without audio, or with more than
+    /// [`MAX_AUDIO_BUFFERS`] buffers.
+    fn with_audio_buffers<R>(&self, f: impl FnOnce(&AudioBuffersView<'_>) -> R) -> Option<R>;
+
     /// Output presentation timestamp (after timing adjustments).
     fn output_presentation_timestamp(&self) -> CMTime;
 
@@ -454,6 +516,42 @@ impl CMSampleBufferExt for CMSampleBuffer {
         }
     }
 
+    fn with_audio_buffers<R>(&self, f: impl FnOnce(&AudioBuffersView<'_>) -> R) -> Option<R> {
+        /// Releases the block buffer even if `f` panics.
+        struct BlockBufferGuard(*mut std::ffi::c_void);
+
+        impl Drop for BlockBufferGuard {
+            fn drop(&mut self) {
+                if !self.0.is_null() {
+                    unsafe { ffi::cm_block_buffer_release(self.0) };
+                }
+            }
+        }
+
+        let mut buffers = [AudioBuffer::EMPTY; MAX_AUDIO_BUFFERS];
+        let mut block_buffer_ptr: *mut std::ffi::c_void = std::ptr::null_mut();
+        let capacity = u32::try_from(MAX_AUDIO_BUFFERS).unwrap_or(u32::MAX);
+        // SAFETY: `buffers` has room for `capacity` entries with the same
+        // layout as the Swift `AudioBufferBridge`, and the returned +1 block
+        // buffer is released by the guard.
+        let count = unsafe {
+            ffi::cm_sample_buffer_fill_audio_buffers(
+                self.as_ptr(),
+                buffers.as_mut_ptr().cast(),
+                capacity,
+                &mut block_buffer_ptr,
+            )
+        };
+        let _block_buffer = BlockBufferGuard(block_buffer_ptr);
+        let count = usize::try_from(count).unwrap_or(0).min(MAX_AUDIO_BUFFERS);
+        if count == 0 {
+            return None;
+        }
+        Some(f(&AudioBuffersView {
+            buffers: &buffers[..count],
+        }))
+    }
+
     fn output_presentation_timestamp(&self) -> CMTime {
         unsafe {
             let mut value: i64 = 0;
@@ -587,6 +685,60 @@ impl CMSampleBufferExt for CMSampleBuffer {
     }
 }
 
+/// Keeping sample buffers past the output callback.
+///
+/// Handlers receive each [`CMSampleBuffer`] by value, so it can already be
+/// moved to another thread, and [`Clone`] adds another handle to the same
+/// buffer (`CFRetain`). Cloning is cheap, but the underlying pixel buffer
+/// still belongs to the stream's pool: while any handle is alive, that
+/// surface counts against
+/// [`queue_depth`](crate::stream::configuration::SCStreamConfiguration::queue_depth)
+/// and capture stalls once every surface is held. This adds a copy that
+/// stops holding on to `ScreenCaptureKit`'s surface pool.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::sync::mpsc;
+/// use screencapturekit::cm::{CMSampleBuffer, CMSampleBufferRetainExt};
+///
+/// fn forward(sample: &CMSampleBuffer, worker: &mpsc::Sender<CMSampleBuffer>) {
+///     // Copy out of the capture pool so a slow worker can't stall capture.
+///     if let Ok(owned) = sample.make_owned() {
+///         let _ = worker.send(owned);
+///     }
+/// }
+/// ```
+pub trait CMSampleBufferRetainExt {
+    /// Copy the sample buffer so it no longer references the capture pool.
+    ///
+    /// Video frames are copied into a newly allocated pixel buffer with the
+    /// same format, timing and `SCStreamFrameInfo` attachments. Audio sample
+    /// buffers get a new container over the same immutable data.
+    ///
+    /// # Errors
+    ///
+    /// Returns the `OSStatus` if allocating or copying the buffer fails.
+    fn make_owned(&self) -> Result<CMSampleBuffer, i32>;
+}
+
+impl CMSampleBufferRetainExt for CMSampleBuffer {
+    fn make_owned(&self) -> Result<CMSampleBuffer, i32> {
+        let mut status: i32 = 0;
+        let ptr = unsafe { ffi::cm_sample_buffer_create_owned_copy(self.as_ptr(), &mut status) };
+        if ptr.is_null() || status != 0 {
+            return Err(status);
+        }
+        if let Some(image) = self.image_buffer() {
+            use crate::cv::frame_copy::{FrameCopyExt, RowLayout};
+            let bytes = image.copy_size(RowLayout::Packed).unwrap_or(0);
+            crate::instrument::buffer_copied("make_owned", bytes);
+        }
+        // Safety: the Swift bridge returns a retained sample buffer on success.
+        Ok(unsafe { Self::from_ptr(ptr) })
+    }
+}
+
 // ------------------------------------------------------------------
 // data_buffer wrapper that returns the *local* CMBlockBuffer type
 // (for backward compat). apple_cf::cm::CMSampleBuffer also has its own
@@ -1,3 +1,344 @@
-//! Core Media time types shared with `apple-cf`.
+//! Core Media time types shared with `apple-cf`, plus [`MachTime`] for the
+//! host-clock timestamps `ScreenCaptureKit` attaches to frames, and helpers
+//! to relate other clocks to it.
+
+use std::ffi::c_void;
+use std::fmt;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
 
 pub use apple_cf::cm::{CMClock, CMSampleTimingInfo, CMTime};
+
+const NANOS_PER_SECOND: i32 = 1_000_000_000;
+
+/// A `mach_absolute_time` timestamp
+///
+/// This is the host clock behind `SCStreamFrameInfo.displayTime`, the
+/// stream's synchronization clock and every sample buffer's presentation
+/// time, so it compares directly against all three. It stops while the
+/// machine sleeps.
+///
+/// # Examples
+///
+/// ```no_run
+/// use screencapturekit::cm::{CMSampleBuffer, CMSampleBufferSCExt};
+///
+/// fn log_latency(sample: &CMSampleBuffer) {
+///     if let Some(displayed) = sample.display_mach_time() {
+///         println!("frame reached us {:?} after it was composited", displayed.elapsed());
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct MachTime(u64);
+
+impl MachTime {
+    /// Wrap a raw `mach_absolute_time` value
+    pub const fn from_ticks(ticks: u64) -> Self {
+        Self(ticks)
+    }
+
+    /// The host time `nanos` nanoseconds after boot, excluding sleep
+    pub fn from_nanos(nanos: u64) -> Self {
+        Self(nanos_to_ticks(nanos))
+    }
+
+    /// The host time a [`CMTime`] on the host time clock refers to
+    ///
+    /// Accepts any timescale, e.g. a presentation timestamp or a time read
+    /// from [`CMClockExt::host_time_clock`]. Returns `None` for invalid,
+    /// infinite, indefinite or negative times.
+    pub fn from_cmtime(time: CMTime) -> Option<Self> {
+        let nanos = cmtime_nanos(time)?;
+        u64::try_from(nanos).ok().map(Self::from_nanos)
+    }
+
+    /// The host time matching an [`Instant`]
+    ///
+    /// Anchored on `Instant::now()` like [`to_instant`](Self::to_instant),
+    /// so the two round-trip to within a few nanoseconds.
+    pub fn from_instant(instant: Instant) -> Self {
+        let (now_instant, now) = (Instant::now(), Self::now());
+        let now_nanos = ticks_to_nanos(now.0);
+        let nanos = if instant <= now_instant {
+            let ago =
+                u64::try_from(now_instant.duration_since(instant).as_nanos()).unwrap_or(u64::MAX);
+            now_nanos.saturating_sub(ago)
+        } else {
+            let ahead =
+                u64::try_from(instant.duration_since(now_instant).as_nanos()).unwrap_or(u64::MAX);
+            now_nanos.saturating_add(ahead)
+        };
+        Self::from_nanos(nanos)
+    }
+
+    /// The current host time
+    pub fn now() -> Self {
+        Self(unsafe { mach_absolute_time() })
+    }
+
+    /// The raw `mach_absolute_time` value
+    pub const fn ticks(self) -> u64 {
+        self.0
+    }
+
+    /// Time since boot, excluding sleep
+    pub fn as_duration(self) -> Duration {
+        Duration::from_nanos(ticks_to_nanos(self.0))
+    }
+
+    /// Time from `earlier` to `self`, or zero if `earlier` is later
+    pub fn saturating_duration_since(self, earlier: Self) -> Duration {
+        Duration::from_nanos(ticks_to_nanos(self.0.saturating_sub(earlier.0)))
+    }
+
+    /// Time from `self` to now, or zero if `self` is in the future
+    pub fn elapsed(self) -> Duration {
+        Self::now().saturating_duration_since(self)
+    }
+
+    /// The matching point on the [`Instant`] clock
+    ///
+    /// `Instant` cannot be built from raw ticks, so this anchors on
+    /// `Instant::now()`; the result is accurate to the few nanoseconds
+    /// between the two clock reads.
+    pub fn to_instant(self) -> Instant {
+        let (now_instant, now) = (Instant::now(), Self::now());
+        if self <= now {
+            let ago = now.saturating_duration_since(self);
+            now_instant.checked_sub(ago).unwrap_or(now_instant)
+        } else {
+            now_instant + self.saturating_duration_since(now)
+        }
+    }
+
+    /// The same moment as a [`CMTime`] on the host time clock, in
+    /// nanoseconds
+    ///
+    /// Comparable with presentation timestamps and with times read from
+    /// [`SCStream::synchronization_clock`](crate::stream::sc_stream::SCStream::synchronization_clock).
+    pub fn to_cmtime(self) -> CMTime {
+        let nanos = i64::try_from(ticks_to_nanos(self.0)).unwrap_or(i64::MAX);
+        CMTime::new(nanos, NANOS_PER_SECOND)
+    }
+
+    /// The same moment on `clock`, in nanoseconds
+    ///
+    /// Reads `clock` and host time together and offsets the reading by how
+    /// far `self` lies from now, so it suits clocks that don't run on host
+    /// time, such as an audio device's. Returns `None` if the clock's time
+    /// isn't a finite value.
+    pub fn to_clock_time(self, clock: &CMClock) -> Option<CMTime> {
+        let before = Self::now();
+        let clock_nanos = cmtime_nanos(clock.time())?;
+        let after = Self::now();
+        let now = (i128::from(ticks_to_nanos(before.0)) + i128::from(ticks_to_nanos(after.0))) / 2;
+        let nanos = clock_nanos - (now - i128::from(ticks_to_nanos(self.0)));
+        i64::try_from(nanos)
+            .ok()
+            .map(|nanos| CMTime::new(nanos, NANOS_PER_SECOND))
+    }
+}
+
+impl fmt::Display for MachTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} since boot", self.as_duration())
+    }
+}
+
+/// Host time clock access for [`CMClock`]
+pub trait CMClockExt {
+    /// The host time clock, `CMClockGetHostTimeClock`
+    ///
+    /// Its time is `mach_absolute_time` in nanoseconds: the timeline of
+    /// [`MachTime`], of presentation timestamps and of frame display times.
+    ///
+    /// # Panics
+    ///
+    /// Panics if Core Media returns no clock, which it documents it never
+    /// does.
+    fn host_time_clock() -> CMClock;
+
+    /// The clock's current time as a [`MachTime`]
+    ///
+    /// Only meaningful for clocks that run on host time, such as
+    /// [`host_time_clock`](Self::host_time_clock). Use [`ClockDrift`] to
+    /// relate any other clock to host time.
+    fn mach_time(&self) -> Option<MachTime>;
+}
+
+impl CMClockExt for CMClock {
+    fn host_time_clock() -> CMClock {
+        // SAFETY: the clock is a +0 singleton; `from_raw` retains it.
+        Self::from_raw(unsafe { CMClockGetHostTimeClock() })
+            .expect("CMClockGetHostTimeClock returned NULL")
+    }
+
+    fn mach_time(&self) -> Option<MachTime> {
+        MachTime::from_cmtime(self.time())
+    }
+}
+
+/// How fast a clock runs compared to host time
+///
+/// Audio devices and some synchronization clocks tick at a rate that
+/// differs slightly from `mach_absolute_time`, so timestamps from the two
+/// slowly drift apart over a long recording. Feed [`sample`](Self::sample)
+/// every so often, e.g. once a second, and use [`rate`](Self::rate) or
+/// [`drift`](Self::drift) to resample or re-time output.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use screencapturekit::cm::{CMClock, CMClockExt, ClockDrift};
+///
+/// let clock = CMClock::host_time_clock();
+/// let mut drift = ClockDrift::new();
+/// for _ in 0..5 {
+///     drift.sample(&clock);
+///     std::thread::sleep(Duration::from_millis(200));
+/// }
+/// println!("{:+.1} ppm", drift.ppm().unwrap_or(0.0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ClockDrift {
+    /// First and latest (host nanoseconds, clock nanoseconds) pairs.
+    first: Option<(i128, i128)>,
+    latest: Option<(i128, i128)>,
+}
+
+impl ClockDrift {
+    /// An estimator with no samples yet
+    pub const fn new() -> Self {
+        Self {
+            first: None,
+            latest: None,
+        }
+    }
+
+    /// Read `clock` and host time together and record the pair
+    ///
+    /// The host time is taken on both sides of the clock read and averaged,
+    /// which halves the error from being preempted in between.
+    pub fn sample(&mut self, clock: &CMClock) {
+        let before = MachTime::now();
+        let time = clock.time();
+        let after = MachTime::now();
+        let host = i128::from(ticks_to_nanos(before.0)) + i128::from(ticks_to_nanos(after.0));
+        if let Some(clock_nanos) = cmtime_nanos(time) {
+            self.push(host / 2, clock_nanos);
+        }
+    }
+
+    /// Record a pair of readings taken at the same moment, e.g. a sample's
+    /// host presentation time and the device time it was captured at.
+    /// Invalid or infinite clock times are ignored.
+    pub fn record(&mut self, host: MachTime, clock_time: CMTime) {
+        if let Some(clock_nanos) = cmtime_nanos(clock_time) {
+            self.push(i128::from(ticks_to_nanos(host.0)), clock_nanos);
+        }
+    }
+
+    fn push(&mut self, host: i128, clock: i128) {
+        self.first.get_or_insert((host, clock));
+        self.latest = Some((host, clock));
+    }
+
+    /// Host time covered by the samples so far
+    pub fn elapsed(&self) -> Duration {
+        self.span().map_or(Duration::ZERO, |(host, _)| {
+            Duration::from_nanos(u64::try_from(host).unwrap_or(0))
+        })
+    }
+
+    /// Clock seconds per host second, `1.0` for a clock in step with host
+    /// time; `None` until two samples at different host times are in
+    pub fn rate(&self) -> Option<f64> {
+        let (host, clock) = self.span()?;
+        if host <= 0 {
+            return None;
+        }
+        #[allow(clippy::cast_precision_loss)]
+        let rate = clock as f64 / host as f64;
+        Some(rate)
+    }
+
+    /// Drift in parts per million: positive when the clock runs fast
+    pub fn ppm(&self) -> Option<f64> {
+        self.rate().map(|rate| (rate - 1.0) * 1_000_000.0)
+    }
+
+    /// How far the clock has moved beyond host time since the first
+    /// sample, in seconds; negative when it runs slow
+    pub fn drift(&self) -> Option<f64> {
+        let (host, clock) = self.span()?;
+        #[allow(clippy::cast_precision_loss)]
+        let seconds = (clock - host) as f64 / f64::from(NANOS_PER_SECOND);
+        Some(seconds)
+    }
+
+    /// Host and clock nanoseconds between the first and latest samples.
+    fn span(&self) -> Option<(i128, i128)> {
+        let ((host0, clock0), (host1, clock1)) = (self.first?, self.latest?);
+        Some((host1 - host0, clock1 - clock0))
+    }
+}
+
+/// Nanoseconds `time` stands for, or `None` if it isn't a finite value.
+fn cmtime_nanos(time: CMTime) -> Option<i128> {
+    if !time.is_valid()
+        || time.is_indefinite()
+        || time.is_positive_infinity()
+        || time.is_negative_infinity()
+        || time.timescale <= 0
+    {
+        return None;
+    }
+    Some(i128::from(time.value) * i128::from(NANOS_PER_SECOND) / i128::from(time.timescale))
+}
+
+fn timebase() -> (u32, u32) {
+    static TIMEBASE: OnceLock<(u32, u32)> = OnceLock::new();
+    *TIMEBASE.get_or_init(|| {
+        let mut info = MachTimebaseInfo { numer: 0, denom: 0 };
+        if unsafe { mach_timebase_info(&mut info) } != 0 || info.denom == 0 || info.numer == 0 {
+            return (1, 1);
+        }
+        (info.numer, info.denom)
+    })
+}
+
+fn ticks_to_nanos(ticks: u64) -> u64 {
+    let (numer, denom) = timebase();
+    if numer == denom {
+        return ticks;
+    }
+    let nanos = u128::from(ticks) * u128::from(numer) / u128::from(denom);
+    u64::try_from(nanos).unwrap_or(u64::MAX)
+}
+
+fn nanos_to_ticks(nanos: u64) -> u64 {
+    let (numer, denom) = timebase();
+    if numer == denom {
+        return nanos;
+    }
+    let ticks = u128::from(nanos) * u128::from(denom) / u128::from(numer);
+    u64::try_from(ticks).unwrap_or(u64::MAX)
+}
+
+#[repr(C)]
+struct MachTimebaseInfo {
+    numer: u32,
+    denom: u32,
+}
+
+extern "C" {
+    fn mach_absolute_time() -> u64;
+    fn mach_timebase_info(info: *mut MachTimebaseInfo) -> i32;
+}
+
+#[link(name = "CoreMedia", kind = "framework")]
+extern "C" {
+    fn CMClockGetHostTimeClock() -> *const c_void;
+}
@@ -56,6 +56,67 @@
 //! }
 //! ```
 //!
+//! ## Share audio with the picked content
+//!
+//! The system picker has no audio controls, so apps that offer a
+//! "Share audio" checkbox (the way browsers do) pass that choice to
+//! [`SCPickerResult::stream_configuration`]:
+//!
+//! ```no_run
+//! use screencapturekit::content_sharing_picker::*;
+//!
+//! let share_audio_checked = true;
+//! let config = SCContentSharingPickerConfiguration::new();
+//! SCContentSharingPicker::show(&config, move |outcome| {
+//!     if let SCPickerOutcome::Picked(result) = outcome {
+//!         let audio = if share_audio_checked {
+//!             SCPickerAudio::Content
+//!         } else {
+//!             SCPickerAudio::None
+//!         };
+//!         let stream_config = result.stream_configuration(audio);
+//!         let filter = result.filter();
+//!         // SCStream::new(&filter, &stream_config) ...
+//!     }
+//! });
+//! ```
+//!
+//! ## Open on a tab, with content preselected
+//!
+//! Apps that can guess what the user wants to share, such as the window
+//! they were just working in, open the picker on the matching tab with
+//! that content already selected:
+//!
+//! ```no_run
+//! use screencapturekit::content_sharing_picker::*;
+//! use screencapturekit::prelude::*;
+//!
+//! # fn example() -> Result<(), SCError> {
+//! let content = SCShareableContent::get()?;
+//! let presentation = SCPickerPresentation::new()
+//!     .with_default_mode(SCContentSharingPickerMode::SingleWindow)
+//!     .with_preselected_window(&content.windows()[0]);
+//! let config = SCContentSharingPickerConfiguration::new();
+//! SCContentSharingPicker::show_with_presentation(&config, &presentation, |outcome| {
+//!     if let SCPickerOutcome::Picked(result) = outcome {
+//!         let _filter = result.filter();
+//!     }
+//! });
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! ## Without Screen Recording permission
+//!
+//! Capturing picked content doesn't need Screen Recording permission, so
+//! the picker works for apps that never ask for it. Check
+//! [`capture_access`](crate::stream::permission_watcher::capture_access)
+//! to decide between listing content yourself and presenting the picker;
+//! [`SCContentFilter::is_from_picker`] marks the filters a stream can use
+//! in that mode. [`SCPickerResult::windows`] and friends still work, but
+//! filters built from them with [`SCContentFilter::for_window`] and the
+//! other constructors are not picked filters and need the permission.
+//!
 //! ## Configure Picker Modes
 //! ```no_run
 //! use screencapturekit::content_sharing_picker::*;
@@ -67,6 +128,7 @@
 //! config.set_excluded_bundle_ids(&["com.apple.finder", "com.apple.dock"]);
 //! ```
 
+use crate::stream::configuration::SCStreamConfiguration;
 use crate::stream::content_filter::SCContentFilter;
 use std::ffi::c_void;
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -84,6 +146,49 @@ pub enum SCPickedSource {
     Unknown,
 }
 
+/// Audio to capture alongside the content picked in the picker
+///
+/// `SCContentSharingPicker` only selects visual content; whether audio is
+/// shared is up to the app. Which audio a stream actually receives follows
+/// from the picked content, see [`SCPickerResult::captures_system_audio`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SCPickerAudio {
+    /// Video only
+    #[default]
+    None,
+    /// Audio produced by the picked content
+    Content,
+    /// Audio produced by the picked content plus the microphone
+    /// (macOS 15.0+; earlier systems capture the content audio only)
+    ContentAndMicrophone,
+}
+
+impl SCPickerAudio {
+    /// Whether content audio is captured.
+    #[must_use]
+    pub const fn captures_audio(self) -> bool {
+        matches!(self, Self::Content | Self::ContentAndMicrophone)
+    }
+
+    /// Whether the microphone is captured.
+    #[must_use]
+    pub const fn captures_microphone(self) -> bool {
+        matches!(self, Self::ContentAndMicrophone)
+    }
+
+    /// Apply this choice to `config`.
+    ///
+    /// Audio from the current process is excluded whenever content audio
+    /// is captured, so a call app does not record its own playback.
+    #[must_use]
+    pub fn apply(self, config: SCStreamConfiguration) -> SCStreamConfiguration {
+        config
+            .with_captures_audio(self.captures_audio())
+            .with_excludes_current_process_audio(self.captures_audio())
+            .with_captures_microphone(self.captures_microphone())
+    }
+}
+
 /// Picker mode determines what content types can be selected
 ///
 /// These modes can be combined to allow users to pick from different source types.
@@ -103,6 +208,96 @@ pub enum SCContentSharingPickerMode {
     MultipleApplications = 4,
 }
 
+impl SCContentSharingPickerMode {
+    /// The picker tab that offers this mode's content
+    #[must_use]
+    pub const fn content_style(self) -> crate::stream::content_filter::SCShareableContentStyle {
+        use crate::stream::content_filter::SCShareableContentStyle;
+        match self {
+            Self::SingleWindow | Self::MultipleWindows => SCShareableContentStyle::Window,
+            Self::SingleDisplay => SCShareableContentStyle::Display,
+            Self::SingleApplication | Self::MultipleApplications => {
+                SCShareableContentStyle::Application
+            }
+        }
+    }
+}
+
+/// How the picker opens: on which tab, and with what already selected
+///
+/// `ScreenCaptureKit` can't preselect content in a fresh picker. When
+/// content is preselected, the picker is instead presented for a
+/// placeholder stream capturing that content, which the picker shows as
+/// the current selection. The placeholder is never started and is
+/// released once the picker reports back.
+#[derive(Debug, Clone, Default)]
+pub struct SCPickerPresentation {
+    default_mode: Option<SCContentSharingPickerMode>,
+    preselected: Option<SCContentFilter>,
+}
+
+impl SCPickerPresentation {
+    /// Open on the system's default tab with nothing selected
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Open on the tab for `mode`
+    ///
+    /// Make sure the configuration allows `mode`; otherwise the picker
+    /// falls back to its first allowed tab.
+    #[must_use]
+    pub fn with_default_mode(mut self, mode: SCContentSharingPickerMode) -> Self {
+        self.default_mode = Some(mode);
+        self
+    }
+
+    /// Preselect `display`, opening on the display tab unless a default
+    /// mode is set
+    #[must_use]
+    pub fn with_preselected_display(self, display: &crate::shareable_content::SCDisplay) -> Self {
+        self.with_preselected_filter(
+            SCContentFilter::for_display(display)
+                .with_excluding_windows(&[])
+                .build(),
+            SCContentSharingPickerMode::SingleDisplay,
+        )
+    }
+
+    /// Preselect `window`, opening on the window tab unless a default mode
+    /// is set
+    #[must_use]
+    pub fn with_preselected_window(self, window: &crate::shareable_content::SCWindow) -> Self {
+        self.with_preselected_filter(
+            SCContentFilter::for_window(window).build(),
+            SCContentSharingPickerMode::SingleWindow,
+        )
+    }
+
+    fn with_preselected_filter(
+        mut self,
+        filter: SCContentFilter,
+        mode: SCContentSharingPickerMode,
+    ) -> Self {
+        self.preselected = Some(filter);
+        self.default_mode.get_or_insert(mode);
+        self
+    }
+
+    /// The tab the picker opens on, or `None` for the system default
+    #[must_use]
+    pub const fn default_mode(&self) -> Option<SCContentSharingPickerMode> {
+        self.default_mode
+    }
+
+    /// Filter for the preselected content, if any
+    #[must_use]
+    pub const fn preselected(&self) -> Option<&SCContentFilter> {
+        self.preselected.as_ref()
+    }
+}
+
 /// Configuration for the content sharing picker
 pub struct SCContentSharingPickerConfiguration {
     ptr: *const c_void,
@@ -338,6 +533,9 @@ impl SCPickerResult {
     }
 
     /// Get the content filter for use with `SCStream::new()`
+    ///
+    /// Streams with this filter capture without Screen Recording
+    /// permission.
     #[must_use]
     pub fn filter(&self) -> SCContentFilter {
         let filter_ptr = unsafe { crate::ffi::sc_picker_result_get_filter(self.ptr) };
@@ -415,8 +613,7 @@ impl SCPickerResult {
     ///         let windows = result.windows();
     ///         if let Some(window) = windows.first() {
     ///             // Create custom filter with a picked window
-    ///             let filter = SCContentFilter::create()
-    ///                 .with_window(window)
+    ///             let filter = SCContentFilter::for_window(window)
     ///                 .build();
     ///         }
     ///     }
@@ -448,8 +645,7 @@ impl SCPickerResult {
     ///         let displays = result.displays();
     ///         if let Some(display) = displays.first() {
     ///             // Create custom filter with the picked display
-    ///             let filter = SCContentFilter::create()
-    ///                 .with_display(display)
+    ///             let filter = SCContentFilter::for_display(display)
     ///                 .with_excluding_windows(&[])
     ///                 .build();
     ///         }
@@ -516,6 +712,36 @@ impl SCPickerResult {
             SCPickedSource::Unknown
         }
     }
+
+    /// Whether a stream of this pick captures audio from every application
+    ///
+    /// Audio is scoped by the filter, not by what is visible: picking a
+    /// display captures system-wide audio, while picking windows or
+    /// applications captures only the audio of the applications that own
+    /// them.
+    #[must_use]
+    pub fn captures_system_audio(&self) -> bool {
+        unsafe {
+            crate::ffi::sc_picker_result_get_windows_count(self.ptr) == 0
+                && crate::ffi::sc_picker_result_get_applications_count(self.ptr) == 0
+                && crate::ffi::sc_picker_result_get_displays_count(self.ptr) > 0
+        }
+    }
+
+    /// Build a stream configuration that matches the picked content
+    ///
+    /// The output size is the picked content's [`pixel_size`](Self::pixel_size),
+    /// and `audio` decides what is captured alongside it (see
+    /// [`SCPickerAudio::apply`]). Everything else keeps the
+    /// [`SCStreamConfiguration`] defaults and can be adjusted afterwards.
+    #[must_use]
+    pub fn stream_configuration(&self, audio: SCPickerAudio) -> SCStreamConfiguration {
+        let (width, height) = self.pixel_size();
+        let config = SCStreamConfiguration::new()
+            .with_width(width)
+            .with_height(height);
+        audio.apply(config)
+    }
 }
 
 crate::utils::retained::sc_retained!(
@@ -648,7 +874,7 @@ impl SCContentSharingPicker {
     ///     let content = SCShareableContent::get().ok()?;
     ///     let displays = content.displays();
     ///     let display = displays.first()?;
-    ///     let filter = SCContentFilter::create().with_display(display).with_excluding_windows(&[]).build();
+    ///     let filter = SCContentFilter::for_display(display).with_excluding_windows(&[]).build();
     ///     let stream_config = SCStreamConfiguration::new();
     ///     let stream = SCStream::new(&filter, &stream_config);
     ///
@@ -767,6 +993,41 @@ impl SCContentSharingPicker {
         }
     }
 
+    /// Show the picker on the tab and with the selection in `presentation`
+    ///
+    /// See [`SCPickerPresentation`] for how preselection works.
+    pub fn show_with_presentation<F>(
+        config: &SCContentSharingPickerConfiguration,
+        presentation: &SCPickerPresentation,
+        callback: F,
+    ) where
+        F: FnOnce(SCPickerOutcome) + Send + 'static,
+    {
+        let style = presentation
+            .default_mode
+            .map(SCContentSharingPickerMode::content_style);
+        let Some(filter) = presentation.preselected.as_ref() else {
+            match style {
+                Some(style) => Self::show_using_style(config, style, callback),
+                None => Self::show(config, callback),
+            }
+            return;
+        };
+
+        let placeholder = crate::stream::SCStream::new(filter, &SCStreamConfiguration::new());
+        let handle = placeholder.clone();
+        let callback = move |outcome| {
+            drop(handle);
+            callback(outcome);
+        };
+        match style {
+            Some(style) => {
+                Self::show_for_stream_using_style(config, &placeholder, style, callback);
+            }
+            None => Self::show_for_stream(config, &placeholder, callback),
+        }
+    }
+
     /// Set the maximum number of streams that can be created from the picker
     ///
     /// Pass 0 to allow unlimited streams.
@@ -0,0 +1,107 @@
+//! Conversions between [`CGImage`] and [`CVPixelBuffer`].
+//!
+//! Screenshots come back as `CGImage`s while stream frames are pixel
+//! buffers, so a pipeline that mixes the two — an encoder fed a screenshot
+//! as its first frame, or a stream frame saved with
+//! [`CGImageExt::save`](crate::screenshot_manager::CGImageExt::save) —
+//! needs to convert one into the other.
+//!
+//! [`PixelBufferFromImageExt::from_cgimage`] renders an image into a new
+//! `IOSurface`-backed buffer of any [`PixelFormat`] a stream can deliver,
+//! tagged with the image's color space. YCbCr buffers use the BT.709
+//! matrix, like `ScreenCaptureKit`'s own frames.
+//! [`CGImageFromPixelBufferExt::from_pixel_buffer`] goes the other way for
+//! every format `VideoToolbox` can read.
+//!
+//! ```no_run
+//! use screencapturekit::cv::cg_image::{CGImageFromPixelBufferExt, PixelBufferFromImageExt};
+//! use screencapturekit::cv::CVPixelBuffer;
+//! use screencapturekit::stream::configuration::PixelFormat;
+//! use screencapturekit::CGImage;
+//!
+//! fn round_trip(screenshot: &CGImage) -> Result<CGImage, screencapturekit::error::SCError> {
+//!     let frame = CVPixelBuffer::from_cgimage(screenshot, PixelFormat::YCbCr_420v)?;
+//!     CGImage::from_pixel_buffer(&frame)
+//! }
+//! ```
+
+use crate::cv::CVPixelBuffer;
+use crate::error::SCError;
+use crate::stream::configuration::PixelFormat;
+use crate::CGImage;
+use crate::FourCharCode;
+
+/// Create a pixel buffer from a [`CGImage`].
+pub trait PixelBufferFromImageExt: Sized {
+    /// Render `image` into a new pixel buffer of `format`, the same size as
+    /// the image.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidPixelFormat`] for
+    /// [`PixelFormat::Unknown`], [`SCError::InvalidDimension`] for an empty
+    /// image, or an error if the buffer cannot be allocated.
+    fn from_cgimage(image: &CGImage, format: PixelFormat) -> Result<Self, SCError>;
+}
+
+/// Create a [`CGImage`] from a pixel buffer.
+pub trait CGImageFromPixelBufferExt: Sized {
+    /// Copy the pixels of `buffer` into a new image, converting YCbCr and
+    /// 10-bit formats to RGB.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error with the `OSStatus` if `VideoToolbox` cannot convert
+    /// the buffer's pixel format.
+    fn from_pixel_buffer(buffer: &CVPixelBuffer) -> Result<Self, SCError>;
+}
+
+impl PixelBufferFromImageExt for CVPixelBuffer {
+    fn from_cgimage(image: &CGImage, format: PixelFormat) -> Result<Self, SCError> {
+        if let PixelFormat::Unknown(code) = format {
+            return Err(SCError::InvalidPixelFormat(format!(
+                "cannot render an image into {} pixels",
+                code.display()
+            )));
+        }
+        let mut status = 0;
+        let ptr = unsafe {
+            crate::ffi::sc_pixel_buffer_create_from_cgimage(
+                image.as_ptr(),
+                FourCharCode::from(format).as_u32(),
+                &mut status,
+            )
+        };
+        if ptr.is_null() {
+            return Err(match status {
+                -1 => SCError::invalid_dimension("image width", 0),
+                code => {
+                    SCError::os_error(code, format!("failed to create a {format} pixel buffer"))
+                }
+            });
+        }
+        // `sc_pixel_buffer_create_from_cgimage` returns a +1 CVPixelBuffer,
+        // adopted here.
+        Self::from_raw(ptr.cast_mut())
+            .ok_or_else(|| SCError::null_pointer("pixel buffer from image"))
+    }
+}
+
+impl CGImageFromPixelBufferExt for CGImage {
+    fn from_pixel_buffer(buffer: &CVPixelBuffer) -> Result<Self, SCError> {
+        let mut status = 0;
+        let ptr = unsafe {
+            crate::ffi::sc_cgimage_create_from_pixel_buffer(buffer.as_ptr(), &mut status)
+        };
+        if ptr.is_null() {
+            let format = PixelFormat::from(buffer.pixel_format());
+            return Err(SCError::os_error(
+                status,
+                format!("failed to create an image from {format} pixels"),
+            ));
+        }
+        // SAFETY: `sc_cgimage_create_from_pixel_buffer` returns a +1 CGImage,
+        // adopted here.
+        Ok(unsafe { Self::from_raw(ptr.cast_mut()) })
+    }
+}
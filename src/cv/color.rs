@@ -0,0 +1,318 @@
+//! Per-frame color tagging of captured pixel buffers.
+//!
+//! `ScreenCaptureKit` tags every frame with the color primaries, transfer
+//! function and (for YCbCr formats) conversion matrix of the content it
+//! captured. SDR captures are usually BT.709 / sRGB, but HDR captures
+//! (`l10r` with an HDR preset) come out as BT.2020 with a PQ or HLG transfer
+//! function, and wide-gamut displays may report Display P3. Encoders should
+//! copy these tags into their output rather than assuming BT.709, otherwise
+//! players will show washed-out or oversaturated colors.
+//!
+//! The `cicp_code` methods map each value to its ITU-T H.273 code point, the
+//! numbering used by H.264/HEVC VUI, AV1 and the `colr` box in MP4.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use screencapturekit::cm::{CMSampleBuffer, CMSampleBufferExt};
+//! use screencapturekit::cv::color::CVPixelBufferColorExt;
+//!
+//! fn on_frame(sample: &CMSampleBuffer) {
+//!     let Some(buffer) = sample.image_buffer() else { return };
+//!     let color = buffer.color_attachments();
+//!     if color.is_hdr() {
+//!         println!("HDR frame: {color:?}");
+//!     }
+//! }
+//! ```
+
+use std::fmt;
+
+use crate::cv::CVPixelBuffer;
+use crate::utils::ffi_string::{ffi_string_from_buffer, SMALL_BUFFER_SIZE};
+
+const KEY_COLOR_PRIMARIES: i32 = 0;
+const KEY_TRANSFER_FUNCTION: i32 = 1;
+const KEY_YCBCR_MATRIX: i32 = 2;
+
+/// `kCVImageBufferColorPrimaries` values.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ColorPrimaries {
+    /// ITU-R BT.709 (sRGB / HD video).
+    Bt709,
+    /// ITU-R BT.2020 (UHD / HDR).
+    Bt2020,
+    /// Display P3 (P3 primaries, D65 white point).
+    P3D65,
+    /// DCI-P3 (P3 primaries, DCI white point).
+    DciP3,
+    /// EBU Tech 3213 (PAL).
+    Ebu3213,
+    /// SMPTE C (NTSC).
+    SmpteC,
+    /// Phosphor set P22.
+    P22,
+    /// A value this crate does not know, as reported by `CoreVideo`.
+    Other(String),
+}
+
+/// `kCVImageBufferTransferFunction` values.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TransferFunction {
+    /// ITU-R BT.709.
+    Bt709,
+    /// IEC 61966-2-1 sRGB.
+    Srgb,
+    /// ITU-R BT.2020 (same curve as BT.709 at higher precision).
+    Bt2020,
+    /// SMPTE ST 2084 perceptual quantizer (HDR10).
+    Pq,
+    /// ITU-R BT.2100 hybrid log-gamma.
+    Hlg,
+    /// Linear light.
+    Linear,
+    /// SMPTE 240M.
+    Smpte240M,
+    /// SMPTE ST 428-1 (DCI).
+    SmpteSt428,
+    /// Pure gamma curve; the exponent is in the buffer's gamma attachment.
+    UseGamma,
+    /// A value this crate does not know, as reported by `CoreVideo`.
+    Other(String),
+}
+
+/// `kCVImageBufferYCbCrMatrix` values.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum YCbCrMatrix {
+    /// ITU-R BT.709.
+    Bt709,
+    /// ITU-R BT.601.
+    Bt601,
+    /// SMPTE 240M.
+    Smpte240M,
+    /// ITU-R BT.2020 non-constant luminance.
+    Bt2020,
+    /// A value this crate does not know, as reported by `CoreVideo`.
+    Other(String),
+}
+
+impl ColorPrimaries {
+    /// Parse a `CoreVideo` attachment string.
+    #[must_use]
+    pub fn from_cv_string(value: &str) -> Self {
+        match value {
+            "ITU_R_709_2" => Self::Bt709,
+            "ITU_R_2020" => Self::Bt2020,
+            "P3_D65" => Self::P3D65,
+            "DCI_P3" => Self::DciP3,
+            "EBU_3213" => Self::Ebu3213,
+            "SMPTE_C" => Self::SmpteC,
+            "P22" => Self::P22,
+            other => Self::Other(other.to_string()),
+        }
+    }
+
+    /// The `CoreVideo` attachment string.
+    #[must_use]
+    pub fn as_cv_str(&self) -> &str {
+        match self {
+            Self::Bt709 => "ITU_R_709_2",
+            Self::Bt2020 => "ITU_R_2020",
+            Self::P3D65 => "P3_D65",
+            Self::DciP3 => "DCI_P3",
+            Self::Ebu3213 => "EBU_3213",
+            Self::SmpteC => "SMPTE_C",
+            Self::P22 => "P22",
+            Self::Other(value) => value,
+        }
+    }
+
+    /// ITU-T H.273 `ColourPrimaries` code point.
+    #[must_use]
+    pub const fn cicp_code(&self) -> Option<u8> {
+        match self {
+            Self::Bt709 => Some(1),
+            Self::SmpteC => Some(6),
+            Self::Bt2020 => Some(9),
+            Self::DciP3 => Some(11),
+            Self::P3D65 => Some(12),
+            Self::Ebu3213 | Self::P22 => Some(22),
+            Self::Other(_) => None,
+        }
+    }
+}
+
+impl TransferFunction {
+    /// Parse a `CoreVideo` attachment string.
+    #[must_use]
+    pub fn from_cv_string(value: &str) -> Self {
+        match value {
+            "ITU_R_709_2" => Self::Bt709,
+            "IEC_sRGB" => Self::Srgb,
+            "ITU_R_2020" => Self::Bt2020,
+            "SMPTE_ST_2084_PQ" => Self::Pq,
+            "ITU_R_2100_HLG" => Self::Hlg,
+            "Linear" => Self::Linear,
+            "SMPTE_240M_1995" => Self::Smpte240M,
+            "SMPTE_ST_428_1" => Self::SmpteSt428,
+            "UseGamma" => Self::UseGamma,
+            other => Self::Other(other.to_string()),
+        }
+    }
+
+    /// The `CoreVideo` attachment string.
+    #[must_use]
+    pub fn as_cv_str(&self) -> &str {
+        match self {
+            Self::Bt709 => "ITU_R_709_2",
+            Self::Srgb => "IEC_sRGB",
+            Self::Bt2020 => "ITU_R_2020",
+            Self::Pq => "SMPTE_ST_2084_PQ",
+            Self::Hlg => "ITU_R_2100_HLG",
+            Self::Linear => "Linear",
+            Self::Smpte240M => "SMPTE_240M_1995",
+            Self::SmpteSt428 => "SMPTE_ST_428_1",
+            Self::UseGamma => "UseGamma",
+            Self::Other(value) => value,
+        }
+    }
+
+    /// ITU-T H.273 `TransferCharacteristics` code point.
+    #[must_use]
+    pub const fn cicp_code(&self) -> Option<u8> {
+        match self {
+            Self::Bt709 => Some(1),
+            Self::Smpte240M => Some(7),
+            Self::Linear => Some(8),
+            Self::Srgb => Some(13),
+            Self::Bt2020 => Some(14),
+            Self::Pq => Some(16),
+            Self::SmpteSt428 => Some(17),
+            Self::Hlg => Some(18),
+            Self::UseGamma | Self::Other(_) => None,
+        }
+    }
+
+    /// `true` for the HDR transfer functions (PQ and HLG).
+    #[must_use]
+    pub const fn is_hdr(&self) -> bool {
+        matches!(self, Self::Pq | Self::Hlg)
+    }
+}
+
+impl YCbCrMatrix {
+    /// Parse a `CoreVideo` attachment string.
+    #[must_use]
+    pub fn from_cv_string(value: &str) -> Self {
+        match value {
+            "ITU_R_709_2" => Self::Bt709,
+            "ITU_R_601_4" => Self::Bt601,
+            "SMPTE_240M_1995" => Self::Smpte240M,
+            "ITU_R_2020" => Self::Bt2020,
+            other => Self::Other(other.to_string()),
+        }
+    }
+
+    /// The `CoreVideo` attachment string.
+    #[must_use]
+    pub fn as_cv_str(&self) -> &str {
+        match self {
+            Self::Bt709 => "ITU_R_709_2",
+            Self::Bt601 => "ITU_R_601_4",
+            Self::Smpte240M => "SMPTE_240M_1995",
+            Self::Bt2020 => "ITU_R_2020",
+            Self::Other(value) => value,
+        }
+    }
+
+    /// ITU-T H.273 `MatrixCoefficients` code point.
+    #[must_use]
+    pub const fn cicp_code(&self) -> Option<u8> {
+        match self {
+            Self::Bt709 => Some(1),
+            Self::Bt601 => Some(6),
+            Self::Smpte240M => Some(7),
+            Self::Bt2020 => Some(9),
+            Self::Other(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for ColorPrimaries {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_cv_str())
+    }
+}
+
+impl fmt::Display for TransferFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_cv_str())
+    }
+}
+
+impl fmt::Display for YCbCrMatrix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_cv_str())
+    }
+}
+
+/// All color tags of a pixel buffer. Absent attachments are `None`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ColorAttachments {
+    /// Color primaries.
+    pub color_primaries: Option<ColorPrimaries>,
+    /// Transfer function.
+    pub transfer_function: Option<TransferFunction>,
+    /// YCbCr conversion matrix (only meaningful for YCbCr pixel formats).
+    pub ycbcr_matrix: Option<YCbCrMatrix>,
+}
+
+impl ColorAttachments {
+    /// `true` if the transfer function is PQ or HLG.
+    #[must_use]
+    pub fn is_hdr(&self) -> bool {
+        self.transfer_function
+            .as_ref()
+            .is_some_and(TransferFunction::is_hdr)
+    }
+}
+
+/// Color attachment accessors for [`CVPixelBuffer`].
+pub trait CVPixelBufferColorExt {
+    /// `kCVImageBufferColorPrimariesKey` attachment.
+    fn color_primaries(&self) -> Option<ColorPrimaries>;
+    /// `kCVImageBufferTransferFunctionKey` attachment.
+    fn transfer_function(&self) -> Option<TransferFunction>;
+    /// `kCVImageBufferYCbCrMatrixKey` attachment.
+    fn ycbcr_matrix(&self) -> Option<YCbCrMatrix>;
+    /// All three attachments at once.
+    fn color_attachments(&self) -> ColorAttachments {
+        ColorAttachments {
+            color_primaries: self.color_primaries(),
+            transfer_function: self.transfer_function(),
+            ycbcr_matrix: self.ycbcr_matrix(),
+        }
+    }
+}
+
+impl CVPixelBufferColorExt for CVPixelBuffer {
+    fn color_primaries(&self) -> Option<ColorPrimaries> {
+        attachment(self, KEY_COLOR_PRIMARIES).map(|v| ColorPrimaries::from_cv_string(&v))
+    }
+
+    fn transfer_function(&self) -> Option<TransferFunction> {
+        attachment(self, KEY_TRANSFER_FUNCTION).map(|v| TransferFunction::from_cv_string(&v))
+    }
+
+    fn ycbcr_matrix(&self) -> Option<YCbCrMatrix> {
+        attachment(self, KEY_YCBCR_MATRIX).map(|v| YCbCrMatrix::from_cv_string(&v))
+    }
+}
+
+fn attachment(buffer: &CVPixelBuffer, key: i32) -> Option<String> {
+    unsafe {
+        ffi_string_from_buffer(SMALL_BUFFER_SIZE, |buf, len| {
+            crate::cm::ffi::cv_pixel_buffer_get_color_attachment(buffer.as_ptr(), key, buf, len)
+        })
+    }
+}
@@ -0,0 +1,196 @@
+//! Cut a region out of a pixel buffer.
+//!
+//! Following one window inside a display capture, or zooming into part of
+//! the screen, means copying a sub-rectangle of every frame.
+//! [`PixelBufferCropExt::crop`] does that with vImage into a new
+//! `IOSurface`-backed buffer of the same pixel format, keeping the source's
+//! color attachments. Pass a [`CVPixelBufferPool`] of the crop's size to
+//! [`crop_with_pool`](PixelBufferCropExt::crop_with_pool) to reuse buffers
+//! across frames, or use
+//! [`crop_to_io_surface`](PixelBufferCropExt::crop_to_io_surface) to hand
+//! the result straight to Metal.
+//!
+//! Regions are in pixels, with the origin at the top-left. For 4:2:0
+//! formats every edge must fall on an even pixel so that chroma samples are
+//! not split.
+//!
+//! ```no_run
+//! use screencapturekit::cg::CGRect;
+//! use screencapturekit::cm::{CMSampleBuffer, CMSampleBufferExt};
+//! use screencapturekit::cv::crop::PixelBufferCropExt;
+//!
+//! fn on_frame(sample: &CMSampleBuffer) {
+//!     let Some(frame) = sample.image_buffer() else { return };
+//!     let window = frame.crop(CGRect::new(200.0, 100.0, 800.0, 600.0)).unwrap();
+//!     assert_eq!(window.width(), 800);
+//! }
+//! ```
+
+use std::ffi::c_void;
+
+use crate::cg::CGRect;
+use crate::cm::IOSurface;
+use crate::cv::frame_copy::bytes_per_element;
+use crate::cv::{CVPixelBuffer, CVPixelBufferPool};
+use crate::error::SCError;
+
+/// Copy a region of a pixel buffer into a new one.
+pub trait PixelBufferCropExt {
+    /// Copy the pixels inside `rect` into a new pixel buffer of the same
+    /// format.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidConfiguration`] if `rect` is not in whole
+    /// pixels, [`SCError::InvalidDimension`] if it is empty, reaches outside
+    /// the buffer or splits chroma samples, [`SCError::InvalidPixelFormat`]
+    /// for formats that cannot be cropped, or an error if allocating or
+    /// copying fails.
+    fn crop(&self, rect: CGRect) -> Result<CVPixelBuffer, SCError>;
+
+    /// Like [`crop`](Self::crop), taking the new buffer from `pool`.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`crop`](Self::crop); also
+    /// [`SCError::InvalidConfiguration`] if the pool makes buffers of a
+    /// different size or pixel format.
+    fn crop_with_pool(
+        &self,
+        rect: CGRect,
+        pool: &CVPixelBufferPool,
+    ) -> Result<CVPixelBuffer, SCError>;
+
+    /// Like [`crop`](Self::crop), returning the `IOSurface` that backs the
+    /// new buffer.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`crop`](Self::crop).
+    fn crop_to_io_surface(&self, rect: CGRect) -> Result<IOSurface, SCError>;
+}
+
+/// `rect` as whole pixels `[x, y, width, height]`, checked against
+/// `buffer`'s bounds. `what` names the region in error messages.
+pub(super) fn whole_pixel_region(
+    buffer: &CVPixelBuffer,
+    rect: CGRect,
+    what: &str,
+) -> Result<[usize; 4], SCError> {
+    let fields = [
+        ("x", rect.origin.x),
+        ("y", rect.origin.y),
+        ("width", rect.size.width),
+        ("height", rect.size.height),
+    ];
+    let mut region = [0usize; 4];
+    for (slot, (field, value)) in region.iter_mut().zip(fields) {
+        if !value.is_finite() || value < 0.0 || value.fract() != 0.0 {
+            return Err(SCError::invalid_config(format!(
+                "{what} {field} must be a whole, non-negative pixel count, got {value}"
+            )));
+        }
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        {
+            *slot = value as usize;
+        }
+    }
+    let [x, y, width, height] = region;
+    if width == 0 {
+        return Err(SCError::invalid_dimension(format!("{what} width"), width));
+    }
+    if height == 0 {
+        return Err(SCError::invalid_dimension(format!("{what} height"), height));
+    }
+    if x.saturating_add(width) > buffer.width() {
+        return Err(SCError::invalid_dimension(
+            format!("{what} right edge"),
+            x + width,
+        ));
+    }
+    if y.saturating_add(height) > buffer.height() {
+        return Err(SCError::invalid_dimension(
+            format!("{what} bottom edge"),
+            y + height,
+        ));
+    }
+    Ok(region)
+}
+
+/// `rect` as whole pixels, checked against `buffer`'s bounds and chroma
+/// subsampling.
+fn pixel_region(buffer: &CVPixelBuffer, rect: CGRect) -> Result<[usize; 4], SCError> {
+    let region = whole_pixel_region(buffer, rect, "crop")?;
+    let [x, y, width, height] = region;
+
+    let subsampled = buffer.plane_count() > 1 && buffer.width_of_plane(1) < buffer.width();
+    if subsampled {
+        for (field, value) in [
+            ("crop x", x),
+            ("crop y", y),
+            ("crop width", width),
+            ("crop height", height),
+        ] {
+            if value % 2 != 0 {
+                return Err(SCError::invalid_dimension(field, value));
+            }
+        }
+    }
+    Ok(region)
+}
+
+fn crop_into(
+    buffer: &CVPixelBuffer,
+    rect: CGRect,
+    pool: *const c_void,
+) -> Result<CVPixelBuffer, SCError> {
+    let [x, y, width, height] = pixel_region(buffer, rect)?;
+    let elements = bytes_per_element(buffer.pixel_format().into())?;
+    let mut status = 0;
+    let ptr = unsafe {
+        crate::ffi::sc_pixel_buffer_crop(
+            buffer.as_ptr(),
+            x,
+            y,
+            width,
+            height,
+            pool,
+            elements.as_ptr(),
+            elements.len(),
+            &mut status,
+        )
+    };
+    if ptr.is_null() {
+        return Err(match status {
+            -1 => SCError::buffer_lock_error("failed to lock pixel buffers for cropping"),
+            -2 => SCError::invalid_config(format!(
+                "pool does not make {width}x{height} buffers of the source's pixel format"
+            )),
+            -3 => SCError::InvalidBuffer("plane layout does not match the pixel format".into()),
+            code => SCError::os_error(code, "failed to crop pixel buffer"),
+        });
+    }
+    // `sc_pixel_buffer_crop` returns a +1 CVPixelBuffer, adopted here.
+    CVPixelBuffer::from_raw(ptr.cast_mut())
+        .ok_or_else(|| SCError::null_pointer("cropped pixel buffer"))
+}
+
+impl PixelBufferCropExt for CVPixelBuffer {
+    fn crop(&self, rect: CGRect) -> Result<CVPixelBuffer, SCError> {
+        crop_into(self, rect, std::ptr::null())
+    }
+
+    fn crop_with_pool(
+        &self,
+        rect: CGRect,
+        pool: &CVPixelBufferPool,
+    ) -> Result<CVPixelBuffer, SCError> {
+        crop_into(self, rect, pool.as_ptr())
+    }
+
+    fn crop_to_io_surface(&self, rect: CGRect) -> Result<IOSurface, SCError> {
+        self.crop(rect)?
+            .io_surface()
+            .ok_or_else(|| SCError::InvalidBuffer("cropped buffer has no IOSurface".into()))
+    }
+}
@@ -0,0 +1,477 @@
+//! Frame copies into caller-provided buffers.
+//!
+//! [`CGImageExt::rgba_data`](crate::screenshot_manager::CGImageExt::rgba_data)
+//! and friends allocate a fresh `Vec` for every frame. At 60 fps and 4K that
+//! is half a gigabyte of allocator traffic per second, which shows up in
+//! profiles long before the copy itself does. [`FrameCopyExt::copy_into`]
+//! writes into a slice you own instead, so the destination can come from an
+//! arena, a pool of reusable buffers or a mapped GPU upload heap.
+//!
+//! Pixels are copied in the source's native format — BGRA stays BGRA, 4:2:0
+//! YCbCr stays bi-planar — with rows laid out according to a [`RowLayout`].
+//! Planes are written back to back, luma first.
+//!
+//! | Format | Bytes per row, per plane |
+//! |--------|--------------------------|
+//! | BGRA, `l10r` | `width × 4` |
+//! | `RGhA` | `width × 8` |
+//! | 420v / 420f | `width`, then `chroma_width × 2` |
+//! | `xf44` | `width × 2`, then `width × 4` |
+//!
+//! [`CGImage`]s are always copied as BGRA, the order they render in without
+//! a channel swap.
+//!
+//! Most software encoders and image libraries want tightly packed rows and
+//! some want them bottom-up, as in BMP or an OpenGL texture upload.
+//! [`packed_data`](FrameCopyExt::packed_data) and
+//! [`copy_packed_into`](FrameCopyExt::copy_packed_into) strip the source's
+//! row padding in the same pass as an optional vertical flip, copying
+//! whole planes at once when there is no padding to strip.
+//!
+//! ```no_run
+//! use screencapturekit::cm::{CMSampleBuffer, CMSampleBufferExt};
+//! use screencapturekit::cv::frame_copy::{FrameCopyExt, RowLayout};
+//!
+//! fn on_frame(sample: &CMSampleBuffer, scratch: &mut Vec<u8>) {
+//!     let Some(buffer) = sample.image_buffer() else { return };
+//!     let Ok(size) = buffer.copy_size(RowLayout::Packed) else { return };
+//!     scratch.resize(size, 0); // no-op once the buffer has grown
+//!     buffer.copy_into(scratch, RowLayout::Packed).unwrap();
+//! }
+//! ```
+
+use crate::cm::{IOSurface, IOSurfaceLockOptions};
+use crate::cv::{CVPixelBuffer, CVPixelBufferLockFlags};
+use crate::error::SCError;
+use crate::stream::configuration::PixelFormat;
+use crate::CGImage;
+
+/// How rows are laid out in the destination buffer.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum RowLayout {
+    /// Rows follow each other with no padding.
+    #[default]
+    Packed,
+    /// Every row starts this many bytes after the previous one, for
+    /// destinations with alignment requirements (e.g. 256-byte rows for
+    /// texture uploads). Padding bytes are left untouched.
+    Strided(usize),
+}
+
+impl RowLayout {
+    /// Destination row stride for rows holding `row_bytes` bytes of pixels.
+    #[must_use]
+    pub const fn bytes_per_row(self, row_bytes: usize) -> usize {
+        match self {
+            Self::Packed => row_bytes,
+            Self::Strided(stride) => stride,
+        }
+    }
+}
+
+/// Order in which rows are written to the destination.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum RowOrder {
+    /// The top row comes first, as in the source.
+    #[default]
+    TopDown,
+    /// The bottom row comes first, flipping the image vertically. Each
+    /// plane is flipped on its own, so planar layouts stay valid.
+    BottomUp,
+}
+
+/// Copy pixel data into a caller-provided buffer.
+pub trait FrameCopyExt {
+    /// Number of bytes [`copy_into`](Self::copy_into) writes with `layout`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidPixelFormat`] for formats this module cannot
+    /// lay out, or [`SCError::InvalidDimension`] if a strided layout is
+    /// narrower than a row.
+    fn copy_size(&self, layout: RowLayout) -> Result<usize, SCError>;
+
+    /// Copy the pixels into `dest` and return the number of bytes written.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `dest` is shorter than
+    /// [`copy_size`](Self::copy_size), the stride is too small, the format is
+    /// unsupported, or the source cannot be locked.
+    fn copy_into(&self, dest: &mut [u8], layout: RowLayout) -> Result<usize, SCError> {
+        self.copy_rows_into(dest, layout, RowOrder::TopDown)
+    }
+
+    /// Like [`copy_into`](Self::copy_into), writing rows in `order`.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`copy_into`](Self::copy_into).
+    fn copy_rows_into(
+        &self,
+        dest: &mut [u8],
+        layout: RowLayout,
+        order: RowOrder,
+    ) -> Result<usize, SCError>;
+
+    /// Copy the pixels without row padding into `dest`, in `order`, and
+    /// return the number of bytes written.
+    ///
+    /// `dest` must hold at least `copy_size(RowLayout::Packed)` bytes.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`copy_into`](Self::copy_into).
+    fn copy_packed_into(&self, dest: &mut [u8], order: RowOrder) -> Result<usize, SCError> {
+        self.copy_rows_into(dest, RowLayout::Packed, order)
+    }
+
+    /// The pixels without row padding, in `order`, in a new `Vec`.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`copy_into`](Self::copy_into).
+    fn packed_data(&self, order: RowOrder) -> Result<Vec<u8>, SCError> {
+        let mut data = vec![0; self.copy_size(RowLayout::Packed)?];
+        let written = self.copy_packed_into(&mut data, order)?;
+        data.truncate(written);
+        Ok(data)
+    }
+}
+
+/// Geometry of one source plane.
+#[derive(Debug, Clone, Copy)]
+struct PlaneShape {
+    row_bytes: usize,
+    rows: usize,
+}
+
+/// One locked source plane.
+struct SourcePlane<'a> {
+    data: &'a [u8],
+    bytes_per_row: usize,
+    shape: PlaneShape,
+}
+
+/// Bytes per pixel of each plane, for the formats `ScreenCaptureKit` emits.
+pub(crate) fn bytes_per_element(format: PixelFormat) -> Result<&'static [usize], SCError> {
+    match format {
+        PixelFormat::BGRA | PixelFormat::l10r => Ok(&[4]),
+        PixelFormat::RGhA => Ok(&[8]),
+        PixelFormat::YCbCr_420v | PixelFormat::YCbCr_420f => Ok(&[1, 2]),
+        PixelFormat::xf44 => Ok(&[2, 4]),
+        other => Err(SCError::InvalidPixelFormat(format!(
+            "cannot lay out {other} pixels for copying"
+        ))),
+    }
+}
+
+/// Total destination size for `shapes`, checking that the stride fits.
+fn required_size(shapes: &[PlaneShape], layout: RowLayout) -> Result<usize, SCError> {
+    shapes.iter().try_fold(0usize, |total, shape| {
+        let stride = layout.bytes_per_row(shape.row_bytes);
+        if stride < shape.row_bytes {
+            return Err(SCError::invalid_dimension("bytes_per_row", stride));
+        }
+        stride
+            .checked_mul(shape.rows)
+            .and_then(|plane| total.checked_add(plane))
+            .ok_or_else(|| SCError::internal_error("frame copy size overflows usize"))
+    })
+}
+
+/// Copy `planes` into `dest` back to back. `dest` must already be checked
+/// against [`required_size`].
+fn copy_planes(
+    planes: &[SourcePlane<'_>],
+    dest: &mut [u8],
+    layout: RowLayout,
+    order: RowOrder,
+) -> usize {
+    let mut offset = 0;
+    for plane in planes {
+        let PlaneShape { row_bytes, rows } = plane.shape;
+        let stride = layout.bytes_per_row(row_bytes);
+        if order == RowOrder::TopDown && stride == row_bytes && plane.bytes_per_row == row_bytes {
+            let len = row_bytes * rows;
+            dest[offset..offset + len].copy_from_slice(&plane.data[..len]);
+        } else {
+            for row in 0..rows {
+                let src = &plane.data[row * plane.bytes_per_row..][..row_bytes];
+                let dest_row = match order {
+                    RowOrder::TopDown => row,
+                    RowOrder::BottomUp => rows - 1 - row,
+                };
+                dest[offset + dest_row * stride..][..row_bytes].copy_from_slice(src);
+            }
+        }
+        offset += stride * rows;
+    }
+    offset
+}
+
+/// Reverse the order of `rows` rows of `row_bytes` bytes, `stride` apart,
+/// at the start of `data`.
+fn flip_rows(data: &mut [u8], stride: usize, row_bytes: usize, rows: usize) {
+    for top in 0..rows / 2 {
+        let bottom = rows - 1 - top;
+        let (head, tail) = data.split_at_mut(bottom * stride);
+        head[top * stride..][..row_bytes].swap_with_slice(&mut tail[..row_bytes]);
+    }
+}
+
+fn check_dest(dest: &[u8], required: usize) -> Result<(), SCError> {
+    if dest.len() < required {
+        return Err(SCError::invalid_dimension("destination length", dest.len()));
+    }
+    Ok(())
+}
+
+fn check_source(plane: &SourcePlane<'_>) -> Result<(), SCError> {
+    let PlaneShape { row_bytes, rows } = plane.shape;
+    let fits = plane.bytes_per_row >= row_bytes
+        && rows.checked_mul(plane.bytes_per_row).is_some_and(|len| {
+            rows == 0 || len - plane.bytes_per_row + row_bytes <= plane.data.len()
+        });
+    if fits {
+        Ok(())
+    } else {
+        Err(SCError::InvalidBuffer(format!(
+            "source plane is smaller than {rows} rows of {row_bytes} bytes"
+        )))
+    }
+}
+
+fn copy_into_checked(
+    planes: &[SourcePlane<'_>],
+    dest: &mut [u8],
+    layout: RowLayout,
+    order: RowOrder,
+) -> Result<usize, SCError> {
+    let shapes: Vec<PlaneShape> = planes.iter().map(|p| p.shape).collect();
+    check_dest(dest, required_size(&shapes, layout)?)?;
+    for plane in planes {
+        check_source(plane)?;
+    }
+    let written = copy_planes(planes, dest, layout, order);
+    crate::instrument::buffer_copied("frame_copy", written);
+    Ok(written)
+}
+
+fn pixel_buffer_shapes(buffer: &CVPixelBuffer) -> Result<Vec<PlaneShape>, SCError> {
+    let elements = bytes_per_element(buffer.pixel_format().into())?;
+    if elements.len() == 1 {
+        return Ok(vec![PlaneShape {
+            row_bytes: buffer.width() * elements[0],
+            rows: buffer.height(),
+        }]);
+    }
+    let ptr = buffer.as_ptr();
+    // SAFETY: plane geometry queries don't require a lock.
+    unsafe {
+        if crate::cm::ffi::cv_pixel_buffer_get_plane_count(ptr) < elements.len() {
+            return Err(SCError::InvalidBuffer(format!(
+                "expected {} planes",
+                elements.len()
+            )));
+        }
+        Ok(elements
+            .iter()
+            .enumerate()
+            .map(|(index, bytes)| PlaneShape {
+                row_bytes: crate::cm::ffi::cv_pixel_buffer_get_width_of_plane(ptr, index) * bytes,
+                rows: crate::cm::ffi::cv_pixel_buffer_get_height_of_plane(ptr, index),
+            })
+            .collect())
+    }
+}
+
+impl FrameCopyExt for CVPixelBuffer {
+    fn copy_size(&self, layout: RowLayout) -> Result<usize, SCError> {
+        required_size(&pixel_buffer_shapes(self)?, layout)
+    }
+
+    fn copy_rows_into(
+        &self,
+        dest: &mut [u8],
+        layout: RowLayout,
+        order: RowOrder,
+    ) -> Result<usize, SCError> {
+        let shapes = pixel_buffer_shapes(self)?;
+        let guard = self
+            .lock(CVPixelBufferLockFlags::READ_ONLY)
+            .map_err(|status| SCError::buffer_lock_error(format!("{status:?}")))?;
+
+        if let [shape] = shapes[..] {
+            let plane = SourcePlane {
+                data: guard.as_slice(),
+                bytes_per_row: guard.bytes_per_row(),
+                shape,
+            };
+            return copy_into_checked(&[plane], dest, layout, order);
+        }
+
+        let ptr = self.as_ptr();
+        let mut planes = Vec::with_capacity(shapes.len());
+        for (index, shape) in shapes.into_iter().enumerate() {
+            // SAFETY: `guard` keeps the buffer locked, so the plane base
+            // address and its size stay valid until it is dropped below.
+            let plane = unsafe {
+                let base = crate::cm::ffi::cv_pixel_buffer_get_base_address_of_plane(ptr, index);
+                if base.is_null() {
+                    return Err(SCError::null_pointer(format!("plane {index} base address")));
+                }
+                let bytes_per_row =
+                    crate::cm::ffi::cv_pixel_buffer_get_bytes_per_row_of_plane(ptr, index);
+                SourcePlane {
+                    data: std::slice::from_raw_parts(base.cast::<u8>(), bytes_per_row * shape.rows),
+                    bytes_per_row,
+                    shape,
+                }
+            };
+            planes.push(plane);
+        }
+        let written = copy_into_checked(&planes, dest, layout, order);
+        drop(guard);
+        written
+    }
+}
+
+fn surface_shapes(surface: &IOSurface) -> Result<Vec<PlaneShape>, SCError> {
+    let elements = bytes_per_element(surface.pixel_format().into())?;
+    if elements.len() == 1 {
+        return Ok(vec![PlaneShape {
+            row_bytes: surface.width() * elements[0],
+            rows: surface.height(),
+        }]);
+    }
+    if surface.plane_count() < elements.len() {
+        return Err(SCError::InvalidBuffer(format!(
+            "expected {} planes",
+            elements.len()
+        )));
+    }
+    Ok(elements
+        .iter()
+        .enumerate()
+        .map(|(index, bytes)| PlaneShape {
+            row_bytes: surface.width_of_plane(index) * bytes,
+            rows: surface.height_of_plane(index),
+        })
+        .collect())
+}
+
+impl FrameCopyExt for IOSurface {
+    fn copy_size(&self, layout: RowLayout) -> Result<usize, SCError> {
+        required_size(&surface_shapes(self)?, layout)
+    }
+
+    fn copy_rows_into(
+        &self,
+        dest: &mut [u8],
+        layout: RowLayout,
+        order: RowOrder,
+    ) -> Result<usize, SCError> {
+        let shapes = surface_shapes(self)?;
+        let guard = self
+            .lock(IOSurfaceLockOptions::READ_ONLY)
+            .map_err(|status| SCError::buffer_lock_error(format!("{status:?}")))?;
+
+        let mut planes = Vec::with_capacity(shapes.len());
+        if let [shape] = shapes[..] {
+            let base = guard.as_ptr();
+            if base.is_null() {
+                return Err(SCError::null_pointer("IOSurface base address"));
+            }
+            let bytes_per_row = guard.bytes_per_row();
+            // SAFETY: the surface is locked for as long as `guard` lives.
+            let data = unsafe {
+                std::slice::from_raw_parts(base.cast::<u8>(), bytes_per_row * shape.rows)
+            };
+            planes.push(SourcePlane {
+                data,
+                bytes_per_row,
+                shape,
+            });
+        } else {
+            for (index, shape) in shapes.into_iter().enumerate() {
+                #[allow(clippy::cast_possible_wrap)]
+                let base = unsafe {
+                    crate::cm::ffi::iosurface_get_base_address_of_plane(
+                        self.as_ptr(),
+                        index as isize,
+                    )
+                };
+                if base.is_null() {
+                    return Err(SCError::null_pointer(format!("plane {index} base address")));
+                }
+                let bytes_per_row = self.bytes_per_row_of_plane(index);
+                // SAFETY: as above, the lock covers every plane.
+                let data = unsafe {
+                    std::slice::from_raw_parts(base.cast::<u8>(), bytes_per_row * shape.rows)
+                };
+                planes.push(SourcePlane {
+                    data,
+                    bytes_per_row,
+                    shape,
+                });
+            }
+        }
+        let written = copy_into_checked(&planes, dest, layout, order);
+        drop(guard);
+        written
+    }
+}
+
+impl FrameCopyExt for CGImage {
+    fn copy_size(&self, layout: RowLayout) -> Result<usize, SCError> {
+        required_size(&[image_shape(self)?], layout)
+    }
+
+    fn copy_rows_into(
+        &self,
+        dest: &mut [u8],
+        layout: RowLayout,
+        order: RowOrder,
+    ) -> Result<usize, SCError> {
+        let shape = image_shape(self)?;
+        let required = required_size(&[shape], layout)?;
+        check_dest(dest, required)?;
+        if required == 0 {
+            return Ok(0);
+        }
+
+        let stride = layout.bytes_per_row(shape.row_bytes);
+        // SAFETY: `dest` holds at least `required` bytes, checked above.
+        let written = unsafe {
+            crate::ffi::cgimage_render_bgra_into_strided(
+                self.as_ptr(),
+                dest.as_mut_ptr(),
+                dest.len(),
+                stride,
+            )
+        };
+        if written != required {
+            return Err(SCError::internal_error(format!(
+                "CGImage render wrote {written} of {required} bytes"
+            )));
+        }
+        if order == RowOrder::BottomUp {
+            flip_rows(dest, stride, shape.row_bytes, shape.rows);
+        }
+        crate::instrument::buffer_copied("frame_copy", written);
+        Ok(written)
+    }
+}
+
+/// A `CGImage` renders as one BGRA plane.
+fn image_shape(image: &CGImage) -> Result<PlaneShape, SCError> {
+    let row_bytes = image
+        .width()
+        .checked_mul(4)
+        .ok_or_else(|| SCError::internal_error("CGImage row size overflows usize"))?;
+    Ok(PlaneShape {
+        row_bytes,
+        rows: image.height(),
+    })
+}
@@ -0,0 +1,178 @@
+//! CPU decoding of `l10r` (10-bit ARGB) frames.
+//!
+//! [`PixelFormat::l10r`](crate::stream::configuration::PixelFormat::l10r)
+//! frames are packed little-endian `ARGB2101010` words: blue in bits 0–9,
+//! green in 10–19, red in 20–29 and a 2-bit alpha in 30–31. The cursor API
+//! reads 8-bit BGRA, so use these helpers to get at the actual pixel values.
+//!
+//! Values are returned as stored — no transfer function or color space
+//! conversion is applied. Tag the configuration with the color space you want
+//! (e.g. `kCGColorSpaceDisplayP3`) and interpret the output accordingly.
+//!
+//! ```no_run
+//! use screencapturekit::prelude::*;
+//! use screencapturekit::cv::l10r::L10rPixelBufferExt;
+//!
+//! # fn handle(sample: CMSampleBuffer) -> Result<(), SCError> {
+//! if let Some(buffer) = sample.image_buffer() {
+//!     let rgba = buffer.l10r_to_rgba16()?;
+//!     println!("first pixel: {:?}", &rgba[..4]);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::cv::{CVPixelBuffer, CVPixelBufferLockFlags};
+use crate::error::SCError;
+use crate::stream::configuration::PixelFormat;
+
+/// `'l10r'` as a `CVPixelFormatType`.
+pub const L10R_PIXEL_FORMAT: u32 = u32::from_be_bytes(*b"l10r");
+
+/// Bytes per packed `l10r` pixel.
+pub const BYTES_PER_PIXEL: usize = 4;
+
+/// Split a packed `ARGB2101010` word into its raw `[r, g, b, a]` components
+/// (10-bit color, 2-bit alpha).
+#[must_use]
+#[allow(clippy::cast_possible_truncation)]
+pub const fn unpack(word: u32) -> [u16; 4] {
+    [
+        ((word >> 20) & 0x3FF) as u16,
+        ((word >> 10) & 0x3FF) as u16,
+        (word & 0x3FF) as u16,
+        ((word >> 30) & 0x3) as u16,
+    ]
+}
+
+/// Decode one packed pixel to 16-bit `[r, g, b, a]`, scaling each component
+/// to the full `0..=65535` range.
+#[must_use]
+pub const fn decode_rgba16(word: u32) -> [u16; 4] {
+    let [r, g, b, a] = unpack(word);
+    [expand10(r), expand10(g), expand10(b), a * 0x5555]
+}
+
+/// Decode one packed pixel to normalized `[r, g, b, a]` in `0.0..=1.0`.
+#[must_use]
+pub fn decode_rgba_f32(word: u32) -> [f32; 4] {
+    let [r, g, b, a] = unpack(word);
+    [
+        f32::from(r) / 1023.0,
+        f32::from(g) / 1023.0,
+        f32::from(b) / 1023.0,
+        f32::from(a) / 3.0,
+    ]
+}
+
+/// Replicate the high bits into the low bits so 0x3FF maps to 0xFFFF.
+const fn expand10(v: u16) -> u16 {
+    (v << 6) | (v >> 4)
+}
+
+/// Decode a locked `l10r` plane into tightly packed 16-bit RGBA
+/// (`width * height * 4` values).
+///
+/// # Errors
+///
+/// Returns [`SCError::InvalidDimension`] if `bytes_per_row` is too small for
+/// `width`, or if `data` is shorter than `height` rows.
+pub fn to_rgba16(
+    data: &[u8],
+    width: usize,
+    height: usize,
+    bytes_per_row: usize,
+) -> Result<Vec<u16>, SCError> {
+    decode_plane(data, width, height, bytes_per_row, decode_rgba16)
+}
+
+/// Decode a locked `l10r` plane into tightly packed normalized float RGBA
+/// (`width * height * 4` values).
+///
+/// # Errors
+///
+/// Same as [`to_rgba16`].
+pub fn to_rgba_f32(
+    data: &[u8],
+    width: usize,
+    height: usize,
+    bytes_per_row: usize,
+) -> Result<Vec<f32>, SCError> {
+    decode_plane(data, width, height, bytes_per_row, decode_rgba_f32)
+}
+
+fn decode_plane<T>(
+    data: &[u8],
+    width: usize,
+    height: usize,
+    bytes_per_row: usize,
+    decode: impl Fn(u32) -> [T; 4],
+) -> Result<Vec<T>, SCError> {
+    let row_bytes = width * BYTES_PER_PIXEL;
+    if bytes_per_row < row_bytes {
+        return Err(SCError::invalid_dimension("bytes_per_row", bytes_per_row));
+    }
+    if height > 0 && data.len() < bytes_per_row * (height - 1) + row_bytes {
+        return Err(SCError::invalid_dimension("data length", data.len()));
+    }
+
+    crate::instrument::buffer_copied("l10r", row_bytes * height);
+    let mut out = Vec::with_capacity(width * height * 4);
+    for y in 0..height {
+        let row = &data[y * bytes_per_row..y * bytes_per_row + row_bytes];
+        for px in row.chunks_exact(BYTES_PER_PIXEL) {
+            out.extend(decode(u32::from_le_bytes([px[0], px[1], px[2], px[3]])));
+        }
+    }
+    Ok(out)
+}
+
+/// Decode `l10r` pixel buffers on the CPU.
+pub trait L10rPixelBufferExt {
+    /// Lock the buffer read-only and decode it to 16-bit RGBA.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidPixelFormat`] if the buffer is not `l10r`,
+    /// or [`SCError::BufferLockError`] if it cannot be locked.
+    fn l10r_to_rgba16(&self) -> Result<Vec<u16>, SCError>;
+
+    /// Lock the buffer read-only and decode it to normalized float RGBA.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`l10r_to_rgba16`](Self::l10r_to_rgba16).
+    fn l10r_to_rgba_f32(&self) -> Result<Vec<f32>, SCError>;
+}
+
+impl L10rPixelBufferExt for CVPixelBuffer {
+    fn l10r_to_rgba16(&self) -> Result<Vec<u16>, SCError> {
+        decode_buffer(self, to_rgba16)
+    }
+
+    fn l10r_to_rgba_f32(&self) -> Result<Vec<f32>, SCError> {
+        decode_buffer(self, to_rgba_f32)
+    }
+}
+
+/// A plane decoder such as [`to_rgba16`]: data, width, height, bytes per row.
+type PlaneDecoder<T> = fn(&[u8], usize, usize, usize) -> Result<Vec<T>, SCError>;
+
+fn decode_buffer<T>(buffer: &CVPixelBuffer, decode: PlaneDecoder<T>) -> Result<Vec<T>, SCError> {
+    let format = buffer.pixel_format();
+    if format != L10R_PIXEL_FORMAT {
+        return Err(SCError::InvalidPixelFormat(format!(
+            "expected an l10r pixel buffer, got {}",
+            PixelFormat::from(format)
+        )));
+    }
+    let guard = buffer
+        .lock(CVPixelBufferLockFlags::READ_ONLY)
+        .map_err(|status| SCError::buffer_lock_error(format!("{status:?}")))?;
+    decode(
+        guard.as_slice(),
+        guard.width(),
+        guard.height(),
+        guard.bytes_per_row(),
+    )
+}
@@ -1,5 +1,13 @@
 //! `CoreVideo` types — re-exported from `apple-cf`.
 
+pub mod cg_image;
+pub mod color;
+pub mod crop;
+pub mod frame_copy;
+pub mod l10r;
+pub mod pixel_reader;
+pub mod sampling;
+
 pub use apple_cf::cv::{
     CVPixelBuffer, CVPixelBufferLockFlags, CVPixelBufferLockGuard, CVPixelBufferPool,
     PixelBufferCursorExt,
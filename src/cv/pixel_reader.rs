@@ -0,0 +1,257 @@
+//! Format-aware pixel reads.
+//!
+//! [`PixelBufferCursorExt::read_pixel`](crate::cv::PixelBufferCursorExt) hands
+//! back the next four raw bytes, which is only a pixel when the stream is
+//! BGRA. [`PixelReader`] looks at the buffer's pixel format first and decodes
+//! accordingly, returning normalized RGBA for every format it understands and
+//! [`SCError::InvalidPixelFormat`] for the rest:
+//!
+//! | Format | Decoding |
+//! |--------|----------|
+//! | [`PixelFormat::BGRA`] | 8-bit BGRA |
+//! | [`PixelFormat::l10r`] | 10-bit ARGB2101010, see [`l10r`](super::l10r) |
+//! | [`PixelFormat::YCbCr_420v`] / [`PixelFormat::YCbCr_420f`] | Bi-planar 4:2:0, BT.709 matrix |
+//!
+//! YCbCr is converted with the BT.709 matrix, which is what `ScreenCaptureKit`
+//! uses unless the configuration sets a different `color_matrix`.
+//!
+//! ```no_run
+//! use screencapturekit::prelude::*;
+//! use screencapturekit::cv::pixel_reader::PixelBufferReadExt;
+//!
+//! # fn handle(sample: CMSampleBuffer) -> Result<(), SCError> {
+//! if let Some(buffer) = sample.image_buffer() {
+//!     let reader = buffer.pixel_reader()?;
+//!     let [r, g, b, a] = reader.read_pixel_rgba(reader.width() / 2, reader.height() / 2)?;
+//!     println!("center: {r:.3} {g:.3} {b:.3} {a:.3}");
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+
+use super::l10r;
+use crate::cv::{CVPixelBuffer, CVPixelBufferLockFlags, CVPixelBufferLockGuard};
+use crate::error::SCError;
+use crate::stream::configuration::PixelFormat;
+
+/// One locked plane of the buffer.
+#[derive(Clone, Copy)]
+struct Plane<'a> {
+    data: &'a [u8],
+    bytes_per_row: usize,
+}
+
+/// A read-only lock on a [`CVPixelBuffer`] that decodes pixels according to
+/// the buffer's pixel format.
+///
+/// The buffer stays locked until the reader is dropped.
+pub struct PixelReader<'a> {
+    format: PixelFormat,
+    width: usize,
+    height: usize,
+    planes: [Plane<'a>; 2],
+    _guard: CVPixelBufferLockGuard<'a>,
+}
+
+impl fmt::Debug for PixelReader<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PixelReader")
+            .field("format", &self.format)
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a> PixelReader<'a> {
+    /// Lock `buffer` read-only and prepare to decode it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidPixelFormat`] if the buffer's format is not
+    /// one of the supported formats, or [`SCError::BufferLockError`] if it
+    /// cannot be locked.
+    pub fn new(buffer: &'a CVPixelBuffer) -> Result<Self, SCError> {
+        let format = PixelFormat::from(buffer.pixel_format());
+        let plane_count = match format {
+            PixelFormat::BGRA | PixelFormat::l10r => 1,
+            PixelFormat::YCbCr_420v | PixelFormat::YCbCr_420f => 2,
+            other => {
+                return Err(SCError::InvalidPixelFormat(format!(
+                    "cannot decode {other} pixels on the CPU"
+                )));
+            }
+        };
+
+        let guard = buffer
+            .lock(CVPixelBufferLockFlags::READ_ONLY)
+            .map_err(|status| SCError::buffer_lock_error(format!("{status:?}")))?;
+
+        let width = guard.width();
+        let height = guard.height();
+        let planes = if plane_count == 1 {
+            let data = guard.as_slice();
+            // SAFETY: the slice borrows the locked base address, which stays
+            // valid for as long as `guard` (stored alongside it) is alive.
+            let data = unsafe { std::slice::from_raw_parts(data.as_ptr(), data.len()) };
+            let plane = Plane {
+                data,
+                bytes_per_row: guard.bytes_per_row(),
+            };
+            [plane, plane]
+        } else {
+            [plane(buffer, 0)?, plane(buffer, 1)?]
+        };
+
+        Ok(Self {
+            format,
+            width,
+            height,
+            planes,
+            _guard: guard,
+        })
+    }
+
+    /// The buffer's pixel format.
+    #[must_use]
+    pub const fn pixel_format(&self) -> PixelFormat {
+        self.format
+    }
+
+    /// Width in pixels.
+    #[must_use]
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Height in pixels.
+    #[must_use]
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Read the pixel at (`x`, `y`) as normalized `[r, g, b, a]` in `0.0..=1.0`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidDimension`] if the coordinates are outside
+    /// the buffer.
+    pub fn read_pixel_rgba(&self, x: usize, y: usize) -> Result<[f32; 4], SCError> {
+        if x >= self.width {
+            return Err(SCError::invalid_dimension("x", x));
+        }
+        if y >= self.height {
+            return Err(SCError::invalid_dimension("y", y));
+        }
+
+        let [main, chroma] = self.planes;
+        match self.format {
+            PixelFormat::BGRA => {
+                let i = y * main.bytes_per_row + x * 4;
+                let px = main.data.get(i..i + 4).ok_or_else(|| out_of_bounds(i))?;
+                Ok([
+                    f32::from(px[2]) / 255.0,
+                    f32::from(px[1]) / 255.0,
+                    f32::from(px[0]) / 255.0,
+                    f32::from(px[3]) / 255.0,
+                ])
+            }
+            PixelFormat::l10r => {
+                let i = y * main.bytes_per_row + x * l10r::BYTES_PER_PIXEL;
+                let px = main.data.get(i..i + 4).ok_or_else(|| out_of_bounds(i))?;
+                Ok(l10r::decode_rgba_f32(u32::from_le_bytes([
+                    px[0], px[1], px[2], px[3],
+                ])))
+            }
+            _ => {
+                let yi = y * main.bytes_per_row + x;
+                let ci = (y / 2) * chroma.bytes_per_row + (x / 2) * 2;
+                let luma = *main.data.get(yi).ok_or_else(|| out_of_bounds(yi))?;
+                let cbcr = chroma
+                    .data
+                    .get(ci..ci + 2)
+                    .ok_or_else(|| out_of_bounds(ci))?;
+                let full_range = self.format == PixelFormat::YCbCr_420f;
+                Ok(ycbcr_to_rgba(luma, cbcr[0], cbcr[1], full_range))
+            }
+        }
+    }
+}
+
+/// Format-aware reads on [`CVPixelBuffer`].
+pub trait PixelBufferReadExt {
+    /// Lock the buffer and return a [`PixelReader`] for it.
+    ///
+    /// # Errors
+    ///
+    /// See [`PixelReader::new`].
+    fn pixel_reader(&self) -> Result<PixelReader<'_>, SCError>;
+
+    /// Read a single pixel as normalized RGBA.
+    ///
+    /// Locks and unlocks the buffer on every call; use
+    /// [`pixel_reader`](Self::pixel_reader) when reading many pixels.
+    ///
+    /// # Errors
+    ///
+    /// See [`PixelReader::new`] and [`PixelReader::read_pixel_rgba`].
+    fn read_pixel_rgba(&self, x: usize, y: usize) -> Result<[f32; 4], SCError>;
+}
+
+impl PixelBufferReadExt for CVPixelBuffer {
+    fn pixel_reader(&self) -> Result<PixelReader<'_>, SCError> {
+        PixelReader::new(self)
+    }
+
+    fn read_pixel_rgba(&self, x: usize, y: usize) -> Result<[f32; 4], SCError> {
+        PixelReader::new(self)?.read_pixel_rgba(x, y)
+    }
+}
+
+/// Borrow plane `index` of a locked bi-planar buffer.
+fn plane(buffer: &CVPixelBuffer, index: usize) -> Result<Plane<'_>, SCError> {
+    // SAFETY: the caller holds a lock on `buffer`, so the plane base address
+    // and its geometry are stable until the lock is released.
+    unsafe {
+        let ptr = buffer.as_ptr();
+        let base = crate::cm::ffi::cv_pixel_buffer_get_base_address_of_plane(ptr, index);
+        if base.is_null() {
+            return Err(SCError::null_pointer(format!("plane {index} base address")));
+        }
+        let bytes_per_row = crate::cm::ffi::cv_pixel_buffer_get_bytes_per_row_of_plane(ptr, index);
+        let rows = crate::cm::ffi::cv_pixel_buffer_get_height_of_plane(ptr, index);
+        Ok(Plane {
+            data: std::slice::from_raw_parts(base.cast::<u8>(), bytes_per_row * rows),
+            bytes_per_row,
+        })
+    }
+}
+
+fn out_of_bounds(offset: usize) -> SCError {
+    SCError::InvalidBuffer(format!(
+        "pixel offset {offset} is past the end of the plane"
+    ))
+}
+
+/// BT.709 `Y'CbCr` → `R'G'B'`, clamped to `0.0..=1.0`.
+fn ycbcr_to_rgba(y: u8, cb: u8, cr: u8, full_range: bool) -> [f32; 4] {
+    let (y, cb, cr) = if full_range {
+        (
+            f32::from(y) / 255.0,
+            (f32::from(cb) - 128.0) / 255.0,
+            (f32::from(cr) - 128.0) / 255.0,
+        )
+    } else {
+        (
+            (f32::from(y) - 16.0) / 219.0,
+            (f32::from(cb) - 128.0) / 224.0,
+            (f32::from(cr) - 128.0) / 224.0,
+        )
+    };
+    let r = 1.5748f32.mul_add(cr, y);
+    let g = 0.4681f32.mul_add(-cr, 0.1873f32.mul_add(-cb, y));
+    let b = 1.8556f32.mul_add(cb, y);
+    [r.clamp(0.0, 1.0), g.clamp(0.0, 1.0), b.clamp(0.0, 1.0), 1.0]
+}
@@ -0,0 +1,279 @@
+//! Color statistics of a frame without building an image.
+//!
+//! Bias-lighting apps want the average color along each screen edge, and UI
+//! tests want to check that a region turned red or went dark. Both only
+//! need a few numbers per frame. [`PixelBufferSamplingExt`] computes them
+//! with vImage straight from the locked pixel buffer, in any format
+//! [`PixelReader`](super::pixel_reader::PixelReader) understands:
+//!
+//! - [`average_color`](PixelBufferSamplingExt::average_color) of a region,
+//!   from per-channel histograms
+//! - [`dominant_colors`](PixelBufferSamplingExt::dominant_colors), from a
+//!   downscaled copy of the frame
+//! - [`luminance_histogram`](PixelBufferSamplingExt::luminance_histogram),
+//!   256 bins of BT.709 luma
+//!
+//! Colors are normalized RGBA in `0.0..=1.0`, converted to sRGB. Regions are
+//! in pixels with the origin at the top-left.
+//!
+//! ```no_run
+//! use screencapturekit::cg::CGRect;
+//! use screencapturekit::cm::{CMSampleBuffer, CMSampleBufferExt};
+//! use screencapturekit::cv::sampling::PixelBufferSamplingExt;
+//!
+//! fn on_frame(sample: &CMSampleBuffer) {
+//!     let Some(frame) = sample.image_buffer() else { return };
+//!     #[allow(clippy::cast_precision_loss)]
+//!     let top_edge = CGRect::new(0.0, 0.0, frame.width() as f64, 32.0);
+//!     let [r, g, b, _] = frame.average_color(top_edge).unwrap();
+//!     println!("top edge: {r:.2} {g:.2} {b:.2}");
+//!
+//!     let histogram = frame.luminance_histogram().unwrap();
+//!     if histogram.mean() < 0.05 {
+//!         println!("screen is dark");
+//!     }
+//! }
+//! ```
+
+use std::collections::HashMap;
+
+use super::crop::whole_pixel_region;
+use crate::cg::CGRect;
+use crate::cv::CVPixelBuffer;
+use crate::error::SCError;
+
+/// Longest side of the copy [`dominant_colors`](PixelBufferSamplingExt::dominant_colors)
+/// clusters.
+const THUMBNAIL_SIDE: usize = 64;
+
+/// Bits kept per channel when grouping similar colors.
+const BUCKET_BITS: u32 = 4;
+
+/// A color that covers part of a frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DominantColor {
+    /// Average normalized RGBA of the pixels in this group.
+    pub color: [f32; 4],
+    /// Share of the frame's pixels in this group, `0.0..=1.0`.
+    pub fraction: f32,
+}
+
+/// Pixel counts for each of 256 luma levels.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LuminanceHistogram {
+    bins: [u64; 256],
+}
+
+impl LuminanceHistogram {
+    /// Pixel count per luma level, darkest first.
+    #[must_use]
+    pub const fn bins(&self) -> &[u64; 256] {
+        &self.bins
+    }
+
+    /// Number of pixels counted.
+    #[must_use]
+    pub fn total(&self) -> u64 {
+        self.bins.iter().sum()
+    }
+
+    /// Mean luma, `0.0..=1.0`.
+    #[must_use]
+    pub fn mean(&self) -> f32 {
+        channel_mean(&self.bins)
+    }
+
+    /// The luma level, `0.0..=1.0`, below which `fraction` of the pixels
+    /// fall.
+    #[must_use]
+    pub fn percentile(&self, fraction: f32) -> f32 {
+        #[allow(
+            clippy::cast_precision_loss,
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss
+        )]
+        let target = (self.total() as f64 * f64::from(fraction.clamp(0.0, 1.0))).ceil() as u64;
+        let mut seen = 0;
+        for (level, count) in self.bins.iter().enumerate() {
+            seen += count;
+            if seen >= target.max(1) {
+                return f32::from(u8::try_from(level).unwrap_or(u8::MAX)) / 255.0;
+            }
+        }
+        1.0
+    }
+}
+
+/// Color statistics over a [`CVPixelBuffer`].
+pub trait PixelBufferSamplingExt {
+    /// Average color of the pixels inside `rect`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidConfiguration`] if `rect` is not in whole
+    /// pixels, [`SCError::InvalidDimension`] if it is empty or reaches
+    /// outside the buffer, or an error if the buffer can't be converted.
+    fn average_color(&self, rect: CGRect) -> Result<[f32; 4], SCError>;
+
+    /// Up to `count` of the most common colors in the frame, most common
+    /// first.
+    ///
+    /// Similar colors are grouped together, so a gradient or anti-aliased
+    /// text counts towards the color it is closest to.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the buffer can't be converted.
+    fn dominant_colors(&self, count: usize) -> Result<Vec<DominantColor>, SCError>;
+
+    /// Luma histogram of the whole frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the buffer can't be converted.
+    fn luminance_histogram(&self) -> Result<LuminanceHistogram, SCError>;
+
+    /// Luma histogram of the pixels inside `rect`.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`average_color`](Self::average_color).
+    fn luminance_histogram_in(&self, rect: CGRect) -> Result<LuminanceHistogram, SCError>;
+}
+
+fn check(status: i32, what: &str) -> Result<(), SCError> {
+    match status {
+        0 => Ok(()),
+        -1 => Err(SCError::InvalidPixelFormat(format!(
+            "cannot convert pixel buffer to compute {what}"
+        ))),
+        code => Err(SCError::os_error(code, format!("failed to compute {what}"))),
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn full_frame(buffer: &CVPixelBuffer) -> CGRect {
+    CGRect::new(0.0, 0.0, buffer.width() as f64, buffer.height() as f64)
+}
+
+/// Mean of a 256-bin histogram, normalized to `0.0..=1.0`.
+fn channel_mean(bins: &[u64]) -> f32 {
+    let total: u64 = bins.iter().sum();
+    if total == 0 {
+        return 0.0;
+    }
+    let weighted: u64 = bins
+        .iter()
+        .zip(0u64..)
+        .map(|(count, level)| count * level)
+        .sum();
+    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+    {
+        (weighted as f64 / total as f64 / 255.0) as f32
+    }
+}
+
+impl PixelBufferSamplingExt for CVPixelBuffer {
+    fn average_color(&self, rect: CGRect) -> Result<[f32; 4], SCError> {
+        let [x, y, width, height] = whole_pixel_region(self, rect, "sample")?;
+        let mut histograms = vec![0u64; 4 * 256];
+        check(
+            unsafe {
+                crate::ffi::sc_pixel_buffer_rgba_histograms(
+                    self.as_ptr(),
+                    x,
+                    y,
+                    width,
+                    height,
+                    histograms.as_mut_ptr(),
+                )
+            },
+            "average color",
+        )?;
+        let mut color = [0.0; 4];
+        for (channel, bins) in color.iter_mut().zip(histograms.chunks_exact(256)) {
+            *channel = channel_mean(bins);
+        }
+        Ok(color)
+    }
+
+    fn dominant_colors(&self, count: usize) -> Result<Vec<DominantColor>, SCError> {
+        let (width, height) = (self.width(), self.height());
+        if count == 0 || width == 0 || height == 0 {
+            return Ok(Vec::new());
+        }
+        let scale = width.max(height).div_ceil(THUMBNAIL_SIDE).max(1);
+        let (thumb_width, thumb_height) = (width.div_ceil(scale), height.div_ceil(scale));
+        let mut pixels = vec![0u8; thumb_width * thumb_height * 4];
+        check(
+            unsafe {
+                crate::ffi::sc_pixel_buffer_rgba_thumbnail(
+                    self.as_ptr(),
+                    0,
+                    0,
+                    width,
+                    height,
+                    thumb_width,
+                    thumb_height,
+                    pixels.as_mut_ptr(),
+                )
+            },
+            "dominant colors",
+        )?;
+
+        // Per bucket: pixel count and channel sums.
+        let mut buckets: HashMap<u32, (u64, [u64; 4])> = HashMap::new();
+        let shift = 8 - BUCKET_BITS;
+        for pixel in pixels.chunks_exact(4) {
+            let key = (u32::from(pixel[0] >> shift) << (2 * BUCKET_BITS))
+                | (u32::from(pixel[1] >> shift) << BUCKET_BITS)
+                | u32::from(pixel[2] >> shift);
+            let (n, sums) = buckets.entry(key).or_default();
+            *n += 1;
+            for (sum, value) in sums.iter_mut().zip(pixel) {
+                *sum += u64::from(*value);
+            }
+        }
+
+        let mut ranked: Vec<_> = buckets.into_iter().collect();
+        ranked.sort_unstable_by(|(ka, (na, _)), (kb, (nb, _))| nb.cmp(na).then(ka.cmp(kb)));
+        #[allow(clippy::cast_precision_loss)]
+        let total = (thumb_width * thumb_height) as f32;
+        #[allow(clippy::cast_precision_loss)]
+        let colors = ranked
+            .into_iter()
+            .take(count)
+            .map(|(_, (n, sums))| DominantColor {
+                color: sums.map(|sum| sum as f32 / n as f32 / 255.0),
+                fraction: n as f32 / total,
+            })
+            .collect();
+        Ok(colors)
+    }
+
+    fn luminance_histogram(&self) -> Result<LuminanceHistogram, SCError> {
+        if self.width() == 0 || self.height() == 0 {
+            return Ok(LuminanceHistogram { bins: [0; 256] });
+        }
+        self.luminance_histogram_in(full_frame(self))
+    }
+
+    fn luminance_histogram_in(&self, rect: CGRect) -> Result<LuminanceHistogram, SCError> {
+        let [x, y, width, height] = whole_pixel_region(self, rect, "sample")?;
+        let mut bins = [0u64; 256];
+        check(
+            unsafe {
+                crate::ffi::sc_pixel_buffer_luma_histogram(
+                    self.as_ptr(),
+                    x,
+                    y,
+                    width,
+                    height,
+                    bins.as_mut_ptr(),
+                )
+            },
+            "luminance histogram",
+        )?;
+        Ok(LuminanceHistogram { bins })
+    }
+}
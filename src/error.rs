@@ -8,6 +8,8 @@
 //! - [`SCError`] - The main error type for all `ScreenCaptureKit` operations
 //! - [`SCResult<T>`] - Type alias for `Result<T, SCError>`
 //! - [`SCStreamErrorCode`] - Specific error codes from `ScreenCaptureKit` framework
+//! - [`NSErrorInfo`] - Domain, code and `userInfo` of a framework `NSError`
+//! - [`RecoveryHint`] - What to tell the user, from [`SCError::recovery_hint`]
 //!
 //! ## Error Handling Example
 //!
@@ -37,4 +39,8 @@
 //! }
 //! ```
 
-pub use crate::utils::error::{SCError, SCResult, SCStreamErrorCode, SC_STREAM_ERROR_DOMAIN};
+pub use crate::utils::error::{
+    CaptureStartDiagnostics, NSErrorInfo, SCError, SCResult, SCStreamErrorCode,
+    BRIDGE_ERROR_DOMAIN, SC_STREAM_ERROR_DOMAIN,
+};
+pub use crate::utils::recovery::{RecoveryHint, SettingsPane};
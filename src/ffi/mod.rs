@@ -115,6 +115,12 @@ extern "C" {
     pub fn sc_verify_ffi_layout() -> bool;
 }
 
+#[link(name = "CoreGraphics", kind = "framework")]
+extern "C" {
+    /// Whether the process has screen recording permission, without prompting
+    pub fn CGPreflightScreenCaptureAccess() -> bool;
+}
+
 // MARK: - SCShareableContent
 extern "C" {
     /// Synchronous blocking call to get shareable content.
@@ -265,6 +271,21 @@ extern "C" {
     pub fn sc_window_is_on_screen(window: *const c_void) -> bool;
     pub fn sc_window_get_owning_application(window: *const c_void) -> *const c_void;
     pub fn sc_window_is_active(window: *const c_void) -> bool;
+    /// Look up a window's current frame and title by ID, without fetching
+    /// shareable content. Returns false if the window is gone. A non-null
+    /// title must be freed with `sc_free_string`.
+    pub fn sc_window_copy_live_info(
+        window_id: u32,
+        x: *mut f64,
+        y: *mut f64,
+        width: *mut f64,
+        height: *mut f64,
+        title: *mut *mut i8,
+    ) -> bool;
+    /// Copy up to `capacity` on-screen Notification Center windows: IDs
+    /// into `ids` and x, y, width, height quadruples into `rects`. Returns
+    /// the number of windows, which may exceed `capacity`.
+    pub fn sc_notification_windows_copy(ids: *mut u32, rects: *mut f64, capacity: isize) -> isize;
 }
 
 // MARK: - SCRunningApplication
@@ -294,11 +315,23 @@ extern "C" {
     pub fn sc_free_string(str: *mut i8);
 }
 
+// MARK: - NSError
+// Errors are lent to Rust callbacks as +0 pointers, valid only for the call.
+extern "C" {
+    pub fn sc_error_copy_domain(error: *const c_void) -> *mut i8;
+    pub fn sc_error_get_code(error: *const c_void) -> i64;
+    pub fn sc_error_copy_description(error: *const c_void) -> *mut i8;
+    pub fn sc_error_copy_user_info_string(error: *const c_void, key: *const i8) -> *mut i8;
+    pub fn sc_error_get_underlying(error: *const c_void) -> *const c_void;
+}
+
 // MARK: - SCStreamConfiguration
 extern "C" {
     pub fn sc_stream_configuration_create() -> *const c_void;
     pub fn sc_stream_configuration_retain(config: *const c_void) -> *const c_void;
     pub fn sc_stream_configuration_release(config: *const c_void);
+    /// Property-by-property copy into a new +1 retained configuration.
+    pub fn sc_stream_configuration_copy(config: *const c_void) -> *const c_void;
 
     pub fn sc_stream_configuration_set_width(config: *const c_void, width: isize);
     pub fn sc_stream_configuration_get_width(config: *const c_void) -> isize;
@@ -549,11 +582,17 @@ extern "C" {
         filter: *const c_void,
         config: *const c_void,
         context: *mut c_void,
-        error_callback: extern "C" fn(*mut c_void, i32, *const i8),
+        error_callback: extern "C" fn(*mut c_void, *const c_void),
         sample_callback: extern "C" fn(*mut c_void, *const c_void, i32),
         context_retain: extern "C" fn(*mut c_void),
         context_release: extern "C" fn(*mut c_void),
     ) -> *const c_void;
+    /// `callback` receives the stream context and whether a video effect
+    /// started (`true`) or stopped.
+    pub fn sc_stream_set_video_effect_callback(
+        stream: *const c_void,
+        callback: extern "C" fn(*mut c_void, bool),
+    );
     pub fn sc_stream_add_stream_output(stream: *const c_void, output_type: i32) -> bool;
     pub fn sc_stream_add_stream_output_with_queue(
         stream: *const c_void,
@@ -561,38 +600,55 @@ extern "C" {
         dispatch_queue: *const c_void,
     ) -> bool;
     pub fn sc_stream_remove_stream_output(stream: *const c_void, output_type: i32) -> bool;
+    /// Move `output_type` to `dispatch_queue`, or remember it for the next
+    /// registration of that type.
+    pub fn sc_stream_set_output_queue(
+        stream: *const c_void,
+        output_type: i32,
+        dispatch_queue: *const c_void,
+    ) -> bool;
+    /// Label of the queue delivering `output_type`; false if none is registered.
+    pub fn sc_stream_get_output_queue_label(
+        stream: *const c_void,
+        output_type: i32,
+        buffer: *mut i8,
+        buffer_size: isize,
+    ) -> bool;
     pub fn sc_stream_start_capture(
         stream: *const c_void,
         context: *mut c_void,
-        callback: extern "C" fn(*mut c_void, bool, *const i8),
+        callback: extern "C" fn(*mut c_void, bool, *const c_void),
     );
     pub fn sc_stream_stop_capture(
         stream: *const c_void,
         context: *mut c_void,
-        callback: extern "C" fn(*mut c_void, bool, *const i8),
+        callback: extern "C" fn(*mut c_void, bool, *const c_void),
     );
     pub fn sc_stream_update_configuration(
         stream: *const c_void,
         config: *const c_void,
         context: *mut c_void,
-        callback: extern "C" fn(*mut c_void, bool, *const i8),
+        callback: extern "C" fn(*mut c_void, bool, *const c_void),
     );
     pub fn sc_stream_update_content_filter(
         stream: *const c_void,
         filter: *const c_void,
         context: *mut c_void,
-        callback: extern "C" fn(*mut c_void, bool, *const i8),
+        callback: extern "C" fn(*mut c_void, bool, *const c_void),
     );
+    /// Attach a recording output. The callback receives `(context, success,
+    /// error)`; `error` is a borrowed `NSError` (read it with `sc_error_*`)
+    /// and is null on success.
     pub fn sc_stream_add_recording_output(
         stream: *const c_void,
         recording_output: *const c_void,
-        callback: extern "C" fn(*mut c_void, bool, *const i8),
+        callback: extern "C" fn(*mut c_void, bool, *const c_void),
         context: *mut c_void,
     );
     pub fn sc_stream_remove_recording_output(
         stream: *const c_void,
         recording_output: *const c_void,
-        callback: extern "C" fn(*mut c_void, bool, *const i8),
+        callback: extern "C" fn(*mut c_void, bool, *const c_void),
         context: *mut c_void,
     );
     pub fn sc_stream_retain(stream: *const c_void) -> *const c_void;
@@ -600,6 +656,19 @@ extern "C" {
 
     // macOS 13.0+ - synchronizationClock
     pub fn sc_stream_get_synchronization_clock(stream: *const c_void) -> *const c_void;
+
+    /// Seconds since the Unix epoch at which capture started, negative if
+    /// the stream is not capturing.
+    pub fn sc_stream_get_capture_start_time(stream: *const c_void) -> f64;
+    /// Invokes `callback(stream, context, user_data)` for each capturing
+    /// stream of this process. `stream` is a new +1 handle, balanced by
+    /// `sc_stream_release`; `context` is the stream's `StreamContext`,
+    /// alive for the duration of the callback.
+    pub fn sc_stream_copy_active_streams(
+        callback: extern "C" fn(*const c_void, *mut c_void, *mut c_void),
+        user_data: *mut c_void,
+    );
+    pub fn sc_stream_get_active_count() -> isize;
 }
 
 // MARK: - Dispatch Queue
@@ -857,6 +926,35 @@ extern "C" {
         dest_capacity: usize,
         dest_bytes_per_row: usize,
     ) -> usize;
+    /// Crop to a pixel-space rect (origin top-left). Returns a +1 retained
+    /// `CGImage` sharing the source's backing store, or null if the rect
+    /// does not intersect the image.
+    pub fn cgimage_create_cropped(
+        image: *const c_void,
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+    ) -> *const c_void;
+    /// Scale to `width` x `height` with high-quality interpolation. Returns a
+    /// +1 retained BGRA `CGImage`, or null on failure.
+    pub fn cgimage_create_resized(
+        image: *const c_void,
+        width: usize,
+        height: usize,
+    ) -> *const c_void;
+    pub fn cgimage_get_bits_per_component(image: *const c_void) -> usize;
+    pub fn cgimage_get_bits_per_pixel(image: *const c_void) -> usize;
+    pub fn cgimage_get_bytes_per_row(image: *const c_void) -> usize;
+    /// Raw `CGBitmapInfo` (alpha info, float flag and byte order).
+    pub fn cgimage_get_bitmap_info(image: *const c_void) -> u32;
+    pub fn cgimage_get_color_space_name(
+        image: *const c_void,
+        buffer: *mut i8,
+        buffer_size: isize,
+    ) -> bool;
+    /// Components in the color space, or 0 if the image has none.
+    pub fn cgimage_get_color_space_components(image: *const c_void) -> usize;
     pub fn cgimage_release(image: *const c_void);
     pub fn cgimage_save_png(image: *const c_void, path: *const i8) -> bool;
     pub fn cgimage_save_to_file(
@@ -865,6 +963,15 @@ extern "C" {
         format: i32,
         quality: f32,
     ) -> bool;
+    /// Encode through `ImageIO` into a buffer freed with
+    /// `cgimage_encoded_data_free`. Returns null on failure.
+    pub fn cgimage_encode_to_data(
+        image: *const c_void,
+        format: i32,
+        quality: f32,
+        out_length: *mut usize,
+    ) -> *mut u8;
+    pub fn cgimage_encoded_data_free(buffer: *mut u8);
 }
 
 // MARK: - SCScreenshotConfiguration (macOS 26.0+)
@@ -905,6 +1012,14 @@ extern "C" {
     );
     pub fn sc_screenshot_configuration_set_dynamic_range(config: *const c_void, dynamic_range: i32);
     pub fn sc_screenshot_configuration_set_file_url(config: *const c_void, path: *const i8);
+    pub fn sc_screenshot_configuration_get_width(config: *const c_void) -> isize;
+    pub fn sc_screenshot_configuration_get_height(config: *const c_void) -> isize;
+    pub fn sc_screenshot_configuration_get_shows_cursor(config: *const c_void) -> bool;
+    pub fn sc_screenshot_configuration_get_ignore_shadows(config: *const c_void) -> bool;
+    pub fn sc_screenshot_configuration_get_ignore_clipping(config: *const c_void) -> bool;
+    pub fn sc_screenshot_configuration_get_include_child_windows(config: *const c_void) -> bool;
+    pub fn sc_screenshot_configuration_get_display_intent(config: *const c_void) -> i32;
+    pub fn sc_screenshot_configuration_get_dynamic_range(config: *const c_void) -> i32;
     pub fn sc_screenshot_configuration_release(config: *const c_void);
 
     // Content type support (macOS 26.0+)
@@ -1061,7 +1176,7 @@ extern "C" {
     pub fn sc_recording_output_create_with_delegate(
         config: *const c_void,
         started_callback: Option<extern "C" fn(*mut c_void)>,
-        failed_callback: Option<extern "C" fn(*mut c_void, i32, *const i8)>,
+        failed_callback: Option<extern "C" fn(*mut c_void, *const c_void)>,
         finished_callback: Option<extern "C" fn(*mut c_void)>,
         context: *mut c_void,
     ) -> *const c_void;
@@ -1071,6 +1186,30 @@ extern "C" {
         timescale: *mut i32,
     );
     pub fn sc_recording_output_get_recorded_file_size(output: *const c_void) -> i64;
+    pub fn sc_volume_available_capacity(path: *const i8) -> i64;
+    pub fn sc_recording_get_duration(path: *const i8, value: *mut i64, timescale: *mut i32)
+        -> bool;
+    /// Export `[start_nanos, end_nanos)` of `input` to `output`; `callback`
+    /// runs once with the outcome and a borrowed `NSError` on failure.
+    pub fn sc_recording_trim(
+        input: *const i8,
+        output: *const i8,
+        start_nanos: i64,
+        end_nanos: i64,
+        context: *mut c_void,
+        callback: extern "C" fn(*mut c_void, bool, *const c_void),
+    );
+    /// Copy `input` to `output` with a chapter track built from `count`
+    /// titles and offsets; `callback` is invoked like `sc_recording_trim`'s.
+    pub fn sc_recording_write_chapters(
+        input: *const i8,
+        output: *const i8,
+        titles: *const *const i8,
+        offsets_nanos: *const i64,
+        count: isize,
+        context: *mut c_void,
+        callback: extern "C" fn(*mut c_void, bool, *const c_void),
+    );
 }
 
 // MARK: - Audio Input Devices (AVFoundation)
@@ -1096,4 +1235,555 @@ extern "C" {
 
     /// Get the default audio input device name into buffer
     pub fn sc_audio_get_default_input_device_name(buffer: *mut i8, buffer_size: isize) -> bool;
+
+    /// Nominal sample rate of the device with this UID, or 0 if unknown
+    pub fn sc_audio_get_device_nominal_sample_rate(uid: *const i8) -> f64;
+
+    /// Write up to `capacity` (min, max) pairs; returns the total range count or -1
+    pub fn sc_audio_get_device_sample_rate_ranges(
+        uid: *const i8,
+        out: *mut f64,
+        capacity: isize,
+    ) -> isize;
+
+    /// Input channel count of the device with this UID, or -1 if unknown
+    pub fn sc_audio_get_device_input_channel_count(uid: *const i8) -> i32;
+
+    /// Register for device list / default input changes; returns null on failure
+    pub fn sc_audio_device_listener_create(
+        callback: extern "C" fn(*mut c_void),
+        user_data: *mut c_void,
+    ) -> *mut c_void;
+
+    /// Unregister and release a listener; no callback runs after this returns
+    pub fn sc_audio_device_listener_release(listener: *mut c_void);
+}
+
+// MARK: - Audio Output Devices and Taps (Core Audio)
+extern "C" {
+    /// Number of devices with at least one output stream
+    pub fn sc_audio_get_output_device_count() -> isize;
+
+    /// Get the UID of the output device at index into buffer
+    pub fn sc_audio_get_output_device_id(index: isize, buffer: *mut i8, buffer_size: isize)
+        -> bool;
+
+    /// Get the name of the output device at index into buffer
+    pub fn sc_audio_get_output_device_name(
+        index: isize,
+        buffer: *mut i8,
+        buffer_size: isize,
+    ) -> bool;
+
+    /// Get the default output device's UID into buffer
+    pub fn sc_audio_get_default_output_device_id(buffer: *mut i8, buffer_size: isize) -> bool;
+
+    /// Start tapping an output device (null UID for the default output);
+    /// returns null and writes an `OSStatus` on failure
+    pub fn sc_audio_output_tap_create(
+        device_uid: *const i8,
+        exclude_current_process: bool,
+        callback: extern "C" fn(*mut c_void, *const f32, isize, u32, f64, u64),
+        user_data: *mut c_void,
+        out_status: *mut i32,
+    ) -> *mut c_void;
+
+    /// Start tapping the stereo mixdown of `count` processes; released
+    /// with `sc_audio_output_tap_release` like an output tap
+    pub fn sc_audio_app_tap_create(
+        process_ids: *const i32,
+        count: isize,
+        mutes_processes: bool,
+        callback: extern "C" fn(*mut c_void, *const f32, isize, u32, f64, u64),
+        user_data: *mut c_void,
+        out_status: *mut i32,
+    ) -> *mut c_void;
+
+    /// Sample rate of the tap's audio
+    pub fn sc_audio_output_tap_sample_rate(tap: *mut c_void) -> f64;
+
+    /// Channel count of the tap's audio
+    pub fn sc_audio_output_tap_channel_count(tap: *mut c_void) -> u32;
+
+    /// Stop and destroy a tap; no callback runs after this returns
+    pub fn sc_audio_output_tap_release(tap: *mut c_void);
+}
+
+// MARK: - Region Selector (macOS 14.0+)
+extern "C" {
+    /// Present the interactive region-selection overlay on every screen.
+    ///
+    /// The callback receives `(code, x, y, width, height, display_id, scale,
+    /// user_data)` where `code` is 1 for a selection and 0 for cancel. The
+    /// rect is in global display coordinates (points, top-left origin).
+    pub fn sc_region_selector_show(
+        callback: extern "C" fn(i32, f64, f64, f64, f64, u32, f64, *mut c_void),
+        user_data: *mut c_void,
+    );
+}
+
+// MARK: - Audio Encoder (AudioToolbox)
+extern "C" {
+    pub fn sc_audio_encoder_create(
+        codec: i32,
+        sample_rate: f64,
+        channels: u32,
+        bitrate: u32,
+        out_status: *mut i32,
+    ) -> *const c_void;
+    pub fn sc_audio_encoder_release(encoder: *const c_void);
+    pub fn sc_audio_encoder_frames_per_packet(encoder: *const c_void) -> u32;
+    pub fn sc_audio_encoder_max_packet_size(encoder: *const c_void) -> u32;
+    pub fn sc_audio_encoder_priming_frames(encoder: *const c_void) -> u32;
+    pub fn sc_audio_encoder_magic_cookie(
+        encoder: *const c_void,
+        buffer: *mut c_void,
+        capacity: isize,
+    ) -> isize;
+    /// Feed `frames` interleaved `f32` frames (`0` = end of stream) and write
+    /// at most one packet to `output`.
+    pub fn sc_audio_encoder_encode(
+        encoder: *const c_void,
+        samples: *const f32,
+        frames: u32,
+        output: *mut c_void,
+        output_capacity: u32,
+        out_size: *mut u32,
+    ) -> i32;
+}
+
+// MARK: - Preview (AVSampleBufferDisplayLayer)
+extern "C" {
+    pub fn sc_preview_create() -> *const c_void;
+    pub fn sc_preview_release(preview: *const c_void);
+    /// The display layer, +0.
+    pub fn sc_preview_get_layer(preview: *const c_void) -> *const c_void;
+    pub fn sc_preview_attach_to_view(preview: *const c_void, view: *mut c_void);
+    pub fn sc_preview_attach_to_layer(preview: *const c_void, superlayer: *mut c_void);
+    pub fn sc_preview_enqueue(preview: *const c_void, sample: *const c_void) -> bool;
+    pub fn sc_preview_flush(preview: *const c_void, removing_image: bool);
+    /// 0 resize aspect, 1 resize aspect fill, 2 resize.
+    pub fn sc_preview_set_video_gravity(preview: *const c_void, gravity: i32);
+    pub fn sc_preview_dropped_frames(preview: *const c_void) -> u64;
+
+    pub fn sc_preview_window_create(
+        preview: *const c_void,
+        title: *const i8,
+        width: f64,
+        height: f64,
+    ) -> *const c_void;
+    pub fn sc_preview_window_release(window: *const c_void);
+    pub fn sc_preview_window_show(window: *const c_void);
+    pub fn sc_preview_window_hide(window: *const c_void);
+    pub fn sc_preview_window_set_size(window: *const c_void, width: f64, height: f64);
+    pub fn sc_preview_window_set_title(window: *const c_void, title: *const i8);
+    pub fn sc_preview_window_is_visible(window: *const c_void) -> bool;
+    pub fn sc_preview_window_is_closed(window: *const c_void) -> bool;
+    /// Runs the main event loop; false off the main thread. Negative
+    /// `timeout` means no limit.
+    pub fn sc_preview_window_run(window: *const c_void, timeout: f64) -> bool;
+}
+
+// MARK: - Syphon Server (runtime-loaded Syphon.framework)
+extern "C" {
+    pub fn sc_syphon_load_framework(path: *const i8) -> bool;
+    pub fn sc_syphon_server_create(name: *const i8, device: *mut c_void) -> *const c_void;
+    pub fn sc_syphon_server_release(server: *const c_void);
+    pub fn sc_syphon_server_publish_iosurface(
+        server: *const c_void,
+        iosurface: *mut c_void,
+    ) -> bool;
+    pub fn sc_syphon_server_has_clients(server: *const c_void) -> bool;
+    pub fn sc_syphon_server_stop(server: *const c_void);
+}
+
+// MARK: - XPC capture helper
+extern "C" {
+    pub fn sc_xpc_connection_create(
+        kind: i32,
+        name: *const i8,
+        endpoint: *const c_void,
+        context: *mut c_void,
+        on_message: extern "C" fn(*mut c_void, *const c_void, *const u8, isize, *const c_void),
+        on_error: extern "C" fn(*mut c_void, *const c_void, i32),
+        release_context: extern "C" fn(*mut c_void),
+    ) -> *const c_void;
+    pub fn sc_xpc_connection_retain(connection: *const c_void) -> *const c_void;
+    pub fn sc_xpc_connection_release(connection: *const c_void);
+    pub fn sc_xpc_connection_cancel(connection: *const c_void);
+    pub fn sc_xpc_connection_pid(connection: *const c_void) -> i32;
+    pub fn sc_xpc_connection_send(
+        connection: *const c_void,
+        payload: *const u8,
+        length: isize,
+        surface: *mut c_void,
+        seconds: f64,
+    );
+    pub fn sc_xpc_listener_create(
+        name: *const i8,
+        context: *mut c_void,
+        on_message: extern "C" fn(*mut c_void, *const c_void, *const u8, isize, *const c_void),
+        on_error: extern "C" fn(*mut c_void, *const c_void, i32),
+        release_context: extern "C" fn(*mut c_void),
+    ) -> *const c_void;
+    pub fn sc_xpc_listener_copy_endpoint(listener: *const c_void) -> *const c_void;
+    pub fn sc_xpc_listener_release(listener: *const c_void);
+    pub fn sc_xpc_endpoint_release(endpoint: *const c_void);
+    pub fn sc_xpc_service_main(
+        context: *mut c_void,
+        on_message: extern "C" fn(*mut c_void, *const c_void, *const u8, isize, *const c_void),
+        on_error: extern "C" fn(*mut c_void, *const c_void, i32),
+        release_context: extern "C" fn(*mut c_void),
+    ) -> !;
+}
+
+// MARK: - H.264 Encoder (VideoToolbox)
+extern "C" {
+    pub fn sc_h264_encoder_create(
+        width: i32,
+        height: i32,
+        fps: f64,
+        bitrate: i32,
+        keyframe_interval: i32,
+        out_status: *mut i32,
+    ) -> *const c_void;
+    pub fn sc_h264_encoder_release(encoder: *const c_void);
+    pub fn sc_h264_encoder_encode(
+        encoder: *const c_void,
+        pixel_buffer: *mut c_void,
+        pts_value: i64,
+        pts_scale: i32,
+        force_keyframe: bool,
+    ) -> i32;
+    pub fn sc_h264_encoder_flush(encoder: *const c_void);
+    pub fn sc_h264_encoder_take_error(encoder: *const c_void) -> i32;
+    pub fn sc_h264_encoder_next_frame_size(encoder: *const c_void) -> isize;
+    pub fn sc_h264_encoder_pop_frame(
+        encoder: *const c_void,
+        buffer: *mut c_void,
+        capacity: isize,
+        out_pts_value: *mut i64,
+        out_pts_scale: *mut i32,
+        out_keyframe: *mut bool,
+    ) -> bool;
+    pub fn sc_h264_encoder_avcc(
+        encoder: *const c_void,
+        buffer: *mut c_void,
+        capacity: isize,
+    ) -> isize;
+}
+
+// MARK: - Encoded movie writer (AVAssetWriter passthrough)
+extern "C" {
+    /// Create a passthrough writer with an H.264 track and, when `cookie` is
+    /// non-null, an AAC track. `file_type` is 0 for MP4, 1 for MOV.
+    pub fn sc_movie_writer_create(
+        path: *const i8,
+        file_type: i32,
+        avcc: *const c_void,
+        avcc_length: usize,
+        width: i32,
+        height: i32,
+        cookie: *const c_void,
+        cookie_length: usize,
+        sample_rate: f64,
+        channels: u32,
+        start_value: i64,
+        start_scale: i32,
+        out_status: *mut i32,
+    ) -> *const c_void;
+    /// Append one packet to track 0 (video) or 1 (audio).
+    pub fn sc_movie_writer_append(
+        writer: *const c_void,
+        track: i32,
+        data: *const c_void,
+        length: usize,
+        pts_value: i64,
+        pts_scale: i32,
+        duration_value: i64,
+        duration_scale: i32,
+        keyframe: bool,
+    ) -> bool;
+    pub fn sc_movie_writer_finish(
+        writer: *const c_void,
+        context: *mut c_void,
+        callback: extern "C" fn(*mut c_void, bool, *const c_void),
+    );
+    pub fn sc_movie_writer_release(writer: *const c_void);
+}
+
+// MARK: - Muxer (AVAssetWriter)
+extern "C" {
+    /// `file_type` is 0 for MP4, 1 for MOV.
+    pub fn sc_muxer_create(path: *const i8, file_type: i32, out_status: *mut i32) -> *const c_void;
+    /// `codec` is 0 for H.264, 1 for HEVC. Returns the track index or -1.
+    pub fn sc_muxer_add_video_track(
+        muxer: *const c_void,
+        codec: i32,
+        width: i32,
+        height: i32,
+        bitrate: i32,
+        real_time: bool,
+    ) -> i32;
+    pub fn sc_muxer_add_audio_track(
+        muxer: *const c_void,
+        sample_rate: f64,
+        channels: i32,
+        bitrate: i32,
+        real_time: bool,
+    ) -> i32;
+    pub fn sc_muxer_add_passthrough_track(
+        muxer: *const c_void,
+        format: *const c_void,
+        real_time: bool,
+    ) -> i32;
+    /// 0 = appended, 1 = skipped (no media), negative on failure.
+    pub fn sc_muxer_append(
+        muxer: *const c_void,
+        track: i32,
+        sample: *const c_void,
+        retime: bool,
+        pts_value: i64,
+        pts_scale: i32,
+    ) -> i32;
+    pub fn sc_muxer_get_error(muxer: *const c_void, buffer: *mut i8, buffer_size: isize) -> bool;
+    pub fn sc_muxer_finish(
+        muxer: *const c_void,
+        context: *mut c_void,
+        callback: extern "C" fn(*mut c_void, bool, *const c_void),
+    );
+    pub fn sc_muxer_release(muxer: *const c_void);
+}
+
+// MARK: - Camera overlay (AVCaptureSession + Core Image)
+extern "C" {
+    pub fn sc_camera_get_device_count() -> isize;
+    pub fn sc_camera_get_device_id(index: isize, buffer: *mut i8, buffer_size: isize) -> bool;
+    pub fn sc_camera_get_device_name(index: isize, buffer: *mut i8, buffer_size: isize) -> bool;
+    /// `device_id` may be NULL for the default camera. `corner`: 0 top-left,
+    /// 1 top-right, 2 bottom-left, 3 bottom-right.
+    pub fn sc_camera_overlay_create(
+        device_id: *const i8,
+        corner: i32,
+        scale: f64,
+        margin: f64,
+        mirrored: bool,
+        out_status: *mut i32,
+    ) -> *const c_void;
+    pub fn sc_camera_overlay_set_layout(
+        overlay: *const c_void,
+        corner: i32,
+        scale: f64,
+        margin: f64,
+        mirrored: bool,
+    );
+    pub fn sc_camera_overlay_has_frame(overlay: *const c_void) -> bool;
+    /// Returns a +1 `CMSampleBuffer`, or NULL if nothing was composited.
+    pub fn sc_camera_overlay_composite(
+        overlay: *const c_void,
+        sample: *const c_void,
+    ) -> *const c_void;
+    pub fn sc_camera_overlay_release(overlay: *const c_void);
+}
+
+// MARK: - Region mask (Core Image)
+extern "C" {
+    pub fn sc_region_mask_create() -> *const c_void;
+    pub fn sc_region_mask_release(mask: *const c_void);
+    /// `rects` holds `count` x, y, width, height quadruples in pixels from
+    /// the frame's top-left corner. Returns a +1 `CMSampleBuffer`, or NULL
+    /// if the sample has no image or rendering failed.
+    pub fn sc_region_mask_apply(
+        mask: *const c_void,
+        sample: *const c_void,
+        rects: *const f64,
+        count: isize,
+    ) -> *const c_void;
+}
+
+// MARK: - Display mode (CoreGraphics)
+extern "C" {
+    /// Current mode of `display_id` in points and pixels; false if offline.
+    pub fn sc_display_get_current_mode(
+        display_id: u32,
+        point_width: *mut isize,
+        point_height: *mut isize,
+        pixel_width: *mut isize,
+        pixel_height: *mut isize,
+    ) -> bool;
+}
+
+// MARK: - Permissions
+extern "C" {
+    /// Open System Settings at the Screen Recording privacy pane.
+    pub fn sc_open_screen_recording_settings() -> bool;
+    /// Open an `x-apple.systempreferences:` URL in System Settings.
+    pub fn sc_open_settings_url(url: *const i8) -> bool;
+    /// Whether `SCContentSharingPicker` is available (macOS 14+).
+    pub fn sc_content_sharing_picker_is_available() -> bool;
+}
+
+// MARK: - Power (ProcessInfo / IOKit)
+extern "C" {
+    /// `ProcessInfo.thermalState` raw value, 0 (nominal) to 3 (critical).
+    pub fn sc_power_get_thermal_state() -> i32;
+    pub fn sc_power_is_low_power_mode() -> bool;
+    pub fn sc_power_is_on_battery() -> bool;
+}
+
+// MARK: - System info (ProcessInfo / NSRunningApplication)
+extern "C" {
+    /// `ProcessInfo.operatingSystemVersionString`; free with `sc_free_string`.
+    pub fn sc_system_os_version() -> *mut i8;
+    /// Bundle short version of the app running as `pid`, or NULL. Free with
+    /// `sc_free_string`.
+    pub fn sc_application_version(pid: i32) -> *mut i8;
+    /// Bundle short version of the main bundle, or NULL. Free with
+    /// `sc_free_string`.
+    pub fn sc_main_bundle_version() -> *mut i8;
+}
+
+// MARK: - Display sleep (CoreGraphics / NSWorkspace)
+extern "C" {
+    /// Bit 0: the display (main display for 0) is asleep; bit 1: the
+    /// screen is locked; bit 2: the screensaver is running; bit 3: the session
+    /// dictionary couldn't be read, so bit 1 is meaningless.
+    pub fn sc_display_sleep_state(display_id: u32) -> u32;
+}
+
+// MARK: - Pixel buffer crop (vImage)
+extern "C" {
+    /// Returns a +1 `CVPixelBuffer`, or NULL with `out_status` set.
+    pub fn sc_pixel_buffer_crop(
+        source: *const c_void,
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+        pool: *const c_void,
+        element_bytes: *const usize,
+        plane_count: usize,
+        out_status: *mut i32,
+    ) -> *const c_void;
+}
+
+// MARK: - ICC profiles
+extern "C" {
+    /// ICC profile of the image's color space, in a buffer freed with
+    /// `cgimage_encoded_data_free`. Returns null if there is none.
+    pub fn cgimage_copy_icc_profile(image: *const c_void, out_length: *mut usize) -> *mut u8;
+    /// Returns a +1 `CGImage` tagged with `profile`, or null if the profile
+    /// is invalid or doesn't match the image's components.
+    pub fn cgimage_create_with_icc_profile(
+        image: *const c_void,
+        profile: *const u8,
+        length: usize,
+    ) -> *const c_void;
+    /// ICC profile of the display's color space, freed like
+    /// `cgimage_copy_icc_profile`.
+    pub fn sc_display_copy_icc_profile(display_id: u32, out_length: *mut usize) -> *mut u8;
+}
+
+// MARK: - Pixel sampling (vImage)
+extern "C" {
+    /// Red, green, blue and alpha histograms of a region, 4×256 counts.
+    /// Returns 0, -1 if the buffer can't be converted, or a `vImage_Error`.
+    pub fn sc_pixel_buffer_rgba_histograms(
+        source: *const c_void,
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+        out_histograms: *mut u64,
+    ) -> i32;
+    /// BT.709 luma histogram of a region, 256 counts. Statuses as above.
+    pub fn sc_pixel_buffer_luma_histogram(
+        source: *const c_void,
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+        out_histogram: *mut u64,
+    ) -> i32;
+    /// Region scaled to `out_width`×`out_height` packed RGBA8888 pixels.
+    pub fn sc_pixel_buffer_rgba_thumbnail(
+        source: *const c_void,
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+        out_width: usize,
+        out_height: usize,
+        out_pixels: *mut u8,
+    ) -> i32;
+}
+
+// MARK: - Text recognition (Vision)
+extern "C" {
+    /// Pass a `CVPixelBuffer` or, with `pixel_buffer` NULL, a `CGImage`.
+    /// `languages` is comma-separated or NULL. Returns a +1 results handle,
+    /// or NULL with `out_error` set (free with `sc_free_string`).
+    pub fn sc_vision_recognize_text(
+        pixel_buffer: *const c_void,
+        image: *const c_void,
+        accurate: bool,
+        language_correction: bool,
+        languages: *const i8,
+        minimum_text_height: f32,
+        out_error: *mut *mut i8,
+    ) -> *const c_void;
+    pub fn sc_vision_text_results_count(results: *const c_void) -> isize;
+    /// Caller must free with `sc_free_string`.
+    pub fn sc_vision_text_results_string(results: *const c_void, index: isize) -> *mut i8;
+    pub fn sc_vision_text_results_confidence(results: *const c_void, index: isize) -> f32;
+    /// Writes x, y, width, height (normalized, top-left origin) to `out_rect`.
+    pub fn sc_vision_text_results_box(results: *const c_void, index: isize, out_rect: *mut f64);
+    pub fn sc_vision_text_results_release(results: *const c_void);
+}
+
+// MARK: - Image sequences (Core Image / ImageIO)
+extern "C" {
+    /// Write a `CVPixelBuffer` to `path` as PNG (0), 16-bit TIFF (1),
+    /// `OpenEXR` (2) or 10-bit HEIF (3). Returns false with `out_error` set
+    /// (free with `sc_free_string`).
+    pub fn sc_image_sequence_write_frame(
+        pixel_buffer: *const c_void,
+        path: *const i8,
+        format: i32,
+        quality: f32,
+        out_error: *mut *mut i8,
+    ) -> bool;
+}
+
+// MARK: - CGImage <-> CVPixelBuffer (Core Image / VideoToolbox)
+extern "C" {
+    /// Returns a +1 `CVPixelBuffer` of `format` holding `image`, or NULL with
+    /// `out_status` set (-1 for an empty image, otherwise a `CVReturn`).
+    pub fn sc_pixel_buffer_create_from_cgimage(
+        image: *const c_void,
+        format: u32,
+        out_status: *mut i32,
+    ) -> *const c_void;
+    /// Returns a +1 `CGImage` of the buffer's pixels, or NULL with
+    /// `out_status` set to an `OSStatus`.
+    pub fn sc_cgimage_create_from_pixel_buffer(
+        pixel_buffer: *const c_void,
+        out_status: *mut i32,
+    ) -> *const c_void;
+}
+
+// MARK: - Cursor (AppKit)
+extern "C" {
+    /// Cursor location in global points and the seed of its shape (0 if
+    /// unknown). Returns false if the location can't be read.
+    pub fn sc_cursor_sample(out_x: *mut f64, out_y: *mut f64, out_seed: *mut u64) -> bool;
+    /// The cursor image as a +1 `CGImage`, with its hot spot and size in
+    /// points and its shape seed; null if there is no system cursor.
+    pub fn sc_cursor_copy_shape(
+        out_hot_x: *mut f64,
+        out_hot_y: *mut f64,
+        out_width: *mut f64,
+        out_height: *mut f64,
+        out_seed: *mut u64,
+    ) -> *const c_void;
 }
@@ -0,0 +1,226 @@
+//! One video frame, whether it came from a stream or a screenshot
+//!
+//! Stream callbacks hand out [`CMSampleBuffer`]s, while
+//! [`SCScreenshotManager`](crate::screenshot_manager::SCScreenshotManager)
+//! returns [`CGImage`]s. [`Frame`] wraps either one behind the same size,
+//! format, timestamp and pixel-copy accessors, so processing code can be
+//! written once and fed from both.
+//!
+//! ```no_run
+//! use screencapturekit::cv::frame_copy::{FrameCopyExt, RowLayout};
+//! use screencapturekit::frame::Frame;
+//! use screencapturekit::prelude::*;
+//!
+//! fn process(frame: &Frame) -> Result<Vec<u8>, SCError> {
+//!     println!(
+//!         "{:?} frame {}x{} ({}) at {:?}",
+//!         frame.origin(),
+//!         frame.width(),
+//!         frame.height(),
+//!         frame.pixel_format(),
+//!         frame.timestamp(),
+//!     );
+//!     let mut pixels = vec![0; frame.copy_size(RowLayout::Packed)?];
+//!     frame.copy_into(&mut pixels, RowLayout::Packed)?;
+//!     Ok(pixels)
+//! }
+//!
+//! fn on_sample(sample: CMSampleBuffer) {
+//!     if let Ok(frame) = Frame::from_sample_buffer(sample) {
+//!         let _ = process(&frame);
+//!     }
+//! }
+//! ```
+
+use std::fmt;
+
+use crate::cm::{CMSampleBuffer, CMSampleBufferExt, CMTime, MachTime};
+use crate::cv::frame_copy::{FrameCopyExt, RowLayout, RowOrder};
+use crate::cv::CVPixelBuffer;
+use crate::error::SCError;
+use crate::stream::configuration::PixelFormat;
+use crate::CGImage;
+
+/// Where a [`Frame`] came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FrameOrigin {
+    /// A sample buffer delivered by an [`SCStream`](crate::stream::SCStream).
+    Stream,
+    /// An image from a screenshot.
+    Screenshot,
+}
+
+enum FrameContent {
+    Stream {
+        sample: CMSampleBuffer,
+        buffer: CVPixelBuffer,
+    },
+    Screenshot(CGImage),
+}
+
+/// A video frame from a stream or a screenshot.
+///
+/// Copy its pixels with [`FrameCopyExt`]: stream frames are copied in their
+/// native [`pixel_format`](Self::pixel_format), screenshots as BGRA.
+pub struct Frame {
+    content: FrameContent,
+    timestamp: CMTime,
+}
+
+impl Frame {
+    /// Wrap a video sample buffer from a stream.
+    ///
+    /// The timestamp is the sample's presentation time.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidBuffer`] if the sample carries no image,
+    /// such as an audio sample or an idle frame.
+    pub fn from_sample_buffer(sample: CMSampleBuffer) -> Result<Self, SCError> {
+        let buffer = sample
+            .image_buffer()
+            .ok_or_else(|| SCError::InvalidBuffer("sample buffer has no image".into()))?;
+        Ok(Self {
+            timestamp: sample.presentation_timestamp(),
+            content: FrameContent::Stream { sample, buffer },
+        })
+    }
+
+    /// Wrap a screenshot.
+    ///
+    /// The timestamp is the current host time, on the same clock as stream
+    /// presentation times.
+    pub fn from_screenshot(image: CGImage) -> Self {
+        Self {
+            content: FrameContent::Screenshot(image),
+            timestamp: MachTime::now().to_cmtime(),
+        }
+    }
+
+    /// Where the frame came from.
+    pub const fn origin(&self) -> FrameOrigin {
+        match self.content {
+            FrameContent::Stream { .. } => FrameOrigin::Stream,
+            FrameContent::Screenshot(_) => FrameOrigin::Screenshot,
+        }
+    }
+
+    /// When the frame was captured, on the host time clock.
+    pub const fn timestamp(&self) -> CMTime {
+        self.timestamp
+    }
+
+    /// Width in pixels.
+    pub fn width(&self) -> usize {
+        match &self.content {
+            FrameContent::Stream { buffer, .. } => buffer.width(),
+            FrameContent::Screenshot(image) => image.width(),
+        }
+    }
+
+    /// Height in pixels.
+    pub fn height(&self) -> usize {
+        match &self.content {
+            FrameContent::Stream { buffer, .. } => buffer.height(),
+            FrameContent::Screenshot(image) => image.height(),
+        }
+    }
+
+    /// Layout of the bytes [`copy_into`](FrameCopyExt::copy_into) writes.
+    ///
+    /// The stream's pixel format for stream frames, and always
+    /// [`PixelFormat::BGRA`] for screenshots, which are rendered to 8-bit
+    /// BGRA when copied.
+    pub fn pixel_format(&self) -> PixelFormat {
+        match &self.content {
+            FrameContent::Stream { buffer, .. } => buffer.pixel_format().into(),
+            FrameContent::Screenshot(_) => PixelFormat::BGRA,
+        }
+    }
+
+    /// The sample buffer behind a stream frame.
+    pub const fn sample_buffer(&self) -> Option<&CMSampleBuffer> {
+        match &self.content {
+            FrameContent::Stream { sample, .. } => Some(sample),
+            FrameContent::Screenshot(_) => None,
+        }
+    }
+
+    /// The pixel buffer behind a stream frame.
+    pub const fn pixel_buffer(&self) -> Option<&CVPixelBuffer> {
+        match &self.content {
+            FrameContent::Stream { buffer, .. } => Some(buffer),
+            FrameContent::Screenshot(_) => None,
+        }
+    }
+
+    /// The image behind a screenshot frame.
+    pub const fn image(&self) -> Option<&CGImage> {
+        match &self.content {
+            FrameContent::Stream { .. } => None,
+            FrameContent::Screenshot(image) => Some(image),
+        }
+    }
+
+    /// Turn the frame into a [`CGImage`], converting stream frames of any
+    /// pixel format.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a stream frame cannot be converted.
+    pub fn into_cg_image(self) -> Result<CGImage, SCError> {
+        match self.content {
+            FrameContent::Stream { sample, .. } => sample
+                .cg_image()
+                .map_err(|status| SCError::os_error(status, "failed to convert frame to CGImage")),
+            FrameContent::Screenshot(image) => Ok(image),
+        }
+    }
+}
+
+impl FrameCopyExt for Frame {
+    fn copy_size(&self, layout: RowLayout) -> Result<usize, SCError> {
+        match &self.content {
+            FrameContent::Stream { buffer, .. } => buffer.copy_size(layout),
+            FrameContent::Screenshot(image) => image.copy_size(layout),
+        }
+    }
+
+    fn copy_rows_into(
+        &self,
+        dest: &mut [u8],
+        layout: RowLayout,
+        order: RowOrder,
+    ) -> Result<usize, SCError> {
+        match &self.content {
+            FrameContent::Stream { buffer, .. } => buffer.copy_rows_into(dest, layout, order),
+            FrameContent::Screenshot(image) => image.copy_rows_into(dest, layout, order),
+        }
+    }
+}
+
+impl TryFrom<CMSampleBuffer> for Frame {
+    type Error = SCError;
+
+    fn try_from(sample: CMSampleBuffer) -> Result<Self, SCError> {
+        Self::from_sample_buffer(sample)
+    }
+}
+
+impl From<CGImage> for Frame {
+    fn from(image: CGImage) -> Self {
+        Self::from_screenshot(image)
+    }
+}
+
+impl fmt::Debug for Frame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Frame")
+            .field("origin", &self.origin())
+            .field("width", &self.width())
+            .field("height", &self.height())
+            .field("pixel_format", &self.pixel_format())
+            .field("timestamp", &self.timestamp)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,142 @@
+//! Real-time H.264 encoding through a `VTCompressionSession`.
+
+use std::ffi::c_void;
+
+use crate::cm::CMTime;
+use crate::cv::CVPixelBuffer;
+use crate::error::SCError;
+
+/// One encoded access unit in AVCC (length-prefixed NAL unit) form.
+#[derive(Debug)]
+pub struct EncodedVideoFrame {
+    pub data: Vec<u8>,
+    pub pts: CMTime,
+    pub keyframe: bool,
+}
+
+pub struct H264Encoder {
+    ptr: *const c_void,
+}
+
+// SAFETY: the Swift side guards its output queue with a lock, and
+// `VTCompressionSession` may be driven from any thread.
+unsafe impl Send for H264Encoder {}
+
+impl std::fmt::Debug for H264Encoder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("H264Encoder").finish_non_exhaustive()
+    }
+}
+
+impl H264Encoder {
+    #[allow(clippy::cast_possible_wrap)]
+    pub fn new(
+        width: u32,
+        height: u32,
+        fps: f64,
+        bitrate: u32,
+        keyframe_interval: u32,
+    ) -> Result<Self, SCError> {
+        let mut status = 0;
+        let ptr = unsafe {
+            crate::ffi::sc_h264_encoder_create(
+                width as i32,
+                height as i32,
+                fps,
+                bitrate as i32,
+                keyframe_interval as i32,
+                &mut status,
+            )
+        };
+        if ptr.is_null() {
+            return Err(SCError::os_error(
+                status,
+                format!("failed to create {width}x{height} H.264 encoder"),
+            ));
+        }
+        Ok(Self { ptr })
+    }
+
+    /// Submit a frame. Output arrives asynchronously; collect it with
+    /// [`pop_frame`](Self::pop_frame).
+    pub fn encode(&mut self, buffer: &CVPixelBuffer, pts: CMTime) -> Result<(), SCError> {
+        let status = unsafe {
+            crate::ffi::sc_h264_encoder_encode(
+                self.ptr,
+                buffer.as_ptr(),
+                pts.value,
+                pts.timescale,
+                false,
+            )
+        };
+        if status != 0 {
+            return Err(SCError::os_error(status, "H.264 encode failed"));
+        }
+        self.take_error()
+    }
+
+    /// Block until every submitted frame has been emitted.
+    pub fn flush(&mut self) -> Result<(), SCError> {
+        unsafe { crate::ffi::sc_h264_encoder_flush(self.ptr) };
+        self.take_error()
+    }
+
+    fn take_error(&self) -> Result<(), SCError> {
+        match unsafe { crate::ffi::sc_h264_encoder_take_error(self.ptr) } {
+            0 => Ok(()),
+            status => Err(SCError::os_error(status, "H.264 encoder callback failed")),
+        }
+    }
+
+    #[allow(clippy::cast_sign_loss)]
+    pub fn pop_frame(&mut self) -> Option<EncodedVideoFrame> {
+        let size = unsafe { crate::ffi::sc_h264_encoder_next_frame_size(self.ptr) };
+        if size < 0 {
+            return None;
+        }
+        let mut data = vec![0u8; size as usize];
+        let mut pts_value = 0i64;
+        let mut pts_scale = 0i32;
+        let mut keyframe = false;
+        let popped = unsafe {
+            crate::ffi::sc_h264_encoder_pop_frame(
+                self.ptr,
+                data.as_mut_ptr().cast(),
+                size,
+                &mut pts_value,
+                &mut pts_scale,
+                &mut keyframe,
+            )
+        };
+        popped.then(|| EncodedVideoFrame {
+            data,
+            pts: CMTime::new(pts_value, pts_scale),
+            keyframe,
+        })
+    }
+
+    /// The `avcC` decoder configuration record, available once the first
+    /// frame has been encoded.
+    #[allow(clippy::cast_sign_loss, clippy::cast_possible_wrap)]
+    pub fn avcc(&self) -> Option<Vec<u8>> {
+        let size = unsafe { crate::ffi::sc_h264_encoder_avcc(self.ptr, std::ptr::null_mut(), 0) };
+        if size <= 0 {
+            return None;
+        }
+        let mut avcc = vec![0u8; size as usize];
+        unsafe {
+            crate::ffi::sc_h264_encoder_avcc(
+                self.ptr,
+                avcc.as_mut_ptr().cast(),
+                avcc.len() as isize,
+            )
+        };
+        Some(avcc)
+    }
+}
+
+impl Drop for H264Encoder {
+    fn drop(&mut self) {
+        unsafe { crate::ffi::sc_h264_encoder_release(self.ptr) }
+    }
+}
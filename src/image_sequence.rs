@@ -0,0 +1,547 @@
+//! Writing captured frames as numbered image files
+//!
+//! VFX and archival pipelines often want every frame as its own file
+//! instead of a video: `frame_000001.png`, `frame_000002.png`, … An
+//! [`ImageSequenceWriter`] does that for a stream. Its
+//! [`output_handler`](ImageSequenceWriter::output_handler) takes each
+//! complete screen frame and queues it for a writer thread, which encodes
+//! it through Core Image as:
+//!
+//! - [`SequenceFormat::Png`] — 8-bit, for SDR captures
+//! - [`SequenceFormat::Tiff16`] — 16-bit, lossless beyond 8 bits
+//! - [`SequenceFormat::Exr`] — linear half-float `OpenEXR`, keeping HDR
+//!   values above 1.0
+//! - [`SequenceFormat::Heif10`] — 10-bit HEIF in the frame's color space
+//!
+//! ## Back-pressure
+//!
+//! Encoding a large frame can take longer than a frame interval, and every
+//! queued frame holds one of the stream's pixel buffers. The queue is
+//! therefore bounded ([`ImageSequence::with_queue_capacity`]); a frame that
+//! arrives while it is full is dropped and counted in
+//! [`ImageSequenceStats::dropped`]. Numbers are assigned on arrival, so a
+//! dropped frame leaves a gap in the sequence rather than shifting the
+//! frames after it.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use screencapturekit::image_sequence::{ImageSequence, ImageSequenceWriter, SequenceFormat};
+//! use screencapturekit::prelude::*;
+//!
+//! # fn example(filter: &SCContentFilter, config: &SCStreamConfiguration) -> Result<(), SCError> {
+//! let writer = ImageSequenceWriter::start(
+//!     ImageSequence::new("/tmp/capture", SequenceFormat::Png).with_prefix("shot"),
+//! )?;
+//! let mut stream = SCStream::new(filter, config);
+//! stream.add_output_handler(writer.output_handler(), SCStreamOutputType::Screen);
+//! stream.start_capture()?;
+//! std::thread::sleep(std::time::Duration::from_secs(5));
+//! stream.stop_capture()?;
+//!
+//! let stats = writer.finish();
+//! println!("{} frames written, {} dropped", stats.written, stats.dropped);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::VecDeque;
+use std::ffi::CString;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Condvar, Mutex, PoisonError};
+use std::thread::JoinHandle;
+
+use crate::cm::{CMSampleBuffer, CMSampleBufferExt, CMSampleBufferSCExt, SCFrameStatus};
+use crate::cv::CVPixelBuffer;
+use crate::error::SCError;
+use crate::stream::output_trait::{SCStreamOutputTrait, SampleDelivery};
+use crate::stream::output_type::SCStreamOutputType;
+use crate::utils::ffi_string::ffi_string_owned;
+
+/// File format of an image sequence
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SequenceFormat {
+    /// 8-bit PNG (lossless)
+    Png,
+    /// 16-bit TIFF (lossless)
+    Tiff16,
+    /// Half-float `OpenEXR` in linear sRGB primaries, for HDR
+    Exr,
+    /// 10-bit HEIF with quality (0.0-1.0), for HDR
+    Heif10(f32),
+}
+
+impl SequenceFormat {
+    /// Format ids match `writeImageSequenceFrame` in the Swift bridge.
+    const fn id(self) -> i32 {
+        match self {
+            Self::Png => 0,
+            Self::Tiff16 => 1,
+            Self::Exr => 2,
+            Self::Heif10(_) => 3,
+        }
+    }
+
+    fn quality(self) -> f32 {
+        match self {
+            Self::Heif10(q) => q.clamp(0.0, 1.0),
+            _ => 1.0,
+        }
+    }
+
+    /// File extension for this format
+    #[must_use]
+    pub const fn extension(&self) -> &'static str {
+        match self {
+            Self::Png => "png",
+            Self::Tiff16 => "tiff",
+            Self::Exr => "exr",
+            Self::Heif10(_) => "heic",
+        }
+    }
+
+    /// Whether the format keeps values outside the SDR range
+    #[must_use]
+    pub const fn is_hdr(&self) -> bool {
+        matches!(self, Self::Exr | Self::Heif10(_))
+    }
+}
+
+/// Where and how an [`ImageSequenceWriter`] writes frames
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImageSequence {
+    directory: PathBuf,
+    format: SequenceFormat,
+    prefix: String,
+    digits: usize,
+    first_number: u64,
+    queue_capacity: usize,
+}
+
+impl ImageSequence {
+    /// Write `format` files into `directory`, named `frame_000001.<ext>`
+    /// onwards, with up to 8 frames waiting to be written
+    #[must_use]
+    pub fn new(directory: impl Into<PathBuf>, format: SequenceFormat) -> Self {
+        Self {
+            directory: directory.into(),
+            format,
+            prefix: "frame".to_string(),
+            digits: 6,
+            first_number: 1,
+            queue_capacity: 8,
+        }
+    }
+
+    /// Start file names with `prefix` and an underscore; an empty prefix
+    /// leaves just the number
+    pub fn set_prefix(&mut self, prefix: impl Into<String>) -> &mut Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Start file names with `prefix` (builder pattern)
+    #[must_use]
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.set_prefix(prefix);
+        self
+    }
+
+    /// Zero-pad frame numbers to `digits` digits
+    pub fn set_digits(&mut self, digits: usize) -> &mut Self {
+        self.digits = digits;
+        self
+    }
+
+    /// Zero-pad frame numbers to `digits` digits (builder pattern)
+    #[must_use]
+    pub fn with_digits(mut self, digits: usize) -> Self {
+        self.set_digits(digits);
+        self
+    }
+
+    /// Number the first frame `number`, e.g. 1001 for VFX plates
+    pub fn set_first_number(&mut self, number: u64) -> &mut Self {
+        self.first_number = number;
+        self
+    }
+
+    /// Number the first frame `number` (builder pattern)
+    #[must_use]
+    pub fn with_first_number(mut self, number: u64) -> Self {
+        self.set_first_number(number);
+        self
+    }
+
+    /// Let at most `capacity` frames wait for the writer thread
+    pub fn set_queue_capacity(&mut self, capacity: usize) -> &mut Self {
+        self.queue_capacity = capacity;
+        self
+    }
+
+    /// Let at most `capacity` frames wait for the writer thread (builder
+    /// pattern)
+    #[must_use]
+    pub fn with_queue_capacity(mut self, capacity: usize) -> Self {
+        self.set_queue_capacity(capacity);
+        self
+    }
+
+    /// Directory the files go into
+    #[must_use]
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// File format
+    #[must_use]
+    pub const fn format(&self) -> SequenceFormat {
+        self.format
+    }
+
+    /// Number of the first frame
+    #[must_use]
+    pub const fn first_number(&self) -> u64 {
+        self.first_number
+    }
+
+    /// Most frames waiting for the writer thread
+    #[must_use]
+    pub const fn queue_capacity(&self) -> usize {
+        self.queue_capacity
+    }
+
+    /// Path of frame `number`
+    #[must_use]
+    pub fn path_for(&self, number: u64) -> PathBuf {
+        let digits = self.digits;
+        let extension = self.format.extension();
+        let name = if self.prefix.is_empty() {
+            format!("{number:0digits$}.{extension}")
+        } else {
+            format!("{}_{number:0digits$}.{extension}", self.prefix)
+        };
+        self.directory.join(name)
+    }
+
+    /// Check that frames can be written as configured
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidConfiguration`] for a zero queue capacity
+    /// or a prefix containing a path separator or NUL byte.
+    pub fn validate(&self) -> Result<(), SCError> {
+        if self.queue_capacity == 0 {
+            return Err(SCError::invalid_config(
+                "image sequence queue capacity is zero",
+            ));
+        }
+        if self.prefix.contains(['/', '\0']) {
+            return Err(SCError::invalid_config(format!(
+                "image sequence prefix {:?} contains a path separator or NUL byte",
+                self.prefix
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Frame counts of an [`ImageSequenceWriter`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ImageSequenceStats {
+    /// Files written
+    pub written: u64,
+    /// Frames dropped because the queue was full
+    pub dropped: u64,
+    /// Frames that could not be encoded or written; see
+    /// [`ImageSequenceWriter::last_error`]
+    pub failed: u64,
+}
+
+struct SequenceQueue {
+    frames: VecDeque<(u64, CVPixelBuffer)>,
+    closed: bool,
+}
+
+struct SequenceShared {
+    queue: Mutex<SequenceQueue>,
+    available: Condvar,
+    next_number: AtomicU64,
+    written: AtomicU64,
+    dropped: AtomicU64,
+    failed: AtomicU64,
+    last_error: Mutex<Option<SCError>>,
+    capacity: usize,
+}
+
+impl SequenceShared {
+    fn stats(&self) -> ImageSequenceStats {
+        ImageSequenceStats {
+            written: self.written.load(Ordering::Relaxed),
+            dropped: self.dropped.load(Ordering::Relaxed),
+            failed: self.failed.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Writes frames delivered to its [`output_handler`](Self::output_handler)
+/// as an image sequence
+///
+/// Dropping the writer, or calling [`finish`](Self::finish), writes the
+/// frames still queued and joins the writer thread.
+pub struct ImageSequenceWriter {
+    sequence: ImageSequence,
+    shared: Arc<SequenceShared>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl ImageSequenceWriter {
+    /// Create the directory if needed and start the writer thread
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidConfiguration`] if the sequence is invalid
+    /// (see [`ImageSequence::validate`]), or [`SCError::InternalError`] if
+    /// the directory can't be created or the operating system refuses to
+    /// spawn the writer thread.
+    pub fn start(sequence: ImageSequence) -> Result<Self, SCError> {
+        sequence.validate()?;
+        std::fs::create_dir_all(&sequence.directory).map_err(|e| {
+            SCError::internal_error(format!(
+                "failed to create {}: {e}",
+                sequence.directory.display()
+            ))
+        })?;
+
+        let shared = Arc::new(SequenceShared {
+            queue: Mutex::new(SequenceQueue {
+                frames: VecDeque::new(),
+                closed: false,
+            }),
+            available: Condvar::new(),
+            next_number: AtomicU64::new(sequence.first_number),
+            written: AtomicU64::new(0),
+            dropped: AtomicU64::new(0),
+            failed: AtomicU64::new(0),
+            last_error: Mutex::new(None),
+            capacity: sequence.queue_capacity,
+        });
+        let thread = {
+            let shared = Arc::clone(&shared);
+            let sequence = sequence.clone();
+            std::thread::Builder::new()
+                .name("sc-image-sequence".to_string())
+                .spawn(move || writer_loop(&shared, &sequence))
+                .map_err(|e| {
+                    SCError::internal_error(format!(
+                        "failed to spawn image sequence writer thread: {e}"
+                    ))
+                })?
+        };
+
+        Ok(Self {
+            sequence,
+            shared,
+            thread: Some(thread),
+        })
+    }
+
+    /// Output handler that queues complete screen frames for this writer
+    ///
+    /// Idle and blank frames carry no new image and are skipped without
+    /// using up a number.
+    #[must_use]
+    pub fn output_handler(&self) -> ImageSequenceOutput {
+        ImageSequenceOutput {
+            shared: Arc::clone(&self.shared),
+        }
+    }
+
+    /// The sequence being written
+    #[must_use]
+    pub const fn sequence(&self) -> &ImageSequence {
+        &self.sequence
+    }
+
+    /// Frames written, dropped and failed so far
+    #[must_use]
+    pub fn stats(&self) -> ImageSequenceStats {
+        self.shared.stats()
+    }
+
+    /// Frames waiting to be written
+    #[must_use]
+    pub fn queued(&self) -> usize {
+        self.shared
+            .queue
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .frames
+            .len()
+    }
+
+    /// Why the last failed frame couldn't be written
+    #[must_use]
+    pub fn last_error(&self) -> Option<SCError> {
+        self.shared
+            .last_error
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Write the frames still queued, stop the writer thread and return
+    /// the final counts
+    ///
+    /// Frames delivered to the output handler afterwards are dropped.
+    #[must_use]
+    pub fn finish(mut self) -> ImageSequenceStats {
+        self.shutdown();
+        self.stats()
+    }
+
+    fn shutdown(&mut self) {
+        self.shared
+            .queue
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .closed = true;
+        self.shared.available.notify_all();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn writer_loop(shared: &SequenceShared, sequence: &ImageSequence) {
+    loop {
+        let next = {
+            let mut queue = shared.queue.lock().unwrap_or_else(PoisonError::into_inner);
+            loop {
+                if let Some(next) = queue.frames.pop_front() {
+                    break Some(next);
+                }
+                if queue.closed {
+                    break None;
+                }
+                queue = shared
+                    .available
+                    .wait(queue)
+                    .unwrap_or_else(PoisonError::into_inner);
+            }
+        };
+        let Some((number, frame)) = next else {
+            return;
+        };
+        match write_frame(&frame, &sequence.path_for(number), sequence.format) {
+            Ok(()) => {
+                shared.written.fetch_add(1, Ordering::Relaxed);
+            }
+            Err(error) => {
+                shared.failed.fetch_add(1, Ordering::Relaxed);
+                *shared
+                    .last_error
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner) = Some(error);
+            }
+        }
+    }
+}
+
+fn write_frame(frame: &CVPixelBuffer, path: &Path, format: SequenceFormat) -> Result<(), SCError> {
+    let c_path = CString::new(path.to_string_lossy().as_bytes())
+        .map_err(|_| SCError::invalid_config("image sequence path contains a NUL byte"))?;
+    let mut error = std::ptr::null_mut();
+    let written = unsafe {
+        crate::ffi::sc_image_sequence_write_frame(
+            frame.as_ptr(),
+            c_path.as_ptr(),
+            format.id(),
+            format.quality(),
+            &mut error,
+        )
+    };
+    if written {
+        return Ok(());
+    }
+    let message =
+        unsafe { ffi_string_owned(|| error) }.unwrap_or_else(|| "unknown error".to_string());
+    Err(SCError::internal_error(format!(
+        "failed to write {}: {message}",
+        path.display()
+    )))
+}
+
+impl Drop for ImageSequenceWriter {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+impl fmt::Debug for ImageSequenceWriter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ImageSequenceWriter")
+            .field("directory", &self.sequence.directory)
+            .field("format", &self.sequence.format)
+            .field("stats", &self.stats())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Output handler feeding an [`ImageSequenceWriter`]
+///
+/// Created by [`ImageSequenceWriter::output_handler`].
+#[derive(Clone)]
+pub struct ImageSequenceOutput {
+    shared: Arc<SequenceShared>,
+}
+
+impl SCStreamOutputTrait for ImageSequenceOutput {
+    fn did_output_sample_buffer(&self, sample_buffer: CMSampleBuffer, of_type: SCStreamOutputType) {
+        self.did_output_sample_buffer_borrowed(&sample_buffer, of_type);
+    }
+
+    fn delivery(&self) -> SampleDelivery {
+        SampleDelivery::Borrowed
+    }
+
+    fn did_output_sample_buffer_borrowed(
+        &self,
+        sample_buffer: &CMSampleBuffer,
+        of_type: SCStreamOutputType,
+    ) {
+        if of_type != SCStreamOutputType::Screen
+            || sample_buffer.frame_status() != Some(SCFrameStatus::Complete)
+        {
+            return;
+        }
+        let Some(frame) = sample_buffer.image_buffer() else {
+            return;
+        };
+        let number = self.shared.next_number.fetch_add(1, Ordering::Relaxed);
+        let mut queue = self
+            .shared
+            .queue
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if queue.closed || queue.frames.len() >= self.shared.capacity {
+            drop(queue);
+            self.shared.dropped.fetch_add(1, Ordering::Relaxed);
+            // `frame` is released here, outside the queue lock.
+            return;
+        }
+        queue.frames.push_back((number, frame));
+        drop(queue);
+        self.shared.available.notify_one();
+    }
+}
+
+impl fmt::Debug for ImageSequenceOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ImageSequenceOutput")
+            .field("stats", &self.shared.stats())
+            .finish()
+    }
+}
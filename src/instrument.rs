@@ -0,0 +1,118 @@
+//! Internal instrumentation hooks.
+//!
+//! Every function here is a no-op unless the `metrics` feature is enabled, in
+//! which case it records into the names documented in `crate::metrics`.
+
+use std::time::Instant;
+
+use crate::cm::CMSampleBuffer;
+#[cfg(feature = "metrics")]
+use crate::cm::SCFrameStatus;
+use crate::stream::output_type::SCStreamOutputType;
+
+/// Start time for a timed section, or `None` when metrics are disabled so the
+/// hot path doesn't pay for the clock read.
+#[inline]
+pub fn start() -> Option<Instant> {
+    cfg!(feature = "metrics").then(Instant::now)
+}
+
+/// A sample arrived from `ScreenCaptureKit`. Screen samples are also counted
+/// by frame status.
+#[inline]
+pub fn sample_received(sample: &CMSampleBuffer, of_type: SCStreamOutputType) {
+    #[cfg(feature = "metrics")]
+    {
+        use crate::cm::CMSampleBufferSCExt;
+
+        metrics::counter!(crate::metrics::SAMPLES_TOTAL, "output" => output_label(of_type))
+            .increment(1);
+        if of_type == SCStreamOutputType::Screen {
+            if let Some(status) = sample.frame_status() {
+                metrics::counter!(
+                    crate::metrics::FRAME_STATUS_TOTAL,
+                    "status" => status_label(status)
+                )
+                .increment(1);
+            }
+        }
+    }
+    #[cfg(not(feature = "metrics"))]
+    {
+        let _ = (sample, of_type);
+    }
+}
+
+/// An output handler call that began at `started` has returned.
+#[inline]
+pub fn callback_finished(of_type: SCStreamOutputType, started: Option<Instant>) {
+    #[cfg(feature = "metrics")]
+    {
+        if let Some(started) = started {
+            metrics::histogram!(
+                crate::metrics::CALLBACK_DURATION_SECONDS,
+                "output" => output_label(of_type)
+            )
+            .record(started.elapsed().as_secs_f64());
+        }
+    }
+    #[cfg(not(feature = "metrics"))]
+    {
+        let _ = (of_type, started);
+    }
+}
+
+/// `component` copied or converted `bytes` of captured data on the CPU.
+#[inline]
+pub fn buffer_copied(component: &'static str, bytes: usize) {
+    #[cfg(feature = "metrics")]
+    {
+        metrics::counter!(crate::metrics::BUFFER_COPIES_TOTAL, "component" => component)
+            .increment(1);
+        metrics::counter!(crate::metrics::BUFFER_COPY_BYTES_TOTAL, "component" => component)
+            .increment(bytes as u64);
+    }
+    #[cfg(not(feature = "metrics"))]
+    {
+        let _ = (component, bytes);
+    }
+}
+
+/// A pooled output handler accepted a sample (`dropped` when the queue was
+/// full and the oldest one was evicted). `depth` is the queue length after.
+#[inline]
+pub fn output_pool_enqueued(dropped: bool, depth: usize) {
+    #[cfg(feature = "metrics")]
+    {
+        let result = if dropped { "dropped" } else { "queued" };
+        metrics::counter!(crate::metrics::OUTPUT_POOL_SAMPLES_TOTAL, "result" => result)
+            .increment(1);
+        #[allow(clippy::cast_precision_loss)]
+        metrics::gauge!(crate::metrics::OUTPUT_POOL_QUEUE_DEPTH).set(depth as f64);
+    }
+    #[cfg(not(feature = "metrics"))]
+    {
+        let _ = (dropped, depth);
+    }
+}
+
+#[cfg(feature = "metrics")]
+const fn output_label(of_type: SCStreamOutputType) -> &'static str {
+    match of_type {
+        SCStreamOutputType::Screen => "screen",
+        SCStreamOutputType::Audio => "audio",
+        SCStreamOutputType::Microphone => "microphone",
+    }
+}
+
+#[cfg(feature = "metrics")]
+const fn status_label(status: SCFrameStatus) -> &'static str {
+    match status {
+        SCFrameStatus::Complete => "complete",
+        SCFrameStatus::Idle => "idle",
+        SCFrameStatus::Blank => "blank",
+        SCFrameStatus::Suspended => "suspended",
+        SCFrameStatus::Started => "started",
+        SCFrameStatus::Stopped => "stopped",
+    }
+}
@@ -89,7 +89,7 @@
 //! # fn main() -> Result<(), Box<dyn std::error::Error>> {
 //! # let content = SCShareableContent::get()?;
 //! # let display = content.displays().into_iter().next().unwrap();
-//! # let filter = SCContentFilter::create().with_display(&display).with_excluding_windows(&[]).build();
+//! # let filter = SCContentFilter::for_display(&display).with_excluding_windows(&[]).build();
 //! # let config = SCStreamConfiguration::new();
 //! let frame_count = Arc::new(AtomicUsize::new(0));
 //! let count_clone = frame_count.clone();
@@ -120,8 +120,7 @@
 //! let display = content.displays().into_iter().next().ok_or("No display")?;
 //!
 //! // Configure what to capture
-//! let filter = SCContentFilter::create()
-//!     .with_display(&display)
+//! let filter = SCContentFilter::for_display(&display)
 //!     .with_excluding_windows(&[])
 //!     .build();
 //!
@@ -220,6 +219,12 @@
 //! # }
 //! ```
 //!
+//! The cursor reads raw bytes and assumes 8-bit BGRA. For other formats use
+//! [`cv::pixel_reader`], which decodes BGRA, `l10r` and 4:2:0 YCbCr to
+//! normalized RGBA, or [`cv::l10r`] to convert whole `l10r` frames. When
+//! only averages, dominant colors or a luminance histogram are needed,
+//! [`cv::sampling`] computes them without reading pixels one by one.
+//!
 //! ### [`IOSurface`] (GPU)
 //!
 //! For Metal/OpenGL integration, access the underlying [`IOSurface`]:
@@ -286,8 +291,7 @@
 //! let content = SCShareableContent::get()?;
 //! let display = content.displays().into_iter().next().ok_or("No display")?;
 //!
-//! let filter = SCContentFilter::create()
-//!     .with_display(&display)
+//! let filter = SCContentFilter::for_display(&display)
 //!     .with_excluding_windows(&[])
 //!     .build();
 //!
@@ -320,7 +324,7 @@
 //! # fn main() -> Result<(), Box<dyn std::error::Error>> {
 //! # let content = SCShareableContent::get()?;
 //! # let display = content.displays().into_iter().next().unwrap();
-//! # let filter = SCContentFilter::create().with_display(&display).with_excluding_windows(&[]).build();
+//! # let filter = SCContentFilter::for_display(&display).with_excluding_windows(&[]).build();
 //! # let config = SCStreamConfiguration::new().with_width(640).with_height(480);
 //! # struct MyHandler;
 //! # impl SCStreamOutputTrait for MyHandler {
@@ -342,7 +346,7 @@
 //! // Switch to a different window
 //! let windows = content.windows();
 //! if let Some(window) = windows.iter().find(|w| w.is_on_screen()) {
-//!     let window_filter = SCContentFilter::create().with_window(window).build();
+//!     let window_filter = SCContentFilter::for_window(window).build();
 //!     stream.update_content_filter(&window_filter)?;
 //! }
 //!
@@ -362,7 +366,7 @@
 //! # fn main() -> Result<(), Box<dyn std::error::Error>> {
 //! # let content = SCShareableContent::get()?;
 //! # let display = content.displays().into_iter().next().unwrap();
-//! # let filter = SCContentFilter::create().with_display(&display).with_excluding_windows(&[]).build();
+//! # let filter = SCContentFilter::for_display(&display).with_excluding_windows(&[]).build();
 //! # let config = SCStreamConfiguration::new();
 //! // Create an error handler using a closure
 //! let error_handler = ErrorHandler::new(|error| {
@@ -391,7 +395,7 @@
 //! # fn main() -> Result<(), Box<dyn std::error::Error>> {
 //! # let content = SCShareableContent::get()?;
 //! # let display = content.displays().into_iter().next().unwrap();
-//! # let filter = SCContentFilter::create().with_display(&display).with_excluding_windows(&[]).build();
+//! # let filter = SCContentFilter::for_display(&display).with_excluding_windows(&[]).build();
 //! # let config = SCStreamConfiguration::new();
 //! let mut stream = SCStream::new(&filter, &config);
 //!
@@ -421,8 +425,7 @@
 //!     let content = AsyncSCShareableContent::get().await?;
 //!     let display = &content.displays()[0];
 //!     
-//!     let filter = SCContentFilter::create()
-//!         .with_display(display)
+//!     let filter = SCContentFilter::for_display(display)
 //!         .with_excluding_windows(&[])
 //!         .build();
 //!     
@@ -469,8 +472,7 @@
 //! let content = SCShareableContent::get()?;
 //! let display = &content.displays()[0];
 //!
-//! let filter = SCContentFilter::create()
-//!     .with_display(display)
+//! let filter = SCContentFilter::for_display(display)
 //!     .with_excluding_windows(&[])
 //!     .build();
 //!
@@ -501,8 +503,7 @@
 //! let content = SCShareableContent::get()?;
 //! let display = &content.displays()[0];
 //!
-//! let filter = SCContentFilter::create()
-//!     .with_display(display)
+//! let filter = SCContentFilter::for_display(display)
 //!     .with_excluding_windows(&[])
 //!     .build();
 //!
@@ -544,13 +545,33 @@
 //! | [`stream`] | Stream configuration and management ([`SCStream`], [`SCContentFilter`]) |
 //! | [`shareable_content`] | Display, window, and application enumeration |
 //! | [`cm`] | Core Media types ([`CMSampleBuffer`], [`CMTime`], [`IOSurface`]) |
-//! | [`cv`] | Core Video types ([`CVPixelBuffer`], lock guards) |
-//! | [`cg`] | Core Graphics types ([`CGRect`], [`CGSize`]) |
+//! | [`cv`] | Core Video types ([`CVPixelBuffer`], lock guards, `l10r` decoding, color tags, copies into caller buffers) |
+//! | [`cg`] | Core Graphics types ([`CGRect`], [`CGSize`]) and coordinate-space conversions |
 //! | [`metal`] | Metal texture helpers for zero-copy GPU rendering |
 //! | [`dispatch_queue`] | Custom dispatch queues for callbacks |
-//! | [`error`] | Error types and result aliases |
+//! | [`error`] | Error types, result aliases and recovery hints |
+//! | [`muxer`] | Write sample buffers from any source into MP4 / MOV |
+//! | [`frame`] | One frame type for stream samples and screenshots |
+//! | [`image_sequence`] | Numbered PNG / TIFF / EXR / HEIF files, one per frame |
+//! | [`preview`] | Live preview in an `AVSampleBufferDisplayLayer` inside an app's view |
+//! | [`region_mask`] | Black out screen regions, such as the notification area, in captured frames |
 //! | `async_api` | Async wrappers (requires `async` feature) |
+//! | `audio_encoder` | AAC / Opus audio encoding (requires `audio_encoder` feature) |
+//! | `rtmp` | Live streaming to RTMP servers (requires `rtmp` feature) |
+//! | `replay_buffer` | Save the last few seconds of capture on demand (requires `replay_buffer` feature) |
+//! | `camera_overlay` | Webcam picture-in-picture composited into frames (requires `camera_overlay` feature) |
+//! | `metrics` | Capture-health metric names (requires `metrics` feature) |
+//! | `opengl` | `CVOpenGLTextureCache` textures for OpenGL renderers (requires `opengl` feature) |
+//! | `syphon` | Publish frames to Syphon clients (requires `syphon` feature) |
+//! | `xpc` | Capture in an XPC helper process (requires `xpc` feature) |
+//! | `profile` | Saved capture setups in TOML / JSON (requires `profiles` feature) |
+//! | `recording_metadata` | JSON sidecars with a recording's setup, frame counts, chapters and markers (requires `recording_metadata` feature) |
+//! | `vision` | Text recognition on captured frames (requires `vision` feature) |
+//! | `spectrum` | FFT spectrum and band levels of captured audio (requires `spectrum` feature) |
 //! | [`screenshot_manager`] | Single-frame capture (macOS 14.0+) |
+//! | `region_selector` | Interactive crosshair region selection (macOS 14.0+) |
+//! | `audio_output_tap` | System audio from a single output device (macOS 14.2+) |
+//! | `audio_taps` | Audio from specific applications via Core Audio taps (macOS 14.4+) |
 //! | `recording_output` | Direct file recording (macOS 15.0+) |
 //!
 //! [`SCStream`]: stream::sc_stream::SCStream
@@ -567,10 +588,24 @@
 //! | Feature | Description |
 //! |---------|-------------|
 //! | `async` | Runtime-agnostic async API |
+//! | `audio_encoder` | AAC / Opus encoding of captured audio |
+//! | `rtmp` | H.264 + AAC publishing to RTMP endpoints (implies `audio_encoder`) |
+//! | `replay_buffer` | In-memory ring of encoded GOPs, flushed to MP4/MOV on demand (implies `audio_encoder`) |
+//! | `camera_overlay` | Facecam overlay from an `AVCaptureDevice`, composited before delivery |
+//! | `metrics` | Frame, callback, copy and pool counters via the `metrics` crate |
+//! | `opengl` | Zero-copy GL textures from captured frames |
+//! | `preview_window` | Standalone preview window for debugging and demos |
+//! | `syphon` | Syphon server output for VJ and production tools |
+//! | `xpc` | Crash-isolated capture in an XPC helper, with `IOSurface` handoff |
+//! | `profiles` | Persist capture profiles as TOML or JSON (adds `serde`, `serde_json`, `toml`) |
+//! | `recording_metadata` | JSON sidecars next to recordings (adds `serde`, `serde_json`) |
+//! | `vision` | OCR of frames and screenshots with `VNRecognizeTextRequest` |
+//! | `spectrum` | Radix-2 FFT spectrum analyzer for audio visualizers |
+//! | `debug_attachments` | Stringified dump of every sample buffer attachment |
 //! | `macos_13_0` | macOS 13.0+ APIs (audio capture, synchronization clock) |
 //! | `macos_14_0` | macOS 14.0+ APIs (screenshots, content picker) |
-//! | `macos_14_2` | macOS 14.2+ APIs (menu bar, child windows, presenter overlay) |
-//! | `macos_14_4` | macOS 14.4+ APIs (current process shareable content) |
+//! | `macos_14_2` | macOS 14.2+ APIs (menu bar, child windows, presenter overlay, output device taps) |
+//! | `macos_14_4` | macOS 14.4+ APIs (current process shareable content, app audio taps) |
 //! | `macos_15_0` | macOS 15.0+ APIs (recording output, HDR, microphone) |
 //! | `macos_15_2` | macOS 15.2+ APIs (screenshot in rect, stream delegates) |
 //! | `macos_26_0` | macOS 26.0+ APIs (advanced screenshot config, HDR output) |
@@ -622,8 +657,7 @@
 //!     .find(|w| w.title().is_some_and(|t| t.contains("Safari")))
 //!     .ok_or("Window not found")?;
 //!
-//! let filter = SCContentFilter::create()
-//!     .with_window(window)
+//! let filter = SCContentFilter::for_window(window)
 //!     .build();
 //! # Ok(())
 //! # }
@@ -646,8 +680,7 @@
 //!     .ok_or("Safari not found")?;
 //!
 //! // Capture only windows from this app
-//! let filter = SCContentFilter::create()
-//!     .with_display(&display)
+//! let filter = SCContentFilter::for_display(&display)
 //!     .with_including_applications(&[safari], &[])  // Include Safari, no excepted windows
 //!     .build();
 //! # Ok(())
@@ -673,8 +706,7 @@
 //!     .collect();
 //!
 //! // Capture everything except our windows
-//! let filter = SCContentFilter::create()
-//!     .with_display(&display)
+//! let filter = SCContentFilter::for_display(&display)
 //!     .with_excluding_windows(&my_windows)
 //!     .build();
 //! # Ok(())
@@ -725,6 +757,18 @@
 #![allow(clippy::missing_const_for_fn)]
 
 pub mod audio_devices;
+#[cfg(feature = "audio_encoder")]
+#[cfg_attr(docsrs, doc(cfg(feature = "audio_encoder")))]
+pub mod audio_encoder;
+#[cfg(feature = "macos_14_2")]
+#[cfg_attr(docsrs, doc(cfg(feature = "macos_14_2")))]
+pub mod audio_output_tap;
+#[cfg(feature = "macos_14_4")]
+#[cfg_attr(docsrs, doc(cfg(feature = "macos_14_4")))]
+pub mod audio_taps;
+#[cfg(feature = "camera_overlay")]
+#[cfg_attr(docsrs, doc(cfg(feature = "camera_overlay")))]
+pub mod camera_overlay;
 pub mod cg;
 pub mod cm;
 #[cfg(feature = "macos_14_0")]
@@ -734,7 +778,23 @@ pub mod cv;
 pub mod dispatch_queue;
 pub mod error;
 pub mod ffi;
+pub mod frame;
+#[cfg(any(feature = "rtmp", feature = "replay_buffer"))]
+mod h264;
+pub mod image_sequence;
+mod instrument;
 pub mod metal;
+#[cfg(feature = "metrics")]
+#[cfg_attr(docsrs, doc(cfg(feature = "metrics")))]
+pub mod metrics;
+pub mod muxer;
+#[cfg(feature = "opengl")]
+#[cfg_attr(docsrs, doc(cfg(feature = "opengl")))]
+pub mod opengl;
+pub mod preview;
+#[cfg(feature = "profiles")]
+#[cfg_attr(docsrs, doc(cfg(feature = "profiles")))]
+pub mod profile;
 
 pub use apple_cf::cg::CGImage;
 /// Re-export of the lightweight [`apple-metal`](https://crates.io/crates/apple-metal)
@@ -747,15 +807,40 @@ pub use apple_cf::cg::CGImage;
 /// and `screencapturekit::metal::MetalDevice::as_apple_metal()` bridges
 /// between the two device handles.
 pub use apple_metal;
+#[cfg(feature = "recording_metadata")]
+#[cfg_attr(docsrs, doc(cfg(feature = "recording_metadata")))]
+pub mod recording_metadata;
 #[cfg(feature = "macos_15_0")]
 #[cfg_attr(docsrs, doc(cfg(feature = "macos_15_0")))]
 pub mod recording_output;
+pub mod region_mask;
+#[cfg(feature = "macos_14_0")]
+#[cfg_attr(docsrs, doc(cfg(feature = "macos_14_0")))]
+pub mod region_selector;
+#[cfg(feature = "replay_buffer")]
+#[cfg_attr(docsrs, doc(cfg(feature = "replay_buffer")))]
+pub mod replay_buffer;
+#[cfg(feature = "rtmp")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rtmp")))]
+pub mod rtmp;
 #[cfg(feature = "macos_14_0")]
 #[cfg_attr(docsrs, doc(cfg(feature = "macos_14_0")))]
 pub mod screenshot_manager;
 pub mod shareable_content;
+#[cfg(feature = "spectrum")]
+#[cfg_attr(docsrs, doc(cfg(feature = "spectrum")))]
+pub mod spectrum;
 pub mod stream;
+#[cfg(feature = "syphon")]
+#[cfg_attr(docsrs, doc(cfg(feature = "syphon")))]
+pub mod syphon;
 pub mod utils;
+#[cfg(feature = "vision")]
+#[cfg_attr(docsrs, doc(cfg(feature = "vision")))]
+pub mod vision;
+#[cfg(feature = "xpc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "xpc")))]
+pub mod xpc;
 
 #[cfg(feature = "async")]
 #[cfg_attr(docsrs, doc(cfg(feature = "async")))]
@@ -785,9 +870,24 @@ pub use utils::FourCharCode;
 ///
 /// | Feature | Module to import explicitly |
 /// |---|---|
-/// | `macos_14_0` | `screencapturekit::screenshot_manager`, `screencapturekit::content_sharing_picker` |
+/// | `macos_14_0` | `screencapturekit::screenshot_manager`, `screencapturekit::content_sharing_picker`, `screencapturekit::region_selector` |
+/// | `macos_14_2` | `screencapturekit::audio_output_tap` |
+/// | `macos_14_4` | `screencapturekit::audio_taps` |
 /// | `macos_15_0` | `screencapturekit::recording_output` |
 /// | `async` | `screencapturekit::async_api` |
+/// | `audio_encoder` | `screencapturekit::audio_encoder` |
+/// | `rtmp` | `screencapturekit::rtmp` |
+/// | `replay_buffer` | `screencapturekit::replay_buffer` |
+/// | `camera_overlay` | `screencapturekit::camera_overlay` |
+/// | `metrics` | `screencapturekit::metrics` |
+/// | `opengl` | `screencapturekit::opengl` |
+/// | `preview_window` | `screencapturekit::preview::PreviewWindow` |
+/// | `syphon` | `screencapturekit::syphon` |
+/// | `xpc` | `screencapturekit::xpc` |
+/// | `profiles` | `screencapturekit::profile` |
+/// | `recording_metadata` | `screencapturekit::recording_metadata` |
+/// | `vision` | `screencapturekit::vision` |
+/// | `spectrum` | `screencapturekit::spectrum` |
 ///
 /// Example:
 /// ```rust,no_run
@@ -812,6 +912,7 @@ pub mod prelude {
         output_trait::SCStreamOutputTrait,
         output_type::SCStreamOutputType,
         sc_stream::SCStream,
+        state::SCStreamState,
         ErrorHandler,
     };
 }
@@ -26,6 +26,8 @@
 //! 1. Get `IOSurface` from captured frame via [`CMSampleBuffer::image_buffer()`](crate::cm::CMSampleBuffer::image_buffer)
 //! 2. Create Metal textures with [`IOSurface::create_metal_textures()`](crate::cm::IOSurface::create_metal_textures)
 //! 3. Render using the built-in shaders or your own
+//! 4. Call [`MetalCommandBuffer::hold_textures_until_completed`] before
+//!    committing, so capture can't reuse the surface while the GPU reads it
 //!
 //! ## Example
 //!
@@ -499,6 +501,17 @@ extern "C" {
     // Command Buffer
     fn metal_command_buffer_present_drawable(cmd_buffer: *mut c_void, drawable: *mut c_void);
     fn metal_command_buffer_commit(cmd_buffer: *mut c_void);
+    fn metal_command_buffer_wait_until_completed(cmd_buffer: *mut c_void);
+    fn metal_command_buffer_hold_iosurface(cmd_buffer: *mut c_void, iosurface: *mut c_void);
+    fn metal_command_buffer_hold_texture_iosurface(
+        cmd_buffer: *mut c_void,
+        texture: *mut c_void,
+    ) -> bool;
+    fn metal_command_buffer_add_completed_handler(
+        cmd_buffer: *mut c_void,
+        callback: extern "C" fn(*mut c_void),
+        user_data: *mut c_void,
+    );
     fn metal_command_buffer_release(cmd_buffer: *mut c_void);
 
     // Render Pass
@@ -1171,6 +1184,59 @@ impl MetalCommandBuffer {
         unsafe { metal_command_buffer_commit(self.ptr.as_ptr()) }
     }
 
+    /// Block until the GPU has finished executing the committed buffer
+    pub fn wait_until_completed(&self) {
+        unsafe { metal_command_buffer_wait_until_completed(self.ptr.as_ptr()) }
+    }
+
+    /// Keep `surface` marked in use until this command buffer completes
+    ///
+    /// `ScreenCaptureKit` recycles a frame's surface as soon as nothing
+    /// holds the sample buffer and the surface's use count is zero. A
+    /// texture created from the surface does not count, so a frame whose
+    /// sample buffer is dropped while the GPU is still sampling it can be
+    /// overwritten by the next capture, which shows up as tearing. Call
+    /// this before [`commit`](Self::commit); the use count is released from
+    /// the buffer's completion handler.
+    ///
+    /// A buffer that is never committed keeps the surface in use for good.
+    pub fn hold_surface_until_completed(&self, surface: &IOSurface) {
+        unsafe { metal_command_buffer_hold_iosurface(self.ptr.as_ptr(), surface.as_ptr()) }
+    }
+
+    /// Keep the surfaces behind `textures` in use until this command
+    /// buffer completes
+    ///
+    /// Same as [`hold_surface_until_completed`](Self::hold_surface_until_completed),
+    /// for when only the textures reach the render code.
+    pub fn hold_textures_until_completed(&self, textures: &MetalCapturedTextures) {
+        // Both planes come from the same surface, so holding one is enough.
+        unsafe {
+            metal_command_buffer_hold_texture_iosurface(
+                self.ptr.as_ptr(),
+                textures.plane0.as_ptr(),
+            );
+        }
+    }
+
+    /// Run `handler` once the GPU has finished executing this buffer
+    ///
+    /// Must be called before [`commit`](Self::commit). The handler runs on
+    /// a Metal-owned thread.
+    pub fn add_completed_handler<F>(&self, handler: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        let handler: Box<Box<dyn FnOnce() + Send>> = Box::new(Box::new(handler));
+        unsafe {
+            metal_command_buffer_add_completed_handler(
+                self.ptr.as_ptr(),
+                command_buffer_completed,
+                Box::into_raw(handler).cast(),
+            );
+        }
+    }
+
     /// Get the raw pointer
     #[must_use]
     pub fn as_ptr(&self) -> *mut c_void {
@@ -1184,6 +1250,13 @@ impl Drop for MetalCommandBuffer {
     }
 }
 
+extern "C" fn command_buffer_completed(user_data: *mut c_void) {
+    // SAFETY: `user_data` is the handler boxed by `add_completed_handler`;
+    // Metal calls the completion handler exactly once.
+    let handler = unsafe { Box::from_raw(user_data.cast::<Box<dyn FnOnce() + Send>>()) };
+    crate::utils::panic_safe::catch_user_panic("metal completed handler", handler);
+}
+
 // MARK: - Render Pass Descriptor
 
 /// A render pass descriptor
@@ -0,0 +1,89 @@
+//! Capture-health metrics through the [`metrics`](https://docs.rs/metrics) facade
+//!
+//! Requires the `metrics` feature.
+//!
+//! With the feature enabled the crate records the counters and histograms
+//! below into whatever `metrics` recorder the application installed
+//! (Prometheus, `StatsD`, OpenTelemetry, …). Nothing is recorded until a
+//! recorder is installed, and without the feature the instrumentation
+//! compiles away entirely.
+//!
+//! | Name | Kind | Labels | Meaning |
+//! |------|------|--------|---------|
+//! | [`SAMPLES_TOTAL`] | counter | `output` | Samples delivered by `ScreenCaptureKit`; take its rate for frames/sec |
+//! | [`FRAME_STATUS_TOTAL`] | counter | `status` | Screen samples by [`SCFrameStatus`](crate::cm::SCFrameStatus) (`complete`, `idle`, …) |
+//! | [`CALLBACK_DURATION_SECONDS`] | histogram | `output` | Time spent in each output handler call |
+//! | [`BUFFER_COPIES_TOTAL`] | counter | `component` | CPU-side copies/conversions of captured data |
+//! | [`BUFFER_COPY_BYTES_TOTAL`] | counter | `component` | Bytes read by those copies |
+//! | [`OUTPUT_POOL_SAMPLES_TOTAL`] | counter | `result` | [`PooledOutputHandler`](crate::stream::pooled_output::PooledOutputHandler) queue hits (`queued`) and misses (`dropped`) |
+//! | [`OUTPUT_POOL_QUEUE_DEPTH`] | gauge | — | Samples waiting for a pool worker |
+//!
+//! `output` is `screen`, `audio` or `microphone`; `component` is `l10r`,
+//! `audio_chunker`, `frame_copy` or `make_owned`.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! // Install any `metrics` recorder first, e.g. `metrics-exporter-prometheus`.
+//! screencapturekit::metrics::describe();
+//! ```
+
+use metrics::{describe_counter, describe_gauge, describe_histogram, Unit};
+
+/// Samples delivered to output handlers, labelled by `output`.
+pub const SAMPLES_TOTAL: &str = "screencapturekit_samples_total";
+/// Screen samples by frame status, labelled by `status`.
+pub const FRAME_STATUS_TOTAL: &str = "screencapturekit_frame_status_total";
+/// Output handler call duration in seconds, labelled by `output`.
+pub const CALLBACK_DURATION_SECONDS: &str = "screencapturekit_callback_duration_seconds";
+/// CPU-side buffer copies, labelled by `component`.
+pub const BUFFER_COPIES_TOTAL: &str = "screencapturekit_buffer_copies_total";
+/// Bytes read by CPU-side buffer copies, labelled by `component`.
+pub const BUFFER_COPY_BYTES_TOTAL: &str = "screencapturekit_buffer_copy_bytes_total";
+/// Pooled output handler enqueue outcomes, labelled by `result`.
+pub const OUTPUT_POOL_SAMPLES_TOTAL: &str = "screencapturekit_output_pool_samples_total";
+/// Samples waiting in pooled output handler queues.
+pub const OUTPUT_POOL_QUEUE_DEPTH: &str = "screencapturekit_output_pool_queue_depth";
+
+/// Register units and descriptions for every metric with the installed
+/// recorder.
+///
+/// Optional — metrics are recorded either way — but exporters use the
+/// descriptions for help text. Call it once after installing the recorder.
+pub fn describe() {
+    describe_counter!(
+        SAMPLES_TOTAL,
+        Unit::Count,
+        "Samples delivered by ScreenCaptureKit"
+    );
+    describe_counter!(
+        FRAME_STATUS_TOTAL,
+        Unit::Count,
+        "Screen samples by SCFrameStatus"
+    );
+    describe_histogram!(
+        CALLBACK_DURATION_SECONDS,
+        Unit::Seconds,
+        "Time spent in an output handler call"
+    );
+    describe_counter!(
+        BUFFER_COPIES_TOTAL,
+        Unit::Count,
+        "CPU-side copies of captured buffers"
+    );
+    describe_counter!(
+        BUFFER_COPY_BYTES_TOTAL,
+        Unit::Bytes,
+        "Bytes read by CPU-side buffer copies"
+    );
+    describe_counter!(
+        OUTPUT_POOL_SAMPLES_TOTAL,
+        Unit::Count,
+        "Pooled output handler enqueue outcomes"
+    );
+    describe_gauge!(
+        OUTPUT_POOL_QUEUE_DEPTH,
+        Unit::Count,
+        "Samples waiting for a pooled output worker"
+    );
+}
@@ -0,0 +1,476 @@
+//! Write video and audio from any source into one MP4 or MOV file
+//!
+//! [`SCMuxer`] wraps `AVAssetWriter` without tying it to a stream the way
+//! [`SCRecordingOutput`](https://developer.apple.com/documentation/screencapturekit/screcordingoutput)
+//! is. Add the tracks first, then append [`CMSampleBuffer`]s from capture,
+//! a camera, an external microphone pipeline or an encoder of your own:
+//!
+//! - [`add_video_track`](SCMuxer::add_video_track) and
+//!   [`add_audio_track`](SCMuxer::add_audio_track) take raw pixel buffers
+//!   and PCM and encode them to H.264/HEVC and AAC.
+//! - [`add_passthrough_track`](SCMuxer::add_passthrough_track) takes
+//!   buffers that are already encoded and writes them unchanged.
+//!
+//! The file's timeline starts at the first appended sample. Sources on
+//! different clocks can be lined up with [`SCMuxer::append_at`], which
+//! moves a buffer to a new presentation time before writing it; samples
+//! earlier than the start are cut off by the writer.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use screencapturekit::muxer::{AudioTrackSettings, SCMuxer, VideoTrackSettings};
+//! use screencapturekit::prelude::*;
+//! use std::sync::Arc;
+//!
+//! # fn main() -> Result<(), SCError> {
+//! let muxer = Arc::new(SCMuxer::new("/tmp/combined.mp4")?);
+//! let video = muxer.add_video_track(&VideoTrackSettings::new(1920, 1080))?;
+//! let audio = muxer.add_audio_track(&AudioTrackSettings::new(48_000, 2))?;
+//!
+//! # let (filter, config): (SCContentFilter, SCStreamConfiguration) = todo!();
+//! let mut stream = SCStream::new(&filter, &config);
+//! let sink = Arc::clone(&muxer);
+//! stream.add_output_handler(
+//!     move |sample, of_type| {
+//!         let track = if of_type == SCStreamOutputType::Screen { video } else { audio };
+//!         let _ = sink.append(track, &sample);
+//!     },
+//!     SCStreamOutputType::Screen,
+//! );
+//! // ... a second handler for audio, a camera feeding `video2`, etc.
+//! stream.start_capture()?;
+//! // ...
+//! stream.stop_capture()?;
+//! drop(stream);
+//! Arc::try_unwrap(muxer).expect("handlers dropped").finish()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::ffi::c_void;
+use std::path::{Path, PathBuf};
+
+use crate::cm::{CMFormatDescription, CMSampleBuffer, CMTime};
+use crate::error::SCError;
+use crate::utils::completion::SyncCompletion;
+use crate::utils::ffi_string::{ffi_string_from_buffer, SMALL_BUFFER_SIZE};
+
+/// Codec for an [`SCMuxer`] video track that encodes raw frames.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum MuxerVideoCodec {
+    /// H.264 / AVC
+    #[default]
+    H264,
+    /// H.265 / HEVC
+    Hevc,
+}
+
+/// A video track that encodes raw pixel buffers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VideoTrackSettings {
+    codec: MuxerVideoCodec,
+    width: u32,
+    height: u32,
+    bitrate: u32,
+    real_time: bool,
+}
+
+impl VideoTrackSettings {
+    /// H.264 at `width` x `height` with the encoder's default bitrate.
+    #[must_use]
+    pub const fn new(width: u32, height: u32) -> Self {
+        Self {
+            codec: MuxerVideoCodec::H264,
+            width,
+            height,
+            bitrate: 0,
+            real_time: true,
+        }
+    }
+
+    /// Output codec.
+    #[must_use]
+    pub const fn with_codec(mut self, codec: MuxerVideoCodec) -> Self {
+        self.codec = codec;
+        self
+    }
+
+    /// Average bitrate in bits per second; 0 leaves it to the encoder.
+    #[must_use]
+    pub const fn with_bitrate(mut self, bitrate: u32) -> Self {
+        self.bitrate = bitrate;
+        self
+    }
+
+    /// Whether frames arrive live (the default). Turn off when appending
+    /// from a file or another source that can wait, so the writer
+    /// interleaves tracks optimally instead of favoring low latency.
+    #[must_use]
+    pub const fn with_real_time(mut self, real_time: bool) -> Self {
+        self.real_time = real_time;
+        self
+    }
+
+    /// Output codec.
+    #[must_use]
+    pub const fn codec(&self) -> MuxerVideoCodec {
+        self.codec
+    }
+
+    /// Output size in pixels.
+    #[must_use]
+    pub const fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    /// Average bitrate, or 0 for the encoder default.
+    #[must_use]
+    pub const fn bitrate(&self) -> u32 {
+        self.bitrate
+    }
+
+    /// Whether frames arrive live.
+    #[must_use]
+    pub const fn real_time(&self) -> bool {
+        self.real_time
+    }
+}
+
+/// An audio track that encodes raw PCM to AAC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AudioTrackSettings {
+    sample_rate: u32,
+    channel_count: u32,
+    bitrate: u32,
+    real_time: bool,
+}
+
+impl AudioTrackSettings {
+    /// AAC at `sample_rate` Hz with `channel_count` channels and the
+    /// encoder's default bitrate.
+    #[must_use]
+    pub const fn new(sample_rate: u32, channel_count: u32) -> Self {
+        Self {
+            sample_rate,
+            channel_count,
+            bitrate: 0,
+            real_time: true,
+        }
+    }
+
+    /// Bitrate in bits per second; 0 leaves it to the encoder.
+    #[must_use]
+    pub const fn with_bitrate(mut self, bitrate: u32) -> Self {
+        self.bitrate = bitrate;
+        self
+    }
+
+    /// Whether audio arrives live (the default). See
+    /// [`VideoTrackSettings::with_real_time`].
+    #[must_use]
+    pub const fn with_real_time(mut self, real_time: bool) -> Self {
+        self.real_time = real_time;
+        self
+    }
+
+    /// Output sample rate in Hz.
+    #[must_use]
+    pub const fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Output channel count.
+    #[must_use]
+    pub const fn channel_count(&self) -> u32 {
+        self.channel_count
+    }
+
+    /// Bitrate, or 0 for the encoder default.
+    #[must_use]
+    pub const fn bitrate(&self) -> u32 {
+        self.bitrate
+    }
+
+    /// Whether audio arrives live.
+    #[must_use]
+    pub const fn real_time(&self) -> bool {
+        self.real_time
+    }
+}
+
+/// Handle to a track of an [`SCMuxer`], returned when the track is added.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MuxerTrack(i32);
+
+impl MuxerTrack {
+    /// Position of the track in the file, starting at 0.
+    #[must_use]
+    #[allow(clippy::cast_sign_loss)]
+    pub const fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// `AVAssetWriter`-backed muxer for sample buffers from any source.
+///
+/// All methods take `&self` and are serialized internally, so one muxer can
+/// be shared between output handlers running on different queues. See the
+/// [module documentation](self).
+pub struct SCMuxer {
+    ptr: *const c_void,
+    path: PathBuf,
+}
+
+// SAFETY: the Swift side serializes every call on the writer with a lock.
+unsafe impl Send for SCMuxer {}
+unsafe impl Sync for SCMuxer {}
+
+impl std::fmt::Debug for SCMuxer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SCMuxer")
+            .field("path", &self.path)
+            .finish_non_exhaustive()
+    }
+}
+
+impl SCMuxer {
+    /// Create a muxer writing to `path`.
+    ///
+    /// The file is MOV if `path` ends in `.mov`, otherwise MP4. An existing
+    /// file is replaced.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidConfiguration`] if `path` contains a NUL
+    /// byte, or [`SCError::OSError`] if `AVAssetWriter` rejects the path.
+    pub fn new(path: impl AsRef<Path>) -> Result<Self, SCError> {
+        let path = path.as_ref();
+        let c_path = std::ffi::CString::new(path.to_string_lossy().as_bytes())
+            .map_err(|_| SCError::invalid_config("muxer path contains a NUL byte"))?;
+        let is_mov = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("mov"));
+        let mut status = 0;
+        let ptr =
+            unsafe { crate::ffi::sc_muxer_create(c_path.as_ptr(), i32::from(is_mov), &mut status) };
+        if ptr.is_null() {
+            return Err(SCError::os_error(
+                status,
+                format!("failed to create muxer for {}", path.display()),
+            ));
+        }
+        Ok(Self {
+            ptr,
+            path: path.to_path_buf(),
+        })
+    }
+
+    /// Where the file is written.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Add a track that encodes raw video frames.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidDimension`] for a zero size, or
+    /// [`SCError::InvalidConfiguration`] if samples have already been
+    /// appended or the writer rejects the settings.
+    #[allow(clippy::cast_possible_wrap)]
+    pub fn add_video_track(&self, settings: &VideoTrackSettings) -> Result<MuxerTrack, SCError> {
+        if settings.width == 0 {
+            return Err(SCError::invalid_dimension("width", 0));
+        }
+        if settings.height == 0 {
+            return Err(SCError::invalid_dimension("height", 0));
+        }
+        let codec = match settings.codec {
+            MuxerVideoCodec::H264 => 0,
+            MuxerVideoCodec::Hevc => 1,
+        };
+        self.track(unsafe {
+            crate::ffi::sc_muxer_add_video_track(
+                self.ptr,
+                codec,
+                settings.width as i32,
+                settings.height as i32,
+                settings.bitrate as i32,
+                settings.real_time,
+            )
+        })
+    }
+
+    /// Add a track that encodes raw PCM audio to AAC.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidDimension`] for a zero sample rate or
+    /// channel count, or [`SCError::InvalidConfiguration`] if samples have
+    /// already been appended or the writer rejects the settings.
+    #[allow(clippy::cast_possible_wrap)]
+    pub fn add_audio_track(&self, settings: &AudioTrackSettings) -> Result<MuxerTrack, SCError> {
+        if settings.sample_rate == 0 {
+            return Err(SCError::invalid_dimension("sample_rate", 0));
+        }
+        if settings.channel_count == 0 {
+            return Err(SCError::invalid_dimension("channel_count", 0));
+        }
+        self.track(unsafe {
+            crate::ffi::sc_muxer_add_audio_track(
+                self.ptr,
+                f64::from(settings.sample_rate),
+                settings.channel_count as i32,
+                settings.bitrate as i32,
+                settings.real_time,
+            )
+        })
+    }
+
+    /// Add a track for already-encoded buffers described by `format`, which
+    /// are written without re-encoding.
+    ///
+    /// Take `format` from the encoder's first output buffer. The track is
+    /// treated as live; see [`VideoTrackSettings::with_real_time`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidConfiguration`] if samples have already
+    /// been appended or the writer can't store this format in the file type.
+    pub fn add_passthrough_track(
+        &self,
+        format: &CMFormatDescription,
+    ) -> Result<MuxerTrack, SCError> {
+        self.track(unsafe {
+            crate::ffi::sc_muxer_add_passthrough_track(self.ptr, format.as_ptr(), true)
+        })
+    }
+
+    fn track(&self, index: i32) -> Result<MuxerTrack, SCError> {
+        if index < 0 {
+            return Err(SCError::invalid_config(format!(
+                "cannot add track to {}: writing has started or the settings were rejected",
+                self.path.display()
+            )));
+        }
+        Ok(MuxerTrack(index))
+    }
+
+    /// Append `sample` to `track` at its own presentation time.
+    ///
+    /// Buffers without media, such as idle capture frames, are skipped.
+    /// The first appended sample starts the file's timeline; no tracks can
+    /// be added after that.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidConfiguration`] for a track from another
+    /// muxer, or [`SCError::InternalError`] if the writer stays busy or
+    /// rejects the buffer.
+    pub fn append(&self, track: MuxerTrack, sample: &CMSampleBuffer) -> Result<(), SCError> {
+        self.append_inner(track, sample, None)
+    }
+
+    /// Append `sample` to `track`, moved to presentation time `pts`.
+    ///
+    /// Use this to line up sources on different clocks, for example by
+    /// subtracting each source's first timestamp and adding a shared origin.
+    /// Multi-sample buffers (audio) keep their internal spacing.
+    ///
+    /// # Errors
+    ///
+    /// See [`append`](Self::append).
+    pub fn append_at(
+        &self,
+        track: MuxerTrack,
+        sample: &CMSampleBuffer,
+        pts: CMTime,
+    ) -> Result<(), SCError> {
+        self.append_inner(track, sample, Some(pts))
+    }
+
+    fn append_inner(
+        &self,
+        track: MuxerTrack,
+        sample: &CMSampleBuffer,
+        pts: Option<CMTime>,
+    ) -> Result<(), SCError> {
+        let (retime, value, timescale) =
+            pts.map_or((false, 0, 0), |t| (true, t.value, t.timescale));
+        let status = unsafe {
+            crate::ffi::sc_muxer_append(
+                self.ptr,
+                track.0,
+                sample.as_ptr(),
+                retime,
+                value,
+                timescale,
+            )
+        };
+        match status {
+            0 | 1 => Ok(()),
+            -1 => Err(SCError::invalid_config(format!(
+                "muxer has no track {}",
+                track.index()
+            ))),
+            -2 => Err(SCError::internal_error("failed to retime sample buffer")),
+            -3 => Err(SCError::internal_error(format!(
+                "muxer track {} is not accepting data",
+                track.index()
+            ))),
+            _ => Err(SCError::internal_error(self.writer_error().map_or_else(
+                || format!("muxer rejected a sample for track {}", track.index()),
+                |error| format!("muxer rejected a sample: {error}"),
+            ))),
+        }
+    }
+
+    fn writer_error(&self) -> Option<String> {
+        unsafe {
+            ffi_string_from_buffer(SMALL_BUFFER_SIZE, |buffer, len| {
+                crate::ffi::sc_muxer_get_error(self.ptr, buffer, len)
+            })
+        }
+    }
+
+    /// Finish writing and close the file. Blocks until it is complete.
+    ///
+    /// Dropping a muxer without calling this cancels the file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if nothing was appended or `AVAssetWriter` fails to
+    /// finalize the file.
+    pub fn finish(self) -> Result<(), SCError> {
+        let (completion, context) = SyncCompletion::<Result<(), SCError>>::new();
+        unsafe { crate::ffi::sc_muxer_finish(self.ptr, context, finish_callback) };
+        completion
+            .wait()
+            .map_err(SCError::InternalError)
+            .and_then(|result| result)
+    }
+}
+
+impl Drop for SCMuxer {
+    fn drop(&mut self) {
+        unsafe { crate::ffi::sc_muxer_release(self.ptr) }
+    }
+}
+
+extern "C" fn finish_callback(context: *mut c_void, success: bool, error: *const c_void) {
+    crate::utils::panic_safe::catch_user_panic("muxer_finish_callback", move || {
+        let result = if success || error.is_null() {
+            Ok(())
+        } else {
+            // SAFETY: Swift lends a live NSError for the duration of the callback.
+            Err(SCError::from_ns_error(unsafe {
+                crate::error::NSErrorInfo::from_borrowed(error)
+            }))
+        };
+        // SAFETY: `context` is the one-shot completion context from
+        // `SyncCompletion::new()`; Swift invokes this callback exactly once.
+        unsafe { SyncCompletion::<Result<(), SCError>>::complete_ok(context, result) };
+    });
+}
@@ -0,0 +1,284 @@
+//! OpenGL texture helpers for captured frames
+//!
+//! Requires the `opengl` feature.
+//!
+//! Wraps Core Video's `CVOpenGLTextureCache`, which turns an `IOSurface`-backed
+//! [`CVPixelBuffer`] into a GL texture name without copying pixels. This is
+//! the OpenGL counterpart of [`crate::metal`], for renderers that have not
+//! moved off OpenGL (overlays, older game engines, legacy preview views).
+//!
+//! OpenGL is deprecated on macOS; prefer [`crate::metal`] for new code.
+//!
+//! ## Texture targets
+//!
+//! Surface-backed textures are bound to `GL_TEXTURE_RECTANGLE`
+//! ([`GL_TEXTURE_RECTANGLE`]), so they are sampled with pixel rather than
+//! normalized coordinates. Use [`OpenGLTexture::clean_tex_coords`] to get the
+//! corners of the visible area in the texture's own coordinate space, and
+//! [`OpenGLTexture::is_flipped`] to learn whether the image is stored
+//! upside down.
+//!
+//! ## Lifetimes
+//!
+//! An [`OpenGLTexture`] keeps its pixel buffer alive, so the capture pool
+//! cannot hand the surface back to `ScreenCaptureKit` while GL reads it. Drop
+//! textures as soon as the frame has been drawn and call
+//! [`OpenGLTextureCache::flush`] once per frame so the cache can recycle
+//! texture names.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use std::ffi::c_void;
+//! use screencapturekit::cm::{CMSampleBuffer, CMSampleBufferExt};
+//! use screencapturekit::opengl::OpenGLTextureCache;
+//!
+//! # let cgl_context: *mut c_void = std::ptr::null_mut();
+//! // `cgl_context` is the `CGLContextObj` your view renders with.
+//! let cache = unsafe { OpenGLTextureCache::new(cgl_context, std::ptr::null_mut()) }.unwrap();
+//!
+//! let draw = |sample: &CMSampleBuffer| {
+//!     let Some(pixel_buffer) = sample.image_buffer() else { return };
+//!     let texture = cache.texture(&pixel_buffer).unwrap();
+//!     // glBindTexture(texture.target(), texture.name());
+//!     // ... draw a quad using texture.clean_tex_coords() ...
+//!     drop(texture);
+//!     cache.flush();
+//! };
+//! # let _ = draw;
+//! ```
+
+use std::ffi::c_void;
+use std::fmt;
+use std::ptr::{self, NonNull};
+
+use crate::cv::CVPixelBuffer;
+use crate::error::SCError;
+
+/// `GL_TEXTURE_2D`.
+pub const GL_TEXTURE_2D: u32 = 0x0DE1;
+
+/// `GL_TEXTURE_RECTANGLE`, the target used for `IOSurface`-backed textures.
+pub const GL_TEXTURE_RECTANGLE: u32 = 0x84F5;
+
+/// A cache that maps captured pixel buffers to OpenGL textures.
+///
+/// The cache is tied to the CGL context it was created with. Use it, and the
+/// textures it returns, only while that context is current on the calling
+/// thread.
+pub struct OpenGLTextureCache {
+    ptr: NonNull<c_void>,
+}
+
+impl OpenGLTextureCache {
+    /// Create a texture cache for `cgl_context`.
+    ///
+    /// Pass a null `cgl_pixel_format` to use the context's own pixel format.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidConfiguration`] for a null context, or
+    /// [`SCError::OSError`] with the `CVReturn` code if Core Video cannot
+    /// create the cache.
+    ///
+    /// # Safety
+    ///
+    /// `cgl_context` must be a valid `CGLContextObj`, and `cgl_pixel_format`
+    /// must be null or a valid `CGLPixelFormatObj` compatible with it. The
+    /// context must outlive the cache.
+    pub unsafe fn new(
+        cgl_context: *mut c_void,
+        cgl_pixel_format: *mut c_void,
+    ) -> Result<Self, SCError> {
+        if cgl_context.is_null() {
+            return Err(SCError::invalid_config("CGL context is null"));
+        }
+        let pixel_format = if cgl_pixel_format.is_null() {
+            unsafe { CGLGetPixelFormat(cgl_context) }
+        } else {
+            cgl_pixel_format
+        };
+
+        let mut cache = ptr::null_mut();
+        let status = unsafe {
+            CVOpenGLTextureCacheCreate(
+                ptr::null(),
+                ptr::null(),
+                cgl_context,
+                pixel_format,
+                ptr::null(),
+                &mut cache,
+            )
+        };
+        match NonNull::new(cache) {
+            Some(ptr) if status == K_CV_RETURN_SUCCESS => Ok(Self { ptr }),
+            _ => Err(SCError::os_error(
+                status,
+                "failed to create CVOpenGLTextureCache",
+            )),
+        }
+    }
+
+    /// Create (or reuse) a GL texture backed by `pixel_buffer`.
+    ///
+    /// The pixel buffer must be `IOSurface`-backed, which every frame
+    /// delivered by `SCStream` is.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::OSError`] with the `CVReturn` code if Core Video
+    /// cannot create the texture, e.g. for a pixel format the context's
+    /// renderer does not support.
+    pub fn texture(&self, pixel_buffer: &CVPixelBuffer) -> Result<OpenGLTexture, SCError> {
+        let mut texture = ptr::null_mut();
+        let status = unsafe {
+            CVOpenGLTextureCacheCreateTextureFromImage(
+                ptr::null(),
+                self.ptr.as_ptr(),
+                pixel_buffer.as_ptr(),
+                ptr::null(),
+                &mut texture,
+            )
+        };
+        match NonNull::new(texture) {
+            Some(ptr) if status == K_CV_RETURN_SUCCESS => Ok(OpenGLTexture { ptr }),
+            _ => Err(SCError::os_error(
+                status,
+                "failed to create OpenGL texture from pixel buffer",
+            )),
+        }
+    }
+
+    /// Let the cache recycle textures that are no longer referenced.
+    ///
+    /// Call once per frame, after the frame's textures have been dropped.
+    pub fn flush(&self) {
+        unsafe { CVOpenGLTextureCacheFlush(self.ptr.as_ptr(), 0) }
+    }
+
+    /// Raw `CVOpenGLTextureCacheRef`, borrowed.
+    pub fn as_ptr(&self) -> *mut c_void {
+        self.ptr.as_ptr()
+    }
+}
+
+impl Drop for OpenGLTextureCache {
+    fn drop(&mut self) {
+        unsafe { CFRelease(self.ptr.as_ptr()) }
+    }
+}
+
+impl fmt::Debug for OpenGLTextureCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OpenGLTextureCache")
+            .field("ptr", &self.ptr)
+            .finish()
+    }
+}
+
+/// An OpenGL texture that shares storage with a captured frame.
+pub struct OpenGLTexture {
+    ptr: NonNull<c_void>,
+}
+
+impl OpenGLTexture {
+    /// The GL texture name to pass to `glBindTexture`.
+    pub fn name(&self) -> u32 {
+        unsafe { CVOpenGLTextureGetName(self.ptr.as_ptr()) }
+    }
+
+    /// The GL texture target, normally [`GL_TEXTURE_RECTANGLE`].
+    pub fn target(&self) -> u32 {
+        unsafe { CVOpenGLTextureGetTarget(self.ptr.as_ptr()) }
+    }
+
+    /// Whether the image is stored with its origin at the bottom left.
+    pub fn is_flipped(&self) -> bool {
+        unsafe { CVOpenGLTextureIsFlipped(self.ptr.as_ptr()) != 0 }
+    }
+
+    /// Texture coordinates of the visible area's corners, in the order
+    /// lower-left, lower-right, upper-right, upper-left.
+    ///
+    /// Coordinates are in pixels for [`GL_TEXTURE_RECTANGLE`] textures and
+    /// already account for [`is_flipped`](Self::is_flipped).
+    pub fn clean_tex_coords(&self) -> [[f32; 2]; 4] {
+        let mut corners = [[0.0_f32; 2]; 4];
+        let [lower_left, lower_right, upper_right, upper_left] = &mut corners;
+        unsafe {
+            CVOpenGLTextureGetCleanTexCoords(
+                self.ptr.as_ptr(),
+                lower_left.as_mut_ptr(),
+                lower_right.as_mut_ptr(),
+                upper_right.as_mut_ptr(),
+                upper_left.as_mut_ptr(),
+            );
+        }
+        corners
+    }
+
+    /// Raw `CVOpenGLTextureRef`, borrowed.
+    pub fn as_ptr(&self) -> *mut c_void {
+        self.ptr.as_ptr()
+    }
+}
+
+impl Drop for OpenGLTexture {
+    fn drop(&mut self) {
+        unsafe { CFRelease(self.ptr.as_ptr()) }
+    }
+}
+
+impl fmt::Debug for OpenGLTexture {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OpenGLTexture")
+            .field("name", &self.name())
+            .field("target", &format_args!("{:#06x}", self.target()))
+            .field("flipped", &self.is_flipped())
+            .finish()
+    }
+}
+
+// MARK: - FFI Declarations
+
+const K_CV_RETURN_SUCCESS: i32 = 0;
+
+#[link(name = "OpenGL", kind = "framework")]
+extern "C" {
+    fn CGLGetPixelFormat(ctx: *mut c_void) -> *mut c_void;
+}
+
+#[link(name = "CoreVideo", kind = "framework")]
+extern "C" {
+    fn CVOpenGLTextureCacheCreate(
+        allocator: *const c_void,
+        cache_attributes: *const c_void,
+        cgl_context: *mut c_void,
+        cgl_pixel_format: *mut c_void,
+        texture_attributes: *const c_void,
+        cache_out: *mut *mut c_void,
+    ) -> i32;
+    fn CVOpenGLTextureCacheCreateTextureFromImage(
+        allocator: *const c_void,
+        texture_cache: *mut c_void,
+        source_image: *mut c_void,
+        attributes: *const c_void,
+        texture_out: *mut *mut c_void,
+    ) -> i32;
+    fn CVOpenGLTextureCacheFlush(texture_cache: *mut c_void, options: u64);
+    fn CVOpenGLTextureGetName(image: *mut c_void) -> u32;
+    fn CVOpenGLTextureGetTarget(image: *mut c_void) -> u32;
+    fn CVOpenGLTextureIsFlipped(image: *mut c_void) -> u8;
+    fn CVOpenGLTextureGetCleanTexCoords(
+        image: *mut c_void,
+        lower_left: *mut f32,
+        lower_right: *mut f32,
+        upper_right: *mut f32,
+        upper_left: *mut f32,
+    );
+}
+
+#[link(name = "CoreFoundation", kind = "framework")]
+extern "C" {
+    fn CFRelease(cf: *mut c_void);
+}
@@ -0,0 +1,403 @@
+//! Live preview of a capture in an `AVSampleBufferDisplayLayer`
+//!
+//! A capture app usually shows what it records. Drawing frames yourself
+//! means a Metal or OpenGL renderer, color conversion for YCbCr formats and
+//! a `CVDisplayLink`; `AVSampleBufferDisplayLayer` does all of that for
+//! sample buffers handed to it. [`PreviewLayer`] owns such a layer, attaches
+//! it to an `NSView` or `CALayer` of the app's, and
+//! [`output_handler`](PreviewLayer::output_handler) feeds it every screen
+//! frame straight from the stream, with no copies.
+//!
+//! Frames are shown as they arrive rather than at their presentation time,
+//! so the preview runs as close to live as the layer allows. When the layer
+//! is still decoding earlier frames, new ones are dropped and counted in
+//! [`dropped_frames`](PreviewLayer::dropped_frames); a preview that falls
+//! behind would only show stale content.
+//!
+//! With the `preview_window` feature, `PreviewWindow` puts a preview in a
+//! window of its own, for debugging and demos that have no UI yet.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use std::ffi::c_void;
+//! use screencapturekit::preview::PreviewLayer;
+//! use screencapturekit::prelude::*;
+//!
+//! # fn example(stream: &mut SCStream, ns_view: *mut c_void) {
+//! let preview = PreviewLayer::new();
+//! // SAFETY: `ns_view` is a live NSView owned by the app's window.
+//! unsafe { preview.attach_to_view(ns_view) };
+//! stream.add_output_handler(preview.output_handler(), SCStreamOutputType::Screen);
+//! # }
+//! ```
+
+use std::ffi::c_void;
+#[cfg(feature = "preview_window")]
+use std::ffi::CString;
+use std::fmt;
+use std::sync::Arc;
+#[cfg(feature = "preview_window")]
+use std::time::Duration;
+
+use crate::cm::{CMSampleBuffer, CMSampleBufferSCExt, SCFrameStatus};
+#[cfg(feature = "preview_window")]
+use crate::error::SCError;
+use crate::stream::output_trait::{SCStreamOutputTrait, SampleDelivery};
+use crate::stream::output_type::SCStreamOutputType;
+
+/// How frames fill the layer when their aspect ratio differs from it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum PreviewGravity {
+    /// Fit inside the layer, letterboxed
+    #[default]
+    ResizeAspect,
+    /// Fill the layer, cropping the overflow
+    ResizeAspectFill,
+    /// Stretch to the layer's size
+    Resize,
+}
+
+impl PreviewGravity {
+    const fn id(self) -> i32 {
+        match self {
+            Self::ResizeAspect => 0,
+            Self::ResizeAspectFill => 1,
+            Self::Resize => 2,
+        }
+    }
+}
+
+struct PreviewHandle(*const c_void);
+
+impl Drop for PreviewHandle {
+    fn drop(&mut self) {
+        unsafe { crate::ffi::sc_preview_release(self.0) }
+    }
+}
+
+// SAFETY: the Swift box serializes enqueue and flush with its own lock and
+// moves every layer-tree change to the main thread.
+unsafe impl Send for PreviewHandle {}
+unsafe impl Sync for PreviewHandle {}
+
+/// An `AVSampleBufferDisplayLayer` showing captured frames
+///
+/// Clones share the layer. It is detached from its superlayer when the last
+/// clone and every [`PreviewOutput`] are dropped.
+#[derive(Clone)]
+pub struct PreviewLayer {
+    handle: Arc<PreviewHandle>,
+}
+
+impl PreviewLayer {
+    /// A new, unattached layer with a black background
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            handle: Arc::new(PreviewHandle(unsafe { crate::ffi::sc_preview_create() })),
+        }
+    }
+
+    /// Show the layer in `view`, filling it and following its size
+    ///
+    /// Turns on `wantsLayer` and adds the preview as a sublayer of the
+    /// view's backing layer. The change is made on the main thread; from
+    /// another thread it is queued there and this returns right away.
+    ///
+    /// # Safety
+    ///
+    /// `view` must be a valid `NSView` pointer that stays alive until the
+    /// change has run on the main thread.
+    pub unsafe fn attach_to_view(&self, view: *mut c_void) {
+        unsafe { crate::ffi::sc_preview_attach_to_view(self.handle.0, view) }
+    }
+
+    /// Show the layer inside `superlayer`, filling it and following its
+    /// size
+    ///
+    /// Made on the main thread, like [`attach_to_view`](Self::attach_to_view).
+    ///
+    /// # Safety
+    ///
+    /// `superlayer` must be a valid `CALayer` pointer that stays alive until
+    /// the change has run on the main thread.
+    pub unsafe fn attach_to_layer(&self, superlayer: *mut c_void) {
+        unsafe { crate::ffi::sc_preview_attach_to_layer(self.handle.0, superlayer) }
+    }
+
+    /// The `AVSampleBufferDisplayLayer` itself, for apps that lay it out
+    /// on their own
+    ///
+    /// The pointer is not retained and stays valid while this preview
+    /// lives.
+    #[must_use]
+    pub fn layer_ptr(&self) -> *mut c_void {
+        unsafe { crate::ffi::sc_preview_get_layer(self.handle.0).cast_mut() }
+    }
+
+    /// Set how frames fill the layer
+    pub fn set_gravity(&self, gravity: PreviewGravity) {
+        unsafe { crate::ffi::sc_preview_set_video_gravity(self.handle.0, gravity.id()) }
+    }
+
+    /// Show `sample` as soon as it's decoded
+    ///
+    /// Returns `false` without showing anything for samples that carry no
+    /// image (idle frames, audio) and for frames dropped because the layer
+    /// is busy. A layer that failed, e.g. after the GPU was reset, is
+    /// flushed and starts over with this frame.
+    pub fn enqueue(&self, sample: &CMSampleBuffer) -> bool {
+        unsafe { crate::ffi::sc_preview_enqueue(self.handle.0, sample.as_ptr()) }
+    }
+
+    /// Discard queued frames, keeping the one on screen
+    pub fn flush(&self) {
+        unsafe { crate::ffi::sc_preview_flush(self.handle.0, false) }
+    }
+
+    /// Discard queued frames and blank the layer, e.g. after capture stops
+    pub fn clear(&self) {
+        unsafe { crate::ffi::sc_preview_flush(self.handle.0, true) }
+    }
+
+    /// Frames dropped because the layer wasn't ready for them
+    #[must_use]
+    pub fn dropped_frames(&self) -> u64 {
+        unsafe { crate::ffi::sc_preview_dropped_frames(self.handle.0) }
+    }
+
+    /// An output handler that shows every complete screen frame
+    #[must_use]
+    pub fn output_handler(&self) -> PreviewOutput {
+        PreviewOutput {
+            layer: self.clone(),
+        }
+    }
+}
+
+impl Default for PreviewLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for PreviewLayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PreviewLayer")
+            .field("layer", &self.layer_ptr())
+            .field("dropped_frames", &self.dropped_frames())
+            .finish()
+    }
+}
+
+/// Output handler feeding a [`PreviewLayer`]
+///
+/// Created by [`PreviewLayer::output_handler`]. Audio samples and frames
+/// without new content are ignored.
+#[derive(Clone, Debug)]
+pub struct PreviewOutput {
+    layer: PreviewLayer,
+}
+
+impl SCStreamOutputTrait for PreviewOutput {
+    fn did_output_sample_buffer(&self, sample_buffer: CMSampleBuffer, of_type: SCStreamOutputType) {
+        self.did_output_sample_buffer_borrowed(&sample_buffer, of_type);
+    }
+
+    fn delivery(&self) -> SampleDelivery {
+        SampleDelivery::Borrowed
+    }
+
+    fn did_output_sample_buffer_borrowed(
+        &self,
+        sample_buffer: &CMSampleBuffer,
+        of_type: SCStreamOutputType,
+    ) {
+        if of_type == SCStreamOutputType::Screen
+            && sample_buffer.frame_status() == Some(SCFrameStatus::Complete)
+        {
+            self.layer.enqueue(sample_buffer);
+        }
+    }
+}
+
+// MARK: - Preview window
+
+/// A window showing what a stream captures
+///
+/// Requires the `preview_window` feature.
+///
+/// The window is an ordinary titled, resizable `NSWindow` holding a
+/// [`PreviewLayer`]; it starts hidden. All methods can be called from any
+/// thread and change the window on the main thread. The main thread has to
+/// run an event loop for the window to appear: an app's `NSApplication`
+/// does, and a command-line tool can hand its main thread to
+/// [`run_until_closed`](Self::run_until_closed).
+///
+/// # Example
+///
+/// ```no_run
+/// use screencapturekit::preview::PreviewWindow;
+/// use screencapturekit::prelude::*;
+///
+/// # fn example(filter: &SCContentFilter, config: &SCStreamConfiguration) -> Result<(), SCError> {
+/// let window = PreviewWindow::new("Capture preview", 960.0, 540.0)?;
+/// let mut stream = SCStream::new(filter, config);
+/// stream.add_output_handler(window.output_handler(), SCStreamOutputType::Screen);
+/// stream.start_capture()?;
+///
+/// window.show();
+/// window.run_until_closed(None)?; // on the main thread
+/// stream.stop_capture()
+/// # }
+/// ```
+#[cfg(feature = "preview_window")]
+#[cfg_attr(docsrs, doc(cfg(feature = "preview_window")))]
+pub struct PreviewWindow {
+    ptr: *const c_void,
+    layer: PreviewLayer,
+}
+
+#[cfg(feature = "preview_window")]
+impl PreviewWindow {
+    /// A hidden window titled `title` with a `width` x `height` point
+    /// content area
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidConfiguration`] if the title contains a
+    /// NUL byte or the size isn't positive.
+    pub fn new(title: &str, width: f64, height: f64) -> Result<Self, SCError> {
+        let title = window_title(title)?;
+        check_window_size(width, height)?;
+        let layer = PreviewLayer::new();
+        let ptr = unsafe {
+            crate::ffi::sc_preview_window_create(layer.handle.0, title.as_ptr(), width, height)
+        };
+        Ok(Self { ptr, layer })
+    }
+
+    /// The preview in the window
+    #[must_use]
+    pub const fn layer(&self) -> &PreviewLayer {
+        &self.layer
+    }
+
+    /// An output handler that shows every complete screen frame in the
+    /// window
+    #[must_use]
+    pub fn output_handler(&self) -> PreviewOutput {
+        self.layer.output_handler()
+    }
+
+    /// Bring the window to the front, also after the user closed it
+    pub fn show(&self) {
+        unsafe { crate::ffi::sc_preview_window_show(self.ptr) }
+    }
+
+    /// Take the window off screen; frames keep arriving for the next
+    /// [`show`](Self::show)
+    pub fn hide(&self) {
+        unsafe { crate::ffi::sc_preview_window_hide(self.ptr) }
+    }
+
+    /// Resize the content area to `width` x `height` points, keeping the
+    /// top-left corner in place
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidConfiguration`] if the size isn't
+    /// positive.
+    pub fn set_size(&self, width: f64, height: f64) -> Result<(), SCError> {
+        check_window_size(width, height)?;
+        unsafe { crate::ffi::sc_preview_window_set_size(self.ptr, width, height) };
+        Ok(())
+    }
+
+    /// Change the window title
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidConfiguration`] if the title contains a
+    /// NUL byte.
+    pub fn set_title(&self, title: &str) -> Result<(), SCError> {
+        let title = window_title(title)?;
+        unsafe { crate::ffi::sc_preview_window_set_title(self.ptr, title.as_ptr()) };
+        Ok(())
+    }
+
+    /// Whether the window is shown and not minimized
+    #[must_use]
+    pub fn is_visible(&self) -> bool {
+        unsafe { crate::ffi::sc_preview_window_is_visible(self.ptr) }
+    }
+
+    /// Whether the user closed the window since it was last shown
+    #[must_use]
+    pub fn is_closed(&self) -> bool {
+        unsafe { crate::ffi::sc_preview_window_is_closed(self.ptr) }
+    }
+
+    /// Run the main thread's event loop until the user closes the window or
+    /// `timeout` passes
+    ///
+    /// For command-line tools without an `NSApplication` of their own.
+    /// Returns right away if the window is already closed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidConfiguration`] when not called on the
+    /// main thread, which is the only one that can run `AppKit`'s loop.
+    pub fn run_until_closed(&self, timeout: Option<Duration>) -> Result<(), SCError> {
+        let seconds = timeout.map_or(-1.0, |timeout| timeout.as_secs_f64());
+        if unsafe { crate::ffi::sc_preview_window_run(self.ptr, seconds) } {
+            Ok(())
+        } else {
+            Err(SCError::invalid_config(
+                "PreviewWindow::run_until_closed must be called on the main thread",
+            ))
+        }
+    }
+}
+
+#[cfg(feature = "preview_window")]
+fn window_title(title: &str) -> Result<CString, SCError> {
+    CString::new(title).map_err(|_| SCError::invalid_config("window title contains a NUL byte"))
+}
+
+#[cfg(feature = "preview_window")]
+fn check_window_size(width: f64, height: f64) -> Result<(), SCError> {
+    if width.is_finite() && height.is_finite() && width > 0.0 && height > 0.0 {
+        Ok(())
+    } else {
+        Err(SCError::invalid_config(format!(
+            "preview window size {width}x{height} must be positive"
+        )))
+    }
+}
+
+#[cfg(feature = "preview_window")]
+impl Drop for PreviewWindow {
+    fn drop(&mut self) {
+        unsafe { crate::ffi::sc_preview_window_release(self.ptr) }
+    }
+}
+
+#[cfg(feature = "preview_window")]
+impl fmt::Debug for PreviewWindow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PreviewWindow")
+            .field("visible", &self.is_visible())
+            .field("closed", &self.is_closed())
+            .field("layer", &self.layer)
+            .finish_non_exhaustive()
+    }
+}
+
+// SAFETY: the Swift box only touches the window on the main thread and
+// guards the state flags with a lock.
+#[cfg(feature = "preview_window")]
+unsafe impl Send for PreviewWindow {}
+#[cfg(feature = "preview_window")]
+unsafe impl Sync for PreviewWindow {}
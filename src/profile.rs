@@ -0,0 +1,577 @@
+//! Save and restore capture setups
+//!
+//! Requires the `profiles` feature.
+//!
+//! A [`ConfigProfile`] records what to capture, how to configure the stream
+//! and, optionally, where to record, in a form that survives restarts. It
+//! stores descriptions rather than live objects: a window is remembered by
+//! its application and title, because window IDs change every time the app
+//! relaunches. [`ConfigProfile::resolve`] matches the descriptions against
+//! the current shareable content to get a filter and configuration back.
+//!
+//! Profiles are saved as TOML or JSON:
+//!
+//! ```no_run
+//! use screencapturekit::prelude::*;
+//! use screencapturekit::profile::{ConfigProfile, FilterDescriptor};
+//!
+//! # fn main() -> Result<(), SCError> {
+//! let content = SCShareableContent::get()?;
+//! let profile = ConfigProfile::new(
+//!     FilterDescriptor::display(&content.displays()[0]),
+//!     &SCStreamConfiguration::new().with_width(1920).with_height(1080),
+//! )
+//! .with_name("Main display");
+//! profile.save("capture.toml")?;
+//!
+//! // Next launch
+//! let resolved = ConfigProfile::load("capture.toml")?.resolve(&SCShareableContent::get()?)?;
+//! let stream = SCStream::new(&resolved.filter, &resolved.configuration);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::SCError;
+use crate::shareable_content::{SCDisplay, SCRunningApplication, SCShareableContent, SCWindow};
+use crate::stream::configuration::{PixelFormat, SCStreamConfiguration};
+use crate::stream::content_filter::SCContentFilter;
+use crate::utils::FourCharCode;
+
+/// Format version written into every profile.
+const PROFILE_VERSION: u32 = 1;
+
+/// Which content a profile captures
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum FilterDescriptor {
+    /// A whole display, minus some applications
+    Display {
+        /// The display, or `None` for whichever display is first
+        display_id: Option<u32>,
+        /// Bundle IDs of applications left out of the capture
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        excluded_applications: Vec<String>,
+    },
+    /// A single window
+    Window {
+        /// Bundle ID of the window's application
+        bundle_id: Option<String>,
+        /// The window's title when the profile was saved
+        title: Option<String>,
+        /// The window's ID, only trusted while its application keeps running
+        window_id: Option<u32>,
+    },
+    /// Every window of one application on a display
+    Application {
+        /// The application's bundle ID
+        bundle_id: String,
+        /// The display, or `None` for whichever display is first
+        display_id: Option<u32>,
+    },
+}
+
+impl FilterDescriptor {
+    /// Describe `display`
+    pub fn display(display: &SCDisplay) -> Self {
+        Self::Display {
+            display_id: Some(display.display_id()),
+            excluded_applications: Vec::new(),
+        }
+    }
+
+    /// Describe `window` by its application, title and ID
+    pub fn window(window: &SCWindow) -> Self {
+        Self::Window {
+            bundle_id: window
+                .owning_application()
+                .map(|app| app.bundle_identifier()),
+            title: window.title(),
+            window_id: Some(window.window_id()),
+        }
+    }
+
+    /// Describe the application with `bundle_id` on `display`
+    pub fn application(display: &SCDisplay, bundle_id: impl Into<String>) -> Self {
+        Self::Application {
+            bundle_id: bundle_id.into(),
+            display_id: Some(display.display_id()),
+        }
+    }
+
+    /// Leave the application with `bundle_id` out of a display capture
+    ///
+    /// Has no effect on window and application descriptors.
+    #[must_use]
+    pub fn with_excluded_application(mut self, bundle_id: impl Into<String>) -> Self {
+        if let Self::Display {
+            excluded_applications,
+            ..
+        } = &mut self
+        {
+            excluded_applications.push(bundle_id.into());
+        }
+        self
+    }
+
+    /// Build a filter for the content this descriptor matches in `content`
+    ///
+    /// A window matches by ID when that window still belongs to the same
+    /// application, and otherwise by application and title. Excluded
+    /// applications that aren't running are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::DisplayNotFound`], [`SCError::WindowNotFound`] or
+    /// [`SCError::ApplicationNotFound`] if nothing matches.
+    pub fn resolve(&self, content: &SCShareableContent) -> Result<SCContentFilter, SCError> {
+        match self {
+            Self::Display {
+                display_id,
+                excluded_applications,
+            } => {
+                let display = find_display(content, *display_id)?;
+                let applications: Vec<SCRunningApplication> = content
+                    .applications()
+                    .into_iter()
+                    .filter(|app| excluded_applications.contains(&app.bundle_identifier()))
+                    .collect();
+                if applications.is_empty() {
+                    return Ok(SCContentFilter::for_display(&display).build());
+                }
+                let applications: Vec<&SCRunningApplication> = applications.iter().collect();
+                Ok(SCContentFilter::for_display(&display)
+                    .with_excluding_applications(&applications, &[])
+                    .build())
+            }
+            Self::Window {
+                bundle_id,
+                title,
+                window_id,
+            } => {
+                let window =
+                    find_window(content, bundle_id.as_deref(), title.as_deref(), *window_id)
+                        .ok_or_else(|| {
+                            SCError::WindowNotFound(format!(
+                                "{} \"{}\"",
+                                bundle_id.as_deref().unwrap_or("unknown application"),
+                                title.as_deref().unwrap_or_default()
+                            ))
+                        })?;
+                Ok(SCContentFilter::for_window(&window).build())
+            }
+            Self::Application {
+                bundle_id,
+                display_id,
+            } => {
+                let display = find_display(content, *display_id)?;
+                SCContentFilter::for_application_in(content, &display, bundle_id)
+            }
+        }
+    }
+}
+
+fn find_display(
+    content: &SCShareableContent,
+    display_id: Option<u32>,
+) -> Result<SCDisplay, SCError> {
+    let displays = content.displays();
+    match display_id {
+        Some(id) => displays.into_iter().find(|d| d.display_id() == id),
+        None => displays.into_iter().next(),
+    }
+    .ok_or_else(|| {
+        SCError::DisplayNotFound(display_id.map_or_else(
+            || "no displays available".to_string(),
+            |id| format!("display {id}"),
+        ))
+    })
+}
+
+fn find_window(
+    content: &SCShareableContent,
+    bundle_id: Option<&str>,
+    title: Option<&str>,
+    window_id: Option<u32>,
+) -> Option<SCWindow> {
+    let windows = content.windows();
+    let bundle_of = |window: &SCWindow| {
+        window
+            .owning_application()
+            .map(|app| app.bundle_identifier())
+    };
+    let same_app =
+        |window: &SCWindow| bundle_id.is_none() || bundle_of(window).as_deref() == bundle_id;
+
+    if let Some(id) = window_id {
+        if let Some(window) = windows.iter().find(|w| w.window_id() == id && same_app(w)) {
+            return Some(window.clone());
+        }
+    }
+    bundle_id?;
+    let mut candidates = windows.into_iter().filter(|w| same_app(w));
+    match title {
+        Some(title) => candidates.find(|w| w.title().as_deref() == Some(title)),
+        None => candidates.next(),
+    }
+}
+
+/// Container format for [`RecordingSettings`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordingFileType {
+    /// MPEG-4 (`.mp4`)
+    #[default]
+    Mp4,
+    /// `QuickTime` movie (`.mov`)
+    Mov,
+}
+
+/// Video codec for [`RecordingSettings`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordingCodec {
+    /// H.264
+    #[default]
+    H264,
+    /// H.265 / HEVC
+    Hevc,
+}
+
+/// Where and how a profile records to disk
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordingSettings {
+    path: PathBuf,
+    #[serde(default)]
+    file_type: RecordingFileType,
+    #[serde(default)]
+    codec: RecordingCodec,
+}
+
+impl RecordingSettings {
+    /// Record to `path` as H.264 in an MPEG-4 file
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            file_type: RecordingFileType::default(),
+            codec: RecordingCodec::default(),
+        }
+    }
+
+    /// Set the container format
+    #[must_use]
+    pub fn with_file_type(mut self, file_type: RecordingFileType) -> Self {
+        self.file_type = file_type;
+        self
+    }
+
+    /// Set the video codec
+    #[must_use]
+    pub fn with_codec(mut self, codec: RecordingCodec) -> Self {
+        self.codec = codec;
+        self
+    }
+
+    /// The output file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The container format
+    pub fn file_type(&self) -> RecordingFileType {
+        self.file_type
+    }
+
+    /// The video codec
+    pub fn codec(&self) -> RecordingCodec {
+        self.codec
+    }
+
+    /// A recording output configuration for these settings
+    #[cfg(feature = "macos_15_0")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "macos_15_0")))]
+    pub fn to_configuration(&self) -> crate::recording_output::SCRecordingOutputConfiguration {
+        use crate::recording_output::{
+            SCRecordingOutputCodec, SCRecordingOutputConfiguration, SCRecordingOutputFileType,
+        };
+
+        SCRecordingOutputConfiguration::new()
+            .with_output_url(&self.path)
+            .with_output_file_type(match self.file_type {
+                RecordingFileType::Mp4 => SCRecordingOutputFileType::MP4,
+                RecordingFileType::Mov => SCRecordingOutputFileType::MOV,
+            })
+            .with_video_codec(match self.codec {
+                RecordingCodec::H264 => SCRecordingOutputCodec::H264,
+                RecordingCodec::Hevc => SCRecordingOutputCodec::HEVC,
+            })
+    }
+}
+
+/// The stream configuration properties a profile keeps.
+///
+/// The flags mirror `SCStreamConfiguration` one to one, which keeps the
+/// file format readable.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+struct StreamSettings {
+    width: u32,
+    height: u32,
+    frame_rate: f64,
+    pixel_format: String,
+    shows_cursor: bool,
+    queue_depth: u32,
+    scales_to_fit: bool,
+    preserves_aspect_ratio: bool,
+    captures_audio: bool,
+    sample_rate: i32,
+    channel_count: i32,
+    excludes_current_process_audio: bool,
+    captures_microphone: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    microphone_device_id: Option<String>,
+}
+
+impl Default for StreamSettings {
+    fn default() -> Self {
+        Self::from_configuration(&SCStreamConfiguration::new())
+    }
+}
+
+impl StreamSettings {
+    fn from_configuration(configuration: &SCStreamConfiguration) -> Self {
+        Self {
+            width: configuration.width(),
+            height: configuration.height(),
+            frame_rate: configuration.frame_rate(),
+            pixel_format: configuration.pixel_format().to_string(),
+            shows_cursor: configuration.shows_cursor(),
+            queue_depth: configuration.queue_depth(),
+            scales_to_fit: configuration.scales_to_fit(),
+            preserves_aspect_ratio: configuration.preserves_aspect_ratio(),
+            captures_audio: configuration.captures_audio(),
+            sample_rate: configuration.sample_rate(),
+            channel_count: configuration.channel_count(),
+            excludes_current_process_audio: configuration.excludes_current_process_audio(),
+            captures_microphone: configuration.captures_microphone(),
+            microphone_device_id: configuration.microphone_capture_device_id(),
+        }
+    }
+
+    fn to_configuration(&self) -> Result<SCStreamConfiguration, SCError> {
+        let pixel_format: FourCharCode = self.pixel_format.parse().map_err(|_| {
+            SCError::invalid_config(format!("invalid pixel format \"{}\"", self.pixel_format))
+        })?;
+        let mut configuration = SCStreamConfiguration::new()
+            .with_width(self.width)
+            .with_height(self.height)
+            .with_frame_rate(self.frame_rate)
+            .with_pixel_format(PixelFormat::from(pixel_format))
+            .with_shows_cursor(self.shows_cursor)
+            .with_queue_depth(self.queue_depth)
+            .with_scales_to_fit(self.scales_to_fit)
+            .with_preserves_aspect_ratio(self.preserves_aspect_ratio)
+            .with_captures_audio(self.captures_audio)
+            .with_sample_rate(self.sample_rate)
+            .with_channel_count(self.channel_count)
+            .with_excludes_current_process_audio(self.excludes_current_process_audio)
+            .with_captures_microphone(self.captures_microphone);
+        if let Some(device_id) = &self.microphone_device_id {
+            configuration.set_microphone_capture_device_id(device_id);
+        }
+        Ok(configuration)
+    }
+}
+
+/// A saved capture setup
+///
+/// See the [module docs](self) for an example.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfigProfile {
+    version: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    filter: FilterDescriptor,
+    #[serde(default)]
+    stream: StreamSettings,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    recording: Option<RecordingSettings>,
+}
+
+/// A profile matched against current content, ready to start a stream
+#[derive(Debug)]
+pub struct ResolvedProfile {
+    /// The filter for the content the profile describes
+    pub filter: SCContentFilter,
+    /// The saved stream configuration
+    pub configuration: SCStreamConfiguration,
+    /// The saved recording settings, if any
+    pub recording: Option<RecordingSettings>,
+}
+
+impl ConfigProfile {
+    /// A profile capturing `filter` with `configuration`
+    pub fn new(filter: FilterDescriptor, configuration: &SCStreamConfiguration) -> Self {
+        Self {
+            version: PROFILE_VERSION,
+            name: None,
+            filter,
+            stream: StreamSettings::from_configuration(configuration),
+            recording: None,
+        }
+    }
+
+    /// Name the profile, e.g. for a settings menu
+    #[must_use]
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Record to disk with `recording`
+    #[must_use]
+    pub fn with_recording(mut self, recording: RecordingSettings) -> Self {
+        self.recording = Some(recording);
+        self
+    }
+
+    /// The profile's name
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// What the profile captures
+    pub fn filter(&self) -> &FilterDescriptor {
+        &self.filter
+    }
+
+    /// The saved recording settings
+    pub fn recording(&self) -> Option<&RecordingSettings> {
+        self.recording.as_ref()
+    }
+
+    /// The saved stream configuration
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidConfiguration`] if the saved pixel format
+    /// isn't a four-character code.
+    pub fn configuration(&self) -> Result<SCStreamConfiguration, SCError> {
+        self.stream.to_configuration()
+    }
+
+    /// Match the profile against `content`
+    ///
+    /// # Errors
+    ///
+    /// See [`FilterDescriptor::resolve`] and [`configuration`](Self::configuration).
+    pub fn resolve(&self, content: &SCShareableContent) -> Result<ResolvedProfile, SCError> {
+        Ok(ResolvedProfile {
+            filter: self.filter.resolve(content)?,
+            configuration: self.configuration()?,
+            recording: self.recording.clone(),
+        })
+    }
+
+    /// Serialize as pretty-printed JSON
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidConfiguration`] if serialization fails.
+    pub fn to_json(&self) -> Result<String, SCError> {
+        serde_json::to_string_pretty(self).map_err(profile_error)
+    }
+
+    /// Parse a profile saved with [`to_json`](Self::to_json)
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidConfiguration`] if the text isn't a profile
+    /// or was written by a newer version of this crate.
+    pub fn from_json(json: &str) -> Result<Self, SCError> {
+        serde_json::from_str::<Self>(json)
+            .map_err(profile_error)?
+            .checked()
+    }
+
+    /// Serialize as TOML
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidConfiguration`] if serialization fails.
+    pub fn to_toml(&self) -> Result<String, SCError> {
+        toml::to_string_pretty(self).map_err(profile_error)
+    }
+
+    /// Parse a profile saved with [`to_toml`](Self::to_toml)
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidConfiguration`] if the text isn't a profile
+    /// or was written by a newer version of this crate.
+    pub fn from_toml(toml: &str) -> Result<Self, SCError> {
+        toml::from_str::<Self>(toml)
+            .map_err(profile_error)?
+            .checked()
+    }
+
+    /// Write the profile to `path`, as JSON if it ends in `.json` and as
+    /// TOML otherwise
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InternalError`] if the file can't be written.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), SCError> {
+        let path = path.as_ref();
+        let text = if is_json(path) {
+            self.to_json()?
+        } else {
+            self.to_toml()?
+        };
+        std::fs::write(path, text).map_err(|e| {
+            SCError::internal_error(format!("failed to write {}: {e}", path.display()))
+        })
+    }
+
+    /// Read a profile written by [`save`](Self::save)
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InternalError`] if the file can't be read, or
+    /// [`SCError::InvalidConfiguration`] if it isn't a profile.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, SCError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(|e| {
+            SCError::internal_error(format!("failed to read {}: {e}", path.display()))
+        })?;
+        if is_json(path) {
+            Self::from_json(&text)
+        } else {
+            Self::from_toml(&text)
+        }
+    }
+
+    fn checked(self) -> Result<Self, SCError> {
+        if self.version > PROFILE_VERSION {
+            return Err(SCError::invalid_config(format!(
+                "profile version {} is newer than the supported version {PROFILE_VERSION}",
+                self.version
+            )));
+        }
+        Ok(self)
+    }
+}
+
+fn is_json(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+}
+
+fn profile_error(error: impl fmt::Display) -> SCError {
+    SCError::invalid_config(format!("invalid capture profile: {error}"))
+}
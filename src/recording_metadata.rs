@@ -0,0 +1,585 @@
+//! JSON sidecar files describing a recording
+//!
+//! A movie file says little about how it was made. [`RecordingSidecar`]
+//! collects what post-processing tools usually have to guess — the stream
+//! configuration, the displays and windows captured, OS and application
+//! versions, frame statistics, chapters and user event markers — and
+//! writes it next to the recording as `<recording>.json`.
+//!
+//! Chapters divide the recording into named sections; markers flag single
+//! moments worth jumping back to in a long capture ("bug reproduced",
+//! "meeting started"). Either can also be written into the movie as
+//! `QuickTime` chapters with `Recording::write_chapters` (`macos_15_0`).
+//!
+//! The sidecar doesn't depend on how the file is written, so it works with
+//! [`muxer`](crate::muxer), `recording_output` or your own encoder.
+//!
+//! ```no_run
+//! use screencapturekit::prelude::*;
+//! use screencapturekit::recording_metadata::RecordingSidecar;
+//!
+//! # fn example() -> Result<(), SCError> {
+//! let content = SCShareableContent::get()?;
+//! let display = &content.displays()[0];
+//! let config = SCStreamConfiguration::new().with_width(1920).with_height(1080);
+//!
+//! let sidecar = RecordingSidecar::new("/tmp/capture.mp4")
+//!     .with_configuration(&config)
+//!     .with_display(display);
+//!
+//! let filter = SCContentFilter::for_display(display).with_excluding_windows(&[]).build();
+//! let mut stream = SCStream::new(&filter, &config);
+//! stream.add_output_handler(sidecar.frame_counter(), SCStreamOutputType::Screen);
+//! stream.start_capture()?;
+//!
+//! sidecar.add_chapter("Intro");
+//! // ... record ...
+//! sidecar.add_chapter("Demo");
+//! sidecar.add_marker("Crash reproduced");
+//!
+//! stream.stop_capture()?;
+//! let path = sidecar.write()?; // /tmp/capture.json
+//! # let _ = path;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! ## Format
+//!
+//! A single JSON object with `format_version` (currently 1), `recording`,
+//! `started_at` (seconds since the Unix epoch), `versions`,
+//! `configuration`, `sources`, `frames`, `chapters` and `markers` keys.
+//! Chapters are `{"title", "offset_seconds"}` and markers
+//! `{"label", "offset_seconds"}` objects, each sorted by offset from the
+//! start of the recording. Keys may be added in later versions without bumping
+//! `format_version`.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use serde::{Serialize, Serializer};
+
+use crate::cg::CGRect;
+use crate::cm::{CMSampleBuffer, CMSampleBufferSCExt, SCFrameStatus};
+use crate::error::SCError;
+use crate::shareable_content::{SCDisplay, SCWindow};
+use crate::stream::configuration::SCStreamConfiguration;
+use crate::stream::output_trait::{SCStreamOutputTrait, SampleDelivery};
+use crate::stream::output_type::SCStreamOutputType;
+use crate::utils::ffi_string::ffi_string_owned;
+
+/// Version of the sidecar layout, written as `format_version`.
+pub const FORMAT_VERSION: u32 = 1;
+
+/// A named section of a recording, starting at `offset`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chapter {
+    /// Label shown for the chapter
+    pub title: String,
+    /// Time from the start of the recording
+    pub offset: Duration,
+}
+
+/// A user-flagged moment in a recording
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Marker {
+    /// What happened
+    pub label: String,
+    /// Time from the start of the recording
+    pub offset: Duration,
+}
+
+impl From<Marker> for Chapter {
+    fn from(marker: Marker) -> Self {
+        Self {
+            title: marker.label,
+            offset: marker.offset,
+        }
+    }
+}
+
+/// Screen frame counts for the sidecar's `frames` section
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct FrameStats {
+    /// Frames with new content
+    pub complete: u64,
+    /// Frames repeating the previous content
+    pub idle: u64,
+    /// Blank frames, e.g. while the display sleeps
+    pub blank: u64,
+    /// Frames delivered while capture was suspended
+    pub suspended: u64,
+    /// Frames lost before reaching the file, as reported through
+    /// [`RecordingSidecar::add_dropped_frames`]
+    pub dropped: u64,
+}
+
+#[derive(Default)]
+struct FrameCounters {
+    complete: AtomicU64,
+    idle: AtomicU64,
+    blank: AtomicU64,
+    suspended: AtomicU64,
+    dropped: AtomicU64,
+}
+
+impl FrameCounters {
+    fn snapshot(&self) -> FrameStats {
+        FrameStats {
+            complete: self.complete.load(Ordering::Relaxed),
+            idle: self.idle.load(Ordering::Relaxed),
+            blank: self.blank.load(Ordering::Relaxed),
+            suspended: self.suspended.load(Ordering::Relaxed),
+            dropped: self.dropped.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Metadata for one recording, written as a JSON sidecar
+///
+/// Build it when the recording starts; the chapter and marker clock starts
+/// at [`new`](Self::new). Chapters, markers and frame counts can be added
+/// from any thread while recording.
+pub struct RecordingSidecar {
+    recording: PathBuf,
+    sidecar: PathBuf,
+    started_at: SystemTime,
+    started: Instant,
+    configuration: Option<ConfigurationJson>,
+    sources: Vec<SourceJson>,
+    applications: Vec<(String, String)>,
+    frames: Arc<FrameCounters>,
+    chapters: Mutex<Vec<Chapter>>,
+    markers: Mutex<Vec<Marker>>,
+}
+
+impl RecordingSidecar {
+    /// Start describing the recording at `recording`
+    ///
+    /// The sidecar goes next to it with a `.json` extension; see
+    /// [`with_sidecar_path`](Self::with_sidecar_path) to change that.
+    #[must_use]
+    pub fn new(recording: impl Into<PathBuf>) -> Self {
+        let recording = recording.into();
+        Self {
+            sidecar: recording.with_extension("json"),
+            recording,
+            started_at: SystemTime::now(),
+            started: Instant::now(),
+            configuration: None,
+            sources: Vec::new(),
+            applications: Vec::new(),
+            frames: Arc::default(),
+            chapters: Mutex::new(Vec::new()),
+            markers: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Write the sidecar to `path` instead
+    #[must_use]
+    pub fn with_sidecar_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.sidecar = path.into();
+        self
+    }
+
+    /// Record the stream configuration
+    #[must_use]
+    pub fn with_configuration(mut self, config: &SCStreamConfiguration) -> Self {
+        let interval = config.minimum_frame_interval();
+        self.configuration = Some(ConfigurationJson {
+            width: config.width(),
+            height: config.height(),
+            pixel_format: config.pixel_format().to_string(),
+            minimum_frame_interval: FrameIntervalJson {
+                value: interval.value,
+                timescale: interval.timescale,
+            },
+            queue_depth: config.queue_depth(),
+            scales_to_fit: config.scales_to_fit(),
+            shows_cursor: config.shows_cursor(),
+            captures_audio: config.captures_audio(),
+            sample_rate: config.sample_rate(),
+            channel_count: config.channel_count(),
+        });
+        self
+    }
+
+    /// Record a captured display
+    #[must_use]
+    pub fn with_display(mut self, display: &SCDisplay) -> Self {
+        self.sources.push(SourceJson::Display {
+            display_id: display.display_id(),
+            frame: display.frame().into(),
+            width: display.width(),
+            height: display.height(),
+        });
+        self
+    }
+
+    /// Record a captured window, with its application's name, bundle
+    /// identifier and version
+    #[must_use]
+    pub fn with_window(mut self, window: &SCWindow) -> Self {
+        let application = window.owning_application().map(|app| ApplicationJson {
+            name: app.application_name(),
+            bundle_identifier: app.bundle_identifier(),
+            version: unsafe {
+                ffi_string_owned(|| crate::ffi::sc_application_version(app.process_id()))
+            },
+        });
+        self.sources.push(SourceJson::Window {
+            window_id: window.window_id(),
+            title: window.title(),
+            frame: window.frame().into(),
+            application,
+        });
+        self
+    }
+
+    /// Record the version of an application involved in the recording,
+    /// such as your own or a plugin
+    ///
+    /// The OS version, this crate's version and the main bundle's version
+    /// are always included.
+    #[must_use]
+    pub fn with_application_version(
+        mut self,
+        name: impl Into<String>,
+        version: impl Into<String>,
+    ) -> Self {
+        self.applications.push((name.into(), version.into()));
+        self
+    }
+
+    /// The recording this sidecar describes
+    #[must_use]
+    pub fn recording_path(&self) -> &Path {
+        &self.recording
+    }
+
+    /// Where [`write`](Self::write) puts the sidecar
+    #[must_use]
+    pub fn sidecar_path(&self) -> &Path {
+        &self.sidecar
+    }
+
+    /// Mark a chapter now, returning its offset into the recording
+    pub fn add_chapter(&self, title: impl Into<String>) -> Duration {
+        let offset = self.started.elapsed();
+        self.add_chapter_at(title, offset);
+        offset
+    }
+
+    /// Mark a chapter at `offset` into the recording
+    pub fn add_chapter_at(&self, title: impl Into<String>, offset: Duration) {
+        let mut chapters = self.chapters.lock().unwrap_or_else(PoisonError::into_inner);
+        let index = chapters.partition_point(|chapter| chapter.offset <= offset);
+        chapters.insert(
+            index,
+            Chapter {
+                title: title.into(),
+                offset,
+            },
+        );
+    }
+
+    /// Chapters so far, sorted by offset
+    #[must_use]
+    pub fn chapters(&self) -> Vec<Chapter> {
+        self.chapters
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Mark a moment now, returning its offset into the recording
+    ///
+    /// Cheap enough to call from a hotkey handler or a log hook.
+    pub fn add_marker(&self, label: impl Into<String>) -> Duration {
+        let offset = self.started.elapsed();
+        self.add_marker_at(label, offset);
+        offset
+    }
+
+    /// Mark a moment at `offset` into the recording
+    pub fn add_marker_at(&self, label: impl Into<String>, offset: Duration) {
+        let mut markers = self.markers.lock().unwrap_or_else(PoisonError::into_inner);
+        let index = markers.partition_point(|marker| marker.offset <= offset);
+        markers.insert(
+            index,
+            Marker {
+                label: label.into(),
+                offset,
+            },
+        );
+    }
+
+    /// Markers so far, sorted by offset
+    #[must_use]
+    pub fn markers(&self) -> Vec<Marker> {
+        self.markers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Count `count` frames lost before reaching the file, e.g. from a
+    /// pooled handler's
+    /// [`dropped_samples`](crate::stream::pooled_output::PooledOutputHandler::dropped_samples)
+    pub fn add_dropped_frames(&self, count: u64) {
+        self.frames.dropped.fetch_add(count, Ordering::Relaxed);
+    }
+
+    /// Frame counts so far
+    #[must_use]
+    pub fn frame_stats(&self) -> FrameStats {
+        self.frames.snapshot()
+    }
+
+    /// An output handler that counts screen frames by status into this
+    /// sidecar
+    ///
+    /// Add it to the stream next to the handler that writes the file. It
+    /// borrows each sample, so it costs no extra retain.
+    #[must_use]
+    pub fn frame_counter(&self) -> SidecarFrameCounter {
+        SidecarFrameCounter {
+            frames: Arc::clone(&self.frames),
+        }
+    }
+
+    /// The sidecar as pretty-printed JSON
+    ///
+    /// # Panics
+    ///
+    /// Never in practice: every field has a plain JSON representation.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let started_at = self
+            .started_at
+            .duration_since(UNIX_EPOCH)
+            .map_or(0.0, |since| since.as_secs_f64());
+        let chapters = self.chapters();
+        let markers = self.markers();
+        let sidecar = SidecarJson {
+            format_version: FORMAT_VERSION,
+            recording: self.recording.to_string_lossy(),
+            started_at,
+            versions: VersionsJson {
+                os: unsafe { ffi_string_owned(|| crate::ffi::sc_system_os_version()) },
+                screencapturekit: env!("CARGO_PKG_VERSION"),
+                main_bundle: unsafe { ffi_string_owned(|| crate::ffi::sc_main_bundle_version()) },
+                applications: ApplicationVersions(&self.applications),
+            },
+            configuration: self.configuration.as_ref(),
+            sources: &self.sources,
+            frames: self.frame_stats(),
+            chapters: chapters
+                .iter()
+                .map(|chapter| ChapterJson {
+                    title: &chapter.title,
+                    offset_seconds: chapter.offset.as_secs_f64(),
+                })
+                .collect(),
+            markers: markers
+                .iter()
+                .map(|marker| MarkerJson {
+                    label: &marker.label,
+                    offset_seconds: marker.offset.as_secs_f64(),
+                })
+                .collect(),
+        };
+        let mut out =
+            serde_json::to_string_pretty(&sidecar).expect("sidecar fields serialize to JSON");
+        out.push('\n');
+        out
+    }
+
+    /// Write the sidecar to [`sidecar_path`](Self::sidecar_path)
+    ///
+    /// Writes a temporary file and renames it over the target, so readers
+    /// never see a half-written sidecar. Can be called repeatedly, e.g. to
+    /// checkpoint a long recording.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InternalError`] if the file can't be written.
+    pub fn write(&self) -> Result<PathBuf, SCError> {
+        let mut partial = self.sidecar.clone().into_os_string();
+        partial.push(".partial");
+        let partial = PathBuf::from(partial);
+        std::fs::write(&partial, self.to_json())
+            .and_then(|()| std::fs::rename(&partial, &self.sidecar))
+            .map_err(|e| {
+                SCError::internal_error(format!("failed to write {}: {e}", self.sidecar.display()))
+            })?;
+        Ok(self.sidecar.clone())
+    }
+}
+
+impl std::fmt::Debug for RecordingSidecar {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RecordingSidecar")
+            .field("recording", &self.recording)
+            .field("sidecar", &self.sidecar)
+            .field("sources", &self.sources.len())
+            .field("frames", &self.frame_stats())
+            .field("chapters", &self.chapters().len())
+            .field("markers", &self.markers().len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Output handler counting screen frames into a [`RecordingSidecar`]
+///
+/// Created by [`RecordingSidecar::frame_counter`].
+#[derive(Clone)]
+pub struct SidecarFrameCounter {
+    frames: Arc<FrameCounters>,
+}
+
+impl SCStreamOutputTrait for SidecarFrameCounter {
+    fn did_output_sample_buffer(&self, sample_buffer: CMSampleBuffer, of_type: SCStreamOutputType) {
+        self.did_output_sample_buffer_borrowed(&sample_buffer, of_type);
+    }
+
+    fn delivery(&self) -> SampleDelivery {
+        SampleDelivery::Borrowed
+    }
+
+    fn did_output_sample_buffer_borrowed(
+        &self,
+        sample_buffer: &CMSampleBuffer,
+        of_type: SCStreamOutputType,
+    ) {
+        if of_type != SCStreamOutputType::Screen {
+            return;
+        }
+        let counter = match sample_buffer.frame_status() {
+            Some(SCFrameStatus::Complete) => &self.frames.complete,
+            Some(SCFrameStatus::Idle) => &self.frames.idle,
+            Some(SCFrameStatus::Blank) => &self.frames.blank,
+            Some(SCFrameStatus::Suspended) => &self.frames.suspended,
+            _ => return,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+impl std::fmt::Debug for SidecarFrameCounter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SidecarFrameCounter")
+            .field("frames", &self.frames.snapshot())
+            .finish()
+    }
+}
+
+#[derive(Serialize)]
+struct SidecarJson<'a> {
+    format_version: u32,
+    recording: std::borrow::Cow<'a, str>,
+    started_at: f64,
+    versions: VersionsJson<'a>,
+    configuration: Option<&'a ConfigurationJson>,
+    sources: &'a [SourceJson],
+    frames: FrameStats,
+    chapters: Vec<ChapterJson<'a>>,
+    markers: Vec<MarkerJson<'a>>,
+}
+
+#[derive(Serialize)]
+struct VersionsJson<'a> {
+    os: Option<String>,
+    screencapturekit: &'static str,
+    main_bundle: Option<String>,
+    applications: ApplicationVersions<'a>,
+}
+
+/// Application versions as a `{"name": "version"}` object, in the order
+/// they were added.
+struct ApplicationVersions<'a>(&'a [(String, String)]);
+
+impl Serialize for ApplicationVersions<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.0.iter().map(|(name, version)| (name, version)))
+    }
+}
+
+#[derive(Serialize)]
+struct ConfigurationJson {
+    width: u32,
+    height: u32,
+    pixel_format: String,
+    minimum_frame_interval: FrameIntervalJson,
+    queue_depth: u32,
+    scales_to_fit: bool,
+    shows_cursor: bool,
+    captures_audio: bool,
+    sample_rate: i32,
+    channel_count: i32,
+}
+
+#[derive(Serialize)]
+struct FrameIntervalJson {
+    value: i64,
+    timescale: i32,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum SourceJson {
+    Display {
+        display_id: u32,
+        frame: RectJson,
+        width: u32,
+        height: u32,
+    },
+    Window {
+        window_id: u32,
+        title: Option<String>,
+        frame: RectJson,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        application: Option<ApplicationJson>,
+    },
+}
+
+#[derive(Serialize)]
+struct ApplicationJson {
+    name: String,
+    bundle_identifier: String,
+    version: Option<String>,
+}
+
+/// A rectangle; `serde_json` writes non-finite coordinates as `null`.
+#[derive(Serialize)]
+struct RectJson {
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+}
+
+impl From<CGRect> for RectJson {
+    fn from(rect: CGRect) -> Self {
+        Self {
+            x: rect.origin.x,
+            y: rect.origin.y,
+            width: rect.size.width,
+            height: rect.size.height,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ChapterJson<'a> {
+    title: &'a str,
+    offset_seconds: f64,
+}
+
+#[derive(Serialize)]
+struct MarkerJson<'a> {
+    label: &'a str,
+    offset_seconds: f64,
+}
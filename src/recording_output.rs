@@ -27,7 +27,7 @@
 //! # fn example() -> Result<(), Box<dyn std::error::Error>> {
 //! let content = SCShareableContent::get()?;
 //! let display = &content.displays()[0];
-//! let filter = SCContentFilter::create().with_display(display).with_excluding_windows(&[]).build();
+//! let filter = SCContentFilter::for_display(display).with_excluding_windows(&[]).build();
 //! let config = SCStreamConfiguration::new()
 //!     .with_width(1920)
 //!     .with_height(1080);
@@ -51,15 +51,61 @@
 //! # Ok(())
 //! # }
 //! ```
+//!
+//! ## Segmented recordings
+//!
+//! For long monitoring or compliance recordings, [`RotatingRecording`] splits
+//! the output into segments, starting a new file once the current one
+//! reaches a [`RotationPolicy`] limit:
+//!
+//! ```no_run
+//! use std::time::Duration;
+//! use screencapturekit::prelude::*;
+//! use screencapturekit::recording_output::{
+//!     RotatingRecording, RotationPolicy, SCRecordingOutputConfiguration,
+//! };
+//!
+//! # fn example(stream: &SCStream) -> Result<(), SCError> {
+//! let policy = RotationPolicy::new()
+//!     .with_max_duration(Duration::from_secs(15 * 60))
+//!     .with_max_file_size(2 << 30);
+//! let recording = RotatingRecording::start(
+//!     stream,
+//!     &SCRecordingOutputConfiguration::new(),
+//!     policy,
+//!     |index| format!("/tmp/capture-{index:04}.mp4").into(),
+//!     |segment| println!("finished {}", segment.path.display()),
+//! )?;
+//! // ... capture for hours ...
+//! recording.stop();
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! ## Trimming
+//!
+//! Once a file is finished, [`Recording::trim`] cuts it down to a time
+//! range, for example to drop the seconds spent reaching for the stop button.
+//! `Recording::write_chapters` copies it with `QuickTime` chapters, such
+//! as the markers collected in a `RecordingSidecar` while recording
+//! (`recording_metadata` feature).
 
 use std::collections::HashMap;
 use std::ffi::c_void;
+use std::fmt;
 use std::path::{Path, PathBuf};
-use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex, PoisonError};
+use std::time::Duration;
 
 use crate::cm::CMTime;
+use crate::error::SCError;
+#[cfg(feature = "recording_metadata")]
+use crate::recording_metadata::Chapter;
+use crate::stream::sc_stream::SCStream;
+use crate::utils::completion::SyncCompletion;
 use crate::utils::ffi_string::{ffi_string_from_buffer, SMALL_BUFFER_SIZE};
+use crate::utils::poller::{Poller, PollerContext};
 
 /// Global registry for recording delegates - maps unique ID to delegate entry
 static RECORDING_DELEGATE_REGISTRY: Mutex<Option<HashMap<usize, RecordingDelegateEntry>>> =
@@ -326,8 +372,26 @@ pub trait SCRecordingOutputDelegate: Send + 'static {
     fn recording_did_start(&self) {}
     /// Called when recording fails with an error
     fn recording_did_fail(&self, _error: String) {}
+    /// Called when recording fails, with the framework error's domain, code
+    /// and `userInfo` intact
+    ///
+    /// The default forwards the error's message to
+    /// [`recording_did_fail`](Self::recording_did_fail).
+    fn recording_did_fail_with_error(&self, error: crate::error::SCError) {
+        self.recording_did_fail(error.to_string());
+    }
     /// Called when recording finishes successfully
     fn recording_did_finish(&self) {}
+    /// Called by a [`DiskSpaceMonitor`] when free space on the recording's
+    /// volume drops below its warning threshold
+    fn recording_disk_space_low(&self, _available_bytes: u64) {}
+    /// Called by a [`DiskSpaceMonitor`] after it removed the output from the
+    /// stream because free space fell below its stop threshold
+    ///
+    /// The file is finalized normally, so everything recorded so far stays
+    /// playable; [`recording_did_finish`](Self::recording_did_finish)
+    /// follows.
+    fn recording_stopped_for_disk_space(&self, _available_bytes: u64) {}
 }
 
 /// Builder for closure-based recording delegate
@@ -363,6 +427,8 @@ pub struct RecordingCallbacks {
     on_start: Option<Box<dyn Fn() + Send + 'static>>,
     on_fail: Option<Box<dyn Fn(String) + Send + 'static>>,
     on_finish: Option<Box<dyn Fn() + Send + 'static>>,
+    on_disk_space_low: Option<Box<dyn Fn(u64) + Send + 'static>>,
+    on_stopped_for_disk_space: Option<Box<dyn Fn(u64) + Send + 'static>>,
 }
 
 impl RecordingCallbacks {
@@ -373,6 +439,8 @@ impl RecordingCallbacks {
             on_start: None,
             on_fail: None,
             on_finish: None,
+            on_disk_space_low: None,
+            on_stopped_for_disk_space: None,
         }
     }
 
@@ -405,6 +473,29 @@ impl RecordingCallbacks {
         self.on_finish = Some(Box::new(f));
         self
     }
+
+    /// Set the callback for when free disk space runs low
+    ///
+    /// Receives the available bytes. Only fires while a
+    /// [`DiskSpaceMonitor`] watches the recording.
+    #[must_use]
+    pub fn on_disk_space_low<F>(mut self, f: F) -> Self
+    where
+        F: Fn(u64) + Send + 'static,
+    {
+        self.on_disk_space_low = Some(Box::new(f));
+        self
+    }
+
+    /// Set the callback for when a [`DiskSpaceMonitor`] stops the recording
+    #[must_use]
+    pub fn on_stopped_for_disk_space<F>(mut self, f: F) -> Self
+    where
+        F: Fn(u64) + Send + 'static,
+    {
+        self.on_stopped_for_disk_space = Some(Box::new(f));
+        self
+    }
 }
 
 impl Default for RecordingCallbacks {
@@ -419,6 +510,11 @@ impl std::fmt::Debug for RecordingCallbacks {
             .field("on_start", &self.on_start.is_some())
             .field("on_fail", &self.on_fail.is_some())
             .field("on_finish", &self.on_finish.is_some())
+            .field("on_disk_space_low", &self.on_disk_space_low.is_some())
+            .field(
+                "on_stopped_for_disk_space",
+                &self.on_stopped_for_disk_space.is_some(),
+            )
             .finish()
     }
 }
@@ -441,6 +537,18 @@ impl SCRecordingOutputDelegate for RecordingCallbacks {
             f();
         }
     }
+
+    fn recording_disk_space_low(&self, available_bytes: u64) {
+        if let Some(ref f) = self.on_disk_space_low {
+            f(available_bytes);
+        }
+    }
+
+    fn recording_stopped_for_disk_space(&self, available_bytes: u64) {
+        if let Some(ref f) = self.on_stopped_for_disk_space {
+            f(available_bytes);
+        }
+    }
 }
 
 /// Recording output for direct video file encoding
@@ -467,32 +575,23 @@ extern "C" fn recording_started_callback(ctx: *mut c_void) {
     }
 }
 
-extern "C" fn recording_failed_callback(ctx: *mut c_void, error_code: i32, error: *const i8) {
+extern "C" fn recording_failed_callback(ctx: *mut c_void, ns_error: *const c_void) {
     let key = ctx as usize;
-    let error_str = if error.is_null() {
-        String::from("Unknown error")
-    } else {
-        unsafe { std::ffi::CStr::from_ptr(error) }
-            .to_string_lossy()
-            .into_owned()
-    };
-
-    // Include error code in the message if it's a known SCStreamError
-    let full_error = if error_code != 0 {
-        crate::error::SCStreamErrorCode::from_raw(error_code).map_or_else(
-            || format!("{error_str} (code: {error_code})"),
-            |code| format!("{error_str} ({code})"),
-        )
+    let error = if ns_error.is_null() {
+        crate::error::SCError::StreamError("Unknown error".to_string())
     } else {
-        error_str
+        // SAFETY: Swift lends a live NSError for the duration of the callback.
+        crate::error::SCError::from_ns_error(unsafe {
+            crate::error::NSErrorInfo::from_borrowed(ns_error)
+        })
     };
 
     if let Ok(registry) = RECORDING_DELEGATE_REGISTRY.lock() {
         if let Some(ref delegates) = *registry {
             if let Some(entry) = delegates.get(&key) {
                 crate::utils::panic_safe::catch_user_panic(
-                    "SCRecordingOutputDelegate::recording_did_fail",
-                    || entry.delegate.recording_did_fail(full_error),
+                    "SCRecordingOutputDelegate::recording_did_fail_with_error",
+                    || entry.delegate.recording_did_fail_with_error(error),
                 );
             }
         }
@@ -620,6 +719,18 @@ impl SCRecordingOutput {
     pub fn as_ptr(&self) -> *const c_void {
         self.ptr
     }
+
+    /// Run `f` against this output's delegate, if it has one.
+    fn notify_delegate(&self, label: &'static str, f: impl FnOnce(&dyn SCRecordingOutputDelegate)) {
+        let Some(delegate_id) = self.delegate_id else {
+            return;
+        };
+        if let Ok(registry) = RECORDING_DELEGATE_REGISTRY.lock() {
+            if let Some(entry) = registry.as_ref().and_then(|d| d.get(&delegate_id)) {
+                crate::utils::panic_safe::catch_user_panic(label, || f(entry.delegate.as_ref()));
+            }
+        }
+    }
 }
 
 impl Clone for SCRecordingOutput {
@@ -689,3 +800,925 @@ unsafe impl Sync for SCRecordingOutput {}
 // Safety: SCRecordingOutputConfiguration wraps an Objective-C object that is thread-safe
 unsafe impl Send for SCRecordingOutputConfiguration {}
 unsafe impl Sync for SCRecordingOutputConfiguration {}
+
+// MARK: - Disk space
+
+/// Free bytes on the volume that holds `path`
+///
+/// Counts space macOS can reclaim from purgeable files for an important
+/// write, the same figure Finder shows. `path` does not have to exist yet,
+/// so an output file path can be passed before recording starts.
+///
+/// # Errors
+///
+/// Returns [`SCError::InvalidConfiguration`] if the path contains a NUL byte,
+/// or [`SCError::InternalError`] if the volume's capacity can't be read.
+pub fn available_disk_space(path: &Path) -> Result<u64, SCError> {
+    let c_path = path_to_cstring(path)?;
+    let available = unsafe { crate::ffi::sc_volume_available_capacity(c_path.as_ptr()) };
+    u64::try_from(available).map_err(|_| {
+        SCError::internal_error(format!("failed to read free space for {}", path.display()))
+    })
+}
+
+/// Free-space thresholds for a [`DiskSpaceMonitor`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiskSpacePolicy {
+    warn_below: u64,
+    stop_below: u64,
+    poll_interval: Duration,
+}
+
+impl DiskSpacePolicy {
+    /// Warn below 2 GiB, stop below 512 MiB, check every 5 seconds
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            warn_below: 2 << 30,
+            stop_below: 512 << 20,
+            poll_interval: Duration::from_secs(5),
+        }
+    }
+
+    /// Report [`recording_disk_space_low`](SCRecordingOutputDelegate::recording_disk_space_low)
+    /// once free space drops below `bytes`
+    #[must_use]
+    pub const fn with_warn_below(mut self, bytes: u64) -> Self {
+        self.warn_below = bytes;
+        self
+    }
+
+    /// Stop recording once free space drops below `bytes`
+    ///
+    /// Leave enough headroom for the encoder to write the file's trailer.
+    #[must_use]
+    pub const fn with_stop_below(mut self, bytes: u64) -> Self {
+        self.stop_below = bytes;
+        self
+    }
+
+    /// How often free space is checked
+    #[must_use]
+    pub const fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// The warning threshold in bytes
+    pub const fn warn_below(&self) -> u64 {
+        self.warn_below
+    }
+
+    /// The stop threshold in bytes
+    pub const fn stop_below(&self) -> u64 {
+        self.stop_below
+    }
+
+    /// How often free space is checked
+    pub const fn poll_interval(&self) -> Duration {
+        self.poll_interval
+    }
+
+    /// Check that recording to `path` can start
+    ///
+    /// Returns the free bytes on success.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InsufficientDiskSpace`] if the volume is already
+    /// below the stop threshold, or the errors of [`available_disk_space`].
+    pub fn preflight(&self, path: &Path) -> Result<u64, SCError> {
+        let available = available_disk_space(path)?;
+        if available < self.stop_below {
+            return Err(SCError::InsufficientDiskSpace {
+                available,
+                required: self.stop_below,
+            });
+        }
+        Ok(available)
+    }
+
+    fn validate(&self) -> Result<(), SCError> {
+        if self.stop_below > self.warn_below {
+            return Err(SCError::invalid_config(
+                "disk space stop threshold must not exceed the warning threshold",
+            ));
+        }
+        if self.poll_interval.is_zero() {
+            return Err(SCError::invalid_config(
+                "disk space poll_interval must be non-zero",
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Default for DiskSpacePolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+struct DiskShared {
+    available: AtomicU64,
+    stopped_recording: AtomicBool,
+}
+
+/// Watches free space while a recording runs
+///
+/// Reports a low-space warning through the recording's delegate, and before
+/// the volume fills up removes the output from the stream so the file is
+/// closed cleanly instead of being cut off mid-write. Stops watching when
+/// dropped; the recording itself is left running.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::path::Path;
+/// use screencapturekit::prelude::*;
+/// use screencapturekit::recording_output::{
+///     DiskSpaceMonitor, DiskSpacePolicy, RecordingCallbacks, SCRecordingOutput,
+///     SCRecordingOutputConfiguration,
+/// };
+///
+/// # fn example(stream: &SCStream) -> Result<(), SCError> {
+/// let path = Path::new("/tmp/long-recording.mp4");
+/// let callbacks = RecordingCallbacks::new()
+///     .on_disk_space_low(|free| eprintln!("only {} MiB left", free >> 20))
+///     .on_stopped_for_disk_space(|_| eprintln!("disk full, recording stopped"));
+/// let config = SCRecordingOutputConfiguration::new().with_output_url(path);
+/// let recording = SCRecordingOutput::new_with_delegate(&config, callbacks)
+///     .ok_or_else(|| SCError::internal_error("recording output unavailable"))?;
+///
+/// let policy = DiskSpacePolicy::new();
+/// policy.preflight(path)?;
+/// stream.add_recording_output(&recording)?;
+/// let _monitor = DiskSpaceMonitor::start(stream, &recording, path, policy)?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct DiskSpaceMonitor {
+    shared: Arc<DiskShared>,
+    poller: Poller,
+}
+
+impl DiskSpaceMonitor {
+    /// Watch the volume holding `path` while `recording` writes to it
+    ///
+    /// `recording` should already be attached to `stream`. The first check
+    /// runs before this returns.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidConfiguration`] if the stop threshold is
+    /// above the warning threshold or the poll interval is zero, and the
+    /// errors of [`DiskSpacePolicy::preflight`]. Returns
+    /// [`SCError::InternalError`] if the operating system refuses to spawn
+    /// the monitor thread.
+    pub fn start(
+        stream: &SCStream,
+        recording: &SCRecordingOutput,
+        path: &Path,
+        policy: DiskSpacePolicy,
+    ) -> Result<Self, SCError> {
+        policy.validate()?;
+        let available = policy.preflight(path)?;
+        let shared = Arc::new(DiskShared {
+            available: AtomicU64::new(available),
+            stopped_recording: AtomicBool::new(false),
+        });
+
+        let poller = {
+            let shared = Arc::clone(&shared);
+            let stream = stream.clone();
+            let recording = recording.clone();
+            let path = path.to_path_buf();
+            Poller::spawn("disk-space-monitor", move |context| {
+                monitor_disk(context, &shared, &stream, &recording, &path, policy);
+            })?
+        };
+
+        Ok(Self { shared, poller })
+    }
+
+    /// Free bytes at the last check
+    #[must_use]
+    pub fn available(&self) -> u64 {
+        self.shared.available.load(Ordering::Relaxed)
+    }
+
+    /// Whether the monitor has stopped the recording
+    #[must_use]
+    pub fn stopped_recording(&self) -> bool {
+        self.shared.stopped_recording.load(Ordering::Acquire)
+    }
+
+    /// The last error reading free space or removing the output, if any
+    #[must_use]
+    pub fn last_error(&self) -> Option<SCError> {
+        self.poller.last_error()
+    }
+}
+
+fn monitor_disk(
+    context: &PollerContext,
+    shared: &DiskShared,
+    stream: &SCStream,
+    recording: &SCRecordingOutput,
+    path: &Path,
+    policy: DiskSpacePolicy,
+) {
+    let set_error = |error: SCError| context.set_last_error(Some(error));
+    let mut warned = false;
+    let mut available = shared.available.load(Ordering::Relaxed);
+    loop {
+        if available < policy.stop_below {
+            match stream.remove_recording_output(recording) {
+                Ok(()) => {
+                    shared.stopped_recording.store(true, Ordering::Release);
+                    recording.notify_delegate(
+                        "SCRecordingOutputDelegate::recording_stopped_for_disk_space",
+                        |delegate| delegate.recording_stopped_for_disk_space(available),
+                    );
+                    return;
+                }
+                Err(error) => set_error(error),
+            }
+        } else if available < policy.warn_below {
+            if !warned {
+                warned = true;
+                recording.notify_delegate(
+                    "SCRecordingOutputDelegate::recording_disk_space_low",
+                    |delegate| delegate.recording_disk_space_low(available),
+                );
+            }
+        } else {
+            // Re-arm once space has been freed.
+            warned = false;
+        }
+
+        if context.sleep(policy.poll_interval) {
+            return;
+        }
+
+        match available_disk_space(path) {
+            Ok(bytes) => {
+                available = bytes;
+                shared.available.store(bytes, Ordering::Relaxed);
+            }
+            Err(error) => set_error(error),
+        }
+    }
+}
+
+impl fmt::Debug for DiskSpaceMonitor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DiskSpaceMonitor")
+            .field("available", &self.available())
+            .field("stopped_recording", &self.stopped_recording())
+            .finish_non_exhaustive()
+    }
+}
+
+// MARK: - Rotation
+
+/// When a [`RotatingRecording`] starts a new file
+///
+/// A segment is closed as soon as it reaches either limit. With no limits
+/// set, everything is recorded into the first segment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RotationPolicy {
+    max_duration: Option<Duration>,
+    max_file_size: Option<u64>,
+    poll_interval: Duration,
+}
+
+impl RotationPolicy {
+    /// A policy with no limits, checked every 500 ms
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            max_duration: None,
+            max_file_size: None,
+            poll_interval: Duration::from_millis(500),
+        }
+    }
+
+    /// Start a new file after this much recorded time
+    #[must_use]
+    pub const fn with_max_duration(mut self, max_duration: Duration) -> Self {
+        self.max_duration = Some(max_duration);
+        self
+    }
+
+    /// Start a new file once the current one reaches this many bytes
+    ///
+    /// Sizes are sampled every [`poll_interval`](Self::poll_interval), so a
+    /// segment can overshoot by up to one interval's worth of data.
+    #[must_use]
+    pub const fn with_max_file_size(mut self, max_file_size: u64) -> Self {
+        self.max_file_size = Some(max_file_size);
+        self
+    }
+
+    /// How often the current segment's duration and size are checked
+    #[must_use]
+    pub const fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// The duration limit, if any
+    pub const fn max_duration(&self) -> Option<Duration> {
+        self.max_duration
+    }
+
+    /// The size limit in bytes, if any
+    pub const fn max_file_size(&self) -> Option<u64> {
+        self.max_file_size
+    }
+
+    /// How often limits are checked
+    pub const fn poll_interval(&self) -> Duration {
+        self.poll_interval
+    }
+
+    /// Whether a segment with this much recorded time and data should be
+    /// closed
+    pub fn should_rotate(&self, duration: Duration, file_size: u64) -> bool {
+        self.max_duration.is_some_and(|max| duration >= max)
+            || self.max_file_size.is_some_and(|max| file_size >= max)
+    }
+
+    fn validate(&self) -> Result<(), SCError> {
+        if self.max_duration == Some(Duration::ZERO) {
+            return Err(SCError::invalid_config(
+                "rotation max_duration must be non-zero",
+            ));
+        }
+        if self.max_file_size == Some(0) {
+            return Err(SCError::invalid_config(
+                "rotation max_file_size must be non-zero",
+            ));
+        }
+        if self.poll_interval.is_zero() {
+            return Err(SCError::invalid_config(
+                "rotation poll_interval must be non-zero",
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Default for RotationPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A finished file from a [`RotatingRecording`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RecordingSegment {
+    /// Position in the recording, starting at 0
+    pub index: u64,
+    /// Where the segment was written
+    pub path: PathBuf,
+}
+
+/// How long [`RotatingRecording::stop`] waits for the last segments to be
+/// finalized.
+const FINALIZE_TIMEOUT: Duration = Duration::from_secs(5);
+
+type SegmentCallback = Arc<dyn Fn(RecordingSegment) + Send + Sync>;
+
+struct RotationShared {
+    segment_index: AtomicU64,
+    last_error: Mutex<Option<SCError>>,
+    /// Signalled as segments finish, for [`Rotator::finish`].
+    finalizing: Mutex<()>,
+    finalized: Condvar,
+}
+
+impl RotationShared {
+    fn set_error(&self, error: SCError) {
+        *self
+            .last_error
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = Some(error);
+    }
+
+    /// Tell a waiting [`Rotator::finish`] that a segment is done, without
+    /// losing the notification.
+    fn notify(&self) {
+        let _guard = self
+            .finalizing
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        self.finalized.notify_all();
+    }
+}
+
+/// Delegate attached to each segment's recording output
+struct SegmentDelegate {
+    segment: RecordingSegment,
+    on_segment: SegmentCallback,
+    done: Arc<AtomicBool>,
+    shared: Arc<RotationShared>,
+}
+
+impl SCRecordingOutputDelegate for SegmentDelegate {
+    fn recording_did_fail_with_error(&self, error: SCError) {
+        self.shared.set_error(error);
+        self.done.store(true, Ordering::Release);
+        self.shared.notify();
+    }
+
+    fn recording_did_finish(&self) {
+        (self.on_segment)(self.segment.clone());
+        self.done.store(true, Ordering::Release);
+        self.shared.notify();
+    }
+}
+
+struct Segment {
+    output: SCRecordingOutput,
+    done: Arc<AtomicBool>,
+}
+
+impl Segment {
+    fn is_done(&self) -> bool {
+        self.done.load(Ordering::Acquire)
+    }
+}
+
+/// A recording split into consecutive files by a [`RotationPolicy`]
+///
+/// Each segment is its own [`SCRecordingOutput`] on the stream. At rollover
+/// the next segment is attached before the current one is removed, so no
+/// frames fall between files; if the system refuses a second simultaneous
+/// output, the current segment is removed first instead, leaving a gap of a
+/// few frames. `on_segment` runs once each file has been finalized and is
+/// safe to move or upload.
+///
+/// Limits are checked on a background thread. Stops, and finalizes the
+/// current segment, when dropped.
+pub struct RotatingRecording {
+    shared: Arc<RotationShared>,
+    poller: Poller,
+}
+
+impl RotatingRecording {
+    /// Start recording `stream` into segments
+    ///
+    /// `template` supplies the codec and file type; its output URL is
+    /// ignored. `segment_path` names the file for each segment index, and
+    /// `on_segment` is called from a framework queue when a segment is
+    /// complete. The first segment is attached before this returns.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidConfiguration`] for a policy with a zero
+    /// limit or poll interval, or if the first recording output can't be
+    /// created, and any error from
+    /// [`SCStream::add_recording_output`]. Returns
+    /// [`SCError::InternalError`] if the operating system refuses to spawn
+    /// the rotation thread; the first segment is detached again in that
+    /// case.
+    pub fn start<P, F>(
+        stream: &SCStream,
+        template: &SCRecordingOutputConfiguration,
+        policy: RotationPolicy,
+        segment_path: P,
+        on_segment: F,
+    ) -> Result<Self, SCError>
+    where
+        P: Fn(u64) -> PathBuf + Send + 'static,
+        F: Fn(RecordingSegment) + Send + Sync + 'static,
+    {
+        policy.validate()?;
+        let shared = Arc::new(RotationShared {
+            segment_index: AtomicU64::new(0),
+            last_error: Mutex::new(None),
+            finalizing: Mutex::new(()),
+            finalized: Condvar::new(),
+        });
+        let rotator = Rotator {
+            shared: Arc::clone(&shared),
+            stream: stream.clone(),
+            template: template.clone(),
+            policy,
+            segment_path: Box::new(segment_path),
+            on_segment: Arc::new(on_segment),
+        };
+
+        let first = rotator.open_segment(0)?;
+        stream.add_recording_output(&first.output)?;
+
+        let attached = first.output.clone();
+        let poller = Poller::spawn("recording-rotation", move |context| {
+            rotator.run(context, first);
+        })
+        .map_err(|error| {
+            let _ = stream.remove_recording_output(&attached);
+            error
+        })?;
+
+        Ok(Self { shared, poller })
+    }
+
+    /// Index of the segment currently being written
+    #[must_use]
+    pub fn segment_index(&self) -> u64 {
+        self.shared.segment_index.load(Ordering::Relaxed)
+    }
+
+    /// The last rollover or recording error, if any
+    ///
+    /// A failed rollover keeps writing to the current segment and tries
+    /// again at the next check.
+    #[must_use]
+    pub fn last_error(&self) -> Option<SCError> {
+        self.shared
+            .last_error
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Finalize the current segment and stop rotating
+    ///
+    /// Blocks until the remaining segments are written out, or for at most
+    /// five seconds.
+    pub fn stop(mut self) {
+        self.poller.stop();
+    }
+}
+
+impl fmt::Debug for RotatingRecording {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RotatingRecording")
+            .field("segment_index", &self.segment_index())
+            .field("last_error", &self.last_error())
+            .finish_non_exhaustive()
+    }
+}
+
+/// State owned by the rotation thread
+struct Rotator {
+    shared: Arc<RotationShared>,
+    stream: SCStream,
+    template: SCRecordingOutputConfiguration,
+    policy: RotationPolicy,
+    segment_path: Box<dyn Fn(u64) -> PathBuf + Send>,
+    on_segment: SegmentCallback,
+}
+
+impl Rotator {
+    fn open_segment(&self, index: u64) -> Result<Segment, SCError> {
+        let path = (self.segment_path)(index);
+        let config = SCRecordingOutputConfiguration::new()
+            .with_output_url(&path)
+            .with_video_codec(self.template.video_codec())
+            .with_output_file_type(self.template.output_file_type());
+        let done = Arc::new(AtomicBool::new(false));
+        let delegate = SegmentDelegate {
+            segment: RecordingSegment {
+                index,
+                path: path.clone(),
+            },
+            on_segment: Arc::clone(&self.on_segment),
+            done: Arc::clone(&done),
+            shared: Arc::clone(&self.shared),
+        };
+        let output = SCRecordingOutput::new_with_delegate(&config, delegate).ok_or_else(|| {
+            SCError::invalid_config(format!(
+                "failed to create recording output for {}",
+                path.display()
+            ))
+        })?;
+        Ok(Segment { output, done })
+    }
+
+    /// Replace `current` with a fresh segment, moving the old one to
+    /// `retired` to be finalized.
+    fn roll_over(
+        &self,
+        current: &mut Option<Segment>,
+        retired: &mut Vec<Segment>,
+        index: u64,
+    ) -> Result<(), SCError> {
+        let next = self.open_segment(index)?;
+        let Some(active) = current.take() else {
+            self.stream.add_recording_output(&next.output)?;
+            *current = Some(next);
+            return Ok(());
+        };
+        if self.stream.add_recording_output(&next.output).is_ok() {
+            let removed = self.stream.remove_recording_output(&active.output);
+            retired.push(active);
+            *current = Some(next);
+            return removed;
+        }
+        // Only one output at a time on this system: close, then open.
+        if let Err(error) = self.stream.remove_recording_output(&active.output) {
+            *current = Some(active);
+            return Err(error);
+        }
+        retired.push(active);
+        self.stream.add_recording_output(&next.output)?;
+        *current = Some(next);
+        Ok(())
+    }
+
+    fn run(self, context: &PollerContext, first: Segment) {
+        let mut current = Some(first);
+        let mut retired = Vec::new();
+        let mut index = 0;
+        while !context.sleep(self.policy.poll_interval) {
+            // Outputs stay alive until their delegate has reported back.
+            retired.retain(|segment: &Segment| !segment.is_done());
+
+            // `current` is only empty after a rollover failed halfway.
+            if let Some(segment) = &current {
+                let recorded = cmtime_to_duration(segment.output.recorded_duration());
+                let size = u64::try_from(segment.output.recorded_file_size()).unwrap_or(0);
+                if !self.policy.should_rotate(recorded, size) {
+                    continue;
+                }
+            }
+            match self.roll_over(&mut current, &mut retired, index + 1) {
+                Ok(()) => {
+                    index += 1;
+                    self.shared.segment_index.store(index, Ordering::Relaxed);
+                }
+                Err(error) => self.shared.set_error(error),
+            }
+        }
+        self.finish(current, retired);
+    }
+
+    fn finish(&self, current: Option<Segment>, mut retired: Vec<Segment>) {
+        if let Some(segment) = current {
+            if let Err(error) = self.stream.remove_recording_output(&segment.output) {
+                self.shared.set_error(error);
+            }
+            retired.push(segment);
+        }
+        let finalizing = self
+            .shared
+            .finalizing
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        drop(
+            self.shared
+                .finalized
+                .wait_timeout_while(finalizing, FINALIZE_TIMEOUT, |()| {
+                    retired.iter().any(|segment| !segment.is_done())
+                })
+                .unwrap_or_else(PoisonError::into_inner),
+        );
+    }
+}
+
+// MARK: - Trimming and chapters
+
+/// A finished movie file, such as the output of an [`SCRecordingOutput`]
+/// once the stream reports it finished, or a [`RecordingSegment`]
+///
+/// # Examples
+///
+/// Drop the last two seconds, where the user was reaching for the stop
+/// button:
+///
+/// ```no_run
+/// use std::path::Path;
+/// use std::time::Duration;
+/// use screencapturekit::recording_output::Recording;
+///
+/// # fn example() -> Result<(), screencapturekit::error::SCError> {
+/// let recording = Recording::new("/tmp/recording.mp4");
+/// let end = recording.duration()?.saturating_sub(Duration::from_secs(2));
+/// let trimmed = recording.trim(Duration::ZERO, end, Path::new("/tmp/trimmed.mp4"))?;
+/// println!("kept {:?}", trimmed.duration()?);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Recording {
+    path: PathBuf,
+}
+
+impl Recording {
+    /// Refer to the movie file at `path`
+    ///
+    /// The file is not opened until it is used.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Where the movie is stored
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Length of the movie
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidConfiguration`] if the path contains a NUL
+    /// byte, or [`SCError::InternalError`] if the file can't be read as a
+    /// movie.
+    pub fn duration(&self) -> Result<Duration, SCError> {
+        let c_path = path_to_cstring(&self.path)?;
+        let mut value = 0;
+        let mut timescale = 0;
+        let ok = unsafe {
+            crate::ffi::sc_recording_get_duration(c_path.as_ptr(), &mut value, &mut timescale)
+        };
+        if !ok {
+            return Err(SCError::internal_error(format!(
+                "failed to read the duration of {}",
+                self.path.display()
+            )));
+        }
+        Ok(cmtime_to_duration(CMTime::new(value, timescale)))
+    }
+
+    /// Write the part of the movie from `start` to `end` to `output`
+    ///
+    /// The range is re-encoded rather than copied, so the cut lands on the
+    /// requested frame instead of the nearest keyframe; expect it to take
+    /// a noticeable fraction of the kept duration. The container follows
+    /// `output`'s extension (`.mov`, `.m4v`, anything else is MP4), and an
+    /// existing file at `output` is replaced. Blocks until the export
+    /// finishes.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidConfiguration`] if the range is empty or
+    /// extends past the end of the movie, if `output` is the source file, or
+    /// if a path contains a NUL byte. Returns the export error if
+    /// `AVFoundation` fails to write the file.
+    pub fn trim(&self, start: Duration, end: Duration, output: &Path) -> Result<Self, SCError> {
+        if start >= end {
+            return Err(SCError::invalid_config(format!(
+                "trim range is empty: {start:?}..{end:?}"
+            )));
+        }
+        if output == self.path {
+            return Err(SCError::invalid_config(
+                "trim output must differ from the source recording",
+            ));
+        }
+        let duration = self.duration()?;
+        if end > duration {
+            return Err(SCError::invalid_config(format!(
+                "trim end {end:?} is past the end of the {duration:?} recording"
+            )));
+        }
+
+        let c_input = path_to_cstring(&self.path)?;
+        let c_output = path_to_cstring(output)?;
+        let start_nanos = i64::try_from(start.as_nanos()).unwrap_or(i64::MAX);
+        let end_nanos = i64::try_from(end.as_nanos()).unwrap_or(i64::MAX);
+
+        let (completion, context) = SyncCompletion::<Result<(), SCError>>::new();
+        unsafe {
+            crate::ffi::sc_recording_trim(
+                c_input.as_ptr(),
+                c_output.as_ptr(),
+                start_nanos,
+                end_nanos,
+                context,
+                export_callback,
+            );
+        }
+        completion
+            .wait()
+            .map_err(SCError::InternalError)
+            .and_then(|result| result)?;
+        Ok(Self::new(output))
+    }
+
+    /// Copy the movie to `output` with a `QuickTime` chapter track
+    ///
+    /// Audio and video are copied without re-encoding, so this is quick
+    /// even for long recordings. Each chapter lasts until the next one
+    /// starts; players show nothing before the first, so start one at zero
+    /// to cover the whole movie. `.mov` and `.m4v` outputs are the most
+    /// widely understood; anything other than `.mp4` and `.m4v` is written
+    /// as a `QuickTime` movie. An existing file at `output` is replaced.
+    /// Blocks until the copy finishes.
+    ///
+    /// ```no_run
+    /// use std::path::Path;
+    /// use screencapturekit::recording_metadata::{Chapter, RecordingSidecar};
+    /// use screencapturekit::recording_output::Recording;
+    ///
+    /// # fn example(sidecar: &RecordingSidecar) -> Result<(), screencapturekit::error::SCError> {
+    /// let chapters: Vec<Chapter> = sidecar.markers().into_iter().map(Chapter::from).collect();
+    /// Recording::new(sidecar.recording_path())
+    ///     .write_chapters(&chapters, Path::new("/tmp/chaptered.mov"))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidConfiguration`] if `chapters` is empty, a
+    /// chapter starts past the end of the movie, `output` is the source
+    /// file, or a path or title contains a NUL byte. Returns the
+    /// `AVFoundation` error if the copy fails.
+    #[cfg(feature = "recording_metadata")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "recording_metadata")))]
+    pub fn write_chapters(&self, chapters: &[Chapter], output: &Path) -> Result<Self, SCError> {
+        if chapters.is_empty() {
+            return Err(SCError::invalid_config("no chapters to write"));
+        }
+        if output == self.path {
+            return Err(SCError::invalid_config(
+                "chapter output must differ from the source recording",
+            ));
+        }
+        let duration = self.duration()?;
+        let mut chapters = chapters.to_vec();
+        chapters.sort_by_key(|chapter| chapter.offset);
+        if let Some(late) = chapters.iter().find(|chapter| chapter.offset >= duration) {
+            return Err(SCError::invalid_config(format!(
+                "chapter {:?} at {:?} is past the end of the {duration:?} recording",
+                late.title, late.offset
+            )));
+        }
+
+        let c_input = path_to_cstring(&self.path)?;
+        let c_output = path_to_cstring(output)?;
+        let titles = chapters
+            .iter()
+            .map(|chapter| std::ffi::CString::new(chapter.title.as_bytes()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| SCError::invalid_config("chapter title contains a NUL byte"))?;
+        let title_ptrs: Vec<*const i8> = titles.iter().map(|title| title.as_ptr()).collect();
+        let offsets: Vec<i64> = chapters
+            .iter()
+            .map(|chapter| i64::try_from(chapter.offset.as_nanos()).unwrap_or(i64::MAX))
+            .collect();
+        let count = isize::try_from(chapters.len())
+            .map_err(|_| SCError::invalid_config("too many chapters"))?;
+
+        let (completion, context) = SyncCompletion::<Result<(), SCError>>::new();
+        // Swift copies the titles and offsets before returning.
+        unsafe {
+            crate::ffi::sc_recording_write_chapters(
+                c_input.as_ptr(),
+                c_output.as_ptr(),
+                title_ptrs.as_ptr(),
+                offsets.as_ptr(),
+                count,
+                context,
+                export_callback,
+            );
+        }
+        completion
+            .wait()
+            .map_err(SCError::InternalError)
+            .and_then(|result| result)?;
+        Ok(Self::new(output))
+    }
+}
+
+impl From<RecordingSegment> for Recording {
+    fn from(segment: RecordingSegment) -> Self {
+        Self::new(segment.path)
+    }
+}
+
+fn path_to_cstring(path: &Path) -> Result<std::ffi::CString, SCError> {
+    std::ffi::CString::new(path.to_string_lossy().as_bytes())
+        .map_err(|_| SCError::invalid_config("recording path contains a NUL byte"))
+}
+
+extern "C" fn export_callback(context: *mut c_void, success: bool, error: *const c_void) {
+    crate::utils::panic_safe::catch_user_panic("export_callback", move || {
+        let result = if success || error.is_null() {
+            Ok(())
+        } else {
+            // SAFETY: Swift lends a live NSError for the duration of the callback.
+            Err(SCError::from_ns_error(unsafe {
+                crate::error::NSErrorInfo::from_borrowed(error)
+            }))
+        };
+        // SAFETY: `context` is the one-shot completion context from
+        // `SyncCompletion::new()`; Swift invokes this callback exactly once.
+        unsafe { SyncCompletion::<Result<(), SCError>>::complete_ok(context, result) };
+    });
+}
+
+fn cmtime_to_duration(time: CMTime) -> Duration {
+    if time.timescale <= 0 || time.value <= 0 {
+        return Duration::ZERO;
+    }
+    #[allow(clippy::cast_precision_loss)]
+    let seconds = time.value as f64 / f64::from(time.timescale);
+    Duration::from_secs_f64(seconds)
+}
@@ -0,0 +1,434 @@
+//! Black out parts of the screen in captured frames
+//!
+//! `SCContentFilter` can leave out windows and applications but not a
+//! region of the screen: a notification banner in the top-right corner
+//! is drawn by the system and shows up in a display capture however the
+//! filter is built. [`SCRegionMasker`] fills rectangles of each frame with
+//! opaque black before anything else sees it. Wrap an output handler in a
+//! [`MaskedOutput`] and it only ever receives masked frames, so a
+//! [`SCMuxer`](crate::muxer::SCMuxer), a replay buffer or a network
+//! encoder can't leak the region. Audio passes through untouched.
+//!
+//! Regions are given in global display points ([`MaskSpace::Screen`]),
+//! the coordinates of [`SCDisplay::frame`] and
+//! [`SCWindow::frame`](crate::shareable_content::SCWindow::frame), and
+//! follow the captured content through scaling and `source_rect` crops.
+//! [`MaskSpace::Pixels`] places them in frame pixels instead.
+//! [`NotificationWatcher::start_masking`](crate::shareable_content::notification_watcher::NotificationWatcher::start_masking)
+//! adds a mask over each notification banner only while it is on screen.
+//!
+//! Masked frames keep the capture's timing and `SCStreamFrameInfo`
+//! attachments, pixel format and size. Core Image renders them, so BGRA and
+//! the 8-bit 4:2:0 formats work; 10-bit packed formats such as `l10r` may
+//! not. When a region runs along a whole edge of the display, cropping it
+//! away with
+//! [`with_source_rect`](crate::stream::configuration::SCStreamConfiguration::with_source_rect)
+//! costs nothing per frame and is the better choice.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use screencapturekit::prelude::*;
+//! use screencapturekit::region_mask::{MaskedOutput, RegionMask, SCRegionMasker};
+//! use std::sync::Arc;
+//!
+//! # fn main() -> Result<(), SCError> {
+//! let content = SCShareableContent::get()?;
+//! let display = content.displays().into_iter().next().expect("a display");
+//! let filter = SCContentFilter::for_display(&display)
+//!     .with_excluding_windows(&[])
+//!     .build();
+//! let config = SCStreamConfiguration::new();
+//!
+//! // Never record the corner where notification banners appear.
+//! let masker = Arc::new(SCRegionMasker::new(RegionMask::excluding_notifications(&display))?);
+//!
+//! let mut stream = SCStream::new(&filter, &config);
+//! stream.add_output_handler(
+//!     MaskedOutput::new(Arc::clone(&masker), |sample: CMSampleBuffer, _| {
+//!         // The notification area is black in `sample`.
+//!     }),
+//!     SCStreamOutputType::Screen,
+//! );
+//! stream.start_capture()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::ffi::c_void;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use crate::cg::{CGRect, CGRectExt, CGSize};
+use crate::cm::{CMSampleBuffer, CMSampleBufferExt};
+use crate::error::SCError;
+use crate::shareable_content::SCDisplay;
+use crate::stream::cursor::FrameGeometry;
+use crate::stream::output_trait::SCStreamOutputTrait;
+use crate::stream::output_type::SCStreamOutputType;
+
+/// Size of [`RegionMask::notification_area`], in points
+///
+/// Wide enough for a banner and its margin, tall enough for the menu bar
+/// and two stacked banners. Grouped alerts can grow taller; add a larger
+/// region for them.
+pub const NOTIFICATION_AREA_SIZE: CGSize = CGSize {
+    width: 400.0,
+    height: 320.0,
+};
+
+/// Coordinates of a [`RegionMask`]'s regions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum MaskSpace {
+    /// Global display points with the origin at the top-left of the main
+    /// display, as in [`SCDisplay::frame`]. Regions stay on the same part
+    /// of the screen whatever the output size.
+    #[default]
+    Screen,
+    /// Pixels of the captured frame, from its top-left corner
+    Pixels,
+}
+
+/// The rectangles to black out
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RegionMask {
+    space: MaskSpace,
+    regions: Vec<CGRect>,
+}
+
+impl RegionMask {
+    /// An empty mask in `space`
+    #[must_use]
+    pub const fn new(space: MaskSpace) -> Self {
+        Self {
+            space,
+            regions: Vec::new(),
+        }
+    }
+
+    /// A mask over [`notification_area`](Self::notification_area) of
+    /// `display`
+    #[must_use]
+    pub fn excluding_notifications(display: &SCDisplay) -> Self {
+        Self::new(MaskSpace::Screen).with_region(Self::notification_area(display))
+    }
+
+    /// Where `display` shows notification banners, in global points: the
+    /// top-right [`NOTIFICATION_AREA_SIZE`] of the display, menu bar
+    /// included
+    ///
+    /// Banners appear on the display with the active menu bar, so mask
+    /// every display that may have it.
+    #[must_use]
+    pub fn notification_area(display: &SCDisplay) -> CGRect {
+        let frame = display.frame().standardized();
+        let width = NOTIFICATION_AREA_SIZE.width.min(frame.size.width);
+        let height = NOTIFICATION_AREA_SIZE.height.min(frame.size.height);
+        CGRect::new(
+            frame.origin.x + frame.size.width - width,
+            frame.origin.y,
+            width,
+            height,
+        )
+    }
+
+    /// Add a region to black out
+    #[must_use]
+    pub fn with_region(mut self, region: CGRect) -> Self {
+        self.regions.push(region);
+        self
+    }
+
+    /// Add several regions to black out
+    #[must_use]
+    pub fn with_regions(mut self, regions: impl IntoIterator<Item = CGRect>) -> Self {
+        self.regions.extend(regions);
+        self
+    }
+
+    /// Coordinates the regions are in
+    #[must_use]
+    pub const fn space(&self) -> MaskSpace {
+        self.space
+    }
+
+    /// The regions to black out
+    #[must_use]
+    pub fn regions(&self) -> &[CGRect] {
+        &self.regions
+    }
+
+    /// Check that every region is finite and covers some area.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidConfiguration`] naming the first bad
+    /// region.
+    pub fn validate(&self) -> Result<(), SCError> {
+        for (index, region) in self.regions.iter().enumerate() {
+            let finite = [
+                region.origin.x,
+                region.origin.y,
+                region.size.width,
+                region.size.height,
+            ]
+            .iter()
+            .all(|value| value.is_finite());
+            if !finite || region.has_no_area() {
+                return Err(SCError::invalid_config(format!(
+                    "mask region {index} must be finite and cover some area, got {region:?}"
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `region` in pixels of a `frame_size` frame with `geometry`, clipped to
+/// the frame and rounded out to whole pixels
+fn pixel_rect(
+    space: MaskSpace,
+    region: CGRect,
+    geometry: Option<&FrameGeometry>,
+    frame_size: CGSize,
+) -> Option<CGRect> {
+    let rect = match (space, geometry) {
+        (MaskSpace::Pixels, _) => region.standardized(),
+        (MaskSpace::Screen, Some(geometry)) => geometry.pixels_of(region),
+        (MaskSpace::Screen, None) => return None,
+    };
+    let bounds = CGRect::new(0.0, 0.0, frame_size.width, frame_size.height);
+    rect.integral().intersection(&bounds)
+}
+
+/// Blacks out the regions of a [`RegionMask`] in captured frames.
+///
+/// Thread-safe; share it behind an [`Arc`] between the stream handler and
+/// whatever changes the mask. See the [module documentation](self).
+pub struct SCRegionMasker {
+    ptr: *const c_void,
+    mask: Mutex<RegionMask>,
+    extra: Mutex<Vec<CGRect>>,
+    geometry: Mutex<Option<FrameGeometry>>,
+}
+
+// SAFETY: the Swift side guards its render state with a lock; the mask and
+// geometry are behind mutexes here.
+unsafe impl Send for SCRegionMasker {}
+unsafe impl Sync for SCRegionMasker {}
+
+impl fmt::Debug for SCRegionMasker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SCRegionMasker")
+            .field("mask", &self.mask())
+            .field("extra_regions", &self.extra_regions())
+            .finish_non_exhaustive()
+    }
+}
+
+impl SCRegionMasker {
+    /// A masker for `mask`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidConfiguration`] for an invalid mask.
+    pub fn new(mask: RegionMask) -> Result<Self, SCError> {
+        mask.validate()?;
+        let ptr = unsafe { crate::ffi::sc_region_mask_create() };
+        Ok(Self {
+            ptr,
+            mask: Mutex::new(mask),
+            extra: Mutex::new(Vec::new()),
+            geometry: Mutex::new(None),
+        })
+    }
+
+    /// The current mask.
+    pub fn mask(&self) -> RegionMask {
+        self.mask
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Change the mask. Applies from the next frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidConfiguration`] for an invalid mask.
+    pub fn set_mask(&self, mask: RegionMask) -> Result<(), SCError> {
+        mask.validate()?;
+        *self.mask.lock().unwrap_or_else(PoisonError::into_inner) = mask;
+        Ok(())
+    }
+
+    /// Screen regions blacked out on top of the mask.
+    pub fn extra_regions(&self) -> Vec<CGRect> {
+        self.extra
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Replace the regions blacked out on top of the mask, in
+    /// [`MaskSpace::Screen`] coordinates whatever the mask's space.
+    ///
+    /// Meant for content that comes and goes while the mask stays, such as
+    /// the banners a
+    /// [`NotificationWatcher`](crate::shareable_content::notification_watcher::NotificationWatcher)
+    /// finds. Applies from the next frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidConfiguration`] if a region is not finite
+    /// or covers no area.
+    pub fn set_extra_regions(&self, regions: Vec<CGRect>) -> Result<(), SCError> {
+        RegionMask::new(MaskSpace::Screen)
+            .with_regions(regions.iter().copied())
+            .validate()?;
+        *self.extra.lock().unwrap_or_else(PoisonError::into_inner) = regions;
+        Ok(())
+    }
+
+    /// Black out the mask's regions and the extra regions in `frame`.
+    ///
+    /// Returns `Ok(None)` when `frame` needs no masking: it has no image
+    /// (audio, idle frames) or no region overlaps it. Use the original
+    /// sample in that case.
+    ///
+    /// Idle frames carry no geometry, so [`MaskSpace::Screen`] regions are
+    /// placed with the most recent frame that did.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidBuffer`] if screen regions can't be placed
+    /// because no frame has said where its content is yet, or
+    /// [`SCError::InternalError`] if rendering fails. Don't pass the
+    /// original on in either case; it is unmasked.
+    pub fn apply(&self, frame: &CMSampleBuffer) -> Result<Option<CMSampleBuffer>, SCError> {
+        let Some(image) = frame.image_buffer() else {
+            return Ok(None);
+        };
+        let geometry = {
+            let mut last = self.geometry.lock().unwrap_or_else(PoisonError::into_inner);
+            if let Some(geometry) = FrameGeometry::of(frame) {
+                *last = Some(geometry);
+            }
+            *last
+        };
+        let mask = self.mask();
+        let extra = self.extra_regions();
+        if mask.regions.is_empty() && extra.is_empty() {
+            return Ok(None);
+        }
+        let needs_geometry = mask.space == MaskSpace::Screen || !extra.is_empty();
+        if needs_geometry && geometry.is_none() {
+            return Err(SCError::InvalidBuffer(
+                "no frame has a content rect to place screen regions with yet".to_string(),
+            ));
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let size = CGSize::new(image.width() as f64, image.height() as f64);
+        let rects: Vec<CGRect> = mask
+            .regions
+            .iter()
+            .map(|region| (mask.space, *region))
+            .chain(extra.iter().map(|region| (MaskSpace::Screen, *region)))
+            .filter_map(|(space, region)| pixel_rect(space, region, geometry.as_ref(), size))
+            .collect();
+        if rects.is_empty() {
+            return Ok(None);
+        }
+        let flat: Vec<f64> = rects
+            .iter()
+            .flat_map(|rect| {
+                [
+                    rect.origin.x,
+                    rect.origin.y,
+                    rect.size.width,
+                    rect.size.height,
+                ]
+            })
+            .collect();
+        let count = isize::try_from(rects.len())
+            .map_err(|_| SCError::invalid_config("too many mask regions"))?;
+        let ptr = unsafe {
+            crate::ffi::sc_region_mask_apply(self.ptr, frame.as_ptr(), flat.as_ptr(), count)
+        };
+        if ptr.is_null() {
+            return Err(SCError::internal_error("failed to render masked frame"));
+        }
+        // SAFETY: a non-NULL result is a +1 CMSampleBuffer we now own.
+        Ok(Some(unsafe { CMSampleBuffer::from_ptr(ptr.cast_mut()) }))
+    }
+}
+
+impl Drop for SCRegionMasker {
+    fn drop(&mut self) {
+        unsafe { crate::ffi::sc_region_mask_release(self.ptr) }
+    }
+}
+
+/// Output handler that blacks out regions of screen frames before passing
+/// them to `inner`.
+///
+/// Frames that can't be masked are dropped rather than passed on unmasked;
+/// [`dropped_frames`](Self::dropped_frames) counts them. Samples of other
+/// types reach `inner` unchanged.
+pub struct MaskedOutput<H> {
+    masker: Arc<SCRegionMasker>,
+    inner: H,
+    dropped: AtomicU64,
+}
+
+impl<H: SCStreamOutputTrait> MaskedOutput<H> {
+    /// Wrap `inner` so it only receives masked frames.
+    pub const fn new(masker: Arc<SCRegionMasker>, inner: H) -> Self {
+        Self {
+            masker,
+            inner,
+            dropped: AtomicU64::new(0),
+        }
+    }
+
+    /// The masker frames go through.
+    pub const fn masker(&self) -> &Arc<SCRegionMasker> {
+        &self.masker
+    }
+
+    /// The wrapped handler.
+    pub const fn inner(&self) -> &H {
+        &self.inner
+    }
+
+    /// Frames withheld from `inner` because they couldn't be masked.
+    pub fn dropped_frames(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+impl<H> fmt::Debug for MaskedOutput<H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MaskedOutput")
+            .field("masker", &self.masker)
+            .field("dropped", &self.dropped.load(Ordering::Relaxed))
+            .finish_non_exhaustive()
+    }
+}
+
+impl<H: SCStreamOutputTrait> SCStreamOutputTrait for MaskedOutput<H> {
+    fn did_output_sample_buffer(&self, sample_buffer: CMSampleBuffer, of_type: SCStreamOutputType) {
+        let sample_buffer = if of_type == SCStreamOutputType::Screen {
+            let Ok(masked) = self.masker.apply(&sample_buffer) else {
+                self.dropped.fetch_add(1, Ordering::Relaxed);
+                return;
+            };
+            masked.unwrap_or(sample_buffer)
+        } else {
+            sample_buffer
+        };
+        self.inner.did_output_sample_buffer(sample_buffer, of_type);
+    }
+}
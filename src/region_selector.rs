@@ -0,0 +1,176 @@
+//! Interactive region selection
+//!
+//! Available on macOS 14.0+.
+//!
+//! Presents a `screencapture -i`-style overlay on every display: the screen
+//! dims, the cursor becomes a crosshair, and the user drags out a rectangle.
+//! Pressing Escape (or clicking without dragging) cancels.
+//!
+//! The selection is reported in global display coordinates — points with the
+//! origin at the top-left of the main display, the same space as
+//! [`SCDisplay::frame`](crate::shareable_content::SCDisplay::frame) — and can
+//! be captured directly with
+//! [`SCScreenshotManager::capture_region`](crate::screenshot_manager::SCScreenshotManager::capture_region).
+//!
+//! Like the content sharing picker, the overlay is driven by the main run
+//! loop, so selection is callback-based and never blocks the calling thread.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use screencapturekit::region_selector::{SCRegionSelectionOutcome, SCRegionSelector};
+//! use screencapturekit::screenshot_manager::{CGImageExt, ImageFormat, SCScreenshotManager};
+//!
+//! SCRegionSelector::show(|outcome| {
+//!     if let SCRegionSelectionOutcome::Selected(selection) = outcome {
+//!         let image = SCScreenshotManager::capture_region(&selection).unwrap();
+//!         image.save("selection.png", ImageFormat::Png).unwrap();
+//!     }
+//! });
+//! ```
+
+use std::ffi::c_void;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::cg::CGRect;
+
+/// A rectangle picked with [`SCRegionSelector`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SCRegionSelection {
+    rect: CGRect,
+    display_id: u32,
+    scale: f64,
+}
+
+impl SCRegionSelection {
+    /// The selected rectangle in global display coordinates (points, top-left origin).
+    #[must_use]
+    pub const fn rect(&self) -> CGRect {
+        self.rect
+    }
+
+    /// The `CGDirectDisplayID` of the display the selection was drawn on.
+    #[must_use]
+    pub const fn display_id(&self) -> u32 {
+        self.display_id
+    }
+
+    /// Backing scale factor (pixels per point) of that display.
+    #[must_use]
+    pub const fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// Size of the selection in pixels at the display's backing scale.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn pixel_size(&self) -> (u32, u32) {
+        (
+            (self.rect.size.width * self.scale).round() as u32,
+            (self.rect.size.height * self.scale).round() as u32,
+        )
+    }
+}
+
+impl fmt::Display for SCRegionSelection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Region ({}, {}, {}x{}) on display {} @{}x",
+            self.rect.origin.x,
+            self.rect.origin.y,
+            self.rect.size.width,
+            self.rect.size.height,
+            self.display_id,
+            self.scale
+        )
+    }
+}
+
+/// Result of an interactive region selection.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SCRegionSelectionOutcome {
+    /// The user dragged out a rectangle.
+    Selected(SCRegionSelection),
+    /// The user pressed Escape, clicked without dragging, or the overlay was
+    /// replaced by a newer [`SCRegionSelector::show`] call.
+    Cancelled,
+}
+
+/// Interactive crosshair region selector.
+///
+/// Available on macOS 14.0+
+#[derive(Debug)]
+pub struct SCRegionSelector;
+
+impl SCRegionSelector {
+    /// Show the selection overlay and invoke `callback` once the user finishes.
+    ///
+    /// This is non-blocking. The callback runs on the main thread, so avoid
+    /// blocking work inside it; hand the selection to another thread if you
+    /// need to capture synchronously.
+    ///
+    /// Calling `show` while an overlay is already visible cancels the
+    /// previous one.
+    pub fn show<F>(callback: F)
+    where
+        F: FnOnce(SCRegionSelectionOutcome) + Send + 'static,
+    {
+        let context = Box::new(SelectorCallbackContext {
+            consumed: AtomicBool::new(false),
+            closure: Box::new(callback),
+        });
+        unsafe {
+            crate::ffi::sc_region_selector_show(
+                selector_trampoline,
+                Box::into_raw(context).cast::<c_void>(),
+            );
+        }
+    }
+}
+
+/// One-shot context handed to Swift; reclaimed exactly once by
+/// [`selector_trampoline`].
+struct SelectorCallbackContext {
+    consumed: AtomicBool,
+    closure: Box<dyn FnOnce(SCRegionSelectionOutcome) + Send>,
+}
+
+#[allow(clippy::too_many_arguments)]
+extern "C" fn selector_trampoline(
+    code: i32,
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+    display_id: u32,
+    scale: f64,
+    context: *mut c_void,
+) {
+    if context.is_null() {
+        return;
+    }
+
+    // SAFETY: `context` is a live `SelectorCallbackContext` created in
+    // `SCRegionSelector::show`; only the winner of the swap takes ownership.
+    let consumed = unsafe { &(*context.cast::<SelectorCallbackContext>()).consumed };
+    if consumed.swap(true, Ordering::AcqRel) {
+        return;
+    }
+
+    // SAFETY: unique reclaim of the box leaked in `SCRegionSelector::show`.
+    let context = unsafe { Box::from_raw(context.cast::<SelectorCallbackContext>()) };
+    let outcome = if code == 1 {
+        SCRegionSelectionOutcome::Selected(SCRegionSelection {
+            rect: CGRect::new(x, y, width, height),
+            display_id,
+            scale,
+        })
+    } else {
+        SCRegionSelectionOutcome::Cancelled
+    };
+    crate::utils::panic_safe::catch_user_panic("region selector callback", move || {
+        (context.closure)(outcome);
+    });
+}
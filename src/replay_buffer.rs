@@ -0,0 +1,751 @@
+//! Retroactive recording: keep the last few seconds and save them on demand
+//!
+//! Requires the `replay_buffer` feature.
+//!
+//! [`SCReplayBuffer`] encodes captured frames to H.264 with `VideoToolbox`
+//! and captured audio to AAC with [`SCAudioEncoder`], and keeps only the most
+//! recent [`ReplayBufferOptions::with_duration`] worth of packets in memory.
+//! [`SCReplayBuffer::save`] writes what is buffered to an MP4 or MOV file
+//! without re-encoding, while capture carries on — the "clip the last 30
+//! seconds" feature of game recorders and OBS.
+//!
+//! Packets are dropped a whole GOP (keyframe to keyframe) at a time, so a
+//! saved clip always opens on a keyframe and may run up to one
+//! [keyframe interval](ReplayBufferOptions::with_keyframe_interval) longer
+//! than requested.
+//!
+//! Like the RTMP publisher, the buffer
+//! implements [`SCStreamOutputTrait`] for both [`SCStreamOutputType::Screen`]
+//! and [`SCStreamOutputType::Audio`]. Encoder failures inside the output
+//! callback are kept and exposed through [`SCReplayBuffer::last_error`].
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use screencapturekit::prelude::*;
+//! use screencapturekit::replay_buffer::{ReplayBufferOptions, SCReplayBuffer};
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let content = SCShareableContent::get()?;
+//! let display = &content.displays()[0];
+//! let filter = SCContentFilter::for_display(display)
+//!     .with_excluding_windows(&[])
+//!     .build();
+//! let config = SCStreamConfiguration::new()
+//!     .with_width(1920)
+//!     .with_height(1080)
+//!     .with_captures_audio(true)
+//!     .with_sample_rate(48_000)
+//!     .with_channel_count(2);
+//!
+//! let replay = Arc::new(SCReplayBuffer::new(
+//!     ReplayBufferOptions::default()
+//!         .with_size(1920, 1080)
+//!         .with_duration(Duration::from_secs(30)),
+//! )?);
+//!
+//! let mut stream = SCStream::new(&filter, &config);
+//! let video = Arc::clone(&replay);
+//! stream.add_output_handler(
+//!     move |sample, of_type| video.did_output_sample_buffer(sample, of_type),
+//!     SCStreamOutputType::Screen,
+//! );
+//! let audio = Arc::clone(&replay);
+//! stream.add_output_handler(
+//!     move |sample, of_type| audio.did_output_sample_buffer(sample, of_type),
+//!     SCStreamOutputType::Audio,
+//! );
+//! stream.start_capture()?;
+//!
+//! // ... when the user presses the "save replay" hotkey:
+//! let length = replay.save("/tmp/replay.mp4")?;
+//! println!("saved the last {length:?}");
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::VecDeque;
+use std::ffi::c_void;
+use std::path::Path;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+use crate::audio_encoder::{AudioCodec, EncodedAudioPacket, SCAudioEncoder};
+use crate::cm::{CMSampleBuffer, CMSampleBufferExt, CMSampleBufferSCExt, CMTime, SCFrameStatus};
+use crate::error::SCError;
+use crate::h264::{EncodedVideoFrame, H264Encoder};
+use crate::stream::output_trait::SCStreamOutputTrait;
+use crate::stream::output_type::SCStreamOutputType;
+use crate::utils::completion::SyncCompletion;
+
+const NANOS_PER_SECOND: i32 = 1_000_000_000;
+
+/// Encoder and retention settings for [`SCReplayBuffer`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplayBufferOptions {
+    duration: Duration,
+    width: u32,
+    height: u32,
+    fps: f64,
+    video_bitrate: u32,
+    keyframe_interval: u32,
+    captures_audio: bool,
+    audio_bitrate: u32,
+    sample_rate: u32,
+    channel_count: u32,
+}
+
+impl Default for ReplayBufferOptions {
+    /// The last 30 seconds of 1080p30 at 8 Mbit/s with a keyframe every
+    /// second, and 160 kbit/s stereo AAC at 48 kHz.
+    fn default() -> Self {
+        Self {
+            duration: Duration::from_secs(30),
+            width: 1920,
+            height: 1080,
+            fps: 30.0,
+            video_bitrate: 8_000_000,
+            keyframe_interval: 30,
+            captures_audio: true,
+            audio_bitrate: 160_000,
+            sample_rate: 48_000,
+            channel_count: 2,
+        }
+    }
+}
+
+impl ReplayBufferOptions {
+    /// How much recent capture to keep.
+    #[must_use]
+    pub const fn with_duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Output video size in pixels. Should match the captured frame size.
+    #[must_use]
+    pub const fn with_size(mut self, width: u32, height: u32) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    /// Expected frame rate, used for rate control.
+    #[must_use]
+    pub const fn with_fps(mut self, fps: f64) -> Self {
+        self.fps = fps;
+        self
+    }
+
+    /// Average video bitrate in bits per second. Together with the duration
+    /// this decides how much memory the buffer holds.
+    #[must_use]
+    pub const fn with_video_bitrate(mut self, bitrate: u32) -> Self {
+        self.video_bitrate = bitrate;
+        self
+    }
+
+    /// Maximum number of frames between keyframes. Shorter intervals trim
+    /// the buffer more precisely at a small cost in quality.
+    #[must_use]
+    pub const fn with_keyframe_interval(mut self, frames: u32) -> Self {
+        self.keyframe_interval = frames;
+        self
+    }
+
+    /// Whether to encode and keep audio. When off, audio pushes are ignored
+    /// and saved clips have a video track only.
+    #[must_use]
+    pub const fn with_captures_audio(mut self, captures_audio: bool) -> Self {
+        self.captures_audio = captures_audio;
+        self
+    }
+
+    /// AAC bitrate in bits per second.
+    #[must_use]
+    pub const fn with_audio_bitrate(mut self, bitrate: u32) -> Self {
+        self.audio_bitrate = bitrate;
+        self
+    }
+
+    /// Sample rate and channel count of the captured audio.
+    #[must_use]
+    pub const fn with_audio_format(mut self, sample_rate: u32, channel_count: u32) -> Self {
+        self.sample_rate = sample_rate;
+        self.channel_count = channel_count;
+        self
+    }
+
+    /// How much recent capture is kept.
+    #[must_use]
+    pub const fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Output video width.
+    #[must_use]
+    pub const fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Output video height.
+    #[must_use]
+    pub const fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Expected frame rate.
+    #[must_use]
+    pub const fn fps(&self) -> f64 {
+        self.fps
+    }
+
+    /// Average video bitrate in bits per second.
+    #[must_use]
+    pub const fn video_bitrate(&self) -> u32 {
+        self.video_bitrate
+    }
+
+    /// Maximum frames between keyframes.
+    #[must_use]
+    pub const fn keyframe_interval(&self) -> u32 {
+        self.keyframe_interval
+    }
+
+    /// Whether audio is encoded and kept.
+    #[must_use]
+    pub const fn captures_audio(&self) -> bool {
+        self.captures_audio
+    }
+
+    /// AAC bitrate in bits per second.
+    #[must_use]
+    pub const fn audio_bitrate(&self) -> u32 {
+        self.audio_bitrate
+    }
+
+    /// Audio sample rate in Hz.
+    #[must_use]
+    pub const fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Audio channel count.
+    #[must_use]
+    pub const fn channel_count(&self) -> u32 {
+        self.channel_count
+    }
+
+    fn validate(&self) -> Result<(), SCError> {
+        if self.duration.is_zero() {
+            return Err(SCError::invalid_config(
+                "replay buffer duration must be non-zero",
+            ));
+        }
+        if self.width == 0 {
+            return Err(SCError::invalid_dimension("width", 0));
+        }
+        if self.height == 0 {
+            return Err(SCError::invalid_dimension("height", 0));
+        }
+        if self.fps.is_nan() || self.fps <= 0.0 {
+            return Err(SCError::invalid_config(format!(
+                "replay buffer frame rate must be positive, got {}",
+                self.fps
+            )));
+        }
+        Ok(())
+    }
+}
+
+struct ReplayState {
+    options: ReplayBufferOptions,
+    video_encoder: H264Encoder,
+    audio_encoder: Option<SCAudioEncoder>,
+    /// Encoded frames, always starting on a keyframe.
+    video: VecDeque<Arc<EncodedVideoFrame>>,
+    audio: VecDeque<Arc<EncodedAudioPacket>>,
+    error: Option<SCError>,
+}
+
+impl ReplayState {
+    fn push_video(&mut self, sample: &CMSampleBuffer) -> Result<(), SCError> {
+        if sample.frame_status() != Some(SCFrameStatus::Complete) {
+            return Ok(());
+        }
+        let Some(buffer) = sample.image_buffer() else {
+            return Ok(());
+        };
+        self.video_encoder
+            .encode(&buffer, sample.presentation_timestamp())?;
+        self.drain_video();
+        Ok(())
+    }
+
+    fn drain_video(&mut self) {
+        while let Some(frame) = self.video_encoder.pop_frame() {
+            // A clip has to open on a keyframe.
+            if self.video.is_empty() && !frame.keyframe {
+                continue;
+            }
+            self.video.push_back(Arc::new(frame));
+        }
+        self.trim();
+    }
+
+    fn push_audio(&mut self, sample: &CMSampleBuffer) -> Result<(), SCError> {
+        let Some(encoder) = self.audio_encoder.as_mut() else {
+            return Ok(());
+        };
+        let packets = encoder.encode(sample)?;
+        self.audio.extend(packets.into_iter().map(Arc::new));
+        self.trim();
+        Ok(())
+    }
+
+    /// Drop whole GOPs from the front while the rest still covers the
+    /// requested duration, then drop audio that precedes the first frame.
+    fn trim(&mut self) {
+        let window = self.options.duration;
+        if let Some(newest) = self.video.back().map(|frame| frame.pts) {
+            while let Some(next_key) = self
+                .video
+                .iter()
+                .skip(1)
+                .position(|frame| frame.keyframe)
+                .map(|i| i + 1)
+            {
+                if elapsed(self.video[next_key].pts, newest) < window {
+                    break;
+                }
+                self.video.drain(..next_key);
+            }
+        }
+
+        let audio_start = match self.video.front() {
+            Some(frame) => nanos(frame.pts),
+            // No keyframe yet: keep at most `window` of audio.
+            None => match self.audio.back() {
+                Some(packet) => nanos(packet.pts()).saturating_sub(duration_nanos(window)),
+                None => return,
+            },
+        };
+        while self
+            .audio
+            .front()
+            .is_some_and(|packet| nanos(packet.pts()) < audio_start)
+        {
+            self.audio.pop_front();
+        }
+    }
+
+    fn buffered_duration(&self) -> Duration {
+        match (self.video.front(), self.video.back()) {
+            (Some(first), Some(last)) => elapsed(first.pts, last.pts) + self.frame_duration(),
+            _ => Duration::ZERO,
+        }
+    }
+
+    fn frame_duration(&self) -> Duration {
+        Duration::from_secs_f64(1.0 / self.options.fps)
+    }
+
+    /// Push out frames still inside the encoder and take a snapshot of the
+    /// buffer to write without holding the lock.
+    fn snapshot(&mut self) -> Result<Clip, SCError> {
+        self.video_encoder.flush()?;
+        self.drain_video();
+        if self.video.is_empty() {
+            return Err(SCError::StreamError(
+                "replay buffer has no keyframe to start a clip from".into(),
+            ));
+        }
+        let avcc = self
+            .video_encoder
+            .avcc()
+            .ok_or_else(|| SCError::internal_error("H.264 encoder has no avcC record"))?;
+        let audio = self
+            .audio_encoder
+            .as_ref()
+            .map(SCAudioEncoder::magic_cookie)
+            .filter(|cookie| !cookie.is_empty())
+            .map(|cookie| (cookie, self.audio.iter().cloned().collect()));
+        Ok(Clip {
+            avcc,
+            width: self.options.width,
+            height: self.options.height,
+            sample_rate: self.options.sample_rate,
+            channel_count: self.options.channel_count,
+            last_frame_duration: self.frame_duration(),
+            video: self.video.iter().cloned().collect(),
+            audio,
+        })
+    }
+}
+
+/// Everything needed to write one saved clip.
+struct Clip {
+    avcc: Vec<u8>,
+    width: u32,
+    height: u32,
+    sample_rate: u32,
+    channel_count: u32,
+    last_frame_duration: Duration,
+    video: Vec<Arc<EncodedVideoFrame>>,
+    /// Magic cookie and packets, if audio is being kept.
+    audio: Option<(Vec<u8>, Vec<Arc<EncodedAudioPacket>>)>,
+}
+
+impl Clip {
+    fn duration(&self) -> Duration {
+        match (self.video.first(), self.video.last()) {
+            (Some(first), Some(last)) => elapsed(first.pts, last.pts) + self.last_frame_duration,
+            _ => Duration::ZERO,
+        }
+    }
+
+    #[allow(clippy::cast_possible_wrap)]
+    fn write(&self, path: &Path) -> Result<(), SCError> {
+        let start = self.video[0].pts;
+        let (cookie, packets) = match &self.audio {
+            Some((cookie, packets)) => (Some(cookie.as_slice()), packets.as_slice()),
+            None => (None, &[][..]),
+        };
+        let writer = MovieWriter::create(
+            path,
+            &self.avcc,
+            self.width as i32,
+            self.height as i32,
+            cookie,
+            self.sample_rate,
+            self.channel_count,
+            start,
+        )?;
+
+        // AVAssetWriter interleaves tracks itself and stalls an input that
+        // runs too far ahead, so packets go in presentation order.
+        let mut audio = packets.iter().peekable();
+        for (i, frame) in self.video.iter().enumerate() {
+            while let Some(packet) = audio.next_if(|p| nanos(p.pts()) <= nanos(frame.pts)) {
+                writer.append_audio(packet, path)?;
+            }
+            let duration = self.video.get(i + 1).map_or_else(
+                || duration_nanos(self.last_frame_duration),
+                |next| nanos(next.pts).saturating_sub(nanos(frame.pts)),
+            );
+            writer.append_video(frame, duration, path)?;
+        }
+        for packet in audio {
+            writer.append_audio(packet, path)?;
+        }
+        writer.finish()
+    }
+}
+
+/// Owns a Swift `AVAssetWriter` wrapper; cancels an unfinished file on drop.
+struct MovieWriter {
+    ptr: *const c_void,
+}
+
+impl MovieWriter {
+    #[allow(clippy::too_many_arguments)]
+    fn create(
+        path: &Path,
+        avcc: &[u8],
+        width: i32,
+        height: i32,
+        cookie: Option<&[u8]>,
+        sample_rate: u32,
+        channel_count: u32,
+        start: CMTime,
+    ) -> Result<Self, SCError> {
+        let c_path = std::ffi::CString::new(path.to_string_lossy().as_bytes())
+            .map_err(|_| SCError::invalid_config("replay path contains a NUL byte"))?;
+        let is_mov = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("mov"));
+        let mut status = 0;
+        let ptr = unsafe {
+            crate::ffi::sc_movie_writer_create(
+                c_path.as_ptr(),
+                i32::from(is_mov),
+                avcc.as_ptr().cast(),
+                avcc.len(),
+                width,
+                height,
+                cookie.map_or(std::ptr::null(), |c| c.as_ptr().cast()),
+                cookie.map_or(0, <[u8]>::len),
+                f64::from(sample_rate),
+                channel_count,
+                start.value,
+                start.timescale,
+                &mut status,
+            )
+        };
+        if ptr.is_null() {
+            return Err(SCError::os_error(
+                status,
+                format!("failed to create movie writer for {}", path.display()),
+            ));
+        }
+        Ok(Self { ptr })
+    }
+
+    fn append_video(
+        &self,
+        frame: &EncodedVideoFrame,
+        duration_nanos: i64,
+        path: &Path,
+    ) -> Result<(), SCError> {
+        let duration = CMTime::new(duration_nanos, NANOS_PER_SECOND);
+        self.append(0, &frame.data, frame.pts, duration, frame.keyframe, path)
+    }
+
+    fn append_audio(&self, packet: &EncodedAudioPacket, path: &Path) -> Result<(), SCError> {
+        self.append(
+            1,
+            packet.data(),
+            packet.pts(),
+            packet.duration(),
+            true,
+            path,
+        )
+    }
+
+    fn append(
+        &self,
+        track: i32,
+        data: &[u8],
+        pts: CMTime,
+        duration: CMTime,
+        keyframe: bool,
+        path: &Path,
+    ) -> Result<(), SCError> {
+        let appended = unsafe {
+            crate::ffi::sc_movie_writer_append(
+                self.ptr,
+                track,
+                data.as_ptr().cast(),
+                data.len(),
+                pts.value,
+                pts.timescale,
+                duration.value,
+                duration.timescale,
+                keyframe,
+            )
+        };
+        if appended {
+            Ok(())
+        } else {
+            Err(SCError::internal_error(format!(
+                "failed to write {} packet to {}",
+                if track == 0 { "video" } else { "audio" },
+                path.display()
+            )))
+        }
+    }
+
+    fn finish(self) -> Result<(), SCError> {
+        let (completion, context) = SyncCompletion::<Result<(), SCError>>::new();
+        unsafe { crate::ffi::sc_movie_writer_finish(self.ptr, context, finish_callback) };
+        completion
+            .wait()
+            .map_err(SCError::InternalError)
+            .and_then(|result| result)
+    }
+}
+
+impl Drop for MovieWriter {
+    fn drop(&mut self) {
+        unsafe { crate::ffi::sc_movie_writer_release(self.ptr) }
+    }
+}
+
+extern "C" fn finish_callback(context: *mut c_void, success: bool, error: *const c_void) {
+    crate::utils::panic_safe::catch_user_panic("replay_buffer_finish_callback", move || {
+        let result = if success || error.is_null() {
+            Ok(())
+        } else {
+            // SAFETY: Swift lends a live NSError for the duration of the callback.
+            Err(SCError::from_ns_error(unsafe {
+                crate::error::NSErrorInfo::from_borrowed(error)
+            }))
+        };
+        // SAFETY: `context` is the one-shot completion context from
+        // `SyncCompletion::new()`; Swift invokes this callback exactly once.
+        unsafe { SyncCompletion::<Result<(), SCError>>::complete_ok(context, result) };
+    });
+}
+
+/// Keeps the last few seconds of encoded capture in memory.
+///
+/// Requires the `replay_buffer` feature. See the [module documentation](self).
+pub struct SCReplayBuffer {
+    state: Mutex<ReplayState>,
+}
+
+impl std::fmt::Debug for SCReplayBuffer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut s = f.debug_struct("SCReplayBuffer");
+        if let Ok(state) = self.state.lock() {
+            s.field("options", &state.options)
+                .field("buffered_duration", &state.buffered_duration())
+                .field("error", &state.error);
+        }
+        s.finish_non_exhaustive()
+    }
+}
+
+impl SCReplayBuffer {
+    /// Create the encoders and an empty buffer.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidConfiguration`] or
+    /// [`SCError::InvalidDimension`] for invalid options, or
+    /// [`SCError::OSError`] if an encoder cannot be created.
+    pub fn new(options: ReplayBufferOptions) -> Result<Self, SCError> {
+        options.validate()?;
+        let video_encoder = H264Encoder::new(
+            options.width,
+            options.height,
+            options.fps,
+            options.video_bitrate,
+            options.keyframe_interval,
+        )?;
+        let audio_encoder = if options.captures_audio {
+            Some(SCAudioEncoder::new(
+                AudioCodec::Aac,
+                options.sample_rate,
+                options.channel_count,
+                options.audio_bitrate,
+            )?)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            state: Mutex::new(ReplayState {
+                options,
+                video_encoder,
+                audio_encoder,
+                video: VecDeque::new(),
+                audio: VecDeque::new(),
+                error: None,
+            }),
+        })
+    }
+
+    /// Encode a captured video frame into the buffer. Idle and dropped
+    /// frames are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns the encoder error, or the error that stopped the buffer
+    /// earlier.
+    pub fn push_video(&self, sample: &CMSampleBuffer) -> Result<(), SCError> {
+        self.with_state(|state| state.push_video(sample))
+    }
+
+    /// Encode a captured audio buffer into the buffer.
+    ///
+    /// # Errors
+    ///
+    /// See [`push_video`](Self::push_video).
+    pub fn push_audio(&self, sample: &CMSampleBuffer) -> Result<(), SCError> {
+        self.with_state(|state| state.push_audio(sample))
+    }
+
+    /// Length of video currently held.
+    #[must_use]
+    pub fn buffered_duration(&self) -> Duration {
+        self.lock().buffered_duration()
+    }
+
+    /// Write the buffered video and audio to `path` and return the length of
+    /// the clip.
+    ///
+    /// The file is MOV if `path` ends in `.mov`, otherwise MP4, and replaces
+    /// any existing file. Capture continues while the file is written; the
+    /// buffer keeps its contents, so saving twice produces overlapping clips.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::StreamError`] if no keyframe has been buffered yet,
+    /// [`SCError::InvalidConfiguration`] if `path` contains a NUL byte, or
+    /// the encoder or `AVAssetWriter` error.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<Duration, SCError> {
+        let path = path.as_ref();
+        let clip = {
+            let mut state = self.lock();
+            if let Some(error) = &state.error {
+                return Err(error.clone());
+            }
+            state.snapshot()?
+        };
+        clip.write(path)?;
+        Ok(clip.duration())
+    }
+
+    /// Discard everything buffered so far.
+    pub fn clear(&self) {
+        let mut state = self.lock();
+        state.video.clear();
+        state.audio.clear();
+    }
+
+    /// The error that stopped buffering, if any.
+    #[must_use]
+    pub fn last_error(&self) -> Option<SCError> {
+        self.lock().error.clone()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ReplayState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn with_state(
+        &self,
+        f: impl FnOnce(&mut ReplayState) -> Result<(), SCError>,
+    ) -> Result<(), SCError> {
+        let mut state = self.lock();
+        if let Some(error) = &state.error {
+            return Err(error.clone());
+        }
+        let result = f(&mut state);
+        if let Err(error) = &result {
+            state.error = Some(error.clone());
+        }
+        result
+    }
+}
+
+impl SCStreamOutputTrait for SCReplayBuffer {
+    fn did_output_sample_buffer(&self, sample_buffer: CMSampleBuffer, of_type: SCStreamOutputType) {
+        // Errors are kept in `last_error`.
+        let _ = match of_type {
+            SCStreamOutputType::Screen => self.push_video(&sample_buffer),
+            SCStreamOutputType::Audio => self.push_audio(&sample_buffer),
+            SCStreamOutputType::Microphone => Ok(()),
+        };
+    }
+}
+
+/// `time` in nanoseconds, for comparing times with different timescales.
+fn nanos(time: CMTime) -> i64 {
+    if time.timescale <= 0 {
+        return 0;
+    }
+    let nanos = i128::from(time.value) * i128::from(NANOS_PER_SECOND) / i128::from(time.timescale);
+    i64::try_from(nanos).unwrap_or(if nanos < 0 { i64::MIN } else { i64::MAX })
+}
+
+fn duration_nanos(duration: Duration) -> i64 {
+    i64::try_from(duration.as_nanos()).unwrap_or(i64::MAX)
+}
+
+fn elapsed(from: CMTime, to: CMTime) -> Duration {
+    Duration::from_nanos(u64::try_from(nanos(to).saturating_sub(nanos(from))).unwrap_or(0))
+}
@@ -0,0 +1,187 @@
+//! Minimal AMF0 encoding/decoding for RTMP command messages.
+
+/// An AMF0 value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Amf {
+    Number(f64),
+    Boolean(bool),
+    String(String),
+    Object(Vec<(String, Self)>),
+    EcmaArray(Vec<(String, Self)>),
+    Null,
+    Undefined,
+}
+
+impl Amf {
+    pub fn str(s: &str) -> Self {
+        Self::String(s.to_string())
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub const fn as_number(&self) -> Option<f64> {
+        match self {
+            Self::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// Property `key` of an object or ECMA array.
+    pub fn get(&self, key: &str) -> Option<&Self> {
+        match self {
+            Self::Object(props) | Self::EcmaArray(props) => {
+                props.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Encode a sequence of values.
+pub fn encode(values: &[Amf]) -> Vec<u8> {
+    let mut out = Vec::new();
+    for value in values {
+        encode_value(&mut out, value);
+    }
+    out
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn encode_key(out: &mut Vec<u8>, key: &str) {
+    out.extend_from_slice(&(key.len() as u16).to_be_bytes());
+    out.extend_from_slice(key.as_bytes());
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn encode_value(out: &mut Vec<u8>, value: &Amf) {
+    match value {
+        Amf::Number(n) => {
+            out.push(0x00);
+            out.extend_from_slice(&n.to_be_bytes());
+        }
+        Amf::Boolean(b) => {
+            out.push(0x01);
+            out.push(u8::from(*b));
+        }
+        Amf::String(s) => {
+            out.push(0x02);
+            encode_key(out, s);
+        }
+        Amf::Object(props) => {
+            out.push(0x03);
+            encode_props(out, props);
+        }
+        Amf::EcmaArray(props) => {
+            out.push(0x08);
+            out.extend_from_slice(&(props.len() as u32).to_be_bytes());
+            encode_props(out, props);
+        }
+        Amf::Null => out.push(0x05),
+        Amf::Undefined => out.push(0x06),
+    }
+}
+
+fn encode_props(out: &mut Vec<u8>, props: &[(String, Amf)]) {
+    for (key, value) in props {
+        encode_key(out, key);
+        encode_value(out, value);
+    }
+    out.extend_from_slice(&[0x00, 0x00, 0x09]);
+}
+
+/// Objects and arrays nested deeper than this are treated as malformed, so
+/// a hostile server can't exhaust the stack.
+const MAX_DEPTH: usize = 32;
+
+/// Decode as many values as `data` holds. Unknown types, and nesting
+/// deeper than [`MAX_DEPTH`], stop decoding.
+pub fn decode(mut data: &[u8]) -> Vec<Amf> {
+    let mut values = Vec::new();
+    while let Some((value, rest)) = decode_value(data, 0) {
+        values.push(value);
+        data = rest;
+    }
+    values
+}
+
+fn decode_value(data: &[u8], depth: usize) -> Option<(Amf, &[u8])> {
+    let (&marker, rest) = data.split_first()?;
+    match marker {
+        0x00 => {
+            let bytes: [u8; 8] = rest.get(..8)?.try_into().ok()?;
+            Some((Amf::Number(f64::from_be_bytes(bytes)), &rest[8..]))
+        }
+        0x01 => Some((Amf::Boolean(*rest.first()? != 0), &rest[1..])),
+        0x02 => {
+            let (s, rest) = decode_key(rest)?;
+            Some((Amf::String(s), rest))
+        }
+        0x03 => {
+            let (props, rest) = decode_props(rest, depth + 1)?;
+            Some((Amf::Object(props), rest))
+        }
+        0x05 => Some((Amf::Null, rest)),
+        0x06 => Some((Amf::Undefined, rest)),
+        0x08 => {
+            let (props, rest) = decode_props(rest.get(4..)?, depth + 1)?;
+            Some((Amf::EcmaArray(props), rest))
+        }
+        _ => None,
+    }
+}
+
+/// Key-value pairs of an AMF0 object or ECMA array.
+type Properties = Vec<(String, Amf)>;
+
+fn decode_key(data: &[u8]) -> Option<(String, &[u8])> {
+    let len = usize::from(u16::from_be_bytes([*data.first()?, *data.get(1)?]));
+    let bytes = data.get(2..2 + len)?;
+    Some((
+        String::from_utf8_lossy(bytes).into_owned(),
+        &data[2 + len..],
+    ))
+}
+
+fn decode_props(mut data: &[u8], depth: usize) -> Option<(Properties, &[u8])> {
+    if depth > MAX_DEPTH {
+        return None;
+    }
+    let mut props = Vec::new();
+    loop {
+        if data.starts_with(&[0x00, 0x00, 0x09]) {
+            return Some((props, &data[3..]));
+        }
+        let (key, rest) = decode_key(data)?;
+        let (value, rest) = decode_value(rest, depth)?;
+        props.push((key, value));
+        data = rest;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nested(depth: usize) -> Amf {
+        (0..depth).fold(Amf::Null, |inner, _| {
+            Amf::Object(vec![("a".to_string(), inner)])
+        })
+    }
+
+    #[test]
+    fn test_decode_limits_nesting() {
+        let allowed = nested(MAX_DEPTH);
+        assert_eq!(decode(&encode(&[allowed.clone()])), vec![allowed]);
+        assert!(decode(&encode(&[nested(MAX_DEPTH + 1)])).is_empty());
+
+        // Deep enough to overflow the stack without the limit.
+        let mut data = vec![0x03, 0x00, 0x01, b'a'].repeat(100_000);
+        data.push(0x05);
+        assert!(decode(&data).is_empty());
+    }
+}
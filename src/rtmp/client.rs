@@ -0,0 +1,441 @@
+//! RTMP publishing client: handshake, chunk stream and the
+//! `connect` → `createStream` → `publish` command sequence.
+
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+use super::amf::{self, Amf};
+use crate::error::SCError;
+
+/// Chunk size we announce and write with.
+const OUT_CHUNK_SIZE: usize = 4096;
+
+const CSID_PROTOCOL: u8 = 2;
+const CSID_COMMAND: u8 = 3;
+const CSID_AUDIO: u8 = 4;
+const CSID_VIDEO: u8 = 6;
+
+const MSG_SET_CHUNK_SIZE: u8 = 1;
+const MSG_USER_CONTROL: u8 = 4;
+const MSG_AUDIO: u8 = 8;
+const MSG_VIDEO: u8 = 9;
+const MSG_DATA_AMF0: u8 = 18;
+const MSG_COMMAND_AMF0: u8 = 20;
+
+/// Where to publish, split out of an `rtmp://host[:port]/app` URL. IPv6
+/// hosts are bracketed, as in `rtmp://[::1]:1935/app`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RtmpUrl {
+    pub host: String,
+    pub port: u16,
+    pub app: String,
+    pub tc_url: String,
+}
+
+impl RtmpUrl {
+    pub fn parse(url: &str) -> Result<Self, SCError> {
+        if url.starts_with("rtmps://") {
+            return Err(SCError::invalid_config(
+                "rtmps:// is not supported; use an rtmp:// ingest URL",
+            ));
+        }
+        let rest = url
+            .strip_prefix("rtmp://")
+            .ok_or_else(|| SCError::invalid_config(format!("not an rtmp:// URL: {url}")))?;
+        let (authority, app) = rest.split_once('/').unwrap_or((rest, ""));
+        let app = app.trim_end_matches('/');
+        if authority.is_empty() || app.is_empty() {
+            return Err(SCError::invalid_config(format!(
+                "RTMP URL needs a host and an application path: {url}"
+            )));
+        }
+        let invalid_host = || SCError::invalid_config(format!("invalid RTMP host in {url}"));
+        // IPv6 literals are bracketed: `[::1]:1935`.
+        let (host, port) = if let Some(bracketed) = authority.strip_prefix('[') {
+            let (host, rest) = bracketed.split_once(']').ok_or_else(invalid_host)?;
+            let port = match rest {
+                "" => None,
+                rest => Some(rest.strip_prefix(':').ok_or_else(invalid_host)?),
+            };
+            (host, port)
+        } else {
+            match authority.rsplit_once(':') {
+                Some((host, port)) => (host, Some(port)),
+                None => (authority, None),
+            }
+        };
+        if host.is_empty() || (host.contains(':') && !authority.starts_with('[')) {
+            return Err(invalid_host());
+        }
+        let port = match port {
+            Some(port) => port
+                .parse()
+                .map_err(|_| SCError::invalid_config(format!("invalid RTMP port in {url}")))?,
+            None => 1935,
+        };
+        Ok(Self {
+            host: host.to_string(),
+            port,
+            app: app.to_string(),
+            tc_url: format!("rtmp://{authority}/{app}"),
+        })
+    }
+}
+
+/// Reassembly state for one incoming chunk stream.
+#[derive(Default)]
+struct InboundChunkStream {
+    length: usize,
+    message_type: u8,
+    extended: bool,
+    payload: Vec<u8>,
+}
+
+/// A connected, publishing RTMP session.
+pub struct RtmpConnection {
+    socket: TcpStream,
+    stream_id: u32,
+    in_chunk_size: usize,
+    inbound: HashMap<u32, InboundChunkStream>,
+}
+
+impl RtmpConnection {
+    /// Connect, handshake and start publishing `stream_key`.
+    pub fn publish(url: &RtmpUrl, stream_key: &str, timeout: Duration) -> Result<Self, SCError> {
+        let addr = (url.host.as_str(), url.port)
+            .to_socket_addrs()
+            .map_err(|e| io_error(&e))?
+            .next()
+            .ok_or_else(|| SCError::StreamError(format!("RTMP: cannot resolve {}", url.host)))?;
+        let socket = TcpStream::connect_timeout(&addr, timeout).map_err(|e| io_error(&e))?;
+        socket.set_nodelay(true).map_err(|e| io_error(&e))?;
+        socket
+            .set_read_timeout(Some(timeout))
+            .map_err(|e| io_error(&e))?;
+        socket
+            .set_write_timeout(Some(timeout))
+            .map_err(|e| io_error(&e))?;
+
+        let mut conn = Self {
+            socket,
+            stream_id: 0,
+            in_chunk_size: 128,
+            inbound: HashMap::new(),
+        };
+        conn.handshake()?;
+
+        #[allow(clippy::cast_possible_truncation)]
+        let chunk_size = (OUT_CHUNK_SIZE as u32).to_be_bytes();
+        conn.write_message(CSID_PROTOCOL, MSG_SET_CHUNK_SIZE, 0, 0, &chunk_size)?;
+
+        conn.command(&[
+            Amf::str("connect"),
+            Amf::Number(1.0),
+            Amf::Object(vec![
+                ("app".into(), Amf::str(&url.app)),
+                ("type".into(), Amf::str("nonprivate")),
+                (
+                    "flashVer".into(),
+                    Amf::str("FMLE/3.0 (compatible; screencapturekit)"),
+                ),
+                ("tcUrl".into(), Amf::str(&url.tc_url)),
+            ]),
+        ])?;
+        conn.wait_for_result(1.0)?;
+
+        conn.command(&[
+            Amf::str("releaseStream"),
+            Amf::Number(2.0),
+            Amf::Null,
+            Amf::str(stream_key),
+        ])?;
+        conn.command(&[
+            Amf::str("FCPublish"),
+            Amf::Number(3.0),
+            Amf::Null,
+            Amf::str(stream_key),
+        ])?;
+        conn.command(&[Amf::str("createStream"), Amf::Number(4.0), Amf::Null])?;
+        let result = conn.wait_for_result(4.0)?;
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let stream_id = result.get(3).and_then(Amf::as_number).ok_or_else(|| {
+            SCError::StreamError("RTMP: createStream returned no stream id".into())
+        })? as u32;
+        conn.stream_id = stream_id;
+
+        conn.command(&[
+            Amf::str("publish"),
+            Amf::Number(5.0),
+            Amf::Null,
+            Amf::str(stream_key),
+            Amf::str("live"),
+        ])?;
+        conn.wait_for_publish_start()?;
+        Ok(conn)
+    }
+
+    /// Send `@setDataFrame onMetaData`.
+    pub fn send_metadata(&mut self, properties: Vec<(String, Amf)>) -> Result<(), SCError> {
+        let payload = amf::encode(&[
+            Amf::str("@setDataFrame"),
+            Amf::str("onMetaData"),
+            Amf::EcmaArray(properties),
+        ]);
+        self.write_message(CSID_COMMAND, MSG_DATA_AMF0, self.stream_id, 0, &payload)
+    }
+
+    pub fn send_audio(&mut self, timestamp_ms: u32, body: &[u8]) -> Result<(), SCError> {
+        self.write_message(CSID_AUDIO, MSG_AUDIO, self.stream_id, timestamp_ms, body)
+    }
+
+    pub fn send_video(&mut self, timestamp_ms: u32, body: &[u8]) -> Result<(), SCError> {
+        self.write_message(CSID_VIDEO, MSG_VIDEO, self.stream_id, timestamp_ms, body)
+    }
+
+    /// Politely end the session. Errors are ignored; the socket closes on drop.
+    pub fn close(&mut self, stream_key: &str) {
+        let _ = self.command(&[
+            Amf::str("FCUnpublish"),
+            Amf::Number(6.0),
+            Amf::Null,
+            Amf::str(stream_key),
+        ]);
+        let _ = self.command(&[
+            Amf::str("deleteStream"),
+            Amf::Number(7.0),
+            Amf::Null,
+            Amf::Number(f64::from(self.stream_id)),
+        ]);
+        let _ = self.socket.flush();
+    }
+
+    /// Simple (unencrypted) RTMP handshake.
+    fn handshake(&mut self) -> Result<(), SCError> {
+        let mut c0c1 = vec![0u8; 1 + 1536];
+        c0c1[0] = 3;
+        // Bytes 1..9 are time + zero; the rest only needs to be unpredictable
+        // enough for the server to echo it back.
+        let mut seed = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0x2545_F491, |d| d.subsec_nanos());
+        for byte in &mut c0c1[9..] {
+            seed ^= seed << 13;
+            seed ^= seed >> 17;
+            seed ^= seed << 5;
+            *byte = seed.to_le_bytes()[0];
+        }
+        self.socket.write_all(&c0c1).map_err(|e| io_error(&e))?;
+
+        let mut s0s1 = vec![0u8; 1 + 1536];
+        self.socket
+            .read_exact(&mut s0s1)
+            .map_err(|e| io_error(&e))?;
+        if s0s1[0] != 3 {
+            return Err(SCError::StreamError(format!(
+                "RTMP: unsupported server version {}",
+                s0s1[0]
+            )));
+        }
+        self.socket
+            .write_all(&s0s1[1..])
+            .map_err(|e| io_error(&e))?;
+
+        let mut s2 = vec![0u8; 1536];
+        self.socket.read_exact(&mut s2).map_err(|e| io_error(&e))
+    }
+
+    fn command(&mut self, values: &[Amf]) -> Result<(), SCError> {
+        let payload = amf::encode(values);
+        let stream_id = if values.first().and_then(Amf::as_str) == Some("publish") {
+            self.stream_id
+        } else {
+            0
+        };
+        self.write_message(CSID_COMMAND, MSG_COMMAND_AMF0, stream_id, 0, &payload)
+    }
+
+    /// Read until `_result` / `_error` for `transaction` arrives.
+    fn wait_for_result(&mut self, transaction: f64) -> Result<Vec<Amf>, SCError> {
+        loop {
+            let values = self.read_command()?;
+            let name = values.first().and_then(Amf::as_str);
+            let id = values.get(1).and_then(Amf::as_number);
+            #[allow(clippy::float_cmp)]
+            match (name, id) {
+                (Some("_result"), Some(id)) if id == transaction => return Ok(values),
+                (Some("_error"), Some(id)) if id == transaction => {
+                    return Err(SCError::StreamError(format!(
+                        "RTMP: server rejected command: {}",
+                        status_description(&values)
+                    )));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn wait_for_publish_start(&mut self) -> Result<(), SCError> {
+        loop {
+            let values = self.read_command()?;
+            if values.first().and_then(Amf::as_str) != Some("onStatus") {
+                continue;
+            }
+            let info = values.get(3);
+            let code = info.and_then(|i| i.get("code")).and_then(Amf::as_str);
+            let level = info.and_then(|i| i.get("level")).and_then(Amf::as_str);
+            if code == Some("NetStream.Publish.Start") {
+                return Ok(());
+            }
+            if level == Some("error") {
+                return Err(SCError::StreamError(format!(
+                    "RTMP: publish failed: {}",
+                    status_description(&values)
+                )));
+            }
+        }
+    }
+
+    /// Read messages until an AMF0 command arrives, handling protocol control
+    /// messages along the way.
+    fn read_command(&mut self) -> Result<Vec<Amf>, SCError> {
+        loop {
+            let (message_type, payload) = self.read_message()?;
+            match message_type {
+                MSG_SET_CHUNK_SIZE if payload.len() >= 4 => {
+                    let size = u32::from_be_bytes([payload[0], payload[1], payload[2], payload[3]]);
+                    self.in_chunk_size = (size & 0x7FFF_FFFF).max(1) as usize;
+                }
+                // Ping request → pong, so the server doesn't drop us.
+                MSG_USER_CONTROL if payload.len() >= 6 && payload[..2] == [0, 6] => {
+                    let mut pong = vec![0, 7];
+                    pong.extend_from_slice(&payload[2..6]);
+                    self.write_message(CSID_PROTOCOL, MSG_USER_CONTROL, 0, 0, &pong)?;
+                }
+                MSG_COMMAND_AMF0 => return Ok(amf::decode(&payload)),
+                _ => {}
+            }
+        }
+    }
+
+    fn read_message(&mut self) -> Result<(u8, Vec<u8>), SCError> {
+        loop {
+            let first = self.read_u8()?;
+            let format = first >> 6;
+            let csid = match first & 0x3F {
+                0 => 64 + u32::from(self.read_u8()?),
+                1 => {
+                    let lo = u32::from(self.read_u8()?);
+                    let hi = u32::from(self.read_u8()?);
+                    64 + lo + hi * 256
+                }
+                id => u32::from(id),
+            };
+
+            let mut header = [0u8; 11];
+            let header_len = [11, 7, 3, 0][usize::from(format)];
+            self.read_exact(&mut header[..header_len])?;
+
+            let state = self.inbound.entry(csid).or_default();
+            if format <= 2 {
+                state.extended = header[..3] == [0xFF, 0xFF, 0xFF];
+            }
+            if format <= 1 {
+                state.length = u32::from_be_bytes([0, header[3], header[4], header[5]]) as usize;
+                state.message_type = header[6];
+            }
+            // Timestamps are irrelevant for the commands we read, so an
+            // extended timestamp is skipped rather than tracked.
+            let extended = state.extended;
+            let want = state
+                .length
+                .saturating_sub(state.payload.len())
+                .min(self.in_chunk_size);
+            if extended {
+                let mut ts = [0u8; 4];
+                self.read_exact(&mut ts)?;
+            }
+
+            let mut chunk = vec![0u8; want];
+            self.read_exact(&mut chunk)?;
+            let state = self.inbound.entry(csid).or_default();
+            state.payload.extend_from_slice(&chunk);
+            if state.payload.len() >= state.length {
+                return Ok((state.message_type, std::mem::take(&mut state.payload)));
+            }
+        }
+    }
+
+    fn read_u8(&mut self) -> Result<u8, SCError> {
+        let mut byte = [0u8; 1];
+        self.read_exact(&mut byte)?;
+        Ok(byte[0])
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), SCError> {
+        self.socket.read_exact(buf).map_err(|e| io_error(&e))
+    }
+
+    /// Write one message, split into chunks.
+    fn write_message(
+        &mut self,
+        csid: u8,
+        message_type: u8,
+        stream_id: u32,
+        timestamp: u32,
+        payload: &[u8],
+    ) -> Result<(), SCError> {
+        let extended = timestamp >= 0x00FF_FFFF;
+        let ts_field = if extended { 0x00FF_FFFF } else { timestamp };
+        #[allow(clippy::cast_possible_truncation)]
+        let length = payload.len() as u32;
+
+        let mut out = Vec::with_capacity(payload.len() + 16 + payload.len() / OUT_CHUNK_SIZE * 5);
+        out.push(csid);
+        out.extend_from_slice(&ts_field.to_be_bytes()[1..]);
+        out.extend_from_slice(&length.to_be_bytes()[1..]);
+        out.push(message_type);
+        out.extend_from_slice(&stream_id.to_le_bytes());
+        if extended {
+            out.extend_from_slice(&timestamp.to_be_bytes());
+        }
+
+        for (i, chunk) in payload.chunks(OUT_CHUNK_SIZE).enumerate() {
+            if i > 0 {
+                out.push(0xC0 | csid);
+                if extended {
+                    out.extend_from_slice(&timestamp.to_be_bytes());
+                }
+            }
+            out.extend_from_slice(chunk);
+        }
+        self.socket.write_all(&out).map_err(|e| io_error(&e))
+    }
+}
+
+impl std::fmt::Debug for RtmpConnection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RtmpConnection")
+            .field("peer", &self.socket.peer_addr().ok())
+            .field("stream_id", &self.stream_id)
+            .finish_non_exhaustive()
+    }
+}
+
+fn status_description(values: &[Amf]) -> String {
+    values
+        .iter()
+        .skip(2)
+        .find_map(|v| {
+            v.get("description")
+                .or_else(|| v.get("code"))
+                .and_then(Amf::as_str)
+        })
+        .unwrap_or("no description")
+        .to_string()
+}
+
+fn io_error(error: &std::io::Error) -> SCError {
+    SCError::StreamError(format!("RTMP: {error}"))
+}
@@ -0,0 +1,64 @@
+//! FLV tag bodies for RTMP audio (type 8) and video (type 9) messages.
+//!
+//! RTMP carries the FLV tag *body* only; the 11-byte FLV tag header is
+//! replaced by the RTMP message header.
+
+/// `AudioTagHeader` for AAC: sound format 10, 44 kHz, 16-bit, stereo. The
+/// rate/size/type bits are fixed for AAC; the real values come from the
+/// `AudioSpecificConfig`.
+const AAC_TAG_HEADER: u8 = 0xAF;
+
+/// Video tag for an H.264 keyframe / inter frame (codec id 7).
+const AVC_KEYFRAME: u8 = 0x17;
+const AVC_INTER_FRAME: u8 = 0x27;
+
+/// AAC sequence header carrying a two-byte AAC-LC `AudioSpecificConfig`.
+pub fn aac_sequence_header(sample_rate: u32, channels: u32) -> Vec<u8> {
+    let config = audio_specific_config(sample_rate, channels);
+    vec![AAC_TAG_HEADER, 0x00, config[0], config[1]]
+}
+
+/// One raw AAC frame.
+pub fn aac_frame(data: &[u8]) -> Vec<u8> {
+    let mut body = Vec::with_capacity(data.len() + 2);
+    body.extend_from_slice(&[AAC_TAG_HEADER, 0x01]);
+    body.extend_from_slice(data);
+    body
+}
+
+/// AVC sequence header wrapping an `avcC` decoder configuration record.
+pub fn avc_sequence_header(avcc: &[u8]) -> Vec<u8> {
+    let mut body = Vec::with_capacity(avcc.len() + 5);
+    body.extend_from_slice(&[AVC_KEYFRAME, 0x00, 0x00, 0x00, 0x00]);
+    body.extend_from_slice(avcc);
+    body
+}
+
+/// One AVCC (length-prefixed) access unit. Composition time is always zero
+/// because the encoder runs without frame reordering.
+pub fn avc_frame(data: &[u8], keyframe: bool) -> Vec<u8> {
+    let mut body = Vec::with_capacity(data.len() + 5);
+    let frame_type = if keyframe {
+        AVC_KEYFRAME
+    } else {
+        AVC_INTER_FRAME
+    };
+    body.extend_from_slice(&[frame_type, 0x01, 0x00, 0x00, 0x00]);
+    body.extend_from_slice(data);
+    body
+}
+
+/// MPEG-4 `AudioSpecificConfig` for AAC-LC (object type 2).
+#[allow(clippy::cast_possible_truncation)]
+fn audio_specific_config(sample_rate: u32, channels: u32) -> [u8; 2] {
+    const RATES: [u32; 13] = [
+        96_000, 88_200, 64_000, 48_000, 44_100, 32_000, 24_000, 22_050, 16_000, 12_000, 11_025,
+        8_000, 7_350,
+    ];
+    let index = RATES.iter().position(|&r| r == sample_rate).unwrap_or(3) as u8;
+    let channels = channels.min(7) as u8;
+    [
+        (2 << 3) | (index >> 1),
+        ((index & 1) << 7) | (channels << 3),
+    ]
+}
@@ -0,0 +1,668 @@
+//! Live streaming to RTMP ingest servers
+//!
+//! Requires the `rtmp` feature.
+//!
+//! [`SCRtmpPublisher`] encodes captured frames to H.264 with `VideoToolbox`
+//! and captured audio to AAC with [`SCAudioEncoder`](crate::audio_encoder::SCAudioEncoder),
+//! muxes both into FLV tags and publishes them over a plain RTMP connection
+//! (Twitch, `YouTube Live`, `nginx-rtmp`, OBS-compatible servers, …). No
+//! `ffmpeg` or other native libraries are involved.
+//!
+//! The publisher implements [`SCStreamOutputTrait`], so it can be added
+//! directly as a stream output for both [`SCStreamOutputType::Screen`] and
+//! [`SCStreamOutputType::Audio`]. Network and encoder failures inside the
+//! output callback cannot be returned, so they are kept and exposed through
+//! [`SCRtmpPublisher::last_error`]; after an error the publisher stops
+//! sending.
+//!
+//! Encoding happens on the capture queue, but the network writes run on a
+//! writer thread behind a bounded queue, so a slow connection never holds
+//! up capture. When the queue is full, packets are dropped and counted in
+//! [`SCRtmpPublisher::dropped_packets`]; video then resumes at the next
+//! keyframe.
+//!
+//! ## Limitations
+//!
+//! - Only `rtmp://` is supported; `rtmps://` (TLS) endpoints are rejected.
+//! - Frames are encoded at the size they are captured at, so
+//!   [`RtmpOptions::with_size`] should match the stream configuration.
+//! - Audio must be captured at [`RtmpOptions::with_audio_format`]'s sample
+//!   rate and channel count.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use screencapturekit::prelude::*;
+//! use screencapturekit::rtmp::{RtmpOptions, SCRtmpPublisher};
+//! use std::sync::Arc;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let content = SCShareableContent::get()?;
+//! let display = &content.displays()[0];
+//! let filter = SCContentFilter::for_display(display)
+//!     .with_excluding_windows(&[])
+//!     .build();
+//! let config = SCStreamConfiguration::new()
+//!     .with_width(1920)
+//!     .with_height(1080)
+//!     .with_captures_audio(true)
+//!     .with_sample_rate(48_000)
+//!     .with_channel_count(2);
+//!
+//! let publisher = Arc::new(SCRtmpPublisher::connect(
+//!     "rtmp://live.twitch.tv/app",
+//!     "stream-key",
+//!     RtmpOptions::default().with_size(1920, 1080),
+//! )?);
+//!
+//! let mut stream = SCStream::new(&filter, &config);
+//! let video = Arc::clone(&publisher);
+//! stream.add_output_handler(
+//!     move |sample, of_type| video.did_output_sample_buffer(sample, of_type),
+//!     SCStreamOutputType::Screen,
+//! );
+//! let audio = Arc::clone(&publisher);
+//! stream.add_output_handler(
+//!     move |sample, of_type| audio.did_output_sample_buffer(sample, of_type),
+//!     SCStreamOutputType::Audio,
+//! );
+//! stream.start_capture()?;
+//!
+//! // ... later
+//! stream.stop_capture()?;
+//! publisher.close()?;
+//! # Ok(())
+//! # }
+//! ```
+
+mod amf;
+mod client;
+mod flv;
+
+use std::sync::mpsc::{self, Receiver, SyncSender, TrySendError};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use self::amf::Amf;
+use self::client::{RtmpConnection, RtmpUrl};
+use crate::audio_encoder::{AudioCodec, SCAudioEncoder};
+use crate::cm::{CMSampleBuffer, CMSampleBufferExt, CMSampleBufferSCExt, CMTime, SCFrameStatus};
+use crate::error::SCError;
+use crate::h264::H264Encoder;
+use crate::stream::output_trait::SCStreamOutputTrait;
+use crate::stream::output_type::SCStreamOutputType;
+
+/// Encoder and connection settings for [`SCRtmpPublisher`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct RtmpOptions {
+    width: u32,
+    height: u32,
+    fps: f64,
+    video_bitrate: u32,
+    keyframe_interval: u32,
+    audio_bitrate: u32,
+    sample_rate: u32,
+    channel_count: u32,
+    timeout: Duration,
+    send_queue: usize,
+}
+
+impl Default for RtmpOptions {
+    /// 1080p30 at 6 Mbit/s with a keyframe every two seconds, and 160 kbit/s
+    /// stereo AAC at 48 kHz.
+    fn default() -> Self {
+        Self {
+            width: 1920,
+            height: 1080,
+            fps: 30.0,
+            video_bitrate: 6_000_000,
+            keyframe_interval: 60,
+            audio_bitrate: 160_000,
+            sample_rate: 48_000,
+            channel_count: 2,
+            timeout: Duration::from_secs(10),
+            send_queue: 256,
+        }
+    }
+}
+
+impl RtmpOptions {
+    /// Output video size in pixels. Should match the captured frame size.
+    #[must_use]
+    pub const fn with_size(mut self, width: u32, height: u32) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    /// Expected frame rate, used for rate control and stream metadata.
+    #[must_use]
+    pub const fn with_fps(mut self, fps: f64) -> Self {
+        self.fps = fps;
+        self
+    }
+
+    /// Average video bitrate in bits per second.
+    #[must_use]
+    pub const fn with_video_bitrate(mut self, bitrate: u32) -> Self {
+        self.video_bitrate = bitrate;
+        self
+    }
+
+    /// Maximum number of frames between keyframes. Most ingest services
+    /// require a keyframe at least every 2–4 seconds.
+    #[must_use]
+    pub const fn with_keyframe_interval(mut self, frames: u32) -> Self {
+        self.keyframe_interval = frames;
+        self
+    }
+
+    /// AAC bitrate in bits per second.
+    #[must_use]
+    pub const fn with_audio_bitrate(mut self, bitrate: u32) -> Self {
+        self.audio_bitrate = bitrate;
+        self
+    }
+
+    /// Sample rate and channel count of the captured audio.
+    #[must_use]
+    pub const fn with_audio_format(mut self, sample_rate: u32, channel_count: u32) -> Self {
+        self.sample_rate = sample_rate;
+        self.channel_count = channel_count;
+        self
+    }
+
+    /// Network timeout for connecting and for each read or write.
+    #[must_use]
+    pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Number of encoded packets that may wait for the network before new
+    /// ones are dropped. The default of 256 covers a few seconds at 30 fps.
+    #[must_use]
+    pub const fn with_send_queue(mut self, packets: usize) -> Self {
+        self.send_queue = packets;
+        self
+    }
+
+    /// Output video width.
+    #[must_use]
+    pub const fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Output video height.
+    #[must_use]
+    pub const fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Expected frame rate.
+    #[must_use]
+    pub const fn fps(&self) -> f64 {
+        self.fps
+    }
+
+    /// Average video bitrate in bits per second.
+    #[must_use]
+    pub const fn video_bitrate(&self) -> u32 {
+        self.video_bitrate
+    }
+
+    /// Maximum frames between keyframes.
+    #[must_use]
+    pub const fn keyframe_interval(&self) -> u32 {
+        self.keyframe_interval
+    }
+
+    /// AAC bitrate in bits per second.
+    #[must_use]
+    pub const fn audio_bitrate(&self) -> u32 {
+        self.audio_bitrate
+    }
+
+    /// Audio sample rate in Hz.
+    #[must_use]
+    pub const fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Audio channel count.
+    #[must_use]
+    pub const fn channel_count(&self) -> u32 {
+        self.channel_count
+    }
+
+    /// Network timeout.
+    #[must_use]
+    pub const fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Packets that may wait for the network.
+    #[must_use]
+    pub const fn send_queue(&self) -> usize {
+        self.send_queue
+    }
+
+    fn validate(&self) -> Result<(), SCError> {
+        if self.width == 0 {
+            return Err(SCError::invalid_dimension("width", 0));
+        }
+        if self.height == 0 {
+            return Err(SCError::invalid_dimension("height", 0));
+        }
+        if self.fps.is_nan() || self.fps <= 0.0 {
+            return Err(SCError::invalid_config(format!(
+                "RTMP frame rate must be positive, got {}",
+                self.fps
+            )));
+        }
+        if self.send_queue == 0 {
+            return Err(SCError::invalid_config(
+                "RTMP send queue must hold at least one packet",
+            ));
+        }
+        Ok(())
+    }
+
+    fn metadata(&self) -> Vec<(String, Amf)> {
+        vec![
+            ("width".into(), Amf::Number(f64::from(self.width))),
+            ("height".into(), Amf::Number(f64::from(self.height))),
+            ("framerate".into(), Amf::Number(self.fps)),
+            ("videocodecid".into(), Amf::Number(7.0)),
+            (
+                "videodatarate".into(),
+                Amf::Number(f64::from(self.video_bitrate) / 1000.0),
+            ),
+            ("audiocodecid".into(), Amf::Number(10.0)),
+            (
+                "audiodatarate".into(),
+                Amf::Number(f64::from(self.audio_bitrate) / 1000.0),
+            ),
+            (
+                "audiosamplerate".into(),
+                Amf::Number(f64::from(self.sample_rate)),
+            ),
+            (
+                "audiochannels".into(),
+                Amf::Number(f64::from(self.channel_count)),
+            ),
+            ("stereo".into(), Amf::Boolean(self.channel_count > 1)),
+            ("encoder".into(), Amf::str("screencapturekit-rs")),
+        ]
+    }
+}
+
+/// A message for the writer thread.
+enum Packet {
+    Metadata(Vec<(String, Amf)>),
+    Video { timestamp: u32, body: Vec<u8> },
+    Audio { timestamp: u32, body: Vec<u8> },
+}
+
+/// Write `packets` until the publisher hangs up, then end the session.
+fn write_packets(
+    mut connection: RtmpConnection,
+    stream_key: &str,
+    packets: &Receiver<Packet>,
+    error: &Mutex<Option<SCError>>,
+) {
+    for packet in packets {
+        let result = match packet {
+            Packet::Metadata(properties) => connection.send_metadata(properties),
+            Packet::Video { timestamp, body } => connection.send_video(timestamp, &body),
+            Packet::Audio { timestamp, body } => connection.send_audio(timestamp, &body),
+        };
+        if let Err(e) = result {
+            // Dropping `packets` on return tells the publisher to stop.
+            *error.lock().unwrap_or_else(PoisonError::into_inner) = Some(e);
+            return;
+        }
+    }
+    connection.close(stream_key);
+}
+
+/// Where the video stream is, as far as players are concerned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum VideoPhase {
+    /// The metadata and AVC sequence header haven't been sent yet.
+    Header,
+    /// Frames are going out.
+    Sending,
+    /// A video packet was dropped; frames are skipped until a keyframe.
+    AwaitingKeyframe,
+}
+
+struct PublisherState {
+    /// `None` once closed.
+    packets: Option<SyncSender<Packet>>,
+    writer_error: Arc<Mutex<Option<SCError>>>,
+    options: RtmpOptions,
+    video: H264Encoder,
+    audio: SCAudioEncoder,
+    /// Capture time that maps to RTMP timestamp 0.
+    origin: Option<CMTime>,
+    video_phase: VideoPhase,
+    sent_audio_header: bool,
+    /// Wait for room in the queue instead of dropping, while closing.
+    blocking: bool,
+    dropped: u64,
+    error: Option<SCError>,
+}
+
+impl PublisherState {
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    fn timestamp_ms(&mut self, pts: CMTime) -> u32 {
+        let seconds = |t: CMTime| t.value as f64 / f64::from(t.timescale.max(1));
+        let origin = *self.origin.get_or_insert(pts);
+        ((seconds(pts) - seconds(origin)) * 1000.0).max(0.0) as u32
+    }
+
+    /// Queue `packet` for the writer thread. Returns `Ok(false)` if the
+    /// queue was full and the packet was dropped.
+    fn send(&mut self, packet: Packet) -> Result<bool, SCError> {
+        let Some(packets) = &self.packets else {
+            return Err(SCError::StreamError("RTMP publisher is closed".into()));
+        };
+        let result = if self.blocking {
+            packets
+                .send(packet)
+                .map_err(|mpsc::SendError(packet)| TrySendError::Disconnected(packet))
+        } else {
+            packets.try_send(packet)
+        };
+        match result {
+            Ok(()) => Ok(true),
+            Err(TrySendError::Full(_)) => {
+                self.dropped += 1;
+                Ok(false)
+            }
+            Err(TrySendError::Disconnected(_)) => Err(self
+                .writer_error
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .clone()
+                .unwrap_or_else(|| SCError::StreamError("RTMP writer thread stopped".into()))),
+        }
+    }
+
+    fn push_video(&mut self, sample: &CMSampleBuffer) -> Result<(), SCError> {
+        if sample.frame_status() != Some(SCFrameStatus::Complete) {
+            return Ok(());
+        }
+        let Some(buffer) = sample.image_buffer() else {
+            return Ok(());
+        };
+        self.video
+            .encode(&buffer, sample.presentation_timestamp())?;
+        self.drain_video()
+    }
+
+    fn drain_video(&mut self) -> Result<(), SCError> {
+        while let Some(frame) = self.video.pop_frame() {
+            // Players can't start, or pick up after a gap, before a keyframe.
+            if self.video_phase != VideoPhase::Sending && !frame.keyframe {
+                continue;
+            }
+            if self.video_phase == VideoPhase::Header {
+                let Some(avcc) = self.video.avcc() else {
+                    continue;
+                };
+                let header = Packet::Video {
+                    timestamp: 0,
+                    body: flv::avc_sequence_header(&avcc),
+                };
+                if !self.send(Packet::Metadata(self.options.metadata()))? || !self.send(header)? {
+                    continue;
+                }
+            }
+            let timestamp = self.timestamp_ms(frame.pts);
+            let body = flv::avc_frame(&frame.data, frame.keyframe);
+            self.video_phase = if self.send(Packet::Video { timestamp, body })? {
+                VideoPhase::Sending
+            } else {
+                VideoPhase::AwaitingKeyframe
+            };
+        }
+        Ok(())
+    }
+
+    fn push_audio(&mut self, sample: &CMSampleBuffer) -> Result<(), SCError> {
+        let packets = self.audio.encode(sample)?;
+        // Hold audio until video has started so the stream opens on a keyframe.
+        if self.video_phase == VideoPhase::Header {
+            return Ok(());
+        }
+        for packet in packets {
+            if !self.sent_audio_header {
+                let body =
+                    flv::aac_sequence_header(self.options.sample_rate, self.options.channel_count);
+                if !self.send(Packet::Audio { timestamp: 0, body })? {
+                    continue;
+                }
+                self.sent_audio_header = true;
+            }
+            let timestamp = self.timestamp_ms(packet.pts());
+            let body = flv::aac_frame(packet.data());
+            self.send(Packet::Audio { timestamp, body })?;
+        }
+        Ok(())
+    }
+
+    /// Send the encoder's last frames and hang up on the writer thread,
+    /// which ends the session once the queue is written.
+    fn close(&mut self) -> Result<(), SCError> {
+        if self.packets.is_none() {
+            return Ok(());
+        }
+        self.blocking = true;
+        let result = if self.error.is_some() {
+            Ok(())
+        } else {
+            self.video.flush().and_then(|()| self.drain_video())
+        };
+        self.packets = None;
+        result
+    }
+}
+
+/// Publishes captured video and audio to an RTMP server.
+///
+/// Requires the `rtmp` feature. See the [module documentation](self).
+pub struct SCRtmpPublisher {
+    state: Mutex<PublisherState>,
+    writer: Mutex<Option<JoinHandle<()>>>,
+    writer_error: Arc<Mutex<Option<SCError>>>,
+}
+
+impl std::fmt::Debug for SCRtmpPublisher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut s = f.debug_struct("SCRtmpPublisher");
+        if let Ok(state) = self.state.lock() {
+            s.field("options", &state.options)
+                .field("dropped_packets", &state.dropped)
+                .field("closed", &state.packets.is_none());
+        }
+        s.field("error", &self.last_error()).finish_non_exhaustive()
+    }
+}
+
+impl SCRtmpPublisher {
+    /// Connect to `url` (`rtmp://host[:port]/app`) and start publishing
+    /// `stream_key`.
+    ///
+    /// IPv6 hosts go in brackets, e.g. `rtmp://[::1]:1935/app`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidConfiguration`] for a malformed or
+    /// `rtmps://` URL or invalid options, [`SCError::OSError`] if an encoder
+    /// cannot be created, or [`SCError::StreamError`] if the connection,
+    /// handshake or publish request fails.
+    pub fn connect(url: &str, stream_key: &str, options: RtmpOptions) -> Result<Self, SCError> {
+        let url = RtmpUrl::parse(url)?;
+        options.validate()?;
+        let video = H264Encoder::new(
+            options.width,
+            options.height,
+            options.fps,
+            options.video_bitrate,
+            options.keyframe_interval,
+        )?;
+        let audio = SCAudioEncoder::new(
+            AudioCodec::Aac,
+            options.sample_rate,
+            options.channel_count,
+            options.audio_bitrate,
+        )?;
+        let connection = RtmpConnection::publish(&url, stream_key, options.timeout)?;
+
+        let (sender, receiver) = mpsc::sync_channel(options.send_queue);
+        let writer_error = Arc::new(Mutex::new(None));
+        let writer = {
+            let error = Arc::clone(&writer_error);
+            let stream_key = stream_key.to_string();
+            std::thread::Builder::new()
+                .name("sc-rtmp-writer".to_string())
+                .spawn(move || write_packets(connection, &stream_key, &receiver, &error))
+                .map_err(|e| {
+                    SCError::internal_error(format!("failed to spawn RTMP writer thread: {e}"))
+                })?
+        };
+
+        Ok(Self {
+            state: Mutex::new(PublisherState {
+                packets: Some(sender),
+                writer_error: Arc::clone(&writer_error),
+                options,
+                video,
+                audio,
+                origin: None,
+                video_phase: VideoPhase::Header,
+                sent_audio_header: false,
+                blocking: false,
+                dropped: 0,
+                error: None,
+            }),
+            writer: Mutex::new(Some(writer)),
+            writer_error,
+        })
+    }
+
+    /// Encode and send a captured video frame. Idle and dropped frames are
+    /// skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns the encoder or network error, or the error that stopped the
+    /// publisher earlier.
+    pub fn push_video(&self, sample: &CMSampleBuffer) -> Result<(), SCError> {
+        self.with_state(|state| state.push_video(sample))
+    }
+
+    /// Encode and send a captured audio buffer.
+    ///
+    /// # Errors
+    ///
+    /// See [`push_video`](Self::push_video).
+    pub fn push_audio(&self, sample: &CMSampleBuffer) -> Result<(), SCError> {
+        self.with_state(|state| state.push_audio(sample))
+    }
+
+    /// The error that stopped publishing, if any.
+    #[must_use]
+    pub fn last_error(&self) -> Option<SCError> {
+        let error = self.state.lock().ok().and_then(|state| state.error.clone());
+        error.or_else(|| {
+            self.writer_error
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .clone()
+        })
+    }
+
+    /// Packets dropped because the network fell behind.
+    #[must_use]
+    pub fn dropped_packets(&self) -> u64 {
+        self.state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .dropped
+    }
+
+    /// Flush the video encoder, wait for queued packets to be written and
+    /// end the RTMP session. Further pushes fail.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error from sending the final frames.
+    pub fn close(&self) -> Result<(), SCError> {
+        let result = self
+            .state
+            .lock()
+            .map_err(|_| SCError::internal_error("RTMP publisher lock poisoned"))?
+            .close();
+        let writer = self
+            .writer
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take();
+        if let Some(writer) = writer {
+            let _ = writer.join();
+        }
+        result?;
+        let error = self
+            .writer_error
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+        error.map_or(Ok(()), Err)
+    }
+
+    fn with_state(
+        &self,
+        f: impl FnOnce(&mut PublisherState) -> Result<(), SCError>,
+    ) -> Result<(), SCError> {
+        let mut state = self
+            .state
+            .lock()
+            .map_err(|_| SCError::internal_error("RTMP publisher lock poisoned"))?;
+        if let Some(error) = &state.error {
+            return Err(error.clone());
+        }
+        if state.packets.is_none() {
+            return Err(SCError::StreamError("RTMP publisher is closed".into()));
+        }
+        let result = f(&mut state);
+        if let Err(error) = &result {
+            state.error = Some(error.clone());
+        }
+        result
+    }
+}
+
+impl SCStreamOutputTrait for SCRtmpPublisher {
+    fn did_output_sample_buffer(&self, sample_buffer: CMSampleBuffer, of_type: SCStreamOutputType) {
+        // Errors are kept in `last_error`.
+        let _ = match of_type {
+            SCStreamOutputType::Screen => self.push_video(&sample_buffer),
+            SCStreamOutputType::Audio => self.push_audio(&sample_buffer),
+            SCStreamOutputType::Microphone => Ok(()),
+        };
+    }
+}
+
+impl Drop for SCRtmpPublisher {
+    fn drop(&mut self) {
+        let _ = self.close();
+    }
+}
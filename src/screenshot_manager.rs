@@ -9,6 +9,8 @@
 //! - A single screenshot rather than continuous capture
 //! - Quick capture without stream setup/teardown overhead
 //! - Direct saving to image files
+//! - Thumbnails of many windows at once, via
+//!   [`SCScreenshotManager::capture_all_windows`]
 //!
 //! For continuous capture, use [`SCStream`](crate::stream::SCStream) instead.
 //!
@@ -22,7 +24,7 @@
 //! # fn example() -> Result<(), Box<dyn std::error::Error>> {
 //! let content = SCShareableContent::get()?;
 //! let display = &content.displays()[0];
-//! let filter = SCContentFilter::create().with_display(display).with_excluding_windows(&[]).build();
+//! let filter = SCContentFilter::for_display(display).with_excluding_windows(&[]).build();
 //! let config = SCStreamConfiguration::new()
 //!     .with_width(1920)
 //!     .with_height(1080);
@@ -41,12 +43,12 @@
 //! ```
 
 use crate::error::SCError;
+use crate::shareable_content::SCDisplay;
 use crate::stream::configuration::SCStreamConfiguration;
 use crate::stream::content_filter::SCContentFilter;
 use crate::utils::completion::{error_from_cstr, SyncCompletion};
 use std::ffi::c_void;
 
-#[cfg(feature = "macos_15_2")]
 use crate::cg::CGRect;
 
 #[doc(no_inline)]
@@ -115,6 +117,20 @@ impl ImageFormat {
             Self::Heic(_) => "heic",
         }
     }
+
+    /// Get the MIME type for this format, as used in `data:` URLs and HTTP
+    /// `Content-Type` headers
+    #[must_use]
+    pub const fn mime_type(&self) -> &'static str {
+        match self {
+            Self::Png => "image/png",
+            Self::Jpeg(_) => "image/jpeg",
+            Self::Tiff => "image/tiff",
+            Self::Gif => "image/gif",
+            Self::Bmp => "image/bmp",
+            Self::Heic(_) => "image/heic",
+        }
+    }
 }
 
 /// # Safety
@@ -235,7 +251,7 @@ pub trait CGImageExt {
     /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// # let content = SCShareableContent::get()?;
     /// # let display = &content.displays()[0];
-    /// # let filter = SCContentFilter::create().with_display(display).with_excluding_windows(&[]).build();
+    /// # let filter = SCContentFilter::for_display(display).with_excluding_windows(&[]).build();
     /// # let config = SCStreamConfiguration::new().with_width(1920).with_height(1080);
     /// let mut buffer: Vec<u8> = vec![0; 1920 * 1080 * 4];
     /// let img = SCScreenshotManager::capture_image(&filter, &config)?;
@@ -289,6 +305,10 @@ pub trait CGImageExt {
 
     /// Save the image to a file in the specified format.
     ///
+    /// PNG, JPEG, TIFF and HEIC files carry the image's
+    /// [`icc_profile`](Self::icc_profile), so color-managed viewers show the
+    /// captured colors.
+    ///
     /// # Errors
     /// Returns an error if the path contains interior null bytes or the export fails.
     ///
@@ -301,7 +321,7 @@ pub trait CGImageExt {
     /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// # let content = SCShareableContent::get()?;
     /// # let display = &content.displays()[0];
-    /// # let filter = SCContentFilter::create().with_display(display).with_excluding_windows(&[]).build();
+    /// # let filter = SCContentFilter::for_display(display).with_excluding_windows(&[]).build();
     /// # let config = SCStreamConfiguration::new().with_width(1920).with_height(1080);
     /// let image = SCScreenshotManager::capture_image(&filter, &config)?;
     /// image.save("screenshot.png", ImageFormat::Png)?;
@@ -311,8 +331,467 @@ pub trait CGImageExt {
     /// # }
     /// ```
     fn save(&self, path: &str, format: ImageFormat) -> Result<(), SCError>;
+
+    /// Crop the image to `rect`, given in pixel coordinates with the origin
+    /// at the top-left corner.
+    ///
+    /// The rect is clamped to the image bounds and rounded outwards to whole
+    /// pixels. The returned image shares the source's backing store, so
+    /// cropping does not copy pixel data.
+    ///
+    /// # Errors
+    /// Returns [`SCError::InvalidDimension`] if `rect` has zero area, or an
+    /// error if it lies entirely outside the image.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use screencapturekit::screenshot_manager::{CGImageExt, SCScreenshotManager};
+    /// # use screencapturekit::stream::{content_filter::SCContentFilter, configuration::SCStreamConfiguration};
+    /// # use screencapturekit::shareable_content::SCShareableContent;
+    /// # use screencapturekit::cg::CGRect;
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let content = SCShareableContent::get()?;
+    /// # let display = &content.displays()[0];
+    /// # let filter = SCContentFilter::for_display(display).with_excluding_windows(&[]).build();
+    /// # let config = SCStreamConfiguration::new().with_width(1920).with_height(1080);
+    /// let image = SCScreenshotManager::capture_image(&filter, &config)?;
+    /// let selection = image.crop(CGRect::new(100.0, 100.0, 640.0, 480.0))?;
+    /// let thumbnail = selection.resized(160, 120)?;
+    /// assert_eq!(thumbnail.width(), 160);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn crop(&self, rect: CGRect) -> Result<CGImage, SCError>;
+
+    /// Scale the image to exactly `width` x `height` pixels.
+    ///
+    /// Uses CoreGraphics high-quality interpolation and produces an 8-bit
+    /// premultiplied BGRA image. The aspect ratio is not preserved.
+    ///
+    /// # Errors
+    /// Returns [`SCError::InvalidDimension`] if either dimension is zero, or
+    /// an error if CoreGraphics fails to allocate the scaled image.
+    fn resized(&self, width: usize, height: usize) -> Result<CGImage, SCError>;
+
+    /// Render the image to RGBA and return it as a row-addressable buffer.
+    ///
+    /// # Errors
+    /// Returns an error if the pixel data cannot be extracted.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use screencapturekit::screenshot_manager::{CGImageExt, SCScreenshotManager};
+    /// # use screencapturekit::stream::{content_filter::SCContentFilter, configuration::SCStreamConfiguration};
+    /// # use screencapturekit::shareable_content::SCShareableContent;
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let content = SCShareableContent::get()?;
+    /// # let display = &content.displays()[0];
+    /// # let filter = SCContentFilter::for_display(display).with_excluding_windows(&[]).build();
+    /// # let config = SCStreamConfiguration::new().with_width(1920).with_height(1080);
+    /// let image = SCScreenshotManager::capture_image(&filter, &config)?;
+    /// let pixels = image.rgba_pixels()?;
+    /// for (y, row) in pixels.rows().enumerate() {
+    ///     let opaque = row.chunks_exact(4).filter(|px| px[3] == 255).count();
+    ///     println!("row {y}: {opaque} opaque pixels");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn rgba_pixels(&self) -> Result<CGImagePixels, SCError>;
+
+    /// Render the image to BGRA and return it as a row-addressable buffer.
+    ///
+    /// # Errors
+    /// Returns an error if the pixel data cannot be extracted.
+    fn bgra_pixels(&self) -> Result<CGImagePixels, SCError>;
+
+    /// Encode the image in memory in the specified format.
+    ///
+    /// Runs the same `ImageIO` pipeline as [`save`](CGImageExt::save)
+    /// without touching the file system.
+    ///
+    /// # Errors
+    /// Returns an error if `ImageIO` cannot encode the image in `format`.
+    fn encode(&self, format: ImageFormat) -> Result<Vec<u8>, SCError>;
+
+    /// Encode the image as a base64 `data:` URL.
+    ///
+    /// The result can be handed straight to a web view (`<img src>`, CSS
+    /// `url()`, a canvas `Image`), which makes it the simplest way to get a
+    /// capture from a Tauri or Electron backend to its frontend. Use PNG or
+    /// JPEG; web engines don't reliably decode the other formats.
+    ///
+    /// # Errors
+    /// Returns an error if `ImageIO` cannot encode the image in `format`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use screencapturekit::screenshot_manager::{CGImageExt, ImageFormat, SCScreenshotManager};
+    /// # use screencapturekit::stream::{content_filter::SCContentFilter, configuration::SCStreamConfiguration};
+    /// # use screencapturekit::shareable_content::SCShareableContent;
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let content = SCShareableContent::get()?;
+    /// # let display = &content.displays()[0];
+    /// # let filter = SCContentFilter::for_display(display).build();
+    /// # let config = SCStreamConfiguration::new().with_width(1920).with_height(1080);
+    /// let image = SCScreenshotManager::capture_image(&filter, &config)?;
+    /// let url = image.to_data_url(ImageFormat::Jpeg(0.8))?;
+    /// assert!(url.starts_with("data:image/jpeg;base64,"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn to_data_url(&self, format: ImageFormat) -> Result<String, SCError>;
+
+    /// Bits in each color or alpha component: 8 for ordinary captures,
+    /// 16 for HDR screenshots.
+    fn bits_per_component(&self) -> usize;
+
+    /// Bits in one pixel, across all components and padding.
+    fn bits_per_pixel(&self) -> usize;
+
+    /// Bytes in one row of the image's backing store, including any
+    /// padding at the end of the row.
+    ///
+    /// This describes the source image, not the tightly-packed buffers
+    /// produced by [`rgba_data`](CGImageExt::rgba_data) and friends.
+    fn bytes_per_row(&self) -> usize;
+
+    /// Whether and where the image stores alpha.
+    fn alpha_info(&self) -> CGImageAlphaInfo;
+
+    /// Whether components are floating-point values rather than integers.
+    ///
+    /// Extended-range (HDR) images use float components so values can
+    /// exceed SDR white.
+    fn has_float_components(&self) -> bool;
+
+    /// Number of color components excluding alpha: 1 for grayscale, 3 for
+    /// RGB. Returns 0 for images without a color space, such as masks.
+    fn color_components(&self) -> usize;
+
+    /// Name of the image's color space, such as `kCGColorSpaceSRGB` or
+    /// `kCGColorSpaceExtendedLinearDisplayP3`.
+    ///
+    /// Returns `None` if the image has no color space or the color space is
+    /// unnamed (for example, one built from an ICC profile).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use screencapturekit::screenshot_manager::{CGImageExt, SCScreenshotManager};
+    /// # use screencapturekit::stream::{content_filter::SCContentFilter, configuration::SCStreamConfiguration};
+    /// # use screencapturekit::shareable_content::SCShareableContent;
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let content = SCShareableContent::get()?;
+    /// # let display = &content.displays()[0];
+    /// # let filter = SCContentFilter::for_display(display).build();
+    /// # let config = SCStreamConfiguration::new();
+    /// let image = SCScreenshotManager::capture_image(&filter, &config)?;
+    /// if image.bits_per_component() > 8 || image.has_float_components() {
+    ///     println!("high bit depth image in {:?}", image.color_space_name());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn color_space_name(&self) -> Option<String>;
+
+    /// The ICC profile of the image's color space.
+    ///
+    /// This is the profile [`save`](Self::save) and [`encode`](Self::encode)
+    /// embed in PNG, JPEG, TIFF and HEIC output. Returns `None` if the image
+    /// has no color space or its color space can't be expressed as an ICC
+    /// profile.
+    fn icc_profile(&self) -> Option<Vec<u8>>;
+
+    /// Tag the image with the color space described by an ICC `profile`.
+    ///
+    /// The pixel values are left as they are: use this to label pixels that
+    /// already are in the profile's color space, such as a capture in a
+    /// display's native colors, not to convert between color spaces. The
+    /// returned image shares the source's backing store.
+    ///
+    /// # Errors
+    /// Returns [`SCError::InvalidConfiguration`] if `profile` is not a
+    /// valid ICC profile, or describes a color space with a different
+    /// number of components than the image.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use screencapturekit::screenshot_manager::{CGImageExt, ImageFormat, SCScreenshotManager};
+    /// # use screencapturekit::stream::{content_filter::SCContentFilter, configuration::SCStreamConfiguration};
+    /// # use screencapturekit::shareable_content::SCShareableContent;
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let content = SCShareableContent::get()?;
+    /// # let display = &content.displays()[0];
+    /// # let filter = SCContentFilter::for_display(display).build();
+    /// # let config = SCStreamConfiguration::new();
+    /// let image = SCScreenshotManager::capture_image(&filter, &config)?;
+    /// if let Some(profile) = image.icc_profile() {
+    ///     // Keep the profile when the pixels go through another pipeline.
+    ///     let processed = image.resized(640, 360)?.with_icc_profile(&profile)?;
+    ///     processed.save("thumbnail.png", ImageFormat::Png)?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn with_icc_profile(&self, profile: &[u8]) -> Result<CGImage, SCError>;
 }
 
+/// Image export for video sample buffers.
+///
+/// Converts the frame through [`CMSampleBufferExt::cg_image`], so it accepts
+/// every pixel format a stream can deliver, including YCbCr.
+///
+/// [`CMSampleBufferExt::cg_image`]: crate::cm::CMSampleBufferExt::cg_image
+pub trait CMSampleBufferImageExt {
+    /// Encode the frame in memory in the specified format.
+    ///
+    /// # Errors
+    /// Returns [`SCError::InvalidBuffer`] if the sample has no image (an
+    /// audio sample, or an idle frame), or an error if encoding fails.
+    fn encode_image(&self, format: ImageFormat) -> Result<Vec<u8>, SCError>;
+
+    /// Encode the frame as a base64 `data:` URL.
+    ///
+    /// See [`CGImageExt::to_data_url`].
+    ///
+    /// # Errors
+    /// Returns [`SCError::InvalidBuffer`] if the sample has no image (an
+    /// audio sample, or an idle frame), or an error if encoding fails.
+    fn to_data_url(&self, format: ImageFormat) -> Result<String, SCError>;
+}
+
+impl CMSampleBufferImageExt for crate::cm::CMSampleBuffer {
+    fn encode_image(&self, format: ImageFormat) -> Result<Vec<u8>, SCError> {
+        sample_image(self)?.encode(format)
+    }
+
+    fn to_data_url(&self, format: ImageFormat) -> Result<String, SCError> {
+        sample_image(self)?.to_data_url(format)
+    }
+}
+
+fn sample_image(sample: &crate::cm::CMSampleBuffer) -> Result<CGImage, SCError> {
+    use crate::cm::CMSampleBufferExt;
+    sample.cg_image().map_err(|status| {
+        SCError::InvalidBuffer(format!("sample buffer has no image (OSStatus {status})"))
+    })
+}
+
+/// A screenshot as both a [`CGImage`] and the sample buffer it came from
+///
+/// Returned by [`SCScreenshotManager::capture_both`]. The image is built
+/// from the sample's pixel buffer, so both describe the same capture, and
+/// the sample keeps the frame attachments a plain image loses: the display
+/// scale factor, where the content sits in the image and how much it was
+/// scaled to fit.
+#[derive(Debug)]
+pub struct Screenshot {
+    image: CGImage,
+    sample_buffer: crate::cm::CMSampleBuffer,
+}
+
+impl Screenshot {
+    pub(crate) fn from_sample_buffer(
+        sample_buffer: crate::cm::CMSampleBuffer,
+    ) -> Result<Self, SCError> {
+        let image = sample_image(&sample_buffer)?;
+        Ok(Self {
+            image,
+            sample_buffer,
+        })
+    }
+
+    /// The captured image
+    #[must_use]
+    pub const fn image(&self) -> &CGImage {
+        &self.image
+    }
+
+    /// The sample buffer, with its pixel buffer and attachments
+    #[must_use]
+    pub const fn sample_buffer(&self) -> &crate::cm::CMSampleBuffer {
+        &self.sample_buffer
+    }
+
+    /// Backing scale factor of the captured display, e.g. `2.0` on Retina
+    #[must_use]
+    pub fn scale_factor(&self) -> Option<f64> {
+        use crate::cm::CMSampleBufferSCExt;
+        self.sample_buffer.scale_factor()
+    }
+
+    /// Where the captured content lies in the image, in points
+    #[must_use]
+    pub fn content_rect(&self) -> Option<CGRect> {
+        use crate::cm::CMSampleBufferSCExt;
+        self.sample_buffer.content_rect()
+    }
+
+    /// How much the content was scaled to fit the configured size
+    #[must_use]
+    pub fn content_scale(&self) -> Option<f64> {
+        use crate::cm::CMSampleBufferSCExt;
+        self.sample_buffer.content_scale()
+    }
+
+    /// Split into the image and the sample buffer
+    #[must_use]
+    pub fn into_parts(self) -> (CGImage, crate::cm::CMSampleBuffer) {
+        (self.image, self.sample_buffer)
+    }
+}
+
+/// Tightly-packed 4-byte-per-pixel image data with row and pixel accessors.
+///
+/// Returned by [`CGImageExt::rgba_pixels`] and [`CGImageExt::bgra_pixels`].
+/// The channel order matches the method that produced it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CGImagePixels {
+    data: Vec<u8>,
+    width: usize,
+    height: usize,
+}
+
+impl CGImagePixels {
+    /// Bytes per pixel (always 4).
+    pub const BYTES_PER_PIXEL: usize = 4;
+
+    /// Width in pixels.
+    #[must_use]
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Height in pixels.
+    #[must_use]
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Bytes per row (`width * 4`; rows are tightly packed).
+    #[must_use]
+    pub const fn bytes_per_row(&self) -> usize {
+        self.width * Self::BYTES_PER_PIXEL
+    }
+
+    /// The raw pixel bytes.
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Consume the wrapper and return the raw pixel bytes.
+    #[must_use]
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.data
+    }
+
+    /// Get row `y`, or `None` if it is out of bounds.
+    #[must_use]
+    pub fn row(&self, y: usize) -> Option<&[u8]> {
+        if y >= self.height {
+            return None;
+        }
+        let stride = self.bytes_per_row();
+        self.data.get(y * stride..(y + 1) * stride)
+    }
+
+    /// Get the 4 channel bytes of the pixel at (`x`, `y`), or `None` if it
+    /// is out of bounds.
+    #[must_use]
+    pub fn pixel(&self, x: usize, y: usize) -> Option<[u8; 4]> {
+        if x >= self.width {
+            return None;
+        }
+        let offset = x * Self::BYTES_PER_PIXEL;
+        let px = self.row(y)?.get(offset..offset + Self::BYTES_PER_PIXEL)?;
+        Some([px[0], px[1], px[2], px[3]])
+    }
+
+    /// Iterate over rows from top to bottom.
+    pub fn rows(&self) -> std::slice::ChunksExact<'_, u8> {
+        // `chunks_exact` panics on a zero chunk size; a zero-width image has
+        // no bytes, so any non-zero stride yields an empty iterator.
+        self.data.chunks_exact(self.bytes_per_row().max(1))
+    }
+}
+
+/// Alpha channel layout of a [`CGImage`], mirroring `CGImageAlphaInfo`.
+///
+/// Returned by [`CGImageExt::alpha_info`]. "First" and "Last" refer to the
+/// component order in memory before any byte swapping, so little-endian
+/// BGRA captures report [`PremultipliedFirst`](Self::PremultipliedFirst).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CGImageAlphaInfo {
+    /// No alpha channel.
+    None,
+    /// Alpha is the last component, and color components are premultiplied.
+    PremultipliedLast,
+    /// Alpha is the first component, and color components are premultiplied.
+    PremultipliedFirst,
+    /// Alpha is the last component, color components are not premultiplied.
+    Last,
+    /// Alpha is the first component, color components are not premultiplied.
+    First,
+    /// The last component is padding and should be ignored.
+    NoneSkipLast,
+    /// The first component is padding and should be ignored.
+    NoneSkipFirst,
+    /// The image is an alpha-only mask.
+    Only,
+    /// A value this crate does not recognize.
+    Unknown(u32),
+}
+
+impl CGImageAlphaInfo {
+    const MASK: u32 = 0x1F;
+
+    /// Convert from a raw `CGImageAlphaInfo` or `CGBitmapInfo` value.
+    ///
+    /// Bits outside the alpha-info mask are ignored.
+    #[must_use]
+    pub const fn from_raw(value: u32) -> Self {
+        match value & Self::MASK {
+            0 => Self::None,
+            1 => Self::PremultipliedLast,
+            2 => Self::PremultipliedFirst,
+            3 => Self::Last,
+            4 => Self::First,
+            5 => Self::NoneSkipLast,
+            6 => Self::NoneSkipFirst,
+            7 => Self::Only,
+            other => Self::Unknown(other),
+        }
+    }
+
+    /// Whether the image carries a meaningful alpha channel.
+    #[must_use]
+    pub const fn has_alpha(self) -> bool {
+        matches!(
+            self,
+            Self::PremultipliedLast
+                | Self::PremultipliedFirst
+                | Self::Last
+                | Self::First
+                | Self::Only
+        )
+    }
+
+    /// Whether color components are premultiplied by alpha.
+    #[must_use]
+    pub const fn is_premultiplied(self) -> bool {
+        matches!(self, Self::PremultipliedLast | Self::PremultipliedFirst)
+    }
+}
+
+/// `kCGBitmapFloatComponents`
+const BITMAP_FLOAT_COMPONENTS: u32 = 1 << 8;
+
 /// Internal selector for the channel ordering passed to the Swift renderer.
 #[derive(Debug, Clone, Copy)]
 enum PixelLayout {
@@ -423,6 +902,184 @@ impl CGImageExt for CGImage {
             )))
         }
     }
+
+    fn crop(&self, rect: CGRect) -> Result<CGImage, SCError> {
+        if rect.size.width <= 0.0 {
+            return Err(SCError::invalid_dimension("crop width", 0));
+        }
+        if rect.size.height <= 0.0 {
+            return Err(SCError::invalid_dimension("crop height", 0));
+        }
+
+        let ptr = unsafe {
+            crate::ffi::cgimage_create_cropped(
+                self.as_ptr(),
+                rect.origin.x,
+                rect.origin.y,
+                rect.size.width,
+                rect.size.height,
+            )
+        };
+        if ptr.is_null() {
+            return Err(SCError::internal_error(format!(
+                "Crop rect ({}, {}, {}x{}) does not intersect {}x{} image",
+                rect.origin.x,
+                rect.origin.y,
+                rect.size.width,
+                rect.size.height,
+                self.width(),
+                self.height()
+            )));
+        }
+        Ok(unsafe { cgimage_from_retained_ptr(ptr) })
+    }
+
+    fn resized(&self, width: usize, height: usize) -> Result<CGImage, SCError> {
+        if width == 0 {
+            return Err(SCError::invalid_dimension("width", width));
+        }
+        if height == 0 {
+            return Err(SCError::invalid_dimension("height", height));
+        }
+
+        let ptr = unsafe { crate::ffi::cgimage_create_resized(self.as_ptr(), width, height) };
+        if ptr.is_null() {
+            return Err(SCError::internal_error(format!(
+                "Failed to resize CGImage to {width}x{height}"
+            )));
+        }
+        Ok(unsafe { cgimage_from_retained_ptr(ptr) })
+    }
+
+    fn rgba_pixels(&self) -> Result<CGImagePixels, SCError> {
+        Ok(CGImagePixels {
+            data: render_pixel_data(self, PixelLayout::Rgba)?,
+            width: self.width(),
+            height: self.height(),
+        })
+    }
+
+    fn bgra_pixels(&self) -> Result<CGImagePixels, SCError> {
+        Ok(CGImagePixels {
+            data: render_pixel_data(self, PixelLayout::Bgra)?,
+            width: self.width(),
+            height: self.height(),
+        })
+    }
+
+    fn encode(&self, format: ImageFormat) -> Result<Vec<u8>, SCError> {
+        let mut len = 0;
+        let ptr = unsafe {
+            crate::ffi::cgimage_encode_to_data(
+                self.as_ptr(),
+                format.to_format_id(),
+                format.quality(),
+                &mut len,
+            )
+        };
+        if ptr.is_null() {
+            return Err(SCError::internal_error(format!(
+                "Failed to encode image as {}",
+                format.extension().to_uppercase()
+            )));
+        }
+        // SAFETY: the bridge returned a buffer of exactly `len` bytes, which
+        // we copy out before handing it back.
+        let bytes = unsafe { std::slice::from_raw_parts(ptr, len) }.to_vec();
+        unsafe { crate::ffi::cgimage_encoded_data_free(ptr) };
+        Ok(bytes)
+    }
+
+    fn to_data_url(&self, format: ImageFormat) -> Result<String, SCError> {
+        Ok(data_url(format.mime_type(), &self.encode(format)?))
+    }
+
+    fn bits_per_component(&self) -> usize {
+        unsafe { crate::ffi::cgimage_get_bits_per_component(self.as_ptr()) }
+    }
+
+    fn bits_per_pixel(&self) -> usize {
+        unsafe { crate::ffi::cgimage_get_bits_per_pixel(self.as_ptr()) }
+    }
+
+    fn bytes_per_row(&self) -> usize {
+        unsafe { crate::ffi::cgimage_get_bytes_per_row(self.as_ptr()) }
+    }
+
+    fn alpha_info(&self) -> CGImageAlphaInfo {
+        CGImageAlphaInfo::from_raw(unsafe { crate::ffi::cgimage_get_bitmap_info(self.as_ptr()) })
+    }
+
+    fn has_float_components(&self) -> bool {
+        unsafe { crate::ffi::cgimage_get_bitmap_info(self.as_ptr()) & BITMAP_FLOAT_COMPONENTS != 0 }
+    }
+
+    fn color_components(&self) -> usize {
+        unsafe { crate::ffi::cgimage_get_color_space_components(self.as_ptr()) }
+    }
+
+    fn color_space_name(&self) -> Option<String> {
+        unsafe {
+            crate::utils::ffi_string::ffi_string_from_buffer(256, |buffer, len| {
+                crate::ffi::cgimage_get_color_space_name(self.as_ptr(), buffer, len)
+            })
+        }
+    }
+
+    fn icc_profile(&self) -> Option<Vec<u8>> {
+        let mut len = 0;
+        let ptr = unsafe { crate::ffi::cgimage_copy_icc_profile(self.as_ptr(), &mut len) };
+        if ptr.is_null() {
+            return None;
+        }
+        // SAFETY: the bridge returned a buffer of exactly `len` bytes, which
+        // we copy out before handing it back.
+        let profile = unsafe { std::slice::from_raw_parts(ptr, len) }.to_vec();
+        unsafe { crate::ffi::cgimage_encoded_data_free(ptr) };
+        Some(profile)
+    }
+
+    fn with_icc_profile(&self, profile: &[u8]) -> Result<CGImage, SCError> {
+        let ptr = unsafe {
+            crate::ffi::cgimage_create_with_icc_profile(
+                self.as_ptr(),
+                profile.as_ptr(),
+                profile.len(),
+            )
+        };
+        if ptr.is_null() {
+            return Err(SCError::invalid_config(format!(
+                "ICC profile ({} bytes) is invalid or does not match the image's {} color components",
+                profile.len(),
+                self.color_components()
+            )));
+        }
+        Ok(unsafe { cgimage_from_retained_ptr(ptr) })
+    }
+}
+
+/// `data:<mime>;base64,<payload>`
+fn data_url(mime_type: &str, bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let prefix = format!("data:{mime_type};base64,");
+    let mut url = String::with_capacity(prefix.len() + bytes.len().div_ceil(3) * 4);
+    url.push_str(&prefix);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+        for (i, shift) in [18, 12, 6, 0].into_iter().enumerate() {
+            if i <= chunk.len() {
+                url.push(char::from(ALPHABET[(n >> shift) as usize & 0x3F]));
+            } else {
+                url.push('=');
+            }
+        }
+    }
+    url
 }
 
 fn render_pixel_data(image: &CGImage, layout: PixelLayout) -> Result<Vec<u8>, SCError> {
@@ -541,7 +1198,7 @@ fn required_byte_size(image: &CGImage) -> Result<usize, SCError> {
 /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
 /// let content = SCShareableContent::get()?;
 /// let display = &content.displays()[0];
-/// let filter = SCContentFilter::create().with_display(display).with_excluding_windows(&[]).build();
+/// let filter = SCContentFilter::for_display(display).with_excluding_windows(&[]).build();
 /// let config = SCStreamConfiguration::new()
 ///     .with_width(1920)
 ///     .with_height(1080);
@@ -583,6 +1240,140 @@ impl SCScreenshotManager {
         completion.wait().map_err(SCError::ScreenshotError)
     }
 
+    /// Capture the area picked with
+    /// [`SCRegionSelector`](crate::region_selector::SCRegionSelector).
+    ///
+    /// The region is captured at the display's native backing scale by
+    /// filtering to the selected display and setting the configuration's
+    /// source rect, so this works on every macOS 14.0+ system (unlike
+    /// [`capture_image_in_rect`](Self::capture_image_in_rect), which needs 15.2).
+    ///
+    /// This blocks until the capture completes; don't call it from the
+    /// selector callback itself, which runs on the main thread.
+    ///
+    /// # Errors
+    /// Returns [`SCError::DisplayNotFound`] if the selection's display is no
+    /// longer connected, or an error if the capture fails.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use screencapturekit::region_selector::{SCRegionSelectionOutcome, SCRegionSelector};
+    /// use screencapturekit::screenshot_manager::SCScreenshotManager;
+    ///
+    /// SCRegionSelector::show(|outcome| {
+    ///     if let SCRegionSelectionOutcome::Selected(selection) = outcome {
+    ///         std::thread::spawn(move || {
+    ///             if let Ok(image) = SCScreenshotManager::capture_region(&selection) {
+    ///                 println!("Captured {}x{}", image.width(), image.height());
+    ///             }
+    ///         });
+    ///     }
+    /// });
+    /// ```
+    pub fn capture_region(
+        selection: &crate::region_selector::SCRegionSelection,
+    ) -> Result<CGImage, SCError> {
+        let content = crate::shareable_content::SCShareableContent::get()?;
+        let display = content
+            .displays()
+            .into_iter()
+            .find(|d| d.display_id() == selection.display_id())
+            .ok_or_else(|| {
+                SCError::DisplayNotFound(format!("display {}", selection.display_id()))
+            })?;
+
+        // The selection is in global coordinates; `sourceRect` is relative
+        // to the captured display.
+        let frame = display.frame();
+        let rect = selection.rect();
+        let source_rect = CGRect::new(
+            rect.origin.x - frame.origin.x,
+            rect.origin.y - frame.origin.y,
+            rect.size.width,
+            rect.size.height,
+        );
+        let (width, height) = selection.pixel_size();
+        if width == 0 {
+            return Err(SCError::invalid_dimension("width", 0));
+        }
+        if height == 0 {
+            return Err(SCError::invalid_dimension("height", 0));
+        }
+
+        let filter = SCContentFilter::for_display(&display)
+            .with_excluding_windows(&[])
+            .try_build()?;
+        let config = SCStreamConfiguration::new()
+            .with_source_rect(source_rect)
+            .with_width(width)
+            .with_height(height);
+
+        Self::capture_image(&filter, &config)
+    }
+
+    /// Capture a single screenshot as a [`Frame`](crate::frame::Frame)
+    ///
+    /// Lets code written against [`Frame`](crate::frame::Frame) take
+    /// screenshots and stream samples alike.
+    ///
+    /// # Errors
+    /// Returns an error if the capture fails; see
+    /// [`capture_image`](Self::capture_image).
+    pub fn capture_frame(
+        content_filter: &SCContentFilter,
+        configuration: &SCStreamConfiguration,
+    ) -> Result<crate::frame::Frame, SCError> {
+        Self::capture_image(content_filter, configuration).map(crate::frame::Frame::from_screenshot)
+    }
+
+    /// Capture a screenshot that carries `display`'s color profile.
+    ///
+    /// When `ScreenCaptureKit` hands back the display's native pixels without
+    /// an ICC-backed color space, the image is tagged with the display's
+    /// profile (see [`SCDisplay::icc_profile`]), so that
+    /// [`CGImageExt::save`] embeds it and designers comparing colors see
+    /// what was on screen. An image that already has a profile, for
+    /// example because the configuration asked for a specific color space,
+    /// is returned unchanged.
+    ///
+    /// # Errors
+    /// Returns an error if the capture fails or the display's profile does
+    /// not fit the captured image.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use screencapturekit::screenshot_manager::{CGImageExt, ImageFormat, SCScreenshotManager};
+    /// use screencapturekit::stream::{content_filter::SCContentFilter, configuration::SCStreamConfiguration};
+    /// use screencapturekit::shareable_content::SCShareableContent;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let content = SCShareableContent::get()?;
+    /// let display = &content.displays()[0];
+    /// let filter = SCContentFilter::for_display(display).build();
+    /// let config = SCStreamConfiguration::new();
+    ///
+    /// let image = SCScreenshotManager::capture_image_with_display_profile(&filter, &config, display)?;
+    /// image.save("screenshot.heic", ImageFormat::Heic(0.9))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn capture_image_with_display_profile(
+        content_filter: &SCContentFilter,
+        configuration: &SCStreamConfiguration,
+        display: &SCDisplay,
+    ) -> Result<CGImage, SCError> {
+        let image = Self::capture_image(content_filter, configuration)?;
+        if image.icc_profile().is_some() {
+            return Ok(image);
+        }
+        match display.icc_profile() {
+            Some(profile) => image.with_icc_profile(&profile),
+            None => Ok(image),
+        }
+    }
+
     /// Capture a single screenshot as a `CMSampleBuffer`
     ///
     /// Returns the sample buffer for advanced processing.
@@ -613,6 +1404,50 @@ impl SCScreenshotManager {
         completion.wait().map_err(SCError::ScreenshotError)
     }
 
+    /// Capture a single screenshot as both a `CGImage` and a `CMSampleBuffer`
+    ///
+    /// One capture gives the image along with the sample's attachments, such
+    /// as [`scale_factor`](Screenshot::scale_factor) and
+    /// [`content_rect`](Screenshot::content_rect), without calling
+    /// [`capture_image`](Self::capture_image) and
+    /// [`capture_sample_buffer`](Self::capture_sample_buffer) separately,
+    /// which would capture the screen twice.
+    ///
+    /// # Errors
+    /// Returns an error if the capture fails; see
+    /// [`capture_sample_buffer`](Self::capture_sample_buffer). Returns
+    /// [`SCError::InvalidBuffer`] if the sample holds no image.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use screencapturekit::screenshot_manager::SCScreenshotManager;
+    /// use screencapturekit::stream::{content_filter::SCContentFilter, configuration::SCStreamConfiguration};
+    /// use screencapturekit::shareable_content::SCShareableContent;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let content = SCShareableContent::get()?;
+    /// let display = &content.displays()[0];
+    /// let filter = SCContentFilter::for_display(display).build();
+    /// let config = SCStreamConfiguration::new();
+    ///
+    /// let shot = SCScreenshotManager::capture_both(&filter, &config)?;
+    /// println!(
+    ///     "{}x{} at {:?}x",
+    ///     shot.image().width(),
+    ///     shot.image().height(),
+    ///     shot.scale_factor()
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn capture_both(
+        content_filter: &SCContentFilter,
+        configuration: &SCStreamConfiguration,
+    ) -> Result<Screenshot, SCError> {
+        Screenshot::from_sample_buffer(Self::capture_sample_buffer(content_filter, configuration)?)
+    }
+
     /// Capture a screenshot of a specific screen region (macOS 15.2+)
     ///
     /// This method captures the content within the specified rectangle,
@@ -678,7 +1513,7 @@ impl SCScreenshotManager {
     ///     let content = SCShareableContent::get().ok()?;
     ///     let displays = content.displays();
     ///     let display = displays.first()?;
-    ///     let filter = SCContentFilter::create().with_display(display).with_excluding_windows(&[]).build();
+    ///     let filter = SCContentFilter::for_display(display).with_excluding_windows(&[]).build();
     ///     let config = SCScreenshotConfiguration::new()
     ///         .with_width(1920)
     ///         .with_height(1080)
@@ -741,23 +1576,317 @@ impl SCScreenshotManager {
     }
 }
 
+// ============================================================================
+// Window snapshots
+// ============================================================================
+
+/// Options for [`SCScreenshotManager::capture_windows`] and
+/// [`SCScreenshotManager::capture_all_windows`]
+///
+/// Every window is captured with a copy of one shared configuration, sized
+/// to the window's frame times [`scale`](Self::with_scale) and shrunk to fit
+/// [`max_size`](Self::with_max_size) if set. Up to
+/// [`max_concurrency`](Self::with_max_concurrency) captures run at a time.
+///
+/// # Examples
+///
+/// ```no_run
+/// use screencapturekit::screenshot_manager::{SCScreenshotManager, WindowCaptureOptions};
+///
+/// # fn example() -> Result<(), screencapturekit::error::SCError> {
+/// // Retina thumbnails no larger than 480x300 for a window switcher
+/// let options = WindowCaptureOptions::new()
+///     .with_scale(2.0)
+///     .with_max_size(480, 300)
+///     .with_max_concurrency(8);
+/// for (window, image) in SCScreenshotManager::capture_all_windows(&options)? {
+///     println!("{:?}: {}x{}", window.title(), image.width(), image.height());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct WindowCaptureOptions {
+    configuration: SCStreamConfiguration,
+    scale: f64,
+    max_size: Option<(u32, u32)>,
+    max_concurrency: usize,
+    all_layers: bool,
+}
+
+impl Default for WindowCaptureOptions {
+    fn default() -> Self {
+        Self {
+            configuration: SCStreamConfiguration::new().with_shows_cursor(false),
+            scale: 1.0,
+            max_size: None,
+            max_concurrency: 4,
+            all_layers: false,
+        }
+    }
+}
+
+impl WindowCaptureOptions {
+    /// One pixel per point, no size limit, four captures at a time, cursor
+    /// hidden
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Base configuration shared by every capture (pixel format, cursor,
+    /// shadows, ...)
+    ///
+    /// Its width and height are replaced per window.
+    #[must_use]
+    pub fn with_configuration(mut self, configuration: &SCStreamConfiguration) -> Self {
+        self.configuration = configuration.deep_copy();
+        self
+    }
+
+    /// Pixels per point, e.g. 2.0 for Retina-sharp images
+    #[must_use]
+    pub fn with_scale(mut self, scale: f64) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Shrink images to fit within `width` x `height` pixels, keeping the
+    /// aspect ratio. Images are never enlarged.
+    #[must_use]
+    pub fn with_max_size(mut self, width: u32, height: u32) -> Self {
+        self.max_size = Some((width, height));
+        self
+    }
+
+    /// How many captures may be in flight at once
+    ///
+    /// `ScreenCaptureKit` serves captures in parallel, but each one holds a
+    /// thread here and a frame in `WindowServer`, so a few at a time is
+    /// usually fastest.
+    #[must_use]
+    pub fn with_max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.max_concurrency = max_concurrency;
+        self
+    }
+
+    /// Also capture windows outside the normal window layer (menu bar
+    /// extras, panels, overlays) in
+    /// [`capture_all_windows`](SCScreenshotManager::capture_all_windows)
+    #[must_use]
+    pub fn with_all_layers(mut self, all_layers: bool) -> Self {
+        self.all_layers = all_layers;
+        self
+    }
+
+    /// Pixels per point
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// Size limit in pixels, if any
+    pub fn max_size(&self) -> Option<(u32, u32)> {
+        self.max_size
+    }
+
+    /// Maximum number of captures in flight
+    pub fn max_concurrency(&self) -> usize {
+        self.max_concurrency
+    }
+
+    /// Whether windows outside the normal layer are included
+    pub fn all_layers(&self) -> bool {
+        self.all_layers
+    }
+
+    /// Pixel size of the image for a window with this frame
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn output_size(&self, frame: CGRect) -> (u32, u32) {
+        let mut width = frame.size.width * self.scale;
+        let mut height = frame.size.height * self.scale;
+        if let Some((max_width, max_height)) = self.max_size {
+            let fit = (f64::from(max_width) / width)
+                .min(f64::from(max_height) / height)
+                .min(1.0);
+            width *= fit;
+            height *= fit;
+        }
+        (
+            width.round().clamp(1.0, f64::from(u32::MAX)) as u32,
+            height.round().clamp(1.0, f64::from(u32::MAX)) as u32,
+        )
+    }
+
+    fn validate(&self) -> Result<(), SCError> {
+        if self.max_concurrency == 0 {
+            return Err(SCError::invalid_config(
+                "max_concurrency must be at least 1",
+            ));
+        }
+        if !(self.scale.is_finite() && self.scale > 0.0) {
+            return Err(SCError::invalid_config(format!(
+                "scale must be positive, got {}",
+                self.scale
+            )));
+        }
+        match self.max_size {
+            Some((0, _)) => Err(SCError::invalid_dimension("max width", 0)),
+            Some((_, 0)) => Err(SCError::invalid_dimension("max height", 0)),
+            _ => Ok(()),
+        }
+    }
+
+    fn capture(&self, window: &crate::shareable_content::SCWindow) -> Result<CGImage, SCError> {
+        let (width, height) = self.output_size(window.frame());
+        let filter = SCContentFilter::for_window(window).build();
+        let config = self
+            .configuration
+            .deep_copy()
+            .with_width(width)
+            .with_height(height);
+        SCScreenshotManager::capture_image(&filter, &config)
+    }
+}
+
+impl SCScreenshotManager {
+    /// Capture every on-screen window, e.g. for an Exposé-style picker
+    ///
+    /// Windows are those [`SCShareableContent`] reports as on screen, in
+    /// its front-to-back order, limited to the normal window layer unless
+    /// [`with_all_layers`](WindowCaptureOptions::with_all_layers) is set.
+    /// Zero-sized windows are skipped. See
+    /// [`capture_windows`](Self::capture_windows) for how failures are
+    /// handled.
+    ///
+    /// [`SCShareableContent`]: crate::shareable_content::SCShareableContent
+    ///
+    /// # Errors
+    ///
+    /// Returns the error from [`SCShareableContent::get`], or the errors
+    /// listed on [`capture_windows`](Self::capture_windows).
+    ///
+    /// [`SCShareableContent::get`]: crate::shareable_content::SCShareableContent::get
+    pub fn capture_all_windows(
+        options: &WindowCaptureOptions,
+    ) -> Result<Vec<(crate::shareable_content::SCWindow, CGImage)>, SCError> {
+        options.validate()?;
+        let content = crate::shareable_content::SCShareableContent::get()?;
+        let windows: Vec<_> = content
+            .windows()
+            .into_iter()
+            .filter(|w| w.is_on_screen() && (options.all_layers || w.window_layer() == 0))
+            .filter(|w| {
+                let size = w.frame().size;
+                size.width >= 1.0 && size.height >= 1.0
+            })
+            .collect();
+        Self::capture_windows(&windows, options)
+    }
+
+    /// Capture each of `windows`, several at a time
+    ///
+    /// Returns `(window, image)` pairs in the order of `windows`. A window
+    /// that can't be captured, usually because it closed after it was
+    /// listed, is left out rather than failing the batch.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidConfiguration`] or
+    /// [`SCError::InvalidDimension`] for invalid options, and the first
+    /// capture error if every window failed (e.g. screen recording
+    /// permission is missing).
+    pub fn capture_windows(
+        windows: &[crate::shareable_content::SCWindow],
+        options: &WindowCaptureOptions,
+    ) -> Result<Vec<(crate::shareable_content::SCWindow, CGImage)>, SCError> {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::{Mutex, PoisonError};
+
+        options.validate()?;
+        let next = AtomicUsize::new(0);
+        let results: Mutex<Vec<Option<Result<CGImage, SCError>>>> =
+            Mutex::new(windows.iter().map(|_| None).collect());
+
+        std::thread::scope(|scope| {
+            for _ in 0..options.max_concurrency.min(windows.len()) {
+                scope.spawn(|| loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(window) = windows.get(index) else {
+                        break;
+                    };
+                    let result = options.capture(window);
+                    results.lock().unwrap_or_else(PoisonError::into_inner)[index] = Some(result);
+                });
+            }
+        });
+
+        let mut first_error = None;
+        let mut captured = Vec::with_capacity(windows.len());
+        let results = results.into_inner().unwrap_or_else(PoisonError::into_inner);
+        for (window, result) in windows.iter().zip(results) {
+            match result {
+                Some(Ok(image)) => captured.push((window.clone(), image)),
+                Some(Err(error)) => {
+                    first_error.get_or_insert(error);
+                }
+                None => {}
+            }
+        }
+        match first_error {
+            Some(error) if captured.is_empty() => Err(error),
+            _ => Ok(captured),
+        }
+    }
+}
+
 // ============================================================================
 // SCScreenshotConfiguration (macOS 26.0+)
 // ============================================================================
 
 /// Display intent for screenshot rendering (macOS 26.0+)
+///
+/// Decides which display's color characteristics the screenshot is
+/// rendered for. This matters most for HDR output, where the available
+/// headroom differs between displays.
 #[cfg(feature = "macos_26_0")]
 #[repr(i32)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum SCScreenshotDisplayIntent {
-    /// Render on the canonical display
+    /// Render for a reference display, independent of the hardware the
+    /// capture runs on. Use this for images that will be viewed elsewhere.
     #[default]
     Canonical = 0,
-    /// Render on the local display
+    /// Render for the display the content is on, matching what the user
+    /// currently sees.
     Local = 1,
 }
 
+#[cfg(feature = "macos_26_0")]
+impl SCScreenshotDisplayIntent {
+    /// Convert from the raw `SCScreenshotDisplayIntent` value.
+    #[must_use]
+    pub const fn from_raw(value: i32) -> Option<Self> {
+        match value {
+            0 => Some(Self::Canonical),
+            1 => Some(Self::Local),
+            _ => None,
+        }
+    }
+}
+
 /// Dynamic range for screenshot output (macOS 26.0+)
+///
+/// Selects which images [`SCScreenshotOutput`] carries:
+///
+/// | Value | [`sdr_image`](SCScreenshotOutput::sdr_image) | [`hdr_image`](SCScreenshotOutput::hdr_image) |
+/// |---|---|---|
+/// | `SDR` | yes | no |
+/// | `HDR` | no | yes |
+/// | `BothSDRAndHDR` | yes | yes |
+///
+/// HDR images keep extended-range values and the color space that
+/// describes them; SDR images are tone mapped. Content without HDR still
+/// produces an HDR image, it just does not exceed SDR white.
 #[cfg(feature = "macos_26_0")]
 #[repr(i32)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
@@ -771,14 +1900,45 @@ pub enum SCScreenshotDynamicRange {
     BothSDRAndHDR = 2,
 }
 
+#[cfg(feature = "macos_26_0")]
+impl SCScreenshotDynamicRange {
+    /// Convert from the raw `SCScreenshotDynamicRange` value.
+    #[must_use]
+    pub const fn from_raw(value: i32) -> Option<Self> {
+        match value {
+            0 => Some(Self::SDR),
+            1 => Some(Self::HDR),
+            2 => Some(Self::BothSDRAndHDR),
+            _ => None,
+        }
+    }
+
+    /// Whether the output will contain a tone-mapped SDR image.
+    #[must_use]
+    pub const fn includes_sdr(self) -> bool {
+        matches!(self, Self::SDR | Self::BothSDRAndHDR)
+    }
+
+    /// Whether the output will contain an HDR image.
+    #[must_use]
+    pub const fn includes_hdr(self) -> bool {
+        matches!(self, Self::HDR | Self::BothSDRAndHDR)
+    }
+}
+
 /// Configuration for advanced screenshot capture (macOS 26.0+)
 ///
 /// Provides fine-grained control over screenshot output including:
 /// - Output dimensions
 /// - Source and destination rectangles
 /// - Shadow and clipping behavior
-/// - HDR/SDR dynamic range
-/// - File output
+/// - HDR/SDR dynamic range and display intent
+/// - Output file and image format
+///
+/// Screenshots taken with this type skip stream-only settings such as
+/// frame rate, queue depth and audio, which
+/// [`SCStreamConfiguration`](crate::stream::configuration::SCStreamConfiguration)
+/// would otherwise carry along unused.
 ///
 /// # Examples
 ///
@@ -922,6 +2082,58 @@ impl SCScreenshotConfiguration {
         self
     }
 
+    /// Get the output width in pixels (`0` means the source size)
+    #[must_use]
+    pub fn width(&self) -> usize {
+        let width = unsafe { crate::ffi::sc_screenshot_configuration_get_width(self.ptr) };
+        usize::try_from(width).unwrap_or(0)
+    }
+
+    /// Get the output height in pixels (`0` means the source size)
+    #[must_use]
+    pub fn height(&self) -> usize {
+        let height = unsafe { crate::ffi::sc_screenshot_configuration_get_height(self.ptr) };
+        usize::try_from(height).unwrap_or(0)
+    }
+
+    /// Get whether the cursor is shown
+    #[must_use]
+    pub fn shows_cursor(&self) -> bool {
+        unsafe { crate::ffi::sc_screenshot_configuration_get_shows_cursor(self.ptr) }
+    }
+
+    /// Get whether shadows are ignored
+    #[must_use]
+    pub fn ignore_shadows(&self) -> bool {
+        unsafe { crate::ffi::sc_screenshot_configuration_get_ignore_shadows(self.ptr) }
+    }
+
+    /// Get whether clipping is ignored
+    #[must_use]
+    pub fn ignore_clipping(&self) -> bool {
+        unsafe { crate::ffi::sc_screenshot_configuration_get_ignore_clipping(self.ptr) }
+    }
+
+    /// Get whether child windows are included
+    #[must_use]
+    pub fn include_child_windows(&self) -> bool {
+        unsafe { crate::ffi::sc_screenshot_configuration_get_include_child_windows(self.ptr) }
+    }
+
+    /// Get the display intent
+    #[must_use]
+    pub fn display_intent(&self) -> SCScreenshotDisplayIntent {
+        let raw = unsafe { crate::ffi::sc_screenshot_configuration_get_display_intent(self.ptr) };
+        SCScreenshotDisplayIntent::from_raw(raw).unwrap_or_default()
+    }
+
+    /// Get the dynamic range
+    #[must_use]
+    pub fn dynamic_range(&self) -> SCScreenshotDynamicRange {
+        let raw = unsafe { crate::ffi::sc_screenshot_configuration_get_dynamic_range(self.ptr) };
+        SCScreenshotDynamicRange::from_raw(raw).unwrap_or_default()
+    }
+
     /// Set the output file URL
     ///
     /// If `path` contains an interior NUL byte it cannot be converted to a C
@@ -1021,6 +2233,10 @@ impl SCScreenshotConfiguration {
 impl std::fmt::Debug for SCScreenshotConfiguration {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("SCScreenshotConfiguration")
+            .field("width", &self.width())
+            .field("height", &self.height())
+            .field("dynamic_range", &self.dynamic_range())
+            .field("display_intent", &self.display_intent())
             .field("content_type", &self.content_type())
             .finish_non_exhaustive()
     }
@@ -0,0 +1,69 @@
+//! Process-wide cache for [`SCShareableContent::get_cached`].
+//!
+//! A fresh `SCShareableContent` costs a round trip through the window server
+//! (often 100 ms or more with many windows open), which is too slow to repeat
+//! every time a source picker redraws. The cache keeps the last result of
+//! [`SCShareableContent::get`] together with the time it was fetched.
+//!
+//! A generation counter guards against a race with [`invalidate`]: if the
+//! cache is invalidated while a fetch is in flight, the fetched content is
+//! still returned to its caller but is not stored, so the next caller fetches
+//! again.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use super::SCShareableContent;
+use crate::error::SCError;
+
+struct CachedContent {
+    content: SCShareableContent,
+    fetched_at: Instant,
+}
+
+static CACHE: Mutex<Option<CachedContent>> = Mutex::new(None);
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Return the cached content if it is younger than `ttl`, otherwise fetch it.
+pub fn get(ttl: Duration) -> Result<SCShareableContent, SCError> {
+    let generation = GENERATION.load(Ordering::Acquire);
+    {
+        let cache = CACHE.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(cached) = cache.as_ref() {
+            if cached.fetched_at.elapsed() < ttl {
+                return Ok(cached.content.clone());
+            }
+        }
+    }
+
+    // Fetch without holding the lock: concurrent callers may each fetch once,
+    // but none of them waits behind another's window-server round trip.
+    let content = SCShareableContent::get()?;
+    let mut cache = CACHE.lock().unwrap_or_else(PoisonError::into_inner);
+    if GENERATION.load(Ordering::Acquire) == generation {
+        *cache = Some(CachedContent {
+            content: content.clone(),
+            fetched_at: Instant::now(),
+        });
+    }
+    drop(cache);
+    Ok(content)
+}
+
+/// Drop the cached content so the next [`get`] fetches a fresh copy.
+pub fn invalidate() {
+    GENERATION.fetch_add(1, Ordering::AcqRel);
+    // Take the value out so it is released after the lock.
+    let stale = CACHE.lock().unwrap_or_else(PoisonError::into_inner).take();
+    drop(stale);
+}
+
+/// Age of the cached content, if any.
+pub fn age() -> Option<Duration> {
+    CACHE
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .as_ref()
+        .map(|cached| cached.fetched_at.elapsed())
+}
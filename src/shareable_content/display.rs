@@ -130,6 +130,26 @@ impl SCDisplay {
             crate::ffi::sc_display_get_width(self.0) as u32
         }
     }
+
+    /// The ICC profile of the display's color space, as selected in System
+    /// Settings > Displays.
+    ///
+    /// Screenshots of this display show its colors correctly in
+    /// color-managed apps when tagged with this profile; see
+    /// `SCScreenshotManager::capture_image_with_display_profile`. Returns
+    /// `None` if the display is gone or its color space has no ICC form.
+    pub fn icc_profile(&self) -> Option<Vec<u8>> {
+        let mut len = 0;
+        let ptr = unsafe { crate::ffi::sc_display_copy_icc_profile(self.display_id(), &mut len) };
+        if ptr.is_null() {
+            return None;
+        }
+        // SAFETY: the bridge returned a malloc'd buffer of `len` bytes, copied
+        // out before it is freed.
+        let profile = unsafe { std::slice::from_raw_parts(ptr, len) }.to_vec();
+        unsafe { crate::ffi::cgimage_encoded_data_free(ptr) };
+        Some(profile)
+    }
 }
 
 crate::utils::retained::sc_retained!(
@@ -9,6 +9,8 @@
 //! - [`SCDisplay`] - A physical or virtual display that can be captured
 //! - [`SCWindow`] - A window that can be captured
 //! - [`SCRunningApplication`] - A running application whose windows can be captured
+//! - [`WindowObserver`](window_observer::WindowObserver) - Reports when a window is renamed, moved, resized or closed
+//! - [`NotificationWatcher`](notification_watcher::NotificationWatcher) - Reports notification banners over captured content and can mask them
 //!
 //! ## Workflow
 //!
@@ -66,11 +68,21 @@
 //! # Ok(())
 //! # }
 //! ```
+//!
+//! ## Cached Lookups
+//!
+//! Fetching content takes a window-server round trip. UIs that refresh a
+//! source list often can use [`SCShareableContent::get_cached`] and call
+//! [`SCShareableContent::invalidate_cache`] when they learn that windows or
+//! displays changed.
 
+mod cache;
 pub mod display;
+pub mod notification_watcher;
 pub mod running_application;
 pub mod snapshot;
 pub mod window;
+pub mod window_observer;
 pub use display::SCDisplay;
 pub use running_application::SCRunningApplication;
 pub use snapshot::{ApplicationSnapshot, ContentSnapshot, DisplaySnapshot, WindowSnapshot};
@@ -165,6 +177,51 @@ impl SCShareableContent {
         SCShareableContentOptions::default().get()
     }
 
+    /// Get shareable content, reusing a copy fetched within the last `ttl`
+    ///
+    /// The cache is shared by the whole process and holds the result of
+    /// [`get`](Self::get) (default options). Content older than `ttl` is
+    /// fetched again; a `ttl` of zero always fetches. Windows opened or
+    /// closed since the cached copy was taken are not reflected until it
+    /// expires or [`invalidate_cache`](Self::invalidate_cache) is called.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use screencapturekit::shareable_content::SCShareableContent;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// // Cheap enough to call on every redraw of a source picker.
+    /// let content = SCShareableContent::get_cached(Duration::from_secs(2))?;
+    /// println!("{} windows", content.windows().len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache is stale and fetching fails.
+    pub fn get_cached(ttl: std::time::Duration) -> Result<Self, SCError> {
+        cache::get(ttl)
+    }
+
+    /// Discard the content cached by [`get_cached`](Self::get_cached)
+    ///
+    /// Call this when windows or displays are known to have changed, for
+    /// example from a `NSWorkspace` launch/terminate notification or a
+    /// display reconfiguration callback.
+    pub fn invalidate_cache() {
+        cache::invalidate();
+    }
+
+    /// How long ago the cached content was fetched, or `None` if nothing is
+    /// cached
+    #[must_use]
+    pub fn cache_age() -> Option<std::time::Duration> {
+        cache::age()
+    }
+
     /// Create options builder for customizing shareable content retrieval
     ///
     /// # Examples
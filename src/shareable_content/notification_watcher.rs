@@ -0,0 +1,327 @@
+//! Notice notification banners over captured content
+//!
+//! macOS draws notification banners and alerts above every app, so a
+//! display capture records them: a tutorial can end up showing a private
+//! message. [`NotificationWatcher`] polls the window server for windows of
+//! the Notification Center agent on a background thread and reports each
+//! banner that appears, moves or goes away as a [`NotificationEvent`].
+//!
+//! [`NotificationWatcher::start_masking`] also keeps an
+//! [`SCRegionMasker`](crate::region_mask::SCRegionMasker) blacking out the
+//! banners while they are on screen. Polling sees a banner up to one
+//! interval after it appears, so a frame or two may show it sliding in;
+//! when nothing may leak, mask
+//! [`RegionMask::notification_area`](crate::region_mask::RegionMask::notification_area)
+//! for the whole recording instead.
+//!
+//! Like [`WindowObserver`](super::window_observer::WindowObserver), this
+//! reads the window list directly and needs no extra permission. Turning
+//! on Focus or Do Not Disturb before recording stops banners altogether.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use screencapturekit::region_mask::{RegionMask, SCRegionMasker};
+//! use screencapturekit::shareable_content::notification_watcher::{
+//!     NotificationEvent, NotificationWatcher,
+//! };
+//! use std::sync::Arc;
+//!
+//! # fn example() -> Result<(), screencapturekit::error::SCError> {
+//! let masker = Arc::new(SCRegionMasker::new(RegionMask::default())?);
+//! let watcher = NotificationWatcher::start_masking(
+//!     Arc::clone(&masker),
+//!     NotificationWatcher::DEFAULT_INTERVAL,
+//!     |event| {
+//!         if let NotificationEvent::Appeared(banner) = event {
+//!             println!("notification at {:?} masked", banner.frame);
+//!         }
+//!     },
+//! )?;
+//! // Wrap the stream's output handler in `MaskedOutput::new(masker, ...)`.
+//! # drop(watcher);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+use crate::cg::{CGRect, CGRectExt};
+use crate::error::SCError;
+use crate::region_mask::SCRegionMasker;
+use crate::utils::poller::{Poller, PollerContext};
+
+/// Space masked around a banner, in points, for its shadow and the part of
+/// its slide-in animation polling misses.
+const BANNER_MARGIN: f64 = 16.0;
+
+/// A notification banner or alert on screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotificationBanner {
+    /// Window server ID of the banner's window
+    pub window_id: u32,
+    /// The window's frame in global points
+    pub frame: CGRect,
+}
+
+impl NotificationBanner {
+    /// The banners on screen now, front to back
+    ///
+    /// **Not cached**; each call reads the window list.
+    #[must_use]
+    pub fn visible() -> Vec<Self> {
+        let count = unsafe {
+            crate::ffi::sc_notification_windows_copy(std::ptr::null_mut(), std::ptr::null_mut(), 0)
+        };
+        let Ok(capacity) = usize::try_from(count) else {
+            return Vec::new();
+        };
+        if capacity == 0 {
+            return Vec::new();
+        }
+        let mut ids = vec![0u32; capacity];
+        let mut rects = vec![0.0f64; capacity * 4];
+        let count = unsafe {
+            crate::ffi::sc_notification_windows_copy(ids.as_mut_ptr(), rects.as_mut_ptr(), count)
+        };
+        // More banners may have appeared between the calls; keep the ones
+        // that fit.
+        let count = usize::try_from(count).unwrap_or(0).min(capacity);
+        ids.iter()
+            .zip(rects.chunks_exact(4))
+            .take(count)
+            .map(|(id, rect)| Self {
+                window_id: *id,
+                frame: CGRect::new(rect[0], rect[1], rect[2], rect[3]),
+            })
+            .collect()
+    }
+
+    /// The region to black out for this banner: its frame with a margin,
+    /// stretched right by its own width to cover it sliding in and out
+    #[must_use]
+    pub fn mask_region(&self) -> CGRect {
+        let frame = self.frame.standardized();
+        CGRect::new(
+            frame.origin.x - BANNER_MARGIN,
+            frame.origin.y - BANNER_MARGIN,
+            frame.size.width.mul_add(2.0, BANNER_MARGIN * 2.0),
+            BANNER_MARGIN.mul_add(2.0, frame.size.height),
+        )
+    }
+}
+
+/// A change to the notification banners on screen
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NotificationEvent {
+    /// A banner appeared
+    Appeared(NotificationBanner),
+    /// A banner moved or changed size, e.g. when another one stacked above
+    /// it or it expanded to show actions
+    Moved {
+        window_id: u32,
+        old: CGRect,
+        new: CGRect,
+    },
+    /// A banner went away
+    Disappeared(NotificationBanner),
+}
+
+impl NotificationEvent {
+    /// ID of the banner's window
+    #[must_use]
+    pub const fn window_id(&self) -> u32 {
+        match self {
+            Self::Appeared(banner) | Self::Disappeared(banner) => banner.window_id,
+            Self::Moved { window_id, .. } => *window_id,
+        }
+    }
+
+    /// The events that turn the banners in `old` into those in `new`:
+    /// disappearances first, then appearances and moves in `new`'s order
+    #[must_use]
+    pub fn diff(old: &[NotificationBanner], new: &[NotificationBanner]) -> Vec<Self> {
+        let mut events = Vec::new();
+        for banner in old {
+            if !new.iter().any(|next| next.window_id == banner.window_id) {
+                events.push(Self::Disappeared(*banner));
+            }
+        }
+        for banner in new {
+            match old
+                .iter()
+                .find(|previous| previous.window_id == banner.window_id)
+            {
+                None => events.push(Self::Appeared(*banner)),
+                Some(previous) if previous.frame != banner.frame => {
+                    events.push(Self::Moved {
+                        window_id: banner.window_id,
+                        old: previous.frame,
+                        new: banner.frame,
+                    });
+                }
+                Some(_) => {}
+            }
+        }
+        events
+    }
+}
+
+struct WatcherShared {
+    banners: Mutex<Vec<NotificationBanner>>,
+    events: AtomicU64,
+}
+
+/// Reports notification banners as they come and go
+///
+/// The handler runs on the watcher's own thread. Banners already on screen
+/// when the watcher starts are reported as appearing. Stops polling when
+/// dropped.
+pub struct NotificationWatcher {
+    shared: Arc<WatcherShared>,
+    _poller: Poller,
+}
+
+impl NotificationWatcher {
+    /// A poll interval short enough to catch banners as they slide in
+    pub const DEFAULT_INTERVAL: Duration = Duration::from_millis(100);
+
+    /// Start polling every `interval` and call `handler` for each change
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InternalError`] if the operating system refuses
+    /// to spawn the polling thread.
+    pub fn start<F>(interval: Duration, handler: F) -> Result<Self, SCError>
+    where
+        F: FnMut(NotificationEvent) + Send + 'static,
+    {
+        Self::spawn(interval, None, handler)
+    }
+
+    /// Like [`start`](Self::start), also keeping `masker`'s
+    /// [extra regions](SCRegionMasker::set_extra_regions) on the banners
+    ///
+    /// The extra regions are cleared when the watcher is dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InternalError`] if the operating system refuses
+    /// to spawn the polling thread.
+    pub fn start_masking<F>(
+        masker: Arc<SCRegionMasker>,
+        interval: Duration,
+        handler: F,
+    ) -> Result<Self, SCError>
+    where
+        F: FnMut(NotificationEvent) + Send + 'static,
+    {
+        Self::spawn(interval, Some(masker), handler)
+    }
+
+    fn spawn<F>(
+        interval: Duration,
+        masker: Option<Arc<SCRegionMasker>>,
+        handler: F,
+    ) -> Result<Self, SCError>
+    where
+        F: FnMut(NotificationEvent) + Send + 'static,
+    {
+        let shared = Arc::new(WatcherShared {
+            banners: Mutex::new(Vec::new()),
+            events: AtomicU64::new(0),
+        });
+
+        let poller = {
+            let shared = Arc::clone(&shared);
+            Poller::spawn("notification-watcher", move |context| {
+                watch(context, &shared, interval, masker.as_deref(), handler);
+            })?
+        };
+
+        Ok(Self {
+            shared,
+            _poller: poller,
+        })
+    }
+
+    /// The banners on screen as of the last poll
+    #[must_use]
+    pub fn banners(&self) -> Vec<NotificationBanner> {
+        self.shared
+            .banners
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Whether a banner was on screen at the last poll
+    #[must_use]
+    pub fn is_showing(&self) -> bool {
+        !self
+            .shared
+            .banners
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .is_empty()
+    }
+
+    /// Number of events reported so far
+    #[must_use]
+    pub fn event_count(&self) -> u64 {
+        self.shared.events.load(Ordering::Relaxed)
+    }
+}
+
+fn watch<F>(
+    context: &PollerContext,
+    shared: &WatcherShared,
+    interval: Duration,
+    masker: Option<&SCRegionMasker>,
+    mut handler: F,
+) where
+    F: FnMut(NotificationEvent),
+{
+    loop {
+        let next = NotificationBanner::visible();
+        let events = {
+            let mut current = shared
+                .banners
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            let events = NotificationEvent::diff(&current, &next);
+            current.clone_from(&next);
+            events
+        };
+        if !events.is_empty() {
+            if let Some(masker) = masker {
+                // Window frames always cover some area, so this can't fail.
+                let _ = masker
+                    .set_extra_regions(next.iter().map(NotificationBanner::mask_region).collect());
+            }
+            for event in events {
+                shared.events.fetch_add(1, Ordering::Relaxed);
+                handler(event);
+            }
+        }
+
+        if context.sleep(interval) {
+            if let Some(masker) = masker {
+                let _ = masker.set_extra_regions(Vec::new());
+            }
+            return;
+        }
+    }
+}
+
+impl fmt::Debug for NotificationWatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NotificationWatcher")
+            .field("banners", &self.banners())
+            .field("event_count", &self.event_count())
+            .finish_non_exhaustive()
+    }
+}
@@ -3,6 +3,7 @@ use crate::utils::ffi_string::ffi_string_owned;
 use core::fmt;
 use std::ffi::c_void;
 
+use super::window_observer::{WindowEvent, WindowObserver};
 use super::SCRunningApplication;
 
 /// Wrapper around `SCWindow` from `ScreenCaptureKit`
@@ -118,6 +119,37 @@ impl SCWindow {
     pub fn is_active(&self) -> bool {
         unsafe { crate::ffi::sc_window_is_active(self.0) }
     }
+
+    /// Watch this window for title changes, moves, resizes and closing
+    ///
+    /// Polls every [`WindowObserver::DEFAULT_INTERVAL`]; use
+    /// [`WindowObserver::start`] to choose the interval. `handler` runs on
+    /// the observer's thread until the window closes or the returned
+    /// observer is dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InternalError`](crate::error::SCError::InternalError)
+    /// if the observer thread cannot be spawned.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use screencapturekit::shareable_content::SCShareableContent;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let content = SCShareableContent::get()?;
+    /// let window = &content.windows()[0];
+    /// let _observer = window.observe(|event| println!("{event:?}"))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn observe<F>(&self, handler: F) -> Result<WindowObserver, crate::error::SCError>
+    where
+        F: FnMut(WindowEvent) + Send + 'static,
+    {
+        WindowObserver::start(self.window_id(), WindowObserver::DEFAULT_INTERVAL, handler)
+    }
 }
 
 crate::utils::retained::sc_retained!(
@@ -0,0 +1,226 @@
+//! Follow a window's title, frame and lifetime
+//!
+//! [`SCWindow`](super::SCWindow) is a snapshot: its `title()` and `frame()`
+//! don't change after the content was fetched. Capture UIs that label a
+//! window or draw its outline want to hear when the user renames, moves,
+//! resizes or closes it. [`WindowObserver`] polls the window server for one
+//! window on a background thread and reports each change as a
+//! [`WindowEvent`].
+//!
+//! Polling reads the window list directly rather than fetching shareable
+//! content, so a short interval is cheap, and unlike Accessibility
+//! observers it needs no extra permission. Titles are only visible with
+//! screen recording permission; without it titles stay `None` and only
+//! frame changes and closing are reported.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use screencapturekit::shareable_content::window_observer::WindowEvent;
+//! use screencapturekit::shareable_content::SCShareableContent;
+//!
+//! # fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let content = SCShareableContent::get()?;
+//! let window = &content.windows()[0];
+//!
+//! let observer = window.observe(|event| match event {
+//!     WindowEvent::TitleChanged { new, .. } => println!("now titled {new:?}"),
+//!     WindowEvent::FrameChanged { new, .. } => println!("now at {new:?}"),
+//!     WindowEvent::Closed => println!("closed"),
+//! })?;
+//! # drop(observer);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::cg::CGRect;
+use crate::error::SCError;
+use crate::utils::ffi_string::ffi_string_owned;
+use crate::utils::poller::{Poller, PollerContext};
+
+/// A change to an observed window
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WindowEvent {
+    /// The window's title changed
+    TitleChanged {
+        old: Option<String>,
+        new: Option<String>,
+    },
+    /// The window moved, was resized, or both
+    FrameChanged { old: CGRect, new: CGRect },
+    /// The window no longer exists; no further events follow
+    Closed,
+}
+
+impl WindowEvent {
+    /// Whether this is a frame change that moved the window's origin
+    #[must_use]
+    pub fn is_move(&self) -> bool {
+        matches!(self, Self::FrameChanged { old, new } if old.origin != new.origin)
+    }
+
+    /// Whether this is a frame change that changed the window's size
+    #[must_use]
+    pub fn is_resize(&self) -> bool {
+        matches!(self, Self::FrameChanged { old, new } if old.size != new.size)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct WindowState {
+    frame: CGRect,
+    title: Option<String>,
+}
+
+fn query(window_id: u32) -> Option<WindowState> {
+    let mut x = 0.0;
+    let mut y = 0.0;
+    let mut width = 0.0;
+    let mut height = 0.0;
+    let mut title: *mut i8 = std::ptr::null_mut();
+    // SAFETY: every out-pointer refers to a live local; the bridge writes
+    // them before returning and hands over ownership of `title`.
+    let found = unsafe {
+        crate::ffi::sc_window_copy_live_info(
+            window_id,
+            &mut x,
+            &mut y,
+            &mut width,
+            &mut height,
+            &mut title,
+        )
+    };
+    // SAFETY: `title` is null or a `strdup`ed string we now own.
+    let title = unsafe { ffi_string_owned(|| title) };
+    found.then(|| WindowState {
+        frame: CGRect::new(x, y, width, height),
+        title,
+    })
+}
+
+struct ObserverShared {
+    events: AtomicU64,
+    closed: AtomicBool,
+}
+
+/// Reports title, frame and close events for one window
+///
+/// Created by [`SCWindow::observe`](super::SCWindow::observe) or
+/// [`WindowObserver::start`]. The handler runs on the observer's own thread.
+/// Stops polling when dropped, or after reporting [`WindowEvent::Closed`].
+pub struct WindowObserver {
+    window_id: u32,
+    shared: Arc<ObserverShared>,
+    _poller: Poller,
+}
+
+impl WindowObserver {
+    /// How often [`SCWindow::observe`](super::SCWindow::observe) polls
+    pub const DEFAULT_INTERVAL: Duration = Duration::from_millis(250);
+
+    /// Start polling the window with `window_id` every `interval` and call
+    /// `handler` for each change
+    ///
+    /// The window's state when the observer starts is the baseline; only
+    /// later changes are reported. If the window doesn't exist,
+    /// [`WindowEvent::Closed`] is reported after the first interval.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InternalError`] if the operating system refuses
+    /// to spawn the polling thread.
+    pub fn start<F>(window_id: u32, interval: Duration, handler: F) -> Result<Self, SCError>
+    where
+        F: FnMut(WindowEvent) + Send + 'static,
+    {
+        let shared = Arc::new(ObserverShared {
+            events: AtomicU64::new(0),
+            closed: AtomicBool::new(false),
+        });
+
+        let poller = {
+            let shared = Arc::clone(&shared);
+            Poller::spawn("window-observer", move |context| {
+                observe(context, &shared, window_id, interval, handler);
+            })?
+        };
+
+        Ok(Self {
+            window_id,
+            shared,
+            _poller: poller,
+        })
+    }
+
+    /// ID of the observed window
+    #[must_use]
+    pub const fn window_id(&self) -> u32 {
+        self.window_id
+    }
+
+    /// Number of events reported so far
+    #[must_use]
+    pub fn event_count(&self) -> u64 {
+        self.shared.events.load(Ordering::Relaxed)
+    }
+
+    /// Whether the window has closed and the observer stopped
+    #[must_use]
+    pub fn is_closed(&self) -> bool {
+        self.shared.closed.load(Ordering::Acquire)
+    }
+}
+
+fn observe<F>(
+    context: &PollerContext,
+    shared: &ObserverShared,
+    window_id: u32,
+    interval: Duration,
+    mut handler: F,
+) where
+    F: FnMut(WindowEvent),
+{
+    let mut current = query(window_id);
+    let mut emit = |event| {
+        shared.events.fetch_add(1, Ordering::Relaxed);
+        handler(event);
+    };
+
+    while !context.sleep(interval) {
+        let Some(next) = query(window_id) else {
+            shared.closed.store(true, Ordering::Release);
+            emit(WindowEvent::Closed);
+            return;
+        };
+        if let Some(previous) = current.as_ref() {
+            if next.title != previous.title {
+                emit(WindowEvent::TitleChanged {
+                    old: previous.title.clone(),
+                    new: next.title.clone(),
+                });
+            }
+            if next.frame != previous.frame {
+                emit(WindowEvent::FrameChanged {
+                    old: previous.frame,
+                    new: next.frame,
+                });
+            }
+        }
+        current = Some(next);
+    }
+}
+
+impl fmt::Debug for WindowObserver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WindowObserver")
+            .field("window_id", &self.window_id)
+            .field("event_count", &self.event_count())
+            .field("closed", &self.is_closed())
+            .finish_non_exhaustive()
+    }
+}
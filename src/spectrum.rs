@@ -0,0 +1,381 @@
+//! Audio spectrum analysis for visualizers
+//!
+//! Requires the `spectrum` feature.
+//!
+//! [`SpectrumAnalyzer`] keeps the most recent `fft_size` samples of captured
+//! audio, mixed down to mono, and turns them into a [`Spectrum`] with a
+//! radix-2 FFT: window, transform, then magnitudes in dBFS, where a
+//! full-scale sine reads 0 dB. [`Spectrum::bands`] groups the bins into
+//! logarithmically spaced bands, the usual input for a bar visualizer.
+//!
+//! Everything is plain Rust with buffers allocated up front, so analysing a
+//! frame's worth of audio allocates only the returned spectrum.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use screencapturekit::cm::CMSampleBuffer;
+//! use screencapturekit::spectrum::{SpectrumAnalyzer, WindowFunction};
+//!
+//! let mut analyzer = SpectrumAnalyzer::new(2048, 48_000)
+//!     .unwrap()
+//!     .with_window(WindowFunction::Hann);
+//!
+//! let mut on_audio = |sample: &CMSampleBuffer| {
+//!     analyzer.push(sample).unwrap();
+//!     let spectrum = analyzer.spectrum();
+//!     // 32 bars from 40 Hz to 16 kHz, each 0.0..=1.0
+//!     let bars: Vec<f32> = spectrum
+//!         .bands(32, 40.0, 16_000.0)
+//!         .into_iter()
+//!         .map(|db| spectrum.normalize(db))
+//!         .collect();
+//! #   let _ = bars;
+//! };
+//! # let _ = &mut on_audio;
+//! ```
+
+#![allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+
+use std::f64::consts::TAU;
+
+use crate::cm::{AudioChunk, AudioChunkLayout, CMSampleBuffer};
+use crate::error::SCError;
+
+/// Window applied to each block before the FFT
+///
+/// Windowing tapers the block's edges so a tone between two bins doesn't
+/// smear across the whole spectrum.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum WindowFunction {
+    /// No tapering: the sharpest peaks, and the most leakage
+    Rectangular,
+    /// A good default for music and speech
+    #[default]
+    Hann,
+    /// Like Hann with a lower first side lobe
+    Hamming,
+    /// The least leakage, with wider peaks
+    Blackman,
+}
+
+impl WindowFunction {
+    fn coefficient(self, index: usize, size: usize) -> f64 {
+        let phase = TAU * index as f64 / size as f64;
+        match self {
+            Self::Rectangular => 1.0,
+            Self::Hann => 0.5f64.mul_add(-phase.cos(), 0.5),
+            Self::Hamming => 0.46f64.mul_add(-phase.cos(), 0.54),
+            Self::Blackman => {
+                0.08f64.mul_add((2.0 * phase).cos(), 0.5f64.mul_add(-phase.cos(), 0.42))
+            }
+        }
+    }
+}
+
+/// Magnitudes of one analysed block
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spectrum {
+    magnitudes_db: Vec<f32>,
+    bin_width: f32,
+    floor_db: f32,
+}
+
+impl Spectrum {
+    /// Magnitude of each bin in dBFS, from 0 Hz up to the Nyquist frequency
+    /// (`fft_size / 2 + 1` bins), never below the analyzer's floor
+    #[must_use]
+    pub fn magnitudes_db(&self) -> &[f32] {
+        &self.magnitudes_db
+    }
+
+    /// Width of one bin in Hz
+    #[must_use]
+    pub const fn bin_width(&self) -> f32 {
+        self.bin_width
+    }
+
+    /// Centre frequency of bin `index` in Hz
+    #[must_use]
+    pub fn bin_frequency(&self, index: usize) -> f32 {
+        index as f32 * self.bin_width
+    }
+
+    /// The loudest bin above 0 Hz, as `(frequency in Hz, level in dB)`
+    #[must_use]
+    pub fn peak(&self) -> Option<(f32, f32)> {
+        self.magnitudes_db
+            .iter()
+            .enumerate()
+            .skip(1)
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .map(|(index, &db)| (self.bin_frequency(index), db))
+    }
+
+    /// Levels of `count` bands spaced logarithmically from `min_hz` to
+    /// `max_hz`, each the loudest bin it covers, in dB
+    ///
+    /// Bands too narrow to contain a bin take the level of the nearest bin,
+    /// so low bands don't read as silence at small FFT sizes.
+    #[must_use]
+    pub fn bands(&self, count: usize, min_hz: f32, max_hz: f32) -> Vec<f32> {
+        let last = self.magnitudes_db.len().saturating_sub(1);
+        if count == 0 || last == 0 {
+            return vec![self.floor_db; count];
+        }
+        let min_hz = min_hz.max(self.bin_width);
+        let max_hz = max_hz.max(min_hz);
+        let ratio = (max_hz / min_hz).powf(1.0 / count as f32);
+        let bin = |hz: f32| ((hz / self.bin_width).round() as usize).min(last);
+
+        let mut low = min_hz;
+        (0..count)
+            .map(|_| {
+                let high = low * ratio;
+                let (start, end) = (bin(low), bin(high).max(bin(low)));
+                low = high;
+                self.magnitudes_db[start..=end]
+                    .iter()
+                    .copied()
+                    .fold(self.floor_db, f32::max)
+            })
+            .collect()
+    }
+
+    /// Map a level in dB onto `0.0..=1.0`, from the floor up to 0 dBFS
+    #[must_use]
+    pub fn normalize(&self, db: f32) -> f32 {
+        ((db - self.floor_db) / -self.floor_db).clamp(0.0, 1.0)
+    }
+
+    /// The lowest level reported, in dB
+    #[must_use]
+    pub const fn floor_db(&self) -> f32 {
+        self.floor_db
+    }
+}
+
+/// Sliding-window FFT spectrum analyzer over captured audio
+#[derive(Debug, Clone)]
+pub struct SpectrumAnalyzer {
+    sample_rate: u32,
+    window: WindowFunction,
+    floor_db: f32,
+    /// Window coefficients, with the scale that puts a full-scale sine at
+    /// 0 dB folded in.
+    coefficients: Vec<f32>,
+    /// `(cos, sin)` of `-2πk / fft_size` for `k < fft_size / 2`.
+    twiddles: Vec<(f32, f32)>,
+    bit_reversed: Vec<usize>,
+    /// Most recent samples, oldest first once `history` wraps.
+    history: Vec<f32>,
+    write: usize,
+    real: Vec<f32>,
+    imag: Vec<f32>,
+}
+
+impl SpectrumAnalyzer {
+    /// Default floor of reported levels, in dB
+    pub const DEFAULT_FLOOR_DB: f32 = -100.0;
+
+    /// Create an analyzer over blocks of `fft_size` samples of audio at
+    /// `sample_rate`
+    ///
+    /// Larger blocks resolve frequencies more finely (`sample_rate /
+    /// fft_size` Hz per bin) but react more slowly; 1024 to 4096 suits a
+    /// visualizer at 48 kHz. Uses a Hann window until
+    /// [`with_window`](Self::with_window) picks another.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidConfiguration`] if `fft_size` isn't a power
+    /// of two of at least 4, or `sample_rate` is zero.
+    pub fn new(fft_size: usize, sample_rate: u32) -> Result<Self, SCError> {
+        if fft_size < 4 || !fft_size.is_power_of_two() {
+            return Err(SCError::invalid_config(format!(
+                "FFT size must be a power of two of at least 4, got {fft_size}"
+            )));
+        }
+        if sample_rate == 0 {
+            return Err(SCError::invalid_config("sample rate must not be zero"));
+        }
+        let bits = fft_size.trailing_zeros();
+        let mut analyzer = Self {
+            sample_rate,
+            window: WindowFunction::default(),
+            floor_db: Self::DEFAULT_FLOOR_DB,
+            coefficients: Vec::new(),
+            twiddles: (0..fft_size / 2)
+                .map(|k| {
+                    let angle = -TAU * k as f64 / fft_size as f64;
+                    (angle.cos() as f32, angle.sin() as f32)
+                })
+                .collect(),
+            bit_reversed: (0..fft_size)
+                .map(|i| i.reverse_bits() >> (usize::BITS - bits))
+                .collect(),
+            history: vec![0.0; fft_size],
+            write: 0,
+            real: vec![0.0; fft_size],
+            imag: vec![0.0; fft_size],
+        };
+        analyzer.update_coefficients();
+        Ok(analyzer)
+    }
+
+    /// Use `window` for every block
+    #[must_use]
+    pub fn with_window(mut self, window: WindowFunction) -> Self {
+        self.window = window;
+        self.update_coefficients();
+        self
+    }
+
+    /// Report levels below `floor_db` (a negative dB value) as `floor_db`
+    #[must_use]
+    pub fn with_floor_db(mut self, floor_db: f32) -> Self {
+        self.floor_db = floor_db.min(-f32::EPSILON);
+        self
+    }
+
+    fn update_coefficients(&mut self) {
+        let size = self.history.len();
+        let window: Vec<f64> = (0..size)
+            .map(|i| self.window.coefficient(i, size))
+            .collect();
+        // A sine of amplitude A puts A/2 × Σw into its bin.
+        let scale = 2.0 / window.iter().sum::<f64>();
+        self.coefficients = window.iter().map(|w| (w * scale) as f32).collect();
+    }
+
+    /// Number of samples per analysed block
+    #[must_use]
+    pub fn fft_size(&self) -> usize {
+        self.history.len()
+    }
+
+    /// Sample rate of the analysed audio, in Hz
+    #[must_use]
+    pub const fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// The window applied to each block
+    #[must_use]
+    pub const fn window(&self) -> WindowFunction {
+        self.window
+    }
+
+    /// Append mono samples
+    pub fn push_samples(&mut self, samples: &[f32]) {
+        let size = self.history.len();
+        // Only the last `size` samples can survive.
+        let samples = &samples[samples.len().saturating_sub(size)..];
+        for &sample in samples {
+            self.history[self.write] = sample;
+            self.write = (self.write + 1) & (size - 1);
+        }
+    }
+
+    /// Append a chunk, averaging its channels into mono
+    pub fn push_chunk(&mut self, chunk: &AudioChunk) {
+        let channels = chunk.channel_count().max(1);
+        if channels == 1 {
+            self.push_samples(chunk.samples());
+            return;
+        }
+        let frames = chunk.frame_count();
+        let samples = chunk.samples();
+        let scale = 1.0 / channels as f32;
+        for frame in 0..frames {
+            let sum: f32 = match chunk.layout() {
+                AudioChunkLayout::Planar => (0..channels)
+                    .map(|channel| samples[channel * frames + frame])
+                    .sum(),
+                AudioChunkLayout::Interleaved => samples[frame * channels..(frame + 1) * channels]
+                    .iter()
+                    .sum(),
+            };
+            self.push_samples(&[sum * scale]);
+        }
+    }
+
+    /// Append one captured audio buffer
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidBuffer`] if the buffer holds no audio.
+    pub fn push(&mut self, sample: &CMSampleBuffer) -> Result<(), SCError> {
+        let chunk = AudioChunk::from_sample_buffer(sample, self.sample_rate)?;
+        self.push_chunk(&chunk);
+        Ok(())
+    }
+
+    /// Forget all pushed audio
+    pub fn reset(&mut self) {
+        self.history.fill(0.0);
+        self.write = 0;
+    }
+
+    /// Analyse the most recent `fft_size` samples
+    ///
+    /// Samples not pushed yet count as silence.
+    #[must_use]
+    pub fn spectrum(&mut self) -> Spectrum {
+        let size = self.history.len();
+        for i in 0..size {
+            let sample = self.history[(self.write + i) & (size - 1)];
+            let target = self.bit_reversed[i];
+            self.real[target] = sample * self.coefficients[i];
+            self.imag[target] = 0.0;
+        }
+        self.transform();
+
+        let floor_db = self.floor_db;
+        let magnitudes_db = self.real[..=size / 2]
+            .iter()
+            .zip(&self.imag)
+            .enumerate()
+            .map(|(bin, (re, im))| {
+                // 0 Hz and Nyquist have no mirror image to share energy with.
+                let magnitude = if bin == 0 || bin == size / 2 {
+                    re.hypot(*im) / 2.0
+                } else {
+                    re.hypot(*im)
+                };
+                (20.0 * magnitude.max(f32::MIN_POSITIVE).log10()).max(floor_db)
+            })
+            .collect();
+        Spectrum {
+            magnitudes_db,
+            bin_width: self.sample_rate as f32 / size as f32,
+            floor_db,
+        }
+    }
+
+    /// In-place iterative radix-2 FFT over bit-reversed input
+    fn transform(&mut self) {
+        let size = self.real.len();
+        let mut half = 1;
+        while half < size {
+            let stride = size / (half * 2);
+            for start in (0..size).step_by(half * 2) {
+                for k in 0..half {
+                    let (cos, sin) = self.twiddles[k * stride];
+                    let (even, odd) = (start + k, start + k + half);
+                    let re = self.real[odd].mul_add(cos, -self.imag[odd] * sin);
+                    let im = self.real[odd].mul_add(sin, self.imag[odd] * cos);
+                    self.real[odd] = self.real[even] - re;
+                    self.imag[odd] = self.imag[even] - im;
+                    self.real[even] += re;
+                    self.imag[even] += im;
+                }
+            }
+            half *= 2;
+        }
+    }
+}
@@ -0,0 +1,185 @@
+//! Keep an application-scoped stream pointed at the application
+//!
+//! A filter from
+//! [`SCContentFilter::for_application`](super::content_filter::SCContentFilter::for_application)
+//! names the application's *processes*. When the app quits and is launched
+//! again, or spawns another process under the same bundle identifier, the
+//! filter still refers to the old set and the stream goes blank.
+//! [`ApplicationFollower`] polls shareable content on a background thread and
+//! rebuilds the stream's filter whenever the application's processes or
+//! windows change. Each change it sees also invalidates the
+//! [`SCShareableContent::get_cached`] cache.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use std::time::Duration;
+//! use screencapturekit::prelude::*;
+//! use screencapturekit::stream::application_follower::ApplicationFollower;
+//!
+//! # fn example() -> Result<(), SCError> {
+//! let content = SCShareableContent::get()?;
+//! let display = &content.displays()[0];
+//! let filter = SCContentFilter::for_application(display, "com.apple.Safari")?;
+//! let stream = SCStream::new(&filter, &SCStreamConfiguration::default());
+//! stream.start_capture()?;
+//!
+//! // Re-targets the stream when Safari relaunches or opens windows.
+//! let follower = ApplicationFollower::start(&stream, display, "com.apple.Safari", Duration::from_secs(1))?;
+//! # drop(follower);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::error::SCError;
+use crate::shareable_content::{SCDisplay, SCShareableContent};
+use crate::utils::poller::{Poller, PollerContext};
+
+use super::content_filter::SCContentFilter;
+use super::SCStream;
+
+/// Rebuilds a stream's application filter as the application's processes
+/// and windows come and go.
+///
+/// Stops polling when dropped.
+pub struct ApplicationFollower {
+    bundle_id: String,
+    updates: Arc<AtomicU64>,
+    poller: Poller,
+}
+
+impl ApplicationFollower {
+    /// Start polling every `interval` for changes to the application with
+    /// `bundle_id` and update `stream`'s filter to capture it on `display`.
+    ///
+    /// The application's state when the follower starts is taken as the one
+    /// the stream's current filter was built from.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InternalError`] if the operating system refuses
+    /// to spawn the polling thread.
+    pub fn start(
+        stream: &SCStream,
+        display: &SCDisplay,
+        bundle_id: impl Into<String>,
+        interval: Duration,
+    ) -> Result<Self, SCError> {
+        let bundle_id = bundle_id.into();
+        let updates = Arc::new(AtomicU64::new(0));
+        let poller = {
+            let updates = Arc::clone(&updates);
+            let stream = stream.clone();
+            let display = display.clone();
+            let bundle_id = bundle_id.clone();
+            Poller::spawn("application-follower", move |context| {
+                follow(context, &updates, &stream, &display, &bundle_id, interval);
+            })?
+        };
+
+        Ok(Self {
+            bundle_id,
+            updates,
+            poller,
+        })
+    }
+
+    /// Bundle identifier being followed.
+    #[must_use]
+    pub fn bundle_id(&self) -> &str {
+        &self.bundle_id
+    }
+
+    /// Number of times the stream's filter has been replaced.
+    #[must_use]
+    pub fn update_count(&self) -> u64 {
+        self.updates.load(Ordering::Relaxed)
+    }
+
+    /// The most recent failure to fetch content or update the filter, if the
+    /// last poll failed. Cleared by the next successful poll.
+    #[must_use]
+    pub fn last_error(&self) -> Option<SCError> {
+        self.poller.last_error()
+    }
+}
+
+/// Process IDs and window IDs owned by the application, sorted.
+type Signature = (Vec<i32>, Vec<u32>);
+
+fn signature(content: &SCShareableContent, bundle_id: &str) -> Option<Signature> {
+    let snapshot = content.snapshot()?;
+    let owned: Vec<usize> = snapshot
+        .applications
+        .iter()
+        .enumerate()
+        .filter(|(_, app)| app.bundle_identifier == bundle_id)
+        .map(|(index, _)| index)
+        .collect();
+    let mut pids: Vec<i32> = owned
+        .iter()
+        .map(|&index| snapshot.applications[index].process_id)
+        .collect();
+    let mut windows: Vec<u32> = snapshot
+        .windows
+        .iter()
+        .filter(|w| w.owning_app_index.is_some_and(|i| owned.contains(&i)))
+        .map(|w| w.window_id)
+        .collect();
+    pids.sort_unstable();
+    windows.sort_unstable();
+    Some((pids, windows))
+}
+
+fn fetch() -> Result<SCShareableContent, SCError> {
+    SCShareableContent::create()
+        .with_on_screen_windows_only(false)
+        .get()
+}
+
+fn follow(
+    context: &PollerContext,
+    updates: &AtomicU64,
+    stream: &SCStream,
+    display: &SCDisplay,
+    bundle_id: &str,
+    interval: Duration,
+) {
+    let mut current = fetch().ok().and_then(|c| signature(&c, bundle_id));
+
+    while !context.sleep(interval) {
+        let result = fetch().and_then(|content| {
+            let next = signature(&content, bundle_id);
+            // Nothing to point the filter at while the app isn't running;
+            // keep the old filter until it comes back.
+            let running = next.as_ref().is_some_and(|(pids, _)| !pids.is_empty());
+            if next != current {
+                SCShareableContent::invalidate_cache();
+            }
+            if next == current || !running {
+                current = next;
+                return Ok(());
+            }
+            let filter = SCContentFilter::for_application_in(&content, display, bundle_id)?;
+            stream.update_content_filter(&filter)?;
+            updates.fetch_add(1, Ordering::Relaxed);
+            current = next;
+            Ok(())
+        });
+        context.set_last_error(result.err());
+    }
+}
+
+impl fmt::Debug for ApplicationFollower {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ApplicationFollower")
+            .field("bundle_id", &self.bundle_id)
+            .field("update_count", &self.update_count())
+            .finish_non_exhaustive()
+    }
+}
@@ -27,7 +27,7 @@ impl SCStreamConfiguration {
     pub fn set_ignores_shadows_single_window(&mut self, ignores_shadows: bool) -> &mut Self {
         unsafe {
             crate::ffi::sc_stream_configuration_set_ignores_shadows_single_window(
-                self.as_ptr(),
+                self.access_mut().ptr(),
                 ignores_shadows,
             );
         }
@@ -45,7 +45,9 @@ impl SCStreamConfiguration {
     #[cfg(feature = "macos_14_0")]
     pub fn ignores_shadows_single_window(&self) -> bool {
         unsafe {
-            crate::ffi::sc_stream_configuration_get_ignores_shadows_single_window(self.as_ptr())
+            crate::ffi::sc_stream_configuration_get_ignores_shadows_single_window(
+                self.access().ptr(),
+            )
         }
     }
 
@@ -53,14 +55,15 @@ impl SCStreamConfiguration {
     ///
     /// A Boolean value that indicates whether the stream treats the transparency
     /// of the captured content as opaque.
-    /// Available on macOS 13.0+
+    /// Takes effect on macOS 14.0+; on earlier versions the setting is ignored
+    /// and [`should_be_opaque`](Self::should_be_opaque) reads back `false`.
     ///
     /// Requires the `macos_13_0` feature flag to be enabled.
     #[cfg(feature = "macos_13_0")]
     pub fn set_should_be_opaque(&mut self, should_be_opaque: bool) -> &mut Self {
         unsafe {
             crate::ffi::sc_stream_configuration_set_should_be_opaque(
-                self.as_ptr(),
+                self.access_mut().ptr(),
                 should_be_opaque,
             );
         }
@@ -75,9 +78,10 @@ impl SCStreamConfiguration {
         self
     }
 
+    /// Get whether captured content is treated as opaque (macOS 14.0+).
     #[cfg(feature = "macos_13_0")]
     pub fn should_be_opaque(&self) -> bool {
-        unsafe { crate::ffi::sc_stream_configuration_get_should_be_opaque(self.as_ptr()) }
+        unsafe { crate::ffi::sc_stream_configuration_get_should_be_opaque(self.access().ptr()) }
     }
 
     /// Sets whether to include child windows in capture.
@@ -90,7 +94,7 @@ impl SCStreamConfiguration {
     pub fn set_includes_child_windows(&mut self, includes_child_windows: bool) -> &mut Self {
         unsafe {
             crate::ffi::sc_stream_configuration_set_includes_child_windows(
-                self.as_ptr(),
+                self.access_mut().ptr(),
                 includes_child_windows,
             );
         }
@@ -107,7 +111,9 @@ impl SCStreamConfiguration {
 
     #[cfg(feature = "macos_14_2")]
     pub fn includes_child_windows(&self) -> bool {
-        unsafe { crate::ffi::sc_stream_configuration_get_includes_child_windows(self.as_ptr()) }
+        unsafe {
+            crate::ffi::sc_stream_configuration_get_includes_child_windows(self.access().ptr())
+        }
     }
 
     /// Sets the presenter overlay privacy alert setting.
@@ -123,7 +129,7 @@ impl SCStreamConfiguration {
     ) -> &mut Self {
         unsafe {
             crate::ffi::sc_stream_configuration_set_presenter_overlay_privacy_alert_setting(
-                self.as_ptr(),
+                self.access_mut().ptr(),
                 setting as i32,
             );
         }
@@ -145,7 +151,7 @@ impl SCStreamConfiguration {
     pub fn presenter_overlay_privacy_alert_setting(&self) -> SCPresenterOverlayAlertSetting {
         let value = unsafe {
             crate::ffi::sc_stream_configuration_get_presenter_overlay_privacy_alert_setting(
-                self.as_ptr(),
+                self.access().ptr(),
             )
         };
         match value {
@@ -164,7 +170,7 @@ impl SCStreamConfiguration {
     pub fn set_ignores_shadow_display_configuration(&mut self, ignores_shadow: bool) -> &mut Self {
         unsafe {
             crate::ffi::sc_stream_configuration_set_ignores_shadow_display_configuration(
-                self.as_ptr(),
+                self.access_mut().ptr(),
                 ignores_shadow,
             );
         }
@@ -183,7 +189,7 @@ impl SCStreamConfiguration {
     pub fn ignores_shadow_display_configuration(&self) -> bool {
         unsafe {
             crate::ffi::sc_stream_configuration_get_ignores_shadow_display_configuration(
-                self.as_ptr(),
+                self.access().ptr(),
             )
         }
     }
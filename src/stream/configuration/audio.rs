@@ -17,7 +17,24 @@
 //! |---------------|-------------|
 //! | 1 | Mono |
 //! | 2 | Stereo (default) |
+//!
+//! ## System audio and microphone formats
+//!
+//! A configuration has one [`sample_rate`](SCStreamConfiguration::sample_rate)
+//! and one [`channel_count`](SCStreamConfiguration::channel_count);
+//! `ScreenCaptureKit` has no separate settings for the microphone. System
+//! audio is converted to the configured format. Microphone samples follow
+//! it where the input device and macOS allow, but a device can still
+//! deliver its own rate or channel layout — a mono headset microphone next
+//! to stereo system audio, for example.
+//!
+//! Don't assume either format: once samples arrive,
+//! [`SCStream::audio_format`](crate::stream::SCStream::audio_format)
+//! reports what each audio output actually delivers, and
+//! [`AudioFormat::of`] reads it from a single sample buffer. Mix or encode
+//! the two outputs separately, or convert one to match the other.
 
+use crate::cm::CMSampleBuffer;
 use crate::utils::ffi_string::{ffi_string_from_buffer, SMALL_BUFFER_SIZE};
 
 use super::internal::SCStreamConfiguration;
@@ -163,9 +180,72 @@ impl From<AudioChannelCount> for i32 {
     }
 }
 
+/// Sample rate and channel count of delivered audio
+///
+/// Read from the samples themselves, so it reflects what was negotiated
+/// rather than what was requested; see the
+/// [module documentation](self#system-audio-and-microphone-formats).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AudioFormat {
+    /// Frames per second
+    pub sample_rate: f64,
+    /// Channels per frame
+    pub channel_count: u32,
+}
+
+impl AudioFormat {
+    /// The format of an audio sample buffer, or `None` if it carries no
+    /// audio
+    #[must_use]
+    pub fn of(sample: &CMSampleBuffer) -> Option<Self> {
+        let mut sample_rate = 0.0;
+        let mut channel_count = 0;
+        let found = unsafe {
+            crate::cm::ffi::cm_sample_buffer_get_audio_format(
+                sample.as_ptr(),
+                &mut sample_rate,
+                &mut channel_count,
+            )
+        };
+        found.then_some(Self {
+            sample_rate,
+            channel_count,
+        })
+    }
+
+    /// Whether this is the sample rate and channel count `config` asks for
+    #[must_use]
+    pub fn matches(&self, config: &SCStreamConfiguration) -> bool {
+        (self.sample_rate - f64::from(config.sample_rate())).abs() < 0.5
+            && i64::from(self.channel_count) == i64::from(config.channel_count())
+    }
+}
+
+impl std::fmt::Display for AudioFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let channels = if self.channel_count == 1 {
+            "channel"
+        } else {
+            "channels"
+        };
+        write!(
+            f,
+            "{} Hz, {} {channels}",
+            self.sample_rate, self.channel_count
+        )
+    }
+}
+
 impl SCStreamConfiguration {
     /// Enable or disable audio capture
     ///
+    /// The captured audio is the system mix across every output device,
+    /// narrowed only by the content filter's applications. To record what
+    /// one device plays, such as headphones while speakers play something
+    /// else, use an
+    /// [`AudioOutputTap`](crate::audio_output_tap::AudioOutputTap)
+    /// (`macos_14_2` feature) alongside a video-only stream.
+    ///
     /// # Examples
     ///
     /// ```
@@ -177,7 +257,10 @@ impl SCStreamConfiguration {
     /// ```
     pub fn set_captures_audio(&mut self, captures_audio: bool) -> &mut Self {
         unsafe {
-            crate::ffi::sc_stream_configuration_set_captures_audio(self.as_ptr(), captures_audio);
+            crate::ffi::sc_stream_configuration_set_captures_audio(
+                self.access_mut().ptr(),
+                captures_audio,
+            );
         }
         self
     }
@@ -191,7 +274,7 @@ impl SCStreamConfiguration {
 
     /// Check if audio capture is enabled
     pub fn captures_audio(&self) -> bool {
-        unsafe { crate::ffi::sc_stream_configuration_get_captures_audio(self.as_ptr()) }
+        unsafe { crate::ffi::sc_stream_configuration_get_captures_audio(self.access().ptr()) }
     }
 
     /// Set the audio sample rate
@@ -203,6 +286,9 @@ impl SCStreamConfiguration {
     /// `ScreenCaptureKit` supports: 8000, 16000, 24000, 48000 Hz.
     /// Other values will default to 48000 Hz.
     ///
+    /// The rate is shared by system audio and the microphone; see
+    /// [System audio and microphone formats](self#system-audio-and-microphone-formats).
+    ///
     /// # Examples
     ///
     /// ```
@@ -220,7 +306,7 @@ impl SCStreamConfiguration {
     pub fn set_sample_rate(&mut self, sample_rate: impl Into<i32>) -> &mut Self {
         unsafe {
             crate::ffi::sc_stream_configuration_set_sample_rate(
-                self.as_ptr(),
+                self.access_mut().ptr(),
                 sample_rate.into() as isize,
             );
         }
@@ -239,7 +325,7 @@ impl SCStreamConfiguration {
         // FFI returns isize but sample rate fits in i32 (typical values: 44100, 48000)
         #[allow(clippy::cast_possible_truncation)]
         unsafe {
-            crate::ffi::sc_stream_configuration_get_sample_rate(self.as_ptr()) as i32
+            crate::ffi::sc_stream_configuration_get_sample_rate(self.access().ptr()) as i32
         }
     }
 
@@ -259,6 +345,9 @@ impl SCStreamConfiguration {
     /// `ScreenCaptureKit` supports: 1 (mono), 2 (stereo).
     /// Other values will default to stereo.
     ///
+    /// Like the sample rate, this applies to the microphone too, as far as
+    /// its input device allows.
+    ///
     /// # Examples
     ///
     /// ```
@@ -276,7 +365,7 @@ impl SCStreamConfiguration {
     pub fn set_channel_count(&mut self, channel_count: impl Into<i32>) -> &mut Self {
         unsafe {
             crate::ffi::sc_stream_configuration_set_channel_count(
-                self.as_ptr(),
+                self.access_mut().ptr(),
                 channel_count.into() as isize,
             );
         }
@@ -295,7 +384,7 @@ impl SCStreamConfiguration {
         // FFI returns isize but channel count fits in i32 (typical values: 1-8)
         #[allow(clippy::cast_possible_truncation)]
         unsafe {
-            crate::ffi::sc_stream_configuration_get_channel_count(self.as_ptr()) as i32
+            crate::ffi::sc_stream_configuration_get_channel_count(self.access().ptr()) as i32
         }
     }
 
@@ -330,7 +419,7 @@ impl SCStreamConfiguration {
     pub fn set_captures_microphone(&mut self, captures_microphone: bool) -> &mut Self {
         unsafe {
             crate::ffi::sc_stream_configuration_set_captures_microphone(
-                self.as_ptr(),
+                self.access_mut().ptr(),
                 captures_microphone,
             );
         }
@@ -346,7 +435,7 @@ impl SCStreamConfiguration {
 
     /// Get whether microphone capture is enabled (macOS 15.0+).
     pub fn captures_microphone(&self) -> bool {
-        unsafe { crate::ffi::sc_stream_configuration_get_captures_microphone(self.as_ptr()) }
+        unsafe { crate::ffi::sc_stream_configuration_get_captures_microphone(self.access().ptr()) }
     }
 
     /// Exclude current process audio from capture.
@@ -365,7 +454,7 @@ impl SCStreamConfiguration {
     pub fn set_excludes_current_process_audio(&mut self, excludes: bool) -> &mut Self {
         unsafe {
             crate::ffi::sc_stream_configuration_set_excludes_current_process_audio(
-                self.as_ptr(),
+                self.access_mut().ptr(),
                 excludes,
             );
         }
@@ -382,7 +471,9 @@ impl SCStreamConfiguration {
     /// Get whether current process audio is excluded from capture.
     pub fn excludes_current_process_audio(&self) -> bool {
         unsafe {
-            crate::ffi::sc_stream_configuration_get_excludes_current_process_audio(self.as_ptr())
+            crate::ffi::sc_stream_configuration_get_excludes_current_process_audio(
+                self.access().ptr(),
+            )
         }
     }
 
@@ -409,7 +500,7 @@ impl SCStreamConfiguration {
         unsafe {
             if let Ok(c_id) = std::ffi::CString::new(device_id) {
                 crate::ffi::sc_stream_configuration_set_microphone_capture_device_id(
-                    self.as_ptr(),
+                    self.access_mut().ptr(),
                     c_id.as_ptr(),
                 );
             }
@@ -428,7 +519,7 @@ impl SCStreamConfiguration {
     pub fn clear_microphone_capture_device_id(&mut self) -> &mut Self {
         unsafe {
             crate::ffi::sc_stream_configuration_set_microphone_capture_device_id(
-                self.as_ptr(),
+                self.access_mut().ptr(),
                 std::ptr::null(),
             );
         }
@@ -440,7 +531,7 @@ impl SCStreamConfiguration {
         unsafe {
             ffi_string_from_buffer(SMALL_BUFFER_SIZE, |buf, len| {
                 crate::ffi::sc_stream_configuration_get_microphone_capture_device_id(
-                    self.as_ptr(),
+                    self.access().ptr(),
                     buf,
                     len,
                 )
@@ -19,7 +19,10 @@ impl SCStreamConfiguration {
     /// ```
     pub fn set_shows_cursor(&mut self, shows_cursor: bool) -> &mut Self {
         unsafe {
-            crate::ffi::sc_stream_configuration_set_shows_cursor(self.as_ptr(), shows_cursor);
+            crate::ffi::sc_stream_configuration_set_shows_cursor(
+                self.access_mut().ptr(),
+                shows_cursor,
+            );
         }
         self
     }
@@ -33,7 +36,7 @@ impl SCStreamConfiguration {
 
     /// Check if cursor is shown in capture
     pub fn shows_cursor(&self) -> bool {
-        unsafe { crate::ffi::sc_stream_configuration_get_shows_cursor(self.as_ptr()) }
+        unsafe { crate::ffi::sc_stream_configuration_get_shows_cursor(self.access().ptr()) }
     }
 
     /// Show mouse click indicators (macOS 15.0+)
@@ -56,7 +59,7 @@ impl SCStreamConfiguration {
     pub fn set_shows_mouse_clicks(&mut self, shows_mouse_clicks: bool) -> &mut Self {
         unsafe {
             crate::ffi::sc_stream_configuration_set_shows_mouse_clicks(
-                self.as_ptr(),
+                self.access_mut().ptr(),
                 shows_mouse_clicks,
             );
         }
@@ -74,7 +77,7 @@ impl SCStreamConfiguration {
     /// Check if mouse click indicators are shown (macOS 15.0+)
     #[cfg(feature = "macos_15_0")]
     pub fn shows_mouse_clicks(&self) -> bool {
-        unsafe { crate::ffi::sc_stream_configuration_get_shows_mouse_clicks(self.as_ptr()) }
+        unsafe { crate::ffi::sc_stream_configuration_get_shows_mouse_clicks(self.access().ptr()) }
     }
 
     /// Capture only window shadows (macOS 14.0+)
@@ -99,7 +102,7 @@ impl SCStreamConfiguration {
     pub fn set_captures_shadows_only(&mut self, captures_shadows_only: bool) -> &mut Self {
         unsafe {
             crate::ffi::sc_stream_configuration_set_captures_shadows_only(
-                self.as_ptr(),
+                self.access_mut().ptr(),
                 captures_shadows_only,
             );
         }
@@ -117,7 +120,9 @@ impl SCStreamConfiguration {
     /// Get whether only window shadows are captured (macOS 14.0+).
     #[cfg(feature = "macos_14_0")]
     pub fn captures_shadows_only(&self) -> bool {
-        unsafe { crate::ffi::sc_stream_configuration_get_captures_shadows_only(self.as_ptr()) }
+        unsafe {
+            crate::ffi::sc_stream_configuration_get_captures_shadows_only(self.access().ptr())
+        }
     }
 
     /// Ignore shadows for display capture (macOS 14.0+)
@@ -130,7 +135,7 @@ impl SCStreamConfiguration {
     pub fn set_ignores_shadows_display(&mut self, ignores_shadows: bool) -> &mut Self {
         unsafe {
             crate::ffi::sc_stream_configuration_set_ignores_shadows_display(
-                self.as_ptr(),
+                self.access_mut().ptr(),
                 ignores_shadows,
             );
         }
@@ -148,7 +153,9 @@ impl SCStreamConfiguration {
     /// Check if shadows are ignored for display capture (macOS 14.0+)
     #[cfg(feature = "macos_14_0")]
     pub fn ignores_shadows_display(&self) -> bool {
-        unsafe { crate::ffi::sc_stream_configuration_get_ignores_shadows_display(self.as_ptr()) }
+        unsafe {
+            crate::ffi::sc_stream_configuration_get_ignores_shadows_display(self.access().ptr())
+        }
     }
 
     /// Ignore global clip for display capture (macOS 14.0+)
@@ -161,7 +168,7 @@ impl SCStreamConfiguration {
     pub fn set_ignore_global_clip_display(&mut self, ignore: bool) -> &mut Self {
         unsafe {
             crate::ffi::sc_stream_configuration_set_ignore_global_clip_display(
-                self.as_ptr(),
+                self.access_mut().ptr(),
                 ignore,
             );
         }
@@ -179,7 +186,9 @@ impl SCStreamConfiguration {
     /// Check if global clip is ignored for display capture (macOS 14.0+)
     #[cfg(feature = "macos_14_0")]
     pub fn ignore_global_clip_display(&self) -> bool {
-        unsafe { crate::ffi::sc_stream_configuration_get_ignore_global_clip_display(self.as_ptr()) }
+        unsafe {
+            crate::ffi::sc_stream_configuration_get_ignore_global_clip_display(self.access().ptr())
+        }
     }
 
     /// Ignore global clip for single window capture (macOS 14.0+)
@@ -192,7 +201,7 @@ impl SCStreamConfiguration {
     pub fn set_ignore_global_clip_single_window(&mut self, ignore: bool) -> &mut Self {
         unsafe {
             crate::ffi::sc_stream_configuration_set_ignore_global_clip_single_window(
-                self.as_ptr(),
+                self.access_mut().ptr(),
                 ignore,
             );
         }
@@ -211,7 +220,9 @@ impl SCStreamConfiguration {
     #[cfg(feature = "macos_14_0")]
     pub fn ignore_global_clip_single_window(&self) -> bool {
         unsafe {
-            crate::ffi::sc_stream_configuration_get_ignore_global_clip_single_window(self.as_ptr())
+            crate::ffi::sc_stream_configuration_get_ignore_global_clip_single_window(
+                self.access().ptr(),
+            )
         }
     }
 }
@@ -11,7 +11,7 @@ impl SCStreamConfiguration {
         #[allow(clippy::cast_possible_wrap)]
         unsafe {
             crate::ffi::sc_stream_configuration_set_queue_depth(
-                self.as_ptr(),
+                self.access_mut().ptr(),
                 queue_depth as isize,
             );
         }
@@ -29,7 +29,7 @@ impl SCStreamConfiguration {
         // FFI returns isize but queue depth is always positive and fits in u32
         #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
         unsafe {
-            crate::ffi::sc_stream_configuration_get_queue_depth(self.as_ptr()) as u32
+            crate::ffi::sc_stream_configuration_get_queue_depth(self.access().ptr()) as u32
         }
     }
 
@@ -37,7 +37,7 @@ impl SCStreamConfiguration {
     pub fn set_minimum_frame_interval(&mut self, cm_time: &CMTime) -> &mut Self {
         unsafe {
             crate::ffi::sc_stream_configuration_set_minimum_frame_interval(
-                self.as_ptr(),
+                self.access_mut().ptr(),
                 cm_time.value,
                 cm_time.timescale,
                 cm_time.flags,
@@ -54,6 +54,10 @@ impl SCStreamConfiguration {
         self
     }
 
+    /// Get the minimum frame interval
+    ///
+    /// The shortest time `ScreenCaptureKit` waits between delivered frames.
+    /// A zero interval means frames arrive at the display's refresh rate.
     pub fn minimum_frame_interval(&self) -> CMTime {
         unsafe {
             let mut value: i64 = 0;
@@ -62,7 +66,7 @@ impl SCStreamConfiguration {
             let mut epoch: i64 = 0;
 
             crate::ffi::sc_stream_configuration_get_minimum_frame_interval(
-                self.as_ptr(),
+                self.access().ptr(),
                 &mut value,
                 &mut timescale,
                 &mut flags,
@@ -80,17 +84,27 @@ impl SCStreamConfiguration {
 
     /// Get the target frame rate in frames per second
     ///
-    /// Converts the minimum frame interval (`CMTime`) to FPS.
-    /// Returns 0 if the frame interval is invalid.
-    #[allow(clippy::cast_possible_truncation)]
+    /// Converts the minimum frame interval (`CMTime`) to FPS, rounded to the
+    /// nearest whole frame, so an NTSC interval of 1001/30000 reads as 30.
+    /// Use [`frame_rate`](Self::frame_rate) for the exact value. Returns 0 if
+    /// the frame interval is zero or invalid.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
     pub fn fps(&self) -> u32 {
+        self.frame_rate().round() as u32
+    }
+
+    /// Get the target frame rate in frames per second, as a fraction
+    ///
+    /// Exactly `timescale / value` of the minimum frame interval, e.g.
+    /// 29.97002997 for 1001/30000. Returns 0.0 if the interval is zero or
+    /// invalid.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn frame_rate(&self) -> f64 {
         let cm_time = self.minimum_frame_interval();
-        if cm_time.value == 0 {
-            return 0;
+        if cm_time.value <= 0 || cm_time.timescale <= 0 {
+            return 0.0;
         }
-        #[allow(clippy::cast_sign_loss)]
-        let fps = (i64::from(cm_time.timescale) / cm_time.value) as u32;
-        fps
+        f64::from(cm_time.timescale) / cm_time.value as f64
     }
 
     /// Set the target frame rate in frames per second
@@ -124,6 +138,47 @@ impl SCStreamConfiguration {
         self
     }
 
+    /// Set a fractional target frame rate, e.g. 29.97 or 59.94
+    ///
+    /// The interval is stored as `1000 / round(fps * 1000)`, so rates are
+    /// kept to a thousandth of a frame per second: 29.97 becomes
+    /// 1000/29970. For the exact NTSC interval of 1001/30000, pass the
+    /// `CMTime` to
+    /// [`set_minimum_frame_interval`](Self::set_minimum_frame_interval)
+    /// instead.
+    ///
+    /// Zero, negative, NaN and infinite rates clear the limit (a zero
+    /// interval), letting frames arrive at the display's refresh rate.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use screencapturekit::stream::configuration::SCStreamConfiguration;
+    ///
+    /// let config = SCStreamConfiguration::new().with_frame_rate(29.97);
+    /// assert_eq!(config.fps(), 30);
+    /// ```
+    pub fn set_frame_rate(&mut self, fps: f64) -> &mut Self {
+        let millis = (fps * 1000.0).round();
+        let cm_time = if fps.is_finite() && millis >= 1.0 {
+            #[allow(clippy::cast_possible_truncation)]
+            let timescale = millis.min(f64::from(i32::MAX)) as i32;
+            CMTime::new(1000, timescale)
+        } else {
+            CMTime::new(0, 1)
+        };
+        self.set_minimum_frame_interval(&cm_time)
+    }
+
+    /// Set a fractional target frame rate (builder pattern)
+    ///
+    /// See [`set_frame_rate`](Self::set_frame_rate) for rounding.
+    #[must_use]
+    pub fn with_frame_rate(mut self, fps: f64) -> Self {
+        self.set_frame_rate(fps);
+        self
+    }
+
     /// Set the capture resolution type (macOS 14.0+)
     ///
     /// Controls how the capture resolution is determined.
@@ -146,7 +201,7 @@ impl SCStreamConfiguration {
     ) -> &mut Self {
         unsafe {
             crate::ffi::sc_stream_configuration_set_capture_resolution_type(
-                self.as_ptr(),
+                self.access_mut().ptr(),
                 resolution_type as i32,
             );
         }
@@ -168,7 +223,7 @@ impl SCStreamConfiguration {
     #[cfg(feature = "macos_14_0")]
     pub fn capture_resolution_type(&self) -> SCCaptureResolutionType {
         let value = unsafe {
-            crate::ffi::sc_stream_configuration_get_capture_resolution_type(self.as_ptr())
+            crate::ffi::sc_stream_configuration_get_capture_resolution_type(self.access().ptr())
         };
         match value {
             1 => SCCaptureResolutionType::Best,
@@ -34,7 +34,7 @@ impl SCStreamConfiguration {
         let four_char_code: FourCharCode = pixel_format.into();
         unsafe {
             crate::ffi::sc_stream_configuration_set_pixel_format(
-                self.as_ptr(),
+                self.access_mut().ptr(),
                 four_char_code.as_u32(),
             );
         }
@@ -51,7 +51,7 @@ impl SCStreamConfiguration {
     /// Get the current pixel format
     pub fn pixel_format(&self) -> PixelFormat {
         unsafe {
-            let value = crate::ffi::sc_stream_configuration_get_pixel_format(self.as_ptr());
+            let value = crate::ffi::sc_stream_configuration_get_pixel_format(self.access().ptr());
             PixelFormat::from(value)
         }
     }
@@ -61,7 +61,13 @@ impl SCStreamConfiguration {
     /// Available on macOS 13.0+
     pub fn set_background_color_rgba(&mut self, r: f32, g: f32, b: f32, a: f32) -> &mut Self {
         unsafe {
-            crate::ffi::sc_stream_configuration_set_background_color(self.as_ptr(), r, g, b, a);
+            crate::ffi::sc_stream_configuration_set_background_color(
+                self.access_mut().ptr(),
+                r,
+                g,
+                b,
+                a,
+            );
         }
         self
     }
@@ -100,7 +106,7 @@ impl SCStreamConfiguration {
         // so there is no Rust-side cache to leak or to go stale on pointer reuse.
         let was_set = unsafe {
             crate::ffi::sc_stream_configuration_get_background_color(
-                self.as_ptr(),
+                self.access().ptr(),
                 &mut r,
                 &mut g,
                 &mut b,
@@ -121,7 +127,7 @@ impl SCStreamConfiguration {
         if let Ok(c_name) = std::ffi::CString::new(name) {
             unsafe {
                 crate::ffi::sc_stream_configuration_set_color_space_name(
-                    self.as_ptr(),
+                    self.access_mut().ptr(),
                     c_name.as_ptr(),
                 );
             }
@@ -140,7 +146,11 @@ impl SCStreamConfiguration {
     pub fn color_space_name(&self) -> Option<String> {
         unsafe {
             ffi_string_from_buffer(SMALL_BUFFER_SIZE, |buf, len| {
-                crate::ffi::sc_stream_configuration_get_color_space_name(self.as_ptr(), buf, len)
+                crate::ffi::sc_stream_configuration_get_color_space_name(
+                    self.access().ptr(),
+                    buf,
+                    len,
+                )
             })
         }
     }
@@ -156,7 +166,7 @@ impl SCStreamConfiguration {
         if let Ok(c_matrix) = std::ffi::CString::new(matrix) {
             unsafe {
                 crate::ffi::sc_stream_configuration_set_color_matrix(
-                    self.as_ptr(),
+                    self.access_mut().ptr(),
                     c_matrix.as_ptr(),
                 );
             }
@@ -170,7 +180,7 @@ impl SCStreamConfiguration {
     pub fn color_matrix(&self) -> Option<String> {
         unsafe {
             ffi_string_from_buffer(SMALL_BUFFER_SIZE, |buf, len| {
-                crate::ffi::sc_stream_configuration_get_color_matrix(self.as_ptr(), buf, len)
+                crate::ffi::sc_stream_configuration_get_color_matrix(self.access().ptr(), buf, len)
             })
         }
     }
@@ -0,0 +1,387 @@
+//! Configuration diffing
+//!
+//! Compare two configurations property by property, e.g. to log or validate
+//! what a live [`SCStream::update_configuration`](crate::stream::SCStream::update_configuration)
+//! call is about to change.
+
+use std::fmt;
+
+use super::internal::SCStreamConfiguration;
+
+/// A single `SCStreamConfiguration` property that can be compared by
+/// [`SCStreamConfiguration::diff`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ConfigurationProperty {
+    /// Output width in pixels
+    Width,
+    /// Output height in pixels
+    Height,
+    /// Whether content scales to fit the output size
+    ScalesToFit,
+    /// Region of the source captured
+    SourceRect,
+    /// Region of the output the content is drawn into
+    DestinationRect,
+    /// Whether scaling keeps the content's aspect ratio
+    PreservesAspectRatio,
+    /// Whether the cursor is drawn
+    ShowsCursor,
+    /// Number of frames queued before the oldest is dropped
+    QueueDepth,
+    /// Minimum time between frames
+    MinimumFrameInterval,
+    /// Pixel format of output frames
+    PixelFormat,
+    /// Color filling the output around the content
+    BackgroundColor,
+    /// Color space of output frames
+    ColorSpaceName,
+    /// YCbCr matrix of output frames
+    ColorMatrix,
+    /// Whether system audio is captured
+    CapturesAudio,
+    /// Audio sample rate
+    SampleRate,
+    /// Audio channel count
+    ChannelCount,
+    /// Whether this process's own audio is left out
+    ExcludesCurrentProcessAudio,
+    /// Name the stream reports to the system
+    StreamName,
+    /// Whether the content is treated as opaque
+    #[cfg(feature = "macos_13_0")]
+    ShouldBeOpaque,
+    /// Resolution content is captured at
+    #[cfg(feature = "macos_14_0")]
+    CaptureResolutionType,
+    /// Whether single-window captures leave out the window's shadow
+    #[cfg(feature = "macos_14_0")]
+    IgnoresShadowsSingleWindow,
+    /// Whether display captures leave out window shadows
+    #[cfg(feature = "macos_14_0")]
+    IgnoresShadowsDisplay,
+    /// Whether display captures ignore the global clip
+    #[cfg(feature = "macos_14_0")]
+    IgnoreGlobalClipDisplay,
+    /// Whether single-window captures ignore the global clip
+    #[cfg(feature = "macos_14_0")]
+    IgnoreGlobalClipSingleWindow,
+    /// Whether only window shadows are captured
+    #[cfg(feature = "macos_14_0")]
+    CapturesShadowsOnly,
+    /// Whether child windows of captured windows are included
+    #[cfg(feature = "macos_14_2")]
+    IncludesChildWindows,
+    /// When Presenter Overlay shows its privacy alert
+    #[cfg(feature = "macos_14_2")]
+    PresenterOverlayPrivacyAlertSetting,
+    /// Whether mouse clicks are drawn
+    #[cfg(feature = "macos_15_0")]
+    ShowsMouseClicks,
+    /// Dynamic range of output frames
+    #[cfg(feature = "macos_15_0")]
+    CaptureDynamicRange,
+    /// Whether the microphone is captured
+    #[cfg(feature = "macos_15_0")]
+    CapturesMicrophone,
+    /// Microphone captured, if not the default one
+    #[cfg(feature = "macos_15_0")]
+    MicrophoneCaptureDeviceId,
+}
+
+impl ConfigurationProperty {
+    /// The Rust getter name for this property (e.g. `"shows_cursor"`).
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Width => "width",
+            Self::Height => "height",
+            Self::ScalesToFit => "scales_to_fit",
+            Self::SourceRect => "source_rect",
+            Self::DestinationRect => "destination_rect",
+            Self::PreservesAspectRatio => "preserves_aspect_ratio",
+            Self::ShowsCursor => "shows_cursor",
+            Self::QueueDepth => "queue_depth",
+            Self::MinimumFrameInterval => "minimum_frame_interval",
+            Self::PixelFormat => "pixel_format",
+            Self::BackgroundColor => "background_color",
+            Self::ColorSpaceName => "color_space_name",
+            Self::ColorMatrix => "color_matrix",
+            Self::CapturesAudio => "captures_audio",
+            Self::SampleRate => "sample_rate",
+            Self::ChannelCount => "channel_count",
+            Self::ExcludesCurrentProcessAudio => "excludes_current_process_audio",
+            Self::StreamName => "stream_name",
+            #[cfg(feature = "macos_13_0")]
+            Self::ShouldBeOpaque => "should_be_opaque",
+            #[cfg(feature = "macos_14_0")]
+            Self::CaptureResolutionType => "capture_resolution_type",
+            #[cfg(feature = "macos_14_0")]
+            Self::IgnoresShadowsSingleWindow => "ignores_shadows_single_window",
+            #[cfg(feature = "macos_14_0")]
+            Self::IgnoresShadowsDisplay => "ignores_shadows_display",
+            #[cfg(feature = "macos_14_0")]
+            Self::IgnoreGlobalClipDisplay => "ignore_global_clip_display",
+            #[cfg(feature = "macos_14_0")]
+            Self::IgnoreGlobalClipSingleWindow => "ignore_global_clip_single_window",
+            #[cfg(feature = "macos_14_0")]
+            Self::CapturesShadowsOnly => "captures_shadows_only",
+            #[cfg(feature = "macos_14_2")]
+            Self::IncludesChildWindows => "includes_child_windows",
+            #[cfg(feature = "macos_14_2")]
+            Self::PresenterOverlayPrivacyAlertSetting => "presenter_overlay_privacy_alert_setting",
+            #[cfg(feature = "macos_15_0")]
+            Self::ShowsMouseClicks => "shows_mouse_clicks",
+            #[cfg(feature = "macos_15_0")]
+            Self::CaptureDynamicRange => "capture_dynamic_range",
+            #[cfg(feature = "macos_15_0")]
+            Self::CapturesMicrophone => "captures_microphone",
+            #[cfg(feature = "macos_15_0")]
+            Self::MicrophoneCaptureDeviceId => "microphone_capture_device_id",
+        }
+    }
+}
+
+impl fmt::Display for ConfigurationProperty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// One changed property, with both values rendered via `Debug`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigurationChange {
+    /// The property that differs.
+    pub property: ConfigurationProperty,
+    /// Value in the configuration `diff` was called on.
+    pub old: String,
+    /// Value in the configuration passed to `diff`.
+    pub new: String,
+}
+
+impl fmt::Display for ConfigurationChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} -> {}", self.property, self.old, self.new)
+    }
+}
+
+/// The set of properties that differ between two configurations.
+///
+/// Returned by [`SCStreamConfiguration::diff`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SCStreamConfigurationDelta {
+    changes: Vec<ConfigurationChange>,
+}
+
+impl SCStreamConfigurationDelta {
+    /// `true` if the two configurations are equivalent.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Number of changed properties.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.changes.len()
+    }
+
+    /// `true` if `property` changed.
+    #[must_use]
+    pub fn contains(&self, property: ConfigurationProperty) -> bool {
+        self.changes.iter().any(|c| c.property == property)
+    }
+
+    /// The change for `property`, if it changed.
+    #[must_use]
+    pub fn get(&self, property: ConfigurationProperty) -> Option<&ConfigurationChange> {
+        self.changes.iter().find(|c| c.property == property)
+    }
+
+    /// Iterate over the changed properties.
+    pub fn iter(&self) -> std::slice::Iter<'_, ConfigurationChange> {
+        self.changes.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a SCStreamConfigurationDelta {
+    type Item = &'a ConfigurationChange;
+    type IntoIter = std::slice::Iter<'a, ConfigurationChange>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl fmt::Display for SCStreamConfigurationDelta {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.changes.is_empty() {
+            return f.write_str("no changes");
+        }
+        for (i, change) in self.changes.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{change}")?;
+        }
+        Ok(())
+    }
+}
+
+impl SCStreamConfiguration {
+    /// Compare this configuration against `other`, property by property.
+    ///
+    /// Values are read back through the getters, so only properties this
+    /// crate exposes (and that are enabled by the active feature flags) are
+    /// compared.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use screencapturekit::prelude::*;
+    /// use screencapturekit::stream::configuration::ConfigurationProperty;
+    ///
+    /// let current = SCStreamConfiguration::new().with_width(1920).with_shows_cursor(true);
+    /// let next = current.deep_copy().with_width(1280);
+    ///
+    /// let delta = current.diff(&next);
+    /// assert!(delta.contains(ConfigurationProperty::Width));
+    /// assert!(!delta.contains(ConfigurationProperty::ShowsCursor));
+    /// println!("{delta}"); // width: 1920 -> 1280
+    /// ```
+    #[must_use]
+    pub fn diff(&self, other: &Self) -> SCStreamConfigurationDelta {
+        let changes = property_values(self)
+            .into_iter()
+            .zip(property_values(other))
+            .filter(|((_, old), (_, new))| old != new)
+            .map(|((property, old), (_, new))| ConfigurationChange { property, old, new })
+            .collect();
+        SCStreamConfigurationDelta { changes }
+    }
+}
+
+/// Read every comparable property, in a fixed order.
+pub(super) fn property_values(
+    config: &SCStreamConfiguration,
+) -> Vec<(ConfigurationProperty, String)> {
+    use ConfigurationProperty as P;
+
+    #[allow(unused_mut)]
+    let mut values = vec![
+        (P::Width, format!("{:?}", config.width())),
+        (P::Height, format!("{:?}", config.height())),
+        (P::ScalesToFit, format!("{:?}", config.scales_to_fit())),
+        (P::SourceRect, format!("{:?}", config.source_rect())),
+        (
+            P::DestinationRect,
+            format!("{:?}", config.destination_rect()),
+        ),
+        (
+            P::PreservesAspectRatio,
+            format!("{:?}", config.preserves_aspect_ratio()),
+        ),
+        (P::ShowsCursor, format!("{:?}", config.shows_cursor())),
+        (P::QueueDepth, format!("{:?}", config.queue_depth())),
+        (
+            P::MinimumFrameInterval,
+            format!("{:?}", config.minimum_frame_interval()),
+        ),
+        (P::PixelFormat, format!("{:?}", config.pixel_format())),
+        (
+            P::BackgroundColor,
+            format!("{:?}", config.background_color()),
+        ),
+        (
+            P::ColorSpaceName,
+            format!("{:?}", config.color_space_name()),
+        ),
+        (P::ColorMatrix, format!("{:?}", config.color_matrix())),
+        (P::CapturesAudio, format!("{:?}", config.captures_audio())),
+        (P::SampleRate, format!("{:?}", config.sample_rate())),
+        (P::ChannelCount, format!("{:?}", config.channel_count())),
+        (
+            P::ExcludesCurrentProcessAudio,
+            format!("{:?}", config.excludes_current_process_audio()),
+        ),
+        (P::StreamName, format!("{:?}", config.stream_name())),
+    ];
+    #[cfg(feature = "macos_13_0")]
+    push_versioned_values(config, &mut values);
+    values
+}
+
+/// Append the properties that need a newer macOS to `values`.
+#[cfg(feature = "macos_13_0")]
+fn push_versioned_values(
+    config: &SCStreamConfiguration,
+    values: &mut Vec<(ConfigurationProperty, String)>,
+) {
+    use ConfigurationProperty as P;
+
+    values.push((
+        P::ShouldBeOpaque,
+        format!("{:?}", config.should_be_opaque()),
+    ));
+
+    #[cfg(feature = "macos_14_0")]
+    values.extend([
+        (
+            P::CaptureResolutionType,
+            format!("{:?}", config.capture_resolution_type()),
+        ),
+        (
+            P::IgnoresShadowsSingleWindow,
+            format!("{:?}", config.ignores_shadows_single_window()),
+        ),
+        (
+            P::IgnoresShadowsDisplay,
+            format!("{:?}", config.ignores_shadows_display()),
+        ),
+        (
+            P::IgnoreGlobalClipDisplay,
+            format!("{:?}", config.ignore_global_clip_display()),
+        ),
+        (
+            P::IgnoreGlobalClipSingleWindow,
+            format!("{:?}", config.ignore_global_clip_single_window()),
+        ),
+        (
+            P::CapturesShadowsOnly,
+            format!("{:?}", config.captures_shadows_only()),
+        ),
+    ]);
+
+    #[cfg(feature = "macos_14_2")]
+    values.extend([
+        (
+            P::IncludesChildWindows,
+            format!("{:?}", config.includes_child_windows()),
+        ),
+        (
+            P::PresenterOverlayPrivacyAlertSetting,
+            format!("{:?}", config.presenter_overlay_privacy_alert_setting()),
+        ),
+    ]);
+
+    #[cfg(feature = "macos_15_0")]
+    values.extend([
+        (
+            P::ShowsMouseClicks,
+            format!("{:?}", config.shows_mouse_clicks()),
+        ),
+        (
+            P::CaptureDynamicRange,
+            format!("{:?}", config.capture_dynamic_range()),
+        ),
+        (
+            P::CapturesMicrophone,
+            format!("{:?}", config.captures_microphone()),
+        ),
+        (
+            P::MicrophoneCaptureDeviceId,
+            format!("{:?}", config.microphone_capture_device_id()),
+        ),
+    ]);
+}
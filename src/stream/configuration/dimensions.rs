@@ -3,7 +3,8 @@
 //! This module provides methods to configure the output dimensions, scaling behavior,
 //! and source/destination rectangles for captured streams.
 
-use crate::cg::CGRect;
+use crate::cg::{CGRect, CGRectExt, CGSize};
+use crate::error::SCError;
 
 use super::internal::SCStreamConfiguration;
 
@@ -25,7 +26,7 @@ impl SCStreamConfiguration {
         // FFI expects isize; u32 may wrap on 32-bit platforms (acceptable)
         #[allow(clippy::cast_possible_wrap)]
         unsafe {
-            crate::ffi::sc_stream_configuration_set_width(self.as_ptr(), width as isize);
+            crate::ffi::sc_stream_configuration_set_width(self.access_mut().ptr(), width as isize);
         }
         self
     }
@@ -62,7 +63,7 @@ impl SCStreamConfiguration {
         // FFI returns isize but width is always positive and fits in u32
         #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
         unsafe {
-            crate::ffi::sc_stream_configuration_get_width(self.as_ptr()) as u32
+            crate::ffi::sc_stream_configuration_get_width(self.access().ptr()) as u32
         }
     }
 
@@ -83,7 +84,10 @@ impl SCStreamConfiguration {
         // FFI expects isize; u32 may wrap on 32-bit platforms (acceptable)
         #[allow(clippy::cast_possible_wrap)]
         unsafe {
-            crate::ffi::sc_stream_configuration_set_height(self.as_ptr(), height as isize);
+            crate::ffi::sc_stream_configuration_set_height(
+                self.access_mut().ptr(),
+                height as isize,
+            );
         }
         self
     }
@@ -120,14 +124,23 @@ impl SCStreamConfiguration {
         // FFI returns isize but height is always positive and fits in u32
         #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
         unsafe {
-            crate::ffi::sc_stream_configuration_get_height(self.as_ptr()) as u32
+            crate::ffi::sc_stream_configuration_get_height(self.access().ptr()) as u32
         }
     }
 
     /// Enable or disable scaling to fit the output dimensions
     ///
-    /// When enabled, the source content will be scaled to fit within the
-    /// configured width and height, potentially changing aspect ratio.
+    /// When enabled, the source content is scaled to the configured width and
+    /// height. When disabled, it is drawn at its native size and cropped or
+    /// padded to the output. Together with
+    /// [`set_preserves_aspect_ratio`](Self::set_preserves_aspect_ratio) this
+    /// picks how a window lands in a fixed-size frame such as 1920×1080:
+    ///
+    /// | `scales_to_fit` | `preserves_aspect_ratio` | Result |
+    /// |-----------------|--------------------------|--------|
+    /// | `true` | `true` | Letterboxed / pillarboxed, no distortion |
+    /// | `true` | `false` | Stretched to fill the frame |
+    /// | `false` | — | Native size, no scaling |
     ///
     /// # Examples
     ///
@@ -140,7 +153,10 @@ impl SCStreamConfiguration {
     /// ```
     pub fn set_scales_to_fit(&mut self, scales_to_fit: bool) -> &mut Self {
         unsafe {
-            crate::ffi::sc_stream_configuration_set_scales_to_fit(self.as_ptr(), scales_to_fit);
+            crate::ffi::sc_stream_configuration_set_scales_to_fit(
+                self.access_mut().ptr(),
+                scales_to_fit,
+            );
         }
         self
     }
@@ -154,13 +170,24 @@ impl SCStreamConfiguration {
 
     /// Check if scaling to fit is enabled
     pub fn scales_to_fit(&self) -> bool {
-        unsafe { crate::ffi::sc_stream_configuration_get_scales_to_fit(self.as_ptr()) }
+        unsafe { crate::ffi::sc_stream_configuration_get_scales_to_fit(self.access().ptr()) }
     }
 
     /// Set the source rectangle to capture
     ///
-    /// Defines which portion of the source content to capture. Coordinates are
-    /// relative to the source content's coordinate system.
+    /// Defines which portion of the source content to capture, e.g. one
+    /// corner of a display. The rectangle is in **points**, relative to the
+    /// top-left corner of the filter's content: on a 2× Retina display the
+    /// full-screen source rect of a 3024×1964 pixel display is
+    /// `(0, 0, 1512, 982)`. A rect with no area captures the whole content.
+    ///
+    /// The captured region is scaled into the output frame, so set
+    /// [`width`](Self::set_width) and [`height`](Self::set_height) to the
+    /// region's size times the display scale to keep it pixel-exact. Check
+    /// the rect against the content with
+    /// [`validate_rects`](Self::validate_rects) before starting a stream;
+    /// `ScreenCaptureKit` silently clamps or ignores rects that fall
+    /// outside it.
     ///
     /// # Examples
     ///
@@ -176,7 +203,7 @@ impl SCStreamConfiguration {
     pub fn set_source_rect(&mut self, source_rect: CGRect) -> &mut Self {
         unsafe {
             crate::ffi::sc_stream_configuration_set_source_rect(
-                self.as_ptr(),
+                self.access_mut().ptr(),
                 source_rect.origin.x,
                 source_rect.origin.y,
                 source_rect.size.width,
@@ -201,7 +228,7 @@ impl SCStreamConfiguration {
             let mut width = 0.0;
             let mut height = 0.0;
             crate::ffi::sc_stream_configuration_get_source_rect(
-                self.as_ptr(),
+                self.access().ptr(),
                 &mut x,
                 &mut y,
                 &mut width,
@@ -214,7 +241,11 @@ impl SCStreamConfiguration {
     /// Set the destination rectangle for captured content
     ///
     /// Defines where the captured content will be placed in the output frame.
-    /// Useful for picture-in-picture or multi-source compositions.
+    /// Useful for picture-in-picture or multi-source compositions. Unlike the
+    /// [source rect](Self::set_source_rect), this rectangle is in **pixels**
+    /// of the output frame, measured from its top-left corner, and must fit
+    /// inside the configured width and height. A rect with no area fills
+    /// the whole frame.
     ///
     /// # Examples
     ///
@@ -230,7 +261,7 @@ impl SCStreamConfiguration {
     pub fn set_destination_rect(&mut self, destination_rect: CGRect) -> &mut Self {
         unsafe {
             crate::ffi::sc_stream_configuration_set_destination_rect(
-                self.as_ptr(),
+                self.access_mut().ptr(),
                 destination_rect.origin.x,
                 destination_rect.origin.y,
                 destination_rect.size.width,
@@ -255,7 +286,7 @@ impl SCStreamConfiguration {
             let mut width = 0.0;
             let mut height = 0.0;
             crate::ffi::sc_stream_configuration_get_destination_rect(
-                self.as_ptr(),
+                self.access().ptr(),
                 &mut x,
                 &mut y,
                 &mut width,
@@ -265,10 +296,80 @@ impl SCStreamConfiguration {
         }
     }
 
+    /// Check the source and destination rects before starting a stream
+    ///
+    /// `content_size` is the size in points of the content the filter
+    /// captures, e.g. a display's frame size. The source rect must lie
+    /// inside it and the destination rect inside the configured output
+    /// size; rects with no area are treated as unset and pass.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use screencapturekit::cg::{CGRect, CGSize};
+    /// use screencapturekit::prelude::*;
+    ///
+    /// let config = SCStreamConfiguration::new()
+    ///     .with_width(1512)
+    ///     .with_height(982)
+    ///     .with_source_rect(CGRect::new(756.0, 491.0, 756.0, 491.0));
+    /// assert!(config.validate_rects(CGSize::new(1512.0, 982.0)).is_ok());
+    /// assert!(config.validate_rects(CGSize::new(1280.0, 800.0)).is_err());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidConfiguration`] if either rect has a
+    /// non-finite or negative component, or doesn't fit where it has to.
+    pub fn validate_rects(&self, content_size: CGSize) -> Result<(), SCError> {
+        check_rect(
+            "source rect",
+            self.source_rect(),
+            content_size,
+            "content",
+            "points",
+        )?;
+        let output = CGSize::new(f64::from(self.width()), f64::from(self.height()));
+        check_rect(
+            "destination rect",
+            self.destination_rect(),
+            output,
+            "output frame",
+            "pixels",
+        )
+    }
+
+    /// Check the source and destination rects against `filter`'s content
+    /// rect (macOS 14.2+)
+    ///
+    /// Same as [`validate_rects`](Self::validate_rects) with the size of
+    /// [`SCContentFilter::content_rect`](crate::stream::content_filter::SCContentFilter::content_rect).
+    /// If the filter doesn't report a content rect, only the shape of the
+    /// source rect is checked.
+    ///
+    /// # Errors
+    ///
+    /// As for [`validate_rects`](Self::validate_rects).
+    #[cfg(feature = "macos_14_2")]
+    pub fn validate_rects_for(
+        &self,
+        filter: &crate::stream::content_filter::SCContentFilter,
+    ) -> Result<(), SCError> {
+        let content = filter.content_rect().standardized();
+        let size = if content.has_no_area() {
+            CGSize::new(f64::INFINITY, f64::INFINITY)
+        } else {
+            content.size
+        };
+        self.validate_rects(size)
+    }
+
     /// Preserve aspect ratio when scaling
     ///
     /// When enabled, the content will be scaled while maintaining its original
-    /// aspect ratio, potentially adding letterboxing or pillarboxing.
+    /// aspect ratio, potentially adding letterboxing or pillarboxing. When
+    /// disabled and [`scales_to_fit`](Self::scales_to_fit) is on, the content
+    /// is stretched to the output size instead.
     ///
     /// Note: This property requires macOS 14.0+. On older versions, the setter
     /// is a no-op and the getter returns `false`.
@@ -286,7 +387,7 @@ impl SCStreamConfiguration {
     pub fn set_preserves_aspect_ratio(&mut self, preserves_aspect_ratio: bool) -> &mut Self {
         unsafe {
             crate::ffi::sc_stream_configuration_set_preserves_aspect_ratio(
-                self.as_ptr(),
+                self.access_mut().ptr(),
                 preserves_aspect_ratio,
             );
         }
@@ -302,6 +403,47 @@ impl SCStreamConfiguration {
 
     /// Check if aspect ratio preservation is enabled
     pub fn preserves_aspect_ratio(&self) -> bool {
-        unsafe { crate::ffi::sc_stream_configuration_get_preserves_aspect_ratio(self.as_ptr()) }
+        unsafe {
+            crate::ffi::sc_stream_configuration_get_preserves_aspect_ratio(self.access().ptr())
+        }
+    }
+}
+
+/// Check that `rect` is unset or lies inside a `bounds`-sized area at the
+/// origin.
+fn check_rect(
+    name: &str,
+    rect: CGRect,
+    bounds: CGSize,
+    within: &str,
+    unit: &str,
+) -> Result<(), SCError> {
+    // `CGRectNull` has an infinite origin but no area; it means unset too.
+    if rect.size.width.is_finite() && rect.size.height.is_finite() && rect.has_no_area() {
+        return Ok(());
+    }
+    let values = [
+        rect.origin.x,
+        rect.origin.y,
+        rect.size.width,
+        rect.size.height,
+    ];
+    if values.iter().any(|value| !value.is_finite()) {
+        return Err(SCError::invalid_config(format!(
+            "{name} {rect:?} has a non-finite component"
+        )));
+    }
+    if rect.size.width < 0.0 || rect.size.height < 0.0 {
+        return Err(SCError::invalid_config(format!(
+            "{name} {rect:?} has a negative size"
+        )));
+    }
+    let area = CGRect::new(0.0, 0.0, bounds.width, bounds.height);
+    if !area.contains_rect(&rect) {
+        return Err(SCError::invalid_config(format!(
+            "{name} {rect:?} lies outside the {}x{} {unit} {within}",
+            bounds.width, bounds.height
+        )));
     }
+    Ok(())
 }
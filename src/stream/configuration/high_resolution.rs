@@ -0,0 +1,163 @@
+//! Capturing 5K and 6K displays within a size and memory budget
+//!
+//! A 6K display is 6016×3384 pixels: 81 MB per BGRA frame, and
+//! `ScreenCaptureKit` keeps up to [`queue_depth`](SCStreamConfiguration::queue_depth)
+//! of them in flight. An encoder that falls a little behind holds on to
+//! several surfaces at once, the pool runs dry and frames are dropped, or
+//! the process's memory climbs by hundreds of megabytes.
+//!
+//! [`with_display_size_capped`](SCStreamConfiguration::with_display_size_capped)
+//! asks `ScreenCaptureKit` to downscale while capturing, which is far
+//! cheaper than scaling full-size frames afterwards, and
+//! [`with_memory_budget`](SCStreamConfiguration::with_memory_budget) picks
+//! the deepest queue that fits a byte budget.
+//!
+//! ```no_run
+//! use screencapturekit::prelude::*;
+//! use screencapturekit::stream::configuration::PixelFormat;
+//!
+//! # fn example() -> Result<(), SCError> {
+//! let content = SCShareableContent::get()?;
+//! let display = &content.displays()[0];
+//! let config = SCStreamConfiguration::new()
+//!     .with_pixel_format(PixelFormat::YCbCr_420v)
+//!     .with_display_size_capped(display, 3840)
+//!     .with_memory_budget(128 * 1024 * 1024);
+//! println!(
+//!     "{}x{}, queue depth {}",
+//!     config.width(),
+//!     config.height(),
+//!     config.queue_depth()
+//! );
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::cv::frame_copy::bytes_per_element;
+use crate::shareable_content::SCDisplay;
+use crate::stream::display_follower::DisplayMode;
+
+use super::internal::SCStreamConfiguration;
+use super::PixelFormat;
+
+/// Fewest frames `ScreenCaptureKit` accepts as a queue depth.
+pub const MIN_QUEUE_DEPTH: u32 = 3;
+
+/// Most frames `ScreenCaptureKit` accepts as a queue depth.
+pub const MAX_QUEUE_DEPTH: u32 = 8;
+
+/// Scale `width`×`height` down so neither side exceeds `max_dimension`,
+/// keeping the aspect ratio.
+///
+/// Scaled sides are rounded to even numbers, which 4:2:0 formats and most
+/// video encoders require. Sizes that already fit, and a `max_dimension`
+/// of 0, are returned unchanged; nothing is scaled up.
+///
+/// ```
+/// use screencapturekit::stream::configuration::high_resolution::fit_within;
+///
+/// assert_eq!(fit_within(6016, 3384, 3840), (3840, 2160));
+/// assert_eq!(fit_within(1920, 1080, 3840), (1920, 1080));
+/// ```
+#[must_use]
+pub fn fit_within(width: u32, height: u32, max_dimension: u32) -> (u32, u32) {
+    let long = width.max(height);
+    if max_dimension == 0 || long <= max_dimension {
+        return (width, height);
+    }
+    let scale = |side: u32| {
+        let scaled =
+            (u64::from(side) * u64::from(max_dimension) + u64::from(long) / 2) / u64::from(long);
+        u32::try_from(scaled).map_or(max_dimension, |scaled| (scaled & !1).max(2))
+    };
+    (scale(width), scale(height))
+}
+
+/// Bytes one `width`×`height` frame occupies in `format`, without row
+/// padding.
+///
+/// Formats this crate can't lay out are counted at 4 bytes per pixel.
+#[must_use]
+pub fn frame_bytes(width: u32, height: u32, format: PixelFormat) -> u64 {
+    let (width, height) = (u64::from(width), u64::from(height));
+    match format {
+        PixelFormat::YCbCr_420v | PixelFormat::YCbCr_420f => {
+            width * height + width.div_ceil(2) * height.div_ceil(2) * 2
+        }
+        other => {
+            let per_pixel =
+                bytes_per_element(other).map_or(4, |planes| planes.iter().sum::<usize>());
+            width * height * u64::try_from(per_pixel).unwrap_or(4)
+        }
+    }
+}
+
+/// The deepest queue, within `ScreenCaptureKit`'s limits, whose frames of
+/// `frame_bytes` each fit in `budget` bytes.
+///
+/// Never returns less than [`MIN_QUEUE_DEPTH`], even when that exceeds the
+/// budget; lower the capture size to go further.
+#[must_use]
+pub fn queue_depth_for_budget(frame_bytes: u64, budget: u64) -> u32 {
+    let frames = budget.checked_div(frame_bytes).unwrap_or(u64::MAX);
+    u32::try_from(frames)
+        .unwrap_or(MAX_QUEUE_DEPTH)
+        .clamp(MIN_QUEUE_DEPTH, MAX_QUEUE_DEPTH)
+}
+
+impl SCStreamConfiguration {
+    /// Size the output to `display`'s native pixel resolution, scaled down
+    /// so neither side exceeds `max_dimension`
+    ///
+    /// Turns on [`scales_to_fit`](Self::scales_to_fit) and
+    /// [`preserves_aspect_ratio`](Self::preserves_aspect_ratio) so
+    /// `ScreenCaptureKit` does the scaling. Uses the display's size in
+    /// points if its current mode can't be read. See [`fit_within`] for the
+    /// rounding.
+    pub fn set_display_size_capped(
+        &mut self,
+        display: &SCDisplay,
+        max_dimension: u32,
+    ) -> &mut Self {
+        let (width, height) = DisplayMode::current(display.display_id()).map_or_else(
+            || (display.width(), display.height()),
+            |mode| (mode.pixel_width(), mode.pixel_height()),
+        );
+        let (width, height) = fit_within(width, height, max_dimension);
+        self.set_width(width)
+            .set_height(height)
+            .set_scales_to_fit(true)
+            .set_preserves_aspect_ratio(true)
+    }
+
+    /// Size the output to `display`, capped at `max_dimension` (builder
+    /// pattern)
+    #[must_use]
+    pub fn with_display_size_capped(mut self, display: &SCDisplay, max_dimension: u32) -> Self {
+        self.set_display_size_capped(display, max_dimension);
+        self
+    }
+
+    /// Bytes one frame at the configured size and pixel format occupies
+    #[must_use]
+    pub fn estimated_frame_bytes(&self) -> u64 {
+        frame_bytes(self.width(), self.height(), self.pixel_format())
+    }
+
+    /// Pick the deepest queue whose frames fit in `budget` bytes
+    ///
+    /// Based on the configured size and pixel format, so set those first.
+    /// See [`queue_depth_for_budget`].
+    pub fn set_memory_budget(&mut self, budget: u64) -> &mut Self {
+        let depth = queue_depth_for_budget(self.estimated_frame_bytes(), budget);
+        self.set_queue_depth(depth)
+    }
+
+    /// Pick the deepest queue whose frames fit in `budget` bytes (builder
+    /// pattern)
+    #[must_use]
+    pub fn with_memory_budget(mut self, budget: u64) -> Self {
+        self.set_memory_budget(budget);
+        self
+    }
+}
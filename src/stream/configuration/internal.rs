@@ -1,6 +1,8 @@
 use std::ffi::c_void;
 use std::fmt;
 
+use crate::utils::object_lock::{self, ObjectAccess};
+
 /// Opaque wrapper around `SCStreamConfiguration`
 ///
 /// Configuration for a screen capture stream, including dimensions,
@@ -15,12 +17,30 @@ use std::fmt;
 ///     .with_width(1920)
 ///     .with_height(1080);
 /// ```
+///
+/// # Thread Safety
+///
+/// [`Clone`] shares the underlying Objective-C object, and that object is not
+/// thread-safe. Accesses through clones are serialized; in debug builds, a
+/// setter that overlaps another access from a different thread panics, since
+/// the outcome would depend on timing. Give each thread its own
+/// [`deep_copy`](Self::deep_copy) when they need to change settings
+/// independently. Streams keep a private copy of the configuration they are
+/// given, so changing it after [`SCStream::new`](crate::stream::SCStream::new)
+/// or during an update never affects a running stream.
 #[repr(transparent)]
 pub struct SCStreamConfiguration(pub(crate) *const c_void);
 
+/// Configurations compare by value: every property [`diff`](Self::diff)
+/// reads must match. Check `new_config != current` before
+/// [`update_configuration`](crate::stream::SCStream::update_configuration)
+/// to skip updates that would only cost the stream a hiccup.
+///
+/// The hash is taken from the same properties, so a configuration changed
+/// through a setter after being put in a `HashSet` is lost there.
 impl PartialEq for SCStreamConfiguration {
     fn eq(&self, other: &Self) -> bool {
-        self.0 == other.0
+        self.0 == other.0 || self.diff(other).is_empty()
     }
 }
 
@@ -28,7 +48,7 @@ impl Eq for SCStreamConfiguration {}
 
 impl std::hash::Hash for SCStreamConfiguration {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        self.0.hash(state);
+        super::diff::property_values(self).hash(state);
     }
 }
 
@@ -43,6 +63,21 @@ impl SCStreamConfiguration {
     pub(crate) fn as_ptr(&self) -> *const c_void {
         self.0
     }
+
+    /// Shared access for one FFI getter call; see [`object_lock`].
+    pub(crate) fn access(&self) -> ObjectAccess {
+        object_lock::read(self.0, "SCStreamConfiguration")
+    }
+
+    /// Exclusive access for one FFI setter call; see [`object_lock`].
+    ///
+    /// Only the pointer is read, but the `&mut` receiver lets the borrow
+    /// checker rule out overlaps on a single handle, leaving the runtime
+    /// check for clones.
+    #[allow(clippy::needless_pass_by_ref_mut)]
+    pub(crate) fn access_mut(&mut self) -> ObjectAccess {
+        object_lock::write(self.0, "SCStreamConfiguration")
+    }
 }
 
 // `Clone::clone` is not a `memcpy`: it crosses the Swift FFI boundary and calls
@@ -5,12 +5,15 @@ pub mod audio;
 pub mod captured_elements;
 pub mod captured_frames;
 pub mod colors;
+pub mod diff;
 pub mod dimensions;
+pub mod high_resolution;
 pub mod pixel_format;
 pub mod stream_properties;
 
 pub use advanced::SCPresenterOverlayAlertSetting;
-pub use audio::{AudioChannelCount, AudioSampleRate};
+pub use audio::{AudioChannelCount, AudioFormat, AudioSampleRate};
+pub use diff::{ConfigurationChange, ConfigurationProperty, SCStreamConfigurationDelta};
 pub use internal::SCStreamConfiguration;
 pub use pixel_format::PixelFormat;
 pub use stream_properties::SCCaptureDynamicRange;
@@ -114,6 +117,28 @@ impl SCStreamConfiguration {
         }
     }
 
+    /// Create an independent copy of this configuration.
+    ///
+    /// [`Clone`] only retains the underlying Objective-C object, so a clone
+    /// observes later mutations made through the original. `deep_copy`
+    /// copies every property into a new object instead, which is what you
+    /// want when keeping a snapshot to [`diff`](Self::diff) against.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use screencapturekit::prelude::*;
+    ///
+    /// let mut config = SCStreamConfiguration::new().with_width(1920);
+    /// let snapshot = config.deep_copy();
+    /// config.set_width(1280);
+    /// assert_eq!(snapshot.width(), 1920);
+    /// ```
+    #[must_use]
+    pub fn deep_copy(&self) -> Self {
+        Self(unsafe { crate::ffi::sc_stream_configuration_copy(self.access().ptr()) })
+    }
+
     #[cfg(feature = "macos_15_0")]
     pub(crate) unsafe fn from_ptr(ptr: *const std::ffi::c_void) -> Self {
         Self(ptr)
@@ -37,13 +37,13 @@ impl SCStreamConfiguration {
             if let Some(stream_name) = name {
                 if let Ok(c_name) = std::ffi::CString::new(stream_name) {
                     crate::ffi::sc_stream_configuration_set_stream_name(
-                        self.as_ptr(),
+                        self.access_mut().ptr(),
                         c_name.as_ptr(),
                     );
                 }
             } else {
                 crate::ffi::sc_stream_configuration_set_stream_name(
-                    self.as_ptr(),
+                    self.access_mut().ptr(),
                     std::ptr::null(),
                 );
             }
@@ -64,7 +64,7 @@ impl SCStreamConfiguration {
     pub fn stream_name(&self) -> Option<String> {
         unsafe {
             ffi_string_from_buffer(SMALL_BUFFER_SIZE, |buf, len| {
-                crate::ffi::sc_stream_configuration_get_stream_name(self.as_ptr(), buf, len)
+                crate::ffi::sc_stream_configuration_get_stream_name(self.access().ptr(), buf, len)
             })
         }
     }
@@ -97,7 +97,7 @@ impl SCStreamConfiguration {
     pub fn set_capture_dynamic_range(&mut self, dynamic_range: SCCaptureDynamicRange) -> &mut Self {
         unsafe {
             crate::ffi::sc_stream_configuration_set_capture_dynamic_range(
-                self.as_ptr(),
+                self.access_mut().ptr(),
                 dynamic_range as i32,
             );
         }
@@ -119,8 +119,9 @@ impl SCStreamConfiguration {
     /// Requires the `macos_15_0` feature flag to be enabled.
     #[cfg(feature = "macos_15_0")]
     pub fn capture_dynamic_range(&self) -> SCCaptureDynamicRange {
-        let value =
-            unsafe { crate::ffi::sc_stream_configuration_get_capture_dynamic_range(self.as_ptr()) };
+        let value = unsafe {
+            crate::ffi::sc_stream_configuration_get_capture_dynamic_range(self.access().ptr())
+        };
         match value {
             1 => SCCaptureDynamicRange::HDRLocalDisplay,
             2 => SCCaptureDynamicRange::HDRCanonicalDisplay,
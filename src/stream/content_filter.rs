@@ -13,25 +13,74 @@
 //! let display = &content.displays()[0];
 //!
 //! // Capture entire display
-//! let filter = SCContentFilter::create()
-//!     .with_display(display)
+//! let filter = SCContentFilter::for_display(display)
 //!     .with_excluding_windows(&[])
 //!     .build();
 //! # Ok(())
 //! # }
 //! ```
+//!
+//! # Windows on other Spaces, full-screen apps and off-screen windows
+//!
+//! Single-window filters are created with
+//! `SCContentFilter(desktopIndependentWindow:)`. The window is captured on its
+//! own, independent of where it sits on the desktop:
+//!
+//! - windows on another Space or inside another app's full-screen Space keep
+//!   delivering frames, as long as the window server still renders them;
+//! - occluding windows are not included — only the target window's pixels;
+//! - minimized and hidden windows stop producing new frames (the stream
+//!   reports idle frames) until they are shown again.
+//!
+//! [`SCShareableContent::get`](crate::shareable_content::SCShareableContent::get)
+//! only lists on-screen windows by default, so a window on another Space may
+//! be missing from it. Query with
+//! [`with_on_screen_windows_only(false)`](crate::shareable_content::SCShareableContentOptions::with_on_screen_windows_only)
+//! to find it, or use [`SCContentFilter::for_window_id`], which does that for
+//! you.
+//!
+//! The login window, lock screen and other system-secure surfaces are never
+//! capturable.
+//!
+//! # Menu bar, Dock and wallpaper
+//!
+//! Display filters can leave out the desktop's furniture:
+//!
+//! ```no_run
+//! use screencapturekit::prelude::*;
+//!
+//! # fn example() -> Result<(), SCError> {
+//! let content = SCShareableContent::get()?;
+//! let filter = SCContentFilter::for_display(&content.displays()[0])
+//!     .with_dock(false)
+//!     .with_wallpaper(false)
+//!     .try_build()?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The menu bar is an `SCContentFilter` property (macOS 14.2+, see
+//! [`DisplayFilterBuilder::with_menu_bar`]). The Dock and wallpaper are not:
+//! their windows are looked up by owning application when the filter is
+//! built and excluded like any other window. The same lookup applies an
+//! [`ExclusionPolicy`], a saved list of applications (password managers,
+//! say) to keep out of every capture.
 
 use std::ffi::c_void;
 use std::fmt;
+use std::marker::PhantomData;
+use std::sync::Arc;
 
 #[cfg(feature = "macos_14_2")]
 use crate::cg::CGRect;
 use crate::{
     error::{SCError, SCResult},
     ffi,
-    shareable_content::{SCDisplay, SCRunningApplication, SCWindow},
+    shareable_content::{SCDisplay, SCRunningApplication, SCShareableContent, SCWindow},
 };
 
+use super::exclusion_policy::ExclusionPolicy;
+
 /// Content filter for `ScreenCaptureKit` streams
 ///
 /// Defines what content to capture (displays, windows, or applications).
@@ -47,24 +96,44 @@ use crate::{
 /// let display = &content.displays()[0];
 ///
 /// // Capture entire display
-/// let filter = SCContentFilter::create()
-///     .with_display(display)
+/// let filter = SCContentFilter::for_display(display)
 ///     .with_excluding_windows(&[])
 ///     .build();
 ///
 /// // Or capture a specific window
 /// let window = &content.windows()[0];
-/// let filter = SCContentFilter::create()
-///     .with_window(window)
+/// let filter = SCContentFilter::for_window(window)
 ///     .build();
 /// # Ok(())
 /// # }
 /// ```
-pub struct SCContentFilter(*const c_void);
+pub struct SCContentFilter {
+    ptr: *const c_void,
+    /// Content info snapshot taken by
+    /// [`SCContentFilterBuilder::with_content_info`].
+    #[cfg(feature = "macos_14_0")]
+    dimensions: Option<SCFilterDimensions>,
+    /// Whether `SCContentSharingPicker` produced this filter.
+    from_picker: bool,
+    /// What the builder selected; `None` for picked filters.
+    selection: Option<Arc<FilterSelection>>,
+}
 
+/// Filters compare by what they capture: two filters built separately from
+/// the same display and windows are equal, so an app can skip an
+/// [`update_content_filter`](crate::stream::SCStream::update_content_filter)
+/// that would change nothing. The content rect and menu bar setting count
+/// where the enabled features can read them back. Filters from
+/// `SCContentSharingPicker` only equal their own clones.
 impl PartialEq for SCContentFilter {
     fn eq(&self, other: &Self) -> bool {
-        self.0 == other.0
+        if self.ptr == other.ptr {
+            return true;
+        }
+        match (&self.selection, &other.selection) {
+            (Some(a), Some(b)) => a == b && self.settings() == other.settings(),
+            _ => false,
+        }
     }
 }
 
@@ -72,16 +141,87 @@ impl Eq for SCContentFilter {}
 
 impl std::hash::Hash for SCContentFilter {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        self.0.hash(state);
+        if let Some(selection) = &self.selection {
+            selection.hash(state);
+            self.settings().hash(state);
+        } else {
+            self.ptr.hash(state);
+        }
+    }
+}
+
+/// The content a built filter captures, by ID.
+#[derive(Debug, PartialEq, Eq, Hash)]
+struct FilterSelection {
+    kind: SelectionKind,
+    display_id: Option<u32>,
+    /// Sorted, since the order windows were passed in doesn't matter.
+    window_ids: Vec<u32>,
+    /// Sorted, like `window_ids`.
+    process_ids: Vec<i32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum SelectionKind {
+    Window,
+    DisplayExcluding,
+    DisplayIncluding,
+    DisplayIncludingApplications,
+    DisplayExcludingApplications,
+}
+
+impl FilterSelection {
+    fn new(
+        kind: SelectionKind,
+        display: Option<&SCDisplay>,
+        windows: &[SCWindow],
+        applications: &[SCRunningApplication],
+    ) -> Self {
+        let mut window_ids: Vec<u32> = windows.iter().map(SCWindow::window_id).collect();
+        window_ids.sort_unstable();
+        window_ids.dedup();
+        let mut process_ids: Vec<i32> = applications
+            .iter()
+            .map(SCRunningApplication::process_id)
+            .collect();
+        process_ids.sort_unstable();
+        process_ids.dedup();
+        Self {
+            kind,
+            display_id: display.map(SCDisplay::display_id),
+            window_ids,
+            process_ids,
+        }
     }
 }
 
 // Note: We intentionally do NOT implement Default for SCContentFilter.
 // A null filter would cause panics/crashes when used with SCStream.
-// Users should always use SCContentFilter::create() to create valid filters.
+// Users should always use SCContentFilter::for_display() or
+// SCContentFilter::for_window() to create valid filters.
 
 impl SCContentFilter {
-    /// Creates a content filter builder
+    /// Creates an untyped content filter builder
+    ///
+    /// This builder accepts any combination of calls, including ones that
+    /// only fail (or are silently ignored) at build time. Use
+    /// [`for_display`](Self::for_display) or [`for_window`](Self::for_window)
+    /// instead, which only offer the calls that make sense for the chosen
+    /// content.
+    #[must_use]
+    #[deprecated(
+        since = "8.1.0",
+        note = "Use SCContentFilter::for_display() or SCContentFilter::for_window() instead"
+    )]
+    pub fn create() -> SCContentFilterBuilder {
+        SCContentFilterBuilder::new()
+    }
+
+    /// Starts a filter that captures `display`
+    ///
+    /// Build it as is to capture the whole display, or narrow it down with
+    /// exactly one of the window or application selections on
+    /// [`DisplayFilterBuilder`].
     ///
     /// # Examples
     ///
@@ -92,16 +232,136 @@ impl SCContentFilter {
     /// let content = SCShareableContent::get()?;
     /// let display = &content.displays()[0];
     ///
-    /// let filter = SCContentFilter::create()
-    ///     .with_display(display)
-    ///     .with_excluding_windows(&[])
+    /// let filter = SCContentFilter::for_display(display).build();
+    ///
+    /// let window = &content.windows()[0];
+    /// let filter = SCContentFilter::for_display(display)
+    ///     .with_excluding_windows(&[window])
     ///     .build();
     /// # Ok(())
     /// # }
     /// ```
     #[must_use]
-    pub fn create() -> SCContentFilterBuilder {
-        SCContentFilterBuilder::new()
+    pub fn for_display(display: &SCDisplay) -> DisplayFilterBuilder {
+        DisplayFilterBuilder::new(SCContentFilterBuilder::new().with_display(display))
+    }
+
+    /// Starts a filter that captures a single window, independent of the
+    /// desktop it is on
+    ///
+    /// See the
+    /// [module docs](self#windows-on-other-spaces-full-screen-apps-and-off-screen-windows)
+    /// for what a desktop-independent window filter captures.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use screencapturekit::prelude::*;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let content = SCShareableContent::get()?;
+    /// let window = &content.windows()[0];
+    /// let filter = SCContentFilter::for_window(window).build();
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn for_window(window: &SCWindow) -> WindowFilterBuilder {
+        WindowFilterBuilder {
+            inner: SCContentFilterBuilder::new().with_window(window),
+        }
+    }
+
+    /// Creates a filter that captures a single window by its window ID,
+    /// wherever it is.
+    ///
+    /// Unlike [`SCShareableContent::get`](crate::shareable_content::SCShareableContent::get),
+    /// the lookup includes off-screen windows, so this finds windows on other
+    /// Spaces and in other apps' full-screen Spaces. The filter is a
+    /// desktop-independent window filter; see the
+    /// [module docs](self#windows-on-other-spaces-full-screen-apps-and-off-screen-windows)
+    /// for what that captures.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::WindowNotFound`] if no shareable window has that ID,
+    /// or the error from fetching shareable content.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use screencapturekit::prelude::*;
+    ///
+    /// # fn example(window_id: u32) -> Result<(), SCError> {
+    /// let filter = SCContentFilter::for_window_id(window_id)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn for_window_id(window_id: u32) -> SCResult<Self> {
+        let content = crate::shareable_content::SCShareableContent::create()
+            .with_on_screen_windows_only(false)
+            .get()?;
+        let window = content
+            .windows()
+            .into_iter()
+            .find(|w| w.window_id() == window_id)
+            .ok_or_else(|| SCError::WindowNotFound(format!("window {window_id}")))?;
+        Ok(Self::for_window(&window).build())
+    }
+
+    /// Creates a filter that captures every window of an application on
+    /// `display`.
+    ///
+    /// All running processes with `bundle_id` are included, so apps that run
+    /// several processes under one bundle identifier are captured whole.
+    /// Windows the application opens later are part of the filter's
+    /// application set; to also follow the app across relaunches (a new
+    /// process) keep the stream's filter current with an
+    /// [`ApplicationFollower`](crate::stream::application_follower::ApplicationFollower).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::ApplicationNotFound`] if no running application has
+    /// that bundle identifier, or the error from fetching shareable content.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use screencapturekit::prelude::*;
+    ///
+    /// # fn example() -> Result<(), SCError> {
+    /// let content = SCShareableContent::get()?;
+    /// let display = &content.displays()[0];
+    /// let filter = SCContentFilter::for_application(display, "com.apple.Safari")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn for_application(display: &SCDisplay, bundle_id: &str) -> SCResult<Self> {
+        let content = crate::shareable_content::SCShareableContent::create()
+            .with_on_screen_windows_only(false)
+            .get()?;
+        Self::for_application_in(&content, display, bundle_id)
+    }
+
+    /// [`for_application`](Self::for_application) against content the caller
+    /// already fetched.
+    pub(crate) fn for_application_in(
+        content: &crate::shareable_content::SCShareableContent,
+        display: &SCDisplay,
+        bundle_id: &str,
+    ) -> SCResult<Self> {
+        let applications: Vec<SCRunningApplication> = content
+            .applications()
+            .into_iter()
+            .filter(|app| app.bundle_identifier() == bundle_id)
+            .collect();
+        if applications.is_empty() {
+            return Err(SCError::ApplicationNotFound(bundle_id.to_string()));
+        }
+        let app_refs: Vec<&SCRunningApplication> = applications.iter().collect();
+        Ok(Self::for_display(display)
+            .with_including_applications(&app_refs, &[])
+            .build())
     }
 
     /// Creates a content filter from a picker-returned pointer
@@ -109,12 +369,52 @@ impl SCContentFilter {
     /// This is used internally when the content sharing picker returns a filter.
     #[cfg(feature = "macos_14_0")]
     pub(crate) fn from_picker_ptr(ptr: *const c_void) -> Self {
-        Self(ptr)
+        let mut filter = Self::from_ptr(ptr);
+        filter.from_picker = true;
+        filter
+    }
+
+    fn from_ptr(ptr: *const c_void) -> Self {
+        Self {
+            ptr,
+            #[cfg(feature = "macos_14_0")]
+            dimensions: None,
+            from_picker: false,
+            selection: None,
+        }
+    }
+
+    /// Settings that can change after building, as comparable bits.
+    #[allow(clippy::unused_self)]
+    fn settings(&self) -> Vec<u64> {
+        #[allow(unused_mut)]
+        let mut bits = Vec::new();
+        #[cfg(feature = "macos_14_2")]
+        {
+            let rect = self.content_rect();
+            bits.extend([
+                rect.origin.x.to_bits(),
+                rect.origin.y.to_bits(),
+                rect.size.width.to_bits(),
+                rect.size.height.to_bits(),
+                u64::from(self.include_menu_bar()),
+            ]);
+        }
+        bits
     }
 
     /// Returns the raw pointer to the content filter
     pub(crate) fn as_ptr(&self) -> *const c_void {
-        self.0
+        self.ptr
+    }
+
+    /// Whether the user chose this content in `SCContentSharingPicker`
+    ///
+    /// Picked filters can be captured without Screen Recording permission;
+    /// see [`CaptureAccess`](crate::stream::permission_watcher::CaptureAccess).
+    #[must_use]
+    pub const fn is_from_picker(&self) -> bool {
+        self.from_picker
     }
 
     /// Sets the content rectangle for this filter (macOS 14.2+)
@@ -122,10 +422,11 @@ impl SCContentFilter {
     /// Specifies the rectangle within the content filter to capture.
     #[cfg(feature = "macos_14_2")]
     #[must_use]
-    pub fn set_content_rect(self, rect: CGRect) -> Self {
+    pub fn set_content_rect(mut self, rect: CGRect) -> Self {
+        self.dimensions = None;
         unsafe {
             ffi::sc_content_filter_set_content_rect(
-                self.0,
+                self.ptr,
                 rect.origin.x,
                 rect.origin.y,
                 rect.size.width,
@@ -144,7 +445,7 @@ impl SCContentFilter {
             let mut width = 0.0;
             let mut height = 0.0;
             ffi::sc_content_filter_get_content_rect(
-                self.0,
+                self.ptr,
                 &mut x,
                 &mut y,
                 &mut width,
@@ -159,7 +460,7 @@ impl SCContentFilter {
     /// Returns the type of content being captured (window, display, application, or none).
     #[cfg(feature = "macos_14_0")]
     pub fn style(&self) -> SCShareableContentStyle {
-        let value = unsafe { ffi::sc_content_filter_get_style(self.0) };
+        let value = unsafe { ffi::sc_content_filter_get_style(self.ptr) };
         SCShareableContentStyle::from(value)
     }
 
@@ -168,17 +469,58 @@ impl SCContentFilter {
     /// Returns whether this filter captures a window or a display.
     #[cfg(feature = "macos_14_0")]
     pub fn stream_type(&self) -> SCStreamType {
-        let value = unsafe { ffi::sc_content_filter_get_stream_type(self.0) };
+        let value = unsafe { ffi::sc_content_filter_get_stream_type(self.ptr) };
         SCStreamType::from(value)
     }
 
+    /// Native pixel size of the filtered content (macOS 14.0+)
+    ///
+    /// Size a stream with this instead of a hardcoded resolution so Retina
+    /// content is captured at full detail rather than silently scaled:
+    ///
+    /// ```no_run
+    /// use screencapturekit::prelude::*;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let content = SCShareableContent::get()?;
+    /// let filter = SCContentFilter::for_display(&content.displays()[0])
+    ///     .with_excluding_windows(&[])
+    ///     .with_content_info()
+    ///     .build();
+    ///
+    /// let (width, height) = filter.recommended_dimensions().unwrap_or((1920, 1080));
+    /// let config = SCStreamConfiguration::new()
+    ///     .with_width(width)
+    ///     .with_height(height);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Uses the snapshot taken by
+    /// [`SCContentFilterBuilder::with_content_info`] when there is one, and
+    /// asks `SCShareableContentInfo` otherwise. Returns `None` if the size is
+    /// unknown or zero.
+    #[cfg(feature = "macos_14_0")]
+    pub fn recommended_dimensions(&self) -> Option<(u32, u32)> {
+        self.content_dimensions().map(|d| (d.width, d.height))
+    }
+
+    /// Pixel size and scale of the filtered content (macOS 14.0+)
+    ///
+    /// Same source as [`recommended_dimensions`](Self::recommended_dimensions),
+    /// with the point-to-pixel scale alongside.
+    #[cfg(feature = "macos_14_0")]
+    pub fn content_dimensions(&self) -> Option<SCFilterDimensions> {
+        self.dimensions.or_else(|| SCFilterDimensions::query(self))
+    }
+
     /// Get the point-to-pixel scale factor (macOS 14.0+)
     ///
     /// Returns the scaling factor used to convert points to pixels.
     /// Typically 2.0 for Retina displays.
     #[cfg(feature = "macos_14_0")]
     pub fn point_pixel_scale(&self) -> f32 {
-        unsafe { ffi::sc_content_filter_get_point_pixel_scale(self.0) }
+        unsafe { ffi::sc_content_filter_get_point_pixel_scale(self.ptr) }
     }
 
     /// Include the menu bar in capture (macOS 14.2+)
@@ -187,15 +529,16 @@ impl SCContentFilter {
     /// This property has no effect for window filters.
     #[cfg(feature = "macos_14_2")]
     pub fn set_include_menu_bar(&mut self, include: bool) {
+        self.dimensions = None;
         unsafe {
-            ffi::sc_content_filter_set_include_menu_bar(self.0, include);
+            ffi::sc_content_filter_set_include_menu_bar(self.ptr, include);
         }
     }
 
     /// Check if menu bar is included in capture (macOS 14.2+)
     #[cfg(feature = "macos_14_2")]
     pub fn include_menu_bar(&self) -> bool {
-        unsafe { ffi::sc_content_filter_get_include_menu_bar(self.0) }
+        unsafe { ffi::sc_content_filter_get_include_menu_bar(self.ptr) }
     }
 
     /// Get included displays (macOS 15.2+)
@@ -203,7 +546,7 @@ impl SCContentFilter {
     /// Returns the displays currently included in this filter.
     #[cfg(feature = "macos_15_2")]
     pub fn included_displays(&self) -> Vec<SCDisplay> {
-        let count = unsafe { ffi::sc_content_filter_get_included_displays_count(self.0) };
+        let count = unsafe { ffi::sc_content_filter_get_included_displays_count(self.ptr) };
         if count <= 0 {
             return Vec::new();
         }
@@ -212,7 +555,7 @@ impl SCContentFilter {
             .filter_map(|i| {
                 #[allow(clippy::cast_possible_wrap)]
                 let ptr =
-                    unsafe { ffi::sc_content_filter_get_included_display_at(self.0, i as isize) };
+                    unsafe { ffi::sc_content_filter_get_included_display_at(self.ptr, i as isize) };
                 unsafe { SCDisplay::from_retained_ptr(ptr) }
             })
             .collect()
@@ -223,7 +566,7 @@ impl SCContentFilter {
     /// Returns the windows currently included in this filter.
     #[cfg(feature = "macos_15_2")]
     pub fn included_windows(&self) -> Vec<SCWindow> {
-        let count = unsafe { ffi::sc_content_filter_get_included_windows_count(self.0) };
+        let count = unsafe { ffi::sc_content_filter_get_included_windows_count(self.ptr) };
         if count <= 0 {
             return Vec::new();
         }
@@ -232,7 +575,7 @@ impl SCContentFilter {
             .filter_map(|i| {
                 #[allow(clippy::cast_possible_wrap)]
                 let ptr =
-                    unsafe { ffi::sc_content_filter_get_included_window_at(self.0, i as isize) };
+                    unsafe { ffi::sc_content_filter_get_included_window_at(self.ptr, i as isize) };
                 unsafe { SCWindow::from_retained_ptr(ptr) }
             })
             .collect()
@@ -243,7 +586,7 @@ impl SCContentFilter {
     /// Returns the applications currently included in this filter.
     #[cfg(feature = "macos_15_2")]
     pub fn included_applications(&self) -> Vec<SCRunningApplication> {
-        let count = unsafe { ffi::sc_content_filter_get_included_applications_count(self.0) };
+        let count = unsafe { ffi::sc_content_filter_get_included_applications_count(self.ptr) };
         if count <= 0 {
             return Vec::new();
         }
@@ -252,7 +595,7 @@ impl SCContentFilter {
             .filter_map(|i| {
                 #[allow(clippy::cast_possible_wrap)]
                 let ptr = unsafe {
-                    ffi::sc_content_filter_get_included_application_at(self.0, i as isize)
+                    ffi::sc_content_filter_get_included_application_at(self.ptr, i as isize)
                 };
                 unsafe { SCRunningApplication::from_retained_ptr(ptr) }
             })
@@ -260,6 +603,34 @@ impl SCContentFilter {
     }
 }
 
+/// Pixel size and scale of a filter's content (macOS 14.0+)
+///
+/// Read from `SCShareableContentInfo`; see
+/// [`SCContentFilter::content_dimensions`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg(feature = "macos_14_0")]
+pub struct SCFilterDimensions {
+    /// Width in pixels
+    pub width: u32,
+    /// Height in pixels
+    pub height: u32,
+    /// Pixels per point, typically 2.0 on Retina displays
+    pub point_pixel_scale: f32,
+}
+
+#[cfg(feature = "macos_14_0")]
+impl SCFilterDimensions {
+    fn query(filter: &SCContentFilter) -> Option<Self> {
+        let info = crate::shareable_content::SCShareableContentInfo::for_filter(filter)?;
+        let (width, height) = info.pixel_size();
+        (width > 0 && height > 0).then(|| Self {
+            width,
+            height,
+            point_pixel_scale: info.point_pixel_scale(),
+        })
+    }
+}
+
 /// Content style for filters (macOS 14.0+)
 #[repr(i32)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
@@ -338,15 +709,66 @@ impl std::fmt::Display for SCStreamType {
 // per-call `.clone()`.
 crate::utils::retained::sc_retained!(
     SCContentFilter,
-    retain = crate::ffi::sc_content_filter_retain,
+    field = ptr,
     release = crate::ffi::sc_content_filter_release,
 );
 
+impl Clone for SCContentFilter {
+    fn clone(&self) -> Self {
+        Self {
+            ptr: unsafe { crate::ffi::sc_content_filter_retain(self.ptr) },
+            #[cfg(feature = "macos_14_0")]
+            dimensions: self.dimensions,
+            from_picker: self.from_picker,
+            selection: self.selection.clone(),
+        }
+    }
+}
+
 impl fmt::Debug for SCContentFilter {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("SCContentFilter")
-            .field("ptr", &self.0)
-            .finish()
+            .field("ptr", &self.ptr)
+            .field("from_picker", &self.from_picker)
+            .field("selection", &self.selection)
+            .finish_non_exhaustive()
+    }
+}
+
+impl SCContentFilter {
+    /// One-line description for diagnostics, using whichever getters the
+    /// enabled macOS features provide.
+    #[allow(clippy::unused_self)]
+    pub(crate) fn summary(&self) -> String {
+        #[allow(unused_mut)]
+        let mut parts: Vec<String> = Vec::new();
+        #[cfg(feature = "macos_14_0")]
+        {
+            parts.push(format!("{} content", self.style()));
+            parts.push(format!("{}x scale", self.point_pixel_scale()));
+        }
+        #[cfg(feature = "macos_14_2")]
+        {
+            let rect = self.content_rect();
+            parts.push(format!(
+                "rect {}x{} at ({}, {})",
+                rect.size.width, rect.size.height, rect.origin.x, rect.origin.y
+            ));
+        }
+        #[cfg(feature = "macos_15_2")]
+        {
+            parts.push(format!(
+                "{} display(s), {} window(s), {} app(s) included",
+                self.included_displays().len(),
+                self.included_windows().len(),
+                self.included_applications().len()
+            ));
+        }
+        if parts.is_empty() {
+            "SCContentFilter (enable macos_14_0 for details)".to_string()
+        } else {
+            parts.join(", ")
+        }
     }
 }
 
@@ -361,41 +783,21 @@ impl fmt::Display for SCContentFilter {
 unsafe impl Send for SCContentFilter {}
 unsafe impl Sync for SCContentFilter {}
 
-/// Builder for creating `SCContentFilter` instances
+/// Untyped builder for creating `SCContentFilter` instances
 ///
-/// # Examples
-///
-/// ```no_run
-/// use screencapturekit::prelude::*;
-///
-/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
-/// let content = SCShareableContent::get()?;
-/// let display = &content.displays()[0];
-///
-/// // Capture entire display
-/// let filter = SCContentFilter::create()
-///     .with_display(display)
-///     .with_excluding_windows(&[])
-///     .build();
-///
-/// // Capture with specific windows excluded
-/// let window = &content.windows()[0];
-/// let filter = SCContentFilter::create()
-///     .with_display(display)
-///     .with_excluding_windows(&[window])
-///     .build();
-///
-/// // Capture specific window
-/// let filter = SCContentFilter::create()
-///     .with_window(window)
-///     .build();
-/// # Ok(())
-/// # }
-/// ```
+/// Returned by the deprecated [`SCContentFilter::create`]. It accepts calls
+/// that don't fit together, such as window exclusions on a window filter,
+/// and ignores them; [`DisplayFilterBuilder`] and [`WindowFilterBuilder`]
+/// reject them at compile time instead.
 pub struct SCContentFilterBuilder {
     filter_type: FilterType,
     #[cfg(feature = "macos_14_2")]
     content_rect: Option<CGRect>,
+    #[cfg(feature = "macos_14_0")]
+    content_info: bool,
+    #[cfg(feature = "macos_14_2")]
+    include_menu_bar: Option<bool>,
+    exclusions: Exclusions,
 }
 
 enum FilterType {
@@ -419,6 +821,242 @@ enum FilterType {
         applications: Vec<SCRunningApplication>,
         excepting_windows: Vec<SCWindow>,
     },
+    DisplayApplication {
+        display: SCDisplay,
+        bundle_id: String,
+    },
+}
+
+impl FilterType {
+    /// The selection to record on the built filter.
+    fn selection(&self) -> Option<FilterSelection> {
+        use SelectionKind as K;
+
+        Some(match self {
+            Self::Window(window) => {
+                FilterSelection::new(K::Window, None, std::slice::from_ref(window), &[])
+            }
+            Self::DisplayExcluding { display, windows } => {
+                FilterSelection::new(K::DisplayExcluding, Some(display), windows, &[])
+            }
+            Self::DisplayIncluding { display, windows } => {
+                FilterSelection::new(K::DisplayIncluding, Some(display), windows, &[])
+            }
+            Self::DisplayIncludingApplications {
+                display,
+                applications,
+                excepting_windows,
+            } => FilterSelection::new(
+                K::DisplayIncludingApplications,
+                Some(display),
+                excepting_windows,
+                applications,
+            ),
+            Self::DisplayExcludingApplications {
+                display,
+                applications,
+                excepting_windows,
+            } => FilterSelection::new(
+                K::DisplayExcludingApplications,
+                Some(display),
+                excepting_windows,
+                applications,
+            ),
+            Self::DisplayApplication { .. } | Self::None => return None,
+        })
+    }
+}
+
+/// Bundle identifier of the Dock, which also owns the wallpaper windows on
+/// macOS 13 and earlier.
+const DOCK_BUNDLE_ID: &str = "com.apple.dock";
+/// Bundle identifier of the agent that draws the wallpaper on macOS 14+.
+const WALLPAPER_BUNDLE_ID: &str = "com.apple.wallpaper.agent";
+
+/// Desktop furniture that can be left out of display captures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DesktopChrome {
+    Dock,
+    Wallpaper,
+}
+
+impl DesktopChrome {
+    /// Classify a window by its owning application's `bundle_id` and, for
+    /// the Dock's windows, its title.
+    fn of(bundle_id: &str, window: &SCWindow) -> Option<Self> {
+        if bundle_id == WALLPAPER_BUNDLE_ID {
+            return Some(Self::Wallpaper);
+        }
+        if bundle_id != DOCK_BUNDLE_ID {
+            return None;
+        }
+        let title = window.title().unwrap_or_default();
+        if title.starts_with("Wallpaper") || title.starts_with("Desktop Picture") {
+            Some(Self::Wallpaper)
+        } else {
+            Some(Self::Dock)
+        }
+    }
+}
+
+/// Windows a display filter leaves out, looked up when it is built.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Exclusions {
+    include_dock: bool,
+    include_wallpaper: bool,
+    bundle_ids: Vec<String>,
+}
+
+impl Default for Exclusions {
+    fn default() -> Self {
+        Self {
+            include_dock: true,
+            include_wallpaper: true,
+            bundle_ids: Vec::new(),
+        }
+    }
+}
+
+impl Exclusions {
+    fn is_empty(&self) -> bool {
+        self.include_dock && self.include_wallpaper && self.bundle_ids.is_empty()
+    }
+}
+
+/// Split `content`'s windows into those `exclusions` hides and the Dock and
+/// wallpaper windows it keeps.
+fn partition_windows(
+    content: &SCShareableContent,
+    exclusions: &Exclusions,
+) -> (Vec<SCWindow>, Vec<SCWindow>) {
+    let mut hidden = Vec::new();
+    let mut kept = Vec::new();
+    for window in content.windows() {
+        let Some(owner) = window.owning_application() else {
+            continue;
+        };
+        let bundle_id = owner.bundle_identifier();
+        if exclusions.bundle_ids.contains(&bundle_id) {
+            hidden.push(window);
+            continue;
+        }
+        match DesktopChrome::of(&bundle_id, &window) {
+            Some(DesktopChrome::Dock) if !exclusions.include_dock => hidden.push(window),
+            Some(DesktopChrome::Wallpaper) if !exclusions.include_wallpaper => {
+                hidden.push(window);
+            }
+            Some(_) => kept.push(window),
+            None => {}
+        }
+    }
+    (hidden, kept)
+}
+
+/// Rewrite a display filter so that it leaves out the windows of excluded
+/// applications and, if asked to, the Dock and wallpaper. `content` is
+/// fetched when not given.
+fn apply_exclusions(
+    filter_type: FilterType,
+    exclusions: &Exclusions,
+    content: Option<&SCShareableContent>,
+) -> SCResult<FilterType> {
+    if exclusions.is_empty()
+        || matches!(
+            filter_type,
+            FilterType::None | FilterType::Window(_) | FilterType::DisplayApplication { .. }
+        )
+    {
+        return Ok(filter_type);
+    }
+
+    let fetched;
+    let content = if let Some(content) = content {
+        content
+    } else {
+        fetched = SCShareableContent::get()?;
+        &fetched
+    };
+    let (hidden, kept) = partition_windows(content, exclusions);
+    let hidden_ids: Vec<u32> = hidden.iter().map(SCWindow::window_id).collect();
+    let is_hidden = |window: &SCWindow| hidden_ids.contains(&window.window_id());
+
+    // Excluding whole applications also covers windows they open later, so
+    // prefer it when the caller hasn't picked windows to exclude.
+    let filter_type = match filter_type {
+        FilterType::DisplayExcluding { display, windows }
+            if windows.is_empty() && !exclusions.bundle_ids.is_empty() =>
+        {
+            FilterType::DisplayExcludingApplications {
+                display,
+                applications: Vec::new(),
+                excepting_windows: Vec::new(),
+            }
+        }
+        other => other,
+    };
+
+    Ok(match filter_type {
+        FilterType::DisplayExcluding {
+            display,
+            mut windows,
+        } => {
+            windows.extend(hidden);
+            FilterType::DisplayExcluding { display, windows }
+        }
+        FilterType::DisplayIncluding {
+            display,
+            mut windows,
+        } => {
+            windows.retain(|window| !is_hidden(window));
+            FilterType::DisplayIncluding { display, windows }
+        }
+        FilterType::DisplayIncludingApplications {
+            display,
+            applications,
+            mut excepting_windows,
+        } => {
+            excepting_windows.extend(hidden);
+            FilterType::DisplayIncludingApplications {
+                display,
+                applications,
+                excepting_windows,
+            }
+        }
+        FilterType::DisplayExcludingApplications {
+            display,
+            mut applications,
+            mut excepting_windows,
+        } => {
+            // Windows can't be excluded on their own here, so exclude the
+            // owning applications and except the windows that should stay.
+            let excluded_apps = content
+                .applications()
+                .into_iter()
+                .filter(|app| exclusions.bundle_ids.contains(&app.bundle_identifier()));
+            for app in excluded_apps.chain(hidden.iter().filter_map(SCWindow::owning_application)) {
+                if !applications
+                    .iter()
+                    .any(|a| a.process_id() == app.process_id())
+                {
+                    applications.push(app);
+                }
+            }
+            excepting_windows.retain(|window| !is_hidden(window));
+            excepting_windows.extend(kept.into_iter().filter(|window| {
+                window.owning_application().is_some_and(|owner| {
+                    applications
+                        .iter()
+                        .any(|a| a.process_id() == owner.process_id())
+                })
+            }));
+            FilterType::DisplayExcludingApplications {
+                display,
+                applications,
+                excepting_windows,
+            }
+        }
+        other => other,
+    })
 }
 
 impl SCContentFilterBuilder {
@@ -427,6 +1065,11 @@ impl SCContentFilterBuilder {
             filter_type: FilterType::None,
             #[cfg(feature = "macos_14_2")]
             content_rect: None,
+            #[cfg(feature = "macos_14_0")]
+            content_info: false,
+            #[cfg(feature = "macos_14_2")]
+            include_menu_bar: None,
+            exclusions: Exclusions::default(),
         }
     }
 
@@ -441,12 +1084,27 @@ impl SCContentFilterBuilder {
     }
 
     /// Set the window to capture
+    ///
+    /// This creates a desktop-independent window filter; it is the same as
+    /// [`with_desktop_independent_window`](Self::with_desktop_independent_window).
     #[must_use]
     pub fn with_window(mut self, window: &SCWindow) -> Self {
         self.filter_type = FilterType::Window(window.clone());
         self
     }
 
+    /// Capture a single window independently of the desktop it is on.
+    ///
+    /// Maps to `SCContentFilter(desktopIndependentWindow:)`. The window keeps
+    /// being captured when it moves to another Space or sits behind other
+    /// windows; see the
+    /// [module docs](crate::stream::content_filter#windows-on-other-spaces-full-screen-apps-and-off-screen-windows)
+    /// for the full behaviour.
+    #[must_use]
+    pub fn with_desktop_independent_window(self, window: &SCWindow) -> Self {
+        self.with_window(window)
+    }
+
     /// Exclude specific windows from the display capture
     #[must_use]
     pub fn with_excluding_windows(mut self, windows: &[&SCWindow]) -> Self {
@@ -529,6 +1187,26 @@ impl SCContentFilterBuilder {
         self
     }
 
+    /// Capture every window of the application with `bundle_id` on the
+    /// display
+    ///
+    /// The running application is looked up when the filter is built; see
+    /// [`SCContentFilter::for_application`]. Building fails with
+    /// [`SCError::ApplicationNotFound`] if it isn't running, so use
+    /// [`try_build`](Self::try_build) rather than [`build`](Self::build).
+    #[must_use]
+    pub fn with_application(mut self, bundle_id: impl Into<String>) -> Self {
+        if let FilterType::DisplayExcluding { display, .. }
+        | FilterType::DisplayIncluding { display, .. } = self.filter_type
+        {
+            self.filter_type = FilterType::DisplayApplication {
+                display,
+                bundle_id: bundle_id.into(),
+            };
+        }
+        self
+    }
+
     /// Set the content rectangle (macOS 14.2+)
     #[cfg(feature = "macos_14_2")]
     #[must_use]
@@ -537,6 +1215,72 @@ impl SCContentFilterBuilder {
         self
     }
 
+    /// Snapshot the content's pixel size and scale when building (macOS 14.0+)
+    ///
+    /// Queries `SCShareableContentInfo` once in
+    /// [`build`](Self::build) and stores the result on the filter, so
+    /// [`SCContentFilter::recommended_dimensions`] reflects the content as
+    /// it was when the filter was made.
+    #[cfg(feature = "macos_14_0")]
+    #[must_use]
+    pub fn with_content_info(mut self) -> Self {
+        self.content_info = true;
+        self
+    }
+
+    /// Include or leave out the menu bar in display capture (macOS 14.2+)
+    ///
+    /// Sets [`SCContentFilter::set_include_menu_bar`] on the built filter.
+    /// Has no effect on window filters.
+    #[cfg(feature = "macos_14_2")]
+    #[must_use]
+    pub fn with_menu_bar(mut self, include: bool) -> Self {
+        self.include_menu_bar = Some(include);
+        self
+    }
+
+    /// Include or leave out the Dock in display capture
+    ///
+    /// The Dock's windows are found by their owning application
+    /// (`com.apple.dock`) when the filter is built, so building fetches
+    /// shareable content; use [`try_build`](Self::try_build) to handle
+    /// failures. Windows the Dock opens afterwards are not excluded. Has no
+    /// effect on window and single-application filters.
+    #[must_use]
+    pub fn with_dock(mut self, include: bool) -> Self {
+        self.exclusions.include_dock = include;
+        self
+    }
+
+    /// Include or leave out the desktop wallpaper in display capture
+    ///
+    /// The wallpaper windows are found by their owning application (the
+    /// wallpaper agent, or the Dock before macOS 14) when the filter is
+    /// built, with the same caveats as [`with_dock`](Self::with_dock). The
+    /// area behind the wallpaper is captured as black.
+    #[must_use]
+    pub fn with_wallpaper(mut self, include: bool) -> Self {
+        self.exclusions.include_wallpaper = include;
+        self
+    }
+
+    /// Leave out every window of the applications in `policy`
+    ///
+    /// The applications are resolved to their current processes and
+    /// windows when the filter is built, with the same caveats as
+    /// [`with_dock`](Self::with_dock). Use an
+    /// [`ExclusionWatcher`](super::exclusion_policy::ExclusionWatcher) to keep
+    /// a running stream's filter current as they open windows or relaunch.
+    #[must_use]
+    pub fn with_exclusion_policy(mut self, policy: &ExclusionPolicy) -> Self {
+        for bundle_id in policy.bundle_ids() {
+            if !self.exclusions.bundle_ids.contains(bundle_id) {
+                self.exclusions.bundle_ids.push(bundle_id.clone());
+            }
+        }
+        self
+    }
+
     // =========================================================================
     // Deprecated methods - use with_* versions instead
     // =========================================================================
@@ -604,12 +1348,12 @@ impl SCContentFilterBuilder {
     /// # Panics
     ///
     /// Panics if no filter type was set. Call `.display()` or `.window()` before `.build()`.
-    /// For a non-panicking alternative that reports this as a recoverable error, use
-    /// [`try_build`](Self::try_build).
+    /// Also panics if windows are excluded by application and shareable content can't be
+    /// fetched. For a non-panicking alternative that reports these as recoverable errors,
+    /// use [`try_build`](Self::try_build).
     #[must_use]
     pub fn build(self) -> SCContentFilter {
-        self.try_build()
-            .expect("SCContentFilterBuilder: No filter type set. Call .display() or .window() before .build()")
+        self.try_build().unwrap_or_else(|error| panic!("{error}"))
     }
 
     /// Build the content filter, returning an error instead of panicking when no
@@ -618,14 +1362,26 @@ impl SCContentFilterBuilder {
     /// # Errors
     ///
     /// Returns [`SCError::InvalidConfiguration`] if neither `.display()` nor `.window()`
-    /// was called before building.
-    #[allow(clippy::too_many_lines)]
+    /// was called before building, or the error from fetching shareable
+    /// content when windows are excluded by application.
     pub fn try_build(self) -> SCResult<SCContentFilter> {
-        let filter = match self.filter_type {
+        self.try_build_in(None)
+    }
+
+    /// [`try_build`](Self::try_build), resolving exclusions against
+    /// `content` if given.
+    #[allow(clippy::too_many_lines)]
+    pub(crate) fn try_build_in(
+        self,
+        content: Option<&SCShareableContent>,
+    ) -> SCResult<SCContentFilter> {
+        let filter_type = apply_exclusions(self.filter_type, &self.exclusions, content)?;
+        let selection = filter_type.selection().map(Arc::new);
+        let mut filter = match filter_type {
             FilterType::Window(window) => unsafe {
                 let ptr =
                     ffi::sc_content_filter_create_with_desktop_independent_window(window.as_ptr());
-                SCContentFilter(ptr)
+                SCContentFilter::from_ptr(ptr)
             },
             FilterType::DisplayExcluding { display, windows } => {
                 let window_refs: Vec<&SCWindow> = windows.iter().collect();
@@ -647,7 +1403,7 @@ impl SCContentFilterBuilder {
                             window_ptrs.len() as isize,
                         )
                     };
-                    SCContentFilter(ptr)
+                    SCContentFilter::from_ptr(ptr)
                 }
             }
             FilterType::DisplayIncluding { display, windows } => {
@@ -670,7 +1426,7 @@ impl SCContentFilterBuilder {
                             window_ptrs.len() as isize,
                         )
                     };
-                    SCContentFilter(ptr)
+                    SCContentFilter::from_ptr(ptr)
                 }
             }
             FilterType::DisplayIncludingApplications {
@@ -695,7 +1451,7 @@ impl SCContentFilterBuilder {
                         if window_ptrs.is_empty() { std::ptr::null() } else { window_ptrs.as_ptr() },
                         window_ptrs.len() as isize,
                     );
-                    SCContentFilter(ptr)
+                    SCContentFilter::from_ptr(ptr)
                 }
             }
             FilterType::DisplayExcludingApplications {
@@ -720,9 +1476,12 @@ impl SCContentFilterBuilder {
                         if window_ptrs.is_empty() { std::ptr::null() } else { window_ptrs.as_ptr() },
                         window_ptrs.len() as isize,
                     );
-                    SCContentFilter(ptr)
+                    SCContentFilter::from_ptr(ptr)
                 }
             }
+            FilterType::DisplayApplication { display, bundle_id } => {
+                SCContentFilter::for_application(&display, &bundle_id)?
+            }
             FilterType::None => {
                 return Err(SCError::invalid_config(
                     "SCContentFilterBuilder: No filter type set. \
@@ -731,6 +1490,12 @@ impl SCContentFilterBuilder {
             }
         };
 
+        // `DisplayApplication` filters come back from `for_application`
+        // with their selection already recorded.
+        if selection.is_some() {
+            filter.selection = selection;
+        }
+
         // Apply content rect if set (macOS 14.2+)
         #[cfg(feature = "macos_14_2")]
         let filter = if let Some(rect) = self.content_rect {
@@ -739,6 +1504,24 @@ impl SCContentFilterBuilder {
             filter
         };
 
+        #[cfg(feature = "macos_14_2")]
+        let filter = if let Some(include) = self.include_menu_bar {
+            let mut filter = filter;
+            filter.set_include_menu_bar(include);
+            filter
+        } else {
+            filter
+        };
+
+        #[cfg(feature = "macos_14_0")]
+        let filter = if self.content_info {
+            let mut filter = filter;
+            filter.dimensions = SCFilterDimensions::query(&filter);
+            filter
+        } else {
+            filter
+        };
+
         Ok(filter)
     }
 }
@@ -752,6 +1535,7 @@ impl std::fmt::Debug for SCContentFilterBuilder {
             FilterType::DisplayIncluding { .. } => "DisplayIncluding",
             FilterType::DisplayIncludingApplications { .. } => "DisplayIncludingApplications",
             FilterType::DisplayExcludingApplications { .. } => "DisplayExcludingApplications",
+            FilterType::DisplayApplication { .. } => "DisplayApplication",
         };
 
         let mut debug = f.debug_struct("SCContentFilterBuilder");
@@ -760,6 +1544,332 @@ impl std::fmt::Debug for SCContentFilterBuilder {
         #[cfg(feature = "macos_14_2")]
         debug.field("content_rect", &self.content_rect);
 
-        debug.finish()
+        #[cfg(feature = "macos_14_0")]
+        debug.field("content_info", &self.content_info);
+
+        #[cfg(feature = "macos_14_2")]
+        debug.field("include_menu_bar", &self.include_menu_bar);
+
+        debug.field("exclusions", &self.exclusions).finish()
+    }
+}
+
+// MARK: - Type-state builders
+
+/// States of a [`DisplayFilterBuilder`]
+///
+/// Selecting windows or applications moves the builder out of
+/// [`Unselected`](state::Unselected), so a second, conflicting selection
+/// does not compile:
+///
+/// ```compile_fail
+/// use screencapturekit::prelude::*;
+///
+/// # fn example(display: &SCDisplay, window: &SCWindow) {
+/// let filter = SCContentFilter::for_display(display)
+///     .with_excluding_windows(&[window])
+///     .with_including_windows(&[window]) // no such method once selected
+///     .build();
+/// # }
+/// ```
+///
+/// Leaving out the Dock or wallpaper, or adding an
+/// [`ExclusionPolicy`], means fetching shareable content at build time, so
+/// the builder moves to [`Lookup`](state::Lookup) and only offers
+/// `try_build`:
+///
+/// ```compile_fail
+/// use screencapturekit::prelude::*;
+///
+/// # fn example(display: &SCDisplay) {
+/// let filter = SCContentFilter::for_display(display)
+///     .with_dock(false)
+///     .build(); // no such method once a lookup is needed
+/// # }
+/// ```
+pub mod state {
+    use std::marker::PhantomData;
+
+    /// The whole display; no window or application selection yet
+    #[derive(Debug, Clone, Copy)]
+    pub struct Unselected;
+
+    /// Windows or applications have been selected
+    #[derive(Debug, Clone, Copy)]
+    pub struct Selected;
+
+    /// An application is selected by bundle identifier and looked up at
+    /// build time, so only `try_build` is available
+    #[derive(Debug, Clone, Copy)]
+    pub struct Application;
+
+    /// Like `S`, plus windows to exclude that are looked up by application
+    /// at build time (the Dock, the wallpaper or an exclusion policy), so
+    /// only `try_build` is available
+    #[derive(Debug, Clone, Copy)]
+    pub struct Lookup<S>(PhantomData<S>);
+
+    /// States a display filter builder can be built from
+    pub trait Buildable: sealed::Sealed {
+        /// The state after adding windows that are looked up at build time
+        type Lookup: Buildable;
+    }
+
+    impl Buildable for Unselected {
+        type Lookup = Lookup<Self>;
+    }
+    impl Buildable for Selected {
+        type Lookup = Lookup<Self>;
+    }
+    impl Buildable for Application {
+        type Lookup = Self;
+    }
+    impl<S: Buildable> Buildable for Lookup<S> {
+        type Lookup = Self;
+    }
+
+    /// States whose filters can always be built
+    ///
+    /// Building never fetches shareable content in these states, so it can
+    /// neither fail nor block.
+    pub trait Infallible: Buildable {}
+
+    impl Infallible for Unselected {}
+    impl Infallible for Selected {}
+
+    /// States that still take one window or application selection
+    pub trait Selectable: sealed::Sealed {
+        /// The state after selecting windows or applications
+        type Selected: Buildable;
+    }
+
+    impl Selectable for Unselected {
+        type Selected = Selected;
+    }
+    impl Selectable for Lookup<Unselected> {
+        type Selected = Lookup<Selected>;
+    }
+
+    mod sealed {
+        pub trait Sealed {}
+        impl Sealed for super::Unselected {}
+        impl Sealed for super::Selected {}
+        impl Sealed for super::Application {}
+        impl<S: Sealed> Sealed for super::Lookup<S> {}
+    }
+}
+
+/// Builder for a filter that captures (part of) a display
+///
+/// Created by [`SCContentFilter::for_display`]. At most one window or
+/// application selection can be made; the type parameter tracks whether it
+/// has been.
+pub struct DisplayFilterBuilder<S = state::Unselected> {
+    inner: SCContentFilterBuilder,
+    _state: PhantomData<S>,
+}
+
+impl<S> DisplayFilterBuilder<S> {
+    const fn new(inner: SCContentFilterBuilder) -> Self {
+        Self {
+            inner,
+            _state: PhantomData,
+        }
+    }
+
+    /// Set the content rectangle (macOS 14.2+)
+    #[cfg(feature = "macos_14_2")]
+    #[must_use]
+    pub fn with_content_rect(self, rect: CGRect) -> Self {
+        Self::new(self.inner.with_content_rect(rect))
+    }
+
+    /// Snapshot the content's pixel size and scale when building (macOS 14.0+)
+    ///
+    /// See [`SCContentFilter::recommended_dimensions`].
+    #[cfg(feature = "macos_14_0")]
+    #[must_use]
+    pub fn with_content_info(self) -> Self {
+        Self::new(self.inner.with_content_info())
+    }
+
+    /// Include or leave out the menu bar (macOS 14.2+)
+    #[cfg(feature = "macos_14_2")]
+    #[must_use]
+    pub fn with_menu_bar(self, include: bool) -> Self {
+        Self::new(self.inner.with_menu_bar(include))
+    }
+}
+
+impl<S: state::Buildable> DisplayFilterBuilder<S> {
+    /// Include or leave out the Dock
+    ///
+    /// Only [`try_build`](DisplayFilterBuilder::try_build) is available
+    /// afterwards. See [`SCContentFilterBuilder::with_dock`].
+    #[must_use]
+    pub fn with_dock(self, include: bool) -> DisplayFilterBuilder<S::Lookup> {
+        DisplayFilterBuilder::new(self.inner.with_dock(include))
+    }
+
+    /// Include or leave out the desktop wallpaper
+    ///
+    /// Only [`try_build`](DisplayFilterBuilder::try_build) is available
+    /// afterwards. See [`SCContentFilterBuilder::with_wallpaper`].
+    #[must_use]
+    pub fn with_wallpaper(self, include: bool) -> DisplayFilterBuilder<S::Lookup> {
+        DisplayFilterBuilder::new(self.inner.with_wallpaper(include))
+    }
+
+    /// Leave out every window of the applications in `policy`
+    ///
+    /// Only [`try_build`](DisplayFilterBuilder::try_build) is available
+    /// afterwards. See [`SCContentFilterBuilder::with_exclusion_policy`].
+    #[must_use]
+    pub fn with_exclusion_policy(
+        self,
+        policy: &ExclusionPolicy,
+    ) -> DisplayFilterBuilder<S::Lookup> {
+        DisplayFilterBuilder::new(self.inner.with_exclusion_policy(policy))
+    }
+
+    /// Build the content filter
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::ApplicationNotFound`] if an application selected
+    /// with [`with_application`](DisplayFilterBuilder::with_application) is
+    /// not running, or the error from fetching shareable content to look it
+    /// up or to find the windows to exclude. Builders in an
+    /// [`Infallible`](state::Infallible) state never fail.
+    pub fn try_build(self) -> SCResult<SCContentFilter> {
+        self.inner.try_build()
+    }
+
+    /// [`try_build`](Self::try_build) against content the caller already
+    /// fetched.
+    pub(crate) fn try_build_in(self, content: &SCShareableContent) -> SCResult<SCContentFilter> {
+        self.inner.try_build_in(Some(content))
+    }
+}
+
+impl<S: state::Infallible> DisplayFilterBuilder<S> {
+    /// Build the content filter
+    ///
+    /// Nothing is looked up in these states, so this neither fetches
+    /// shareable content nor fails.
+    #[must_use]
+    pub fn build(self) -> SCContentFilter {
+        self.inner.build()
+    }
+}
+
+impl<S: state::Selectable> DisplayFilterBuilder<S> {
+    /// Capture the display except for `windows`
+    #[must_use]
+    pub fn with_excluding_windows(
+        self,
+        windows: &[&SCWindow],
+    ) -> DisplayFilterBuilder<S::Selected> {
+        DisplayFilterBuilder::new(self.inner.with_excluding_windows(windows))
+    }
+
+    /// Capture only `windows` from the display
+    #[must_use]
+    pub fn with_including_windows(
+        self,
+        windows: &[&SCWindow],
+    ) -> DisplayFilterBuilder<S::Selected> {
+        DisplayFilterBuilder::new(self.inner.with_including_windows(windows))
+    }
+
+    /// Capture only `applications`, minus `excepting_windows`
+    #[must_use]
+    pub fn with_including_applications(
+        self,
+        applications: &[&SCRunningApplication],
+        excepting_windows: &[&SCWindow],
+    ) -> DisplayFilterBuilder<S::Selected> {
+        DisplayFilterBuilder::new(
+            self.inner
+                .with_including_applications(applications, excepting_windows),
+        )
+    }
+
+    /// Capture everything except `applications`, keeping
+    /// `excepting_windows` even if their application is excluded
+    #[must_use]
+    pub fn with_excluding_applications(
+        self,
+        applications: &[&SCRunningApplication],
+        excepting_windows: &[&SCWindow],
+    ) -> DisplayFilterBuilder<S::Selected> {
+        DisplayFilterBuilder::new(
+            self.inner
+                .with_excluding_applications(applications, excepting_windows),
+        )
+    }
+
+    /// Capture every window of the application with `bundle_id`
+    ///
+    /// The application is looked up when the filter is built; see
+    /// [`SCContentFilter::for_application`].
+    #[must_use]
+    pub fn with_application(
+        self,
+        bundle_id: impl Into<String>,
+    ) -> DisplayFilterBuilder<state::Application> {
+        DisplayFilterBuilder::new(self.inner.with_application(bundle_id))
+    }
+}
+
+impl<S> fmt::Debug for DisplayFilterBuilder<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("DisplayFilterBuilder")
+            .field(&self.inner)
+            .finish()
+    }
+}
+
+/// Builder for a desktop-independent single-window filter
+///
+/// Created by [`SCContentFilter::for_window`]. Window filters have no
+/// window or application selections.
+pub struct WindowFilterBuilder {
+    inner: SCContentFilterBuilder,
+}
+
+impl WindowFilterBuilder {
+    /// Set the content rectangle (macOS 14.2+)
+    #[cfg(feature = "macos_14_2")]
+    #[must_use]
+    pub fn with_content_rect(self, rect: CGRect) -> Self {
+        Self {
+            inner: self.inner.with_content_rect(rect),
+        }
+    }
+
+    /// Snapshot the content's pixel size and scale when building (macOS 14.0+)
+    ///
+    /// See [`SCContentFilter::recommended_dimensions`].
+    #[cfg(feature = "macos_14_0")]
+    #[must_use]
+    pub fn with_content_info(self) -> Self {
+        Self {
+            inner: self.inner.with_content_info(),
+        }
+    }
+
+    /// Build the content filter
+    #[must_use]
+    pub fn build(self) -> SCContentFilter {
+        self.inner.build()
+    }
+}
+
+impl fmt::Debug for WindowFilterBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("WindowFilterBuilder")
+            .field(&self.inner)
+            .finish()
     }
 }
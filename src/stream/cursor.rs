@@ -0,0 +1,316 @@
+//! The cursor as metadata next to the video
+//!
+//! With [`with_shows_cursor(true)`](crate::stream::configuration::SCStreamConfiguration::with_shows_cursor)
+//! the cursor is drawn into every frame, at the frame's resolution and
+//! under its compression. A remote desktop viewer that scales the picture
+//! ends up with a blurry cursor that lags a frame behind the local one.
+//!
+//! Capturing without the cursor and sending it separately avoids both: the
+//! viewer draws the cursor image on top of the video at whatever scale it
+//! displays, and can move it between frames. [`CursorTracker`] reads the
+//! system cursor when a frame arrives and maps its hot spot into the
+//! frame's pixel space; the cursor image is only fetched again when its
+//! shape changes, e.g. from an arrow to an I-beam.
+//!
+//! [`SCStream::add_output_handler_with_cursor`](crate::stream::SCStream::add_output_handler_with_cursor)
+//! turns the cursor off in the stream's configuration and hands every screen
+//! frame to a handler together with a [`CursorUpdate`].
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use screencapturekit::prelude::*;
+//!
+//! # fn example(filter: &SCContentFilter, config: &SCStreamConfiguration) -> Result<(), SCError> {
+//! let mut stream = SCStream::new(filter, config);
+//! stream.add_output_handler_with_cursor(|frame, cursor| {
+//!     if let Some(shape) = &cursor.shape {
+//!         // Send the new cursor image once; viewers cache it by seed.
+//!         println!("cursor shape {} ({:?} points)", shape.seed(), shape.size());
+//!     }
+//!     if let Some(position) = cursor.position {
+//!         println!("cursor at {:?} in the frame", position.location);
+//!     }
+//!     drop(frame); // encode and send
+//! });
+//! stream.start_capture()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, PoisonError};
+
+use crate::cg::{CGPoint, CGRect, CGRectExt, CGSize};
+use crate::cm::{CMSampleBuffer, CMSampleBufferSCExt};
+use crate::CGImage;
+
+use super::output_trait::SCStreamOutputTrait;
+use super::output_type::SCStreamOutputType;
+
+/// The cursor's image and hot spot
+#[derive(Debug)]
+pub struct CursorShape {
+    image: CGImage,
+    hot_spot: CGPoint,
+    size: CGSize,
+    seed: u64,
+}
+
+impl CursorShape {
+    /// The system cursor's current shape, or `None` if there is none
+    #[must_use]
+    pub fn current() -> Option<Self> {
+        let (mut hot_x, mut hot_y, mut width, mut height, mut seed) = (0.0, 0.0, 0.0, 0.0, 0);
+        let ptr = unsafe {
+            crate::ffi::sc_cursor_copy_shape(
+                &mut hot_x,
+                &mut hot_y,
+                &mut width,
+                &mut height,
+                &mut seed,
+            )
+        };
+        if ptr.is_null() {
+            return None;
+        }
+        // SAFETY: `sc_cursor_copy_shape` returns a +1 CGImage, adopted here.
+        let image = unsafe { CGImage::from_raw(ptr.cast_mut()) };
+        Some(Self {
+            image,
+            hot_spot: CGPoint::new(hot_x, hot_y),
+            size: CGSize::new(width, height),
+            seed,
+        })
+    }
+
+    /// The cursor image, usually at the main display's backing scale
+    #[must_use]
+    pub const fn image(&self) -> &CGImage {
+        &self.image
+    }
+
+    /// The point of the image that marks the cursor position, in points
+    /// from the image's top-left corner
+    #[must_use]
+    pub const fn hot_spot(&self) -> CGPoint {
+        self.hot_spot
+    }
+
+    /// Size the image is drawn at on screen, in points
+    #[must_use]
+    pub const fn size(&self) -> CGSize {
+        self.size
+    }
+
+    /// Image pixels per point
+    #[must_use]
+    pub fn scale(&self) -> f64 {
+        if self.size.width > 0.0 {
+            #[allow(clippy::cast_precision_loss)]
+            let pixels = self.image.width() as f64;
+            pixels / self.size.width
+        } else {
+            1.0
+        }
+    }
+
+    /// Identifies the shape: equal seeds within a process mean the same
+    /// image and hot spot
+    #[must_use]
+    pub const fn seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+/// Where the cursor was when a frame arrived
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CursorPosition {
+    /// The hot spot in the frame, in pixels from its top-left corner
+    pub location: CGPoint,
+    /// The hot spot in global display points
+    pub screen_location: CGPoint,
+    /// Frame pixels per screen point. Draw the cursor at
+    /// [`CursorShape::size`] times this to match the captured content.
+    pub scale: f64,
+    /// Whether the hot spot is over the captured content
+    pub inside: bool,
+}
+
+/// The cursor state that goes with one frame
+#[derive(Debug)]
+pub struct CursorUpdate {
+    /// Where the cursor is, or `None` until a frame has said where its
+    /// content is on screen
+    pub position: Option<CursorPosition>,
+    /// Seed of the current shape, 0 if unknown
+    pub shape_seed: u64,
+    /// The new shape, when it changed since the previous update
+    pub shape: Option<CursorShape>,
+}
+
+/// Where a frame's content is on screen and in the frame.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct FrameGeometry {
+    /// Captured content in global points.
+    screen: CGRect,
+    /// The same content in frame pixels.
+    content: CGRect,
+}
+
+impl FrameGeometry {
+    pub(crate) fn of(frame: &CMSampleBuffer) -> Option<Self> {
+        let screen = frame.screen_rect()?.standardized();
+        let scale = frame
+            .scale_factor()
+            .filter(|scale| *scale > 0.0)
+            .unwrap_or(1.0);
+        let content = frame.content_rect()?.standardized().scaled(scale);
+        (screen.size.width > 0.0 && screen.size.height > 0.0).then_some(Self { screen, content })
+    }
+
+    /// `rect` in global points to frame pixels.
+    pub(crate) fn pixels_of(&self, rect: CGRect) -> CGRect {
+        let scale_x = self.content.size.width / self.screen.size.width;
+        let scale_y = self.content.size.height / self.screen.size.height;
+        let rect = rect.standardized();
+        CGRect::new(
+            (rect.origin.x - self.screen.origin.x).mul_add(scale_x, self.content.origin.x),
+            (rect.origin.y - self.screen.origin.y).mul_add(scale_y, self.content.origin.y),
+            rect.size.width * scale_x,
+            rect.size.height * scale_y,
+        )
+    }
+
+    fn position(&self, screen_location: CGPoint) -> CursorPosition {
+        let scale = self.content.size.width / self.screen.size.width;
+        let scale_y = self.content.size.height / self.screen.size.height;
+        let location = CGPoint::new(
+            (screen_location.x - self.screen.origin.x).mul_add(scale, self.content.origin.x),
+            (screen_location.y - self.screen.origin.y).mul_add(scale_y, self.content.origin.y),
+        );
+        CursorPosition {
+            location,
+            screen_location,
+            scale,
+            inside: self.screen.contains_point(screen_location),
+        }
+    }
+}
+
+/// Reads the cursor for each frame and tracks shape changes
+///
+/// Idle frames carry no geometry, so the most recent frame that did is used
+/// for them: the cursor still moves over an unchanged screen.
+#[derive(Default)]
+pub struct CursorTracker {
+    last_seed: AtomicU64,
+    geometry: Mutex<Option<FrameGeometry>>,
+}
+
+impl CursorTracker {
+    /// A tracker that includes the shape in its first update
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The cursor state for `frame`, read now
+    ///
+    /// The shape is included the first time and whenever it changed since
+    /// the previous call.
+    pub fn update(&self, frame: &CMSampleBuffer) -> CursorUpdate {
+        let geometry = {
+            let mut last = self.geometry.lock().unwrap_or_else(PoisonError::into_inner);
+            if let Some(geometry) = FrameGeometry::of(frame) {
+                *last = Some(geometry);
+            }
+            *last
+        };
+
+        let (mut x, mut y, mut seed) = (0.0, 0.0, 0);
+        if !unsafe { crate::ffi::sc_cursor_sample(&mut x, &mut y, &mut seed) } {
+            return CursorUpdate {
+                position: None,
+                shape_seed: 0,
+                shape: None,
+            };
+        }
+        let position = geometry.map(|geometry| geometry.position(CGPoint::new(x, y)));
+
+        let shape = if seed != 0 && self.last_seed.swap(seed, Ordering::Relaxed) != seed {
+            CursorShape::current()
+        } else {
+            None
+        };
+        CursorUpdate {
+            position,
+            shape_seed: shape.as_ref().map_or(seed, CursorShape::seed),
+            shape,
+        }
+    }
+
+    /// Include the shape in the next update even if it hasn't changed, e.g.
+    /// when a new viewer connects
+    pub fn resend_shape(&self) {
+        self.last_seed.store(0, Ordering::Relaxed);
+    }
+}
+
+impl fmt::Debug for CursorTracker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CursorTracker")
+            .field("last_seed", &self.last_seed.load(Ordering::Relaxed))
+            .finish_non_exhaustive()
+    }
+}
+
+/// Hands every screen frame to a handler together with the cursor state
+///
+/// Usually created through
+/// [`SCStream::add_output_handler_with_cursor`](crate::stream::SCStream::add_output_handler_with_cursor).
+/// Audio samples are ignored.
+pub struct CursorOutputHandler<F> {
+    handler: F,
+    tracker: CursorTracker,
+}
+
+impl<F> CursorOutputHandler<F>
+where
+    F: Fn(CMSampleBuffer, &CursorUpdate) + Send + Sync + 'static,
+{
+    /// Wrap `handler`
+    pub fn new(handler: F) -> Self {
+        Self {
+            handler,
+            tracker: CursorTracker::new(),
+        }
+    }
+
+    /// The tracker, to [`resend_shape`](CursorTracker::resend_shape)
+    pub const fn tracker(&self) -> &CursorTracker {
+        &self.tracker
+    }
+}
+
+impl<F> SCStreamOutputTrait for CursorOutputHandler<F>
+where
+    F: Fn(CMSampleBuffer, &CursorUpdate) + Send + Sync + 'static,
+{
+    fn did_output_sample_buffer(&self, sample: CMSampleBuffer, of_type: SCStreamOutputType) {
+        if of_type != SCStreamOutputType::Screen {
+            return;
+        }
+        let update = self.tracker.update(&sample);
+        (self.handler)(sample, &update);
+    }
+}
+
+impl<F> fmt::Debug for CursorOutputHandler<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CursorOutputHandler")
+            .field("tracker", &self.tracker)
+            .finish_non_exhaustive()
+    }
+}
@@ -5,7 +5,79 @@
 //! Use [`SCStream::new_with_delegate`](crate::stream::SCStream::new_with_delegate)
 //! to create a stream with a delegate that receives error callbacks.
 
+use crate::audio_devices::AudioInputDevice;
+use crate::cg::{CGRect, CGRectExt};
+use crate::cm::{CMSampleBuffer, CMSampleBufferSCExt, SCFrameStatus};
 use crate::error::SCError;
+use crate::stream::display_sleep::DisplaySleepEvent;
+use crate::stream::permission_watcher::PermissionRevoked;
+use crate::stream::state::SCStreamState;
+
+/// A system video effect applied to captured content.
+///
+/// `ScreenCaptureKit` reports effects through
+/// [`SCStreamDelegateTrait::video_effect_did_start`] and
+/// [`video_effect_did_stop`](SCStreamDelegateTrait::video_effect_did_stop).
+/// Only Presenter Overlay is reported today; reactions and gestures are
+/// drawn into the camera feed and never reach the stream delegate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum SCVideoEffect {
+    /// Presenter Overlay, which draws the presenter's camera over or beside
+    /// the shared content. Its privacy alert is configured with
+    /// `SCStreamConfiguration::with_presenter_overlay_privacy_alert_setting`
+    /// (macOS 14.2+).
+    PresenterOverlay,
+}
+
+/// Where the captured content sits in the stream's frames
+///
+/// Presenter Overlay and reactions can shrink the content to make room for
+/// the camera, and resizing a captured window changes it too. Delivered
+/// through [`SCStreamDelegateTrait::content_rect_did_change`] whenever a
+/// complete frame's `contentRect`, `contentScale` or `scaleFactor`
+/// attachments differ from the previous frame's.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ContentRectChanged {
+    /// The content's rectangle in the frame, in points
+    pub content_rect: CGRect,
+    /// How much the content was scaled to fit the frame
+    pub content_scale: f64,
+    /// Pixels per point of the display the content is on
+    pub scale_factor: f64,
+}
+
+impl ContentRectChanged {
+    /// Read the content geometry of a complete screen frame
+    ///
+    /// Returns `None` for other frames, which carry no geometry.
+    #[must_use]
+    pub fn of(sample: &CMSampleBuffer) -> Option<Self> {
+        if sample.frame_status() != Some(SCFrameStatus::Complete) {
+            return None;
+        }
+        Some(Self {
+            content_rect: sample.content_rect()?,
+            content_scale: sample.content_scale().unwrap_or(1.0),
+            scale_factor: sample.scale_factor().unwrap_or(1.0),
+        })
+    }
+
+    /// [`content_rect`](Self::content_rect) in frame pixels, the rectangle
+    /// to crop frames to
+    #[must_use]
+    pub fn pixel_rect(&self) -> CGRect {
+        self.content_rect.scaled(self.scale_factor)
+    }
+}
+
+impl std::fmt::Display for SCVideoEffect {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::PresenterOverlay => write!(f, "Presenter Overlay"),
+        }
+    }
+}
 
 /// Trait for handling stream lifecycle events
 ///
@@ -40,7 +112,7 @@ use crate::error::SCError;
 /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
 /// # let content = SCShareableContent::get()?;
 /// # let display = &content.displays()[0];
-/// # let filter = SCContentFilter::create().with_display(display).with_excluding_windows(&[]).build();
+/// # let filter = SCContentFilter::for_display(display).with_excluding_windows(&[]).build();
 /// # let config = SCStreamConfiguration::default();
 ///
 /// let delegate = StreamCallbacks::new()
@@ -66,6 +138,26 @@ pub trait SCStreamDelegateTrait: Send + Sync {
     /// Notifies when the stream's overlay video effect (presenter overlay) has stopped.
     fn output_video_effect_did_stop_for_stream(&self) {}
 
+    /// Called when the user turns on a video effect for the captured content
+    /// from the Video menu bar item (macOS 14.0+)
+    ///
+    /// Screen sharing UIs use this to move their own overlays out of the
+    /// way. The default implementation calls
+    /// [`output_video_effect_did_start_for_stream`](Self::output_video_effect_did_start_for_stream).
+    fn video_effect_did_start(&self, _effect: SCVideoEffect) {
+        self.output_video_effect_did_start_for_stream();
+    }
+
+    /// Called when a video effect reported by
+    /// [`video_effect_did_start`](Self::video_effect_did_start) is turned off
+    /// (macOS 14.0+)
+    ///
+    /// The default implementation calls
+    /// [`output_video_effect_did_stop_for_stream`](Self::output_video_effect_did_stop_for_stream).
+    fn video_effect_did_stop(&self, _effect: SCVideoEffect) {
+        self.output_video_effect_did_stop_for_stream();
+    }
+
     /// Called when the stream becomes active (macOS 15.2+)
     ///
     /// Notifies the first time any window that was being shared in the stream
@@ -93,6 +185,51 @@ pub trait SCStreamDelegateTrait: Send + Sync {
     /// **not** reported here — observe it through that method's return value.
     fn did_stop_with_error(&self, _error: SCError) {}
 
+    /// Called when the selected microphone disappeared and the stream was
+    /// switched to the default input.
+    ///
+    /// Only delivered while a
+    /// [`MicrophoneDeviceWatcher`](crate::stream::microphone_watcher::MicrophoneDeviceWatcher)
+    /// is running for the stream. `fallback` is the device now in use, or
+    /// `None` if the system reports no default input.
+    fn microphone_device_lost(&self, _lost_device_id: &str, _fallback: Option<&AudioInputDevice>) {}
+
+    /// Called when the process lost Screen Recording permission.
+    ///
+    /// Delivered once per revocation: when the stream stops with
+    /// [`SCStreamErrorCode::UserDeclined`](crate::error::SCStreamErrorCode::UserDeclined)
+    /// (just before [`did_stop_with_error`](Self::did_stop_with_error)), or
+    /// when a running
+    /// [`PermissionWatcher`](crate::stream::permission_watcher::PermissionWatcher)
+    /// sees the preflight check turn false, whichever comes first.
+    fn permission_revoked(&self, _event: &PermissionRevoked) {}
+
+    /// Called when the display sleeps or wakes, the screen locks or
+    /// unlocks, or the screensaver starts or stops.
+    ///
+    /// Only delivered while a
+    /// [`DisplaySleepWatcher`](crate::stream::display_sleep::DisplaySleepWatcher)
+    /// is running for the stream, from its thread.
+    fn display_sleep_changed(&self, _event: DisplaySleepEvent) {}
+
+    /// Called when the content's place in the frame changed, e.g. because
+    /// Presenter Overlay started or a captured window was resized.
+    ///
+    /// Runs on the screen output queue just before the first frame with the
+    /// new geometry reaches the output handlers, and once for the first
+    /// complete frame after each start. Only delivered while a
+    /// [`Screen`](crate::stream::output_type::SCStreamOutputType::Screen)
+    /// output handler is attached.
+    fn content_rect_did_change(&self, _change: &ContentRectChanged) {}
+
+    /// Called after the stream moved from `old` to `new`.
+    ///
+    /// Runs on the thread that caused the change: the caller of
+    /// [`start_capture`](crate::stream::SCStream::start_capture) and
+    /// friends, or a `ScreenCaptureKit` queue when the stream fails on its
+    /// own. Calling back into the stream from here is allowed.
+    fn stream_state_did_change(&self, _old: SCStreamState, _new: SCStreamState) {}
+
     /// Called when stream stops.
     ///
     /// # Parameters
@@ -118,7 +255,7 @@ pub trait SCStreamDelegateTrait: Send + Sync {
 /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
 /// # let content = SCShareableContent::get()?;
 /// # let display = &content.displays()[0];
-/// # let filter = SCContentFilter::create().with_display(display).with_excluding_windows(&[]).build();
+/// # let filter = SCContentFilter::for_display(display).with_excluding_windows(&[]).build();
 /// # let config = SCStreamConfiguration::default();
 ///
 /// let error_handler = ErrorHandler::new(|error| {
@@ -164,6 +301,11 @@ where
     }
 }
 
+type MicrophoneLostHandler = Box<dyn Fn(&str, Option<&AudioInputDevice>) + Send + Sync + 'static>;
+type PermissionRevokedHandler = Box<dyn Fn(&PermissionRevoked) + Send + Sync + 'static>;
+type StateChangeHandler = Box<dyn Fn(SCStreamState, SCStreamState) + Send + Sync + 'static>;
+type ContentRectHandler = Box<dyn Fn(&ContentRectChanged) + Send + Sync + 'static>;
+
 /// Builder for closure-based stream delegate
 ///
 /// Provides a convenient way to create a stream delegate using closures
@@ -178,7 +320,7 @@ where
 /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
 /// # let content = SCShareableContent::get()?;
 /// # let display = &content.displays()[0];
-/// # let filter = SCContentFilter::create().with_display(display).with_excluding_windows(&[]).build();
+/// # let filter = SCContentFilter::for_display(display).with_excluding_windows(&[]).build();
 /// # let config = SCStreamConfiguration::default();
 ///
 /// // Create delegate with multiple callbacks
@@ -206,6 +348,12 @@ pub struct StreamCallbacks {
     on_inactive: Option<Box<dyn Fn() + Send + Sync + 'static>>,
     on_video_effect_start: Option<Box<dyn Fn() + Send + Sync + 'static>>,
     on_video_effect_stop: Option<Box<dyn Fn() + Send + Sync + 'static>>,
+    on_video_effect: Option<Box<dyn Fn(SCVideoEffect, bool) + Send + Sync + 'static>>,
+    on_microphone_device_lost: Option<MicrophoneLostHandler>,
+    on_permission_revoked: Option<PermissionRevokedHandler>,
+    on_display_sleep: Option<Box<dyn Fn(DisplaySleepEvent) + Send + Sync + 'static>>,
+    on_state_change: Option<StateChangeHandler>,
+    on_content_rect_change: Option<ContentRectHandler>,
 }
 
 impl StreamCallbacks {
@@ -219,6 +367,12 @@ impl StreamCallbacks {
             on_inactive: None,
             on_video_effect_start: None,
             on_video_effect_stop: None,
+            on_video_effect: None,
+            on_microphone_device_lost: None,
+            on_permission_revoked: None,
+            on_display_sleep: None,
+            on_state_change: None,
+            on_content_rect_change: None,
         }
     }
 
@@ -289,6 +443,71 @@ impl StreamCallbacks {
         self.on_video_effect_stop = Some(Box::new(f));
         self
     }
+
+    /// Set the callback for when a video effect turns on or off (macOS
+    /// 14.0+), called with the effect and whether it is now active
+    #[must_use]
+    pub fn on_video_effect<F>(mut self, f: F) -> Self
+    where
+        F: Fn(SCVideoEffect, bool) + Send + Sync + 'static,
+    {
+        self.on_video_effect = Some(Box::new(f));
+        self
+    }
+
+    /// Set the callback for when the selected microphone is unplugged and
+    /// the stream falls back to the default input
+    #[must_use]
+    pub fn on_microphone_device_lost<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&str, Option<&AudioInputDevice>) + Send + Sync + 'static,
+    {
+        self.on_microphone_device_lost = Some(Box::new(f));
+        self
+    }
+
+    /// Set the callback for when Screen Recording permission is revoked
+    #[must_use]
+    pub fn on_permission_revoked<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&PermissionRevoked) + Send + Sync + 'static,
+    {
+        self.on_permission_revoked = Some(Box::new(f));
+        self
+    }
+
+    /// Set the callback for when the display sleeps, the screen locks or
+    /// the screensaver starts, and when each of those ends
+    #[must_use]
+    pub fn on_display_sleep<F>(mut self, f: F) -> Self
+    where
+        F: Fn(DisplaySleepEvent) + Send + Sync + 'static,
+    {
+        self.on_display_sleep = Some(Box::new(f));
+        self
+    }
+
+    /// Set the callback for lifecycle state changes, called with the old
+    /// and new state
+    #[must_use]
+    pub fn on_state_change<F>(mut self, f: F) -> Self
+    where
+        F: Fn(SCStreamState, SCStreamState) + Send + Sync + 'static,
+    {
+        self.on_state_change = Some(Box::new(f));
+        self
+    }
+
+    /// Set the callback for changes to where the content sits in the
+    /// frame, e.g. when Presenter Overlay shrinks it
+    #[must_use]
+    pub fn on_content_rect_change<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&ContentRectChanged) + Send + Sync + 'static,
+    {
+        self.on_content_rect_change = Some(Box::new(f));
+        self
+    }
 }
 
 impl Default for StreamCallbacks {
@@ -309,6 +528,21 @@ impl std::fmt::Debug for StreamCallbacks {
                 &self.on_video_effect_start.is_some(),
             )
             .field("on_video_effect_stop", &self.on_video_effect_stop.is_some())
+            .field("on_video_effect", &self.on_video_effect.is_some())
+            .field(
+                "on_microphone_device_lost",
+                &self.on_microphone_device_lost.is_some(),
+            )
+            .field(
+                "on_permission_revoked",
+                &self.on_permission_revoked.is_some(),
+            )
+            .field("on_display_sleep", &self.on_display_sleep.is_some())
+            .field("on_state_change", &self.on_state_change.is_some())
+            .field(
+                "on_content_rect_change",
+                &self.on_content_rect_change.is_some(),
+            )
             .finish()
     }
 }
@@ -358,4 +592,48 @@ impl SCStreamDelegateTrait for StreamCallbacks {
             f();
         }
     }
+
+    fn video_effect_did_start(&self, effect: SCVideoEffect) {
+        if let Some(ref f) = self.on_video_effect {
+            f(effect, true);
+        }
+        self.output_video_effect_did_start_for_stream();
+    }
+
+    fn video_effect_did_stop(&self, effect: SCVideoEffect) {
+        if let Some(ref f) = self.on_video_effect {
+            f(effect, false);
+        }
+        self.output_video_effect_did_stop_for_stream();
+    }
+
+    fn microphone_device_lost(&self, lost_device_id: &str, fallback: Option<&AudioInputDevice>) {
+        if let Some(ref f) = self.on_microphone_device_lost {
+            f(lost_device_id, fallback);
+        }
+    }
+
+    fn permission_revoked(&self, event: &PermissionRevoked) {
+        if let Some(ref f) = self.on_permission_revoked {
+            f(event);
+        }
+    }
+
+    fn display_sleep_changed(&self, event: DisplaySleepEvent) {
+        if let Some(ref f) = self.on_display_sleep {
+            f(event);
+        }
+    }
+
+    fn stream_state_did_change(&self, old: SCStreamState, new: SCStreamState) {
+        if let Some(ref f) = self.on_state_change {
+            f(old, new);
+        }
+    }
+
+    fn content_rect_did_change(&self, change: &ContentRectChanged) {
+        if let Some(ref f) = self.on_content_rect_change {
+            f(change);
+        }
+    }
 }
@@ -0,0 +1,347 @@
+//! Keep a display stream's size in step with the display's resolution
+//!
+//! A stream configured for a display's size keeps that size after the user
+//! picks another resolution in System Settings, an external monitor is
+//! swapped, or a notebook lid opens and the scale factor changes. Frames
+//! are then letterboxed or scaled. [`DisplayFollower`] polls the display's
+//! [`DisplayMode`] on a background thread and, when it changes, resizes the
+//! stream with [`SCStream::update_configuration_with`] and tells the app the
+//! new dimensions.
+//!
+//! The stream keeps its proportion to the display: a stream at half the
+//! display's pixel width stays at half the new pixel width. Configure the
+//! stream at the display's full pixel size to always capture natively.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use std::time::Duration;
+//! use screencapturekit::prelude::*;
+//! use screencapturekit::stream::display_follower::{DisplayFollower, DisplayMode};
+//!
+//! # fn example() -> Result<(), SCError> {
+//! let content = SCShareableContent::get()?;
+//! let display = &content.displays()[0];
+//! let mode = DisplayMode::current(display.display_id()).expect("display online");
+//! let config = SCStreamConfiguration::new()
+//!     .with_width(mode.pixel_width())
+//!     .with_height(mode.pixel_height());
+//! let filter = SCContentFilter::for_display(display).with_excluding_windows(&[]).build();
+//! let stream = SCStream::new(&filter, &config);
+//! stream.start_capture()?;
+//!
+//! let follower = DisplayFollower::start_with_handler(
+//!     &stream,
+//!     display,
+//!     Duration::from_secs(1),
+//!     |resize| println!("now capturing {}x{}", resize.width(), resize.height()),
+//! )?;
+//! # drop(follower);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+use crate::error::SCError;
+use crate::shareable_content::SCDisplay;
+use crate::utils::panic_safe::catch_user_panic;
+use crate::utils::poller::{Poller, PollerContext};
+
+use super::SCStream;
+
+/// A display's resolution: its size in points and in pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DisplayMode {
+    point_width: u32,
+    point_height: u32,
+    pixel_width: u32,
+    pixel_height: u32,
+}
+
+impl DisplayMode {
+    /// The current mode of the display with `display_id`, or `None` if the
+    /// display is offline.
+    pub fn current(display_id: u32) -> Option<Self> {
+        let (mut point_width, mut point_height, mut pixel_width, mut pixel_height) = (0, 0, 0, 0);
+        let ok = unsafe {
+            crate::ffi::sc_display_get_current_mode(
+                display_id,
+                &mut point_width,
+                &mut point_height,
+                &mut pixel_width,
+                &mut pixel_height,
+            )
+        };
+        if !ok {
+            return None;
+        }
+        Some(Self {
+            point_width: u32::try_from(point_width).ok()?,
+            point_height: u32::try_from(point_height).ok()?,
+            pixel_width: u32::try_from(pixel_width).ok()?,
+            pixel_height: u32::try_from(pixel_height).ok()?,
+        })
+    }
+
+    /// Width in points.
+    pub const fn point_width(&self) -> u32 {
+        self.point_width
+    }
+
+    /// Height in points.
+    pub const fn point_height(&self) -> u32 {
+        self.point_height
+    }
+
+    /// Width in pixels.
+    pub const fn pixel_width(&self) -> u32 {
+        self.pixel_width
+    }
+
+    /// Height in pixels.
+    pub const fn pixel_height(&self) -> u32 {
+        self.pixel_height
+    }
+
+    /// Pixels per point, e.g. 2.0 on a Retina display.
+    pub fn scale_factor(&self) -> f64 {
+        if self.point_width == 0 {
+            return 1.0;
+        }
+        f64::from(self.pixel_width) / f64::from(self.point_width)
+    }
+}
+
+/// A resize applied by [`DisplayFollower`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DisplayResize {
+    display_id: u32,
+    previous: DisplayMode,
+    current: DisplayMode,
+    width: u32,
+    height: u32,
+}
+
+impl DisplayResize {
+    /// The display that changed.
+    pub const fn display_id(&self) -> u32 {
+        self.display_id
+    }
+
+    /// The display's mode before the change.
+    pub const fn previous(&self) -> DisplayMode {
+        self.previous
+    }
+
+    /// The display's new mode.
+    pub const fn current(&self) -> DisplayMode {
+        self.current
+    }
+
+    /// The stream's new width in pixels.
+    pub const fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// The stream's new height in pixels.
+    pub const fn height(&self) -> u32 {
+        self.height
+    }
+}
+
+type ResizeHandler = Box<dyn Fn(&DisplayResize) + Send>;
+
+struct FollowerShared {
+    updates: AtomicU64,
+    mode: Mutex<Option<DisplayMode>>,
+}
+
+/// Resizes a display stream when the display's resolution or scale changes.
+///
+/// Stops polling when dropped.
+pub struct DisplayFollower {
+    display_id: u32,
+    shared: Arc<FollowerShared>,
+    poller: Poller,
+}
+
+impl DisplayFollower {
+    /// Start polling `display`'s mode every `interval` and resize `stream`
+    /// when it changes.
+    ///
+    /// The display's mode when the follower starts is taken as the one the
+    /// stream's current size was chosen for.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InternalError`] if the operating system refuses
+    /// to spawn the polling thread.
+    pub fn start(
+        stream: &SCStream,
+        display: &SCDisplay,
+        interval: Duration,
+    ) -> Result<Self, SCError> {
+        Self::spawn(stream, display.display_id(), interval, None)
+    }
+
+    /// Like [`start`](Self::start), and call `on_resize` on the polling
+    /// thread after each resize.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InternalError`] if the operating system refuses
+    /// to spawn the polling thread.
+    pub fn start_with_handler(
+        stream: &SCStream,
+        display: &SCDisplay,
+        interval: Duration,
+        on_resize: impl Fn(&DisplayResize) + Send + 'static,
+    ) -> Result<Self, SCError> {
+        Self::spawn(
+            stream,
+            display.display_id(),
+            interval,
+            Some(Box::new(on_resize)),
+        )
+    }
+
+    fn spawn(
+        stream: &SCStream,
+        display_id: u32,
+        interval: Duration,
+        on_resize: Option<ResizeHandler>,
+    ) -> Result<Self, SCError> {
+        let shared = Arc::new(FollowerShared {
+            updates: AtomicU64::new(0),
+            mode: Mutex::new(DisplayMode::current(display_id)),
+        });
+
+        let poller = {
+            let shared = Arc::clone(&shared);
+            let stream = stream.clone();
+            Poller::spawn("display-follower", move |context| {
+                follow(
+                    context,
+                    &shared,
+                    &stream,
+                    display_id,
+                    interval,
+                    on_resize.as_ref(),
+                );
+            })?
+        };
+
+        Ok(Self {
+            display_id,
+            shared,
+            poller,
+        })
+    }
+
+    /// The display being followed.
+    #[must_use]
+    pub const fn display_id(&self) -> u32 {
+        self.display_id
+    }
+
+    /// The display's mode as of the last poll it was online for, or `None`
+    /// if it has been offline since the follower started.
+    #[must_use]
+    pub fn mode(&self) -> Option<DisplayMode> {
+        *self
+            .shared
+            .mode
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Number of times the stream has been resized.
+    #[must_use]
+    pub fn update_count(&self) -> u64 {
+        self.shared.updates.load(Ordering::Relaxed)
+    }
+
+    /// The most recent failure to resize the stream, if the last change
+    /// could not be applied. Cleared by the next successful resize.
+    #[must_use]
+    pub fn last_error(&self) -> Option<SCError> {
+        self.poller.last_error()
+    }
+}
+
+/// Scale `size` by `to / from`, rounding and keeping it at least 1.
+fn rescale(size: u32, from: u32, to: u32) -> u32 {
+    if from == 0 {
+        return size;
+    }
+    let scaled = (u64::from(size) * u64::from(to) + u64::from(from) / 2) / u64::from(from);
+    u32::try_from(scaled.max(1)).unwrap_or(u32::MAX)
+}
+
+fn follow(
+    context: &PollerContext,
+    shared: &FollowerShared,
+    stream: &SCStream,
+    display_id: u32,
+    interval: Duration,
+    on_resize: Option<&ResizeHandler>,
+) {
+    while !context.sleep(interval) {
+        // Keep the last known mode while the display is offline, so a
+        // change made meanwhile is still seen when it comes back.
+        let Some(current) = DisplayMode::current(display_id) else {
+            continue;
+        };
+        let previous = shared
+            .mode
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .replace(current);
+        let Some(previous) = previous.filter(|previous| *previous != current) else {
+            continue;
+        };
+
+        let mut size = (0, 0);
+        let result = stream.update_configuration_with(|config| {
+            size = (
+                rescale(config.width(), previous.pixel_width, current.pixel_width),
+                rescale(config.height(), previous.pixel_height, current.pixel_height),
+            );
+            config.set_width(size.0);
+            config.set_height(size.1);
+        });
+        let failed = result.is_err();
+        context.set_last_error(result.err());
+        if failed {
+            // Try again on the next poll.
+            *shared.mode.lock().unwrap_or_else(PoisonError::into_inner) = Some(previous);
+            continue;
+        }
+
+        shared.updates.fetch_add(1, Ordering::Relaxed);
+        if let Some(on_resize) = on_resize {
+            let resize = DisplayResize {
+                display_id,
+                previous,
+                current,
+                width: size.0,
+                height: size.1,
+            };
+            catch_user_panic("display follower handler", || on_resize(&resize));
+        }
+    }
+}
+
+impl fmt::Debug for DisplayFollower {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DisplayFollower")
+            .field("display_id", &self.display_id)
+            .field("mode", &self.mode())
+            .field("update_count", &self.update_count())
+            .finish_non_exhaustive()
+    }
+}
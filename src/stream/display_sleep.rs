@@ -0,0 +1,518 @@
+//! Notice when the captured display sleeps, locks or runs the screensaver
+//!
+//! When the display goes to sleep, the screen locks or the screensaver
+//! starts, `ScreenCaptureKit` keeps the stream running but delivers
+//! [`Blank`](SCFrameStatus::Blank) or [`Suspended`](SCFrameStatus::Suspended)
+//! frames, or pictures of the lock screen. A recorder left running
+//! overnight ends up with hours of black video.
+//!
+//! A [`DisplaySleepWatcher`] polls the display's sleep state, the session's
+//! lock state and the screensaver on a background thread. Each change is
+//! delivered to the stream's delegate as a
+//! [`display_sleep_changed`](super::delegate_trait::SCStreamDelegateTrait::display_sleep_changed)
+//! event, and the watcher's [`SleepPolicy`] decides what happens to the
+//! samples that arrive in the meantime. Samples only pass through the
+//! policy when their handler is wrapped with
+//! [`DisplaySleepWatcher::gate`]; the wrapped handler also reports blank
+//! and suspended frames as [`InactiveReason::BlankFrames`].
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use screencapturekit::prelude::*;
+//! use screencapturekit::stream::display_sleep::{DisplaySleepOptions, SleepPolicy};
+//! use screencapturekit::stream::StreamCallbacks;
+//!
+//! # fn example(filter: &SCContentFilter, config: &SCStreamConfiguration) -> Result<(), SCError> {
+//! let callbacks = StreamCallbacks::new().on_display_sleep(|event| println!("{event:?}"));
+//! let mut stream = SCStream::new_with_delegate(filter, config, callbacks);
+//! let watcher = stream.watch_display_sleep(DisplaySleepOptions::new(SleepPolicy::Pause))?;
+//! stream.add_output_handler(
+//!     watcher.gate(|sample: CMSampleBuffer, _| { /* encode */ }),
+//!     SCStreamOutputType::Screen,
+//! );
+//! stream.start_capture()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicU8, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+use crate::cm::{CMSampleBuffer, CMSampleBufferExt, CMSampleBufferSCExt, SCFrameStatus};
+use crate::error::SCError;
+use crate::utils::poller::{Poller, PollerContext};
+
+use super::output_trait::SCStreamOutputTrait;
+use super::output_type::SCStreamOutputType;
+use super::SCStream;
+
+/// Why there is nothing to capture
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InactiveReason {
+    /// The display is asleep
+    DisplaySleep,
+    /// The login session's screen is locked. Read from a private session
+    /// key, whose absence means unlocked; while the session itself can't be
+    /// read, the last known state is kept.
+    ScreenLocked,
+    /// The screensaver is running
+    Screensaver,
+    /// The stream is delivering [`Blank`](SCFrameStatus::Blank) or
+    /// [`Suspended`](SCFrameStatus::Suspended) frames. Only noticed by
+    /// handlers wrapped with [`DisplaySleepWatcher::gate`].
+    BlankFrames,
+}
+
+impl InactiveReason {
+    const ALL: [Self; 4] = [
+        Self::DisplaySleep,
+        Self::ScreenLocked,
+        Self::Screensaver,
+        Self::BlankFrames,
+    ];
+
+    const fn bit(self) -> u8 {
+        match self {
+            Self::DisplaySleep => 1 << 0,
+            Self::ScreenLocked => 1 << 1,
+            Self::Screensaver => 1 << 2,
+            Self::BlankFrames => 1 << 3,
+        }
+    }
+}
+
+impl fmt::Display for InactiveReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DisplaySleep => write!(f, "display asleep"),
+            Self::ScreenLocked => write!(f, "screen locked"),
+            Self::Screensaver => write!(f, "screensaver running"),
+            Self::BlankFrames => write!(f, "blank frames"),
+        }
+    }
+}
+
+/// A change in whether there is anything to capture
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DisplaySleepEvent {
+    /// `reason` started; capture content is gone until it ends
+    Inactive(InactiveReason),
+    /// `reason` ended. Content returns once no other reason remains.
+    Active(InactiveReason),
+}
+
+impl DisplaySleepEvent {
+    /// The reason that started or ended
+    #[must_use]
+    pub const fn reason(&self) -> InactiveReason {
+        match self {
+            Self::Inactive(reason) | Self::Active(reason) => *reason,
+        }
+    }
+}
+
+/// What happens to samples while the display is inactive
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum SleepPolicy {
+    /// Deliver samples unchanged; only report events
+    #[default]
+    Continue,
+    /// Drop every gated sample, audio included, until the display is
+    /// active again
+    Pause,
+    /// Replace screen frames with the last frame that had content,
+    /// retimed to the frame it replaces, so the recording shows a freeze
+    /// instead of black. Other samples are delivered unchanged.
+    Placeholder,
+    /// Stop the stream as soon as the display becomes inactive
+    Stop,
+}
+
+/// How a [`DisplaySleepWatcher`] checks and reacts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DisplaySleepOptions {
+    policy: SleepPolicy,
+    display_id: Option<u32>,
+    interval: Duration,
+}
+
+impl DisplaySleepOptions {
+    /// How often the watcher checks by default
+    pub const DEFAULT_INTERVAL: Duration = Duration::from_millis(500);
+
+    /// Apply `policy`, watching the main display every
+    /// [`DEFAULT_INTERVAL`](Self::DEFAULT_INTERVAL)
+    #[must_use]
+    pub const fn new(policy: SleepPolicy) -> Self {
+        Self {
+            policy,
+            display_id: None,
+            interval: Self::DEFAULT_INTERVAL,
+        }
+    }
+
+    /// Watch the display with this ID instead of the main display
+    #[must_use]
+    pub const fn with_display_id(mut self, display_id: u32) -> Self {
+        self.display_id = Some(display_id);
+        self
+    }
+
+    /// Check every `interval`
+    #[must_use]
+    pub const fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// The policy applied while inactive
+    #[must_use]
+    pub const fn policy(&self) -> SleepPolicy {
+        self.policy
+    }
+
+    /// The watched display, or `None` for the main display
+    #[must_use]
+    pub const fn display_id(&self) -> Option<u32> {
+        self.display_id
+    }
+
+    /// How often the watcher checks
+    #[must_use]
+    pub const fn interval(&self) -> Duration {
+        self.interval
+    }
+}
+
+impl Default for DisplaySleepOptions {
+    fn default() -> Self {
+        Self::new(SleepPolicy::default())
+    }
+}
+
+/// State shared by the watcher thread and its gated handlers.
+struct SleepShared {
+    policy: SleepPolicy,
+    /// [`InactiveReason`] bits currently in effect.
+    reasons: AtomicU8,
+    /// Most recent screen frame with content, for placeholders.
+    latest: Mutex<Option<CMSampleBuffer>>,
+    stream_stopped: AtomicBool,
+    /// Set once the watcher is dropped; gated handlers then pass
+    /// everything through.
+    detached: AtomicBool,
+    dropped: AtomicU64,
+    placeholders: AtomicU64,
+}
+
+impl SleepShared {
+    fn set_reason(&self, reason: InactiveReason, active: bool) {
+        if active {
+            self.reasons.fetch_or(reason.bit(), Ordering::AcqRel);
+        } else {
+            self.reasons.fetch_and(!reason.bit(), Ordering::AcqRel);
+        }
+    }
+
+    fn is_inactive(&self) -> bool {
+        !self.detached.load(Ordering::Acquire) && self.reasons.load(Ordering::Acquire) != 0
+    }
+}
+
+/// Watches for display sleep, screen lock and the screensaver while a
+/// stream runs.
+///
+/// Usually created with [`SCStream::watch_display_sleep`]. Stops when
+/// dropped; handlers returned by [`gate`](Self::gate) then deliver every
+/// sample again.
+pub struct DisplaySleepWatcher {
+    options: DisplaySleepOptions,
+    shared: Arc<SleepShared>,
+    poller: Poller,
+}
+
+impl DisplaySleepWatcher {
+    /// Start watching on behalf of `stream`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InternalError`] if the operating system refuses
+    /// to spawn the watcher thread.
+    pub fn start(stream: &SCStream, options: DisplaySleepOptions) -> Result<Self, SCError> {
+        let shared = Arc::new(SleepShared {
+            policy: options.policy,
+            reasons: AtomicU8::new(0),
+            latest: Mutex::new(None),
+            stream_stopped: AtomicBool::new(false),
+            detached: AtomicBool::new(false),
+            dropped: AtomicU64::new(0),
+            placeholders: AtomicU64::new(0),
+        });
+
+        let poller = {
+            let shared = Arc::clone(&shared);
+            let stream = stream.clone();
+            Poller::spawn("display-sleep-watcher", move |context| {
+                watch(context, &shared, &stream, options);
+            })?
+        };
+
+        Ok(Self {
+            options,
+            shared,
+            poller,
+        })
+    }
+
+    /// Wrap `handler` so its samples follow this watcher's policy.
+    ///
+    /// Register the result with
+    /// [`SCStream::add_output_handler`]. Wrap the audio handler too when
+    /// [`SleepPolicy::Pause`] should pause audio along with video.
+    #[must_use]
+    pub fn gate(&self, handler: impl SCStreamOutputTrait + 'static) -> SleepGatedOutput {
+        SleepGatedOutput {
+            handler: Box::new(handler),
+            shared: Arc::clone(&self.shared),
+        }
+    }
+
+    /// The options in effect
+    #[must_use]
+    pub const fn options(&self) -> DisplaySleepOptions {
+        self.options
+    }
+
+    /// Whether any inactive reason is in effect
+    #[must_use]
+    pub fn is_inactive(&self) -> bool {
+        self.shared.is_inactive()
+    }
+
+    /// The inactive reasons currently in effect
+    #[must_use]
+    pub fn inactive_reasons(&self) -> Vec<InactiveReason> {
+        let bits = self.shared.reasons.load(Ordering::Acquire);
+        InactiveReason::ALL
+            .into_iter()
+            .filter(|reason| bits & reason.bit() != 0)
+            .collect()
+    }
+
+    /// Samples dropped by gated handlers under [`SleepPolicy::Pause`] or
+    /// [`SleepPolicy::Stop`], or because no placeholder was available
+    #[must_use]
+    pub fn dropped_samples(&self) -> u64 {
+        self.shared.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Frames replaced under [`SleepPolicy::Placeholder`]
+    #[must_use]
+    pub fn placeholder_frames(&self) -> u64 {
+        self.shared.placeholders.load(Ordering::Relaxed)
+    }
+}
+
+/// Bit 3 of `sc_display_sleep_state`: the session dictionary couldn't be
+/// read, so the lock state is unknown.
+const LOCK_STATE_UNKNOWN: u32 = 1 << 3;
+
+/// The system reasons that apply, and the reasons that could be checked at
+/// all. An unknown lock state keeps whatever was last seen.
+fn system_reasons(display_id: Option<u32>) -> (u8, u8) {
+    let state = unsafe { crate::ffi::sc_display_sleep_state(display_id.unwrap_or(0)) };
+    let mut checked = InactiveReason::DisplaySleep.bit()
+        | InactiveReason::ScreenLocked.bit()
+        | InactiveReason::Screensaver.bit();
+    if state & LOCK_STATE_UNKNOWN != 0 {
+        checked &= !InactiveReason::ScreenLocked.bit();
+    }
+    let reasons = [
+        InactiveReason::DisplaySleep,
+        InactiveReason::ScreenLocked,
+        InactiveReason::Screensaver,
+    ]
+    .into_iter()
+    .enumerate()
+    .filter(|(index, _)| state & (1 << index) != 0)
+    .fold(0, |bits, (_, reason)| bits | reason.bit());
+    (reasons & checked, checked)
+}
+
+fn watch(
+    context: &PollerContext,
+    shared: &SleepShared,
+    stream: &SCStream,
+    options: DisplaySleepOptions,
+) {
+    let mut reported = 0u8;
+
+    loop {
+        let (system, checked) = system_reasons(options.display_id);
+        let merge = |bits: u8| (bits & !checked) | system;
+        let current =
+            match shared
+                .reasons
+                .fetch_update(Ordering::AcqRel, Ordering::Acquire, |bits| {
+                    Some(merge(bits))
+                }) {
+                Ok(previous) | Err(previous) => merge(previous),
+            };
+
+        for reason in InactiveReason::ALL {
+            let was = reported & reason.bit() != 0;
+            let is = current & reason.bit() != 0;
+            if was != is {
+                let event = if is {
+                    DisplaySleepEvent::Inactive(reason)
+                } else {
+                    DisplaySleepEvent::Active(reason)
+                };
+                stream.notify_display_sleep_changed(event);
+            }
+        }
+        reported = current;
+
+        if current != 0
+            && shared.policy == SleepPolicy::Stop
+            && !shared.stream_stopped.swap(true, Ordering::AcqRel)
+        {
+            let _ = stream.stop_capture();
+        }
+
+        if context.sleep(options.interval) {
+            return;
+        }
+    }
+}
+
+impl Drop for DisplaySleepWatcher {
+    fn drop(&mut self) {
+        self.poller.stop();
+        self.shared.detached.store(true, Ordering::Release);
+        self.shared
+            .latest
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take();
+    }
+}
+
+impl fmt::Debug for DisplaySleepWatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DisplaySleepWatcher")
+            .field("options", &self.options)
+            .field("inactive_reasons", &self.inactive_reasons())
+            .field("dropped_samples", &self.dropped_samples())
+            .field("placeholder_frames", &self.placeholder_frames())
+            .finish_non_exhaustive()
+    }
+}
+
+/// An output handler whose samples follow a [`DisplaySleepWatcher`]'s
+/// [`SleepPolicy`].
+///
+/// Created by [`DisplaySleepWatcher::gate`].
+pub struct SleepGatedOutput {
+    handler: Box<dyn SCStreamOutputTrait>,
+    shared: Arc<SleepShared>,
+}
+
+impl SleepGatedOutput {
+    /// Track blank frames and remember the last frame with content.
+    fn observe(&self, sample: &CMSampleBuffer) {
+        if self.shared.detached.load(Ordering::Acquire) {
+            return;
+        }
+        match sample.frame_status() {
+            Some(SCFrameStatus::Blank | SCFrameStatus::Suspended) => {
+                self.shared.set_reason(InactiveReason::BlankFrames, true);
+            }
+            None | Some(SCFrameStatus::Complete | SCFrameStatus::Started) => {
+                self.shared.set_reason(InactiveReason::BlankFrames, false);
+                if self.shared.policy == SleepPolicy::Placeholder
+                    && sample.image_buffer().is_some()
+                    && !self.shared.is_inactive()
+                {
+                    *self
+                        .shared
+                        .latest
+                        .lock()
+                        .unwrap_or_else(PoisonError::into_inner) = Some(sample.clone());
+                }
+            }
+            Some(SCFrameStatus::Idle | SCFrameStatus::Stopped) => {}
+        }
+    }
+
+    /// The last frame with content, retimed to `sample`'s timing.
+    fn placeholder_for(&self, sample: &CMSampleBuffer) -> Option<CMSampleBuffer> {
+        let latest = self
+            .shared
+            .latest
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .as_ref()?
+            .clone();
+        let timing = sample.sample_timing_info(0).ok()?;
+        let mut retimed = std::ptr::null_mut();
+        // SAFETY: `timing` is one `CMSampleTimingInfo`, laid out like Core
+        // Media's, and lives until the call returns; on success the bridge
+        // hands back a +1 sample buffer.
+        unsafe {
+            let status = crate::cm::ffi::cm_sample_buffer_create_copy_with_new_timing(
+                latest.as_ptr(),
+                1,
+                std::ptr::from_ref(&timing).cast(),
+                &mut retimed,
+            );
+            (status == 0 && !retimed.is_null()).then(|| CMSampleBuffer::from_ptr(retimed))
+        }
+    }
+}
+
+impl SCStreamOutputTrait for SleepGatedOutput {
+    fn did_output_sample_buffer(&self, sample_buffer: CMSampleBuffer, of_type: SCStreamOutputType) {
+        if of_type == SCStreamOutputType::Screen {
+            self.observe(&sample_buffer);
+        }
+        if !self.shared.is_inactive() {
+            self.handler
+                .did_output_sample_buffer(sample_buffer, of_type);
+            return;
+        }
+        match self.shared.policy {
+            SleepPolicy::Continue => {
+                self.handler
+                    .did_output_sample_buffer(sample_buffer, of_type);
+            }
+            SleepPolicy::Placeholder if of_type != SCStreamOutputType::Screen => {
+                self.handler
+                    .did_output_sample_buffer(sample_buffer, of_type);
+            }
+            SleepPolicy::Placeholder => {
+                if let Some(placeholder) = self.placeholder_for(&sample_buffer) {
+                    self.shared.placeholders.fetch_add(1, Ordering::Relaxed);
+                    self.handler.did_output_sample_buffer(placeholder, of_type);
+                } else {
+                    self.shared.dropped.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+            SleepPolicy::Pause | SleepPolicy::Stop => {
+                self.shared.dropped.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+}
+
+impl fmt::Debug for SleepGatedOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SleepGatedOutput")
+            .field("policy", &self.shared.policy)
+            .field("inactive", &self.shared.is_inactive())
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,489 @@
+//! Capture at lower cost while the Mac is on battery or running hot
+//!
+//! A stream keeps capturing at its configured frame rate regardless of
+//! whether the Mac is plugged in, in Low Power Mode, or thermally throttled.
+//! [`EnergyModeController`] samples the [`PowerState`] on a background
+//! thread and, when the [`LowPowerPolicy`] says so, switches the stream to
+//! [`EnergyMode::LowPower`]: a lower frame rate, a shallower frame queue and
+//! SDR instead of HDR. The stream's own settings are restored once
+//! conditions recover.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use std::time::Duration;
+//! use screencapturekit::prelude::*;
+//! use screencapturekit::stream::energy_mode::{EnergyModeController, LowPowerPolicy};
+//!
+//! # fn example(stream: &SCStream) -> Result<(), SCError> {
+//! let policy = LowPowerPolicy::new().with_frame_rate(10.0);
+//! let controller = EnergyModeController::start_with_handler(
+//!     stream,
+//!     policy,
+//!     Duration::from_secs(5),
+//!     |switch| println!("energy mode now {:?}", switch.current()),
+//! )?;
+//! # drop(controller);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+use crate::cm::CMTime;
+use crate::error::SCError;
+use crate::stream::configuration::SCStreamConfiguration;
+use crate::utils::panic_safe::catch_user_panic;
+use crate::utils::poller::{Poller, PollerContext};
+
+use super::SCStream;
+
+/// How hot the system is running, from `ProcessInfo.thermalState`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum ThermalState {
+    /// Within normal limits.
+    #[default]
+    Nominal,
+    /// Slightly elevated.
+    Fair,
+    /// High; the system is reducing performance.
+    Serious,
+    /// Very high; the system is significantly throttled.
+    Critical,
+}
+
+impl ThermalState {
+    /// The current thermal state.
+    pub fn current() -> Self {
+        match unsafe { crate::ffi::sc_power_get_thermal_state() } {
+            1 => Self::Fair,
+            2 => Self::Serious,
+            3 => Self::Critical,
+            _ => Self::Nominal,
+        }
+    }
+}
+
+/// Power source, Low Power Mode and thermal state at one moment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct PowerState {
+    /// Running from the battery rather than external power.
+    pub on_battery: bool,
+    /// Low Power Mode is turned on.
+    pub low_power_mode: bool,
+    /// How hot the system is running.
+    pub thermal_state: ThermalState,
+}
+
+impl PowerState {
+    /// Read the current conditions.
+    pub fn current() -> Self {
+        Self {
+            on_battery: unsafe { crate::ffi::sc_power_is_on_battery() },
+            low_power_mode: unsafe { crate::ffi::sc_power_is_low_power_mode() },
+            thermal_state: ThermalState::current(),
+        }
+    }
+}
+
+/// Whether a stream is capturing with its own settings or reduced ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum EnergyMode {
+    /// The stream's configured settings.
+    #[default]
+    Normal,
+    /// Reduced frame rate and queue depth, SDR only.
+    LowPower,
+}
+
+/// When to switch to [`EnergyMode::LowPower`] and what to reduce.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LowPowerPolicy {
+    frame_rate: f64,
+    queue_depth: u32,
+    on_battery: bool,
+    low_power_mode: bool,
+    thermal_threshold: Option<ThermalState>,
+}
+
+impl LowPowerPolicy {
+    /// Switch on battery, in Low Power Mode, or at
+    /// [`ThermalState::Serious`] and above; capture at 15 fps with a queue
+    /// depth of 3.
+    pub const fn new() -> Self {
+        Self {
+            frame_rate: 15.0,
+            queue_depth: 3,
+            on_battery: true,
+            low_power_mode: true,
+            thermal_threshold: Some(ThermalState::Serious),
+        }
+    }
+
+    /// Frame rate cap while in low-power mode. Streams already slower are
+    /// left alone.
+    #[must_use]
+    pub fn with_frame_rate(mut self, frame_rate: f64) -> Self {
+        self.frame_rate = frame_rate;
+        self
+    }
+
+    /// Queue depth cap while in low-power mode.
+    #[must_use]
+    pub const fn with_queue_depth(mut self, queue_depth: u32) -> Self {
+        self.queue_depth = queue_depth;
+        self
+    }
+
+    /// Whether running on battery triggers low-power mode.
+    #[must_use]
+    pub const fn with_on_battery(mut self, enabled: bool) -> Self {
+        self.on_battery = enabled;
+        self
+    }
+
+    /// Whether the system's Low Power Mode triggers low-power mode.
+    #[must_use]
+    pub const fn with_low_power_mode(mut self, enabled: bool) -> Self {
+        self.low_power_mode = enabled;
+        self
+    }
+
+    /// The thermal state at and above which low-power mode is used, or
+    /// `None` to ignore the thermal state.
+    #[must_use]
+    pub const fn with_thermal_threshold(mut self, threshold: Option<ThermalState>) -> Self {
+        self.thermal_threshold = threshold;
+        self
+    }
+
+    /// Frame rate cap while in low-power mode.
+    pub fn frame_rate(&self) -> f64 {
+        self.frame_rate
+    }
+
+    /// Queue depth cap while in low-power mode.
+    pub const fn queue_depth(&self) -> u32 {
+        self.queue_depth
+    }
+
+    /// Whether running on battery triggers low-power mode.
+    pub const fn on_battery(&self) -> bool {
+        self.on_battery
+    }
+
+    /// Whether the system's Low Power Mode triggers low-power mode.
+    pub const fn low_power_mode(&self) -> bool {
+        self.low_power_mode
+    }
+
+    /// The thermal state at and above which low-power mode is used.
+    pub const fn thermal_threshold(&self) -> Option<ThermalState> {
+        self.thermal_threshold
+    }
+
+    /// The mode this policy picks under `power`.
+    pub fn mode_for(&self, power: &PowerState) -> EnergyMode {
+        let hot = self
+            .thermal_threshold
+            .is_some_and(|threshold| power.thermal_state >= threshold);
+        if (self.on_battery && power.on_battery)
+            || (self.low_power_mode && power.low_power_mode)
+            || hot
+        {
+            EnergyMode::LowPower
+        } else {
+            EnergyMode::Normal
+        }
+    }
+}
+
+impl Default for LowPowerPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A switch between energy modes made by [`EnergyModeController`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EnergyModeSwitch {
+    previous: EnergyMode,
+    current: EnergyMode,
+    power: PowerState,
+}
+
+impl EnergyModeSwitch {
+    /// The mode before the switch.
+    pub const fn previous(&self) -> EnergyMode {
+        self.previous
+    }
+
+    /// The mode now in effect.
+    pub const fn current(&self) -> EnergyMode {
+        self.current
+    }
+
+    /// The conditions that led to the switch.
+    pub const fn power(&self) -> PowerState {
+        self.power
+    }
+}
+
+type SwitchHandler = Box<dyn Fn(&EnergyModeSwitch) + Send>;
+
+/// The stream settings low-power mode overrides, kept to restore them.
+struct NormalSettings {
+    minimum_frame_interval: CMTime,
+    queue_depth: u32,
+    #[cfg(feature = "macos_15_0")]
+    dynamic_range: crate::stream::configuration::SCCaptureDynamicRange,
+}
+
+impl NormalSettings {
+    fn read(config: &SCStreamConfiguration) -> Self {
+        Self {
+            minimum_frame_interval: config.minimum_frame_interval(),
+            queue_depth: config.queue_depth(),
+            #[cfg(feature = "macos_15_0")]
+            dynamic_range: config.capture_dynamic_range(),
+        }
+    }
+
+    fn restore(&self, config: &mut SCStreamConfiguration) {
+        config.set_minimum_frame_interval(&self.minimum_frame_interval);
+        config.set_queue_depth(self.queue_depth);
+        #[cfg(feature = "macos_15_0")]
+        config.set_capture_dynamic_range(self.dynamic_range);
+    }
+}
+
+fn reduce(config: &mut SCStreamConfiguration, policy: &LowPowerPolicy) {
+    let rate = config.frame_rate();
+    if rate <= 0.0 || rate > policy.frame_rate {
+        config.set_frame_rate(policy.frame_rate);
+    }
+    if config.queue_depth() > policy.queue_depth {
+        config.set_queue_depth(policy.queue_depth);
+    }
+    #[cfg(feature = "macos_15_0")]
+    config.set_capture_dynamic_range(crate::stream::configuration::SCCaptureDynamicRange::SDR);
+}
+
+struct ControllerShared {
+    switches: AtomicU64,
+    mode: Mutex<EnergyMode>,
+    forced: Mutex<Option<EnergyMode>>,
+    power: Mutex<PowerState>,
+}
+
+/// Switches a stream between its own settings and reduced ones as power
+/// and thermal conditions change.
+///
+/// The stream's settings are restored when the controller is dropped.
+pub struct EnergyModeController {
+    policy: LowPowerPolicy,
+    shared: Arc<ControllerShared>,
+    poller: Poller,
+}
+
+impl EnergyModeController {
+    /// Check conditions every `interval` and switch `stream` as `policy`
+    /// decides. The first check happens immediately.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InternalError`] if the operating system refuses
+    /// to spawn the controller thread.
+    pub fn start(
+        stream: &SCStream,
+        policy: LowPowerPolicy,
+        interval: Duration,
+    ) -> Result<Self, SCError> {
+        Self::spawn(stream, policy, interval, None)
+    }
+
+    /// Like [`start`](Self::start), and call `on_switch` on the controller
+    /// thread after each switch.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InternalError`] if the operating system refuses
+    /// to spawn the controller thread.
+    pub fn start_with_handler(
+        stream: &SCStream,
+        policy: LowPowerPolicy,
+        interval: Duration,
+        on_switch: impl Fn(&EnergyModeSwitch) + Send + 'static,
+    ) -> Result<Self, SCError> {
+        Self::spawn(stream, policy, interval, Some(Box::new(on_switch)))
+    }
+
+    fn spawn(
+        stream: &SCStream,
+        policy: LowPowerPolicy,
+        interval: Duration,
+        on_switch: Option<SwitchHandler>,
+    ) -> Result<Self, SCError> {
+        let shared = Arc::new(ControllerShared {
+            switches: AtomicU64::new(0),
+            mode: Mutex::new(EnergyMode::Normal),
+            forced: Mutex::new(None),
+            power: Mutex::new(PowerState::default()),
+        });
+
+        let poller = {
+            let shared = Arc::clone(&shared);
+            let stream = stream.clone();
+            Poller::spawn("energy-mode", move |context| {
+                control(
+                    context,
+                    &shared,
+                    &stream,
+                    &policy,
+                    interval,
+                    on_switch.as_ref(),
+                );
+            })?
+        };
+
+        Ok(Self {
+            policy,
+            shared,
+            poller,
+        })
+    }
+
+    /// The policy deciding when to switch.
+    #[must_use]
+    pub const fn policy(&self) -> &LowPowerPolicy {
+        &self.policy
+    }
+
+    /// The mode the stream is in.
+    #[must_use]
+    pub fn mode(&self) -> EnergyMode {
+        *self
+            .shared
+            .mode
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// The conditions seen at the last check.
+    #[must_use]
+    pub fn power_state(&self) -> PowerState {
+        *self
+            .shared
+            .power
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Hold the stream in `mode` regardless of conditions, or pass `None`
+    /// to follow the policy again. Applied at the next check.
+    pub fn set_forced_mode(&self, mode: Option<EnergyMode>) {
+        *self
+            .shared
+            .forced
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = mode;
+    }
+
+    /// The mode set with [`set_forced_mode`](Self::set_forced_mode), if any.
+    #[must_use]
+    pub fn forced_mode(&self) -> Option<EnergyMode> {
+        *self
+            .shared
+            .forced
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Number of switches applied to the stream.
+    #[must_use]
+    pub fn switch_count(&self) -> u64 {
+        self.shared.switches.load(Ordering::Relaxed)
+    }
+
+    /// The most recent failure to reconfigure the stream, if the last switch
+    /// could not be applied. Cleared by the next successful switch.
+    #[must_use]
+    pub fn last_error(&self) -> Option<SCError> {
+        self.poller.last_error()
+    }
+}
+
+fn control(
+    context: &PollerContext,
+    shared: &ControllerShared,
+    stream: &SCStream,
+    policy: &LowPowerPolicy,
+    interval: Duration,
+    on_switch: Option<&SwitchHandler>,
+) {
+    let mut normal: Option<NormalSettings> = None;
+    let mut first = true;
+    loop {
+        if !first && context.sleep(interval) {
+            break;
+        }
+        first = false;
+
+        let power = PowerState::current();
+        *shared.power.lock().unwrap_or_else(PoisonError::into_inner) = power;
+        let forced = *shared.forced.lock().unwrap_or_else(PoisonError::into_inner);
+        let target = forced.unwrap_or_else(|| policy.mode_for(&power));
+        let previous = *shared.mode.lock().unwrap_or_else(PoisonError::into_inner);
+        if target == previous {
+            continue;
+        }
+
+        let result = match target {
+            EnergyMode::LowPower => stream.update_configuration_with(|config| {
+                normal = Some(NormalSettings::read(config));
+                reduce(config, policy);
+            }),
+            EnergyMode::Normal => stream.update_configuration_with(|config| {
+                if let Some(normal) = &normal {
+                    normal.restore(config);
+                }
+            }),
+        };
+        let failed = result.is_err();
+        context.set_last_error(result.err());
+        if failed {
+            // Try again at the next check.
+            continue;
+        }
+
+        *shared.mode.lock().unwrap_or_else(PoisonError::into_inner) = target;
+        shared.switches.fetch_add(1, Ordering::Relaxed);
+        if let Some(on_switch) = on_switch {
+            let switch = EnergyModeSwitch {
+                previous,
+                current: target,
+                power,
+            };
+            catch_user_panic("energy mode handler", || on_switch(&switch));
+        }
+    }
+
+    // Leave the stream as it was configured.
+    let mode = *shared.mode.lock().unwrap_or_else(PoisonError::into_inner);
+    if let (EnergyMode::LowPower, Some(normal)) = (mode, normal) {
+        let _ = stream.update_configuration_with(|config| normal.restore(config));
+    }
+}
+
+impl fmt::Debug for EnergyModeController {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EnergyModeController")
+            .field("policy", &self.policy)
+            .field("mode", &self.mode())
+            .field("switch_count", &self.switch_count())
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,271 @@
+//! Deliver screen frames only when something on screen changed
+//!
+//! `ScreenCaptureKit` keeps delivering frames at the configured rate even
+//! when nothing moves: [`SCFrameStatus::Idle`] frames with no pixels, and
+//! complete frames whose dirty rects cover a blinking cursor. A remote
+//! desktop or screen sharing client only wants to encode and send a frame
+//! when the picture changed. [`EventDrivenOutputHandler`] wraps a handler
+//! and drops idle frames and frames whose changed area is below a
+//! threshold, so the stream behaves like an event source.
+//!
+//! ## Keepalive
+//!
+//! Viewers that join late, or a lossy transport, need a full frame now and
+//! then even on a static screen. With a
+//! [`keepalive`](EventDrivenOptions::with_keepalive) interval set, a frame
+//! is forwarded whenever that long has passed since the last one. If the
+//! screen hasn't changed at all, the most recent frame with content is sent
+//! again.
+//!
+//! Re-sending needs that frame kept alive, which holds one surface of the
+//! stream's pool (see
+//! [`queue_depth`](crate::stream::configuration::SCStreamConfiguration::queue_depth)).
+//! Without a keepalive no frame is held.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use std::time::Duration;
+//! use screencapturekit::prelude::*;
+//! use screencapturekit::stream::event_driven::EventDrivenOptions;
+//!
+//! # fn example(filter: &SCContentFilter, config: &SCStreamConfiguration) {
+//! let mut stream = SCStream::new(filter, config);
+//! stream.add_output_handler_event_driven(
+//!     |sample: CMSampleBuffer, _| { /* encode and send */ },
+//!     SCStreamOutputType::Screen,
+//!     EventDrivenOptions::new()
+//!         .with_min_dirty_fraction(0.001)
+//!         .with_keepalive(Duration::from_secs(2)),
+//! );
+//! # }
+//! ```
+
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use crate::cm::{CMSampleBuffer, CMSampleBufferExt, CMSampleBufferSCExt, SCFrameStatus};
+
+use super::output_trait::SCStreamOutputTrait;
+use super::output_type::SCStreamOutputType;
+
+/// When an [`EventDrivenOutputHandler`] forwards a frame.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct EventDrivenOptions {
+    min_dirty_fraction: f64,
+    keepalive: Option<Duration>,
+}
+
+impl EventDrivenOptions {
+    /// Forward every frame with any change, never re-send.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            min_dirty_fraction: 0.0,
+            keepalive: None,
+        }
+    }
+
+    /// Hold back complete frames until the area changed since the last
+    /// forwarded frame reaches `fraction` of the frame (`0.0`–`1.0`).
+    ///
+    /// Changes add up across held-back frames, so a series of small updates
+    /// is eventually delivered. At `0.0` (the default) only idle frames are
+    /// dropped.
+    #[must_use]
+    pub const fn with_min_dirty_fraction(mut self, fraction: f64) -> Self {
+        self.min_dirty_fraction = fraction;
+        self
+    }
+
+    /// Forward a frame at least every `interval`, re-sending the last one
+    /// if the screen is idle. See the [module docs](self#keepalive).
+    #[must_use]
+    pub const fn with_keepalive(mut self, interval: Duration) -> Self {
+        self.keepalive = Some(interval);
+        self
+    }
+
+    /// Fraction of the frame that must change before a frame is forwarded.
+    #[must_use]
+    pub const fn min_dirty_fraction(&self) -> f64 {
+        self.min_dirty_fraction
+    }
+
+    /// Longest time between forwarded frames, if set.
+    #[must_use]
+    pub const fn keepalive(&self) -> Option<Duration> {
+        self.keepalive
+    }
+}
+
+struct GateState {
+    /// Changed fraction accumulated since the last forwarded frame.
+    dirty: f64,
+    last_forwarded: Option<Instant>,
+    /// Most recent frame with content, kept for keepalives.
+    latest: Option<CMSampleBuffer>,
+}
+
+/// Forwards screen frames to a handler only when enough of the screen
+/// changed, or a keepalive is due.
+///
+/// Usually created through
+/// [`SCStream::add_output_handler_event_driven`](crate::stream::SCStream::add_output_handler_event_driven).
+/// Audio samples and frames reporting a state change
+/// ([`Started`](SCFrameStatus::Started), [`Blank`](SCFrameStatus::Blank),
+/// [`Suspended`](SCFrameStatus::Suspended),
+/// [`Stopped`](SCFrameStatus::Stopped)) are always forwarded. Frames without
+/// a status or dirty rects, such as ones not produced by `ScreenCaptureKit`,
+/// count as fully changed.
+pub struct EventDrivenOutputHandler {
+    handler: Box<dyn SCStreamOutputTrait>,
+    options: EventDrivenOptions,
+    state: Mutex<GateState>,
+    forwarded: AtomicU64,
+    suppressed: AtomicU64,
+    keepalives: AtomicU64,
+}
+
+impl EventDrivenOutputHandler {
+    /// Wrap `handler`, forwarding frames according to `options`.
+    #[must_use]
+    pub fn new(handler: impl SCStreamOutputTrait + 'static, options: EventDrivenOptions) -> Self {
+        Self {
+            handler: Box::new(handler),
+            options,
+            state: Mutex::new(GateState {
+                dirty: 0.0,
+                last_forwarded: None,
+                latest: None,
+            }),
+            forwarded: AtomicU64::new(0),
+            suppressed: AtomicU64::new(0),
+            keepalives: AtomicU64::new(0),
+        }
+    }
+
+    /// The options in effect.
+    #[must_use]
+    pub const fn options(&self) -> EventDrivenOptions {
+        self.options
+    }
+
+    /// Screen frames handed to the handler, keepalives included.
+    #[must_use]
+    pub fn forwarded_frames(&self) -> u64 {
+        self.forwarded.load(Ordering::Relaxed)
+    }
+
+    /// Screen frames dropped because they were idle or changed too little.
+    #[must_use]
+    pub fn suppressed_frames(&self) -> u64 {
+        self.suppressed.load(Ordering::Relaxed)
+    }
+
+    /// Frames forwarded only because the keepalive interval ran out.
+    #[must_use]
+    pub fn keepalive_frames(&self) -> u64 {
+        self.keepalives.load(Ordering::Relaxed)
+    }
+
+    /// Decide what to forward for `sample`, under the state lock.
+    fn gate(&self, sample: CMSampleBuffer) -> Option<(CMSampleBuffer, bool)> {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        let now = Instant::now();
+        let keepalive_due = self.options.keepalive.is_some_and(|interval| {
+            !matches!(state.last_forwarded, Some(last) if now.duration_since(last) < interval)
+        });
+
+        let forward = match sample.frame_status() {
+            Some(SCFrameStatus::Idle) => {
+                if !keepalive_due {
+                    return None;
+                }
+                state.latest.as_ref().map(|latest| (latest.clone(), true))
+            }
+            None | Some(SCFrameStatus::Complete) => {
+                state.dirty += dirty_fraction(&sample);
+                if self.options.keepalive.is_some() {
+                    state.latest = Some(sample.clone());
+                }
+                if state.dirty >= self.options.min_dirty_fraction.max(f64::MIN_POSITIVE) {
+                    Some((sample, false))
+                } else if keepalive_due {
+                    Some((sample, true))
+                } else {
+                    None
+                }
+            }
+            Some(_) => {
+                if sample.image_buffer().is_some() && self.options.keepalive.is_some() {
+                    state.latest = Some(sample.clone());
+                }
+                Some((sample, false))
+            }
+        };
+
+        if forward.is_some() {
+            state.dirty = 0.0;
+            state.last_forwarded = Some(now);
+        }
+        forward
+    }
+}
+
+/// Share of the frame covered by its dirty rects. Overlapping rects may
+/// count twice, which only errs towards forwarding.
+fn dirty_fraction(sample: &CMSampleBuffer) -> f64 {
+    let Some(buffer) = sample.image_buffer() else {
+        return 0.0;
+    };
+    let Some(rects) = sample.dirty_rects() else {
+        return 1.0;
+    };
+    #[allow(clippy::cast_precision_loss)]
+    let (width, height) = (buffer.width() as f64, buffer.height() as f64);
+    if width <= 0.0 || height <= 0.0 {
+        return 0.0;
+    }
+    let area: f64 = rects
+        .iter()
+        .map(|rect| {
+            let (x, y) = (rect.origin.x, rect.origin.y);
+            let w = (x + rect.size.width).min(width) - x.max(0.0);
+            let h = (y + rect.size.height).min(height) - y.max(0.0);
+            w.max(0.0) * h.max(0.0)
+        })
+        .sum();
+    (area / (width * height)).min(1.0)
+}
+
+impl SCStreamOutputTrait for EventDrivenOutputHandler {
+    fn did_output_sample_buffer(&self, sample_buffer: CMSampleBuffer, of_type: SCStreamOutputType) {
+        if of_type != SCStreamOutputType::Screen {
+            self.handler
+                .did_output_sample_buffer(sample_buffer, of_type);
+            return;
+        }
+        let Some((sample, keepalive)) = self.gate(sample_buffer) else {
+            self.suppressed.fetch_add(1, Ordering::Relaxed);
+            return;
+        };
+        self.forwarded.fetch_add(1, Ordering::Relaxed);
+        if keepalive {
+            self.keepalives.fetch_add(1, Ordering::Relaxed);
+        }
+        self.handler.did_output_sample_buffer(sample, of_type);
+    }
+}
+
+impl fmt::Debug for EventDrivenOutputHandler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventDrivenOutputHandler")
+            .field("options", &self.options)
+            .field("forwarded_frames", &self.forwarded_frames())
+            .field("suppressed_frames", &self.suppressed_frames())
+            .finish_non_exhaustive()
+    }
+}
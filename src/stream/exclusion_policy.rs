@@ -0,0 +1,363 @@
+//! Keep applications out of every capture
+//!
+//! An [`ExclusionPolicy`] is a list of bundle identifiers whose windows
+//! should never be captured — password managers, chat apps, anything
+//! private. It is saved as plain text, one bundle identifier per line, so a
+//! settings screen can persist it between launches.
+//!
+//! Window IDs and process IDs change as applications relaunch, so the policy
+//! is resolved against the current shareable content when a filter is built
+//! with
+//! [`with_exclusion_policy`](super::content_filter::DisplayFilterBuilder::with_exclusion_policy).
+//! An [`ExclusionWatcher`] repeats that whenever an excluded application
+//! opens windows or starts another process, and replaces the stream's filter.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use std::time::Duration;
+//! use screencapturekit::prelude::*;
+//! use screencapturekit::stream::exclusion_policy::{ExclusionPolicy, ExclusionWatcher};
+//!
+//! # fn example() -> Result<(), SCError> {
+//! let policy = ExclusionPolicy::load("exclusions.txt")
+//!     .unwrap_or_else(|_| ExclusionPolicy::password_managers());
+//!
+//! let content = SCShareableContent::get()?;
+//! let display = &content.displays()[0];
+//! let filter = SCContentFilter::for_display(display)
+//!     .with_exclusion_policy(&policy)
+//!     .try_build()?;
+//! let stream = SCStream::new(&filter, &SCStreamConfiguration::default());
+//! stream.start_capture()?;
+//!
+//! let _watcher = ExclusionWatcher::start(&stream, display, &policy, Duration::from_secs(1))?;
+//! policy.save("exclusions.txt")?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::error::SCError;
+use crate::shareable_content::{SCDisplay, SCShareableContent, SCWindow};
+use crate::utils::poller::{Poller, PollerContext};
+
+use super::content_filter::{state, DisplayFilterBuilder, SCContentFilter};
+use super::SCStream;
+
+/// Bundle identifiers of common password managers.
+const PASSWORD_MANAGERS: &[&str] = &[
+    "com.1password.1password",
+    "com.agilebits.onepassword7",
+    "com.bitwarden.desktop",
+    "org.keepassxc.keepassxc",
+    "com.apple.Passwords",
+    "com.apple.keychainaccess",
+];
+
+/// Applications whose windows are left out of display captures.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExclusionPolicy {
+    bundle_ids: Vec<String>,
+}
+
+impl ExclusionPolicy {
+    /// An empty policy.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            bundle_ids: Vec::new(),
+        }
+    }
+
+    /// A policy excluding well-known password managers: 1Password,
+    /// Bitwarden, `KeePassXC`, Passwords and Keychain Access.
+    #[must_use]
+    pub fn password_managers() -> Self {
+        PASSWORD_MANAGERS
+            .iter()
+            .fold(Self::new(), |policy, id| policy.with_bundle_id(*id))
+    }
+
+    /// Also exclude the application with `bundle_id`.
+    #[must_use]
+    pub fn with_bundle_id(mut self, bundle_id: impl Into<String>) -> Self {
+        self.insert(bundle_id);
+        self
+    }
+
+    /// Add `bundle_id`, returning `false` if it was already excluded.
+    pub fn insert(&mut self, bundle_id: impl Into<String>) -> bool {
+        let bundle_id = bundle_id.into();
+        if self.contains(&bundle_id) {
+            return false;
+        }
+        self.bundle_ids.push(bundle_id);
+        true
+    }
+
+    /// Remove `bundle_id`, returning `false` if it wasn't excluded.
+    pub fn remove(&mut self, bundle_id: &str) -> bool {
+        let before = self.bundle_ids.len();
+        self.bundle_ids.retain(|id| id != bundle_id);
+        self.bundle_ids.len() != before
+    }
+
+    /// Whether the application with `bundle_id` is excluded.
+    #[must_use]
+    pub fn contains(&self, bundle_id: &str) -> bool {
+        self.bundle_ids.iter().any(|id| id == bundle_id)
+    }
+
+    /// Excluded bundle identifiers, in the order they were added.
+    #[must_use]
+    pub fn bundle_ids(&self) -> &[String] {
+        &self.bundle_ids
+    }
+
+    /// Whether nothing is excluded.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.bundle_ids.is_empty()
+    }
+
+    /// The windows in `content` that belong to excluded applications.
+    #[must_use]
+    pub fn windows(&self, content: &SCShareableContent) -> Vec<SCWindow> {
+        content
+            .windows()
+            .into_iter()
+            .filter(|window| {
+                window
+                    .owning_application()
+                    .is_some_and(|app| self.contains(&app.bundle_identifier()))
+            })
+            .collect()
+    }
+
+    /// Write the policy to `path`, one bundle identifier per line.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InternalError`] if the file can't be written.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), SCError> {
+        let path = path.as_ref();
+        std::fs::write(path, self.to_string()).map_err(|e| {
+            SCError::internal_error(format!("failed to write {}: {e}", path.display()))
+        })
+    }
+
+    /// Read a policy written by [`save`](Self::save).
+    ///
+    /// Blank lines and lines starting with `#` are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InternalError`] if the file can't be read, or
+    /// [`SCError::InvalidConfiguration`] if a line isn't a bundle identifier.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, SCError> {
+        let path = path.as_ref();
+        std::fs::read_to_string(path)
+            .map_err(|e| {
+                SCError::internal_error(format!("failed to read {}: {e}", path.display()))
+            })?
+            .parse()
+    }
+}
+
+impl FromStr for ExclusionPolicy {
+    type Err = SCError;
+
+    fn from_str(text: &str) -> Result<Self, SCError> {
+        let mut policy = Self::new();
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if line.contains(char::is_whitespace) {
+                return Err(SCError::invalid_config(format!(
+                    "line {}: {line:?} is not a bundle identifier",
+                    number + 1
+                )));
+            }
+            policy.insert(line);
+        }
+        Ok(policy)
+    }
+}
+
+impl fmt::Display for ExclusionPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for bundle_id in &self.bundle_ids {
+            writeln!(f, "{bundle_id}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<S: Into<String>> FromIterator<S> for ExclusionPolicy {
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+        iter.into_iter().fold(Self::new(), Self::with_bundle_id)
+    }
+}
+
+/// Re-applies an [`ExclusionPolicy`] to a stream as excluded applications
+/// open windows or relaunch.
+///
+/// Stops polling when dropped.
+pub struct ExclusionWatcher {
+    policy: ExclusionPolicy,
+    updates: Arc<AtomicU64>,
+    poller: Poller,
+}
+
+impl ExclusionWatcher {
+    /// Capture `display` without `policy`'s applications, checking every
+    /// `interval` for new windows or processes of theirs.
+    ///
+    /// The first check runs straight away, so the policy is applied to
+    /// `stream` even if its current filter was built without it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InternalError`] if the operating system refuses
+    /// to spawn the polling thread.
+    pub fn start(
+        stream: &SCStream,
+        display: &SCDisplay,
+        policy: &ExclusionPolicy,
+        interval: Duration,
+    ) -> Result<Self, SCError> {
+        let display = display.clone();
+        Self::start_with_filter(stream, policy, interval, move || {
+            SCContentFilter::for_display(&display)
+        })
+    }
+
+    /// Like [`start`](Self::start), rebuilding the filter from `make_filter`
+    /// with the policy added, so other settings such as
+    /// [`with_dock(false)`](DisplayFilterBuilder::with_dock) carry over.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InternalError`] if the operating system refuses
+    /// to spawn the polling thread.
+    pub fn start_with_filter<S: state::Buildable + 'static>(
+        stream: &SCStream,
+        policy: &ExclusionPolicy,
+        interval: Duration,
+        make_filter: impl Fn() -> DisplayFilterBuilder<S> + Send + 'static,
+    ) -> Result<Self, SCError> {
+        let updates = Arc::new(AtomicU64::new(0));
+        let poller = {
+            let updates = Arc::clone(&updates);
+            let stream = stream.clone();
+            let policy = policy.clone();
+            Poller::spawn("exclusion-watcher", move |context| {
+                watch(context, &updates, &stream, &policy, interval, &make_filter);
+            })?
+        };
+
+        Ok(Self {
+            policy: policy.clone(),
+            updates,
+            poller,
+        })
+    }
+
+    /// The policy being applied.
+    #[must_use]
+    pub fn policy(&self) -> &ExclusionPolicy {
+        &self.policy
+    }
+
+    /// Number of times the stream's filter has been replaced.
+    #[must_use]
+    pub fn update_count(&self) -> u64 {
+        self.updates.load(Ordering::Relaxed)
+    }
+
+    /// The most recent failure to fetch content or update the filter, if the
+    /// last check failed. Cleared by the next successful check.
+    #[must_use]
+    pub fn last_error(&self) -> Option<SCError> {
+        self.poller.last_error()
+    }
+}
+
+/// Process IDs and window IDs of the excluded applications, sorted.
+type Signature = (Vec<i32>, Vec<u32>);
+
+fn signature(content: &SCShareableContent, policy: &ExclusionPolicy) -> Option<Signature> {
+    let snapshot = content.snapshot()?;
+    let excluded: Vec<usize> = snapshot
+        .applications
+        .iter()
+        .enumerate()
+        .filter(|(_, app)| policy.contains(&app.bundle_identifier))
+        .map(|(index, _)| index)
+        .collect();
+    let mut pids: Vec<i32> = excluded
+        .iter()
+        .map(|&index| snapshot.applications[index].process_id)
+        .collect();
+    let mut windows: Vec<u32> = snapshot
+        .windows
+        .iter()
+        .filter(|w| w.owning_app_index.is_some_and(|i| excluded.contains(&i)))
+        .map(|w| w.window_id)
+        .collect();
+    pids.sort_unstable();
+    windows.sort_unstable();
+    Some((pids, windows))
+}
+
+fn watch<S: state::Buildable>(
+    context: &PollerContext,
+    updates: &AtomicU64,
+    stream: &SCStream,
+    policy: &ExclusionPolicy,
+    interval: Duration,
+    make_filter: &dyn Fn() -> DisplayFilterBuilder<S>,
+) {
+    let mut applied: Option<Signature> = None;
+    let mut first = true;
+
+    loop {
+        if !first && context.sleep(interval) {
+            return;
+        }
+        first = false;
+
+        let result = SCShareableContent::get().and_then(|content| {
+            let next = signature(&content, policy);
+            if next.is_some() && next == applied {
+                return Ok(());
+            }
+            let filter = make_filter()
+                .with_exclusion_policy(policy)
+                .try_build_in(&content)?;
+            stream.update_content_filter(&filter)?;
+            updates.fetch_add(1, Ordering::Relaxed);
+            applied = next;
+            Ok(())
+        });
+        context.set_last_error(result.err());
+    }
+}
+
+impl fmt::Debug for ExclusionWatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExclusionWatcher")
+            .field("policy", &self.policy)
+            .field("update_count", &self.update_count())
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,197 @@
+//! Choosing which thread runs an output handler
+//!
+//! By default handlers run on `ScreenCaptureKit`'s dispatch queue, inside
+//! the Objective-C callback. Everything they do there delays the next
+//! sample and runs at the capture queue's priority, which can invert with
+//! lower-priority work the handler waits on. [`HandlerThread`] moves the
+//! handler off that queue: the callback only retains the sample and hands
+//! it over.
+//!
+//! - [`HandlerThread::Dedicated`] — one Rust thread, samples in order.
+//! - [`HandlerThread::Pool`] — a
+//!   [`PooledOutputHandler`](super::pooled_output::PooledOutputHandler)
+//!   with several workers.
+//! - [`HandlerThread::Executor`] — your own executor (rayon, a Tokio
+//!   blocking pool, a game engine's task system) through
+//!   [`SampleExecutor`].
+//!
+//! ```rust,no_run
+//! use std::sync::Arc;
+//! use screencapturekit::prelude::*;
+//! use screencapturekit::stream::executor_output::HandlerThread;
+//!
+//! # fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! # let content = SCShareableContent::get()?;
+//! # let display = &content.displays()[0];
+//! # let filter = SCContentFilter::for_display(display).with_excluding_windows(&[]).build();
+//! # let config = SCStreamConfiguration::default();
+//! let mut stream = SCStream::new(&filter, &config);
+//! // Any `Fn(Box<dyn FnOnce() + Send>)` is an executor.
+//! let spawn = |job: Box<dyn FnOnce() + Send>| {
+//!     std::thread::spawn(job);
+//! };
+//! stream.add_output_handler_on(
+//!     |_sample: CMSampleBuffer, _of_type| { /* runs on the executor */ },
+//!     SCStreamOutputType::Screen,
+//!     HandlerThread::Executor(Arc::new(spawn)),
+//! );
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use crate::cm::CMSampleBuffer;
+use crate::utils::panic_safe::catch_user_panic;
+
+use super::output_trait::SCStreamOutputTrait;
+use super::output_type::SCStreamOutputType;
+
+/// Runs jobs that deliver one sample each to an output handler
+///
+/// Implemented for every `Fn(Box<dyn FnOnce() + Send>)`, so most executors
+/// plug in as a closure around their spawn function.
+pub trait SampleExecutor: Send + Sync {
+    /// Run `job` soon, on a thread other than the caller's
+    fn execute(&self, job: Box<dyn FnOnce() + Send>);
+}
+
+impl<F> SampleExecutor for F
+where
+    F: Fn(Box<dyn FnOnce() + Send>) + Send + Sync,
+{
+    fn execute(&self, job: Box<dyn FnOnce() + Send>) {
+        self(job);
+    }
+}
+
+/// Where an output handler runs
+///
+/// Used with
+/// [`SCStream::add_output_handler_on`](crate::stream::SCStream::add_output_handler_on).
+#[derive(Clone, Default)]
+pub enum HandlerThread {
+    /// On `ScreenCaptureKit`'s dispatch queue, inside the callback
+    #[default]
+    Callback,
+    /// On one dedicated thread, in delivery order
+    Dedicated,
+    /// On a pool of this many worker threads; samples may finish out of
+    /// order
+    Pool(usize),
+    /// On a caller-provided executor
+    Executor(Arc<dyn SampleExecutor>),
+}
+
+impl fmt::Debug for HandlerThread {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Callback => f.write_str("Callback"),
+            Self::Dedicated => f.write_str("Dedicated"),
+            Self::Pool(workers) => f.debug_tuple("Pool").field(workers).finish(),
+            Self::Executor(_) => f.write_str("Executor(..)"),
+        }
+    }
+}
+
+/// Wraps an output handler so each sample is delivered by a job on a
+/// [`SampleExecutor`]
+///
+/// The executor's queue isn't visible from here, so back-pressure is a
+/// cap on samples in flight — submitted but not yet finished. A sample
+/// arriving at the cap is dropped rather than queued, and counted in
+/// [`dropped_samples`](Self::dropped_samples). Keep the cap below the
+/// stream's queue depth or `ScreenCaptureKit` runs out of surfaces.
+///
+/// Dropping the handler doesn't wait for submitted jobs; they finish on
+/// the executor.
+pub struct ExecutorOutputHandler {
+    handler: Arc<dyn SCStreamOutputTrait>,
+    executor: Arc<dyn SampleExecutor>,
+    in_flight: Arc<AtomicUsize>,
+    max_in_flight: usize,
+    dropped: AtomicU64,
+}
+
+impl ExecutorOutputHandler {
+    /// Deliver samples to `handler` through `executor`, holding at most
+    /// `max_in_flight` of them at once
+    ///
+    /// A `max_in_flight` of `0` is treated as `1`.
+    #[must_use]
+    pub fn new(
+        handler: impl SCStreamOutputTrait + 'static,
+        executor: Arc<dyn SampleExecutor>,
+        max_in_flight: usize,
+    ) -> Self {
+        Self {
+            handler: Arc::new(handler),
+            executor,
+            in_flight: Arc::new(AtomicUsize::new(0)),
+            max_in_flight: max_in_flight.max(1),
+            dropped: AtomicU64::new(0),
+        }
+    }
+
+    /// Samples submitted to the executor that haven't finished yet
+    #[must_use]
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::Acquire)
+    }
+
+    /// Most samples held at once
+    #[must_use]
+    pub const fn max_in_flight(&self) -> usize {
+        self.max_in_flight
+    }
+
+    /// Samples discarded because `max_in_flight` were already held
+    #[must_use]
+    pub fn dropped_samples(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+/// Releases an in-flight slot when a job finishes or is dropped unrun.
+struct InFlightSlot(Arc<AtomicUsize>);
+
+impl Drop for InFlightSlot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+impl SCStreamOutputTrait for ExecutorOutputHandler {
+    fn did_output_sample_buffer(&self, sample_buffer: CMSampleBuffer, of_type: SCStreamOutputType) {
+        let reserved = self
+            .in_flight
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |held| {
+                (held < self.max_in_flight).then_some(held + 1)
+            });
+        if reserved.is_err() {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+
+        let slot = InFlightSlot(Arc::clone(&self.in_flight));
+        let handler = Arc::clone(&self.handler);
+        self.executor.execute(Box::new(move || {
+            let _slot = slot;
+            catch_user_panic("executor output handler", || {
+                handler.did_output_sample_buffer(sample_buffer, of_type);
+            });
+        }));
+    }
+}
+
+impl fmt::Debug for ExecutorOutputHandler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExecutorOutputHandler")
+            .field("in_flight", &self.in_flight())
+            .field("max_in_flight", &self.max_in_flight)
+            .field("dropped_samples", &self.dropped_samples())
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,239 @@
+//! Ready-made content filters for common capture setups
+//!
+//! A [`FilterPreset`] picks applications by bundle identifier, either the
+//! only ones to capture or the ones to leave out, and turns that into an
+//! [`SCContentFilter`] for a display against whatever is running right now.
+//! The built-in presets are starting points; add bundle identifiers with
+//! [`with_bundle_id`](FilterPreset::with_bundle_id) to cover other apps.
+//!
+//! | Preset | Captures |
+//! |--------|----------|
+//! | [`browser_windows`](FilterPreset::browser_windows) | Only windows of common web browsers |
+//! | [`exclude_password_managers`](FilterPreset::exclude_password_managers) | The display, minus password managers |
+//! | [`current_app_only`](FilterPreset::current_app_only) | Only this process's windows |
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use screencapturekit::prelude::*;
+//! use screencapturekit::stream::filter_preset::FilterPreset;
+//!
+//! # fn example() -> Result<(), SCError> {
+//! let content = SCShareableContent::get()?;
+//! let display = &content.displays()[0];
+//! let filter = FilterPreset::browser_windows()
+//!     .with_bundle_id("com.example.MyBrowser")
+//!     .resolve(&content, display)?;
+//! let stream = SCStream::new(&filter, &SCStreamConfiguration::default());
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::SCError;
+use crate::shareable_content::{SCDisplay, SCRunningApplication, SCShareableContent};
+
+use super::content_filter::SCContentFilter;
+use super::exclusion_policy::ExclusionPolicy;
+
+/// Bundle identifiers of common web browsers.
+const BROWSERS: &[&str] = &[
+    "com.apple.Safari",
+    "com.apple.SafariTechnologyPreview",
+    "com.google.Chrome",
+    "com.google.Chrome.canary",
+    "org.mozilla.firefox",
+    "com.microsoft.edgemac",
+    "company.thebrowser.Browser",
+    "com.brave.Browser",
+    "com.operasoftware.Opera",
+    "com.vivaldi.Vivaldi",
+];
+
+/// Whether a preset's applications are the ones captured or the ones left
+/// out
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PresetMode {
+    /// Capture only the listed applications
+    Include,
+    /// Capture the whole display except the listed applications
+    Exclude,
+}
+
+/// Applications, by bundle identifier, to capture or leave out
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilterPreset {
+    mode: PresetMode,
+    bundle_ids: Vec<String>,
+    current_process: bool,
+}
+
+impl FilterPreset {
+    /// Capture only the applications with `bundle_ids`
+    #[must_use]
+    pub fn including<I, S>(bundle_ids: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self::new(PresetMode::Include, bundle_ids)
+    }
+
+    /// Capture the display except the applications with `bundle_ids`
+    #[must_use]
+    pub fn excluding<I, S>(bundle_ids: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self::new(PresetMode::Exclude, bundle_ids)
+    }
+
+    fn new<I, S>(mode: PresetMode, bundle_ids: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        bundle_ids.into_iter().fold(
+            Self {
+                mode,
+                bundle_ids: Vec::new(),
+                current_process: false,
+            },
+            Self::with_bundle_id,
+        )
+    }
+
+    /// Windows of Safari, Chrome, Firefox, Edge, Arc, Brave, Opera and
+    /// Vivaldi
+    #[must_use]
+    pub fn browser_windows() -> Self {
+        Self::including(BROWSERS.iter().copied())
+    }
+
+    /// The display without the password managers in
+    /// [`ExclusionPolicy::password_managers`]
+    #[must_use]
+    pub fn exclude_password_managers() -> Self {
+        Self::excluding(ExclusionPolicy::password_managers().bundle_ids())
+    }
+
+    /// Only the windows of the calling process
+    ///
+    /// Matches by process ID, so it also works for executables without a
+    /// bundle identifier.
+    #[must_use]
+    pub fn current_app_only() -> Self {
+        Self::including(std::iter::empty::<String>()).with_current_process(true)
+    }
+
+    /// Also match the application with `bundle_id`
+    #[must_use]
+    pub fn with_bundle_id(mut self, bundle_id: impl Into<String>) -> Self {
+        let bundle_id = bundle_id.into();
+        if !self.contains(&bundle_id) {
+            self.bundle_ids.push(bundle_id);
+        }
+        self
+    }
+
+    /// Also match the calling process, e.g. to keep a recorder's own
+    /// windows out of an [`Exclude`](PresetMode::Exclude) preset
+    #[must_use]
+    pub const fn with_current_process(mut self, include: bool) -> Self {
+        self.current_process = include;
+        self
+    }
+
+    /// Whether the listed applications are captured or left out
+    #[must_use]
+    pub const fn mode(&self) -> PresetMode {
+        self.mode
+    }
+
+    /// Bundle identifiers matched, in the order they were added
+    #[must_use]
+    pub fn bundle_ids(&self) -> &[String] {
+        &self.bundle_ids
+    }
+
+    /// Whether the application with `bundle_id` is matched
+    #[must_use]
+    pub fn contains(&self, bundle_id: &str) -> bool {
+        self.bundle_ids.iter().any(|id| id == bundle_id)
+    }
+
+    /// Whether the calling process is matched
+    #[must_use]
+    pub const fn matches_current_process(&self) -> bool {
+        self.current_process
+    }
+
+    /// The running applications in `content` this preset matches
+    #[must_use]
+    pub fn applications(&self, content: &SCShareableContent) -> Vec<SCRunningApplication> {
+        let pid = std::process::id();
+        content
+            .applications()
+            .into_iter()
+            .filter(|app| {
+                (self.current_process && u32::try_from(app.process_id()).is_ok_and(|p| p == pid))
+                    || self.contains(&app.bundle_identifier())
+            })
+            .collect()
+    }
+
+    /// Build a filter for `display` from the applications running in
+    /// `content`
+    ///
+    /// Listed applications that aren't running are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::ApplicationNotFound`] if this is an
+    /// [`Include`](PresetMode::Include) preset and none of its applications
+    /// are running, since the filter would capture nothing.
+    pub fn resolve(
+        &self,
+        content: &SCShareableContent,
+        display: &SCDisplay,
+    ) -> Result<SCContentFilter, SCError> {
+        let applications = self.applications(content);
+        let applications: Vec<&SCRunningApplication> = applications.iter().collect();
+        match self.mode {
+            PresetMode::Include if applications.is_empty() => {
+                Err(SCError::ApplicationNotFound(self.describe()))
+            }
+            PresetMode::Include => Ok(SCContentFilter::for_display(display)
+                .with_including_applications(&applications, &[])
+                .build()),
+            PresetMode::Exclude if applications.is_empty() => {
+                Ok(SCContentFilter::for_display(display).build())
+            }
+            PresetMode::Exclude => Ok(SCContentFilter::for_display(display)
+                .with_excluding_applications(&applications, &[])
+                .build()),
+        }
+    }
+
+    /// [`resolve`](Self::resolve) against freshly fetched shareable
+    /// content, including off-screen windows
+    ///
+    /// # Errors
+    ///
+    /// Returns the error from fetching shareable content, or from
+    /// [`resolve`](Self::resolve).
+    pub fn build(&self, display: &SCDisplay) -> Result<SCContentFilter, SCError> {
+        let content = SCShareableContent::create()
+            .with_on_screen_windows_only(false)
+            .get()?;
+        self.resolve(&content, display)
+    }
+
+    fn describe(&self) -> String {
+        let mut names = self.bundle_ids.clone();
+        if self.current_process {
+            names.push(format!("process {}", std::process::id()));
+        }
+        names.join(", ")
+    }
+}
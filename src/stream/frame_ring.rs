@@ -0,0 +1,305 @@
+//! Lock-free single-producer, single-consumer frame delivery
+//!
+//! Real-time consumers such as audio and video encoders run on their own
+//! thread and must never wait on the capture callback, nor make the
+//! callback wait on them. [`FrameRing`] is a fixed-size ring of retained
+//! [`CMSampleBuffer`]s shared between the stream's output queue, which
+//! pushes, and one consumer thread, which pops. Both sides only touch a
+//! few atomics: no mutex, no allocation and no system call per sample.
+//!
+//! Create one with [`SCStream::ring_output`](crate::stream::SCStream::ring_output),
+//! which registers the producer for exactly one output type, or with
+//! [`FrameRing::new`] to push from a thread of your own.
+//!
+//! ## Back-pressure
+//!
+//! The producer can't free a slot the consumer may be reading, so when the
+//! ring is full the *incoming* sample is dropped and counted in
+//! [`FrameRing::dropped`]. Size the ring for the longest stall the consumer
+//! can have, and remember each queued screen frame holds one of the stream's
+//! surfaces (see
+//! [`with_queue_depth`](crate::stream::configuration::SCStreamConfiguration::with_queue_depth)).
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use std::time::Duration;
+//! use screencapturekit::prelude::*;
+//!
+//! # fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! # let content = SCShareableContent::get()?;
+//! # let display = &content.displays()[0];
+//! # let filter = SCContentFilter::for_display(display).with_excluding_windows(&[]).build();
+//! # let config = SCStreamConfiguration::default();
+//! let mut stream = SCStream::new(&filter, &config);
+//! let mut ring = stream
+//!     .ring_output(SCStreamOutputType::Audio, 64)
+//!     .expect("audio output");
+//! stream.start_capture()?;
+//!
+//! std::thread::spawn(move || {
+//!     while let Some(sample) = ring.pop_timeout(Duration::from_secs(1)) {
+//!         let _ = sample.presentation_timestamp(); // encode
+//!     }
+//! });
+//! # Ok(())
+//! # }
+//! ```
+
+use std::cell::{Cell, UnsafeCell};
+use std::fmt;
+use std::marker::PhantomData;
+use std::mem::MaybeUninit;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::cm::CMSampleBuffer;
+
+use super::output_trait::SCStreamOutputTrait;
+use super::output_type::SCStreamOutputType;
+
+/// Keeps the producer and consumer indices on separate cache lines, so
+/// pushing doesn't invalidate the line the consumer polls and vice versa.
+#[repr(align(128))]
+struct CachePadded<T>(T);
+
+struct Shared {
+    slots: Box<[UnsafeCell<MaybeUninit<CMSampleBuffer>>]>,
+    mask: usize,
+    /// Next slot to read; written only by the consumer.
+    head: CachePadded<AtomicUsize>,
+    /// Next slot to write; written only by the producer.
+    tail: CachePadded<AtomicUsize>,
+    dropped: AtomicU64,
+    closed: AtomicBool,
+}
+
+// SAFETY: a slot is written only by the one producer while it is outside
+// `head..tail`, and read only by the consumer while it is inside;
+// the Release stores of `tail` and `head` hand each slot over with its
+// contents.
+unsafe impl Send for Shared {}
+unsafe impl Sync for Shared {}
+
+impl Drop for Shared {
+    fn drop(&mut self) {
+        let mut index = *self.head.0.get_mut();
+        let tail = *self.tail.0.get_mut();
+        while index != tail {
+            // SAFETY: slots in `head..tail` hold samples nobody popped.
+            unsafe { self.slots[index & self.mask].get_mut().assume_init_drop() };
+            index = index.wrapping_add(1);
+        }
+    }
+}
+
+/// The consumer end of a frame ring
+///
+/// Owned by exactly one thread at a time: popping takes `&mut self`, and the
+/// type is not `Clone`. Dropping it releases every sample still queued once
+/// the producer is gone too.
+pub struct FrameRing {
+    shared: Arc<Shared>,
+}
+
+/// The producer end of a frame ring
+///
+/// There is exactly one: pushing takes `&mut self`, and the type is neither
+/// `Clone` nor `Sync`, so pushes never race and never wait for each other.
+/// [`SCStream::ring_output`](crate::stream::SCStream::ring_output) registers
+/// it for a single output type, whose samples `ScreenCaptureKit` delivers
+/// from one serial queue.
+pub struct FrameRingProducer {
+    shared: Arc<Shared>,
+    /// Makes the producer `!Sync`.
+    _not_sync: PhantomData<Cell<()>>,
+}
+
+/// Output handler that feeds a [`FrameRingProducer`], registered by
+/// [`SCStream::ring_output`](crate::stream::SCStream::ring_output) for one
+/// output type only
+pub(crate) struct RingOutput {
+    producer: UnsafeCell<FrameRingProducer>,
+}
+
+// SAFETY: `RingOutput` is private to the crate and only ever registered for a
+// single output type. `ScreenCaptureKit` delivers that type on one queue, and
+// every `DispatchQueue` this crate creates is serial, so
+// `did_output_sample_buffer` is never called twice at once and is the only
+// code that reaches the producer.
+unsafe impl Sync for RingOutput {}
+
+impl FrameRing {
+    /// Create a ring holding at least `capacity` samples, rounded up to a
+    /// power of two (and to at least one)
+    #[must_use]
+    pub fn new(capacity: usize) -> (FrameRingProducer, Self) {
+        let capacity = capacity.max(1).next_power_of_two();
+        let slots = (0..capacity)
+            .map(|_| UnsafeCell::new(MaybeUninit::uninit()))
+            .collect();
+        let shared = Arc::new(Shared {
+            slots,
+            mask: capacity - 1,
+            head: CachePadded(AtomicUsize::new(0)),
+            tail: CachePadded(AtomicUsize::new(0)),
+            dropped: AtomicU64::new(0),
+            closed: AtomicBool::new(false),
+        });
+        (
+            FrameRingProducer {
+                shared: Arc::clone(&shared),
+                _not_sync: PhantomData,
+            },
+            Self { shared },
+        )
+    }
+
+    /// Take the oldest queued sample, if any
+    pub fn try_pop(&mut self) -> Option<CMSampleBuffer> {
+        let shared = &*self.shared;
+        let head = shared.head.0.load(Ordering::Relaxed);
+        if head == shared.tail.0.load(Ordering::Acquire) {
+            return None;
+        }
+        // SAFETY: `head < tail`, so the producer has published this slot and
+        // won't touch it again until `head` moves past it.
+        let sample = unsafe { (*shared.slots[head & shared.mask].get()).assume_init_read() };
+        shared.head.0.store(head.wrapping_add(1), Ordering::Release);
+        Some(sample)
+    }
+
+    /// Take the oldest queued sample, waiting up to `timeout` for one
+    ///
+    /// Waits by spinning briefly, then sleeping in short steps, so the
+    /// producer never has to wake anyone. Returns `None` on timeout, or
+    /// straight away once the ring is empty and its producer is gone. A
+    /// timeout too large to represent, such as [`Duration::MAX`], waits
+    /// until one of those happens.
+    pub fn pop_timeout(&mut self, timeout: Duration) -> Option<CMSampleBuffer> {
+        let deadline = Instant::now().checked_add(timeout);
+        let mut idle_rounds = 0u32;
+        loop {
+            if let Some(sample) = self.try_pop() {
+                return Some(sample);
+            }
+            // Re-check after seeing the flag: the last push happens before
+            // the producer is dropped.
+            if self.is_closed() {
+                return self.try_pop();
+            }
+            let mut step = Duration::from_micros(250);
+            if let Some(deadline) = deadline {
+                let now = Instant::now();
+                if now >= deadline {
+                    return None;
+                }
+                step = step.min(deadline - now);
+            }
+            idle_rounds = idle_rounds.saturating_add(1);
+            if idle_rounds < 64 {
+                std::hint::spin_loop();
+            } else if idle_rounds < 128 {
+                std::thread::yield_now();
+            } else {
+                std::thread::sleep(step);
+            }
+        }
+    }
+
+    /// Take samples, oldest first, until the ring is empty
+    pub fn drain(&mut self) -> impl Iterator<Item = CMSampleBuffer> + '_ {
+        std::iter::from_fn(move || self.try_pop())
+    }
+
+    /// Number of samples waiting
+    #[must_use]
+    pub fn len(&self) -> usize {
+        let tail = self.shared.tail.0.load(Ordering::Acquire);
+        tail.wrapping_sub(self.shared.head.0.load(Ordering::Relaxed))
+    }
+
+    /// Whether no sample is waiting
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of samples the ring holds
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.shared.slots.len()
+    }
+
+    /// Samples dropped because the ring was full
+    #[must_use]
+    pub fn dropped(&self) -> u64 {
+        self.shared.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Whether the producer is gone, e.g. because the stream was dropped
+    /// or the handler removed. Samples already queued can still be popped.
+    #[must_use]
+    pub fn is_closed(&self) -> bool {
+        self.shared.closed.load(Ordering::Acquire)
+    }
+}
+
+impl FrameRingProducer {
+    /// Queue `sample`, or drop it and return `false` if the ring is full
+    pub fn push(&mut self, sample: CMSampleBuffer) -> bool {
+        let shared = &*self.shared;
+        let tail = shared.tail.0.load(Ordering::Relaxed);
+        let head = shared.head.0.load(Ordering::Acquire);
+        if tail.wrapping_sub(head) == shared.slots.len() {
+            shared.dropped.fetch_add(1, Ordering::Relaxed);
+            return false;
+        }
+        // SAFETY: the slot is outside `head..tail`, so the consumer is done
+        // with it, and `&mut self` on the only producer makes this the only
+        // writer.
+        unsafe { (*shared.slots[tail & shared.mask].get()).write(sample) };
+        shared.tail.0.store(tail.wrapping_add(1), Ordering::Release);
+        true
+    }
+
+    pub(crate) fn into_output(self) -> RingOutput {
+        RingOutput {
+            producer: UnsafeCell::new(self),
+        }
+    }
+}
+
+impl SCStreamOutputTrait for RingOutput {
+    fn did_output_sample_buffer(&self, sample: CMSampleBuffer, _of_type: SCStreamOutputType) {
+        // SAFETY: calls never overlap (see the `Sync` impl), so this is the
+        // only reference to the producer while it lives.
+        unsafe { (*self.producer.get()).push(sample) };
+    }
+}
+
+impl Drop for FrameRingProducer {
+    fn drop(&mut self) {
+        self.shared.closed.store(true, Ordering::Release);
+    }
+}
+
+impl fmt::Debug for FrameRing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FrameRing")
+            .field("len", &self.len())
+            .field("capacity", &self.capacity())
+            .field("dropped", &self.dropped())
+            .field("closed", &self.is_closed())
+            .finish()
+    }
+}
+
+impl fmt::Debug for FrameRingProducer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FrameRingProducer")
+            .field("capacity", &self.shared.slots.len())
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,239 @@
+//! Microphone-only capture
+//!
+//! `ScreenCaptureKit` has no audio-only stream: every `SCStream` needs a
+//! content filter, even when only
+//! [`Microphone`](super::output_type::SCStreamOutputType::Microphone) samples
+//! are wanted. [`MicCapture`] hides that. It captures the main display at
+//! a 2×2 size and one frame per second with no screen output attached, so
+//! video costs next to nothing, and hands back fixed-size PCM chunks from
+//! the selected input device.
+//!
+//! Microphone capture needs macOS 15.0 and `NSMicrophoneUsageDescription` in
+//! the app's `Info.plist`. Screen recording permission is still required,
+//! because the stream itself is a screen capture.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use screencapturekit::audio_devices::AudioInputDevice;
+//! use screencapturekit::stream::mic_capture::MicCapture;
+//!
+//! # fn example() -> Result<(), screencapturekit::error::SCError> {
+//! let device = AudioInputDevice::default_device();
+//! let mut builder = MicCapture::builder().with_sample_rate(48_000).with_channel_count(1);
+//! if let Some(device) = &device {
+//!     builder = builder.with_device(device);
+//! }
+//! let capture = builder.start(|chunk| {
+//!     // 20 ms of mono f32 PCM
+//!     println!("{} frames at {:?}", chunk.frame_count(), chunk.pts());
+//! })?;
+//! std::thread::sleep(std::time::Duration::from_secs(5));
+//! capture.stop()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+use std::sync::{Mutex, PoisonError};
+
+use crate::audio_devices::AudioInputDevice;
+use crate::cm::{AudioChunk, AudioChunker, CMSampleBuffer, CMTime};
+use crate::error::SCError;
+use crate::shareable_content::SCShareableContent;
+
+use super::configuration::SCStreamConfiguration;
+use super::content_filter::SCContentFilter;
+use super::output_type::SCStreamOutputType;
+use super::SCStream;
+
+/// Builder for [`MicCapture`]
+///
+/// Defaults to the system default input, 48 kHz mono, 20 ms (960-frame)
+/// chunks.
+#[derive(Debug, Clone)]
+pub struct MicCaptureBuilder {
+    device_id: Option<String>,
+    sample_rate: u32,
+    channel_count: u32,
+    frames_per_chunk: usize,
+}
+
+impl Default for MicCaptureBuilder {
+    fn default() -> Self {
+        Self {
+            device_id: None,
+            sample_rate: 48_000,
+            channel_count: 1,
+            frames_per_chunk: 960,
+        }
+    }
+}
+
+impl MicCaptureBuilder {
+    /// Capture from `device` instead of the system default input
+    #[must_use]
+    pub fn with_device(mut self, device: &AudioInputDevice) -> Self {
+        self.device_id = Some(device.id.clone());
+        self
+    }
+
+    /// Capture from the device with this Core Audio UID
+    #[must_use]
+    pub fn with_device_id(mut self, device_id: impl Into<String>) -> Self {
+        self.device_id = Some(device_id.into());
+        self
+    }
+
+    /// Sample rate in Hz (8000, 16000, 24000 or 48000)
+    #[must_use]
+    pub fn with_sample_rate(mut self, sample_rate: u32) -> Self {
+        self.sample_rate = sample_rate;
+        self
+    }
+
+    /// Number of channels (1 or 2)
+    #[must_use]
+    pub fn with_channel_count(mut self, channel_count: u32) -> Self {
+        self.channel_count = channel_count;
+        self
+    }
+
+    /// Frames per delivered chunk, e.g. 1024 for AAC or 960 for Opus
+    #[must_use]
+    pub fn with_frames_per_chunk(mut self, frames_per_chunk: usize) -> Self {
+        self.frames_per_chunk = frames_per_chunk;
+        self
+    }
+
+    /// The stream configuration this builder produces
+    ///
+    /// Useful to tweak further before passing it to an `SCStream` yourself.
+    pub fn configuration(&self) -> SCStreamConfiguration {
+        #[allow(clippy::cast_possible_wrap)]
+        let config = SCStreamConfiguration::new()
+            .with_width(2)
+            .with_height(2)
+            .with_minimum_frame_interval(&CMTime::new(1, 1))
+            .with_queue_depth(3)
+            .with_captures_audio(false)
+            .with_captures_microphone(true)
+            .with_sample_rate(self.sample_rate as i32)
+            .with_channel_count(self.channel_count as i32);
+        match &self.device_id {
+            Some(id) => config.with_microphone_capture_device_id(id),
+            None => config,
+        }
+    }
+
+    /// Start capturing and call `on_chunk` with each complete PCM chunk
+    ///
+    /// Chunks are `f32`, planar for stereo, and timestamped on the
+    /// microphone's timeline. `on_chunk` runs on the stream's audio queue.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidDimension`] for a zero sample rate, channel
+    /// count or chunk size, [`SCError::NoShareableContent`] if no display can
+    /// be found for the carrier filter, and any error from
+    /// [`SCStream::start_capture`].
+    pub fn start<F>(self, on_chunk: F) -> Result<MicCapture, SCError>
+    where
+        F: Fn(AudioChunk) + Send + Sync + 'static,
+    {
+        let chunker = AudioChunker::new(
+            self.frames_per_chunk,
+            self.channel_count as usize,
+            self.sample_rate,
+        )?;
+        let filter = carrier_filter()?;
+        let mut stream = SCStream::new(&filter, &self.configuration());
+
+        let chunker = Mutex::new(chunker);
+        stream.add_output_handler(
+            move |sample: CMSampleBuffer, _of_type: SCStreamOutputType| {
+                let chunks = chunker
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .push(&sample);
+                // A buffer the chunker can't read (e.g. a format change while
+                // the device switches) is dropped rather than ending capture.
+                for chunk in chunks.unwrap_or_default() {
+                    on_chunk(chunk);
+                }
+            },
+            SCStreamOutputType::Microphone,
+        );
+        stream.start_capture()?;
+
+        Ok(MicCapture {
+            stream,
+            device_id: self.device_id,
+        })
+    }
+}
+
+/// The cheapest filter `ScreenCaptureKit` accepts: the main display.
+fn carrier_filter() -> Result<SCContentFilter, SCError> {
+    let content = SCShareableContent::get()?;
+    let display = content
+        .displays()
+        .into_iter()
+        .next()
+        .ok_or_else(|| SCError::NoShareableContent("no display to carry the stream".into()))?;
+    Ok(SCContentFilter::for_display(&display)
+        .with_excluding_windows(&[])
+        .build())
+}
+
+/// A running microphone-only capture
+///
+/// Created by [`MicCapture::builder`]. Stops when dropped.
+pub struct MicCapture {
+    stream: SCStream,
+    device_id: Option<String>,
+}
+
+impl MicCapture {
+    /// Start configuring a microphone capture
+    pub fn builder() -> MicCaptureBuilder {
+        MicCaptureBuilder::default()
+    }
+
+    /// UID of the selected input device, or `None` for the system default
+    pub fn device_id(&self) -> Option<&str> {
+        self.device_id.as_deref()
+    }
+
+    /// The underlying stream, e.g. for
+    /// [`watch_microphone_device`](SCStream::watch_microphone_device)
+    pub fn stream(&self) -> &SCStream {
+        &self.stream
+    }
+
+    /// Stop capturing
+    ///
+    /// # Errors
+    ///
+    /// Returns the error from [`SCStream::stop_capture`].
+    pub fn stop(self) -> Result<(), SCError> {
+        self.stream.stop_capture()
+    }
+}
+
+impl Drop for MicCapture {
+    fn drop(&mut self) {
+        if self.stream.is_capturing() {
+            let _ = self.stream.stop_capture();
+        }
+    }
+}
+
+impl fmt::Debug for MicCapture {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MicCapture")
+            .field("device_id", &self.device_id)
+            .field("capturing", &self.stream.is_capturing())
+            .finish()
+    }
+}
@@ -0,0 +1,127 @@
+//! Fall back to the default microphone when the selected one disappears
+//!
+//! A stream configured with
+//! [`with_microphone_capture_device_id`](super::configuration::SCStreamConfiguration::with_microphone_capture_device_id)
+//! silently stops delivering [`Microphone`](super::output_type::SCStreamOutputType::Microphone)
+//! samples when that device is unplugged. [`MicrophoneDeviceWatcher`] checks
+//! the input device list on a background thread; when the configured device
+//! is gone it clears the device ID so capture continues from the system
+//! default input, and reports the switch to the stream's delegate through
+//! [`microphone_device_lost`](super::delegate_trait::SCStreamDelegateTrait::microphone_device_lost).
+//!
+//! Streams that use the default input (no device ID) are left alone; macOS
+//! already follows default-device changes for them.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use std::time::Duration;
+//! use screencapturekit::prelude::*;
+//! use screencapturekit::stream::StreamCallbacks;
+//!
+//! # fn example(filter: &SCContentFilter, device_id: &str) -> Result<(), SCError> {
+//! let config = SCStreamConfiguration::new()
+//!     .with_captures_microphone(true)
+//!     .with_microphone_capture_device_id(device_id);
+//! let callbacks = StreamCallbacks::new().on_microphone_device_lost(|lost, fallback| {
+//!     let name = fallback.map_or("none", |d| d.name.as_str());
+//!     eprintln!("microphone {lost} unplugged, now using {name}");
+//! });
+//! let stream = SCStream::new_with_delegate(filter, &config, callbacks);
+//! stream.start_capture()?;
+//! let _watcher = stream.watch_microphone_device(Duration::from_secs(1))?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::audio_devices::AudioInputDevice;
+use crate::error::SCError;
+use crate::utils::poller::{Poller, PollerContext};
+
+use super::SCStream;
+
+/// Watches a stream's selected microphone and switches to the default input
+/// when it is disconnected.
+///
+/// Usually created with [`SCStream::watch_microphone_device`]. Stops when
+/// dropped.
+pub struct MicrophoneDeviceWatcher {
+    fallbacks: Arc<AtomicU64>,
+    poller: Poller,
+}
+
+impl MicrophoneDeviceWatcher {
+    /// Check `stream`'s microphone device every `interval`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InternalError`] if the operating system refuses
+    /// to spawn the watcher thread.
+    pub fn start(stream: &SCStream, interval: Duration) -> Result<Self, SCError> {
+        let fallbacks = Arc::new(AtomicU64::new(0));
+        let poller = {
+            let fallbacks = Arc::clone(&fallbacks);
+            let stream = stream.clone();
+            Poller::spawn("microphone-watcher", move |context| {
+                watch(context, &fallbacks, &stream, interval);
+            })?
+        };
+
+        Ok(Self { fallbacks, poller })
+    }
+
+    /// Number of times the stream was switched to the default input.
+    #[must_use]
+    pub fn fallback_count(&self) -> u64 {
+        self.fallbacks.load(Ordering::Relaxed)
+    }
+
+    /// The error from the last failed attempt to switch devices, if any.
+    #[must_use]
+    pub fn last_error(&self) -> Option<SCError> {
+        self.poller.last_error()
+    }
+}
+
+fn watch(context: &PollerContext, fallbacks: &AtomicU64, stream: &SCStream, interval: Duration) {
+    while !context.sleep(interval) {
+        let config = stream.configuration();
+        if !config.captures_microphone() {
+            continue;
+        }
+        let Some(selected) = config
+            .microphone_capture_device_id()
+            .filter(|id| !id.is_empty())
+        else {
+            continue;
+        };
+        let devices = AudioInputDevice::list();
+        if devices.iter().any(|device| device.id == selected) {
+            continue;
+        }
+
+        if let Err(error) = stream.set_microphone_device(None) {
+            context.set_last_error(Some(error));
+            continue;
+        }
+        fallbacks.fetch_add(1, Ordering::Relaxed);
+        let fallback = devices
+            .into_iter()
+            .find(|device| device.is_default)
+            .or_else(AudioInputDevice::default_device);
+        stream.notify_microphone_device_lost(&selected, fallback.as_ref());
+    }
+}
+
+impl fmt::Debug for MicrophoneDeviceWatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MicrophoneDeviceWatcher")
+            .field("fallback_count", &self.fallback_count())
+            .finish_non_exhaustive()
+    }
+}
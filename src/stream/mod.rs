@@ -9,12 +9,31 @@
 //! - [`content_filter::SCContentFilter`] - Filter for selecting what to capture (display, window, app)
 //! - [`output_trait::SCStreamOutputTrait`] - Trait for receiving captured frames
 //! - [`output_type::SCStreamOutputType`] - Type of output (screen, audio, microphone)
+//! - [`pooled_output::PooledOutputHandler`] - Runs an output handler on a worker thread pool
+//! - [`executor_output::HandlerThread`] - Runs an output handler on a dedicated thread, a pool or your own executor
+//! - [`tee_output::TeeOutputHandler`] - Shares each sample between a recording and a preview
+//! - [`cursor::CursorOutputHandler`] - Delivers the cursor's position and shape next to frames captured without it
+//! - [`event_driven::EventDrivenOutputHandler`] - Forwards screen frames only when the screen changed
+//! - [`frame_ring::FrameRing`] - Lock-free single-consumer ring of samples for real-time consumers
+//! - [`application_follower::ApplicationFollower`] - Keeps an application-scoped filter current
+//! - [`display_follower::DisplayFollower`] - Resizes a display stream when the display's resolution changes
+//! - [`energy_mode::EnergyModeController`] - Lowers a stream's frame rate on battery or when the Mac runs hot
+//! - [`exclusion_policy::ExclusionPolicy`] - Applications always left out of display captures
+//! - [`filter_preset::FilterPreset`] - Ready-made filters built from bundle identifier lists
 //! - [`delegate_trait::SCStreamDelegateTrait`] - Trait for stream lifecycle events
+//! - [`state::SCStreamState`] - Where a stream is in its start/update/stop lifecycle
+//! - [`microphone_watcher::MicrophoneDeviceWatcher`] - Falls back to the default microphone when the selected one is unplugged
+//! - [`permission_watcher::PermissionWatcher`] - Reports when Screen Recording permission is revoked
+//! - [`permission_watcher::capture_access`] - Full permission or picker-only capture
+//! - [`display_sleep::DisplaySleepWatcher`] - Pauses, freezes or stops capture while the display sleeps or the screen is locked
+//! - [`mic_capture::MicCapture`] - Microphone-only capture delivering fixed-size PCM chunks
+//! - [`schedule::ScheduledCapture`] - Starts and stops a stream at set times or after a maximum duration
+//! - [`prewarm::PrewarmedStream`] - Creates and optionally starts a stream before the content to capture is known
 //!
 //! ## Workflow
 //!
 //! 1. Query available content with [`SCShareableContent`](crate::shareable_content::SCShareableContent)
-//! 2. Create a content filter with [`SCContentFilter::create()`](content_filter::SCContentFilter::create)
+//! 2. Create a content filter with [`SCContentFilter::for_display()`](content_filter::SCContentFilter::for_display)
 //! 3. Configure the stream with [`SCStreamConfiguration::new()`](configuration::SCStreamConfiguration::new)
 //! 4. Create and start the stream with [`SCStream::new()`](SCStream::new)
 //!
@@ -25,8 +44,7 @@
 //!
 //! # let content = SCShareableContent::get().unwrap();
 //! # let display = &content.displays()[0];
-//! let filter = SCContentFilter::create()
-//!     .with_display(display)
+//! let filter = SCContentFilter::for_display(display)
 //!     .with_excluding_windows(&[])
 //!     .build();
 //! let config = SCStreamConfiguration::new()
@@ -42,18 +60,37 @@
 //! # Ok::<(), screencapturekit::error::SCError>(())
 //! ```
 
+pub mod application_follower;
 pub mod configuration;
 pub mod content_filter;
+pub mod cursor;
 pub mod delegate_trait;
+pub mod display_follower;
+pub mod display_sleep;
+pub mod energy_mode;
+pub mod event_driven;
+pub mod exclusion_policy;
+pub mod executor_output;
+pub mod filter_preset;
+pub mod frame_ring;
+pub mod mic_capture;
+pub mod microphone_watcher;
 pub mod output_trait;
 pub mod output_type;
+pub mod permission_watcher;
+pub mod pooled_output;
+pub mod prewarm;
 pub mod sc_stream;
+pub mod schedule;
+pub mod state;
+pub mod tee_output;
 
 pub use delegate_trait::ErrorHandler;
 pub use delegate_trait::SCStreamDelegateTrait as SCStreamDelegate;
-pub use delegate_trait::StreamCallbacks;
+pub use delegate_trait::{SCVideoEffect, StreamCallbacks};
 pub use output_trait::SCStreamOutputTrait as SCStreamOutput;
-pub use sc_stream::SCStream;
+pub use sc_stream::{OutputHandlerInfo, SCStream, StreamRef};
+pub use state::SCStreamState;
 
 #[cfg(feature = "macos_14_0")]
 pub use content_filter::{SCShareableContentStyle, SCStreamType};
@@ -2,9 +2,12 @@
 //!
 //! Defines the interface for receiving captured frames and audio buffers.
 
+use std::fmt;
+
 use crate::cm::CMSampleBuffer;
 
 use super::output_type::SCStreamOutputType;
+use super::sc_stream::StreamRef;
 
 /// Trait for handling stream output
 ///
@@ -51,7 +54,7 @@ use super::output_type::SCStreamOutputType;
 /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
 /// # let content = SCShareableContent::get()?;
 /// # let display = &content.displays()[0];
-/// # let filter = SCContentFilter::create().with_display(display).with_excluding_windows(&[]).build();
+/// # let filter = SCContentFilter::for_display(display).with_excluding_windows(&[]).build();
 /// # let config = SCStreamConfiguration::default();
 /// let mut stream = SCStream::new(&filter, &config);
 ///
@@ -88,6 +91,98 @@ pub trait SCStreamOutputTrait: Send + Sync {
     /// - `sample_buffer`: The captured sample (video frame or audio buffer)
     /// - `of_type`: Type of output (Screen, Audio, or Microphone)
     fn did_output_sample_buffer(&self, sample_buffer: CMSampleBuffer, of_type: SCStreamOutputType);
+
+    /// How this handler wants samples handed to it
+    ///
+    /// Defaults to [`SampleDelivery::Retained`]. Handlers that return
+    /// [`SampleDelivery::Borrowed`] are called through
+    /// [`did_output_sample_buffer_borrowed`](Self::did_output_sample_buffer_borrowed)
+    /// instead. The stream asks once per sample, so keep this cheap.
+    fn delivery(&self) -> SampleDelivery {
+        SampleDelivery::Retained
+    }
+
+    /// Called instead of [`did_output_sample_buffer`](Self::did_output_sample_buffer)
+    /// when [`delivery`](Self::delivery) returns [`SampleDelivery::Borrowed`]
+    ///
+    /// `sample_buffer` is only valid for the duration of the call. The
+    /// default retains it and forwards to `did_output_sample_buffer`.
+    fn did_output_sample_buffer_borrowed(
+        &self,
+        sample_buffer: &CMSampleBuffer,
+        of_type: SCStreamOutputType,
+    ) {
+        self.did_output_sample_buffer(sample_buffer.clone(), of_type);
+    }
+}
+
+/// How a sample buffer is handed to an output handler
+///
+/// `ScreenCaptureKit` gives the stream one reference to each sample. A
+/// [`Retained`](Self::Retained) handler gets a `CMSampleBuffer` of its own,
+/// which costs a `CFRetain` (and a matching `CFRelease` when it is dropped)
+/// for every handler except a retaining handler that runs last, which is
+/// handed the stream's reference. A [`Borrowed`](Self::Borrowed) handler is
+/// lent the stream's reference and costs nothing extra.
+///
+/// Handlers for the same output type run in registration order, and the
+/// modes can be mixed freely.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SampleDelivery {
+    /// The handler owns its `CMSampleBuffer` and may keep it or send it to
+    /// another thread.
+    #[default]
+    Retained,
+    /// The handler receives `&CMSampleBuffer`, valid only until it returns.
+    Borrowed,
+}
+
+/// Output handler that borrows each sample
+///
+/// Wraps a `Fn(&CMSampleBuffer, SCStreamOutputType)` closure and reports
+/// [`SampleDelivery::Borrowed`]. Usually created through
+/// [`SCStream::add_output_handler_borrowed`](crate::stream::SCStream::add_output_handler_borrowed).
+pub struct BorrowedOutputHandler<F> {
+    handler: F,
+}
+
+impl<F> BorrowedOutputHandler<F>
+where
+    F: Fn(&CMSampleBuffer, SCStreamOutputType) + Send + Sync + 'static,
+{
+    /// Wrap `handler` so it is lent each sample instead of owning it.
+    #[must_use]
+    pub const fn new(handler: F) -> Self {
+        Self { handler }
+    }
+}
+
+impl<F> SCStreamOutputTrait for BorrowedOutputHandler<F>
+where
+    F: Fn(&CMSampleBuffer, SCStreamOutputType) + Send + Sync + 'static,
+{
+    fn did_output_sample_buffer(&self, sample_buffer: CMSampleBuffer, of_type: SCStreamOutputType) {
+        (self.handler)(&sample_buffer, of_type);
+    }
+
+    fn delivery(&self) -> SampleDelivery {
+        SampleDelivery::Borrowed
+    }
+
+    fn did_output_sample_buffer_borrowed(
+        &self,
+        sample_buffer: &CMSampleBuffer,
+        of_type: SCStreamOutputType,
+    ) {
+        (self.handler)(sample_buffer, of_type);
+    }
+}
+
+impl<F> fmt::Debug for BorrowedOutputHandler<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BorrowedOutputHandler")
+            .finish_non_exhaustive()
+    }
 }
 
 /// Blanket implementation for closures
@@ -103,3 +198,71 @@ where
         self(sample_buffer, of_type);
     }
 }
+
+/// Output handler that also learns which stream delivered each sample
+///
+/// A richer variant of [`SCStreamOutputTrait`] for apps that run several
+/// streams through the same handler code, or that need to act on the stream
+/// from a handler. Register it with
+/// [`SCStream::add_output_handler_with_context`](crate::stream::SCStream::add_output_handler_with_context).
+/// The same `Send + Sync` rules apply.
+///
+/// # Examples
+///
+/// ```
+/// use screencapturekit::cm::CMSampleBuffer;
+/// use screencapturekit::stream::{
+///     output_trait::ContextOutputTrait, output_type::SCStreamOutputType, sc_stream::StreamRef,
+/// };
+///
+/// struct PerStreamCounter;
+///
+/// impl ContextOutputTrait for PerStreamCounter {
+///     fn did_output_sample_buffer(
+///         &self,
+///         _sample: CMSampleBuffer,
+///         _of_type: SCStreamOutputType,
+///         stream: &StreamRef<'_>,
+///         handler_id: usize,
+///     ) {
+///         println!("stream {} handler {handler_id}: {}", stream.id(), stream.state());
+///     }
+/// }
+/// ```
+pub trait ContextOutputTrait: Send + Sync {
+    /// Called when a new sample buffer is available
+    ///
+    /// # Parameters
+    ///
+    /// - `sample_buffer`: The captured sample (video frame or audio buffer)
+    /// - `of_type`: Type of output (Screen, Audio, or Microphone)
+    /// - `stream`: The stream that captured the sample
+    /// - `handler_id`: The ID returned when this handler was registered
+    fn did_output_sample_buffer(
+        &self,
+        sample_buffer: CMSampleBuffer,
+        of_type: SCStreamOutputType,
+        stream: &StreamRef<'_>,
+        handler_id: usize,
+    );
+}
+
+/// Blanket implementation for closures
+///
+/// Closures must annotate the `StreamRef` parameter, e.g.
+/// `|sample, of_type, stream: &StreamRef<'_>, id| ...`, so the compiler
+/// accepts them for every borrow lifetime.
+impl<F> ContextOutputTrait for F
+where
+    F: Fn(CMSampleBuffer, SCStreamOutputType, &StreamRef<'_>, usize) + Send + Sync + 'static,
+{
+    fn did_output_sample_buffer(
+        &self,
+        sample_buffer: CMSampleBuffer,
+        of_type: SCStreamOutputType,
+        stream: &StreamRef<'_>,
+        handler_id: usize,
+    ) {
+        self(sample_buffer, of_type, stream, handler_id);
+    }
+}
@@ -33,7 +33,16 @@ pub enum SCStreamOutputType {
     /// Microphone audio output (macOS 15.0+)
     ///
     /// When using microphone capture, this output type allows separate handling
-    /// of microphone audio from system audio.
+    /// of microphone audio from system audio. Samples only arrive when the
+    /// configuration enables
+    /// [`with_captures_microphone`](crate::stream::configuration::SCStreamConfiguration::with_captures_microphone);
+    /// the input comes from
+    /// [`with_microphone_capture_device_id`](crate::stream::configuration::SCStreamConfiguration::with_microphone_capture_device_id)
+    /// or the system default. Switch devices on a running stream with
+    /// [`SCStream::set_microphone_device`](crate::stream::SCStream::set_microphone_device),
+    /// and use a
+    /// [`MicrophoneDeviceWatcher`](crate::stream::microphone_watcher::MicrophoneDeviceWatcher)
+    /// to fall back to the default input when the selected device is unplugged.
     Microphone,
 }
 
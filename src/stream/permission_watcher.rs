@@ -0,0 +1,299 @@
+//! Notice when Screen Recording permission is taken away
+//!
+//! Starting with macOS 15 the system asks users to re-confirm Screen
+//! Recording approval from time to time, and they can switch it off in
+//! System Settings at any moment. A running stream then stops with
+//! [`SCStreamErrorCode::UserDeclined`](crate::error::SCStreamErrorCode::UserDeclined),
+//! and `CGPreflightScreenCaptureAccess` starts returning false.
+//!
+//! Either signal is delivered to the stream's delegate as a single
+//! [`permission_revoked`](super::delegate_trait::SCStreamDelegateTrait::permission_revoked)
+//! event carrying a [`PermissionRevoked`]. The stream error is reported
+//! without any extra setup; a [`PermissionWatcher`] adds the preflight check
+//! and can open System Settings at the Screen Recording pane so the user can
+//! grant access again.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use std::time::Duration;
+//! use screencapturekit::prelude::*;
+//! use screencapturekit::stream::permission_watcher::PermissionWatcher;
+//! use screencapturekit::stream::StreamCallbacks;
+//!
+//! # fn example(filter: &SCContentFilter, config: &SCStreamConfiguration) -> Result<(), SCError> {
+//! let callbacks = StreamCallbacks::new().on_permission_revoked(|event| {
+//!     eprintln!("screen recording permission lost ({:?})", event.source());
+//! });
+//! let stream = SCStream::new_with_delegate(filter, config, callbacks);
+//! stream.start_capture()?;
+//! let _watcher = PermissionWatcher::start_opening_settings(&stream, Duration::from_secs(2))?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! ## Capturing without the permission
+//!
+//! Since macOS 14 an app doesn't need Screen Recording permission to
+//! capture content the user chose in
+//! [`SCContentSharingPicker`](crate::content_sharing_picker): picking is the
+//! consent, and it covers only streams using the picked filter. The process
+//! still can't list displays and windows through
+//! [`SCShareableContent`](crate::shareable_content::SCShareableContent).
+//! [`capture_access`] tells the two modes apart so an app can offer the
+//! picker instead of sending the user to System Settings:
+//!
+//! ```rust,no_run
+//! use screencapturekit::stream::permission_watcher::{
+//!     capture_access, open_screen_recording_settings, CaptureAccess,
+//! };
+//!
+//! # fn example() -> Result<(), screencapturekit::error::SCError> {
+//! match capture_access() {
+//!     CaptureAccess::Full => { /* list content with SCShareableContent */ }
+//!     CaptureAccess::PickerOnly => { /* present SCContentSharingPicker */ }
+//!     CaptureAccess::Unavailable => open_screen_recording_settings()?,
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! A [`PermissionWatcher`] stays quiet for a stream of picked content, as
+//! the process never had the permission it watches.
+
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::error::SCError;
+use crate::utils::poller::{Poller, PollerContext};
+
+use super::content_filter::SCContentFilter;
+use super::SCStream;
+
+/// Whether the process currently has Screen Recording permission.
+///
+/// Never shows the permission prompt.
+pub fn screen_capture_permitted() -> bool {
+    unsafe { crate::ffi::CGPreflightScreenCaptureAccess() }
+}
+
+/// What the process may capture
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CaptureAccess {
+    /// Screen Recording permission is granted: any content can be listed
+    /// and captured.
+    Full,
+    /// No Screen Recording permission, but content the user picks in
+    /// `SCContentSharingPicker` can be captured.
+    PickerOnly,
+    /// Nothing can be captured until the user grants Screen Recording
+    /// permission.
+    Unavailable,
+}
+
+impl CaptureAccess {
+    /// Whether [`SCShareableContent`](crate::shareable_content::SCShareableContent)
+    /// can list displays, windows and applications.
+    #[must_use]
+    pub const fn can_list_content(self) -> bool {
+        matches!(self, Self::Full)
+    }
+
+    /// Whether a stream with `filter` can capture.
+    #[must_use]
+    pub const fn allows(self, filter: &SCContentFilter) -> bool {
+        match self {
+            Self::Full => true,
+            Self::PickerOnly => filter.is_from_picker(),
+            Self::Unavailable => false,
+        }
+    }
+}
+
+impl fmt::Display for CaptureAccess {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Full => write!(f, "full screen recording access"),
+            Self::PickerOnly => write!(f, "picked content only"),
+            Self::Unavailable => write!(f, "no capture access"),
+        }
+    }
+}
+
+/// What the process may capture right now.
+///
+/// [`CaptureAccess::Full`] with Screen Recording permission, otherwise
+/// [`CaptureAccess::PickerOnly`] where `SCContentSharingPicker` exists.
+/// Never shows the permission prompt.
+pub fn capture_access() -> CaptureAccess {
+    if screen_capture_permitted() {
+        CaptureAccess::Full
+    } else if unsafe { crate::ffi::sc_content_sharing_picker_is_available() } {
+        CaptureAccess::PickerOnly
+    } else {
+        CaptureAccess::Unavailable
+    }
+}
+
+/// Open System Settings at Privacy & Security > Screen Recording.
+///
+/// # Errors
+///
+/// Returns an error if the settings pane could not be opened.
+pub fn open_screen_recording_settings() -> Result<(), SCError> {
+    if unsafe { crate::ffi::sc_open_screen_recording_settings() } {
+        Ok(())
+    } else {
+        Err(SCError::internal_error(
+            "Failed to open the Screen Recording settings pane",
+        ))
+    }
+}
+
+/// How a permission revocation was noticed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RevocationSource {
+    /// The stream stopped with a permission error.
+    StreamError,
+    /// `CGPreflightScreenCaptureAccess` turned false while a
+    /// [`PermissionWatcher`] was polling.
+    Preflight,
+}
+
+/// Screen Recording permission was revoked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PermissionRevoked {
+    source: RevocationSource,
+    error: Option<SCError>,
+}
+
+impl PermissionRevoked {
+    pub(crate) const fn from_stream_error(error: SCError) -> Self {
+        Self {
+            source: RevocationSource::StreamError,
+            error: Some(error),
+        }
+    }
+
+    pub(crate) const fn from_preflight() -> Self {
+        Self {
+            source: RevocationSource::Preflight,
+            error: None,
+        }
+    }
+
+    /// How the revocation was noticed.
+    pub const fn source(&self) -> RevocationSource {
+        self.source
+    }
+
+    /// The error the stream stopped with, for
+    /// [`RevocationSource::StreamError`].
+    pub const fn error(&self) -> Option<&SCError> {
+        self.error.as_ref()
+    }
+}
+
+/// Polls Screen Recording permission for a stream and reports when it is
+/// revoked.
+///
+/// Usually created with [`SCStream::watch_permission`]. Stops when dropped.
+pub struct PermissionWatcher {
+    revocations: Arc<AtomicU64>,
+    poller: Poller,
+}
+
+impl PermissionWatcher {
+    /// Check permission every `interval` and deliver
+    /// [`permission_revoked`](super::delegate_trait::SCStreamDelegateTrait::permission_revoked)
+    /// to `stream`'s delegate when it is lost.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InternalError`] if the operating system refuses
+    /// to spawn the watcher thread.
+    pub fn start(stream: &SCStream, interval: Duration) -> Result<Self, SCError> {
+        Self::spawn(stream, interval, false)
+    }
+
+    /// Like [`start`](Self::start), and also open System Settings at the
+    /// Screen Recording pane each time permission is lost.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InternalError`] if the operating system refuses
+    /// to spawn the watcher thread.
+    pub fn start_opening_settings(stream: &SCStream, interval: Duration) -> Result<Self, SCError> {
+        Self::spawn(stream, interval, true)
+    }
+
+    fn spawn(stream: &SCStream, interval: Duration, open_settings: bool) -> Result<Self, SCError> {
+        let revocations = Arc::new(AtomicU64::new(0));
+        let poller = {
+            let revocations = Arc::clone(&revocations);
+            let stream = stream.clone();
+            Poller::spawn("permission-watcher", move |context| {
+                watch(context, &revocations, &stream, interval, open_settings);
+            })?
+        };
+
+        Ok(Self {
+            revocations,
+            poller,
+        })
+    }
+
+    /// Number of revocations seen since the watcher started.
+    #[must_use]
+    pub fn revocation_count(&self) -> u64 {
+        self.revocations.load(Ordering::Relaxed)
+    }
+
+    /// The error from the last failed attempt to open System Settings, if
+    /// any.
+    #[must_use]
+    pub fn last_error(&self) -> Option<SCError> {
+        self.poller.last_error()
+    }
+}
+
+fn watch(
+    context: &PollerContext,
+    revocations: &AtomicU64,
+    stream: &SCStream,
+    interval: Duration,
+    open_settings: bool,
+) {
+    let mut permitted = screen_capture_permitted();
+    let mut revoked = !permitted || stream.permission_revoked_reported();
+    while !context.sleep(interval) {
+        let now_permitted = screen_capture_permitted();
+        if now_permitted && !permitted {
+            stream.clear_permission_revoked();
+        }
+        if permitted && !now_permitted {
+            // A no-op if the stream error already reported this revocation.
+            stream.notify_permission_revoked(&PermissionRevoked::from_preflight());
+        }
+        permitted = now_permitted;
+
+        let now_revoked = !permitted || stream.permission_revoked_reported();
+        if now_revoked && !revoked {
+            revocations.fetch_add(1, Ordering::Relaxed);
+            if open_settings {
+                context.set_last_error(open_screen_recording_settings().err());
+            }
+        }
+        revoked = now_revoked;
+    }
+}
+
+impl fmt::Debug for PermissionWatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PermissionWatcher")
+            .field("revocation_count", &self.revocation_count())
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,221 @@
+//! Output handler that processes samples on a worker thread pool
+//!
+//! `ScreenCaptureKit` delivers samples for each output type on a serial
+//! dispatch queue, so a handler that spends longer than one frame interval on
+//! each sample (CPU encoding, image analysis, …) backs that queue up and the
+//! stream starts dropping frames. [`PooledOutputHandler`] returns from the
+//! callback immediately and hands each sample to one of a fixed number of
+//! worker threads instead.
+//!
+//! Each queued [`CMSampleBuffer`] is an owned, retained handle, so the
+//! underlying pixel or audio data stays valid until the worker that receives
+//! it drops it. Keep in mind that `ScreenCaptureKit` draws frames from a
+//! small surface pool (see
+//! [`with_queue_depth`](crate::stream::configuration::SCStreamConfiguration::with_queue_depth));
+//! holding many frames at once starves capture, which is why the pool's
+//! queue is bounded.
+//!
+//! ## Ordering and back-pressure
+//!
+//! - With more than one worker, samples may be *processed* out of order.
+//!   Use the presentation timestamp if order matters, or a single worker.
+//! - At most `2 × workers` samples wait in the queue. When it is full the
+//!   oldest waiting sample is dropped in favour of the newest (the same
+//!   policy as the async sample iterator). The count is available from
+//!   [`PooledOutputHandler::dropped_samples`].
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use screencapturekit::prelude::*;
+//!
+//! # fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! # let content = SCShareableContent::get()?;
+//! # let display = &content.displays()[0];
+//! # let filter = SCContentFilter::for_display(display).with_excluding_windows(&[]).build();
+//! # let config = SCStreamConfiguration::default();
+//! let mut stream = SCStream::new(&filter, &config);
+//! stream.add_output_handler_pooled(
+//!     |sample: CMSampleBuffer, _of_type| {
+//!         // Expensive per-frame work runs on one of 4 worker threads.
+//!         let _ = sample.image_buffer();
+//!     },
+//!     SCStreamOutputType::Screen,
+//!     4,
+//! );
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Condvar, Mutex, PoisonError};
+use std::thread::JoinHandle;
+
+use crate::cm::CMSampleBuffer;
+use crate::error::SCError;
+use crate::utils::panic_safe::catch_user_panic;
+
+use super::output_trait::SCStreamOutputTrait;
+use super::output_type::SCStreamOutputType;
+
+/// Samples allowed to wait in the queue per worker.
+const QUEUED_PER_WORKER: usize = 2;
+
+struct PoolQueue {
+    samples: VecDeque<(CMSampleBuffer, SCStreamOutputType)>,
+    closed: bool,
+}
+
+struct PoolShared {
+    queue: Mutex<PoolQueue>,
+    available: Condvar,
+    dropped: AtomicU64,
+    capacity: usize,
+}
+
+/// Wraps an output handler so that samples are processed on a bounded pool
+/// of worker threads instead of the capture callback queue.
+///
+/// Usually created through
+/// [`SCStream::add_output_handler_pooled`](crate::stream::SCStream::add_output_handler_pooled).
+/// Dropping the handler (for example by removing it from the stream) lets the
+/// workers finish the samples that are already queued, then joins them.
+pub struct PooledOutputHandler {
+    shared: Arc<PoolShared>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl PooledOutputHandler {
+    /// Start `workers` threads that call `handler` for each sample.
+    ///
+    /// A `workers` value of `0` is treated as `1`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InternalError`] if the operating system refuses to
+    /// spawn a worker thread. The workers already started are joined.
+    pub fn new(
+        handler: impl SCStreamOutputTrait + 'static,
+        workers: usize,
+    ) -> Result<Self, SCError> {
+        let count = workers.max(1);
+        let shared = Arc::new(PoolShared {
+            queue: Mutex::new(PoolQueue {
+                samples: VecDeque::new(),
+                closed: false,
+            }),
+            available: Condvar::new(),
+            dropped: AtomicU64::new(0),
+            capacity: count * QUEUED_PER_WORKER,
+        });
+        let handler: Arc<dyn SCStreamOutputTrait> = Arc::new(handler);
+
+        // Built up front so that a failed spawn drops it, which closes the
+        // queue and joins the workers already running.
+        let mut pool = Self {
+            shared,
+            workers: Vec::with_capacity(count),
+        };
+        for index in 0..count {
+            let shared = Arc::clone(&pool.shared);
+            let handler = Arc::clone(&handler);
+            let worker = std::thread::Builder::new()
+                .name(format!("sc-output-worker-{index}"))
+                .spawn(move || worker_loop(&shared, handler.as_ref()))
+                .map_err(|e| {
+                    SCError::internal_error(format!("failed to spawn output worker thread: {e}"))
+                })?;
+            pool.workers.push(worker);
+        }
+        Ok(pool)
+    }
+
+    /// Number of worker threads.
+    #[must_use]
+    pub fn worker_count(&self) -> usize {
+        self.workers.len()
+    }
+
+    /// Samples discarded because every worker was busy and the queue was full.
+    #[must_use]
+    pub fn dropped_samples(&self) -> u64 {
+        self.shared.dropped.load(Ordering::Relaxed)
+    }
+}
+
+fn worker_loop(shared: &PoolShared, handler: &dyn SCStreamOutputTrait) {
+    loop {
+        let next = {
+            let mut queue = shared.queue.lock().unwrap_or_else(PoisonError::into_inner);
+            loop {
+                if let Some(next) = queue.samples.pop_front() {
+                    break Some(next);
+                }
+                if queue.closed {
+                    break None;
+                }
+                queue = shared
+                    .available
+                    .wait(queue)
+                    .unwrap_or_else(PoisonError::into_inner);
+            }
+        };
+        let Some((sample, of_type)) = next else {
+            return;
+        };
+        catch_user_panic("pooled output handler", || {
+            handler.did_output_sample_buffer(sample, of_type);
+        });
+    }
+}
+
+impl SCStreamOutputTrait for PooledOutputHandler {
+    fn did_output_sample_buffer(&self, sample_buffer: CMSampleBuffer, of_type: SCStreamOutputType) {
+        let (evicted, depth) = {
+            let mut queue = self
+                .shared
+                .queue
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            let evicted = if queue.samples.len() >= self.shared.capacity {
+                queue.samples.pop_front()
+            } else {
+                None
+            };
+            queue.samples.push_back((sample_buffer, of_type));
+            (evicted, queue.samples.len())
+        };
+        if evicted.is_some() {
+            self.shared.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+        crate::instrument::output_pool_enqueued(evicted.is_some(), depth);
+        self.shared.available.notify_one();
+        // `evicted` is released here, outside the queue lock.
+    }
+}
+
+impl Drop for PooledOutputHandler {
+    fn drop(&mut self) {
+        self.shared
+            .queue
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .closed = true;
+        self.shared.available.notify_all();
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+impl fmt::Debug for PooledOutputHandler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PooledOutputHandler")
+            .field("workers", &self.workers.len())
+            .field("capacity", &self.shared.capacity)
+            .field("dropped_samples", &self.dropped_samples())
+            .finish()
+    }
+}
@@ -0,0 +1,173 @@
+//! Shorter time-to-first-frame for "click to share"
+//!
+//! Creating a stream, attaching its outputs and starting capture take a
+//! noticeable fraction of a second, most of it in `startCapture`. When all
+//! of that happens after the user clicks "Share", the first frame arrives
+//! late.
+//!
+//! A [`PrewarmedStream`] does the work ahead of time against a placeholder
+//! filter, typically the main display. Output handlers and their queues are
+//! attached through [`stream_mut`](PrewarmedStream::stream_mut) while the
+//! stream waits. [`keep_warm`](PrewarmedStream::keep_warm) goes further and
+//! starts capture at one 2×2 frame per second, with every sample withheld
+//! from the handlers, so that [`activate`](PrewarmedStream::activate) only
+//! swaps the filter and configuration of a stream that is already running.
+//!
+//! A warm stream is capturing as far as macOS is concerned: the
+//! screen-recording indicator shows while it waits.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use screencapturekit::prelude::*;
+//! use screencapturekit::stream::prewarm::PrewarmedStream;
+//!
+//! # fn example(picked: &SCContentFilter) -> Result<(), SCError> {
+//! let content = SCShareableContent::get()?;
+//! let placeholder = SCContentFilter::for_display(&content.displays()[0])
+//!     .with_excluding_windows(&[])
+//!     .build();
+//! let config = SCStreamConfiguration::new().with_width(1920).with_height(1080);
+//!
+//! let mut prewarmed = PrewarmedStream::new(&placeholder, &config);
+//! prewarmed
+//!     .stream_mut()
+//!     .add_output_handler(|_sample, _of_type| {}, SCStreamOutputType::Screen);
+//! prewarmed.keep_warm()?;
+//!
+//! // Later, when the user clicks "Share":
+//! let stream = prewarmed.activate(picked)?;
+//! # drop(stream);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+
+use crate::cm::CMTime;
+use crate::error::SCError;
+use crate::stream::configuration::SCStreamConfiguration;
+use crate::stream::content_filter::SCContentFilter;
+
+use super::SCStream;
+
+/// Configuration a warm stream idles with: as little work as
+/// `ScreenCaptureKit` allows.
+fn idle_configuration() -> SCStreamConfiguration {
+    SCStreamConfiguration::new()
+        .with_width(2)
+        .with_height(2)
+        .with_minimum_frame_interval(&CMTime::new(1, 1))
+        .with_queue_depth(3)
+        .with_captures_audio(false)
+}
+
+/// A stream created ahead of time, waiting for the content to capture
+///
+/// Dropping it without [`activate`](Self::activate) drops the stream, which
+/// stops a warm one.
+pub struct PrewarmedStream {
+    stream: SCStream,
+    configuration: SCStreamConfiguration,
+}
+
+impl PrewarmedStream {
+    /// Create a stream for `placeholder`, to capture with `configuration`
+    /// once activated
+    ///
+    /// The stream is cold: created, but not capturing.
+    #[must_use]
+    pub fn new(placeholder: &SCContentFilter, configuration: &SCStreamConfiguration) -> Self {
+        Self {
+            stream: SCStream::new(placeholder, configuration),
+            configuration: configuration.clone(),
+        }
+    }
+
+    /// The prewarmed stream
+    #[must_use]
+    pub const fn stream(&self) -> &SCStream {
+        &self.stream
+    }
+
+    /// The prewarmed stream, to attach output handlers before activation
+    pub fn stream_mut(&mut self) -> &mut SCStream {
+        &mut self.stream
+    }
+
+    /// The configuration the stream captures with once activated
+    #[must_use]
+    pub const fn configuration(&self) -> &SCStreamConfiguration {
+        &self.configuration
+    }
+
+    /// Whether the stream is already capturing at idle settings
+    #[must_use]
+    pub fn is_warm(&self) -> bool {
+        self.stream.is_capturing()
+    }
+
+    /// Start capturing at idle settings, withholding samples from the
+    /// handlers until [`activate`](Self::activate)
+    ///
+    /// Does nothing if the stream is already warm.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error from applying the idle configuration or starting
+    /// capture; the stream is left cold.
+    pub fn keep_warm(&self) -> Result<(), SCError> {
+        let stream = &self.stream;
+        if stream.is_capturing() {
+            return Ok(());
+        }
+        stream.set_withholding_samples(true);
+        let started = stream
+            .update_configuration(&idle_configuration())
+            .and_then(|()| stream.start_capture());
+        if started.is_err() {
+            stream.set_withholding_samples(false);
+            let _ = stream.update_configuration(&self.configuration);
+        }
+        started
+    }
+
+    /// Capture `filter` with the stream's configuration
+    ///
+    /// A warm stream swaps its filter and configuration while running and
+    /// then starts delivering samples; a cold one swaps its filter and
+    /// starts.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error from updating the filter or configuration, or from
+    /// starting capture. The stream is stopped and dropped.
+    pub fn activate(self, filter: &SCContentFilter) -> Result<SCStream, SCError> {
+        let stream = self.stream;
+        let result = if stream.is_capturing() {
+            stream
+                .update_content_filter(filter)
+                .and_then(|()| stream.update_configuration(&self.configuration))
+        } else {
+            stream
+                .update_content_filter(filter)
+                .and_then(|()| stream.start_capture())
+        };
+        stream.set_withholding_samples(false);
+        if let Err(error) = result {
+            if stream.state().can_stop() {
+                let _ = stream.stop_capture();
+            }
+            return Err(error);
+        }
+        Ok(stream)
+    }
+}
+
+impl fmt::Debug for PrewarmedStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PrewarmedStream")
+            .field("warm", &self.is_warm())
+            .finish_non_exhaustive()
+    }
+}
@@ -7,21 +7,36 @@
 //! handlers and delegate. The context pointer is passed through FFI so that
 //! callbacks route directly to the owning stream — no global registries.
 
-use std::ffi::{c_void, CStr};
+use std::cell::RefCell;
+use std::ffi::c_void;
 use std::fmt;
-use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::RwLock;
-
-use crate::error::SCError;
-use crate::stream::delegate_trait::SCStreamDelegateTrait;
-use crate::utils::completion::UnitCompletion;
+use std::sync::atomic::{AtomicBool, AtomicPtr, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, RwLock};
+use std::time::Duration;
+
+use crate::cm::{CMFormatDescription, CMSampleBuffer};
+use crate::error::{CaptureStartDiagnostics, NSErrorInfo, SCError};
+use crate::stream::delegate_trait::{ContentRectChanged, SCStreamDelegateTrait, SCVideoEffect};
+use crate::stream::permission_watcher::PermissionRevoked;
+use crate::utils::completion::SyncCompletion;
 use crate::utils::panic_safe::catch_user_panic;
 use crate::{
-    dispatch_queue::DispatchQueue,
+    dispatch_queue::{DispatchQoS, DispatchQueue},
     ffi,
     stream::{
-        configuration::SCStreamConfiguration, content_filter::SCContentFilter,
-        output_trait::SCStreamOutputTrait, output_type::SCStreamOutputType,
+        configuration::{AudioFormat, SCStreamConfiguration},
+        content_filter::SCContentFilter,
+        cursor::{CursorOutputHandler, CursorUpdate},
+        event_driven::{EventDrivenOptions, EventDrivenOutputHandler},
+        executor_output::{ExecutorOutputHandler, HandlerThread},
+        frame_ring::FrameRing,
+        output_trait::{
+            BorrowedOutputHandler, ContextOutputTrait, SCStreamOutputTrait, SampleDelivery,
+        },
+        output_type::SCStreamOutputType,
+        pooled_output::PooledOutputHandler,
+        state::SCStreamState,
+        tee_output::TeeOutputHandler,
     },
 };
 
@@ -29,9 +44,31 @@ use crate::{
 struct HandlerEntry {
     id: usize,
     of_type: SCStreamOutputType,
+    /// Rust type name of the handler as passed in, for [`SCStream::outputs`].
+    type_name: &'static str,
     handler: Box<dyn SCStreamOutputTrait>,
 }
 
+/// The Swift stream pointer and the number of live `SCStream` handles that
+/// each own a +1 reference to it.
+struct StreamHandles {
+    ptr: *const c_void,
+    count: usize,
+}
+
+impl Default for StreamHandles {
+    fn default() -> Self {
+        Self {
+            ptr: std::ptr::null(),
+            count: 0,
+        }
+    }
+}
+
+// SAFETY: the pointer is an `SCStream` object whose retain/release are
+// atomic; it is only retained or released while the owning mutex is held.
+unsafe impl Send for StreamHandles {}
+
 /// Per-stream context holding output handlers and an optional delegate.
 ///
 /// Allocated on the heap via `Box::into_raw` and passed through FFI as an
@@ -43,16 +80,82 @@ struct HandlerEntry {
 /// dispatch queues (e.g. screen + audio) can dispatch in parallel. Slow
 /// user handlers no longer serialise across output types.
 struct StreamContext {
+    /// Process-unique identity, shared by every clone of the stream.
+    id: u64,
+    /// The Swift stream and how many `SCStream` handles own a reference to
+    /// it, so [`StreamRef::upgrade`] never retains a freed stream.
+    handles: std::sync::Mutex<StreamHandles>,
     handlers: RwLock<Vec<HandlerEntry>>,
     delegate: RwLock<Option<Box<dyn SCStreamDelegateTrait>>>,
+    /// Private copy of the configuration last applied to the stream, used as
+    /// the base for [`SCStream::update_configuration_with`].
+    configuration: std::sync::Mutex<Option<SCStreamConfiguration>>,
+    /// Held by [`SCStream::update_configuration_with`] from reading the
+    /// configuration until the update is applied, so concurrent partial
+    /// updates each start from the other's result.
+    configuration_update: std::sync::Mutex<()>,
+    /// Content filter last applied to the stream, kept for diagnostics and
+    /// [`SCStream::capture_access`].
+    filter: std::sync::Mutex<Option<SCContentFilter>>,
+    /// Lifecycle state, shared by every clone of the stream.
+    state: std::sync::Mutex<SCStreamState>,
+    /// Set once `permission_revoked` has been delivered, so the stream error
+    /// and a permission watcher don't both report the same revocation.
+    permission_revoked: AtomicBool,
+    /// Recording outputs currently attached to the stream. Shared through the
+    /// context so every clone of an `SCStream` sees the same set.
+    #[cfg(feature = "macos_15_0")]
+    recording_outputs: std::sync::Mutex<Vec<crate::recording_output::SCRecordingOutput>>,
+    /// How long dropping the last handle waits for a running capture to stop.
+    drop_timeout: std::sync::Mutex<Duration>,
+    /// Format of the latest system audio and microphone samples, in that
+    /// order, with the format description it was read from. Cleared when
+    /// capture starts.
+    audio_formats: std::sync::Mutex<[Option<(AudioFormat, CMFormatDescription)>; 2]>,
+    /// Address of each format description held in `audio_formats`, compared
+    /// without locking so a sample's format is only read when it changes.
+    audio_format_descriptions: [AtomicPtr<c_void>; 2],
+    /// Content geometry of the latest complete screen frame, cleared when
+    /// capture starts.
+    content_rect: std::sync::Mutex<Option<ContentRectChanged>>,
+    /// Whether screen frames' geometry is read at all: set for streams with
+    /// a delegate, and by the first [`SCStream::content_rect`] call.
+    tracks_content_rect: AtomicBool,
+    /// While set, samples are released without reaching any handler; used
+    /// by [`PrewarmedStream`](crate::stream::prewarm::PrewarmedStream).
+    withholding_samples: AtomicBool,
     ref_count: AtomicUsize,
 }
 
+/// Index into `StreamContext::audio_formats` for an audio output type.
+const fn audio_format_slot(of_type: SCStreamOutputType) -> Option<usize> {
+    match of_type {
+        SCStreamOutputType::Screen => None,
+        SCStreamOutputType::Audio => Some(0),
+        SCStreamOutputType::Microphone => Some(1),
+    }
+}
+
 impl StreamContext {
     fn new() -> *mut Self {
         let ctx = Box::new(Self {
+            id: NEXT_STREAM_ID.fetch_add(1, Ordering::Relaxed),
+            handles: std::sync::Mutex::new(StreamHandles::default()),
             handlers: RwLock::new(Vec::new()),
             delegate: RwLock::new(None),
+            configuration: std::sync::Mutex::new(None),
+            configuration_update: std::sync::Mutex::new(()),
+            filter: std::sync::Mutex::new(None),
+            state: std::sync::Mutex::new(SCStreamState::Idle),
+            permission_revoked: AtomicBool::new(false),
+            #[cfg(feature = "macos_15_0")]
+            recording_outputs: std::sync::Mutex::new(Vec::new()),
+            drop_timeout: std::sync::Mutex::new(SCStream::DEFAULT_DROP_TIMEOUT),
+            audio_formats: std::sync::Mutex::new([None, None]),
+            audio_format_descriptions: [AtomicPtr::default(), AtomicPtr::default()],
+            content_rect: std::sync::Mutex::new(None),
+            tracks_content_rect: AtomicBool::new(false),
+            withholding_samples: AtomicBool::new(false),
             ref_count: AtomicUsize::new(1),
         });
         Box::into_raw(ctx)
@@ -60,13 +163,248 @@ impl StreamContext {
 
     fn new_with_delegate(delegate: Box<dyn SCStreamDelegateTrait>) -> *mut Self {
         let ctx = Box::new(Self {
+            id: NEXT_STREAM_ID.fetch_add(1, Ordering::Relaxed),
+            handles: std::sync::Mutex::new(StreamHandles::default()),
             handlers: RwLock::new(Vec::new()),
             delegate: RwLock::new(Some(delegate)),
+            configuration: std::sync::Mutex::new(None),
+            configuration_update: std::sync::Mutex::new(()),
+            filter: std::sync::Mutex::new(None),
+            state: std::sync::Mutex::new(SCStreamState::Idle),
+            permission_revoked: AtomicBool::new(false),
+            #[cfg(feature = "macos_15_0")]
+            recording_outputs: std::sync::Mutex::new(Vec::new()),
+            drop_timeout: std::sync::Mutex::new(SCStream::DEFAULT_DROP_TIMEOUT),
+            audio_formats: std::sync::Mutex::new([None, None]),
+            audio_format_descriptions: [AtomicPtr::default(), AtomicPtr::default()],
+            content_rect: std::sync::Mutex::new(None),
+            tracks_content_rect: AtomicBool::new(true),
+            withholding_samples: AtomicBool::new(false),
             ref_count: AtomicUsize::new(1),
         });
         Box::into_raw(ctx)
     }
 
+    /// Record a new `SCStream` handle owning a +1 reference to `ptr`.
+    fn handle_acquired(&self, ptr: *const c_void) {
+        let mut handles = self
+            .handles
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        handles.ptr = ptr;
+        handles.count += 1;
+    }
+
+    /// Record another handle to a stream that still has one, for streams
+    /// found through the Swift registry. Returns `false` once the last
+    /// handle is being dropped.
+    fn handle_shared(&self) -> bool {
+        let mut handles = self
+            .handles
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if handles.count == 0 {
+            return false;
+        }
+        handles.count += 1;
+        true
+    }
+
+    /// Remember the format of an audio sample for [`SCStream::audio_format`].
+    ///
+    /// Runs for every audio sample, so it only reads the format and takes the
+    /// lock when the sample's format description differs from the last one.
+    fn record_audio_format(&self, of_type: SCStreamOutputType, sample: &CMSampleBuffer) {
+        let Some(slot) = audio_format_slot(of_type) else {
+            return;
+        };
+        // SAFETY: `sample` is a live sample buffer; the description is
+        // borrowed, not retained.
+        let description =
+            unsafe { crate::cm::ffi::cm_sample_buffer_get_format_description(sample.as_ptr()) };
+        // The recorded description is retained in `audio_formats`, so its
+        // address can't be reused by a different format while it's there.
+        if description.is_null()
+            || description == self.audio_format_descriptions[slot].load(Ordering::Acquire)
+        {
+            return;
+        }
+        let (Some(format), Some(description)) =
+            (AudioFormat::of(sample), sample.format_description())
+        else {
+            return;
+        };
+        let mut formats = self
+            .audio_formats
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        self.audio_format_descriptions[slot].store(description.as_ptr(), Ordering::Release);
+        formats[slot] = Some((format, description));
+    }
+
+    /// Forget the recorded audio formats.
+    fn clear_audio_formats(&self) {
+        let mut formats = self
+            .audio_formats
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        for description in &self.audio_format_descriptions {
+            description.store(std::ptr::null_mut(), Ordering::Release);
+        }
+        *formats = [None, None];
+    }
+
+    /// Store the geometry of a complete screen frame and tell the delegate
+    /// if it differs from the previous frame's.
+    fn record_content_rect(&self, of_type: SCStreamOutputType, sample: &CMSampleBuffer) {
+        // Reading the geometry takes several attachment lookups per frame;
+        // skip them while nothing would see the result.
+        if of_type != SCStreamOutputType::Screen
+            || !self.tracks_content_rect.load(Ordering::Relaxed)
+        {
+            return;
+        }
+        let Some(change) = ContentRectChanged::of(sample) else {
+            return;
+        };
+        {
+            let mut latest = self
+                .content_rect
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            if *latest == Some(change) {
+                return;
+            }
+            *latest = Some(change);
+        }
+        let delegate = self
+            .delegate
+            .read()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if let Some(ref delegate) = *delegate {
+            catch_user_panic("delegate.content_rect_did_change", || {
+                delegate.content_rect_did_change(&change);
+            });
+        }
+    }
+
+    fn state(&self) -> SCStreamState {
+        *self
+            .state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Enter the in-flight state `next` picks for the current state, or fail
+    /// with `InvalidState` if it picks none. Returns the state entered.
+    fn begin_transition(
+        &self,
+        operation: &str,
+        next: impl FnOnce(SCStreamState) -> Option<SCStreamState>,
+    ) -> Result<SCStreamState, SCError> {
+        let mut state = self
+            .state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let current = *state;
+        let Some(entered) = next(current) else {
+            return Err(SCError::InvalidState {
+                operation: operation.to_string(),
+                state: current,
+            });
+        };
+        *state = entered;
+        drop(state);
+        self.notify_state_change(current, entered);
+        Ok(entered)
+    }
+
+    /// Leave the in-flight state `from` for `to`. Does nothing if the state
+    /// moved on meanwhile, e.g. because the stream failed mid-update.
+    fn finish_transition(&self, from: SCStreamState, to: SCStreamState) {
+        let mut state = self
+            .state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if *state != from || from == to {
+            return;
+        }
+        *state = to;
+        drop(state);
+        self.notify_state_change(from, to);
+    }
+
+    /// Record that `ScreenCaptureKit` stopped the stream with an error.
+    fn mark_failed(&self) {
+        let mut state = self
+            .state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let old = std::mem::replace(&mut *state, SCStreamState::Failed);
+        drop(state);
+        self.notify_state_change(old, SCStreamState::Failed);
+    }
+
+    fn notify_permission_revoked(&self, event: &PermissionRevoked) {
+        if self.permission_revoked.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        let delegate = self
+            .delegate
+            .read()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if let Some(ref delegate) = *delegate {
+            catch_user_panic("delegate.permission_revoked", || {
+                delegate.permission_revoked(event);
+            });
+        }
+    }
+
+    fn notify_state_change(&self, old: SCStreamState, new: SCStreamState) {
+        if old == new {
+            return;
+        }
+        let delegate = self
+            .delegate
+            .read()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if let Some(ref delegate) = *delegate {
+            catch_user_panic("delegate.stream_state_did_change", || {
+                delegate.stream_state_did_change(old, new);
+            });
+        }
+    }
+
+    /// Detach every output handler and the delegate so Swift has nothing
+    /// left to call into.
+    ///
+    /// Taking the write locks waits for callbacks already running on other
+    /// queues, since each holds a read lock for the whole dispatch. The
+    /// handlers are dropped after the locks are released, so a pooled
+    /// handler joining its workers can't hold up a late callback. Returns
+    /// `false` without draining when called from one of this context's own
+    /// callbacks, where the write lock would deadlock on the read lock the
+    /// callback holds.
+    fn drain(&self) -> bool {
+        if CallbackScope::is_active(self) {
+            return false;
+        }
+        let handlers = std::mem::take(
+            &mut *self
+                .handlers
+                .write()
+                .unwrap_or_else(std::sync::PoisonError::into_inner),
+        );
+        let delegate = self
+            .delegate
+            .write()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .take();
+        drop(handlers);
+        drop(delegate);
+        true
+    }
+
     /// Increment the reference count.
     ///
     /// # Safety
@@ -115,9 +453,46 @@ const _: fn() = || {
     assert_send_sync::<StreamContext>();
 };
 
+thread_local! {
+    /// Contexts whose callbacks are running on this thread, innermost last.
+    static CALLBACK_CONTEXTS: RefCell<Vec<*const StreamContext>> =
+        const { RefCell::new(Vec::new()) };
+}
+
+/// Marks a callback into a `StreamContext` as running on this thread, so
+/// dropping the stream from inside it doesn't wait on itself.
+struct CallbackScope(*const StreamContext);
+
+impl CallbackScope {
+    fn enter(ctx: &StreamContext) -> Self {
+        let key = std::ptr::from_ref(ctx);
+        CALLBACK_CONTEXTS.with(|contexts| contexts.borrow_mut().push(key));
+        Self(key)
+    }
+
+    fn is_active(ctx: &StreamContext) -> bool {
+        let key = std::ptr::from_ref(ctx);
+        CALLBACK_CONTEXTS.with(|contexts| contexts.borrow().contains(&key))
+    }
+}
+
+impl Drop for CallbackScope {
+    fn drop(&mut self) {
+        CALLBACK_CONTEXTS.with(|contexts| {
+            let mut contexts = contexts.borrow_mut();
+            if let Some(pos) = contexts.iter().rposition(|&key| key == self.0) {
+                contexts.remove(pos);
+            }
+        });
+    }
+}
+
 /// Monotonically increasing handler ID generator (process-wide).
 static NEXT_HANDLER_ID: AtomicUsize = AtomicUsize::new(1);
 
+/// Monotonically increasing stream ID generator (process-wide).
+static NEXT_STREAM_ID: AtomicU64 = AtomicU64::new(1);
+
 // C trampoline handed to Swift so the bridge objects (delegate wrapper and
 // output handler) can each take a +1 reference on the `StreamContext` for the
 // duration of their own lifetime. This keeps the context alive while any
@@ -135,6 +510,71 @@ extern "C" fn context_release_cb(context: *mut c_void) {
     unsafe { StreamContext::release(context.cast::<StreamContext>()) };
 }
 
+// C callback for lifecycle operations and recording output add/remove. The
+// error is a borrowed NSError, copied here so failures keep their domain,
+// code and userInfo (e.g. when the OS refuses an extra recording output).
+extern "C" fn stream_control_callback(context: *mut c_void, success: bool, error: *const c_void) {
+    catch_user_panic("stream_control_callback", move || {
+        let result = if success || error.is_null() {
+            Ok(())
+        } else {
+            // SAFETY: Swift lends a live NSError for the duration of the callback.
+            Err(SCError::from_ns_error(unsafe {
+                NSErrorInfo::from_borrowed(error)
+            }))
+        };
+        // SAFETY: `context` is the one-shot completion context from
+        // `SyncCompletion::new()`; Swift invokes this callback exactly once.
+        unsafe { SyncCompletion::<Result<(), SCError>>::complete_ok(context, result) };
+    });
+}
+
+/// One-shot result slot for [`SCStream::start_capture_with_timeout`].
+///
+/// Unlike `SyncCompletion`, the waiter can walk away: the callback holds its
+/// own `Arc`, so a late answer lands in a slot nobody reads instead of freed
+/// memory.
+#[derive(Default)]
+struct TimedCompletion {
+    result: std::sync::Mutex<Option<Result<(), SCError>>>,
+    done: Condvar,
+}
+
+impl TimedCompletion {
+    fn wait(&self, timeout: Duration) -> Option<Result<(), SCError>> {
+        let slot = self
+            .result
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        self.done
+            .wait_timeout_while(slot, timeout, |result| result.is_none())
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .0
+            .take()
+    }
+}
+
+extern "C" fn timed_control_callback(context: *mut c_void, success: bool, error: *const c_void) {
+    catch_user_panic("timed_control_callback", move || {
+        // SAFETY: `context` is the `Arc` leaked in `start_capture_with_timeout`;
+        // Swift invokes this callback exactly once.
+        let completion = unsafe { Arc::from_raw(context.cast::<TimedCompletion>()) };
+        let result = if success || error.is_null() {
+            Ok(())
+        } else {
+            // SAFETY: Swift lends a live NSError for the duration of the callback.
+            Err(SCError::from_ns_error(unsafe {
+                NSErrorInfo::from_borrowed(error)
+            }))
+        };
+        *completion
+            .result
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner) = Some(result);
+        completion.done.notify_all();
+    });
+}
+
 // C callback for stream errors — dispatches to per-stream delegate via context pointer.
 //
 // Safety: this function is called from Swift. A Rust panic unwinding across
@@ -142,36 +582,21 @@ extern "C" fn context_release_cb(context: *mut c_void) {
 // methods) is wrapped in `catch_unwind`. The `delegate` lock is taken with
 // `unwrap_or_else` poisoning recovery so a panic in one callback cannot
 // permanently break the stream by poisoning the lock.
-extern "C" fn delegate_error_callback(context: *mut c_void, error_code: i32, msg: *const i8) {
-    if context.is_null() {
+extern "C" fn delegate_error_callback(context: *mut c_void, ns_error: *const c_void) {
+    if context.is_null() || ns_error.is_null() {
         return;
     }
     // SAFETY: `context` is the +1-retained StreamContext pointer the Swift
     // bridge stored via context_retain_cb; it outlives this callback.
     let ctx = unsafe { &*(context.cast::<StreamContext>()) };
+    let _scope = CallbackScope::enter(ctx);
 
-    let message = if msg.is_null() {
-        "Unknown error".to_string()
-    } else {
-        // Best-effort: if Swift sent a non-UTF-8 buffer, fall back to a
-        // placeholder rather than panicking.
-        unsafe { CStr::from_ptr(msg) }
-            .to_str()
-            .unwrap_or("Unknown error")
-            .to_string()
-    };
-
-    let error = if error_code != 0 {
-        crate::error::SCStreamErrorCode::from_raw(error_code).map_or_else(
-            || SCError::StreamError(format!("{message} (code: {error_code})")),
-            |code| SCError::SCStreamError {
-                code,
-                message: Some(message.clone()),
-            },
-        )
-    } else {
-        SCError::StreamError(message)
-    };
+    // SAFETY: Swift lends a live NSError for the duration of the callback.
+    let error = SCError::from_ns_error(unsafe { NSErrorInfo::from_borrowed(ns_error) });
+    ctx.mark_failed();
+    if error.is_permission_denied() {
+        ctx.notify_permission_revoked(&PermissionRevoked::from_stream_error(error.clone()));
+    }
 
     // Take a read lock and dispatch under it. Multiple delegate callbacks
     // (e.g. error + activity) from independent queues can run concurrently.
@@ -199,6 +624,36 @@ extern "C" fn delegate_error_callback(context: *mut c_void, error_code: i32, msg
     eprintln!("SCStream error: {error}");
 }
 
+// C callback for `outputVideoEffectDidStart/Stop` — dispatches to the delegate.
+//
+// Same safety story as `delegate_error_callback`: user code runs inside
+// `catch_user_panic` under a poison-tolerant read lock.
+extern "C" fn video_effect_callback(context: *mut c_void, started: bool) {
+    if context.is_null() {
+        return;
+    }
+    // SAFETY: `context` is the +1-retained StreamContext pointer the Swift
+    // bridge stored via context_retain_cb; it outlives this callback.
+    let ctx = unsafe { &*(context.cast::<StreamContext>()) };
+    let _scope = CallbackScope::enter(ctx);
+    let delegate = ctx
+        .delegate
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    if let Some(ref delegate) = *delegate {
+        let effect = SCVideoEffect::PresenterOverlay;
+        if started {
+            catch_user_panic("delegate.video_effect_did_start", || {
+                delegate.video_effect_did_start(effect);
+            });
+        } else {
+            catch_user_panic("delegate.video_effect_did_stop", || {
+                delegate.video_effect_did_stop(effect);
+            });
+        }
+    }
+}
+
 // C callback for sample buffers — dispatches to per-stream handlers via context pointer.
 //
 // Safety: this function is called from Swift on a dispatch queue. A Rust
@@ -206,9 +661,10 @@ extern "C" fn delegate_error_callback(context: *mut c_void, error_code: i32, msg
 // `catch_unwind`. The `handlers` lock is a read lock so independent dispatch
 // queues (screen, audio, microphone) can dispatch in parallel — a slow
 // handler on one queue cannot block callbacks on another. The `passRetained`
-// `CMSampleBuffer` reference Swift hands us is consumed exactly once: each
-// non-final matching handler receives a freshly retained clone, and the
-// final matching handler consumes the original.
+// `CMSampleBuffer` reference Swift hands us is consumed exactly once, either
+// by the final matching handler (if it takes ownership) or by dropping it
+// after dispatch. Handlers registered with `SampleDelivery::Borrowed` never
+// cost a retain/release pair.
 extern "C" fn sample_handler(context: *mut c_void, sample_buffer: *const c_void, output_type: i32) {
     if context.is_null() {
         unsafe { crate::cm::ffi::cm_sample_buffer_release(sample_buffer.cast_mut()) };
@@ -217,6 +673,12 @@ extern "C" fn sample_handler(context: *mut c_void, sample_buffer: *const c_void,
     // SAFETY: `context` is the +1-retained StreamContext pointer the Swift
     // bridge stored via context_retain_cb; it outlives this callback.
     let ctx = unsafe { &*(context.cast::<StreamContext>()) };
+    let _scope = CallbackScope::enter(ctx);
+
+    if ctx.withholding_samples.load(Ordering::Acquire) {
+        unsafe { crate::cm::ffi::cm_sample_buffer_release(sample_buffer.cast_mut()) };
+        return;
+    }
 
     let output_type_enum = match output_type {
         0 => SCStreamOutputType::Screen,
@@ -250,29 +712,88 @@ extern "C" fn sample_handler(context: *mut c_void, sample_buffer: *const c_void,
         return;
     }
 
+    // Adopt the `passRetained` reference Swift gave us. Borrowing handlers
+    // are lent this handle; retaining handlers get a `CFRetain`ed clone,
+    // except a retaining handler that runs last, which takes it outright.
+    let mut buffer = Some(unsafe { CMSampleBuffer::from_ptr(sample_buffer.cast_mut()) });
+    if let Some(buffer) = &buffer {
+        crate::instrument::sample_received(buffer, output_type_enum);
+        ctx.record_audio_format(output_type_enum, buffer);
+        ctx.record_content_rect(output_type_enum, buffer);
+    }
+
     while let Some(entry) = matching.next() {
-        // Retain for every handler except the last; the last handler consumes
-        // the original `passRetained` reference Swift gave us. `peek()` after
-        // `next()` reports the next matching entry (or None if `entry` was
-        // the last matching one).
+        // `peek()` after `next()` reports the next matching entry (or None if
+        // `entry` was the last matching one).
         let is_last = matching.peek().is_none();
-        if !is_last {
-            unsafe { crate::cm::ffi::cm_sample_buffer_retain(sample_buffer.cast_mut()) };
+
+        // Wrap user code in catch_unwind so panics never propagate into Swift.
+        // A handler that panics with an owned buffer drops it on unwind,
+        // which releases the retain it was handed, so the accounting holds
+        // either way.
+        let started = crate::instrument::start();
+        match entry.handler.delivery() {
+            SampleDelivery::Borrowed => {
+                let Some(borrowed) = buffer.as_ref() else {
+                    break;
+                };
+                catch_user_panic("output handler", || {
+                    entry
+                        .handler
+                        .did_output_sample_buffer_borrowed(borrowed, output_type_enum);
+                });
+            }
+            SampleDelivery::Retained => {
+                let owned = if is_last {
+                    buffer.take()
+                } else {
+                    buffer.clone()
+                };
+                let Some(owned) = owned else {
+                    break;
+                };
+                catch_user_panic("output handler", || {
+                    entry
+                        .handler
+                        .did_output_sample_buffer(owned, output_type_enum);
+                });
+            }
         }
+        crate::instrument::callback_finished(output_type_enum, started);
+    }
+}
 
-        let buffer = unsafe { crate::cm::CMSampleBuffer::from_ptr(sample_buffer.cast_mut()) };
+// Callback for `sc_stream_copy_active_streams`: adopts the +1 stream and
+// takes a context reference, yielding an `SCStream` like `Clone` would. Runs
+// while Swift holds the stream-state lock, so the context cannot be freed
+// before the retain. A stream whose last handle is shutting down is still
+// registered but is set aside, to be released once Swift lets go of the
+// lock `sc_stream_release` needs.
+#[derive(Default)]
+struct ActiveStreams {
+    streams: Vec<SCStream>,
+    /// +1 stream references that could not become handles.
+    shutting_down: Vec<*const c_void>,
+}
 
-        // Wrap user code in catch_unwind so panics never propagate into Swift.
-        // If the handler panics, `buffer` is dropped on unwind, which calls
-        // `cm_sample_buffer_release` and balances the retain we just did
-        // (or, for the last handler, balances the original `passRetained`).
-        // The retain/release accounting is preserved either way.
-        catch_user_panic("output handler", || {
-            entry
-                .handler
-                .did_output_sample_buffer(buffer, output_type_enum);
-        });
+extern "C" fn collect_active_stream(
+    stream: *const c_void,
+    context: *mut c_void,
+    user: *mut c_void,
+) {
+    let context = context.cast::<StreamContext>();
+    // SAFETY: `user` is the `ActiveStreams` passed by `active_streams`,
+    // which outlives the synchronous enumeration.
+    let found = unsafe { &mut *user.cast::<ActiveStreams>() };
+    if !unsafe { &*context }.handle_shared() {
+        found.shutting_down.push(stream);
+        return;
     }
+    unsafe { StreamContext::retain(context) };
+    found.streams.push(SCStream {
+        ptr: stream,
+        context,
+    });
 }
 
 /// `SCStream` is a lightweight wrapper around the Swift `SCStream` instance.
@@ -292,8 +813,7 @@ extern "C" fn sample_handler(context: *mut c_void, sample_buffer: *const c_void,
 /// let display = &content.displays()[0];
 ///
 /// // Create filter and configuration
-/// let filter = SCContentFilter::create()
-///     .with_display(display)
+/// let filter = SCContentFilter::for_display(display)
 ///     .with_excluding_windows(&[])
 ///     .build();
 /// let config = SCStreamConfiguration::new()
@@ -310,6 +830,29 @@ extern "C" fn sample_handler(context: *mut c_void, sample_buffer: *const c_void,
 /// # Ok(())
 /// # }
 /// ```
+///
+/// # Dropping
+///
+/// Clones share one capture session. Dropping the last handle stops a
+/// running capture, waiting up to [`drop_timeout`](Self::drop_timeout) for
+/// `ScreenCaptureKit` to confirm, then detaches the output handlers and the
+/// delegate. Callbacks already running on other queues finish first, and
+/// once `drop` returns no handler or delegate method of this stream is
+/// called again, even if frames were still in flight.
+///
+/// "Last handle" counts every clone, including the ones held by helpers
+/// running in the background, such as a
+/// [`DisplaySleepWatcher`](crate::stream::display_sleep::DisplaySleepWatcher)
+/// or a [`PermissionWatcher`](crate::stream::permission_watcher::PermissionWatcher).
+/// While one of those is alive, dropping your own handles neither stops
+/// capture nor detaches anything; stop or drop the helpers first, or call
+/// [`stop_capture`](Self::stop_capture) explicitly.
+///
+/// The guarantee doesn't hold when the last handle is dropped from inside
+/// one of the stream's own callbacks, e.g. a handle obtained with
+/// [`StreamRef::upgrade`]. Capture is still stopped, but the handlers stay
+/// attached until Swift releases them, so frames already in flight may
+/// still reach them after `drop` returns.
 pub struct SCStream {
     ptr: *const c_void,
     /// Per-stream context holding handlers and delegate (ref-counted).
@@ -330,8 +873,7 @@ impl SCStream {
     /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let content = SCShareableContent::get()?;
     /// let display = &content.displays()[0];
-    /// let filter = SCContentFilter::create()
-    ///     .with_display(display)
+    /// let filter = SCContentFilter::for_display(display)
     ///     .with_excluding_windows(&[])
     ///     .build();
     /// let config = SCStreamConfiguration::new()
@@ -345,6 +887,9 @@ impl SCStream {
     pub fn new(filter: &SCContentFilter, configuration: &SCStreamConfiguration) -> Self {
         let context = StreamContext::new();
         let context_ptr = context.cast::<c_void>();
+        // Hand Swift its own copy: a clone of `configuration` mutated on
+        // another thread must not race the stream reading it.
+        let configuration = configuration.deep_copy();
 
         let ptr = unsafe {
             ffi::sc_stream_create(
@@ -358,7 +903,14 @@ impl SCStream {
             )
         };
 
-        Self { ptr, context }
+        if !ptr.is_null() {
+            unsafe { ffi::sc_stream_set_video_effect_callback(ptr, video_effect_callback) };
+        }
+        unsafe { &*context }.handle_acquired(ptr);
+        let stream = Self { ptr, context };
+        stream.store_configuration(&configuration);
+        stream.store_filter(filter);
+        stream
     }
 
     /// Create a new stream with a content filter, configuration, and delegate
@@ -379,8 +931,7 @@ impl SCStream {
     /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let content = SCShareableContent::get()?;
     /// let display = &content.displays()[0];
-    /// let filter = SCContentFilter::create()
-    ///     .with_display(display)
+    /// let filter = SCContentFilter::for_display(display)
     ///     .with_excluding_windows(&[])
     ///     .build();
     /// let config = SCStreamConfiguration::new()
@@ -402,6 +953,7 @@ impl SCStream {
     ) -> Self {
         let context = StreamContext::new_with_delegate(Box::new(delegate));
         let context_ptr = context.cast::<c_void>();
+        let configuration = configuration.deep_copy();
 
         let ptr = unsafe {
             ffi::sc_stream_create(
@@ -415,7 +967,14 @@ impl SCStream {
             )
         };
 
-        Self { ptr, context }
+        if !ptr.is_null() {
+            unsafe { ffi::sc_stream_set_video_effect_callback(ptr, video_effect_callback) };
+        }
+        unsafe { &*context }.handle_acquired(ptr);
+        let stream = Self { ptr, context };
+        stream.store_configuration(&configuration);
+        stream.store_filter(filter);
+        stream
     }
 
     /// Add an output handler to receive captured frames
@@ -462,7 +1021,7 @@ impl SCStream {
     /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// # let content = SCShareableContent::get()?;
     /// # let display = &content.displays()[0];
-    /// # let filter = SCContentFilter::create().with_display(display).with_excluding_windows(&[]).build();
+    /// # let filter = SCContentFilter::for_display(display).with_excluding_windows(&[]).build();
     /// # let config = SCStreamConfiguration::default();
     /// let mut stream = SCStream::new(&filter, &config);
     /// stream.add_output_handler(MyHandler, SCStreamOutputType::Screen);
@@ -477,7 +1036,7 @@ impl SCStream {
     /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// # let content = SCShareableContent::get()?;
     /// # let display = &content.displays()[0];
-    /// # let filter = SCContentFilter::create().with_display(display).with_excluding_windows(&[]).build();
+    /// # let filter = SCContentFilter::for_display(display).with_excluding_windows(&[]).build();
     /// # let config = SCStreamConfiguration::default();
     /// let mut stream = SCStream::new(&filter, &config);
     /// stream.add_output_handler(
@@ -505,7 +1064,7 @@ impl SCStream {
     /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// # let content = SCShareableContent::get()?;
     /// # let display = &content.displays()[0];
-    /// # let filter = SCContentFilter::create().with_display(display).with_excluding_windows(&[]).build();
+    /// # let filter = SCContentFilter::for_display(display).with_excluding_windows(&[]).build();
     /// # let config = SCStreamConfiguration::default();
     /// let frame_count = Arc::new(AtomicUsize::new(0));
     /// let count_handler = frame_count.clone();
@@ -548,7 +1107,7 @@ impl SCStream {
     /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// # let content = SCShareableContent::get()?;
     /// # let display = &content.displays()[0];
-    /// # let filter = SCContentFilter::create().with_display(display).with_excluding_windows(&[]).build();
+    /// # let filter = SCContentFilter::for_display(display).with_excluding_windows(&[]).build();
     /// # let config = SCStreamConfiguration::default();
     /// let mut stream = SCStream::new(&filter, &config);
     /// let queue = DispatchQueue::new("com.myapp.capture", DispatchQoS::UserInteractive);
@@ -568,7 +1127,56 @@ impl SCStream {
         queue: Option<&DispatchQueue>,
     ) -> Option<usize> {
         let handler_id = NEXT_HANDLER_ID.fetch_add(1, Ordering::Relaxed);
+        let type_name = std::any::type_name_of_val(&handler);
+        self.register_output_handler(handler_id, type_name, Box::new(handler), of_type, queue)
+    }
+
+    /// Add an output handler that is also told which stream delivered each
+    /// sample and its own handler ID
+    ///
+    /// Use this in apps that run several streams through shared handler
+    /// code, or when a handler needs to act on its stream, e.g. lower the
+    /// frame rate under load via [`StreamRef::upgrade`]. Otherwise it
+    /// behaves like [`add_output_handler`](Self::add_output_handler).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use screencapturekit::prelude::*;
+    /// use screencapturekit::stream::sc_stream::StreamRef;
+    ///
+    /// # fn example(mut stream: SCStream) {
+    /// stream.add_output_handler_with_context(
+    ///     |_sample: CMSampleBuffer, _of_type: SCStreamOutputType, stream: &StreamRef<'_>, handler_id: usize| {
+    ///         println!("frame from stream {} (handler {handler_id})", stream.id());
+    ///     },
+    ///     SCStreamOutputType::Screen,
+    /// );
+    /// # }
+    /// ```
+    pub fn add_output_handler_with_context(
+        &mut self,
+        handler: impl ContextOutputTrait + 'static,
+        of_type: SCStreamOutputType,
+    ) -> Option<usize> {
+        let handler_id = NEXT_HANDLER_ID.fetch_add(1, Ordering::Relaxed);
+        let type_name = std::any::type_name_of_val(&handler);
+        let handler = WithStreamContext {
+            handler,
+            context: self.context,
+            handler_id,
+        };
+        self.register_output_handler(handler_id, type_name, Box::new(handler), of_type, None)
+    }
 
+    fn register_output_handler(
+        &mut self,
+        handler_id: usize,
+        type_name: &'static str,
+        handler: Box<dyn SCStreamOutputTrait>,
+        of_type: SCStreamOutputType,
+        queue: Option<&DispatchQueue>,
+    ) -> Option<usize> {
         // Convert output type to int for Swift
         let output_type_int = match of_type {
             SCStreamOutputType::Screen => 0,
@@ -595,7 +1203,8 @@ impl SCStream {
                 .push(HandlerEntry {
                     id: handler_id,
                     of_type,
-                    handler: Box::new(handler),
+                    type_name,
+                    handler,
                 });
             Some(handler_id)
         } else {
@@ -611,68 +1220,770 @@ impl SCStream {
         }
     }
 
-    /// Remove an output handler
+    /// Add an output handler whose work runs on a pool of `workers` threads
     ///
-    /// # Arguments
+    /// The capture callback only enqueues the (retained) sample buffer and
+    /// returns, so CPU-heavy per-frame processing doesn't stall
+    /// `ScreenCaptureKit`'s delivery queue. The queue is bounded and drops
+    /// the oldest waiting sample when full; with several workers samples can
+    /// complete out of order. See [`PooledOutputHandler`] for details.
     ///
-    /// * `id` - The handler ID returned from [`add_output_handler`](Self::add_output_handler)
-    /// * `of_type` - The type of output the handler was registered for
+    /// Removing the handler with
+    /// [`remove_output_handler`](Self::remove_output_handler) waits for the
+    /// workers to finish the samples already queued.
     ///
     /// # Returns
     ///
-    /// Returns `true` if the handler was found and removed, `false` otherwise.
-    pub fn remove_output_handler(&mut self, id: usize, of_type: SCStreamOutputType) -> bool {
-        // SAFETY: self.context is the Box::into_raw StreamContext created in
-        // SCStream::new; it stays valid for the lifetime of self.
-        let mut handlers = unsafe { &*self.context }
-            .handlers
-            .write()
-            .unwrap_or_else(std::sync::PoisonError::into_inner);
-        let Some(pos) = handlers.iter().position(|e| e.id == id) else {
-            return false;
-        };
-        handlers.remove(pos);
-
-        // If no more handlers for this output type, tell Swift to remove the output
-        let has_type = handlers.iter().any(|e| e.of_type == of_type);
-        drop(handlers);
-
-        if !has_type {
-            let output_type_int = match of_type {
-                SCStreamOutputType::Screen => 0,
-                SCStreamOutputType::Audio => 1,
-                SCStreamOutputType::Microphone => 2,
-            };
-            unsafe { ffi::sc_stream_remove_stream_output(self.ptr, output_type_int) };
-        }
-
-        true
-    }
-
-    /// Start capturing screen content
+    /// Same as [`add_output_handler`](Self::add_output_handler), and also
+    /// `None` if a worker thread can't be spawned.
     ///
-    /// This method blocks until the capture operation completes or fails.
+    /// # Examples
     ///
-    /// # Errors
+    /// ```rust,no_run
+    /// use screencapturekit::prelude::*;
     ///
-    /// Returns `SCError::CaptureStartFailed` if the capture fails to start.
-    pub fn start_capture(&self) -> Result<(), SCError> {
-        let (completion, context) = UnitCompletion::new();
-        unsafe { ffi::sc_stream_start_capture(self.ptr, context, UnitCompletion::callback) };
-        completion.wait().map_err(SCError::CaptureStartFailed)
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let content = SCShareableContent::get()?;
+    /// # let display = &content.displays()[0];
+    /// # let filter = SCContentFilter::for_display(display).with_excluding_windows(&[]).build();
+    /// # let config = SCStreamConfiguration::default();
+    /// let mut stream = SCStream::new(&filter, &config);
+    /// stream.add_output_handler_pooled(
+    ///     |_sample, _type| { /* expensive analysis */ },
+    ///     SCStreamOutputType::Screen,
+    ///     4,
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn add_output_handler_pooled(
+        &mut self,
+        handler: impl SCStreamOutputTrait + 'static,
+        of_type: SCStreamOutputType,
+        workers: usize,
+    ) -> Option<usize> {
+        let pooled = PooledOutputHandler::new(handler, workers).ok()?;
+        self.add_output_handler(pooled, of_type)
     }
 
-    /// Stop capturing screen content
+    /// Add an output handler that runs on `thread` instead of
+    /// `ScreenCaptureKit`'s dispatch queue
+    ///
+    /// The callback only retains the sample and hands it over, so almost
+    /// no time is spent inside it. An [`HandlerThread::Executor`] handler
+    /// holds at most the configured queue depth less one samples at once
+    /// and drops the rest; see [`ExecutorOutputHandler`]. The other modes
+    /// are described on [`HandlerThread`].
+    ///
+    /// # Returns
+    ///
+    /// Same as [`add_output_handler`](Self::add_output_handler), and also
+    /// `None` if a [`Dedicated`](HandlerThread::Dedicated) or
+    /// [`Pool`](HandlerThread::Pool) thread can't be spawned.
+    pub fn add_output_handler_on(
+        &mut self,
+        handler: impl SCStreamOutputTrait + 'static,
+        of_type: SCStreamOutputType,
+        thread: HandlerThread,
+    ) -> Option<usize> {
+        match thread {
+            HandlerThread::Callback => self.add_output_handler(handler, of_type),
+            HandlerThread::Dedicated => self.add_output_handler_pooled(handler, of_type, 1),
+            HandlerThread::Pool(workers) => {
+                self.add_output_handler_pooled(handler, of_type, workers)
+            }
+            HandlerThread::Executor(executor) => {
+                let budget = (self.configuration().queue_depth() as usize).saturating_sub(1);
+                self.add_output_handler(
+                    ExecutorOutputHandler::new(handler, executor, budget),
+                    of_type,
+                )
+            }
+        }
+    }
+
+    /// Add a recording handler and a preview handler that share each sample
+    ///
+    /// Both receive a retained handle to the same buffer, each on its own
+    /// thread. The preview sees only the newest frame and is skipped while
+    /// the samples held would exceed the configured queue depth less one,
+    /// so the recording never waits for a surface. See [`TeeOutputHandler`]
+    /// for details.
+    ///
+    /// # Returns
+    ///
+    /// Same as [`add_output_handler`](Self::add_output_handler), and also
+    /// `None` if a branch thread can't be spawned.
+    pub fn add_output_handler_tee(
+        &mut self,
+        recording: impl SCStreamOutputTrait + 'static,
+        preview: impl SCStreamOutputTrait + 'static,
+        of_type: SCStreamOutputType,
+    ) -> Option<usize> {
+        let budget = (self.configuration().queue_depth() as usize).saturating_sub(1);
+        let tee = TeeOutputHandler::new(recording, preview, budget).ok()?;
+        self.add_output_handler(tee, of_type)
+    }
+
+    /// Add an output handler that only sees frames when the screen changed
+    ///
+    /// Idle frames, and complete frames whose dirty area since the last
+    /// forwarded frame is below `options`' threshold, are dropped before
+    /// reaching `handler`. A keepalive interval forces a frame through
+    /// periodically. See [`EventDrivenOutputHandler`] for details.
+    ///
+    /// # Returns
+    ///
+    /// Same as [`add_output_handler`](Self::add_output_handler).
+    pub fn add_output_handler_event_driven(
+        &mut self,
+        handler: impl SCStreamOutputTrait + 'static,
+        of_type: SCStreamOutputType,
+        options: EventDrivenOptions,
+    ) -> Option<usize> {
+        self.add_output_handler(EventDrivenOutputHandler::new(handler, options), of_type)
+    }
+
+    /// Add a screen output handler that receives the cursor's position and
+    /// shape next to every frame
+    ///
+    /// The stream's configuration is updated to leave the cursor out of the
+    /// frames, so viewers can draw it on top at their own scale. See
+    /// [`CursorOutputHandler`] for details.
+    ///
+    /// # Returns
+    ///
+    /// Same as [`add_output_handler`](Self::add_output_handler); also
+    /// `None` if the configuration could not be updated.
+    pub fn add_output_handler_with_cursor<F>(&mut self, handler: F) -> Option<usize>
+    where
+        F: Fn(CMSampleBuffer, &CursorUpdate) + Send + Sync + 'static,
+    {
+        let mut config = self.configuration();
+        if config.shows_cursor() {
+            config.set_shows_cursor(false);
+            self.update_configuration(&config).ok()?;
+        }
+        self.add_output_handler(
+            CursorOutputHandler::new(handler),
+            SCStreamOutputType::Screen,
+        )
+    }
+
+    /// Deliver samples of `of_type` into a lock-free ring of at least
+    /// `capacity` samples, for one consumer thread to pop
+    ///
+    /// The callback only stores the retained sample and returns; it never
+    /// takes a lock or waits for the consumer. When the ring is full the
+    /// incoming sample is dropped. See [`FrameRing`] for details.
+    ///
+    /// # Returns
+    ///
+    /// The consumer end, or `None` if
+    /// [`add_output_handler`](Self::add_output_handler) would have failed.
+    /// The ring reports [`is_closed`](FrameRing::is_closed) once the stream
+    /// is dropped.
+    pub fn ring_output(
+        &mut self,
+        of_type: SCStreamOutputType,
+        capacity: usize,
+    ) -> Option<FrameRing> {
+        let (producer, ring) = FrameRing::new(capacity);
+        self.add_output_handler(producer.into_output(), of_type)?;
+        Some(ring)
+    }
+
+    /// Add an output handler that borrows each sample instead of owning it
+    ///
+    /// The handler is lent the stream's own reference for the duration of
+    /// the callback, so delivery costs no `CFRetain`/`CFRelease` pair. Use
+    /// it for in-place work at high frame rates (reading pixels, computing a
+    /// histogram, uploading to the GPU). The borrow cannot escape the
+    /// closure; call [`Clone::clone`] or
+    /// [`make_owned`](crate::cm::CMSampleBufferRetainExt::make_owned) to keep
+    /// a particular sample. See [`SampleDelivery`] for how the modes mix.
+    ///
+    /// # Returns
+    ///
+    /// Same as [`add_output_handler`](Self::add_output_handler).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use screencapturekit::prelude::*;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let content = SCShareableContent::get()?;
+    /// # let display = &content.displays()[0];
+    /// # let filter = SCContentFilter::for_display(display).with_excluding_windows(&[]).build();
+    /// # let config = SCStreamConfiguration::default();
+    /// let mut stream = SCStream::new(&filter, &config);
+    /// stream.add_output_handler_borrowed(
+    ///     |sample: &CMSampleBuffer, _type| {
+    ///         if let Some(pixels) = sample.image_buffer() {
+    ///             let _ = pixels.width();
+    ///         }
+    ///     },
+    ///     SCStreamOutputType::Screen,
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn add_output_handler_borrowed<F>(
+        &mut self,
+        handler: F,
+        of_type: SCStreamOutputType,
+    ) -> Option<usize>
+    where
+        F: Fn(&CMSampleBuffer, SCStreamOutputType) + Send + Sync + 'static,
+    {
+        self.add_output_handler(BorrowedOutputHandler::new(handler), of_type)
+    }
+
+    /// Remove an output handler
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The handler ID returned from [`add_output_handler`](Self::add_output_handler)
+    /// * `of_type` - The type of output the handler was registered for
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` if the handler was found and removed, `false` otherwise.
+    pub fn remove_output_handler(&mut self, id: usize, of_type: SCStreamOutputType) -> bool {
+        // SAFETY: self.context is the Box::into_raw StreamContext created in
+        // SCStream::new; it stays valid for the lifetime of self.
+        let mut handlers = unsafe { &*self.context }
+            .handlers
+            .write()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let Some(pos) = handlers.iter().position(|e| e.id == id) else {
+            return false;
+        };
+        handlers.remove(pos);
+
+        // If no more handlers for this output type, tell Swift to remove the output
+        let has_type = handlers.iter().any(|e| e.of_type == of_type);
+        drop(handlers);
+
+        if !has_type {
+            let output_type_int = match of_type {
+                SCStreamOutputType::Screen => 0,
+                SCStreamOutputType::Audio => 1,
+                SCStreamOutputType::Microphone => 2,
+            };
+            unsafe { ffi::sc_stream_remove_stream_output(self.ptr, output_type_int) };
+        }
+
+        true
+    }
+
+    /// List the output handlers attached to this stream, in the order they
+    /// were added
+    ///
+    /// Useful for debugging what a large app has wired up, and for tests
+    /// that assert the expected handlers are attached. Every clone of the
+    /// stream reports the same handlers.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use screencapturekit::prelude::*;
+    ///
+    /// # fn example(mut stream: SCStream) {
+    /// stream.add_output_handler(|_sample, _type| {}, SCStreamOutputType::Screen);
+    /// for output in stream.outputs() {
+    ///     println!(
+    ///         "#{} {:?} {} on {:?}",
+    ///         output.id(),
+    ///         output.of_type(),
+    ///         output.handler_type(),
+    ///         output.queue_label(),
+    ///     );
+    /// }
+    /// # }
+    /// ```
+    pub fn outputs(&self) -> Vec<OutputHandlerInfo> {
+        // SAFETY: self.context is the live StreamContext owned by this stream.
+        let handlers = unsafe { &*self.context }
+            .handlers
+            .read()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        handlers
+            .iter()
+            .map(|entry| OutputHandlerInfo {
+                id: entry.id,
+                of_type: entry.of_type,
+                handler_type: entry.type_name,
+                queue_label: self.output_queue_label(entry.of_type),
+            })
+            .collect()
+    }
+
+    /// Label of the dispatch queue delivering samples of `of_type`, or
+    /// `None` if no handler is attached for that type
+    ///
+    /// All handlers for one output type share a queue: the one passed with
+    /// the first handler added for it, the one set with
+    /// [`set_output_queue`](Self::set_output_queue), or a dedicated
+    /// `com.screencapturekit.output.<n>` queue otherwise.
+    pub fn output_queue_label(&self, of_type: SCStreamOutputType) -> Option<String> {
+        let output_type_int = match of_type {
+            SCStreamOutputType::Screen => 0,
+            SCStreamOutputType::Audio => 1,
+            SCStreamOutputType::Microphone => 2,
+        };
+        unsafe {
+            crate::utils::ffi_string::ffi_string_from_buffer(
+                crate::utils::ffi_string::SMALL_BUFFER_SIZE,
+                |buf, len| {
+                    ffi::sc_stream_get_output_queue_label(self.ptr, output_type_int, buf, len)
+                },
+            )
+        }
+    }
+
+    /// Deliver samples of `of_type` on `queue`
+    ///
+    /// Each output type is delivered on its own queue, but by default they
+    /// all run at user-interactive `QoS`, and otherwise only the first handler
+    /// added for a type picks its queue. Use this to isolate workloads, e.g.
+    /// run heavy video processing on a utility queue so it never competes
+    /// with audio delivery.
+    ///
+    /// If `of_type` already has handlers, its output is moved to `queue`
+    /// right away, which may drop a sample while capturing. Otherwise the
+    /// queue is used when the next handler for `of_type` is added without
+    /// one of its own.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InternalError`] if `ScreenCaptureKit` refuses to
+    /// move the output. Its handlers then receive nothing until another
+    /// handler for `of_type` is added.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use screencapturekit::dispatch_queue::{DispatchQoS, DispatchQueue};
+    /// use screencapturekit::prelude::*;
+    ///
+    /// # fn example(mut stream: SCStream) -> Result<(), SCError> {
+    /// let video = DispatchQueue::new("com.myapp.video", DispatchQoS::Utility);
+    /// stream.set_output_queue(SCStreamOutputType::Screen, &video)?;
+    /// stream.add_output_handler(|_, _| { /* encode */ }, SCStreamOutputType::Screen);
+    /// stream.add_output_handler(|_, _| { /* mix */ }, SCStreamOutputType::Audio);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_output_queue(
+        &mut self,
+        of_type: SCStreamOutputType,
+        queue: &DispatchQueue,
+    ) -> Result<(), SCError> {
+        let output_type_int = match of_type {
+            SCStreamOutputType::Screen => 0,
+            SCStreamOutputType::Audio => 1,
+            SCStreamOutputType::Microphone => 2,
+        };
+        if unsafe { ffi::sc_stream_set_output_queue(self.ptr, output_type_int, queue.as_ptr()) } {
+            Ok(())
+        } else {
+            Err(SCError::internal_error(format!(
+                "failed to move {of_type:?} output to a new dispatch queue"
+            )))
+        }
+    }
+
+    /// Deliver samples of `of_type` on a dedicated
+    /// `com.screencapturekit.output.<n>` queue with quality of service `qos`
+    ///
+    /// Shorthand for [`set_output_queue`](Self::set_output_queue) when only
+    /// the priority matters.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`set_output_queue`](Self::set_output_queue).
+    pub fn set_output_qos(
+        &mut self,
+        of_type: SCStreamOutputType,
+        qos: DispatchQoS,
+    ) -> Result<(), SCError> {
+        let index = match of_type {
+            SCStreamOutputType::Screen => 0,
+            SCStreamOutputType::Audio => 1,
+            SCStreamOutputType::Microphone => 2,
+        };
+        let queue = DispatchQueue::new(&format!("com.screencapturekit.output.{index}"), qos);
+        self.set_output_queue(of_type, &queue)
+    }
+
+    /// Start capturing screen content
     ///
     /// This method blocks until the capture operation completes or fails.
     ///
     /// # Errors
     ///
-    /// Returns `SCError::CaptureStopFailed` if the capture fails to stop.
+    /// Returns [`SCError::SCStreamError`] if `ScreenCaptureKit` refuses to
+    /// start (e.g. [`UserDeclined`](crate::error::SCStreamErrorCode::UserDeclined)),
+    /// [`SCError::NSError`] for failures from other domains, or
+    /// `SCError::CaptureStartFailed` if the bridge never reports back.
+    /// Returns [`SCError::InvalidState`] without contacting
+    /// `ScreenCaptureKit` if the stream is already starting, running or
+    /// stopping.
+    pub fn start_capture(&self) -> Result<(), SCError> {
+        let starting = self.begin_start()?;
+        let (completion, context) = SyncCompletion::<Result<(), SCError>>::new();
+        unsafe { ffi::sc_stream_start_capture(self.ptr, context, stream_control_callback) };
+        let result = completion
+            .wait()
+            .map_err(SCError::CaptureStartFailed)
+            .and_then(|r| r);
+        self.finish_start(starting, result.is_ok());
+        result
+    }
+
+    /// Start capturing, giving up after `timeout`
+    ///
+    /// [`start_capture`](Self::start_capture) waits as long as
+    /// `ScreenCaptureKit` takes to answer, which can be forever when a
+    /// permission prompt is pending or `WindowServer` is unresponsive. This
+    /// variant returns instead, with a snapshot of the state that usually
+    /// explains the hang.
+    ///
+    /// If the start completes after the timeout, the stream captures anyway;
+    /// call [`stop_capture`](Self::stop_capture) if that is not wanted.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::CaptureStartTimeout`] when no answer arrives in
+    /// time, otherwise the same errors as [`start_capture`](Self::start_capture).
+    /// A timed-out stream is left [`Failed`](SCStreamState::Failed), even if
+    /// the start later succeeds.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use screencapturekit::prelude::*;
+    ///
+    /// # fn example(stream: &SCStream) {
+    /// match stream.start_capture_with_timeout(Duration::from_secs(5)) {
+    ///     Ok(()) => println!("capturing"),
+    ///     Err(SCError::CaptureStartTimeout(diagnostics)) => {
+    ///         if !diagnostics.screen_capture_permitted {
+    ///             eprintln!("grant Screen Recording permission and retry");
+    ///         }
+    ///         eprintln!("{diagnostics}");
+    ///     }
+    ///     Err(e) => eprintln!("failed to start: {e}"),
+    /// }
+    /// # }
+    /// ```
+    pub fn start_capture_with_timeout(&self, timeout: Duration) -> Result<(), SCError> {
+        let starting = self.begin_start()?;
+        let completion = Arc::new(TimedCompletion::default());
+        // The callback owns this reference. If Swift never calls back it
+        // leaks, which is the price of not blocking forever.
+        let context = Arc::into_raw(Arc::clone(&completion))
+            .cast_mut()
+            .cast::<c_void>();
+        unsafe { ffi::sc_stream_start_capture(self.ptr, context, timed_control_callback) };
+        let result = completion.wait(timeout).unwrap_or_else(|| {
+            Err(SCError::CaptureStartTimeout(
+                self.start_diagnostics(timeout),
+            ))
+        });
+        self.finish_start(starting, result.is_ok());
+        result
+    }
+
+    fn start_diagnostics(&self, timeout: Duration) -> CaptureStartDiagnostics {
+        // SAFETY: self.context is the live StreamContext owned by this stream.
+        let ctx = unsafe { &*self.context };
+        let mut diagnostics = CaptureStartDiagnostics::new(timeout);
+        diagnostics.screen_capture_permitted = unsafe { ffi::CGPreflightScreenCaptureAccess() };
+        diagnostics.filter = ctx
+            .filter
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .as_ref()
+            .map(SCContentFilter::summary)
+            .unwrap_or_default();
+        diagnostics.output_handlers = ctx
+            .handlers
+            .read()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .len();
+        let capturing = unsafe { ffi::sc_stream_get_active_count() };
+        diagnostics.capturing_streams = usize::try_from(capturing)
+            .unwrap_or(0)
+            .saturating_sub(usize::from(self.is_capturing()));
+        diagnostics
+    }
+
+    /// Stop capturing screen content
+    ///
+    /// This method blocks until the capture operation completes or fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::SCStreamError`] or [`SCError::NSError`] with the
+    /// framework's error if the capture fails to stop, or
+    /// `SCError::CaptureStopFailed` if the bridge never reports back.
+    /// Returns [`SCError::InvalidState`] without contacting
+    /// `ScreenCaptureKit` if the stream is not running.
     pub fn stop_capture(&self) -> Result<(), SCError> {
-        let (completion, context) = UnitCompletion::new();
-        unsafe { ffi::sc_stream_stop_capture(self.ptr, context, UnitCompletion::callback) };
-        completion.wait().map_err(SCError::CaptureStopFailed)
+        let stopping = self.begin_stop()?;
+        let (completion, context) = SyncCompletion::<Result<(), SCError>>::new();
+        unsafe { ffi::sc_stream_stop_capture(self.ptr, context, stream_control_callback) };
+        let result = completion
+            .wait()
+            .map_err(SCError::CaptureStopFailed)
+            .and_then(|r| r);
+        self.finish_stop(stopping, result.is_ok());
+        result
+    }
+
+    /// Default for [`drop_timeout`](Self::drop_timeout)
+    pub const DEFAULT_DROP_TIMEOUT: Duration = Duration::from_secs(2);
+
+    /// How long dropping the last handle waits for a running capture to stop
+    ///
+    /// Shared by every clone of the stream. See [Dropping](Self#dropping).
+    pub fn drop_timeout(&self) -> Duration {
+        // SAFETY: self.context is the live StreamContext owned by this stream.
+        *unsafe { &*self.context }
+            .drop_timeout
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Change how long dropping the last handle waits for a running capture
+    /// to stop
+    ///
+    /// The handlers are detached when the wait ends either way, so a short
+    /// timeout only risks `ScreenCaptureKit` capturing for a moment longer
+    /// with nobody listening. `Duration::ZERO` requests the stop without
+    /// waiting for it.
+    pub fn set_drop_timeout(&self, timeout: Duration) {
+        // SAFETY: self.context is the live StreamContext owned by this stream.
+        *unsafe { &*self.context }
+            .drop_timeout
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner) = timeout;
+    }
+
+    /// Stop a running capture and detach the handlers, as the last handle
+    /// goes away. `self.ptr` is still retained by this handle.
+    fn shut_down(&self) {
+        if !self.ptr.is_null() {
+            if let Ok(stopping) = self.begin_stop() {
+                let completion = Arc::new(TimedCompletion::default());
+                // The callback owns this reference, so a stop confirmed after
+                // the timeout lands in a slot nobody reads.
+                let context = Arc::into_raw(Arc::clone(&completion))
+                    .cast_mut()
+                    .cast::<c_void>();
+                unsafe { ffi::sc_stream_stop_capture(self.ptr, context, timed_control_callback) };
+                let stopped = matches!(completion.wait(self.drop_timeout()), Some(Ok(())));
+                self.finish_stop(stopping, stopped);
+            }
+        }
+        // SAFETY: self.context is the live StreamContext owned by this stream.
+        unsafe { &*self.context }.drain();
+    }
+
+    /// Process-unique identifier of the stream
+    ///
+    /// The same for every clone of a stream, and matches
+    /// [`StreamRef::id`] in handlers registered with
+    /// [`add_output_handler_with_context`](Self::add_output_handler_with_context).
+    pub fn id(&self) -> u64 {
+        // SAFETY: self.context is the live StreamContext owned by this stream.
+        unsafe { &*self.context }.id
+    }
+
+    /// Where the stream is in its lifecycle
+    ///
+    /// Shared by every clone of the stream. See [`stream::state`](crate::stream::state)
+    /// for the transitions.
+    pub fn state(&self) -> SCStreamState {
+        // SAFETY: self.context is the live StreamContext owned by this stream.
+        unsafe { &*self.context }.state()
+    }
+
+    pub(crate) fn begin_start(&self) -> Result<SCStreamState, SCError> {
+        // SAFETY: self.context is the live StreamContext owned by this stream.
+        let ctx = unsafe { &*self.context };
+        let starting = ctx.begin_transition("start capture", |state| {
+            state.can_start().then_some(SCStreamState::Starting)
+        })?;
+        ctx.clear_audio_formats();
+        *ctx.content_rect
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner) = None;
+        Ok(starting)
+    }
+
+    /// The format `of_type`'s audio samples actually arrive in
+    ///
+    /// Read from the latest sample delivered to an output handler of that
+    /// type, so it is `None` until one arrives after
+    /// [`start_capture`](Self::start_capture), and always `None` for
+    /// [`SCStreamOutputType::Screen`]. System audio and the microphone can
+    /// differ from each other and from the configured
+    /// [`sample_rate`](SCStreamConfiguration::sample_rate) and
+    /// [`channel_count`](SCStreamConfiguration::channel_count); see
+    /// [`configuration::audio`](crate::stream::configuration::audio#system-audio-and-microphone-formats).
+    pub fn audio_format(&self, of_type: SCStreamOutputType) -> Option<AudioFormat> {
+        let slot = audio_format_slot(of_type)?;
+        // SAFETY: self.context is the live StreamContext owned by this stream.
+        unsafe { &*self.context }
+            .audio_formats
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)[slot]
+            .as_ref()
+            .map(|(format, _)| *format)
+    }
+
+    /// Where the content sits in the latest complete screen frame
+    ///
+    /// `None` until such a frame reaches a screen output handler after
+    /// [`start_capture`](Self::start_capture). Changes are also delivered to
+    /// the delegate's
+    /// [`content_rect_did_change`](SCStreamDelegateTrait::content_rect_did_change).
+    ///
+    /// Frames are only inspected for streams created with a delegate, or
+    /// once this method has been called: on a stream without a delegate the
+    /// first call returns `None` and later calls see the following frames.
+    pub fn content_rect(&self) -> Option<ContentRectChanged> {
+        // SAFETY: self.context is the live StreamContext owned by this stream.
+        let ctx = unsafe { &*self.context };
+        ctx.tracks_content_rect.store(true, Ordering::Relaxed);
+        *ctx.content_rect
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    pub(crate) fn finish_start(&self, starting: SCStreamState, succeeded: bool) {
+        let next = if succeeded {
+            SCStreamState::Running
+        } else {
+            SCStreamState::Failed
+        };
+        // SAFETY: self.context is the live StreamContext owned by this stream.
+        let ctx = unsafe { &*self.context };
+        if succeeded {
+            // A stream that starts has permission; report the next loss.
+            ctx.permission_revoked.store(false, Ordering::Release);
+        }
+        ctx.finish_transition(starting, next);
+    }
+
+    pub(crate) fn begin_stop(&self) -> Result<SCStreamState, SCError> {
+        // SAFETY: self.context is the live StreamContext owned by this stream.
+        unsafe { &*self.context }.begin_transition("stop capture", |state| {
+            state.can_stop().then_some(SCStreamState::Stopping)
+        })
+    }
+
+    pub(crate) fn finish_stop(&self, stopping: SCStreamState, succeeded: bool) {
+        let next = if succeeded {
+            SCStreamState::Stopped
+        } else {
+            SCStreamState::Failed
+        };
+        // SAFETY: self.context is the live StreamContext owned by this stream.
+        unsafe { &*self.context }.finish_transition(stopping, next);
+    }
+
+    /// Enter `Updating` if the stream is running. Idle and stopped streams
+    /// can be reconfigured without a state change.
+    pub(crate) fn begin_update(&self, operation: &str) -> Result<SCStreamState, SCError> {
+        // SAFETY: self.context is the live StreamContext owned by this stream.
+        unsafe { &*self.context }.begin_transition(operation, |state| {
+            state.can_update().then_some(match state {
+                SCStreamState::Running => SCStreamState::Updating,
+                other => other,
+            })
+        })
+    }
+
+    /// An update never stops the stream, so it returns to `Running` whether
+    /// or not it succeeded.
+    pub(crate) fn finish_update(&self, updating: SCStreamState) {
+        if updating == SCStreamState::Updating {
+            // SAFETY: self.context is the live StreamContext owned by this stream.
+            unsafe { &*self.context }.finish_transition(updating, SCStreamState::Running);
+        }
+    }
+
+    /// Whether the stream is currently capturing
+    ///
+    /// `true` from a successful [`start_capture`](Self::start_capture) until
+    /// [`stop_capture`](Self::stop_capture) succeeds or `ScreenCaptureKit`
+    /// stops the stream on its own (reported to the delegate's
+    /// [`did_stop_with_error`](crate::stream::delegate_trait::SCStreamDelegateTrait::did_stop_with_error)).
+    pub fn is_capturing(&self) -> bool {
+        self.capture_started_at().is_some()
+    }
+
+    /// When the current capture session started, or `None` if the stream
+    /// is not capturing
+    pub fn capture_started_at(&self) -> Option<std::time::SystemTime> {
+        let seconds = unsafe { ffi::sc_stream_get_capture_start_time(self.ptr) };
+        if seconds < 0.0 {
+            return None;
+        }
+        std::time::Duration::try_from_secs_f64(seconds)
+            .ok()
+            .and_then(|since_epoch| std::time::UNIX_EPOCH.checked_add(since_epoch))
+    }
+
+    /// The streams of this process that are currently capturing
+    ///
+    /// Each returned `SCStream` is another handle to a live stream, sharing
+    /// its handlers and state the way [`Clone`] does, so it can be used to
+    /// stop capture from a "Stop sharing" button anywhere in the app.
+    /// Streams created outside this crate are not included.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use screencapturekit::prelude::*;
+    ///
+    /// for stream in SCStream::active_streams() {
+    ///     println!("sharing since {:?}", stream.capture_started_at());
+    ///     let _ = stream.stop_capture();
+    /// }
+    /// ```
+    pub fn active_streams() -> Vec<Self> {
+        let mut found = ActiveStreams::default();
+        unsafe {
+            ffi::sc_stream_copy_active_streams(
+                collect_active_stream,
+                std::ptr::addr_of_mut!(found).cast(),
+            );
+        }
+        for stream in found.shutting_down {
+            unsafe { ffi::sc_stream_release(stream) };
+        }
+        found.streams
+    }
+
+    /// Whether macOS is showing its screen-recording indicator for this
+    /// process
+    ///
+    /// The purple menu bar indicator (and its Control Center entry) is
+    /// shown for as long as the process has a capturing stream, so this is
+    /// the same as `!SCStream::active_streams().is_empty()` without
+    /// creating the handles. Use it to keep in-app "You are sharing"
+    /// banners in sync with what the system shows. There is no public API
+    /// to read the indicator itself; captures made by other frameworks in
+    /// the same process (such as `AVCaptureScreenInput`) are not counted.
+    pub fn is_capture_indicator_visible() -> bool {
+        unsafe { ffi::sc_stream_get_active_count() > 0 }
     }
 
     /// Update the stream configuration
@@ -681,21 +1992,328 @@ impl SCStream {
     ///
     /// # Errors
     ///
-    /// Returns `SCError::StreamError` if the configuration update fails.
+    /// Returns [`SCError::SCStreamError`] or [`SCError::NSError`] with the
+    /// framework's error if the configuration update fails, or
+    /// [`SCError::InvalidState`] while the stream is starting, stopping or
+    /// already updating.
     pub fn update_configuration(
         &self,
         configuration: &SCStreamConfiguration,
     ) -> Result<(), SCError> {
-        let (completion, context) = UnitCompletion::new();
+        let updating = self.begin_update("update the configuration")?;
+        let result = self.apply_configuration(configuration);
+        self.finish_update(updating);
+        result
+    }
+
+    fn apply_configuration(&self, configuration: &SCStreamConfiguration) -> Result<(), SCError> {
+        // The update runs asynchronously in Swift; send a private copy so no
+        // clone of `configuration` can be mutated while it is being applied.
+        let configuration = configuration.deep_copy();
+        let (completion, context) = SyncCompletion::<Result<(), SCError>>::new();
         unsafe {
             ffi::sc_stream_update_configuration(
                 self.ptr,
                 configuration.as_ptr(),
                 context,
-                UnitCompletion::callback,
+                stream_control_callback,
             );
         }
-        completion.wait().map_err(SCError::StreamError)
+        completion.wait().map_err(SCError::StreamError)??;
+        self.store_configuration(&configuration);
+        Ok(())
+    }
+
+    /// A copy of the configuration currently applied to the stream.
+    ///
+    /// This is the configuration passed to [`new`](Self::new) or the last
+    /// successful [`update_configuration`](Self::update_configuration).
+    /// Mutating the returned value does not affect the stream.
+    pub fn configuration(&self) -> SCStreamConfiguration {
+        // SAFETY: self.context is the live StreamContext owned by this stream.
+        let ctx = unsafe { &*self.context };
+        ctx.configuration
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .as_ref()
+            .map_or_else(SCStreamConfiguration::new, SCStreamConfiguration::deep_copy)
+    }
+
+    /// Change only selected properties of the applied configuration.
+    ///
+    /// `update` is run against a copy of the current configuration, so
+    /// properties it doesn't touch keep their live values — changing the frame
+    /// rate won't reset cursor visibility the way rebuilding a configuration
+    /// from scratch would. If nothing changed, no update is sent.
+    ///
+    /// Returns the properties that were changed.
+    ///
+    /// Calls on clones of the same stream run one at a time, so two callers
+    /// changing different properties don't undo each other's change. `update`
+    /// must not call this method on the same stream.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error from [`update_configuration`](Self::update_configuration)
+    /// if the update fails; the stream keeps its previous configuration in
+    /// that case.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use screencapturekit::prelude::*;
+    ///
+    /// # fn example(stream: &SCStream) -> Result<(), SCError> {
+    /// let delta = stream.update_configuration_with(|config| {
+    ///     config.set_fps(30);
+    /// })?;
+    /// for change in &delta {
+    ///     println!("updated {change}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn update_configuration_with<F>(
+        &self,
+        update: F,
+    ) -> Result<crate::stream::configuration::SCStreamConfigurationDelta, SCError>
+    where
+        F: FnOnce(&mut SCStreamConfiguration),
+    {
+        // SAFETY: self.context is the live StreamContext owned by this stream.
+        let _serialized = unsafe { &*self.context }
+            .configuration_update
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let current = self.configuration();
+        let mut next = current.deep_copy();
+        update(&mut next);
+
+        let delta = current.diff(&next);
+        if !delta.is_empty() {
+            self.update_configuration(&next)?;
+        }
+        Ok(delta)
+    }
+
+    /// Switch the microphone input while the stream runs (macOS 15.0+)
+    ///
+    /// `Some(id)` selects the device with that
+    /// [`AudioInputDevice::id`](crate::audio_devices::AudioInputDevice::id);
+    /// `None` reverts to the system default input. Other configuration
+    /// properties keep their live values.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error from [`update_configuration`](Self::update_configuration)
+    /// if the update fails.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use screencapturekit::audio_devices::AudioInputDevice;
+    /// use screencapturekit::prelude::*;
+    ///
+    /// # fn example(stream: &SCStream) -> Result<(), SCError> {
+    /// if let Some(usb) = AudioInputDevice::list().iter().find(|d| d.name.contains("USB")) {
+    ///     stream.set_microphone_device(Some(&usb.id))?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_microphone_device(&self, device_id: Option<&str>) -> Result<(), SCError> {
+        self.update_configuration_with(|config| {
+            match device_id {
+                Some(id) => config.set_microphone_capture_device_id(id),
+                None => config.clear_microphone_capture_device_id(),
+            };
+        })?;
+        Ok(())
+    }
+
+    /// Start a [`MicrophoneDeviceWatcher`] for this stream, checking every
+    /// `interval` whether the selected microphone is still connected.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InternalError`] if the watcher thread cannot be
+    /// spawned.
+    ///
+    /// [`MicrophoneDeviceWatcher`]: crate::stream::microphone_watcher::MicrophoneDeviceWatcher
+    pub fn watch_microphone_device(
+        &self,
+        interval: std::time::Duration,
+    ) -> Result<crate::stream::microphone_watcher::MicrophoneDeviceWatcher, SCError> {
+        crate::stream::microphone_watcher::MicrophoneDeviceWatcher::start(self, interval)
+    }
+
+    /// Deliver `microphone_device_lost` to the stream's delegate, if any.
+    pub(crate) fn notify_microphone_device_lost(
+        &self,
+        lost_device_id: &str,
+        fallback: Option<&crate::audio_devices::AudioInputDevice>,
+    ) {
+        // SAFETY: self.context is the live StreamContext owned by this stream.
+        let ctx = unsafe { &*self.context };
+        let delegate = ctx
+            .delegate
+            .read()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if let Some(ref delegate) = *delegate {
+            catch_user_panic("delegate.microphone_device_lost", || {
+                delegate.microphone_device_lost(lost_device_id, fallback);
+            });
+        }
+    }
+
+    /// Start a [`DisplaySleepWatcher`] for this stream, applying
+    /// `options`' policy while the display sleeps, the screen is locked or
+    /// the screensaver runs.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InternalError`] if the watcher thread cannot be
+    /// spawned.
+    ///
+    /// [`DisplaySleepWatcher`]: crate::stream::display_sleep::DisplaySleepWatcher
+    pub fn watch_display_sleep(
+        &self,
+        options: crate::stream::display_sleep::DisplaySleepOptions,
+    ) -> Result<crate::stream::display_sleep::DisplaySleepWatcher, SCError> {
+        crate::stream::display_sleep::DisplaySleepWatcher::start(self, options)
+    }
+
+    /// Release samples without dispatching them while `withhold` is set.
+    pub(crate) fn set_withholding_samples(&self, withhold: bool) {
+        // SAFETY: self.context is the live StreamContext owned by this stream.
+        unsafe { &*self.context }
+            .withholding_samples
+            .store(withhold, Ordering::Release);
+    }
+
+    /// Deliver `display_sleep_changed` to the stream's delegate, if any.
+    pub(crate) fn notify_display_sleep_changed(
+        &self,
+        event: crate::stream::display_sleep::DisplaySleepEvent,
+    ) {
+        // SAFETY: self.context is the live StreamContext owned by this stream.
+        let ctx = unsafe { &*self.context };
+        let delegate = ctx
+            .delegate
+            .read()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if let Some(ref delegate) = *delegate {
+            catch_user_panic("delegate.display_sleep_changed", || {
+                delegate.display_sleep_changed(event);
+            });
+        }
+    }
+
+    /// Start a [`PermissionWatcher`] for this stream, checking every
+    /// `interval` whether Screen Recording permission is still granted.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InternalError`] if the watcher thread cannot be
+    /// spawned.
+    ///
+    /// [`PermissionWatcher`]: crate::stream::permission_watcher::PermissionWatcher
+    pub fn watch_permission(
+        &self,
+        interval: std::time::Duration,
+    ) -> Result<crate::stream::permission_watcher::PermissionWatcher, SCError> {
+        crate::stream::permission_watcher::PermissionWatcher::start(self, interval)
+    }
+
+    /// What this stream may capture right now
+    ///
+    /// Without Screen Recording permission a stream created from a
+    /// `SCContentSharingPicker` filter reports
+    /// [`CaptureAccess::PickerOnly`]: it captures the picked content, and
+    /// [`update_content_filter`](Self::update_content_filter) only accepts
+    /// other picked filters. Any other stream reports
+    /// [`CaptureAccess::Unavailable`] until permission is granted.
+    ///
+    /// [`CaptureAccess::PickerOnly`]: crate::stream::permission_watcher::CaptureAccess::PickerOnly
+    /// [`CaptureAccess::Unavailable`]: crate::stream::permission_watcher::CaptureAccess::Unavailable
+    pub fn capture_access(&self) -> crate::stream::permission_watcher::CaptureAccess {
+        use crate::stream::permission_watcher::{capture_access, CaptureAccess};
+
+        // SAFETY: self.context is the live StreamContext owned by this stream.
+        let ctx = unsafe { &*self.context };
+        let picked = ctx
+            .filter
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .as_ref()
+            .is_some_and(SCContentFilter::is_from_picker);
+        match capture_access() {
+            CaptureAccess::Full => CaptureAccess::Full,
+            CaptureAccess::PickerOnly if picked => CaptureAccess::PickerOnly,
+            _ => CaptureAccess::Unavailable,
+        }
+    }
+
+    /// Start and stop this stream on `schedule`, reporting to `on_event`
+    ///
+    /// See [`ScheduledCapture`] for how the schedule interacts with the
+    /// stream's state. Dropping the returned session cancels it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidConfiguration`] if the schedule's stop time
+    /// has passed or isn't after its start time, or its maximum duration is
+    /// zero. Returns [`SCError::InternalError`] if the session thread cannot
+    /// be spawned.
+    ///
+    /// [`ScheduledCapture`]: crate::stream::schedule::ScheduledCapture
+    pub fn schedule_capture<F>(
+        &self,
+        schedule: crate::stream::schedule::CaptureSchedule,
+        on_event: F,
+    ) -> Result<crate::stream::schedule::ScheduledCapture, SCError>
+    where
+        F: Fn(crate::stream::schedule::ScheduleEvent) + Send + 'static,
+    {
+        crate::stream::schedule::ScheduledCapture::start(self, schedule, on_event)
+    }
+
+    /// Deliver `permission_revoked` to the stream's delegate, unless this
+    /// revocation was already reported.
+    pub(crate) fn notify_permission_revoked(&self, event: &PermissionRevoked) {
+        // SAFETY: self.context is the live StreamContext owned by this stream.
+        let ctx = unsafe { &*self.context };
+        ctx.notify_permission_revoked(event);
+    }
+
+    /// Whether a revocation has been reported and not yet cleared.
+    pub(crate) fn permission_revoked_reported(&self) -> bool {
+        // SAFETY: self.context is the live StreamContext owned by this stream.
+        let ctx = unsafe { &*self.context };
+        ctx.permission_revoked.load(Ordering::Acquire)
+    }
+
+    /// Permission was granted again; report the next revocation.
+    pub(crate) fn clear_permission_revoked(&self) {
+        // SAFETY: self.context is the live StreamContext owned by this stream.
+        let ctx = unsafe { &*self.context };
+        ctx.permission_revoked.store(false, Ordering::Release);
+    }
+
+    pub(crate) fn store_configuration(&self, configuration: &SCStreamConfiguration) {
+        // SAFETY: self.context is the live StreamContext owned by this stream.
+        let ctx = unsafe { &*self.context };
+        *ctx.configuration
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner) = Some(configuration.deep_copy());
+    }
+
+    fn store_filter(&self, filter: &SCContentFilter) {
+        // SAFETY: self.context is the live StreamContext owned by this stream.
+        let ctx = unsafe { &*self.context };
+        *ctx.filter
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner) = Some(filter.clone());
     }
 
     /// Update the content filter
@@ -704,18 +2322,30 @@ impl SCStream {
     ///
     /// # Errors
     ///
-    /// Returns `SCError::StreamError` if the filter update fails.
+    /// Returns [`SCError::SCStreamError`] or [`SCError::NSError`] with the
+    /// framework's error if the filter update fails, or
+    /// [`SCError::InvalidState`] while the stream is starting, stopping or
+    /// already updating.
     pub fn update_content_filter(&self, filter: &SCContentFilter) -> Result<(), SCError> {
-        let (completion, context) = UnitCompletion::new();
+        let updating = self.begin_update("update the content filter")?;
+        let result = self.apply_content_filter(filter);
+        self.finish_update(updating);
+        result
+    }
+
+    fn apply_content_filter(&self, filter: &SCContentFilter) -> Result<(), SCError> {
+        let (completion, context) = SyncCompletion::<Result<(), SCError>>::new();
         unsafe {
             ffi::sc_stream_update_content_filter(
                 self.ptr,
                 filter.as_ptr(),
                 context,
-                UnitCompletion::callback,
+                stream_control_callback,
             );
         }
-        completion.wait().map_err(SCError::StreamError)
+        completion.wait().map_err(SCError::StreamError)??;
+        self.store_filter(filter);
+        Ok(())
     }
 
     /// Get the synchronization clock for this stream (macOS 13.0+)
@@ -740,48 +2370,125 @@ impl SCStream {
     /// will start when capture begins. The recording is written to the file URL
     /// specified in the `SCRecordingOutputConfiguration`.
     ///
+    /// # Recording alongside output handlers
+    ///
+    /// A recording output is independent of the handlers registered with
+    /// [`add_output_handler`](Self::add_output_handler): the same frames are
+    /// both encoded to disk and delivered to every handler, so a stream can
+    /// record to a file while also feeding a live preview or analysis
+    /// pipeline. The recording uses the stream's configuration (size, frame
+    /// rate, pixel format), so changing it with
+    /// [`update_configuration`](Self::update_configuration) affects both.
+    /// Handlers keep running after the recording output is removed.
+    ///
+    /// # Multiple recording outputs
+    ///
+    /// Whether more than one recording output may be attached at a time is
+    /// decided by `ScreenCaptureKit`. If the OS rejects an additional output
+    /// the call fails with [`SCError::SCStreamError`] carrying the OS error
+    /// code, and outputs that were already attached keep recording.
+    ///
     /// # Errors
     ///
-    /// Returns `SCError::StreamError` if adding the recording output fails.
+    /// - [`SCError::SCStreamError`] with
+    ///   [`SCStreamErrorCode::InvalidParameter`](crate::error::SCStreamErrorCode::InvalidParameter)
+    ///   if `recording_output` is already attached to this stream.
+    /// - [`SCError::SCStreamError`] if `ScreenCaptureKit` rejects the output
+    ///   with an `SCStreamErrorDomain` error.
+    /// - [`SCError::NSError`] for errors from other domains.
     #[cfg(feature = "macos_15_0")]
     pub fn add_recording_output(
         &self,
         recording_output: &crate::recording_output::SCRecordingOutput,
     ) -> Result<(), SCError> {
-        let (completion, context) = UnitCompletion::new();
+        // SAFETY: self.context is the live StreamContext owned by this stream.
+        let ctx = unsafe { &*self.context };
+        let attached = |outputs: &[crate::recording_output::SCRecordingOutput]| {
+            outputs
+                .iter()
+                .any(|o| o.as_ptr() == recording_output.as_ptr())
+        };
+        let already_attached = attached(
+            &ctx.recording_outputs
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner),
+        );
+        if already_attached {
+            return Err(SCError::from_stream_error_code_with_message(
+                crate::error::SCStreamErrorCode::InvalidParameter,
+                "Recording output is already attached to this stream",
+            ));
+        }
+
+        // Not holding the lock while waiting: the completion can take as
+        // long as ScreenCaptureKit needs.
+        let (completion, context) = SyncCompletion::<Result<(), SCError>>::new();
         unsafe {
             ffi::sc_stream_add_recording_output(
                 self.ptr,
                 recording_output.as_ptr(),
-                UnitCompletion::callback,
+                stream_control_callback,
                 context,
             );
         }
-        completion.wait().map_err(SCError::StreamError)
+        completion.wait().map_err(SCError::StreamError)??;
+
+        let mut outputs = ctx
+            .recording_outputs
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        // A concurrent call may have added it meanwhile.
+        if !attached(&outputs) {
+            outputs.push(recording_output.clone());
+        }
+        drop(outputs);
+        Ok(())
     }
 
     /// Remove a recording output from the stream (macOS 15.0+)
     ///
-    /// Stops recording if the stream is currently recording.
+    /// Stops recording if the stream is currently recording. Other recording
+    /// outputs and output handlers are unaffected.
     ///
     /// # Errors
     ///
-    /// Returns `SCError::StreamError` if removing the recording output fails.
+    /// Returns [`SCError::SCStreamError`] if `ScreenCaptureKit` rejects the
+    /// removal with an `SCStreamErrorDomain` error, or [`SCError::NSError`]
+    /// for errors from other domains.
     #[cfg(feature = "macos_15_0")]
     pub fn remove_recording_output(
         &self,
         recording_output: &crate::recording_output::SCRecordingOutput,
     ) -> Result<(), SCError> {
-        let (completion, context) = UnitCompletion::new();
+        let (completion, context) = SyncCompletion::<Result<(), SCError>>::new();
         unsafe {
             ffi::sc_stream_remove_recording_output(
                 self.ptr,
                 recording_output.as_ptr(),
-                UnitCompletion::callback,
+                stream_control_callback,
                 context,
             );
         }
-        completion.wait().map_err(SCError::StreamError)
+        completion.wait().map_err(SCError::StreamError)??;
+
+        // SAFETY: self.context is the live StreamContext owned by this stream.
+        let ctx = unsafe { &*self.context };
+        ctx.recording_outputs
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .retain(|o| o.as_ptr() != recording_output.as_ptr());
+        Ok(())
+    }
+
+    /// Number of recording outputs currently attached to this stream (macOS 15.0+)
+    #[cfg(feature = "macos_15_0")]
+    pub fn recording_output_count(&self) -> usize {
+        // SAFETY: self.context is the live StreamContext owned by this stream.
+        let ctx = unsafe { &*self.context };
+        ctx.recording_outputs
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .len()
     }
 
     /// Returns the raw pointer to the underlying Swift `SCStream` instance.
@@ -791,6 +2498,138 @@ impl SCStream {
     }
 }
 
+/// An output handler attached to a stream, as listed by [`SCStream::outputs`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OutputHandlerInfo {
+    id: usize,
+    of_type: SCStreamOutputType,
+    handler_type: &'static str,
+    queue_label: Option<String>,
+}
+
+impl OutputHandlerInfo {
+    /// The ID returned when the handler was added, for
+    /// [`SCStream::remove_output_handler`]
+    pub const fn id(&self) -> usize {
+        self.id
+    }
+
+    /// The output type the handler receives
+    pub const fn of_type(&self) -> SCStreamOutputType {
+        self.of_type
+    }
+
+    /// Rust type name of the handler as it was passed in, e.g. a closure's
+    /// `my_app::capture::{{closure}}` or a pooled handler's
+    /// `PooledOutputHandler<..>`. Meant for diagnostics; the exact text is
+    /// not stable across compiler versions.
+    pub const fn handler_type(&self) -> &'static str {
+        self.handler_type
+    }
+
+    /// Label of the dispatch queue the handler is called on. See
+    /// [`SCStream::output_queue_label`].
+    pub fn queue_label(&self) -> Option<&str> {
+        self.queue_label.as_deref()
+    }
+}
+
+/// The stream that delivered a sample, as seen from inside an output handler
+///
+/// Passed to [`ContextOutputTrait`] handlers. It borrows the stream's shared
+/// state rather than owning the stream, so handlers never keep a stream
+/// alive; call [`upgrade`](Self::upgrade) for a full [`SCStream`] handle.
+#[derive(Clone, Copy)]
+pub struct StreamRef<'a> {
+    ctx: &'a StreamContext,
+}
+
+impl StreamRef<'_> {
+    /// The stream's [`SCStream::id`]
+    pub fn id(&self) -> u64 {
+        self.ctx.id
+    }
+
+    /// The stream's [`SCStream::state`]
+    pub fn state(&self) -> SCStreamState {
+        self.ctx.state()
+    }
+
+    /// A copy of the configuration currently applied to the stream
+    pub fn configuration(&self) -> Option<SCStreamConfiguration> {
+        self.ctx
+            .configuration
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .as_ref()
+            .map(SCStreamConfiguration::deep_copy)
+    }
+
+    /// A new handle to the stream, or `None` once every `SCStream` handle
+    /// has been dropped
+    ///
+    /// Use it to reconfigure or stop the stream from a handler. Updates
+    /// block the handler's queue until `ScreenCaptureKit` answers, and
+    /// adding or removing output handlers from inside a handler deadlocks;
+    /// move such work to another thread. Don't store the handle in the
+    /// handler itself: the stream would then own a handle to itself and
+    /// never be freed.
+    pub fn upgrade(&self) -> Option<SCStream> {
+        let mut handles = self
+            .ctx
+            .handles
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if handles.count == 0 || handles.ptr.is_null() {
+            return None;
+        }
+        // SAFETY: a live handle owns a reference to `ptr` and can't release
+        // it while we hold the lock.
+        let ptr = unsafe { ffi::sc_stream_retain(handles.ptr) };
+        handles.count += 1;
+        drop(handles);
+
+        let context = std::ptr::from_ref(self.ctx).cast_mut();
+        // SAFETY: `ctx` is alive for `'a`; the new handle takes its own
+        // reference.
+        unsafe { StreamContext::retain(context) };
+        Some(SCStream { ptr, context })
+    }
+}
+
+impl fmt::Debug for StreamRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StreamRef")
+            .field("id", &self.id())
+            .field("state", &self.state())
+            .finish()
+    }
+}
+
+/// Adapts a [`ContextOutputTrait`] handler to the plain output path.
+struct WithStreamContext<H> {
+    handler: H,
+    /// The context whose handler list owns this adapter, so it outlives it.
+    context: *const StreamContext,
+    handler_id: usize,
+}
+
+// SAFETY: `StreamContext` is `Send + Sync` (asserted above) and outlives the
+// adapter, so sharing the pointer across dispatch queues is sound.
+unsafe impl<H: Send> Send for WithStreamContext<H> {}
+unsafe impl<H: Sync> Sync for WithStreamContext<H> {}
+
+impl<H: ContextOutputTrait> SCStreamOutputTrait for WithStreamContext<H> {
+    fn did_output_sample_buffer(&self, sample_buffer: CMSampleBuffer, of_type: SCStreamOutputType) {
+        // SAFETY: see `context`.
+        let stream = StreamRef {
+            ctx: unsafe { &*self.context },
+        };
+        self.handler
+            .did_output_sample_buffer(sample_buffer, of_type, &stream, self.handler_id);
+    }
+}
+
 impl Drop for SCStream {
     // Safety / teardown ordering:
     //
@@ -815,7 +2654,29 @@ impl Drop for SCStream {
     // `createStream` adds +1 per bridge object (delegate + output handler) = 3;
     // this `drop` removes -1 = 2; each bridge object's `deinit` removes -1,
     // reaching 0 and freeing the context.
+    //
+    // The handle count is updated under the handles lock, so
+    // `StreamRef::upgrade` either sees this handle alive and retains first,
+    // or sees it gone. The last handle clears the shared pointer before
+    // letting go of the lock, which keeps the stream to itself while it
+    // stops capture and drains the handlers (see `shut_down`), and only then
+    // releases its own reference.
     fn drop(&mut self) {
+        let last = {
+            // SAFETY: the context is alive until the release below.
+            let mut handles = unsafe { &*self.context }
+                .handles
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            handles.count = handles.count.saturating_sub(1);
+            if handles.count == 0 {
+                handles.ptr = std::ptr::null();
+            }
+            handles.count == 0
+        };
+        if last {
+            self.shut_down();
+        }
         if !self.ptr.is_null() {
             unsafe { ffi::sc_stream_release(self.ptr) };
         }
@@ -844,7 +2705,7 @@ impl Clone for SCStream {
     /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// # let content = SCShareableContent::get()?;
     /// # let display = &content.displays()[0];
-    /// # let filter = SCContentFilter::create().with_display(display).with_excluding_windows(&[]).build();
+    /// # let filter = SCContentFilter::for_display(display).with_excluding_windows(&[]).build();
     /// # let config = SCStreamConfiguration::default();
     /// let mut stream = SCStream::new(&filter, &config);
     /// stream.add_output_handler(|_, _| println!("Handler 1"), SCStreamOutputType::Screen);
@@ -857,9 +2718,11 @@ impl Clone for SCStream {
     /// ```
     fn clone(&self) -> Self {
         unsafe { StreamContext::retain(self.context) };
+        let ptr = unsafe { crate::ffi::sc_stream_retain(self.ptr) };
+        unsafe { &*self.context }.handle_acquired(ptr);
 
         Self {
-            ptr: unsafe { crate::ffi::sc_stream_retain(self.ptr) },
+            ptr,
             context: self.context,
         }
     }
@@ -912,6 +2775,7 @@ mod tests {
             handlers.push(HandlerEntry {
                 id: 1,
                 of_type: SCStreamOutputType::Audio,
+                type_name: "test",
                 handler: Box::new(
                     move |buf: crate::cm::CMSampleBuffer, _ty: SCStreamOutputType| {
                         counter.fetch_add(1, Ordering::Relaxed);
@@ -932,6 +2796,7 @@ mod tests {
             handlers.push(HandlerEntry {
                 id: 2,
                 of_type: SCStreamOutputType::Audio,
+                type_name: "test",
                 handler: Box::new(
                     move |buf: crate::cm::CMSampleBuffer, _ty: SCStreamOutputType| {
                         counter.fetch_add(1, Ordering::Relaxed);
@@ -1011,6 +2876,7 @@ mod tests {
             handlers.push(HandlerEntry {
                 id: 1,
                 of_type: SCStreamOutputType::Screen,
+                type_name: "test",
                 handler: Box::new(
                     move |buf: crate::cm::CMSampleBuffer, _ty: SCStreamOutputType| {
                         counter.fetch_add(1, Ordering::Relaxed);
@@ -1028,6 +2894,7 @@ mod tests {
             handlers.push(HandlerEntry {
                 id: 2,
                 of_type: SCStreamOutputType::Audio,
+                type_name: "test",
                 handler: Box::new(
                     move |buf: crate::cm::CMSampleBuffer, _ty: SCStreamOutputType| {
                         counter.fetch_add(1, Ordering::Relaxed);
@@ -1097,6 +2964,91 @@ mod tests {
         unsafe { StreamContext::release(ctx) };
     }
 
+    /// Context handlers see their own stream and ID, and a `StreamRef`
+    /// without a live `SCStream` handle can't be upgraded.
+    #[test]
+    fn test_context_output_handler_sees_its_stream() {
+        let ctx_a = StreamContext::new();
+        let ctx_b = StreamContext::new();
+        let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+
+        let make = |ctx: *mut StreamContext, handler_id: usize| {
+            let seen = Arc::clone(&seen);
+            WithStreamContext {
+                handler: move |buf: crate::cm::CMSampleBuffer,
+                               _ty: SCStreamOutputType,
+                               stream: &StreamRef<'_>,
+                               id: usize| {
+                    assert!(stream.upgrade().is_none());
+                    seen.lock().unwrap().push((stream.id(), id));
+                    std::mem::forget(buf);
+                },
+                context: ctx,
+                handler_id,
+            }
+        };
+        let handler_a = make(ctx_a, 7);
+        let handler_b = make(ctx_b, 8);
+
+        for handler in [&handler_a, &handler_b] {
+            let buf = unsafe { crate::cm::CMSampleBuffer::from_ptr(std::ptr::null_mut()) };
+            handler.did_output_sample_buffer(buf, SCStreamOutputType::Screen);
+        }
+
+        let (id_a, id_b) = unsafe { ((*ctx_a).id, (*ctx_b).id) };
+        assert_ne!(id_a, id_b);
+        assert_eq!(*seen.lock().unwrap(), vec![(id_a, 7), (id_b, 8)]);
+
+        drop((handler_a, handler_b));
+        unsafe {
+            StreamContext::release(ctx_a);
+            StreamContext::release(ctx_b);
+        }
+    }
+
+    /// Transitions only leave the in-flight state they entered, so an error
+    /// stop that lands mid-update is not overwritten by the update finishing.
+    #[test]
+    fn test_stream_context_state_transitions() {
+        let ctx = StreamContext::new();
+        let state = unsafe { &*ctx };
+        assert_eq!(state.state(), SCStreamState::Idle);
+
+        let err = state
+            .begin_transition("stop capture", |s| {
+                s.can_stop().then_some(SCStreamState::Stopping)
+            })
+            .unwrap_err();
+        assert_eq!(
+            err,
+            SCError::InvalidState {
+                operation: "stop capture".to_string(),
+                state: SCStreamState::Idle,
+            }
+        );
+
+        let starting = state
+            .begin_transition("start capture", |s| {
+                s.can_start().then_some(SCStreamState::Starting)
+            })
+            .unwrap();
+        assert!(state
+            .begin_transition("start capture", |s| s
+                .can_start()
+                .then_some(SCStreamState::Starting))
+            .is_err());
+        state.finish_transition(starting, SCStreamState::Running);
+        assert_eq!(state.state(), SCStreamState::Running);
+
+        *state.state.lock().unwrap() = SCStreamState::Updating;
+        state.mark_failed();
+        state.finish_transition(SCStreamState::Updating, SCStreamState::Running);
+        assert_eq!(state.state(), SCStreamState::Failed);
+        assert!(state.state().can_start() && state.state().can_stop());
+
+        unsafe { StreamContext::release(ctx) };
+    }
+
     /// Regression test: a panic in a user-supplied output handler must NOT
     /// poison the handlers `RwLock`, must NOT propagate across the C ABI,
     /// and must NOT prevent subsequent callbacks from being dispatched.
@@ -1127,6 +3079,7 @@ mod tests {
             handlers.push(HandlerEntry {
                 id: 1,
                 of_type: SCStreamOutputType::Audio,
+                type_name: "test",
                 handler: Box::new(
                     move |buf: crate::cm::CMSampleBuffer, _ty: SCStreamOutputType| {
                         counter.fetch_add(1, Ordering::Relaxed);
@@ -1147,6 +3100,7 @@ mod tests {
             handlers.push(HandlerEntry {
                 id: 2,
                 of_type: SCStreamOutputType::Audio,
+                type_name: "test",
                 handler: Box::new(
                     move |buf: crate::cm::CMSampleBuffer, _ty: SCStreamOutputType| {
                         counter.fetch_add(1, Ordering::Relaxed);
@@ -1207,4 +3161,113 @@ mod tests {
         // Restore the original panic hook so other tests behave normally.
         std::panic::set_hook(original_hook);
     }
+
+    fn push_handler(ctx: *mut StreamContext, handler: impl SCStreamOutputTrait + 'static) {
+        unsafe { &*ctx }
+            .handlers
+            .write()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .push(HandlerEntry {
+                id: 1,
+                of_type: SCStreamOutputType::Screen,
+                type_name: "test",
+                handler: Box::new(handler),
+            });
+    }
+
+    /// Dispatch one fake sample the way `sample_handler` does: inside a
+    /// callback scope, under the handlers read lock.
+    fn dispatch(ctx: &StreamContext) {
+        let _scope = CallbackScope::enter(ctx);
+        let handlers = ctx
+            .handlers
+            .read()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        for entry in handlers.iter() {
+            let buf = unsafe { crate::cm::CMSampleBuffer::from_ptr(std::ptr::null_mut()) };
+            entry
+                .handler
+                .did_output_sample_buffer(buf, SCStreamOutputType::Screen);
+        }
+    }
+
+    /// Teardown race: draining while a handler runs on another thread waits
+    /// for it to return, and nothing is dispatched afterwards.
+    #[test]
+    fn test_drain_waits_for_in_flight_callback() {
+        let entered = Arc::new(AtomicBool::new(false));
+        let finished = Arc::new(AtomicBool::new(false));
+        let calls = Arc::new(AtomicUsize::new(0));
+        let ctx = StreamContext::new();
+        {
+            let (entered, finished, calls) = (entered.clone(), finished.clone(), calls.clone());
+            push_handler(
+                ctx,
+                move |buf: crate::cm::CMSampleBuffer, _ty: SCStreamOutputType| {
+                    std::mem::forget(buf);
+                    calls.fetch_add(1, Ordering::SeqCst);
+                    entered.store(true, Ordering::SeqCst);
+                    std::thread::sleep(Duration::from_millis(100));
+                    finished.store(true, Ordering::SeqCst);
+                },
+            );
+        }
+
+        let context = unsafe { &*ctx };
+        std::thread::scope(|scope| {
+            scope.spawn(|| dispatch(context));
+            while !entered.load(Ordering::SeqCst) {
+                std::thread::yield_now();
+            }
+            assert!(context.drain());
+            assert!(
+                finished.load(Ordering::SeqCst),
+                "drain returned while a handler was still running"
+            );
+        });
+
+        dispatch(context);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert!(context
+            .handlers
+            .read()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .is_empty());
+
+        unsafe { StreamContext::release(ctx) };
+    }
+
+    /// Draining from inside the context's own callback must not deadlock on
+    /// the read lock that callback holds; it is left for later instead.
+    #[test]
+    fn test_drain_inside_own_callback_is_deferred() {
+        let owned = Arc::new(());
+        let ctx = StreamContext::new();
+        let drained_inside = Arc::new(AtomicBool::new(true));
+        {
+            let owned = owned.clone();
+            let drained_inside = drained_inside.clone();
+            let context = ctx as usize;
+            push_handler(
+                ctx,
+                move |buf: crate::cm::CMSampleBuffer, _ty: SCStreamOutputType| {
+                    std::mem::forget(buf);
+                    assert_eq!(Arc::strong_count(&owned), 2);
+                    let ctx = unsafe { &*(context as *const StreamContext) };
+                    drained_inside.store(ctx.drain(), Ordering::SeqCst);
+                },
+            );
+        }
+
+        let context = unsafe { &*ctx };
+        dispatch(context);
+        assert!(!drained_inside.load(Ordering::SeqCst));
+        assert_eq!(Arc::strong_count(&owned), 2);
+
+        // Outside the callback the drain goes ahead and drops the handler.
+        assert!(context.drain());
+        assert_eq!(Arc::strong_count(&owned), 1);
+
+        unsafe { StreamContext::release(ctx) };
+    }
 }
@@ -0,0 +1,440 @@
+//! Starting and stopping capture on a schedule
+//!
+//! Unattended recordings — a webinar at 14:00, an hour of monitoring
+//! overnight — need a stream started at one time and stopped at another.
+//! Doing that with external timers races with the stream's own lifecycle:
+//! a timer fires while a start is still in flight, or stops a stream that
+//! `ScreenCaptureKit` already stopped with an error.
+//!
+//! A [`ScheduledCapture`] owns that timing. Its thread waits for the
+//! [`CaptureSchedule`]'s start time, starts the stream, and stops it after
+//! the maximum duration or at the stop time, whichever comes first. It
+//! checks the stream's [`state`](super::SCStream::state) before each
+//! transition and notices streams that stop on their own. Every outcome
+//! is reported as a [`ScheduleEvent`].
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use std::time::{Duration, SystemTime};
+//! use screencapturekit::prelude::*;
+//! use screencapturekit::stream::schedule::CaptureSchedule;
+//!
+//! # fn example(stream: &SCStream) -> Result<(), SCError> {
+//! let schedule = CaptureSchedule::new()
+//!     .with_start_at(SystemTime::now() + Duration::from_secs(60))
+//!     .with_max_duration(Duration::from_secs(90 * 60));
+//! let session = stream.schedule_capture(schedule, |event| println!("{event:?}"))?;
+//! let reason = session.wait()?;
+//! println!("capture ended: {reason:?}");
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+use std::sync::{Arc, Condvar, Mutex, PoisonError};
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::error::SCError;
+use crate::utils::panic_safe::catch_user_panic;
+use crate::utils::poller::{Poller, PollerContext};
+
+use super::SCStream;
+
+/// Longest single wait, so wall-clock changes and system sleep are
+/// noticed promptly.
+const MAX_WAIT: Duration = Duration::from_secs(1);
+
+/// When a [`ScheduledCapture`] starts and stops the stream
+///
+/// Without a start time the stream starts immediately; without a maximum
+/// duration or stop time it runs until the stream stops or the session
+/// is cancelled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct CaptureSchedule {
+    start_at: Option<SystemTime>,
+    max_duration: Option<Duration>,
+    stop_at: Option<SystemTime>,
+}
+
+impl CaptureSchedule {
+    /// Start now and run until stopped
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            start_at: None,
+            max_duration: None,
+            stop_at: None,
+        }
+    }
+
+    /// Start at `time` on the wall clock
+    pub fn set_start_at(&mut self, time: SystemTime) -> &mut Self {
+        self.start_at = Some(time);
+        self
+    }
+
+    /// Start at `time` on the wall clock (builder pattern)
+    #[must_use]
+    pub fn with_start_at(mut self, time: SystemTime) -> Self {
+        self.set_start_at(time);
+        self
+    }
+
+    /// Stop once the stream has captured for `duration`
+    pub fn set_max_duration(&mut self, duration: Duration) -> &mut Self {
+        self.max_duration = Some(duration);
+        self
+    }
+
+    /// Stop once the stream has captured for `duration` (builder pattern)
+    #[must_use]
+    pub fn with_max_duration(mut self, duration: Duration) -> Self {
+        self.set_max_duration(duration);
+        self
+    }
+
+    /// Stop at `time` on the wall clock
+    pub fn set_stop_at(&mut self, time: SystemTime) -> &mut Self {
+        self.stop_at = Some(time);
+        self
+    }
+
+    /// Stop at `time` on the wall clock (builder pattern)
+    #[must_use]
+    pub fn with_stop_at(mut self, time: SystemTime) -> Self {
+        self.set_stop_at(time);
+        self
+    }
+
+    /// When capture starts, or `None` for immediately
+    #[must_use]
+    pub const fn start_at(&self) -> Option<SystemTime> {
+        self.start_at
+    }
+
+    /// Longest capture, if limited
+    #[must_use]
+    pub const fn max_duration(&self) -> Option<Duration> {
+        self.max_duration
+    }
+
+    /// When capture stops, if scheduled
+    #[must_use]
+    pub const fn stop_at(&self) -> Option<SystemTime> {
+        self.stop_at
+    }
+
+    /// Check that the schedule can run from now
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidConfiguration`] if the stop time has
+    /// passed or isn't after the start time, or the maximum duration is
+    /// zero.
+    pub fn validate(&self) -> Result<(), SCError> {
+        if self.max_duration == Some(Duration::ZERO) {
+            return Err(SCError::invalid_config("maximum capture duration is zero"));
+        }
+        if let Some(stop_at) = self.stop_at {
+            if stop_at <= SystemTime::now() {
+                return Err(SCError::invalid_config("stop time has already passed"));
+            }
+            if self.start_at.is_some_and(|start_at| stop_at <= start_at) {
+                return Err(SCError::invalid_config(
+                    "stop time must be after the start time",
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Why a scheduled capture ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StopReason {
+    /// The maximum duration elapsed
+    MaxDuration,
+    /// The stop time arrived
+    StopTime,
+    /// The session was cancelled or dropped
+    Cancelled,
+    /// The stream stopped without the schedule, e.g. by another caller or
+    /// because `ScreenCaptureKit` reported an error
+    StreamStopped,
+}
+
+impl fmt::Display for StopReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MaxDuration => write!(f, "maximum duration reached"),
+            Self::StopTime => write!(f, "stop time reached"),
+            Self::Cancelled => write!(f, "cancelled"),
+            Self::StreamStopped => write!(f, "stream stopped"),
+        }
+    }
+}
+
+/// Something a [`ScheduledCapture`] did
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScheduleEvent {
+    /// Capture started at `at`
+    Started {
+        /// Wall-clock start time
+        at: SystemTime,
+    },
+    /// Capture ended; the session is finished
+    Stopped {
+        /// Why it ended
+        reason: StopReason,
+        /// How long the stream captured, zero if it never started
+        captured: Duration,
+    },
+    /// Starting or stopping the stream failed; the session is finished
+    Failed(SCError),
+}
+
+/// Where a [`ScheduledCapture`] is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SchedulePhase {
+    /// Waiting for the start time
+    Waiting,
+    /// The stream is capturing
+    Capturing,
+    /// Done; see [`ScheduledCapture::wait`] for the outcome
+    Finished,
+}
+
+struct ScheduleState {
+    phase: SchedulePhase,
+    outcome: Option<Result<StopReason, SCError>>,
+}
+
+struct ScheduleShared {
+    state: Mutex<ScheduleState>,
+    wake: Condvar,
+}
+
+impl ScheduleShared {
+    fn set_phase(&self, phase: SchedulePhase) {
+        self.state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .phase = phase;
+        self.wake.notify_all();
+    }
+
+    fn finish(&self, outcome: Result<StopReason, SCError>) {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        state.phase = SchedulePhase::Finished;
+        state.outcome = Some(outcome);
+        drop(state);
+        self.wake.notify_all();
+    }
+}
+
+/// Time left until `time` on the wall clock; zero once it has passed.
+fn until(time: SystemTime) -> Duration {
+    time.duration_since(SystemTime::now())
+        .unwrap_or(Duration::ZERO)
+}
+
+/// A stream started and stopped by a [`CaptureSchedule`]
+///
+/// Usually created with [`SCStream::schedule_capture`]. Dropping the
+/// session cancels it: a stream it started is stopped, and the event
+/// callback receives [`StopReason::Cancelled`].
+pub struct ScheduledCapture {
+    schedule: CaptureSchedule,
+    shared: Arc<ScheduleShared>,
+    poller: Poller,
+}
+
+impl ScheduledCapture {
+    /// Run `schedule` for `stream`, reporting to `on_event` from the
+    /// session's thread
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidConfiguration`] if the schedule is invalid;
+    /// see [`CaptureSchedule::validate`]. Returns
+    /// [`SCError::InternalError`] if the operating system refuses to spawn
+    /// the session thread.
+    pub fn start<F>(
+        stream: &SCStream,
+        schedule: CaptureSchedule,
+        on_event: F,
+    ) -> Result<Self, SCError>
+    where
+        F: Fn(ScheduleEvent) + Send + 'static,
+    {
+        schedule.validate()?;
+        let shared = Arc::new(ScheduleShared {
+            state: Mutex::new(ScheduleState {
+                phase: SchedulePhase::Waiting,
+                outcome: None,
+            }),
+            wake: Condvar::new(),
+        });
+
+        let poller = {
+            let shared = Arc::clone(&shared);
+            let stream = stream.clone();
+            Poller::spawn("capture-schedule", move |context| {
+                let emit = |event: ScheduleEvent| {
+                    catch_user_panic("capture schedule event", || on_event(event));
+                };
+                run(context, &shared, &stream, schedule, &emit);
+            })?
+        };
+
+        Ok(Self {
+            schedule,
+            shared,
+            poller,
+        })
+    }
+
+    /// The schedule being run
+    #[must_use]
+    pub const fn schedule(&self) -> CaptureSchedule {
+        self.schedule
+    }
+
+    /// Where the session is
+    #[must_use]
+    pub fn phase(&self) -> SchedulePhase {
+        self.shared
+            .state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .phase
+    }
+
+    /// Block until the session finishes
+    ///
+    /// # Errors
+    ///
+    /// Returns the error from starting or stopping the stream, as also
+    /// reported by [`ScheduleEvent::Failed`].
+    pub fn wait(&self) -> Result<StopReason, SCError> {
+        let state = self
+            .shared
+            .state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let state = self
+            .shared
+            .wake
+            .wait_while(state, |state| state.outcome.is_none())
+            .unwrap_or_else(PoisonError::into_inner);
+        state.outcome.clone().unwrap_or(Ok(StopReason::Cancelled))
+    }
+
+    /// Cancel the session, stopping the stream if the session started it,
+    /// and wait for it to finish
+    ///
+    /// # Errors
+    ///
+    /// Returns the session's outcome, as [`wait`](Self::wait) does.
+    pub fn cancel(mut self) -> Result<StopReason, SCError> {
+        self.poller.stop();
+        self.wait()
+    }
+}
+
+/// Sleep for `timeout`, capped at [`MAX_WAIT`], or until cancelled.
+/// Returns whether the session was cancelled.
+fn sleep(context: &PollerContext, timeout: Duration) -> bool {
+    context.sleep(timeout.min(MAX_WAIT))
+}
+
+fn run(
+    context: &PollerContext,
+    shared: &ScheduleShared,
+    stream: &SCStream,
+    schedule: CaptureSchedule,
+    emit: &dyn Fn(ScheduleEvent),
+) {
+    let stopped = |reason, captured| {
+        emit(ScheduleEvent::Stopped { reason, captured });
+        shared.finish(Ok(reason));
+    };
+
+    if let Some(start_at) = schedule.start_at {
+        loop {
+            let remaining = until(start_at);
+            if remaining.is_zero() {
+                break;
+            }
+            if sleep(context, remaining) {
+                stopped(StopReason::Cancelled, Duration::ZERO);
+                return;
+            }
+        }
+    }
+    if schedule
+        .stop_at
+        .is_some_and(|stop_at| until(stop_at).is_zero())
+    {
+        stopped(StopReason::StopTime, Duration::ZERO);
+        return;
+    }
+
+    // A stream someone else already started is adopted rather than
+    // restarted; it is still stopped on schedule.
+    if !stream.state().is_capturing() {
+        if let Err(error) = stream.start_capture() {
+            emit(ScheduleEvent::Failed(error.clone()));
+            shared.finish(Err(error));
+            return;
+        }
+    }
+    let started = Instant::now();
+    shared.set_phase(SchedulePhase::Capturing);
+    emit(ScheduleEvent::Started {
+        at: SystemTime::now(),
+    });
+
+    let reason = loop {
+        let by_duration = schedule
+            .max_duration
+            .map(|max| max.saturating_sub(started.elapsed()));
+        let by_time = schedule.stop_at.map(until);
+        match (by_duration, by_time) {
+            (Some(left), _) if left.is_zero() => break StopReason::MaxDuration,
+            (_, Some(left)) if left.is_zero() => break StopReason::StopTime,
+            _ => {}
+        }
+        if !stream.state().is_capturing() && !stream.state().is_transitioning() {
+            break StopReason::StreamStopped;
+        }
+        let remaining = by_duration
+            .into_iter()
+            .chain(by_time)
+            .min()
+            .unwrap_or(MAX_WAIT);
+        if sleep(context, remaining) {
+            break StopReason::Cancelled;
+        }
+    };
+    let captured = started.elapsed();
+
+    if reason != StopReason::StreamStopped && stream.state().can_stop() {
+        if let Err(error) = stream.stop_capture() {
+            emit(ScheduleEvent::Failed(error.clone()));
+            shared.finish(Err(error));
+            return;
+        }
+    }
+    stopped(reason, captured);
+}
+
+impl fmt::Debug for ScheduledCapture {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScheduledCapture")
+            .field("schedule", &self.schedule)
+            .field("phase", &self.phase())
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,96 @@
+//! Capture session state
+//!
+//! Every [`SCStream`](super::SCStream) tracks where it is in its lifecycle:
+//!
+//! ```text
+//! Idle ──start──▶ Starting ──▶ Running ──stop──▶ Stopping ──▶ Stopped
+//!                    │          │  ▲                 │
+//!                    │     update  │                 │
+//!                    │          ▼  │                 │
+//!                    │         Updating              │
+//!                    └───────────┴──────▶ Failed ◀───┘
+//! ```
+//!
+//! `Stopped` and `Failed` streams can be started again. A stream enters
+//! `Failed` when starting or stopping fails, when
+//! [`start_capture_with_timeout`](super::SCStream::start_capture_with_timeout)
+//! gives up, or when `ScreenCaptureKit` stops it with an error. A failed
+//! [`update_configuration`](super::SCStream::update_configuration) leaves the
+//! stream `Running`, since the old configuration stays in effect.
+//!
+//! Calls made in the wrong state, like stopping a stream that never started
+//! or starting one twice, fail with [`SCError::InvalidState`] before reaching
+//! `ScreenCaptureKit`.
+//!
+//! Read the state with [`SCStream::state`](super::SCStream::state) and
+//! observe changes through
+//! [`SCStreamDelegateTrait::stream_state_did_change`](super::delegate_trait::SCStreamDelegateTrait::stream_state_did_change)
+//! or [`StreamCallbacks::on_state_change`](super::delegate_trait::StreamCallbacks::on_state_change).
+//!
+//! [`SCError::InvalidState`]: crate::error::SCError::InvalidState
+
+use std::fmt;
+
+/// Lifecycle state of an [`SCStream`](super::SCStream)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SCStreamState {
+    /// Created, never started
+    #[default]
+    Idle,
+    /// `start_capture` is waiting for `ScreenCaptureKit`
+    Starting,
+    /// Capturing frames
+    Running,
+    /// Capturing, with a configuration or filter update in flight
+    Updating,
+    /// `stop_capture` is waiting for `ScreenCaptureKit`
+    Stopping,
+    /// Stopped cleanly; can be started again
+    Stopped,
+    /// Stopped by an error, or a start or stop failed; can be started again
+    Failed,
+}
+
+impl SCStreamState {
+    /// Whether the stream is delivering frames
+    pub const fn is_capturing(self) -> bool {
+        matches!(self, Self::Running | Self::Updating)
+    }
+
+    /// Whether a start, stop or update is waiting for `ScreenCaptureKit`
+    pub const fn is_transitioning(self) -> bool {
+        matches!(self, Self::Starting | Self::Updating | Self::Stopping)
+    }
+
+    /// Whether `start_capture` is allowed
+    pub const fn can_start(self) -> bool {
+        matches!(self, Self::Idle | Self::Stopped | Self::Failed)
+    }
+
+    /// Whether `stop_capture` is allowed
+    ///
+    /// A failed stream may still be capturing, e.g. after a start that
+    /// timed out and then succeeded, so stopping it is allowed.
+    pub const fn can_stop(self) -> bool {
+        matches!(self, Self::Running | Self::Failed)
+    }
+
+    /// Whether `update_configuration` and `update_content_filter` are allowed
+    pub const fn can_update(self) -> bool {
+        !self.is_transitioning()
+    }
+}
+
+impl fmt::Display for SCStreamState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Idle => "idle",
+            Self::Starting => "starting",
+            Self::Running => "running",
+            Self::Updating => "updating",
+            Self::Stopping => "stopping",
+            Self::Stopped => "stopped",
+            Self::Failed => "failed",
+        })
+    }
+}
@@ -0,0 +1,351 @@
+//! Feed one capture to a recording and a live preview
+//!
+//! [`TeeOutputHandler`] hands every sample to two handlers, each on its own
+//! thread: a *recording* sink that must see every frame, and a *preview*
+//! that only needs the latest one. Both get a retained handle to the same
+//! [`CMSampleBuffer`], so nothing is copied.
+//!
+//! ## Pool pressure
+//!
+//! Every sample the tee holds keeps a surface of the stream's pool busy
+//! (see [`CMSampleBufferRetainExt`](crate::cm::CMSampleBufferRetainExt)).
+//! The tee counts the distinct samples it holds — queued or being handled
+//! by either branch — against a budget, normally the stream's
+//! [`queue_depth`](crate::stream::configuration::SCStreamConfiguration::queue_depth)
+//! less one. The preview only ever waits on the newest frame; while the
+//! budget is exceeded it gets no frame at all, so a slow preview can't keep
+//! surfaces the recording needs. Recording samples are never dropped by the
+//! tee.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use screencapturekit::prelude::*;
+//!
+//! # fn example(filter: &SCContentFilter, config: &SCStreamConfiguration) {
+//! let mut stream = SCStream::new(filter, config);
+//! stream.add_output_handler_tee(
+//!     |sample: CMSampleBuffer, _| { /* append to a writer */ },
+//!     |sample: CMSampleBuffer, _| { /* draw the latest frame */ },
+//!     SCStreamOutputType::Screen,
+//! );
+//! # }
+//! ```
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Condvar, Mutex, PoisonError};
+use std::thread::JoinHandle;
+
+use crate::cm::CMSampleBuffer;
+use crate::error::SCError;
+use crate::utils::panic_safe::catch_user_panic;
+
+use super::output_trait::SCStreamOutputTrait;
+use super::output_type::SCStreamOutputType;
+
+type Queued = (CMSampleBuffer, SCStreamOutputType);
+
+/// Samples are numbered in arrival order, so the tee can tell whether a
+/// preview frame is one the recording still holds anyway.
+struct TeeQueue {
+    next_seq: u64,
+    recording: VecDeque<(u64, Queued)>,
+    recording_busy: Option<u64>,
+    preview: Option<(u64, Queued)>,
+    preview_busy: Option<u64>,
+    closed: bool,
+}
+
+impl TeeQueue {
+    /// Distinct samples held by either branch. A preview frame only adds
+    /// one once the recording has finished with it.
+    fn in_flight(&self) -> usize {
+        let oldest_recording = self
+            .recording_busy
+            .or_else(|| self.recording.front().map(|(seq, _)| *seq))
+            .unwrap_or(self.next_seq);
+        let preview_only = [
+            self.preview.as_ref().map(|(seq, _)| *seq),
+            self.preview_busy,
+        ]
+        .into_iter()
+        .flatten()
+        .filter(|seq| *seq < oldest_recording)
+        .count();
+        self.recording.len() + usize::from(self.recording_busy.is_some()) + preview_only
+    }
+}
+
+struct TeeShared {
+    queue: Mutex<TeeQueue>,
+    recording_ready: Condvar,
+    preview_ready: Condvar,
+    budget: usize,
+    recorded: AtomicU64,
+    previewed: AtomicU64,
+    preview_dropped: AtomicU64,
+    peak_in_flight: AtomicU64,
+}
+
+/// Sends each sample to a recording handler and a preview handler, dropping
+/// preview frames before the stream's surface pool runs out.
+///
+/// Usually created through
+/// [`SCStream::add_output_handler_tee`](crate::stream::SCStream::add_output_handler_tee).
+/// Dropping the tee lets the recording branch finish every queued sample,
+/// then joins both threads.
+pub struct TeeOutputHandler {
+    shared: Arc<TeeShared>,
+    threads: Vec<JoinHandle<()>>,
+}
+
+impl TeeOutputHandler {
+    /// Start a tee that holds at most `budget` samples at once before it
+    /// starts dropping preview frames.
+    ///
+    /// A `budget` of `0` is treated as `1`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InternalError`] if the operating system refuses to
+    /// spawn a branch thread. A branch already started is joined.
+    pub fn new(
+        recording: impl SCStreamOutputTrait + 'static,
+        preview: impl SCStreamOutputTrait + 'static,
+        budget: usize,
+    ) -> Result<Self, SCError> {
+        let shared = Arc::new(TeeShared {
+            queue: Mutex::new(TeeQueue {
+                next_seq: 0,
+                recording: VecDeque::new(),
+                recording_busy: None,
+                preview: None,
+                preview_busy: None,
+                closed: false,
+            }),
+            recording_ready: Condvar::new(),
+            preview_ready: Condvar::new(),
+            budget: budget.max(1),
+            recorded: AtomicU64::new(0),
+            previewed: AtomicU64::new(0),
+            preview_dropped: AtomicU64::new(0),
+            peak_in_flight: AtomicU64::new(0),
+        });
+
+        // Built up front so that a failed spawn drops it, which closes the
+        // queue and joins the branch already running.
+        let mut tee = Self {
+            shared,
+            threads: Vec::with_capacity(2),
+        };
+        let spawn_error = |e: std::io::Error| {
+            SCError::internal_error(format!("failed to spawn tee branch thread: {e}"))
+        };
+        let recording_thread = {
+            let shared = Arc::clone(&tee.shared);
+            std::thread::Builder::new()
+                .name("sc-tee-recording".to_string())
+                .spawn(move || recording_loop(&shared, &recording))
+                .map_err(spawn_error)?
+        };
+        tee.threads.push(recording_thread);
+        let preview_thread = {
+            let shared = Arc::clone(&tee.shared);
+            std::thread::Builder::new()
+                .name("sc-tee-preview".to_string())
+                .spawn(move || preview_loop(&shared, &preview))
+                .map_err(spawn_error)?
+        };
+        tee.threads.push(preview_thread);
+        Ok(tee)
+    }
+
+    /// Most samples the tee holds before dropping preview frames.
+    #[must_use]
+    pub fn budget(&self) -> usize {
+        self.shared.budget
+    }
+
+    /// Distinct samples held right now, queued or being handled by either
+    /// branch.
+    #[must_use]
+    pub fn in_flight(&self) -> usize {
+        self.shared
+            .queue
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .in_flight()
+    }
+
+    /// Samples waiting for the recording handler.
+    #[must_use]
+    pub fn recording_queue_depth(&self) -> usize {
+        self.shared
+            .queue
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .recording
+            .len()
+    }
+
+    /// Most samples held at once since the tee started.
+    #[must_use]
+    pub fn peak_in_flight(&self) -> u64 {
+        self.shared.peak_in_flight.load(Ordering::Relaxed)
+    }
+
+    /// Samples the recording handler has finished with.
+    #[must_use]
+    pub fn recorded_samples(&self) -> u64 {
+        self.shared.recorded.load(Ordering::Relaxed)
+    }
+
+    /// Samples the preview handler has finished with.
+    #[must_use]
+    pub fn previewed_samples(&self) -> u64 {
+        self.shared.previewed.load(Ordering::Relaxed)
+    }
+
+    /// Samples the preview never saw, because a newer one replaced them or
+    /// the pool budget was reached.
+    #[must_use]
+    pub fn preview_dropped_samples(&self) -> u64 {
+        self.shared.preview_dropped.load(Ordering::Relaxed)
+    }
+}
+
+fn recording_loop(shared: &TeeShared, handler: &dyn SCStreamOutputTrait) {
+    loop {
+        let next = {
+            let mut queue = shared.queue.lock().unwrap_or_else(PoisonError::into_inner);
+            loop {
+                if let Some((seq, next)) = queue.recording.pop_front() {
+                    queue.recording_busy = Some(seq);
+                    break Some(next);
+                }
+                if queue.closed {
+                    break None;
+                }
+                queue = shared
+                    .recording_ready
+                    .wait(queue)
+                    .unwrap_or_else(PoisonError::into_inner);
+            }
+        };
+        let Some((sample, of_type)) = next else {
+            return;
+        };
+        catch_user_panic("tee recording handler", || {
+            handler.did_output_sample_buffer(sample, of_type);
+        });
+        shared.recorded.fetch_add(1, Ordering::Relaxed);
+        shared
+            .queue
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .recording_busy = None;
+    }
+}
+
+fn preview_loop(shared: &TeeShared, handler: &dyn SCStreamOutputTrait) {
+    loop {
+        let next = {
+            let mut queue = shared.queue.lock().unwrap_or_else(PoisonError::into_inner);
+            loop {
+                if queue.closed {
+                    break None;
+                }
+                if let Some((seq, next)) = queue.preview.take() {
+                    queue.preview_busy = Some(seq);
+                    break Some(next);
+                }
+                queue = shared
+                    .preview_ready
+                    .wait(queue)
+                    .unwrap_or_else(PoisonError::into_inner);
+            }
+        };
+        let Some((sample, of_type)) = next else {
+            return;
+        };
+        catch_user_panic("tee preview handler", || {
+            handler.did_output_sample_buffer(sample, of_type);
+        });
+        shared.previewed.fetch_add(1, Ordering::Relaxed);
+        shared
+            .queue
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .preview_busy = None;
+    }
+}
+
+impl SCStreamOutputTrait for TeeOutputHandler {
+    fn did_output_sample_buffer(&self, sample_buffer: CMSampleBuffer, of_type: SCStreamOutputType) {
+        let preview_sample = sample_buffer.clone();
+        let (stale, skipped, in_flight) = {
+            let mut queue = self
+                .shared
+                .queue
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            let seq = queue.next_seq;
+            queue.next_seq += 1;
+            queue.recording.push_back((seq, (sample_buffer, of_type)));
+
+            // Only the latest frame matters to the preview.
+            let stale = queue.preview.take();
+            let in_flight = queue.in_flight();
+            let skipped = if in_flight > self.shared.budget {
+                Some(preview_sample)
+            } else {
+                queue.preview = Some((seq, (preview_sample, of_type)));
+                None
+            };
+            drop(queue);
+            (stale, skipped, in_flight)
+        };
+        self.shared.peak_in_flight.fetch_max(
+            u64::try_from(in_flight).unwrap_or(u64::MAX),
+            Ordering::Relaxed,
+        );
+        let dropped = u64::from(stale.is_some()) + u64::from(skipped.is_some());
+        if dropped > 0 {
+            self.shared
+                .preview_dropped
+                .fetch_add(dropped, Ordering::Relaxed);
+        }
+        self.shared.recording_ready.notify_one();
+        if skipped.is_none() {
+            self.shared.preview_ready.notify_one();
+        }
+        // Dropped preview handles are released here, outside the lock.
+    }
+}
+
+impl Drop for TeeOutputHandler {
+    fn drop(&mut self) {
+        self.shared
+            .queue
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .closed = true;
+        self.shared.recording_ready.notify_all();
+        self.shared.preview_ready.notify_all();
+        for thread in self.threads.drain(..) {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl fmt::Debug for TeeOutputHandler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TeeOutputHandler")
+            .field("budget", &self.shared.budget)
+            .field("in_flight", &self.in_flight())
+            .field("preview_dropped_samples", &self.preview_dropped_samples())
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,203 @@
+//! Publish captured frames as a Syphon server
+//!
+//! Requires the `syphon` feature.
+//!
+//! [Syphon](https://syphon.github.io) shares GPU textures between macOS
+//! applications; OBS (through its Syphon source), Resolume, `MadMapper`
+//! and most VJ tools can receive from a Syphon server without copying
+//! pixels. [`SyphonServer`] publishes each captured `IOSurface` under a
+//! server name those tools list.
+//!
+//! ## Installing Syphon
+//!
+//! Syphon.framework is not part of macOS and this crate does not link it.
+//! It is loaded at runtime the first time a server is created, from the
+//! app bundle's `Frameworks` folder, `/Library/Frameworks` or
+//! `~/Library/Frameworks`. Call [`load_framework`] to load it from
+//! somewhere else. If the app already links Syphon, nothing is loaded.
+//!
+//! ## Pixel formats
+//!
+//! Syphon clients expect BGRA, so configure the stream with
+//! [`PixelFormat::BGRA`](crate::stream::configuration::PixelFormat::BGRA).
+//! Frames in other formats are rejected by [`SyphonServer::publish`].
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use std::sync::Mutex;
+//! use screencapturekit::prelude::*;
+//! use screencapturekit::syphon::SyphonServer;
+//!
+//! let server = Mutex::new(SyphonServer::new("Screen").unwrap());
+//!
+//! # let stream: SCStream = unimplemented!();
+//! let mut stream = stream;
+//! stream.add_output_handler(
+//!     move |sample: CMSampleBuffer, _of_type: SCStreamOutputType| {
+//!         let server = server.lock().unwrap();
+//!         if server.has_clients() {
+//!             let _ = server.publish_sample_buffer(&sample);
+//!         }
+//!     },
+//!     SCStreamOutputType::Screen,
+//! );
+//! ```
+
+use std::ffi::{c_void, CString};
+use std::fmt;
+use std::path::Path;
+use std::ptr::{self, NonNull};
+
+use crate::cm::{CMSampleBuffer, CMSampleBufferExt, IOSurface};
+use crate::error::SCError;
+use crate::metal::MetalDevice;
+
+/// Load Syphon.framework from `path`.
+///
+/// Only needed when the framework lives outside the default search paths
+/// (see the [module docs](self)). Loading is idempotent, and succeeds
+/// immediately if Syphon is already present in the process.
+///
+/// # Errors
+///
+/// Returns [`SCError::InvalidConfiguration`] if the path contains a NUL byte
+/// or the framework can't be loaded from it.
+pub fn load_framework(path: impl AsRef<Path>) -> Result<(), SCError> {
+    let path = path.as_ref();
+    let c_path = CString::new(path.to_string_lossy().as_bytes())
+        .map_err(|_| SCError::invalid_config("Syphon framework path contains a NUL byte"))?;
+    if unsafe { crate::ffi::sc_syphon_load_framework(c_path.as_ptr()) } {
+        Ok(())
+    } else {
+        Err(SCError::invalid_config(format!(
+            "failed to load Syphon.framework from {}",
+            path.display()
+        )))
+    }
+}
+
+/// A Syphon server that publishes captured frames to other applications.
+///
+/// The server is announced when created and withdrawn when dropped.
+pub struct SyphonServer {
+    ptr: NonNull<c_void>,
+    name: String,
+}
+
+impl SyphonServer {
+    /// Create a server named `name` on the system default Metal device.
+    ///
+    /// # Errors
+    ///
+    /// See [`with_device`](Self::with_device).
+    pub fn new(name: impl Into<String>) -> Result<Self, SCError> {
+        Self::create(name.into(), ptr::null_mut())
+    }
+
+    /// Create a server named `name` on `device`.
+    ///
+    /// Use the device your capture pipeline renders on so published frames
+    /// never cross GPUs.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidConfiguration`] if the name contains a NUL
+    /// byte, Syphon.framework can't be found, or Syphon refuses to create the
+    /// server.
+    pub fn with_device(name: impl Into<String>, device: &MetalDevice) -> Result<Self, SCError> {
+        Self::create(name.into(), device.as_ptr())
+    }
+
+    fn create(name: String, device: *mut c_void) -> Result<Self, SCError> {
+        let c_name = CString::new(name.as_str())
+            .map_err(|_| SCError::invalid_config("Syphon server name contains a NUL byte"))?;
+        if !unsafe { crate::ffi::sc_syphon_load_framework(ptr::null()) } {
+            return Err(SCError::invalid_config(
+                "Syphon.framework not found; install it or call syphon::load_framework",
+            ));
+        }
+        let ptr = unsafe { crate::ffi::sc_syphon_server_create(c_name.as_ptr(), device) };
+        match NonNull::new(ptr.cast_mut()) {
+            Some(ptr) => Ok(Self { ptr, name }),
+            None => Err(SCError::invalid_config(format!(
+                "failed to create Syphon server \"{name}\""
+            ))),
+        }
+    }
+
+    /// The name clients see for this server.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Whether any client is currently receiving frames.
+    ///
+    /// Check this before publishing to skip GPU work nobody will see.
+    pub fn has_clients(&self) -> bool {
+        unsafe { crate::ffi::sc_syphon_server_has_clients(self.ptr.as_ptr()) }
+    }
+
+    /// Publish one BGRA surface.
+    ///
+    /// The surface is marked in use until Syphon's GPU copy completes, so
+    /// `ScreenCaptureKit` cannot recycle it mid-blit.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidConfiguration`] if the surface is not BGRA
+    /// or can't be wrapped in a Metal texture.
+    pub fn publish(&self, surface: &IOSurface) -> Result<(), SCError> {
+        if unsafe {
+            crate::ffi::sc_syphon_server_publish_iosurface(self.ptr.as_ptr(), surface.as_ptr())
+        } {
+            Ok(())
+        } else {
+            Err(SCError::invalid_config(
+                "Syphon can only publish BGRA surfaces",
+            ))
+        }
+    }
+
+    /// Publish the frame in a captured sample buffer.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidConfiguration`] if the sample carries no
+    /// `IOSurface`-backed image (e.g. an idle frame or an audio sample), or
+    /// for the reasons listed on [`publish`](Self::publish).
+    pub fn publish_sample_buffer(&self, sample: &CMSampleBuffer) -> Result<(), SCError> {
+        let surface = sample
+            .image_buffer()
+            .and_then(|pixel_buffer| pixel_buffer.io_surface())
+            .ok_or_else(|| SCError::invalid_config("sample buffer has no IOSurface"))?;
+        self.publish(&surface)
+    }
+
+    /// Withdraw the server from the Syphon directory.
+    ///
+    /// Happens automatically on drop; publishing after this does nothing.
+    pub fn stop(&self) {
+        unsafe { crate::ffi::sc_syphon_server_stop(self.ptr.as_ptr()) }
+    }
+}
+
+impl Drop for SyphonServer {
+    fn drop(&mut self) {
+        unsafe { crate::ffi::sc_syphon_server_release(self.ptr.as_ptr()) }
+    }
+}
+
+impl fmt::Debug for SyphonServer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SyphonServer")
+            .field("name", &self.name)
+            .field("has_clients", &self.has_clients())
+            .finish_non_exhaustive()
+    }
+}
+
+// SAFETY: the Swift box holds a retained `SyphonMetalServer` and its own
+// command queue, neither tied to the creating thread. The type is not `Sync`,
+// so calls from several threads have to be serialized (e.g. by a `Mutex`).
+unsafe impl Send for SyphonServer {}
@@ -27,6 +27,7 @@
 //! ```
 
 use std::fmt;
+use std::time::Duration;
 
 /// Result type alias for `ScreenCaptureKit` operations
 ///
@@ -117,6 +118,16 @@ pub enum SCError {
     /// Stream operation error (generic)
     StreamError(String),
 
+    /// The stream is in the wrong state for the requested operation
+    ///
+    /// For example stopping a stream that was never started, or starting
+    /// one that is already running. See
+    /// [`stream::state`](crate::stream::state) for the allowed transitions.
+    InvalidState {
+        operation: String,
+        state: crate::stream::state::SCStreamState,
+    },
+
     /// Failed to start capture
     CaptureStartFailed(String),
 
@@ -153,6 +164,22 @@ pub enum SCError {
     /// Timeout error
     Timeout(String),
 
+    /// The caller gave up on the operation through an async API
+    /// `CancellationToken`
+    Cancelled(String),
+
+    /// [`SCStream::start_capture_with_timeout`](crate::stream::sc_stream::SCStream::start_capture_with_timeout)
+    /// got no answer from `ScreenCaptureKit` in time
+    ///
+    /// Carries a snapshot of the state most likely to explain the hang.
+    CaptureStartTimeout(CaptureStartDiagnostics),
+
+    /// Not enough free space on the recording's volume
+    ///
+    /// Returned by the disk-space preflight in
+    /// [`recording_output`](crate::recording_output) before a file is opened.
+    InsufficientDiskSpace { available: u64, required: u64 },
+
     /// Generic internal error
     InternalError(String),
 
@@ -167,6 +194,12 @@ pub enum SCError {
         code: SCStreamErrorCode,
         message: Option<String>,
     },
+
+    /// `NSError` from a framework domain other than `SCStreamErrorDomain`
+    ///
+    /// Keeps the domain, code and the common `userInfo` entries so callers can
+    /// tell, say, a TCC denial from a transient XPC failure.
+    NSError(NSErrorInfo),
 }
 
 impl fmt::Display for SCError {
@@ -185,6 +218,9 @@ impl fmt::Display for SCError {
             Self::WindowNotFound(msg) => write!(f, "Window not found: {msg}"),
             Self::ApplicationNotFound(msg) => write!(f, "Application not found: {msg}"),
             Self::StreamError(msg) => write!(f, "Stream error: {msg}"),
+            Self::InvalidState { operation, state } => {
+                write!(f, "Cannot {operation} while the stream is {state}")
+            }
             Self::CaptureStartFailed(msg) => write!(f, "Failed to start capture: {msg}"),
             Self::CaptureStopFailed(msg) => write!(f, "Failed to stop capture: {msg}"),
             Self::BufferLockError(msg) => write!(f, "Failed to lock pixel buffer: {msg}"),
@@ -206,6 +242,17 @@ impl fmt::Display for SCError {
             Self::FFIError(msg) => write!(f, "FFI error: {msg}"),
             Self::NullPointer(msg) => write!(f, "Null pointer: {msg}"),
             Self::Timeout(msg) => write!(f, "Operation timed out: {msg}"),
+            Self::Cancelled(msg) => write!(f, "Operation cancelled: {msg}"),
+            Self::CaptureStartTimeout(diagnostics) => {
+                write!(f, "Capture start timed out: {diagnostics}")
+            }
+            Self::InsufficientDiskSpace {
+                available,
+                required,
+            } => write!(
+                f,
+                "Insufficient disk space: {available} bytes free, {required} required"
+            ),
             Self::InternalError(msg) => write!(f, "Internal error: {msg}"),
             Self::OSError { code, message } => write!(f, "OS error {code}: {message}"),
             Self::SCStreamError { code, message } => {
@@ -215,6 +262,7 @@ impl fmt::Display for SCError {
                     write!(f, "SCStream error: {code}")
                 }
             }
+            Self::NSError(info) => write!(f, "{info}"),
         }
     }
 }
@@ -466,6 +514,46 @@ impl SCError {
         )
     }
 
+    /// Create an error from an `NSError` reported by a framework
+    ///
+    /// Errors in `SCStreamErrorDomain` with a known code become
+    /// [`SCError::SCStreamError`]; everything else is kept whole as
+    /// [`SCError::NSError`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use screencapturekit::error::{NSErrorInfo, SCError, SCStreamErrorCode, SC_STREAM_ERROR_DOMAIN};
+    ///
+    /// let err = SCError::from_ns_error(NSErrorInfo::new(SC_STREAM_ERROR_DOMAIN, -3801, "Declined"));
+    /// assert_eq!(err.stream_error_code(), Some(SCStreamErrorCode::UserDeclined));
+    ///
+    /// let err = SCError::from_ns_error(NSErrorInfo::new("NSCocoaErrorDomain", 4, "Missing"));
+    /// assert_eq!(err.ns_error().map(|e| e.code), Some(4));
+    /// ```
+    pub fn from_ns_error(info: NSErrorInfo) -> Self {
+        if info.domain == SC_STREAM_ERROR_DOMAIN {
+            let known = i32::try_from(info.code)
+                .ok()
+                .and_then(SCStreamErrorCode::from_raw);
+            if let Some(code) = known {
+                return Self::SCStreamError {
+                    code,
+                    message: Some(info.message),
+                };
+            }
+        }
+        Self::NSError(info)
+    }
+
+    /// Get the framework error if this is an [`SCError::NSError`]
+    pub const fn ns_error(&self) -> Option<&NSErrorInfo> {
+        match self {
+            Self::NSError(info) => Some(info),
+            _ => None,
+        }
+    }
+
     /// Get the `SCStreamErrorCode` if this is an `SCStreamError`
     ///
     /// # Examples
@@ -485,11 +573,240 @@ impl SCError {
             _ => None,
         }
     }
+
+    /// Whether this error means Screen Recording permission is missing
+    ///
+    /// True for [`SCError::PermissionDenied`] and for
+    /// [`SCStreamErrorCode::UserDeclined`], which is how a running stream
+    /// reports that the user revoked (or macOS expired) its approval.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use screencapturekit::error::{SCError, SCStreamErrorCode};
+    ///
+    /// assert!(SCError::from_stream_error_code(SCStreamErrorCode::UserDeclined).is_permission_denied());
+    /// assert!(SCError::permission_denied("no access").is_permission_denied());
+    /// assert!(!SCError::from_stream_error_code(SCStreamErrorCode::FailedToStart).is_permission_denied());
+    /// ```
+    pub fn is_permission_denied(&self) -> bool {
+        matches!(self, Self::PermissionDenied(_))
+            || self.stream_error_code() == Some(SCStreamErrorCode::UserDeclined)
+    }
+
+    /// Whether the caller cancelled the operation
+    ///
+    /// ```
+    /// use screencapturekit::error::SCError;
+    ///
+    /// assert!(SCError::Cancelled("content query".into()).is_cancelled());
+    /// assert!(!SCError::Timeout("content query".into()).is_cancelled());
+    /// ```
+    pub const fn is_cancelled(&self) -> bool {
+        matches!(self, Self::Cancelled(_))
+    }
 }
 
 /// Error domain for `ScreenCaptureKit` stream errors
 pub const SC_STREAM_ERROR_DOMAIN: &str = "com.apple.ScreenCaptureKit.SCStreamErrorDomain";
 
+/// Domain of errors raised by the Swift bridge itself, e.g. when an API is
+/// missing on the running macOS version
+pub const BRIDGE_ERROR_DOMAIN: &str = "ScreenCaptureKitBridge";
+
+/// Deepest `NSUnderlyingErrorKey` chain copied out of an `NSError`
+const MAX_UNDERLYING_DEPTH: usize = 8;
+
+/// The parts of an `NSError` that survive the trip across the FFI boundary
+///
+/// # Examples
+///
+/// ```
+/// use screencapturekit::error::NSErrorInfo;
+///
+/// let info = NSErrorInfo::new("NSPOSIXErrorDomain", 1, "Operation not permitted")
+///     .with_failure_reason("Sandbox denied access");
+/// assert_eq!(info.code, 1);
+/// assert!(info.to_string().contains("NSPOSIXErrorDomain"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct NSErrorInfo {
+    /// `NSError.domain`
+    pub domain: String,
+    /// `NSError.code`
+    pub code: i64,
+    /// `NSError.localizedDescription`
+    pub message: String,
+    /// `NSLocalizedFailureReasonErrorKey`
+    pub failure_reason: Option<String>,
+    /// `NSLocalizedRecoverySuggestionErrorKey`
+    pub recovery_suggestion: Option<String>,
+    /// `NSUnderlyingErrorKey`
+    pub underlying: Option<Box<Self>>,
+}
+
+impl NSErrorInfo {
+    /// Create an error with no `userInfo` entries
+    pub fn new(domain: impl Into<String>, code: i64, message: impl Into<String>) -> Self {
+        Self {
+            domain: domain.into(),
+            code,
+            message: message.into(),
+            failure_reason: None,
+            recovery_suggestion: None,
+            underlying: None,
+        }
+    }
+
+    /// Set the failure reason
+    #[must_use]
+    pub fn with_failure_reason(mut self, reason: impl Into<String>) -> Self {
+        self.failure_reason = Some(reason.into());
+        self
+    }
+
+    /// Set the recovery suggestion
+    #[must_use]
+    pub fn with_recovery_suggestion(mut self, suggestion: impl Into<String>) -> Self {
+        self.recovery_suggestion = Some(suggestion.into());
+        self
+    }
+
+    /// Set the underlying error
+    #[must_use]
+    pub fn with_underlying(mut self, underlying: Self) -> Self {
+        self.underlying = Some(Box::new(underlying));
+        self
+    }
+
+    /// Whether the error is in `domain` with `code`
+    pub fn is(&self, domain: &str, code: i64) -> bool {
+        self.domain == domain && self.code == code
+    }
+
+    /// This error followed by its chain of underlying errors
+    pub fn chain(&self) -> impl Iterator<Item = &Self> {
+        std::iter::successors(Some(self), |info| info.underlying.as_deref())
+    }
+
+    /// Copy an `NSError` lent by the Swift bridge.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be a live `NSError` for the duration of the call.
+    pub(crate) unsafe fn from_borrowed(ptr: *const std::ffi::c_void) -> Self {
+        unsafe { Self::copy_from(ptr, MAX_UNDERLYING_DEPTH) }
+    }
+
+    unsafe fn copy_from(ptr: *const std::ffi::c_void, depth: usize) -> Self {
+        use crate::ffi;
+        use crate::utils::ffi_string::ffi_string_owned;
+
+        // SAFETY (whole block): the caller guarantees `ptr` is a live NSError,
+        // and an underlying error is kept alive by its parent's userInfo.
+        let user_info = |key: &[u8]| unsafe {
+            ffi_string_owned(|| ffi::sc_error_copy_user_info_string(ptr, key.as_ptr().cast()))
+        };
+        let underlying = if depth == 0 {
+            None
+        } else {
+            let next = unsafe { ffi::sc_error_get_underlying(ptr) };
+            (!next.is_null()).then(|| Box::new(unsafe { Self::copy_from(next, depth - 1) }))
+        };
+        unsafe {
+            Self {
+                domain: ffi_string_owned(|| ffi::sc_error_copy_domain(ptr)).unwrap_or_default(),
+                code: ffi::sc_error_get_code(ptr),
+                message: ffi_string_owned(|| ffi::sc_error_copy_description(ptr))
+                    .unwrap_or_else(|| "Unknown error".to_string()),
+                failure_reason: user_info(b"NSLocalizedFailureReason\0"),
+                recovery_suggestion: user_info(b"NSLocalizedRecoverySuggestion\0"),
+                underlying,
+            }
+        }
+    }
+}
+
+impl fmt::Display for NSErrorInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({} {})", self.message, self.domain, self.code)?;
+        if let Some(reason) = &self.failure_reason {
+            write!(f, ": {reason}")?;
+        }
+        Ok(())
+    }
+}
+
+/// State captured when a capture start times out
+///
+/// `start_capture` waits for `ScreenCaptureKit` to call back, which it may
+/// never do while a permission prompt is up or `WindowServer` is wedged.
+/// These fields are the usual suspects, collected at the moment of giving
+/// up.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use screencapturekit::error::{CaptureStartDiagnostics, SCError};
+///
+/// let diagnostics = CaptureStartDiagnostics::new(Duration::from_secs(5));
+/// let err = SCError::CaptureStartTimeout(diagnostics);
+/// assert!(err.to_string().contains("5s"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct CaptureStartDiagnostics {
+    /// How long the start was given
+    pub timeout: Duration,
+    /// Whether the process had screen recording permission
+    /// (`CGPreflightScreenCaptureAccess`)
+    pub screen_capture_permitted: bool,
+    /// Short description of the stream's content filter
+    pub filter: String,
+    /// Output handlers registered on the stream
+    pub output_handlers: usize,
+    /// Other streams in this process that were capturing
+    pub capturing_streams: usize,
+}
+
+impl CaptureStartDiagnostics {
+    /// Diagnostics with only the timeout filled in
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            timeout,
+            screen_capture_permitted: false,
+            filter: String::new(),
+            output_handlers: 0,
+            capturing_streams: 0,
+        }
+    }
+}
+
+impl fmt::Display for CaptureStartDiagnostics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "no response within {:?} (screen recording permission: {}; filter: {}; \
+             {} output handler(s); {} other capturing stream(s))",
+            self.timeout,
+            if self.screen_capture_permitted {
+                "granted"
+            } else {
+                "not granted"
+            },
+            if self.filter.is_empty() {
+                "unknown"
+            } else {
+                &self.filter
+            },
+            self.output_handlers,
+            self.capturing_streams,
+        )
+    }
+}
+
 /// Error codes from Apple's `SCStreamError.Code`
 ///
 /// These correspond to the error codes returned by `ScreenCaptureKit` operations.
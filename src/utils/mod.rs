@@ -8,6 +8,9 @@
 //! error variants that don't belong in the framework-agnostic foundation.
 
 pub mod error;
+pub(crate) mod object_lock;
+pub(crate) mod poller;
+pub mod recovery;
 pub(crate) mod retained;
 
 pub use apple_cf::utils::FourCharCode;
@@ -0,0 +1,171 @@
+//! Access control for `SCStreamConfiguration` objects shared between Rust
+//! handles.
+//!
+//! `Clone` on `SCStreamConfiguration` only retains the underlying object, so
+//! two Rust values on two threads can end up driving the same
+//! non-thread-safe Objective-C instance. Rust's `&`/`&mut` rules cannot see
+//! that sharing; this module enforces it at runtime instead.
+//!
+//! Only the stream configuration is guarded. `SCContentFilter` and
+//! `SCRecordingOutputConfiguration` clones share their object the same way
+//! but don't go through this module, so they must not be changed while a
+//! clone is in use on another thread.
+//!
+//! Every FFI call on a configuration goes through [`read`] (for `&self`
+//! getters) or [`write`] (for `&mut self` setters):
+//!
+//! - **Release builds** take a striped [`RwLock`], so a setter on one clone
+//!   waits for a getter on another rather than racing it.
+//! - **Debug builds** track accesses per object and panic when a write
+//!   overlaps any other access. Both sides of such an overlap are on
+//!   different threads (FFI calls never nest), so this is always a sharing
+//!   bug, and it is reported where it happens instead of as a crash inside
+//!   the Objective-C runtime later.
+
+#[cfg(debug_assertions)]
+use std::collections::HashMap;
+use std::ffi::c_void;
+#[cfg(debug_assertions)]
+use std::sync::Mutex;
+use std::sync::PoisonError;
+#[cfg(not(debug_assertions))]
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+#[cfg(not(debug_assertions))]
+const STRIPES: usize = 64;
+
+#[cfg(not(debug_assertions))]
+#[allow(clippy::declare_interior_mutable_const)]
+const STRIPE: RwLock<()> = RwLock::new(());
+
+#[cfg(not(debug_assertions))]
+static LOCKS: [RwLock<()>; STRIPES] = [STRIPE; STRIPES];
+
+#[cfg(debug_assertions)]
+#[derive(Default)]
+struct AccessState {
+    readers: usize,
+    writer: bool,
+}
+
+#[cfg(debug_assertions)]
+static ACCESSES: Mutex<Option<HashMap<usize, AccessState>>> = Mutex::new(None);
+
+/// Held for the duration of one FFI call on a guarded object.
+///
+/// Obtain the pointer through [`ObjectAccess::ptr`] inside the call
+/// expression so the guard lives until the call returns.
+pub struct ObjectAccess {
+    ptr: *const c_void,
+    _held: Held,
+}
+
+enum Held {
+    #[cfg(not(debug_assertions))]
+    Read(#[allow(dead_code)] RwLockReadGuard<'static, ()>),
+    #[cfg(not(debug_assertions))]
+    Write(#[allow(dead_code)] RwLockWriteGuard<'static, ()>),
+    #[cfg(debug_assertions)]
+    Tracked { key: usize, write: bool },
+}
+
+impl ObjectAccess {
+    /// The guarded object.
+    pub const fn ptr(&self) -> *const c_void {
+        self.ptr
+    }
+}
+
+#[cfg(not(debug_assertions))]
+fn stripe(ptr: *const c_void) -> &'static RwLock<()> {
+    // Objective-C objects are at least 16-byte aligned; skip the zero bits.
+    &LOCKS[(ptr as usize >> 4) % STRIPES]
+}
+
+#[cfg(debug_assertions)]
+fn track(ptr: *const c_void, type_name: &'static str, write: bool) -> ObjectAccess {
+    let key = ptr as usize;
+    let mut accesses = ACCESSES.lock().unwrap_or_else(PoisonError::into_inner);
+    let state = accesses
+        .get_or_insert_with(HashMap::new)
+        .entry(key)
+        .or_default();
+    let conflict = state.writer || (write && state.readers > 0);
+    if !conflict {
+        if write {
+            state.writer = true;
+        } else {
+            state.readers += 1;
+        }
+    }
+    drop(accesses);
+    assert!(
+        !conflict,
+        "{type_name} at {ptr:p} was mutated on one thread while another thread was \
+         using it. Clones share the underlying Objective-C object; give each thread \
+         its own deep_copy() instead."
+    );
+    ObjectAccess {
+        ptr,
+        _held: Held::Tracked { key, write },
+    }
+}
+
+/// Shared access to the object at `ptr`.
+pub fn read(ptr: *const c_void, type_name: &'static str) -> ObjectAccess {
+    #[cfg(debug_assertions)]
+    {
+        track(ptr, type_name, false)
+    }
+    #[cfg(not(debug_assertions))]
+    {
+        let _ = type_name;
+        ObjectAccess {
+            ptr,
+            _held: Held::Read(stripe(ptr).read().unwrap_or_else(PoisonError::into_inner)),
+        }
+    }
+}
+
+/// Exclusive access to the object at `ptr`.
+pub fn write(ptr: *const c_void, type_name: &'static str) -> ObjectAccess {
+    #[cfg(debug_assertions)]
+    {
+        track(ptr, type_name, true)
+    }
+    #[cfg(not(debug_assertions))]
+    {
+        let _ = type_name;
+        ObjectAccess {
+            ptr,
+            _held: Held::Write(stripe(ptr).write().unwrap_or_else(PoisonError::into_inner)),
+        }
+    }
+}
+
+#[cfg(debug_assertions)]
+impl Drop for Held {
+    fn drop(&mut self) {
+        let Self::Tracked { key, write } = *self;
+        let mut accesses = ACCESSES.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(map) = accesses.as_mut() {
+            if let Some(state) = map.get_mut(&key) {
+                if write {
+                    state.writer = false;
+                } else {
+                    state.readers = state.readers.saturating_sub(1);
+                }
+                if !state.writer && state.readers == 0 {
+                    map.remove(&key);
+                }
+            }
+        }
+        drop(accesses);
+    }
+}
+
+impl std::fmt::Debug for ObjectAccess {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ObjectAccess").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,117 @@
+//! Background threads that poll until their owner is dropped.
+//!
+//! Stream watchers such as
+//! [`ApplicationFollower`](crate::stream::application_follower::ApplicationFollower)
+//! each run one thread that wakes up every so often, looks at the system
+//! and reacts. [`Poller`] owns
+//! that thread: it names and spawns it, lets it sleep until the next round
+//! or until it is told to stop, keeps the last error it reported, and stops
+//! and joins it when dropped.
+//!
+//! The stop flag's lock is only held while waiting, never while the thread
+//! does its work, so stopping never waits for more than the round in
+//! progress.
+
+use std::sync::{Arc, Condvar, Mutex, PoisonError};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use crate::error::SCError;
+
+/// What a [`Poller`]'s thread sees of its owner.
+pub(crate) struct PollerContext {
+    stopped: Mutex<bool>,
+    wake: Condvar,
+    last_error: Mutex<Option<SCError>>,
+}
+
+impl PollerContext {
+    /// Sleep for `timeout`, or until the poller is stopped. Returns whether
+    /// it was stopped.
+    pub(crate) fn sleep(&self, timeout: Duration) -> bool {
+        let stopped = self.stopped.lock().unwrap_or_else(PoisonError::into_inner);
+        *self
+            .wake
+            .wait_timeout_while(stopped, timeout, |stopped| !*stopped)
+            .unwrap_or_else(PoisonError::into_inner)
+            .0
+    }
+
+    /// Replace the error reported by [`Poller::last_error`].
+    pub(crate) fn set_last_error(&self, error: Option<SCError>) {
+        *self
+            .last_error
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = error;
+    }
+}
+
+/// A named background thread, stopped and joined on drop.
+pub(crate) struct Poller {
+    context: Arc<PollerContext>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Poller {
+    /// Run `body` on a new thread called `name`.
+    ///
+    /// `body` should return soon after [`PollerContext::sleep`] reports
+    /// that the poller was stopped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InternalError`] if the operating system refuses
+    /// to spawn the thread.
+    pub(crate) fn spawn(
+        name: &str,
+        body: impl FnOnce(&PollerContext) + Send + 'static,
+    ) -> Result<Self, SCError> {
+        let context = Arc::new(PollerContext {
+            stopped: Mutex::new(false),
+            wake: Condvar::new(),
+            last_error: Mutex::new(None),
+        });
+        let thread = {
+            let context = Arc::clone(&context);
+            std::thread::Builder::new()
+                .name(format!("sc-{name}"))
+                .spawn(move || body(&context))
+                .map_err(|e| {
+                    SCError::internal_error(format!("failed to spawn {name} thread: {e}"))
+                })?
+        };
+        Ok(Self {
+            context,
+            thread: Some(thread),
+        })
+    }
+
+    /// The last error the thread reported, if any.
+    pub(crate) fn last_error(&self) -> Option<SCError> {
+        self.context
+            .last_error
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Stop the thread and wait for it to finish. Does nothing the second
+    /// time.
+    pub(crate) fn stop(&mut self) {
+        *self
+            .context
+            .stopped
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = true;
+        self.context.wake.notify_all();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for Poller {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
@@ -0,0 +1,336 @@
+//! What a user can do about an [`SCError`]
+//!
+//! Error messages from `ScreenCaptureKit` describe what failed ("The user
+//! declined TCCs for application, window, display capture"), not what to do
+//! next. [`SCError::recovery_hint`] turns every error into a sentence an app
+//! can show as-is, says whether retrying may help, and names the System
+//! Settings pane to open when the fix is a permission or free disk space.
+//!
+//! ```no_run
+//! use screencapturekit::error::SCError;
+//!
+//! fn report(err: &SCError) {
+//!     let hint = err.recovery_hint();
+//!     eprintln!("{err}\n{hint}");
+//!     if let Some(pane) = hint.settings_pane() {
+//!         let _ = pane.open();
+//!     }
+//! }
+//! ```
+
+use std::ffi::CString;
+use std::fmt;
+
+use super::error::{SCError, SCStreamErrorCode};
+
+/// A System Settings pane that fixes an error
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum SettingsPane {
+    /// Privacy & Security > Screen & System Audio Recording
+    ScreenRecording,
+    /// Privacy & Security > Microphone
+    Microphone,
+    /// Privacy & Security > Camera
+    Camera,
+    /// General > Storage
+    Storage,
+}
+
+impl SettingsPane {
+    /// The `x-apple.systempreferences:` URL that opens the pane
+    ///
+    /// ```
+    /// use screencapturekit::error::SettingsPane;
+    ///
+    /// assert!(SettingsPane::ScreenRecording.url().ends_with("Privacy_ScreenCapture"));
+    /// ```
+    #[must_use]
+    pub const fn url(self) -> &'static str {
+        match self {
+            Self::ScreenRecording => {
+                "x-apple.systempreferences:com.apple.preference.security?Privacy_ScreenCapture"
+            }
+            Self::Microphone => {
+                "x-apple.systempreferences:com.apple.preference.security?Privacy_Microphone"
+            }
+            Self::Camera => {
+                "x-apple.systempreferences:com.apple.preference.security?Privacy_Camera"
+            }
+            Self::Storage => "x-apple.systempreferences:com.apple.settings.Storage",
+        }
+    }
+
+    /// Open System Settings at this pane
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InternalError`] if the pane could not be opened.
+    pub fn open(self) -> Result<(), SCError> {
+        let url = CString::new(self.url()).map_err(|e| SCError::internal_error(e.to_string()))?;
+        if unsafe { crate::ffi::sc_open_settings_url(url.as_ptr()) } {
+            Ok(())
+        } else {
+            Err(SCError::internal_error(format!(
+                "Failed to open the {self} settings pane"
+            )))
+        }
+    }
+}
+
+impl fmt::Display for SettingsPane {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ScreenRecording => write!(f, "Screen Recording"),
+            Self::Microphone => write!(f, "Microphone"),
+            Self::Camera => write!(f, "Camera"),
+            Self::Storage => write!(f, "Storage"),
+        }
+    }
+}
+
+/// An actionable message for an [`SCError`]
+///
+/// Displays as its [`message`](Self::message).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RecoveryHint {
+    message: String,
+    settings_pane: Option<SettingsPane>,
+    retryable: bool,
+}
+
+impl RecoveryHint {
+    fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            settings_pane: None,
+            retryable: false,
+        }
+    }
+
+    const fn settings(mut self, pane: SettingsPane) -> Self {
+        self.settings_pane = Some(pane);
+        self
+    }
+
+    const fn retry(mut self) -> Self {
+        self.retryable = true;
+        self
+    }
+
+    /// What to do, as a sentence for the user
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The System Settings pane to open, if the fix lives there
+    #[must_use]
+    pub const fn settings_pane(&self) -> Option<SettingsPane> {
+        self.settings_pane
+    }
+
+    /// The deep link to [`settings_pane`](Self::settings_pane), for apps
+    /// that open URLs themselves
+    #[must_use]
+    pub fn settings_url(&self) -> Option<&'static str> {
+        self.settings_pane.map(SettingsPane::url)
+    }
+
+    /// Whether trying the same operation again may succeed without the
+    /// user changing anything
+    #[must_use]
+    pub const fn is_retryable(&self) -> bool {
+        self.retryable
+    }
+}
+
+impl fmt::Display for RecoveryHint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+fn grant_screen_recording() -> RecoveryHint {
+    RecoveryHint::new(
+        "Grant Screen Recording permission in System Settings → Privacy & Security → \
+         Screen & System Audio Recording, then restart the app.",
+    )
+    .settings(SettingsPane::ScreenRecording)
+}
+
+fn wait_for_transition() -> RecoveryHint {
+    RecoveryHint::new("Wait for the capture to finish starting or stopping before trying again.")
+        .retry()
+}
+
+fn report_bug() -> RecoveryHint {
+    RecoveryHint::new("Try again. If this keeps happening, report it with the error details.")
+        .retry()
+}
+
+impl SCError {
+    /// What the user or app can do about this error
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use screencapturekit::error::{SCError, SettingsPane};
+    ///
+    /// let hint = SCError::permission_denied("Screen Recording").recovery_hint();
+    /// assert_eq!(hint.settings_pane(), Some(SettingsPane::ScreenRecording));
+    /// assert!(hint.message().contains("System Settings"));
+    /// assert!(!hint.is_retryable());
+    /// ```
+    #[must_use]
+    pub fn recovery_hint(&self) -> RecoveryHint {
+        match self {
+            Self::InvalidConfiguration(_)
+            | Self::InvalidDimension { .. }
+            | Self::InvalidPixelFormat(_) => RecoveryHint::new(
+                "Change the capture settings: one of the values described in the error isn't \
+                 supported.",
+            ),
+            Self::NoShareableContent(_) => RecoveryHint::new(
+                "Make sure Screen Recording permission is granted and a display is connected \
+                 and awake.",
+            )
+            .settings(SettingsPane::ScreenRecording)
+            .retry(),
+            Self::DisplayNotFound(_) => {
+                RecoveryHint::new("The display was disconnected. Choose another display.")
+            }
+            Self::WindowNotFound(_) => {
+                RecoveryHint::new("The window was closed. Choose another window.")
+            }
+            Self::ApplicationNotFound(_) => RecoveryHint::new(
+                "The application isn't running. Open it or choose another application.",
+            ),
+            Self::InvalidState { .. } => wait_for_transition(),
+            Self::StreamError(_) | Self::CaptureStartFailed(_) | Self::ScreenshotError(_) => {
+                RecoveryHint::new(
+                    "Try again. If capture keeps failing, check that Screen Recording \
+                     permission is still granted.",
+                )
+                .settings(SettingsPane::ScreenRecording)
+                .retry()
+            }
+            Self::CaptureStopFailed(_) => RecoveryHint::new(
+                "Capture may already have stopped; no action is needed unless frames keep \
+                 arriving.",
+            ),
+            Self::BufferLockError(_) | Self::BufferUnlockError(_) | Self::InvalidBuffer(_) => {
+                RecoveryHint::new("Skip this frame; the next one is usually fine.").retry()
+            }
+            Self::PermissionDenied(message) => permission_hint(message),
+            Self::FeatureNotAvailable {
+                feature,
+                required_version,
+            } => RecoveryHint::new(format!(
+                "{feature} needs macOS {required_version} or later. Update macOS or turn the \
+                 feature off."
+            )),
+            Self::FFIError(_)
+            | Self::NullPointer(_)
+            | Self::InternalError(_)
+            | Self::OSError { .. } => report_bug(),
+            Self::Timeout(_) => {
+                RecoveryHint::new("The system is busy. Wait a moment and try again.").retry()
+            }
+            Self::Cancelled(_) => RecoveryHint::new("The operation was cancelled; nothing to do."),
+            Self::CaptureStartTimeout(diagnostics) => {
+                if diagnostics.screen_capture_permitted {
+                    RecoveryHint::new(
+                        "Capture didn't start in time. Stop other screen recordings and try \
+                         again; restarting the Mac clears a stuck capture service.",
+                    )
+                    .retry()
+                } else {
+                    grant_screen_recording()
+                }
+            }
+            Self::InsufficientDiskSpace { .. } => {
+                RecoveryHint::new("Free up disk space or record to another volume.")
+                    .settings(SettingsPane::Storage)
+            }
+            Self::SCStreamError { code, .. } => code.recovery_hint(),
+            Self::NSError(info) => info
+                .recovery_suggestion
+                .as_ref()
+                .map_or_else(report_bug, |suggestion| {
+                    RecoveryHint::new(suggestion.clone()).retry()
+                }),
+        }
+    }
+}
+
+/// `permission_denied` is shared by the screen, microphone and camera
+/// paths; the message says which.
+fn permission_hint(message: &str) -> RecoveryHint {
+    let message = message.to_ascii_lowercase();
+    if message.contains("camera") {
+        RecoveryHint::new("Allow camera access in System Settings → Privacy & Security → Camera.")
+            .settings(SettingsPane::Camera)
+    } else if message.contains("microphone") {
+        RecoveryHint::new(
+            "Allow microphone access in System Settings → Privacy & Security → Microphone.",
+        )
+        .settings(SettingsPane::Microphone)
+    } else {
+        grant_screen_recording()
+    }
+}
+
+impl SCStreamErrorCode {
+    /// What the user or app can do about a stream error with this code
+    ///
+    /// Same as [`SCError::recovery_hint`] for an
+    /// [`SCError::SCStreamError`] with this code.
+    #[must_use]
+    pub fn recovery_hint(self) -> RecoveryHint {
+        match self {
+            Self::UserDeclined => grant_screen_recording(),
+            Self::MissingEntitlements => RecoveryHint::new(
+                "The app isn't signed with the entitlements capture needs. Reinstall it or \
+                 contact its developer.",
+            ),
+            Self::FailedToStart | Self::InternalError | Self::RemovingStream => report_bug(),
+            Self::FailedApplicationConnectionInvalid
+            | Self::FailedApplicationConnectionInterrupted => RecoveryHint::new(
+                "The connection to the capture service was lost. Start capture again.",
+            )
+            .retry(),
+            Self::FailedNoMatchingApplicationContext
+            | Self::NoWindowList
+            | Self::NoDisplayList
+            | Self::NoCaptureSource => RecoveryHint::new(
+                "The selected content is gone. Refresh the list of displays and windows and \
+                 choose again.",
+            ),
+            Self::AttemptToStartStreamState
+            | Self::AttemptToStopStreamState
+            | Self::AttemptToUpdateFilterState
+            | Self::AttemptToConfigState => wait_for_transition(),
+            Self::InvalidParameter => RecoveryHint::new(
+                "Change the capture settings: one of them isn't supported for this content.",
+            ),
+            Self::UserStopped => RecoveryHint::new(
+                "Sharing was stopped from the menu bar. Ask before starting it again.",
+            ),
+            Self::FailedToStartAudioCapture | Self::FailedToStopAudioCapture => RecoveryHint::new(
+                "Check the audio output device in System Settings → Sound and try again.",
+            )
+            .retry(),
+            Self::FailedToStartMicrophoneCapture => RecoveryHint::new(
+                "Allow microphone access in System Settings → Privacy & Security → Microphone \
+                 and check that the microphone is connected.",
+            )
+            .settings(SettingsPane::Microphone),
+            Self::SystemStoppedStream => RecoveryHint::new(
+                "macOS stopped the capture, e.g. for a secure input field or display sleep. \
+                 Start it again once the screen is available.",
+            )
+            .retry(),
+        }
+    }
+}
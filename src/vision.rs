@@ -0,0 +1,287 @@
+//! Read text on screen with the Vision framework
+//!
+//! Automation scripts often capture the screen only to find a label, read
+//! an error message or wait for a button to appear. [`TextRecognitionExt`]
+//! runs Vision's `VNRecognizeTextRequest` on a captured [`CVPixelBuffer`],
+//! a screenshot [`CGImage`] or a [`Frame`], and returns each recognized line
+//! with its confidence and position.
+//!
+//! Recognition runs synchronously and takes tens to hundreds of
+//! milliseconds per frame with [`RecognitionLevel::Accurate`]; run it off
+//! the stream's output queue, for example with
+//! [`add_output_handler_pooled`](crate::stream::SCStream::add_output_handler_pooled),
+//! or on screenshots.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use screencapturekit::prelude::*;
+//! use screencapturekit::screenshot_manager::SCScreenshotManager;
+//! use screencapturekit::vision::{TextRecognitionExt, TextRecognitionOptions};
+//!
+//! # fn main() -> Result<(), SCError> {
+//! let content = SCShareableContent::get()?;
+//! let display = &content.displays()[0];
+//! let filter = SCContentFilter::for_display(display).with_excluding_windows(&[]).build();
+//! let config = SCStreamConfiguration::new()
+//!     .with_width(display.width() * 2)
+//!     .with_height(display.height() * 2);
+//! let image = SCScreenshotManager::capture_image(&filter, &config)?;
+//!
+//! let options = TextRecognitionOptions::default().with_languages(["en-US"]);
+//! for line in image.recognize_text(&options)? {
+//!     let rect = line.pixel_rect(image.width(), image.height());
+//!     println!("{:?} at {rect:?} ({:.0}%)", line.text, line.confidence * 100.0);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::ffi::{c_void, CString};
+
+use crate::cg::CGRect;
+use crate::cv::CVPixelBuffer;
+use crate::error::SCError;
+use crate::frame::Frame;
+use crate::utils::ffi_string::ffi_string_owned;
+use crate::CGImage;
+
+/// Trade-off between speed and accuracy, `VNRequestTextRecognitionLevel`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum RecognitionLevel {
+    /// Character detection only; fast enough to run on every frame.
+    Fast,
+    /// A neural network that also reads stylized and small text.
+    #[default]
+    Accurate,
+}
+
+/// Settings for a text recognition request.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextRecognitionOptions {
+    level: RecognitionLevel,
+    language_correction: bool,
+    languages: Vec<String>,
+    minimum_text_height: f32,
+}
+
+impl Default for TextRecognitionOptions {
+    fn default() -> Self {
+        Self {
+            level: RecognitionLevel::Accurate,
+            language_correction: true,
+            languages: Vec::new(),
+            minimum_text_height: 0.0,
+        }
+    }
+}
+
+impl TextRecognitionOptions {
+    /// Accurate recognition with language correction, in the user's
+    /// languages.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Choose between fast and accurate recognition.
+    #[must_use]
+    pub const fn with_level(mut self, level: RecognitionLevel) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// Correct recognized words against a dictionary. Turn off when reading
+    /// identifiers, code or serial numbers.
+    #[must_use]
+    pub const fn with_language_correction(mut self, enabled: bool) -> Self {
+        self.language_correction = enabled;
+        self
+    }
+
+    /// Languages to recognize, as BCP 47 codes such as `"en-US"`, in order
+    /// of priority.
+    #[must_use]
+    pub fn with_languages<I, S>(mut self, languages: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.languages = languages.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Ignore text shorter than `height`, as a fraction of the image height.
+    /// `0.0` (the default) lets Vision decide.
+    #[must_use]
+    pub const fn with_minimum_text_height(mut self, height: f32) -> Self {
+        self.minimum_text_height = height;
+        self
+    }
+
+    /// The recognition level.
+    #[must_use]
+    pub const fn level(&self) -> RecognitionLevel {
+        self.level
+    }
+
+    /// Whether language correction is on.
+    #[must_use]
+    pub const fn language_correction(&self) -> bool {
+        self.language_correction
+    }
+
+    /// Languages to recognize; empty for the default.
+    #[must_use]
+    pub fn languages(&self) -> &[String] {
+        &self.languages
+    }
+
+    /// Smallest text height, as a fraction of the image height.
+    #[must_use]
+    pub const fn minimum_text_height(&self) -> f32 {
+        self.minimum_text_height
+    }
+}
+
+/// One line of recognized text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecognizedText {
+    /// The most likely reading of the line.
+    pub text: String,
+    /// Vision's confidence in `text`, `0.0..=1.0`.
+    pub confidence: f32,
+    /// Where the line is, normalized to `0.0..=1.0` with the origin at the
+    /// top-left of the image.
+    pub bounding_box: CGRect,
+}
+
+impl RecognizedText {
+    /// [`bounding_box`](Self::bounding_box) in pixels of a `width`×`height`
+    /// image.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn pixel_rect(&self, width: usize, height: usize) -> CGRect {
+        let (width, height) = (width as f64, height as f64);
+        CGRect::new(
+            self.bounding_box.origin.x * width,
+            self.bounding_box.origin.y * height,
+            self.bounding_box.size.width * width,
+            self.bounding_box.size.height * height,
+        )
+    }
+}
+
+/// Recognize text in captured images.
+pub trait TextRecognitionExt {
+    /// Run text recognition and return every line found, in Vision's
+    /// reading order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidConfiguration`] if a language code contains
+    /// a NUL byte, or [`SCError::InternalError`] with Vision's message if
+    /// the request fails, for example for an unsupported language.
+    fn recognize_text(
+        &self,
+        options: &TextRecognitionOptions,
+    ) -> Result<Vec<RecognizedText>, SCError>;
+}
+
+/// Owned `sc_vision_recognize_text` results.
+struct Results(*const c_void);
+
+impl Drop for Results {
+    fn drop(&mut self) {
+        unsafe { crate::ffi::sc_vision_text_results_release(self.0) };
+    }
+}
+
+impl Results {
+    fn lines(&self) -> Vec<RecognizedText> {
+        let count = unsafe { crate::ffi::sc_vision_text_results_count(self.0) };
+        (0..count)
+            .map(|index| {
+                let text = unsafe {
+                    ffi_string_owned(|| crate::ffi::sc_vision_text_results_string(self.0, index))
+                }
+                .unwrap_or_default();
+                let confidence =
+                    unsafe { crate::ffi::sc_vision_text_results_confidence(self.0, index) };
+                let mut rect = [0.0f64; 4];
+                unsafe { crate::ffi::sc_vision_text_results_box(self.0, index, rect.as_mut_ptr()) };
+                RecognizedText {
+                    text,
+                    confidence,
+                    bounding_box: CGRect::new(rect[0], rect[1], rect[2], rect[3]),
+                }
+            })
+            .collect()
+    }
+}
+
+fn recognize(
+    pixel_buffer: *const c_void,
+    image: *const c_void,
+    options: &TextRecognitionOptions,
+) -> Result<Vec<RecognizedText>, SCError> {
+    let languages = if options.languages.is_empty() {
+        None
+    } else {
+        Some(
+            CString::new(options.languages.join(","))
+                .map_err(|_| SCError::invalid_config("recognition language contains a NUL byte"))?,
+        )
+    };
+    let mut error: *mut i8 = std::ptr::null_mut();
+    let ptr = unsafe {
+        crate::ffi::sc_vision_recognize_text(
+            pixel_buffer,
+            image,
+            options.level == RecognitionLevel::Accurate,
+            options.language_correction,
+            languages.as_ref().map_or(std::ptr::null(), |l| l.as_ptr()),
+            options.minimum_text_height,
+            &mut error,
+        )
+    };
+    if ptr.is_null() {
+        let message =
+            unsafe { ffi_string_owned(|| error) }.unwrap_or_else(|| "unknown error".to_string());
+        return Err(SCError::internal_error(format!(
+            "text recognition failed: {message}"
+        )));
+    }
+    Ok(Results(ptr).lines())
+}
+
+impl TextRecognitionExt for CVPixelBuffer {
+    fn recognize_text(
+        &self,
+        options: &TextRecognitionOptions,
+    ) -> Result<Vec<RecognizedText>, SCError> {
+        recognize(self.as_ptr(), std::ptr::null(), options)
+    }
+}
+
+impl TextRecognitionExt for CGImage {
+    fn recognize_text(
+        &self,
+        options: &TextRecognitionOptions,
+    ) -> Result<Vec<RecognizedText>, SCError> {
+        recognize(std::ptr::null(), self.as_ptr(), options)
+    }
+}
+
+impl TextRecognitionExt for Frame {
+    fn recognize_text(
+        &self,
+        options: &TextRecognitionOptions,
+    ) -> Result<Vec<RecognizedText>, SCError> {
+        match (self.pixel_buffer(), self.image()) {
+            (Some(buffer), _) => buffer.recognize_text(options),
+            (None, Some(image)) => image.recognize_text(options),
+            (None, None) => Err(SCError::InvalidBuffer("frame has no image".into())),
+        }
+    }
+}
@@ -0,0 +1,987 @@
+//! Run capture in an XPC helper process
+//!
+//! Requires the `xpc` feature.
+//!
+//! Capturing in a separate process keeps a crash in `ScreenCaptureKit`, a
+//! driver or your own frame code from taking the host app down, and lets the
+//! helper, not the host, own the screen recording permission prompt. The
+//! host drives the helper through a small typed protocol ([`XpcRequest`] in,
+//! [`XpcEvent`] out), and frames cross the process boundary as `IOSurface`s,
+//! so no pixels are copied.
+//!
+//! ## Helper
+//!
+//! Bundle an XPC service (`Contents/XPCServices/Capture.xpc`) whose `main`
+//! hands control to [`run_service`]. The ready-made [`CaptureService`]
+//! captures a display per connected host:
+//!
+//! ```no_run
+//! use screencapturekit::xpc::{run_service, CaptureService};
+//!
+//! fn main() {
+//!     run_service(CaptureService::new());
+//! }
+//! ```
+//!
+//! Implement [`XpcServiceHandler`] to run your own capture logic and answer
+//! through [`XpcPeer`]. A launchd agent listens with
+//! [`XpcListener::mach_service`] instead.
+//!
+//! ## Host
+//!
+//! ```no_run
+//! use screencapturekit::prelude::*;
+//! use screencapturekit::xpc::{XpcCaptureClient, XpcCaptureSettings, XpcEvent, XpcTarget};
+//!
+//! let client = XpcCaptureClient::connect(
+//!     XpcTarget::Service("com.example.app.Capture"),
+//!     |frame: CMSampleBuffer| {
+//!         // `frame.image_buffer()` wraps the helper's IOSurface
+//!     },
+//!     |event: XpcEvent| println!("capture helper: {event:?}"),
+//! )?;
+//! client.start(&XpcCaptureSettings::new().with_size(1920, 1080));
+//! # Ok::<(), screencapturekit::error::SCError>(())
+//! ```
+//!
+//! ## Surface lifetime
+//!
+//! The helper's capture pool reuses a surface once its stream is done with
+//! it, even if the host is still reading it. Use each frame promptly or copy
+//! it, and raise [`XpcCaptureSettings::with_queue_depth`] if the host falls
+//! behind.
+//!
+//! ## Helper crashes
+//!
+//! When the helper exits, the host receives [`XpcEvent::Interrupted`].
+//! launchd relaunches the helper on the next message, so send
+//! [`start`](XpcCaptureClient::start) again to resume.
+//! [`XpcEvent::Invalidated`] means the service can't be reached at all, for
+//! example because it isn't bundled with the app.
+
+use std::collections::HashMap;
+use std::ffi::{c_void, CString};
+use std::fmt;
+use std::ptr::{self, NonNull};
+use std::sync::Mutex;
+
+use crate::cm::{CMSampleBuffer, CMSampleBufferExt, IOSurface};
+use crate::error::SCError;
+use crate::shareable_content::{SCDisplay, SCShareableContent};
+use crate::stream::configuration::SCStreamConfiguration;
+use crate::stream::content_filter::SCContentFilter;
+use crate::stream::output_type::SCStreamOutputType;
+use crate::stream::SCStream;
+use crate::utils::panic_safe::catch_user_panic;
+
+/// Bumped whenever the wire format changes, so a stale helper is reported
+/// instead of misread.
+const PROTOCOL_VERSION: u8 = 1;
+
+const TAG_START: u8 = 1;
+const TAG_UPDATE: u8 = 2;
+const TAG_STOP: u8 = 3;
+const TAG_STARTED: u8 = 16;
+const TAG_UPDATED: u8 = 17;
+const TAG_STOPPED: u8 = 18;
+const TAG_FAILED: u8 = 19;
+const TAG_INTERRUPTED: u8 = 20;
+const TAG_INVALIDATED: u8 = 21;
+const TAG_FRAME: u8 = 32;
+
+const FRAME_PAYLOAD: [u8; 2] = [PROTOCOL_VERSION, TAG_FRAME];
+
+// Values of the Swift side's connection error codes.
+const XPC_ERROR_INTERRUPTED: i32 = 0;
+const XPC_ERROR_INVALIDATED: i32 = 1;
+
+// MARK: - Protocol
+
+/// What to capture, sent with [`XpcRequest::Start`] and
+/// [`XpcRequest::Update`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct XpcCaptureSettings {
+    display_id: Option<u32>,
+    width: u32,
+    height: u32,
+    frame_rate: f64,
+    shows_cursor: bool,
+    queue_depth: u32,
+}
+
+impl Default for XpcCaptureSettings {
+    fn default() -> Self {
+        Self {
+            display_id: None,
+            width: 0,
+            height: 0,
+            frame_rate: 60.0,
+            shows_cursor: true,
+            queue_depth: 3,
+        }
+    }
+}
+
+impl XpcCaptureSettings {
+    /// The main display at its own size, 60 fps, cursor shown
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Capture the display with this ID instead of the main display
+    #[must_use]
+    pub fn with_display_id(mut self, display_id: u32) -> Self {
+        self.display_id = Some(display_id);
+        self
+    }
+
+    /// Output size in pixels; `0` keeps the display's size
+    #[must_use]
+    pub fn with_size(mut self, width: u32, height: u32) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    /// Maximum frames per second
+    #[must_use]
+    pub fn with_frame_rate(mut self, frame_rate: f64) -> Self {
+        self.frame_rate = frame_rate;
+        self
+    }
+
+    /// Whether the cursor is drawn into frames
+    #[must_use]
+    pub fn with_shows_cursor(mut self, shows_cursor: bool) -> Self {
+        self.shows_cursor = shows_cursor;
+        self
+    }
+
+    /// How many surfaces the helper's capture pool cycles through
+    #[must_use]
+    pub fn with_queue_depth(mut self, queue_depth: u32) -> Self {
+        self.queue_depth = queue_depth;
+        self
+    }
+
+    /// The requested display, or `None` for the main display
+    pub fn display_id(&self) -> Option<u32> {
+        self.display_id
+    }
+
+    /// The requested output size; `0` means the display's size
+    pub fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    /// The requested maximum frame rate
+    pub fn frame_rate(&self) -> f64 {
+        self.frame_rate
+    }
+
+    /// Whether the cursor is drawn into frames
+    pub fn shows_cursor(&self) -> bool {
+        self.shows_cursor
+    }
+
+    /// The requested capture pool size
+    pub fn queue_depth(&self) -> u32 {
+        self.queue_depth
+    }
+
+    /// The stream configuration these settings describe for `display`
+    pub fn configuration(&self, display: &SCDisplay) -> SCStreamConfiguration {
+        let width = if self.width == 0 {
+            display.width()
+        } else {
+            self.width
+        };
+        let height = if self.height == 0 {
+            display.height()
+        } else {
+            self.height
+        };
+        SCStreamConfiguration::new()
+            .with_width(width)
+            .with_height(height)
+            .with_frame_rate(self.frame_rate)
+            .with_shows_cursor(self.shows_cursor)
+            .with_queue_depth(self.queue_depth)
+    }
+
+    fn write(&self, out: &mut Vec<u8>) {
+        out.push(u8::from(self.display_id.is_some()));
+        out.extend_from_slice(&self.display_id.unwrap_or(0).to_le_bytes());
+        out.extend_from_slice(&self.width.to_le_bytes());
+        out.extend_from_slice(&self.height.to_le_bytes());
+        out.extend_from_slice(&self.frame_rate.to_le_bytes());
+        out.push(u8::from(self.shows_cursor));
+        out.extend_from_slice(&self.queue_depth.to_le_bytes());
+    }
+
+    fn read(reader: &mut Reader<'_>) -> Result<Self, SCError> {
+        let has_display = reader.bool()?;
+        let display_id = reader.u32()?;
+        Ok(Self {
+            display_id: has_display.then_some(display_id),
+            width: reader.u32()?,
+            height: reader.u32()?,
+            frame_rate: reader.f64()?,
+            shows_cursor: reader.bool()?,
+            queue_depth: reader.u32()?,
+        })
+    }
+}
+
+/// A request from the host to the capture helper
+#[derive(Debug, Clone, PartialEq)]
+pub enum XpcRequest {
+    /// Start capturing, replacing any capture already running for this host
+    Start(XpcCaptureSettings),
+    /// Apply new settings to the running capture
+    Update(XpcCaptureSettings),
+    /// Stop capturing
+    Stop,
+}
+
+impl XpcRequest {
+    /// Encode for the wire
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = vec![PROTOCOL_VERSION];
+        match self {
+            Self::Start(settings) => {
+                out.push(TAG_START);
+                settings.write(&mut out);
+            }
+            Self::Update(settings) => {
+                out.push(TAG_UPDATE);
+                settings.write(&mut out);
+            }
+            Self::Stop => out.push(TAG_STOP),
+        }
+        out
+    }
+
+    /// Decode a request produced by [`encode`](Self::encode)
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::FFIError`] if the bytes are truncated, come from a
+    /// different protocol version, or aren't a request.
+    pub fn decode(bytes: &[u8]) -> Result<Self, SCError> {
+        let mut reader = Reader::new(bytes)?;
+        let request = match reader.u8()? {
+            TAG_START => Self::Start(XpcCaptureSettings::read(&mut reader)?),
+            TAG_UPDATE => Self::Update(XpcCaptureSettings::read(&mut reader)?),
+            TAG_STOP => Self::Stop,
+            tag => return Err(malformed(format!("unknown request tag {tag}"))),
+        };
+        reader.finish()?;
+        Ok(request)
+    }
+}
+
+/// A notification from the capture helper to the host
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum XpcEvent {
+    /// Capture started; frames follow
+    Started,
+    /// New settings were applied
+    Updated,
+    /// Capture stopped
+    Stopped,
+    /// A request failed or the stream stopped with an error
+    Failed(String),
+    /// The helper exited or crashed; the next request relaunches it
+    ///
+    /// Reported by the local connection, never sent by the helper.
+    Interrupted,
+    /// The connection is closed for good
+    ///
+    /// Reported by the local connection, never sent by the helper.
+    Invalidated,
+}
+
+impl XpcEvent {
+    /// Encode for the wire
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = vec![PROTOCOL_VERSION];
+        match self {
+            Self::Started => out.push(TAG_STARTED),
+            Self::Updated => out.push(TAG_UPDATED),
+            Self::Stopped => out.push(TAG_STOPPED),
+            Self::Failed(message) => {
+                out.push(TAG_FAILED);
+                let length = u32::try_from(message.len()).unwrap_or(u32::MAX);
+                out.extend_from_slice(&length.to_le_bytes());
+                out.extend_from_slice(&message.as_bytes()[..length as usize]);
+            }
+            Self::Interrupted => out.push(TAG_INTERRUPTED),
+            Self::Invalidated => out.push(TAG_INVALIDATED),
+        }
+        out
+    }
+
+    /// Decode an event produced by [`encode`](Self::encode)
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::FFIError`] if the bytes are truncated, come from a
+    /// different protocol version, or aren't an event.
+    pub fn decode(bytes: &[u8]) -> Result<Self, SCError> {
+        let mut reader = Reader::new(bytes)?;
+        let event = match reader.u8()? {
+            TAG_STARTED => Self::Started,
+            TAG_UPDATED => Self::Updated,
+            TAG_STOPPED => Self::Stopped,
+            TAG_FAILED => {
+                let length = reader.u32()? as usize;
+                Self::Failed(String::from_utf8_lossy(reader.take(length)?).into_owned())
+            }
+            TAG_INTERRUPTED => Self::Interrupted,
+            TAG_INVALIDATED => Self::Invalidated,
+            tag => return Err(malformed(format!("unknown event tag {tag}"))),
+        };
+        reader.finish()?;
+        Ok(event)
+    }
+}
+
+fn malformed(reason: impl fmt::Display) -> SCError {
+    SCError::ffi_error(format!("malformed XPC message: {reason}"))
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8]) -> Result<Self, SCError> {
+        let mut reader = Self { bytes };
+        match reader.u8()? {
+            PROTOCOL_VERSION => Ok(reader),
+            version => Err(malformed(format!(
+                "protocol version {version}, expected {PROTOCOL_VERSION}"
+            ))),
+        }
+    }
+
+    fn take(&mut self, count: usize) -> Result<&'a [u8], SCError> {
+        if self.bytes.len() < count {
+            return Err(malformed("truncated"));
+        }
+        let (head, rest) = self.bytes.split_at(count);
+        self.bytes = rest;
+        Ok(head)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], SCError> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.take(N)?);
+        Ok(array)
+    }
+
+    fn u8(&mut self) -> Result<u8, SCError> {
+        Ok(self.array::<1>()?[0])
+    }
+
+    fn bool(&mut self) -> Result<bool, SCError> {
+        Ok(self.u8()? != 0)
+    }
+
+    fn u32(&mut self) -> Result<u32, SCError> {
+        Ok(u32::from_le_bytes(self.array()?))
+    }
+
+    fn f64(&mut self) -> Result<f64, SCError> {
+        Ok(f64::from_le_bytes(self.array()?))
+    }
+
+    fn finish(&self) -> Result<(), SCError> {
+        if self.bytes.is_empty() {
+            Ok(())
+        } else {
+            Err(malformed("trailing bytes"))
+        }
+    }
+}
+
+// MARK: - Transport
+
+/// An endpoint of an [`XpcListener`], which [`XpcCaptureClient`] can connect
+/// to with [`XpcTarget::Endpoint`]
+pub struct XpcEndpoint {
+    ptr: NonNull<c_void>,
+}
+
+impl Drop for XpcEndpoint {
+    fn drop(&mut self) {
+        unsafe { crate::ffi::sc_xpc_endpoint_release(self.ptr.as_ptr()) }
+    }
+}
+
+impl fmt::Debug for XpcEndpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("XpcEndpoint").finish_non_exhaustive()
+    }
+}
+
+// SAFETY: XPC endpoints are immutable, reference-counted objects.
+unsafe impl Send for XpcEndpoint {}
+unsafe impl Sync for XpcEndpoint {}
+
+/// Which helper an [`XpcCaptureClient`] connects to
+#[derive(Debug, Clone, Copy)]
+pub enum XpcTarget<'a> {
+    /// An XPC service bundled in the app's `Contents/XPCServices`
+    Service(&'a str),
+    /// A Mach service registered with launchd, e.g. by a launch agent
+    MachService(&'a str),
+    /// An in-process or forwarded [`XpcListener`]
+    Endpoint(&'a XpcEndpoint),
+}
+
+fn service_name(name: &str) -> Result<CString, SCError> {
+    CString::new(name).map_err(|_| SCError::invalid_config("XPC service name contains a NUL byte"))
+}
+
+fn send(connection: NonNull<c_void>, payload: &[u8], surface: Option<&IOSurface>, seconds: f64) {
+    let surface = surface.map_or(ptr::null_mut(), IOSurface::as_ptr);
+    let length = isize::try_from(payload.len()).unwrap_or(isize::MAX);
+    unsafe {
+        crate::ffi::sc_xpc_connection_send(
+            connection.as_ptr(),
+            payload.as_ptr(),
+            length,
+            surface,
+            seconds,
+        );
+    }
+}
+
+/// Adopt the message bytes and frame the Swift side handed to a callback.
+///
+/// # Safety
+///
+/// `payload` must point to `length` readable bytes (or be NULL), and
+/// `frame` must be NULL or a +1 `CMSampleBuffer`.
+unsafe fn received<'a>(
+    payload: *const u8,
+    length: isize,
+    frame: *const c_void,
+) -> (&'a [u8], Option<CMSampleBuffer>) {
+    let bytes = match usize::try_from(length) {
+        Ok(length) if !payload.is_null() => unsafe { std::slice::from_raw_parts(payload, length) },
+        _ => &[],
+    };
+    let frame = (!frame.is_null()).then(|| unsafe { CMSampleBuffer::from_ptr(frame.cast_mut()) });
+    (bytes, frame)
+}
+
+// MARK: - Host
+
+struct ClientContext {
+    on_frame: Box<dyn Fn(CMSampleBuffer) + Send + Sync>,
+    on_event: Box<dyn Fn(XpcEvent) + Send + Sync>,
+}
+
+impl ClientContext {
+    fn event(&self, event: XpcEvent) {
+        catch_user_panic("XPC event handler", || (self.on_event)(event));
+    }
+}
+
+extern "C" fn client_message(
+    context: *mut c_void,
+    _peer: *const c_void,
+    payload: *const u8,
+    length: isize,
+    frame: *const c_void,
+) {
+    // SAFETY: `context` is the `ClientContext` Swift keeps alive until it
+    // calls `release_client_context`; the buffers follow `received`'s rules.
+    let context = unsafe { &*context.cast::<ClientContext>() };
+    let (bytes, frame) = unsafe { received(payload, length, frame) };
+    if bytes == FRAME_PAYLOAD {
+        if let Some(frame) = frame {
+            catch_user_panic("XPC frame handler", || (context.on_frame)(frame));
+        }
+        return;
+    }
+    match XpcEvent::decode(bytes) {
+        Ok(event) => context.event(event),
+        Err(error) => context.event(XpcEvent::Failed(error.to_string())),
+    }
+}
+
+extern "C" fn client_error(context: *mut c_void, _peer: *const c_void, code: i32) {
+    // SAFETY: see `client_message`.
+    let context = unsafe { &*context.cast::<ClientContext>() };
+    context.event(match code {
+        XPC_ERROR_INTERRUPTED => XpcEvent::Interrupted,
+        XPC_ERROR_INVALIDATED => XpcEvent::Invalidated,
+        _ => XpcEvent::Failed("XPC connection error".to_string()),
+    });
+}
+
+extern "C" fn release_client_context(context: *mut c_void) {
+    drop(unsafe { Box::from_raw(context.cast::<ClientContext>()) });
+}
+
+/// The host's connection to a capture helper
+///
+/// Frames and events arrive on a private queue, one at a time. Requests are
+/// sent asynchronously; the helper answers each with an [`XpcEvent`].
+/// Dropping the client closes the connection, which stops the helper's
+/// capture for this host.
+pub struct XpcCaptureClient {
+    connection: NonNull<c_void>,
+}
+
+impl XpcCaptureClient {
+    /// Connect to `target`
+    ///
+    /// The helper is launched lazily by the first request. `on_frame`
+    /// receives each captured frame as a sample buffer backed by the
+    /// helper's `IOSurface`; `on_event` receives helper replies and
+    /// connection changes.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidConfiguration`] if the service name
+    /// contains a NUL byte, or [`SCError::FFIError`] if the connection
+    /// can't be created.
+    pub fn connect(
+        target: XpcTarget<'_>,
+        on_frame: impl Fn(CMSampleBuffer) + Send + Sync + 'static,
+        on_event: impl Fn(XpcEvent) + Send + Sync + 'static,
+    ) -> Result<Self, SCError> {
+        let (kind, name, endpoint) = match target {
+            XpcTarget::Service(name) => (0, Some(service_name(name)?), ptr::null()),
+            XpcTarget::MachService(name) => (1, Some(service_name(name)?), ptr::null()),
+            XpcTarget::Endpoint(endpoint) => (2, None, endpoint.ptr.as_ptr().cast_const()),
+        };
+        let context = Box::into_raw(Box::new(ClientContext {
+            on_frame: Box::new(on_frame),
+            on_event: Box::new(on_event),
+        }));
+        let ptr = unsafe {
+            crate::ffi::sc_xpc_connection_create(
+                kind,
+                name.as_ref().map_or(ptr::null(), |name| name.as_ptr()),
+                endpoint,
+                context.cast(),
+                client_message,
+                client_error,
+                release_client_context,
+            )
+        };
+        NonNull::new(ptr.cast_mut())
+            .map(|connection| Self { connection })
+            .ok_or_else(|| SCError::ffi_error("failed to create XPC connection"))
+    }
+
+    /// Send a request to the helper
+    pub fn send(&self, request: &XpcRequest) {
+        send(self.connection, &request.encode(), None, 0.0);
+    }
+
+    /// Ask the helper to start capturing
+    pub fn start(&self, settings: &XpcCaptureSettings) {
+        self.send(&XpcRequest::Start(settings.clone()));
+    }
+
+    /// Ask the helper to apply new settings
+    pub fn update(&self, settings: &XpcCaptureSettings) {
+        self.send(&XpcRequest::Update(settings.clone()));
+    }
+
+    /// Ask the helper to stop capturing
+    pub fn stop(&self) {
+        self.send(&XpcRequest::Stop);
+    }
+
+    /// The helper's process ID, once it is running
+    pub fn pid(&self) -> Option<i32> {
+        let pid = unsafe { crate::ffi::sc_xpc_connection_pid(self.connection.as_ptr()) };
+        (pid > 0).then_some(pid)
+    }
+}
+
+impl Drop for XpcCaptureClient {
+    fn drop(&mut self) {
+        unsafe {
+            crate::ffi::sc_xpc_connection_cancel(self.connection.as_ptr());
+            crate::ffi::sc_xpc_connection_release(self.connection.as_ptr());
+        }
+    }
+}
+
+impl fmt::Debug for XpcCaptureClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("XpcCaptureClient")
+            .field("pid", &self.pid())
+            .finish()
+    }
+}
+
+// SAFETY: XPC connections may be used from any thread.
+unsafe impl Send for XpcCaptureClient {}
+unsafe impl Sync for XpcCaptureClient {}
+
+// MARK: - Helper
+
+/// A host connected to the helper
+///
+/// Clones refer to the same connection, so a clone can be moved into a
+/// stream's output handler to forward frames.
+pub struct XpcPeer {
+    connection: NonNull<c_void>,
+}
+
+impl XpcPeer {
+    /// Retain the borrowed connection Swift passed to a callback.
+    fn retained(connection: *const c_void) -> Option<Self> {
+        let ptr = unsafe { crate::ffi::sc_xpc_connection_retain(connection) };
+        NonNull::new(ptr.cast_mut()).map(|connection| Self { connection })
+    }
+
+    /// Identifies this host for as long as it stays connected
+    pub fn id(&self) -> usize {
+        self.connection.as_ptr() as usize
+    }
+
+    /// The host's process ID
+    pub fn pid(&self) -> i32 {
+        unsafe { crate::ffi::sc_xpc_connection_pid(self.connection.as_ptr()) }
+    }
+
+    /// Send an event to the host
+    pub fn send_event(&self, event: &XpcEvent) {
+        send(self.connection, &event.encode(), None, 0.0);
+    }
+
+    /// Hand a surface to the host, presented at `seconds`
+    pub fn send_surface(&self, surface: &IOSurface, seconds: f64) {
+        send(self.connection, &FRAME_PAYLOAD, Some(surface), seconds);
+    }
+
+    /// Hand a captured frame to the host
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidBuffer`] if the sample carries no
+    /// `IOSurface`-backed image, e.g. an idle frame or an audio sample.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn send_frame(&self, sample: &CMSampleBuffer) -> Result<(), SCError> {
+        let surface = sample
+            .image_buffer()
+            .and_then(|pixel_buffer| pixel_buffer.io_surface())
+            .ok_or_else(|| SCError::InvalidBuffer("sample buffer has no IOSurface".to_string()))?;
+        let pts = sample.presentation_timestamp();
+        let seconds = if pts.is_valid() && pts.timescale > 0 {
+            pts.value as f64 / f64::from(pts.timescale)
+        } else {
+            0.0
+        };
+        self.send_surface(&surface, seconds);
+        Ok(())
+    }
+
+    /// Close the connection to this host
+    pub fn close(&self) {
+        unsafe { crate::ffi::sc_xpc_connection_cancel(self.connection.as_ptr()) }
+    }
+}
+
+impl Clone for XpcPeer {
+    fn clone(&self) -> Self {
+        Self::retained(self.connection.as_ptr()).expect("retaining a live connection")
+    }
+}
+
+impl Drop for XpcPeer {
+    fn drop(&mut self) {
+        unsafe { crate::ffi::sc_xpc_connection_release(self.connection.as_ptr()) }
+    }
+}
+
+impl fmt::Debug for XpcPeer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("XpcPeer")
+            .field("id", &self.id())
+            .field("pid", &self.pid())
+            .finish()
+    }
+}
+
+// SAFETY: see `XpcCaptureClient`.
+unsafe impl Send for XpcPeer {}
+unsafe impl Sync for XpcPeer {}
+
+/// Helper-side handler for host requests
+///
+/// Requests from one host arrive in order on that host's queue; different
+/// hosts may be served concurrently.
+pub trait XpcServiceHandler: Send + Sync {
+    /// Called for each request; reply with [`XpcPeer::send_event`]
+    fn did_receive_request(&self, peer: &XpcPeer, request: XpcRequest);
+
+    /// Called once a host disconnects or exits
+    fn peer_did_disconnect(&self, _peer: &XpcPeer) {}
+}
+
+impl<F> XpcServiceHandler for F
+where
+    F: Fn(&XpcPeer, XpcRequest) + Send + Sync + 'static,
+{
+    fn did_receive_request(&self, peer: &XpcPeer, request: XpcRequest) {
+        self(peer, request);
+    }
+}
+
+type ServiceContext = Box<dyn XpcServiceHandler>;
+
+extern "C" fn service_message(
+    context: *mut c_void,
+    peer: *const c_void,
+    payload: *const u8,
+    length: isize,
+    frame: *const c_void,
+) {
+    // SAFETY: `context` is the `ServiceContext` Swift keeps alive until it
+    // calls `release_service_context`; the buffers follow `received`'s rules.
+    let handler = unsafe { &*context.cast::<ServiceContext>() };
+    let (bytes, _frame) = unsafe { received(payload, length, frame) };
+    let Some(peer) = XpcPeer::retained(peer) else {
+        return;
+    };
+    match XpcRequest::decode(bytes) {
+        Ok(request) => catch_user_panic("XPC request handler", || {
+            handler.did_receive_request(&peer, request);
+        }),
+        Err(error) => peer.send_event(&XpcEvent::Failed(error.to_string())),
+    }
+}
+
+extern "C" fn service_error(context: *mut c_void, peer: *const c_void, code: i32) {
+    if code != XPC_ERROR_INVALIDATED {
+        return;
+    }
+    // SAFETY: see `service_message`.
+    let handler = unsafe { &*context.cast::<ServiceContext>() };
+    if let Some(peer) = XpcPeer::retained(peer) {
+        catch_user_panic("XPC disconnect handler", || {
+            handler.peer_did_disconnect(&peer);
+        });
+    }
+}
+
+extern "C" fn release_service_context(context: *mut c_void) {
+    drop(unsafe { Box::from_raw(context.cast::<ServiceContext>()) });
+}
+
+fn service_context(handler: impl XpcServiceHandler + 'static) -> *mut c_void {
+    let handler: ServiceContext = Box::new(handler);
+    Box::into_raw(Box::new(handler)).cast()
+}
+
+/// Run the current process as an XPC service bundled with the app
+///
+/// Call it from the service's `main`. It never returns; launchd ends the
+/// process when it is idle.
+pub fn run_service(handler: impl XpcServiceHandler + 'static) -> ! {
+    unsafe {
+        crate::ffi::sc_xpc_service_main(
+            service_context(handler),
+            service_message,
+            service_error,
+            release_service_context,
+        )
+    }
+}
+
+/// Accepts host connections outside a bundled XPC service
+///
+/// Use [`mach_service`](Self::mach_service) in a launch agent, or
+/// [`anonymous`](Self::anonymous) to serve an [`XpcEndpoint`] from the
+/// current process, e.g. in tests. Dropping the listener stops accepting
+/// new hosts.
+pub struct XpcListener {
+    ptr: NonNull<c_void>,
+}
+
+impl XpcListener {
+    /// Listen on the launchd Mach service `name`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidConfiguration`] if the name contains a NUL
+    /// byte, or [`SCError::FFIError`] if the listener can't be created.
+    pub fn mach_service(
+        name: &str,
+        handler: impl XpcServiceHandler + 'static,
+    ) -> Result<Self, SCError> {
+        let name = service_name(name)?;
+        Self::create(name.as_ptr(), handler)
+    }
+
+    /// Listen without a name; connect through [`endpoint`](Self::endpoint)
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::FFIError`] if the listener can't be created.
+    pub fn anonymous(handler: impl XpcServiceHandler + 'static) -> Result<Self, SCError> {
+        Self::create(ptr::null(), handler)
+    }
+
+    fn create(name: *const i8, handler: impl XpcServiceHandler + 'static) -> Result<Self, SCError> {
+        let ptr = unsafe {
+            crate::ffi::sc_xpc_listener_create(
+                name,
+                service_context(handler),
+                service_message,
+                service_error,
+                release_service_context,
+            )
+        };
+        NonNull::new(ptr.cast_mut())
+            .map(|ptr| Self { ptr })
+            .ok_or_else(|| SCError::ffi_error("failed to create XPC listener"))
+    }
+
+    /// An endpoint hosts can connect to
+    ///
+    /// # Panics
+    ///
+    /// Panics if XPC returns no endpoint, which it never does for a live
+    /// listener.
+    pub fn endpoint(&self) -> XpcEndpoint {
+        let ptr = unsafe { crate::ffi::sc_xpc_listener_copy_endpoint(self.ptr.as_ptr()) };
+        XpcEndpoint {
+            ptr: NonNull::new(ptr.cast_mut()).expect("xpc_endpoint_create never fails"),
+        }
+    }
+}
+
+impl Drop for XpcListener {
+    fn drop(&mut self) {
+        unsafe { crate::ffi::sc_xpc_listener_release(self.ptr.as_ptr()) }
+    }
+}
+
+impl fmt::Debug for XpcListener {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("XpcListener").finish_non_exhaustive()
+    }
+}
+
+// SAFETY: see `XpcCaptureClient`.
+unsafe impl Send for XpcListener {}
+unsafe impl Sync for XpcListener {}
+
+// MARK: - Capture service
+
+/// Ready-made helper that captures a display for each connected host
+///
+/// Answers [`XpcRequest::Start`] with [`XpcEvent::Started`] and streams
+/// frames until [`XpcRequest::Stop`] or the host disconnects. Failures are
+/// reported as [`XpcEvent::Failed`].
+#[derive(Default)]
+pub struct CaptureService {
+    streams: Mutex<HashMap<usize, SCStream>>,
+}
+
+impl CaptureService {
+    /// A service with no running captures
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn display(settings: &XpcCaptureSettings) -> Result<SCDisplay, SCError> {
+        let content = SCShareableContent::get()?;
+        let displays = content.displays();
+        match settings.display_id {
+            Some(id) => displays.into_iter().find(|d| d.display_id() == id),
+            None => displays.into_iter().next(),
+        }
+        .ok_or_else(|| {
+            SCError::DisplayNotFound(settings.display_id.map_or_else(
+                || "no displays available".to_string(),
+                |id| format!("display {id}"),
+            ))
+        })
+    }
+
+    fn start(&self, peer: &XpcPeer, settings: &XpcCaptureSettings) -> Result<(), SCError> {
+        let display = Self::display(settings)?;
+        let filter = SCContentFilter::for_display(&display).build();
+        let mut stream = SCStream::new(&filter, &settings.configuration(&display));
+        let host = peer.clone();
+        stream.add_output_handler(
+            move |sample: CMSampleBuffer, _of_type: SCStreamOutputType| {
+                let _ = host.send_frame(&sample);
+            },
+            SCStreamOutputType::Screen,
+        );
+        stream.start_capture()?;
+
+        let previous = self.lock().insert(peer.id(), stream);
+        if let Some(previous) = previous {
+            let _ = previous.stop_capture();
+        }
+        Ok(())
+    }
+
+    fn update(&self, peer: &XpcPeer, settings: &XpcCaptureSettings) -> Result<(), SCError> {
+        let display = Self::display(settings)?;
+        let stream = self
+            .lock()
+            .get(&peer.id())
+            .cloned()
+            .ok_or_else(|| SCError::InvalidState {
+                operation: "update capture".to_string(),
+                state: crate::stream::SCStreamState::Idle,
+            })?;
+        if settings.display_id.is_some() {
+            stream.update_content_filter(&SCContentFilter::for_display(&display).build())?;
+        }
+        stream.update_configuration(&settings.configuration(&display))
+    }
+
+    fn stop(&self, peer: &XpcPeer) -> Result<(), SCError> {
+        let stream = self.lock().remove(&peer.id());
+        stream.map_or(Ok(()), |stream| stream.stop_capture())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<usize, SCStream>> {
+        self.streams
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+impl XpcServiceHandler for CaptureService {
+    fn did_receive_request(&self, peer: &XpcPeer, request: XpcRequest) {
+        let result = match request {
+            XpcRequest::Start(settings) => self.start(peer, &settings).map(|()| XpcEvent::Started),
+            XpcRequest::Update(settings) => {
+                self.update(peer, &settings).map(|()| XpcEvent::Updated)
+            }
+            XpcRequest::Stop => self.stop(peer).map(|()| XpcEvent::Stopped),
+        };
+        peer.send_event(&result.unwrap_or_else(|error| XpcEvent::Failed(error.to_string())));
+    }
+
+    fn peer_did_disconnect(&self, peer: &XpcPeer) {
+        let _ = self.stop(peer);
+    }
+}
+
+impl fmt::Debug for CaptureService {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CaptureService")
+            .field("captures", &self.lock().len())
+            .finish()
+    }
+}
@@ -3,6 +3,7 @@
 import CoreMedia
 import CoreVideo
 import Foundation
+import IOSurface
 import ScreenCaptureKit
 import VideoToolbox
 
@@ -319,6 +320,66 @@ public func cm_sample_buffer_get_frame_info(
     return fields != 0
 }
 
+// Every attachment on the first sample, stringified for debugging.
+//
+// One "key\tvalue" line per attachment, sorted by key, with tabs and
+// newlines in values replaced by spaces. Rects are printed as
+// "(x, y, width, height)" and the frame status by name, so the dump is
+// readable without knowing CoreMedia's dictionary encodings. Returns NULL
+// when the buffer has no attachments; free with `sc_free_string`.
+@_cdecl("cm_sample_buffer_copy_attachment_dump")
+public func cm_sample_buffer_copy_attachment_dump(_ sampleBuffer: UnsafeMutableRawPointer) -> UnsafeMutablePointer<CChar>? {
+    let buffer = Unmanaged<CMSampleBuffer>.fromOpaque(sampleBuffer).takeUnretainedValue()
+
+    guard let attachments = CMSampleBufferGetSampleAttachmentsArray(buffer, createIfNecessary: false) as? [[CFString: Any]],
+          let attachment = attachments.first
+    else {
+        return nil
+    }
+
+    let lines = attachment
+        .map { key, value -> (String, String) in
+            let name = key as String
+            if name == SCStreamFrameInfo.status.rawValue,
+               let raw = value as? Int,
+               let status = SCFrameStatus(rawValue: raw) {
+                return (name, "\(raw) (\(frameStatusName(status)))")
+            }
+            return (name, describeAttachmentValue(value))
+        }
+        .sorted { $0.0 < $1.0 }
+        .map { key, value in
+            let flat = value
+                .replacingOccurrences(of: "\t", with: " ")
+                .replacingOccurrences(of: "\n", with: " ")
+            return "\(key)\t\(flat)"
+        }
+    return strdup(lines.joined(separator: "\n"))
+}
+
+private func frameStatusName(_ status: SCFrameStatus) -> String {
+    switch status {
+    case .complete: return "complete"
+    case .idle: return "idle"
+    case .blank: return "blank"
+    case .suspended: return "suspended"
+    case .started: return "started"
+    case .stopped: return "stopped"
+    @unknown default: return "unknown"
+    }
+}
+
+private func describeAttachmentValue(_ value: Any) -> String {
+    if let dict = value as? [String: Any],
+       let rect = CGRect(dictionaryRepresentation: dict as CFDictionary) {
+        return "(\(rect.origin.x), \(rect.origin.y), \(rect.size.width), \(rect.size.height))"
+    }
+    if let array = value as? [Any] {
+        return "[" + array.map(describeAttachmentValue).joined(separator: ", ") + "]"
+    }
+    return String(describing: value)
+}
+
 @_cdecl("cm_sample_buffer_get_presentation_timestamp_value")
 public func cm_sample_buffer_get_presentation_timestamp_value(_ sampleBuffer: UnsafeMutableRawPointer) -> Int64 {
     let buffer = Unmanaged<CMSampleBuffer>.fromOpaque(sampleBuffer).takeUnretainedValue()
@@ -437,6 +498,24 @@ public func cm_sample_buffer_make_data_ready(_ sampleBuffer: UnsafeMutableRawPoi
 
 // MARK: - Audio Buffer List Bridge
 
+/// Sample rate and channel count from the buffer's audio stream description.
+/// Returns false for buffers without an audio format.
+@_cdecl("cm_sample_buffer_get_audio_format")
+public func cm_sample_buffer_get_audio_format(_ sampleBuffer: UnsafeMutableRawPointer, _ outSampleRate: UnsafeMutablePointer<Float64>, _ outChannelCount: UnsafeMutablePointer<UInt32>) -> Bool {
+    let buffer = Unmanaged<CMSampleBuffer>.fromOpaque(sampleBuffer).takeUnretainedValue()
+
+    guard let format = CMSampleBufferGetFormatDescription(buffer),
+          CMFormatDescriptionGetMediaType(format) == kCMMediaType_Audio,
+          let asbd = CMAudioFormatDescriptionGetStreamBasicDescription(format)?.pointee
+    else {
+        return false
+    }
+
+    outSampleRate.pointee = asbd.mSampleRate
+    outChannelCount.pointee = asbd.mChannelsPerFrame
+    return true
+}
+
 @_cdecl("cm_sample_buffer_get_audio_buffer_list_num_buffers")
 public func cm_sample_buffer_get_audio_buffer_list_num_buffers(_ sampleBuffer: UnsafeMutableRawPointer) -> UInt32 {
     let buffer = Unmanaged<CMSampleBuffer>.fromOpaque(sampleBuffer).takeUnretainedValue()
@@ -546,6 +625,74 @@ public func cm_sample_buffer_get_audio_buffer_list(_ sampleBuffer: UnsafeMutable
     outBlockBuffer.pointee = Unmanaged.passRetained(blockBuffer).toOpaque()
 }
 
+/// Fill `outBuffers` (room for `capacity` entries) with the sample's audio
+/// buffers without allocating on the heap: the `AudioBufferList` is read
+/// into stack memory. Returns the number of buffers written, and a retained
+/// block buffer the caller must release; 0 (and no block buffer) for
+/// non-audio samples or lists with more than `capacity` buffers.
+@_cdecl("cm_sample_buffer_fill_audio_buffers")
+public func cm_sample_buffer_fill_audio_buffers(
+    _ sampleBuffer: UnsafeMutableRawPointer,
+    _ outBuffers: UnsafeMutablePointer<AudioBufferBridge>,
+    _ capacity: UInt32,
+    _ outBlockBuffer: UnsafeMutablePointer<UnsafeMutableRawPointer?>
+) -> UInt32 {
+    let buffer = Unmanaged<CMSampleBuffer>.fromOpaque(sampleBuffer).takeUnretainedValue()
+    outBlockBuffer.pointee = nil
+
+    var bufferListSizeNeeded = 0
+    CMSampleBufferGetAudioBufferListWithRetainedBlockBuffer(
+        buffer,
+        bufferListSizeNeededOut: &bufferListSizeNeeded,
+        bufferListOut: nil,
+        bufferListSize: 0,
+        blockBufferAllocator: nil,
+        blockBufferMemoryAllocator: nil,
+        flags: 0,
+        blockBufferOut: nil
+    )
+    guard bufferListSizeNeeded > 0,
+          bufferListSizeNeeded <= AudioBufferList.sizeInBytes(maximumBuffers: Int(capacity))
+    else {
+        return 0
+    }
+
+    // Small temporary allocations live on the stack.
+    return withUnsafeTemporaryAllocation(
+        byteCount: bufferListSizeNeeded,
+        alignment: MemoryLayout<AudioBufferList>.alignment
+    ) { storage -> UInt32 in
+        let list = storage.baseAddress!.bindMemory(to: AudioBufferList.self, capacity: 1)
+        var blockBuffer: CMBlockBuffer?
+        let status = CMSampleBufferGetAudioBufferListWithRetainedBlockBuffer(
+            buffer,
+            bufferListSizeNeededOut: nil,
+            bufferListOut: list,
+            bufferListSize: bufferListSizeNeeded,
+            blockBufferAllocator: nil,
+            blockBufferMemoryAllocator: nil,
+            flags: 0,
+            blockBufferOut: &blockBuffer
+        )
+        let buffers = UnsafeMutableAudioBufferListPointer(list)
+        guard status == noErr, let blockBuffer, buffers.count > 0, buffers.count <= Int(capacity) else {
+            return 0
+        }
+
+        // Same clamping as cm_sample_buffer_get_audio_buffer_list.
+        let blockDataLength = UInt32(truncatingIfNeeded: CMBlockBufferGetDataLength(blockBuffer))
+        for (index, audioBuffer) in buffers.enumerated() {
+            outBuffers[index] = AudioBufferBridge(
+                number_channels: audioBuffer.mNumberChannels,
+                data_bytes_size: min(audioBuffer.mDataByteSize, blockDataLength),
+                data_ptr: audioBuffer.mData
+            )
+        }
+        outBlockBuffer.pointee = Unmanaged.passRetained(blockBuffer).toOpaque()
+        return UInt32(buffers.count)
+    }
+}
+
 
 
 
@@ -713,6 +860,117 @@ public func cm_sample_buffer_create_for_image_buffer(
     return status
 }
 
+// MARK: - CMSampleBuffer Owned Copy
+
+/// Copy a sample buffer so it no longer references the capture pool.
+///
+/// Video frames are copied into a freshly allocated IOSurface-backed pixel
+/// buffer with the same format, size, attachments and timing, so holding the
+/// copy doesn't keep one of ScreenCaptureKit's `queueDepth` surfaces busy.
+/// Sample buffers without an image buffer (audio) get a new container that
+/// shares the immutable data block via `CMSampleBufferCreateCopy`.
+///
+/// Returns a retained CMSampleBuffer, or NULL with the OSStatus in
+/// `outStatus`.
+@_cdecl("cm_sample_buffer_create_owned_copy")
+public func cm_sample_buffer_create_owned_copy(
+    _ sampleBuffer: UnsafeMutableRawPointer,
+    _ outStatus: UnsafeMutablePointer<Int32>
+) -> UnsafeMutableRawPointer? {
+    let buffer = Unmanaged<CMSampleBuffer>.fromOpaque(sampleBuffer).takeUnretainedValue()
+
+    guard let source = CMSampleBufferGetImageBuffer(buffer) else {
+        var copy: CMSampleBuffer?
+        let status = CMSampleBufferCreateCopy(allocator: kCFAllocatorDefault, sampleBuffer: buffer, sampleBufferOut: &copy)
+        outStatus.pointee = status
+        guard status == noErr, let copy else { return nil }
+        return Unmanaged.passRetained(copy).toOpaque()
+    }
+
+    var destination: CVPixelBuffer?
+    let attributes: [CFString: Any] = [kCVPixelBufferIOSurfacePropertiesKey: [:] as CFDictionary]
+    var status = CVPixelBufferCreate(
+        kCFAllocatorDefault,
+        CVPixelBufferGetWidth(source),
+        CVPixelBufferGetHeight(source),
+        CVPixelBufferGetPixelFormatType(source),
+        attributes as CFDictionary,
+        &destination
+    )
+    guard status == kCVReturnSuccess, let destination else {
+        outStatus.pointee = status
+        return nil
+    }
+
+    CVPixelBufferLockBaseAddress(source, .readOnly)
+    CVPixelBufferLockBaseAddress(destination, [])
+    let planes = CVPixelBufferIsPlanar(source) ? CVPixelBufferGetPlaneCount(source) : 1
+    for plane in 0 ..< planes {
+        let planar = CVPixelBufferIsPlanar(source)
+        let src = planar ? CVPixelBufferGetBaseAddressOfPlane(source, plane) : CVPixelBufferGetBaseAddress(source)
+        let dst = planar ? CVPixelBufferGetBaseAddressOfPlane(destination, plane) : CVPixelBufferGetBaseAddress(destination)
+        let srcStride = planar ? CVPixelBufferGetBytesPerRowOfPlane(source, plane) : CVPixelBufferGetBytesPerRow(source)
+        let dstStride = planar ? CVPixelBufferGetBytesPerRowOfPlane(destination, plane) : CVPixelBufferGetBytesPerRow(destination)
+        let rows = planar ? CVPixelBufferGetHeightOfPlane(source, plane) : CVPixelBufferGetHeight(source)
+        guard let src, let dst else { continue }
+        let rowBytes = min(srcStride, dstStride)
+        for row in 0 ..< rows {
+            memcpy(dst + row * dstStride, src + row * srcStride, rowBytes)
+        }
+    }
+    CVPixelBufferUnlockBaseAddress(destination, [])
+    CVPixelBufferUnlockBaseAddress(source, .readOnly)
+    CVBufferPropagateAttachments(source, destination)
+
+    var formatDescription: CMFormatDescription?
+    status = CMVideoFormatDescriptionCreateForImageBuffer(
+        allocator: kCFAllocatorDefault,
+        imageBuffer: destination,
+        formatDescriptionOut: &formatDescription
+    )
+    guard status == noErr, let format = formatDescription else {
+        outStatus.pointee = status
+        return nil
+    }
+
+    var timing = CMSampleTimingInfo.invalid
+    CMSampleBufferGetSampleTimingInfo(buffer, at: 0, timingInfoOut: &timing)
+
+    var copy: CMSampleBuffer?
+    status = CMSampleBufferCreateReadyWithImageBuffer(
+        allocator: kCFAllocatorDefault,
+        imageBuffer: destination,
+        formatDescription: format,
+        sampleTiming: &timing,
+        sampleBufferOut: &copy
+    )
+    guard status == noErr, let copy else {
+        outStatus.pointee = status
+        return nil
+    }
+
+    // Carry over per-sample attachments (SCStreamFrameInfo: status, dirty
+    // rects, content rect, ...) and buffer-level attachments.
+    if let sourceAttachments = CMSampleBufferGetSampleAttachmentsArray(buffer, createIfNecessary: false) as? [NSDictionary],
+       let first = sourceAttachments.first,
+       let targetArray = CMSampleBufferGetSampleAttachmentsArray(copy, createIfNecessary: true),
+       CFArrayGetCount(targetArray) > 0
+    {
+        let target = unsafeBitCast(CFArrayGetValueAtIndex(targetArray, 0), to: CFMutableDictionary.self)
+        for (key, value) in first {
+            CFDictionarySetValue(
+                target,
+                Unmanaged.passUnretained(key as AnyObject).toOpaque(),
+                Unmanaged.passUnretained(value as AnyObject).toOpaque()
+            )
+        }
+    }
+    CMPropagateAttachments(buffer, destination: copy)
+
+    outStatus.pointee = noErr
+    return Unmanaged.passRetained(copy).toOpaque()
+}
+
 // MARK: - Hash Functions
 
 
@@ -758,3 +1016,51 @@ public func cm_sample_buffer_create_cg_image(
     }
     return OpaquePointer(Unmanaged.passRetained(image).toOpaque())
 }
+
+// MARK: - CVPixelBuffer Color Attachments
+
+/// Copy one of the pixel buffer's color tagging attachments as its raw
+/// CoreVideo string value (e.g. "ITU_R_709_2", "SMPTE_ST_2084_PQ").
+///
+/// `key`: 0 = color primaries, 1 = transfer function, 2 = YCbCr matrix.
+/// Returns false if the attachment is absent or doesn't fit in `buffer`.
+@_cdecl("cv_pixel_buffer_get_color_attachment")
+public func cv_pixel_buffer_get_color_attachment(
+    _ pixelBuffer: UnsafeMutableRawPointer,
+    _ key: Int32,
+    _ buffer: UnsafeMutablePointer<CChar>?,
+    _ bufferSize: Int
+) -> Bool {
+    guard let buffer, bufferSize > 0 else { return false }
+    let imageBuffer = Unmanaged<CVPixelBuffer>.fromOpaque(pixelBuffer).takeUnretainedValue()
+
+    let attachmentKey: CFString
+    switch key {
+    case 0: attachmentKey = kCVImageBufferColorPrimariesKey
+    case 1: attachmentKey = kCVImageBufferTransferFunctionKey
+    case 2: attachmentKey = kCVImageBufferYCbCrMatrixKey
+    default: return false
+    }
+
+    guard let value = CVBufferCopyAttachment(imageBuffer, attachmentKey, nil) as? String else {
+        return false
+    }
+    return value.withCString { src in
+        guard strlen(src) < bufferSize else { return false }
+        strlcpy(buffer, src, bufferSize)
+        return true
+    }
+}
+
+// MARK: - IOSurface Plane Access
+
+/// Base address of plane `plane` of a locked IOSurface, or nil if out of range.
+@_cdecl("iosurface_get_base_address_of_plane")
+public func iosurface_get_base_address_of_plane(
+    _ surface: UnsafeMutableRawPointer,
+    _ plane: Int
+) -> UnsafeMutableRawPointer? {
+    let ioSurface = Unmanaged<IOSurfaceRef>.fromOpaque(surface).takeUnretainedValue()
+    guard plane >= 0, plane < IOSurfaceGetPlaneCount(ioSurface) else { return nil }
+    return IOSurfaceGetBaseAddressOfPlane(ioSurface, plane)
+}
@@ -340,6 +340,45 @@ public func metal_command_buffer_wait_until_completed(_ cmdBuffer: UnsafeMutable
     buf.waitUntilCompleted()
 }
 
+/// Mark `surface` in use until the command buffer completes, so its owner
+/// (e.g. ScreenCaptureKit's frame pool) doesn't recycle it mid-read.
+private func holdUntilCompleted(_ buf: MTLCommandBuffer, _ surface: IOSurfaceRef) {
+    IOSurfaceIncrementUseCount(surface)
+    buf.addCompletedHandler { _ in IOSurfaceDecrementUseCount(surface) }
+}
+
+/// Hold an IOSurface until the command buffer completes
+@_cdecl("metal_command_buffer_hold_iosurface")
+public func metal_command_buffer_hold_iosurface(_ cmdBuffer: UnsafeMutableRawPointer, _ ioSurfacePtr: UnsafeMutableRawPointer) {
+    let buf = Unmanaged<MTLCommandBuffer>.fromOpaque(cmdBuffer).takeUnretainedValue()
+    let surface = Unmanaged<IOSurface>.fromOpaque(ioSurfacePtr).takeUnretainedValue()
+    holdUntilCompleted(buf, surface)
+}
+
+/// Hold the IOSurface backing a texture until the command buffer completes.
+/// Returns false if the texture isn't IOSurface-backed.
+@_cdecl("metal_command_buffer_hold_texture_iosurface")
+public func metal_command_buffer_hold_texture_iosurface(_ cmdBuffer: UnsafeMutableRawPointer, _ texture: UnsafeMutableRawPointer) -> Bool {
+    let buf = Unmanaged<MTLCommandBuffer>.fromOpaque(cmdBuffer).takeUnretainedValue()
+    let tex = Unmanaged<MTLTexture>.fromOpaque(texture).takeUnretainedValue()
+    guard let surface = tex.iosurface else {
+        return false
+    }
+    holdUntilCompleted(buf, surface)
+    return true
+}
+
+/// Call `callback(userData)` once the command buffer completes
+@_cdecl("metal_command_buffer_add_completed_handler")
+public func metal_command_buffer_add_completed_handler(
+    _ cmdBuffer: UnsafeMutableRawPointer,
+    _ callback: @escaping @convention(c) (UnsafeMutableRawPointer?) -> Void,
+    _ userData: UnsafeMutableRawPointer?
+) {
+    let buf = Unmanaged<MTLCommandBuffer>.fromOpaque(cmdBuffer).takeUnretainedValue()
+    buf.addCompletedHandler { _ in callback(userData) }
+}
+
 /// Release command buffer
 @_cdecl("metal_command_buffer_release")
 public func metal_command_buffer_release(_ cmdBuffer: UnsafeMutableRawPointer) {
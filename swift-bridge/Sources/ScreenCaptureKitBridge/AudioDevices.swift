@@ -1,6 +1,7 @@
 // Audio device enumeration using AVFoundation
 
 import AVFoundation
+import CoreAudio
 import Foundation
 
 /// Represents an audio input device (microphone)
@@ -112,3 +113,176 @@ public func getDefaultInputDeviceName(buffer: UnsafeMutablePointer<CChar>?, buff
         return true
     }
 }
+
+// MARK: - Core Audio Device Properties
+
+/// Resolve an AVCaptureDevice uniqueID (the Core Audio device UID) to an AudioDeviceID
+private func audioDeviceID(forUID uid: String) -> AudioDeviceID? {
+    var address = AudioObjectPropertyAddress(
+        mSelector: kAudioHardwarePropertyTranslateUIDToDevice,
+        mScope: kAudioObjectPropertyScopeGlobal,
+        mElement: kAudioObjectPropertyElementMain
+    )
+    var cfUID = uid as CFString
+    var deviceID = AudioDeviceID(kAudioObjectUnknown)
+    var size = UInt32(MemoryLayout<AudioDeviceID>.size)
+    let status = withUnsafePointer(to: &cfUID) { uidPtr in
+        AudioObjectGetPropertyData(
+            AudioObjectID(kAudioObjectSystemObject),
+            &address,
+            UInt32(MemoryLayout<CFString>.size),
+            uidPtr,
+            &size,
+            &deviceID
+        )
+    }
+    guard status == noErr, deviceID != kAudioObjectUnknown else { return nil }
+    return deviceID
+}
+
+/// Nominal (current) sample rate of the device, or 0 if unknown
+@_cdecl("sc_audio_get_device_nominal_sample_rate")
+public func getDeviceNominalSampleRate(uid: UnsafePointer<CChar>) -> Float64 {
+    guard let deviceID = audioDeviceID(forUID: String(cString: uid)) else { return 0 }
+    var address = AudioObjectPropertyAddress(
+        mSelector: kAudioDevicePropertyNominalSampleRate,
+        mScope: kAudioObjectPropertyScopeGlobal,
+        mElement: kAudioObjectPropertyElementMain
+    )
+    var rate = Float64(0)
+    var size = UInt32(MemoryLayout<Float64>.size)
+    guard AudioObjectGetPropertyData(deviceID, &address, 0, nil, &size, &rate) == noErr else {
+        return 0
+    }
+    return rate
+}
+
+/// Write up to `capacity` (min, max) sample-rate ranges as pairs into `out`.
+/// Returns the total number of ranges the device reports, or -1 on failure.
+@_cdecl("sc_audio_get_device_sample_rate_ranges")
+public func getDeviceSampleRateRanges(
+    uid: UnsafePointer<CChar>,
+    out: UnsafeMutablePointer<Float64>?,
+    capacity: Int
+) -> Int {
+    guard let deviceID = audioDeviceID(forUID: String(cString: uid)) else { return -1 }
+    var address = AudioObjectPropertyAddress(
+        mSelector: kAudioDevicePropertyAvailableNominalSampleRates,
+        mScope: kAudioObjectPropertyScopeGlobal,
+        mElement: kAudioObjectPropertyElementMain
+    )
+    var size = UInt32(0)
+    guard AudioObjectGetPropertyDataSize(deviceID, &address, 0, nil, &size) == noErr else {
+        return -1
+    }
+    let count = Int(size) / MemoryLayout<AudioValueRange>.size
+    var ranges = [AudioValueRange](repeating: AudioValueRange(), count: count)
+    guard AudioObjectGetPropertyData(deviceID, &address, 0, nil, &size, &ranges) == noErr else {
+        return -1
+    }
+    if let out {
+        for (i, range) in ranges.prefix(capacity).enumerated() {
+            out[i * 2] = range.mMinimum
+            out[i * 2 + 1] = range.mMaximum
+        }
+    }
+    return count
+}
+
+/// Total input channels across the device's input streams, or -1 on failure
+@_cdecl("sc_audio_get_device_input_channel_count")
+public func getDeviceInputChannelCount(uid: UnsafePointer<CChar>) -> Int32 {
+    guard let deviceID = audioDeviceID(forUID: String(cString: uid)) else { return -1 }
+    var address = AudioObjectPropertyAddress(
+        mSelector: kAudioDevicePropertyStreamConfiguration,
+        mScope: kAudioDevicePropertyScopeInput,
+        mElement: kAudioObjectPropertyElementMain
+    )
+    var size = UInt32(0)
+    guard AudioObjectGetPropertyDataSize(deviceID, &address, 0, nil, &size) == noErr else {
+        return -1
+    }
+    let raw = UnsafeMutableRawPointer.allocate(
+        byteCount: Int(size),
+        alignment: MemoryLayout<AudioBufferList>.alignment
+    )
+    defer { raw.deallocate() }
+    let bufferList = raw.bindMemory(to: AudioBufferList.self, capacity: 1)
+    guard AudioObjectGetPropertyData(deviceID, &address, 0, nil, &size, bufferList) == noErr else {
+        return -1
+    }
+    let buffers = UnsafeMutableAudioBufferListPointer(bufferList)
+    return Int32(buffers.reduce(0) { $0 + Int($1.mNumberChannels) })
+}
+
+// MARK: - Device Change Notifications
+
+/// Listens for device list and default-input changes and forwards them to Rust.
+final class AudioDeviceListener {
+    private let callback: @convention(c) (UnsafeMutableRawPointer?) -> Void
+    private let userData: UnsafeMutableRawPointer?
+    private let queue = DispatchQueue(label: "screencapturekit.audio-device-listener")
+    private var addresses = [
+        AudioObjectPropertyAddress(
+            mSelector: kAudioHardwarePropertyDevices,
+            mScope: kAudioObjectPropertyScopeGlobal,
+            mElement: kAudioObjectPropertyElementMain
+        ),
+        AudioObjectPropertyAddress(
+            mSelector: kAudioHardwarePropertyDefaultInputDevice,
+            mScope: kAudioObjectPropertyScopeGlobal,
+            mElement: kAudioObjectPropertyElementMain
+        ),
+    ]
+    private lazy var block: AudioObjectPropertyListenerBlock = { [callback, userData] _, _ in
+        callback(userData)
+    }
+
+    init(
+        callback: @escaping @convention(c) (UnsafeMutableRawPointer?) -> Void,
+        userData: UnsafeMutableRawPointer?
+    ) {
+        self.callback = callback
+        self.userData = userData
+    }
+
+    func start() -> Bool {
+        for i in addresses.indices {
+            let status = AudioObjectAddPropertyListenerBlock(
+                AudioObjectID(kAudioObjectSystemObject), &addresses[i], queue, block
+            )
+            if status != noErr {
+                stop(upTo: i)
+                return false
+            }
+        }
+        return true
+    }
+
+    func stop(upTo end: Int? = nil) {
+        for i in addresses.indices.prefix(end ?? addresses.count) {
+            AudioObjectRemovePropertyListenerBlock(
+                AudioObjectID(kAudioObjectSystemObject), &addresses[i], queue, block
+            )
+        }
+        // Wait out a notification that was already dispatched so Rust can
+        // free `userData` as soon as this returns.
+        queue.sync {}
+    }
+}
+
+@_cdecl("sc_audio_device_listener_create")
+public func createAudioDeviceListener(
+    callback: @escaping @convention(c) (UnsafeMutableRawPointer?) -> Void,
+    userData: UnsafeMutableRawPointer?
+) -> UnsafeMutableRawPointer? {
+    let listener = AudioDeviceListener(callback: callback, userData: userData)
+    guard listener.start() else { return nil }
+    return Unmanaged.passRetained(listener).toOpaque()
+}
+
+@_cdecl("sc_audio_device_listener_release")
+public func releaseAudioDeviceListener(_ listener: UnsafeMutableRawPointer) {
+    let listener = Unmanaged<AudioDeviceListener>.fromOpaque(listener).takeRetainedValue()
+    listener.stop()
+}
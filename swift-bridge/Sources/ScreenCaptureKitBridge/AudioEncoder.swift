@@ -0,0 +1,213 @@
+// AAC / Opus audio encoding via AudioToolbox
+//
+// Wraps an `AudioConverter` that turns interleaved Float32 PCM into
+// compressed packets. Input is fed one packet's worth of frames at a time
+// (1024 for AAC, 960 for Opus); each call yields at most one packet. The
+// Rust side owns chunking and timestamps.
+
+import AudioToolbox
+import Foundation
+
+/// Returned from the input callback when the current chunk has been consumed.
+private let noMoreInputStatus: OSStatus = 0x6E_6F_64_74 // 'nodt'
+
+private final class AudioEncoderBox {
+    let converter: AudioConverterRef
+    let channels: UInt32
+    let framesPerPacket: UInt32
+    let maxPacketSize: UInt32
+
+    // Current input chunk, valid only for the duration of one encode call.
+    var input: UnsafePointer<Float>?
+    var inputFrames: UInt32 = 0
+    var endOfStream = false
+
+    init(converter: AudioConverterRef, channels: UInt32, framesPerPacket: UInt32, maxPacketSize: UInt32) {
+        self.converter = converter
+        self.channels = channels
+        self.framesPerPacket = framesPerPacket
+        self.maxPacketSize = maxPacketSize
+    }
+
+    deinit {
+        AudioConverterDispose(converter)
+    }
+}
+
+private func encoderInputProc(
+    _: AudioConverterRef,
+    _ ioNumberDataPackets: UnsafeMutablePointer<UInt32>,
+    _ ioData: UnsafeMutablePointer<AudioBufferList>,
+    _: UnsafeMutablePointer<UnsafeMutablePointer<AudioStreamPacketDescription>?>?,
+    _ inUserData: UnsafeMutableRawPointer?
+) -> OSStatus {
+    guard let inUserData else { return noMoreInputStatus }
+    let box = Unmanaged<AudioEncoderBox>.fromOpaque(inUserData).takeUnretainedValue()
+
+    guard let input = box.input, box.inputFrames > 0 else {
+        ioNumberDataPackets.pointee = 0
+        // Returning noErr with zero packets signals end of stream and makes
+        // the converter flush its remaining (primed) output.
+        return box.endOfStream ? noErr : noMoreInputStatus
+    }
+
+    ioNumberDataPackets.pointee = box.inputFrames
+    ioData.pointee.mNumberBuffers = 1
+    ioData.pointee.mBuffers.mNumberChannels = box.channels
+    ioData.pointee.mBuffers.mDataByteSize = box.inputFrames * box.channels * UInt32(MemoryLayout<Float>.size)
+    ioData.pointee.mBuffers.mData = UnsafeMutableRawPointer(mutating: input)
+
+    box.input = nil
+    box.inputFrames = 0
+    return noErr
+}
+
+// MARK: - Audio Encoder Bridge
+
+/// Create an encoder. `codec`: 0 = AAC-LC, 1 = Opus.
+@_cdecl("sc_audio_encoder_create")
+public func createAudioEncoder(
+    _ codec: Int32,
+    _ sampleRate: Double,
+    _ channels: UInt32,
+    _ bitrate: UInt32,
+    _ outStatus: UnsafeMutablePointer<Int32>
+) -> OpaquePointer? {
+    var source = AudioStreamBasicDescription(
+        mSampleRate: sampleRate,
+        mFormatID: kAudioFormatLinearPCM,
+        mFormatFlags: kAudioFormatFlagIsFloat | kAudioFormatFlagIsPacked,
+        mBytesPerPacket: channels * 4,
+        mFramesPerPacket: 1,
+        mBytesPerFrame: channels * 4,
+        mChannelsPerFrame: channels,
+        mBitsPerChannel: 32,
+        mReserved: 0
+    )
+    var destination = AudioStreamBasicDescription()
+    destination.mSampleRate = sampleRate
+    destination.mFormatID = codec == 1 ? kAudioFormatOpus : kAudioFormatMPEG4AAC
+    destination.mChannelsPerFrame = channels
+    destination.mFramesPerPacket = codec == 1 ? 960 : 1024
+
+    var converter: AudioConverterRef?
+    var status = AudioConverterNew(&source, &destination, &converter)
+    guard status == noErr, let converter else {
+        outStatus.pointee = status
+        return nil
+    }
+
+    if bitrate > 0 {
+        var value = bitrate
+        status = AudioConverterSetProperty(
+            converter, kAudioConverterEncodeBitRate, UInt32(MemoryLayout<UInt32>.size), &value
+        )
+        if status != noErr {
+            AudioConverterDispose(converter)
+            outStatus.pointee = status
+            return nil
+        }
+    }
+
+    var maxPacketSize: UInt32 = 0
+    var size = UInt32(MemoryLayout<UInt32>.size)
+    AudioConverterGetProperty(converter, kAudioConverterPropertyMaximumOutputPacketSize, &size, &maxPacketSize)
+
+    var actual = AudioStreamBasicDescription()
+    size = UInt32(MemoryLayout<AudioStreamBasicDescription>.size)
+    AudioConverterGetProperty(converter, kAudioConverterCurrentOutputStreamDescription, &size, &actual)
+    let framesPerPacket = actual.mFramesPerPacket > 0 ? actual.mFramesPerPacket : destination.mFramesPerPacket
+
+    outStatus.pointee = noErr
+    let box = AudioEncoderBox(
+        converter: converter,
+        channels: channels,
+        framesPerPacket: framesPerPacket,
+        maxPacketSize: max(maxPacketSize, 1)
+    )
+    return OpaquePointer(Unmanaged.passRetained(box).toOpaque())
+}
+
+@_cdecl("sc_audio_encoder_release")
+public func releaseAudioEncoder(_ encoder: OpaquePointer) {
+    Unmanaged<AudioEncoderBox>.fromOpaque(UnsafeRawPointer(encoder)).release()
+}
+
+@_cdecl("sc_audio_encoder_frames_per_packet")
+public func audioEncoderFramesPerPacket(_ encoder: OpaquePointer) -> UInt32 {
+    Unmanaged<AudioEncoderBox>.fromOpaque(UnsafeRawPointer(encoder)).takeUnretainedValue().framesPerPacket
+}
+
+@_cdecl("sc_audio_encoder_max_packet_size")
+public func audioEncoderMaxPacketSize(_ encoder: OpaquePointer) -> UInt32 {
+    Unmanaged<AudioEncoderBox>.fromOpaque(UnsafeRawPointer(encoder)).takeUnretainedValue().maxPacketSize
+}
+
+/// Leading priming frames the encoder inserts (2112 for AAC, the pre-skip for Opus).
+@_cdecl("sc_audio_encoder_priming_frames")
+public func audioEncoderPrimingFrames(_ encoder: OpaquePointer) -> UInt32 {
+    let box = Unmanaged<AudioEncoderBox>.fromOpaque(UnsafeRawPointer(encoder)).takeUnretainedValue()
+    var info = AudioConverterPrimeInfo()
+    var size = UInt32(MemoryLayout<AudioConverterPrimeInfo>.size)
+    let status = AudioConverterGetProperty(box.converter, kAudioConverterPrimeInfo, &size, &info)
+    return status == noErr ? info.leadingFrames : 0
+}
+
+/// Copy the codec's magic cookie (AAC `AudioSpecificConfig` / Opus header).
+/// Returns the cookie size; copies only if `capacity` is large enough.
+@_cdecl("sc_audio_encoder_magic_cookie")
+public func audioEncoderMagicCookie(
+    _ encoder: OpaquePointer,
+    _ buffer: UnsafeMutableRawPointer?,
+    _ capacity: Int
+) -> Int {
+    let box = Unmanaged<AudioEncoderBox>.fromOpaque(UnsafeRawPointer(encoder)).takeUnretainedValue()
+    var size: UInt32 = 0
+    guard AudioConverterGetPropertyInfo(box.converter, kAudioConverterCompressionMagicCookie, &size, nil) == noErr,
+          size > 0 else { return 0 }
+    guard let buffer, capacity >= Int(size) else { return Int(size) }
+    let status = AudioConverterGetProperty(box.converter, kAudioConverterCompressionMagicCookie, &size, buffer)
+    return status == noErr ? Int(size) : 0
+}
+
+/// Feed `frames` interleaved frames (0 = end of stream) and write at most one
+/// packet to `output`. `outSize` is 0 when the encoder produced nothing yet.
+@_cdecl("sc_audio_encoder_encode")
+public func audioEncoderEncode(
+    _ encoder: OpaquePointer,
+    _ samples: UnsafePointer<Float>?,
+    _ frames: UInt32,
+    _ output: UnsafeMutableRawPointer,
+    _ outputCapacity: UInt32,
+    _ outSize: UnsafeMutablePointer<UInt32>
+) -> Int32 {
+    let box = Unmanaged<AudioEncoderBox>.fromOpaque(UnsafeRawPointer(encoder)).takeUnretainedValue()
+    box.input = samples
+    box.inputFrames = samples == nil ? 0 : frames
+    box.endOfStream = frames == 0
+
+    var outputList = AudioBufferList(
+        mNumberBuffers: 1,
+        mBuffers: AudioBuffer(mNumberChannels: box.channels, mDataByteSize: outputCapacity, mData: output)
+    )
+    var packetCount: UInt32 = 1
+    var packetDescription = AudioStreamPacketDescription()
+
+    let status = AudioConverterFillComplexBuffer(
+        box.converter,
+        encoderInputProc,
+        Unmanaged.passUnretained(box).toOpaque(),
+        &packetCount,
+        &outputList,
+        &packetDescription
+    )
+    box.input = nil
+    box.inputFrames = 0
+
+    guard status == noErr || status == noMoreInputStatus else {
+        outSize.pointee = 0
+        return status
+    }
+    outSize.pointee = packetCount > 0 ? outputList.mBuffers.mDataByteSize : 0
+    return noErr
+}
@@ -0,0 +1,377 @@
+// Audio output devices, and per-device and per-app audio capture via Core Audio process taps
+
+import CoreAudio
+import Foundation
+
+// MARK: - Output Device Enumeration
+
+private func systemProperty<T>(_ selector: AudioObjectPropertySelector, _ value: inout T) -> Bool {
+    var address = AudioObjectPropertyAddress(
+        mSelector: selector,
+        mScope: kAudioObjectPropertyScopeGlobal,
+        mElement: kAudioObjectPropertyElementMain
+    )
+    var size = UInt32(MemoryLayout<T>.size)
+    return AudioObjectGetPropertyData(
+        AudioObjectID(kAudioObjectSystemObject), &address, 0, nil, &size, &value
+    ) == noErr
+}
+
+private func deviceString(_ deviceID: AudioDeviceID, _ selector: AudioObjectPropertySelector) -> String? {
+    var address = AudioObjectPropertyAddress(
+        mSelector: selector,
+        mScope: kAudioObjectPropertyScopeGlobal,
+        mElement: kAudioObjectPropertyElementMain
+    )
+    var value: Unmanaged<CFString>?
+    var size = UInt32(MemoryLayout<Unmanaged<CFString>?>.size)
+    guard AudioObjectGetPropertyData(deviceID, &address, 0, nil, &size, &value) == noErr,
+          let value
+    else { return nil }
+    return value.takeRetainedValue() as String
+}
+
+private func hasOutputStreams(_ deviceID: AudioDeviceID) -> Bool {
+    var address = AudioObjectPropertyAddress(
+        mSelector: kAudioDevicePropertyStreams,
+        mScope: kAudioDevicePropertyScopeOutput,
+        mElement: kAudioObjectPropertyElementMain
+    )
+    var size = UInt32(0)
+    return AudioObjectGetPropertyDataSize(deviceID, &address, 0, nil, &size) == noErr && size > 0
+}
+
+/// Devices with at least one output stream, in Core Audio's order
+private func outputDeviceIDs() -> [AudioDeviceID] {
+    var address = AudioObjectPropertyAddress(
+        mSelector: kAudioHardwarePropertyDevices,
+        mScope: kAudioObjectPropertyScopeGlobal,
+        mElement: kAudioObjectPropertyElementMain
+    )
+    var size = UInt32(0)
+    let system = AudioObjectID(kAudioObjectSystemObject)
+    guard AudioObjectGetPropertyDataSize(system, &address, 0, nil, &size) == noErr else { return [] }
+    var devices = [AudioDeviceID](repeating: 0, count: Int(size) / MemoryLayout<AudioDeviceID>.size)
+    guard AudioObjectGetPropertyData(system, &address, 0, nil, &size, &devices) == noErr else { return [] }
+    return devices.filter(hasOutputStreams)
+}
+
+private func defaultOutputDeviceUID() -> String? {
+    var deviceID = AudioDeviceID(kAudioObjectUnknown)
+    guard systemProperty(kAudioHardwarePropertyDefaultOutputDevice, &deviceID),
+          deviceID != kAudioObjectUnknown
+    else { return nil }
+    return deviceString(deviceID, kAudioDevicePropertyDeviceUID)
+}
+
+private func copyString(_ string: String?, _ buffer: UnsafeMutablePointer<CChar>?, _ bufferSize: Int) -> Bool {
+    guard let string, let buffer, bufferSize > 0 else { return false }
+    return string.withCString { src in
+        guard strlen(src) < bufferSize else { return false }
+        strcpy(buffer, src)
+        return true
+    }
+}
+
+@_cdecl("sc_audio_get_output_device_count")
+public func getOutputDeviceCount() -> Int {
+    outputDeviceIDs().count
+}
+
+@_cdecl("sc_audio_get_output_device_id")
+public func getOutputDeviceId(index: Int, buffer: UnsafeMutablePointer<CChar>?, bufferSize: Int) -> Bool {
+    let devices = outputDeviceIDs()
+    guard index >= 0, index < devices.count else { return false }
+    return copyString(deviceString(devices[index], kAudioDevicePropertyDeviceUID), buffer, bufferSize)
+}
+
+@_cdecl("sc_audio_get_output_device_name")
+public func getOutputDeviceName(index: Int, buffer: UnsafeMutablePointer<CChar>?, bufferSize: Int) -> Bool {
+    let devices = outputDeviceIDs()
+    guard index >= 0, index < devices.count else { return false }
+    return copyString(deviceString(devices[index], kAudioObjectPropertyName), buffer, bufferSize)
+}
+
+@_cdecl("sc_audio_get_default_output_device_id")
+public func getDefaultOutputDeviceId(buffer: UnsafeMutablePointer<CChar>?, bufferSize: Int) -> Bool {
+    copyString(defaultOutputDeviceUID(), buffer, bufferSize)
+}
+
+// MARK: - Output Device Tap
+
+/// Called with interleaved Float32 frames, the frame count, the channel
+/// count, the sample rate and the host time of the first frame.
+typealias AudioOutputTapCallback = @convention(c) (
+    UnsafeMutableRawPointer?, UnsafePointer<Float>?, Int, UInt32, Float64, UInt64
+) -> Void
+
+#if SCREENCAPTUREKIT_HAS_MACOS15_SDK
+    /// Runs a process tap and delivers what it hears.
+    ///
+    /// The tap is added to a private aggregate device, whose input then
+    /// carries the tap's stereo mixdown. Output taps restrict it to one
+    /// device's first output stream; app taps to a set of processes.
+    @available(macOS 14.2, *)
+    final class AudioOutputTap {
+        private let callback: AudioOutputTapCallback
+        private let userData: UnsafeMutableRawPointer?
+        private let queue = DispatchQueue(label: "screencapturekit.audio-output-tap", qos: .userInteractive)
+        private var tapID = AudioObjectID(kAudioObjectUnknown)
+        private var aggregateID = AudioObjectID(kAudioObjectUnknown)
+        private var procID: AudioDeviceIOProcID?
+        private var interleaved = [Float]()
+        private(set) var sampleRate: Float64 = 0
+        private(set) var channelCount: UInt32 = 0
+
+        init(callback: @escaping AudioOutputTapCallback, userData: UnsafeMutableRawPointer?) {
+            self.callback = callback
+            self.userData = userData
+        }
+
+        func start(deviceUID: String, excludedProcesses: [AudioObjectID]) -> OSStatus {
+            let description = CATapDescription(stereoGlobalTapButExcludeProcesses: excludedProcesses)
+            description.name = "ScreenCaptureKit output tap"
+            description.isPrivate = true
+            description.muteBehavior = .unmuted
+            description.deviceUID = deviceUID
+            description.stream = 0
+            return start(description, mainDeviceUID: deviceUID)
+        }
+
+        func start(_ description: CATapDescription, mainDeviceUID deviceUID: String) -> OSStatus {
+            var status = AudioHardwareCreateProcessTap(description, &tapID)
+            guard status == noErr else { return status }
+
+            var format = AudioStreamBasicDescription()
+            var address = AudioObjectPropertyAddress(
+                mSelector: kAudioTapPropertyFormat,
+                mScope: kAudioObjectPropertyScopeGlobal,
+                mElement: kAudioObjectPropertyElementMain
+            )
+            var size = UInt32(MemoryLayout<AudioStreamBasicDescription>.size)
+            status = AudioObjectGetPropertyData(tapID, &address, 0, nil, &size, &format)
+            guard status == noErr else { return status }
+            sampleRate = format.mSampleRate
+            channelCount = format.mChannelsPerFrame
+
+            let aggregate: [String: Any] = [
+                kAudioAggregateDeviceNameKey: description.name,
+                kAudioAggregateDeviceUIDKey: UUID().uuidString,
+                kAudioAggregateDeviceMainSubDeviceKey: deviceUID,
+                kAudioAggregateDeviceIsPrivateKey: true,
+                kAudioAggregateDeviceIsStackedKey: false,
+                kAudioAggregateDeviceTapAutoStartKey: true,
+                kAudioAggregateDeviceSubDeviceListKey: [[kAudioSubDeviceUIDKey: deviceUID]],
+                kAudioAggregateDeviceTapListKey: [[
+                    kAudioSubTapDriftCompensationKey: true,
+                    kAudioSubTapUIDKey: description.uuid.uuidString,
+                ]],
+            ]
+            status = AudioHardwareCreateAggregateDevice(aggregate as CFDictionary, &aggregateID)
+            guard status == noErr else { return status }
+
+            status = AudioDeviceCreateIOProcIDWithBlock(&procID, aggregateID, queue) {
+                [weak self] _, input, inputTime, _, _ in
+                self?.deliver(input, hostTime: inputTime.pointee.mHostTime)
+            }
+            guard status == noErr, let procID else { return status }
+            return AudioDeviceStart(aggregateID, procID)
+        }
+
+        private func deliver(_ input: UnsafePointer<AudioBufferList>, hostTime: UInt64) {
+            let buffers = UnsafeMutableAudioBufferListPointer(UnsafeMutablePointer(mutating: input))
+            guard let first = buffers.first, first.mDataByteSize > 0 else { return }
+            if buffers.count == 1 {
+                let channels = max(first.mNumberChannels, 1)
+                let frames = Int(first.mDataByteSize) / MemoryLayout<Float>.size / Int(channels)
+                callback(userData, first.mData?.assumingMemoryBound(to: Float.self), frames, channels, sampleRate, hostTime)
+                return
+            }
+            // One buffer per channel: interleave into scratch space.
+            let channels = buffers.count
+            let frames = Int(first.mDataByteSize) / MemoryLayout<Float>.size
+            if interleaved.count < frames * channels {
+                interleaved = [Float](repeating: 0, count: frames * channels)
+            }
+            for (channel, buffer) in buffers.enumerated() {
+                guard let data = buffer.mData?.assumingMemoryBound(to: Float.self) else { continue }
+                for frame in 0 ..< frames {
+                    interleaved[frame * channels + channel] = data[frame]
+                }
+            }
+            interleaved.withUnsafeBufferPointer {
+                callback(userData, $0.baseAddress, frames, UInt32(channels), sampleRate, hostTime)
+            }
+        }
+
+        func stop() {
+            if let procID {
+                AudioDeviceStop(aggregateID, procID)
+                AudioDeviceDestroyIOProcID(aggregateID, procID)
+                self.procID = nil
+            }
+            if aggregateID != kAudioObjectUnknown {
+                AudioHardwareDestroyAggregateDevice(aggregateID)
+                aggregateID = AudioObjectID(kAudioObjectUnknown)
+            }
+            if tapID != kAudioObjectUnknown {
+                AudioHardwareDestroyProcessTap(tapID)
+                tapID = AudioObjectID(kAudioObjectUnknown)
+            }
+            // Wait out a callback already running so Rust can free
+            // `userData` as soon as this returns.
+            queue.sync {}
+        }
+    }
+
+    /// Core Audio's object for a process, which exists once the process
+    /// has done any audio I/O.
+    private func processObject(_ processID: pid_t) -> AudioObjectID? {
+        var address = AudioObjectPropertyAddress(
+            mSelector: kAudioHardwarePropertyTranslatePIDToProcessObject,
+            mScope: kAudioObjectPropertyScopeGlobal,
+            mElement: kAudioObjectPropertyElementMain
+        )
+        var pid = processID
+        var object = AudioObjectID(kAudioObjectUnknown)
+        var size = UInt32(MemoryLayout<AudioObjectID>.size)
+        let status = AudioObjectGetPropertyData(
+            AudioObjectID(kAudioObjectSystemObject),
+            &address,
+            UInt32(MemoryLayout<pid_t>.size),
+            &pid,
+            &size,
+            &object
+        )
+        guard status == noErr, object != kAudioObjectUnknown else { return nil }
+        return object
+    }
+
+    /// Start tapping `deviceUID`, or the default output when null. Returns
+    /// null and writes an OSStatus to `outStatus` on failure.
+    @_cdecl("sc_audio_output_tap_create")
+    public func createAudioOutputTap(
+        deviceUID: UnsafePointer<CChar>?,
+        excludeCurrentProcess: Bool,
+        callback: @escaping AudioOutputTapCallback,
+        userData: UnsafeMutableRawPointer?,
+        outStatus: UnsafeMutablePointer<Int32>
+    ) -> UnsafeMutableRawPointer? {
+        guard #available(macOS 14.2, *) else {
+            outStatus.pointee = Int32(kAudioHardwareUnsupportedOperationError)
+            return nil
+        }
+        guard let uid = deviceUID.map({ String(cString: $0) }) ?? defaultOutputDeviceUID() else {
+            outStatus.pointee = Int32(kAudioHardwareBadDeviceError)
+            return nil
+        }
+        let excluded = excludeCurrentProcess ? processObject(getpid()).map { [$0] } ?? [] : []
+        let tap = AudioOutputTap(callback: callback, userData: userData)
+        let status = tap.start(deviceUID: uid, excludedProcesses: excluded)
+        outStatus.pointee = status
+        guard status == noErr else {
+            tap.stop()
+            return nil
+        }
+        return Unmanaged.passRetained(tap).toOpaque()
+    }
+
+    /// Start tapping the `count` processes in `processIDs`, mixed down to
+    /// stereo, optionally silencing them on the speakers while tapped.
+    /// Returns null and writes an OSStatus to `outStatus` on failure;
+    /// `kAudioHardwareBadObjectError` means a process has no audio object.
+    /// Release with `sc_audio_output_tap_release`.
+    @_cdecl("sc_audio_app_tap_create")
+    public func createAudioAppTap(
+        processIDs: UnsafePointer<Int32>,
+        count: Int,
+        mutesProcesses: Bool,
+        callback: @escaping AudioOutputTapCallback,
+        userData: UnsafeMutableRawPointer?,
+        outStatus: UnsafeMutablePointer<Int32>
+    ) -> UnsafeMutableRawPointer? {
+        guard #available(macOS 14.4, *) else {
+            outStatus.pointee = Int32(kAudioHardwareUnsupportedOperationError)
+            return nil
+        }
+        var objects = [AudioObjectID]()
+        for pid in UnsafeBufferPointer(start: processIDs, count: count) {
+            guard let object = processObject(pid) else {
+                outStatus.pointee = Int32(kAudioHardwareBadObjectError)
+                return nil
+            }
+            objects.append(object)
+        }
+        guard let uid = defaultOutputDeviceUID() else {
+            outStatus.pointee = Int32(kAudioHardwareBadDeviceError)
+            return nil
+        }
+        let description = CATapDescription(stereoMixdownOfProcesses: objects)
+        description.name = "ScreenCaptureKit app tap"
+        description.isPrivate = true
+        description.muteBehavior = mutesProcesses ? .mutedWhenTapped : .unmuted
+
+        let tap = AudioOutputTap(callback: callback, userData: userData)
+        let status = tap.start(description, mainDeviceUID: uid)
+        outStatus.pointee = status
+        guard status == noErr else {
+            tap.stop()
+            return nil
+        }
+        return Unmanaged.passRetained(tap).toOpaque()
+    }
+
+    @_cdecl("sc_audio_output_tap_sample_rate")
+    public func audioOutputTapSampleRate(_ tap: UnsafeMutableRawPointer) -> Float64 {
+        guard #available(macOS 14.2, *) else { return 0 }
+        return Unmanaged<AudioOutputTap>.fromOpaque(tap).takeUnretainedValue().sampleRate
+    }
+
+    @_cdecl("sc_audio_output_tap_channel_count")
+    public func audioOutputTapChannelCount(_ tap: UnsafeMutableRawPointer) -> UInt32 {
+        guard #available(macOS 14.2, *) else { return 0 }
+        return Unmanaged<AudioOutputTap>.fromOpaque(tap).takeUnretainedValue().channelCount
+    }
+
+    /// Stop and destroy the tap; no callback runs after this returns
+    @_cdecl("sc_audio_output_tap_release")
+    public func releaseAudioOutputTap(_ tap: UnsafeMutableRawPointer) {
+        guard #available(macOS 14.2, *) else { return }
+        Unmanaged<AudioOutputTap>.fromOpaque(tap).takeRetainedValue().stop()
+    }
+#else
+    /// Process taps need the macOS 14.2 SDK (fallback for older compilers)
+    @_cdecl("sc_audio_output_tap_create")
+    public func createAudioOutputTap(
+        deviceUID _: UnsafePointer<CChar>?,
+        excludeCurrentProcess _: Bool,
+        callback _: @escaping AudioOutputTapCallback,
+        userData _: UnsafeMutableRawPointer?,
+        outStatus: UnsafeMutablePointer<Int32>
+    ) -> UnsafeMutableRawPointer? {
+        outStatus.pointee = Int32(kAudioHardwareUnsupportedOperationError)
+        return nil
+    }
+
+    @_cdecl("sc_audio_app_tap_create")
+    public func createAudioAppTap(
+        processIDs _: UnsafePointer<Int32>,
+        count _: Int,
+        mutesProcesses _: Bool,
+        callback _: @escaping AudioOutputTapCallback,
+        userData _: UnsafeMutableRawPointer?,
+        outStatus: UnsafeMutablePointer<Int32>
+    ) -> UnsafeMutableRawPointer? {
+        outStatus.pointee = Int32(kAudioHardwareUnsupportedOperationError)
+        return nil
+    }
+
+    @_cdecl("sc_audio_output_tap_sample_rate")
+    public func audioOutputTapSampleRate(_: UnsafeMutableRawPointer) -> Float64 { 0 }
+
+    @_cdecl("sc_audio_output_tap_channel_count")
+    public func audioOutputTapChannelCount(_: UnsafeMutableRawPointer) -> UInt32 { 0 }
+
+    @_cdecl("sc_audio_output_tap_release")
+    public func releaseAudioOutputTap(_: UnsafeMutableRawPointer) {}
+#endif
@@ -0,0 +1,330 @@
+// Webcam picture-in-picture for captured frames.
+//
+// An AVCaptureSession keeps the most recent camera frame; compositing draws
+// it into a corner of a screen frame with Core Image and wraps the result in
+// a new sample buffer that keeps the original timing and SCStreamFrameInfo
+// attachments, so downstream consumers can't tell it apart from a capture.
+
+import AVFoundation
+import CoreImage
+import CoreMedia
+import CoreVideo
+import Foundation
+
+// MARK: - Camera Devices
+
+private func cameraDevices() -> [AVCaptureDevice] {
+    var types: [AVCaptureDevice.DeviceType] = [.builtInWideAngleCamera]
+    if #available(macOS 14.0, *) {
+        types += [.external, .continuityCamera]
+    } else {
+        types.append(.externalUnknown)
+    }
+    return AVCaptureDevice.DiscoverySession(
+        deviceTypes: types,
+        mediaType: .video,
+        position: .unspecified
+    ).devices
+}
+
+private func copyString(_ string: String, _ buffer: UnsafeMutablePointer<CChar>?, _ bufferSize: Int) -> Bool {
+    guard let buffer, bufferSize > 0 else { return false }
+    return string.withCString { src in
+        guard strlen(src) < bufferSize else { return false }
+        strlcpy(buffer, src, bufferSize)
+        return true
+    }
+}
+
+@_cdecl("sc_camera_get_device_count")
+public func getCameraDeviceCount() -> Int {
+    cameraDevices().count
+}
+
+@_cdecl("sc_camera_get_device_id")
+public func getCameraDeviceId(_ index: Int, _ buffer: UnsafeMutablePointer<CChar>?, _ bufferSize: Int) -> Bool {
+    let devices = cameraDevices()
+    guard index >= 0, index < devices.count else { return false }
+    return copyString(devices[index].uniqueID, buffer, bufferSize)
+}
+
+@_cdecl("sc_camera_get_device_name")
+public func getCameraDeviceName(_ index: Int, _ buffer: UnsafeMutablePointer<CChar>?, _ bufferSize: Int) -> Bool {
+    let devices = cameraDevices()
+    guard index >= 0, index < devices.count else { return false }
+    return copyString(devices[index].localizedName, buffer, bufferSize)
+}
+
+// MARK: - Camera Overlay
+
+private struct OverlayLayout {
+    var corner: Int32
+    var scale: Double
+    var margin: Double
+    var mirrored: Bool
+}
+
+private final class CameraOverlay: NSObject, AVCaptureVideoDataOutputSampleBufferDelegate {
+    let session = AVCaptureSession()
+    let queue = DispatchQueue(label: "com.screencapturekit.camera-overlay")
+    let context = CIContext(options: [.cacheIntermediates: false])
+    let lock = NSLock()
+    var layout: OverlayLayout
+    var latest: CIImage?
+    let pool = FramePool()
+
+    init(layout: OverlayLayout) {
+        self.layout = layout
+    }
+
+    func captureOutput(
+        _ output: AVCaptureOutput,
+        didOutput sampleBuffer: CMSampleBuffer,
+        from connection: AVCaptureConnection
+    ) {
+        guard let pixelBuffer = CMSampleBufferGetImageBuffer(sampleBuffer) else { return }
+        let image = CIImage(cvPixelBuffer: pixelBuffer)
+        lock.lock()
+        latest = image
+        lock.unlock()
+    }
+}
+
+private func overlay(_ handle: OpaquePointer) -> CameraOverlay {
+    Unmanaged<CameraOverlay>.fromOpaque(UnsafeRawPointer(handle)).takeUnretainedValue()
+}
+
+/// Open the camera with `deviceId` (or the default camera when NULL) and
+/// start delivering frames. Blocks while the user answers the camera
+/// permission prompt. Returns nil with `outStatus` set on failure: -1 no
+/// camera, -2 access denied, -3 the session rejected the device, otherwise
+/// the AVFoundation error code.
+@_cdecl("sc_camera_overlay_create")
+public func createCameraOverlay(
+    _ deviceId: UnsafePointer<CChar>?,
+    _ corner: Int32,
+    _ scale: Double,
+    _ margin: Double,
+    _ mirrored: Bool,
+    _ outStatus: UnsafeMutablePointer<Int32>
+) -> OpaquePointer? {
+    let device: AVCaptureDevice?
+    if let deviceId {
+        device = AVCaptureDevice(uniqueID: String(cString: deviceId))
+    } else {
+        device = AVCaptureDevice.default(for: .video) ?? cameraDevices().first
+    }
+    guard let device else {
+        outStatus.pointee = -1
+        return nil
+    }
+
+    switch AVCaptureDevice.authorizationStatus(for: .video) {
+    case .authorized:
+        break
+    case .notDetermined:
+        let semaphore = DispatchSemaphore(value: 0)
+        var granted = false
+        AVCaptureDevice.requestAccess(for: .video) { result in
+            granted = result
+            semaphore.signal()
+        }
+        semaphore.wait()
+        guard granted else {
+            outStatus.pointee = -2
+            return nil
+        }
+    default:
+        outStatus.pointee = -2
+        return nil
+    }
+
+    let box = CameraOverlay(layout: OverlayLayout(corner: corner, scale: scale, margin: margin, mirrored: mirrored))
+    let input: AVCaptureDeviceInput
+    do {
+        input = try AVCaptureDeviceInput(device: device)
+    } catch {
+        outStatus.pointee = Int32(truncatingIfNeeded: (error as NSError).code)
+        return nil
+    }
+    let output = AVCaptureVideoDataOutput()
+    output.videoSettings = [kCVPixelBufferPixelFormatTypeKey as String: kCVPixelFormatType_32BGRA]
+    output.alwaysDiscardsLateVideoFrames = true
+    output.setSampleBufferDelegate(box, queue: box.queue)
+
+    box.session.beginConfiguration()
+    guard box.session.canAddInput(input), box.session.canAddOutput(output) else {
+        box.session.commitConfiguration()
+        outStatus.pointee = -3
+        return nil
+    }
+    box.session.addInput(input)
+    box.session.addOutput(output)
+    box.session.commitConfiguration()
+    box.session.startRunning()
+
+    outStatus.pointee = 0
+    return OpaquePointer(Unmanaged.passRetained(box).toOpaque())
+}
+
+/// Change where and how large the camera is drawn. Takes effect on the next
+/// composited frame.
+@_cdecl("sc_camera_overlay_set_layout")
+public func cameraOverlaySetLayout(
+    _ handle: OpaquePointer,
+    _ corner: Int32,
+    _ scale: Double,
+    _ margin: Double,
+    _ mirrored: Bool
+) {
+    let box = overlay(handle)
+    box.lock.lock()
+    box.layout = OverlayLayout(corner: corner, scale: scale, margin: margin, mirrored: mirrored)
+    box.lock.unlock()
+}
+
+@_cdecl("sc_camera_overlay_has_frame")
+public func cameraOverlayHasFrame(_ handle: OpaquePointer) -> Bool {
+    let box = overlay(handle)
+    box.lock.lock()
+    defer { box.lock.unlock() }
+    return box.latest != nil
+}
+
+/// Draw the latest camera frame over the screen frame in `sample`. Returns
+/// a +1 sample buffer with the same timing and attachments, or nil if the
+/// sample has no image or no camera frame has arrived yet.
+@_cdecl("sc_camera_overlay_composite")
+public func cameraOverlayComposite(
+    _ handle: OpaquePointer,
+    _ sample: OpaquePointer
+) -> UnsafeMutableRawPointer? {
+    let box = overlay(handle)
+    let sampleBuffer = Unmanaged<CMSampleBuffer>.fromOpaque(UnsafeRawPointer(sample)).takeUnretainedValue()
+    guard let screenBuffer = CMSampleBufferGetImageBuffer(sampleBuffer) else { return nil }
+
+    box.lock.lock()
+    defer { box.lock.unlock() }
+    guard let camera = box.latest else { return nil }
+    let layout = box.layout
+
+    let width = CVPixelBufferGetWidth(screenBuffer)
+    let height = CVPixelBufferGetHeight(screenBuffer)
+    let format = CVPixelBufferGetPixelFormatType(screenBuffer)
+    guard let output = box.pool.buffer(width: width, height: height, format: format) else { return nil }
+
+    var image = camera
+    let extent = image.extent
+    if layout.mirrored {
+        image = image.transformed(
+            by: CGAffineTransform(scaleX: -1, y: 1).translatedBy(x: -extent.width, y: 0)
+        )
+    }
+    let targetWidth = CGFloat(width) * CGFloat(layout.scale)
+    let factor = targetWidth / extent.width
+    let targetHeight = extent.height * factor
+    let margin = CGFloat(layout.margin)
+    // Core Image's origin is bottom-left. Corners: 0 top-left, 1 top-right,
+    // 2 bottom-left, 3 bottom-right.
+    let x = layout.corner == 1 || layout.corner == 3 ? CGFloat(width) - margin - targetWidth : margin
+    let y = layout.corner <= 1 ? CGFloat(height) - margin - targetHeight : margin
+    image = image
+        .transformed(by: CGAffineTransform(translationX: -image.extent.minX, y: -image.extent.minY))
+        .transformed(by: CGAffineTransform(scaleX: factor, y: factor))
+        .transformed(by: CGAffineTransform(translationX: x, y: y))
+
+    let screen = CIImage(cvPixelBuffer: screenBuffer)
+    let composed = image.composited(over: screen).cropped(to: screen.extent)
+    renderFrame(composed, from: screenBuffer, into: output, with: box.context)
+    guard let result = frameSampleBuffer(like: sampleBuffer, with: output) else { return nil }
+    return Unmanaged.passRetained(result).toOpaque()
+}
+
+@_cdecl("sc_camera_overlay_release")
+public func releaseCameraOverlay(_ handle: OpaquePointer) {
+    let box = Unmanaged<CameraOverlay>.fromOpaque(UnsafeRawPointer(handle)).takeRetainedValue()
+    box.session.stopRunning()
+    for case let output as AVCaptureVideoDataOutput in box.session.outputs {
+        output.setSampleBufferDelegate(nil, queue: nil)
+    }
+}
+
+// MARK: - Composited Frames
+
+// Shared with RegionMask.swift: both redraw a screen frame into a new buffer
+// and hand it on in place of the original.
+
+/// Pixel buffers matching the screen frames, from a pool rebuilt when the
+/// frame size or format changes. Callers serialize access.
+final class FramePool {
+    private var pool: CVPixelBufferPool?
+    private var key: (Int, Int, OSType) = (0, 0, 0)
+
+    func buffer(width: Int, height: Int, format: OSType) -> CVPixelBuffer? {
+        if pool == nil || key != (width, height, format) {
+            let attributes: [CFString: Any] = [
+                kCVPixelBufferPixelFormatTypeKey: format,
+                kCVPixelBufferWidthKey: width,
+                kCVPixelBufferHeightKey: height,
+                kCVPixelBufferIOSurfacePropertiesKey: [:] as [CFString: Any],
+            ]
+            pool = nil
+            CVPixelBufferPoolCreate(kCFAllocatorDefault, nil, attributes as CFDictionary, &pool)
+            key = (width, height, format)
+        }
+        guard let pool else { return nil }
+        var buffer: CVPixelBuffer?
+        CVPixelBufferPoolCreatePixelBuffer(kCFAllocatorDefault, pool, &buffer)
+        return buffer
+    }
+}
+
+/// Render `image` into `output` in the source frame's color space and copy
+/// the frame's color attachments across.
+func renderFrame(_ image: CIImage, from source: CVPixelBuffer, into output: CVPixelBuffer, with context: CIContext) {
+    let colorSpace = CVImageBufferGetColorSpace(source)?.takeUnretainedValue()
+        ?? CGColorSpaceCreateDeviceRGB()
+    let bounds = CGRect(x: 0, y: 0, width: CVPixelBufferGetWidth(source), height: CVPixelBufferGetHeight(source))
+    context.render(image, to: output, bounds: bounds, colorSpace: colorSpace)
+    CVBufferPropagateAttachments(source, output)
+}
+
+/// A sample buffer around `output` with the timing and sample attachments
+/// of `sampleBuffer`.
+func frameSampleBuffer(like sampleBuffer: CMSampleBuffer, with output: CVPixelBuffer) -> CMSampleBuffer? {
+    var formatDescription: CMVideoFormatDescription?
+    guard CMVideoFormatDescriptionCreateForImageBuffer(
+        allocator: kCFAllocatorDefault,
+        imageBuffer: output,
+        formatDescriptionOut: &formatDescription
+    ) == noErr, let formatDescription else { return nil }
+
+    var timing = CMSampleTimingInfo()
+    CMSampleBufferGetSampleTimingInfo(sampleBuffer, at: 0, timingInfoOut: &timing)
+    var result: CMSampleBuffer?
+    guard CMSampleBufferCreateReadyWithImageBuffer(
+        allocator: kCFAllocatorDefault,
+        imageBuffer: output,
+        formatDescription: formatDescription,
+        sampleTiming: &timing,
+        sampleBufferOut: &result
+    ) == noErr, let result else { return nil }
+
+    // Carry SCStreamFrameInfo (status, content rect, scale, dirty rects).
+    if let source = CMSampleBufferGetSampleAttachmentsArray(sampleBuffer, createIfNecessary: false),
+       CFArrayGetCount(source) > 0,
+       let target = CMSampleBufferGetSampleAttachmentsArray(result, createIfNecessary: true),
+       CFArrayGetCount(target) > 0
+    {
+        let from = unsafeBitCast(CFArrayGetValueAtIndex(source, 0), to: NSDictionary.self)
+        let into = unsafeBitCast(CFArrayGetValueAtIndex(target, 0), to: CFMutableDictionary.self)
+        for (key, value) in from {
+            CFDictionarySetValue(
+                into,
+                Unmanaged.passUnretained(key as AnyObject).toOpaque(),
+                Unmanaged.passUnretained(value as AnyObject).toOpaque()
+            )
+        }
+    }
+    return result
+}
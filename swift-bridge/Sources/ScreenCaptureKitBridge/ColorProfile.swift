@@ -0,0 +1,70 @@
+// ICC profiles for screenshots.
+//
+// ImageIO embeds the CGImage's color space as an ICC profile when it writes
+// PNG, HEIC, JPEG or TIFF, so a correctly tagged image keeps its colors in
+// color-managed viewers. These functions read that profile, retag an image
+// with a given one, and fetch the profile of a display.
+//
+// Profile bytes are returned in malloc'd buffers freed with
+// `cgimage_encoded_data_free`.
+
+import CoreGraphics
+import Foundation
+
+// MARK: - ICC Profiles
+
+private func copyBytes(
+    _ data: CFData,
+    _ outLength: UnsafeMutablePointer<Int>
+) -> UnsafeMutablePointer<UInt8>? {
+    let length = CFDataGetLength(data)
+    guard length > 0 else { return nil }
+    let buffer = UnsafeMutablePointer<UInt8>.allocate(capacity: length)
+    CFDataGetBytes(data, CFRange(location: 0, length: length), buffer)
+    outLength.pointee = length
+    return buffer
+}
+
+/// Copy the ICC profile of `image`'s color space. Returns nil for images
+/// without a color space, or whose color space has no ICC representation.
+@_cdecl("cgimage_copy_icc_profile")
+public func copyCGImageICCProfile(
+    _ image: OpaquePointer,
+    _ outLength: UnsafeMutablePointer<Int>
+) -> UnsafeMutablePointer<UInt8>? {
+    outLength.pointee = 0
+    let cgImage = Unmanaged<CGImage>.fromOpaque(UnsafeRawPointer(image)).takeUnretainedValue()
+    guard let data = cgImage.colorSpace?.copyICCData() else { return nil }
+    return copyBytes(data, outLength)
+}
+
+/// Return a +1 copy of `image` tagged with the color space described by
+/// `profile`. The pixels are not converted. Returns nil if the profile is
+/// not a valid ICC profile or its component count doesn't match the image.
+@_cdecl("cgimage_create_with_icc_profile")
+public func createCGImageWithICCProfile(
+    _ image: OpaquePointer,
+    _ profile: UnsafePointer<UInt8>,
+    _ length: Int
+) -> OpaquePointer? {
+    let cgImage = Unmanaged<CGImage>.fromOpaque(UnsafeRawPointer(image)).takeUnretainedValue()
+    guard let data = CFDataCreate(nil, profile, length),
+          let colorSpace = CGColorSpace(iccData: data),
+          let tagged = cgImage.copy(colorSpace: colorSpace)
+    else {
+        return nil
+    }
+    return OpaquePointer(Unmanaged.passRetained(tagged).toOpaque())
+}
+
+/// Copy the ICC profile of the display's current color space (the one set
+/// in System Settings > Displays > Color profile).
+@_cdecl("sc_display_copy_icc_profile")
+public func copyDisplayICCProfile(
+    _ displayID: UInt32,
+    _ outLength: UnsafeMutablePointer<Int>
+) -> UnsafeMutablePointer<UInt8>? {
+    outLength.pointee = 0
+    guard let data = CGDisplayCopyColorSpace(displayID).copyICCData() else { return nil }
+    return copyBytes(data, outLength)
+}
@@ -154,23 +154,73 @@ func errorToCString(_ error: Error) -> UnsafeMutablePointer<CChar>? {
     return strdup(bridgeError.description)
 }
 
-/// Extract SCStreamError code from an error, if applicable
-/// Returns the raw error code, or 0 if not an SCStreamError
-func extractStreamErrorCode(_ error: Error) -> Int32 {
+// MARK: - Structured Errors
+
+/// Domain used for errors raised by the bridge itself rather than a framework
+let bridgeErrorDomain = "ScreenCaptureKitBridge"
+
+/// Wrap a bridge-side failure in an NSError so it travels the same path as
+/// framework errors
+func bridgeNSError(_ error: SCBridgeError) -> NSError {
+    NSError(domain: bridgeErrorDomain, code: -1, userInfo: [NSLocalizedDescriptionKey: error.description])
+}
+
+/// Lend `error` to Rust as an opaque NSError pointer for the duration of `body`.
+/// The pointer is +0; Rust copies what it needs before the callback returns.
+func withErrorPointer<R>(_ error: Error, _ body: (OpaquePointer) -> R) -> R {
     let nsError = error as NSError
-    if nsError.domain == "com.apple.ScreenCaptureKit.SCStreamErrorDomain" {
-        return Int32(nsError.code)
+    return withExtendedLifetime(nsError) {
+        body(OpaquePointer(Unmanaged.passUnretained(nsError).toOpaque()))
+    }
+}
+
+private func borrowedError(_ ptr: OpaquePointer) -> NSError {
+    Unmanaged<NSError>.fromOpaque(UnsafeRawPointer(ptr)).takeUnretainedValue()
+}
+
+/// Error domain (caller must free with sc_free_string)
+@_cdecl("sc_error_copy_domain")
+public func copyErrorDomain(_ error: OpaquePointer) -> UnsafeMutablePointer<CChar>? {
+    strdup(borrowedError(error).domain)
+}
+
+/// Error code within its domain
+@_cdecl("sc_error_get_code")
+public func getErrorCode(_ error: OpaquePointer) -> Int64 {
+    Int64(borrowedError(error).code)
+}
+
+/// Localized description (caller must free with sc_free_string)
+@_cdecl("sc_error_copy_description")
+public func copyErrorDescription(_ error: OpaquePointer) -> UnsafeMutablePointer<CChar>? {
+    strdup(borrowedError(error).localizedDescription)
+}
+
+/// String value of a userInfo entry, or nil if absent or not a string
+/// (caller must free with sc_free_string)
+@_cdecl("sc_error_copy_user_info_string")
+public func copyErrorUserInfoString(
+    _ error: OpaquePointer,
+    _ key: UnsafePointer<CChar>
+) -> UnsafeMutablePointer<CChar>? {
+    let value = borrowedError(error).userInfo[String(cString: key)]
+    switch value {
+    case let string as String:
+        return strdup(string)
+    case let url as URL:
+        return strdup(url.absoluteString)
+    default:
+        return nil
     }
-    return 0
 }
 
-/// Format error with code for FFI transfer
-/// Format: "CODE:message" where CODE is the SCStreamError code or 0
-func errorWithCodeToCString(_ error: Error) -> UnsafeMutablePointer<CChar>? {
-    let code = extractStreamErrorCode(error)
-    let message = error.localizedDescription
-    let formatted = "\(code):\(message)"
-    return strdup(formatted)
+/// The NSUnderlyingErrorKey error, borrowed from its parent (+0)
+@_cdecl("sc_error_get_underlying")
+public func getErrorUnderlying(_ error: OpaquePointer) -> OpaquePointer? {
+    guard let underlying = borrowedError(error).userInfo[NSUnderlyingErrorKey] as? NSError else {
+        return nil
+    }
+    return OpaquePointer(Unmanaged.passUnretained(underlying).toOpaque())
 }
 
 // MARK: - Memory Management
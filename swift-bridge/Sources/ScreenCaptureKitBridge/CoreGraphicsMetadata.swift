@@ -0,0 +1,67 @@
+// CGImage pixel-format metadata.
+//
+// ScreenCaptureKit hands back 8-bit BGRA for ordinary captures, but HDR
+// screenshots use 16-bit float components and extended color spaces. These
+// getters let Rust inspect the actual layout before interpreting the bytes.
+
+import CoreGraphics
+import Foundation
+
+// MARK: - CGImage Metadata
+
+@_cdecl("cgimage_get_bits_per_component")
+public func getCGImageBitsPerComponent(_ image: OpaquePointer) -> Int {
+    let cgImage = Unmanaged<CGImage>.fromOpaque(UnsafeRawPointer(image)).takeUnretainedValue()
+    return cgImage.bitsPerComponent
+}
+
+@_cdecl("cgimage_get_bits_per_pixel")
+public func getCGImageBitsPerPixel(_ image: OpaquePointer) -> Int {
+    let cgImage = Unmanaged<CGImage>.fromOpaque(UnsafeRawPointer(image)).takeUnretainedValue()
+    return cgImage.bitsPerPixel
+}
+
+@_cdecl("cgimage_get_bytes_per_row")
+public func getCGImageBytesPerRow(_ image: OpaquePointer) -> Int {
+    let cgImage = Unmanaged<CGImage>.fromOpaque(UnsafeRawPointer(image)).takeUnretainedValue()
+    return cgImage.bytesPerRow
+}
+
+/// Full `CGBitmapInfo` value: alpha info in the low 5 bits, plus the
+/// float-components flag and byte-order field.
+@_cdecl("cgimage_get_bitmap_info")
+public func getCGImageBitmapInfo(_ image: OpaquePointer) -> UInt32 {
+    let cgImage = Unmanaged<CGImage>.fromOpaque(UnsafeRawPointer(image)).takeUnretainedValue()
+    return cgImage.bitmapInfo.rawValue
+}
+
+/// Copy the color space name (e.g. `kCGColorSpaceDisplayP3`) into `buffer`.
+///
+/// Returns false for images without a color space (image masks), for
+/// unnamed color spaces (ICC-based ones created from raw data), or when the
+/// name does not fit.
+@_cdecl("cgimage_get_color_space_name")
+public func getCGImageColorSpaceName(
+    _ image: OpaquePointer,
+    _ buffer: UnsafeMutablePointer<CChar>?,
+    _ bufferSize: Int
+) -> Bool {
+    guard let buffer, bufferSize > 0 else { return false }
+    let cgImage = Unmanaged<CGImage>.fromOpaque(UnsafeRawPointer(image)).takeUnretainedValue()
+    guard let name = cgImage.colorSpace?.name as String? else {
+        return false
+    }
+    return name.withCString { src in
+        guard strlen(src) < bufferSize else { return false }
+        strlcpy(buffer, src, bufferSize)
+        return true
+    }
+}
+
+/// Number of color components (excluding alpha) in the image's color
+/// space: 1 for grayscale, 3 for RGB, 4 for CMYK. Returns 0 without one.
+@_cdecl("cgimage_get_color_space_components")
+public func getCGImageColorSpaceComponents(_ image: OpaquePointer) -> Int {
+    let cgImage = Unmanaged<CGImage>.fromOpaque(UnsafeRawPointer(image)).takeUnretainedValue()
+    return cgImage.colorSpace?.numberOfComponents ?? 0
+}
@@ -0,0 +1,85 @@
+// CGImage transform helpers (crop / resize).
+//
+// Screenshot post-processing commonly needs to cut a selection rectangle out
+// of a full-display capture or scale it down for a thumbnail. Doing that in
+// Rust means rendering the whole image to RGBA first; these helpers stay in
+// CoreGraphics and hand back a new +1 retained CGImage instead.
+
+import CoreGraphics
+import Foundation
+
+// MARK: - CGImage Transforms
+
+/// Crop `image` to the given pixel-space rectangle (origin top-left).
+///
+/// Returns a retained `CGImage`, or nil if the rectangle does not intersect
+/// the image. `CGImage.cropping(to:)` shares the backing store with the
+/// source, so this does not copy pixel data.
+@_cdecl("cgimage_create_cropped")
+public func createCroppedCGImage(
+    _ image: OpaquePointer,
+    _ x: Double,
+    _ y: Double,
+    _ width: Double,
+    _ height: Double
+) -> OpaquePointer? {
+    let cgImage = Unmanaged<CGImage>.fromOpaque(UnsafeRawPointer(image)).takeUnretainedValue()
+    let rect = CGRect(x: x, y: y, width: width, height: height).integral
+
+    guard !rect.isEmpty, let cropped = cgImage.cropping(to: rect) else {
+        return nil
+    }
+    return OpaquePointer(Unmanaged.passRetained(cropped).toOpaque())
+}
+
+/// Scale `image` to `width` x `height` pixels using high-quality interpolation.
+///
+/// Returns a retained 8-bit premultiplied BGRA `CGImage` (the native
+/// ScreenCaptureKit layout), or nil if the context cannot be created.
+@_cdecl("cgimage_create_resized")
+public func createResizedCGImage(
+    _ image: OpaquePointer,
+    _ width: Int,
+    _ height: Int
+) -> OpaquePointer? {
+    let cgImage = Unmanaged<CGImage>.fromOpaque(UnsafeRawPointer(image)).takeUnretainedValue()
+
+    guard width > 0, height > 0 else {
+        return nil
+    }
+
+    let colorSpace = cgImage.colorSpace ?? CGColorSpaceCreateDeviceRGB()
+    let bitmapInfo = CGImageAlphaInfo.premultipliedFirst.rawValue
+        | CGBitmapInfo.byteOrder32Little.rawValue
+
+    guard let context = CGContext(
+        data: nil,
+        width: width,
+        height: height,
+        bitsPerComponent: 8,
+        bytesPerRow: 0,
+        space: colorSpace,
+        bitmapInfo: bitmapInfo
+    ) ?? CGContext(
+        // Some source color spaces (e.g. indexed / extended-range) can't back
+        // an 8-bit RGB bitmap context; fall back to device RGB.
+        data: nil,
+        width: width,
+        height: height,
+        bitsPerComponent: 8,
+        bytesPerRow: 0,
+        space: CGColorSpaceCreateDeviceRGB(),
+        bitmapInfo: bitmapInfo
+    ) else {
+        return nil
+    }
+
+    context.interpolationQuality = .high
+    context.setBlendMode(.copy)
+    context.draw(cgImage, in: CGRect(x: 0, y: 0, width: width, height: height))
+
+    guard let resized = context.makeImage() else {
+        return nil
+    }
+    return OpaquePointer(Unmanaged.passRetained(resized).toOpaque())
+}
@@ -0,0 +1,70 @@
+// The system cursor's position and shape, for streams that leave the cursor
+// out of their frames and send it alongside instead.
+
+import AppKit
+import CoreGraphics
+import Foundation
+
+// MARK: - Cursor
+
+/// A value that changes whenever the cursor's image or hot spot does.
+private func shapeSeed(_ cursor: NSCursor, _ image: CGImage?) -> UInt64 {
+    var hasher = Hasher()
+    hasher.combine(cursor.hotSpot.x)
+    hasher.combine(cursor.hotSpot.y)
+    hasher.combine(cursor.image.size.width)
+    hasher.combine(cursor.image.size.height)
+    if let image, let data = image.dataProvider?.data {
+        hasher.combine(image.width)
+        hasher.combine(image.height)
+        hasher.combine(bytes: UnsafeRawBufferPointer(
+            start: CFDataGetBytePtr(data), count: CFDataGetLength(data)
+        ))
+    }
+    // 0 means "no shape seen yet" on the Rust side.
+    return UInt64(bitPattern: Int64(hasher.finalize())) | 1
+}
+
+private func cursorImage(_ cursor: NSCursor) -> CGImage? {
+    cursor.image.cgImage(forProposedRect: nil, context: nil, hints: nil)
+}
+
+/// Cursor location in global Quartz points (top-left origin) and the seed
+/// of its current shape. Returns false if the location can't be read.
+@_cdecl("sc_cursor_sample")
+public func sampleCursor(
+    _ outX: UnsafeMutablePointer<Double>,
+    _ outY: UnsafeMutablePointer<Double>,
+    _ outSeed: UnsafeMutablePointer<UInt64>
+) -> Bool {
+    guard let location = CGEvent(source: nil)?.location else { return false }
+    outX.pointee = location.x
+    outY.pointee = location.y
+    if let cursor = NSCursor.currentSystem {
+        outSeed.pointee = shapeSeed(cursor, cursorImage(cursor))
+    } else {
+        outSeed.pointee = 0
+    }
+    return true
+}
+
+/// The current cursor image as a +1 CGImage, with its hot spot and size in
+/// points and its shape seed. Returns nil if there is no system cursor.
+@_cdecl("sc_cursor_copy_shape")
+public func copyCursorShape(
+    _ outHotX: UnsafeMutablePointer<Double>,
+    _ outHotY: UnsafeMutablePointer<Double>,
+    _ outWidth: UnsafeMutablePointer<Double>,
+    _ outHeight: UnsafeMutablePointer<Double>,
+    _ outSeed: UnsafeMutablePointer<UInt64>
+) -> OpaquePointer? {
+    guard let cursor = NSCursor.currentSystem, let image = cursorImage(cursor) else {
+        return nil
+    }
+    outHotX.pointee = cursor.hotSpot.x
+    outHotY.pointee = cursor.hotSpot.y
+    outWidth.pointee = cursor.image.size.width
+    outHeight.pointee = cursor.image.size.height
+    outSeed.pointee = shapeSeed(cursor, image)
+    return OpaquePointer(Unmanaged.passRetained(image).toOpaque())
+}
@@ -0,0 +1,24 @@
+// Current display mode, for following resolution and scale changes.
+
+import CoreGraphics
+import Foundation
+
+/// Read the current mode of `displayID`: its size in points and in pixels.
+/// Returns false if the display is offline or has no mode.
+@_cdecl("sc_display_get_current_mode")
+public func getDisplayCurrentMode(
+    _ displayID: UInt32,
+    _ pointWidth: UnsafeMutablePointer<Int>,
+    _ pointHeight: UnsafeMutablePointer<Int>,
+    _ pixelWidth: UnsafeMutablePointer<Int>,
+    _ pixelHeight: UnsafeMutablePointer<Int>
+) -> Bool {
+    guard CGDisplayIsOnline(displayID) != 0, let mode = CGDisplayCopyDisplayMode(displayID) else {
+        return false
+    }
+    pointWidth.pointee = mode.width
+    pointHeight.pointee = mode.height
+    pixelWidth.pointee = mode.pixelWidth
+    pixelHeight.pointee = mode.pixelHeight
+    return true
+}
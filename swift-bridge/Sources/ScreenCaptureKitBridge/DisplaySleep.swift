@@ -0,0 +1,39 @@
+// Display sleep, screen lock and screensaver state, polled to pause capture
+// while there is nothing worth recording.
+
+import AppKit
+import CoreGraphics
+
+private let displayAsleep: UInt32 = 1 << 0
+private let screenLocked: UInt32 = 1 << 1
+private let screensaverRunning: UInt32 = 1 << 2
+private let lockStateUnknown: UInt32 = 1 << 3
+
+/// Bit 0: `displayID` (the main display when 0) is asleep. Bit 1: the
+/// login session's screen is locked. Bit 2: the screensaver is running.
+/// Bit 3: the session dictionary couldn't be read, so bit 1 means nothing.
+@_cdecl("sc_display_sleep_state")
+public func getDisplaySleepState(_ displayID: UInt32) -> UInt32 {
+    var state: UInt32 = 0
+    let display = displayID == 0 ? CGMainDisplayID() : displayID
+    if CGDisplayIsAsleep(display) != 0 {
+        state |= displayAsleep
+    }
+    // `CGSSessionScreenIsLocked` is a private, undocumented key of the
+    // session dictionary; there is no public API for the lock state. The
+    // key is only present while the screen is locked, so a missing key
+    // means unlocked. Only a missing dictionary leaves the state unknown.
+    if let session = CGSessionCopyCurrentDictionary() as? [String: Any] {
+        if session["CGSSessionScreenIsLocked"] as? Bool == true {
+            state |= screenLocked
+        }
+    } else {
+        state |= lockStateUnknown
+    }
+    if !NSRunningApplication.runningApplications(
+        withBundleIdentifier: "com.apple.ScreenSaver.Engine"
+    ).isEmpty {
+        state |= screensaverRunning
+    }
+    return state
+}
@@ -0,0 +1,281 @@
+// Passthrough movie writer for already-encoded media.
+//
+// The replay buffer keeps H.264 access units (AVCC) and AAC packets in
+// memory. Saving a clip wraps each one in a CMSampleBuffer and appends it to
+// an AVAssetWriter input with no output settings, so nothing is re-encoded.
+
+import AVFoundation
+import CoreMedia
+import Foundation
+
+// MARK: - Encoded Movie Writer
+
+public typealias MovieWriterFinishCallback = @convention(c) (UnsafeMutableRawPointer?, Bool, OpaquePointer?) -> Void
+
+private final class EncodedMovieWriter {
+    let writer: AVAssetWriter
+    let video: AVAssetWriterInput
+    let videoFormat: CMFormatDescription
+    let audio: AVAssetWriterInput?
+    let audioFormat: CMFormatDescription?
+
+    init(
+        writer: AVAssetWriter,
+        video: AVAssetWriterInput,
+        videoFormat: CMFormatDescription,
+        audio: AVAssetWriterInput?,
+        audioFormat: CMFormatDescription?
+    ) {
+        self.writer = writer
+        self.video = video
+        self.videoFormat = videoFormat
+        self.audio = audio
+        self.audioFormat = audioFormat
+    }
+}
+
+private func makeVideoFormat(avcC: Data, width: Int32, height: Int32) -> CMFormatDescription? {
+    let extensions: [CFString: Any] = [
+        kCMFormatDescriptionExtension_SampleDescriptionExtensionAtoms: ["avcC": avcC],
+    ]
+    var format: CMFormatDescription?
+    let status = CMVideoFormatDescriptionCreate(
+        allocator: kCFAllocatorDefault,
+        codecType: kCMVideoCodecType_H264,
+        width: width,
+        height: height,
+        extensions: extensions as CFDictionary,
+        formatDescriptionOut: &format
+    )
+    return status == noErr ? format : nil
+}
+
+private func makeAACFormat(cookie: Data, sampleRate: Double, channels: UInt32) -> CMFormatDescription? {
+    var asbd = AudioStreamBasicDescription(
+        mSampleRate: sampleRate,
+        mFormatID: kAudioFormatMPEG4AAC,
+        mFormatFlags: 0,
+        mBytesPerPacket: 0,
+        mFramesPerPacket: 1024,
+        mBytesPerFrame: 0,
+        mChannelsPerFrame: channels,
+        mBitsPerChannel: 0,
+        mReserved: 0
+    )
+    var format: CMFormatDescription?
+    let status = cookie.withUnsafeBytes { bytes in
+        CMAudioFormatDescriptionCreate(
+            allocator: kCFAllocatorDefault,
+            asbd: &asbd,
+            layoutSize: 0,
+            layout: nil,
+            magicCookieSize: bytes.count,
+            magicCookie: bytes.baseAddress,
+            extensions: nil,
+            formatDescriptionOut: &format
+        )
+    }
+    return status == noErr ? format : nil
+}
+
+/// Create a writer for `path` with an H.264 track described by `avcC` and,
+/// when `cookie` is non-null, an AAC track. The session starts at
+/// `startValue/startScale`. Returns nil (with `outStatus` set) on failure.
+@_cdecl("sc_movie_writer_create")
+public func createMovieWriter(
+    _ path: UnsafePointer<CChar>,
+    _ fileType: Int32,
+    _ avcC: UnsafeRawPointer,
+    _ avcCLength: Int,
+    _ width: Int32,
+    _ height: Int32,
+    _ cookie: UnsafeRawPointer?,
+    _ cookieLength: Int,
+    _ sampleRate: Double,
+    _ channels: UInt32,
+    _ startValue: Int64,
+    _ startScale: Int32,
+    _ outStatus: UnsafeMutablePointer<Int32>
+) -> OpaquePointer? {
+    let url = URL(fileURLWithPath: String(cString: path))
+    try? FileManager.default.removeItem(at: url)
+
+    guard let videoFormat = makeVideoFormat(
+        avcC: Data(bytes: avcC, count: avcCLength),
+        width: width,
+        height: height
+    ) else {
+        outStatus.pointee = -1
+        return nil
+    }
+    var audioFormat: CMFormatDescription?
+    if let cookie {
+        audioFormat = makeAACFormat(
+            cookie: Data(bytes: cookie, count: cookieLength),
+            sampleRate: sampleRate,
+            channels: channels
+        )
+        if audioFormat == nil {
+            outStatus.pointee = -2
+            return nil
+        }
+    }
+
+    let writer: AVAssetWriter
+    do {
+        writer = try AVAssetWriter(outputURL: url, fileType: fileType == 1 ? .mov : .mp4)
+    } catch {
+        outStatus.pointee = Int32(truncatingIfNeeded: (error as NSError).code)
+        return nil
+    }
+
+    let video = AVAssetWriterInput(mediaType: .video, outputSettings: nil, sourceFormatHint: videoFormat)
+    video.expectsMediaDataInRealTime = false
+    writer.add(video)
+
+    var audio: AVAssetWriterInput?
+    if let audioFormat {
+        let input = AVAssetWriterInput(mediaType: .audio, outputSettings: nil, sourceFormatHint: audioFormat)
+        input.expectsMediaDataInRealTime = false
+        writer.add(input)
+        audio = input
+    }
+
+    guard writer.startWriting() else {
+        outStatus.pointee = Int32(truncatingIfNeeded: (writer.error as NSError?)?.code ?? -3)
+        return nil
+    }
+    writer.startSession(atSourceTime: CMTime(value: startValue, timescale: startScale))
+
+    let box = EncodedMovieWriter(
+        writer: writer,
+        video: video,
+        videoFormat: videoFormat,
+        audio: audio,
+        audioFormat: audioFormat
+    )
+    outStatus.pointee = 0
+    return OpaquePointer(Unmanaged.passRetained(box).toOpaque())
+}
+
+/// Append one encoded packet to the video (`track` 0) or audio (`track` 1)
+/// input, waiting for the input to accept more data. Returns false if the
+/// packet could not be wrapped or the writer rejected it.
+@_cdecl("sc_movie_writer_append")
+public func movieWriterAppend(
+    _ handle: OpaquePointer,
+    _ track: Int32,
+    _ data: UnsafeRawPointer,
+    _ length: Int,
+    _ ptsValue: Int64,
+    _ ptsScale: Int32,
+    _ durationValue: Int64,
+    _ durationScale: Int32,
+    _ keyframe: Bool
+) -> Bool {
+    let box = Unmanaged<EncodedMovieWriter>.fromOpaque(UnsafeRawPointer(handle)).takeUnretainedValue()
+    let input: AVAssetWriterInput
+    let format: CMFormatDescription
+    if track == 0 {
+        input = box.video
+        format = box.videoFormat
+    } else {
+        guard let audio = box.audio, let audioFormat = box.audioFormat else { return false }
+        input = audio
+        format = audioFormat
+    }
+
+    var blockBuffer: CMBlockBuffer?
+    guard CMBlockBufferCreateWithMemoryBlock(
+        allocator: kCFAllocatorDefault,
+        memoryBlock: nil,
+        blockLength: length,
+        blockAllocator: kCFAllocatorDefault,
+        customBlockSource: nil,
+        offsetToData: 0,
+        dataLength: length,
+        flags: kCMBlockBufferAssureMemoryNowFlag,
+        blockBufferOut: &blockBuffer
+    ) == noErr, let blockBuffer,
+        CMBlockBufferReplaceDataBytes(
+            with: data,
+            blockBuffer: blockBuffer,
+            offsetIntoDestination: 0,
+            dataLength: length
+        ) == noErr
+    else {
+        return false
+    }
+
+    var timing = CMSampleTimingInfo(
+        duration: CMTime(value: durationValue, timescale: durationScale),
+        presentationTimeStamp: CMTime(value: ptsValue, timescale: ptsScale),
+        decodeTimeStamp: .invalid
+    )
+    var sampleSize = length
+    var sampleBuffer: CMSampleBuffer?
+    guard CMSampleBufferCreateReady(
+        allocator: kCFAllocatorDefault,
+        dataBuffer: blockBuffer,
+        formatDescription: format,
+        sampleCount: 1,
+        sampleTimingEntryCount: 1,
+        sampleTimingArray: &timing,
+        sampleSizeEntryCount: 1,
+        sampleSizeArray: &sampleSize,
+        sampleBufferOut: &sampleBuffer
+    ) == noErr, let sampleBuffer else {
+        return false
+    }
+
+    if track == 0, !keyframe,
+       let attachments = CMSampleBufferGetSampleAttachmentsArray(sampleBuffer, createIfNecessary: true),
+       CFArrayGetCount(attachments) > 0
+    {
+        let dict = unsafeBitCast(CFArrayGetValueAtIndex(attachments, 0), to: CFMutableDictionary.self)
+        CFDictionarySetValue(
+            dict,
+            Unmanaged.passUnretained(kCMSampleAttachmentKey_NotSync).toOpaque(),
+            Unmanaged.passUnretained(kCFBooleanTrue).toOpaque()
+        )
+    }
+
+    while !input.isReadyForMoreMediaData {
+        if box.writer.status != .writing { return false }
+        usleep(1000)
+    }
+    return input.append(sampleBuffer)
+}
+
+/// Finish the file. `callback` runs once, on an arbitrary queue, with a
+/// borrowed NSError on failure.
+@_cdecl("sc_movie_writer_finish")
+public func movieWriterFinish(
+    _ handle: OpaquePointer,
+    _ context: UnsafeMutableRawPointer?,
+    _ callback: @escaping MovieWriterFinishCallback
+) {
+    let box = Unmanaged<EncodedMovieWriter>.fromOpaque(UnsafeRawPointer(handle)).takeUnretainedValue()
+    box.video.markAsFinished()
+    box.audio?.markAsFinished()
+    box.writer.finishWriting {
+        if box.writer.status == .completed {
+            callback(context, true, nil)
+        } else {
+            let error = box.writer.error ?? NSError(
+                domain: "ScreenCaptureKitBridge.EncodedMovieWriter",
+                code: box.writer.status.rawValue,
+                userInfo: [NSLocalizedDescriptionKey: "movie writer did not complete"]
+            )
+            withErrorPointer(error) { callback(context, false, $0) }
+        }
+    }
+}
+
+@_cdecl("sc_movie_writer_release")
+public func releaseMovieWriter(_ handle: OpaquePointer) {
+    let box = Unmanaged<EncodedMovieWriter>.fromOpaque(UnsafeRawPointer(handle)).takeRetainedValue()
+    if box.writer.status == .writing {
+        box.writer.cancelWriting()
+    }
+}
@@ -0,0 +1,63 @@
+// In-memory CGImage encoding.
+//
+// `cgimage_save_to_file` writes through ImageIO to a path. Web frontends
+// want the encoded bytes instead (to base64 them into a data URL), so this
+// runs the same ImageIO pipeline against a CFMutableData.
+
+import CoreGraphics
+import Foundation
+import ImageIO
+import UniformTypeIdentifiers
+
+// MARK: - CGImage Encoding
+
+/// Format ids match `ImageFormat::to_format_id` on the Rust side.
+private func imageType(for format: Int32) -> UTType? {
+    switch format {
+    case 0: return .png
+    case 1: return .jpeg
+    case 2: return .tiff
+    case 3: return .gif
+    case 4: return .bmp
+    case 5: return .heic
+    default: return nil
+    }
+}
+
+/// Encode `image` and return the bytes in a malloc'd buffer, writing its
+/// length to `outLength`. Free the buffer with `cgimage_encoded_data_free`.
+/// Returns nil for an unknown format or if ImageIO can't encode the image.
+@_cdecl("cgimage_encode_to_data")
+public func encodeCGImageToData(
+    _ image: OpaquePointer,
+    _ format: Int32,
+    _ quality: Float,
+    _ outLength: UnsafeMutablePointer<Int>
+) -> UnsafeMutablePointer<UInt8>? {
+    outLength.pointee = 0
+    let cgImage = Unmanaged<CGImage>.fromOpaque(UnsafeRawPointer(image)).takeUnretainedValue()
+    guard let type = imageType(for: format) else { return nil }
+
+    let data = NSMutableData()
+    guard let destination = CGImageDestinationCreateWithData(
+        data as CFMutableData, type.identifier as CFString, 1, nil
+    ) else {
+        return nil
+    }
+    var properties: [CFString: Any] = [:]
+    if format == 1 || format == 5 {
+        properties[kCGImageDestinationLossyCompressionQuality] = quality
+    }
+    CGImageDestinationAddImage(destination, cgImage, properties as CFDictionary)
+    guard CGImageDestinationFinalize(destination), data.length > 0 else { return nil }
+
+    let buffer = UnsafeMutablePointer<UInt8>.allocate(capacity: data.length)
+    data.getBytes(buffer, length: data.length)
+    outLength.pointee = data.length
+    return buffer
+}
+
+@_cdecl("cgimage_encoded_data_free")
+public func freeEncodedCGImageData(_ buffer: UnsafeMutablePointer<UInt8>) {
+    buffer.deallocate()
+}
@@ -0,0 +1,92 @@
+// One image file per captured frame.
+//
+// Frames go through Core Image so every pixel format a stream delivers,
+// YCbCr and 10-bit included, lands in an RGB file. HDR formats keep values
+// above 1.0: EXR as linear half floats, HEIF as 10-bit in the frame's own
+// color space.
+
+import CoreImage
+import CoreVideo
+import Foundation
+import ImageIO
+
+// MARK: - Image Sequence
+
+private let sequenceContext = CIContext(options: [.cacheIntermediates: false])
+
+private let openEXRType = "com.ilm.openexr-image" as CFString
+
+/// RGB color space the frame is tagged with, or sRGB.
+private func frameColorSpace(_ image: CIImage) -> CGColorSpace {
+    if let space = image.colorSpace, space.model == .rgb {
+        return space
+    }
+    return CGColorSpace(name: CGColorSpace.sRGB)!
+}
+
+private func writeOpenEXR(_ image: CIImage, to url: URL) throws {
+    guard let linear = CGColorSpace(name: CGColorSpace.extendedLinearSRGB),
+          let cgImage = sequenceContext.createCGImage(
+              image, from: image.extent, format: .RGBAh, colorSpace: linear
+          ),
+          let destination = CGImageDestinationCreateWithURL(url as CFURL, openEXRType, 1, nil)
+    else {
+        throw NSError(domain: "ScreenCaptureKitBridge.ImageSequence", code: 1, userInfo: [
+            NSLocalizedDescriptionKey: "cannot render a half-float image for OpenEXR",
+        ])
+    }
+    CGImageDestinationAddImage(destination, cgImage, nil)
+    guard CGImageDestinationFinalize(destination) else {
+        throw NSError(domain: "ScreenCaptureKitBridge.ImageSequence", code: 2, userInfo: [
+            NSLocalizedDescriptionKey: "ImageIO could not write \(url.path)",
+        ])
+    }
+}
+
+/// Write `pixelBuffer` to `path`. Format ids match `SequenceFormat::id` on
+/// the Rust side: 0 PNG (8-bit), 1 TIFF (16-bit), 2 OpenEXR (half float,
+/// linear), 3 HEIF (10-bit, `quality` 0–1). Returns false with `outError`
+/// set to a message freed with `sc_free_string`.
+@_cdecl("sc_image_sequence_write_frame")
+public func writeImageSequenceFrame(
+    _ pixelBuffer: OpaquePointer,
+    _ path: UnsafePointer<CChar>,
+    _ format: Int32,
+    _ quality: Float,
+    _ outError: UnsafeMutablePointer<UnsafeMutablePointer<CChar>?>
+) -> Bool {
+    outError.pointee = nil
+    let buffer = Unmanaged<CVPixelBuffer>.fromOpaque(UnsafeRawPointer(pixelBuffer)).takeUnretainedValue()
+    let image = CIImage(cvPixelBuffer: buffer)
+    let url = URL(fileURLWithPath: String(cString: path))
+    let colorSpace = frameColorSpace(image)
+
+    do {
+        switch format {
+        case 0:
+            try sequenceContext.writePNGRepresentation(
+                of: image, to: url, format: .RGBA8, colorSpace: colorSpace
+            )
+        case 1:
+            try sequenceContext.writeTIFFRepresentation(
+                of: image, to: url, format: .RGBA16, colorSpace: colorSpace
+            )
+        case 2:
+            try writeOpenEXR(image, to: url)
+        case 3:
+            let options = [
+                CIImageRepresentationOption(rawValue: kCGImageDestinationLossyCompressionQuality as String): quality,
+            ]
+            try sequenceContext.writeHEIF10Representation(
+                of: image, to: url, colorSpace: colorSpace, options: options
+            )
+        default:
+            outError.pointee = strdup("unknown image sequence format \(format)")
+            return false
+        }
+    } catch {
+        outError.pointee = strdup(error.localizedDescription)
+        return false
+    }
+    return true
+}
@@ -0,0 +1,259 @@
+// General-purpose AVAssetWriter muxer.
+//
+// Unlike SCRecordingOutput this is not tied to a stream: callers add tracks
+// up front, then append sample buffers from any source (capture, camera,
+// external audio pipelines). Raw pixel/PCM buffers are encoded by the writer
+// input; already-encoded buffers go to passthrough inputs unchanged.
+
+import AVFoundation
+import CoreMedia
+import Foundation
+
+// MARK: - Muxer
+
+public typealias MuxerFinishCallback = @convention(c) (UnsafeMutableRawPointer?, Bool, OpaquePointer?) -> Void
+
+private final class MuxerBox {
+    let writer: AVAssetWriter
+    let lock = NSLock()
+    var inputs: [AVAssetWriterInput] = []
+    var started = false
+
+    init(writer: AVAssetWriter) {
+        self.writer = writer
+    }
+}
+
+private func muxer(_ handle: OpaquePointer) -> MuxerBox {
+    Unmanaged<MuxerBox>.fromOpaque(UnsafeRawPointer(handle)).takeUnretainedValue()
+}
+
+/// Add `input` before writing starts. Returns its index, or -1 if writing
+/// has started or the writer rejects it.
+private func addInput(_ box: MuxerBox, _ input: AVAssetWriterInput) -> Int32 {
+    box.lock.lock()
+    defer { box.lock.unlock() }
+    guard !box.started, box.writer.canAdd(input) else { return -1 }
+    box.writer.add(input)
+    box.inputs.append(input)
+    return Int32(box.inputs.count - 1)
+}
+
+/// Create a muxer writing to `path` (`fileType` 0 = MP4, 1 = MOV). An
+/// existing file is replaced. Returns nil with `outStatus` set on failure.
+@_cdecl("sc_muxer_create")
+public func createMuxer(
+    _ path: UnsafePointer<CChar>,
+    _ fileType: Int32,
+    _ outStatus: UnsafeMutablePointer<Int32>
+) -> OpaquePointer? {
+    let url = URL(fileURLWithPath: String(cString: path))
+    try? FileManager.default.removeItem(at: url)
+    do {
+        let writer = try AVAssetWriter(outputURL: url, fileType: fileType == 1 ? .mov : .mp4)
+        outStatus.pointee = 0
+        return OpaquePointer(Unmanaged.passRetained(MuxerBox(writer: writer)).toOpaque())
+    } catch {
+        outStatus.pointee = Int32(truncatingIfNeeded: (error as NSError).code)
+        return nil
+    }
+}
+
+/// Add a track that encodes raw pixel buffers (`codec` 0 = H.264, 1 = HEVC).
+/// `bitrate` 0 leaves the encoder default.
+@_cdecl("sc_muxer_add_video_track")
+public func muxerAddVideoTrack(
+    _ handle: OpaquePointer,
+    _ codec: Int32,
+    _ width: Int32,
+    _ height: Int32,
+    _ bitrate: Int32,
+    _ realTime: Bool
+) -> Int32 {
+    var settings: [String: Any] = [
+        AVVideoCodecKey: codec == 1 ? AVVideoCodecType.hevc : AVVideoCodecType.h264,
+        AVVideoWidthKey: Int(width),
+        AVVideoHeightKey: Int(height),
+    ]
+    if bitrate > 0 {
+        settings[AVVideoCompressionPropertiesKey] = [AVVideoAverageBitRateKey: Int(bitrate)]
+    }
+    let input = AVAssetWriterInput(mediaType: .video, outputSettings: settings)
+    input.expectsMediaDataInRealTime = realTime
+    return addInput(muxer(handle), input)
+}
+
+/// Add a track that encodes raw PCM to AAC. `bitrate` 0 leaves the encoder
+/// default.
+@_cdecl("sc_muxer_add_audio_track")
+public func muxerAddAudioTrack(
+    _ handle: OpaquePointer,
+    _ sampleRate: Double,
+    _ channels: Int32,
+    _ bitrate: Int32,
+    _ realTime: Bool
+) -> Int32 {
+    var settings: [String: Any] = [
+        AVFormatIDKey: kAudioFormatMPEG4AAC,
+        AVSampleRateKey: sampleRate,
+        AVNumberOfChannelsKey: Int(channels),
+    ]
+    if bitrate > 0 {
+        settings[AVEncoderBitRateKey] = Int(bitrate)
+    }
+    let input = AVAssetWriterInput(mediaType: .audio, outputSettings: settings)
+    input.expectsMediaDataInRealTime = realTime
+    return addInput(muxer(handle), input)
+}
+
+/// Add a track that writes already-encoded buffers described by `format`
+/// without re-encoding.
+@_cdecl("sc_muxer_add_passthrough_track")
+public func muxerAddPassthroughTrack(
+    _ handle: OpaquePointer,
+    _ format: OpaquePointer,
+    _ realTime: Bool
+) -> Int32 {
+    let formatDescription = Unmanaged<CMFormatDescription>
+        .fromOpaque(UnsafeRawPointer(format))
+        .takeUnretainedValue()
+    let mediaType = AVMediaType(rawValue: fourCharString(CMFormatDescriptionGetMediaType(formatDescription)))
+    let input = AVAssetWriterInput(mediaType: mediaType, outputSettings: nil, sourceFormatHint: formatDescription)
+    input.expectsMediaDataInRealTime = realTime
+    return addInput(muxer(handle), input)
+}
+
+private func fourCharString(_ code: FourCharCode) -> String {
+    let bytes = [24, 16, 8, 0].map { UInt8((code >> $0) & 0xFF) }
+    return String(bytes: bytes, encoding: .macOSRoman) ?? ""
+}
+
+/// Append `sample` to track `track`. When `retime` is set the buffer is
+/// copied with its presentation time moved to `ptsValue/ptsScale` (and the
+/// decode time cleared). The session starts at the first appended sample.
+///
+/// Returns 0 on success, 1 if the buffer carries no media (idle capture
+/// frames) and was skipped, -1 for an unknown track, -2 if the buffer could
+/// not be retimed, -3 if the input stayed busy, and -4 if the writer
+/// rejected the buffer.
+@_cdecl("sc_muxer_append")
+public func muxerAppend(
+    _ handle: OpaquePointer,
+    _ track: Int32,
+    _ sample: OpaquePointer,
+    _ retime: Bool,
+    _ ptsValue: Int64,
+    _ ptsScale: Int32
+) -> Int32 {
+    let box = muxer(handle)
+    var sampleBuffer = Unmanaged<CMSampleBuffer>.fromOpaque(UnsafeRawPointer(sample)).takeUnretainedValue()
+    if CMSampleBufferGetNumSamples(sampleBuffer) == 0 {
+        return 1
+    }
+
+    if retime {
+        var count: CMItemCount = 0
+        CMSampleBufferGetSampleTimingInfoArray(sampleBuffer, entryCount: 0, arrayToFill: nil, entriesNeededOut: &count)
+        var timing = [CMSampleTimingInfo](repeating: CMSampleTimingInfo(), count: max(count, 1))
+        CMSampleBufferGetSampleTimingInfoArray(sampleBuffer, entryCount: timing.count, arrayToFill: &timing, entriesNeededOut: nil)
+        let shift = CMTimeSubtract(
+            CMTime(value: ptsValue, timescale: ptsScale),
+            CMSampleBufferGetPresentationTimeStamp(sampleBuffer)
+        )
+        for i in timing.indices {
+            timing[i].presentationTimeStamp = CMTimeAdd(timing[i].presentationTimeStamp, shift)
+            timing[i].decodeTimeStamp = .invalid
+        }
+        var copy: CMSampleBuffer?
+        guard CMSampleBufferCreateCopyWithNewTiming(
+            allocator: kCFAllocatorDefault,
+            sampleBuffer: sampleBuffer,
+            sampleTimingEntryCount: timing.count,
+            sampleTimingArray: &timing,
+            sampleBufferOut: &copy
+        ) == noErr, let copy else {
+            return -2
+        }
+        sampleBuffer = copy
+    }
+
+    box.lock.lock()
+    defer { box.lock.unlock() }
+    guard track >= 0, Int(track) < box.inputs.count else { return -1 }
+    let input = box.inputs[Int(track)]
+
+    if !box.started {
+        guard box.writer.startWriting() else { return -4 }
+        box.writer.startSession(atSourceTime: CMSampleBufferGetPresentationTimeStamp(sampleBuffer))
+        box.started = true
+    }
+
+    // Real-time inputs are almost always ready; offline ones wait for the
+    // writer to interleave. Give up after two seconds rather than hang.
+    var waited = 0
+    while !input.isReadyForMoreMediaData {
+        if box.writer.status != .writing || waited >= 2000 { return -3 }
+        usleep(1000)
+        waited += 1
+    }
+    return input.append(sampleBuffer) ? 0 : -4
+}
+
+/// Copy the writer's error description into `buffer`. Returns false if the
+/// writer has no error.
+@_cdecl("sc_muxer_get_error")
+public func muxerGetError(
+    _ handle: OpaquePointer,
+    _ buffer: UnsafeMutablePointer<CChar>?,
+    _ bufferSize: Int
+) -> Bool {
+    guard let buffer, bufferSize > 0, let error = muxer(handle).writer.error else { return false }
+    return error.localizedDescription.withCString { src in
+        strlcpy(buffer, src, bufferSize)
+        return true
+    }
+}
+
+/// Finish the file. `callback` runs once, on an arbitrary queue, with a
+/// borrowed NSError on failure.
+@_cdecl("sc_muxer_finish")
+public func muxerFinish(
+    _ handle: OpaquePointer,
+    _ context: UnsafeMutableRawPointer?,
+    _ callback: @escaping MuxerFinishCallback
+) {
+    let box = muxer(handle)
+    box.lock.lock()
+    let started = box.started
+    box.lock.unlock()
+    guard started else {
+        let error = NSError(
+            domain: "ScreenCaptureKitBridge.Muxer",
+            code: 1,
+            userInfo: [NSLocalizedDescriptionKey: "no samples were appended"]
+        )
+        withErrorPointer(error) { callback(context, false, $0) }
+        return
+    }
+    box.inputs.forEach { $0.markAsFinished() }
+    box.writer.finishWriting {
+        if box.writer.status == .completed {
+            callback(context, true, nil)
+        } else {
+            let error = box.writer.error ?? NSError(
+                domain: "ScreenCaptureKitBridge.Muxer",
+                code: box.writer.status.rawValue,
+                userInfo: [NSLocalizedDescriptionKey: "muxer did not complete"]
+            )
+            withErrorPointer(error) { callback(context, false, $0) }
+        }
+    }
+}
+
+@_cdecl("sc_muxer_release")
+public func releaseMuxer(_ handle: OpaquePointer) {
+    let box = Unmanaged<MuxerBox>.fromOpaque(UnsafeRawPointer(handle)).takeRetainedValue()
+    if box.writer.status == .writing {
+        box.writer.cancelWriting()
+    }
+}
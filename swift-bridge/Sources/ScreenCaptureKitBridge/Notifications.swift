@@ -0,0 +1,62 @@
+// Notification banners on screen, read from the window server.
+//
+// Banners and alerts are windows of the Notification Center agent. Reading
+// the on-screen window list is cheap enough to poll, and unlike shareable
+// content it sees the agent's windows the moment they are ordered in.
+
+import AppKit
+import CoreGraphics
+import Foundation
+
+private let notificationCenterBundleID = "com.apple.notificationcenterui"
+
+private func isNotificationCenter(_ info: [String: Any], _ cache: inout [pid_t: Bool]) -> Bool {
+    guard let pid = info[kCGWindowOwnerPID as String] as? pid_t else { return false }
+    if let known = cache[pid] {
+        return known
+    }
+    let bundleID = NSRunningApplication(processIdentifier: pid)?.bundleIdentifier
+    let owner = (info[kCGWindowOwnerName as String] as? String)?.replacingOccurrences(of: " ", with: "")
+    let result = bundleID == notificationCenterBundleID || (bundleID == nil && owner == "NotificationCenter")
+    cache[pid] = result
+    return result
+}
+
+/// Copy the on-screen windows of Notification Center, front to back: IDs
+/// into `outIds` and x, y, width, height in global points into `outRects`,
+/// up to `capacity` windows. Returns how many there are, which may be more
+/// than `capacity`. Transparent and empty windows are skipped.
+@_cdecl("sc_notification_windows_copy")
+public func copyNotificationWindows(
+    _ outIds: UnsafeMutablePointer<UInt32>?,
+    _ outRects: UnsafeMutablePointer<Double>?,
+    _ capacity: Int
+) -> Int {
+    guard let list = CGWindowListCopyWindowInfo(
+        [.optionOnScreenOnly, .excludeDesktopElements],
+        kCGNullWindowID
+    ) as? [[String: Any]] else { return 0 }
+
+    var cache: [pid_t: Bool] = [:]
+    var count = 0
+    for info in list where isNotificationCenter(info, &cache) {
+        guard let id = info[kCGWindowNumber as String] as? UInt32,
+              let bounds = info[kCGWindowBounds as String] as? NSDictionary,
+              let frame = CGRect(dictionaryRepresentation: bounds as CFDictionary),
+              frame.width > 0, frame.height > 0,
+              (info[kCGWindowAlpha as String] as? Double ?? 1) > 0
+        else { continue }
+        if count < capacity {
+            outIds?[count] = id
+            if let outRects {
+                let values = outRects + count * 4
+                values[0] = frame.origin.x
+                values[1] = frame.origin.y
+                values[2] = frame.size.width
+                values[3] = frame.size.height
+            }
+        }
+        count += 1
+    }
+    return count
+}
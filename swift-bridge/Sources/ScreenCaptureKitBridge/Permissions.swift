@@ -0,0 +1,35 @@
+// Screen Recording permission helpers.
+
+import AppKit
+import Foundation
+
+private let screenCaptureSettingsURL =
+    "x-apple.systempreferences:com.apple.preference.security?Privacy_ScreenCapture"
+
+/// Open System Settings at Privacy & Security > Screen Recording. Returns
+/// false if the settings pane could not be opened.
+@_cdecl("sc_open_screen_recording_settings")
+public func openScreenRecordingSettings() -> Bool {
+    guard let url = URL(string: screenCaptureSettingsURL) else { return false }
+    return NSWorkspace.shared.open(url)
+}
+
+/// Open System Settings at a `x-apple.systempreferences:` URL. Returns false
+/// for other schemes or if the pane could not be opened.
+@_cdecl("sc_open_settings_url")
+public func openSettingsURL(_ url: UnsafePointer<CChar>) -> Bool {
+    guard let url = URL(string: String(cString: url)),
+          url.scheme == "x-apple.systempreferences"
+    else { return false }
+    return NSWorkspace.shared.open(url)
+}
+
+/// Whether `SCContentSharingPicker` exists on this system. Content picked
+/// there can be captured without Screen Recording permission.
+@_cdecl("sc_content_sharing_picker_is_available")
+public func contentSharingPickerIsAvailable() -> Bool {
+    if #available(macOS 14.0, *) {
+        return true
+    }
+    return false
+}
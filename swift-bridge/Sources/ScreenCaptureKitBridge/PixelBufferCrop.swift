@@ -0,0 +1,119 @@
+// Copy a region of a pixel buffer into a new, IOSurface-backed buffer.
+
+import Accelerate
+import CoreVideo
+import Foundation
+
+/// Copy the `width`×`height` region at (`x`, `y`) of `source` into a new
+/// pixel buffer of the same format, taken from `pool` when one is given.
+/// `elementBytes` holds the bytes per pixel of each of the `planeCount`
+/// planes. The caller has checked the region against the buffer's bounds
+/// and chroma subsampling.
+///
+/// Returns a +1 pixel buffer, or nil with `outStatus` set: -1 the source
+/// could not be locked, -2 the pool makes buffers of another size or
+/// format, -3 the plane layout does not match, otherwise a `CVReturn`.
+@_cdecl("sc_pixel_buffer_crop")
+public func cropPixelBuffer(
+    _ source: OpaquePointer,
+    _ x: Int,
+    _ y: Int,
+    _ width: Int,
+    _ height: Int,
+    _ pool: OpaquePointer?,
+    _ elementBytes: UnsafePointer<Int>,
+    _ planeCount: Int,
+    _ outStatus: UnsafeMutablePointer<Int32>
+) -> UnsafeMutableRawPointer? {
+    let src = Unmanaged<CVPixelBuffer>.fromOpaque(UnsafeRawPointer(source)).takeUnretainedValue()
+    let format = CVPixelBufferGetPixelFormatType(src)
+    let fullWidth = CVPixelBufferGetWidth(src)
+    let fullHeight = CVPixelBufferGetHeight(src)
+    let planar = CVPixelBufferIsPlanar(src)
+    guard (planar ? CVPixelBufferGetPlaneCount(src) : 1) == planeCount, fullWidth > 0, fullHeight > 0 else {
+        outStatus.pointee = -3
+        return nil
+    }
+
+    var created: CVPixelBuffer?
+    let status: CVReturn
+    if let pool {
+        let cvPool = Unmanaged<CVPixelBufferPool>.fromOpaque(UnsafeRawPointer(pool)).takeUnretainedValue()
+        status = CVPixelBufferPoolCreatePixelBuffer(kCFAllocatorDefault, cvPool, &created)
+    } else {
+        let attributes: [CFString: Any] = [
+            kCVPixelBufferIOSurfacePropertiesKey: [:] as [CFString: Any],
+        ]
+        status = CVPixelBufferCreate(
+            kCFAllocatorDefault,
+            width,
+            height,
+            format,
+            attributes as CFDictionary,
+            &created
+        )
+    }
+    guard status == kCVReturnSuccess, let dest = created else {
+        outStatus.pointee = status
+        return nil
+    }
+    guard CVPixelBufferGetWidth(dest) == width,
+          CVPixelBufferGetHeight(dest) == height,
+          CVPixelBufferGetPixelFormatType(dest) == format
+    else {
+        outStatus.pointee = -2
+        return nil
+    }
+
+    guard CVPixelBufferLockBaseAddress(src, .readOnly) == kCVReturnSuccess else {
+        outStatus.pointee = -1
+        return nil
+    }
+    defer { CVPixelBufferUnlockBaseAddress(src, .readOnly) }
+    guard CVPixelBufferLockBaseAddress(dest, []) == kCVReturnSuccess else {
+        outStatus.pointee = -1
+        return nil
+    }
+    defer { CVPixelBufferUnlockBaseAddress(dest, []) }
+
+    for plane in 0..<planeCount {
+        let bytes = elementBytes[plane]
+        let srcBase = planar ? CVPixelBufferGetBaseAddressOfPlane(src, plane) : CVPixelBufferGetBaseAddress(src)
+        let dstBase = planar ? CVPixelBufferGetBaseAddressOfPlane(dest, plane) : CVPixelBufferGetBaseAddress(dest)
+        guard let srcBase, let dstBase else {
+            outStatus.pointee = -1
+            return nil
+        }
+        let srcRowBytes = planar ? CVPixelBufferGetBytesPerRowOfPlane(src, plane) : CVPixelBufferGetBytesPerRow(src)
+        let dstRowBytes = planar ? CVPixelBufferGetBytesPerRowOfPlane(dest, plane) : CVPixelBufferGetBytesPerRow(dest)
+        let planeWidth = planar ? CVPixelBufferGetWidthOfPlane(src, plane) : fullWidth
+        let planeHeight = planar ? CVPixelBufferGetHeightOfPlane(src, plane) : fullHeight
+        // Scale the region to this plane's subsampling.
+        let px = x * planeWidth / fullWidth
+        let py = y * planeHeight / fullHeight
+        let pw = width * planeWidth / fullWidth
+        let ph = height * planeHeight / fullHeight
+
+        var from = vImage_Buffer(
+            data: srcBase + py * srcRowBytes + px * bytes,
+            height: vImagePixelCount(ph),
+            width: vImagePixelCount(pw),
+            rowBytes: srcRowBytes
+        )
+        var into = vImage_Buffer(
+            data: dstBase,
+            height: vImagePixelCount(ph),
+            width: vImagePixelCount(pw),
+            rowBytes: dstRowBytes
+        )
+        let copied = vImageCopyBuffer(&from, &into, bytes, vImage_Flags(kvImageNoFlags))
+        guard copied == kvImageNoError else {
+            outStatus.pointee = Int32(truncatingIfNeeded: copied)
+            return nil
+        }
+    }
+
+    CVBufferPropagateAttachments(src, dest)
+    outStatus.pointee = 0
+    return Unmanaged.passRetained(dest).toOpaque()
+}
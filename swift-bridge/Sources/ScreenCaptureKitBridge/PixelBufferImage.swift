@@ -0,0 +1,109 @@
+// Conversions between CGImage and CVPixelBuffer.
+//
+// Screenshots arrive as CGImages, stream frames as pixel buffers. Core Image
+// renders a CGImage into any of the formats a stream can deliver, including
+// YCbCr; VideoToolbox goes the other way for every format it can read.
+
+import CoreImage
+import CoreVideo
+import Foundation
+import VideoToolbox
+
+// MARK: - CGImage <-> CVPixelBuffer
+
+private let conversionContext = CIContext(options: [.cacheIntermediates: false])
+
+private func isYCbCr(_ format: OSType) -> Bool {
+    switch format {
+    case kCVPixelFormatType_420YpCbCr8BiPlanarVideoRange,
+         kCVPixelFormatType_420YpCbCr8BiPlanarFullRange,
+         kCVPixelFormatType_444YpCbCr10BiPlanarFullRange:
+        return true
+    default:
+        return false
+    }
+}
+
+/// Render `image` into a new IOSurface-backed pixel buffer of `format`, the
+/// image's size, tagged with the image's RGB color space (sRGB otherwise).
+/// YCbCr buffers use the BT.709 matrix.
+///
+/// Returns a +1 pixel buffer, or nil with `outStatus` set: -1 the image has
+/// no pixels, otherwise the `CVReturn` from creating the buffer.
+@_cdecl("sc_pixel_buffer_create_from_cgimage")
+public func createPixelBufferFromCGImage(
+    _ image: OpaquePointer,
+    _ format: UInt32,
+    _ outStatus: UnsafeMutablePointer<Int32>
+) -> UnsafeMutableRawPointer? {
+    let cgImage = Unmanaged<CGImage>.fromOpaque(UnsafeRawPointer(image)).takeUnretainedValue()
+    let width = cgImage.width
+    let height = cgImage.height
+    guard width > 0, height > 0 else {
+        outStatus.pointee = -1
+        return nil
+    }
+
+    let attributes: [CFString: Any] = [
+        kCVPixelBufferIOSurfacePropertiesKey: [:] as [CFString: Any],
+    ]
+    var created: CVPixelBuffer?
+    let status = CVPixelBufferCreate(
+        kCFAllocatorDefault,
+        width,
+        height,
+        format,
+        attributes as CFDictionary,
+        &created
+    )
+    guard status == kCVReturnSuccess, let buffer = created else {
+        outStatus.pointee = status
+        return nil
+    }
+
+    var colorSpace = CGColorSpace(name: CGColorSpace.sRGB)!
+    if let space = cgImage.colorSpace, space.model == .rgb {
+        colorSpace = space
+    }
+    CVBufferSetAttachment(buffer, kCVImageBufferCGColorSpaceKey, colorSpace, .shouldPropagate)
+    if isYCbCr(format) {
+        CVBufferSetAttachment(
+            buffer, kCVImageBufferYCbCrMatrixKey, kCVImageBufferYCbCrMatrix_ITU_R_709_2, .shouldPropagate
+        )
+        CVBufferSetAttachment(
+            buffer, kCVImageBufferColorPrimariesKey, kCVImageBufferColorPrimaries_ITU_R_709_2, .shouldPropagate
+        )
+        CVBufferSetAttachment(
+            buffer, kCVImageBufferTransferFunctionKey, kCVImageBufferTransferFunction_ITU_R_709_2, .shouldPropagate
+        )
+    }
+
+    let source = CIImage(cgImage: cgImage)
+    conversionContext.render(
+        source,
+        to: buffer,
+        bounds: CGRect(x: 0, y: 0, width: width, height: height),
+        colorSpace: colorSpace
+    )
+    outStatus.pointee = 0
+    return Unmanaged.passRetained(buffer).toOpaque()
+}
+
+/// Build a CGImage from the pixels of `pixelBuffer`, converting YCbCr and
+/// 10-bit formats with `VTCreateCGImageFromCVPixelBuffer`.
+///
+/// Returns a +1 CGImage, or nil with `outStatus` set to the `OSStatus`.
+@_cdecl("sc_cgimage_create_from_pixel_buffer")
+public func createCGImageFromPixelBuffer(
+    _ pixelBuffer: OpaquePointer,
+    _ outStatus: UnsafeMutablePointer<Int32>
+) -> UnsafeMutableRawPointer? {
+    let buffer = Unmanaged<CVPixelBuffer>.fromOpaque(UnsafeRawPointer(pixelBuffer)).takeUnretainedValue()
+    var cgImage: CGImage?
+    let status = VTCreateCGImageFromCVPixelBuffer(buffer, options: nil, imageOut: &cgImage)
+    outStatus.pointee = status
+    guard status == noErr, let image = cgImage else {
+        return nil
+    }
+    return Unmanaged.passRetained(image).toOpaque()
+}
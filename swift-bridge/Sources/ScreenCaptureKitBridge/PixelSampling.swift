@@ -0,0 +1,175 @@
+// Color statistics over a region of a pixel buffer.
+//
+// Every function converts the region to 8-bit RGBA with vImage first, so
+// BGRA, YCbCr 4:2:0 and 10-bit formats are all handled, then runs a vImage
+// histogram or scale over it. Statuses: 0 success, -1 the buffer could not
+// be locked or converted, otherwise a vImage_Error.
+
+import Accelerate
+import CoreVideo
+import Foundation
+
+// MARK: - Pixel Sampling
+
+/// Convert the `width`×`height` region at (`x`, `y`) of `source` to RGBA8888
+/// and hand it to `body`. The caller has checked the region's bounds.
+private func withRGBARegion(
+    _ source: OpaquePointer,
+    _ x: Int,
+    _ y: Int,
+    _ width: Int,
+    _ height: Int,
+    _ body: (inout vImage_Buffer) -> vImage_Error
+) -> Int32 {
+    let buffer = Unmanaged<CVPixelBuffer>.fromOpaque(UnsafeRawPointer(source)).takeUnretainedValue()
+    var rgba = vImage_Buffer()
+    defer { free(rgba.data) }
+
+    if CVPixelBufferGetPixelFormatType(buffer) == kCVPixelFormatType_32BGRA {
+        guard CVPixelBufferLockBaseAddress(buffer, .readOnly) == kCVReturnSuccess else { return -1 }
+        defer { CVPixelBufferUnlockBaseAddress(buffer, .readOnly) }
+        guard let base = CVPixelBufferGetBaseAddress(buffer) else { return -1 }
+        let rowBytes = CVPixelBufferGetBytesPerRow(buffer)
+        var region = vImage_Buffer(
+            data: base + y * rowBytes + x * 4,
+            height: vImagePixelCount(height),
+            width: vImagePixelCount(width),
+            rowBytes: rowBytes
+        )
+        var error = vImageBuffer_Init(&rgba, region.height, region.width, 32, vImage_Flags(kvImageNoFlags))
+        guard error == kvImageNoError else { return Int32(error) }
+        let bgraToRGBA: [UInt8] = [2, 1, 0, 3]
+        error = vImagePermuteChannels_ARGB8888(&region, &rgba, bgraToRGBA, vImage_Flags(kvImageNoFlags))
+        guard error == kvImageNoError else { return Int32(error) }
+    } else {
+        guard let cvFormat = vImageCVImageFormat_CreateWithCVPixelBuffer(buffer)?.takeRetainedValue() else {
+            return -1
+        }
+        if vImageCVImageFormat_GetColorSpace(cvFormat) == nil {
+            vImageCVImageFormat_SetColorSpace(cvFormat, CGColorSpace(name: CGColorSpace.sRGB))
+        }
+        if vImageCVImageFormat_GetChromaSiting(cvFormat) == nil {
+            vImageCVImageFormat_SetChromaSiting(cvFormat, kCVImageBufferChromaLocation_Center)
+        }
+        guard let colorSpace = CGColorSpace(name: CGColorSpace.sRGB) else { return -1 }
+        var format = vImage_CGImageFormat(
+            bitsPerComponent: 8,
+            bitsPerPixel: 32,
+            colorSpace: Unmanaged.passUnretained(colorSpace),
+            bitmapInfo: CGBitmapInfo(rawValue: CGImageAlphaInfo.last.rawValue),
+            version: 0,
+            decode: nil,
+            renderingIntent: .defaultIntent
+        )
+        var full = vImage_Buffer()
+        let error = vImageBuffer_InitWithCVPixelBuffer(
+            &full, &format, buffer, cvFormat, nil, vImage_Flags(kvImageNoFlags)
+        )
+        guard error == kvImageNoError else { return Int32(error) }
+        // `rgba` owns the whole conversion; only the region is passed on.
+        rgba = full
+        var region = vImage_Buffer(
+            data: full.data + y * full.rowBytes + x * 4,
+            height: vImagePixelCount(height),
+            width: vImagePixelCount(width),
+            rowBytes: full.rowBytes
+        )
+        return Int32(body(&region))
+    }
+    return Int32(body(&rgba))
+}
+
+/// Fill `outHistograms` with 4×256 counts: red, green, blue, then alpha.
+@_cdecl("sc_pixel_buffer_rgba_histograms")
+public func pixelBufferRGBAHistograms(
+    _ source: OpaquePointer,
+    _ x: Int,
+    _ y: Int,
+    _ width: Int,
+    _ height: Int,
+    _ outHistograms: UnsafeMutablePointer<UInt64>
+) -> Int32 {
+    withRGBARegion(source, x, y, width, height) { region in
+        var channels = (0..<4).map { _ in [vImagePixelCount](repeating: 0, count: 256) }
+        let error = channels[0].withUnsafeMutableBufferPointer { r in
+            channels[1].withUnsafeMutableBufferPointer { g in
+                channels[2].withUnsafeMutableBufferPointer { b in
+                    channels[3].withUnsafeMutableBufferPointer { a in
+                        var pointers: [UnsafeMutablePointer<vImagePixelCount>?] = [
+                            r.baseAddress, g.baseAddress, b.baseAddress, a.baseAddress,
+                        ]
+                        return vImageHistogramCalculation_ARGB8888(
+                            &region, &pointers, vImage_Flags(kvImageNoFlags)
+                        )
+                    }
+                }
+            }
+        }
+        guard error == kvImageNoError else { return error }
+        for (channel, counts) in channels.enumerated() {
+            for (bin, count) in counts.enumerated() {
+                outHistograms[channel * 256 + bin] = UInt64(count)
+            }
+        }
+        return kvImageNoError
+    }
+}
+
+/// Fill `outHistogram` with 256 counts of BT.709 luma.
+@_cdecl("sc_pixel_buffer_luma_histogram")
+public func pixelBufferLumaHistogram(
+    _ source: OpaquePointer,
+    _ x: Int,
+    _ y: Int,
+    _ width: Int,
+    _ height: Int,
+    _ outHistogram: UnsafeMutablePointer<UInt64>
+) -> Int32 {
+    withRGBARegion(source, x, y, width, height) { region in
+        var luma = vImage_Buffer()
+        var error = vImageBuffer_Init(&luma, region.height, region.width, 8, vImage_Flags(kvImageNoFlags))
+        guard error == kvImageNoError else { return error }
+        defer { free(luma.data) }
+
+        // 0.2126 R + 0.7152 G + 0.0722 B, in 1/10000ths; alpha ignored.
+        let divisor: Int32 = 10000
+        let matrix: [Int16] = [2126, 7152, 722, 0]
+        var preBias: [Int16] = [0, 0, 0, 0]
+        error = vImageMatrixMultiply_ARGB8888ToPlanar8(
+            &region, &luma, matrix, divisor, &preBias, Int32(divisor / 2), vImage_Flags(kvImageNoFlags)
+        )
+        guard error == kvImageNoError else { return error }
+
+        var counts = [vImagePixelCount](repeating: 0, count: 256)
+        error = vImageHistogramCalculation_Planar8(&luma, &counts, vImage_Flags(kvImageNoFlags))
+        guard error == kvImageNoError else { return error }
+        for (bin, count) in counts.enumerated() {
+            outHistogram[bin] = UInt64(count)
+        }
+        return kvImageNoError
+    }
+}
+
+/// Scale the region down to `outWidth`×`outHeight` RGBA8888 pixels, packed
+/// into `outPixels`.
+@_cdecl("sc_pixel_buffer_rgba_thumbnail")
+public func pixelBufferRGBAThumbnail(
+    _ source: OpaquePointer,
+    _ x: Int,
+    _ y: Int,
+    _ width: Int,
+    _ height: Int,
+    _ outWidth: Int,
+    _ outHeight: Int,
+    _ outPixels: UnsafeMutablePointer<UInt8>
+) -> Int32 {
+    withRGBARegion(source, x, y, width, height) { region in
+        var thumbnail = vImage_Buffer(
+            data: outPixels,
+            height: vImagePixelCount(outHeight),
+            width: vImagePixelCount(outWidth),
+            rowBytes: outWidth * 4
+        )
+        return vImageScale_ARGB8888(&region, &thumbnail, nil, vImage_Flags(kvImageNoFlags))
+    }
+}
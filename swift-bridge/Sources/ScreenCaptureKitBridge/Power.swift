@@ -0,0 +1,28 @@
+// Power and thermal conditions, for adapting capture to save energy.
+
+import Foundation
+import IOKit.ps
+
+/// ProcessInfo.ThermalState raw value: 0 nominal, 1 fair, 2 serious,
+/// 3 critical.
+@_cdecl("sc_power_get_thermal_state")
+public func getPowerThermalState() -> Int32 {
+    Int32(ProcessInfo.processInfo.thermalState.rawValue)
+}
+
+@_cdecl("sc_power_is_low_power_mode")
+public func isPowerLowPowerMode() -> Bool {
+    ProcessInfo.processInfo.isLowPowerModeEnabled
+}
+
+/// Whether the Mac is currently running from its battery rather than
+/// external power. False on desktops.
+@_cdecl("sc_power_is_on_battery")
+public func isPowerOnBattery() -> Bool {
+    guard let info = IOPSCopyPowerSourcesInfo()?.takeRetainedValue(),
+          let type = IOPSGetProvidingPowerSourceType(info)?.takeUnretainedValue()
+    else {
+        return false
+    }
+    return (type as String) == kIOPSBatteryPowerValue
+}
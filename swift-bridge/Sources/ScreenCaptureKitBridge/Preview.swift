@@ -0,0 +1,329 @@
+// Live preview of captured frames through AVSampleBufferDisplayLayer, which
+// decodes and composites sample buffers itself so apps need no renderer.
+
+import AppKit
+import AVFoundation
+import CoreMedia
+import Foundation
+import QuartzCore
+
+// MARK: - Preview Layer
+
+private final class PreviewBox {
+    let layer = AVSampleBufferDisplayLayer()
+    /// Frames dropped because the layer wasn't ready for more data.
+    var dropped: UInt64 = 0
+    let lock = NSLock()
+
+    init() {
+        layer.videoGravity = .resizeAspect
+        layer.backgroundColor = CGColor(gray: 0, alpha: 1)
+    }
+
+    /// Where buffers go: the renderer on macOS 14+, the layer itself before.
+    func enqueue(_ sample: CMSampleBuffer) -> Bool {
+        if #available(macOS 14.0, *) {
+            let renderer = layer.sampleBufferRenderer
+            if renderer.status == .failed {
+                renderer.flush()
+            }
+            guard renderer.isReadyForMoreMediaData else { return false }
+            renderer.enqueue(sample)
+        } else {
+            if layer.status == .failed {
+                layer.flush()
+            }
+            guard layer.isReadyForMoreMediaData else { return false }
+            layer.enqueue(sample)
+        }
+        return true
+    }
+
+    func flush(removingImage: Bool) {
+        if #available(macOS 14.0, *) {
+            layer.sampleBufferRenderer.flush(removingDisplayedImage: removingImage) {}
+        } else if removingImage {
+            layer.flushAndRemoveImage()
+        } else {
+            layer.flush()
+        }
+    }
+}
+
+private func previewBox(_ ptr: OpaquePointer) -> PreviewBox {
+    Unmanaged<PreviewBox>.fromOpaque(UnsafeRawPointer(ptr)).takeUnretainedValue()
+}
+
+private func onMain(_ work: @escaping () -> Void) {
+    if Thread.isMainThread {
+        work()
+    } else {
+        DispatchQueue.main.async(execute: work)
+    }
+}
+
+@_cdecl("sc_preview_create")
+public func previewCreate() -> OpaquePointer {
+    OpaquePointer(Unmanaged.passRetained(PreviewBox()).toOpaque())
+}
+
+@_cdecl("sc_preview_release")
+public func previewRelease(_ ptr: OpaquePointer) {
+    let box = Unmanaged<PreviewBox>.fromOpaque(UnsafeRawPointer(ptr))
+    let layer = box.takeUnretainedValue().layer
+    box.release()
+    // Detach on the main thread, where AppKit expects layer tree changes.
+    onMain { layer.removeFromSuperlayer() }
+}
+
+/// The AVSampleBufferDisplayLayer, +0.
+@_cdecl("sc_preview_get_layer")
+public func previewGetLayer(_ ptr: OpaquePointer) -> OpaquePointer {
+    OpaquePointer(Unmanaged.passUnretained(previewBox(ptr).layer).toOpaque())
+}
+
+/// Add the layer as a sublayer of `view`'s backing layer, sized to fill it.
+/// Runs on the main thread; returns before that if called from elsewhere.
+@_cdecl("sc_preview_attach_to_view")
+public func previewAttachToView(_ ptr: OpaquePointer, _ view: UnsafeMutableRawPointer) {
+    let layer = previewBox(ptr).layer
+    let nsView = Unmanaged<NSView>.fromOpaque(view).takeUnretainedValue()
+    onMain {
+        nsView.wantsLayer = true
+        guard let host = nsView.layer else { return }
+        layer.frame = host.bounds
+        layer.autoresizingMask = [.layerWidthSizable, .layerHeightSizable]
+        host.addSublayer(layer)
+    }
+}
+
+/// Add the layer as a sublayer of `superlayer`, sized to fill it.
+@_cdecl("sc_preview_attach_to_layer")
+public func previewAttachToLayer(_ ptr: OpaquePointer, _ superlayer: UnsafeMutableRawPointer) {
+    let layer = previewBox(ptr).layer
+    let host = Unmanaged<CALayer>.fromOpaque(superlayer).takeUnretainedValue()
+    onMain {
+        layer.frame = host.bounds
+        layer.autoresizingMask = [.layerWidthSizable, .layerHeightSizable]
+        host.addSublayer(layer)
+    }
+}
+
+/// Enqueue a frame for display as soon as it's decoded. Returns false if the
+/// sample has no image or the layer is still busy with earlier frames.
+@_cdecl("sc_preview_enqueue")
+public func previewEnqueue(_ ptr: OpaquePointer, _ sample: UnsafeRawPointer) -> Bool {
+    let box = previewBox(ptr)
+    let buffer = Unmanaged<CMSampleBuffer>.fromOpaque(sample).takeUnretainedValue()
+    guard CMSampleBufferGetImageBuffer(buffer) != nil else { return false }
+
+    // Frames carry host-time timestamps the layer has no timebase for; show
+    // each one on arrival instead.
+    if let attachments = CMSampleBufferGetSampleAttachmentsArray(buffer, createIfNecessary: true),
+       CFArrayGetCount(attachments) > 0 {
+        let dict = unsafeBitCast(CFArrayGetValueAtIndex(attachments, 0), to: CFMutableDictionary.self)
+        CFDictionarySetValue(
+            dict,
+            Unmanaged.passUnretained(kCMSampleAttachmentKey_DisplayImmediately).toOpaque(),
+            Unmanaged.passUnretained(kCFBooleanTrue).toOpaque()
+        )
+    }
+
+    box.lock.lock()
+    defer { box.lock.unlock() }
+    if box.enqueue(buffer) {
+        return true
+    }
+    box.dropped += 1
+    return false
+}
+
+@_cdecl("sc_preview_flush")
+public func previewFlush(_ ptr: OpaquePointer, _ removingImage: Bool) {
+    let box = previewBox(ptr)
+    box.lock.lock()
+    defer { box.lock.unlock() }
+    box.flush(removingImage: removingImage)
+}
+
+/// 0 resize aspect, 1 resize aspect fill, 2 resize.
+@_cdecl("sc_preview_set_video_gravity")
+public func previewSetVideoGravity(_ ptr: OpaquePointer, _ gravity: Int32) {
+    let layer = previewBox(ptr).layer
+    let value: AVLayerVideoGravity
+    switch gravity {
+    case 1: value = .resizeAspectFill
+    case 2: value = .resize
+    default: value = .resizeAspect
+    }
+    onMain { layer.videoGravity = value }
+}
+
+@_cdecl("sc_preview_dropped_frames")
+public func previewDroppedFrames(_ ptr: OpaquePointer) -> UInt64 {
+    let box = previewBox(ptr)
+    box.lock.lock()
+    defer { box.lock.unlock() }
+    return box.dropped
+}
+
+// MARK: - Preview Window
+
+/// A plain titled window showing a preview layer. Every AppKit call is made on
+/// the main thread; the flags let Rust read the state from any thread.
+private final class PreviewWindowBox: NSObject, NSWindowDelegate {
+    let preview: PreviewBox
+    var window: NSWindow?
+    let lock = NSLock()
+    var visible = false
+    var closed = false
+
+    init(preview: PreviewBox) {
+        self.preview = preview
+    }
+
+    func open(title: String, width: Double, height: Double) {
+        let window = NSWindow(
+            contentRect: NSRect(x: 0, y: 0, width: width, height: height),
+            styleMask: [.titled, .closable, .miniaturizable, .resizable],
+            backing: .buffered,
+            defer: false
+        )
+        window.title = title
+        window.isReleasedWhenClosed = false
+        window.delegate = self
+        window.center()
+        if let content = window.contentView {
+            content.wantsLayer = true
+            if let host = content.layer {
+                preview.layer.frame = host.bounds
+                preview.layer.autoresizingMask = [.layerWidthSizable, .layerHeightSizable]
+                host.addSublayer(preview.layer)
+            }
+        }
+        self.window = window
+    }
+
+    func setState(visible: Bool, closed: Bool? = nil) {
+        lock.lock()
+        defer { lock.unlock() }
+        self.visible = visible
+        if let closed { self.closed = closed }
+    }
+
+    func windowWillClose(_: Notification) {
+        setState(visible: false, closed: true)
+    }
+
+    func windowDidMiniaturize(_: Notification) {
+        setState(visible: false)
+    }
+
+    func windowDidDeminiaturize(_: Notification) {
+        setState(visible: true)
+    }
+}
+
+private func previewWindowBox(_ ptr: OpaquePointer) -> PreviewWindowBox {
+    Unmanaged<PreviewWindowBox>.fromOpaque(UnsafeRawPointer(ptr)).takeUnretainedValue()
+}
+
+/// Create a hidden window around `preview`. The window itself is made on the
+/// main thread, right away when called there and queued otherwise.
+@_cdecl("sc_preview_window_create")
+public func previewWindowCreate(
+    _ preview: OpaquePointer,
+    _ title: UnsafePointer<CChar>,
+    _ width: Double,
+    _ height: Double
+) -> OpaquePointer {
+    let box = PreviewWindowBox(preview: previewBox(preview))
+    let title = String(cString: title)
+    onMain { box.open(title: title, width: width, height: height) }
+    return OpaquePointer(Unmanaged.passRetained(box).toOpaque())
+}
+
+@_cdecl("sc_preview_window_release")
+public func previewWindowRelease(_ ptr: OpaquePointer) {
+    let box = Unmanaged<PreviewWindowBox>.fromOpaque(UnsafeRawPointer(ptr)).takeRetainedValue()
+    onMain {
+        box.window?.delegate = nil
+        box.window?.close()
+        box.preview.layer.removeFromSuperlayer()
+    }
+}
+
+@_cdecl("sc_preview_window_show")
+public func previewWindowShow(_ ptr: OpaquePointer) {
+    let box = previewWindowBox(ptr)
+    box.setState(visible: true, closed: false)
+    onMain {
+        NSApplication.shared.setActivationPolicy(.regular)
+        NSApp.activate(ignoringOtherApps: true)
+        box.window?.makeKeyAndOrderFront(nil)
+    }
+}
+
+@_cdecl("sc_preview_window_hide")
+public func previewWindowHide(_ ptr: OpaquePointer) {
+    let box = previewWindowBox(ptr)
+    box.setState(visible: false)
+    onMain { box.window?.orderOut(nil) }
+}
+
+/// Resize the content area to `width` x `height` points, keeping the
+/// window's top-left corner in place.
+@_cdecl("sc_preview_window_set_size")
+public func previewWindowSetSize(_ ptr: OpaquePointer, _ width: Double, _ height: Double) {
+    let box = previewWindowBox(ptr)
+    onMain {
+        guard let window = box.window else { return }
+        var frame = window.frameRect(forContentRect: NSRect(x: 0, y: 0, width: width, height: height))
+        frame.origin.x = window.frame.minX
+        frame.origin.y = window.frame.maxY - frame.height
+        window.setFrame(frame, display: true, animate: false)
+    }
+}
+
+@_cdecl("sc_preview_window_set_title")
+public func previewWindowSetTitle(_ ptr: OpaquePointer, _ title: UnsafePointer<CChar>) {
+    let box = previewWindowBox(ptr)
+    let title = String(cString: title)
+    onMain { box.window?.title = title }
+}
+
+@_cdecl("sc_preview_window_is_visible")
+public func previewWindowIsVisible(_ ptr: OpaquePointer) -> Bool {
+    let box = previewWindowBox(ptr)
+    box.lock.lock()
+    defer { box.lock.unlock() }
+    return box.visible
+}
+
+@_cdecl("sc_preview_window_is_closed")
+public func previewWindowIsClosed(_ ptr: OpaquePointer) -> Bool {
+    let box = previewWindowBox(ptr)
+    box.lock.lock()
+    defer { box.lock.unlock() }
+    return box.closed
+}
+
+/// Run the main thread's event loop until the user closes the window or
+/// `timeout` seconds pass (no limit when negative). Returns false without
+/// running anything off the main thread.
+@_cdecl("sc_preview_window_run")
+public func previewWindowRun(_ ptr: OpaquePointer, _ timeout: Double) -> Bool {
+    guard Thread.isMainThread else { return false }
+    let app = NSApplication.shared
+    app.finishLaunching()
+    let deadline = timeout < 0 ? Date.distantFuture : Date(timeIntervalSinceNow: timeout)
+    while !previewWindowIsClosed(ptr), Date() < deadline {
+        autoreleasepool {
+            let until = min(deadline, Date(timeIntervalSinceNow: 0.1))
+            if let event = app.nextEvent(matching: .any, until: until, inMode: .default, dequeue: true) {
+                app.sendEvent(event)
+            }
+        }
+    }
+    return true
+}
@@ -0,0 +1,293 @@
+// QuickTime chapter tracks for finished recordings.
+//
+// Copies a movie's audio and video samples unchanged into a new file and
+// adds a disabled text track referenced as the chapter list, which is what
+// QuickTime Player, Final Cut and most players read as chapters.
+
+import AVFoundation
+import CoreMedia
+import Foundation
+
+// MARK: - Recording Chapters
+
+private func chapterError(_ code: Int, _ message: String) -> NSError {
+    NSError(
+        domain: "ScreenCaptureKitBridge.RecordingChapters",
+        code: code,
+        userInfo: [NSLocalizedDescriptionKey: message]
+    )
+}
+
+/// Format description for QuickTime text samples holding chapter titles.
+private func chapterFormatDescription() throws -> CMFormatDescription {
+    let extensions: [CFString: Any] = [
+        kCMTextFormatDescriptionExtension_DisplayFlags: 0,
+        kCMTextFormatDescriptionExtension_BackgroundColor: [
+            kCMTextFormatDescriptionColor_Red: 0,
+            kCMTextFormatDescriptionColor_Green: 0,
+            kCMTextFormatDescriptionColor_Blue: 0,
+            kCMTextFormatDescriptionColor_Alpha: 255,
+        ],
+        kCMTextFormatDescriptionExtension_DefaultTextBox: [
+            kCMTextFormatDescriptionRect_Top: 0,
+            kCMTextFormatDescriptionRect_Left: 0,
+            kCMTextFormatDescriptionRect_Bottom: 0,
+            kCMTextFormatDescriptionRect_Right: 0,
+        ],
+        kCMTextFormatDescriptionExtension_DefaultStyle: [
+            kCMTextFormatDescriptionStyle_StartChar: 0,
+            kCMTextFormatDescriptionStyle_EndChar: 0,
+            kCMTextFormatDescriptionStyle_Font: 1,
+            kCMTextFormatDescriptionStyle_FontFace: 0,
+            kCMTextFormatDescriptionStyle_ForegroundColor: [
+                kCMTextFormatDescriptionColor_Red: 255,
+                kCMTextFormatDescriptionColor_Green: 255,
+                kCMTextFormatDescriptionColor_Blue: 255,
+                kCMTextFormatDescriptionColor_Alpha: 255,
+            ],
+            kCMTextFormatDescriptionStyle_FontSize: 12,
+        ],
+        kCMTextFormatDescriptionExtension_HorizontalJustification: 0,
+        kCMTextFormatDescriptionExtension_VerticalJustification: 0,
+        kCMTextFormatDescriptionExtension_FontTable: ["1": "Sans-Serif"],
+    ]
+    var format: CMFormatDescription?
+    let status = CMFormatDescriptionCreate(
+        allocator: kCFAllocatorDefault,
+        mediaType: kCMMediaType_Text,
+        mediaSubType: kCMTextFormatType_QTText,
+        extensions: extensions as CFDictionary,
+        formatDescriptionOut: &format
+    )
+    guard status == noErr, let format else {
+        throw chapterError(Int(status), "cannot create the chapter text format")
+    }
+    return format
+}
+
+/// One text sample: a big-endian length, the UTF-8 title and an `encd`
+/// atom marking the text as UTF-8.
+private func chapterSample(
+    _ title: String,
+    format: CMFormatDescription,
+    start: CMTime,
+    duration: CMTime
+) throws -> CMSampleBuffer {
+    let text = Array(title.utf8.prefix(Int(UInt16.max)))
+    var data = Data()
+    data.append(UInt8(text.count >> 8))
+    data.append(UInt8(text.count & 0xFF))
+    data.append(contentsOf: text)
+    data.append(contentsOf: [0, 0, 0, 12] + Array("encd".utf8) + [0, 0, 1, 0])
+
+    var block: CMBlockBuffer?
+    var status = CMBlockBufferCreateWithMemoryBlock(
+        allocator: kCFAllocatorDefault,
+        memoryBlock: nil,
+        blockLength: data.count,
+        blockAllocator: kCFAllocatorDefault,
+        customBlockSource: nil,
+        offsetToData: 0,
+        dataLength: data.count,
+        flags: 0,
+        blockBufferOut: &block
+    )
+    guard status == noErr, let block else {
+        throw chapterError(Int(status), "cannot allocate a chapter sample")
+    }
+    status = data.withUnsafeBytes {
+        CMBlockBufferReplaceDataBytes(
+            with: $0.baseAddress!,
+            blockBuffer: block,
+            offsetIntoDestination: 0,
+            dataLength: data.count
+        )
+    }
+    guard status == noErr else {
+        throw chapterError(Int(status), "cannot fill a chapter sample")
+    }
+
+    var timing = CMSampleTimingInfo(duration: duration, presentationTimeStamp: start, decodeTimeStamp: .invalid)
+    var size = data.count
+    var sample: CMSampleBuffer?
+    status = CMSampleBufferCreateReady(
+        allocator: kCFAllocatorDefault,
+        dataBuffer: block,
+        formatDescription: format,
+        sampleCount: 1,
+        sampleTimingEntryCount: 1,
+        sampleTimingArray: &timing,
+        sampleSizeEntryCount: 1,
+        sampleSizeArray: &size,
+        sampleBufferOut: &sample
+    )
+    guard status == noErr, let sample else {
+        throw chapterError(Int(status), "cannot create a chapter sample")
+    }
+    return sample
+}
+
+/// The first error hit while copying samples.
+private final class ChapterCopyState {
+    let lock = NSLock()
+    var failure: Error?
+
+    func fail(_ error: Error) {
+        lock.lock()
+        defer { lock.unlock() }
+        if failure == nil { failure = error }
+    }
+}
+
+private func copyWithChapters(
+    from inputURL: URL,
+    to outputURL: URL,
+    chapters: [(title: String, start: CMTime)]
+) async throws {
+    let asset = AVURLAsset(url: inputURL)
+    let duration = try await asset.load(.duration)
+    let tracks = try await asset.load(.tracks)
+
+    let fileType: AVFileType
+    switch outputURL.pathExtension.lowercased() {
+    case "m4v": fileType = .m4v
+    case "mp4": fileType = .mp4
+    default: fileType = .mov
+    }
+    if FileManager.default.fileExists(atPath: outputURL.path) {
+        try FileManager.default.removeItem(at: outputURL)
+    }
+    let reader = try AVAssetReader(asset: asset)
+    let writer = try AVAssetWriter(outputURL: outputURL, fileType: fileType)
+
+    var copies: [(AVAssetReaderTrackOutput, AVAssetWriterInput)] = []
+    for track in tracks where track.mediaType == .video || track.mediaType == .audio {
+        let hint = try await track.load(.formatDescriptions).first
+        let output = AVAssetReaderTrackOutput(track: track, outputSettings: nil)
+        output.alwaysCopiesSampleData = false
+        let input = AVAssetWriterInput(mediaType: track.mediaType, outputSettings: nil, sourceFormatHint: hint)
+        input.expectsMediaDataInRealTime = false
+        if track.mediaType == .video {
+            input.transform = try await track.load(.preferredTransform)
+        }
+        guard reader.canAdd(output), writer.canAdd(input) else {
+            throw chapterError(1, "cannot copy track \(track.trackID) of \(inputURL.path)")
+        }
+        reader.add(output)
+        writer.add(input)
+        copies.append((output, input))
+    }
+
+    let format = try chapterFormatDescription()
+    let chapterInput = AVAssetWriterInput(mediaType: .text, outputSettings: nil, sourceFormatHint: format)
+    chapterInput.marksOutputTrackAsEnabled = false
+    chapterInput.expectsMediaDataInRealTime = false
+    guard writer.canAdd(chapterInput) else {
+        throw chapterError(2, "\(fileType.rawValue) files can't hold a chapter track")
+    }
+    writer.add(chapterInput)
+    for (_, input) in copies {
+        input.addTrackAssociation(
+            withTrackOf: chapterInput,
+            type: AVAssetTrack.AssociationType.chapterList.rawValue
+        )
+    }
+
+    // Each chapter runs until the next one starts, the last to the end.
+    var samples: [CMSampleBuffer] = []
+    for (index, chapter) in chapters.enumerated() {
+        let end = index + 1 < chapters.count ? chapters[index + 1].start : duration
+        samples.append(try chapterSample(
+            chapter.title,
+            format: format,
+            start: chapter.start,
+            duration: CMTimeSubtract(end, chapter.start)
+        ))
+    }
+
+    guard reader.startReading() else {
+        throw reader.error ?? chapterError(3, "cannot read \(inputURL.path)")
+    }
+    guard writer.startWriting() else {
+        throw writer.error ?? chapterError(4, "cannot write \(outputURL.path)")
+    }
+    writer.startSession(atSourceTime: .zero)
+
+    let state = ChapterCopyState()
+    let queue = DispatchQueue(label: "screencapturekit.recording-chapters")
+    let group = DispatchGroup()
+    for (output, input) in copies {
+        group.enter()
+        input.requestMediaDataWhenReady(on: queue) {
+            while input.isReadyForMoreMediaData {
+                guard let sample = output.copyNextSampleBuffer() else {
+                    input.markAsFinished()
+                    group.leave()
+                    return
+                }
+                if !input.append(sample) {
+                    state.fail(writer.error ?? chapterError(5, "cannot append to \(outputURL.path)"))
+                    input.markAsFinished()
+                    group.leave()
+                    return
+                }
+            }
+        }
+    }
+    var pending = samples[...]
+    group.enter()
+    chapterInput.requestMediaDataWhenReady(on: queue) {
+        while chapterInput.isReadyForMoreMediaData {
+            guard let sample = pending.popFirst() else {
+                chapterInput.markAsFinished()
+                group.leave()
+                return
+            }
+            chapterInput.append(sample)
+        }
+    }
+    await withCheckedContinuation { continuation in
+        group.notify(queue: queue) { continuation.resume() }
+    }
+
+    if let failure = state.failure ?? (reader.status == .failed ? reader.error : nil) {
+        reader.cancelReading()
+        writer.cancelWriting()
+        throw failure
+    }
+    await writer.finishWriting()
+    guard writer.status == .completed else {
+        throw writer.error ?? chapterError(6, "writing ended with status \(writer.status.rawValue)")
+    }
+}
+
+/// Copy the movie at `input` to `output` with a chapter track.
+///
+/// `titles` and `offsetsNanos` hold `count` chapters sorted by offset.
+/// Samples are copied without re-encoding. An existing file at `output` is
+/// replaced. `callback` runs once, on an arbitrary queue.
+@_cdecl("sc_recording_write_chapters")
+public func writeRecordingChapters(
+    _ input: UnsafePointer<CChar>,
+    _ output: UnsafePointer<CChar>,
+    _ titles: UnsafePointer<UnsafePointer<CChar>>,
+    _ offsetsNanos: UnsafePointer<Int64>,
+    _ count: Int,
+    _ context: UnsafeMutableRawPointer?,
+    _ callback: @escaping RecordingTrimCallback
+) {
+    let inputURL = URL(fileURLWithPath: String(cString: input))
+    let outputURL = URL(fileURLWithPath: String(cString: output))
+    let chapters = (0..<count).map {
+        (title: String(cString: titles[$0]), start: CMTime(value: offsetsNanos[$0], timescale: 1_000_000_000))
+    }
+
+    Task {
+        do {
+            try await copyWithChapters(from: inputURL, to: outputURL, chapters: chapters)
+            callback(context, true, nil)
+        } catch {
+            withErrorPointer(error) { callback(context, false, $0) }
+        }
+    }
+}
@@ -8,7 +8,7 @@ import ScreenCaptureKit
 
 // Callback type definitions for recording delegate
 public typealias RecordingStartedCallback = @convention(c) (UnsafeMutableRawPointer?) -> Void
-public typealias RecordingFailedCallback = @convention(c) (UnsafeMutableRawPointer?, Int32, UnsafePointer<CChar>) -> Void
+public typealias RecordingFailedCallback = @convention(c) (UnsafeMutableRawPointer?, OpaquePointer) -> Void
 public typealias RecordingFinishedCallback = @convention(c) (UnsafeMutableRawPointer?) -> Void
 
 #if SCREENCAPTUREKIT_HAS_MACOS15_SDK
@@ -30,8 +30,7 @@ public typealias RecordingFinishedCallback = @convention(c) (UnsafeMutableRawPoi
 
         func recordingOutput(_: SCRecordingOutput, didFailWithError error: Error) {
             if let cb = failedCallback {
-                let errorCode = extractStreamErrorCode(error)
-                error.localizedDescription.withCString { cb(context, errorCode, $0) }
+                withErrorPointer(error) { cb(context, $0) }
             }
         }
 
@@ -342,3 +341,26 @@ public typealias RecordingFinishedCallback = @convention(c) (UnsafeMutableRawPoi
     public func releaseRecordingOutput(_: OpaquePointer?) {}
 
 #endif
+
+// MARK: - Disk Space
+
+/// Free bytes on the volume holding `path`, counting space macOS can purge
+/// for important writes. `path` need not exist yet; the nearest existing
+/// ancestor is queried. Returns -1 if the volume can't be read.
+@_cdecl("sc_volume_available_capacity")
+public func volumeAvailableCapacity(_ path: UnsafePointer<CChar>) -> Int64 {
+    var url = URL(fileURLWithPath: String(cString: path))
+    while !FileManager.default.fileExists(atPath: url.path), url.pathComponents.count > 1 {
+        url.deleteLastPathComponent()
+    }
+    guard let values = try? url.resourceValues(forKeys: [
+        .volumeAvailableCapacityForImportantUsageKey,
+        .volumeAvailableCapacityKey,
+    ]) else {
+        return -1
+    }
+    if let important = values.volumeAvailableCapacityForImportantUsage, important > 0 {
+        return important
+    }
+    return values.volumeAvailableCapacity.map(Int64.init) ?? -1
+}
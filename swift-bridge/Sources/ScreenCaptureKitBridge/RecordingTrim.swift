@@ -0,0 +1,107 @@
+// Trimming for finished recordings.
+//
+// Re-exports a time range of a movie file through AVAssetExportSession so
+// apps can drop the first or last few seconds of a capture (the start/stop
+// UI fumbling) without pulling in a separate editing stack.
+
+import AVFoundation
+import Foundation
+
+// MARK: - Recording Trim
+
+public typealias RecordingTrimCallback = @convention(c) (UnsafeMutableRawPointer?, Bool, OpaquePointer?) -> Void
+
+private func trimError(_ code: Int, _ message: String) -> NSError {
+    NSError(
+        domain: "ScreenCaptureKitBridge.RecordingTrim",
+        code: code,
+        userInfo: [NSLocalizedDescriptionKey: message]
+    )
+}
+
+/// Read the duration of the movie at `path`.
+///
+/// Blocks until AVFoundation has loaded the asset. Returns false if the file
+/// can't be opened as a movie.
+@_cdecl("sc_recording_get_duration")
+public func getRecordingDuration(
+    _ path: UnsafePointer<CChar>,
+    _ value: UnsafeMutablePointer<Int64>,
+    _ timescale: UnsafeMutablePointer<Int32>
+) -> Bool {
+    let asset = AVURLAsset(url: URL(fileURLWithPath: String(cString: path)))
+    let semaphore = DispatchSemaphore(value: 0)
+    var duration: CMTime?
+    Task {
+        duration = try? await asset.load(.duration)
+        semaphore.signal()
+    }
+    semaphore.wait()
+
+    guard let duration, duration.isValid, !duration.isIndefinite else {
+        return false
+    }
+    value.pointee = duration.value
+    timescale.pointee = duration.timescale
+    return true
+}
+
+/// Export `[startNanos, endNanos)` of the movie at `input` to `output`.
+///
+/// Re-encodes with the highest-quality preset so the cut lands on the
+/// requested frame rather than the nearest keyframe. The container follows
+/// the output extension (`.mov`, `.m4v`, otherwise MP4). An existing file at
+/// `output` is replaced. `callback` runs once, on an arbitrary queue.
+@_cdecl("sc_recording_trim")
+public func trimRecording(
+    _ input: UnsafePointer<CChar>,
+    _ output: UnsafePointer<CChar>,
+    _ startNanos: Int64,
+    _ endNanos: Int64,
+    _ context: UnsafeMutableRawPointer?,
+    _ callback: @escaping RecordingTrimCallback
+) {
+    let inputURL = URL(fileURLWithPath: String(cString: input))
+    let outputURL = URL(fileURLWithPath: String(cString: output))
+    let asset = AVURLAsset(url: inputURL)
+
+    guard let session = AVAssetExportSession(
+        asset: asset,
+        presetName: AVAssetExportPresetHighestQuality
+    ) else {
+        withErrorPointer(trimError(1, "cannot create an export session for \(inputURL.path)")) {
+            callback(context, false, $0)
+        }
+        return
+    }
+
+    switch outputURL.pathExtension.lowercased() {
+    case "mov": session.outputFileType = .mov
+    case "m4v": session.outputFileType = .m4v
+    default: session.outputFileType = .mp4
+    }
+    session.outputURL = outputURL
+    session.timeRange = CMTimeRange(
+        start: CMTime(value: startNanos, timescale: 1_000_000_000),
+        end: CMTime(value: endNanos, timescale: 1_000_000_000)
+    )
+
+    if FileManager.default.fileExists(atPath: outputURL.path) {
+        do {
+            try FileManager.default.removeItem(at: outputURL)
+        } catch {
+            withErrorPointer(error) { callback(context, false, $0) }
+            return
+        }
+    }
+
+    session.exportAsynchronously {
+        switch session.status {
+        case .completed:
+            callback(context, true, nil)
+        default:
+            let error = session.error ?? trimError(2, "export ended with status \(session.status.rawValue)")
+            withErrorPointer(error) { callback(context, false, $0) }
+        }
+    }
+}
@@ -0,0 +1,69 @@
+// Black out rectangles of captured frames before they leave the process.
+//
+// Masking redraws the frame into a pooled buffer rather than touching the
+// IOSurface ScreenCaptureKit delivered, which other outputs of the same
+// stream may still be reading.
+
+import CoreImage
+import CoreMedia
+import CoreVideo
+import Foundation
+
+private final class RegionMask {
+    let context = CIContext(options: [.cacheIntermediates: false])
+    let pool = FramePool()
+    let lock = NSLock()
+}
+
+@_cdecl("sc_region_mask_create")
+public func regionMaskCreate() -> OpaquePointer {
+    OpaquePointer(Unmanaged.passRetained(RegionMask()).toOpaque())
+}
+
+@_cdecl("sc_region_mask_release")
+public func regionMaskRelease(_ handle: OpaquePointer) {
+    Unmanaged<RegionMask>.fromOpaque(UnsafeRawPointer(handle)).release()
+}
+
+/// Fill `count` rectangles of the frame in `sample` with opaque black.
+/// `rects` holds x, y, width, height for each, in pixels from the frame's
+/// top-left corner. Returns a +1 sample buffer with the same timing and
+/// attachments, or nil if the sample has no image or rendering failed.
+@_cdecl("sc_region_mask_apply")
+public func regionMaskApply(
+    _ handle: OpaquePointer,
+    _ sample: OpaquePointer,
+    _ rects: UnsafePointer<Double>,
+    _ count: Int
+) -> UnsafeMutableRawPointer? {
+    let box = Unmanaged<RegionMask>.fromOpaque(UnsafeRawPointer(handle)).takeUnretainedValue()
+    let sampleBuffer = Unmanaged<CMSampleBuffer>.fromOpaque(UnsafeRawPointer(sample)).takeUnretainedValue()
+    guard let screenBuffer = CMSampleBufferGetImageBuffer(sampleBuffer) else { return nil }
+
+    let width = CVPixelBufferGetWidth(screenBuffer)
+    let height = CVPixelBufferGetHeight(screenBuffer)
+    let format = CVPixelBufferGetPixelFormatType(screenBuffer)
+
+    let screen = CIImage(cvPixelBuffer: screenBuffer)
+    var image = screen
+    let black = CIImage(color: CIColor(red: 0, green: 0, blue: 0, alpha: 1))
+    for index in 0 ..< count {
+        let values = rects + index * 4
+        // Core Image's origin is bottom-left.
+        let rect = CGRect(
+            x: values[0],
+            y: Double(height) - values[1] - values[3],
+            width: values[2],
+            height: values[3]
+        )
+        image = black.cropped(to: rect).composited(over: image)
+    }
+    image = image.cropped(to: screen.extent)
+
+    box.lock.lock()
+    defer { box.lock.unlock() }
+    guard let output = box.pool.buffer(width: width, height: height, format: format) else { return nil }
+    renderFrame(image, from: screenBuffer, into: output, with: box.context)
+    guard let result = frameSampleBuffer(like: sampleBuffer, with: output) else { return nil }
+    return Unmanaged.passRetained(result).toOpaque()
+}
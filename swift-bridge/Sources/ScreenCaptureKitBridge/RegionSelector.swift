@@ -0,0 +1,195 @@
+// Interactive region selector
+//
+// A `screencapture -i`-style overlay: one borderless, transparent window per
+// screen, a crosshair cursor, and a rubber-band rectangle drawn while the
+// user drags. Escape (or a click without a drag) cancels.
+//
+// The selected rectangle is reported in global display coordinates (points,
+// origin at the top-left of the main display) — the same space used by
+// `SCDisplay.frame` and `SCScreenshotManager.captureImage(in:)` — together
+// with the `CGDirectDisplayID` and backing scale of the screen it was drawn on.
+
+import AppKit
+import CoreGraphics
+import Foundation
+
+// MARK: - Overlay Window / View
+
+private final class RegionOverlayWindow: NSWindow {
+    override var canBecomeKey: Bool { true }
+    override var canBecomeMain: Bool { true }
+}
+
+private final class RegionSelectionView: NSView {
+    var onFinish: ((NSRect?) -> Void)?
+    private var startPoint: NSPoint?
+    private var currentRect: NSRect?
+
+    override var acceptsFirstResponder: Bool { true }
+
+    override func resetCursorRects() {
+        addCursorRect(bounds, cursor: .crosshair)
+    }
+
+    override func draw(_: NSRect) {
+        NSColor.black.withAlphaComponent(0.25).setFill()
+        bounds.fill()
+
+        guard let rect = currentRect else { return }
+        // Punch the selection out of the dimmed backdrop and outline it.
+        NSColor.clear.setFill()
+        rect.fill(using: .copy)
+        NSColor.white.setStroke()
+        let path = NSBezierPath(rect: rect.insetBy(dx: 0.5, dy: 0.5))
+        path.lineWidth = 1
+        path.stroke()
+    }
+
+    override func mouseDown(with event: NSEvent) {
+        startPoint = convert(event.locationInWindow, from: nil)
+        currentRect = nil
+        needsDisplay = true
+    }
+
+    override func mouseDragged(with event: NSEvent) {
+        guard let start = startPoint else { return }
+        let point = convert(event.locationInWindow, from: nil)
+        currentRect = NSRect(
+            x: min(start.x, point.x),
+            y: min(start.y, point.y),
+            width: abs(point.x - start.x),
+            height: abs(point.y - start.y)
+        )
+        needsDisplay = true
+    }
+
+    override func mouseUp(with _: NSEvent) {
+        let rect = currentRect
+        startPoint = nil
+        currentRect = nil
+        if let rect = rect, rect.width >= 1, rect.height >= 1 {
+            onFinish?(rect)
+        } else {
+            onFinish?(nil)
+        }
+    }
+
+    override func keyDown(with event: NSEvent) {
+        // 53 = kVK_Escape
+        if event.keyCode == 53 {
+            onFinish?(nil)
+        } else {
+            super.keyDown(with: event)
+        }
+    }
+}
+
+// MARK: - Session
+
+private final class RegionSelectionSession {
+    typealias Callback = @convention(c) (Int32, Double, Double, Double, Double, UInt32, Double, UnsafeMutableRawPointer?) -> Void
+
+    private let callback: Callback
+    private let userData: UnsafeMutableRawPointer?
+    private var windows: [RegionOverlayWindow] = []
+    private var hasCompleted = false
+
+    init(callback: @escaping Callback, userData: UnsafeMutableRawPointer?) {
+        self.callback = callback
+        self.userData = userData
+    }
+
+    /// Must be called on the main queue.
+    func present() {
+        for screen in NSScreen.screens {
+            let window = RegionOverlayWindow(
+                contentRect: screen.frame,
+                styleMask: .borderless,
+                backing: .buffered,
+                defer: false
+            )
+            window.isOpaque = false
+            window.backgroundColor = .clear
+            window.level = .screenSaver
+            window.hasShadow = false
+            window.ignoresMouseEvents = false
+            window.isReleasedWhenClosed = false
+            window.collectionBehavior = [.canJoinAllSpaces, .fullScreenAuxiliary]
+
+            let view = RegionSelectionView(frame: NSRect(origin: .zero, size: screen.frame.size))
+            view.onFinish = { [weak self, weak window] rect in
+                guard let self = self else { return }
+                guard let rect = rect, let window = window, let screen = window.screen else {
+                    self.finish(nil, screen: nil)
+                    return
+                }
+                self.finish(window.convertToScreen(rect), screen: screen)
+            }
+            window.contentView = view
+            window.makeKeyAndOrderFront(nil)
+            window.makeFirstResponder(view)
+            windows.append(window)
+        }
+
+        if windows.isEmpty {
+            finish(nil, screen: nil)
+        }
+    }
+
+    private func finish(_ cocoaRect: NSRect?, screen: NSScreen?) {
+        guard !hasCompleted else { return }
+        hasCompleted = true
+
+        for window in windows {
+            window.orderOut(nil)
+            window.close()
+        }
+        windows.removeAll()
+        activeRegionSelection = nil
+
+        guard let cocoaRect = cocoaRect, let screen = screen else {
+            callback(0, 0, 0, 0, 0, 0, 0, userData) // 0 = cancelled
+            return
+        }
+
+        // Cocoa screen space has its origin at the bottom-left of the main
+        // display; flip into the top-left global display space used by
+        // CoreGraphics and ScreenCaptureKit.
+        let mainHeight = NSScreen.screens.first?.frame.height ?? screen.frame.height
+        let x = Double(cocoaRect.origin.x)
+        let y = Double(mainHeight - cocoaRect.origin.y - cocoaRect.height)
+        let displayID = (screen.deviceDescription[NSDeviceDescriptionKey("NSScreenNumber")] as? NSNumber)?.uint32Value ?? 0
+        let scale = Double(screen.backingScaleFactor)
+
+        callback(1, x, y, Double(cocoaRect.width), Double(cocoaRect.height), displayID, scale, userData)
+    }
+
+    /// Resolve a superseded session as cancelled so its Rust context is reclaimed.
+    func cancel() {
+        finish(nil, screen: nil)
+    }
+}
+
+// Keeps the active session (and its windows) alive while the overlay is up.
+private var activeRegionSelection: RegionSelectionSession?
+
+// MARK: - Region Selector Bridge
+
+@_cdecl("sc_region_selector_show")
+public func showRegionSelector(
+    _ callback: @escaping @convention(c) (Int32, Double, Double, Double, Double, UInt32, Double, UnsafeMutableRawPointer?) -> Void,
+    _ userData: UnsafeMutableRawPointer?
+) {
+    DispatchQueue.main.async {
+        NSApp.setActivationPolicy(.regular)
+        NSApp.activate(ignoringOtherApps: true)
+
+        if let old = activeRegionSelection {
+            old.cancel()
+        }
+
+        let session = RegionSelectionSession(callback: callback, userData: userData)
+        activeRegionSelection = session
+        session.present()
+    }
+}
@@ -248,6 +248,85 @@ import UniformTypeIdentifiers
         }
     }
 
+    @_cdecl("sc_screenshot_configuration_get_width")
+    public func getScreenshotConfigurationWidth(_ config: OpaquePointer) -> Int {
+        if #available(macOS 26.0, *) {
+            let c: SCScreenshotConfiguration = unretained(config)
+            return c.width
+        }
+        return 0
+    }
+
+    @_cdecl("sc_screenshot_configuration_get_height")
+    public func getScreenshotConfigurationHeight(_ config: OpaquePointer) -> Int {
+        if #available(macOS 26.0, *) {
+            let c: SCScreenshotConfiguration = unretained(config)
+            return c.height
+        }
+        return 0
+    }
+
+    @_cdecl("sc_screenshot_configuration_get_shows_cursor")
+    public func getScreenshotConfigurationShowsCursor(_ config: OpaquePointer) -> Bool {
+        if #available(macOS 26.0, *) {
+            let c: SCScreenshotConfiguration = unretained(config)
+            return c.showsCursor
+        }
+        return false
+    }
+
+    @_cdecl("sc_screenshot_configuration_get_ignore_shadows")
+    public func getScreenshotConfigurationIgnoreShadows(_ config: OpaquePointer) -> Bool {
+        if #available(macOS 26.0, *) {
+            let c: SCScreenshotConfiguration = unretained(config)
+            return c.ignoreShadows
+        }
+        return false
+    }
+
+    @_cdecl("sc_screenshot_configuration_get_ignore_clipping")
+    public func getScreenshotConfigurationIgnoreClipping(_ config: OpaquePointer) -> Bool {
+        if #available(macOS 26.0, *) {
+            let c: SCScreenshotConfiguration = unretained(config)
+            return c.ignoreClipping
+        }
+        return false
+    }
+
+    @_cdecl("sc_screenshot_configuration_get_include_child_windows")
+    public func getScreenshotConfigurationIncludeChildWindows(_ config: OpaquePointer) -> Bool {
+        if #available(macOS 26.0, *) {
+            let c: SCScreenshotConfiguration = unretained(config)
+            return c.includeChildWindows
+        }
+        return false
+    }
+
+    @_cdecl("sc_screenshot_configuration_get_display_intent")
+    public func getScreenshotConfigurationDisplayIntent(_ config: OpaquePointer) -> Int32 {
+        if #available(macOS 26.0, *) {
+            let c: SCScreenshotConfiguration = unretained(config)
+            switch c.displayIntent {
+            case .local: return 1
+            default: return 0
+            }
+        }
+        return 0
+    }
+
+    @_cdecl("sc_screenshot_configuration_get_dynamic_range")
+    public func getScreenshotConfigurationDynamicRange(_ config: OpaquePointer) -> Int32 {
+        if #available(macOS 26.0, *) {
+            let c: SCScreenshotConfiguration = unretained(config)
+            switch c.dynamicRange {
+            case .hdr: return 1
+            case .bothSDRAndHDR: return 2
+            default: return 0
+            }
+        }
+        return 0
+    }
+
     @_cdecl("sc_screenshot_configuration_release")
     public func releaseScreenshotConfiguration(_ config: OpaquePointer) {
         release(config)
@@ -450,6 +529,30 @@ import UniformTypeIdentifiers
     @_cdecl("sc_screenshot_configuration_set_file_url")
     public func setScreenshotConfigurationFileURL(_: OpaquePointer, _: UnsafePointer<CChar>) {}
 
+    @_cdecl("sc_screenshot_configuration_get_width")
+    public func getScreenshotConfigurationWidth(_: OpaquePointer) -> Int { 0 }
+
+    @_cdecl("sc_screenshot_configuration_get_height")
+    public func getScreenshotConfigurationHeight(_: OpaquePointer) -> Int { 0 }
+
+    @_cdecl("sc_screenshot_configuration_get_shows_cursor")
+    public func getScreenshotConfigurationShowsCursor(_: OpaquePointer) -> Bool { false }
+
+    @_cdecl("sc_screenshot_configuration_get_ignore_shadows")
+    public func getScreenshotConfigurationIgnoreShadows(_: OpaquePointer) -> Bool { false }
+
+    @_cdecl("sc_screenshot_configuration_get_ignore_clipping")
+    public func getScreenshotConfigurationIgnoreClipping(_: OpaquePointer) -> Bool { false }
+
+    @_cdecl("sc_screenshot_configuration_get_include_child_windows")
+    public func getScreenshotConfigurationIncludeChildWindows(_: OpaquePointer) -> Bool { false }
+
+    @_cdecl("sc_screenshot_configuration_get_display_intent")
+    public func getScreenshotConfigurationDisplayIntent(_: OpaquePointer) -> Int32 { 0 }
+
+    @_cdecl("sc_screenshot_configuration_get_dynamic_range")
+    public func getScreenshotConfigurationDynamicRange(_: OpaquePointer) -> Int32 { 0 }
+
     @_cdecl("sc_screenshot_configuration_release")
     public func releaseScreenshotConfiguration(_: OpaquePointer) {}
 
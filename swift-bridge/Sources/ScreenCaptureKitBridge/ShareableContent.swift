@@ -444,6 +444,37 @@ public func getWindowOwningApplication(_ window: OpaquePointer) -> OpaquePointer
     return retain(app)
 }
 
+/// Look up a window's current frame and title in the window server by ID.
+///
+/// Reads `CGWindowListCopyWindowInfo` directly, which is much cheaper than
+/// fetching shareable content, so it can be polled to follow a window.
+/// Returns false once the window no longer exists. The title is only
+/// readable with screen recording permission and is nil otherwise.
+@_cdecl("sc_window_copy_live_info")
+public func copyWindowLiveInfo(
+    _ windowID: UInt32,
+    _ outX: UnsafeMutablePointer<Double>,
+    _ outY: UnsafeMutablePointer<Double>,
+    _ outW: UnsafeMutablePointer<Double>,
+    _ outH: UnsafeMutablePointer<Double>,
+    _ outTitle: UnsafeMutablePointer<UnsafeMutablePointer<CChar>?>
+) -> Bool {
+    outTitle.pointee = nil
+    guard let list = CGWindowListCopyWindowInfo(.optionIncludingWindow, CGWindowID(windowID)) as? [[String: Any]],
+          let info = list.first(where: { ($0[kCGWindowNumber as String] as? UInt32) == windowID }),
+          let bounds = info[kCGWindowBounds as String] as? NSDictionary,
+          let frame = CGRect(dictionaryRepresentation: bounds as CFDictionary)
+    else { return false }
+    outX.pointee = frame.origin.x
+    outY.pointee = frame.origin.y
+    outW.pointee = frame.size.width
+    outH.pointee = frame.size.height
+    if let title = info[kCGWindowName as String] as? String {
+        outTitle.pointee = strdup(title)
+    }
+    return true
+}
+
 // MARK: - SCRunningApplication
 
 @_cdecl("sc_running_application_retain")
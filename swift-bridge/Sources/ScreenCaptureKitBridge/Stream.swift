@@ -286,14 +286,15 @@ public func getContentFilterIncludeMenuBar(_ filter: OpaquePointer) -> Bool {
 
 private class StreamDelegateWrapper: NSObject, SCStreamDelegate {
     let contextPtr: UnsafeMutableRawPointer
-    let errorCallback: @convention(c) (UnsafeMutableRawPointer, Int32, UnsafePointer<CChar>) -> Void
+    let errorCallback: @convention(c) (UnsafeMutableRawPointer, OpaquePointer) -> Void
     let contextRelease: @convention(c) (UnsafeMutableRawPointer) -> Void
     var activeCallback: (@convention(c) (UnsafeMutableRawPointer) -> Void)?
     var inactiveCallback: (@convention(c) (UnsafeMutableRawPointer) -> Void)?
+    var videoEffectCallback: (@convention(c) (UnsafeMutableRawPointer, Bool) -> Void)?
 
     init(
         contextPtr: UnsafeMutableRawPointer,
-        errorCallback: @escaping @convention(c) (UnsafeMutableRawPointer, Int32, UnsafePointer<CChar>) -> Void,
+        errorCallback: @escaping @convention(c) (UnsafeMutableRawPointer, OpaquePointer) -> Void,
         contextRetain: @escaping @convention(c) (UnsafeMutableRawPointer) -> Void,
         contextRelease: @escaping @convention(c) (UnsafeMutableRawPointer) -> Void
     ) {
@@ -309,12 +310,23 @@ private class StreamDelegateWrapper: NSObject, SCStreamDelegate {
         contextRelease(contextPtr)
     }
 
-    func stream(_: SCStream, didStopWithError error: Error) {
-        let errorCode = extractStreamErrorCode(error)
-        let errorMsg = error.localizedDescription
-        errorMsg.withCString { errorCallback(contextPtr, errorCode, $0) }
+    func stream(_ stream: SCStream, didStopWithError error: Error) {
+        getStreamState(for: stream)?.markStopped()
+        withErrorPointer(error) { errorCallback(contextPtr, $0) }
     }
 
+    #if SCREENCAPTUREKIT_HAS_MACOS14_SDK
+        @available(macOS 14.0, *)
+        func outputVideoEffectDidStart(for _: SCStream) {
+            videoEffectCallback?(contextPtr, true)
+        }
+
+        @available(macOS 14.0, *)
+        func outputVideoEffectDidStop(for _: SCStream) {
+            videoEffectCallback?(contextPtr, false)
+        }
+    #endif
+
     #if SCREENCAPTUREKIT_HAS_MACOS15_SDK
         @available(macOS 15.2, *)
         func streamDidBecomeActive(_: SCStream) {
@@ -371,7 +383,16 @@ private class StreamOutputHandler: NSObject, SCStreamOutput {
 private class StreamState {
     let delegate: StreamDelegateWrapper
     let outputHandler: StreamOutputHandler
-    private var outputTypes: Set<Int32> = []
+    weak var stream: SCStream?
+    // Registered output types and the label of the queue delivering each.
+    private var outputQueues: [Int32: String] = [:]
+    // Queues chosen with `sc_stream_set_output_queue`, used instead of the
+    // bridge's default when an output type is registered without a queue.
+    private var preferredQueues: [Int32: DispatchQueue] = [:]
+    private var startedAt: Date?
+    // Rust `SCStream` handles sharing this stream; the state is dropped with
+    // the last one rather than with whichever handle is released first.
+    private var handles = 1
     private let lock = NSLock()
 
     init(delegate: StreamDelegateWrapper, outputHandler: StreamOutputHandler) {
@@ -382,19 +403,69 @@ private class StreamState {
     func hasOutput(_ type: Int32) -> Bool {
         lock.lock()
         defer { lock.unlock() }
-        return outputTypes.contains(type)
+        return outputQueues[type] != nil
+    }
+
+    func addOutput(_ type: Int32, queue: DispatchQueue) {
+        lock.lock()
+        defer { lock.unlock() }
+        outputQueues[type] = queue.label
     }
 
-    func addOutput(_ type: Int32) {
+    func preferredQueue(_ type: Int32) -> DispatchQueue? {
         lock.lock()
         defer { lock.unlock() }
-        outputTypes.insert(type)
+        return preferredQueues[type]
+    }
+
+    func setPreferredQueue(_ type: Int32, _ queue: DispatchQueue) {
+        lock.lock()
+        defer { lock.unlock() }
+        preferredQueues[type] = queue
     }
 
     func removeOutput(_ type: Int32) {
         lock.lock()
         defer { lock.unlock() }
-        outputTypes.remove(type)
+        outputQueues[type] = nil
+    }
+
+    func outputQueueLabel(_ type: Int32) -> String? {
+        lock.lock()
+        defer { lock.unlock() }
+        return outputQueues[type]
+    }
+
+    var captureStartedAt: Date? {
+        lock.lock()
+        defer { lock.unlock() }
+        return startedAt
+    }
+
+    func markStarted() {
+        lock.lock()
+        defer { lock.unlock() }
+        startedAt = Date()
+    }
+
+    func markStopped() {
+        lock.lock()
+        defer { lock.unlock() }
+        startedAt = nil
+    }
+
+    func retainHandle() {
+        lock.lock()
+        defer { lock.unlock() }
+        handles += 1
+    }
+
+    /// Returns `true` when the last handle was released.
+    func releaseHandle() -> Bool {
+        lock.lock()
+        defer { lock.unlock() }
+        handles -= 1
+        return handles <= 0
     }
 }
 
@@ -427,7 +498,7 @@ public func createStream(
     _ filter: OpaquePointer,
     _ config: OpaquePointer,
     _ context: UnsafeMutableRawPointer,
-    _ errorCallback: @escaping @convention(c) (UnsafeMutableRawPointer, Int32, UnsafePointer<CChar>) -> Void,
+    _ errorCallback: @escaping @convention(c) (UnsafeMutableRawPointer, OpaquePointer) -> Void,
     _ sampleCallback: @escaping @convention(c) (UnsafeMutableRawPointer, OpaquePointer, Int32) -> Void,
     _ contextRetain: @escaping @convention(c) (UnsafeMutableRawPointer) -> Void,
     _ contextRelease: @escaping @convention(c) (UnsafeMutableRawPointer) -> Void
@@ -450,12 +521,41 @@ public func createStream(
 
     let stream = SCStream(filter: scFilter, configuration: scConfig, delegate: delegate)
     let state = StreamState(delegate: delegate, outputHandler: outputHandler)
+    state.stream = stream
     setStreamState(state, for: stream)
 
     let actualStreamPtr = retain(stream)
     return actualStreamPtr
 }
 
+/// Route `outputVideoEffectDidStart/Stop` to `callback`, called with
+/// whether the effect is now active.
+@_cdecl("sc_stream_set_video_effect_callback")
+public func setStreamVideoEffectCallback(
+    _ stream: OpaquePointer,
+    _ callback: @escaping @convention(c) (UnsafeMutableRawPointer, Bool) -> Void
+) {
+    let scStream: SCStream = unretained(stream)
+    getStreamState(for: scStream)?.delegate.videoEffectCallback = callback
+}
+
+private func streamOutputType(_ type: Int32) -> SCStreamOutputType {
+    if type == 0 {
+        return .screen
+    } else if type == 2 {
+        #if SCREENCAPTUREKIT_HAS_MACOS15_SDK
+            if #available(macOS 15.0, *) {
+                return .microphone
+            }
+        #endif
+    }
+    return .audio
+}
+
+private func defaultOutputQueue(_ type: Int32) -> DispatchQueue {
+    DispatchQueue(label: "com.screencapturekit.output.\(type)", qos: .userInteractive)
+}
+
 @_cdecl("sc_stream_add_stream_output")
 public func addStreamOutput(
     _ stream: OpaquePointer,
@@ -469,22 +569,7 @@ public func addStreamOutput(
         return true
     }
 
-    let outputType: SCStreamOutputType
-    if type == 0 {
-        outputType = .screen
-    } else if type == 2 {
-        #if SCREENCAPTUREKIT_HAS_MACOS15_SDK
-            if #available(macOS 15.0, *) {
-                outputType = .microphone
-            } else {
-                outputType = .audio
-            }
-        #else
-            outputType = .audio
-        #endif
-    } else {
-        outputType = .audio
-    }
+    let outputType = streamOutputType(type)
 
     // INTENTIONAL DEVIATION FROM SCStream.addStreamOutput's `nil`→main-queue
     // contract: this bridge always uses a dedicated queue when the caller
@@ -497,11 +582,11 @@ public func addStreamOutput(
     // UIKit access) should pass their own DispatchQueue via
     // `sc_stream_add_stream_output_with_queue` or hop to the main queue
     // from inside their handler.
-    let queue = DispatchQueue(label: "com.screencapturekit.output.\(type)", qos: .userInteractive)
+    let queue = state.preferredQueue(type) ?? defaultOutputQueue(type)
 
     do {
         try scStream.addStreamOutput(state.outputHandler, type: outputType, sampleHandlerQueue: queue)
-        state.addOutput(type)
+        state.addOutput(type, queue: queue)
         return true
     } catch {
         return false
@@ -522,22 +607,7 @@ public func addStreamOutputWithQueue(
         return true
     }
 
-    let outputType: SCStreamOutputType
-    if type == 0 {
-        outputType = .screen
-    } else if type == 2 {
-        #if SCREENCAPTUREKIT_HAS_MACOS15_SDK
-            if #available(macOS 15.0, *) {
-                outputType = .microphone
-            } else {
-                outputType = .audio
-            }
-        #else
-            outputType = .audio
-        #endif
-    } else {
-        outputType = .audio
-    }
+    let outputType = streamOutputType(type)
 
     // See comment in `addStreamOutput` above: when no queue is supplied we
     // intentionally synthesise a dedicated queue rather than passing `nil`
@@ -546,12 +616,12 @@ public func addStreamOutputWithQueue(
     let queue: DispatchQueue = if let queuePtr = dispatchQueue {
         unretained(queuePtr)
     } else {
-        DispatchQueue(label: "com.screencapturekit.output.\(type)", qos: .userInteractive)
+        state.preferredQueue(type) ?? defaultOutputQueue(type)
     }
 
     do {
         try scStream.addStreamOutput(state.outputHandler, type: outputType, sampleHandlerQueue: queue)
-        state.addOutput(type)
+        state.addOutput(type, queue: queue)
         return true
     } catch {
         return false
@@ -567,32 +637,63 @@ public func removeStreamOutput(
     guard let state = getStreamState(for: scStream) else { return false }
     guard state.hasOutput(type) else { return false }
 
-    let outputType: SCStreamOutputType
-    if type == 0 {
-        outputType = .screen
-    } else if type == 2 {
-        #if SCREENCAPTUREKIT_HAS_MACOS15_SDK
-            if #available(macOS 15.0, *) {
-                outputType = .microphone
-            } else {
-                outputType = .audio
-            }
-        #else
-            outputType = .audio
-        #endif
-    } else {
-        outputType = .audio
+    let outputType = streamOutputType(type)
+
+    do {
+        try scStream.removeStreamOutput(state.outputHandler, type: outputType)
+        state.removeOutput(type)
+        return true
+    } catch {
+        return false
     }
+}
 
+/// Deliver output `type` on `dispatchQueue` from now on. A registered output
+/// is removed and re-added on the new queue, which can drop a sample or two
+/// while capturing; otherwise the queue is kept for the next registration.
+@_cdecl("sc_stream_set_output_queue")
+public func setStreamOutputQueue(
+    _ stream: OpaquePointer,
+    _ type: Int32,
+    _ dispatchQueue: OpaquePointer
+) -> Bool {
+    let scStream: SCStream = unretained(stream)
+    guard let state = getStreamState(for: scStream) else { return false }
+    let queue: DispatchQueue = unretained(dispatchQueue)
+    state.setPreferredQueue(type, queue)
+    guard state.hasOutput(type) else { return true }
+
+    let outputType = streamOutputType(type)
     do {
         try scStream.removeStreamOutput(state.outputHandler, type: outputType)
         state.removeOutput(type)
+        try scStream.addStreamOutput(state.outputHandler, type: outputType, sampleHandlerQueue: queue)
+        state.addOutput(type, queue: queue)
         return true
     } catch {
         return false
     }
 }
 
+/// Copy the label of the queue delivering output `type` into `buffer`.
+/// Returns false if the type has no registered output.
+@_cdecl("sc_stream_get_output_queue_label")
+public func getStreamOutputQueueLabel(
+    _ stream: OpaquePointer,
+    _ type: Int32,
+    _ buffer: UnsafeMutablePointer<CChar>?,
+    _ bufferSize: Int
+) -> Bool {
+    let scStream: SCStream = unretained(stream)
+    guard let buffer, bufferSize > 0,
+          let label = getStreamState(for: scStream)?.outputQueueLabel(type)
+    else { return false }
+    return label.withCString { src in
+        strlcpy(buffer, src, bufferSize)
+        return true
+    }
+}
+
 // MARK: - Stream Lifecycle
 
 /// Starts capturing from the stream
@@ -604,16 +705,16 @@ public func removeStreamOutput(
 public func startStreamCapture(
     _ stream: OpaquePointer,
     _ context: UnsafeMutableRawPointer?,
-    _ callback: @escaping @convention(c) (UnsafeMutableRawPointer?, Bool, UnsafePointer<CChar>?) -> Void
+    _ callback: @escaping @convention(c) (UnsafeMutableRawPointer?, Bool, OpaquePointer?) -> Void
 ) {
     let scStream: SCStream = unretained(stream)
     Task {
         do {
             try await scStream.startCapture()
+            getStreamState(for: scStream)?.markStarted()
             callback(context, true, nil)
         } catch {
-            let bridgeError = SCBridgeError.streamError(error.localizedDescription)
-            bridgeError.description.withCString { callback(context, false, $0) }
+            withErrorPointer(error) { callback(context, false, $0) }
         }
     }
 }
@@ -627,16 +728,16 @@ public func startStreamCapture(
 public func stopStreamCapture(
     _ stream: OpaquePointer,
     _ context: UnsafeMutableRawPointer?,
-    _ callback: @escaping @convention(c) (UnsafeMutableRawPointer?, Bool, UnsafePointer<CChar>?) -> Void
+    _ callback: @escaping @convention(c) (UnsafeMutableRawPointer?, Bool, OpaquePointer?) -> Void
 ) {
     let scStream: SCStream = unretained(stream)
     Task {
         do {
             try await scStream.stopCapture()
+            getStreamState(for: scStream)?.markStopped()
             callback(context, true, nil)
         } catch {
-            let bridgeError = SCBridgeError.streamError(error.localizedDescription)
-            bridgeError.description.withCString { callback(context, false, $0) }
+            withErrorPointer(error) { callback(context, false, $0) }
         }
     }
 }
@@ -652,7 +753,7 @@ public func updateStreamContentFilter(
     _ stream: OpaquePointer,
     _ filter: OpaquePointer,
     _ context: UnsafeMutableRawPointer?,
-    _ callback: @escaping @convention(c) (UnsafeMutableRawPointer?, Bool, UnsafePointer<CChar>?) -> Void
+    _ callback: @escaping @convention(c) (UnsafeMutableRawPointer?, Bool, OpaquePointer?) -> Void
 ) {
     let scStream: SCStream = unretained(stream)
     let scFilter: SCContentFilter = unretained(filter)
@@ -661,8 +762,7 @@ public func updateStreamContentFilter(
             try await scStream.updateContentFilter(scFilter)
             callback(context, true, nil)
         } catch {
-            let bridgeError = SCBridgeError.streamError(error.localizedDescription)
-            bridgeError.description.withCString { callback(context, false, $0) }
+            withErrorPointer(error) { callback(context, false, $0) }
         }
     }
 }
@@ -678,7 +778,7 @@ public func updateStreamConfiguration(
     _ stream: OpaquePointer,
     _ config: OpaquePointer,
     _ context: UnsafeMutableRawPointer?,
-    _ callback: @escaping @convention(c) (UnsafeMutableRawPointer?, Bool, UnsafePointer<CChar>?) -> Void
+    _ callback: @escaping @convention(c) (UnsafeMutableRawPointer?, Bool, OpaquePointer?) -> Void
 ) {
     if #available(macOS 14.0, *) {
         let scStream: SCStream = unretained(stream)
@@ -688,18 +788,52 @@ public func updateStreamConfiguration(
                 try await scStream.updateConfiguration(scConfig)
                 callback(context, true, nil)
             } catch {
-                let bridgeError = SCBridgeError.configurationError(error.localizedDescription)
-                bridgeError.description.withCString { callback(context, false, $0) }
+                withErrorPointer(error) { callback(context, false, $0) }
             }
         }
     } else {
         let bridgeError = SCBridgeError.configurationError("updateConfiguration requires macOS 14.0 or later")
-        bridgeError.description.withCString { callback(context, false, $0) }
+        withErrorPointer(bridgeNSError(bridgeError)) { callback(context, false, $0) }
     }
 }
 
 // MARK: - Stream Properties
 
+/// Seconds since 1970 at which capture started, or a negative value if the
+/// stream is not capturing.
+@_cdecl("sc_stream_get_capture_start_time")
+public func getStreamCaptureStartTime(_ stream: OpaquePointer) -> Double {
+    let s: SCStream = unretained(stream)
+    return getStreamState(for: s)?.captureStartedAt?.timeIntervalSince1970 ?? -1
+}
+
+/// Calls `callback` with a +1 stream handle and its Rust context for every
+/// stream of this process that is capturing. The callback runs under the
+/// state lock, while each stream's delegate still holds its context
+/// reference.
+@_cdecl("sc_stream_copy_active_streams")
+public func copyActiveStreams(
+    _ callback: @convention(c) (OpaquePointer, UnsafeMutableRawPointer, UnsafeMutableRawPointer?) -> Void,
+    _ userData: UnsafeMutableRawPointer?
+) {
+    streamStatesLock.lock()
+    defer { streamStatesLock.unlock() }
+    for state in streamStates.values {
+        guard state.captureStartedAt != nil, let stream = state.stream else { continue }
+        state.retainHandle()
+        callback(retain(stream), state.delegate.contextPtr, userData)
+    }
+}
+
+/// Number of streams of this process that are capturing.
+@_cdecl("sc_stream_get_active_count")
+public func getActiveStreamCount() -> Int {
+    streamStatesLock.lock()
+    let states = Array(streamStates.values)
+    streamStatesLock.unlock()
+    return states.filter { $0.captureStartedAt != nil }.count
+}
+
 /// Get the synchronization clock for the stream (macOS 13.0+)
 @_cdecl("sc_stream_get_synchronization_clock")
 public func getStreamSynchronizationClock(_ stream: OpaquePointer) -> OpaquePointer? {
@@ -718,13 +852,16 @@ public func getStreamSynchronizationClock(_ stream: OpaquePointer) -> OpaquePoin
 @_cdecl("sc_stream_retain")
 public func retainStream(_ stream: OpaquePointer) -> OpaquePointer {
     let s: SCStream = unretained(stream)
+    getStreamState(for: s)?.retainHandle()
     return retain(s)
 }
 
 @_cdecl("sc_stream_release")
 public func releaseStream(_ stream: OpaquePointer) {
     let s: SCStream = unretained(stream)
-    removeStreamState(for: s)
+    if getStreamState(for: s)?.releaseHandle() == true {
+        removeStreamState(for: s)
+    }
     release(stream)
 }
 
@@ -757,7 +894,7 @@ public func releaseStream(_ stream: OpaquePointer) {
     public func addRecordingOutput(
         _ stream: OpaquePointer,
         _ recordingOutput: OpaquePointer,
-        _ callback: @escaping @convention(c) (UnsafeMutableRawPointer?, Bool, UnsafePointer<CChar>?) -> Void,
+        _ callback: @escaping @convention(c) (UnsafeMutableRawPointer?, Bool, OpaquePointer?) -> Void,
         _ context: UnsafeMutableRawPointer?
     ) {
         if #available(macOS 15.0, *) {
@@ -765,11 +902,11 @@ public func releaseStream(_ stream: OpaquePointer) {
                 try addRecordingOutputImpl(stream, recordingOutput)
                 callback(context, true, nil)
             } catch {
-                error.localizedDescription.withCString { callback(context, false, $0) }
+                withErrorPointer(error) { callback(context, false, $0) }
             }
         } else {
             let bridgeError = SCBridgeError.configurationError("addRecordingOutput requires macOS 15.0 or later")
-            bridgeError.description.withCString { callback(context, false, $0) }
+            withErrorPointer(bridgeNSError(bridgeError)) { callback(context, false, $0) }
         }
     }
 
@@ -777,7 +914,7 @@ public func releaseStream(_ stream: OpaquePointer) {
     public func removeRecordingOutput(
         _ stream: OpaquePointer,
         _ recordingOutput: OpaquePointer,
-        _ callback: @escaping @convention(c) (UnsafeMutableRawPointer?, Bool, UnsafePointer<CChar>?) -> Void,
+        _ callback: @escaping @convention(c) (UnsafeMutableRawPointer?, Bool, OpaquePointer?) -> Void,
         _ context: UnsafeMutableRawPointer?
     ) {
         if #available(macOS 15.0, *) {
@@ -785,11 +922,11 @@ public func releaseStream(_ stream: OpaquePointer) {
                 try removeRecordingOutputImpl(stream, recordingOutput)
                 callback(context, true, nil)
             } catch {
-                error.localizedDescription.withCString { callback(context, false, $0) }
+                withErrorPointer(error) { callback(context, false, $0) }
             }
         } else {
             let bridgeError = SCBridgeError.configurationError("removeRecordingOutput requires macOS 15.0 or later")
-            bridgeError.description.withCString { callback(context, false, $0) }
+            withErrorPointer(bridgeNSError(bridgeError)) { callback(context, false, $0) }
         }
     }
 
@@ -800,22 +937,22 @@ public func releaseStream(_ stream: OpaquePointer) {
     public func addRecordingOutput(
         _: OpaquePointer,
         _: OpaquePointer,
-        _ callback: @escaping @convention(c) (UnsafeMutableRawPointer?, Bool, UnsafePointer<CChar>?) -> Void,
+        _ callback: @escaping @convention(c) (UnsafeMutableRawPointer?, Bool, OpaquePointer?) -> Void,
         _ context: UnsafeMutableRawPointer?
     ) {
         let bridgeError = SCBridgeError.configurationError("addRecordingOutput requires macOS 15.0 SDK or later")
-        bridgeError.description.withCString { callback(context, false, $0) }
+        withErrorPointer(bridgeNSError(bridgeError)) { callback(context, false, $0) }
     }
 
     @_cdecl("sc_stream_remove_recording_output")
     public func removeRecordingOutput(
         _: OpaquePointer,
         _: OpaquePointer,
-        _ callback: @escaping @convention(c) (UnsafeMutableRawPointer?, Bool, UnsafePointer<CChar>?) -> Void,
+        _ callback: @escaping @convention(c) (UnsafeMutableRawPointer?, Bool, OpaquePointer?) -> Void,
         _ context: UnsafeMutableRawPointer?
     ) {
         let bridgeError = SCBridgeError.configurationError("removeRecordingOutput requires macOS 15.0 SDK or later")
-        bridgeError.description.withCString { callback(context, false, $0) }
+        withErrorPointer(bridgeNSError(bridgeError)) { callback(context, false, $0) }
     }
 
 #endif
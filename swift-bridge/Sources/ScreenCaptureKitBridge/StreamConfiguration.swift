@@ -806,3 +806,64 @@ public func getStreamConfigurationIgnoreGlobalClipSingleWindow(_ config: OpaqueP
         retain(SCStreamConfiguration())
     }
 #endif
+
+// MARK: - Copy
+
+/// Create an independent copy of a configuration.
+///
+/// `SCStreamConfiguration` does not adopt `NSCopying`, so every property the
+/// bridge exposes is copied explicitly. The Rust side relies on this to keep
+/// a private snapshot of the configuration last applied to a stream; a plain
+/// retain would alias the caller's object and let later mutations leak in.
+@_cdecl("sc_stream_configuration_copy")
+public func copyStreamConfiguration(_ config: OpaquePointer) -> OpaquePointer {
+    let src: SCStreamConfiguration = unretained(config)
+    let dst = SCStreamConfiguration()
+
+    dst.width = src.width
+    dst.height = src.height
+    dst.showsCursor = src.showsCursor
+    dst.scalesToFit = src.scalesToFit
+    dst.capturesAudio = src.capturesAudio
+    dst.sampleRate = src.sampleRate
+    dst.channelCount = src.channelCount
+    dst.minimumFrameInterval = src.minimumFrameInterval
+    dst.queueDepth = src.queueDepth
+    dst.pixelFormat = src.pixelFormat
+    dst.sourceRect = src.sourceRect
+    dst.destinationRect = src.destinationRect
+    dst.excludesCurrentProcessAudio = src.excludesCurrentProcessAudio
+    dst.backgroundColor = src.backgroundColor
+    dst.colorSpaceName = src.colorSpaceName
+    dst.colorMatrix = src.colorMatrix
+
+    if #available(macOS 14.0, *) {
+        dst.preservesAspectRatio = src.preservesAspectRatio
+        dst.captureResolution = src.captureResolution
+        dst.shouldBeOpaque = src.shouldBeOpaque
+        dst.ignoreShadowsDisplay = src.ignoreShadowsDisplay
+        dst.ignoreShadowsSingleWindow = src.ignoreShadowsSingleWindow
+        dst.ignoreGlobalClipDisplay = src.ignoreGlobalClipDisplay
+        dst.ignoreGlobalClipSingleWindow = src.ignoreGlobalClipSingleWindow
+        dst.capturesShadowsOnly = src.capturesShadowsOnly
+        dst.presenterOverlayPrivacyAlertSetting = src.presenterOverlayPrivacyAlertSetting
+        dst.streamName = src.streamName
+    }
+    if #available(macOS 14.2, *) {
+        dst.includeChildWindows = src.includeChildWindows
+    }
+    #if SCREENCAPTUREKIT_HAS_MACOS15_SDK
+        if #available(macOS 15.0, *) {
+            dst.captureMicrophone = src.captureMicrophone
+            dst.microphoneCaptureDeviceID = src.microphoneCaptureDeviceID
+            dst.captureDynamicRange = src.captureDynamicRange
+            dst.showMouseClicks = src.showMouseClicks
+        }
+    #endif
+
+    retainStreamConfigurationState(for: dst)
+    if let values = streamConfigurationState(for: src) {
+        updateStreamConfigurationState(for: dst) { $0 = values }
+    }
+    return retain(dst)
+}
@@ -0,0 +1,174 @@
+// Syphon server publishing
+//
+// Syphon.framework does not ship with macOS, so it is never linked. The
+// framework is loaded at runtime and `SyphonMetalServer` is driven through
+// the Objective-C runtime; without it, server creation simply fails.
+
+import CoreVideo
+import Foundation
+import IOSurface
+import Metal
+import ObjectiveC
+
+private let syphonServerClassName = "SyphonMetalServer"
+private let syphonLoadLock = NSLock()
+
+private func defaultSyphonFrameworkPaths() -> [String] {
+    var paths: [String] = []
+    if let bundled = Bundle.main.privateFrameworksPath {
+        paths.append("\(bundled)/Syphon.framework")
+    }
+    paths.append("/Library/Frameworks/Syphon.framework")
+    paths.append(NSHomeDirectory() + "/Library/Frameworks/Syphon.framework")
+    return paths
+}
+
+/// Look up the implementation of `name` on `target` (an instance, or a class
+/// object for class methods) and cast it to the C function type `T`.
+private func implementation<T>(_ target: AnyObject, _ name: String, as _: T.Type) -> (Selector, T)? {
+    let selector = NSSelectorFromString(name)
+    guard target.responds(to: selector),
+          let cls = object_getClass(target),
+          let imp = class_getMethodImplementation(cls, selector)
+    else { return nil }
+    return (selector, unsafeBitCast(imp, to: T.self))
+}
+
+private typealias AllocFn = @convention(c) (AnyObject, Selector) -> UnsafeMutableRawPointer?
+private typealias InitFn = @convention(c) (
+    UnsafeMutableRawPointer, Selector, NSString, MTLDevice, NSDictionary?
+) -> UnsafeMutableRawPointer?
+private typealias PublishFn = @convention(c) (
+    AnyObject, Selector, MTLTexture, MTLCommandBuffer, CGRect, ObjCBool
+) -> Void
+
+private final class SyphonServerBox {
+    let server: NSObject
+    let queue: MTLCommandQueue
+    let publish: (Selector, PublishFn)
+
+    init(server: NSObject, queue: MTLCommandQueue, publish: (Selector, PublishFn)) {
+        self.server = server
+        self.queue = queue
+        self.publish = publish
+    }
+
+    func stop() {
+        let selector = NSSelectorFromString("stop")
+        if server.responds(to: selector) {
+            server.perform(selector)
+        }
+    }
+
+    deinit {
+        stop()
+    }
+}
+
+/// Load Syphon.framework from `path`, or from the app bundle and the
+/// standard framework folders when `path` is NULL. Succeeds without loading
+/// anything if the app already links Syphon.
+@_cdecl("sc_syphon_load_framework")
+public func syphonLoadFramework(_ path: UnsafePointer<CChar>?) -> Bool {
+    syphonLoadLock.lock()
+    defer { syphonLoadLock.unlock() }
+    if NSClassFromString(syphonServerClassName) != nil {
+        return true
+    }
+    let candidates = path.map { [String(cString: $0)] } ?? defaultSyphonFrameworkPaths()
+    for candidate in candidates {
+        if let bundle = Bundle(path: candidate), bundle.load(),
+           NSClassFromString(syphonServerClassName) != nil {
+            return true
+        }
+    }
+    return false
+}
+
+/// Create a `SyphonMetalServer`. `device` may be NULL for the system default
+/// device. Returns NULL if Syphon is not loaded or the server can't be made.
+@_cdecl("sc_syphon_server_create")
+public func syphonServerCreate(
+    _ name: UnsafePointer<CChar>,
+    _ device: UnsafeMutableRawPointer?
+) -> OpaquePointer? {
+    guard let cls = NSClassFromString(syphonServerClassName) else { return nil }
+    let mtlDevice: MTLDevice
+    if let device {
+        mtlDevice = Unmanaged<MTLDevice>.fromOpaque(device).takeUnretainedValue()
+    } else if let systemDevice = MTLCreateSystemDefaultDevice() {
+        mtlDevice = systemDevice
+    } else {
+        return nil
+    }
+    guard let queue = mtlDevice.makeCommandQueue(),
+          let alloc = implementation(cls as AnyObject, "alloc", as: AllocFn.self),
+          let allocated = alloc.1(cls as AnyObject, alloc.0)
+    else { return nil }
+
+    let initSel = NSSelectorFromString("initWithName:device:options:")
+    guard let initImp = class_getMethodImplementation(cls, initSel) else { return nil }
+    let initialize = unsafeBitCast(initImp, to: InitFn.self)
+    guard let raw = initialize(allocated, initSel, String(cString: name) as NSString, mtlDevice, nil) else {
+        return nil
+    }
+    let server = Unmanaged<NSObject>.fromOpaque(raw).takeRetainedValue()
+    guard let publish = implementation(
+        server, "publishFrameTexture:onCommandBuffer:imageRegion:flipped:", as: PublishFn.self
+    ) else { return nil }
+
+    let box = SyphonServerBox(server: server, queue: queue, publish: publish)
+    return OpaquePointer(Unmanaged.passRetained(box).toOpaque())
+}
+
+@_cdecl("sc_syphon_server_release")
+public func syphonServerRelease(_ server: OpaquePointer) {
+    Unmanaged<SyphonServerBox>.fromOpaque(UnsafeRawPointer(server)).release()
+}
+
+/// Publish a BGRA `IOSurface`. Returns false for other pixel formats or if
+/// Metal can't wrap the surface.
+@_cdecl("sc_syphon_server_publish_iosurface")
+public func syphonServerPublishIOSurface(_ server: OpaquePointer, _ surfacePtr: UnsafeMutableRawPointer) -> Bool {
+    let box = Unmanaged<SyphonServerBox>.fromOpaque(UnsafeRawPointer(server)).takeUnretainedValue()
+    let surface = Unmanaged<IOSurface>.fromOpaque(surfacePtr).takeUnretainedValue()
+    guard IOSurfaceGetPixelFormat(surface) == kCVPixelFormatType_32BGRA else { return false }
+
+    let width = IOSurfaceGetWidth(surface)
+    let height = IOSurfaceGetHeight(surface)
+    let descriptor = MTLTextureDescriptor.texture2DDescriptor(
+        pixelFormat: .bgra8Unorm,
+        width: width,
+        height: height,
+        mipmapped: false
+    )
+    descriptor.storageMode = .shared
+    descriptor.usage = .shaderRead
+    guard let texture = box.queue.device.makeTexture(descriptor: descriptor, iosurface: surface, plane: 0),
+          let commandBuffer = box.queue.makeCommandBuffer()
+    else { return false }
+
+    // Keep the capture pool from recycling the surface until Syphon's blit
+    // has run.
+    IOSurfaceIncrementUseCount(surface)
+    commandBuffer.addCompletedHandler { _ in
+        IOSurfaceDecrementUseCount(surface)
+    }
+    let (selector, publish) = box.publish
+    let region = CGRect(x: 0, y: 0, width: width, height: height)
+    publish(box.server, selector, texture, commandBuffer, region, ObjCBool(false))
+    commandBuffer.commit()
+    return true
+}
+
+@_cdecl("sc_syphon_server_has_clients")
+public func syphonServerHasClients(_ server: OpaquePointer) -> Bool {
+    let box = Unmanaged<SyphonServerBox>.fromOpaque(UnsafeRawPointer(server)).takeUnretainedValue()
+    return (box.server.value(forKey: "hasClients") as? Bool) ?? false
+}
+
+@_cdecl("sc_syphon_server_stop")
+public func syphonServerStop(_ server: OpaquePointer) {
+    let box = Unmanaged<SyphonServerBox>.fromOpaque(UnsafeRawPointer(server)).takeUnretainedValue()
+    box.stop()
+}
@@ -0,0 +1,34 @@
+// Versions of the OS and of running applications, for recording metadata.
+
+import AppKit
+import Foundation
+
+/// Human-readable macOS version, e.g. "Version 15.1 (Build 24B83)".
+/// Free with `sc_free_string`.
+@_cdecl("sc_system_os_version")
+public func getSystemOSVersion() -> UnsafeMutablePointer<CChar>? {
+    strdup(ProcessInfo.processInfo.operatingSystemVersionString)
+}
+
+/// `CFBundleShortVersionString` of the application running as `pid`, or
+/// NULL if it has no bundle or no version. Free with `sc_free_string`.
+@_cdecl("sc_application_version")
+public func getApplicationVersion(_ pid: Int32) -> UnsafeMutablePointer<CChar>? {
+    guard let app = NSRunningApplication(processIdentifier: pid),
+          let url = app.bundleURL,
+          let version = Bundle(url: url)?.infoDictionary?["CFBundleShortVersionString"] as? String
+    else {
+        return nil
+    }
+    return strdup(version)
+}
+
+/// `CFBundleShortVersionString` of the current process's main bundle, or
+/// NULL for bare executables. Free with `sc_free_string`.
+@_cdecl("sc_main_bundle_version")
+public func getMainBundleVersion() -> UnsafeMutablePointer<CChar>? {
+    guard let version = Bundle.main.infoDictionary?["CFBundleShortVersionString"] as? String else {
+        return nil
+    }
+    return strdup(version)
+}
@@ -0,0 +1,126 @@
+// On-screen text recognition with the Vision framework.
+//
+// VNRecognizeTextRequest runs synchronously on a captured pixel buffer or a
+// screenshot; the observations are kept in a results object that Rust reads
+// through index accessors and then releases. Bounding boxes are converted
+// from Vision's bottom-left origin to the top-left origin used elsewhere in
+// the crate, still normalized to 0...1.
+
+import CoreGraphics
+import CoreVideo
+import Foundation
+import Vision
+
+// MARK: - Text Recognition
+
+private final class TextRecognitionResults {
+    struct Line {
+        let text: String
+        let confidence: Float
+        let box: CGRect
+    }
+
+    let lines: [Line]
+
+    init(_ lines: [Line]) {
+        self.lines = lines
+    }
+}
+
+private func results(_ pointer: OpaquePointer) -> TextRecognitionResults {
+    Unmanaged<TextRecognitionResults>.fromOpaque(UnsafeRawPointer(pointer)).takeUnretainedValue()
+}
+
+/// Recognize text in `pixelBuffer`, or in `image` when no buffer is given.
+/// `languages` is a comma-separated list of BCP 47 codes, or NULL for the
+/// default. Returns a +1 results object, or nil with `outError` set to a
+/// message freed with `sc_free_string`.
+@_cdecl("sc_vision_recognize_text")
+public func recognizeText(
+    _ pixelBuffer: OpaquePointer?,
+    _ image: OpaquePointer?,
+    _ accurate: Bool,
+    _ languageCorrection: Bool,
+    _ languages: UnsafePointer<CChar>?,
+    _ minimumTextHeight: Float,
+    _ outError: UnsafeMutablePointer<UnsafeMutablePointer<CChar>?>
+) -> OpaquePointer? {
+    outError.pointee = nil
+    let handler: VNImageRequestHandler
+    if let pixelBuffer {
+        let buffer = Unmanaged<CVPixelBuffer>.fromOpaque(UnsafeRawPointer(pixelBuffer)).takeUnretainedValue()
+        handler = VNImageRequestHandler(cvPixelBuffer: buffer, options: [:])
+    } else if let image {
+        let cgImage = Unmanaged<CGImage>.fromOpaque(UnsafeRawPointer(image)).takeUnretainedValue()
+        handler = VNImageRequestHandler(cgImage: cgImage, options: [:])
+    } else {
+        outError.pointee = strdup("no image to recognize text in")
+        return nil
+    }
+
+    let request = VNRecognizeTextRequest()
+    request.recognitionLevel = accurate ? .accurate : .fast
+    request.usesLanguageCorrection = languageCorrection
+    request.minimumTextHeight = minimumTextHeight
+    if let languages {
+        let codes = String(cString: languages).split(separator: ",").map(String.init)
+        if !codes.isEmpty {
+            request.recognitionLanguages = codes
+        }
+    }
+
+    do {
+        try handler.perform([request])
+    } catch {
+        outError.pointee = strdup(error.localizedDescription)
+        return nil
+    }
+
+    let lines = (request.results ?? []).compactMap { observation -> TextRecognitionResults.Line? in
+        guard let candidate = observation.topCandidates(1).first else { return nil }
+        let box = observation.boundingBox
+        return TextRecognitionResults.Line(
+            text: candidate.string,
+            confidence: candidate.confidence,
+            box: CGRect(x: box.minX, y: 1 - box.maxY, width: box.width, height: box.height)
+        )
+    }
+    return OpaquePointer(Unmanaged.passRetained(TextRecognitionResults(lines)).toOpaque())
+}
+
+@_cdecl("sc_vision_text_results_count")
+public func textResultsCount(_ pointer: OpaquePointer) -> Int {
+    results(pointer).lines.count
+}
+
+/// Text of line `index` (caller must free with sc_free_string).
+@_cdecl("sc_vision_text_results_string")
+public func textResultsString(_ pointer: OpaquePointer, _ index: Int) -> UnsafeMutablePointer<CChar>? {
+    let lines = results(pointer).lines
+    guard index >= 0, index < lines.count else { return nil }
+    return strdup(lines[index].text)
+}
+
+@_cdecl("sc_vision_text_results_confidence")
+public func textResultsConfidence(_ pointer: OpaquePointer, _ index: Int) -> Float {
+    let lines = results(pointer).lines
+    guard index >= 0, index < lines.count else { return 0 }
+    return lines[index].confidence
+}
+
+/// Normalized, top-left-origin box of line `index` as x, y, width, height.
+@_cdecl("sc_vision_text_results_box")
+public func textResultsBox(_ pointer: OpaquePointer, _ index: Int, _ outRect: UnsafeMutablePointer<Double>) {
+    let lines = results(pointer).lines
+    guard index >= 0, index < lines.count else { return }
+    let box = lines[index].box
+    outRect[0] = Double(box.minX)
+    outRect[1] = Double(box.minY)
+    outRect[2] = Double(box.width)
+    outRect[3] = Double(box.height)
+}
+
+@_cdecl("sc_vision_text_results_release")
+public func releaseTextResults(_ pointer: OpaquePointer) {
+    Unmanaged<TextRecognitionResults>.fromOpaque(UnsafeRawPointer(pointer)).release()
+}
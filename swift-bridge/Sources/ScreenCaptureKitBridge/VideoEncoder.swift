@@ -0,0 +1,219 @@
+// H.264 encoding via VideoToolbox
+//
+// A real-time `VTCompressionSession` without frame reordering (so DTS == PTS)
+// that queues AVCC (length-prefixed) access units for the Rust side to pull.
+// The `avcC` decoder configuration record is captured from the first output
+// format description.
+
+import CoreMedia
+import Foundation
+import VideoToolbox
+
+private struct EncodedVideoFrame {
+    let data: Data
+    let ptsValue: Int64
+    let ptsScale: Int32
+    let keyframe: Bool
+}
+
+private final class H264EncoderBox {
+    var session: VTCompressionSession?
+    let lock = NSLock()
+    var frames: [EncodedVideoFrame] = []
+    var avcC: Data?
+    var lastStatus: OSStatus = noErr
+
+    func push(_ frame: EncodedVideoFrame, avcC: Data?) {
+        lock.lock()
+        frames.append(frame)
+        if self.avcC == nil, let avcC {
+            self.avcC = avcC
+        }
+        lock.unlock()
+    }
+
+    deinit {
+        if let session {
+            VTCompressionSessionCompleteFrames(session, untilPresentationTimeStamp: .invalid)
+            VTCompressionSessionInvalidate(session)
+        }
+    }
+}
+
+private func extractAvcC(_ format: CMFormatDescription) -> Data? {
+    guard let atoms = CMFormatDescriptionGetExtension(
+        format,
+        extensionKey: kCMFormatDescriptionExtension_SampleDescriptionExtensionAtoms
+    ) as? [String: Any] else { return nil }
+    return atoms["avcC"] as? Data
+}
+
+// MARK: - H.264 Encoder Bridge
+
+@_cdecl("sc_h264_encoder_create")
+public func createH264Encoder(
+    _ width: Int32,
+    _ height: Int32,
+    _ fps: Double,
+    _ bitrate: Int32,
+    _ keyframeInterval: Int32,
+    _ outStatus: UnsafeMutablePointer<Int32>
+) -> OpaquePointer? {
+    var session: VTCompressionSession?
+    let status = VTCompressionSessionCreate(
+        allocator: nil,
+        width: width,
+        height: height,
+        codecType: kCMVideoCodecType_H264,
+        encoderSpecification: nil,
+        imageBufferAttributes: nil,
+        compressedDataAllocator: nil,
+        outputCallback: nil,
+        refcon: nil,
+        compressionSessionOut: &session
+    )
+    guard status == noErr, let session else {
+        outStatus.pointee = status
+        return nil
+    }
+
+    VTSessionSetProperty(session, key: kVTCompressionPropertyKey_RealTime, value: kCFBooleanTrue)
+    VTSessionSetProperty(session, key: kVTCompressionPropertyKey_AllowFrameReordering, value: kCFBooleanFalse)
+    VTSessionSetProperty(session, key: kVTCompressionPropertyKey_ProfileLevel, value: kVTProfileLevel_H264_Main_AutoLevel)
+    VTSessionSetProperty(session, key: kVTCompressionPropertyKey_AverageBitRate, value: NSNumber(value: bitrate))
+    VTSessionSetProperty(session, key: kVTCompressionPropertyKey_ExpectedFrameRate, value: NSNumber(value: fps))
+    VTSessionSetProperty(session, key: kVTCompressionPropertyKey_MaxKeyFrameInterval, value: NSNumber(value: keyframeInterval))
+    VTCompressionSessionPrepareToEncodeFrames(session)
+
+    let box = H264EncoderBox()
+    box.session = session
+    outStatus.pointee = noErr
+    return OpaquePointer(Unmanaged.passRetained(box).toOpaque())
+}
+
+@_cdecl("sc_h264_encoder_release")
+public func releaseH264Encoder(_ encoder: OpaquePointer) {
+    Unmanaged<H264EncoderBox>.fromOpaque(UnsafeRawPointer(encoder)).release()
+}
+
+@_cdecl("sc_h264_encoder_encode")
+public func h264EncoderEncode(
+    _ encoder: OpaquePointer,
+    _ pixelBuffer: UnsafeMutableRawPointer,
+    _ ptsValue: Int64,
+    _ ptsScale: Int32,
+    _ forceKeyframe: Bool
+) -> Int32 {
+    let box = Unmanaged<H264EncoderBox>.fromOpaque(UnsafeRawPointer(encoder)).takeUnretainedValue()
+    guard let session = box.session else { return -1 }
+    let imageBuffer = Unmanaged<CVPixelBuffer>.fromOpaque(pixelBuffer).takeUnretainedValue()
+    let pts = CMTime(value: ptsValue, timescale: ptsScale)
+    let properties: CFDictionary? = forceKeyframe
+        ? [kVTEncodeFrameOptionKey_ForceKeyFrame: kCFBooleanTrue] as CFDictionary
+        : nil
+
+    return VTCompressionSessionEncodeFrame(
+        session,
+        imageBuffer: imageBuffer,
+        presentationTimeStamp: pts,
+        duration: .invalid,
+        frameProperties: properties,
+        infoFlagsOut: nil
+    ) { [weak box] status, _, sampleBuffer in
+        guard let box else { return }
+        guard status == noErr, let sampleBuffer, let dataBuffer = CMSampleBufferGetDataBuffer(sampleBuffer) else {
+            box.lock.lock()
+            box.lastStatus = status
+            box.lock.unlock()
+            return
+        }
+
+        var length = 0
+        var pointer: UnsafeMutablePointer<CChar>?
+        guard CMBlockBufferGetDataPointer(
+            dataBuffer, atOffset: 0, lengthAtOffsetOut: nil, totalLengthOut: &length, dataPointerOut: &pointer
+        ) == noErr, let pointer else { return }
+
+        var keyframe = true
+        if let attachments = CMSampleBufferGetSampleAttachmentsArray(sampleBuffer, createIfNecessary: false)
+            as? [[CFString: Any]], let first = attachments.first {
+            keyframe = !(first[kCMSampleAttachmentKey_NotSync] as? Bool ?? false)
+        }
+
+        let framePts = CMSampleBufferGetPresentationTimeStamp(sampleBuffer)
+        let avcC = CMSampleBufferGetFormatDescription(sampleBuffer).flatMap(extractAvcC)
+        box.push(
+            EncodedVideoFrame(
+                data: Data(bytes: pointer, count: length),
+                ptsValue: framePts.value,
+                ptsScale: framePts.timescale,
+                keyframe: keyframe
+            ),
+            avcC: avcC
+        )
+    }
+}
+
+/// Wait for every pending frame to be emitted.
+@_cdecl("sc_h264_encoder_flush")
+public func h264EncoderFlush(_ encoder: OpaquePointer) {
+    let box = Unmanaged<H264EncoderBox>.fromOpaque(UnsafeRawPointer(encoder)).takeUnretainedValue()
+    if let session = box.session {
+        VTCompressionSessionCompleteFrames(session, untilPresentationTimeStamp: .invalid)
+    }
+}
+
+/// Last asynchronous encode failure (0 if none), cleared on read.
+@_cdecl("sc_h264_encoder_take_error")
+public func h264EncoderTakeError(_ encoder: OpaquePointer) -> Int32 {
+    let box = Unmanaged<H264EncoderBox>.fromOpaque(UnsafeRawPointer(encoder)).takeUnretainedValue()
+    box.lock.lock()
+    defer { box.lock.unlock() }
+    let status = box.lastStatus
+    box.lastStatus = noErr
+    return status
+}
+
+/// Size of the next queued frame, or -1 if the queue is empty.
+@_cdecl("sc_h264_encoder_next_frame_size")
+public func h264EncoderNextFrameSize(_ encoder: OpaquePointer) -> Int {
+    let box = Unmanaged<H264EncoderBox>.fromOpaque(UnsafeRawPointer(encoder)).takeUnretainedValue()
+    box.lock.lock()
+    defer { box.lock.unlock() }
+    return box.frames.first?.data.count ?? -1
+}
+
+/// Pop the next frame into `buffer` (which must hold `next_frame_size` bytes).
+@_cdecl("sc_h264_encoder_pop_frame")
+public func h264EncoderPopFrame(
+    _ encoder: OpaquePointer,
+    _ buffer: UnsafeMutableRawPointer,
+    _ capacity: Int,
+    _ outPtsValue: UnsafeMutablePointer<Int64>,
+    _ outPtsScale: UnsafeMutablePointer<Int32>,
+    _ outKeyframe: UnsafeMutablePointer<Bool>
+) -> Bool {
+    let box = Unmanaged<H264EncoderBox>.fromOpaque(UnsafeRawPointer(encoder)).takeUnretainedValue()
+    box.lock.lock()
+    defer { box.lock.unlock() }
+    guard let frame = box.frames.first, frame.data.count <= capacity else { return false }
+    box.frames.removeFirst()
+    frame.data.copyBytes(to: buffer.assumingMemoryBound(to: UInt8.self), count: frame.data.count)
+    outPtsValue.pointee = frame.ptsValue
+    outPtsScale.pointee = frame.ptsScale
+    outKeyframe.pointee = frame.keyframe
+    return true
+}
+
+/// Copy the `avcC` record. Returns its size (0 until the first frame is encoded).
+@_cdecl("sc_h264_encoder_avcc")
+public func h264EncoderAvcC(_ encoder: OpaquePointer, _ buffer: UnsafeMutableRawPointer?, _ capacity: Int) -> Int {
+    let box = Unmanaged<H264EncoderBox>.fromOpaque(UnsafeRawPointer(encoder)).takeUnretainedValue()
+    box.lock.lock()
+    defer { box.lock.unlock() }
+    guard let avcC = box.avcC else { return 0 }
+    if let buffer, capacity >= avcC.count {
+        avcC.copyBytes(to: buffer.assumingMemoryBound(to: UInt8.self), count: avcC.count)
+    }
+    return avcC.count
+}
@@ -0,0 +1,291 @@
+// XPC transport for running capture in a helper process
+//
+// Messages are dictionaries with an opaque `payload` (encoded on the Rust
+// side) and, for frames, an IOSurface handed over as an XPC object plus its
+// presentation time. Received surfaces are wrapped in a CMSampleBuffer so the
+// host consumes them like locally captured frames.
+//
+// Every connection and listener shares one `XPCHandlers` with the peers it
+// accepts; the Rust context is released when the last of them goes away.
+
+import CoreMedia
+import CoreVideo
+import Foundation
+import IOSurface
+import XPC
+
+public typealias XPCMessageCallback = @convention(c) (
+    UnsafeMutableRawPointer?, UnsafeMutableRawPointer, UnsafePointer<UInt8>?, Int, UnsafeMutableRawPointer?
+) -> Void
+public typealias XPCErrorCallback = @convention(c) (UnsafeMutableRawPointer?, UnsafeMutableRawPointer, Int32) -> Void
+public typealias XPCContextReleaseCallback = @convention(c) (UnsafeMutableRawPointer?) -> Void
+
+private let payloadKey = "payload"
+private let surfaceKey = "surface"
+private let timeKey = "time"
+
+// Values passed to `XPCErrorCallback`.
+private let xpcErrorInterrupted: Int32 = 0
+private let xpcErrorInvalidated: Int32 = 1
+private let xpcErrorOther: Int32 = 2
+
+private final class XPCHandlers {
+    let context: UnsafeMutableRawPointer?
+    let onMessage: XPCMessageCallback
+    let onError: XPCErrorCallback
+    let releaseContext: XPCContextReleaseCallback
+
+    init(
+        context: UnsafeMutableRawPointer?,
+        onMessage: XPCMessageCallback,
+        onError: XPCErrorCallback,
+        releaseContext: XPCContextReleaseCallback
+    ) {
+        self.context = context
+        self.onMessage = onMessage
+        self.onError = onError
+        self.releaseContext = releaseContext
+    }
+
+    deinit {
+        releaseContext(context)
+    }
+}
+
+private final class XPCConnectionBox {
+    let connection: xpc_connection_t
+    let handlers: XPCHandlers
+
+    init(connection: xpc_connection_t, handlers: XPCHandlers) {
+        self.connection = connection
+        self.handlers = handlers
+    }
+}
+
+private final class XPCListenerBox {
+    let listener: xpc_connection_t
+
+    init(listener: xpc_connection_t) {
+        self.listener = listener
+    }
+}
+
+private final class XPCEndpointBox {
+    let endpoint: xpc_endpoint_t
+
+    init(endpoint: xpc_endpoint_t) {
+        self.endpoint = endpoint
+    }
+}
+
+/// Wrap a received surface in a ready sample buffer. Returns a +1 reference.
+private func makeSampleBuffer(_ surface: IOSurfaceRef, seconds: Double) -> UnsafeMutableRawPointer? {
+    var unmanagedPixelBuffer: Unmanaged<CVPixelBuffer>?
+    guard CVPixelBufferCreateWithIOSurface(kCFAllocatorDefault, surface, nil, &unmanagedPixelBuffer) == kCVReturnSuccess,
+          let pixelBuffer = unmanagedPixelBuffer?.takeRetainedValue()
+    else { return nil }
+
+    var format: CMVideoFormatDescription?
+    guard CMVideoFormatDescriptionCreateForImageBuffer(
+        allocator: kCFAllocatorDefault,
+        imageBuffer: pixelBuffer,
+        formatDescriptionOut: &format
+    ) == noErr, let format else { return nil }
+
+    var timing = CMSampleTimingInfo(
+        duration: .invalid,
+        presentationTimeStamp: CMTime(seconds: seconds, preferredTimescale: 1_000_000_000),
+        decodeTimeStamp: .invalid
+    )
+    var sample: CMSampleBuffer?
+    guard CMSampleBufferCreateReadyWithImageBuffer(
+        allocator: kCFAllocatorDefault,
+        imageBuffer: pixelBuffer,
+        formatDescription: format,
+        sampleTiming: &timing,
+        sampleBufferOut: &sample
+    ) == noErr, let sample else { return nil }
+    return Unmanaged.passRetained(sample).toOpaque()
+}
+
+/// Route a connection's events to the Rust callbacks and resume it.
+///
+/// The event handler keeps the box alive until XPC delivers the final
+/// `XPC_ERROR_CONNECTION_INVALID` and releases the handler.
+private func activate(_ box: XPCConnectionBox) {
+    xpc_connection_set_event_handler(box.connection) { event in
+        let handlers = box.handlers
+        let peer = Unmanaged.passUnretained(box).toOpaque()
+        let type = xpc_get_type(event)
+        if type == XPC_TYPE_DICTIONARY {
+            var length = 0
+            let payload = xpc_dictionary_get_data(event, payloadKey, &length)
+            var frame: UnsafeMutableRawPointer?
+            if let object = xpc_dictionary_get_value(event, surfaceKey),
+               let surface = IOSurfaceLookupFromXPCObject(object) {
+                frame = makeSampleBuffer(surface, seconds: xpc_dictionary_get_double(event, timeKey))
+            }
+            handlers.onMessage(
+                handlers.context, peer, payload?.assumingMemoryBound(to: UInt8.self), length, frame
+            )
+        } else if type == XPC_TYPE_ERROR {
+            let code: Int32
+            if xpc_equal(event, XPC_ERROR_CONNECTION_INTERRUPTED) {
+                code = xpcErrorInterrupted
+            } else if xpc_equal(event, XPC_ERROR_CONNECTION_INVALID) {
+                code = xpcErrorInvalidated
+            } else {
+                code = xpcErrorOther
+            }
+            handlers.onError(handlers.context, peer, code)
+        }
+    }
+    xpc_connection_resume(box.connection)
+}
+
+private func accept(_ peer: xpc_connection_t, handlers: XPCHandlers) {
+    activate(XPCConnectionBox(connection: peer, handlers: handlers))
+}
+
+/// Connect to a service. `kind` 0 is an XPC service bundled with the app,
+/// 1 a launchd Mach service, 2 the listener behind `endpoint`.
+@_cdecl("sc_xpc_connection_create")
+public func xpcConnectionCreate(
+    _ kind: Int32,
+    _ name: UnsafePointer<CChar>?,
+    _ endpoint: UnsafeMutableRawPointer?,
+    _ context: UnsafeMutableRawPointer?,
+    _ onMessage: XPCMessageCallback,
+    _ onError: XPCErrorCallback,
+    _ releaseContext: XPCContextReleaseCallback
+) -> UnsafeMutableRawPointer {
+    let handlers = XPCHandlers(
+        context: context, onMessage: onMessage, onError: onError, releaseContext: releaseContext
+    )
+    let queue = DispatchQueue(label: "com.screencapturekit.xpc.connection")
+    let connection: xpc_connection_t
+    switch (kind, name, endpoint) {
+    case let (1, name?, _):
+        connection = xpc_connection_create_mach_service(name, queue, 0)
+    case let (2, _, endpoint?):
+        let box = Unmanaged<XPCEndpointBox>.fromOpaque(endpoint).takeUnretainedValue()
+        connection = xpc_connection_create_from_endpoint(box.endpoint)
+        xpc_connection_set_target_queue(connection, queue)
+    default:
+        connection = xpc_connection_create(name, queue)
+    }
+    let box = XPCConnectionBox(connection: connection, handlers: handlers)
+    activate(box)
+    return Unmanaged.passRetained(box).toOpaque()
+}
+
+@_cdecl("sc_xpc_connection_retain")
+public func xpcConnectionRetain(_ connection: UnsafeMutableRawPointer) -> UnsafeMutableRawPointer {
+    Unmanaged<XPCConnectionBox>.fromOpaque(connection).retain().toOpaque()
+}
+
+@_cdecl("sc_xpc_connection_release")
+public func xpcConnectionRelease(_ connection: UnsafeMutableRawPointer) {
+    Unmanaged<XPCConnectionBox>.fromOpaque(connection).release()
+}
+
+@_cdecl("sc_xpc_connection_cancel")
+public func xpcConnectionCancel(_ connection: UnsafeMutableRawPointer) {
+    let box = Unmanaged<XPCConnectionBox>.fromOpaque(connection).takeUnretainedValue()
+    xpc_connection_cancel(box.connection)
+}
+
+@_cdecl("sc_xpc_connection_pid")
+public func xpcConnectionPid(_ connection: UnsafeMutableRawPointer) -> Int32 {
+    let box = Unmanaged<XPCConnectionBox>.fromOpaque(connection).takeUnretainedValue()
+    return xpc_connection_get_pid(box.connection)
+}
+
+/// Send `payload`, plus `surface` (may be NULL) presented at `seconds`.
+@_cdecl("sc_xpc_connection_send")
+public func xpcConnectionSend(
+    _ connection: UnsafeMutableRawPointer,
+    _ payload: UnsafePointer<UInt8>,
+    _ length: Int,
+    _ surface: UnsafeMutableRawPointer?,
+    _ seconds: Double
+) {
+    let box = Unmanaged<XPCConnectionBox>.fromOpaque(connection).takeUnretainedValue()
+    let message = xpc_dictionary_create(nil, nil, 0)
+    xpc_dictionary_set_data(message, payloadKey, payload, length)
+    if let surface {
+        let ioSurface = Unmanaged<IOSurfaceRef>.fromOpaque(surface).takeUnretainedValue()
+        xpc_dictionary_set_value(message, surfaceKey, IOSurfaceCreateXPCObject(ioSurface))
+        xpc_dictionary_set_double(message, timeKey, seconds)
+    }
+    xpc_connection_send_message(box.connection, message)
+}
+
+/// Listen on the Mach service `name`, or anonymously when `name` is NULL.
+@_cdecl("sc_xpc_listener_create")
+public func xpcListenerCreate(
+    _ name: UnsafePointer<CChar>?,
+    _ context: UnsafeMutableRawPointer?,
+    _ onMessage: XPCMessageCallback,
+    _ onError: XPCErrorCallback,
+    _ releaseContext: XPCContextReleaseCallback
+) -> UnsafeMutableRawPointer {
+    let handlers = XPCHandlers(
+        context: context, onMessage: onMessage, onError: onError, releaseContext: releaseContext
+    )
+    let queue = DispatchQueue(label: "com.screencapturekit.xpc.listener")
+    let listener: xpc_connection_t
+    if let name {
+        listener = xpc_connection_create_mach_service(
+            name, queue, UInt64(XPC_CONNECTION_MACH_SERVICE_LISTENER)
+        )
+    } else {
+        listener = xpc_connection_create(nil, queue)
+    }
+    xpc_connection_set_event_handler(listener) { event in
+        if xpc_get_type(event) == XPC_TYPE_CONNECTION {
+            accept(event, handlers: handlers)
+        }
+    }
+    xpc_connection_resume(listener)
+    return Unmanaged.passRetained(XPCListenerBox(listener: listener)).toOpaque()
+}
+
+@_cdecl("sc_xpc_listener_copy_endpoint")
+public func xpcListenerCopyEndpoint(_ listener: UnsafeMutableRawPointer) -> UnsafeMutableRawPointer {
+    let box = Unmanaged<XPCListenerBox>.fromOpaque(listener).takeUnretainedValue()
+    let endpoint = XPCEndpointBox(endpoint: xpc_endpoint_create(box.listener))
+    return Unmanaged.passRetained(endpoint).toOpaque()
+}
+
+@_cdecl("sc_xpc_listener_release")
+public func xpcListenerRelease(_ listener: UnsafeMutableRawPointer) {
+    let box = Unmanaged<XPCListenerBox>.fromOpaque(listener).takeRetainedValue()
+    xpc_connection_cancel(box.listener)
+}
+
+@_cdecl("sc_xpc_endpoint_release")
+public func xpcEndpointRelease(_ endpoint: UnsafeMutableRawPointer) {
+    Unmanaged<XPCEndpointBox>.fromOpaque(endpoint).release()
+}
+
+// `xpc_main` takes a plain C function, so the service handlers live here.
+private var serviceHandlers: XPCHandlers?
+
+/// Run the calling process as an XPC service. Never returns.
+@_cdecl("sc_xpc_service_main")
+public func xpcServiceMain(
+    _ context: UnsafeMutableRawPointer?,
+    _ onMessage: XPCMessageCallback,
+    _ onError: XPCErrorCallback,
+    _ releaseContext: XPCContextReleaseCallback
+) -> Never {
+    serviceHandlers = XPCHandlers(
+        context: context, onMessage: onMessage, onError: onError, releaseContext: releaseContext
+    )
+    xpc_main { peer in
+        if let handlers = serviceHandlers {
+            accept(peer, handlers: handlers)
+        }
+    }
+}
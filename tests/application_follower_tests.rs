@@ -0,0 +1,44 @@
+//! `ApplicationFollower` tests
+
+use std::time::Duration;
+
+use screencapturekit::shareable_content::SCShareableContent;
+use screencapturekit::stream::application_follower::ApplicationFollower;
+use screencapturekit::stream::configuration::SCStreamConfiguration;
+use screencapturekit::stream::content_filter::SCContentFilter;
+use screencapturekit::stream::SCStream;
+
+// Initialize CoreGraphics to prevent CGS_REQUIRE_INIT crashes in CI
+fn cg_init_for_headless_ci() {
+    extern "C" {
+        fn sc_initialize_core_graphics();
+    }
+    unsafe { sc_initialize_core_graphics() }
+}
+
+#[test]
+fn test_follower_stops_promptly_on_drop() {
+    cg_init_for_headless_ci();
+    let content = SCShareableContent::get().expect("Failed to get shareable content");
+    let display = &content.displays()[0];
+    let filter = SCContentFilter::for_display(display)
+        .with_excluding_windows(&[])
+        .build();
+    let stream = SCStream::new(&filter, &SCStreamConfiguration::default());
+
+    let follower = ApplicationFollower::start(
+        &stream,
+        display,
+        "com.example.not-installed",
+        Duration::from_secs(3600),
+    )
+    .expect("spawn follower thread");
+    assert_eq!(follower.bundle_id(), "com.example.not-installed");
+    assert_eq!(follower.update_count(), 0);
+    assert!(follower.last_error().is_none());
+    assert!(format!("{follower:?}").contains("ApplicationFollower"));
+
+    let started = std::time::Instant::now();
+    drop(follower);
+    assert!(started.elapsed() < Duration::from_secs(30));
+}
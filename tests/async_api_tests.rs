@@ -5,6 +5,8 @@
 
 #![cfg(feature = "async")]
 #![allow(clippy::match_same_arms)]
+// Exercises the deprecated `SCContentFilter::create()` builder on purpose.
+#![allow(deprecated)]
 
 use screencapturekit::async_api::*;
 use screencapturekit::stream::output_type::SCStreamOutputType;
@@ -1281,6 +1283,31 @@ mod tokio_async_tests {
             }
         }
     }
+
+    #[cfg(feature = "macos_14_0")]
+    #[tokio::test]
+    async fn test_async_screenshot_capture_both() {
+        use screencapturekit::async_api::AsyncSCScreenshotManager;
+
+        let content = AsyncSCShareableContent::get().await.unwrap();
+        if let Some(display) = content.displays().first() {
+            let filter = SCContentFilter::for_display(display)
+                .with_excluding_windows(&[])
+                .build();
+
+            let config = SCStreamConfiguration::new()
+                .with_width(320)
+                .with_height(240);
+
+            let result = AsyncSCScreenshotManager::capture_both(&filter, &config).await;
+            if let Ok(shot) = result {
+                assert!(shot.image().width() > 0);
+                assert!(shot.sample_buffer().is_valid());
+            } else {
+                // Permission error is okay
+            }
+        }
+    }
 }
 
 // ============================================================================
@@ -1371,3 +1398,118 @@ async fn test_async_frame_delivery_assertive() {
         "clean capture should leave no stop error"
     );
 }
+
+// ============================================================================
+// Cancellation Tests
+// ============================================================================
+
+mod cancellation_tests {
+    use screencapturekit::async_api::*;
+    use screencapturekit::error::SCError;
+    use screencapturekit::shareable_content::SCShareableContent;
+    use screencapturekit::stream::configuration::SCStreamConfiguration;
+    use screencapturekit::stream::content_filter::SCContentFilter;
+    use screencapturekit::stream::output_type::SCStreamOutputType;
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::task::{Context, Poll, Wake, Waker};
+
+    struct CountingWaker(AtomicUsize);
+
+    impl Wake for CountingWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    fn counting_waker() -> (Arc<CountingWaker>, Waker) {
+        let counter = Arc::new(CountingWaker(AtomicUsize::new(0)));
+        (Arc::clone(&counter), Waker::from(counter))
+    }
+
+    #[test]
+    fn test_cancel_wakes_and_resolves_pending_future() {
+        let token = CancellationToken::new();
+        let mut future = token.guard(std::future::pending::<Result<(), SCError>>());
+        let (counter, waker) = counting_waker();
+        let mut cx = Context::from_waker(&waker);
+
+        assert!(Pin::new(&mut future).poll(&mut cx).is_pending());
+        assert!(!token.is_cancelled());
+
+        // Any clone of the token cancels the guarded future.
+        let cloned = token.clone();
+        drop(token);
+        cloned.cancel();
+        assert!(cloned.is_cancelled());
+        assert_eq!(counter.0.load(Ordering::SeqCst), 1);
+        match Pin::new(&mut future).poll(&mut cx) {
+            Poll::Ready(Err(err)) => assert!(err.is_cancelled()),
+            other => panic!("expected cancellation, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_uncancelled_future_passes_through() {
+        let token = CancellationToken::new();
+        let mut future = token.guard(std::future::ready(Ok::<_, SCError>(42)));
+        let (_, waker) = counting_waker();
+        let mut cx = Context::from_waker(&waker);
+        assert!(matches!(
+            Pin::new(&mut future).poll(&mut cx),
+            Poll::Ready(Ok(42))
+        ));
+    }
+
+    #[test]
+    fn test_already_cancelled_token_short_circuits() {
+        let token = CancellationToken::new();
+        token.cancel();
+        let mut future = token.guard(std::future::ready(Ok::<_, SCError>(())));
+        let (counter, waker) = counting_waker();
+        let mut cx = Context::from_waker(&waker);
+        assert!(matches!(
+            Pin::new(&mut future).poll(&mut cx),
+            Poll::Ready(Err(SCError::Cancelled(_)))
+        ));
+        assert_eq!(counter.0.load(Ordering::SeqCst), 0);
+        assert!(format!("{future:?}").contains("cancelled: true"));
+    }
+
+    #[test]
+    fn test_next_with_cancel() {
+        let Ok(content) = SCShareableContent::get() else {
+            println!("⚠ Skipping - no screen recording permission");
+            return;
+        };
+        let Some(display) = content.displays().into_iter().next() else {
+            println!("⚠ No displays available");
+            return;
+        };
+        let filter = SCContentFilter::for_display(&display)
+            .with_excluding_windows(&[])
+            .build();
+        let config = SCStreamConfiguration::new()
+            .with_width(160)
+            .with_height(120);
+        let stream = AsyncSCStream::new(&filter, &config, 5, SCStreamOutputType::Screen);
+
+        // Never started, so nothing arrives until the token is cancelled.
+        let token = CancellationToken::new();
+        let mut future = stream.next_with_cancel(&token);
+        let (counter, waker) = counting_waker();
+        let mut cx = Context::from_waker(&waker);
+        if let Poll::Ready(result) = Pin::new(&mut future).poll(&mut cx) {
+            assert!(result.is_ok());
+            return;
+        }
+        token.cancel();
+        assert_eq!(counter.0.load(Ordering::SeqCst), 1);
+        assert!(matches!(
+            Pin::new(&mut future).poll(&mut cx),
+            Poll::Ready(Err(SCError::Cancelled(_)))
+        ));
+    }
+}
@@ -1,6 +1,7 @@
 //! `AudioBuffer` and `AudioBufferList` tests
 
-use screencapturekit::cm::AudioBuffer;
+use screencapturekit::cm::{AudioBuffer, CMSampleBuffer, CMSampleBufferExt, CMTime};
+use screencapturekit::cv::CVPixelBuffer;
 
 #[test]
 fn test_audio_buffer_display() {
@@ -25,3 +26,14 @@ fn test_audio_buffer_hash() {
     fn assert_hash_impl<T: std::hash::Hash>() {}
     assert_hash_impl::<AudioBuffer>();
 }
+
+#[test]
+fn test_with_audio_buffers_skips_video_samples() {
+    let pixels = CVPixelBuffer::create(16, 16, 0x4247_5241).expect("create BGRA pixel buffer");
+    let sample = CMSampleBuffer::create_for_image_buffer(&pixels, CMTime::ZERO, CMTime::ZERO)
+        .expect("wrap in sample buffer");
+    let mut called = false;
+    assert_eq!(sample.with_audio_buffers(|_| called = true), None);
+    assert!(!called);
+    assert!(sample.audio_buffer_list().is_none());
+}
@@ -0,0 +1,154 @@
+//! `AudioChunker` tests
+
+use screencapturekit::cm::{AudioChunkLayout, AudioChunker, AudioGapDetector, CMTime};
+use screencapturekit::error::SCError;
+
+#[test]
+fn test_audio_chunker_rejects_zero_sizes() {
+    assert!(matches!(
+        AudioChunker::new(0, 2, 48_000),
+        Err(SCError::InvalidDimension { .. })
+    ));
+    assert!(AudioChunker::new(1024, 0, 48_000).is_err());
+    assert!(AudioChunker::new(1024, 2, 0).is_err());
+}
+
+#[test]
+fn test_audio_chunker_emits_fixed_planar_chunks() {
+    let mut chunker = AudioChunker::new(4, 2, 48_000).unwrap();
+    let left: Vec<f32> = (0..6u8).map(f32::from).collect();
+    let right: Vec<f32> = (0..6u8).map(|i| -f32::from(i)).collect();
+
+    let chunks = chunker
+        .push_planar(CMTime::new(48_000, 48_000), &[&left, &right])
+        .unwrap();
+    assert_eq!(chunks.len(), 1);
+    assert_eq!(chunker.buffered_frames(), 2);
+
+    let chunk = &chunks[0];
+    assert_eq!(chunk.frame_count(), 4);
+    assert_eq!(chunk.channel_count(), 2);
+    assert_eq!(chunk.pts(), CMTime::new(48_000, 48_000));
+    assert_eq!(chunk.duration(), CMTime::new(4, 48_000));
+    assert_eq!(chunk.channel(0).unwrap(), &[0.0, 1.0, 2.0, 3.0]);
+    assert_eq!(chunk.channel(1).unwrap(), &[0.0, -1.0, -2.0, -3.0]);
+
+    // Continues the timeline from the samples, not the input buffer
+    let chunks = chunker
+        .push_planar(CMTime::INVALID, &[&left[..2], &right[..2]])
+        .unwrap();
+    assert_eq!(chunks.len(), 1);
+    assert_eq!(chunks[0].pts(), CMTime::new(48_004, 48_000));
+    assert_eq!(chunks[0].channel(0).unwrap(), &[4.0, 5.0, 0.0, 1.0]);
+}
+
+#[test]
+fn test_audio_chunker_interleaved_output_and_flush() {
+    let mut chunker = AudioChunker::new(3, 2, 48_000)
+        .unwrap()
+        .with_layout(AudioChunkLayout::Interleaved);
+
+    let samples = [1.0, -1.0, 2.0, -2.0, 3.0, -3.0, 4.0, -4.0];
+    let chunks = chunker.push_interleaved(CMTime::ZERO, &samples).unwrap();
+    assert_eq!(chunks.len(), 1);
+    assert_eq!(chunks[0].samples(), &samples[..6]);
+    assert!(chunks[0].channel(0).is_none());
+
+    let tail = chunker.flush().expect("one frame buffered");
+    assert_eq!(tail.samples(), &[4.0, -4.0, 0.0, 0.0, 0.0, 0.0]);
+    assert_eq!(tail.pts(), CMTime::new(3, 48_000));
+    assert!(chunker.flush().is_none());
+
+    assert!(chunker.push_interleaved(CMTime::ZERO, &[1.0]).is_err());
+}
+
+#[test]
+fn test_audio_chunker_fills_timestamp_gaps_with_silence() {
+    let mut chunker = AudioChunker::new(4, 1, 1_000).unwrap();
+    chunker
+        .push_planar(CMTime::new(0, 1_000), &[&[1.0, 1.0]])
+        .unwrap();
+
+    // Two frames missing between the pushes
+    let chunks = chunker
+        .push_planar(CMTime::new(4, 1_000), &[&[2.0, 2.0]])
+        .unwrap();
+    assert_eq!(chunks.len(), 1);
+    assert_eq!(chunks[0].samples(), &[1.0, 1.0, 0.0, 0.0]);
+    assert_eq!(chunker.buffered_frames(), 2);
+
+    chunker.reset();
+    assert_eq!(chunker.buffered_frames(), 0);
+}
+
+#[test]
+fn test_audio_gap_detector_reports_missing_frames() {
+    assert!(matches!(
+        AudioGapDetector::new(0),
+        Err(SCError::InvalidDimension { .. })
+    ));
+    let mut detector = AudioGapDetector::new(48_000).unwrap();
+
+    // First buffer only establishes the timeline
+    assert!(detector
+        .observe_timing(CMTime::new(48_000, 48_000), 1024)
+        .is_none());
+    // Contiguous, and within the default one-frame tolerance
+    assert!(detector
+        .observe_timing(CMTime::new(49_024, 48_000), 1024)
+        .is_none());
+    assert!(detector
+        .observe_timing(CMTime::new(50_049, 48_000), 1024)
+        .is_none());
+
+    // 479 frames missing, expressed in a nanosecond timescale
+    let gap = detector
+        .observe_timing(CMTime::new(1_074_000_000, 1_000_000_000), 1024)
+        .expect("gap");
+    assert_eq!(gap.start(), CMTime::new(51_073, 48_000));
+    assert_eq!(gap.missing_frames(), 479);
+    assert_eq!(gap.duration(), CMTime::new(479, 48_000));
+    assert_eq!(detector.gap_count(), 1);
+    assert_eq!(detector.missing_frames(), 479);
+
+    // Overlap and backwards jumps re-anchor without reporting
+    assert!(detector
+        .observe_timing(CMTime::new(48_000, 48_000), 1024)
+        .is_none());
+    // An invalid timestamp continues from the previous buffer
+    assert!(detector.observe_timing(CMTime::INVALID, 1024).is_none());
+    assert!(detector
+        .observe_timing(CMTime::new(50_048, 48_000), 1024)
+        .is_none());
+
+    detector.reset();
+    assert_eq!(detector.gap_count(), 0);
+    assert!(detector
+        .observe_timing(CMTime::new(0, 48_000), 1024)
+        .is_none());
+}
+
+#[test]
+fn test_audio_gap_tolerance_and_silence() {
+    let mut detector = AudioGapDetector::new(48_000).unwrap().with_tolerance(64);
+    let mut chunker = AudioChunker::new(4, 2, 48_000).unwrap();
+    let chunk = chunker
+        .push_planar(CMTime::new(0, 48_000), &[&[0.5; 4], &[0.5; 4]])
+        .unwrap()
+        .remove(0);
+    assert!(detector.observe(&chunk).is_none());
+    assert!(detector
+        .observe_timing(CMTime::new(68, 48_000), 4)
+        .is_none());
+
+    let gap = detector
+        .observe_timing(CMTime::new(200, 48_000), 4)
+        .expect("gap beyond tolerance");
+    assert_eq!(gap.start(), CMTime::new(72, 48_000));
+    let silence = gap.silence(2, AudioChunkLayout::Interleaved);
+    assert_eq!(silence.pts(), gap.start());
+    assert_eq!(silence.frame_count(), 128);
+    assert_eq!(silence.channel_count(), 2);
+    assert_eq!(silence.samples().len(), 256);
+    assert!(silence.samples().iter().all(|&s| s == 0.0));
+}
@@ -1,6 +1,6 @@
 //! Tests for audio input device enumeration
 
-use screencapturekit::audio_devices::AudioInputDevice;
+use screencapturekit::audio_devices::{AudioInputDevice, AudioOutputDevice};
 
 #[test]
 fn test_list_audio_devices() {
@@ -26,3 +26,87 @@ fn test_default_device() {
         println!("No default audio input device");
     }
 }
+
+#[test]
+fn test_device_capabilities() {
+    for device in AudioInputDevice::list() {
+        assert_eq!(device.uid(), device.id);
+        let rates = device.available_sample_rates();
+        if let Some(rate) = device.nominal_sample_rate() {
+            assert!(rate > 0.0);
+            if !rates.is_empty() {
+                assert!(device.supports_sample_rate(rate));
+            }
+        }
+        for range in &rates {
+            assert!(range.min <= range.max);
+        }
+        println!(
+            "  {}: {:?} Hz, {:?} channels",
+            device.name,
+            device.nominal_sample_rate(),
+            device.input_channel_count()
+        );
+    }
+}
+
+#[test]
+fn test_unknown_device_has_no_capabilities() {
+    let device = AudioInputDevice {
+        id: "com.example.no-such-device".to_string(),
+        name: "Missing".to_string(),
+        is_default: false,
+    };
+    assert_eq!(device.nominal_sample_rate(), None);
+    assert!(device.available_sample_rates().is_empty());
+    assert!(!device.supports_sample_rate(48_000.0));
+    assert_eq!(device.input_channel_count(), None);
+}
+
+#[test]
+fn test_sample_rate_range_contains() {
+    use screencapturekit::audio_devices::SampleRateRange;
+
+    let fixed = SampleRateRange {
+        min: 48_000.0,
+        max: 48_000.0,
+    };
+    assert!(fixed.contains(48_000.0));
+    assert!(!fixed.contains(44_100.0));
+
+    let continuous = SampleRateRange {
+        min: 8_000.0,
+        max: 96_000.0,
+    };
+    assert!(continuous.contains(44_100.0));
+    assert!(!continuous.contains(192_000.0));
+}
+
+#[test]
+fn test_device_monitor_lifecycle() {
+    use screencapturekit::audio_devices::AudioDeviceMonitor;
+    use std::time::Duration;
+
+    let (monitor, events) = AudioDeviceMonitor::channel().expect("listener should register");
+    assert_eq!(monitor.devices().len(), AudioInputDevice::list().len());
+    assert!(format!("{monitor:?}").contains("AudioDeviceMonitor"));
+
+    // No hardware changes happen during the test, so nothing is delivered.
+    assert!(events.recv_timeout(Duration::from_millis(100)).is_err());
+    drop(monitor);
+    assert!(events.recv().is_err(), "channel closes with the monitor");
+}
+
+#[test]
+fn test_list_output_devices() {
+    let devices = AudioOutputDevice::list();
+    println!("Found {} audio output devices", devices.len());
+    assert!(devices.iter().filter(|d| d.is_default).count() <= 1);
+    for device in &devices {
+        assert!(!device.id.is_empty());
+    }
+    if let Some(device) = AudioOutputDevice::default_device() {
+        assert!(device.is_default);
+        assert!(devices.contains(&device));
+    }
+}
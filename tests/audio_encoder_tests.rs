@@ -0,0 +1,64 @@
+//! Audio encoder tests
+
+#![cfg(feature = "audio_encoder")]
+
+use screencapturekit::audio_encoder::{AudioCodec, SCAudioEncoder};
+use screencapturekit::cm::CMTime;
+use screencapturekit::error::SCError;
+
+/// One second of a 440 Hz stereo sine wave, interleaved.
+fn sine(sample_rate: u32) -> Vec<f32> {
+    let rate = f64::from(sample_rate);
+    (0..sample_rate)
+        .flat_map(|i| {
+            #[allow(clippy::cast_possible_truncation)]
+            let v = (f64::from(i) / rate * 440.0 * std::f64::consts::TAU).sin() as f32 * 0.5;
+            [v, v]
+        })
+        .collect()
+}
+
+#[test]
+fn test_audio_encoder_rejects_invalid_config() {
+    assert!(matches!(
+        SCAudioEncoder::new(AudioCodec::Opus, 44_100, 2, 0),
+        Err(SCError::InvalidConfiguration(_))
+    ));
+    assert!(matches!(
+        SCAudioEncoder::new(AudioCodec::Aac, 48_000, 0, 0),
+        Err(SCError::InvalidDimension { .. })
+    ));
+}
+
+#[test]
+fn test_audio_encoder_aac_packets() {
+    let mut encoder = SCAudioEncoder::new(AudioCodec::Aac, 48_000, 2, 128_000).unwrap();
+    assert_eq!(encoder.frames_per_packet(), 1024);
+    assert!(!encoder.magic_cookie().is_empty());
+
+    let mut packets = encoder
+        .encode_interleaved(CMTime::new(0, 48_000), &sine(48_000))
+        .unwrap();
+    packets.extend(encoder.finish().unwrap());
+
+    assert!(!packets.is_empty());
+    assert!(packets.iter().all(|p| !p.data().is_empty()));
+    assert_eq!(packets[0].pts(), CMTime::new(0, 48_000));
+    assert_eq!(packets[0].duration(), CMTime::new(1024, 48_000));
+    assert!(packets
+        .windows(2)
+        .all(|w| w[0].pts().value < w[1].pts().value));
+}
+
+#[test]
+fn test_audio_encoder_opus_packets() {
+    let mut encoder = SCAudioEncoder::new(AudioCodec::Opus, 48_000, 2, 64_000).unwrap();
+    assert_eq!(encoder.codec(), AudioCodec::Opus);
+    assert_eq!(encoder.frames_per_packet(), 960);
+
+    let mut packets = encoder
+        .encode_interleaved(CMTime::new(0, 48_000), &sine(48_000))
+        .unwrap();
+    packets.extend(encoder.finish().unwrap());
+    assert!(!packets.is_empty());
+}
@@ -0,0 +1,77 @@
+//! Negotiated audio format tests
+
+use screencapturekit::cm::{CMSampleBuffer, CMSampleBufferExt, CMTime};
+use screencapturekit::cv::CVPixelBuffer;
+use screencapturekit::shareable_content::SCShareableContent;
+use screencapturekit::stream::configuration::{AudioFormat, SCStreamConfiguration};
+use screencapturekit::stream::content_filter::SCContentFilter;
+use screencapturekit::stream::output_type::SCStreamOutputType;
+use screencapturekit::stream::SCStream;
+
+#[test]
+fn test_video_sample_has_no_audio_format() {
+    let pixels = CVPixelBuffer::create(16, 16, 0x4247_5241).expect("create BGRA pixel buffer");
+    let sample = CMSampleBuffer::create_for_image_buffer(&pixels, CMTime::ZERO, CMTime::ZERO)
+        .expect("wrap in sample buffer");
+    assert_eq!(AudioFormat::of(&sample), None);
+}
+
+#[test]
+fn test_matches_configuration() {
+    let config = SCStreamConfiguration::new()
+        .with_sample_rate(48_000)
+        .with_channel_count(2);
+    let stereo = AudioFormat {
+        sample_rate: 48_000.0,
+        channel_count: 2,
+    };
+    let mono = AudioFormat {
+        channel_count: 1,
+        ..stereo
+    };
+    let slower = AudioFormat {
+        sample_rate: 44_100.0,
+        ..stereo
+    };
+    assert!(stereo.matches(&config));
+    assert!(!mono.matches(&config));
+    assert!(!slower.matches(&config));
+}
+
+#[test]
+fn test_display() {
+    let mono = AudioFormat {
+        sample_rate: 24_000.0,
+        channel_count: 1,
+    };
+    assert_eq!(mono.to_string(), "24000 Hz, 1 channel");
+    let stereo = AudioFormat {
+        sample_rate: 48_000.0,
+        channel_count: 2,
+    };
+    assert_eq!(stereo.to_string(), "48000 Hz, 2 channels");
+}
+
+#[test]
+fn test_stream_format_unknown_before_samples() {
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - no screen recording permission");
+        return;
+    };
+    let Some(display) = content.displays().into_iter().next() else {
+        println!("⚠ No displays available");
+        return;
+    };
+    let filter = SCContentFilter::for_display(&display)
+        .with_excluding_windows(&[])
+        .build();
+    let config = SCStreamConfiguration::new().with_captures_audio(true);
+    let stream = SCStream::new(&filter, &config);
+    for of_type in [
+        SCStreamOutputType::Screen,
+        SCStreamOutputType::Audio,
+        SCStreamOutputType::Microphone,
+    ] {
+        assert_eq!(stream.audio_format(of_type), None);
+    }
+}
@@ -0,0 +1,63 @@
+//! Audio output tap tests (macOS 14.2+)
+
+#![cfg(feature = "macos_14_2")]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use screencapturekit::audio_devices::AudioOutputDevice;
+use screencapturekit::audio_output_tap::AudioOutputTap;
+use screencapturekit::error::SCError;
+
+#[test]
+fn test_unknown_device_is_rejected() {
+    let result = AudioOutputTap::builder()
+        .with_device_id("com.example.no-such-device")
+        .start(|_| {});
+    assert!(matches!(
+        result,
+        Err(SCError::OSError { .. } | SCError::FeatureNotAvailable { .. })
+    ));
+}
+
+#[test]
+fn test_nul_in_device_id_is_rejected() {
+    let result = AudioOutputTap::builder()
+        .with_device_id("bad\0uid")
+        .start(|_| {});
+    assert!(matches!(result, Err(SCError::InvalidConfiguration(_))));
+}
+
+#[test]
+fn test_tap_default_output() {
+    let Some(device) = AudioOutputDevice::default_device() else {
+        println!("⚠ Skipping - no audio output device");
+        return;
+    };
+    let buffers = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&buffers);
+    let tap = match AudioOutputTap::builder()
+        .with_device(&device)
+        .with_excludes_current_process(true)
+        .start(move |buffer| {
+            assert_eq!(buffer.samples().len() % buffer.channel_count() as usize, 0);
+            counter.fetch_add(1, Ordering::Relaxed);
+        }) {
+        Ok(tap) => tap,
+        Err(error) => {
+            println!("⚠ Skipping - no audio capture permission ({error})");
+            return;
+        }
+    };
+    assert_eq!(tap.device_id(), Some(device.id.as_str()));
+    assert!(tap.sample_rate() > 0.0);
+    assert!(tap.channel_count() > 0);
+
+    std::thread::sleep(Duration::from_millis(500));
+    tap.stop();
+    // Nothing is delivered once the tap is gone.
+    let delivered = buffers.load(Ordering::Relaxed);
+    std::thread::sleep(Duration::from_millis(100));
+    assert_eq!(buffers.load(Ordering::Relaxed), delivered);
+}
@@ -0,0 +1,63 @@
+//! App audio tap tests (macOS 14.4+)
+
+#![cfg(feature = "macos_14_4")]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use screencapturekit::audio_taps::AppAudioTap;
+use screencapturekit::error::SCError;
+
+#[test]
+fn test_builder_deduplicates_processes() {
+    let builder = AppAudioTap::builder()
+        .with_process_id(42)
+        .with_process_id(7)
+        .with_process_id(42);
+    assert_eq!(builder.process_ids(), &[42, 7]);
+}
+
+#[test]
+fn test_tap_without_processes_is_rejected() {
+    let result = AppAudioTap::builder().start(|_| {});
+    assert!(matches!(result, Err(SCError::InvalidConfiguration(_))));
+}
+
+#[test]
+fn test_process_without_audio_is_rejected() {
+    let result = AppAudioTap::builder()
+        .with_process_id(i32::MAX)
+        .start(|_| {});
+    assert!(matches!(
+        result,
+        Err(SCError::InvalidConfiguration(_) | SCError::FeatureNotAvailable { .. })
+    ));
+}
+
+#[test]
+fn test_tap_current_process() {
+    let buffers = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&buffers);
+    let tap = match AppAudioTap::builder()
+        .with_process_id(std::process::id().try_into().unwrap())
+        .start(move |buffer| {
+            assert!(buffer.presentation_time().is_valid());
+            counter.fetch_add(1, Ordering::Relaxed);
+        }) {
+        Ok(tap) => tap,
+        Err(error) => {
+            // The test process has usually not touched audio.
+            println!("⚠ Skipping - cannot tap this process ({error})");
+            return;
+        }
+    };
+    assert!(tap.sample_rate() > 0.0);
+    assert_eq!(tap.channel_count(), 2);
+
+    std::thread::sleep(Duration::from_millis(300));
+    tap.stop();
+    let delivered = buffers.load(Ordering::Relaxed);
+    std::thread::sleep(Duration::from_millis(100));
+    assert_eq!(buffers.load(Ordering::Relaxed), delivered);
+}
@@ -77,8 +77,7 @@ fn test_screen_capture_with_audio() {
     let display = &displays[0];
     println!("Using display: {}", display.display_id());
 
-    let filter = SCContentFilter::create()
-        .with_display(display)
+    let filter = SCContentFilter::for_display(display)
         .with_excluding_windows(&[])
         .build();
 
@@ -165,8 +164,7 @@ fn test_combined_video_audio_capture() {
     let display = &displays[0];
     println!("Capturing display: {}", display.display_id());
 
-    let filter = SCContentFilter::create()
-        .with_display(display)
+    let filter = SCContentFilter::for_display(display)
         .with_excluding_windows(&[])
         .build();
 
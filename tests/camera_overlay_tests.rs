@@ -0,0 +1,64 @@
+//! Camera overlay tests
+
+#![cfg(feature = "camera_overlay")]
+
+use screencapturekit::camera_overlay::{
+    CameraDevice, CameraOverlayLayout, OverlayCorner, SCCameraOverlay,
+};
+use screencapturekit::prelude::*;
+
+#[test]
+fn test_camera_overlay_layout_defaults_and_builders() {
+    let layout = CameraOverlayLayout::default();
+    assert_eq!(layout.corner(), OverlayCorner::BottomRight);
+    assert!((layout.scale() - 0.25).abs() < f64::EPSILON);
+    assert!(layout.mirrored());
+    assert!(layout.validate().is_ok());
+
+    let layout = layout
+        .with_corner(OverlayCorner::TopLeft)
+        .with_scale(0.5)
+        .with_margin(0.0)
+        .with_mirrored(false);
+    assert_eq!(layout.corner(), OverlayCorner::TopLeft);
+    assert!((layout.scale() - 0.5).abs() < f64::EPSILON);
+    assert!(layout.margin().abs() < f64::EPSILON);
+    assert!(!layout.mirrored());
+}
+
+#[test]
+fn test_camera_overlay_layout_validation() {
+    for scale in [0.0, -0.1, 1.5, f64::NAN] {
+        let layout = CameraOverlayLayout::default().with_scale(scale);
+        assert!(matches!(
+            layout.validate(),
+            Err(SCError::InvalidConfiguration(_))
+        ));
+        assert!(matches!(
+            SCCameraOverlay::new(layout),
+            Err(SCError::InvalidConfiguration(_))
+        ));
+    }
+    for margin in [-1.0, f64::INFINITY] {
+        assert!(CameraOverlayLayout::default()
+            .with_margin(margin)
+            .validate()
+            .is_err());
+    }
+}
+
+#[test]
+fn test_camera_overlay_rejects_unknown_device() {
+    assert!(matches!(
+        SCCameraOverlay::for_device("no-such-camera", CameraOverlayLayout::default()),
+        Err(SCError::InvalidConfiguration(_))
+    ));
+}
+
+#[test]
+fn test_camera_device_list() {
+    for device in CameraDevice::list() {
+        assert!(!device.id.is_empty());
+        println!("camera: {} ({})", device.name, device.id);
+    }
+}
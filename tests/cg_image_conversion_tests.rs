@@ -0,0 +1,47 @@
+//! `CGImage` ↔ `CVPixelBuffer` conversion tests
+
+use screencapturekit::cv::cg_image::{CGImageFromPixelBufferExt, PixelBufferFromImageExt};
+use screencapturekit::cv::CVPixelBuffer;
+use screencapturekit::error::SCError;
+use screencapturekit::stream::configuration::PixelFormat;
+use screencapturekit::{CGImage, FourCharCode};
+
+fn test_image() -> CGImage {
+    let pixels = CVPixelBuffer::create(64, 48, 0x4247_5241).expect("create BGRA pixel buffer");
+    CGImage::from_pixel_buffer(&pixels).expect("image from pixel buffer")
+}
+
+#[test]
+fn test_image_from_pixel_buffer_keeps_size() {
+    let image = test_image();
+    assert_eq!(image.width(), 64);
+    assert_eq!(image.height(), 48);
+}
+
+#[test]
+fn test_pixel_buffer_from_image_in_stream_formats() {
+    let image = test_image();
+    for format in [
+        PixelFormat::BGRA,
+        PixelFormat::l10r,
+        PixelFormat::YCbCr_420v,
+        PixelFormat::YCbCr_420f,
+    ] {
+        let buffer = CVPixelBuffer::from_cgimage(&image, format).expect("render image");
+        assert_eq!(buffer.width(), 64);
+        assert_eq!(buffer.height(), 48);
+        assert_eq!(PixelFormat::from(buffer.pixel_format()), format);
+
+        let back = CGImage::from_pixel_buffer(&buffer).expect("convert back");
+        assert_eq!((back.width(), back.height()), (64, 48));
+    }
+}
+
+#[test]
+fn test_unknown_format_rejected() {
+    let format = PixelFormat::Unknown(FourCharCode::from_bytes(*b"none"));
+    assert!(matches!(
+        CVPixelBuffer::from_cgimage(&test_image(), format),
+        Err(SCError::InvalidPixelFormat(_))
+    ));
+}
@@ -2,7 +2,9 @@
 //!
 //! Tests for `CMTime`, `CMSampleTimingInfo`, and related types
 
-use screencapturekit::cm::{CMSampleTimingInfo, CMTime};
+use std::time::{Duration, Instant};
+
+use screencapturekit::cm::{CMClock, CMClockExt, CMSampleTimingInfo, CMTime, ClockDrift, MachTime};
 
 #[test]
 fn test_cmtime_creation() {
@@ -140,3 +142,153 @@ fn test_sample_buffer_cg_image_returns_err_on_no_image_buffer() {
     assert_eq!(cg.width(), 8);
     assert_eq!(cg.height(), 8);
 }
+
+#[test]
+fn test_sample_buffer_clone_shares_buffer() {
+    use screencapturekit::cm::{CMSampleBuffer, CMSampleBufferExt};
+    use screencapturekit::cv::CVPixelBuffer;
+
+    let pb = CVPixelBuffer::create(16, 16, 0x4247_5241).expect("create BGRA pixel buffer");
+    let sb = CMSampleBuffer::create_for_image_buffer(&pb, CMTime::new(3, 30), CMTime::ZERO)
+        .expect("wrap in sample buffer");
+    let held = sb.clone();
+    assert_eq!(held.as_ptr(), sb.as_ptr());
+    drop(sb);
+    // The cloned handle keeps the buffer alive on its own.
+    assert_eq!(held.presentation_timestamp(), CMTime::new(3, 30));
+}
+
+#[test]
+fn test_sample_buffer_make_owned_copies_pixels_and_timing() {
+    use screencapturekit::cm::{CMSampleBuffer, CMSampleBufferExt, CMSampleBufferRetainExt};
+    use screencapturekit::cv::CVPixelBuffer;
+
+    let pb = CVPixelBuffer::create(32, 24, 0x4247_5241).expect("create BGRA pixel buffer");
+    let sb = CMSampleBuffer::create_for_image_buffer(&pb, CMTime::new(7, 60), CMTime::ZERO)
+        .expect("wrap in sample buffer");
+    let owned = sb.make_owned().expect("make_owned");
+    assert_ne!(owned.as_ptr(), sb.as_ptr());
+    assert_eq!(owned.presentation_timestamp(), CMTime::new(7, 60));
+
+    let copy = owned.image_buffer().expect("copied image buffer");
+    assert_ne!(copy.as_ptr(), pb.as_ptr());
+    assert_eq!(copy.width(), 32);
+    assert_eq!(copy.height(), 24);
+    assert_eq!(copy.pixel_format(), 0x4247_5241);
+}
+
+#[test]
+fn test_mach_time_conversions() {
+    let before = MachTime::now();
+    std::thread::sleep(Duration::from_millis(5));
+    let after = MachTime::now();
+
+    assert!(after > before);
+    assert!(after.saturating_duration_since(before) >= Duration::from_millis(5));
+    assert_eq!(before.saturating_duration_since(after), Duration::ZERO);
+    assert!(before.elapsed() >= Duration::from_millis(5));
+    assert!(after.as_duration() > before.as_duration());
+
+    // Anchored on Instant::now(), so only approximately equal
+    let instant = before.to_instant();
+    let skew = Instant::now().duration_since(instant);
+    assert!(skew >= Duration::from_millis(5) && skew < Duration::from_secs(1));
+
+    let cm = after.to_cmtime();
+    assert_eq!(cm.timescale, 1_000_000_000);
+    assert_eq!(
+        u128::try_from(cm.value).unwrap(),
+        after.as_duration().as_nanos()
+    );
+
+    assert_eq!(MachTime::from_ticks(42).ticks(), 42);
+}
+
+#[test]
+fn test_mach_time_from_cmtime_and_instant() {
+    let now = MachTime::now();
+    let nanos = u64::try_from(now.as_duration().as_nanos()).unwrap();
+    let rebuilt = MachTime::from_nanos(nanos);
+    // Tick → nanosecond → tick rounds down by at most one tick.
+    assert!(now.saturating_duration_since(rebuilt) < Duration::from_micros(1));
+    assert!(rebuilt.saturating_duration_since(now) < Duration::from_micros(1));
+
+    let from_cm = MachTime::from_cmtime(now.to_cmtime()).expect("finite host time");
+    assert!(now.saturating_duration_since(from_cm) < Duration::from_micros(1));
+    // Any timescale works.
+    assert_eq!(
+        MachTime::from_cmtime(CMTime::new(3, 2)).map(MachTime::as_duration),
+        MachTime::from_cmtime(CMTime::new(1_500, 1_000)).map(MachTime::as_duration)
+    );
+    assert!(MachTime::from_cmtime(CMTime::INVALID).is_none());
+    assert!(MachTime::from_cmtime(CMTime::positive_infinity()).is_none());
+    assert!(MachTime::from_cmtime(CMTime::new(-1, 30)).is_none());
+
+    let instant = Instant::now();
+    let host = MachTime::from_instant(instant);
+    let back = host.to_instant();
+    let skew = if back > instant {
+        back - instant
+    } else {
+        instant - back
+    };
+    assert!(skew < Duration::from_millis(1));
+}
+
+#[test]
+fn test_host_time_clock_tracks_mach_time() {
+    let clock = CMClock::host_time_clock();
+    let before = MachTime::now();
+    let reading = clock.mach_time().expect("host clock time");
+    let after = MachTime::now();
+    assert!(reading.saturating_duration_since(after) < Duration::from_millis(1));
+    assert!(before.saturating_duration_since(reading) < Duration::from_millis(1));
+
+    let mut drift = ClockDrift::new();
+    drift.sample(&clock);
+    std::thread::sleep(Duration::from_millis(20));
+    drift.sample(&clock);
+    assert!(drift.elapsed() >= Duration::from_millis(20));
+    assert!(drift.ppm().unwrap().abs() < 1_000.0);
+}
+
+#[test]
+fn test_mach_time_on_host_time_clock() {
+    let clock = CMClock::host_time_clock();
+    let earlier = MachTime::from_instant(
+        Instant::now()
+            .checked_sub(Duration::from_millis(50))
+            .expect("an instant 50ms ago"),
+    );
+    let converted = earlier.to_clock_time(&clock).expect("clock time");
+    let converted = MachTime::from_cmtime(converted).expect("host time");
+    let error = if converted > earlier {
+        converted.saturating_duration_since(earlier)
+    } else {
+        earlier.saturating_duration_since(converted)
+    };
+    assert!(error < Duration::from_millis(1));
+}
+
+#[test]
+fn test_clock_drift_from_recorded_pairs() {
+    let mut drift = ClockDrift::new();
+    assert_eq!(drift.rate(), None);
+    assert_eq!(drift.drift(), None);
+
+    let start = MachTime::from_nanos(1_000_000_000);
+    drift.record(start, CMTime::new(0, 1_000_000));
+    // The same instant again: no elapsed host time, no rate yet.
+    drift.record(start, CMTime::new(0, 1_000_000));
+    assert_eq!(drift.rate(), None);
+
+    // After 10 host seconds the clock reads 10.001 s: 100 ppm fast.
+    let later = MachTime::from_nanos(11_000_000_000);
+    drift.record(later, CMTime::new(10_001_000, 1_000_000));
+    drift.record(later, CMTime::INVALID);
+    let elapsed = drift.elapsed();
+    assert!(elapsed > Duration::from_millis(9_999) && elapsed <= Duration::from_secs(10));
+    assert!((drift.ppm().unwrap() - 100.0).abs() < 1.0);
+    assert!((drift.drift().unwrap() - 0.001).abs() < 1e-5);
+    assert!(drift.rate().unwrap() > 1.0);
+}
@@ -0,0 +1,11 @@
+//! Fixtures shared by the integration tests
+
+use screencapturekit::cm::{CMSampleBuffer, CMTime};
+use screencapturekit::cv::CVPixelBuffer;
+
+/// An 8×8 BGRA frame presented at `frame`/60 s, lasting 1/60 s.
+pub fn sample(frame: i64) -> CMSampleBuffer {
+    let buffer = CVPixelBuffer::create(8, 8, 0x4247_5241).expect("create BGRA pixel buffer");
+    CMSampleBuffer::create_for_image_buffer(&buffer, CMTime::new(frame, 60), CMTime::new(1, 60))
+        .expect("wrap in sample buffer")
+}
@@ -2,7 +2,7 @@
 //!
 //! Comprehensive tests for the `SCStreamConfiguration` builder pattern
 
-use screencapturekit::cg::CGRect;
+use screencapturekit::cg::{CGRect, CGSize};
 use screencapturekit::cm::CMTime;
 use screencapturekit::stream::configuration::{PixelFormat, SCStreamConfiguration};
 
@@ -69,6 +69,35 @@ fn test_builder_with_fps() {
     println!("FPS: {fps}");
 }
 
+#[test]
+fn test_builder_with_frame_rate() {
+    let config = SCStreamConfiguration::new().with_frame_rate(29.97);
+    let interval = config.minimum_frame_interval();
+    if interval.is_valid() {
+        assert_eq!(interval.value, 1000);
+        assert_eq!(interval.timescale, 29_970);
+        assert!((config.frame_rate() - 29.97).abs() < 1e-9);
+        assert_eq!(config.fps(), 30);
+    }
+
+    // NTSC as an exact interval rounds to the nominal whole rate
+    let ntsc = SCStreamConfiguration::new().with_minimum_frame_interval(&CMTime::new(1001, 60_000));
+    if ntsc.minimum_frame_interval().is_valid() {
+        assert!((ntsc.frame_rate() - 59.94).abs() < 1e-3);
+        assert_eq!(ntsc.fps(), 60);
+    }
+}
+
+#[test]
+fn test_builder_with_frame_rate_clears_limit_for_invalid_rates() {
+    for fps in [0.0, -30.0, f64::NAN, f64::INFINITY] {
+        let config = SCStreamConfiguration::new().with_frame_rate(fps);
+        assert_eq!(config.minimum_frame_interval().value, 0);
+        assert!(config.frame_rate().abs() < f64::EPSILON);
+        assert_eq!(config.fps(), 0);
+    }
+}
+
 #[test]
 fn test_builder_with_scales_to_fit() {
     let config = SCStreamConfiguration::new().with_scales_to_fit(true);
@@ -78,6 +107,19 @@ fn test_builder_with_scales_to_fit() {
     assert!(!config2.scales_to_fit());
 }
 
+#[test]
+#[cfg(feature = "macos_14_0")]
+fn test_builder_with_preserves_aspect_ratio() {
+    let config = SCStreamConfiguration::new()
+        .with_scales_to_fit(true)
+        .with_preserves_aspect_ratio(true);
+    assert!(config.scales_to_fit());
+    assert!(config.preserves_aspect_ratio());
+
+    let config2 = SCStreamConfiguration::new().with_preserves_aspect_ratio(false);
+    assert!(!config2.preserves_aspect_ratio());
+}
+
 #[test]
 fn test_builder_with_source_rect() {
     let rect = CGRect::new(0.0, 0.0, 1920.0, 1080.0);
@@ -97,6 +139,60 @@ fn test_builder_with_destination_rect() {
     println!("Destination rect: {result:?}");
 }
 
+#[test]
+fn test_validate_rects_unset() {
+    let config = SCStreamConfiguration::new()
+        .with_width(1920)
+        .with_height(1080);
+    assert!(config.validate_rects(CGSize::new(1512.0, 982.0)).is_ok());
+}
+
+#[test]
+fn test_validate_source_rect_in_points() {
+    // A 2x display is 1512x982 points; the source rect is in points.
+    let content = CGSize::new(1512.0, 982.0);
+    let inside = SCStreamConfiguration::new()
+        .with_width(1512)
+        .with_height(982)
+        .with_source_rect(CGRect::new(756.0, 491.0, 756.0, 491.0));
+    assert!(inside.validate_rects(content).is_ok());
+
+    // The same region given in pixels runs off the content.
+    let pixels = SCStreamConfiguration::new()
+        .with_width(1512)
+        .with_height(982)
+        .with_source_rect(CGRect::new(1512.0, 982.0, 1512.0, 982.0));
+    let err = pixels.validate_rects(content).unwrap_err();
+    assert!(err.to_string().contains("source rect"), "{err}");
+}
+
+#[test]
+fn test_validate_source_rect_negative_origin() {
+    let config = SCStreamConfiguration::new()
+        .with_width(100)
+        .with_height(100)
+        .with_source_rect(CGRect::new(-10.0, 0.0, 100.0, 100.0));
+    assert!(config.validate_rects(CGSize::new(1000.0, 1000.0)).is_err());
+}
+
+#[test]
+fn test_validate_destination_rect_in_output_pixels() {
+    let fits = SCStreamConfiguration::new()
+        .with_width(1920)
+        .with_height(1080)
+        .with_destination_rect(CGRect::new(1280.0, 720.0, 640.0, 360.0));
+    assert!(fits.validate_rects(CGSize::new(1512.0, 982.0)).is_ok());
+
+    let overflows = SCStreamConfiguration::new()
+        .with_width(1280)
+        .with_height(720)
+        .with_destination_rect(CGRect::new(1280.0, 720.0, 640.0, 360.0));
+    let err = overflows
+        .validate_rects(CGSize::new(1512.0, 982.0))
+        .unwrap_err();
+    assert!(err.to_string().contains("destination rect"), "{err}");
+}
+
 #[test]
 fn test_builder_with_background_color() {
     let config = SCStreamConfiguration::new().with_background_color(1.0, 0.0, 0.0);
@@ -218,13 +314,32 @@ fn test_configuration_equality() {
     let config1 = SCStreamConfiguration::new();
     let config2 = SCStreamConfiguration::new();
 
-    // Two separately created configs have different pointers, so they're not equal
-    // This tests pointer-based equality
-    assert_ne!(config1, config2);
+    // Separately created configs with the same settings are equal
+    assert_eq!(config1, config2);
 
-    // A clone shares the same underlying object (ref counted), so pointers are equal
+    // A clone shares the same underlying object
     let config3 = config1.clone();
     assert_eq!(config1, config3);
+
+    let config4 = SCStreamConfiguration::new().with_width(1280);
+    assert_ne!(config1, config4);
+    assert_eq!(config4, config4.deep_copy());
+}
+
+#[test]
+fn test_configuration_equality_skips_noop_update() {
+    let current = SCStreamConfiguration::new()
+        .with_width(1920)
+        .with_height(1080)
+        .with_shows_cursor(true);
+    let next = SCStreamConfiguration::new()
+        .with_shows_cursor(true)
+        .with_height(1080)
+        .with_width(1920);
+    assert_eq!(current, next, "{}", current.diff(&next));
+
+    let next = next.with_shows_cursor(false);
+    assert_ne!(current, next);
 }
 
 #[test]
@@ -233,14 +348,32 @@ fn test_configuration_hash() {
 
     let config1 = SCStreamConfiguration::new();
     let config2 = SCStreamConfiguration::new();
+    let config3 = SCStreamConfiguration::new().with_width(640);
 
     let mut set = HashSet::new();
     set.insert(config1);
     set.insert(config2);
-    assert!(!set.is_empty(), "Set should contain configurations");
+    set.insert(config3);
+
+    // The two default configs collapse into one entry
+    assert_eq!(set.len(), 2);
+    assert!(set.contains(&SCStreamConfiguration::new().with_width(640)));
+}
+
+#[test]
+#[cfg(feature = "macos_15_0")]
+fn test_configuration_equality_includes_microphone() {
+    use std::collections::HashSet;
+
+    let base = SCStreamConfiguration::new().with_captures_microphone(true);
+    let other_mic = base
+        .deep_copy()
+        .with_microphone_capture_device_id("BuiltInMicrophoneDevice");
+    assert_ne!(base, other_mic);
+    assert_ne!(base, SCStreamConfiguration::new());
 
-    // Both should hash to the same value since they're default configs
-    // Note: This depends on the Hash implementation
+    let set: HashSet<_> = [base.deep_copy(), base, other_mic].into_iter().collect();
+    assert_eq!(set.len(), 2);
 }
 
 // MARK: - Mutable vs Builder Pattern Consistency
@@ -630,3 +630,182 @@ fn test_hdr_pixel_formats() {
         // Just verify HDR formats can be set
     }
 }
+
+// MARK: - Deep copy and diff
+
+#[test]
+fn test_configuration_deep_copy_is_independent() {
+    let mut config = SCStreamConfiguration::new()
+        .with_width(1920)
+        .with_height(1080)
+        .with_shows_cursor(false);
+    let copy = config.deep_copy();
+    assert_eq!(config, copy, "deep copy starts with the same settings");
+
+    config.set_width(640);
+    assert_ne!(config, copy, "deep copy must be a distinct object");
+    assert_eq!(copy.width(), 1920);
+    assert_eq!(copy.height(), 1080);
+    assert!(!copy.shows_cursor());
+}
+
+#[test]
+fn test_configuration_diff() {
+    use screencapturekit::stream::configuration::ConfigurationProperty;
+
+    let current = SCStreamConfiguration::new()
+        .with_width(1920)
+        .with_shows_cursor(true);
+    assert!(current.diff(&current.deep_copy()).is_empty());
+
+    let next = current.deep_copy().with_width(1280).with_queue_depth(7);
+    let delta = current.diff(&next);
+    assert_eq!(delta.len(), 2);
+    assert!(delta.contains(ConfigurationProperty::Width));
+    assert!(delta.contains(ConfigurationProperty::QueueDepth));
+    assert!(!delta.contains(ConfigurationProperty::ShowsCursor));
+
+    let width = delta.get(ConfigurationProperty::Width).unwrap();
+    assert_eq!(width.old, "1920");
+    assert_eq!(width.new, "1280");
+    assert!(delta.to_string().contains("width: 1920 -> 1280"));
+}
+
+#[test]
+#[cfg(feature = "macos_15_0")]
+fn test_configuration_diff_microphone() {
+    use screencapturekit::stream::configuration::ConfigurationProperty;
+
+    let current = SCStreamConfiguration::new();
+    let next = current
+        .deep_copy()
+        .with_captures_microphone(true)
+        .with_microphone_capture_device_id("BuiltInMicrophoneDevice");
+    let delta = current.diff(&next);
+    assert!(delta.contains(ConfigurationProperty::CapturesMicrophone));
+    assert!(delta.contains(ConfigurationProperty::MicrophoneCaptureDeviceId));
+    assert_ne!(current, next);
+}
+
+#[test]
+fn test_configuration_clone_handed_between_threads() {
+    let config = SCStreamConfiguration::new().with_width(1920);
+    let mut shared = config.clone();
+
+    // Sequential use of a shared object from another thread is fine.
+    std::thread::spawn(move || {
+        shared.set_width(1280);
+    })
+    .join()
+    .unwrap();
+    assert_eq!(config.width(), 1280);
+}
+
+#[test]
+fn test_configuration_deep_copies_mutated_concurrently() {
+    let base = SCStreamConfiguration::new().with_width(1920);
+    let handles: Vec<_> = (0..4u32)
+        .map(|i| {
+            let mut own = base.deep_copy();
+            std::thread::spawn(move || {
+                for _ in 0..100 {
+                    own.set_width(640 + i);
+                    assert_eq!(own.width(), 640 + i);
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(base.width(), 1920);
+}
+
+#[test]
+fn test_configuration_concurrent_reads_through_clones() {
+    let config = SCStreamConfiguration::new().with_width(1920);
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let reader = config.clone();
+            std::thread::spawn(move || {
+                for _ in 0..100 {
+                    assert_eq!(reader.width(), 1920);
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+}
+
+// MARK: - Advanced flag read-back (macOS 14.0+ at runtime)
+
+/// Major version of the running macOS, or 0 if it can't be determined.
+#[cfg(feature = "macos_14_0")]
+fn runtime_macos_major() -> u32 {
+    std::process::Command::new("sw_vers")
+        .arg("-productVersion")
+        .output()
+        .ok()
+        .and_then(|out| String::from_utf8(out.stdout).ok())
+        .and_then(|version| version.trim().split('.').next()?.parse().ok())
+        .unwrap_or(0)
+}
+
+#[test]
+#[cfg(feature = "macos_14_0")]
+fn test_advanced_flags_read_back() {
+    if runtime_macos_major() < 14 {
+        println!("⚠ Skipping - these flags are ignored before macOS 14.0");
+        return;
+    }
+
+    let mut config = SCStreamConfiguration::default();
+    assert!(!config.should_be_opaque());
+    assert!(!config.captures_shadows_only());
+    assert!(!config.ignore_global_clip_display());
+    assert!(!config.ignore_global_clip_single_window());
+
+    for value in [true, false] {
+        config.set_should_be_opaque(value);
+        config.set_captures_shadows_only(value);
+        config.set_ignore_global_clip_display(value);
+        config.set_ignore_global_clip_single_window(value);
+        config.set_ignores_shadows_display(value);
+        config.set_ignores_shadows_single_window(value);
+
+        assert_eq!(config.should_be_opaque(), value);
+        assert_eq!(config.captures_shadows_only(), value);
+        assert_eq!(config.ignore_global_clip_display(), value);
+        assert_eq!(config.ignore_global_clip_single_window(), value);
+        assert_eq!(config.ignores_shadows_display(), value);
+        assert_eq!(config.ignores_shadows_single_window(), value);
+    }
+}
+
+#[test]
+#[cfg(feature = "macos_14_0")]
+fn test_advanced_flags_builder_read_back() {
+    if runtime_macos_major() < 14 {
+        println!("⚠ Skipping - these flags are ignored before macOS 14.0");
+        return;
+    }
+
+    let config = SCStreamConfiguration::new()
+        .with_should_be_opaque(true)
+        .with_captures_shadows_only(true)
+        .with_ignore_global_clip_display(true)
+        .with_ignore_global_clip_single_window(true);
+    assert!(config.should_be_opaque());
+    assert!(config.captures_shadows_only());
+    assert!(config.ignore_global_clip_display());
+    assert!(config.ignore_global_clip_single_window());
+
+    // Flags survive a deep copy of the underlying configuration
+    let copy = config.deep_copy();
+    assert!(copy.should_be_opaque());
+    assert!(copy.captures_shadows_only());
+    assert!(copy.ignore_global_clip_display());
+    assert!(copy.ignore_global_clip_single_window());
+}
@@ -1,5 +1,8 @@
 //! `SCContentFilter` tests
 
+// Exercises the deprecated `SCContentFilter::create()` builder on purpose.
+#![allow(deprecated)]
+
 #[cfg(feature = "macos_14_2")]
 use screencapturekit::cg::CGRect;
 use screencapturekit::shareable_content::SCShareableContent;
@@ -41,6 +44,31 @@ fn test_content_filter_builder_window() {
     }
 }
 
+#[test]
+fn test_content_filter_desktop_independent_window() {
+    cg_init_for_headless_ci();
+    let content = SCShareableContent::create()
+        .with_on_screen_windows_only(false)
+        .get()
+        .expect("Failed to get shareable content");
+
+    if let Some(window) = content.windows().first() {
+        let filter = SCContentFilter::create()
+            .with_desktop_independent_window(window)
+            .build();
+        assert!(format!("{filter:?}").contains("SCContentFilter"));
+
+        let by_id = SCContentFilter::for_window_id(window.window_id())
+            .expect("window listed by shareable content should be found by id");
+        assert!(format!("{by_id:?}").contains("SCContentFilter"));
+    }
+
+    assert!(matches!(
+        SCContentFilter::for_window_id(u32::MAX),
+        Err(screencapturekit::error::SCError::WindowNotFound(_))
+    ));
+}
+
 #[test]
 fn test_content_filter_exclude_windows() {
     cg_init_for_headless_ci();
@@ -198,6 +226,42 @@ fn test_content_filter_equality() {
     assert_eq!(filter1, filter1);
     // Cloned filters have same pointer
     assert_eq!(filter1, filter2);
+
+    // A filter built again from the same display is equal too
+    let filter3 = SCContentFilter::for_display(display)
+        .with_excluding_windows(&[])
+        .build();
+    assert_eq!(filter1, filter3);
+}
+
+#[test]
+fn test_content_filter_equality_by_selection() {
+    cg_init_for_headless_ci();
+    let content = SCShareableContent::get().expect("Failed to get shareable content");
+    let display = &content.displays()[0];
+    let windows = content.windows();
+    if windows.len() < 2 {
+        println!("⚠ Skipping - need at least two windows");
+        return;
+    }
+
+    let forward = SCContentFilter::for_display(display)
+        .with_excluding_windows(&[&windows[0], &windows[1]])
+        .build();
+    let reversed = SCContentFilter::for_display(display)
+        .with_excluding_windows(&[&windows[1], &windows[0]])
+        .build();
+    assert_eq!(forward, reversed, "window order doesn't matter");
+
+    let one = SCContentFilter::for_display(display)
+        .with_excluding_windows(&[&windows[0]])
+        .build();
+    assert_ne!(forward, one);
+
+    let including = SCContentFilter::for_display(display)
+        .with_including_windows(&[&windows[0]])
+        .build();
+    assert_ne!(one, including, "excluding and including differ");
 }
 
 #[test]
@@ -217,6 +281,11 @@ fn test_content_filter_hash() {
     set.insert(filter.clone());
 
     assert!(set.contains(&filter));
+
+    let rebuilt = SCContentFilter::for_display(display)
+        .with_excluding_windows(&[])
+        .build();
+    assert!(set.contains(&rebuilt));
 }
 
 // MARK: - New Content Filter Features (macOS 14.0+)
@@ -280,6 +349,35 @@ fn test_content_filter_point_pixel_scale() {
     assert!(scale > 0.0);
 }
 
+#[test]
+#[cfg(feature = "macos_14_0")]
+fn test_content_filter_recommended_dimensions() {
+    cg_init_for_headless_ci();
+
+    let content = SCShareableContent::get().expect("Failed to get shareable content");
+    let display = &content.displays()[0];
+
+    let filter = SCContentFilter::create()
+        .with_display(display)
+        .with_excluding_windows(&[])
+        .with_content_info()
+        .build();
+
+    if let Some(dimensions) = filter.content_dimensions() {
+        assert_eq!(
+            filter.recommended_dimensions(),
+            Some((dimensions.width, dimensions.height))
+        );
+        // Native pixels are never fewer than the display's points
+        assert!(dimensions.width >= display.width());
+        assert!(dimensions.point_pixel_scale > 0.0);
+        // The snapshot travels with clones
+        let cloned = filter.clone();
+        drop(filter);
+        assert_eq!(cloned.content_dimensions(), Some(dimensions));
+    }
+}
+
 #[test]
 #[cfg(feature = "macos_14_2")]
 fn test_content_filter_include_menu_bar() {
@@ -414,3 +512,95 @@ fn test_shareable_content_style_from_i32() {
         SCShareableContentStyle::None
     ); // Unknown
 }
+
+#[test]
+fn test_content_filter_for_missing_application() {
+    use screencapturekit::error::SCError;
+
+    cg_init_for_headless_ci();
+    let content = SCShareableContent::get().expect("Failed to get shareable content");
+    let display = &content.displays()[0];
+
+    let result = SCContentFilter::for_application(display, "com.example.not-installed");
+    assert!(matches!(result, Err(SCError::ApplicationNotFound(_))));
+
+    let builder = SCContentFilter::create()
+        .with_display(display)
+        .with_application("com.example.not-installed");
+    assert!(format!("{builder:?}").contains("DisplayApplication"));
+    assert!(matches!(
+        builder.try_build(),
+        Err(SCError::ApplicationNotFound(_))
+    ));
+}
+
+#[cfg(feature = "macos_15_2")]
+#[test]
+fn test_content_filter_for_running_application() {
+    cg_init_for_headless_ci();
+    let content = SCShareableContent::get().expect("Failed to get shareable content");
+    let display = &content.displays()[0];
+    let Some(app) = content
+        .applications()
+        .into_iter()
+        .find(|app| !app.bundle_identifier().is_empty())
+    else {
+        return;
+    };
+
+    let bundle_id = app.bundle_identifier();
+    let filter = SCContentFilter::for_application(display, &bundle_id)
+        .expect("running application should resolve");
+    assert!(filter
+        .included_applications()
+        .iter()
+        .all(|included| included.bundle_identifier() == bundle_id));
+}
+
+#[test]
+fn test_content_filter_without_dock_and_wallpaper() {
+    cg_init_for_headless_ci();
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - no screen recording permission");
+        return;
+    };
+    let display = &content.displays()[0];
+
+    let filter = SCContentFilter::for_display(display)
+        .with_dock(false)
+        .with_wallpaper(false)
+        .try_build()
+        .expect("display filter without Dock and wallpaper");
+    assert!(format!("{filter:?}").contains("SCContentFilter"));
+
+    // Also allowed after a selection, on top of the caller's exclusions
+    let window = content.windows().into_iter().next();
+    let excluded: Vec<_> = window.iter().collect();
+    let filter = SCContentFilter::for_display(display)
+        .with_excluding_windows(&excluded)
+        .with_dock(false)
+        .try_build();
+    assert!(filter.is_ok());
+}
+
+#[test]
+#[cfg(feature = "macos_14_2")]
+fn test_content_filter_builder_menu_bar() {
+    cg_init_for_headless_ci();
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - no screen recording permission");
+        return;
+    };
+    let display = &content.displays()[0];
+
+    let filter = SCContentFilter::for_display(display)
+        .with_menu_bar(false)
+        .build();
+    assert!(!filter.include_menu_bar());
+
+    let filter = SCContentFilter::for_display(display)
+        .with_menu_bar(true)
+        .build();
+    // includeMenuBar only takes effect on macOS 14.2+
+    let _ = filter.include_menu_bar();
+}
@@ -185,3 +185,95 @@ fn test_picker_is_active_get_set_roundtrip() {
     // Restore.
     SCContentSharingPicker::set_active(original);
 }
+
+#[test]
+fn test_picker_audio_flags() {
+    use screencapturekit::content_sharing_picker::SCPickerAudio;
+
+    assert_eq!(SCPickerAudio::default(), SCPickerAudio::None);
+    assert!(!SCPickerAudio::None.captures_audio());
+    assert!(!SCPickerAudio::None.captures_microphone());
+    assert!(SCPickerAudio::Content.captures_audio());
+    assert!(!SCPickerAudio::Content.captures_microphone());
+    assert!(SCPickerAudio::ContentAndMicrophone.captures_audio());
+    assert!(SCPickerAudio::ContentAndMicrophone.captures_microphone());
+}
+
+#[test]
+fn test_picker_audio_applies_to_configuration() {
+    use screencapturekit::content_sharing_picker::SCPickerAudio;
+    use screencapturekit::stream::configuration::SCStreamConfiguration;
+
+    let config = SCPickerAudio::Content.apply(SCStreamConfiguration::new().with_width(800));
+    assert!(config.captures_audio());
+    assert!(config.excludes_current_process_audio());
+    assert_eq!(config.width(), 800);
+
+    let config = SCPickerAudio::None.apply(
+        SCStreamConfiguration::new()
+            .with_captures_audio(true)
+            .with_excludes_current_process_audio(true),
+    );
+    assert!(!config.captures_audio());
+    assert!(!config.excludes_current_process_audio());
+}
+
+#[test]
+fn test_picker_mode_content_style() {
+    use screencapturekit::content_sharing_picker::SCContentSharingPickerMode;
+    use screencapturekit::stream::content_filter::SCShareableContentStyle;
+
+    assert_eq!(
+        SCContentSharingPickerMode::SingleWindow.content_style(),
+        SCShareableContentStyle::Window
+    );
+    assert_eq!(
+        SCContentSharingPickerMode::MultipleWindows.content_style(),
+        SCShareableContentStyle::Window
+    );
+    assert_eq!(
+        SCContentSharingPickerMode::SingleDisplay.content_style(),
+        SCShareableContentStyle::Display
+    );
+    assert_eq!(
+        SCContentSharingPickerMode::MultipleApplications.content_style(),
+        SCShareableContentStyle::Application
+    );
+}
+
+#[test]
+fn test_picker_presentation_preselection() {
+    use screencapturekit::content_sharing_picker::{
+        SCContentSharingPickerMode, SCPickerPresentation,
+    };
+    use screencapturekit::shareable_content::SCShareableContent;
+
+    let presentation = SCPickerPresentation::new();
+    assert_eq!(presentation.default_mode(), None);
+    assert!(presentation.preselected().is_none());
+
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - no screen recording permission");
+        return;
+    };
+    let Some(display) = content.displays().into_iter().next() else {
+        println!("⚠ No displays available");
+        return;
+    };
+
+    let presentation = SCPickerPresentation::new().with_preselected_display(&display);
+    assert_eq!(
+        presentation.default_mode(),
+        Some(SCContentSharingPickerMode::SingleDisplay)
+    );
+    assert!(presentation.preselected().is_some());
+
+    // An explicit default mode wins over the one implied by the content.
+    let presentation = SCPickerPresentation::new()
+        .with_default_mode(SCContentSharingPickerMode::SingleWindow)
+        .with_preselected_display(&display);
+    assert_eq!(
+        presentation.default_mode(),
+        Some(SCContentSharingPickerMode::SingleWindow)
+    );
+}
@@ -17,6 +17,9 @@
 //!   capture is in flight, which exercises the `RwLock<Vec<HandlerEntry>>`
 //!   write path against an active dispatch reader.
 
+// Exercises the deprecated `SCContentFilter::create()` builder on purpose.
+#![allow(deprecated)]
+
 use screencapturekit::cm::CMSampleBufferExt;
 #[cfg(feature = "macos_14_2")]
 use screencapturekit::cm::CMSampleBufferSCExt;
@@ -0,0 +1,97 @@
+//! Pixel buffer crop tests
+
+use screencapturekit::cg::CGRect;
+use screencapturekit::cv::crop::PixelBufferCropExt;
+use screencapturekit::cv::frame_copy::{FrameCopyExt, RowLayout};
+use screencapturekit::cv::{CVPixelBuffer, CVPixelBufferLockFlags};
+use screencapturekit::error::SCError;
+
+const BGRA: u32 = 0x4247_5241;
+const YCBCR_420V: u32 = 0x3432_3076;
+
+/// A BGRA buffer whose first byte of each pixel is `x + y * 16`.
+fn gradient(width: usize, height: usize) -> CVPixelBuffer {
+    let buffer = CVPixelBuffer::create(width, height, BGRA).expect("create BGRA pixel buffer");
+    {
+        let mut guard = buffer
+            .lock(CVPixelBufferLockFlags::NONE)
+            .expect("lock for writing");
+        let stride = guard.bytes_per_row();
+        let base = guard.base_address_mut().expect("base address").cast::<u8>();
+        for y in 0..height {
+            for x in 0..width {
+                let value = u8::try_from(x + y * 16).unwrap();
+                unsafe { *base.add(y * stride + x * 4) = value };
+            }
+        }
+    }
+    buffer
+}
+
+#[test]
+fn test_crop_copies_region() {
+    let source = gradient(16, 8);
+    let cropped = source
+        .crop(CGRect::new(2.0, 3.0, 4.0, 2.0))
+        .expect("crop region");
+    assert_eq!(cropped.width(), 4);
+    assert_eq!(cropped.height(), 2);
+    assert_eq!(cropped.pixel_format(), source.pixel_format());
+
+    let mut pixels = vec![0u8; cropped.copy_size(RowLayout::Packed).unwrap()];
+    cropped.copy_into(&mut pixels, RowLayout::Packed).unwrap();
+    let firsts: Vec<u8> = pixels.chunks(4).map(|pixel| pixel[0]).collect();
+    assert_eq!(firsts, vec![50, 51, 52, 53, 66, 67, 68, 69]);
+}
+
+#[test]
+fn test_crop_to_io_surface() {
+    let source = gradient(16, 8);
+    let surface = source
+        .crop_to_io_surface(CGRect::new(0.0, 0.0, 8.0, 8.0))
+        .expect("crop to IOSurface");
+    assert_eq!(surface.width(), 8);
+    assert_eq!(surface.height(), 8);
+}
+
+#[test]
+fn test_crop_rejects_bad_regions() {
+    let source = gradient(16, 8);
+    assert!(matches!(
+        source.crop(CGRect::new(0.0, 0.0, 0.0, 4.0)),
+        Err(SCError::InvalidDimension { .. })
+    ));
+    assert!(matches!(
+        source.crop(CGRect::new(10.0, 0.0, 8.0, 4.0)),
+        Err(SCError::InvalidDimension { .. })
+    ));
+    assert!(matches!(
+        source.crop(CGRect::new(0.0, 6.0, 4.0, 4.0)),
+        Err(SCError::InvalidDimension { .. })
+    ));
+    assert!(matches!(
+        source.crop(CGRect::new(0.5, 0.0, 4.0, 4.0)),
+        Err(SCError::InvalidConfiguration(_))
+    ));
+    assert!(matches!(
+        source.crop(CGRect::new(-1.0, 0.0, 4.0, 4.0)),
+        Err(SCError::InvalidConfiguration(_))
+    ));
+}
+
+#[test]
+fn test_crop_biplanar_needs_even_edges() {
+    let Ok(source) = CVPixelBuffer::create(16, 16, YCBCR_420V) else {
+        println!("⚠ Skipping - 420v pixel buffers unavailable");
+        return;
+    };
+    assert!(matches!(
+        source.crop(CGRect::new(1.0, 0.0, 4.0, 4.0)),
+        Err(SCError::InvalidDimension { .. })
+    ));
+    let cropped = source
+        .crop(CGRect::new(2.0, 4.0, 8.0, 6.0))
+        .expect("crop 420v region");
+    assert_eq!((cropped.width(), cropped.height()), (8, 6));
+    assert_eq!(cropped.plane_count(), 2);
+}
@@ -0,0 +1,72 @@
+//! Cursor metadata tests
+
+use std::sync::{Arc, Mutex};
+
+use screencapturekit::cm::{CMSampleBuffer, CMSampleBufferExt, CMTime};
+use screencapturekit::cv::CVPixelBuffer;
+use screencapturekit::shareable_content::SCShareableContent;
+use screencapturekit::stream::configuration::SCStreamConfiguration;
+use screencapturekit::stream::content_filter::SCContentFilter;
+use screencapturekit::stream::cursor::{CursorShape, CursorTracker};
+use screencapturekit::stream::SCStream;
+
+fn bare_frame() -> CMSampleBuffer {
+    let pixels = CVPixelBuffer::create(16, 16, 0x4247_5241).expect("create BGRA pixel buffer");
+    CMSampleBuffer::create_for_image_buffer(&pixels, CMTime::ZERO, CMTime::ZERO)
+        .expect("wrap in sample buffer")
+}
+
+#[test]
+fn test_no_position_without_frame_geometry() {
+    let tracker = CursorTracker::new();
+    let update = tracker.update(&bare_frame());
+    assert!(update.position.is_none());
+}
+
+#[test]
+fn test_shape_sent_once_until_resent() {
+    let tracker = CursorTracker::new();
+    let frame = bare_frame();
+    let first = tracker.update(&frame);
+    if first.shape_seed == 0 {
+        println!("⚠ Skipping - no system cursor");
+        return;
+    }
+    if let Some(shape) = &first.shape {
+        assert_eq!(shape.seed(), first.shape_seed);
+        assert!(shape.scale() > 0.0);
+    }
+    let second = tracker.update(&frame);
+    if second.shape_seed == first.shape_seed {
+        assert!(second.shape.is_none());
+    }
+    tracker.resend_shape();
+    let third = tracker.update(&frame);
+    assert_eq!(third.shape.is_some(), CursorShape::current().is_some());
+}
+
+#[test]
+fn test_handler_hides_cursor() {
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - no screen recording permission");
+        return;
+    };
+    let Some(display) = content.displays().into_iter().next() else {
+        println!("⚠ No displays available");
+        return;
+    };
+    let filter = SCContentFilter::for_display(&display)
+        .with_excluding_windows(&[])
+        .build();
+    let config = SCStreamConfiguration::new().with_shows_cursor(true);
+    let mut stream = SCStream::new(&filter, &config);
+    let seen = Arc::new(Mutex::new(0usize));
+    let counter = Arc::clone(&seen);
+    let Some(_id) = stream.add_output_handler_with_cursor(move |_frame, _cursor| {
+        *counter.lock().unwrap() += 1;
+    }) else {
+        println!("⚠ Skipping - configuration could not be updated");
+        return;
+    };
+    assert!(!stream.configuration().shows_cursor());
+}
@@ -0,0 +1,22 @@
+//! Sample buffer attachment dump tests
+
+#![cfg(feature = "debug_attachments")]
+
+use screencapturekit::cm::{CMSampleBuffer, CMSampleBufferExt, CMSampleBufferSCExt, CMTime};
+use screencapturekit::cv::CVPixelBuffer;
+
+#[test]
+fn test_dump_has_no_frame_info_outside_screencapturekit() {
+    let pixels = CVPixelBuffer::create(16, 16, 0x4247_5241).expect("create BGRA pixel buffer");
+    let sample =
+        CMSampleBuffer::create_for_image_buffer(&pixels, CMTime::new(0, 60), CMTime::new(1, 60))
+            .expect("wrap in sample buffer");
+
+    let dump = sample.debug_attachments();
+    assert!(dump
+        .keys()
+        .all(|key| !key.starts_with("SCStreamUpdateFrame")));
+    assert!(dump
+        .iter()
+        .all(|(key, value)| !key.contains('\t') && !value.contains('\n')));
+}
@@ -7,7 +7,7 @@
 
 use screencapturekit::error::SCError;
 use screencapturekit::stream::delegate_trait::{
-    ErrorHandler, SCStreamDelegateTrait, StreamCallbacks,
+    ErrorHandler, SCStreamDelegateTrait, SCVideoEffect, StreamCallbacks,
 };
 use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 use std::sync::Arc;
@@ -246,6 +246,168 @@ fn test_stream_callbacks_on_video_effects() {
     assert_eq!(stop_count.load(Ordering::SeqCst), 1);
 }
 
+#[test]
+fn test_stream_callbacks_on_typed_video_effects() {
+    use std::sync::Mutex;
+
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let legacy_starts = Arc::new(AtomicU32::new(0));
+    let callbacks = {
+        let seen = Arc::clone(&seen);
+        let legacy_starts = Arc::clone(&legacy_starts);
+        StreamCallbacks::new()
+            .on_video_effect(move |effect, active| seen.lock().unwrap().push((effect, active)))
+            .on_video_effect_start(move || {
+                legacy_starts.fetch_add(1, Ordering::SeqCst);
+            })
+    };
+
+    callbacks.video_effect_did_start(SCVideoEffect::PresenterOverlay);
+    callbacks.video_effect_did_stop(SCVideoEffect::PresenterOverlay);
+
+    assert_eq!(
+        *seen.lock().unwrap(),
+        vec![
+            (SCVideoEffect::PresenterOverlay, true),
+            (SCVideoEffect::PresenterOverlay, false),
+        ]
+    );
+    // The untyped callbacks still fire.
+    assert_eq!(legacy_starts.load(Ordering::SeqCst), 1);
+    assert_eq!(
+        SCVideoEffect::PresenterOverlay.to_string(),
+        "Presenter Overlay"
+    );
+}
+
+#[test]
+fn test_typed_video_effect_defaults_to_legacy_callbacks() {
+    struct Legacy(Arc<AtomicU32>);
+    impl SCStreamDelegateTrait for Legacy {
+        fn output_video_effect_did_stop_for_stream(&self) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    let stops = Arc::new(AtomicU32::new(0));
+    let delegate = Legacy(Arc::clone(&stops));
+    delegate.video_effect_did_start(SCVideoEffect::PresenterOverlay);
+    delegate.video_effect_did_stop(SCVideoEffect::PresenterOverlay);
+    assert_eq!(stops.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_stream_callbacks_on_microphone_device_lost() {
+    use screencapturekit::audio_devices::AudioInputDevice;
+    use std::sync::Mutex;
+
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let seen_clone = Arc::clone(&seen);
+    let callbacks = StreamCallbacks::new().on_microphone_device_lost(move |lost, fallback| {
+        seen_clone
+            .lock()
+            .unwrap()
+            .push((lost.to_string(), fallback.map(|d| d.id.clone())));
+    });
+    assert!(format!("{callbacks:?}").contains("on_microphone_device_lost: true"));
+
+    let builtin = AudioInputDevice {
+        id: "BuiltInMicrophoneDevice".to_string(),
+        name: "MacBook Pro Microphone".to_string(),
+        is_default: true,
+    };
+    callbacks.microphone_device_lost("usb-mic", Some(&builtin));
+    callbacks.microphone_device_lost("usb-mic-2", None);
+
+    assert_eq!(
+        *seen.lock().unwrap(),
+        vec![
+            (
+                "usb-mic".to_string(),
+                Some("BuiltInMicrophoneDevice".to_string())
+            ),
+            ("usb-mic-2".to_string(), None),
+        ]
+    );
+
+    // The default trait method is a no-op
+    StreamCallbacks::new().microphone_device_lost("usb-mic", None);
+}
+
+#[test]
+fn test_stream_callbacks_on_state_change() {
+    use screencapturekit::stream::state::SCStreamState;
+    use std::sync::Mutex;
+
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let seen_clone = Arc::clone(&seen);
+    let callbacks = StreamCallbacks::new().on_state_change(move |old, new| {
+        seen_clone.lock().unwrap().push((old, new));
+    });
+    assert!(format!("{callbacks:?}").contains("on_state_change: true"));
+
+    callbacks.stream_state_did_change(SCStreamState::Running, SCStreamState::Failed);
+    assert_eq!(
+        *seen.lock().unwrap(),
+        vec![(SCStreamState::Running, SCStreamState::Failed)]
+    );
+}
+
+#[test]
+fn test_stream_callbacks_on_content_rect_change() {
+    use screencapturekit::cg::CGRect;
+    use screencapturekit::stream::delegate_trait::ContentRectChanged;
+    use std::sync::Mutex;
+
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let seen_clone = Arc::clone(&seen);
+    let callbacks = StreamCallbacks::new().on_content_rect_change(move |change| {
+        seen_clone.lock().unwrap().push(*change);
+    });
+    assert!(format!("{callbacks:?}").contains("on_content_rect_change: true"));
+
+    let change = ContentRectChanged {
+        content_rect: CGRect::new(0.0, 90.0, 1280.0, 540.0),
+        content_scale: 0.5,
+        scale_factor: 2.0,
+    };
+    assert_eq!(change.pixel_rect(), CGRect::new(0.0, 180.0, 2560.0, 1080.0));
+    callbacks.content_rect_did_change(&change);
+    assert_eq!(*seen.lock().unwrap(), vec![change]);
+}
+
+#[test]
+fn test_content_rect_of_frame_without_attachments() {
+    use screencapturekit::cm::{CMSampleBuffer, CMSampleBufferExt, CMTime};
+    use screencapturekit::cv::CVPixelBuffer;
+    use screencapturekit::stream::delegate_trait::ContentRectChanged;
+
+    let pixels = CVPixelBuffer::create(16, 16, 0x4247_5241).expect("create BGRA pixel buffer");
+    let sample = CMSampleBuffer::create_for_image_buffer(&pixels, CMTime::ZERO, CMTime::ZERO)
+        .expect("wrap in sample buffer");
+    assert_eq!(ContentRectChanged::of(&sample), None);
+}
+
+#[test]
+fn test_stream_state_predicates() {
+    use screencapturekit::stream::state::SCStreamState;
+
+    assert_eq!(SCStreamState::default(), SCStreamState::Idle);
+    for state in [
+        SCStreamState::Idle,
+        SCStreamState::Stopped,
+        SCStreamState::Failed,
+    ] {
+        assert!(state.can_start(), "{state}");
+    }
+    assert!(!SCStreamState::Running.can_start());
+    assert!(SCStreamState::Running.can_stop());
+    assert!(!SCStreamState::Idle.can_stop());
+    assert!(!SCStreamState::Stopping.can_update());
+    assert!(SCStreamState::Updating.is_capturing());
+    assert_eq!(SCStreamState::Updating.to_string(), "updating");
+}
+
 #[test]
 fn test_stream_callbacks_all_callbacks() {
     let stop_called = Arc::new(AtomicBool::new(false));
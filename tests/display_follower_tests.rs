@@ -0,0 +1,58 @@
+//! `DisplayFollower` tests
+
+use std::time::Duration;
+
+use screencapturekit::shareable_content::SCShareableContent;
+use screencapturekit::stream::configuration::SCStreamConfiguration;
+use screencapturekit::stream::content_filter::SCContentFilter;
+use screencapturekit::stream::display_follower::{DisplayFollower, DisplayMode};
+use screencapturekit::stream::SCStream;
+
+#[test]
+fn test_display_mode_of_unknown_display() {
+    assert!(DisplayMode::current(u32::MAX).is_none());
+}
+
+#[test]
+fn test_display_follower_tracks_mode_and_stops_on_drop() {
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - no screen recording permission");
+        return;
+    };
+    let Some(display) = content.displays().into_iter().next() else {
+        println!("⚠ No displays available");
+        return;
+    };
+
+    let mode = DisplayMode::current(display.display_id()).expect("online display has a mode");
+    assert_eq!(mode.point_width(), display.width());
+    assert!(mode.pixel_width() >= mode.point_width());
+    assert!(mode.scale_factor() >= 1.0);
+
+    let filter = SCContentFilter::for_display(&display)
+        .with_excluding_windows(&[])
+        .build();
+    let config = SCStreamConfiguration::new()
+        .with_width(mode.pixel_width())
+        .with_height(mode.pixel_height());
+    let stream = SCStream::new(&filter, &config);
+
+    let follower = DisplayFollower::start_with_handler(
+        &stream,
+        &display,
+        Duration::from_millis(50),
+        |resize| println!("resized to {}x{}", resize.width(), resize.height()),
+    )
+    .expect("spawn follower thread");
+    assert_eq!(follower.display_id(), display.display_id());
+    assert_eq!(follower.mode(), Some(mode));
+    std::thread::sleep(Duration::from_millis(200));
+    // Nothing changed the display meanwhile
+    assert_eq!(follower.update_count(), 0);
+    assert!(follower.last_error().is_none());
+    assert!(format!("{follower:?}").contains("DisplayFollower"));
+
+    let started = std::time::Instant::now();
+    drop(follower);
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
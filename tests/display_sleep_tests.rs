@@ -0,0 +1,101 @@
+//! Display sleep watcher tests
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use screencapturekit::cm::{CMSampleBuffer, CMSampleBufferExt, CMTime};
+use screencapturekit::cv::CVPixelBuffer;
+use screencapturekit::shareable_content::SCShareableContent;
+use screencapturekit::stream::configuration::SCStreamConfiguration;
+use screencapturekit::stream::content_filter::SCContentFilter;
+use screencapturekit::stream::display_sleep::{
+    DisplaySleepEvent, DisplaySleepOptions, InactiveReason, SleepPolicy,
+};
+use screencapturekit::stream::output_trait::SCStreamOutputTrait;
+use screencapturekit::stream::output_type::SCStreamOutputType;
+use screencapturekit::stream::{SCStream, StreamCallbacks};
+
+#[test]
+fn test_options_defaults_and_builders() {
+    let options = DisplaySleepOptions::default();
+    assert_eq!(options.policy(), SleepPolicy::Continue);
+    assert_eq!(options.display_id(), None);
+    assert_eq!(options.interval(), DisplaySleepOptions::DEFAULT_INTERVAL);
+
+    let options = DisplaySleepOptions::new(SleepPolicy::Placeholder)
+        .with_display_id(7)
+        .with_interval(Duration::from_millis(100));
+    assert_eq!(options.policy(), SleepPolicy::Placeholder);
+    assert_eq!(options.display_id(), Some(7));
+    assert_eq!(options.interval(), Duration::from_millis(100));
+}
+
+#[test]
+fn test_event_reason() {
+    let event = DisplaySleepEvent::Inactive(InactiveReason::ScreenLocked);
+    assert_eq!(event.reason(), InactiveReason::ScreenLocked);
+    assert_eq!(
+        DisplaySleepEvent::Active(InactiveReason::Screensaver).reason(),
+        InactiveReason::Screensaver
+    );
+    assert_eq!(InactiveReason::DisplaySleep.to_string(), "display asleep");
+}
+
+#[test]
+fn test_callbacks_register_display_sleep() {
+    let callbacks = StreamCallbacks::new().on_display_sleep(|_| {});
+    assert!(format!("{callbacks:?}").contains("on_display_sleep: true"));
+    assert!(format!("{:?}", StreamCallbacks::new()).contains("on_display_sleep: false"));
+}
+
+#[test]
+fn test_gate_forwards_while_active() {
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - no screen recording permission");
+        return;
+    };
+    let Some(display) = content.displays().into_iter().next() else {
+        println!("⚠ No displays available");
+        return;
+    };
+    let filter = SCContentFilter::for_display(&display)
+        .with_excluding_windows(&[])
+        .build();
+    let stream = SCStream::new(&filter, &SCStreamConfiguration::new());
+
+    let watcher = stream
+        .watch_display_sleep(
+            DisplaySleepOptions::new(SleepPolicy::Pause)
+                .with_display_id(display.display_id())
+                .with_interval(Duration::from_millis(50)),
+        )
+        .expect("spawn watcher thread");
+    std::thread::sleep(Duration::from_millis(150));
+    if watcher.is_inactive() {
+        println!(
+            "⚠ Skipping - display is inactive: {:?}",
+            watcher.inactive_reasons()
+        );
+        return;
+    }
+    assert!(watcher.inactive_reasons().is_empty());
+
+    let delivered = Arc::new(AtomicUsize::new(0));
+    let seen = Arc::clone(&delivered);
+    let gated = watcher.gate(move |_: CMSampleBuffer, _| {
+        seen.fetch_add(1, Ordering::SeqCst);
+    });
+    let pixels = CVPixelBuffer::create(16, 16, 0x4247_5241).expect("create BGRA pixel buffer");
+    let sample =
+        CMSampleBuffer::create_for_image_buffer(&pixels, CMTime::new(1, 60), CMTime::new(1, 60))
+            .expect("wrap in sample buffer");
+    gated.did_output_sample_buffer(sample, SCStreamOutputType::Screen);
+    assert_eq!(delivered.load(Ordering::SeqCst), 1);
+    assert_eq!(watcher.dropped_samples(), 0);
+    assert!(format!("{watcher:?}").contains("DisplaySleepWatcher"));
+
+    let started = std::time::Instant::now();
+    drop(watcher);
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
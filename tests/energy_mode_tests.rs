@@ -0,0 +1,133 @@
+//! Energy mode tests
+
+use std::time::Duration;
+
+use screencapturekit::shareable_content::SCShareableContent;
+use screencapturekit::stream::configuration::SCStreamConfiguration;
+use screencapturekit::stream::content_filter::SCContentFilter;
+use screencapturekit::stream::energy_mode::{
+    EnergyMode, EnergyModeController, LowPowerPolicy, PowerState, ThermalState,
+};
+use screencapturekit::stream::SCStream;
+
+#[test]
+fn test_policy_defaults_and_builders() {
+    let policy = LowPowerPolicy::default();
+    assert!((policy.frame_rate() - 15.0).abs() < f64::EPSILON);
+    assert_eq!(policy.queue_depth(), 3);
+    assert!(policy.on_battery());
+    assert!(policy.low_power_mode());
+    assert_eq!(policy.thermal_threshold(), Some(ThermalState::Serious));
+
+    let policy = LowPowerPolicy::new()
+        .with_frame_rate(5.0)
+        .with_queue_depth(2)
+        .with_on_battery(false)
+        .with_low_power_mode(false)
+        .with_thermal_threshold(None);
+    assert!((policy.frame_rate() - 5.0).abs() < f64::EPSILON);
+    assert_eq!(policy.queue_depth(), 2);
+    assert!(!policy.on_battery());
+    assert!(!policy.low_power_mode());
+    assert_eq!(policy.thermal_threshold(), None);
+}
+
+#[test]
+fn test_policy_mode_for_conditions() {
+    let policy = LowPowerPolicy::new();
+    let calm = PowerState::default();
+    assert_eq!(policy.mode_for(&calm), EnergyMode::Normal);
+
+    let battery = PowerState {
+        on_battery: true,
+        ..calm
+    };
+    assert_eq!(policy.mode_for(&battery), EnergyMode::LowPower);
+    assert_eq!(
+        policy.with_on_battery(false).mode_for(&battery),
+        EnergyMode::Normal
+    );
+
+    let low_power = PowerState {
+        low_power_mode: true,
+        ..calm
+    };
+    assert_eq!(policy.mode_for(&low_power), EnergyMode::LowPower);
+
+    let fair = PowerState {
+        thermal_state: ThermalState::Fair,
+        ..calm
+    };
+    let critical = PowerState {
+        thermal_state: ThermalState::Critical,
+        ..calm
+    };
+    assert_eq!(policy.mode_for(&fair), EnergyMode::Normal);
+    assert_eq!(policy.mode_for(&critical), EnergyMode::LowPower);
+    assert_eq!(
+        policy.with_thermal_threshold(None).mode_for(&critical),
+        EnergyMode::Normal
+    );
+    assert_eq!(
+        policy
+            .with_thermal_threshold(Some(ThermalState::Fair))
+            .mode_for(&fair),
+        EnergyMode::LowPower
+    );
+}
+
+#[test]
+fn test_power_state_is_readable() {
+    let power = PowerState::current();
+    println!("power state: {power:?}");
+    assert!(ThermalState::current() <= ThermalState::Critical);
+}
+
+#[test]
+fn test_forced_low_power_reduces_and_restores_stream() {
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - no screen recording permission");
+        return;
+    };
+    let Some(display) = content.displays().into_iter().next() else {
+        println!("⚠ No displays available");
+        return;
+    };
+
+    let filter = SCContentFilter::for_display(&display)
+        .with_excluding_windows(&[])
+        .build();
+    let config = SCStreamConfiguration::new()
+        .with_width(640)
+        .with_height(360)
+        .with_fps(60)
+        .with_queue_depth(8);
+    let stream = SCStream::new(&filter, &config);
+
+    // Follow nothing but the forced mode.
+    let policy = LowPowerPolicy::new()
+        .with_on_battery(false)
+        .with_low_power_mode(false)
+        .with_thermal_threshold(None);
+    let controller = EnergyModeController::start(&stream, policy, Duration::from_millis(50))
+        .expect("spawn controller thread");
+    controller.set_forced_mode(Some(EnergyMode::LowPower));
+    assert_eq!(controller.forced_mode(), Some(EnergyMode::LowPower));
+    std::thread::sleep(Duration::from_millis(300));
+
+    if let Some(error) = controller.last_error() {
+        println!("⚠ Skipping - stream could not be reconfigured: {error}");
+        return;
+    }
+    assert_eq!(controller.mode(), EnergyMode::LowPower);
+    assert_eq!(controller.switch_count(), 1);
+    assert_eq!(stream.configuration().fps(), 15);
+    assert_eq!(stream.configuration().queue_depth(), 3);
+
+    controller.set_forced_mode(Some(EnergyMode::Normal));
+    std::thread::sleep(Duration::from_millis(300));
+    assert_eq!(controller.mode(), EnergyMode::Normal);
+    assert_eq!(stream.configuration().fps(), 60);
+    assert_eq!(stream.configuration().queue_depth(), 8);
+    assert!(format!("{controller:?}").contains("EnergyModeController"));
+}
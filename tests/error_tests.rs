@@ -2,7 +2,13 @@
 //!
 //! Tests for error types and error handling
 
-use screencapturekit::error::SCError;
+use std::time::Duration;
+
+use screencapturekit::error::{
+    CaptureStartDiagnostics, NSErrorInfo, SCError, SCStreamErrorCode, SettingsPane,
+    SC_STREAM_ERROR_DOMAIN,
+};
+use screencapturekit::stream::state::SCStreamState;
 
 #[test]
 fn test_invalid_dimension_error() {
@@ -151,11 +157,22 @@ fn test_all_error_variants_display() {
         SCError::FFIError("test".to_string()),
         SCError::NullPointer("test".to_string()),
         SCError::Timeout("test".to_string()),
+        SCError::Cancelled("test".to_string()),
+        SCError::CaptureStartTimeout(CaptureStartDiagnostics::new(Duration::from_secs(1))),
+        SCError::InsufficientDiskSpace {
+            available: 1,
+            required: 2,
+        },
+        SCError::InvalidState {
+            operation: "stop capture".to_string(),
+            state: SCStreamState::Idle,
+        },
         SCError::InternalError("test".to_string()),
         SCError::OSError {
             code: 1,
             message: "test".to_string(),
         },
+        SCError::NSError(NSErrorInfo::new("NSCocoaErrorDomain", 1, "test")),
     ];
 
     for err in errors {
@@ -166,3 +183,140 @@ fn test_all_error_variants_display() {
         );
     }
 }
+
+#[test]
+fn test_from_ns_error_maps_stream_domain_to_stream_error() {
+    let info = NSErrorInfo::new(SC_STREAM_ERROR_DOMAIN, -3817, "The user stopped the stream")
+        .with_failure_reason("Stopped from the menu bar");
+    let err = SCError::from_ns_error(info);
+
+    assert_eq!(
+        err.stream_error_code(),
+        Some(SCStreamErrorCode::UserStopped)
+    );
+    assert!(err.ns_error().is_none());
+    assert!(err.to_string().contains("The user stopped the stream"));
+}
+
+#[test]
+fn test_from_ns_error_keeps_unknown_stream_codes_whole() {
+    let info = NSErrorInfo::new(SC_STREAM_ERROR_DOMAIN, -3999, "Future error");
+    let err = SCError::from_ns_error(info.clone());
+
+    assert_eq!(err.stream_error_code(), None);
+    assert_eq!(err.ns_error(), Some(&info));
+}
+
+#[test]
+fn test_from_ns_error_preserves_user_info() {
+    let info = NSErrorInfo::new("NSOSStatusErrorDomain", -54, "Permission error")
+        .with_failure_reason("TCC denied access")
+        .with_recovery_suggestion("Allow screen recording in System Settings")
+        .with_underlying(NSErrorInfo::new(
+            "NSPOSIXErrorDomain",
+            1,
+            "Operation not permitted",
+        ));
+    let err = SCError::from_ns_error(info);
+
+    let ns = err.ns_error().expect("non-stream domain stays an NSError");
+    assert!(ns.is("NSOSStatusErrorDomain", -54));
+    assert_eq!(ns.failure_reason.as_deref(), Some("TCC denied access"));
+    assert_eq!(
+        ns.recovery_suggestion.as_deref(),
+        Some("Allow screen recording in System Settings")
+    );
+
+    let chain: Vec<_> = ns.chain().map(|e| e.domain.as_str()).collect();
+    assert_eq!(chain, ["NSOSStatusErrorDomain", "NSPOSIXErrorDomain"]);
+
+    let display = err.to_string();
+    assert!(display.contains("-54"));
+    assert!(display.contains("TCC denied access"));
+}
+
+#[test]
+fn test_capture_start_timeout_display() {
+    let mut diagnostics = CaptureStartDiagnostics::new(Duration::from_millis(2500));
+    diagnostics.filter = "Display content".to_string();
+    diagnostics.output_handlers = 2;
+    let err = SCError::CaptureStartTimeout(diagnostics.clone());
+
+    let display = err.to_string();
+    assert!(display.contains("2.5s"));
+    assert!(display.contains("not granted"));
+    assert!(display.contains("Display content"));
+    assert!(display.contains("2 output handler"));
+
+    diagnostics.screen_capture_permitted = true;
+    diagnostics.filter.clear();
+    let display = diagnostics.to_string();
+    assert!(display.contains("granted"));
+    assert!(!display.contains("not granted"));
+    assert!(display.contains("filter: unknown"));
+}
+
+// MARK: - Recovery hints
+
+#[test]
+fn test_recovery_hint_permission_links_screen_recording() {
+    let hint = SCError::from_stream_error_code(SCStreamErrorCode::UserDeclined).recovery_hint();
+    assert_eq!(hint.settings_pane(), Some(SettingsPane::ScreenRecording));
+    assert_eq!(
+        hint.settings_url(),
+        Some("x-apple.systempreferences:com.apple.preference.security?Privacy_ScreenCapture")
+    );
+    assert!(!hint.is_retryable());
+}
+
+#[test]
+fn test_recovery_hint_permission_by_device() {
+    let camera = SCError::permission_denied("camera access was denied").recovery_hint();
+    assert_eq!(camera.settings_pane(), Some(SettingsPane::Camera));
+
+    let mic = SCError::from_stream_error_code(SCStreamErrorCode::FailedToStartMicrophoneCapture)
+        .recovery_hint();
+    assert_eq!(mic.settings_pane(), Some(SettingsPane::Microphone));
+}
+
+#[test]
+fn test_recovery_hint_feature_names_version() {
+    let hint = SCError::feature_not_available("App audio taps", "14.4").recovery_hint();
+    assert!(hint.message().contains("14.4"));
+    assert!(hint.to_string().contains("App audio taps"));
+    assert_eq!(hint.settings_pane(), None);
+}
+
+#[test]
+fn test_recovery_hint_retryable() {
+    assert!(SCError::Timeout("content query".into())
+        .recovery_hint()
+        .is_retryable());
+    assert!(!SCError::invalid_dimension("width", 0)
+        .recovery_hint()
+        .is_retryable());
+    let disk = SCError::InsufficientDiskSpace {
+        available: 1,
+        required: 2,
+    };
+    assert_eq!(
+        disk.recovery_hint().settings_pane(),
+        Some(SettingsPane::Storage)
+    );
+}
+
+#[test]
+fn test_recovery_hint_uses_ns_error_suggestion() {
+    let info = NSErrorInfo::new("NSPOSIXErrorDomain", 28, "No space left on device")
+        .with_recovery_suggestion("Delete old recordings");
+    let hint = SCError::from_ns_error(info).recovery_hint();
+    assert_eq!(hint.message(), "Delete old recordings");
+}
+
+#[test]
+fn test_recovery_hint_every_stream_code_has_message() {
+    for raw in -3821..=-3801 {
+        let code = SCStreamErrorCode::from_raw(raw).expect("known code");
+        assert!(!code.recovery_hint().message().is_empty(), "{code:?}");
+    }
+}
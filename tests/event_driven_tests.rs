@@ -0,0 +1,84 @@
+//! Event-driven output handler tests
+//!
+//! Synthetic sample buffers carry no `SCStreamFrameInfo`, so each one counts
+//! as a complete, fully changed frame.
+
+mod common;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use screencapturekit::cm::CMSampleBuffer;
+use screencapturekit::stream::event_driven::{EventDrivenOptions, EventDrivenOutputHandler};
+use screencapturekit::stream::output_trait::SCStreamOutputTrait;
+use screencapturekit::stream::output_type::SCStreamOutputType;
+
+use common::sample;
+
+fn counting(options: EventDrivenOptions) -> (EventDrivenOutputHandler, Arc<AtomicUsize>) {
+    let seen = Arc::new(AtomicUsize::new(0));
+    let handler = {
+        let seen = Arc::clone(&seen);
+        EventDrivenOutputHandler::new(
+            move |_sample: CMSampleBuffer, _of_type| {
+                seen.fetch_add(1, Ordering::SeqCst);
+            },
+            options,
+        )
+    };
+    (handler, seen)
+}
+
+#[test]
+fn test_options_builder() {
+    let options = EventDrivenOptions::new()
+        .with_min_dirty_fraction(0.25)
+        .with_keepalive(Duration::from_secs(2));
+    assert!((options.min_dirty_fraction() - 0.25).abs() < f64::EPSILON);
+    assert_eq!(options.keepalive(), Some(Duration::from_secs(2)));
+    assert_eq!(EventDrivenOptions::default(), EventDrivenOptions::new());
+}
+
+#[test]
+fn test_changed_frames_are_forwarded() {
+    let (handler, seen) = counting(EventDrivenOptions::new());
+    for frame in 0..3 {
+        handler.did_output_sample_buffer(sample(frame), SCStreamOutputType::Screen);
+    }
+    assert_eq!(seen.load(Ordering::SeqCst), 3);
+    assert_eq!(handler.forwarded_frames(), 3);
+    assert_eq!(handler.suppressed_frames(), 0);
+    assert_eq!(handler.keepalive_frames(), 0);
+}
+
+#[test]
+fn test_dirty_area_accumulates_until_threshold() {
+    // Each synthetic frame is fully dirty, so two are needed to reach 2.0.
+    let (handler, seen) = counting(
+        EventDrivenOptions::new()
+            .with_min_dirty_fraction(2.0)
+            .with_keepalive(Duration::from_secs(3600)),
+    );
+
+    // The first frame goes through as a keepalive: nothing was sent yet.
+    handler.did_output_sample_buffer(sample(0), SCStreamOutputType::Screen);
+    assert_eq!(handler.keepalive_frames(), 1);
+
+    handler.did_output_sample_buffer(sample(1), SCStreamOutputType::Screen);
+    assert_eq!(handler.suppressed_frames(), 1);
+
+    handler.did_output_sample_buffer(sample(2), SCStreamOutputType::Screen);
+    assert_eq!(seen.load(Ordering::SeqCst), 2);
+    assert_eq!(handler.forwarded_frames(), 2);
+    assert_eq!(handler.keepalive_frames(), 1);
+}
+
+#[test]
+fn test_non_screen_samples_pass_through() {
+    let (handler, seen) = counting(EventDrivenOptions::new().with_min_dirty_fraction(2.0));
+    handler.did_output_sample_buffer(sample(0), SCStreamOutputType::Audio);
+    assert_eq!(seen.load(Ordering::SeqCst), 1);
+    assert_eq!(handler.forwarded_frames(), 0);
+    assert!(format!("{handler:?}").contains("EventDrivenOutputHandler"));
+}
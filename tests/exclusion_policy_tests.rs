@@ -0,0 +1,91 @@
+//! `ExclusionPolicy` and `ExclusionWatcher` tests
+
+use std::time::Duration;
+
+use screencapturekit::error::SCError;
+use screencapturekit::shareable_content::SCShareableContent;
+use screencapturekit::stream::configuration::SCStreamConfiguration;
+use screencapturekit::stream::content_filter::SCContentFilter;
+use screencapturekit::stream::exclusion_policy::{ExclusionPolicy, ExclusionWatcher};
+use screencapturekit::stream::SCStream;
+
+#[test]
+fn test_policy_deduplicates_bundle_ids() {
+    let mut policy = ExclusionPolicy::new()
+        .with_bundle_id("com.example.a")
+        .with_bundle_id("com.example.b")
+        .with_bundle_id("com.example.a");
+    assert_eq!(policy.bundle_ids(), ["com.example.a", "com.example.b"]);
+    assert!(policy.contains("com.example.b"));
+
+    assert!(!policy.insert("com.example.b"));
+    assert!(policy.remove("com.example.a"));
+    assert!(!policy.remove("com.example.a"));
+    assert_eq!(policy.bundle_ids(), ["com.example.b"]);
+
+    assert!(ExclusionPolicy::default().is_empty());
+    assert!(!ExclusionPolicy::password_managers().is_empty());
+    assert!(ExclusionPolicy::password_managers().contains("com.bitwarden.desktop"));
+}
+
+#[test]
+fn test_policy_text_format() {
+    let policy: ExclusionPolicy =
+        "# private apps\n\ncom.example.a\n  com.example.b  \ncom.example.a\n"
+            .parse()
+            .expect("parse");
+    assert_eq!(policy.bundle_ids(), ["com.example.a", "com.example.b"]);
+    assert_eq!(policy.to_string(), "com.example.a\ncom.example.b\n");
+
+    assert!(matches!(
+        "com.example.a\nnot a bundle id\n".parse::<ExclusionPolicy>(),
+        Err(SCError::InvalidConfiguration(_))
+    ));
+}
+
+#[test]
+fn test_policy_save_and_load() {
+    let path = std::env::temp_dir().join(format!("sck-exclusions-{}.txt", std::process::id()));
+    let policy: ExclusionPolicy = ["com.example.a", "com.example.b"].into_iter().collect();
+
+    policy.save(&path).expect("save");
+    let loaded = ExclusionPolicy::load(&path).expect("load");
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(loaded, policy);
+
+    assert!(matches!(
+        ExclusionPolicy::load(&path),
+        Err(SCError::InternalError(_))
+    ));
+}
+
+#[test]
+fn test_display_filter_with_exclusion_policy() {
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - no screen recording permission");
+        return;
+    };
+    let Some(display) = content.displays().into_iter().next() else {
+        return;
+    };
+    let policy = ExclusionPolicy::password_managers().with_bundle_id("com.example.not-installed");
+    assert!(policy
+        .windows(&content)
+        .iter()
+        .all(|w| w.owning_application().is_some()));
+
+    let filter = SCContentFilter::for_display(&display)
+        .with_exclusion_policy(&policy)
+        .try_build()
+        .expect("build filter");
+    let stream = SCStream::new(&filter, &SCStreamConfiguration::default());
+
+    let watcher = ExclusionWatcher::start(&stream, &display, &policy, Duration::from_secs(3600))
+        .expect("spawn watcher thread");
+    assert_eq!(watcher.policy(), &policy);
+    assert!(format!("{watcher:?}").contains("ExclusionWatcher"));
+
+    let started = std::time::Instant::now();
+    drop(watcher);
+    assert!(started.elapsed() < Duration::from_secs(30));
+}
@@ -0,0 +1,115 @@
+//! Executor output handler tests
+//!
+//! These drive `ExecutorOutputHandler` directly with synthetic sample
+//! buffers, so they don't need screen-recording permission.
+
+mod common;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+
+use screencapturekit::cm::CMSampleBuffer;
+use screencapturekit::stream::executor_output::{
+    ExecutorOutputHandler, HandlerThread, SampleExecutor,
+};
+use screencapturekit::stream::output_trait::SCStreamOutputTrait;
+use screencapturekit::stream::output_type::SCStreamOutputType;
+
+use common::sample;
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// Holds jobs until the test runs them.
+#[derive(Default)]
+struct ManualExecutor {
+    jobs: Mutex<Vec<Job>>,
+}
+
+impl SampleExecutor for ManualExecutor {
+    fn execute(&self, job: Job) {
+        self.jobs.lock().unwrap().push(job);
+    }
+}
+
+#[test]
+fn test_executor_runs_handler_off_thread() {
+    let spawned: Arc<Mutex<Vec<JoinHandle<()>>>> = Arc::default();
+    let executor = {
+        let spawned = Arc::clone(&spawned);
+        move |job: Job| spawned.lock().unwrap().push(std::thread::spawn(job))
+    };
+    let caller = std::thread::current().id();
+    let off_thread = Arc::new(AtomicUsize::new(0));
+    let handler = {
+        let off_thread = Arc::clone(&off_thread);
+        ExecutorOutputHandler::new(
+            move |_sample: CMSampleBuffer, _of_type| {
+                if std::thread::current().id() != caller {
+                    off_thread.fetch_add(1, Ordering::SeqCst);
+                }
+            },
+            Arc::new(executor),
+            4,
+        )
+    };
+
+    for frame in 0..3 {
+        handler.did_output_sample_buffer(sample(frame), SCStreamOutputType::Screen);
+    }
+    for thread in spawned.lock().unwrap().drain(..) {
+        thread.join().unwrap();
+    }
+    assert_eq!(off_thread.load(Ordering::SeqCst), 3);
+    assert_eq!(handler.in_flight(), 0);
+    assert_eq!(handler.dropped_samples(), 0);
+}
+
+#[test]
+fn test_executor_drops_samples_over_the_in_flight_cap() {
+    let executor = Arc::new(ManualExecutor::default());
+    let processed = Arc::new(AtomicUsize::new(0));
+    let handler = {
+        let processed = Arc::clone(&processed);
+        ExecutorOutputHandler::new(
+            move |_sample: CMSampleBuffer, _of_type| {
+                processed.fetch_add(1, Ordering::SeqCst);
+            },
+            Arc::clone(&executor) as Arc<dyn SampleExecutor>,
+            2,
+        )
+    };
+
+    for frame in 0..5 {
+        handler.did_output_sample_buffer(sample(frame), SCStreamOutputType::Screen);
+    }
+    assert_eq!(handler.in_flight(), 2);
+    assert_eq!(handler.dropped_samples(), 3);
+
+    // Finishing a job frees its slot.
+    let jobs: Vec<Job> = executor.jobs.lock().unwrap().drain(..).collect();
+    for job in jobs {
+        job();
+    }
+    assert_eq!(processed.load(Ordering::SeqCst), 2);
+    assert_eq!(handler.in_flight(), 0);
+    handler.did_output_sample_buffer(sample(5), SCStreamOutputType::Screen);
+    assert_eq!(handler.in_flight(), 1);
+
+    // A job the executor discards unrun frees its slot too.
+    executor.jobs.lock().unwrap().clear();
+    assert_eq!(handler.in_flight(), 0);
+}
+
+#[test]
+fn test_zero_cap_and_debug() {
+    let handler = ExecutorOutputHandler::new(
+        |_sample: CMSampleBuffer, _of_type| {},
+        Arc::new(ManualExecutor::default()),
+        0,
+    );
+    assert_eq!(handler.max_in_flight(), 1);
+    assert!(format!("{handler:?}").contains("max_in_flight: 1"));
+    assert_eq!(format!("{:?}", HandlerThread::Pool(3)), "Pool(3)");
+    assert_eq!(format!("{:?}", HandlerThread::default()), "Callback");
+}
@@ -0,0 +1,89 @@
+//! Type-state filter builder tests
+//!
+//! `SCContentFilter::for_display` and `SCContentFilter::for_window` should
+//! build the same filters as the deprecated `SCContentFilter::create()`
+//! builder, whose own coverage stays in the other test files.
+
+use screencapturekit::error::SCError;
+use screencapturekit::shareable_content::SCShareableContent;
+use screencapturekit::stream::content_filter::{state, DisplayFilterBuilder, SCContentFilter};
+
+// Initialize CoreGraphics to prevent CGS_REQUIRE_INIT crashes in CI
+fn cg_init_for_headless_ci() {
+    extern "C" {
+        fn sc_initialize_core_graphics();
+    }
+    unsafe { sc_initialize_core_graphics() }
+}
+
+#[test]
+fn test_display_builder_states() {
+    cg_init_for_headless_ci();
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - no screen recording permission");
+        return;
+    };
+    let display = &content.displays()[0];
+
+    let whole: DisplayFilterBuilder<state::Unselected> = SCContentFilter::for_display(display);
+    assert!(format!("{whole:?}").contains("DisplayFilterBuilder"));
+    assert!(whole.try_build().is_ok());
+
+    let windows = content.windows();
+    let window_refs: Vec<&_> = windows.iter().take(2).collect();
+    let selected: DisplayFilterBuilder<state::Selected> =
+        SCContentFilter::for_display(display).with_including_windows(&window_refs);
+    assert!(format!("{:?}", selected.build()).contains("SCContentFilter"));
+
+    let apps = content.applications();
+    let app_refs: Vec<&_> = apps.iter().take(1).collect();
+    let filter = SCContentFilter::for_display(display)
+        .with_excluding_applications(&app_refs, &[])
+        .try_build();
+    assert!(filter.is_ok());
+}
+
+#[test]
+fn test_display_builder_application_state() {
+    cg_init_for_headless_ci();
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - no screen recording permission");
+        return;
+    };
+    let display = &content.displays()[0];
+
+    // Only `try_build` is offered once an application is selected by ID.
+    let builder: DisplayFilterBuilder<state::Application> =
+        SCContentFilter::for_display(display).with_application("com.example.not-installed");
+    assert!(matches!(
+        builder.try_build(),
+        Err(SCError::ApplicationNotFound(_))
+    ));
+}
+
+#[test]
+#[cfg(feature = "macos_14_0")]
+fn test_type_state_builders_build_same_filters_as_legacy_builder() {
+    cg_init_for_headless_ci();
+    let content = SCShareableContent::get().expect("Failed to get shareable content");
+    let display = &content.displays()[0];
+
+    // Selecting windows moves the builder to the `Selected` state.
+    let builder: DisplayFilterBuilder<state::Selected> =
+        SCContentFilter::for_display(display).with_excluding_windows(&[]);
+    let typed = builder.build();
+
+    #[allow(deprecated)]
+    let legacy = SCContentFilter::create()
+        .with_display(display)
+        .with_excluding_windows(&[])
+        .build();
+    assert_eq!(typed.style(), legacy.style());
+
+    if let Some(window) = content.windows().first() {
+        let typed = SCContentFilter::for_window(window).build();
+        #[allow(deprecated)]
+        let legacy = SCContentFilter::create().with_window(window).build();
+        assert_eq!(typed.style(), legacy.style());
+    }
+}
@@ -0,0 +1,71 @@
+//! `FilterPreset` tests
+
+use screencapturekit::error::SCError;
+use screencapturekit::shareable_content::SCShareableContent;
+use screencapturekit::stream::exclusion_policy::ExclusionPolicy;
+use screencapturekit::stream::filter_preset::{FilterPreset, PresetMode};
+
+#[test]
+fn test_builtin_presets() {
+    let browsers = FilterPreset::browser_windows();
+    assert_eq!(browsers.mode(), PresetMode::Include);
+    assert!(browsers.contains("com.apple.Safari"));
+    assert!(!browsers.matches_current_process());
+
+    let private = FilterPreset::exclude_password_managers();
+    assert_eq!(private.mode(), PresetMode::Exclude);
+    assert_eq!(
+        private.bundle_ids(),
+        ExclusionPolicy::password_managers().bundle_ids()
+    );
+
+    let own = FilterPreset::current_app_only();
+    assert_eq!(own.mode(), PresetMode::Include);
+    assert!(own.bundle_ids().is_empty());
+    assert!(own.matches_current_process());
+}
+
+#[test]
+fn test_presets_are_extendable() {
+    let preset = FilterPreset::browser_windows()
+        .with_bundle_id("com.example.browser")
+        .with_bundle_id("com.apple.Safari");
+    assert!(preset.contains("com.example.browser"));
+    let safari = preset
+        .bundle_ids()
+        .iter()
+        .filter(|id| *id == "com.apple.Safari")
+        .count();
+    assert_eq!(safari, 1);
+
+    let custom =
+        FilterPreset::excluding(["com.example.a", "com.example.b"]).with_current_process(true);
+    assert_eq!(custom.bundle_ids(), ["com.example.a", "com.example.b"]);
+    assert!(custom.matches_current_process());
+}
+
+#[test]
+fn test_resolve_against_content() {
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - no screen recording permission");
+        return;
+    };
+    let Some(display) = content.displays().into_iter().next() else {
+        return;
+    };
+
+    let missing = FilterPreset::including(["com.example.not-installed"]);
+    assert!(missing.applications(&content).is_empty());
+    assert!(matches!(
+        missing.resolve(&content, &display),
+        Err(SCError::ApplicationNotFound(_))
+    ));
+
+    // Excluding apps that aren't running still captures the display.
+    FilterPreset::excluding(["com.example.not-installed"])
+        .resolve(&content, &display)
+        .expect("display filter");
+    FilterPreset::exclude_password_managers()
+        .resolve(&content, &display)
+        .expect("display filter");
+}
@@ -0,0 +1,112 @@
+//! Packed and flipped frame copy tests
+
+use screencapturekit::cm::{CMSampleBuffer, CMSampleBufferExt, CMTime};
+use screencapturekit::cv::frame_copy::{FrameCopyExt, RowLayout, RowOrder};
+use screencapturekit::cv::{CVPixelBuffer, CVPixelBufferLockFlags};
+use screencapturekit::error::SCError;
+
+const BGRA: u32 = 0x4247_5241;
+
+/// A BGRA buffer whose first byte of each pixel is `x + y * 16`. An odd
+/// width leaves row padding in the source.
+fn gradient(width: usize, height: usize) -> CVPixelBuffer {
+    let buffer = CVPixelBuffer::create(width, height, BGRA).expect("create BGRA pixel buffer");
+    {
+        let mut guard = buffer
+            .lock(CVPixelBufferLockFlags::NONE)
+            .expect("lock for writing");
+        let stride = guard.bytes_per_row();
+        let base = guard.base_address_mut().expect("base address").cast::<u8>();
+        for y in 0..height {
+            for x in 0..width {
+                let value = u8::try_from(x + y * 16).unwrap();
+                unsafe { *base.add(y * stride + x * 4) = value };
+            }
+        }
+    }
+    buffer
+}
+
+fn first_bytes(pixels: &[u8]) -> Vec<u8> {
+    pixels.chunks(4).map(|pixel| pixel[0]).collect()
+}
+
+#[test]
+fn test_packed_data_strips_row_padding() {
+    let source = gradient(5, 3);
+    let pixels = source.packed_data(RowOrder::TopDown).expect("packed copy");
+    assert_eq!(pixels.len(), 5 * 3 * 4);
+    assert_eq!(
+        first_bytes(&pixels),
+        vec![0, 1, 2, 3, 4, 16, 17, 18, 19, 20, 32, 33, 34, 35, 36]
+    );
+}
+
+#[test]
+fn test_packed_data_flips_vertically() {
+    let source = gradient(5, 3);
+    let pixels = source
+        .packed_data(RowOrder::BottomUp)
+        .expect("flipped copy");
+    assert_eq!(
+        first_bytes(&pixels),
+        vec![32, 33, 34, 35, 36, 16, 17, 18, 19, 20, 0, 1, 2, 3, 4]
+    );
+}
+
+#[test]
+fn test_copy_packed_into_matches_packed_data() {
+    let source = gradient(7, 4);
+    let mut dest = vec![0; source.copy_size(RowLayout::Packed).unwrap() + 8];
+    let written = source
+        .copy_packed_into(&mut dest, RowOrder::BottomUp)
+        .expect("copy into larger buffer");
+    assert_eq!(written, 7 * 4 * 4);
+    assert_eq!(
+        dest[..written],
+        source.packed_data(RowOrder::BottomUp).unwrap()[..]
+    );
+}
+
+#[test]
+fn test_copy_packed_into_rejects_short_destination() {
+    let source = gradient(4, 4);
+    let mut dest = vec![0; 4 * 4 * 4 - 1];
+    assert!(matches!(
+        source.copy_packed_into(&mut dest, RowOrder::TopDown),
+        Err(SCError::InvalidDimension { .. })
+    ));
+}
+
+#[test]
+fn test_flipped_strided_copy() {
+    let source = gradient(4, 2);
+    let mut dest = vec![0xAA; 32 * 2];
+    let written = source
+        .copy_rows_into(&mut dest, RowLayout::Strided(32), RowOrder::BottomUp)
+        .expect("strided flipped copy");
+    assert_eq!(written, 64);
+    assert_eq!(first_bytes(&dest[..16]), vec![16, 17, 18, 19]);
+    assert_eq!(first_bytes(&dest[32..48]), vec![0, 1, 2, 3]);
+    assert!(dest[16..32].iter().all(|&byte| byte == 0xAA));
+}
+
+#[test]
+fn test_flipped_cg_image_copy() {
+    let image = CMSampleBuffer::create_for_image_buffer(
+        &gradient(3, 2),
+        CMTime::new(0, 60),
+        CMTime::new(1, 60),
+    )
+    .ok()
+    .and_then(|sample| sample.cg_image().ok());
+    let Some(image) = image else {
+        println!("⚠ Skipping - could not render CGImage");
+        return;
+    };
+    let top_down = image.packed_data(RowOrder::TopDown).expect("copy");
+    let bottom_up = image.packed_data(RowOrder::BottomUp).expect("flipped copy");
+    assert_eq!(top_down.len(), bottom_up.len());
+    assert_eq!(top_down[..12], bottom_up[12..]);
+    assert_eq!(top_down[12..], bottom_up[..12]);
+}
@@ -0,0 +1,63 @@
+//! `Frame` tests
+
+use screencapturekit::cm::{CMSampleBuffer, CMSampleBufferExt, CMTime};
+use screencapturekit::cv::frame_copy::{FrameCopyExt, RowLayout};
+use screencapturekit::cv::CVPixelBuffer;
+use screencapturekit::frame::{Frame, FrameOrigin};
+use screencapturekit::stream::configuration::PixelFormat;
+
+fn bgra_sample(width: usize, height: usize) -> CMSampleBuffer {
+    let pb = CVPixelBuffer::create(width, height, 0x4247_5241).expect("create BGRA pixel buffer");
+    CMSampleBuffer::create_for_image_buffer(&pb, CMTime::new(90, 60), CMTime::new(1, 60))
+        .expect("wrap in sample buffer")
+}
+
+#[test]
+fn test_frame_from_sample_buffer() {
+    let frame = Frame::from_sample_buffer(bgra_sample(32, 24)).expect("video sample");
+
+    assert_eq!(frame.origin(), FrameOrigin::Stream);
+    assert_eq!((frame.width(), frame.height()), (32, 24));
+    assert_eq!(frame.pixel_format(), PixelFormat::BGRA);
+    assert_eq!(frame.timestamp(), CMTime::new(90, 60));
+    assert!(frame.sample_buffer().is_some());
+    assert!(frame.pixel_buffer().is_some());
+    assert!(frame.image().is_none());
+
+    let size = frame.copy_size(RowLayout::Packed).expect("copy size");
+    assert_eq!(size, 32 * 24 * 4);
+    let mut dest = vec![0; size];
+    assert_eq!(frame.copy_into(&mut dest, RowLayout::Packed), Ok(size));
+}
+
+#[test]
+fn test_frame_from_screenshot_image() {
+    let image = bgra_sample(16, 8).cg_image().expect("cg_image");
+    let frame = Frame::from(image);
+
+    assert_eq!(frame.origin(), FrameOrigin::Screenshot);
+    assert_eq!((frame.width(), frame.height()), (16, 8));
+    assert_eq!(frame.pixel_format(), PixelFormat::BGRA);
+    assert!(frame.image().is_some());
+    assert!(frame.sample_buffer().is_none());
+    assert!(frame.timestamp().value > 0);
+
+    let mut dest = vec![0; 64 * 8];
+    assert_eq!(
+        frame.copy_into(&mut dest, RowLayout::Strided(64)),
+        Ok(64 * 8)
+    );
+    assert!(format!("{frame:?}").contains("Screenshot"));
+}
+
+#[test]
+fn test_frame_converts_to_cg_image() {
+    let frame = Frame::try_from(bgra_sample(20, 10)).expect("video sample");
+    let image = frame.into_cg_image().expect("convert");
+    assert_eq!((image.width(), image.height()), (20, 10));
+
+    let round_trip = Frame::from_screenshot(image)
+        .into_cg_image()
+        .expect("unwrap");
+    assert_eq!(round_trip.width(), 20);
+}
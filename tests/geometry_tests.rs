@@ -0,0 +1,99 @@
+//! Rectangle math and coordinate space tests
+
+use screencapturekit::cg::geometry::{cocoa_to_quartz, quartz_to_cocoa};
+use screencapturekit::cg::{CGPoint, CGPointExt, CGRect, CGRectExt, DisplaySpace};
+
+fn assert_rect(rect: CGRect, expected: [f64; 4]) {
+    let actual = [
+        rect.origin.x,
+        rect.origin.y,
+        rect.size.width,
+        rect.size.height,
+    ];
+    for (a, e) in actual.iter().zip(expected) {
+        assert!((a - e).abs() < 1e-9, "{actual:?} != {expected:?}");
+    }
+}
+
+#[test]
+fn test_intersection_and_union() {
+    let a = CGRect::new(0.0, 0.0, 100.0, 100.0);
+    let b = CGRect::new(50.0, -20.0, 100.0, 40.0);
+    assert_rect(a.intersection(&b).unwrap(), [50.0, 0.0, 50.0, 20.0]);
+    assert_rect(a.union(&b), [0.0, -20.0, 150.0, 120.0]);
+
+    // Touching edges don't overlap.
+    let c = CGRect::new(100.0, 0.0, 10.0, 10.0);
+    assert!(a.intersection(&c).is_none());
+    assert!(!a.intersects(&c));
+
+    // Empty rects are ignored by union.
+    assert_rect(
+        a.union(&CGRect::new(500.0, 500.0, 0.0, 0.0)),
+        [0.0, 0.0, 100.0, 100.0],
+    );
+}
+
+#[test]
+fn test_negative_sizes_are_standardized() {
+    let rect = CGRect::new(10.0, 10.0, -10.0, -5.0);
+    assert_rect(rect.standardized(), [0.0, 5.0, 10.0, 5.0]);
+    assert!(rect.contains_point(CGPoint::new(5.0, 7.0)));
+}
+
+#[test]
+fn test_contains() {
+    let rect = CGRect::new(-1920.0, 0.0, 1920.0, 1080.0);
+    assert!(rect.contains_point(CGPoint::new(-1920.0, 0.0)));
+    assert!(!rect.contains_point(CGPoint::new(0.0, 0.0)));
+    assert!(rect.contains_rect(&CGRect::new(-100.0, 10.0, 100.0, 10.0)));
+    assert!(!rect.contains_rect(&CGRect::new(-100.0, 10.0, 101.0, 10.0)));
+}
+
+#[test]
+fn test_scaling_integral_and_flip() {
+    let rect = CGRect::new(10.25, 20.5, 100.0, 50.0);
+    assert_rect(rect.scaled(2.0), [20.5, 41.0, 200.0, 100.0]);
+    assert_rect(rect.integral(), [10.0, 20.0, 101.0, 51.0]);
+    assert_rect(
+        CGRect::new(0.0, 0.0, 100.0, 50.0).flipped(1080.0),
+        [0.0, 1030.0, 100.0, 50.0],
+    );
+    let point = CGPoint::new(3.0, 4.0).scaled(2.0).offset_by(1.0, 1.0);
+    assert!((point.x - 7.0).abs() < 1e-9 && (point.y - 9.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_cocoa_quartz_round_trip() {
+    let quartz = CGRect::new(100.0, 100.0, 400.0, 300.0);
+    let cocoa = quartz_to_cocoa(quartz, 1080.0);
+    assert_rect(cocoa, [100.0, 680.0, 400.0, 300.0]);
+    assert_rect(cocoa_to_quartz(cocoa, 1080.0), [100.0, 100.0, 400.0, 300.0]);
+}
+
+#[test]
+fn test_display_space_conversions() {
+    // A Retina display to the left of the main display.
+    let display = DisplaySpace::new(CGRect::new(-1512.0, -200.0, 1512.0, 982.0), 2.0);
+    let window = CGRect::new(-1000.0, -100.0, 400.0, 300.0);
+
+    assert_rect(
+        display.global_to_local(window),
+        [512.0, 100.0, 400.0, 300.0],
+    );
+    let pixels = display.global_to_pixels(window);
+    assert_rect(pixels, [1024.0, 200.0, 800.0, 600.0]);
+    assert_rect(
+        display.pixels_to_global(pixels),
+        [-1000.0, -100.0, 400.0, 300.0],
+    );
+
+    let point = display.global_point_to_pixels(CGPoint::new(-1512.0, -200.0));
+    assert!(point.x.abs() < 1e-9 && point.y.abs() < 1e-9);
+    assert!(display.contains(CGPoint::new(-1.0, 0.0)));
+    assert!(!display.contains(CGPoint::new(0.0, 0.0)));
+
+    let size = display.pixel_size();
+    assert!((size.width - 3024.0).abs() < 1e-9 && (size.height - 1964.0).abs() < 1e-9);
+    assert!((DisplaySpace::new(window, 0.0).scale() - 1.0).abs() < 1e-9);
+}
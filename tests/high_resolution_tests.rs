@@ -0,0 +1,81 @@
+//! Downscaled capture and memory budget tests
+
+use screencapturekit::shareable_content::SCShareableContent;
+use screencapturekit::stream::configuration::high_resolution::{
+    fit_within, frame_bytes, queue_depth_for_budget, MAX_QUEUE_DEPTH, MIN_QUEUE_DEPTH,
+};
+use screencapturekit::stream::configuration::{PixelFormat, SCStreamConfiguration};
+
+#[test]
+fn test_fit_within_preserves_aspect() {
+    assert_eq!(fit_within(6016, 3384, 3840), (3840, 2160));
+    assert_eq!(fit_within(5120, 2880, 2560), (2560, 1440));
+    // Portrait displays cap the height.
+    assert_eq!(fit_within(3384, 6016, 1920), (1080, 1920));
+}
+
+#[test]
+fn test_fit_within_rounds_to_even() {
+    let (width, height) = fit_within(5120, 2160, 1001);
+    assert_eq!(width, 1000);
+    assert_eq!(height % 2, 0);
+    assert_eq!(height, 422);
+}
+
+#[test]
+fn test_fit_within_never_upscales() {
+    assert_eq!(fit_within(1920, 1080, 3840), (1920, 1080));
+    assert_eq!(fit_within(6016, 3384, 0), (6016, 3384));
+    assert_eq!(fit_within(3840, 2160, 3840), (3840, 2160));
+}
+
+#[test]
+fn test_frame_bytes_by_format() {
+    assert_eq!(frame_bytes(6016, 3384, PixelFormat::BGRA), 6016 * 3384 * 4);
+    assert_eq!(
+        frame_bytes(1920, 1080, PixelFormat::YCbCr_420v),
+        1920 * 1080 * 3 / 2
+    );
+    assert_eq!(frame_bytes(3, 3, PixelFormat::YCbCr_420f), 9 + 2 * 2 * 2);
+    assert_eq!(frame_bytes(10, 10, PixelFormat::RGhA), 800);
+}
+
+#[test]
+fn test_queue_depth_for_budget() {
+    let frame = frame_bytes(6016, 3384, PixelFormat::BGRA);
+    assert_eq!(queue_depth_for_budget(frame, frame * 5), 5);
+    assert_eq!(queue_depth_for_budget(frame, frame), MIN_QUEUE_DEPTH);
+    assert_eq!(queue_depth_for_budget(frame, u64::MAX), MAX_QUEUE_DEPTH);
+    assert_eq!(queue_depth_for_budget(0, 1024), MAX_QUEUE_DEPTH);
+}
+
+#[test]
+fn test_memory_budget_sets_queue_depth() {
+    let config = SCStreamConfiguration::new()
+        .with_width(3840)
+        .with_height(2160)
+        .with_pixel_format(PixelFormat::BGRA);
+    assert_eq!(config.estimated_frame_bytes(), 3840 * 2160 * 4);
+
+    let config = config.with_memory_budget(3840 * 2160 * 4 * 4);
+    assert_eq!(config.queue_depth(), 4);
+}
+
+#[test]
+fn test_display_size_capped() {
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - no screen recording permission");
+        return;
+    };
+    let Some(display) = content.displays().into_iter().next() else {
+        println!("⚠ No displays available");
+        return;
+    };
+
+    let config = SCStreamConfiguration::new().with_display_size_capped(&display, 1280);
+    assert!(config.width() <= 1280);
+    assert!(config.height() <= 1280);
+    assert!(config.width() > 0 && config.height() > 0);
+    assert!(config.scales_to_fit());
+    assert!(config.preserves_aspect_ratio());
+}
@@ -0,0 +1,81 @@
+//! ICC profile tests for screenshots
+
+#![cfg(feature = "macos_14_0")]
+
+use screencapturekit::cm::{CMSampleBuffer, CMSampleBufferExt, CMTime};
+use screencapturekit::cv::CVPixelBuffer;
+use screencapturekit::error::SCError;
+use screencapturekit::screenshot_manager::{CGImage, CGImageExt, ImageFormat, SCScreenshotManager};
+use screencapturekit::shareable_content::SCShareableContent;
+use screencapturekit::stream::configuration::SCStreamConfiguration;
+use screencapturekit::stream::content_filter::SCContentFilter;
+
+fn synthetic_image() -> CGImage {
+    let pb = CVPixelBuffer::create(16, 16, 0x4247_5241).expect("create BGRA pixel buffer");
+    let sb = CMSampleBuffer::create_for_image_buffer(&pb, CMTime::ZERO, CMTime::ZERO)
+        .expect("wrap in sample buffer");
+    sb.cg_image().expect("cg_image from sample buffer")
+}
+
+#[test]
+fn test_with_icc_profile_rejects_invalid_profiles() {
+    let image = synthetic_image();
+    assert!(matches!(
+        image.with_icc_profile(&[]),
+        Err(SCError::InvalidConfiguration(_))
+    ));
+    assert!(matches!(
+        image.with_icc_profile(b"not an icc profile"),
+        Err(SCError::InvalidConfiguration(_))
+    ));
+}
+
+#[test]
+fn test_icc_profile_round_trips_and_is_embedded() {
+    let image = synthetic_image();
+    let Some(profile) = image.icc_profile() else {
+        println!("⚠ Skipping - synthetic image has no ICC profile");
+        return;
+    };
+    // Every ICC profile carries the 'acsp' signature at offset 36.
+    assert!(profile.len() > 128);
+    assert_eq!(&profile[36..40], b"acsp");
+
+    let tagged = image.with_icc_profile(&profile).expect("retag");
+    assert_eq!((tagged.width(), tagged.height()), (16, 16));
+    assert_eq!(tagged.icc_profile().as_deref(), Some(profile.as_slice()));
+
+    let png = tagged.encode(ImageFormat::Png).expect("encode PNG");
+    assert!(
+        png.windows(4).any(|chunk| chunk == b"iCCP"),
+        "PNG has no embedded ICC profile"
+    );
+}
+
+#[test]
+fn test_capture_image_with_display_profile() {
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - no screen recording permission");
+        return;
+    };
+    let Some(display) = content.displays().into_iter().next() else {
+        return;
+    };
+    let display_profile = display.icc_profile();
+    if let Some(profile) = &display_profile {
+        assert_eq!(&profile[36..40], b"acsp");
+    }
+
+    let filter = SCContentFilter::for_display(&display)
+        .with_excluding_windows(&[])
+        .build();
+    let config = SCStreamConfiguration::new().with_width(64).with_height(64);
+    let Ok(image) =
+        SCScreenshotManager::capture_image_with_display_profile(&filter, &config, &display)
+    else {
+        return;
+    };
+    if display_profile.is_some() {
+        assert!(image.icc_profile().is_some());
+    }
+}
@@ -0,0 +1,67 @@
+//! Image sequence writer tests
+
+use std::path::PathBuf;
+
+use screencapturekit::error::SCError;
+use screencapturekit::image_sequence::{
+    ImageSequence, ImageSequenceStats, ImageSequenceWriter, SequenceFormat,
+};
+
+fn temp_dir(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("sck-image-sequence-{name}-{}", std::process::id()))
+}
+
+#[test]
+fn test_format_extensions() {
+    assert_eq!(SequenceFormat::Png.extension(), "png");
+    assert_eq!(SequenceFormat::Tiff16.extension(), "tiff");
+    assert_eq!(SequenceFormat::Exr.extension(), "exr");
+    assert_eq!(SequenceFormat::Heif10(0.9).extension(), "heic");
+    assert!(!SequenceFormat::Png.is_hdr());
+    assert!(SequenceFormat::Exr.is_hdr());
+    assert!(SequenceFormat::Heif10(0.9).is_hdr());
+}
+
+#[test]
+fn test_frame_paths() {
+    let sequence = ImageSequence::new("/tmp/plates", SequenceFormat::Exr);
+    assert_eq!(
+        sequence.path_for(sequence.first_number()),
+        PathBuf::from("/tmp/plates/frame_000001.exr")
+    );
+
+    let vfx = sequence
+        .with_prefix("")
+        .with_digits(4)
+        .with_first_number(1001);
+    assert_eq!(vfx.path_for(1001), PathBuf::from("/tmp/plates/1001.exr"));
+    assert_eq!(vfx.path_for(12345), PathBuf::from("/tmp/plates/12345.exr"));
+}
+
+#[test]
+fn test_sequence_validation() {
+    let invalid = [
+        ImageSequence::new("/tmp", SequenceFormat::Png).with_queue_capacity(0),
+        ImageSequence::new("/tmp", SequenceFormat::Png).with_prefix("a/b"),
+    ];
+    for sequence in invalid {
+        assert!(matches!(
+            sequence.validate(),
+            Err(SCError::InvalidConfiguration(_))
+        ));
+        assert!(ImageSequenceWriter::start(sequence).is_err());
+    }
+}
+
+#[test]
+fn test_writer_creates_directory_and_finishes_empty() {
+    let dir = temp_dir("empty").join("nested");
+    let writer = ImageSequenceWriter::start(ImageSequence::new(&dir, SequenceFormat::Png))
+        .expect("start writer");
+    assert!(dir.is_dir());
+    assert_eq!(writer.queued(), 0);
+    assert!(writer.last_error().is_none());
+    let _handler = writer.output_handler();
+    assert_eq!(writer.finish(), ImageSequenceStats::default());
+    let _ = std::fs::remove_dir_all(temp_dir("empty"));
+}
@@ -1,3 +1,6 @@
+// Exercises the deprecated `SCContentFilter::create()` builder on purpose.
+#![allow(deprecated)]
+
 use screencapturekit::cm::CMSampleBufferExt;
 use screencapturekit::{
     cv::CVPixelBufferLockFlags,
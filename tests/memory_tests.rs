@@ -8,6 +8,8 @@
 //! `cargo run --example 15_memory_leak_check`
 
 #![allow(clippy::items_after_statements)]
+// Exercises the deprecated `SCContentFilter::create()` builder on purpose.
+#![allow(deprecated)]
 
 use screencapturekit::prelude::*;
 use std::sync::atomic::{AtomicUsize, Ordering};
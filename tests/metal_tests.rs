@@ -267,6 +267,41 @@ mod metal_device_tests {
         assert!(debug_str.contains("MetalCommandQueue"));
     }
 
+    #[test]
+    fn test_command_buffer_holds_surface_until_completed() {
+        use screencapturekit::cm::IOSurface;
+        use screencapturekit::metal::IOSurfaceMetalExt;
+        use std::sync::mpsc;
+
+        let device = MetalDevice::system_default().expect("No Metal device");
+        let queue = device.create_command_queue().expect("No command queue");
+        let surface = IOSurface::create(64, 64, 0x42475241, 4).expect("Failed to create IOSurface");
+        let textures = surface
+            .create_metal_textures(&device)
+            .expect("Failed to create textures");
+
+        let cmd_buffer = queue.command_buffer().expect("No command buffer");
+        cmd_buffer.hold_textures_until_completed(&textures);
+        assert!(
+            surface.is_in_use(),
+            "surface should be held until completion"
+        );
+
+        let (tx, rx) = mpsc::channel();
+        cmd_buffer.add_completed_handler(move || {
+            let _ = tx.send(());
+        });
+        cmd_buffer.commit();
+        cmd_buffer.wait_until_completed();
+
+        rx.recv_timeout(std::time::Duration::from_secs(5))
+            .expect("completed handler should run");
+        assert!(
+            !surface.is_in_use(),
+            "hold should be released on completion"
+        );
+    }
+
     #[test]
     fn test_metal_library_creation() {
         use screencapturekit::metal::SHADER_SOURCE;
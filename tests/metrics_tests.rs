@@ -0,0 +1,128 @@
+//! Metrics instrumentation tests
+
+#![cfg(feature = "metrics")]
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use metrics::{
+    with_local_recorder, Counter, Gauge, Histogram, Key, KeyName, Metadata, Recorder, SharedString,
+    Unit,
+};
+use screencapturekit::cm::{CMSampleBuffer, CMSampleBufferExt, CMTime};
+use screencapturekit::cv::{l10r, CVPixelBuffer};
+use screencapturekit::metrics::{
+    BUFFER_COPIES_TOTAL, BUFFER_COPY_BYTES_TOTAL, OUTPUT_POOL_QUEUE_DEPTH,
+    OUTPUT_POOL_SAMPLES_TOTAL,
+};
+use screencapturekit::stream::output_trait::SCStreamOutputTrait;
+use screencapturekit::stream::output_type::SCStreamOutputType;
+use screencapturekit::stream::pooled_output::PooledOutputHandler;
+
+/// Records counters and gauges as `name{label=value,...}` → value.
+#[derive(Default)]
+struct TestRecorder {
+    values: Mutex<HashMap<String, Arc<AtomicU64>>>,
+}
+
+impl TestRecorder {
+    fn slot(&self, key: &Key) -> Arc<AtomicU64> {
+        let labels: Vec<String> = key
+            .labels()
+            .map(|l| format!("{}={}", l.key(), l.value()))
+            .collect();
+        let name = format!("{}{{{}}}", key.name(), labels.join(","));
+        Arc::clone(self.values.lock().unwrap().entry(name).or_default())
+    }
+
+    fn counter(&self, name: &str) -> u64 {
+        self.values
+            .lock()
+            .unwrap()
+            .get(name)
+            .map_or(0, |v| v.load(Ordering::SeqCst))
+    }
+
+    fn gauge(&self, name: &str) -> f64 {
+        f64::from_bits(self.counter(name))
+    }
+}
+
+impl Recorder for TestRecorder {
+    fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+    fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+    fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+    fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+        Counter::from_arc(self.slot(key))
+    }
+
+    fn register_gauge(&self, key: &Key, _: &Metadata<'_>) -> Gauge {
+        Gauge::from_arc(self.slot(key))
+    }
+
+    fn register_histogram(&self, _: &Key, _: &Metadata<'_>) -> Histogram {
+        Histogram::noop()
+    }
+}
+
+#[test]
+fn test_metrics_count_l10r_copies() {
+    let recorder = TestRecorder::default();
+    let data = vec![0u8; 4 * 4 * 2];
+    with_local_recorder(&recorder, || {
+        screencapturekit::metrics::describe();
+        l10r::to_rgba16(&data, 4, 2, 16).unwrap();
+        l10r::to_rgba_f32(&data, 4, 2, 16).unwrap();
+    });
+
+    assert_eq!(
+        recorder.counter(&format!("{BUFFER_COPIES_TOTAL}{{component=l10r}}")),
+        2
+    );
+    assert_eq!(
+        recorder.counter(&format!("{BUFFER_COPY_BYTES_TOTAL}{{component=l10r}}")),
+        64
+    );
+}
+
+#[test]
+fn test_metrics_count_output_pool_hits_and_misses() {
+    let recorder = TestRecorder::default();
+    let gate = Arc::new(Mutex::new(()));
+    let held = gate.lock().unwrap();
+
+    let pool = {
+        let gate = Arc::clone(&gate);
+        PooledOutputHandler::new(
+            move |_sample: CMSampleBuffer, _of_type| {
+                drop(gate.lock());
+            },
+            1,
+        )
+        .expect("spawn output workers")
+    };
+    let buffer = CVPixelBuffer::create(8, 8, 0x4247_5241).unwrap();
+    with_local_recorder(&recorder, || {
+        for frame in 0..6 {
+            let sample = CMSampleBuffer::create_for_image_buffer(
+                &buffer,
+                CMTime::new(frame, 60),
+                CMTime::new(1, 60),
+            )
+            .unwrap();
+            pool.did_output_sample_buffer(sample, SCStreamOutputType::Screen);
+        }
+    });
+    drop(held);
+    let dropped = pool.dropped_samples();
+    drop(pool);
+
+    let queued = recorder.counter(&format!("{OUTPUT_POOL_SAMPLES_TOTAL}{{result=queued}}"));
+    let misses = recorder.counter(&format!("{OUTPUT_POOL_SAMPLES_TOTAL}{{result=dropped}}"));
+    assert_eq!(queued + misses, 6);
+    assert_eq!(misses, dropped);
+    assert!(misses > 0);
+    assert!(recorder.gauge(&format!("{OUTPUT_POOL_QUEUE_DEPTH}{{}}")) <= 2.0);
+}
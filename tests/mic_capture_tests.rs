@@ -0,0 +1,38 @@
+//! Microphone-only capture tests
+
+use screencapturekit::stream::mic_capture::MicCapture;
+
+#[test]
+fn test_mic_capture_default_configuration() {
+    let config = MicCapture::builder().configuration();
+
+    assert!(config.captures_microphone());
+    assert!(!config.captures_audio());
+    assert_eq!(config.width(), 2);
+    assert_eq!(config.height(), 2);
+    assert_eq!(config.sample_rate(), 48_000);
+    assert_eq!(config.channel_count(), 1);
+    assert_eq!(config.microphone_capture_device_id(), None);
+}
+
+#[test]
+fn test_mic_capture_builder_options() {
+    let config = MicCapture::builder()
+        .with_device_id("BuiltInMicrophoneDevice")
+        .with_sample_rate(24_000)
+        .with_channel_count(2)
+        .configuration();
+
+    assert_eq!(config.sample_rate(), 24_000);
+    assert_eq!(config.channel_count(), 2);
+    assert_eq!(
+        config.microphone_capture_device_id().as_deref(),
+        Some("BuiltInMicrophoneDevice")
+    );
+}
+
+#[test]
+fn test_mic_capture_rejects_empty_chunks() {
+    let result = MicCapture::builder().with_frames_per_chunk(0).start(|_| {});
+    assert!(result.is_err());
+}
@@ -0,0 +1,133 @@
+//! Muxer tests
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use screencapturekit::muxer::{AudioTrackSettings, MuxerVideoCodec, SCMuxer, VideoTrackSettings};
+use screencapturekit::prelude::*;
+
+fn temp_path(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("sck-muxer-{}-{name}", std::process::id()))
+}
+
+#[test]
+fn test_track_settings_builders() {
+    let video = VideoTrackSettings::new(1280, 720)
+        .with_codec(MuxerVideoCodec::Hevc)
+        .with_bitrate(4_000_000)
+        .with_real_time(false);
+    assert_eq!(video.size(), (1280, 720));
+    assert_eq!(video.codec(), MuxerVideoCodec::Hevc);
+    assert_eq!(video.bitrate(), 4_000_000);
+    assert!(!video.real_time());
+    assert!(VideoTrackSettings::new(1, 1).real_time());
+
+    let audio = AudioTrackSettings::new(48_000, 2).with_bitrate(128_000);
+    assert_eq!(audio.sample_rate(), 48_000);
+    assert_eq!(audio.channel_count(), 2);
+    assert_eq!(audio.bitrate(), 128_000);
+    assert!(audio.real_time());
+}
+
+#[test]
+fn test_muxer_rejects_invalid_input() {
+    assert!(matches!(
+        SCMuxer::new("/tmp/bad\0path.mp4"),
+        Err(SCError::InvalidConfiguration(_))
+    ));
+
+    let path = temp_path("invalid.mp4");
+    let muxer = SCMuxer::new(&path).expect("create muxer");
+    assert_eq!(muxer.path(), path);
+    assert!(matches!(
+        muxer.add_video_track(&VideoTrackSettings::new(0, 720)),
+        Err(SCError::InvalidDimension { .. })
+    ));
+    assert!(matches!(
+        muxer.add_audio_track(&AudioTrackSettings::new(48_000, 0)),
+        Err(SCError::InvalidDimension { .. })
+    ));
+
+    let video = muxer
+        .add_video_track(&VideoTrackSettings::new(640, 360))
+        .expect("add video track");
+    let audio = muxer
+        .add_audio_track(&AudioTrackSettings::new(48_000, 2))
+        .expect("add audio track");
+    assert_eq!(video.index(), 0);
+    assert_eq!(audio.index(), 1);
+
+    // Nothing appended, so there is no file to finish.
+    assert!(muxer.finish().is_err());
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn test_muxer_send_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<SCMuxer>();
+}
+
+#[test]
+fn test_muxer_writes_captured_frames() {
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - no screen recording permission");
+        return;
+    };
+    let Some(display) = content.displays().into_iter().next() else {
+        println!("⚠ No displays available");
+        return;
+    };
+
+    let filter = SCContentFilter::for_display(&display)
+        .with_excluding_windows(&[])
+        .build();
+    let config = SCStreamConfiguration::new()
+        .with_width(640)
+        .with_height(360)
+        .with_frame_rate(30.0);
+
+    let path = temp_path("capture.mov");
+    let muxer = SCMuxer::new(&path).expect("create muxer");
+    let track = muxer
+        .add_video_track(&VideoTrackSettings::new(640, 360))
+        .expect("add video track");
+    let muxer = Arc::new(Mutex::new(Some(muxer)));
+    let errors = Arc::new(Mutex::new(Vec::new()));
+
+    let mut stream = SCStream::new(&filter, &config);
+    let sink = Arc::clone(&muxer);
+    let sink_errors = Arc::clone(&errors);
+    stream.add_output_handler(
+        move |sample: CMSampleBuffer, _| {
+            if let Some(muxer) = sink.lock().unwrap().as_ref() {
+                if let Err(error) = muxer.append(track, &sample) {
+                    sink_errors.lock().unwrap().push(error);
+                }
+            }
+        },
+        SCStreamOutputType::Screen,
+    );
+    if stream.start_capture().is_err() {
+        println!("⚠ Skipping - capture could not start");
+        return;
+    }
+    std::thread::sleep(Duration::from_secs(2));
+    let _ = stream.stop_capture();
+    drop(stream);
+
+    assert!(
+        errors.lock().unwrap().is_empty(),
+        "{:?}",
+        errors.lock().unwrap()
+    );
+    let muxer = muxer.lock().unwrap().take().expect("muxer still present");
+    match muxer.finish() {
+        Ok(()) => {
+            assert!(std::fs::metadata(&path).is_ok_and(|m| m.len() > 0));
+        }
+        // A static screen may deliver only idle frames.
+        Err(error) => println!("⚠ No frames written: {error}"),
+    }
+    let _ = std::fs::remove_file(&path);
+}
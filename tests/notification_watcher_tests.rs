@@ -0,0 +1,100 @@
+//! Notification watcher tests
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use screencapturekit::cg::CGRect;
+use screencapturekit::region_mask::{MaskSpace, RegionMask, SCRegionMasker};
+use screencapturekit::shareable_content::notification_watcher::{
+    NotificationBanner, NotificationEvent, NotificationWatcher,
+};
+
+const fn banner(window_id: u32, y: f64) -> NotificationBanner {
+    NotificationBanner {
+        window_id,
+        frame: CGRect::new(1100.0, y, 344.0, 64.0),
+    }
+}
+
+#[test]
+fn test_diff_reports_changes() {
+    let old = [banner(1, 40.0), banner(2, 120.0)];
+    let new = [banner(3, 40.0), banner(1, 120.0)];
+    let events = NotificationEvent::diff(&old, &new);
+    assert_eq!(
+        events,
+        vec![
+            NotificationEvent::Disappeared(banner(2, 120.0)),
+            NotificationEvent::Appeared(banner(3, 40.0)),
+            NotificationEvent::Moved {
+                window_id: 1,
+                old: banner(1, 40.0).frame,
+                new: banner(1, 120.0).frame,
+            },
+        ]
+    );
+    let ids: Vec<u32> = events.iter().map(NotificationEvent::window_id).collect();
+    assert_eq!(ids, vec![2, 3, 1]);
+
+    assert!(NotificationEvent::diff(&new, &new).is_empty());
+    assert!(NotificationEvent::diff(&[], &[]).is_empty());
+}
+
+#[test]
+fn test_mask_region_covers_slide_in() {
+    let region = banner(1, 40.0).mask_region();
+    let frame = banner(1, 40.0).frame;
+    assert!(region.origin.x < frame.origin.x);
+    assert!(region.origin.y < frame.origin.y);
+    // Stretched right by the banner's width for the animation.
+    assert!(region.origin.x + region.size.width > frame.size.width.mul_add(2.0, frame.origin.x));
+    assert!(region.origin.y + region.size.height > frame.origin.y + frame.size.height);
+}
+
+#[test]
+fn test_visible_banners_have_area() {
+    for banner in NotificationBanner::visible() {
+        assert!(banner.frame.size.width > 0.0);
+        assert!(banner.frame.size.height > 0.0);
+    }
+}
+
+#[test]
+fn test_watcher_starts_and_stops() {
+    let watcher = NotificationWatcher::start(Duration::from_millis(10), |event| {
+        println!("{event:?}");
+    })
+    .expect("spawn watcher thread");
+    std::thread::sleep(Duration::from_millis(50));
+    assert_eq!(watcher.is_showing(), !watcher.banners().is_empty());
+    println!("{watcher:?}");
+    drop(watcher);
+}
+
+#[test]
+fn test_masking_watcher_clears_extra_regions() {
+    let masker = Arc::new(SCRegionMasker::new(RegionMask::default()).expect("create masker"));
+    masker
+        .set_extra_regions(vec![CGRect::new(0.0, 0.0, 10.0, 10.0)])
+        .expect("valid regions");
+    assert_eq!(masker.extra_regions().len(), 1);
+
+    let watcher =
+        NotificationWatcher::start_masking(Arc::clone(&masker), Duration::from_millis(10), |_| {})
+            .expect("spawn watcher thread");
+    std::thread::sleep(Duration::from_millis(50));
+    drop(watcher);
+    assert!(masker.extra_regions().is_empty());
+}
+
+#[test]
+fn test_extra_regions_are_validated() {
+    let masker = SCRegionMasker::new(RegionMask::new(MaskSpace::Pixels)).expect("create masker");
+    assert!(masker
+        .set_extra_regions(vec![CGRect::new(0.0, 0.0, f64::NAN, 10.0)])
+        .is_err());
+    assert!(masker
+        .set_extra_regions(vec![CGRect::new(0.0, 0.0, 0.0, 10.0)])
+        .is_err());
+    assert!(masker.extra_regions().is_empty());
+}
@@ -0,0 +1,18 @@
+//! OpenGL texture cache tests
+
+#![cfg(feature = "opengl")]
+
+use screencapturekit::error::SCError;
+use screencapturekit::opengl::{OpenGLTextureCache, GL_TEXTURE_2D, GL_TEXTURE_RECTANGLE};
+
+#[test]
+fn test_texture_targets() {
+    assert_eq!(GL_TEXTURE_2D, 0x0DE1);
+    assert_eq!(GL_TEXTURE_RECTANGLE, 0x84F5);
+}
+
+#[test]
+fn test_cache_rejects_null_context() {
+    let result = unsafe { OpenGLTextureCache::new(std::ptr::null_mut(), std::ptr::null_mut()) };
+    assert!(matches!(result, Err(SCError::InvalidConfiguration(_))));
+}
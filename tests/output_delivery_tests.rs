@@ -0,0 +1,62 @@
+//! Sample delivery mode tests
+//!
+//! Drive output handlers directly with synthetic sample buffers to check
+//! which entry point each delivery mode uses.
+
+mod common;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use screencapturekit::cm::{CMSampleBuffer, CMSampleBufferExt, CMTime};
+use screencapturekit::stream::output_trait::{
+    BorrowedOutputHandler, SCStreamOutputTrait, SampleDelivery,
+};
+use screencapturekit::stream::output_type::SCStreamOutputType;
+
+use common::sample;
+
+#[test]
+fn test_closure_handlers_default_to_retained_delivery() {
+    let handler = |_sample: CMSampleBuffer, _of_type: SCStreamOutputType| {};
+    assert_eq!(handler.delivery(), SampleDelivery::Retained);
+    assert_eq!(SampleDelivery::default(), SampleDelivery::Retained);
+}
+
+#[test]
+fn test_borrowed_handler_is_lent_the_callers_buffer() {
+    let seen = Arc::new(AtomicUsize::new(0));
+    let handler = {
+        let seen = Arc::clone(&seen);
+        BorrowedOutputHandler::new(move |sample: &CMSampleBuffer, of_type| {
+            assert_eq!(of_type, SCStreamOutputType::Screen);
+            assert_eq!(sample.presentation_timestamp(), CMTime::new(5, 60));
+            seen.store(sample.as_ptr() as usize, Ordering::SeqCst);
+        })
+    };
+    assert_eq!(handler.delivery(), SampleDelivery::Borrowed);
+
+    let buffer = sample(5);
+    handler.did_output_sample_buffer_borrowed(&buffer, SCStreamOutputType::Screen);
+    assert_eq!(seen.load(Ordering::SeqCst), buffer.as_ptr() as usize);
+    // The caller still owns the buffer after a borrowed delivery.
+    assert!(buffer.image_buffer().is_some());
+}
+
+#[test]
+fn test_retained_handler_called_through_borrowed_entry_point_gets_a_clone() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let handler = {
+        let calls = Arc::clone(&calls);
+        move |sample: CMSampleBuffer, _of_type: SCStreamOutputType| {
+            assert_eq!(sample.presentation_timestamp(), CMTime::new(5, 60));
+            calls.fetch_add(1, Ordering::SeqCst);
+        }
+    };
+
+    let buffer = sample(5);
+    handler.did_output_sample_buffer_borrowed(&buffer, SCStreamOutputType::Screen);
+    handler.did_output_sample_buffer_borrowed(&buffer, SCStreamOutputType::Screen);
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+    assert!(buffer.image_buffer().is_some());
+}
@@ -0,0 +1,121 @@
+//! Permission revocation tests
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use screencapturekit::error::{SCError, SCStreamErrorCode};
+use screencapturekit::shareable_content::SCShareableContent;
+use screencapturekit::stream::configuration::SCStreamConfiguration;
+use screencapturekit::stream::content_filter::SCContentFilter;
+use screencapturekit::stream::permission_watcher::{
+    capture_access, screen_capture_permitted, CaptureAccess, PermissionWatcher,
+};
+use screencapturekit::stream::{SCStream, StreamCallbacks};
+
+#[test]
+fn test_permission_errors_are_recognized() {
+    assert!(
+        SCError::from_stream_error_code(SCStreamErrorCode::UserDeclined).is_permission_denied()
+    );
+    assert!(SCError::from_error_code(-3801).is_permission_denied());
+    assert!(SCError::permission_denied("Screen Recording").is_permission_denied());
+    assert!(
+        !SCError::from_stream_error_code(SCStreamErrorCode::NoCaptureSource).is_permission_denied()
+    );
+    assert!(!SCError::internal_error("boom").is_permission_denied());
+}
+
+#[test]
+fn test_callbacks_register_permission_revoked() {
+    let callbacks = StreamCallbacks::new().on_permission_revoked(|_| {});
+    assert!(format!("{callbacks:?}").contains("on_permission_revoked: true"));
+    assert!(format!("{:?}", StreamCallbacks::new()).contains("on_permission_revoked: false"));
+}
+
+#[test]
+fn test_preflight_matches_shareable_content_access() {
+    // Shareable content is only listed with permission, so a successful
+    // fetch implies the preflight check passes.
+    if SCShareableContent::get().is_ok() {
+        assert!(screen_capture_permitted());
+    }
+}
+
+#[test]
+fn test_permission_watcher_quiet_while_permitted() {
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - no screen recording permission");
+        return;
+    };
+    let Some(display) = content.displays().into_iter().next() else {
+        println!("⚠ No displays available");
+        return;
+    };
+
+    let revoked = Arc::new(AtomicUsize::new(0));
+    let seen = Arc::clone(&revoked);
+    let callbacks = StreamCallbacks::new().on_permission_revoked(move |_| {
+        seen.fetch_add(1, Ordering::SeqCst);
+    });
+    let filter = SCContentFilter::for_display(&display)
+        .with_excluding_windows(&[])
+        .build();
+    let stream = SCStream::new_with_delegate(&filter, &SCStreamConfiguration::new(), callbacks);
+
+    let watcher = stream
+        .watch_permission(Duration::from_millis(50))
+        .expect("spawn watcher thread");
+    std::thread::sleep(Duration::from_millis(200));
+    assert_eq!(watcher.revocation_count(), 0);
+    assert!(watcher.last_error().is_none());
+    assert_eq!(revoked.load(Ordering::SeqCst), 0);
+    assert!(format!("{watcher:?}").contains("PermissionWatcher"));
+
+    let started = std::time::Instant::now();
+    drop(watcher);
+    assert!(started.elapsed() < Duration::from_secs(5));
+
+    let watcher = PermissionWatcher::start_opening_settings(&stream, Duration::from_millis(50))
+        .expect("spawn watcher thread");
+    std::thread::sleep(Duration::from_millis(100));
+    // Settings are only opened after a revocation.
+    assert_eq!(watcher.revocation_count(), 0);
+    assert!(watcher.last_error().is_none());
+}
+
+#[test]
+fn test_capture_access_follows_preflight() {
+    let access = capture_access();
+    assert_eq!(access == CaptureAccess::Full, screen_capture_permitted());
+    assert_eq!(access.can_list_content(), screen_capture_permitted());
+    assert!(!CaptureAccess::PickerOnly.can_list_content());
+    assert_eq!(CaptureAccess::PickerOnly.to_string(), "picked content only");
+}
+
+#[test]
+fn test_only_picked_filters_allowed_without_permission() {
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - no screen recording permission");
+        return;
+    };
+    let Some(display) = content.displays().into_iter().next() else {
+        println!("⚠ No displays available");
+        return;
+    };
+    let filter = SCContentFilter::for_display(&display)
+        .with_excluding_windows(&[])
+        .build();
+    assert!(!filter.is_from_picker());
+    assert!(CaptureAccess::Full.allows(&filter));
+    assert!(!CaptureAccess::PickerOnly.allows(&filter));
+    assert!(!CaptureAccess::Unavailable.allows(&filter));
+
+    let stream = SCStream::new(&filter, &SCStreamConfiguration::new());
+    assert_eq!(stream.capture_access(), CaptureAccess::Full);
+
+    // The flag travels with clones.
+    let cloned = filter.clone();
+    drop(filter);
+    assert!(!cloned.is_from_picker());
+}
@@ -242,3 +242,299 @@ mod iosurface_plane_tests {
         }
     }
 }
+
+mod l10r_decoding_tests {
+    use screencapturekit::cv::l10r::{self, L10rPixelBufferExt};
+    use screencapturekit::cv::CVPixelBuffer;
+    use screencapturekit::error::SCError;
+
+    const fn pack(r: u32, g: u32, b: u32, a: u32) -> u32 {
+        (a << 30) | (r << 20) | (g << 10) | b
+    }
+
+    #[test]
+    fn test_l10r_unpack_and_decode() {
+        let word = pack(0x3FF, 0x200, 0, 3);
+        assert_eq!(l10r::unpack(word), [0x3FF, 0x200, 0, 3]);
+        assert_eq!(l10r::decode_rgba16(word), [0xFFFF, 0x8020, 0, 0xFFFF]);
+
+        let [r, g, b, a] = l10r::decode_rgba_f32(word);
+        assert!((r - 1.0).abs() < f32::EPSILON);
+        assert!((g - 512.0 / 1023.0).abs() < 1e-6);
+        assert!(b.abs() < f32::EPSILON);
+        assert!((a - 1.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_l10r_plane_respects_row_padding() {
+        // 2x2 image with 4 bytes of padding per row
+        let bytes_per_row = 12;
+        let mut data = vec![0xAAu8; bytes_per_row * 2];
+        let pixels = [
+            pack(1, 2, 3, 0),
+            pack(4, 5, 6, 1),
+            pack(7, 8, 9, 2),
+            pack(10, 11, 12, 3),
+        ];
+        for (i, px) in pixels.iter().enumerate() {
+            let offset = (i / 2) * bytes_per_row + (i % 2) * 4;
+            data[offset..offset + 4].copy_from_slice(&px.to_le_bytes());
+        }
+
+        let rgba = l10r::to_rgba16(&data, 2, 2, bytes_per_row).unwrap();
+        assert_eq!(rgba.len(), 16);
+        assert_eq!(&rgba[12..], &l10r::decode_rgba16(pixels[3]));
+
+        let floats = l10r::to_rgba_f32(&data, 2, 2, bytes_per_row).unwrap();
+        assert_eq!(floats.len(), 16);
+
+        assert!(matches!(
+            l10r::to_rgba16(&data, 4, 2, bytes_per_row),
+            Err(SCError::InvalidDimension { .. })
+        ));
+        assert!(matches!(
+            l10r::to_rgba16(&data[..16], 2, 2, bytes_per_row),
+            Err(SCError::InvalidDimension { .. })
+        ));
+    }
+
+    #[test]
+    fn test_l10r_pixel_buffer_decode() {
+        let buffer = CVPixelBuffer::create(16, 8, l10r::L10R_PIXEL_FORMAT)
+            .expect("Failed to create l10r pixel buffer");
+        let rgba = buffer.l10r_to_rgba16().unwrap();
+        assert_eq!(rgba.len(), 16 * 8 * 4);
+
+        let bgra = CVPixelBuffer::create(16, 8, 0x42475241).unwrap();
+        assert!(matches!(
+            bgra.l10r_to_rgba_f32(),
+            Err(SCError::InvalidPixelFormat(_))
+        ));
+    }
+}
+
+mod pixel_reader_tests {
+    use screencapturekit::cv::pixel_reader::PixelBufferReadExt;
+    use screencapturekit::cv::CVPixelBuffer;
+    use screencapturekit::error::SCError;
+    use screencapturekit::stream::configuration::PixelFormat;
+    use screencapturekit::FourCharCode;
+
+    fn create(format: PixelFormat) -> CVPixelBuffer {
+        let code: FourCharCode = format.into();
+        CVPixelBuffer::create(16, 16, code.as_u32()).expect("Failed to create pixel buffer")
+    }
+
+    #[test]
+    fn test_pixel_reader_supported_formats() {
+        for format in [
+            PixelFormat::BGRA,
+            PixelFormat::l10r,
+            PixelFormat::YCbCr_420v,
+            PixelFormat::YCbCr_420f,
+        ] {
+            let buffer = create(format);
+            let reader = buffer.pixel_reader().expect("format should be supported");
+            assert_eq!(reader.pixel_format(), format);
+            assert_eq!((reader.width(), reader.height()), (16, 16));
+
+            let rgba = reader.read_pixel_rgba(15, 15).unwrap();
+            assert!(rgba.iter().all(|c| (0.0..=1.0).contains(c)));
+
+            assert!(matches!(
+                reader.read_pixel_rgba(16, 0),
+                Err(SCError::InvalidDimension { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_pixel_reader_rejects_unsupported_format() {
+        let buffer = create(PixelFormat::RGhA);
+        assert!(matches!(
+            buffer.read_pixel_rgba(0, 0),
+            Err(SCError::InvalidPixelFormat(_))
+        ));
+    }
+}
+
+mod color_attachment_tests {
+    use screencapturekit::cv::color::{
+        CVPixelBufferColorExt, ColorAttachments, ColorPrimaries, TransferFunction, YCbCrMatrix,
+    };
+    use screencapturekit::cv::CVPixelBuffer;
+
+    #[test]
+    fn test_color_values_round_trip_cv_strings() {
+        for primaries in [
+            ColorPrimaries::Bt709,
+            ColorPrimaries::Bt2020,
+            ColorPrimaries::P3D65,
+            ColorPrimaries::DciP3,
+        ] {
+            assert_eq!(
+                ColorPrimaries::from_cv_string(primaries.as_cv_str()),
+                primaries
+            );
+        }
+        for transfer in [
+            TransferFunction::Bt709,
+            TransferFunction::Srgb,
+            TransferFunction::Pq,
+            TransferFunction::Hlg,
+        ] {
+            assert_eq!(
+                TransferFunction::from_cv_string(transfer.as_cv_str()),
+                transfer
+            );
+        }
+        assert_eq!(
+            YCbCrMatrix::from_cv_string("ITU_R_2020"),
+            YCbCrMatrix::Bt2020
+        );
+        assert_eq!(
+            ColorPrimaries::from_cv_string("Future_Primaries"),
+            ColorPrimaries::Other("Future_Primaries".to_string())
+        );
+    }
+
+    #[test]
+    fn test_color_values_cicp_codes() {
+        assert_eq!(ColorPrimaries::Bt709.cicp_code(), Some(1));
+        assert_eq!(ColorPrimaries::Bt2020.cicp_code(), Some(9));
+        assert_eq!(TransferFunction::Pq.cicp_code(), Some(16));
+        assert_eq!(TransferFunction::Hlg.cicp_code(), Some(18));
+        assert_eq!(YCbCrMatrix::Bt709.cicp_code(), Some(1));
+        assert_eq!(YCbCrMatrix::Other("x".into()).cicp_code(), None);
+    }
+
+    #[test]
+    fn test_color_attachments_hdr_detection() {
+        let hdr = ColorAttachments {
+            color_primaries: Some(ColorPrimaries::Bt2020),
+            transfer_function: Some(TransferFunction::Pq),
+            ycbcr_matrix: Some(YCbCrMatrix::Bt2020),
+        };
+        assert!(hdr.is_hdr());
+        assert!(!ColorAttachments::default().is_hdr());
+    }
+
+    #[test]
+    fn test_untagged_pixel_buffer_has_no_hdr_tags() {
+        let buffer = CVPixelBuffer::create(16, 16, 0x4247_5241).unwrap();
+        assert!(!buffer.color_attachments().is_hdr());
+    }
+}
+
+#[cfg(test)]
+mod frame_copy_tests {
+    use screencapturekit::cm::{IOSurface, IOSurfaceLockOptions};
+    use screencapturekit::cv::frame_copy::{FrameCopyExt, RowLayout};
+    use screencapturekit::cv::CVPixelBuffer;
+    use screencapturekit::error::SCError;
+
+    #[test]
+    fn test_row_layout_bytes_per_row() {
+        assert_eq!(RowLayout::default(), RowLayout::Packed);
+        assert_eq!(RowLayout::Packed.bytes_per_row(64), 64);
+        assert_eq!(RowLayout::Strided(256).bytes_per_row(64), 256);
+    }
+
+    #[test]
+    fn test_pixel_buffer_copy_sizes() {
+        let bgra = CVPixelBuffer::create(16, 8, 0x4247_5241).unwrap();
+        assert_eq!(bgra.copy_size(RowLayout::Packed).unwrap(), 16 * 4 * 8);
+        assert_eq!(bgra.copy_size(RowLayout::Strided(128)).unwrap(), 128 * 8);
+
+        // '420v': full-size luma plane plus half-height interleaved chroma
+        let ycbcr = CVPixelBuffer::create(16, 16, 0x3432_3076).unwrap();
+        assert_eq!(
+            ycbcr.copy_size(RowLayout::Packed).unwrap(),
+            16 * 16 + 16 * 8
+        );
+    }
+
+    #[test]
+    fn test_pixel_buffer_copy_into_reused_buffer() {
+        let buffer = CVPixelBuffer::create(16, 8, 0x4247_5241).unwrap();
+        let mut dest = vec![0u8; 16 * 4 * 8 + 32];
+        for _ in 0..3 {
+            let written = buffer.copy_into(&mut dest, RowLayout::Packed).unwrap();
+            assert_eq!(written, 16 * 4 * 8);
+        }
+        assert!(dest[16 * 4 * 8..].iter().all(|&b| b == 0));
+
+        let ycbcr = CVPixelBuffer::create(16, 16, 0x3432_3066).unwrap();
+        let mut dest = vec![0u8; ycbcr.copy_size(RowLayout::Strided(32)).unwrap()];
+        assert_eq!(
+            ycbcr.copy_into(&mut dest, RowLayout::Strided(32)).unwrap(),
+            32 * 24
+        );
+    }
+
+    #[test]
+    fn test_copy_into_rejects_short_destination_and_stride() {
+        let buffer = CVPixelBuffer::create(16, 8, 0x4247_5241).unwrap();
+        let mut short = vec![0u8; 16 * 4 * 8 - 1];
+        assert!(matches!(
+            buffer.copy_into(&mut short, RowLayout::Packed),
+            Err(SCError::InvalidDimension { .. })
+        ));
+
+        let mut dest = vec![0u8; 1024];
+        assert!(matches!(
+            buffer.copy_into(&mut dest, RowLayout::Strided(63)),
+            Err(SCError::InvalidDimension { .. })
+        ));
+        assert!(buffer.copy_size(RowLayout::Strided(63)).is_err());
+    }
+
+    #[test]
+    fn test_iosurface_copy_strips_and_adds_padding() {
+        let (width, height, bytes_per_row) = (4, 2, 64);
+        let Some(surface) = IOSurface::create_with_properties(
+            width,
+            height,
+            0x42475241, // BGRA
+            4,
+            bytes_per_row,
+            bytes_per_row * height,
+            None,
+        ) else {
+            return;
+        };
+
+        {
+            let mut guard = surface.lock(IOSurfaceLockOptions::AVOID_SYNC).unwrap();
+            let base = guard.base_address_mut().unwrap();
+            let row_stride = guard.bytes_per_row();
+            for row in 0..height {
+                for byte in 0..row_stride {
+                    let value = if byte < width * 4 {
+                        u8::try_from(row * 16 + byte).unwrap()
+                    } else {
+                        0xEE
+                    };
+                    unsafe { *base.cast::<u8>().add(row * row_stride + byte) = value };
+                }
+            }
+        }
+
+        let mut packed = vec![0u8; surface.copy_size(RowLayout::Packed).unwrap()];
+        assert_eq!(packed.len(), width * 4 * height);
+        surface.copy_into(&mut packed, RowLayout::Packed).unwrap();
+        assert_eq!(&packed[..16], &(0..16).collect::<Vec<u8>>()[..]);
+        assert_eq!(&packed[16..], &(16..32).collect::<Vec<u8>>()[..]);
+
+        let mut strided = vec![0xAAu8; 24 * height];
+        assert_eq!(
+            surface
+                .copy_into(&mut strided, RowLayout::Strided(24))
+                .unwrap(),
+            48
+        );
+        assert_eq!(&strided[..16], &packed[..16]);
+        assert_eq!(&strided[16..24], &[0xAA; 8]);
+        assert_eq!(&strided[24..40], &packed[16..]);
+    }
+}
@@ -0,0 +1,114 @@
+//! Pooled output handler tests
+//!
+//! These drive `PooledOutputHandler` directly with synthetic sample buffers,
+//! so they don't need screen-recording permission.
+
+mod common;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Barrier, Mutex};
+use std::thread::ThreadId;
+use std::time::Duration;
+
+use screencapturekit::cm::CMSampleBuffer;
+use screencapturekit::stream::output_trait::SCStreamOutputTrait;
+use screencapturekit::stream::output_type::SCStreamOutputType;
+use screencapturekit::stream::pooled_output::PooledOutputHandler;
+
+use common::sample;
+
+#[test]
+fn test_pooled_handler_processes_every_sample_off_thread() {
+    let processed = Arc::new(AtomicUsize::new(0));
+    let threads = Arc::new(Mutex::new(Vec::<ThreadId>::new()));
+
+    let pool = {
+        let processed = Arc::clone(&processed);
+        let threads = Arc::clone(&threads);
+        PooledOutputHandler::new(
+            move |_sample: CMSampleBuffer, _of_type| {
+                threads.lock().unwrap().push(std::thread::current().id());
+                processed.fetch_add(1, Ordering::SeqCst);
+            },
+            2,
+        )
+        .expect("spawn output workers")
+    };
+    assert_eq!(pool.worker_count(), 2);
+
+    // Stay within the queue bound so nothing is dropped.
+    for frame in 0..4 {
+        pool.did_output_sample_buffer(sample(frame), SCStreamOutputType::Screen);
+    }
+    // Dropping the pool drains the queue and joins the workers.
+    drop(pool);
+
+    assert_eq!(processed.load(Ordering::SeqCst), 4);
+    let caller = std::thread::current().id();
+    assert!(threads.lock().unwrap().iter().all(|id| *id != caller));
+}
+
+#[test]
+fn test_pooled_handler_zero_workers_uses_one() {
+    let pool = PooledOutputHandler::new(|_sample: CMSampleBuffer, _of_type| {}, 0)
+        .expect("spawn output workers");
+    assert_eq!(pool.worker_count(), 1);
+}
+
+#[test]
+fn test_pooled_handler_drops_oldest_when_full() {
+    // Block the single worker so the queue fills up behind it.
+    let gate = Arc::new(Barrier::new(2));
+    let seen = Arc::new(Mutex::new(Vec::new()));
+
+    let pool = {
+        let gate = Arc::clone(&gate);
+        let seen = Arc::clone(&seen);
+        PooledOutputHandler::new(
+            move |sample: CMSampleBuffer, _of_type| {
+                let frame = sample.presentation_timestamp().value;
+                if frame == 0 {
+                    gate.wait();
+                }
+                seen.lock().unwrap().push(frame);
+            },
+            1,
+        )
+        .expect("spawn output workers")
+    };
+
+    pool.did_output_sample_buffer(sample(0), SCStreamOutputType::Screen);
+    // Give the worker time to pick up frame 0 and block on the gate.
+    std::thread::sleep(Duration::from_millis(100));
+    for frame in 1..=5 {
+        pool.did_output_sample_buffer(sample(frame), SCStreamOutputType::Screen);
+    }
+    assert_eq!(pool.dropped_samples(), 3);
+
+    gate.wait();
+    drop(pool);
+    assert_eq!(*seen.lock().unwrap(), vec![0, 4, 5]);
+}
+
+#[test]
+fn test_pooled_handler_survives_handler_panic() {
+    let processed = Arc::new(AtomicUsize::new(0));
+    let pool = {
+        let processed = Arc::clone(&processed);
+        PooledOutputHandler::new(
+            move |sample: CMSampleBuffer, _of_type| {
+                assert!(
+                    sample.presentation_timestamp().value != 0,
+                    "intentional test panic"
+                );
+                processed.fetch_add(1, Ordering::SeqCst);
+            },
+            1,
+        )
+        .expect("spawn output workers")
+    };
+    pool.did_output_sample_buffer(sample(0), SCStreamOutputType::Screen);
+    pool.did_output_sample_buffer(sample(1), SCStreamOutputType::Screen);
+    drop(pool);
+    assert_eq!(processed.load(Ordering::SeqCst), 1);
+}
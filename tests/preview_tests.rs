@@ -0,0 +1,83 @@
+//! Preview layer tests
+//!
+//! These use synthetic sample buffers and never attach the layer to a
+//! window, so they run headless and without screen-recording permission.
+
+mod common;
+
+use screencapturekit::preview::{PreviewGravity, PreviewLayer};
+use screencapturekit::stream::output_trait::SCStreamOutputTrait;
+use screencapturekit::stream::output_type::SCStreamOutputType;
+
+use common::sample;
+
+#[test]
+fn test_preview_layer_create() {
+    let preview = PreviewLayer::new();
+    assert!(!preview.layer_ptr().is_null());
+    assert_eq!(preview.dropped_frames(), 0);
+
+    // Clones share the layer
+    let clone = preview.clone();
+    assert_eq!(clone.layer_ptr(), preview.layer_ptr());
+}
+
+#[test]
+fn test_preview_gravity_default() {
+    assert_eq!(PreviewGravity::default(), PreviewGravity::ResizeAspect);
+    let preview = PreviewLayer::new();
+    preview.set_gravity(PreviewGravity::ResizeAspectFill);
+    preview.set_gravity(PreviewGravity::Resize);
+}
+
+#[test]
+fn test_preview_enqueue_and_flush() {
+    let preview = PreviewLayer::new();
+    let accepted = (0..3).filter(|i| preview.enqueue(&sample(*i))).count();
+    let dropped = usize::try_from(preview.dropped_frames()).unwrap();
+    assert_eq!(accepted + dropped, 3);
+
+    preview.flush();
+    preview.clear();
+}
+
+#[test]
+fn test_preview_output_ignores_audio() {
+    let preview = PreviewLayer::new();
+    let output = preview.output_handler();
+    // Synthetic frames have no SCStreamFrameInfo status, so the handler
+    // leaves them alone like idle frames.
+    output.did_output_sample_buffer(sample(0), SCStreamOutputType::Screen);
+    output.did_output_sample_buffer(sample(1), SCStreamOutputType::Audio);
+    assert_eq!(preview.dropped_frames(), 0);
+    println!("{preview:?}");
+}
+
+#[cfg(feature = "preview_window")]
+mod window {
+    use screencapturekit::error::SCError;
+    use screencapturekit::preview::PreviewWindow;
+
+    #[test]
+    fn test_preview_window_rejects_bad_input() {
+        assert!(matches!(
+            PreviewWindow::new("bad\0title", 640.0, 360.0),
+            Err(SCError::InvalidConfiguration(_))
+        ));
+        assert!(PreviewWindow::new("Preview", 0.0, 360.0).is_err());
+        assert!(PreviewWindow::new("Preview", 640.0, f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_preview_window_starts_hidden() {
+        // Tests don't run on the main thread, so the window itself is only
+        // queued there; the state flags are readable right away.
+        let window = PreviewWindow::new("Preview", 640.0, 360.0).expect("create window");
+        assert!(!window.is_visible());
+        assert!(!window.is_closed());
+        assert!(window.set_size(-1.0, 10.0).is_err());
+        assert!(window.set_title("Renamed").is_ok());
+        assert!(window.run_until_closed(None).is_err());
+        assert_eq!(window.layer().dropped_frames(), 0);
+    }
+}
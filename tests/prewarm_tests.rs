@@ -0,0 +1,73 @@
+//! Prewarmed stream tests
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use screencapturekit::shareable_content::SCShareableContent;
+use screencapturekit::stream::configuration::SCStreamConfiguration;
+use screencapturekit::stream::content_filter::SCContentFilter;
+use screencapturekit::stream::output_type::SCStreamOutputType;
+use screencapturekit::stream::prewarm::PrewarmedStream;
+
+fn display_filter() -> Option<SCContentFilter> {
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - no screen recording permission");
+        return None;
+    };
+    let Some(display) = content.displays().into_iter().next() else {
+        println!("⚠ No displays available");
+        return None;
+    };
+    Some(
+        SCContentFilter::for_display(&display)
+            .with_excluding_windows(&[])
+            .build(),
+    )
+}
+
+#[test]
+fn test_prewarmed_stream_starts_cold() {
+    let Some(filter) = display_filter() else {
+        return;
+    };
+    let config = SCStreamConfiguration::new()
+        .with_width(640)
+        .with_height(360);
+    let prewarmed = PrewarmedStream::new(&filter, &config);
+    assert!(!prewarmed.is_warm());
+    assert!(!prewarmed.stream().is_capturing());
+    assert_eq!(prewarmed.configuration().width(), 640);
+}
+
+#[test]
+fn test_warm_stream_withholds_samples_until_activated() {
+    let Some(filter) = display_filter() else {
+        return;
+    };
+    let config = SCStreamConfiguration::new()
+        .with_width(640)
+        .with_height(360);
+    let frames = Arc::new(AtomicUsize::new(0));
+    let mut prewarmed = PrewarmedStream::new(&filter, &config);
+    let counter = Arc::clone(&frames);
+    prewarmed.stream_mut().add_output_handler(
+        move |_sample, _of_type| {
+            counter.fetch_add(1, Ordering::Relaxed);
+        },
+        SCStreamOutputType::Screen,
+    );
+
+    if let Err(error) = prewarmed.keep_warm() {
+        println!("⚠ Skipping - capture failed to start: {error}");
+        return;
+    }
+    assert!(prewarmed.is_warm());
+    std::thread::sleep(Duration::from_millis(1500));
+    assert_eq!(frames.load(Ordering::Relaxed), 0);
+
+    let stream = prewarmed.activate(&filter).expect("activate warm stream");
+    assert!(stream.is_capturing());
+    assert_eq!(stream.configuration().width(), 640);
+    stream.stop_capture().expect("stop capture");
+}
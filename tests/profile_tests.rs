@@ -0,0 +1,142 @@
+//! Capture profile tests
+
+#![cfg(feature = "profiles")]
+
+use screencapturekit::prelude::*;
+use screencapturekit::profile::{
+    ConfigProfile, FilterDescriptor, RecordingCodec, RecordingFileType, RecordingSettings,
+};
+
+fn sample_profile() -> ConfigProfile {
+    let filter = FilterDescriptor::Display {
+        display_id: Some(1),
+        excluded_applications: Vec::new(),
+    }
+    .with_excluded_application("com.apple.dock");
+    let configuration = SCStreamConfiguration::new()
+        .with_width(1280)
+        .with_height(720)
+        .with_frame_rate(30.0)
+        .with_shows_cursor(false)
+        .with_captures_audio(true);
+    ConfigProfile::new(filter, &configuration)
+        .with_name("Meeting")
+        .with_recording(
+            RecordingSettings::new("/tmp/meeting.mov")
+                .with_file_type(RecordingFileType::Mov)
+                .with_codec(RecordingCodec::Hevc),
+        )
+}
+
+#[test]
+fn test_profile_json_round_trip() {
+    let profile = sample_profile();
+    let json = profile.to_json().unwrap();
+    assert!(json.contains("\"kind\": \"display\""));
+    assert_eq!(ConfigProfile::from_json(&json).unwrap(), profile);
+}
+
+#[test]
+fn test_profile_toml_round_trip() {
+    let profile = sample_profile();
+    let toml = profile.to_toml().unwrap();
+    let loaded = ConfigProfile::from_toml(&toml).unwrap();
+    assert_eq!(loaded, profile);
+    assert_eq!(loaded.name(), Some("Meeting"));
+
+    let configuration = loaded.configuration().unwrap();
+    assert_eq!((configuration.width(), configuration.height()), (1280, 720));
+    assert!((configuration.frame_rate() - 30.0).abs() < 0.01);
+    assert!(!configuration.shows_cursor());
+    assert!(configuration.captures_audio());
+}
+
+#[test]
+fn test_profile_save_and_load() {
+    let dir = std::env::temp_dir().join(format!("sck-profile-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let profile = sample_profile();
+    for file in ["profile.toml", "profile.json"] {
+        let path = dir.join(file);
+        profile.save(&path).unwrap();
+        assert_eq!(ConfigProfile::load(&path).unwrap(), profile);
+    }
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_profile_rejects_invalid_input() {
+    let newer = sample_profile()
+        .to_json()
+        .unwrap()
+        .replace("\"version\": 1", "\"version\": 99");
+    assert!(matches!(
+        ConfigProfile::from_json(&newer),
+        Err(SCError::InvalidConfiguration(_))
+    ));
+    assert!(matches!(
+        ConfigProfile::from_toml("not a profile"),
+        Err(SCError::InvalidConfiguration(_))
+    ));
+    assert!(matches!(
+        ConfigProfile::load("/nonexistent/profile.toml"),
+        Err(SCError::InternalError(_))
+    ));
+}
+
+#[test]
+fn test_minimal_profile_uses_default_configuration() {
+    let profile = ConfigProfile::from_toml(
+        r#"
+        version = 1
+
+        [filter]
+        kind = "application"
+        bundle_id = "com.apple.Safari"
+        "#,
+    )
+    .unwrap();
+    assert_eq!(
+        profile.filter(),
+        &FilterDescriptor::Application {
+            bundle_id: "com.apple.Safari".to_string(),
+            display_id: None,
+        }
+    );
+    assert!(profile.recording().is_none());
+    assert!(profile.configuration().is_ok());
+}
+
+#[test]
+fn test_profile_resolves_against_current_content() {
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - no screen recording permission");
+        return;
+    };
+
+    let Some(display) = content.displays().into_iter().next() else {
+        println!("⚠ No displays available");
+        return;
+    };
+    let profile = ConfigProfile::new(
+        FilterDescriptor::display(&display),
+        &SCStreamConfiguration::new(),
+    );
+    let resolved = profile.resolve(&content).expect("resolve display");
+    assert!(resolved.recording.is_none());
+    #[cfg(feature = "macos_15_2")]
+    assert_eq!(resolved.filter.included_displays(), vec![display]);
+
+    let missing = ConfigProfile::new(
+        FilterDescriptor::Window {
+            bundle_id: Some("com.example.not-running".to_string()),
+            title: Some("Nope".to_string()),
+            window_id: None,
+        },
+        &SCStreamConfiguration::new(),
+    );
+    assert!(matches!(
+        missing.resolve(&content),
+        Err(SCError::WindowNotFound(_))
+    ));
+}
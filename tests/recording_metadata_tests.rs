@@ -0,0 +1,162 @@
+//! Recording sidecar tests
+
+#![cfg(feature = "recording_metadata")]
+
+use std::time::Duration;
+
+use screencapturekit::cm::{CMSampleBuffer, CMSampleBufferExt, CMTime};
+use screencapturekit::cv::CVPixelBuffer;
+use screencapturekit::recording_metadata::{
+    Chapter, FrameStats, Marker, RecordingSidecar, FORMAT_VERSION,
+};
+use screencapturekit::shareable_content::SCShareableContent;
+use screencapturekit::stream::configuration::SCStreamConfiguration;
+use screencapturekit::stream::output_trait::SCStreamOutputTrait;
+use screencapturekit::stream::output_type::SCStreamOutputType;
+
+#[test]
+fn test_sidecar_path_defaults_next_to_recording() {
+    let sidecar = RecordingSidecar::new("/tmp/capture.mp4");
+    assert_eq!(sidecar.sidecar_path().to_str(), Some("/tmp/capture.json"));
+    let sidecar = sidecar.with_sidecar_path("/tmp/meta/capture.mp4.json");
+    assert_eq!(
+        sidecar.sidecar_path().to_str(),
+        Some("/tmp/meta/capture.mp4.json")
+    );
+    assert_eq!(sidecar.recording_path().to_str(), Some("/tmp/capture.mp4"));
+}
+
+#[test]
+fn test_chapters_are_sorted_by_offset() {
+    let sidecar = RecordingSidecar::new("/tmp/capture.mp4");
+    sidecar.add_chapter_at("Outro", Duration::from_secs(90));
+    sidecar.add_chapter_at("Intro", Duration::ZERO);
+    sidecar.add_chapter_at("Demo", Duration::from_secs(30));
+    let now = sidecar.add_chapter("Live");
+
+    let titles: Vec<_> = sidecar
+        .chapters()
+        .into_iter()
+        .map(|chapter| chapter.title)
+        .collect();
+    assert_eq!(titles[..2], ["Intro".to_string(), "Live".to_string()]);
+    assert!(now < Duration::from_secs(30));
+    assert_eq!(
+        sidecar.chapters().last(),
+        Some(&Chapter {
+            title: "Outro".to_string(),
+            offset: Duration::from_secs(90),
+        })
+    );
+}
+
+#[test]
+fn test_markers_are_kept_apart_from_chapters() {
+    let sidecar = RecordingSidecar::new("/tmp/capture.mp4");
+    sidecar.add_chapter_at("Intro", Duration::ZERO);
+    sidecar.add_marker_at("Glitch", Duration::from_secs(42));
+    sidecar.add_marker_at("Login", Duration::from_secs(5));
+
+    assert_eq!(sidecar.chapters().len(), 1);
+    let markers = sidecar.markers();
+    assert_eq!(
+        markers,
+        [
+            Marker {
+                label: "Login".to_string(),
+                offset: Duration::from_secs(5),
+            },
+            Marker {
+                label: "Glitch".to_string(),
+                offset: Duration::from_secs(42),
+            },
+        ]
+    );
+    assert_eq!(
+        Chapter::from(markers[1].clone()),
+        Chapter {
+            title: "Glitch".to_string(),
+            offset: Duration::from_secs(42),
+        }
+    );
+
+    let json = sidecar.to_json();
+    assert!(json.contains("\"markers\": ["));
+    assert!(json.contains("\"label\": \"Login\""));
+    assert!(json.contains("\"offset_seconds\": 42"));
+}
+
+#[test]
+fn test_json_contents() {
+    let config = SCStreamConfiguration::new()
+        .with_width(1280)
+        .with_height(720);
+    let sidecar = RecordingSidecar::new("/tmp/capture.mp4")
+        .with_configuration(&config)
+        .with_application_version("Recorder \"Pro\"", "2.1");
+    sidecar.add_chapter_at("Line\nbreak", Duration::from_millis(1500));
+    sidecar.add_dropped_frames(3);
+
+    let json = sidecar.to_json();
+    assert!(json.starts_with('{') && json.ends_with("}\n"));
+    assert!(json.contains(&format!("\"format_version\": {FORMAT_VERSION}")));
+    assert!(json.contains("\"width\": 1280"));
+    assert!(json.contains("\"height\": 720"));
+    assert!(json.contains("\"Recorder \\\"Pro\\\"\": \"2.1\""));
+    assert!(json.contains("\"title\": \"Line\\nbreak\""));
+    assert!(json.contains("\"offset_seconds\": 1.5"));
+    assert!(json.contains("\"dropped\": 3"));
+    assert!(json.contains(&format!(
+        "\"screencapturekit\": \"{}\"",
+        env!("CARGO_PKG_VERSION")
+    )));
+}
+
+#[test]
+fn test_frame_counter_ignores_samples_without_status() {
+    let sidecar = RecordingSidecar::new("/tmp/capture.mp4");
+    let counter = sidecar.frame_counter();
+    let pixels = CVPixelBuffer::create(8, 8, 0x4247_5241).expect("create BGRA pixel buffer");
+    let sample =
+        CMSampleBuffer::create_for_image_buffer(&pixels, CMTime::new(0, 60), CMTime::new(1, 60))
+            .expect("wrap in sample buffer");
+    counter.did_output_sample_buffer(sample, SCStreamOutputType::Screen);
+    assert_eq!(sidecar.frame_stats().complete, 0);
+    assert_eq!(sidecar.frame_stats(), FrameStats::default());
+}
+
+#[test]
+fn test_write_replaces_sidecar() {
+    let dir = std::env::temp_dir().join(format!("sck-sidecar-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let sidecar = RecordingSidecar::new(dir.join("capture.mov"));
+    sidecar.add_chapter("First");
+    let path = sidecar.write().expect("write sidecar");
+    assert_eq!(path, dir.join("capture.json"));
+
+    sidecar.add_chapter("Second");
+    sidecar.write().expect("rewrite sidecar");
+    let text = std::fs::read_to_string(&path).unwrap();
+    assert!(text.contains("\"Second\""));
+    assert!(!dir.join("capture.json.partial").exists());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_sources_from_shareable_content() {
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - no screen recording permission");
+        return;
+    };
+    let Some(display) = content.displays().into_iter().next() else {
+        println!("⚠ No displays available");
+        return;
+    };
+    let mut sidecar = RecordingSidecar::new("/tmp/capture.mp4").with_display(&display);
+    if let Some(window) = content.windows().into_iter().next() {
+        sidecar = sidecar.with_window(&window);
+        assert!(sidecar.to_json().contains("\"kind\": \"window\""));
+    }
+    let json = sidecar.to_json();
+    assert!(json.contains(&format!("\"display_id\": {}", display.display_id())));
+}
@@ -601,3 +601,266 @@ fn test_recording_output_with_delegate() {
         }
     }
 }
+
+// MARK: - Attaching to a stream
+
+#[test]
+fn test_recording_output_attach_twice_is_typed_error() {
+    use screencapturekit::error::{SCError, SCStreamErrorCode};
+    use screencapturekit::prelude::*;
+    use std::path::PathBuf;
+
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - shareable content unavailable");
+        return;
+    };
+    let Some(display) = content.displays().into_iter().next() else {
+        return;
+    };
+    let filter = SCContentFilter::for_display(&display)
+        .with_excluding_windows(&[])
+        .build();
+    let config = SCStreamConfiguration::new()
+        .with_width(320)
+        .with_height(240);
+    let mut stream = SCStream::new(&filter, &config);
+
+    // Frames keep flowing to Rust handlers alongside the recording output.
+    let handler_id = stream.add_output_handler(
+        |_: CMSampleBuffer, _: SCStreamOutputType| {},
+        SCStreamOutputType::Screen,
+    );
+    assert!(handler_id.is_some());
+
+    let path = PathBuf::from("/tmp/test_attach_twice.mp4");
+    let rec_config = SCRecordingOutputConfiguration::new().with_output_url(&path);
+    let Some(recording) = SCRecordingOutput::new(&rec_config) else {
+        println!("⚠ Recording output creation requires macOS 15.0+ runtime");
+        return;
+    };
+
+    if stream.add_recording_output(&recording).is_err() {
+        println!("⚠ add_recording_output unavailable in this environment");
+        return;
+    }
+    assert_eq!(stream.recording_output_count(), 1);
+
+    let err = stream
+        .add_recording_output(&recording)
+        .expect_err("attaching the same output twice must fail");
+    assert!(matches!(
+        err,
+        SCError::SCStreamError {
+            code: SCStreamErrorCode::InvalidParameter,
+            ..
+        }
+    ));
+    assert_eq!(stream.recording_output_count(), 1);
+
+    stream
+        .remove_recording_output(&recording)
+        .expect("remove attached output");
+    assert_eq!(stream.recording_output_count(), 0);
+}
+
+// MARK: - Rotation
+
+#[test]
+fn test_rotation_policy_limits() {
+    use screencapturekit::recording_output::RotationPolicy;
+    use std::time::Duration;
+
+    let unlimited = RotationPolicy::new();
+    assert_eq!(unlimited, RotationPolicy::default());
+    assert!(!unlimited.should_rotate(Duration::from_secs(86_400), u64::MAX));
+
+    let policy = RotationPolicy::new()
+        .with_max_duration(Duration::from_secs(60))
+        .with_max_file_size(1_000_000)
+        .with_poll_interval(Duration::from_millis(100));
+    assert_eq!(policy.max_duration(), Some(Duration::from_secs(60)));
+    assert_eq!(policy.max_file_size(), Some(1_000_000));
+    assert_eq!(policy.poll_interval(), Duration::from_millis(100));
+
+    assert!(!policy.should_rotate(Duration::from_secs(59), 999_999));
+    assert!(policy.should_rotate(Duration::from_secs(60), 0));
+    assert!(policy.should_rotate(Duration::ZERO, 1_000_000));
+}
+
+#[test]
+fn test_rotating_recording_rejects_zero_limits() {
+    use screencapturekit::error::SCError;
+    use screencapturekit::prelude::*;
+    use screencapturekit::recording_output::{RotatingRecording, RotationPolicy};
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Shareable content unavailable in this environment");
+        return;
+    };
+    let Some(display) = content.displays().into_iter().next() else {
+        return;
+    };
+    let filter = SCContentFilter::for_display(&display)
+        .with_excluding_windows(&[])
+        .build();
+    let stream = SCStream::new(&filter, &SCStreamConfiguration::new());
+
+    for policy in [
+        RotationPolicy::new().with_max_duration(Duration::ZERO),
+        RotationPolicy::new().with_max_file_size(0),
+        RotationPolicy::new().with_poll_interval(Duration::ZERO),
+    ] {
+        let err = RotatingRecording::start(
+            &stream,
+            &SCRecordingOutputConfiguration::new(),
+            policy,
+            |index| PathBuf::from(format!("/tmp/test_rotation_{index}.mp4")),
+            |_segment| {},
+        )
+        .expect_err("zero limits must be rejected");
+        assert!(matches!(err, SCError::InvalidConfiguration(_)));
+    }
+    assert_eq!(stream.recording_output_count(), 0);
+}
+
+// MARK: - Disk space
+
+#[test]
+fn test_disk_space_policy_builder() {
+    use screencapturekit::recording_output::DiskSpacePolicy;
+    use std::time::Duration;
+
+    let policy = DiskSpacePolicy::new();
+    assert_eq!(policy, DiskSpacePolicy::default());
+    assert!(policy.stop_below() < policy.warn_below());
+
+    let policy = policy
+        .with_warn_below(10 << 30)
+        .with_stop_below(1 << 30)
+        .with_poll_interval(Duration::from_secs(1));
+    assert_eq!(policy.warn_below(), 10 << 30);
+    assert_eq!(policy.stop_below(), 1 << 30);
+    assert_eq!(policy.poll_interval(), Duration::from_secs(1));
+}
+
+#[test]
+fn test_disk_space_preflight() {
+    use screencapturekit::error::SCError;
+    use screencapturekit::recording_output::{available_disk_space, DiskSpacePolicy};
+    use std::path::Path;
+
+    // The file doesn't exist yet; its volume is still measurable.
+    let path = Path::new("/tmp/not-yet-recorded/preflight.mp4");
+    let Ok(available) = available_disk_space(path) else {
+        println!("⚠ Volume capacity unavailable in this environment");
+        return;
+    };
+    assert!(available > 0);
+
+    let err = DiskSpacePolicy::new()
+        .with_warn_below(u64::MAX)
+        .with_stop_below(u64::MAX)
+        .preflight(path)
+        .expect_err("no volume has u64::MAX bytes free");
+    assert!(matches!(
+        err,
+        SCError::InsufficientDiskSpace {
+            required: u64::MAX,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_recording_callbacks_disk_space_events() {
+    use screencapturekit::recording_output::{RecordingCallbacks, SCRecordingOutputDelegate};
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::Arc;
+
+    let low = Arc::new(AtomicU64::new(0));
+    let stopped = Arc::new(AtomicU64::new(0));
+    let callbacks = RecordingCallbacks::new()
+        .on_disk_space_low({
+            let low = Arc::clone(&low);
+            move |free| low.store(free, Ordering::SeqCst)
+        })
+        .on_stopped_for_disk_space({
+            let stopped = Arc::clone(&stopped);
+            move |free| stopped.store(free, Ordering::SeqCst)
+        });
+
+    callbacks.recording_disk_space_low(1_000);
+    callbacks.recording_stopped_for_disk_space(10);
+    assert_eq!(low.load(Ordering::SeqCst), 1_000);
+    assert_eq!(stopped.load(Ordering::SeqCst), 10);
+}
+
+#[test]
+fn test_recording_trim_rejects_invalid_requests() {
+    use screencapturekit::error::SCError;
+    use screencapturekit::recording_output::{Recording, RecordingSegment};
+    use std::path::Path;
+    use std::time::Duration;
+
+    let recording = Recording::from(RecordingSegment {
+        index: 0,
+        path: "/nonexistent/recording.mp4".into(),
+    });
+    assert_eq!(recording.path(), Path::new("/nonexistent/recording.mp4"));
+    assert!(matches!(
+        recording.duration(),
+        Err(SCError::InternalError(_))
+    ));
+
+    let out = Path::new("/tmp/trimmed.mp4");
+    for (start, end) in [
+        (Duration::from_secs(2), Duration::from_secs(1)),
+        (Duration::ZERO, Duration::ZERO),
+    ] {
+        assert!(matches!(
+            recording.trim(start, end, out),
+            Err(SCError::InvalidConfiguration(_))
+        ));
+    }
+    assert!(matches!(
+        recording.trim(Duration::ZERO, Duration::from_secs(1), recording.path()),
+        Err(SCError::InvalidConfiguration(_))
+    ));
+    assert!(matches!(
+        recording.trim(Duration::ZERO, Duration::from_secs(1), out),
+        Err(SCError::InternalError(_))
+    ));
+}
+
+#[test]
+#[cfg(feature = "recording_metadata")]
+fn test_recording_write_chapters_rejects_invalid_requests() {
+    use screencapturekit::error::SCError;
+    use screencapturekit::recording_metadata::Chapter;
+    use screencapturekit::recording_output::Recording;
+    use std::path::Path;
+    use std::time::Duration;
+
+    let recording = Recording::new("/nonexistent/recording.mov");
+    let out = Path::new("/tmp/chaptered.mov");
+    let chapters = [Chapter {
+        title: "Intro".to_string(),
+        offset: Duration::ZERO,
+    }];
+
+    assert!(matches!(
+        recording.write_chapters(&[], out),
+        Err(SCError::InvalidConfiguration(_))
+    ));
+    assert!(matches!(
+        recording.write_chapters(&chapters, recording.path()),
+        Err(SCError::InvalidConfiguration(_))
+    ));
+    // The source can't be read, so its duration can't be checked.
+    assert!(matches!(
+        recording.write_chapters(&chapters, out),
+        Err(SCError::InternalError(_))
+    ));
+}
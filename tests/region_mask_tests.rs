@@ -0,0 +1,214 @@
+//! Region mask tests
+//!
+//! These use synthetic sample buffers, so they run without screen-recording
+//! permission. Synthetic frames carry no `SCStreamFrameInfo`, which is what
+//! the screen-space cases rely on.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use screencapturekit::cg::CGRect;
+use screencapturekit::cm::{CMSampleBuffer, CMSampleBufferExt, CMTime};
+use screencapturekit::cv::frame_copy::{FrameCopyExt, RowLayout};
+use screencapturekit::cv::{CVPixelBuffer, CVPixelBufferLockFlags};
+use screencapturekit::error::SCError;
+use screencapturekit::region_mask::{
+    MaskSpace, MaskedOutput, RegionMask, SCRegionMasker, NOTIFICATION_AREA_SIZE,
+};
+use screencapturekit::shareable_content::SCShareableContent;
+use screencapturekit::stream::output_trait::SCStreamOutputTrait;
+use screencapturekit::stream::output_type::SCStreamOutputType;
+
+const BGRA: u32 = 0x4247_5241;
+
+/// A white 16x8 BGRA frame.
+fn white_frame() -> CMSampleBuffer {
+    let buffer = CVPixelBuffer::create(16, 8, BGRA).expect("create BGRA pixel buffer");
+    {
+        let mut guard = buffer
+            .lock(CVPixelBufferLockFlags::NONE)
+            .expect("lock for writing");
+        let stride = guard.bytes_per_row();
+        let base = guard.base_address_mut().expect("base address").cast::<u8>();
+        unsafe { std::ptr::write_bytes(base, 0xFF, stride * 8) };
+    }
+    CMSampleBuffer::create_for_image_buffer(&buffer, CMTime::new(0, 60), CMTime::new(1, 60))
+        .expect("wrap in sample buffer")
+}
+
+/// Green channel of each pixel, row by row.
+fn greens(sample: &CMSampleBuffer) -> Vec<u8> {
+    let buffer = sample.image_buffer().expect("image buffer");
+    let mut pixels = vec![0u8; buffer.copy_size(RowLayout::Packed).unwrap()];
+    buffer.copy_into(&mut pixels, RowLayout::Packed).unwrap();
+    pixels.chunks(4).map(|pixel| pixel[1]).collect()
+}
+
+#[test]
+fn test_mask_validate() {
+    assert!(RegionMask::default().validate().is_ok());
+    assert!(RegionMask::new(MaskSpace::Pixels)
+        .with_region(CGRect::new(0.0, 0.0, 4.0, 4.0))
+        .validate()
+        .is_ok());
+
+    for bad in [
+        CGRect::new(0.0, 0.0, 0.0, 4.0),
+        CGRect::new(f64::NAN, 0.0, 4.0, 4.0),
+        CGRect::new(0.0, 0.0, f64::INFINITY, 4.0),
+    ] {
+        let mask = RegionMask::default().with_regions([CGRect::new(0.0, 0.0, 1.0, 1.0), bad]);
+        let err = mask.validate().expect_err("bad region");
+        assert!(matches!(err, SCError::InvalidConfiguration(_)));
+        assert!(err.to_string().contains("region 1"));
+        assert!(SCRegionMasker::new(mask).is_err());
+    }
+}
+
+#[test]
+fn test_mask_builder() {
+    let mask = RegionMask::new(MaskSpace::Pixels)
+        .with_region(CGRect::new(1.0, 2.0, 3.0, 4.0))
+        .with_regions([CGRect::new(5.0, 6.0, 7.0, 8.0)]);
+    assert_eq!(mask.space(), MaskSpace::Pixels);
+    assert_eq!(mask.regions().len(), 2);
+    assert_eq!(RegionMask::default().space(), MaskSpace::Screen);
+}
+
+#[test]
+fn test_masker_blacks_out_pixel_region() {
+    let mask = RegionMask::new(MaskSpace::Pixels).with_region(CGRect::new(12.0, 0.0, 4.0, 2.0));
+    let masker = SCRegionMasker::new(mask).expect("create masker");
+    let frame = white_frame();
+    let output = masker
+        .apply(&frame)
+        .expect("mask frame")
+        .expect("region overlaps the frame");
+
+    for (index, green) in greens(&output).iter().enumerate() {
+        let (x, y) = (index % 16, index / 16);
+        let expected = if x >= 12 && y < 2 { 0 } else { 0xFF };
+        assert_eq!(*green, expected, "pixel ({x}, {y})");
+    }
+    // The delivered frame is untouched.
+    assert!(greens(&frame).iter().all(|green| *green == 0xFF));
+    assert_eq!(
+        output.presentation_timestamp(),
+        frame.presentation_timestamp()
+    );
+}
+
+#[test]
+fn test_masker_clips_and_rounds_out() {
+    // Half inside the frame, on fractional pixels: masks (14, 6)..(16, 8).
+    let mask = RegionMask::new(MaskSpace::Pixels).with_region(CGRect::new(14.5, 6.5, 10.0, 10.0));
+    let masker = SCRegionMasker::new(mask).expect("create masker");
+    let output = masker.apply(&white_frame()).unwrap().unwrap();
+    let black = greens(&output)
+        .into_iter()
+        .filter(|green| *green == 0)
+        .count();
+    assert_eq!(black, 2 * 2);
+}
+
+#[test]
+fn test_masker_skips_frames_without_overlap() {
+    let outside =
+        RegionMask::new(MaskSpace::Pixels).with_region(CGRect::new(100.0, 100.0, 4.0, 4.0));
+    let masker = SCRegionMasker::new(outside).expect("create masker");
+    assert!(masker.apply(&white_frame()).unwrap().is_none());
+
+    masker
+        .set_mask(RegionMask::new(MaskSpace::Pixels))
+        .expect("empty mask");
+    assert!(masker.apply(&white_frame()).unwrap().is_none());
+    assert!(masker
+        .set_mask(RegionMask::default().with_region(CGRect::new(0.0, 0.0, -1.0, 0.0)))
+        .is_err());
+    assert!(masker.mask().regions().is_empty());
+}
+
+#[test]
+fn test_screen_mask_needs_frame_geometry() {
+    let mask = RegionMask::default().with_region(CGRect::new(0.0, 0.0, 4.0, 4.0));
+    let masker = SCRegionMasker::new(mask).expect("create masker");
+    assert!(matches!(
+        masker.apply(&white_frame()),
+        Err(SCError::InvalidBuffer(_))
+    ));
+}
+
+#[test]
+fn test_masked_output_drops_unmaskable_frames() {
+    let delivered = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&delivered);
+    let mask = RegionMask::default().with_region(CGRect::new(0.0, 0.0, 4.0, 4.0));
+    let output = MaskedOutput::new(
+        Arc::new(SCRegionMasker::new(mask).expect("create masker")),
+        move |_sample: CMSampleBuffer, _of_type: SCStreamOutputType| {
+            counter.fetch_add(1, Ordering::Relaxed);
+        },
+    );
+
+    output.did_output_sample_buffer(white_frame(), SCStreamOutputType::Screen);
+    assert_eq!(delivered.load(Ordering::Relaxed), 0);
+    assert_eq!(output.dropped_frames(), 1);
+
+    // Other sample types pass through.
+    output.did_output_sample_buffer(white_frame(), SCStreamOutputType::Audio);
+    assert_eq!(delivered.load(Ordering::Relaxed), 1);
+    println!("{output:?}");
+}
+
+#[test]
+fn test_masked_output_delivers_masked_frames() {
+    let delivered = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&delivered);
+    let mask = RegionMask::new(MaskSpace::Pixels).with_region(CGRect::new(0.0, 0.0, 16.0, 8.0));
+    let output = MaskedOutput::new(
+        Arc::new(SCRegionMasker::new(mask).expect("create masker")),
+        move |sample: CMSampleBuffer, _of_type: SCStreamOutputType| {
+            assert!(greens(&sample).iter().all(|green| *green == 0));
+            counter.fetch_add(1, Ordering::Relaxed);
+        },
+    );
+    output.did_output_sample_buffer(white_frame(), SCStreamOutputType::Screen);
+    assert_eq!(delivered.load(Ordering::Relaxed), 1);
+    assert_eq!(output.dropped_frames(), 0);
+}
+
+#[test]
+fn test_notification_area() {
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - no screen recording permission");
+        return;
+    };
+    let Some(display) = content.displays().into_iter().next() else {
+        println!("⚠ Skipping - no display");
+        return;
+    };
+    let frame = display.frame();
+    let area = RegionMask::notification_area(&display);
+    assert!(area.size.width <= NOTIFICATION_AREA_SIZE.width);
+    assert!(area.size.height <= NOTIFICATION_AREA_SIZE.height);
+    assert!((area.origin.x + area.size.width - (frame.origin.x + frame.size.width)).abs() < 1e-9);
+    assert!((area.origin.y - frame.origin.y).abs() < 1e-9);
+
+    let mask = RegionMask::excluding_notifications(&display);
+    assert_eq!(mask.space(), MaskSpace::Screen);
+    assert_eq!(mask.regions(), [area]);
+}
+
+#[test]
+fn test_extra_regions_need_frame_geometry() {
+    // Extra regions are screen regions even on a pixel mask.
+    let masker = SCRegionMasker::new(RegionMask::new(MaskSpace::Pixels)).expect("create masker");
+    assert!(masker.apply(&white_frame()).unwrap().is_none());
+    masker
+        .set_extra_regions(vec![CGRect::new(0.0, 0.0, 4.0, 4.0)])
+        .expect("valid regions");
+    assert!(matches!(
+        masker.apply(&white_frame()),
+        Err(SCError::InvalidBuffer(_))
+    ));
+}
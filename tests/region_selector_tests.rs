@@ -0,0 +1,25 @@
+//! Region selector tests (macOS 14.0+)
+//!
+//! The overlay itself needs a user to drag a rectangle, so these tests only
+//! cover the types exposed to callers.
+
+#![cfg(feature = "macos_14_0")]
+
+use screencapturekit::region_selector::{
+    SCRegionSelection, SCRegionSelectionOutcome, SCRegionSelector,
+};
+
+#[test]
+fn test_region_selector_types_are_send_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<SCRegionSelection>();
+    assert_send_sync::<SCRegionSelectionOutcome>();
+    assert_send_sync::<SCRegionSelector>();
+}
+
+#[test]
+fn test_region_selection_outcome_cancelled() {
+    let outcome = SCRegionSelectionOutcome::Cancelled;
+    assert_eq!(outcome, SCRegionSelectionOutcome::Cancelled);
+    assert!(!matches!(outcome, SCRegionSelectionOutcome::Selected(_)));
+}
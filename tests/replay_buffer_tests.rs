@@ -0,0 +1,120 @@
+//! Replay buffer tests
+
+#![cfg(feature = "replay_buffer")]
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use screencapturekit::prelude::*;
+use screencapturekit::replay_buffer::{ReplayBufferOptions, SCReplayBuffer};
+
+#[test]
+fn test_replay_buffer_options_defaults_and_builders() {
+    let options = ReplayBufferOptions::default();
+    assert_eq!(options.duration(), Duration::from_secs(30));
+    assert_eq!((options.width(), options.height()), (1920, 1080));
+    assert!(options.captures_audio());
+
+    let options = options
+        .with_duration(Duration::from_secs(10))
+        .with_size(1280, 720)
+        .with_fps(60.0)
+        .with_video_bitrate(4_000_000)
+        .with_keyframe_interval(60)
+        .with_captures_audio(false)
+        .with_audio_bitrate(128_000)
+        .with_audio_format(44_100, 1);
+    assert_eq!(options.duration(), Duration::from_secs(10));
+    assert_eq!((options.width(), options.height()), (1280, 720));
+    assert!((options.fps() - 60.0).abs() < f64::EPSILON);
+    assert_eq!(options.video_bitrate(), 4_000_000);
+    assert_eq!(options.keyframe_interval(), 60);
+    assert!(!options.captures_audio());
+    assert_eq!(options.audio_bitrate(), 128_000);
+    assert_eq!(
+        (options.sample_rate(), options.channel_count()),
+        (44_100, 1)
+    );
+}
+
+#[test]
+fn test_replay_buffer_rejects_invalid_options() {
+    assert!(matches!(
+        SCReplayBuffer::new(ReplayBufferOptions::default().with_duration(Duration::ZERO)),
+        Err(SCError::InvalidConfiguration(_))
+    ));
+    assert!(matches!(
+        SCReplayBuffer::new(ReplayBufferOptions::default().with_size(0, 720)),
+        Err(SCError::InvalidDimension { .. })
+    ));
+    assert!(matches!(
+        SCReplayBuffer::new(ReplayBufferOptions::default().with_fps(0.0)),
+        Err(SCError::InvalidConfiguration(_))
+    ));
+}
+
+#[test]
+fn test_empty_replay_buffer_cannot_save() {
+    let replay =
+        SCReplayBuffer::new(ReplayBufferOptions::default().with_size(640, 360)).expect("create");
+    assert_eq!(replay.buffered_duration(), Duration::ZERO);
+    let path = std::env::temp_dir().join("sck-empty-replay.mp4");
+    assert!(matches!(replay.save(&path), Err(SCError::StreamError(_))));
+    assert!(replay.last_error().is_none());
+}
+
+#[test]
+fn test_replay_buffer_saves_recent_capture() {
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - no screen recording permission");
+        return;
+    };
+    let Some(display) = content.displays().into_iter().next() else {
+        println!("⚠ No displays available");
+        return;
+    };
+
+    let filter = SCContentFilter::for_display(&display)
+        .with_excluding_windows(&[])
+        .build();
+    let config = SCStreamConfiguration::new()
+        .with_width(640)
+        .with_height(360)
+        .with_frame_rate(30.0);
+    let replay = Arc::new(
+        SCReplayBuffer::new(
+            ReplayBufferOptions::default()
+                .with_size(640, 360)
+                .with_duration(Duration::from_secs(1))
+                .with_captures_audio(false),
+        )
+        .expect("create replay buffer"),
+    );
+
+    let mut stream = SCStream::new(&filter, &config);
+    let handler = Arc::clone(&replay);
+    stream.add_output_handler(
+        move |sample, of_type| handler.did_output_sample_buffer(sample, of_type),
+        SCStreamOutputType::Screen,
+    );
+    if stream.start_capture().is_err() {
+        println!("⚠ Skipping - capture could not start");
+        return;
+    }
+    std::thread::sleep(Duration::from_secs(3));
+    let _ = stream.stop_capture();
+    assert!(replay.last_error().is_none());
+
+    let path = std::env::temp_dir().join(format!("sck-replay-{}.mp4", std::process::id()));
+    match replay.save(&path) {
+        Ok(length) => {
+            // One GOP of slack on top of the requested second.
+            assert!(length <= Duration::from_secs(3), "clip is {length:?}");
+            assert!(std::fs::metadata(&path).is_ok_and(|m| m.len() > 0));
+            let _ = std::fs::remove_file(&path);
+        }
+        // A static screen may produce no complete frames at all.
+        Err(SCError::StreamError(_)) => println!("⚠ No frames captured"),
+        Err(error) => panic!("save failed: {error}"),
+    }
+}
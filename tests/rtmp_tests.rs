@@ -0,0 +1,119 @@
+//! RTMP publisher tests
+
+#![cfg(feature = "rtmp")]
+
+use std::net::TcpListener;
+use std::time::Duration;
+
+use screencapturekit::error::SCError;
+use screencapturekit::rtmp::{RtmpOptions, SCRtmpPublisher};
+
+#[test]
+fn test_rtmp_options_defaults_and_builders() {
+    let options = RtmpOptions::default();
+    assert_eq!((options.width(), options.height()), (1920, 1080));
+    assert!((options.fps() - 30.0).abs() < f64::EPSILON);
+    assert_eq!(options.sample_rate(), 48_000);
+    assert_eq!(options.channel_count(), 2);
+
+    let options = options
+        .with_size(1280, 720)
+        .with_fps(60.0)
+        .with_video_bitrate(4_500_000)
+        .with_keyframe_interval(120)
+        .with_audio_bitrate(128_000)
+        .with_audio_format(44_100, 1)
+        .with_timeout(Duration::from_secs(3))
+        .with_send_queue(32);
+    assert_eq!((options.width(), options.height()), (1280, 720));
+    assert_eq!(options.video_bitrate(), 4_500_000);
+    assert_eq!(options.keyframe_interval(), 120);
+    assert_eq!(options.audio_bitrate(), 128_000);
+    assert_eq!(
+        (options.sample_rate(), options.channel_count()),
+        (44_100, 1)
+    );
+    assert_eq!(options.timeout(), Duration::from_secs(3));
+    assert_eq!(options.send_queue(), 32);
+}
+
+#[test]
+fn test_rtmp_rejects_unsupported_urls() {
+    for url in [
+        "rtmps://live.example.com/app",
+        "http://live.example.com/app",
+        "rtmp://live.example.com",
+        "rtmp://live.example.com:notaport/app",
+        "rtmp://::1/app",
+        "rtmp://[::1/app",
+        "rtmp://[::1]x/app",
+        "rtmp://[]:1935/app",
+    ] {
+        assert!(
+            matches!(
+                SCRtmpPublisher::connect(url, "key", RtmpOptions::default()),
+                Err(SCError::InvalidConfiguration(_))
+            ),
+            "{url} should be rejected"
+        );
+    }
+}
+
+#[test]
+fn test_rtmp_rejects_invalid_options() {
+    let result = SCRtmpPublisher::connect(
+        "rtmp://127.0.0.1/app",
+        "key",
+        RtmpOptions::default().with_size(0, 720),
+    );
+    assert!(matches!(result, Err(SCError::InvalidDimension { .. })));
+
+    let result = SCRtmpPublisher::connect(
+        "rtmp://127.0.0.1/app",
+        "key",
+        RtmpOptions::default().with_send_queue(0),
+    );
+    assert!(matches!(result, Err(SCError::InvalidConfiguration(_))));
+}
+
+#[test]
+fn test_rtmp_connect_failure_is_stream_error() {
+    // Grab a free port, then close it so the connection is refused.
+    let port = TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    let result = SCRtmpPublisher::connect(
+        &format!("rtmp://127.0.0.1:{port}/app"),
+        "key",
+        RtmpOptions::default()
+            .with_size(640, 360)
+            .with_timeout(Duration::from_secs(1)),
+    );
+    assert!(matches!(result, Err(SCError::StreamError(_))));
+}
+
+#[test]
+fn test_rtmp_connects_to_bracketed_ipv6_host() {
+    let Ok(listener) = TcpListener::bind("[::1]:0") else {
+        println!("⚠ Skipping - no IPv6 loopback");
+        return;
+    };
+    let port = listener.local_addr().unwrap().port();
+    // Accept, then hang up before the handshake completes.
+    let server = std::thread::spawn(move || listener.accept().is_ok());
+
+    let result = SCRtmpPublisher::connect(
+        &format!("rtmp://[::1]:{port}/app"),
+        "key",
+        RtmpOptions::default()
+            .with_size(640, 360)
+            .with_timeout(Duration::from_secs(1)),
+    );
+    assert!(
+        server.join().unwrap(),
+        "publisher never reached [::1]:{port}"
+    );
+    assert!(matches!(result, Err(SCError::StreamError(_))));
+}
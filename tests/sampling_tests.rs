@@ -0,0 +1,93 @@
+//! Pixel sampling tests
+
+use screencapturekit::cg::CGRect;
+use screencapturekit::cv::sampling::PixelBufferSamplingExt;
+use screencapturekit::cv::{CVPixelBuffer, CVPixelBufferLockFlags};
+use screencapturekit::error::SCError;
+
+const BGRA: u32 = 0x4247_5241;
+
+/// A BGRA buffer whose left half is red and right half is blue.
+fn split(width: usize, height: usize) -> CVPixelBuffer {
+    let buffer = CVPixelBuffer::create(width, height, BGRA).expect("create BGRA pixel buffer");
+    {
+        let mut guard = buffer
+            .lock(CVPixelBufferLockFlags::NONE)
+            .expect("lock for writing");
+        let stride = guard.bytes_per_row();
+        let base = guard.base_address_mut().expect("base address").cast::<u8>();
+        for y in 0..height {
+            for x in 0..width {
+                let pixel: [u8; 4] = if x < width / 2 {
+                    [0, 0, 255, 255]
+                } else {
+                    [255, 0, 0, 255]
+                };
+                for (i, byte) in pixel.into_iter().enumerate() {
+                    unsafe { *base.add(y * stride + x * 4 + i) = byte };
+                }
+            }
+        }
+    }
+    buffer
+}
+
+fn close(a: f32, b: f32) -> bool {
+    (a - b).abs() < 0.02
+}
+
+#[test]
+fn test_average_color_of_regions() {
+    let buffer = split(16, 8);
+
+    let [r, g, b, a] = buffer
+        .average_color(CGRect::new(0.0, 0.0, 8.0, 8.0))
+        .expect("left half");
+    assert!(close(r, 1.0) && close(g, 0.0) && close(b, 0.0) && close(a, 1.0));
+
+    let [r, _, b, _] = buffer
+        .average_color(CGRect::new(0.0, 0.0, 16.0, 8.0))
+        .expect("whole frame");
+    assert!(close(r, 0.5) && close(b, 0.5));
+}
+
+#[test]
+fn test_average_color_rejects_bad_regions() {
+    let buffer = split(16, 8);
+    assert!(matches!(
+        buffer.average_color(CGRect::new(0.5, 0.0, 4.0, 4.0)),
+        Err(SCError::InvalidConfiguration(_))
+    ));
+    assert!(matches!(
+        buffer.average_color(CGRect::new(12.0, 0.0, 8.0, 4.0)),
+        Err(SCError::InvalidDimension { .. })
+    ));
+    assert!(matches!(
+        buffer.luminance_histogram_in(CGRect::new(0.0, 0.0, 0.0, 4.0)),
+        Err(SCError::InvalidDimension { .. })
+    ));
+}
+
+#[test]
+fn test_dominant_colors() {
+    let buffer = split(16, 8);
+    let colors = buffer.dominant_colors(4).expect("dominant colors");
+    assert_eq!(colors.len(), 2);
+    assert!(close(colors[0].fraction, 0.5) && close(colors[1].fraction, 0.5));
+    assert!(colors.iter().any(|c| close(c.color[0], 1.0)));
+    assert!(colors.iter().any(|c| close(c.color[2], 1.0)));
+
+    assert!(buffer.dominant_colors(0).unwrap().is_empty());
+    assert_eq!(buffer.dominant_colors(1).unwrap().len(), 1);
+}
+
+#[test]
+fn test_luminance_histogram() {
+    let buffer = split(16, 8);
+    let histogram = buffer.luminance_histogram().expect("histogram");
+    assert_eq!(histogram.total(), 16 * 8);
+    // Pure red and pure blue land in two bins, red brighter than blue.
+    assert_eq!(histogram.bins().iter().filter(|&&n| n > 0).count(), 2);
+    assert!(histogram.percentile(0.25) < histogram.percentile(0.75));
+    assert!(histogram.mean() > 0.0 && histogram.mean() < 0.3);
+}
@@ -0,0 +1,132 @@
+//! Scheduled capture tests
+
+use std::sync::mpsc;
+use std::time::{Duration, SystemTime};
+
+use screencapturekit::error::SCError;
+use screencapturekit::shareable_content::SCShareableContent;
+use screencapturekit::stream::configuration::SCStreamConfiguration;
+use screencapturekit::stream::content_filter::SCContentFilter;
+use screencapturekit::stream::schedule::{
+    CaptureSchedule, ScheduleEvent, SchedulePhase, StopReason,
+};
+use screencapturekit::stream::SCStream;
+
+fn display_stream() -> Option<SCStream> {
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - no screen recording permission");
+        return None;
+    };
+    let Some(display) = content.displays().into_iter().next() else {
+        println!("⚠ No displays available");
+        return None;
+    };
+    let filter = SCContentFilter::for_display(&display)
+        .with_excluding_windows(&[])
+        .build();
+    Some(SCStream::new(&filter, &SCStreamConfiguration::new()))
+}
+
+#[test]
+fn test_schedule_builder() {
+    let start = SystemTime::now() + Duration::from_secs(60);
+    let stop = start + Duration::from_secs(3600);
+    let schedule = CaptureSchedule::new()
+        .with_start_at(start)
+        .with_max_duration(Duration::from_secs(600))
+        .with_stop_at(stop);
+    assert_eq!(schedule.start_at(), Some(start));
+    assert_eq!(schedule.max_duration(), Some(Duration::from_secs(600)));
+    assert_eq!(schedule.stop_at(), Some(stop));
+    assert!(schedule.validate().is_ok());
+    assert_eq!(CaptureSchedule::default(), CaptureSchedule::new());
+}
+
+#[test]
+fn test_schedule_validation() {
+    let now = SystemTime::now();
+    let invalid = [
+        CaptureSchedule::new().with_max_duration(Duration::ZERO),
+        CaptureSchedule::new().with_stop_at(now - Duration::from_secs(1)),
+        CaptureSchedule::new()
+            .with_start_at(now + Duration::from_secs(120))
+            .with_stop_at(now + Duration::from_secs(60)),
+    ];
+    for schedule in invalid {
+        assert!(matches!(
+            schedule.validate(),
+            Err(SCError::InvalidConfiguration(_))
+        ));
+    }
+}
+
+#[test]
+fn test_stop_reason_display() {
+    assert_eq!(
+        StopReason::MaxDuration.to_string(),
+        "maximum duration reached"
+    );
+    assert_eq!(StopReason::Cancelled.to_string(), "cancelled");
+}
+
+#[test]
+fn test_cancel_before_start() {
+    let Some(stream) = display_stream() else {
+        return;
+    };
+    let (tx, rx) = mpsc::channel();
+    let schedule =
+        CaptureSchedule::new().with_start_at(SystemTime::now() + Duration::from_secs(3600));
+    let session = stream
+        .schedule_capture(schedule, move |event| {
+            let _ = tx.send(event);
+        })
+        .expect("valid schedule");
+    assert_eq!(session.phase(), SchedulePhase::Waiting);
+
+    let started = std::time::Instant::now();
+    assert_eq!(session.cancel(), Ok(StopReason::Cancelled));
+    assert!(started.elapsed() < Duration::from_secs(5));
+    assert_eq!(
+        rx.recv_timeout(Duration::from_secs(1)),
+        Ok(ScheduleEvent::Stopped {
+            reason: StopReason::Cancelled,
+            captured: Duration::ZERO,
+        })
+    );
+    assert!(!stream.is_capturing());
+}
+
+#[test]
+fn test_max_duration_stops_stream() {
+    let Some(stream) = display_stream() else {
+        return;
+    };
+    let (tx, rx) = mpsc::channel();
+    let session = stream
+        .schedule_capture(
+            CaptureSchedule::new().with_max_duration(Duration::from_millis(500)),
+            move |event| {
+                let _ = tx.send(event);
+            },
+        )
+        .expect("valid schedule");
+
+    match session.wait() {
+        Ok(reason) => assert_eq!(reason, StopReason::MaxDuration),
+        Err(error) => {
+            println!("⚠ Skipping - capture failed to start: {error}");
+            return;
+        }
+    }
+    assert_eq!(session.phase(), SchedulePhase::Finished);
+    assert!(matches!(rx.recv(), Ok(ScheduleEvent::Started { .. })));
+    match rx.recv() {
+        Ok(ScheduleEvent::Stopped { reason, captured }) => {
+            assert_eq!(reason, StopReason::MaxDuration);
+            assert!(captured >= Duration::from_millis(500));
+        }
+        other => panic!("expected a stop event, got {other:?}"),
+    }
+    assert!(!stream.is_capturing());
+}
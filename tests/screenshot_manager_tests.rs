@@ -1,7 +1,10 @@
 //! Screenshot manager tests (macOS 14.0+)
 
 #![cfg(feature = "macos_14_0")]
+// Exercises the deprecated `SCContentFilter::create()` builder on purpose.
+#![allow(deprecated)]
 
+use screencapturekit::cm::CMSampleBufferExt;
 use screencapturekit::screenshot_manager::{CGImage, CGImageExt, SCScreenshotManager};
 use screencapturekit::shareable_content::SCShareableContent;
 use screencapturekit::stream::configuration::SCStreamConfiguration;
@@ -68,6 +71,39 @@ fn test_capture_sample_buffer() {
     }
 }
 
+#[test]
+fn test_capture_both() {
+    cg_init_for_headless_ci();
+    let content = SCShareableContent::get().expect("Failed to get shareable content");
+    let display = &content.displays()[0];
+
+    let filter = SCContentFilter::for_display(display)
+        .with_excluding_windows(&[])
+        .build();
+
+    let config = SCStreamConfiguration::new()
+        .with_width(640)
+        .with_height(480);
+
+    let Ok(shot) = SCScreenshotManager::capture_both(&filter, &config) else {
+        println!("⚠ Skipping - no screen recording permission");
+        return;
+    };
+    let buffer = shot.sample_buffer().image_buffer().expect("pixel buffer");
+    assert_eq!(shot.image().width(), buffer.width());
+    assert_eq!(shot.image().height(), buffer.height());
+    if let Some(scale) = shot.scale_factor() {
+        assert!(scale >= 1.0);
+    }
+    if let Some(rect) = shot.content_rect() {
+        assert!(rect.size.width > 0.0 && rect.size.height > 0.0);
+    }
+
+    let (image, sample) = shot.into_parts();
+    assert!(image.width() > 0);
+    assert!(sample.presentation_timestamp().is_valid());
+}
+
 #[test]
 fn test_cgimage_send_sync() {
     fn assert_send<T: Send>() {}
@@ -228,6 +264,103 @@ fn test_cgimage_data_into_buffer_apis() {
     );
 }
 
+#[test]
+fn test_cgimage_crop_resize_and_rows() {
+    use screencapturekit::cg::CGRect;
+    use screencapturekit::error::SCError;
+
+    cg_init_for_headless_ci();
+    let Ok(content) = SCShareableContent::get() else {
+        return;
+    };
+    let display = &content.displays()[0];
+    let filter = SCContentFilter::create()
+        .with_display(display)
+        .with_excluding_windows(&[])
+        .build();
+    let config = SCStreamConfiguration::new().with_width(64).with_height(64);
+    let Ok(image) = SCScreenshotManager::capture_image(&filter, &config) else {
+        return;
+    };
+
+    let cropped = image
+        .crop(CGRect::new(8.0, 8.0, 16.0, 12.0))
+        .expect("crop inside bounds");
+    assert_eq!((cropped.width(), cropped.height()), (16, 12));
+
+    let resized = image.resized(32, 20).expect("resize");
+    assert_eq!((resized.width(), resized.height()), (32, 20));
+
+    assert!(matches!(
+        image.crop(CGRect::new(0.0, 0.0, 0.0, 10.0)),
+        Err(SCError::InvalidDimension { .. })
+    ));
+    assert!(image
+        .crop(CGRect::new(10_000.0, 10_000.0, 4.0, 4.0))
+        .is_err());
+    assert!(matches!(
+        image.resized(0, 10),
+        Err(SCError::InvalidDimension { .. })
+    ));
+
+    let pixels = cropped.rgba_pixels().expect("rgba_pixels");
+    assert_eq!(pixels.bytes_per_row(), 16 * 4);
+    assert_eq!(pixels.rows().count(), 12);
+    assert!(pixels.rows().all(|row| row.len() == 16 * 4));
+    assert_eq!(pixels.row(11).map(<[u8]>::len), Some(16 * 4));
+    assert!(pixels.row(12).is_none());
+    assert!(pixels.pixel(15, 11).is_some());
+    assert!(pixels.pixel(16, 0).is_none());
+}
+
+#[test]
+fn test_cgimage_alpha_info_from_raw() {
+    use screencapturekit::screenshot_manager::CGImageAlphaInfo;
+
+    assert_eq!(CGImageAlphaInfo::from_raw(0), CGImageAlphaInfo::None);
+    // kCGImageAlphaPremultipliedFirst | kCGBitmapByteOrder32Little
+    let bgra = CGImageAlphaInfo::from_raw(2 | (2 << 12));
+    assert_eq!(bgra, CGImageAlphaInfo::PremultipliedFirst);
+    assert!(bgra.has_alpha() && bgra.is_premultiplied());
+    assert!(!CGImageAlphaInfo::NoneSkipFirst.has_alpha());
+    assert!(!CGImageAlphaInfo::Last.is_premultiplied());
+    assert_eq!(CGImageAlphaInfo::from_raw(9), CGImageAlphaInfo::Unknown(9));
+}
+
+#[test]
+fn test_cgimage_format_metadata() {
+    use screencapturekit::screenshot_manager::CGImageAlphaInfo;
+
+    cg_init_for_headless_ci();
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - no screen recording permission");
+        return;
+    };
+    let display = &content.displays()[0];
+    let filter = SCContentFilter::for_display(display)
+        .with_excluding_windows(&[])
+        .build();
+    let config = SCStreamConfiguration::new().with_width(64).with_height(64);
+    let Ok(image) = SCScreenshotManager::capture_image(&filter, &config) else {
+        return;
+    };
+    assert!(image.bits_per_component() >= 8);
+    assert!(image.bits_per_pixel() >= image.bits_per_component() * image.color_components());
+    assert!(image.bytes_per_row() >= image.width() * image.bits_per_pixel() / 8);
+
+    // Resizing always renders into an 8-bit premultiplied BGRA context.
+    let resized = image.resized(16, 16).expect("resize");
+    assert_eq!(resized.bits_per_component(), 8);
+    assert_eq!(resized.bits_per_pixel(), 32);
+    assert!(resized.bytes_per_row() >= 16 * 4);
+    assert_eq!(resized.alpha_info(), CGImageAlphaInfo::PremultipliedFirst);
+    assert!(!resized.has_float_components());
+    assert_eq!(resized.color_components(), 3);
+    if let Some(name) = resized.color_space_name() {
+        assert!(name.starts_with("kCGColorSpace"), "unexpected name {name}");
+    }
+}
+
 // MARK: - New Screenshot Features (macOS 15.2+)
 
 #[test]
@@ -386,6 +519,60 @@ fn test_screenshot_dynamic_range_enum() {
     assert_eq!(default, SCScreenshotDynamicRange::SDR);
 }
 
+#[test]
+#[cfg(feature = "macos_26_0")]
+fn test_screenshot_configuration_getters_round_trip() {
+    use screencapturekit::screenshot_manager::{
+        SCScreenshotConfiguration, SCScreenshotDisplayIntent, SCScreenshotDynamicRange,
+    };
+
+    let config = SCScreenshotConfiguration::new()
+        .with_width(640)
+        .with_height(480)
+        .with_shows_cursor(true)
+        .with_ignore_shadows(true)
+        .with_include_child_windows(true)
+        .with_display_intent(SCScreenshotDisplayIntent::Local)
+        .with_dynamic_range(SCScreenshotDynamicRange::BothSDRAndHDR);
+
+    assert_eq!(config.width(), 640);
+    assert_eq!(config.height(), 480);
+    assert!(config.shows_cursor());
+    assert!(config.ignore_shadows());
+    assert!(config.include_child_windows());
+    assert_eq!(config.display_intent(), SCScreenshotDisplayIntent::Local);
+    assert_eq!(
+        config.dynamic_range(),
+        SCScreenshotDynamicRange::BothSDRAndHDR
+    );
+}
+
+#[test]
+#[cfg(feature = "macos_26_0")]
+fn test_screenshot_dynamic_range_outputs() {
+    use screencapturekit::screenshot_manager::{
+        SCScreenshotDisplayIntent, SCScreenshotDynamicRange,
+    };
+
+    assert!(SCScreenshotDynamicRange::SDR.includes_sdr());
+    assert!(!SCScreenshotDynamicRange::SDR.includes_hdr());
+    assert!(!SCScreenshotDynamicRange::HDR.includes_sdr());
+    assert!(SCScreenshotDynamicRange::HDR.includes_hdr());
+    assert!(SCScreenshotDynamicRange::BothSDRAndHDR.includes_sdr());
+    assert!(SCScreenshotDynamicRange::BothSDRAndHDR.includes_hdr());
+
+    assert_eq!(
+        SCScreenshotDynamicRange::from_raw(2),
+        Some(SCScreenshotDynamicRange::BothSDRAndHDR)
+    );
+    assert_eq!(SCScreenshotDynamicRange::from_raw(3), None);
+    assert_eq!(
+        SCScreenshotDisplayIntent::from_raw(1),
+        Some(SCScreenshotDisplayIntent::Local)
+    );
+    assert_eq!(SCScreenshotDisplayIntent::from_raw(-1), None);
+}
+
 #[test]
 #[cfg(feature = "macos_26_0")]
 fn test_capture_screenshot_with_configuration() {
@@ -461,3 +648,98 @@ fn test_capture_screenshot_in_rect_with_configuration() {
         }
     }
 }
+
+#[test]
+fn test_image_format_mime_types() {
+    use screencapturekit::screenshot_manager::ImageFormat;
+
+    assert_eq!(ImageFormat::Png.mime_type(), "image/png");
+    assert_eq!(ImageFormat::Jpeg(0.8).mime_type(), "image/jpeg");
+    assert_eq!(ImageFormat::Heic(0.9).mime_type(), "image/heic");
+}
+
+#[test]
+fn test_encode_and_data_url_from_sample_buffer() {
+    use screencapturekit::cm::{CMSampleBuffer, CMSampleBufferExt, CMTime};
+    use screencapturekit::cv::CVPixelBuffer;
+    use screencapturekit::screenshot_manager::{CMSampleBufferImageExt, ImageFormat};
+
+    let pixel_buffer = CVPixelBuffer::create(32, 16, 0x4247_5241).expect("create BGRA buffer");
+    let sample = CMSampleBuffer::create_for_image_buffer(&pixel_buffer, CMTime::ZERO, CMTime::ZERO)
+        .expect("wrap in sample buffer");
+
+    let png = sample.encode_image(ImageFormat::Png).expect("encode PNG");
+    assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+
+    // Base64 of the PNG signature is "iVBORw0KGgo".
+    let url = sample.to_data_url(ImageFormat::Png).expect("PNG data URL");
+    assert!(url.starts_with("data:image/png;base64,iVBORw0KGgo"));
+    let payload = &url["data:image/png;base64,".len()..];
+    assert_eq!(payload.len(), png.len().div_ceil(3) * 4);
+
+    let image = sample.cg_image().expect("cg_image");
+    let jpeg = image.encode(ImageFormat::Jpeg(0.7)).expect("encode JPEG");
+    assert_eq!(&jpeg[..2], &[0xFF, 0xD8]);
+    assert!(image
+        .to_data_url(ImageFormat::Jpeg(0.7))
+        .expect("JPEG data URL")
+        .starts_with("data:image/jpeg;base64,/9j/"));
+}
+
+#[test]
+fn test_window_capture_options_output_size() {
+    use screencapturekit::cg::CGRect;
+    use screencapturekit::screenshot_manager::WindowCaptureOptions;
+
+    let frame = CGRect::new(100.0, 100.0, 1200.0, 800.0);
+    assert_eq!(WindowCaptureOptions::new().output_size(frame), (1200, 800));
+
+    let retina = WindowCaptureOptions::new().with_scale(2.0);
+    assert_eq!(retina.output_size(frame), (2400, 1600));
+
+    // Fit inside the box, keeping the 3:2 aspect ratio
+    let thumbnail = retina.with_max_size(480, 300);
+    assert_eq!(thumbnail.output_size(frame), (450, 300));
+
+    // Small windows are not enlarged
+    let small = CGRect::new(0.0, 0.0, 100.0, 50.0);
+    assert_eq!(thumbnail.output_size(small), (200, 100));
+}
+
+#[test]
+fn test_capture_windows_rejects_invalid_options() {
+    use screencapturekit::screenshot_manager::WindowCaptureOptions;
+
+    for options in [
+        WindowCaptureOptions::new().with_max_concurrency(0),
+        WindowCaptureOptions::new().with_scale(0.0),
+        WindowCaptureOptions::new().with_scale(f64::NAN),
+        WindowCaptureOptions::new().with_max_size(0, 300),
+    ] {
+        assert!(SCScreenshotManager::capture_windows(&[], &options).is_err());
+    }
+    let captured = SCScreenshotManager::capture_windows(&[], &WindowCaptureOptions::new());
+    assert!(captured.unwrap().is_empty());
+}
+
+#[test]
+fn test_capture_all_windows() {
+    use screencapturekit::screenshot_manager::WindowCaptureOptions;
+
+    let options = WindowCaptureOptions::new()
+        .with_max_size(320, 240)
+        .with_max_concurrency(4);
+    let captured = match SCScreenshotManager::capture_all_windows(&options) {
+        Ok(captured) => captured,
+        Err(e) => {
+            println!("⚠ Skipping - window capture failed: {e}");
+            return;
+        }
+    };
+    for (window, image) in &captured {
+        assert!(window.is_on_screen());
+        assert_eq!(window.window_layer(), 0);
+        assert!(image.width() <= 320 && image.height() <= 240);
+    }
+    println!("✓ Captured {} windows", captured.len());
+}
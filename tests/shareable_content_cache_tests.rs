@@ -0,0 +1,44 @@
+//! `SCShareableContent::get_cached` tests
+
+use std::time::Duration;
+
+use screencapturekit::shareable_content::SCShareableContent;
+
+// Initialize CoreGraphics to prevent CGS_REQUIRE_INIT crashes in CI
+fn cg_init_for_headless_ci() {
+    extern "C" {
+        fn sc_initialize_core_graphics();
+    }
+    unsafe { sc_initialize_core_graphics() }
+}
+
+// The cache is process-wide, so the scenarios run in one test to avoid
+// interference between parallel test threads.
+#[test]
+fn test_get_cached_reuses_and_invalidates() {
+    cg_init_for_headless_ci();
+
+    SCShareableContent::invalidate_cache();
+    assert!(SCShareableContent::cache_age().is_none());
+
+    let first = SCShareableContent::get_cached(Duration::from_secs(60))
+        .expect("Failed to get shareable content");
+    let age = SCShareableContent::cache_age().expect("content should be cached");
+    assert!(age < Duration::from_secs(60));
+
+    let second = SCShareableContent::get_cached(Duration::from_secs(60)).unwrap();
+    assert_eq!(first, second, "fresh cache should return the same object");
+
+    let refetched = SCShareableContent::get_cached(Duration::ZERO).unwrap();
+    assert_ne!(first, refetched, "zero TTL should always fetch");
+
+    SCShareableContent::invalidate_cache();
+    assert!(SCShareableContent::cache_age().is_none());
+    let after_invalidate = SCShareableContent::get_cached(Duration::from_secs(60)).unwrap();
+    assert_ne!(refetched, after_invalidate);
+    assert_eq!(
+        after_invalidate.displays().len(),
+        first.displays().len(),
+        "display list should be stable across fetches"
+    );
+}
@@ -1,6 +1,8 @@
 //! `SCShareableContentInfo` tests (macOS 14.0+)
 
 #![cfg(feature = "macos_14_0")]
+// Exercises the deprecated `SCContentFilter::create()` builder on purpose.
+#![allow(deprecated)]
 
 use screencapturekit::shareable_content::{SCShareableContent, SCShareableContentInfo};
 use screencapturekit::stream::content_filter::SCContentFilter;
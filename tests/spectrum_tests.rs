@@ -0,0 +1,119 @@
+//! Spectrum analyzer tests
+
+#![cfg(feature = "spectrum")]
+
+use std::f32::consts::TAU;
+
+use screencapturekit::error::SCError;
+use screencapturekit::spectrum::{SpectrumAnalyzer, WindowFunction};
+
+const RATE: u32 = 48_000;
+
+/// `count` samples of a sine at `hz` with `amplitude`.
+fn sine(hz: f32, amplitude: f32, count: u16) -> Vec<f32> {
+    (0..count)
+        .map(|i| amplitude * (TAU * hz * f32::from(i) / 48_000.0).sin())
+        .collect()
+}
+
+#[test]
+fn test_rejects_bad_sizes() {
+    for size in [0, 2, 1000] {
+        assert!(matches!(
+            SpectrumAnalyzer::new(size, RATE),
+            Err(SCError::InvalidConfiguration(_))
+        ));
+    }
+    assert!(SpectrumAnalyzer::new(1024, 0).is_err());
+    let analyzer = SpectrumAnalyzer::new(1024, RATE).expect("analyzer");
+    assert_eq!(analyzer.fft_size(), 1024);
+    assert_eq!(analyzer.window(), WindowFunction::Hann);
+}
+
+#[test]
+fn test_full_scale_sine_peaks_at_zero_db() {
+    // 1500 Hz falls exactly on bin 32 of a 1024-point FFT at 48 kHz.
+    let mut analyzer = SpectrumAnalyzer::new(1024, RATE).expect("analyzer");
+    analyzer.push_samples(&sine(1500.0, 1.0, 1024));
+    let spectrum = analyzer.spectrum();
+
+    assert_eq!(spectrum.magnitudes_db().len(), 513);
+    assert!((spectrum.bin_width() - 46.875).abs() < 1e-3);
+    let (hz, db) = spectrum.peak().expect("peak");
+    assert!((hz - 1500.0).abs() < 1.0, "peak at {hz} Hz");
+    assert!(db.abs() < 0.1, "peak level {db} dB");
+    // Far from the tone the Hann window keeps leakage low.
+    assert!(spectrum.magnitudes_db()[200] < -60.0);
+}
+
+#[test]
+fn test_half_amplitude_reads_minus_six_db() {
+    let mut analyzer = SpectrumAnalyzer::new(1024, RATE)
+        .expect("analyzer")
+        .with_window(WindowFunction::Rectangular);
+    analyzer.push_samples(&sine(1500.0, 0.5, 1024));
+    let (_, db) = analyzer.spectrum().peak().expect("peak");
+    assert!((db + 6.02).abs() < 0.1, "peak level {db} dB");
+}
+
+#[test]
+fn test_silence_sits_at_the_floor() {
+    let mut analyzer = SpectrumAnalyzer::new(256, RATE)
+        .expect("analyzer")
+        .with_floor_db(-80.0);
+    let spectrum = analyzer.spectrum();
+    assert!(spectrum
+        .magnitudes_db()
+        .iter()
+        .all(|&db| (db + 80.0).abs() < f32::EPSILON));
+    assert!(spectrum.normalize(-80.0).abs() < f32::EPSILON);
+    assert!((spectrum.normalize(0.0) - 1.0).abs() < f32::EPSILON);
+    assert!((spectrum.normalize(-40.0) - 0.5).abs() < 1e-6);
+}
+
+#[test]
+fn test_window_reduces_leakage() {
+    // Halfway between two bins, the worst case for leakage.
+    let tone = sine(1523.4375, 1.0, 1024);
+    let far_bin = |window| {
+        let mut analyzer = SpectrumAnalyzer::new(1024, RATE)
+            .expect("analyzer")
+            .with_window(window);
+        analyzer.push_samples(&tone);
+        analyzer.spectrum().magnitudes_db()[300]
+    };
+    assert!(far_bin(WindowFunction::Blackman) < far_bin(WindowFunction::Rectangular) - 20.0);
+}
+
+#[test]
+fn test_bands_follow_the_tone() {
+    let mut analyzer = SpectrumAnalyzer::new(2048, RATE).expect("analyzer");
+    analyzer.push_samples(&sine(4000.0, 1.0, 2048));
+    let spectrum = analyzer.spectrum();
+    let bands = spectrum.bands(16, 50.0, 16_000.0);
+    assert_eq!(bands.len(), 16);
+
+    let loudest = bands
+        .iter()
+        .enumerate()
+        .max_by(|a, b| a.1.total_cmp(b.1))
+        .map(|(index, _)| index)
+        .expect("bands");
+    // 4 kHz lies in band 12 of 16 log-spaced bands from 50 Hz to 16 kHz.
+    assert_eq!(loudest, 12);
+    assert!(bands[0] < -60.0);
+    assert!(spectrum.bands(0, 50.0, 16_000.0).is_empty());
+}
+
+#[test]
+fn test_history_keeps_only_the_latest_block() {
+    let mut analyzer = SpectrumAnalyzer::new(512, RATE).expect("analyzer");
+    analyzer.push_samples(&sine(3000.0, 1.0, 4096));
+    analyzer.push_samples(&sine(750.0, 1.0, 512));
+    let (hz, _) = analyzer.spectrum().peak().expect("peak");
+    assert!((hz - 750.0).abs() < 1.0, "peak at {hz} Hz");
+
+    analyzer.reset();
+    let (_, db) = analyzer.spectrum().peak().expect("peak");
+    assert!((db - SpectrumAnalyzer::DEFAULT_FLOOR_DB).abs() < f32::EPSILON);
+}
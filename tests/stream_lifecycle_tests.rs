@@ -2,6 +2,9 @@
 //!
 //! Tests for `SCStream` lifecycle and operations.
 
+// Exercises the deprecated `SCContentFilter::create()` builder on purpose.
+#![allow(deprecated)]
+
 use screencapturekit::prelude::*;
 
 #[test]
@@ -236,3 +239,327 @@ fn test_stream_debug_display() {
 
     println!("✓ Debug and Display traits work");
 }
+
+#[test]
+fn test_stream_capture_state_tracking() {
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - no screen recording permission");
+        return;
+    };
+
+    if content.displays().is_empty() {
+        println!("⚠ No displays available");
+        return;
+    }
+
+    let display = &content.displays()[0];
+    let filter = SCContentFilter::create().with_display(display).build();
+    let config = SCStreamConfiguration::default();
+
+    let mut stream = SCStream::new(&filter, &config);
+    stream.add_output_handler(|_, _| {}, SCStreamOutputType::Screen);
+    assert!(!stream.is_capturing());
+    assert!(stream.capture_started_at().is_none());
+    assert_eq!(stream.state(), SCStreamState::Idle);
+
+    if stream.start_capture().is_err() {
+        println!("⚠ Skipping - capture could not start");
+        return;
+    }
+    assert!(stream.is_capturing());
+    assert_eq!(stream.state(), SCStreamState::Running);
+    assert!(stream.capture_started_at().is_some());
+    assert!(SCStream::is_capture_indicator_visible());
+
+    // Dropping another handle must not lose the original's state.
+    let active = SCStream::active_streams();
+    assert!(!active.is_empty());
+    drop(active);
+    drop(stream.clone());
+    assert!(stream.is_capturing());
+
+    stream.stop_capture().expect("stop capture");
+    assert!(!stream.is_capturing());
+    assert_eq!(stream.state(), SCStreamState::Stopped);
+}
+
+#[test]
+fn test_stream_rejects_calls_in_wrong_state() {
+    use screencapturekit::stream::delegate_trait::StreamCallbacks;
+    use std::sync::{Arc, Mutex};
+
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - no screen recording permission");
+        return;
+    };
+
+    if content.displays().is_empty() {
+        println!("⚠ No displays available");
+        return;
+    }
+
+    let display = &content.displays()[0];
+    let filter = SCContentFilter::for_display(display).build();
+    let config = SCStreamConfiguration::default();
+
+    let changes = Arc::new(Mutex::new(Vec::new()));
+    let recorded = Arc::clone(&changes);
+    let delegate = StreamCallbacks::new().on_state_change(move |old, new| {
+        recorded.lock().unwrap().push((old, new));
+    });
+    let mut stream = SCStream::new_with_delegate(&filter, &config, delegate);
+    stream.add_output_handler(|_, _| {}, SCStreamOutputType::Screen);
+
+    // Stopping a stream that never started fails before reaching the OS
+    let err = stream.stop_capture().unwrap_err();
+    assert!(matches!(
+        err,
+        SCError::InvalidState {
+            state: SCStreamState::Idle,
+            ..
+        }
+    ));
+    assert!(changes.lock().unwrap().is_empty());
+
+    if stream.start_capture().is_err() {
+        println!("⚠ Skipping - capture could not start");
+        return;
+    }
+    assert!(matches!(
+        stream.start_capture(),
+        Err(SCError::InvalidState {
+            state: SCStreamState::Running,
+            ..
+        })
+    ));
+
+    let _ = stream.update_configuration(&config);
+    stream.stop_capture().expect("stop capture");
+
+    assert_eq!(
+        *changes.lock().unwrap(),
+        vec![
+            (SCStreamState::Idle, SCStreamState::Starting),
+            (SCStreamState::Starting, SCStreamState::Running),
+            (SCStreamState::Running, SCStreamState::Updating),
+            (SCStreamState::Updating, SCStreamState::Running),
+            (SCStreamState::Running, SCStreamState::Stopping),
+            (SCStreamState::Stopping, SCStreamState::Stopped),
+        ]
+    );
+}
+
+#[test]
+fn test_context_output_handler_identifies_stream() {
+    use screencapturekit::stream::StreamRef;
+    use std::sync::mpsc;
+
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - no screen recording permission");
+        return;
+    };
+
+    if content.displays().is_empty() {
+        println!("⚠ No displays available");
+        return;
+    }
+
+    let display = &content.displays()[0];
+    let filter = SCContentFilter::for_display(display).build();
+    let config = SCStreamConfiguration::default();
+
+    let mut stream = SCStream::new(&filter, &config);
+    assert_eq!(stream.id(), stream.clone().id());
+
+    let (tx, rx) = mpsc::sync_channel(1);
+    let tx = std::sync::Mutex::new(tx);
+    let handler_id = stream
+        .add_output_handler_with_context(
+            move |_sample: CMSampleBuffer,
+                  _of_type: SCStreamOutputType,
+                  stream: &StreamRef<'_>,
+                  id: usize| {
+                let upgraded = stream.upgrade().map(|s| s.id());
+                let _ = tx.lock().unwrap().try_send((stream.id(), id, upgraded));
+            },
+            SCStreamOutputType::Screen,
+        )
+        .expect("register handler");
+
+    if stream.start_capture().is_err() {
+        println!("⚠ Skipping - capture could not start");
+        return;
+    }
+    let received = rx.recv_timeout(std::time::Duration::from_secs(5));
+    stream.stop_capture().expect("stop capture");
+
+    let (stream_id, id, upgraded) = received.expect("no frame delivered");
+    assert_eq!(stream_id, stream.id());
+    assert_eq!(id, handler_id);
+    assert_eq!(upgraded, Some(stream.id()));
+}
+
+#[test]
+fn test_drop_stops_capture_and_detaches_handlers() {
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - no screen recording permission");
+        return;
+    };
+
+    if content.displays().is_empty() {
+        println!("⚠ No displays available");
+        return;
+    }
+
+    let display = &content.displays()[0];
+    let filter = SCContentFilter::for_display(display).build();
+    let config = SCStreamConfiguration::default();
+
+    let mut stream = SCStream::new(&filter, &config);
+    assert_eq!(stream.drop_timeout(), SCStream::DEFAULT_DROP_TIMEOUT);
+    stream.clone().set_drop_timeout(Duration::from_secs(5));
+    assert_eq!(stream.drop_timeout(), Duration::from_secs(5));
+
+    let frames = Arc::new(AtomicUsize::new(0));
+    let dropped = Arc::new(AtomicBool::new(false));
+    let late_calls = Arc::new(AtomicUsize::new(0));
+    {
+        let (frames, dropped, late_calls) = (frames.clone(), dropped.clone(), late_calls.clone());
+        stream.add_output_handler(
+            move |_, _| {
+                frames.fetch_add(1, Ordering::SeqCst);
+                // Slow enough that drop usually lands mid-callback.
+                std::thread::sleep(Duration::from_millis(20));
+                if dropped.load(Ordering::SeqCst) {
+                    late_calls.fetch_add(1, Ordering::SeqCst);
+                }
+            },
+            SCStreamOutputType::Screen,
+        );
+    }
+
+    if stream.start_capture().is_err() {
+        println!("⚠ Skipping - capture could not start");
+        return;
+    }
+    let deadline = std::time::Instant::now() + Duration::from_secs(5);
+    while frames.load(Ordering::SeqCst) == 0 && std::time::Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+
+    let clone = stream.clone();
+    drop(stream);
+    // Another handle is alive: capture continues.
+    assert!(clone.is_capturing());
+
+    drop(clone);
+    dropped.store(true, Ordering::SeqCst);
+
+    std::thread::sleep(Duration::from_millis(200));
+    assert_eq!(
+        late_calls.load(Ordering::SeqCst),
+        0,
+        "handler ran after the last stream handle was dropped"
+    );
+    // The handler and its captures are gone with the stream.
+    assert_eq!(Arc::strong_count(&dropped), 1);
+}
+
+#[test]
+#[cfg(feature = "macos_15_0")]
+fn test_set_microphone_device_updates_configuration() {
+    use screencapturekit::audio_devices::AudioInputDevice;
+
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - no screen recording permission");
+        return;
+    };
+    let Some(display) = content.displays().into_iter().next() else {
+        println!("⚠ No displays available");
+        return;
+    };
+    let Some(device) = AudioInputDevice::list().into_iter().next() else {
+        println!("⚠ Skipping - no microphone");
+        return;
+    };
+
+    let filter = SCContentFilter::for_display(&display).build();
+    let config = SCStreamConfiguration::default().with_captures_microphone(true);
+    let mut stream = SCStream::new(&filter, &config);
+    stream.add_output_handler(|_, _| {}, SCStreamOutputType::Microphone);
+    if stream.start_capture().is_err() {
+        println!("⚠ Skipping - capture could not start");
+        return;
+    }
+
+    stream
+        .set_microphone_device(Some(&device.id))
+        .expect("select microphone");
+    assert_eq!(
+        stream.configuration().microphone_capture_device_id(),
+        Some(device.id)
+    );
+
+    stream
+        .set_microphone_device(None)
+        .expect("default microphone");
+    assert_eq!(stream.configuration().microphone_capture_device_id(), None);
+    let _ = stream.stop_capture();
+}
+
+#[test]
+fn test_concurrent_partial_updates_keep_each_change() {
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - no screen recording permission");
+        return;
+    };
+    let Some(display) = content.displays().into_iter().next() else {
+        println!("⚠ No displays available");
+        return;
+    };
+
+    let filter = SCContentFilter::for_display(&display).build();
+    let config = SCStreamConfiguration::new()
+        .with_width(640)
+        .with_height(360)
+        .with_fps(60);
+    let stream = SCStream::new(&filter, &config);
+    if stream.start_capture().is_err() {
+        println!("⚠ Skipping - capture could not start");
+        return;
+    }
+
+    let resizer = stream.clone();
+    std::thread::scope(|scope| {
+        let rate = scope.spawn(|| {
+            for fps in [30, 20, 15] {
+                stream
+                    .update_configuration_with(|config| {
+                        config.set_fps(fps);
+                    })
+                    .expect("update frame rate");
+            }
+        });
+        let size = scope.spawn(|| {
+            for (width, height) in [(800, 450), (1024, 576), (1280, 720)] {
+                resizer
+                    .update_configuration_with(|config| {
+                        config.set_width(width);
+                        config.set_height(height);
+                    })
+                    .expect("update size");
+            }
+        });
+        rate.join().expect("frame rate thread");
+        size.join().expect("size thread");
+    });
+
+    let applied = stream.configuration();
+    assert_eq!(applied.fps(), 15);
+    assert_eq!((applied.width(), applied.height()), (1280, 720));
+    let _ = stream.stop_capture();
+}
@@ -88,8 +88,7 @@ fn test_two_concurrent_streams_route_samples_independently() {
         return;
     };
 
-    let filter = SCContentFilter::create()
-        .with_display(display)
+    let filter = SCContentFilter::for_display(display)
         .with_excluding_windows(&[])
         .build();
 
@@ -172,3 +171,142 @@ fn test_two_concurrent_streams_route_samples_independently() {
          under the old global routing one stream would see ~2× the other's frames"
     );
 }
+
+/// `SCStream::outputs` reports every attached handler with its type and
+/// the queue it runs on, and stops listing handlers once removed.
+#[test]
+fn test_outputs_lists_attached_handlers() {
+    let Ok(content) = SCShareableContent::get() else {
+        eprintln!("skip: screen-recording permission required");
+        return;
+    };
+    let displays = content.displays();
+    let Some(display) = displays.first() else {
+        eprintln!("skip: no displays available");
+        return;
+    };
+    let filter = SCContentFilter::for_display(display)
+        .with_excluding_windows(&[])
+        .build();
+    let mut config = SCStreamConfiguration::default();
+    config.set_captures_audio(true);
+
+    let mut stream = SCStream::new(&filter, &config);
+    assert!(stream.outputs().is_empty());
+    assert!(stream
+        .output_queue_label(SCStreamOutputType::Screen)
+        .is_none());
+
+    let (tagged, _count) = TaggedHandler::new("screen");
+    let screen = stream
+        .add_output_handler(tagged, SCStreamOutputType::Screen)
+        .expect("screen handler");
+    let queue = screencapturekit::dispatch_queue::DispatchQueue::new(
+        "com.screencapturekit.tests.audio",
+        screencapturekit::dispatch_queue::DispatchQoS::Default,
+    );
+    let audio = stream
+        .add_output_handler_with_queue(|_, _| {}, SCStreamOutputType::Audio, Some(&queue))
+        .expect("audio handler");
+
+    let outputs = stream.outputs();
+    assert_eq!(outputs.len(), 2);
+    assert_eq!(outputs[0].id(), screen);
+    assert_eq!(outputs[0].of_type(), SCStreamOutputType::Screen);
+    assert!(outputs[0].handler_type().ends_with("TaggedHandler"));
+    assert_eq!(
+        outputs[0].queue_label(),
+        Some("com.screencapturekit.output.0")
+    );
+    assert_eq!(outputs[1].id(), audio);
+    assert_eq!(outputs[1].of_type(), SCStreamOutputType::Audio);
+    assert_eq!(
+        outputs[1].queue_label(),
+        Some("com.screencapturekit.tests.audio")
+    );
+    assert_eq!(stream.clone().outputs(), outputs);
+
+    assert!(stream.remove_output_handler(screen, SCStreamOutputType::Screen));
+    let outputs = stream.outputs();
+    assert_eq!(outputs.len(), 1);
+    assert_eq!(outputs[0].id(), audio);
+    assert!(stream
+        .output_queue_label(SCStreamOutputType::Screen)
+        .is_none());
+}
+
+/// `SCStream::set_output_queue` picks the queue for handlers added later and
+/// moves an output type that already has handlers.
+#[test]
+fn test_output_queue_per_type() {
+    use screencapturekit::dispatch_queue::{DispatchQoS, DispatchQueue};
+
+    let Ok(content) = SCShareableContent::get() else {
+        eprintln!("skip: screen-recording permission required");
+        return;
+    };
+    let displays = content.displays();
+    let Some(display) = displays.first() else {
+        eprintln!("skip: no displays available");
+        return;
+    };
+    let filter = SCContentFilter::for_display(display)
+        .with_excluding_windows(&[])
+        .build();
+    let mut config = SCStreamConfiguration::default();
+    config.set_captures_audio(true);
+
+    let mut stream = SCStream::new(&filter, &config);
+    let video = DispatchQueue::new("com.screencapturekit.tests.video", DispatchQoS::Utility);
+    stream
+        .set_output_queue(SCStreamOutputType::Screen, &video)
+        .expect("set screen queue");
+    // Nothing is registered until a handler is added.
+    assert!(stream
+        .output_queue_label(SCStreamOutputType::Screen)
+        .is_none());
+
+    stream
+        .add_output_handler(|_, _| {}, SCStreamOutputType::Screen)
+        .expect("screen handler");
+    stream
+        .add_output_handler(|_, _| {}, SCStreamOutputType::Audio)
+        .expect("audio handler");
+    assert_eq!(
+        stream
+            .output_queue_label(SCStreamOutputType::Screen)
+            .as_deref(),
+        Some("com.screencapturekit.tests.video")
+    );
+    assert_eq!(
+        stream
+            .output_queue_label(SCStreamOutputType::Audio)
+            .as_deref(),
+        Some("com.screencapturekit.output.1")
+    );
+
+    // Moving a registered type keeps its handlers attached.
+    let audio = DispatchQueue::new(
+        "com.screencapturekit.tests.audio",
+        DispatchQoS::UserInteractive,
+    );
+    stream
+        .set_output_queue(SCStreamOutputType::Audio, &audio)
+        .expect("move audio queue");
+    assert_eq!(
+        stream
+            .output_queue_label(SCStreamOutputType::Audio)
+            .as_deref(),
+        Some("com.screencapturekit.tests.audio")
+    );
+    stream
+        .set_output_qos(SCStreamOutputType::Screen, DispatchQoS::Background)
+        .expect("set screen QoS");
+    assert_eq!(
+        stream
+            .output_queue_label(SCStreamOutputType::Screen)
+            .as_deref(),
+        Some("com.screencapturekit.output.0")
+    );
+    assert_eq!(stream.outputs().len(), 2);
+}
@@ -0,0 +1,21 @@
+//! Syphon server tests
+
+#![cfg(feature = "syphon")]
+
+use screencapturekit::error::SCError;
+use screencapturekit::syphon::{load_framework, SyphonServer};
+
+#[test]
+fn test_load_framework_missing_path() {
+    let result = load_framework("/nonexistent/Syphon.framework");
+    // Succeeds only when Syphon is already loaded into the test process.
+    if let Err(err) = result {
+        assert!(matches!(err, SCError::InvalidConfiguration(_)));
+    }
+}
+
+#[test]
+fn test_server_name_rejects_nul() {
+    let result = SyphonServer::new("bad\0name");
+    assert!(matches!(result, Err(SCError::InvalidConfiguration(_))));
+}
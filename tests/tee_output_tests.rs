@@ -0,0 +1,109 @@
+//! Tee output handler tests
+//!
+//! These drive `TeeOutputHandler` directly with synthetic sample buffers,
+//! so they don't need screen-recording permission.
+
+mod common;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use screencapturekit::cm::CMSampleBuffer;
+use screencapturekit::stream::output_trait::SCStreamOutputTrait;
+use screencapturekit::stream::output_type::SCStreamOutputType;
+use screencapturekit::stream::tee_output::TeeOutputHandler;
+
+use common::sample;
+
+fn wait_until(condition: impl Fn() -> bool) {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while !condition() {
+        assert!(Instant::now() < deadline, "timed out");
+        std::thread::sleep(Duration::from_millis(5));
+    }
+}
+
+#[test]
+fn test_tee_records_every_sample_in_order() {
+    let recorded = Arc::new(Mutex::new(Vec::new()));
+    let previewed = Arc::new(AtomicUsize::new(0));
+
+    let tee = {
+        let recorded = Arc::clone(&recorded);
+        let previewed = Arc::clone(&previewed);
+        TeeOutputHandler::new(
+            move |sample: CMSampleBuffer, _of_type| {
+                recorded
+                    .lock()
+                    .unwrap()
+                    .push(sample.presentation_timestamp().value);
+            },
+            move |_sample: CMSampleBuffer, _of_type| {
+                previewed.fetch_add(1, Ordering::SeqCst);
+            },
+            16,
+        )
+        .expect("spawn tee threads")
+    };
+    assert_eq!(tee.budget(), 16);
+
+    for frame in 0..10 {
+        tee.did_output_sample_buffer(sample(frame), SCStreamOutputType::Screen);
+    }
+    wait_until(|| tee.recorded_samples() == 10);
+    wait_until(|| tee.previewed_samples() >= 1);
+    assert!(tee.peak_in_flight() >= 1);
+    drop(tee);
+
+    assert_eq!(*recorded.lock().unwrap(), (0..10).collect::<Vec<_>>());
+    assert!(previewed.load(Ordering::SeqCst) >= 1);
+}
+
+#[test]
+fn test_tee_skips_preview_when_over_budget() {
+    let (release_tx, release_rx) = mpsc::channel::<()>();
+    let release_rx = Mutex::new(release_rx);
+    let (started_tx, started_rx) = mpsc::channel::<()>();
+    let started_tx = Mutex::new(started_tx);
+
+    let tee = TeeOutputHandler::new(
+        |_sample: CMSampleBuffer, _of_type| {},
+        move |_sample: CMSampleBuffer, _of_type| {
+            started_tx.lock().unwrap().send(()).unwrap();
+            let _ = release_rx.lock().unwrap().recv();
+        },
+        1,
+    )
+    .expect("spawn tee threads");
+
+    // The preview holds frame 0 after the recording has finished with it.
+    tee.did_output_sample_buffer(sample(0), SCStreamOutputType::Screen);
+    started_rx
+        .recv_timeout(Duration::from_secs(5))
+        .expect("preview started");
+    wait_until(|| tee.recorded_samples() == 1);
+    assert_eq!(tee.in_flight(), 1);
+
+    // Frame 1 would need a second surface, so the preview skips it.
+    tee.did_output_sample_buffer(sample(1), SCStreamOutputType::Screen);
+    assert_eq!(tee.preview_dropped_samples(), 1);
+    wait_until(|| tee.recorded_samples() == 2);
+    assert_eq!(tee.recording_queue_depth(), 0);
+
+    release_tx.send(()).unwrap();
+    wait_until(|| tee.previewed_samples() == 1);
+    assert!(format!("{tee:?}").contains("TeeOutputHandler"));
+}
+
+#[test]
+fn test_tee_zero_budget_uses_one() {
+    let tee = TeeOutputHandler::new(
+        |_sample: CMSampleBuffer, _of_type| {},
+        |_sample: CMSampleBuffer, _of_type| {},
+        0,
+    )
+    .expect("spawn tee threads");
+    assert_eq!(tee.budget(), 1);
+}
@@ -0,0 +1,57 @@
+//! Text recognition tests
+
+#![cfg(feature = "vision")]
+
+use screencapturekit::cg::CGRect;
+use screencapturekit::cv::CVPixelBuffer;
+use screencapturekit::error::SCError;
+use screencapturekit::vision::{
+    RecognitionLevel, RecognizedText, TextRecognitionExt, TextRecognitionOptions,
+};
+
+#[test]
+fn test_options_builder() {
+    let options = TextRecognitionOptions::new()
+        .with_level(RecognitionLevel::Fast)
+        .with_language_correction(false)
+        .with_languages(["en-US", "de-DE"])
+        .with_minimum_text_height(0.02);
+    assert_eq!(options.level(), RecognitionLevel::Fast);
+    assert!(!options.language_correction());
+    assert_eq!(options.languages(), ["en-US", "de-DE"]);
+    assert!((options.minimum_text_height() - 0.02).abs() < f32::EPSILON);
+
+    let defaults = TextRecognitionOptions::default();
+    assert_eq!(defaults.level(), RecognitionLevel::Accurate);
+    assert!(defaults.language_correction());
+    assert!(defaults.languages().is_empty());
+}
+
+#[test]
+fn test_pixel_rect_scales_normalized_box() {
+    let line = RecognizedText {
+        text: "Hello".to_string(),
+        confidence: 1.0,
+        bounding_box: CGRect::new(0.25, 0.5, 0.5, 0.125),
+    };
+    let rect = line.pixel_rect(800, 400);
+    assert_eq!(rect, CGRect::new(200.0, 200.0, 400.0, 50.0));
+}
+
+#[test]
+fn test_blank_buffer_has_no_text() {
+    let buffer = CVPixelBuffer::create(64, 64, 0x4247_5241).expect("create BGRA pixel buffer");
+    let options = TextRecognitionOptions::new().with_level(RecognitionLevel::Fast);
+    let lines = buffer.recognize_text(&options).expect("recognize");
+    assert!(lines.iter().all(|line| line.text.trim().is_empty()) || lines.is_empty());
+}
+
+#[test]
+fn test_language_with_nul_is_rejected() {
+    let buffer = CVPixelBuffer::create(16, 16, 0x4247_5241).expect("create BGRA pixel buffer");
+    let options = TextRecognitionOptions::new().with_languages(["en\0US"]);
+    assert!(matches!(
+        buffer.recognize_text(&options),
+        Err(SCError::InvalidConfiguration(_))
+    ));
+}
@@ -0,0 +1,67 @@
+//! Window observer tests
+
+use std::sync::mpsc;
+use std::time::Duration;
+
+use screencapturekit::cg::CGRect;
+use screencapturekit::shareable_content::window_observer::{WindowEvent, WindowObserver};
+use screencapturekit::shareable_content::{SCShareableContent, SCWindow};
+
+#[test]
+fn test_missing_window_reports_closed() {
+    let (tx, rx) = mpsc::channel();
+    let observer = WindowObserver::start(u32::MAX, Duration::from_millis(10), move |event| {
+        let _ = tx.send(event);
+    })
+    .expect("spawn observer thread");
+    assert_eq!(observer.window_id(), u32::MAX);
+
+    let event = rx
+        .recv_timeout(Duration::from_secs(5))
+        .expect("closed event");
+    assert_eq!(event, WindowEvent::Closed);
+    // Nothing follows a close, and the sender went away with the thread.
+    assert!(rx.recv_timeout(Duration::from_secs(1)).is_err());
+    assert!(observer.is_closed());
+    assert_eq!(observer.event_count(), 1);
+}
+
+#[test]
+fn test_frame_change_kinds() {
+    let at = |x, y, width, height| CGRect::new(x, y, width, height);
+    let moved = WindowEvent::FrameChanged {
+        old: at(0.0, 0.0, 100.0, 100.0),
+        new: at(10.0, 0.0, 100.0, 100.0),
+    };
+    assert!(moved.is_move() && !moved.is_resize());
+
+    let resized = WindowEvent::FrameChanged {
+        old: at(0.0, 0.0, 100.0, 100.0),
+        new: at(0.0, 0.0, 200.0, 100.0),
+    };
+    assert!(resized.is_resize() && !resized.is_move());
+    assert!(!WindowEvent::Closed.is_move());
+}
+
+#[test]
+fn test_observe_live_window_stops_on_drop() {
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - no screen recording permission");
+        return;
+    };
+    let Some(window) = content.windows().into_iter().find(SCWindow::is_on_screen) else {
+        return;
+    };
+
+    let (tx, rx) = mpsc::channel();
+    let observer = window
+        .observe(move |event| {
+            let _ = tx.send(event);
+        })
+        .expect("spawn observer thread");
+    assert_eq!(observer.window_id(), window.window_id());
+    assert!(!observer.is_closed());
+    drop(observer);
+    // The handler, and with it the sender, is gone once the observer is.
+    while rx.recv_timeout(Duration::from_secs(5)).is_ok() {}
+}
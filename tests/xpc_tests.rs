@@ -0,0 +1,116 @@
+//! XPC capture helper tests
+
+#![cfg(feature = "xpc")]
+
+use std::sync::mpsc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use screencapturekit::cm::IOSurface;
+use screencapturekit::prelude::*;
+use screencapturekit::xpc::{
+    XpcCaptureClient, XpcCaptureSettings, XpcEvent, XpcListener, XpcPeer, XpcRequest, XpcTarget,
+};
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+#[test]
+fn test_request_round_trip() {
+    let settings = XpcCaptureSettings::new()
+        .with_display_id(7)
+        .with_size(1280, 720)
+        .with_frame_rate(29.97)
+        .with_shows_cursor(false)
+        .with_queue_depth(6);
+    for request in [
+        XpcRequest::Start(settings.clone()),
+        XpcRequest::Update(XpcCaptureSettings::new()),
+        XpcRequest::Stop,
+    ] {
+        assert_eq!(XpcRequest::decode(&request.encode()).unwrap(), request);
+    }
+    assert_eq!(settings.display_id(), Some(7));
+    assert_eq!(settings.size(), (1280, 720));
+}
+
+#[test]
+fn test_event_round_trip() {
+    for event in [
+        XpcEvent::Started,
+        XpcEvent::Updated,
+        XpcEvent::Stopped,
+        XpcEvent::Failed("no displays available".to_string()),
+        XpcEvent::Interrupted,
+        XpcEvent::Invalidated,
+    ] {
+        assert_eq!(XpcEvent::decode(&event.encode()).unwrap(), event);
+    }
+}
+
+#[test]
+fn test_decode_rejects_malformed_messages() {
+    let mut wrong_version = XpcRequest::Stop.encode();
+    wrong_version[0] += 1;
+    let mut trailing = XpcRequest::Stop.encode();
+    trailing.push(0);
+    let start = XpcRequest::Start(XpcCaptureSettings::new()).encode();
+    let event = XpcEvent::Started.encode();
+
+    for bytes in [
+        &[][..],
+        wrong_version.as_slice(),
+        trailing.as_slice(),
+        &start[..start.len() - 1],
+        event.as_slice(),
+    ] {
+        assert!(matches!(
+            XpcRequest::decode(bytes),
+            Err(SCError::FFIError(_))
+        ));
+    }
+}
+
+#[test]
+fn test_anonymous_listener_round_trip() {
+    let listener = XpcListener::anonymous(|peer: &XpcPeer, request: XpcRequest| {
+        let surface = IOSurface::create(64, 32, 0x4247_5241, 4).expect("create IOSurface");
+        match request {
+            XpcRequest::Start(_) => {
+                peer.send_event(&XpcEvent::Started);
+                peer.send_surface(&surface, 1.5);
+            }
+            _ => peer.send_event(&XpcEvent::Failed("unexpected request".to_string())),
+        }
+    })
+    .expect("create listener");
+    let endpoint = listener.endpoint();
+
+    let (event_tx, event_rx) = mpsc::channel();
+    let (frame_tx, frame_rx) = mpsc::channel();
+    let event_tx = Mutex::new(event_tx);
+    let frame_tx = Mutex::new(frame_tx);
+    let client = XpcCaptureClient::connect(
+        XpcTarget::Endpoint(&endpoint),
+        move |frame: CMSampleBuffer| {
+            let size = frame
+                .image_buffer()
+                .map(|buffer| (buffer.width(), buffer.height()));
+            let _ = frame_tx.lock().unwrap().send(size);
+        },
+        move |event: XpcEvent| {
+            let _ = event_tx.lock().unwrap().send(event);
+        },
+    )
+    .expect("connect");
+
+    client.start(&XpcCaptureSettings::new());
+    assert_eq!(event_rx.recv_timeout(TIMEOUT), Ok(XpcEvent::Started));
+    assert_eq!(frame_rx.recv_timeout(TIMEOUT), Ok(Some((64, 32))));
+    assert!(client.pid().is_some());
+}
+
+#[test]
+fn test_service_name_rejects_nul() {
+    let result = XpcCaptureClient::connect(XpcTarget::Service("bad\0name"), |_| {}, |_| {});
+    assert!(matches!(result, Err(SCError::InvalidConfiguration(_))));
+}